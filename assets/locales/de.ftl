@@ -0,0 +1,620 @@
+# Deutsche UI-Texte. Fehlende Schlüssel fallen auf en.ftl zurück.
+
+## App
+
+app-saving = Wird gespeichert…
+search-conversations = Unterhaltungen durchsuchen
+
+## Sidebar
+
+sidebar-new-chat = Neuer Chat
+sidebar-mark-all-read = Alle als gelesen markieren
+sidebar-clear-day-filter = Alle Tage anzeigen
+sidebar-labels = Labels
+sidebar-load-more = 20 weitere laden... ({ $loaded }/{ $total })
+sidebar-settings = Einstellungen
+sidebar-sort-updated = Zuletzt geändert
+sidebar-sort-viewed = Zuletzt angesehen
+sidebar-sort-created = Zuletzt erstellt
+sidebar-continue = Weiterarbeiten
+sidebar-all-folders = Alle Ordner
+sidebar-all-tags = Alle Tags
+sidebar-organize-shown = Angezeigte ordnen…
+sidebar-remove-tag = Tag aus allen Unterhaltungen entfernen
+sidebar-import = Importieren…
+sidebar-import-tooltip = Aus ChatGPT oder Claude exportierte Unterhaltungen importieren (conversations.json)
+
+## Conversation item
+
+conversation-label = Label
+conversation-clear-label = Label entfernen
+conversation-tags = Tags & Ordner…
+conversation-tags-title = Tags & Ordner
+conversation-tags-title-bulk = { $count } Unterhaltungen ordnen
+conversation-tags-label = Tags (durch Kommas getrennt)
+conversation-tags-add-label = Tags hinzufügen (durch Kommas getrennt)
+conversation-tags-placeholder = z. B. rust, Kundenprojekt
+conversation-folder-label = Ordner
+conversation-folder-move-label = In Ordner verschieben (leer lassen zum Beibehalten)
+conversation-folder-placeholder = z. B. kunden/acme
+conversation-export = Unterhaltung exportieren…
+conversation-export-title = Unterhaltung exportieren
+conversation-export-description = Speichert die Unterhaltung mit Tool-Aufrufen, Anhängen und Prüfnotizen.
+conversation-print = Drucken…
+conversation-lock = Sperren (schreibgeschützt)
+conversation-unlock = Entsperren
+conversation-delete = Löschen
+
+## Chat input
+
+chat-send = Senden
+chat-stop = Stopp
+chat-queue = Einreihen
+chat-queue-tooltip = Diese Nachricht senden, sobald die aktuelle Antwort fertig ist
+chat-queued = Ausstehend
+chat-queued-attachments = { $count } angehängt
+chat-queued-remove = Aus der Warteschlange entfernen
+chat-no-models = Keine Modelle verfügbar
+chat-history-tooltip = Verlauf, der bei jedem Schritt an das Modell geht
+chat-history-heading = Verlauf pro Nachricht
+chat-turn-params-tooltip = Parameter nur für die nächste Nachricht
+chat-turn-params-once = Einmalig ▾
+chat-turn-params-next = Nächste Nachricht: { $summary }
+chat-turn-model-heading = Modell (nächste Nachricht)
+chat-turn-model-conversation = Modell der Unterhaltung
+chat-turn-model-default = Modellstandard
+chat-turn-temperature-heading = Temperatur
+chat-turn-reasoning-heading = Denkaufwand
+chat-turn-clear = Zurücksetzen
+chat-attach-tooltip = Anhänge hinzufügen
+chat-attach-image = Bild
+chat-select-images = Bilder auswählen
+chat-select-pdfs = PDF-Dateien auswählen
+chat-select-working-dir = Arbeitsverzeichnis auswählen
+chat-reset-working-dir = Auf globales Arbeitsverzeichnis zurücksetzen
+chat-workspace-rules = Regeln
+chat-workspace-rules-active = Arbeitsbereichsregeln aktiv: { $path }
+chat-voice = Sprache
+chat-voice-tooltip = Freihändiges Sprachgespräch starten
+chat-voice-stop-tooltip = Sprachgespräch beenden
+chat-voice-listening = Hört zu…
+chat-voice-transcribing = Transkribiert…
+chat-voice-thinking = Denkt nach…
+chat-voice-speaking = Spricht…
+chat-polish = Überarbeiten
+chat-polishing = Wird überarbeitet…
+chat-polish-tooltip = Rechtschreibung und Grammatik mit dem gewählten Modell korrigieren
+chat-spelling-hint = Rechtsklick für Vorschläge
+chat-spelling-ignore = Ignorieren
+chat-spelling-no-suggestions = Keine Vorschläge
+chat-draft-tokens = ~{ $count } Tokens
+chat-draft-tokens-warning = Diese Nachricht allein hat etwa { $count } Tokens und liegt über der Warnschwelle von { $limit }. Nachricht kürzen oder weniger Dateien anhängen.
+chat-cold-start = Nicht geladen
+chat-cold-start-tooltip = { $model } ist in Ollama noch nicht geladen. Die Antwort beginnt erst, wenn das Modell geladen ist, was bei großen Modellen dauern kann.
+chat-preparing-tools = Werkzeuge werden vorbereitet…
+chat-preparing-tools-tooltip = Werkzeuge und Shell für diese Unterhaltung werden gestartet, damit die erste Antwort schneller beginnt.
+chat-composer-code-block = Codeblock (umschließt die Auswahl)
+chat-composer-quote = Auswahl zitieren
+chat-composer-show-preview = Markdown-Vorschau anzeigen
+chat-composer-hide-preview = Markdown-Vorschau ausblenden
+chat-composer-expand = Eingabefeld vergrößern
+chat-composer-collapse = Eingabefeld verkleinern
+
+## Background jobs
+
+jobs-indicator-tooltip = Hintergrundaufgaben
+pack-updates-indicator-tooltip = Updates für Prompt-Pakete verfügbar
+jobs-title = Hintergrundaufgaben
+jobs-empty = Keine Hintergrundaufgaben ausstehend.
+jobs-untitled = Unbenannte Unterhaltung
+jobs-kind-title = Titel erzeugen
+jobs-kind-export-atif = ATIF-Trajektorie exportieren
+jobs-kind-export-jsonl = JSONL-Trainingsdaten exportieren
+jobs-kind-summarize = Verlauf zusammenfassen
+jobs-kind-topic-shift = Themenwechsel prüfen
+jobs-state-pending = Wartet
+jobs-state-retrying = Versuch { $attempt } in { $seconds } s
+jobs-state-running = Läuft
+jobs-state-failed = Nach { $attempts } Versuchen fehlgeschlagen
+jobs-state-not-started = Nicht gestartet
+jobs-retry = Wiederholen
+jobs-dismiss = Verwerfen
+jobs-retry-all = Fehlgeschlagene wiederholen
+
+## Onboarding
+
+onboarding-title = Modellanbieter einrichten
+onboarding-subtitle = Chatty braucht einen Anbieter, bevor es antworten kann. Schlüssel werden vor dem Speichern geprüft.
+onboarding-openrouter-description = Hunderte gehostete Modelle mit einem API-Schlüssel
+onboarding-ollama-description = Modelle auf diesem Computer ausführen, ohne Schlüssel
+onboarding-azure-description = Die Azure-OpenAI-Ressource der eigenen Organisation
+onboarding-api-key = API-Schlüssel
+onboarding-endpoint = Endpunkt-URL
+onboarding-ollama-url = Ollama-URL
+onboarding-test = Prüfen und weiter
+onboarding-testing = Wird geprüft…
+onboarding-discovering = Modelle werden gesucht…
+onboarding-no-models = Für diesen Anbieter wurden keine Modelle gefunden.
+onboarding-azure-models = Azure-Deployments unter Einstellungen > Modelle als Modelle hinzufügen.
+onboarding-open-settings = Einstellungen öffnen
+onboarding-pick-default = Standardmodell für neue Unterhaltungen
+onboarding-suggested = Empfohlen
+onboarding-back = Zurück
+onboarding-finish = Loslegen
+onboarding-skip = Einrichtung überspringen
+
+## Status footer
+
+footer-indicator-errors = Fehler & Warnungen
+footer-indicator-jobs = Hintergrundaufgaben
+footer-indicator-pack-updates = Prompt-Paket-Updates
+footer-indicator-auto-update = App-Updates
+footer-indicator-tokens = Kontext & Kosten
+footer-indicator-local-models = Lokale Modelle (Ollama)
+footer-indicator-fetch = Web-Abruf
+footer-indicator-network = Sandbox-Netzwerk
+footer-indicator-tools = Werkzeuge
+footer-indicator-mcp = MCP-Server
+footer-indicator-agent = Sub-Agenten
+footer-customize = Anpassen…
+footer-agents-title = Agenten
+footer-api-tools = API-Werkzeuge
+footer-local-models-tooltip = Ollama: geladene Modelle und Speicher
+footer-mcp-servers = MCP-Server
+footer-mcp-use-server = Diesen Server in der aktuellen Unterhaltung verwenden
+footer-paid = Kostenpflichtig
+footer-tokens-actual = Tatsächlich (vom Anbieter):
+footer-tokens-breakdown = Woraus der Prompt besteht
+footer-tokens-session = Summe der Sitzung:
+footer-tools-blocked = Gesperrt
+footer-tools-code-execution = Codeausführung
+footer-tools-docker-fallback = Docker-Ausweichlösung
+footer-tools-filesystem = Dateisystem-Werkzeuge
+footer-tools-shell = Shell (gefährlich!)
+footer-update-downloading = Wird heruntergeladen...
+footer-local-models-memory = GPU { $vram } · RAM { $ram } ({ $percent } % auf der GPU)
+footer-local-models-unloads = wird in { $minutes } min entladen
+footer-local-models-idle = inaktiv
+footer-local-models-none = Keine Modelle geladen. Die nächste Nachricht lädt zuerst ihr Modell.
+footer-local-models-total = Gesamt: GPU { $vram } · RAM { $ram }
+
+## Tool reference
+
+tool-reference-open = Tool-Referenz…
+tool-reference-title = Tool-Referenz
+tool-reference-search-placeholder = Tools nach Name, Beschreibung oder Server suchen...
+tool-reference-empty = Noch keine Tools. Sie werden aufgelistet, sobald eine Unterhaltung ihren Agenten gestartet hat.
+tool-reference-no-matches = Keine passenden Tools.
+tool-reference-schema = Parameter (JSON-Schema)
+tool-reference-examples = Beispielaufrufe
+
+## Settings pages
+
+settings-page-general = Allgemein
+settings-page-models = Modelle
+settings-page-models-description = KI-Modelle und ihre Parameter konfigurieren
+settings-page-providers = Anbieter
+settings-page-extensions = Erweiterungen
+settings-page-internet = Internet
+settings-page-code-execution = Codeausführung
+settings-page-guardrails = Schutzregeln
+settings-page-custom-tools = Eigene Werkzeuge
+settings-page-remote-hosts = Entfernte Hosts
+settings-page-memory = Gedächtnis
+settings-page-training-data = Trainingsdaten
+settings-page-batch-runs = Stapelläufe
+settings-page-compare-runs = Läufe vergleichen
+settings-page-snippets = Textbausteine
+settings-page-prompt-packs = Prompt-Pakete
+settings-page-secrets = Geheimnisse
+settings-page-managed = Verwaltet
+settings-page-telemetry = Telemetrie
+settings-page-tool-usage = Werkzeugnutzung
+settings-page-token-usage = Token-Verbrauch
+settings-page-storage = Speicher
+settings-page-diagnostics = Diagnose
+
+## General settings
+
+settings-appearance = Darstellung
+settings-language = Sprache
+settings-language-description = Sprache von Menüs, Schaltflächen und Beschriftungen. Änderungen gelten sofort.
+settings-language-system = System ({ $language })
+settings-layout-direction = Layoutrichtung
+settings-layout-direction-description = Von rechts nach links setzt die Seitenleiste nach rechts und richtet Text ohne eindeutige Richtung rechts aus. Absätze in Arabisch, Hebräisch und anderen linksläufigen Schriften werden immer rechtsbündig angezeigt.
+settings-layout-auto = Automatisch (nach Sprache)
+settings-layout-ltr = Von links nach rechts
+settings-layout-rtl = Von rechts nach links (gespiegelt)
+settings-theme = Design
+settings-theme-description = Designfamilie auswählen (hell/dunkel über den Dunkelmodus-Schalter)
+settings-dark-mode = Dunkelmodus
+settings-dark-mode-description = Zwischen heller und dunkler Variante des gewählten Designs wechseln.
+settings-text = Text
+settings-font-size = Schriftgröße
+settings-font-size-description = Standard-Schriftgröße anpassen.
+settings-speech = Sprachmodus
+settings-speech-description = Freihändiger Sprachchat: Nachricht sprechen, Antwort anhören und zum Unterbrechen einfach dazwischenreden. Transkripte bleiben wie getippte Nachrichten in der Unterhaltung.
+settings-speech-record-command = Aufnahmebefehl
+settings-speech-record-command-description = Shell-Befehl, der das Mikrofon als rohes 16-kHz-Mono-PCM mit 16 Bit auf stdout ausgibt, z. B. sox -q -d -t raw -r 16000 -c 1 -b 16 -e signed -. Leer lassen, um den Sprachmodus abzuschalten.
+settings-speech-transcribe-command = Transkriptionsbefehl
+settings-speech-transcribe-command-description = Shell-Befehl, der das Transkript einer Äußerung ausgibt; {"{"}audio{"}"} wird durch den Pfad einer 16-kHz-WAV-Datei ersetzt, z. B. whisper-cli -nt -f {"{"}audio{"}"}.
+settings-speech-tts-command = Sprachausgabebefehl
+settings-speech-tts-command-description = Optionaler Shell-Befehl, der die Antwort von stdin vorliest, z. B. say oder espeak-ng --stdin. Leer lassen, um nur zuzuhören.
+settings-composer = Eingabefeld
+settings-send-shortcut = Senden mit
+settings-send-shortcut-description = Die andere Tastenkombination fügt einen Zeilenumbruch ein. Umschalt+Enter fügt immer einen Zeilenumbruch ein.
+settings-send-enter = Enter
+settings-send-ctrl-enter = Strg+Enter
+settings-send-cmd-enter = Cmd+Enter
+settings-composer-expanded = Großes Eingabefeld
+settings-composer-expanded-description = Ein hohes, mehrzeiliges Eingabefeld für längere Nachrichten anzeigen. Auch über die Werkzeugleiste der Eingabe umschaltbar.
+settings-composer-preview = Markdown-Vorschau
+settings-composer-preview-description = Den Entwurf unter dem großen Eingabefeld als Markdown darstellen.
+settings-spelling = Rechtschreibung
+settings-spell-check = Rechtschreibprüfung
+settings-spell-check-description = Falsch geschriebene Wörter in der Chat-Eingabe mit einem Hunspell-Wörterbuch unterstreichen.
+settings-spell-dictionary = Wörterbuch
+settings-spell-dictionary-auto = Wie UI-Sprache
+settings-spell-dictionary-description = Hunspell-Wörterbücher werden in /usr/share/hunspell, ~/Library/Spelling und im Ordner „dictionaries“ im Datenverzeichnis von Chatty gesucht.
+settings-message-size = Nachrichtengröße
+settings-draft-token-warning = Token-Warnschwelle
+settings-draft-token-warning-description = Die Chat-Eingabe zeigt live eine Token-Schätzung des Entwurfs und seiner Anhänge und wird gelb, wenn eine einzelne Nachricht mehr Tokens hat. 0 schaltet die Warnung aus.
+settings-streaming = Streaming
+settings-smooth-streaming = Gleichmäßiges Streaming
+settings-smooth-streaming-description = Antworten in gleichmäßigem Tipptempo anzeigen statt stoßweise, wie der Anbieter sie sendet. Der Text liegt nie mehr als eine Sekunde hinter dem Netzwerk, und die vollständige Antwort erscheint, sobald sie fertig ist.
+settings-smooth-streaming-rate = Tippgeschwindigkeit
+settings-smooth-streaming-rate-description = Zeichen pro Sekunde, die bei gleichmäßigem Streaming angezeigt werden.
+settings-video = Video-Anhänge
+settings-video-description = Videos werden als einige gleichmäßig verteilte Einzelbilder gesendet (benötigt ffmpeg). Die Anzahl der Bilder kann in den Modelleinstellungen pro Modell begrenzt werden.
+settings-video-transcribe-command = Transkriptionsbefehl
+settings-video-transcribe-command-description = Optionaler Shell-Befehl, der ein Transkript der Tonspur ausgibt; {"{"}audio{"}"} wird durch den Pfad einer 16-kHz-WAV-Datei ersetzt, z. B. whisper-cli -nt -f {"{"}audio{"}"}. Leer lassen, um nur Bilder zu senden.
+settings-startup = Start
+settings-restore-session = Vorherige Sitzung wiederherstellen
+settings-restore-session-description = Öffnet das Fenster an der alten Position mit der letzten Unterhaltung und Scrollposition. Wenn aus, startet Chatty mit einem leeren Chat.
+settings-copy-messages = Nachrichten kopieren
+settings-copy-format = Kopieren-Schaltfläche
+settings-copy-format-description = Was die Kopieren-Schaltfläche, das Kopieren-Tastenkürzel und /copy bei Antworten des Assistenten in die Zwischenablage legen. Das Menü neben der Kopieren-Schaltfläche bietet die anderen Formate an.
+settings-copy-format-markdown = Markdown-Quelltext
+settings-copy-format-plain = Reiner Text
+settings-copy-format-html = HTML
+settings-copy-format-clean = Markdown ohne Zitate und Denkprozess
+settings-copy-context = Als Kontext kopieren
+settings-include-traces = Traces einbeziehen
+settings-include-traces-description = Beim Kopieren ausgewählter Nachrichten als Kontext auch Denkzusammenfassungen und Werkzeugaufrufe (mit gekürzten Ausgaben) einfügen.
+settings-printing = Drucken
+settings-print-traces = Traces drucken
+settings-print-traces-description = Beim Drucken einer Unterhaltung auch Denkzusammenfassungen und Werkzeugaufrufe einfügen.
+settings-print-thumbnails = Anhang-Vorschaubilder
+settings-print-thumbnails-description = Bild-, PDF- und Videoanhänge als kleine Vorschau statt nur mit ihrem Dateinamen drucken.
+settings-print-header = Kopfzeile
+settings-print-header-description = Titel und Datum der Unterhaltung oben auf jeder gedruckten Seite wiederholen.
+settings-status-footer = Statusleiste
+settings-footer-indicators = Anzeigen
+settings-footer-indicators-description = Wählen Sie, welche Anzeigen die Statusleiste in welcher Reihenfolge zeigt. Ausgeblendete Anzeigen bleiben über das …-Menü am rechten Ende der Statusleiste erreichbar.
+settings-topic-changes = Themenwechsel
+settings-suggest-split = Aufteilen von Unterhaltungen vorschlagen
+settings-suggest-split-description = Wechselt eine Nachricht zu einem fremden Thema, wird angeboten, in einer neuen Unterhaltung mit einer Zusammenfassung dieser weiterzumachen. Benötigt ein Embedding-Modell.
+settings-cli = Kommandozeilen-Tool
+settings-cli-description = Die Kommandozeile chatty-tui installieren, um Chatty im Terminal zu nutzen.
+settings-cli-install = CLI installieren…
+settings-cli-installing = Wird installiert…
+settings-cli-reinstall = CLI neu installieren
+settings-cli-failed = Installation fehlgeschlagen: { $error }
+settings-cli-retry = Erneut versuchen
+
+## Models settings
+
+settings-models-list = Modellliste
+settings-models-list-description = Alle konfigurierten KI-Modelle
+settings-routing = Modell-Routing
+settings-routing-description = Jede Nachricht als faktisch, agentisch oder visuell einstufen und an das günstigste passende Modell senden. Modelle mit dem Alias "factual", "agentic" oder "vision" werden fest dieser Klasse zugeordnet.
+settings-route-each = Jede Nachricht routen
+settings-route-each-description = Die Routing-Entscheidung erscheint im Trace jeder Antwort.
+settings-classifier = Klassifizierungsmodell
+settings-classifier-heuristic = Heuristik (kein Modell)
+settings-classifier-description = Am besten eignet sich ein kleines lokales Ollama-Modell; ohne Modell wird eine Schlüsselwort-Heuristik verwendet.
+models-aliases = Aliasse (durch Kommas getrennt)
+models-base-model = Basismodell
+models-dataset-hash = Hash des Trainingsdatensatzes
+models-embeddings = Embeddings
+models-embeddings-batch-size = Batchgröße (Texte pro Anfrage)
+models-embeddings-dimensions = Dimensionen
+models-embeddings-dimensions-placeholder = Bei bekannten Modellen automatisch erkannt, z. B. 768
+models-embeddings-empty = Keine Embedding-Modelle registriert.
+models-embeddings-gemini-hint = Gemini-Embedding-Modelle sind über den OpenRouter-Anbieter erreichbar.
+models-embeddings-name = Name *
+models-embeddings-rate = Max. Anfragen pro Minute
+models-embeddings-rate-placeholder = Unbegrenzt, z. B. 60
+models-identifier = Modellkennung *
+models-lineage = Fine-Tuning-Herkunft (optional)
+models-max-context = Max. Kontextfenster (optional)
+models-max-tokens = Max. Tokens (optional)
+models-max-video-frames = Max. Videobilder (optional)
+models-name = Modellname *
+models-no-matches = Keine Treffer
+models-no-results-hint = Versuchen Sie andere Suchbegriffe
+models-openrouter-catalog = OpenRouter-Katalog
+models-openrouter-search = OpenRouter-Modelle durchsuchen...
+models-preamble = Präambel / Systemprompt
+models-preamble-placeholder = Systemanweisungen für das Modell
+models-provider = Anbieter *
+models-tab-advanced = Erweitert
+models-tab-basic = Grundlegend
+models-temperature = Temperatur
+models-title = KI-Modelle
+models-top-p = Top P (optional)
+models-training-date = Trainingsdatum
+
+## Common actions
+
+action-approve = Genehmigen
+action-cancel = Abbrechen
+action-check-updates = Nach Updates suchen
+action-clear = Leeren
+action-copy-png = Als PNG kopieren
+action-delete = Löschen
+action-deny = Ablehnen
+action-edit = Bearbeiten
+action-install = Installieren
+action-preview = Vorschau
+action-refresh = Aktualisieren
+action-register = Registrieren
+action-remove = Entfernen
+action-save = Speichern
+action-search = Suchen
+action-sign-in = Anmelden
+
+## Chat view
+
+chat-edit-hint = Spätere Nachrichten werden entfernt; das Original bleibt im Bearbeitungsprotokoll erhalten.
+chat-edit-save-rerun = Speichern & neu ausführen
+chat-edit-tooltip = Diese Nachricht bearbeiten und die Unterhaltung ab hier neu ausführen
+chat-find-close = Schließen (Escape)
+chat-find-next = Nächster Treffer (Enter)
+chat-find-placeholder = In der Unterhaltung suchen
+chat-find-previous = Vorheriger Treffer (Umschalt-Enter)
+chat-input-placeholder = Nachricht eingeben...
+chat-message-shortcuts = Tastenkürzel für Nachrichten
+chat-topic-split-new = Neue Unterhaltung
+chat-topic-split-new-tooltip = Eine neue Unterhaltung mit einer angehefteten Zusammenfassung dieser beginnen
+chat-topic-split-prompt = Das sieht nach einem neuen Thema aus. In einer neuen Unterhaltung fortfahren?
+chat-topic-split-stay = In dieser Unterhaltung bleiben
+chat-turn-error = Die Antwort ist fehlgeschlagen
+chat-turn-retry = Erneut versuchen
+chat-turn-show-error-log = Im Fehlerprotokoll anzeigen
+chat-unlock-tooltip = Änderungen an dieser Unterhaltung wieder erlauben
+chat-welcome = Willkommen bei Chatty
+chat-locked = Diese Unterhaltung ist gesperrt. Nachrichten können nicht gesendet, gelöscht oder neu erzeugt werden.
+
+## Messages
+
+chart-no-candlestick-data = Keine Kerzendaten vorhanden
+message-add-reaction = Reaktion hinzufügen
+message-bad-response = Schlechte Antwort
+message-copy-as = Kopieren als…
+message-copy-code = Code kopieren
+message-copy-latex = LaTeX kopieren
+message-copy-mermaid = Mermaid kopieren
+message-export-note = Antwort als Notiz exportieren
+message-good-response = Gute Antwort
+message-note-placeholder = Private Notiz (wird nicht an das Modell gesendet)
+message-pin = An den Kontext anheften
+message-references = Quellen:
+message-regenerate = Antwort neu erzeugen
+message-thinking = Denkt nach
+
+## Agent activity
+
+approval-execute = Ausführen:
+question-skip = Überspringen
+question-title = Der Agent fragt
+trace-approval-requested = Ausführungsfreigabe angefordert
+trace-error = Fehler:
+trace-question = Frage an Sie
+trace-running = Läuft...
+
+## Side panels
+
+artifacts-open = Öffnen
+artifacts-title = Artefakte
+pdf-ask-page = Zu dieser Seite fragen
+pdf-no-text = Kein Text auf dieser Seite.
+pdf-rendering = Seite wird gerendert…
+pinned-title = Angehefteter Kontext
+pinned-unpin = Lösen
+plan-clear = Plan leeren
+variables-hide = Ausblenden
+variables-title = Variablen
+artifacts-export-prompt = Artefakte exportieren
+artifacts-exported = { $count } Datei(en) nach { $dest } exportiert
+artifacts-exported-missing = { $count } Datei(en) nach { $dest } exportiert ({ $missing } existieren nicht mehr)
+artifacts-export-failed = Export fehlgeschlagen: { $error }
+artifacts-all = Alle
+artifacts-kind-images = Bilder
+artifacts-kind-documents = Dokumente
+artifacts-kind-data = Daten
+artifacts-kind-other = Sonstige
+artifacts-turn = Runde { $turn }
+artifacts-all-turns = Alle Runden
+artifacts-detail = Runde { $turn } · { $tool }
+artifacts-detail-missing = Runde { $turn } · Datei existiert nicht mehr
+artifacts-none = Noch keine Artefakte
+artifacts-count = { $total } Datei(en)
+artifacts-count-filtered = { $shown } von { $total } Datei(en)
+artifacts-exporting = Wird exportiert…
+artifacts-export = In Ordner exportieren…
+artifacts-empty = Bilder, Dokumente und andere Dateien, die Werkzeuge in dieser Unterhaltung erzeugen, erscheinen hier.
+artifacts-no-matches = Keine Artefakte entsprechen den Filtern.
+pdf-page-of = Seite { $page } von { $count }
+pdf-loading = Wird geladen…
+pdf-show-page = Seite
+pdf-show-text = Text
+
+## Dialogs
+
+annotate-blur = Unschärfe
+annotate-hint = Zum Markieren auf dem Bild ziehen. Nur die bearbeitete Kopie wird gesendet.
+annotate-undo = Rückgängig
+error-log-clear-all = Alle löschen
+error-log-copy = Fehler kopieren
+error-log-empty = Keine Fehler oder Warnungen vorhanden.
+error-log-provider-checks = Anbieterprüfungen
+search-conversations-placeholder = Titel und Nachrichten durchsuchen...
+annotate-title = Bild markieren
+annotate-crop = Zuschneiden
+annotate-arrow = Pfeil
+annotate-box = Rahmen
+annotate-apply = Anwenden
+annotate-applying = Wird angewendet…
+
+## Extensions settings
+
+extensions-add = Hinzufügen
+extensions-add-mcp = MCP-Server hinzufügen
+extensions-api-key-placeholder = Optionaler API-Schlüssel
+extensions-email = E-Mail
+extensions-installed = Installiert
+extensions-new-password-placeholder = Passwort (mindestens 12 Zeichen)
+extensions-none-installed = Noch keine Erweiterungen installiert. Unten im Marktplatz stöbern.
+extensions-not-signed-in = Nicht angemeldet
+extensions-password = Passwort
+extensions-search-placeholder = Erweiterungen durchsuchen...
+extensions-sign-out = Abmelden
+extensions-uninstall = Deinstallieren
+extensions-username-placeholder = Benutzername (3–39 Zeichen, Kleinbuchstaben)
+
+## Guardrails settings
+
+guardrails-empty = Keine Schutzregeln konfiguriert. Unten eine Regel hinzufügen.
+guardrails-name = Name
+guardrails-name-placeholder = Regelname
+
+## Custom tools settings
+
+custom-tools-base-url = Basis-URL
+custom-tools-import = Importieren
+custom-tools-import-openapi = OpenAPI importieren…
+custom-tools-load = Laden
+custom-tools-no-graphql = Keine GraphQL-Endpunkte konfiguriert. Unten einen hinzufügen.
+custom-tools-no-rest = Keine REST-Werkzeuge definiert. Unten eines hinzufügen.
+custom-tools-openapi-spec = OpenAPI-3.x-Spezifikation (Dateipfad oder Inhalt)
+
+## Remote hosts settings
+
+ssh-hosts-empty = Keine SSH-Hosts konfiguriert. Unten einen hinzufügen.
+
+## Memory settings
+
+memory-browser-disabled = Aktivieren Sie oben das Agentengedächtnis, um den Erinnerungs-Browser zu nutzen.
+memory-keyword = Stichwort
+memory-load = Erinnerungen laden
+memory-loading = Erinnerungen werden geladen…
+memory-purge-all = Gesamtes Gedächtnis löschen
+memory-search-placeholder = Erinnerungen durchsuchen...
+memory-semantic = Semantisch
+
+## Training data settings
+
+training-comment = Kommentar
+training-comment-placeholder = Warum ist dieses Beispiel gut oder schlecht?
+training-reject = Ablehnen
+training-write-dataset = Genehmigten Datensatz schreiben
+
+## Batch runs settings
+
+batch-start = Starten
+
+## Compare runs settings
+
+compare-export-markdown = Als Markdown exportieren
+compare-no-matching-turn = Kein passender Durchgang
+
+## Snippets settings
+
+snippets-content = Inhalt
+snippets-content-placeholder = Einzufügender Text oder Code
+snippets-name = Name
+
+## Prompt packs settings
+
+prompt-packs-browse = Durchsuchen…
+prompt-packs-empty = Keine Prompt-Pakete installiert.
+prompt-packs-description = Geteilte Snippets, Modellprofile und Werkzeuge aus der Community
+prompt-packs-import-description = Importieren Sie ein signiertes Paket von einer URL oder aus einer Datei. Vorlagen werden zu Snippets, Profile zu Modellen und Werkzeuge werden dem MCP-Katalog hinzugefügt. Prüfen Sie vor der Installation den Schlüssel des Herausgebers; Updates müssen mit demselben Schlüssel signiert sein.
+prompt-packs-source-placeholder = https://example.com/packs/writing.json oder ein lokaler Dateipfad
+prompt-packs-select = Prompt-Paket auswählen
+prompt-packs-update = Aktualisieren
+prompt-packs-update-to = Auf v{ $version } aktualisieren
+prompt-packs-provenance = Von { $author } · signiert mit Schlüssel { $key } · aus { $source }
+prompt-packs-unknown-author = unbekanntem Autor
+prompt-packs-publisher-changed = Diese Version ist von einem anderen Herausgeber signiert als die installierte und kann nicht darüber installiert werden. Entfernen Sie zuerst das installierte Paket, wenn Sie dem neuen Herausgeber vertrauen.
+prompt-packs-kind-template = Vorlage
+prompt-packs-kind-profile = Profil
+prompt-packs-kind-tool = Werkzeug
+prompt-packs-change-new = Neu
+prompt-packs-change-updated = Aktualisiert
+prompt-packs-change-unchanged = Unverändert
+prompt-packs-change-conflict = Ihre behalten
+prompt-packs-change-needs-setup = Einrichtung nötig
+prompt-packs-change-removed = Entfernt
+prompt-packs-installed-summary = v{ $version } · { $templates } Vorlagen · { $profiles } Profile · { $tools } Werkzeuge · Schlüssel { $key }
+
+## Secrets settings
+
+secrets-value = Wert
+secrets-variable-name = Variablenname
+
+## Telemetry settings
+
+telemetry-clear = Daten löschen
+telemetry-empty = Nichts erfasst
+telemetry-export = JSON exportieren…
+
+## Tool usage settings
+
+tool-stats-calls = Aufrufe
+tool-stats-clear = Statistik löschen
+tool-stats-empty = Noch keine Werkzeugaufrufe aufgezeichnet
+tool-stats-latency = Ø Latenz
+tool-stats-success = Erfolg
+tool-stats-tool = Werkzeug
+
+## Token usage settings
+
+token-usage-export = CSV exportieren…
+token-usage-description = Exportieren Sie Tokens, Modell, Latenz und geschätzte Kosten jeder Antwort, für Spesenabrechnungen oder eigene Auswertungen.
+token-usage-activity = Aktivität
+token-usage-activity-description = Nachrichten pro Tag, nach dem Zeitpunkt, zu dem jede Nachricht gesendet oder empfangen wurde. Klicken Sie auf einen Tag, um seine Unterhaltungen in der Seitenleiste anzuzeigen.
+token-usage-counting = Nachrichten werden gezählt…
+token-usage-calendar-summary = { $count } Nachrichten in den letzten { $weeks } Wochen
+token-usage-day-none = Keine Nachrichten am { $day }
+token-usage-day-one = 1 Nachricht am { $day }
+token-usage-day-many = { $count } Nachrichten am { $day }
+token-usage-export-title = Export
+token-usage-export-description = Eine Zeile pro Antwort, in UTC. Die Latenz wird vom Senden des Prompts bis zur fertigen Antwort gemessen; die Kosten bleiben bei Modellen ohne Preisangabe leer.
+token-usage-this-month = Dieser Monat
+token-usage-last-30-days = Letzte 30 Tage
+token-usage-all-time = Gesamter Zeitraum
+
+## Storage settings
+
+storage-remove-unused = Ungenutzte Dateien entfernen
+storage-description = Anhänge und von Werkzeugen erzeugte Dateien werden in einen Ordner kopiert und nach ihrem Inhalt benannt, sodass eine mehrfach angehängte Datei nur einmal gespeichert wird.
+storage-attachments = Anhänge
+storage-attachments-description = Dateien bleiben erhalten, solange eine Unterhaltung sie verwendet. Ungenutzte Dateien, z. B. aus gelöschten Unterhaltungen, werden nur auf Wunsch entfernt und nie innerhalb einer Stunde nach dem Hinzufügen.
+storage-scanning = Anhänge werden geprüft…
+storage-summary = { $count } Dateien, { $size } ({ $saved } durch Deduplizierung gespart)
+storage-no-orphans = Jede Datei wird von einer Unterhaltung verwendet
+storage-orphan = 1 Datei ({ $size }) wird von keiner Unterhaltung verwendet
+storage-orphans = { $count } Dateien ({ $size }) werden von keiner Unterhaltung verwendet
+storage-unused = ungenutzt
+storage-reference = 1 Verweis
+storage-references = { $count } Verweise
+storage-removed = { $count } Dateien entfernt ({ $size })
+storage-removed-kept = { $count } Dateien entfernt ({ $size }); { $kept } in der letzten Stunde hinzugefügte behalten
+storage-cleanup-failed = Bereinigung fehlgeschlagen: { $error }
+storage-cleanup-skipped = Bereinigung übersprungen: Unterhaltungen konnten nicht geladen werden ({ $error })
+
+## Diagnostics settings
+
+diagnostics-no-phases = Keine Phasen aufgezeichnet
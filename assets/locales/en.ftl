@@ -0,0 +1,623 @@
+# English UI strings. Every other catalog falls back to this one.
+#
+# Keys are grouped by the view that shows them. Placeholders use Fluent's
+# { $name } syntax.
+
+## App
+
+app-saving = Saving…
+search-conversations = Search conversations
+
+## Sidebar
+
+sidebar-new-chat = New Chat
+sidebar-mark-all-read = Mark all read
+sidebar-clear-day-filter = Show all days
+sidebar-labels = Labels
+sidebar-load-more = Load 20 more... ({ $loaded }/{ $total })
+sidebar-settings = Settings
+sidebar-sort-updated = Recently updated
+sidebar-sort-viewed = Recently viewed
+sidebar-sort-created = Recently created
+sidebar-continue = Continue where you left off
+sidebar-all-folders = All folders
+sidebar-all-tags = All tags
+sidebar-organize-shown = Organize shown…
+sidebar-remove-tag = Remove tag from all conversations
+sidebar-import = Import…
+sidebar-import-tooltip = Import conversations exported from ChatGPT or Claude (conversations.json)
+
+## Conversation item
+
+conversation-label = Label
+conversation-clear-label = Clear label
+conversation-tags = Tags & folder…
+conversation-tags-title = Tags & Folder
+conversation-tags-title-bulk = Organize { $count } conversations
+conversation-tags-label = Tags (comma-separated)
+conversation-tags-add-label = Add tags (comma-separated)
+conversation-tags-placeholder = e.g. rust, client work
+conversation-folder-label = Folder
+conversation-folder-move-label = Move to folder (leave empty to keep)
+conversation-folder-placeholder = e.g. clients/acme
+conversation-export = Export conversation…
+conversation-export-title = Export conversation
+conversation-export-description = Save the conversation with its tool calls, attachments and reviewer notes.
+conversation-print = Print…
+conversation-lock = Lock (read-only)
+conversation-unlock = Unlock
+conversation-delete = Delete
+
+## Chat input
+
+chat-send = Send
+chat-stop = Stop
+chat-queue = Queue
+chat-queue-tooltip = Send this message when the current reply finishes
+chat-queued = Pending
+chat-queued-attachments = { $count } attached
+chat-queued-remove = Remove from the queue
+chat-no-models = No Models Available
+chat-history-tooltip = History sent to the model each turn
+chat-history-heading = History sent each turn
+chat-turn-params-tooltip = Parameters for the next message only
+chat-turn-params-once = Once ▾
+chat-turn-params-next = Next message: { $summary }
+chat-turn-model-heading = Model (next message)
+chat-turn-model-conversation = Conversation model
+chat-turn-model-default = Model default
+chat-turn-temperature-heading = Temperature
+chat-turn-reasoning-heading = Reasoning effort
+chat-turn-clear = Clear
+chat-attach-tooltip = Add attachments
+chat-attach-image = Image
+chat-select-images = Select Images
+chat-select-pdfs = Select PDF Files
+chat-select-working-dir = Select Working Directory
+chat-reset-working-dir = Reset to global working directory
+chat-workspace-rules = Rules
+chat-workspace-rules-active = Workspace rules active: { $path }
+chat-voice = Voice
+chat-voice-tooltip = Start a hands-free voice conversation
+chat-voice-stop-tooltip = End the voice conversation
+chat-voice-listening = Listening…
+chat-voice-transcribing = Transcribing…
+chat-voice-thinking = Thinking…
+chat-voice-speaking = Speaking…
+chat-polish = Polish
+chat-polishing = Polishing…
+chat-polish-tooltip = Fix spelling and grammar with the selected model
+chat-spelling-hint = Right-click for suggestions
+chat-spelling-ignore = Ignore
+chat-spelling-no-suggestions = No suggestions
+chat-draft-tokens = ~{ $count } tokens
+chat-draft-tokens-warning = This message alone is about { $count } tokens, above your warning threshold of { $limit }. Consider trimming it or attaching fewer files.
+chat-cold-start = Not loaded
+chat-cold-start-tooltip = { $model } is not loaded in Ollama yet. The reply starts once the model has loaded, which can take a while for large models.
+chat-preparing-tools = Preparing tools…
+chat-preparing-tools-tooltip = Connecting tools and starting the shell for this conversation so the first reply starts sooner.
+chat-composer-code-block = Code block (wraps the selection)
+chat-composer-quote = Quote the selection
+chat-composer-show-preview = Show Markdown preview
+chat-composer-hide-preview = Hide Markdown preview
+chat-composer-expand = Expand the composer
+chat-composer-collapse = Collapse the composer
+
+## Background jobs
+
+jobs-indicator-tooltip = Background jobs
+pack-updates-indicator-tooltip = Prompt pack updates available
+jobs-title = Background Jobs
+jobs-empty = No background jobs are waiting.
+jobs-untitled = Untitled conversation
+jobs-kind-title = Generate title
+jobs-kind-export-atif = Export ATIF trajectory
+jobs-kind-export-jsonl = Export JSONL training data
+jobs-kind-summarize = Summarize history
+jobs-kind-topic-shift = Check for a topic change
+jobs-state-pending = Waiting
+jobs-state-retrying = Attempt { $attempt } in { $seconds }s
+jobs-state-running = Running
+jobs-state-failed = Failed after { $attempts } attempts
+jobs-state-not-started = Not started
+jobs-retry = Retry
+jobs-dismiss = Dismiss
+jobs-retry-all = Retry Failed
+
+## Onboarding
+
+onboarding-title = Set up a model provider
+onboarding-subtitle = Chatty needs a provider before it can answer. Keys are tested before they are saved.
+onboarding-openrouter-description = Hundreds of hosted models with one API key
+onboarding-ollama-description = Run models on this computer, no key needed
+onboarding-azure-description = Your organisation's Azure OpenAI resource
+onboarding-api-key = API key
+onboarding-endpoint = Endpoint URL
+onboarding-ollama-url = Ollama URL
+onboarding-test = Test and continue
+onboarding-testing = Testing…
+onboarding-discovering = Looking for models…
+onboarding-no-models = No models were found for this provider.
+onboarding-azure-models = Add your Azure deployments as models in Settings > Models.
+onboarding-open-settings = Open Settings
+onboarding-pick-default = Default model for new conversations
+onboarding-suggested = Suggested
+onboarding-back = Back
+onboarding-finish = Start chatting
+onboarding-skip = Skip setup
+
+## Status footer
+
+footer-indicator-errors = Errors & warnings
+footer-indicator-jobs = Background jobs
+footer-indicator-pack-updates = Prompt pack updates
+footer-indicator-auto-update = App updates
+footer-indicator-tokens = Context & cost
+footer-indicator-local-models = Local models (Ollama)
+footer-indicator-fetch = Web fetch
+footer-indicator-network = Sandbox network
+footer-indicator-tools = Tools
+footer-indicator-mcp = MCP servers
+footer-indicator-agent = Sub-agents
+footer-customize = Customize…
+footer-agents-title = Agents
+footer-api-tools = API Tools
+footer-local-models-tooltip = Ollama: loaded models and memory
+footer-mcp-servers = MCP Servers
+footer-mcp-use-server = Use this server in the current conversation
+footer-paid = Paid
+footer-tokens-actual = Actual (from provider):
+footer-tokens-breakdown = What filled the prompt
+footer-tokens-session = Session totals:
+footer-tools-blocked = Blocked
+footer-tools-code-execution = Code Execution
+footer-tools-docker-fallback = Docker Fallback
+footer-tools-filesystem = Filesystem Tools
+footer-tools-shell = Shell (danger!)
+footer-update-downloading = Downloading...
+footer-local-models-memory = GPU { $vram } · RAM { $ram } ({ $percent }% on GPU)
+footer-local-models-unloads = unloads in { $minutes } min
+footer-local-models-idle = idle
+footer-local-models-none = No models loaded. The next message loads its model first.
+footer-local-models-total = Total: GPU { $vram } · RAM { $ram }
+
+## Tool reference
+
+tool-reference-open = Tool reference…
+tool-reference-title = Tool reference
+tool-reference-search-placeholder = Search tools by name, description or server...
+tool-reference-empty = No tools yet. Tools are listed once a conversation has started its agent.
+tool-reference-no-matches = No matching tools.
+tool-reference-schema = Parameters (JSON schema)
+tool-reference-examples = Example calls
+
+## Settings pages
+
+settings-page-general = General
+settings-page-models = Models
+settings-page-models-description = Configure AI models and their parameters
+settings-page-providers = Providers
+settings-page-extensions = Extensions
+settings-page-internet = Internet
+settings-page-code-execution = Code Execution
+settings-page-guardrails = Guardrails
+settings-page-custom-tools = Custom Tools
+settings-page-remote-hosts = Remote Hosts
+settings-page-memory = Memory
+settings-page-training-data = Training Data
+settings-page-batch-runs = Batch Runs
+settings-page-compare-runs = Compare Runs
+settings-page-snippets = Snippets
+settings-page-prompt-packs = Prompt Packs
+settings-page-secrets = Secrets
+settings-page-managed = Managed
+settings-page-telemetry = Telemetry
+settings-page-tool-usage = Tool Usage
+settings-page-token-usage = Token Usage
+settings-page-storage = Storage
+settings-page-diagnostics = Diagnostics
+
+## General settings
+
+settings-appearance = Appearance
+settings-language = Language
+settings-language-description = Language of menus, buttons and labels. Changes apply immediately.
+settings-language-system = System ({ $language })
+settings-layout-direction = Layout Direction
+settings-layout-direction-description = Right to left puts the sidebar on the right and aligns text without a clear direction to the right. Paragraphs in Arabic, Hebrew and other right-to-left scripts are always aligned right.
+settings-layout-auto = Automatic (follow language)
+settings-layout-ltr = Left to right
+settings-layout-rtl = Right to left (mirrored)
+settings-theme = Theme
+settings-theme-description = Select a theme family (use Dark Mode toggle for light/dark variant)
+settings-dark-mode = Dark Mode
+settings-dark-mode-description = Switch between light and dark variants of the selected theme.
+settings-text = Text Settings
+settings-font-size = Font Size
+settings-font-size-description = Adjust the default font size.
+settings-speech = Speech Mode
+settings-speech-description = Hands-free voice chat: speak a message, hear the reply, and talk over it to interrupt. Transcripts are kept in the conversation like typed messages.
+settings-speech-record-command = Recording Command
+settings-speech-record-command-description = Shell command that streams the microphone as raw 16 kHz mono 16-bit PCM on stdout, e.g. sox -q -d -t raw -r 16000 -c 1 -b 16 -e signed -. Leave empty to turn speech mode off.
+settings-speech-transcribe-command = Transcription Command
+settings-speech-transcribe-command-description = Shell command that prints the transcript of an utterance; {"{"}audio{"}"} is replaced with the path of a 16 kHz WAV file, e.g. whisper-cli -nt -f {"{"}audio{"}"}.
+settings-speech-tts-command = Text-to-Speech Command
+settings-speech-tts-command-description = Optional shell command that reads the reply from stdin aloud, e.g. say or espeak-ng --stdin. Leave empty to only listen.
+settings-composer = Composer
+settings-send-shortcut = Send With
+settings-send-shortcut-description = The other key combination inserts a newline. Shift+Enter always inserts a newline.
+settings-send-enter = Enter
+settings-send-ctrl-enter = Ctrl+Enter
+settings-send-cmd-enter = Cmd+Enter
+settings-composer-expanded = Expanded Composer
+settings-composer-expanded-description = Show a tall multi-line input for writing longer messages. Also toggled from the input toolbar.
+settings-composer-preview = Markdown Preview
+settings-composer-preview-description = Render the draft as Markdown below the expanded composer.
+settings-spelling = Spelling
+settings-spell-check = Check Spelling
+settings-spell-check-description = Underline misspelled words in the chat input using a Hunspell dictionary.
+settings-spell-dictionary = Dictionary
+settings-spell-dictionary-auto = Match UI language
+settings-spell-dictionary-description = Hunspell dictionaries are found in /usr/share/hunspell, ~/Library/Spelling and the dictionaries folder in Chatty's data directory.
+settings-message-size = Message Size
+settings-draft-token-warning = Token Warning Threshold
+settings-draft-token-warning-description = The chat input shows a live token estimate of the draft and its attachments, and turns amber when a single message is above this many tokens. Set to 0 to never warn.
+settings-streaming = Streaming
+settings-smooth-streaming = Smooth Streaming
+settings-smooth-streaming-description = Reveal responses at a steady typing pace instead of in bursts as the provider sends them. Text is never more than a second behind the network, and the full response is shown as soon as it finishes.
+settings-smooth-streaming-rate = Typing Speed
+settings-smooth-streaming-rate-description = Characters per second revealed while smooth streaming is on.
+settings-video = Video Attachments
+settings-video-description = Videos are sent as a handful of evenly spaced frames (requires ffmpeg). The number of frames can be capped per model in the model settings.
+settings-video-transcribe-command = Transcription Command
+settings-video-transcribe-command-description = Optional shell command that prints a transcript of the audio track; {"{"}audio{"}"} is replaced with the path of a 16 kHz WAV file, e.g. whisper-cli -nt -f {"{"}audio{"}"}. Leave empty to send frames only.
+settings-startup = Startup
+settings-restore-session = Restore Previous Session
+settings-restore-session-description = Reopen the window where it was, with the last conversation and scroll position. When off, Chatty starts with an empty chat.
+settings-copy-messages = Copying Messages
+settings-copy-format = Copy Button
+settings-copy-format-description = What the copy button, the copy shortcut and /copy put on the clipboard for assistant answers. The menu next to the copy button offers the other formats.
+settings-copy-format-markdown = Markdown source
+settings-copy-format-plain = Plain text
+settings-copy-format-html = HTML
+settings-copy-format-clean = Markdown without citations or thinking
+settings-copy-context = Copy as Context
+settings-include-traces = Include Traces
+settings-include-traces-description = Add thinking summaries and tool calls (with truncated outputs) when copying selected messages as context.
+settings-printing = Printing
+settings-print-traces = Print Traces
+settings-print-traces-description = Include thinking summaries and tool calls when printing a conversation.
+settings-print-thumbnails = Attachment Thumbnails
+settings-print-thumbnails-description = Print image, PDF and video attachments as small previews instead of just their file names.
+settings-print-header = Page Header
+settings-print-header-description = Repeat the conversation title and date at the top of every printed page.
+settings-status-footer = Status Footer
+settings-footer-indicators = Indicators
+settings-footer-indicators-description = Choose which indicators the footer shows and in what order. Hidden indicators stay reachable from the … menu at the right end of the footer.
+settings-topic-changes = Topic Changes
+settings-suggest-split = Suggest Splitting Conversations
+settings-suggest-split-description = When a message moves to an unrelated topic, offer to continue in a new conversation seeded with a summary of this one. Requires an embedding model.
+settings-cli = CLI Tool
+settings-cli-description = Install the chatty-tui command-line interface so you can use Chatty from your terminal.
+settings-cli-install = Install CLI…
+settings-cli-installing = Installing…
+settings-cli-reinstall = Reinstall CLI
+settings-cli-failed = Installation failed: { $error }
+settings-cli-retry = Retry
+
+## Models settings
+
+settings-models-list = Models List
+settings-models-list-description = All configured AI models
+settings-routing = Model Routing
+settings-routing-description = Classify each message as factual, agentic or vision and send it to the cheapest suitable model. Give models the alias "factual", "agentic" or "vision" to pin a class to them.
+settings-route-each = Route Each Message
+settings-route-each-description = The routing decision is shown in each response's trace.
+settings-classifier = Classifier Model
+settings-classifier-heuristic = Heuristic (no model)
+settings-classifier-description = A small local Ollama model works best; without one a keyword heuristic is used.
+models-aliases = Aliases (comma-separated)
+models-base-model = Base Model
+models-dataset-hash = Training Dataset Hash
+models-embeddings = Embeddings
+models-embeddings-batch-size = Batch Size (texts per request)
+models-embeddings-dimensions = Dimensions
+models-embeddings-dimensions-placeholder = Auto-detected for known models, e.g. 768
+models-embeddings-empty = No embedding models registered.
+models-embeddings-gemini-hint = Gemini embedding models are reached through the OpenRouter provider.
+models-embeddings-name = Name *
+models-embeddings-rate = Max Requests Per Minute
+models-embeddings-rate-placeholder = Unlimited, e.g. 60
+models-identifier = Model Identifier *
+models-lineage = Fine-Tune Lineage (optional)
+models-max-context = Max Context Window (optional)
+models-max-tokens = Max Tokens (optional)
+models-max-video-frames = Max Video Frames (optional)
+models-name = Model Name *
+models-no-matches = No matches
+models-no-results-hint = Try adjusting your search terms
+models-openrouter-catalog = OpenRouter Catalog
+models-openrouter-search = Search OpenRouter models...
+models-preamble = Preamble / System Prompt
+models-preamble-placeholder = System instructions for the model
+models-provider = Provider *
+models-tab-advanced = Advanced
+models-tab-basic = Basic
+models-temperature = Temperature
+models-title = AI Models
+models-top-p = Top P (optional)
+models-training-date = Training Date
+
+## Common actions
+
+action-approve = Approve
+action-cancel = Cancel
+action-check-updates = Check for Updates
+action-clear = Clear
+action-copy-png = Copy as PNG
+action-delete = Delete
+action-deny = Deny
+action-edit = Edit
+action-install = Install
+action-preview = Preview
+action-refresh = Refresh
+action-register = Register
+action-remove = Remove
+action-save = Save
+action-search = Search
+action-sign-in = Sign In
+
+## Chat view
+
+chat-edit-hint = Later messages are removed; the original is kept in the edit log.
+chat-edit-save-rerun = Save & re-run
+chat-edit-tooltip = Edit this message and re-run the conversation from it
+chat-find-close = Close (Escape)
+chat-find-next = Next match (Enter)
+chat-find-placeholder = Find in conversation
+chat-find-previous = Previous match (Shift-Enter)
+chat-input-placeholder = Type a message...
+chat-message-shortcuts = Message shortcuts
+chat-topic-split-new = New Conversation
+chat-topic-split-new-tooltip = Start a new conversation with a summary of this one pinned
+chat-topic-split-prompt = This looks like a new topic. Continue it in a new conversation?
+chat-topic-split-stay = Stay in this conversation
+chat-turn-error = The response failed
+chat-turn-retry = Retry
+chat-turn-show-error-log = Show in Error Log
+chat-unlock-tooltip = Allow changes to this conversation again
+chat-welcome = Welcome to Chatty
+chat-locked = This conversation is locked. Messages can't be sent, deleted or regenerated.
+
+## Messages
+
+chart-no-candlestick-data = No candlestick data provided
+message-add-reaction = Add reaction
+message-bad-response = Bad response
+message-copy-as = Copy as…
+message-copy-code = Copy code
+message-copy-latex = Copy LaTeX
+message-copy-mermaid = Copy Mermaid
+message-export-note = Export answer to note
+message-good-response = Good response
+message-note-placeholder = Private note (not sent to the model)
+message-pin = Pin to context
+message-references = References:
+message-regenerate = Regenerate response
+message-thinking = Thinking
+
+## Agent activity
+
+approval-execute = Execute:
+question-skip = Skip
+question-title = The agent is asking
+trace-approval-requested = Execution approval requested
+trace-error = Error:
+trace-question = Question for you
+trace-running = Running...
+
+## Side panels
+
+artifacts-open = Open
+artifacts-title = Artifacts
+pdf-ask-page = Ask about this page
+pdf-no-text = No text on this page.
+pdf-rendering = Rendering page…
+pinned-title = Pinned context
+pinned-unpin = Unpin
+plan-clear = Clear plan
+variables-hide = Hide
+variables-title = Variables
+artifacts-export-prompt = Export artifacts
+artifacts-exported = Exported { $count } file(s) to { $dest }
+artifacts-exported-missing = Exported { $count } file(s) to { $dest } ({ $missing } no longer exist)
+artifacts-export-failed = Export failed: { $error }
+artifacts-all = All
+artifacts-kind-images = Images
+artifacts-kind-documents = Documents
+artifacts-kind-data = Data
+artifacts-kind-other = Other
+artifacts-turn = Turn { $turn }
+artifacts-all-turns = All turns
+artifacts-detail = Turn { $turn } · { $tool }
+artifacts-detail-missing = Turn { $turn } · file no longer exists
+artifacts-none = No artifacts yet
+artifacts-count = { $total } file(s)
+artifacts-count-filtered = { $shown } of { $total } file(s)
+artifacts-exporting = Exporting…
+artifacts-export = Export to folder…
+artifacts-empty = Images, documents and other files produced by tools in this conversation appear here.
+artifacts-no-matches = No artifacts match the filters.
+pdf-page-of = Page { $page } of { $count }
+pdf-loading = Loading…
+pdf-show-page = Page
+pdf-show-text = Text
+
+## Dialogs
+
+annotate-blur = Blur
+annotate-hint = Drag on the image to annotate. Only the edited copy is sent.
+annotate-undo = Undo
+error-log-clear-all = Clear All
+error-log-copy = Copy error
+error-log-empty = No errors or warnings to display.
+error-log-provider-checks = Provider checks
+search-conversations-placeholder = Search titles and messages...
+annotate-title = Annotate Image
+annotate-crop = Crop
+annotate-arrow = Arrow
+annotate-box = Box
+annotate-apply = Apply
+annotate-applying = Applying…
+
+## Extensions settings
+
+extensions-add = Add
+extensions-add-mcp = Add MCP Server
+extensions-api-key-placeholder = Optional API key
+extensions-email = Email
+extensions-installed = Installed
+extensions-new-password-placeholder = Password (12+ characters)
+extensions-none-installed = No extensions installed yet. Browse the marketplace below.
+extensions-not-signed-in = Not signed in
+extensions-password = Password
+extensions-search-placeholder = Search extensions...
+extensions-sign-out = Sign Out
+extensions-uninstall = Uninstall
+extensions-username-placeholder = Username (3-39 chars, lowercase)
+
+## Guardrails settings
+
+guardrails-empty = No guardrails configured. Add a rule below.
+guardrails-name = Name
+guardrails-name-placeholder = Rule name
+
+## Custom tools settings
+
+custom-tools-base-url = Base URL
+custom-tools-import = Import
+custom-tools-import-openapi = Import OpenAPI…
+custom-tools-load = Load
+custom-tools-no-graphql = No GraphQL endpoints configured. Add one below.
+custom-tools-no-rest = No REST tools defined. Add one below.
+custom-tools-openapi-spec = OpenAPI 3.x spec (file path or contents)
+
+## Remote hosts settings
+
+ssh-hosts-empty = No SSH hosts configured. Add one below.
+
+## Memory settings
+
+memory-browser-disabled = Enable Agent Memory above to use the Memory Browser.
+memory-keyword = Keyword
+memory-load = Load Memories
+memory-loading = Loading memories…
+memory-purge-all = Purge All Memory
+memory-search-placeholder = Search memories...
+memory-semantic = Semantic
+
+## Training data settings
+
+training-comment = Comment
+training-comment-placeholder = Why is this example good or bad?
+training-reject = Reject
+training-write-dataset = Write Approved Dataset
+
+## Batch runs settings
+
+batch-start = Start
+
+## Compare runs settings
+
+compare-export-markdown = Export Markdown
+compare-no-matching-turn = No matching turn
+
+## Snippets settings
+
+snippets-content = Content
+snippets-content-placeholder = Text or code to insert
+snippets-name = Name
+
+## Prompt packs settings
+
+prompt-packs-browse = Browse…
+prompt-packs-empty = No prompt packs installed.
+prompt-packs-description = Shared snippets, model profiles and tools from the community
+prompt-packs-import-description = Import a signed pack from a URL or file. Templates become snippets, profiles become models and tools are added to the MCP catalog. Check the publisher key before installing; updates must be signed by the same key.
+prompt-packs-source-placeholder = https://example.com/packs/writing.json or a local file path
+prompt-packs-select = Select prompt pack
+prompt-packs-update = Update
+prompt-packs-update-to = Update to v{ $version }
+prompt-packs-provenance = By { $author } · signed by key { $key } · from { $source }
+prompt-packs-unknown-author = unknown author
+prompt-packs-publisher-changed = This version is signed by a different publisher than the installed one and cannot be installed over it. Remove the installed pack first if you trust the new publisher.
+prompt-packs-kind-template = Template
+prompt-packs-kind-profile = Profile
+prompt-packs-kind-tool = Tool
+prompt-packs-change-new = New
+prompt-packs-change-updated = Updated
+prompt-packs-change-unchanged = Unchanged
+prompt-packs-change-conflict = Kept yours
+prompt-packs-change-needs-setup = Needs setup
+prompt-packs-change-removed = Removed
+prompt-packs-installed-summary = v{ $version } · { $templates } templates · { $profiles } profiles · { $tools } tools · key { $key }
+
+## Secrets settings
+
+secrets-value = Value
+secrets-variable-name = Variable Name
+
+## Telemetry settings
+
+telemetry-clear = Clear Data
+telemetry-empty = Nothing collected
+telemetry-export = Export JSON…
+
+## Tool usage settings
+
+tool-stats-calls = Calls
+tool-stats-clear = Clear Statistics
+tool-stats-empty = No tool calls recorded yet
+tool-stats-latency = Avg latency
+tool-stats-success = Success
+tool-stats-tool = Tool
+
+## Token usage settings
+
+token-usage-export = Export CSV…
+token-usage-description = Export the tokens, model, latency and estimated cost of every response, for expense reports or your own analysis.
+token-usage-activity = Activity
+token-usage-activity-description = Messages per day, from the time each message was sent or received. Click a day to list its conversations in the sidebar.
+token-usage-counting = Counting messages…
+token-usage-calendar-summary = { $count } messages in the last { $weeks } weeks
+token-usage-day-none = No messages on { $day }
+token-usage-day-one = 1 message on { $day }
+token-usage-day-many = { $count } messages on { $day }
+token-usage-export-title = Export
+token-usage-export-description = One row per response, in UTC. Latency is measured from sending the prompt to the finished response; cost is empty for models without pricing.
+token-usage-this-month = This Month
+token-usage-last-30-days = Last 30 Days
+token-usage-all-time = All Time
+
+## Storage settings
+
+storage-remove-unused = Remove Unused Files
+storage-description = Attachments and files produced by tools are copied into one folder, named by their content, so a file attached many times is stored once.
+storage-attachments = Attachments
+storage-attachments-description = Files stay while any conversation uses them. Unused files, e.g. from deleted conversations, are only removed when you ask, and never within an hour of being added.
+storage-scanning = Scanning attachments…
+storage-summary = { $count } files, { $size } ({ $saved } saved by deduplication)
+storage-no-orphans = Every file is used by a conversation
+storage-orphan = 1 file ({ $size }) is not used by any conversation
+storage-orphans = { $count } files ({ $size }) are not used by any conversation
+storage-unused = unused
+storage-reference = 1 reference
+storage-references = { $count } references
+storage-removed = Removed { $count } files ({ $size })
+storage-removed-kept = Removed { $count } files ({ $size }); kept { $kept } added in the last hour
+storage-cleanup-failed = Cleanup failed: { $error }
+storage-cleanup-skipped = Cleanup skipped: conversations could not be loaded ({ $error })
+
+## Diagnostics settings
+
+diagnostics-no-phases = No phases recorded
@@ -82,6 +82,9 @@ impl CuratedMcpEntry {
             api_key: None,
             enabled: self.default_enabled,
             is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
         }
     }
 
@@ -38,7 +38,7 @@ const SCHEMA_VERSION: &str = "ATIF-v1.6";
 /// 2. Build the agent block from model_id and optional ModelConfig
 /// 3. Iterate through message history, building one ATIF step per message
 /// 4. Build final_metrics from ConversationTokenUsage totals
-/// 5. Build the extra block (feedback + regenerations)
+/// 5. Build the extra block (feedback + regenerations + reviewer annotations)
 pub fn conversation_to_atif(
     conversation: &ConversationData,
     model_config: Option<&ModelConfig>,
@@ -58,6 +58,10 @@ pub fn conversation_to_atif(
         serde_json::from_str(&conversation.message_feedback).unwrap_or_default();
     let regeneration_records: Vec<RegenerationRecord> =
         serde_json::from_str(&conversation.regeneration_records).unwrap_or_default();
+    let reactions: Vec<Vec<String>> =
+        serde_json::from_str(&conversation.message_reactions).unwrap_or_default();
+    let notes: Vec<Option<String>> =
+        serde_json::from_str(&conversation.message_notes).unwrap_or_default();
 
     // PHASE 2: Build agent block
     let agent = build_agent(&conversation.model_id, model_config);
@@ -101,8 +105,9 @@ pub fn conversation_to_atif(
         total_steps: Some(steps.len() as u32),
     };
 
-    // PHASE 5: Build extra (feedback + regenerations)
-    let extra = build_extra(&feedback, &regeneration_records);
+    // PHASE 5: Build extra (feedback + regenerations + reviewer annotations)
+    let annotations = ReviewerAnnotation::collect(&reactions, &notes);
+    let extra = build_extra(&feedback, &regeneration_records, annotations);
 
     let export = AtifExport {
         schema_version: SCHEMA_VERSION.to_string(),
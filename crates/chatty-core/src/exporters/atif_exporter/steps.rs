@@ -26,11 +26,16 @@ pub(super) fn build_agent(model_id: &str, model_config: Option<&ModelConfig>) ->
     match model_config {
         Some(cfg) => {
             let provider = provider_name(&cfg.provider_type);
+            let mut extra = serde_json::json!({ "provider": provider });
+            // Fine-tune provenance, so trajectories can be traced to a training run
+            if let Some(lineage) = &cfg.lineage {
+                extra["fine_tune"] = serde_json::json!(lineage);
+            }
             AtifAgent {
                 name: "chatty".to_string(),
                 version: env!("CARGO_PKG_VERSION").to_string(),
                 model_name: Some(cfg.model_identifier.clone()),
-                extra: Some(serde_json::json!({ "provider": provider })),
+                extra: Some(extra),
             }
         }
         None => AtifAgent {
@@ -238,6 +243,7 @@ pub(super) fn parse_trace(
 pub(super) fn build_extra(
     feedback: &[Option<MessageFeedback>],
     regenerations: &[RegenerationRecord],
+    annotations: Vec<ReviewerAnnotation>,
 ) -> AtifExtra {
     let feedback_strings: Vec<Option<String>> = feedback
         .iter()
@@ -260,5 +266,6 @@ pub(super) fn build_extra(
     AtifExtra {
         feedback: feedback_strings,
         regenerations: atif_regenerations,
+        annotations,
     }
 }
@@ -8,6 +8,7 @@ use crate::models::message_types::{
     SystemTrace, ThinkingBlock, ThinkingState, ToolCallBlock, ToolCallState, ToolSource, TraceItem,
 };
 use crate::models::token_usage::TokenUsage;
+use crate::settings::models::models_store::ModelLineage;
 use crate::settings::models::providers_store::ProviderType;
 use rig_core::OneOrMany;
 use rig_core::completion::message::{AssistantContent, Text, UserContent};
@@ -35,11 +36,28 @@ fn make_conversation_data(
         attachment_paths: serde_json::to_string(&attachment_paths).unwrap(),
         message_timestamps: serde_json::to_string(&timestamps).unwrap(),
         message_feedback: serde_json::to_string(&feedback).unwrap(),
+        message_reactions: "[]".to_string(),
+        message_notes: "[]".to_string(),
+        message_errors: "[]".to_string(),
         regeneration_records: serde_json::to_string(&regeneration_records).unwrap(),
         created_at: 1700000000,
         updated_at: 1700000100,
         working_dir: None,
         agent_task_snapshot: None,
+        pinned_items: "[]".to_string(),
+        variables: "{}".to_string(),
+        execution_plan: None,
+        confidential: false,
+        history_policy: r#"{"mode":"full"}"#.to_string(),
+        disabled_mcp_servers: "[]".to_string(),
+        disabled_tools: "[]".to_string(),
+        label: "{}".to_string(),
+        tags: "[]".to_string(),
+        folder: None,
+        history_edits: "[]".to_string(),
+        compaction_records: "[]".to_string(),
+        locked: false,
+        quality_score: None,
     }
 }
 
@@ -60,6 +78,10 @@ fn make_model_config(provider_type: ProviderType) -> ModelConfig {
         supports_pdf: true,
         supports_temperature: true,
         max_context_window: None,
+        max_video_frames: None,
+        aliases: Vec::new(),
+        default_rules: Vec::new(),
+        lineage: None,
     }
 }
 
@@ -131,7 +153,7 @@ fn build_extra_maps_feedback() {
         Some(MessageFeedback::ThumbsDown),
         None,
     ];
-    let extra = build_extra(&feedback, &[]);
+    let extra = build_extra(&feedback, &[], Vec::new());
     assert_eq!(
         extra.feedback,
         vec![
@@ -152,7 +174,7 @@ fn build_extra_regenerations() {
         original_timestamp: 1700000000,
         regeneration_timestamp: 1700000010,
     }];
-    let extra = build_extra(&[], &regen);
+    let extra = build_extra(&[], &regen, Vec::new());
     assert_eq!(extra.regenerations.len(), 1);
     assert_eq!(extra.regenerations[0].message_index, 1);
     assert_eq!(extra.regenerations[0].original_text, "old response");
@@ -179,6 +201,10 @@ fn parse_trace_extracts_thinking_content() {
         })],
         total_duration: None,
         active_tool_index: None,
+        routing: None,
+        request_privacy: None,
+        overrides: None,
+        budget_reached: None,
     };
     let json = serde_json::to_value(&trace).unwrap();
     let (reasoning, _) = parse_trace(Some(json));
@@ -204,6 +230,10 @@ fn parse_trace_joins_multiple_thinking_blocks() {
         ],
         total_duration: None,
         active_tool_index: None,
+        routing: None,
+        request_privacy: None,
+        overrides: None,
+        budget_reached: None,
     };
     let json = serde_json::to_value(&trace).unwrap();
     let (reasoning, _) = parse_trace(Some(json));
@@ -231,6 +261,10 @@ fn parse_trace_extracts_tool_output() {
         })],
         total_duration: None,
         active_tool_index: None,
+        routing: None,
+        request_privacy: None,
+        overrides: None,
+        budget_reached: None,
     };
     let json = serde_json::to_value(&trace).unwrap();
     let (_, outputs) = parse_trace(Some(json));
@@ -324,6 +358,36 @@ fn agent_from_model_config() {
     assert_eq!(result["agent"]["extra"]["provider"], "openrouter");
 }
 
+#[test]
+fn agent_extra_carries_fine_tune_lineage() {
+    let conv = make_conversation_data(
+        "id",
+        "model-1",
+        vec![],
+        vec![],
+        ConversationTokenUsage::default(),
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+    );
+    let mut cfg = make_model_config(ProviderType::AzureOpenAI);
+    cfg.lineage = Some(ModelLineage {
+        base_model: "gpt-4o-mini-2024-07-18".to_string(),
+        dataset_hash: Some("3f2a".to_string()),
+        trained_on: None,
+    });
+    let result = conversation_to_atif(&conv, Some(&cfg)).unwrap();
+    let fine_tune = &result["agent"]["extra"]["fine_tune"];
+    assert_eq!(fine_tune["base_model"], "gpt-4o-mini-2024-07-18");
+    assert_eq!(fine_tune["dataset_hash"], "3f2a");
+    assert!(fine_tune.get("trained_on").is_none());
+
+    let cfg = make_model_config(ProviderType::AzureOpenAI);
+    let result = conversation_to_atif(&conv, Some(&cfg)).unwrap();
+    assert!(result["agent"]["extra"].get("fine_tune").is_none());
+}
+
 #[test]
 fn agent_fallback_without_model_config() {
     let conv = make_conversation_data(
@@ -553,6 +617,10 @@ fn tool_calls_with_observation() {
         })],
         total_duration: None,
         active_tool_index: None,
+        routing: None,
+        request_privacy: None,
+        overrides: None,
+        budget_reached: None,
     };
 
     let history = vec![Message::Assistant {
@@ -610,6 +678,10 @@ fn reasoning_extracted_from_trace() {
         })],
         total_duration: None,
         active_tool_index: None,
+        routing: None,
+        request_privacy: None,
+        overrides: None,
+        budget_reached: None,
     };
 
     let conv = make_conversation_data(
@@ -665,6 +737,10 @@ fn duplicate_tool_names_matched_by_id() {
         ],
         total_duration: None,
         active_tool_index: None,
+        routing: None,
+        request_privacy: None,
+        overrides: None,
+        budget_reached: None,
     };
 
     let history = vec![Message::Assistant {
@@ -765,6 +841,46 @@ fn regeneration_records_in_extra() {
     assert_eq!(regens[0]["timestamp"], 1700000010);
 }
 
+#[test]
+fn reviewer_annotations_in_extra() {
+    let mut conv = make_conversation_data(
+        "id",
+        "m",
+        vec![user_message("Hi"), assistant_message("Hello")],
+        vec![None, None],
+        ConversationTokenUsage::default(),
+        vec![vec![], vec![]],
+        vec![None, None],
+        vec![None, None],
+        vec![],
+    );
+    conv.message_reactions = r#"[[], ["🎯", "🐛"]]"#.to_string();
+    conv.message_notes = r#"[null, "Good answer, wrong unit"]"#.to_string();
+    let result = conversation_to_atif(&conv, None).unwrap();
+    let annotations = result["extra"]["annotations"].as_array().unwrap();
+    assert_eq!(annotations.len(), 1);
+    assert_eq!(annotations[0]["message_index"], 1);
+    assert_eq!(annotations[0]["reactions"][1], "🐛");
+    assert_eq!(annotations[0]["note"], "Good answer, wrong unit");
+}
+
+#[test]
+fn no_annotations_key_without_reviewer_data() {
+    let conv = make_conversation_data(
+        "id",
+        "m",
+        vec![user_message("Hi"), assistant_message("Hello")],
+        vec![None, None],
+        ConversationTokenUsage::default(),
+        vec![vec![], vec![]],
+        vec![None, None],
+        vec![None, None],
+        vec![],
+    );
+    let result = conversation_to_atif(&conv, None).unwrap();
+    assert!(result["extra"].get("annotations").is_none());
+}
+
 // ── Edge cases ────────────────────────────────────────────────────
 
 #[test]
@@ -779,11 +895,28 @@ fn malformed_token_usage_defaults_to_zero() {
         attachment_paths: "[]".to_string(),
         message_timestamps: "[]".to_string(),
         message_feedback: "[]".to_string(),
+        message_reactions: "[]".to_string(),
+        message_notes: "[]".to_string(),
+        message_errors: "[]".to_string(),
         regeneration_records: "[]".to_string(),
         created_at: 0,
         updated_at: 0,
         working_dir: None,
         agent_task_snapshot: None,
+        pinned_items: "[]".to_string(),
+        variables: "{}".to_string(),
+        execution_plan: None,
+        confidential: false,
+        history_policy: r#"{"mode":"full"}"#.to_string(),
+        disabled_mcp_servers: "[]".to_string(),
+        disabled_tools: "[]".to_string(),
+        label: "{}".to_string(),
+        tags: "[]".to_string(),
+        folder: None,
+        history_edits: "[]".to_string(),
+        compaction_records: "[]".to_string(),
+        locked: false,
+        quality_score: None,
     };
     let result = conversation_to_atif(&conv, None).unwrap();
     assert_eq!(result["final_metrics"]["total_prompt_tokens"], 0);
@@ -803,11 +936,28 @@ fn malformed_message_history_returns_err() {
         attachment_paths: "[]".to_string(),
         message_timestamps: "[]".to_string(),
         message_feedback: "[]".to_string(),
+        message_reactions: "[]".to_string(),
+        message_notes: "[]".to_string(),
+        message_errors: "[]".to_string(),
         regeneration_records: "[]".to_string(),
         created_at: 0,
         updated_at: 0,
         working_dir: None,
         agent_task_snapshot: None,
+        pinned_items: "[]".to_string(),
+        variables: "{}".to_string(),
+        execution_plan: None,
+        confidential: false,
+        history_policy: r#"{"mode":"full"}"#.to_string(),
+        disabled_mcp_servers: "[]".to_string(),
+        disabled_tools: "[]".to_string(),
+        label: "{}".to_string(),
+        tags: "[]".to_string(),
+        folder: None,
+        history_edits: "[]".to_string(),
+        compaction_records: "[]".to_string(),
+        locked: false,
+        quality_score: None,
     };
     assert!(conversation_to_atif(&conv, None).is_err());
 }
@@ -864,6 +1014,10 @@ fn snapshot_full_conversation() {
         ],
         total_duration: None,
         active_tool_index: None,
+        routing: None,
+        request_privacy: None,
+        overrides: None,
+        budget_reached: None,
     };
 
     let mut usage = ConversationTokenUsage::default();
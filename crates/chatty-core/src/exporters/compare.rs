@@ -0,0 +1,465 @@
+//! Turn-by-turn comparison of two conversation runs.
+//!
+//! A run is either an ATIF export (`*.atif.json`) or a batch results file
+//! (`*.jsonl` written by `batch_runner`). Batch results are aligned by row id,
+//! ATIF exports by turn position. Each aligned pair gets a word-level diff of
+//! the assistant replies and a list of tool-call divergences, and the whole
+//! comparison can be rendered as Markdown.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+use similar::{ChangeTag, TextDiff};
+
+/// Replies longer than this are compared without a word-level diff.
+const MAX_DIFF_CHARS: usize = 50_000;
+
+/// One user prompt and everything the assistant did in reply.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CompareTurn {
+    /// Alignment key (batch row id); `None` aligns by position
+    pub key: Option<String>,
+    pub user: String,
+    pub assistant: String,
+    /// Tool calls in order, as `name(arguments)`
+    pub tool_calls: Vec<String>,
+}
+
+/// One side of a comparison.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompareRun {
+    pub label: String,
+    pub turns: Vec<CompareTurn>,
+}
+
+/// A piece of a word-level diff between two replies.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiffSpan {
+    Same(String),
+    /// Only in the left reply
+    Removed(String),
+    /// Only in the right reply
+    Added(String),
+}
+
+/// Two aligned turns. Either side is `None` when one run has no matching turn.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TurnComparison {
+    pub label: String,
+    pub left: Option<CompareTurn>,
+    pub right: Option<CompareTurn>,
+    /// Word-level diff of the assistant replies (empty when a side is missing)
+    pub diff: Vec<DiffSpan>,
+    pub tool_calls_differ: bool,
+}
+
+impl TurnComparison {
+    pub fn is_identical(&self) -> bool {
+        !self.tool_calls_differ
+            && self.left.is_some()
+            && self.right.is_some()
+            && self
+                .diff
+                .iter()
+                .all(|span| matches!(span, DiffSpan::Same(_)))
+    }
+
+    /// Ratio of unchanged reply characters, from 0.0 to 1.0.
+    pub fn similarity(&self) -> f32 {
+        let (mut same, mut total) = (0usize, 0usize);
+        for span in &self.diff {
+            match span {
+                DiffSpan::Same(text) => {
+                    same += 2 * text.len();
+                    total += 2 * text.len();
+                }
+                DiffSpan::Removed(text) | DiffSpan::Added(text) => total += text.len(),
+            }
+        }
+        if total == 0 {
+            if self.left.is_some() && self.right.is_some() {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            same as f32 / total as f32
+        }
+    }
+}
+
+/// Load a run from an ATIF export or a batch results file.
+pub async fn load_run(path: &Path) -> Result<CompareRun> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let label = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let turns = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => {
+            let value: Value = serde_json::from_str(&content)
+                .with_context(|| format!("{} is not valid JSON", path.display()))?;
+            turns_from_atif(&value)?
+        }
+        Some("jsonl") => turns_from_batch_results(&content)?,
+        _ => bail!("Unsupported file; use an ATIF .json export or a batch .jsonl result"),
+    };
+    Ok(CompareRun { label, turns })
+}
+
+fn message_text(message: &Value) -> String {
+    match message {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Group ATIF steps into turns: each user step starts a turn and the agent
+/// steps that follow are folded into it.
+pub fn turns_from_atif(export: &Value) -> Result<Vec<CompareTurn>> {
+    let Some(steps) = export.get("steps").and_then(Value::as_array) else {
+        bail!("Not an ATIF export: missing \"steps\"");
+    };
+
+    let mut turns: Vec<CompareTurn> = Vec::new();
+    for step in steps {
+        let text = step.get("message").map(message_text).unwrap_or_default();
+        match step.get("source").and_then(Value::as_str) {
+            Some("user") => turns.push(CompareTurn {
+                user: text,
+                ..Default::default()
+            }),
+            Some("agent") => {
+                if turns.is_empty() {
+                    turns.push(CompareTurn::default());
+                }
+                let turn = turns.last_mut().expect("turn pushed above");
+                if !text.is_empty() {
+                    if !turn.assistant.is_empty() {
+                        turn.assistant.push_str("\n\n");
+                    }
+                    turn.assistant.push_str(&text);
+                }
+                for call in step
+                    .get("tool_calls")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                {
+                    let name = call
+                        .get("function_name")
+                        .and_then(Value::as_str)
+                        .unwrap_or("?");
+                    let args = call.get("arguments").cloned().unwrap_or(Value::Null);
+                    turn.tool_calls.push(format!("{name}({args})"));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(turns)
+}
+
+/// One turn per batch result line, keyed by row id.
+pub fn turns_from_batch_results(content: &str) -> Result<Vec<CompareTurn>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(ix, line)| {
+            let value: Value = serde_json::from_str(line)
+                .with_context(|| format!("Invalid JSON on line {}", ix + 1))?;
+            let field = |name: &str| {
+                value
+                    .get(name)
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let assistant = match value.get("error").and_then(Value::as_str) {
+                Some(error) => format!("[error] {error}"),
+                None => field("response"),
+            };
+            Ok(CompareTurn {
+                key: value.get("id").and_then(Value::as_str).map(str::to_string),
+                user: field("prompt"),
+                assistant,
+                tool_calls: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+fn diff_replies(left: &str, right: &str) -> Vec<DiffSpan> {
+    if left.len() + right.len() > MAX_DIFF_CHARS {
+        return if left == right {
+            vec![DiffSpan::Same(left.to_string())]
+        } else {
+            vec![
+                DiffSpan::Removed(left.to_string()),
+                DiffSpan::Added(right.to_string()),
+            ]
+        };
+    }
+
+    let mut spans: Vec<DiffSpan> = Vec::new();
+    for change in TextDiff::from_words(left, right).iter_all_changes() {
+        let text = change.value();
+        match (change.tag(), spans.last_mut()) {
+            (ChangeTag::Equal, Some(DiffSpan::Same(prev)))
+            | (ChangeTag::Delete, Some(DiffSpan::Removed(prev)))
+            | (ChangeTag::Insert, Some(DiffSpan::Added(prev))) => prev.push_str(text),
+            (ChangeTag::Equal, _) => spans.push(DiffSpan::Same(text.to_string())),
+            (ChangeTag::Delete, _) => spans.push(DiffSpan::Removed(text.to_string())),
+            (ChangeTag::Insert, _) => spans.push(DiffSpan::Added(text.to_string())),
+        }
+    }
+    spans
+}
+
+fn compare_turns(
+    label: String,
+    left: Option<&CompareTurn>,
+    right: Option<&CompareTurn>,
+) -> TurnComparison {
+    let diff = match (left, right) {
+        (Some(l), Some(r)) => diff_replies(&l.assistant, &r.assistant),
+        _ => Vec::new(),
+    };
+    let tool_calls_differ = match (left, right) {
+        (Some(l), Some(r)) => l.tool_calls != r.tool_calls,
+        _ => false,
+    };
+    TurnComparison {
+        label,
+        left: left.cloned(),
+        right: right.cloned(),
+        diff,
+        tool_calls_differ,
+    }
+}
+
+/// Align the turns of two runs. Runs where every turn has a key are aligned
+/// by key (in left-then-right order); otherwise turns are paired by position.
+pub fn compare_runs(left: &CompareRun, right: &CompareRun) -> Vec<TurnComparison> {
+    let keyed = |run: &CompareRun| run.turns.iter().all(|t| t.key.is_some());
+    if !left.turns.is_empty() && keyed(left) && keyed(right) {
+        let mut seen = BTreeSet::new();
+        return left
+            .turns
+            .iter()
+            .chain(&right.turns)
+            .filter_map(|t| t.key.as_deref())
+            .filter(|key| seen.insert(*key))
+            .map(|key| {
+                let find = |run: &'_ CompareRun| {
+                    run.turns
+                        .iter()
+                        .find(|t| t.key.as_deref() == Some(key))
+                        .cloned()
+                };
+                let (l, r) = (find(left), find(right));
+                compare_turns(format!("id {key}"), l.as_ref(), r.as_ref())
+            })
+            .collect();
+    }
+
+    (0..left.turns.len().max(right.turns.len()))
+        .map(|ix| {
+            compare_turns(
+                format!("Turn {}", ix + 1),
+                left.turns.get(ix),
+                right.turns.get(ix),
+            )
+        })
+        .collect()
+}
+
+/// Render a comparison as Markdown. Removed words are shown as `~~struck~~`
+/// and added words in **bold**.
+pub fn comparison_to_markdown(
+    left: &CompareRun,
+    right: &CompareRun,
+    comparisons: &[TurnComparison],
+) -> String {
+    let identical = comparisons.iter().filter(|c| c.is_identical()).count();
+    let mut md = format!(
+        "# Comparison\n\n- **Left:** {}\n- **Right:** {}\n- **Turns:** {} ({} identical)\n\n",
+        left.label,
+        right.label,
+        comparisons.len(),
+        identical
+    );
+
+    for comparison in comparisons {
+        md.push_str(&format!(
+            "## {} — {:.0}% similar\n\n",
+            comparison.label,
+            comparison.similarity() * 100.0
+        ));
+        let prompt = comparison
+            .left
+            .as_ref()
+            .or(comparison.right.as_ref())
+            .map(|t| t.user.trim())
+            .unwrap_or_default();
+        if !prompt.is_empty() {
+            md.push_str(&format!("**Prompt:** {prompt}\n\n"));
+        }
+
+        match (&comparison.left, &comparison.right) {
+            (Some(_), Some(_)) => {
+                md.push_str("**Reply diff:**\n\n");
+                for span in &comparison.diff {
+                    match span {
+                        DiffSpan::Same(text) => md.push_str(text),
+                        DiffSpan::Removed(text) if !text.trim().is_empty() => {
+                            md.push_str(&format!("~~{}~~ ", text.trim()))
+                        }
+                        DiffSpan::Added(text) if !text.trim().is_empty() => {
+                            md.push_str(&format!("**{}** ", text.trim()))
+                        }
+                        DiffSpan::Removed(text) | DiffSpan::Added(text) => md.push_str(text),
+                    }
+                }
+                md.push_str("\n\n");
+            }
+            (Some(_), None) => md.push_str("_Only in the left run._\n\n"),
+            (None, Some(_)) => md.push_str("_Only in the right run._\n\n"),
+            (None, None) => {}
+        }
+
+        if comparison.tool_calls_differ {
+            md.push_str("**Tool calls diverge:**\n\n| Left | Right |\n|---|---|\n");
+            let (l, r) = (
+                comparison
+                    .left
+                    .as_ref()
+                    .map(|t| &t.tool_calls[..])
+                    .unwrap_or_default(),
+                comparison
+                    .right
+                    .as_ref()
+                    .map(|t| &t.tool_calls[..])
+                    .unwrap_or_default(),
+            );
+            for ix in 0..l.len().max(r.len()) {
+                let cell = |calls: &[String]| {
+                    calls
+                        .get(ix)
+                        .map(|c| format!("`{}`", c.replace('|', "\\|")))
+                        .unwrap_or_else(|| "—".to_string())
+                };
+                md.push_str(&format!("| {} | {} |\n", cell(l), cell(r)));
+            }
+            md.push('\n');
+        }
+    }
+    md
+}
+
+/// Path for a Markdown comparison of `left` and `right` in `dir`.
+pub fn markdown_path(dir: &Path, left: &CompareRun, right: &CompareRun) -> PathBuf {
+    let stem = |label: &str| {
+        label
+            .split('.')
+            .next()
+            .unwrap_or(label)
+            .chars()
+            .take(40)
+            .collect::<String>()
+    };
+    dir.join(format!(
+        "{}-vs-{}.compare.md",
+        stem(&left.label),
+        stem(&right.label)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn atif(reply: &str, tool: &str) -> CompareRun {
+        let export = json!({
+            "steps": [
+                {"step_id": 1, "source": "user", "message": "Find the bug"},
+                {"step_id": 2, "source": "agent", "message": "",
+                 "tool_calls": [{"tool_call_id": "1", "function_name": tool, "arguments": {"path": "a.rs"}}]},
+                {"step_id": 3, "source": "agent", "message": reply},
+                {"step_id": 4, "source": "user", "message": "Thanks"},
+            ]
+        });
+        CompareRun {
+            label: "run.atif.json".into(),
+            turns: turns_from_atif(&export).unwrap(),
+        }
+    }
+
+    #[test]
+    fn aligns_atif_turns_by_position_and_flags_divergence() {
+        let left = atif("The bug is in parse", "read_file");
+        let right = atif("The bug is in lex", "search_code");
+        assert_eq!(left.turns.len(), 2);
+        assert_eq!(
+            left.turns[0].tool_calls,
+            vec![r#"read_file({"path":"a.rs"})"#]
+        );
+
+        let comparisons = compare_runs(&left, &right);
+        assert_eq!(comparisons.len(), 2);
+        assert!(comparisons[0].tool_calls_differ);
+        assert!(
+            comparisons[0]
+                .diff
+                .contains(&DiffSpan::Removed("parse".into()))
+        );
+        assert!(comparisons[0].diff.contains(&DiffSpan::Added("lex".into())));
+        assert!(comparisons[1].is_identical());
+
+        let md = comparison_to_markdown(&left, &right, &comparisons);
+        assert!(md.contains("~~parse~~"));
+        assert!(md.contains("**lex**"));
+        assert!(md.contains("| `read_file({\"path\":\"a.rs\"})` | `search_code"));
+    }
+
+    #[test]
+    fn aligns_batch_results_by_id() {
+        let left = CompareRun {
+            label: "l.jsonl".into(),
+            turns: turns_from_batch_results(
+                "{\"id\":\"a\",\"prompt\":\"p\",\"response\":\"yes\"}\n\
+                 {\"id\":\"b\",\"prompt\":\"q\",\"response\":\"no\"}\n",
+            )
+            .unwrap(),
+        };
+        let right = CompareRun {
+            label: "r.jsonl".into(),
+            turns: turns_from_batch_results(
+                "{\"id\":\"b\",\"prompt\":\"q\",\"response\":\"no\"}\n\
+                 {\"id\":\"c\",\"prompt\":\"r\",\"error\":\"timeout\"}\n",
+            )
+            .unwrap(),
+        };
+
+        let comparisons = compare_runs(&left, &right);
+        let labels: Vec<_> = comparisons.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, ["id a", "id b", "id c"]);
+        assert!(comparisons[0].right.is_none());
+        assert!(comparisons[1].is_identical());
+        assert_eq!(
+            comparisons[2].right.as_ref().unwrap().assistant,
+            "[error] timeout"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! "Copy as context": messages serialized as one compact quoted block for
+//! pasting into another tool or a new conversation.
+//!
+//! Each message starts with its role (`**User:**` / `**Assistant:**`).
+//! Trace items are optional; tool inputs and outputs are collapsed to a
+//! single line and truncated to [`TOOL_TEXT_CHARS`].
+
+use crate::models::message_types::{SystemTrace, ToolCallState, TraceItem};
+
+/// Characters of a tool input or output kept before truncating.
+pub const TOOL_TEXT_CHARS: usize = 300;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuoteRole {
+    User,
+    Assistant,
+}
+
+impl QuoteRole {
+    fn label(self) -> &'static str {
+        match self {
+            Self::User => "User",
+            Self::Assistant => "Assistant",
+        }
+    }
+}
+
+/// One message to quote.
+pub struct QuotedMessage<'a> {
+    pub role: QuoteRole,
+    pub content: &'a str,
+    pub trace: Option<&'a SystemTrace>,
+}
+
+/// Quote `messages` in order. Trace items (thinking summaries, tool calls,
+/// answered questions) are only included with `include_traces`.
+pub fn format_context_quote(messages: &[QuotedMessage<'_>], include_traces: bool) -> String {
+    let blocks: Vec<Vec<String>> = messages
+        .iter()
+        .map(|msg| {
+            let mut lines = Vec::new();
+            if include_traces && let Some(trace) = msg.trace {
+                lines.extend(trace.items.iter().filter_map(trace_line));
+            }
+            lines.extend(msg.content.trim().lines().map(str::to_string));
+            if let Some(first) = lines.first_mut() {
+                *first = format!("**{}:** {first}", msg.role.label());
+            } else {
+                lines.push(format!("**{}:**", msg.role.label()));
+            }
+            lines
+        })
+        .collect();
+
+    blocks
+        .iter()
+        .map(|lines| {
+            lines
+                .iter()
+                .map(|line| quote_line(line))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n>\n")
+}
+
+fn quote_line(line: &str) -> String {
+    if line.is_empty() {
+        ">".to_string()
+    } else {
+        format!("> {line}")
+    }
+}
+
+fn trace_line(item: &TraceItem) -> Option<String> {
+    match item {
+        TraceItem::Thinking(thinking) => {
+            let summary = compact(&thinking.summary);
+            (!summary.is_empty()).then(|| format!("[thinking] {summary}"))
+        }
+        TraceItem::ToolCall(tool_call) => {
+            let result = match &tool_call.state {
+                ToolCallState::Running => "(running)".to_string(),
+                ToolCallState::Error(err) => format!("error: {}", compact(err)),
+                ToolCallState::Success => tool_call
+                    .output
+                    .as_deref()
+                    .or(tool_call.output_preview.as_deref())
+                    .map(compact)
+                    .unwrap_or_default(),
+            };
+            Some(format!(
+                "[tool {}] {} → {result}",
+                tool_call.display_name,
+                compact(&tool_call.input)
+            ))
+        }
+        TraceItem::ApprovalPrompt(approval) if approval.question.is_some() => Some(format!(
+            "[question] {} → {}",
+            compact(&approval.command),
+            approval.answer.as_deref().map(compact).unwrap_or_default()
+        )),
+        TraceItem::ApprovalPrompt(_) => None,
+        TraceItem::Reflection(reflection) => Some(format!(
+            "[self-check {}/5] {}",
+            reflection.score,
+            compact(&reflection.critique)
+        )),
+    }
+}
+
+/// `text` on one line, truncated to [`TOOL_TEXT_CHARS`].
+fn compact(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match line.char_indices().nth(TOOL_TEXT_CHARS) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::message_types::{ToolCallBlock, ToolSource};
+
+    fn trace_with_tool(output: &str) -> SystemTrace {
+        SystemTrace {
+            items: vec![TraceItem::ToolCall(ToolCallBlock {
+                id: "tc_1".to_string(),
+                tool_name: "read_file".to_string(),
+                display_name: "read_file".to_string(),
+                input: "{\n  \"path\": \"a.txt\"\n}".to_string(),
+                output: Some(output.to_string()),
+                output_preview: None,
+                state: ToolCallState::Success,
+                duration: None,
+                text_before: String::new(),
+                source: ToolSource::Local,
+                execution_engine: None,
+            })],
+            total_duration: None,
+            active_tool_index: None,
+            routing: None,
+            request_privacy: None,
+            overrides: None,
+            budget_reached: None,
+        }
+    }
+
+    #[test]
+    fn messages_are_role_prefixed_and_quoted() {
+        let quote = format_context_quote(
+            &[
+                QuotedMessage {
+                    role: QuoteRole::User,
+                    content: "Hi\n\nthere",
+                    trace: None,
+                },
+                QuotedMessage {
+                    role: QuoteRole::Assistant,
+                    content: "Hello",
+                    trace: None,
+                },
+            ],
+            false,
+        );
+        assert_eq!(
+            quote,
+            "> **User:** Hi\n>\n> there\n>\n> **Assistant:** Hello"
+        );
+    }
+
+    #[test]
+    fn traces_are_optional_and_tool_output_is_truncated() {
+        let trace = trace_with_tool(&"x".repeat(TOOL_TEXT_CHARS + 50));
+        let msg = QuotedMessage {
+            role: QuoteRole::Assistant,
+            content: "Done",
+            trace: Some(&trace),
+        };
+
+        assert_eq!(
+            format_context_quote(std::slice::from_ref(&msg), false),
+            "> **Assistant:** Done"
+        );
+
+        let quote = format_context_quote(&[msg], true);
+        let expected = format!(
+            "> **Assistant:** [tool read_file] {{ \"path\": \"a.txt\" }} → {}…\n> Done",
+            "x".repeat(TOOL_TEXT_CHARS)
+        );
+        assert_eq!(quote, expected);
+    }
+}
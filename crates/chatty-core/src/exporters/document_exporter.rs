@@ -0,0 +1,704 @@
+//! Conversation documents for the "Export conversation…" action: Markdown,
+//! standalone HTML and PDF.
+//!
+//! All three carry the full record — thinking and tool calls under each
+//! answer, attachments, reviewer reactions and notes, and manual history
+//! edits. HTML comes from [`html_exporter`](super::html_exporter) with
+//! attachment thumbnails embedded. PDF is typeset with Typst and needs the
+//! `math-render` feature; see [`DocumentFormat::is_available`].
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rig_core::completion::Message;
+use rig_core::completion::message::{AssistantContent, UserContent};
+
+use super::handoff::truncate;
+use super::html_exporter::{
+    HtmlExportOptions, TRACE_TEXT_CHARS, conversation_to_html, format_date,
+};
+use crate::models::message_types::{
+    ApprovalState, SystemTrace, ThinkingState, ToolCallState, TraceItem,
+};
+use crate::models::{CompactionRecord, HistoryEdit};
+use crate::repositories::ConversationData;
+use crate::services::message_copy::format_for_copy;
+use crate::settings::models::CopyFormat;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocumentFormat {
+    Markdown,
+    Html,
+    Pdf,
+}
+
+impl DocumentFormat {
+    pub const ALL: [DocumentFormat; 3] = [Self::Markdown, Self::Html, Self::Pdf];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Pdf => "pdf",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Markdown => "Markdown",
+            Self::Html => "HTML",
+            Self::Pdf => "PDF",
+        }
+    }
+
+    /// Whether this build can write the format. PDF needs the Typst
+    /// compiler from the `math-render` feature.
+    pub fn is_available(self) -> bool {
+        match self {
+            Self::Pdf => cfg!(feature = "math-render"),
+            _ => true,
+        }
+    }
+}
+
+/// File name offered in the save dialog: the title with characters that
+/// file systems reject replaced.
+pub fn suggested_file_name(title: &str, format: DocumentFormat) -> String {
+    format!(
+        "{}.{}",
+        title.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_"),
+        format.extension()
+    )
+}
+
+/// Render a persisted conversation in `format`.
+pub fn render_document(data: &ConversationData, format: DocumentFormat) -> Result<Vec<u8>> {
+    match format {
+        DocumentFormat::Markdown => conversation_to_markdown(data).map(String::into_bytes),
+        DocumentFormat::Html => {
+            let options = HtmlExportOptions {
+                include_traces: true,
+                attachment_thumbnails: true,
+                page_header: false,
+                print_on_load: false,
+            };
+            conversation_to_html(data, &options).map(String::into_bytes)
+        }
+        DocumentFormat::Pdf => conversation_to_pdf(data),
+    }
+}
+
+/// One message of the history with what the exports show around it.
+struct ExportEntry {
+    role: &'static str,
+    text: String,
+    trace: Option<serde_json::Value>,
+    attachments: Vec<String>,
+    reactions: Vec<String>,
+    note: Option<String>,
+}
+
+/// The conversation's messages, skipping system messages and empty turns.
+fn export_entries(data: &ConversationData) -> Result<Vec<ExportEntry>> {
+    let history: Vec<Message> =
+        serde_json::from_str(&data.message_history).context("Failed to parse message_history")?;
+    let mut traces: Vec<Option<serde_json::Value>> =
+        serde_json::from_str(&data.system_traces).unwrap_or_default();
+    let mut attachments: Vec<Vec<String>> =
+        serde_json::from_str(&data.attachment_paths).unwrap_or_default();
+    let mut reactions: Vec<Vec<String>> =
+        serde_json::from_str(&data.message_reactions).unwrap_or_default();
+    let mut notes: Vec<Option<String>> =
+        serde_json::from_str(&data.message_notes).unwrap_or_default();
+
+    let mut entries = Vec::new();
+    for (ix, message) in history.into_iter().enumerate() {
+        let (role, text) = match message {
+            Message::User { content } => (
+                "User",
+                content
+                    .iter()
+                    .filter_map(|c| match c {
+                        UserContent::Text(t) => Some(t.text.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n\n"),
+            ),
+            Message::Assistant { content, .. } => (
+                "Assistant",
+                content
+                    .iter()
+                    .filter_map(|c| match c {
+                        AssistantContent::Text(t) => Some(t.text.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n\n"),
+            ),
+            Message::System { .. } => continue,
+        };
+        let entry = ExportEntry {
+            role,
+            text,
+            trace: traces.get_mut(ix).and_then(Option::take),
+            attachments: attachments
+                .get_mut(ix)
+                .map(std::mem::take)
+                .unwrap_or_default(),
+            reactions: reactions
+                .get_mut(ix)
+                .map(std::mem::take)
+                .unwrap_or_default(),
+            note: notes.get_mut(ix).and_then(Option::take),
+        };
+        if entry.text.trim().is_empty() && entry.trace.is_none() && entry.attachments.is_empty() {
+            continue;
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn history_edits(data: &ConversationData) -> Vec<HistoryEdit> {
+    serde_json::from_str(&data.history_edits).unwrap_or_default()
+}
+
+fn compaction_records(data: &ConversationData) -> Vec<CompactionRecord> {
+    serde_json::from_str(&data.compaction_records).unwrap_or_default()
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+// ── Markdown ───────────────────────────────────────────────────────────────
+
+/// Render a persisted conversation as Markdown.
+pub fn conversation_to_markdown(data: &ConversationData) -> Result<String> {
+    let mut md = format!("# {}\n\n", data.title);
+    for entry in export_entries(data)? {
+        md.push_str(&format!("---\n\n**{}**\n\n", entry.role));
+        if !entry.text.trim().is_empty() {
+            md.push_str(&entry.text);
+            md.push_str("\n\n");
+        }
+        if !entry.attachments.is_empty() {
+            md.push_str("Attachments:\n\n");
+            for path in &entry.attachments {
+                md.push_str(&format!("- [{}](<{path}>)\n", file_name(path)));
+            }
+            md.push('\n');
+        }
+        if let Some(trace) = &entry.trace {
+            push_trace_markdown(&mut md, trace);
+        }
+
+        // Reviewer metadata (reactions + private note) as a blockquote
+        if !entry.reactions.is_empty() || entry.note.is_some() {
+            md.push_str("> **Reviewer:**");
+            if !entry.reactions.is_empty() {
+                md.push(' ');
+                md.push_str(&entry.reactions.join(" "));
+            }
+            if let Some(note) = &entry.note {
+                for line in note.lines() {
+                    md.push_str(&format!("\n> {line}"));
+                }
+            }
+            md.push_str("\n\n");
+        }
+    }
+
+    // Manual deletions and edits, so readers can tell the transcript was changed
+    let edits = history_edits(data);
+    if !edits.is_empty() {
+        md.push_str("---\n\n## History Edits\n\n");
+        for edit in edits {
+            md.push_str(&format!("- {}\n", edit.summary()));
+            // Edited messages keep their original wording in full
+            if let Some(original) = &edit.original_text {
+                for line in original.lines() {
+                    md.push_str(&format!("  > {line}\n"));
+                }
+            }
+        }
+        md.push('\n');
+    }
+
+    // Summarized older messages, with the summary that replaced them
+    let compactions = compaction_records(data);
+    if !compactions.is_empty() {
+        md.push_str("---\n\n## Compactions\n\n");
+        for compaction in compactions {
+            md.push_str(&format!("- {}\n\n", compaction.description()));
+            push_markdown_code_block(&mut md, "text", &compaction.summary);
+        }
+    }
+    Ok(md)
+}
+
+fn push_markdown_code_block(md: &mut String, language: &str, body: &str) {
+    if body.trim().is_empty() {
+        return;
+    }
+
+    md.push_str(&format!("```{language}\n{body}\n```\n\n"));
+}
+
+fn push_trace_markdown(md: &mut String, trace_json: &serde_json::Value) {
+    match serde_json::from_value::<SystemTrace>(trace_json.clone()) {
+        Ok(trace) if trace.has_items() || trace.routing.is_some() || trace.overrides.is_some() => {
+            md.push_str("### Trace\n\n");
+
+            if let Some(routing) = &trace.routing {
+                md.push_str(&format!("- **Routed:** {}\n\n", routing.summary()));
+            }
+
+            if let Some(overrides) = &trace.overrides {
+                md.push_str(&format!(
+                    "- **This turn only:** {}\n\n",
+                    overrides.summary()
+                ));
+            }
+
+            for (index, item) in trace.items.iter().enumerate() {
+                match item {
+                    TraceItem::Thinking(thinking) => {
+                        let status = match thinking.state {
+                            ThinkingState::Processing => "running",
+                            ThinkingState::Completed => "completed",
+                        };
+                        md.push_str(&format!("{}. **Thinking** ({status})\n", index + 1));
+
+                        if !thinking.summary.trim().is_empty() {
+                            md.push_str(&format!("   - Summary: {}\n", thinking.summary.trim()));
+                        }
+
+                        if !thinking.content.trim().is_empty() {
+                            md.push_str("   - Details:\n\n");
+                            push_markdown_code_block(md, "text", thinking.content.trim());
+                        } else {
+                            md.push('\n');
+                        }
+                    }
+                    TraceItem::ToolCall(tool_call) => {
+                        let status = match &tool_call.state {
+                            ToolCallState::Running => "running".to_string(),
+                            ToolCallState::Success => "success".to_string(),
+                            ToolCallState::Error(err) => format!("error: {err}"),
+                        };
+
+                        md.push_str(&format!(
+                            "{}. **Tool:** `{}` ({status})\n",
+                            index + 1,
+                            tool_call.display_name
+                        ));
+
+                        if !tool_call.input.trim().is_empty() {
+                            md.push_str("   - Input:\n\n");
+                            push_markdown_code_block(md, "text", tool_call.input.trim());
+                        }
+
+                        if let Some(output) = tool_call.output.as_deref()
+                            && !output.trim().is_empty()
+                        {
+                            md.push_str("   - Output:\n\n");
+                            push_markdown_code_block(md, "text", output.trim());
+                        } else if let Some(output_preview) = tool_call.output_preview.as_deref()
+                            && !output_preview.trim().is_empty()
+                        {
+                            md.push_str("   - Output Preview:\n\n");
+                            push_markdown_code_block(md, "text", output_preview.trim());
+                        } else {
+                            md.push('\n');
+                        }
+                    }
+                    TraceItem::ApprovalPrompt(approval) if approval.question.is_some() => {
+                        md.push_str(&format!(
+                            "{}. **Question**\n   - Asked: {}\n   - Answer: {}\n\n",
+                            index + 1,
+                            approval.command,
+                            approval.answer.as_deref().unwrap_or("(none)")
+                        ));
+                    }
+                    TraceItem::ApprovalPrompt(approval) => {
+                        let status = match approval.state {
+                            ApprovalState::Pending => "pending",
+                            ApprovalState::Approved => "approved",
+                            ApprovalState::Denied => "denied",
+                        };
+
+                        md.push_str(&format!(
+                            "{}. **Approval** ({status})\n   - Command: `{}`\n\n",
+                            index + 1,
+                            approval.command
+                        ));
+                    }
+                    TraceItem::Reflection(reflection) => {
+                        md.push_str(&format!(
+                            "{}. **Self-check** (scored {}/5, below {}; retried)\n   - Critique: {}\n   - First attempt:\n\n",
+                            index + 1,
+                            reflection.score,
+                            reflection.threshold,
+                            reflection.critique
+                        ));
+                        push_markdown_code_block(md, "text", reflection.first_attempt.trim());
+                    }
+                }
+            }
+        }
+        Ok(_) => {}
+        Err(error) => {
+            tracing::warn!(error = ?error, "Failed to deserialize trace for markdown export");
+            md.push_str("### Trace (raw)\n\n");
+            match serde_json::to_string_pretty(trace_json) {
+                Ok(raw_json) => push_markdown_code_block(md, "json", &raw_json),
+                Err(error) => {
+                    tracing::warn!(error = ?error, "Failed to pretty-print raw trace JSON for export");
+                }
+            }
+        }
+    }
+}
+
+// ── PDF ────────────────────────────────────────────────────────────────────
+
+const TYPST_PREAMBLE: &str = r#"#set page(paper: "a4", margin: (x: 16mm, y: 18mm), numbering: "1 / 1")
+#set text(size: 10.5pt)
+#set par(justify: false)
+#show raw.where(block: true): block.with(fill: luma(246), inset: 6pt, radius: 3pt, width: 100%)
+"#;
+
+/// Render a persisted conversation as Typst source for the PDF export.
+///
+/// Prose goes through the plain-text copy converter, so Markdown syntax is
+/// dropped rather than typeset; fenced code keeps its own block. All text is
+/// passed as Typst string literals, so nothing in a message is interpreted
+/// as Typst markup.
+pub fn conversation_to_typst(data: &ConversationData) -> Result<String> {
+    let mut doc = TYPST_PREAMBLE.to_string();
+    doc.push_str(&format!(
+        "#heading(level: 1, {})\n#text(size: 9pt, fill: gray, {})\n\n",
+        typst_str(&data.title),
+        typst_str(&format!(
+            "{} · {}",
+            data.model_id,
+            format_date(data.updated_at)
+        ))
+    ));
+
+    for entry in export_entries(data)? {
+        doc.push_str("#line(length: 100%, stroke: 0.5pt + luma(200))\n");
+        doc.push_str(&format!(
+            "#text(size: 9pt, weight: \"bold\", fill: luma(90), {})\n\n",
+            typst_str(&entry.role.to_uppercase())
+        ));
+        if let Some(trace) = entry
+            .trace
+            .and_then(|value| serde_json::from_value::<SystemTrace>(value).ok())
+            .filter(|trace| trace.has_items())
+        {
+            push_trace_typst(&mut doc, &trace);
+        }
+        push_text_typst(&mut doc, &entry.text);
+        if !entry.attachments.is_empty() {
+            let names: Vec<String> = entry.attachments.iter().map(|p| file_name(p)).collect();
+            doc.push_str(&format!(
+                "#text(size: 8.5pt, fill: luma(90), {})\n\n",
+                typst_str(&format!("Attachments: {}", names.join(", ")))
+            ));
+        }
+        if !entry.reactions.is_empty() || entry.note.is_some() {
+            let mut review = format!("Reviewer: {}", entry.reactions.join(" "));
+            if let Some(note) = &entry.note {
+                review.push('\n');
+                review.push_str(note);
+            }
+            doc.push_str(&format!(
+                "#quote(block: true, {})\n\n",
+                typst_str(review.trim())
+            ));
+        }
+    }
+
+    let edits = history_edits(data);
+    if !edits.is_empty() {
+        doc.push_str("#heading(level: 2, \"History Edits\")\n");
+        for edit in edits {
+            doc.push_str(&format!("- #{}\n", typst_str(&edit.summary())));
+        }
+    }
+
+    let compactions = compaction_records(data);
+    if !compactions.is_empty() {
+        doc.push_str("#heading(level: 2, \"Compactions\")\n");
+        for compaction in compactions {
+            doc.push_str(&format!("- #{}\n", typst_str(&compaction.description())));
+        }
+    }
+    Ok(doc)
+}
+
+/// Message text as paragraphs and code blocks.
+fn push_text_typst(doc: &mut String, text: &str) {
+    let mut prose = String::new();
+    let mut code: Option<(String, String)> = None;
+    for line in text.lines() {
+        let fence = line.trim_start().strip_prefix("```");
+        match (&mut code, fence) {
+            (None, Some(lang)) => {
+                push_prose_typst(doc, &std::mem::take(&mut prose));
+                code = Some((lang.trim().to_string(), String::new()));
+            }
+            (Some(_), Some(_)) => {
+                let (lang, body) = code.take().unwrap_or_default();
+                push_code_typst(doc, &lang, &body);
+            }
+            (Some((_, body)), None) => {
+                body.push_str(line);
+                body.push('\n');
+            }
+            (None, None) => {
+                prose.push_str(line);
+                prose.push('\n');
+            }
+        }
+    }
+    // An unclosed fence still shows as code
+    if let Some((lang, body)) = code {
+        push_code_typst(doc, &lang, &body);
+    }
+    push_prose_typst(doc, &prose);
+}
+
+fn push_prose_typst(doc: &mut String, markdown: &str) {
+    let plain = format_for_copy(markdown, CopyFormat::PlainText);
+    for paragraph in plain.split("\n\n").filter(|p| !p.trim().is_empty()) {
+        doc.push_str(&format!("#{}\n\n", typst_str(paragraph.trim_end())));
+    }
+}
+
+fn push_code_typst(doc: &mut String, lang: &str, body: &str) {
+    if body.trim().is_empty() {
+        return;
+    }
+    let lang = if lang.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') && !lang.is_empty() {
+        format!(", lang: {}", typst_str(lang))
+    } else {
+        String::new()
+    };
+    doc.push_str(&format!(
+        "#raw(block: true{lang}, {})\n\n",
+        typst_str(body.trim_end())
+    ));
+}
+
+fn push_trace_typst(doc: &mut String, trace: &SystemTrace) {
+    let mut lines = Vec::new();
+    if let Some(routing) = &trace.routing {
+        lines.push(format!("Routed: {}", routing.summary()));
+    }
+    if let Some(overrides) = &trace.overrides {
+        lines.push(format!("This turn only: {}", overrides.summary()));
+    }
+    let mut blocks = Vec::new();
+    for item in &trace.items {
+        match item {
+            TraceItem::Thinking(thinking) => {
+                let text = if thinking.summary.trim().is_empty() {
+                    thinking.content.trim()
+                } else {
+                    thinking.summary.trim()
+                };
+                lines.push(format!("Thinking: {}", truncate(text, TRACE_TEXT_CHARS)));
+            }
+            TraceItem::ToolCall(call) => {
+                let status = match &call.state {
+                    ToolCallState::Running => "running".to_string(),
+                    ToolCallState::Success => "success".to_string(),
+                    ToolCallState::Error(err) => format!("error: {err}"),
+                };
+                lines.push(format!("Tool: {} ({status})", call.display_name));
+                let output = call
+                    .output
+                    .as_deref()
+                    .or(call.output_preview.as_deref())
+                    .unwrap_or_default();
+                for body in [call.input.trim(), output.trim()] {
+                    if !body.is_empty() {
+                        blocks.push((lines.len(), truncate(body, TRACE_TEXT_CHARS)));
+                    }
+                }
+            }
+            TraceItem::ApprovalPrompt(approval) if approval.question.is_some() => {
+                lines.push(format!(
+                    "Question: {} — {}",
+                    approval.command,
+                    approval.answer.as_deref().unwrap_or("(none)")
+                ));
+            }
+            TraceItem::ApprovalPrompt(approval) => {
+                let status = match approval.state {
+                    ApprovalState::Pending => "pending",
+                    ApprovalState::Approved => "approved",
+                    ApprovalState::Denied => "denied",
+                };
+                lines.push(format!("Approval ({status}): {}", approval.command));
+            }
+            TraceItem::Reflection(reflection) => {
+                lines.push(format!(
+                    "Self-check (scored {}/5, below {}; retried): {}",
+                    reflection.score, reflection.threshold, reflection.critique
+                ));
+            }
+        }
+    }
+
+    let mut body = String::new();
+    let mut blocks = blocks.into_iter().peekable();
+    for (ix, line) in lines.iter().enumerate() {
+        body.push_str(&format!("#{}\n\n", typst_str(line)));
+        while let Some((_, text)) = blocks.next_if(|(after, _)| *after == ix + 1) {
+            body.push_str(&format!("#raw(block: true, {})\n\n", typst_str(&text)));
+        }
+    }
+    doc.push_str(&format!(
+        "#block(stroke: (left: 2pt + luma(190)), inset: (left: 8pt, y: 4pt))[\n#set text(size: 8.5pt, fill: luma(70))\n{body}]\n\n"
+    ));
+}
+
+/// `text` as a Typst string literal.
+fn typst_str(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(feature = "math-render")]
+fn conversation_to_pdf(data: &ConversationData) -> Result<Vec<u8>> {
+    let source = conversation_to_typst(data)?;
+    let (pdf, _pages) =
+        crate::services::typst_compiler_service::TypstCompilerService::compile_to_pdf(
+            &source, None,
+        )?;
+    Ok(pdf)
+}
+
+#[cfg(not(feature = "math-render"))]
+fn conversation_to_pdf(_data: &ConversationData) -> Result<Vec<u8>> {
+    anyhow::bail!("PDF export needs the math-render feature")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::message_types::{ToolCallBlock, ToolSource};
+    use crate::testing::builders::ConversationDataBuilder;
+
+    fn conversation() -> ConversationData {
+        let mut trace = SystemTrace::new();
+        trace.items = vec![TraceItem::ToolCall(ToolCallBlock {
+            id: String::new(),
+            tool_name: "read_file".to_string(),
+            display_name: "Read File".to_string(),
+            input: r#"{"path": "notes.md"}"#.to_string(),
+            output: Some("# Notes".to_string()),
+            output_preview: None,
+            state: ToolCallState::Success,
+            duration: None,
+            text_before: String::new(),
+            source: ToolSource::Local,
+            execution_engine: None,
+        })];
+        let mut data = ConversationDataBuilder::new("conv-1")
+            .title("Notes \"& summary\"")
+            .user("Summarize these")
+            .assistant("The notes cover **two** topics:\n\n```rust\nlet x = \"#x\";\n```")
+            .build();
+        data.system_traces =
+            serde_json::to_string(&vec![None, Some(serde_json::to_value(&trace).unwrap())])
+                .unwrap();
+        data.attachment_paths =
+            serde_json::to_string(&vec![vec!["/tmp/scans/page one.png".to_string()]]).unwrap();
+        data.message_reactions = r#"[[], ["👍"]]"#.to_string();
+        data.message_notes = r#"[null, "Check the numbers"]"#.to_string();
+        data
+    }
+
+    #[test]
+    fn markdown_includes_traces_attachments_and_review() {
+        let md = conversation_to_markdown(&conversation()).unwrap();
+
+        assert!(md.starts_with("# Notes \"& summary\"\n\n"));
+        assert!(md.contains("**User**\n\nSummarize these"));
+        assert!(md.contains("- [page one.png](</tmp/scans/page one.png>)"));
+        assert!(md.contains("1. **Tool:** `Read File` (success)"));
+        assert!(md.contains("   - Output:\n\n```text\n# Notes\n```"));
+        assert!(md.contains("> **Reviewer:** 👍\n> Check the numbers"));
+        assert!(!md.contains("## Compactions"));
+    }
+
+    #[test]
+    fn markdown_lists_compactions() {
+        let mut data = conversation();
+        data.compaction_records = serde_json::to_string(&vec![CompactionRecord {
+            trigger: crate::models::CompactionTrigger::Automatic,
+            messages_compacted: 6,
+            estimated_tokens_freed: 900,
+            summary: "- Read the notes".to_string(),
+            compacted_at: 0,
+        }])
+        .unwrap();
+
+        let md = conversation_to_markdown(&data).unwrap();
+        assert!(md.contains(
+            "## Compactions\n\n- Automatically compacted 6 messages (~900 tokens freed)\n\n```text\n- Read the notes\n```"
+        ));
+    }
+
+    #[test]
+    fn typst_source_quotes_message_text() {
+        let doc = conversation_to_typst(&conversation()).unwrap();
+
+        assert!(doc.contains("#heading(level: 1, \"Notes \\\"& summary\\\"\")"));
+        assert!(doc.contains("#\"The notes cover two topics:\""));
+        assert!(doc.contains("#raw(block: true, lang: \"rust\", \"let x = \\\"#x\\\";\")"));
+        assert!(doc.contains("#\"Tool: Read File (success)\""));
+        assert!(doc.contains("Attachments: page one.png"));
+    }
+
+    #[test]
+    fn file_names_and_availability() {
+        assert_eq!(
+            suggested_file_name("a/b: c?", DocumentFormat::Markdown),
+            "a_b_ c_.md"
+        );
+        assert!(DocumentFormat::Html.is_available());
+        assert_eq!(
+            DocumentFormat::Pdf.is_available(),
+            cfg!(feature = "math-render")
+        );
+    }
+
+    #[cfg(feature = "math-render")]
+    #[test]
+    fn renders_pdf() {
+        let pdf = render_document(&conversation(), DocumentFormat::Pdf).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+    }
+}
@@ -0,0 +1,500 @@
+//! Agent handoff: a conversation's task state written out for a CLI coding
+//! agent to continue.
+//!
+//! [`HandoffFormat::Brief`] is a Markdown brief that works as an aider
+//! `--message-file` or as the first prompt of any other agent;
+//! [`HandoffFormat::TaskJson`] carries the same fields for scripts. Both list
+//! the goal, the user's instructions in order, pinned notes, the files the
+//! conversation attached, read or edited, the plan checklist and the latest
+//! answer.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use rig_core::completion::Message;
+use rig_core::completion::message::AssistantContent;
+use serde::Serialize;
+
+use crate::models::Conversation;
+use crate::models::message_types::{SystemTrace, ToolCallState, TraceItem};
+use crate::models::pinned_context::PinnedItemKind;
+use crate::repositories::ConversationData;
+use crate::services::AgentTodoStatus;
+use crate::services::message_orchestrator::extract_user_text;
+
+/// Directory, relative to the working directory, the handoff file is written to.
+pub const HANDOFF_DIR: &str = ".chatty";
+
+/// Characters of the latest answer kept before truncating.
+pub const LATEST_ANSWER_CHARS: usize = 4000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandoffFormat {
+    /// Markdown brief (`handoff.md`).
+    Brief,
+    /// Task JSON (`handoff.json`).
+    TaskJson,
+}
+
+impl HandoffFormat {
+    /// Read a format name: `aider`, `md` or `brief` for the brief, `json` for
+    /// the task file.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "" | "aider" | "md" | "markdown" | "brief" => Some(Self::Brief),
+            "json" | "task" => Some(Self::TaskJson),
+            _ => None,
+        }
+    }
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Brief => "handoff.md",
+            Self::TaskJson => "handoff.json",
+        }
+    }
+}
+
+/// How the conversation used a file. Later roles win when a file is used
+/// several ways.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileRole {
+    Attached,
+    Read,
+    Edited,
+}
+
+impl FileRole {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Attached => "attached",
+            Self::Read => "read",
+            Self::Edited => "edited",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HandoffFile {
+    /// Relative to the working directory when the file lies inside it.
+    pub path: String,
+    pub role: FileRole,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HandoffStep {
+    pub title: String,
+    pub done: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// The task state handed to another agent.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AgentHandoff {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    /// The user's messages, oldest first.
+    pub instructions: Vec<String>,
+    /// Notes the user pinned to the conversation's context.
+    pub notes: Vec<String>,
+    pub files: Vec<HandoffFile>,
+    pub plan: Vec<HandoffStep>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_answer: Option<String>,
+}
+
+impl AgentHandoff {
+    /// Collect the handoff from a persisted conversation. The planner-mode
+    /// plan is used when there is one, otherwise the agent's todo list.
+    pub fn from_conversation(data: &ConversationData) -> Result<Self> {
+        let history: Vec<Message> = serde_json::from_str(&data.message_history)
+            .context("Failed to parse message_history")?;
+        let traces: Vec<Option<serde_json::Value>> =
+            serde_json::from_str(&data.system_traces).unwrap_or_default();
+        let attachments: Vec<Vec<String>> =
+            serde_json::from_str(&data.attachment_paths).unwrap_or_default();
+        let working_dir = data.working_dir.clone();
+
+        let mut instructions = Vec::new();
+        let mut latest_answer = None;
+        for message in &history {
+            match message {
+                Message::User { content } => {
+                    let text = extract_user_text(&content.iter().cloned().collect::<Vec<_>>());
+                    if !text.trim().is_empty() {
+                        instructions.push(text.trim().to_string());
+                    }
+                }
+                Message::Assistant { content, .. } => {
+                    let text = content
+                        .iter()
+                        .filter_map(|c| match c {
+                            AssistantContent::Text(t) => Some(t.text.as_str()),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n\n");
+                    if !text.trim().is_empty() {
+                        latest_answer = Some(truncate(text.trim(), LATEST_ANSWER_CHARS));
+                    }
+                }
+                Message::System { .. } => {}
+            }
+        }
+
+        let mut files = BTreeMap::new();
+        let mut add_file = |path: &str, role: FileRole| {
+            let path = relative_to(path, working_dir.as_deref());
+            let entry = files.entry(path).or_insert(role);
+            *entry = (*entry).max(role);
+        };
+        for path in attachments.iter().flatten() {
+            add_file(path, FileRole::Attached);
+        }
+        for trace in traces.into_iter().flatten() {
+            let Ok(trace) = serde_json::from_value::<SystemTrace>(trace) else {
+                continue;
+            };
+            for item in &trace.items {
+                if let TraceItem::ToolCall(call) = item
+                    && call.state == ToolCallState::Success
+                    && let Some((path, role)) = touched_file(&call.tool_name, &call.input)
+                {
+                    add_file(&path, role);
+                }
+            }
+        }
+        let files = files
+            .into_iter()
+            .map(|(path, role)| HandoffFile { path, role })
+            .collect();
+
+        let (goal, plan) = plan_from(data);
+        let notes = Conversation::deserialize_pinned_items(&data.pinned_items)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|item| item.kind == PinnedItemKind::Note)
+            .map(|item| item.content)
+            .collect();
+
+        Ok(Self {
+            title: data.title.clone(),
+            goal,
+            working_dir,
+            instructions,
+            notes,
+            files,
+            plan,
+            latest_answer,
+        })
+    }
+
+    pub fn render(&self, format: HandoffFormat) -> Result<String> {
+        match format {
+            HandoffFormat::Brief => Ok(self.to_markdown()),
+            HandoffFormat::TaskJson => {
+                serde_json::to_string_pretty(self).context("Failed to serialize handoff JSON")
+            }
+        }
+    }
+
+    /// The Markdown brief.
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!(
+            "# Handoff: {}\n\nContinue the task below, which was started in another \
+             assistant. The instructions are the user's messages in order; the plan \
+             shows what is already done.\n",
+            self.title
+        );
+        if let Some(goal) = &self.goal {
+            md.push_str(&format!("\n## Goal\n\n{goal}\n"));
+        }
+        if !self.instructions.is_empty() {
+            md.push_str("\n## Instructions\n\n");
+            for (ix, text) in self.instructions.iter().enumerate() {
+                md.push_str(&format!("{}. {}\n", ix + 1, indent(text, "   ")));
+            }
+        }
+        if !self.notes.is_empty() {
+            md.push_str("\n## Notes\n\n");
+            for note in &self.notes {
+                md.push_str(&format!("- {}\n", indent(note, "  ")));
+            }
+        }
+        if !self.files.is_empty() {
+            md.push_str("\n## Relevant Files\n\n");
+            for file in &self.files {
+                md.push_str(&format!("- `{}` ({})\n", file.path, file.role.label()));
+            }
+        }
+        if !self.plan.is_empty() {
+            md.push_str("\n## Plan\n\n");
+            for step in &self.plan {
+                let mark = if step.done { "x" } else { " " };
+                match &step.note {
+                    Some(note) => md.push_str(&format!("- [{mark}] {} — {note}\n", step.title)),
+                    None => md.push_str(&format!("- [{mark}] {}\n", step.title)),
+                }
+            }
+        }
+        if let Some(answer) = &self.latest_answer {
+            md.push_str(&format!("\n## Latest Answer\n\n{answer}\n"));
+        }
+        md
+    }
+
+    /// Shell command that starts aider on the brief at `brief` with the
+    /// conversation's files added to the chat.
+    pub fn aider_command(&self, brief: &Path) -> String {
+        let mut command = format!(
+            "aider --message-file {}",
+            shell_quote(&brief.to_string_lossy())
+        );
+        for file in &self.files {
+            command.push(' ');
+            command.push_str(&shell_quote(&file.path));
+        }
+        command
+    }
+
+    /// Write the handoff to `<dir>/.chatty/<file name>` and return the path.
+    pub async fn write(&self, dir: &Path, format: HandoffFormat) -> Result<PathBuf> {
+        let contents = self.render(format)?;
+        let handoff_dir = dir.join(HANDOFF_DIR);
+        tokio::fs::create_dir_all(&handoff_dir)
+            .await
+            .with_context(|| format!("Failed to create {}", handoff_dir.display()))?;
+        let path = handoff_dir.join(format.file_name());
+        tokio::fs::write(&path, contents)
+            .await
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// The file a successful filesystem tool call read or changed.
+fn touched_file(tool_name: &str, input: &str) -> Option<(String, FileRole)> {
+    let (key, role) = match tool_name {
+        "read_file" => ("path", FileRole::Read),
+        "write_file" | "apply_diff" => ("path", FileRole::Edited),
+        "move_file" => ("destination", FileRole::Edited),
+        _ => return None,
+    };
+    let args: serde_json::Value = serde_json::from_str(input).ok()?;
+    let path = args.get(key)?.as_str()?.trim();
+    (!path.is_empty()).then(|| (path.to_string(), role))
+}
+
+/// Goal and checklist from the planner-mode plan, else from the agent's todos.
+fn plan_from(data: &ConversationData) -> (Option<String>, Vec<HandoffStep>) {
+    if let Some(plan) = data
+        .execution_plan
+        .as_deref()
+        .and_then(|json| Conversation::deserialize_execution_plan(json).ok())
+    {
+        let steps = plan
+            .steps
+            .into_iter()
+            .map(|step| HandoffStep {
+                done: step.status.is_finished(),
+                title: step.title,
+                note: step.note,
+            })
+            .collect();
+        return (Some(plan.goal), steps);
+    }
+    if let Some(snapshot) = data
+        .agent_task_snapshot
+        .as_deref()
+        .and_then(|json| Conversation::deserialize_agent_task_snapshot(json).ok())
+    {
+        let steps = snapshot
+            .todos
+            .into_iter()
+            .map(|todo| HandoffStep {
+                done: todo.status == AgentTodoStatus::Done,
+                title: todo.title,
+                note: todo.blocked_reason,
+            })
+            .collect();
+        return (snapshot.goal, steps);
+    }
+    (None, Vec::new())
+}
+
+fn relative_to(path: &str, working_dir: Option<&str>) -> String {
+    working_dir
+        .and_then(|dir| Path::new(path).strip_prefix(dir).ok())
+        .map(|rel| rel.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+pub(crate) fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// Indent every line after the first, so multi-line text stays inside its
+/// list item.
+pub(crate) fn indent(text: &str, prefix: &str) -> String {
+    text.lines()
+        .collect::<Vec<_>>()
+        .join(&format!("\n{prefix}"))
+}
+
+fn shell_quote(arg: &str) -> String {
+    if arg
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
+    {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{ConversationDataBuilder, tool_call, trace};
+    use crate::tools::{ExecutionPlan, PlanStep, PlanStepStatus};
+
+    fn conversation() -> ConversationData {
+        let plan = ExecutionPlan {
+            goal: "Parser handles comments".to_string(),
+            steps: vec![
+                PlanStep {
+                    id: "s1".to_string(),
+                    title: "Add a failing test".to_string(),
+                    status: PlanStepStatus::Done,
+                    note: None,
+                },
+                PlanStep {
+                    id: "s2".to_string(),
+                    title: "Skip comment tokens".to_string(),
+                    status: PlanStepStatus::InProgress,
+                    note: Some("lexer only".to_string()),
+                },
+            ],
+        };
+        ConversationDataBuilder::new("conv-1")
+            .title("Comment support")
+            .working_dir("/work")
+            .execution_plan(&plan)
+            .user("Make the parser skip comments.\nKeep the API.")
+            .attachments(&["/work/docs/spec.md"])
+            .assistant("I added a test.")
+            .trace(trace([
+                tool_call(
+                    "read_file",
+                    serde_json::json!({"path": "/work/src/lib.rs"}),
+                    ToolCallState::Success,
+                ),
+                tool_call(
+                    "apply_diff",
+                    serde_json::json!({"path": "/work/src/lib.rs", "old_content": "", "new_content": ""}),
+                    ToolCallState::Success,
+                ),
+                tool_call(
+                    "read_file",
+                    serde_json::json!({"path": "/work/README.md"}),
+                    ToolCallState::Success,
+                ),
+                tool_call(
+                    "write_file",
+                    serde_json::json!({"path": "/work/failed.rs", "content": ""}),
+                    ToolCallState::Error("denied".to_string()),
+                ),
+            ]))
+            .user("Go on")
+            .assistant("Lexer updated; parser next.")
+            .build()
+    }
+
+    #[test]
+    fn collects_instructions_files_and_plan() {
+        let handoff = AgentHandoff::from_conversation(&conversation()).unwrap();
+
+        assert_eq!(handoff.goal.as_deref(), Some("Parser handles comments"));
+        assert_eq!(
+            handoff.instructions,
+            vec!["Make the parser skip comments.\nKeep the API.", "Go on"]
+        );
+        assert_eq!(
+            handoff.files,
+            vec![
+                HandoffFile {
+                    path: "README.md".to_string(),
+                    role: FileRole::Read
+                },
+                HandoffFile {
+                    path: "docs/spec.md".to_string(),
+                    role: FileRole::Attached
+                },
+                HandoffFile {
+                    path: "src/lib.rs".to_string(),
+                    role: FileRole::Edited
+                },
+            ]
+        );
+        assert_eq!(handoff.plan.len(), 2);
+        assert!(handoff.plan[0].done && !handoff.plan[1].done);
+        assert_eq!(
+            handoff.latest_answer.as_deref(),
+            Some("Lexer updated; parser next.")
+        );
+    }
+
+    #[test]
+    fn brief_lists_sections_and_checklist() {
+        let handoff = AgentHandoff::from_conversation(&conversation()).unwrap();
+        let md = handoff.render(HandoffFormat::Brief).unwrap();
+
+        assert!(md.starts_with("# Handoff: Comment support\n"));
+        assert!(md.contains("1. Make the parser skip comments.\n   Keep the API.\n2. Go on\n"));
+        assert!(md.contains("- `src/lib.rs` (edited)\n"));
+        assert!(md.contains("- [x] Add a failing test\n- [ ] Skip comment tokens — lexer only\n"));
+        assert!(md.contains("## Latest Answer\n\nLexer updated; parser next.\n"));
+    }
+
+    #[test]
+    fn task_json_round_trips_the_fields() {
+        let handoff = AgentHandoff::from_conversation(&conversation()).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&handoff.render(HandoffFormat::TaskJson).unwrap()).unwrap();
+
+        assert_eq!(json["goal"], "Parser handles comments");
+        assert_eq!(json["working_dir"], "/work");
+        assert_eq!(json["files"][2]["role"], "edited");
+        assert_eq!(json["plan"][1]["note"], "lexer only");
+    }
+
+    #[test]
+    fn aider_command_quotes_paths() {
+        let mut handoff = AgentHandoff::from_conversation(&conversation()).unwrap();
+        handoff.files.push(HandoffFile {
+            path: "my notes.md".to_string(),
+            role: FileRole::Attached,
+        });
+        assert_eq!(
+            handoff.aider_command(Path::new(".chatty/handoff.md")),
+            "aider --message-file .chatty/handoff.md README.md docs/spec.md src/lib.rs 'my notes.md'"
+        );
+    }
+
+    #[test]
+    fn parses_format_names() {
+        assert_eq!(HandoffFormat::parse(""), Some(HandoffFormat::Brief));
+        assert_eq!(HandoffFormat::parse("Aider"), Some(HandoffFormat::Brief));
+        assert_eq!(HandoffFormat::parse("json"), Some(HandoffFormat::TaskJson));
+        assert_eq!(HandoffFormat::parse("pdf"), None);
+    }
+}
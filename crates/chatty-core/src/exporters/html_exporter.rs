@@ -0,0 +1,438 @@
+//! Standalone HTML rendering of a conversation, laid out for printing.
+//!
+//! The document carries its own print stylesheet: A4/Letter margins, page
+//! numbers where the browser supports `@page` margin boxes, code blocks,
+//! tables and images kept on one page, and an optional running header with
+//! the conversation title and date repeated on every page. With
+//! [`HtmlExportOptions::print_on_load`] the page opens the browser's print
+//! dialog as soon as it has loaded, which is how the desktop app prints.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use base64::Engine as _;
+use rig_core::completion::Message;
+use rig_core::completion::message::{AssistantContent, UserContent};
+
+use super::handoff::truncate;
+use crate::models::attachment_validation::{
+    is_image_extension, is_pdf_extension, is_video_extension,
+};
+use crate::models::message_types::{
+    ApprovalState, SystemTrace, ThinkingState, ToolCallState, TraceItem,
+};
+use crate::repositories::ConversationData;
+use crate::services::message_copy::format_for_copy;
+use crate::services::video_frames::cached_sample;
+use crate::settings::models::CopyFormat;
+
+/// Directory under the system temp dir that print files are written to.
+pub const PRINT_DIR: &str = "chatty-print";
+
+/// Characters of a tool call's input or output kept in the printout.
+pub const TRACE_TEXT_CHARS: usize = 1500;
+
+/// Images larger than this are listed by name instead of embedded.
+const MAX_EMBEDDED_IMAGE_BYTES: u64 = 8 * 1024 * 1024;
+
+const STYLE: &str = r#"
+@page {
+  margin: 20mm 16mm 18mm;
+  @bottom-right { content: counter(page) " / " counter(pages); font-size: 9pt; color: #666; }
+}
+body { font: 11pt/1.5 -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: #111; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; }
+h1 { font-size: 18pt; margin: 0 0 0.2em; }
+.meta { color: #666; font-size: 9pt; margin: 0 0 1.5em; }
+.message { margin: 0 0 1.2em; padding-top: 0.6em; border-top: 1px solid #ddd; }
+.role { font-weight: 600; font-size: 9pt; text-transform: uppercase; letter-spacing: 0.05em; color: #555; }
+.user .content { background: #f4f4f5; padding: 0.1em 0.8em; border-radius: 6px; }
+pre { background: #f6f8fa; padding: 0.6em 0.8em; border-radius: 4px; white-space: pre-wrap; word-break: break-word; font-size: 9pt; }
+code { font-family: ui-monospace, Menlo, Consolas, monospace; font-size: 0.9em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.5em; }
+blockquote { margin-left: 0; padding-left: 0.8em; border-left: 3px solid #ccc; color: #444; }
+img { max-width: 100%; }
+.attachments { display: flex; flex-wrap: wrap; gap: 0.5em; margin-top: 0.4em; }
+.attachment { font-size: 8pt; color: #555; text-align: center; }
+.attachment img { display: block; max-width: 42mm; max-height: 42mm; border: 1px solid #ddd; border-radius: 4px; }
+.attachment .name { border: 1px solid #ddd; border-radius: 4px; padding: 0.3em 0.6em; }
+.trace { margin-top: 0.6em; padding: 0.4em 0.8em; border-left: 3px solid #bbb; font-size: 9pt; color: #444; }
+.trace-item { margin: 0.3em 0; }
+.page-header { display: none; }
+.layout, .layout > thead > tr > td, .layout > tbody > tr > td { border: 0; padding: 0; border-collapse: collapse; width: 100%; }
+@media print {
+  body { margin: 0; max-width: none; padding: 0; }
+  .page-header { display: flex; justify-content: space-between; position: fixed; top: 0; left: 0; right: 0; height: 8mm; font-size: 9pt; color: #666; border-bottom: 1px solid #ccc; }
+  .header-space { height: 11mm; }
+  pre, table, img, blockquote, .trace-item, .attachments { break-inside: avoid; }
+  h1, h2, h3, h4, .role { break-after: avoid; }
+}
+"#;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HtmlExportOptions {
+    /// Thinking summaries and tool calls under each answer.
+    pub include_traces: bool,
+    /// Images, first PDF pages and video frames as thumbnails; otherwise
+    /// attachments are listed by name.
+    pub attachment_thumbnails: bool,
+    /// Title and date repeated at the top of every printed page.
+    pub page_header: bool,
+    /// Open the print dialog when the page has loaded.
+    pub print_on_load: bool,
+}
+
+impl Default for HtmlExportOptions {
+    fn default() -> Self {
+        Self {
+            include_traces: false,
+            attachment_thumbnails: true,
+            page_header: true,
+            print_on_load: false,
+        }
+    }
+}
+
+/// Render a persisted conversation as a standalone HTML document.
+pub fn conversation_to_html(
+    data: &ConversationData,
+    options: &HtmlExportOptions,
+) -> Result<String> {
+    let history: Vec<Message> =
+        serde_json::from_str(&data.message_history).context("Failed to parse message_history")?;
+    let traces: Vec<Option<serde_json::Value>> =
+        serde_json::from_str(&data.system_traces).unwrap_or_default();
+    let attachments: Vec<Vec<String>> =
+        serde_json::from_str(&data.attachment_paths).unwrap_or_default();
+
+    let title = escape(&data.title);
+    let date = format_date(data.updated_at);
+    let mut body = String::new();
+    for (ix, message) in history.iter().enumerate() {
+        let files = attachments.get(ix).map(Vec::as_slice).unwrap_or_default();
+        match message {
+            Message::User { content } => {
+                let text = content
+                    .iter()
+                    .filter_map(|c| match c {
+                        UserContent::Text(t) => Some(t.text.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                if text.trim().is_empty() && files.is_empty() {
+                    continue;
+                }
+                body.push_str("<article class=\"message user\">\n<div class=\"role\">You</div>\n");
+                body.push_str(&format!(
+                    "<div class=\"content\">\n{}\n</div>\n",
+                    format_for_copy(&text, CopyFormat::Html)
+                ));
+                push_attachments(&mut body, files, options.attachment_thumbnails);
+                body.push_str("</article>\n");
+            }
+            Message::Assistant { content, .. } => {
+                let text = content
+                    .iter()
+                    .filter_map(|c| match c {
+                        AssistantContent::Text(t) => Some(t.text.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                let trace = options
+                    .include_traces
+                    .then(|| traces.get(ix).cloned().flatten())
+                    .flatten()
+                    .and_then(|value| serde_json::from_value::<SystemTrace>(value).ok())
+                    .filter(|trace| trace.has_items());
+                if text.trim().is_empty() && trace.is_none() {
+                    continue;
+                }
+                body.push_str(
+                    "<article class=\"message assistant\">\n<div class=\"role\">Assistant</div>\n",
+                );
+                if let Some(trace) = &trace {
+                    push_trace(&mut body, trace);
+                }
+                body.push_str(&format!(
+                    "<div class=\"content\">\n{}\n</div>\n",
+                    format_for_copy(&text, CopyFormat::Html)
+                ));
+                push_attachments(&mut body, files, options.attachment_thumbnails);
+                body.push_str("</article>\n");
+            }
+            Message::System { .. } => {}
+        }
+    }
+
+    let script = if options.print_on_load {
+        "<script>window.addEventListener(\"load\", () => window.print());</script>\n"
+    } else {
+        ""
+    };
+    let main = format!(
+        "<h1>{title}</h1>\n<p class=\"meta\">{} · {date}</p>\n{body}",
+        escape(&data.model_id)
+    );
+    let main = if options.page_header {
+        // The empty table header repeats on every printed page and keeps the
+        // fixed page header from covering the text.
+        format!(
+            "<header class=\"page-header\"><span>{title}</span><span>{date}</span></header>\n\
+             <table class=\"layout\"><thead><tr><td><div class=\"header-space\"></div></td></tr></thead>\n\
+             <tbody><tr><td>\n{main}</td></tr></tbody></table>\n"
+        )
+    } else {
+        main
+    };
+    Ok(format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>{STYLE}</style>\n{script}</head>\n<body>\n{main}</body>\n</html>\n"
+    ))
+}
+
+/// Write the conversation to a print file in the temp directory and return
+/// its path, for opening in the browser.
+pub fn write_print_file(data: &ConversationData, options: &HtmlExportOptions) -> Result<PathBuf> {
+    let html = conversation_to_html(data, options)?;
+    let dir = std::env::temp_dir().join(PRINT_DIR);
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!("{}.html", data.id));
+    std::fs::write(&path, html).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+fn push_attachments(body: &mut String, files: &[String], thumbnails: bool) {
+    if files.is_empty() {
+        return;
+    }
+    body.push_str("<div class=\"attachments\">\n");
+    for file in files {
+        let path = Path::new(file);
+        let name = escape(
+            &path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| file.clone()),
+        );
+        match thumbnails.then(|| thumbnail_uri(path)).flatten() {
+            Some(uri) => body.push_str(&format!(
+                "<figure class=\"attachment\"><img src=\"{uri}\" alt=\"{name}\"><figcaption>{name}</figcaption></figure>\n"
+            )),
+            None => body.push_str(&format!(
+                "<div class=\"attachment\"><span class=\"name\">{name}</span></div>\n"
+            )),
+        }
+    }
+    body.push_str("</div>\n");
+}
+
+/// Data URI of an image attachment, a PDF's first page or a video's first
+/// sampled frame.
+fn thumbnail_uri(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let image = if is_image_extension(&ext) {
+        path.to_path_buf()
+    } else if is_pdf_extension(&ext) {
+        pdf_first_page(path)?
+    } else if is_video_extension(&ext) {
+        cached_sample(path)?.frames.first()?.path.clone()
+    } else {
+        return None;
+    };
+    if std::fs::metadata(&image).ok()?.len() > MAX_EMBEDDED_IMAGE_BYTES {
+        return None;
+    }
+    let mime = match image.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        _ => "image/png",
+    };
+    let bytes = std::fs::read(&image).ok()?;
+    Some(format!(
+        "data:{mime};base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    ))
+}
+
+#[cfg(feature = "pdf")]
+fn pdf_first_page(path: &Path) -> Option<PathBuf> {
+    crate::services::pdf_thumbnail::render_pdf_thumbnail(path).ok()
+}
+
+#[cfg(not(feature = "pdf"))]
+fn pdf_first_page(_path: &Path) -> Option<PathBuf> {
+    None
+}
+
+fn push_trace(body: &mut String, trace: &SystemTrace) {
+    body.push_str("<section class=\"trace\">\n");
+    if let Some(routing) = &trace.routing {
+        body.push_str(&format!(
+            "<div class=\"trace-item\"><strong>Routed:</strong> {}</div>\n",
+            escape(&routing.summary())
+        ));
+    }
+    if let Some(overrides) = &trace.overrides {
+        body.push_str(&format!(
+            "<div class=\"trace-item\"><strong>This turn only:</strong> {}</div>\n",
+            escape(&overrides.summary())
+        ));
+    }
+    for item in &trace.items {
+        body.push_str("<div class=\"trace-item\">");
+        match item {
+            TraceItem::Thinking(thinking) => {
+                let status = match thinking.state {
+                    ThinkingState::Processing => " (running)",
+                    ThinkingState::Completed => "",
+                };
+                let text = if thinking.summary.trim().is_empty() {
+                    thinking.content.trim()
+                } else {
+                    thinking.summary.trim()
+                };
+                body.push_str(&format!(
+                    "<strong>Thinking{status}:</strong> {}",
+                    escape(&truncate(text, TRACE_TEXT_CHARS))
+                ));
+            }
+            TraceItem::ToolCall(call) => {
+                let status = match &call.state {
+                    ToolCallState::Running => "running".to_string(),
+                    ToolCallState::Success => "success".to_string(),
+                    ToolCallState::Error(err) => format!("error: {err}"),
+                };
+                body.push_str(&format!(
+                    "<strong>Tool:</strong> <code>{}</code> ({})",
+                    escape(&call.display_name),
+                    escape(&status)
+                ));
+                if !call.input.trim().is_empty() {
+                    body.push_str(&format!(
+                        "<pre>{}</pre>",
+                        escape(&truncate(call.input.trim(), TRACE_TEXT_CHARS))
+                    ));
+                }
+                if let Some(output) = call
+                    .output
+                    .as_deref()
+                    .or(call.output_preview.as_deref())
+                    .filter(|o| !o.trim().is_empty())
+                {
+                    body.push_str(&format!(
+                        "<pre>{}</pre>",
+                        escape(&truncate(output.trim(), TRACE_TEXT_CHARS))
+                    ));
+                }
+            }
+            TraceItem::ApprovalPrompt(approval) if approval.question.is_some() => {
+                body.push_str(&format!(
+                    "<strong>Question:</strong> {} — <em>{}</em>",
+                    escape(&approval.command),
+                    escape(approval.answer.as_deref().unwrap_or("(none)"))
+                ));
+            }
+            TraceItem::ApprovalPrompt(approval) => {
+                let status = match approval.state {
+                    ApprovalState::Pending => "pending",
+                    ApprovalState::Approved => "approved",
+                    ApprovalState::Denied => "denied",
+                };
+                body.push_str(&format!(
+                    "<strong>Approval</strong> ({status}): <code>{}</code>",
+                    escape(&approval.command)
+                ));
+            }
+            TraceItem::Reflection(reflection) => {
+                body.push_str(&format!(
+                    "<strong>Self-check</strong> (scored {}/5, below {}; retried): {}",
+                    reflection.score,
+                    reflection.threshold,
+                    escape(&reflection.critique)
+                ));
+            }
+        }
+        body.push_str("</div>\n");
+    }
+    body.push_str("</section>\n");
+}
+
+pub(super) fn format_date(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|utc| {
+            utc.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{ConversationDataBuilder, tool_call, trace};
+
+    fn conversation() -> ConversationData {
+        let mut read = tool_call(
+            "read_file",
+            serde_json::json!({"path": "notes.md"}),
+            ToolCallState::Success,
+        );
+        read.display_name = "Read File".to_string();
+        read.output = Some("# Notes".to_string());
+        ConversationDataBuilder::new("conv-1")
+            .title("Notes & summary")
+            .user("Summarize <notes>")
+            .attachments(&["/missing/scan.png"])
+            .assistant("The notes cover **two** topics.")
+            .trace(trace([read]))
+            .build()
+    }
+
+    #[test]
+    fn renders_messages_with_escaped_text() {
+        let html = conversation_to_html(&conversation(), &HtmlExportOptions::default()).unwrap();
+
+        assert!(html.contains("<title>Notes &amp; summary</title>"));
+        assert!(html.contains("<p>Summarize &lt;notes&gt;</p>"));
+        assert!(html.contains("<p>The notes cover <strong>two</strong> topics.</p>"));
+        // A missing attachment falls back to its name.
+        assert!(html.contains("<span class=\"name\">scan.png</span>"));
+        assert!(!html.contains("Read File"));
+        assert!(!html.contains("window.print()"));
+    }
+
+    #[test]
+    fn options_add_traces_header_and_print_script() {
+        let options = HtmlExportOptions {
+            include_traces: true,
+            page_header: true,
+            print_on_load: true,
+            ..HtmlExportOptions::default()
+        };
+        let html = conversation_to_html(&conversation(), &options).unwrap();
+
+        assert!(html.contains("<code>Read File</code> (success)"));
+        assert!(html.contains("<pre># Notes</pre>"));
+        assert!(html.contains("<header class=\"page-header\"><span>Notes &amp; summary</span>"));
+        assert!(html.contains("window.print()"));
+
+        let plain = HtmlExportOptions {
+            page_header: false,
+            ..HtmlExportOptions::default()
+        };
+        let html = conversation_to_html(&conversation(), &plain).unwrap();
+        assert!(!html.contains("page-header\">"));
+    }
+}
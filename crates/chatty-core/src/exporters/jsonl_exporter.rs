@@ -4,8 +4,10 @@ use anyhow::{Context, Result};
 use rig_core::completion::Message;
 use rig_core::completion::message::{AssistantContent, UserContent};
 
+use crate::exporters::types::ReviewerAnnotation;
 use crate::models::conversation::RegenerationRecord;
 use crate::models::message_types::{SystemTrace, TraceItem};
+use crate::models::quality_score::QualityScore;
 use crate::repositories::ConversationData;
 use crate::settings::models::models_store::ModelConfig;
 
@@ -41,7 +43,8 @@ impl Default for SftExportOptions {
 /// 1. Deserialize parallel arrays from ConversationData
 /// 2. Apply min_messages filter
 /// 3. Build ChatML messages array (text-only, stripping multimodal content)
-/// 4. Return JSON object with messages and _conversation_id
+/// 4. Return JSON object with messages, _conversation_id and, when the
+///    conversation was annotated or scored, _reviewer and _quality metadata
 pub fn conversation_to_sft_jsonl(
     conversation: &ConversationData,
     model_config: Option<&ModelConfig>,
@@ -165,10 +168,28 @@ pub fn conversation_to_sft_jsonl(
     }
 
     // PHASE 4: Return result
-    Ok(Some(serde_json::json!({
+    let mut result = serde_json::json!({
         "messages": messages,
         "_conversation_id": conversation.id
-    })))
+    });
+    let reactions: Vec<Vec<String>> =
+        serde_json::from_str(&conversation.message_reactions).unwrap_or_default();
+    let notes: Vec<Option<String>> =
+        serde_json::from_str(&conversation.message_notes).unwrap_or_default();
+    let annotations = ReviewerAnnotation::collect(&reactions, &notes);
+    if !annotations.is_empty() {
+        result["_reviewer"] = serde_json::to_value(&annotations)?;
+    }
+    if let Some(score) = conversation
+        .quality_score
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<QualityScore>(json).ok())
+    {
+        let mut quality = serde_json::to_value(&score)?;
+        quality["overall"] = serde_json::json!(score.overall());
+        result["_quality"] = quality;
+    }
+    Ok(Some(result))
 }
 
 /// Convert a persisted conversation into DPO (Direct Preference Optimization) JSONL lines.
@@ -404,11 +425,28 @@ mod tests {
             attachment_paths: "[]".to_string(),
             message_timestamps: "[]".to_string(),
             message_feedback: serde_json::to_string(&feedback).unwrap(),
+            message_reactions: "[]".to_string(),
+            message_notes: "[]".to_string(),
+            message_errors: "[]".to_string(),
             regeneration_records: serde_json::to_string(&regeneration_records).unwrap(),
             created_at: 1700000000,
             updated_at: 1700000100,
             working_dir: None,
             agent_task_snapshot: None,
+            pinned_items: "[]".to_string(),
+            variables: "{}".to_string(),
+            execution_plan: None,
+            confidential: false,
+            history_policy: r#"{"mode":"full"}"#.to_string(),
+            disabled_mcp_servers: "[]".to_string(),
+            disabled_tools: "[]".to_string(),
+            label: "{}".to_string(),
+            tags: "[]".to_string(),
+            folder: None,
+            history_edits: "[]".to_string(),
+            compaction_records: "[]".to_string(),
+            locked: false,
+            quality_score: None,
         }
     }
 
@@ -429,6 +467,10 @@ mod tests {
             supports_pdf: true,
             supports_temperature: true,
             max_context_window: None,
+            max_video_frames: None,
+            aliases: Vec::new(),
+            default_rules: Vec::new(),
+            lineage: None,
         }
     }
 
@@ -625,6 +667,10 @@ mod tests {
             })],
             total_duration: None,
             active_tool_index: None,
+            routing: None,
+            request_privacy: None,
+            overrides: None,
+            budget_reached: None,
         };
 
         let history = vec![
@@ -701,6 +747,59 @@ mod tests {
         assert_eq!(val["_conversation_id"], "my-unique-id");
     }
 
+    #[test]
+    fn sft_includes_reviewer_annotations() {
+        let mut conv = make_conversation_data(
+            "id",
+            "m",
+            vec![user_message("Hi"), assistant_message("Hello")],
+            vec![None, None],
+            vec![None, None],
+            vec![],
+        );
+        let opts = SftExportOptions::default();
+        let plain = conversation_to_sft_jsonl(&conv, None, &opts)
+            .unwrap()
+            .unwrap();
+        assert!(plain.get("_reviewer").is_none());
+
+        conv.message_reactions = r#"[[], ["❤️"]]"#.to_string();
+        conv.message_notes = r#"[null, "keep for training"]"#.to_string();
+        let val = conversation_to_sft_jsonl(&conv, None, &opts)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            val["_reviewer"],
+            serde_json::json!([{
+                "message_index": 1,
+                "reactions": ["❤️"],
+                "note": "keep for training"
+            }])
+        );
+    }
+
+    #[test]
+    fn sft_includes_quality_score() {
+        let mut conv = make_conversation_data(
+            "id",
+            "m",
+            vec![user_message("Hi"), assistant_message("Hello")],
+            vec![None, None],
+            vec![None, None],
+            vec![],
+        );
+        conv.quality_score = Some(
+            r#"{"helpfulness":5,"tool_efficiency":4,"resolution":3,"judge_model_id":"j","message_count":2,"scored_at":0}"#
+                .to_string(),
+        );
+        let val = conversation_to_sft_jsonl(&conv, None, &SftExportOptions::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(val["_quality"]["helpfulness"], 5);
+        assert_eq!(val["_quality"]["judge_model_id"], "j");
+        assert_eq!(val["_quality"]["overall"], 4.0);
+    }
+
     // ── DPO tests ─────────────────────────────────────────────────────
 
     #[test]
@@ -839,11 +938,28 @@ mod tests {
             attachment_paths: "[]".to_string(),
             message_timestamps: "[]".to_string(),
             message_feedback: "[]".to_string(),
+            message_reactions: "[]".to_string(),
+            message_notes: "[]".to_string(),
+            message_errors: "[]".to_string(),
             regeneration_records: "[]".to_string(),
             created_at: 0,
             updated_at: 0,
             working_dir: None,
             agent_task_snapshot: None,
+            pinned_items: "[]".to_string(),
+            variables: "{}".to_string(),
+            execution_plan: None,
+            confidential: false,
+            history_policy: r#"{"mode":"full"}"#.to_string(),
+            disabled_mcp_servers: "[]".to_string(),
+            disabled_tools: "[]".to_string(),
+            label: "{}".to_string(),
+            tags: "[]".to_string(),
+            folder: None,
+            history_edits: "[]".to_string(),
+            compaction_records: "[]".to_string(),
+            locked: false,
+            quality_score: None,
         };
         let opts = SftExportOptions::default();
         assert!(conversation_to_sft_jsonl(&conv, None, &opts).is_err());
@@ -1,6 +1,15 @@
 pub mod atif_exporter;
+pub mod compare;
+pub mod context_quote;
+pub mod document_exporter;
+pub mod handoff;
+pub mod html_exporter;
 pub mod jsonl_exporter;
+pub mod pii;
+pub mod project;
+pub mod review;
 pub mod types;
+pub mod usage_csv;
 
 // Pre-built API: re-exports for training data pipeline (not yet wired to UI)
 #[allow(unused_imports)]
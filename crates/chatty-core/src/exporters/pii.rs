@@ -0,0 +1,458 @@
+//! PII scrubbing for training exports.
+//!
+//! Exported ATIF/JSONL values are walked string by string and names, email
+//! addresses, API keys and home-directory paths are replaced with stable
+//! placeholders such as `[EMAIL_3]`. The placeholder → original mapping is kept
+//! in `pii_mapping.json` so scrubbing can be reversed locally; that file holds
+//! the original values and must never be shared with the dataset. Scrub counts
+//! per conversation and export are recorded in `pii_report.json`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Placeholder → original mapping used to reverse scrubbing.
+pub const PII_MAPPING_FILE: &str = "pii_mapping.json";
+/// Scrub counts per conversation and export.
+pub const PII_REPORT_FILE: &str = "pii_report.json";
+
+/// Serializes mapping/report updates: ATIF and JSONL exports run concurrently
+/// and must not hand out the same placeholder for different values.
+static EXPORT_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+static KEY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"-----BEGIN [A-Z ]*PRIVATE KEY-----[\s\S]*?-----END [A-Z ]*PRIVATE KEY-----",
+        r"|\bsk-[A-Za-z0-9_\-]{20,}",
+        r"|\bgh[pousr]_[A-Za-z0-9]{30,}",
+        r"|\bgithub_pat_[A-Za-z0-9_]{22,}",
+        r"|\bAKIA[0-9A-Z]{16}\b",
+        r"|\bAIza[0-9A-Za-z_\-]{35}",
+        r"|\bxox[abprs]-[A-Za-z0-9\-]{10,}",
+        r"|\beyJ[A-Za-z0-9_\-]{10,}\.[A-Za-z0-9_\-]{10,}\.[A-Za-z0-9_\-]{10,}",
+    ))
+    .unwrap()
+});
+
+static EMAIL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}\b").unwrap());
+
+/// Home-directory prefixes; only the part naming the user is replaced so the
+/// rest of the path stays useful for training.
+static PATH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:/(?:Users|home)/[^\s/\\'"`<>]+|\b[A-Za-z]:\\Users\\[^\s/\\'"`<>]+)"#).unwrap()
+});
+
+static PLACEHOLDER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[(?:NAME|EMAIL|KEY|PATH)_\d+\]").unwrap());
+
+/// Which categories of PII to scrub.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PiiScrubConfig {
+    /// Run exports through the scrubber. Opt-in: disabled by default.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Names to pseudonymize (matched case-insensitively on word boundaries)
+    #[serde(default)]
+    pub names: Vec<String>,
+    #[serde(default = "default_true")]
+    pub emails: bool,
+    #[serde(default = "default_true")]
+    pub keys: bool,
+    #[serde(default = "default_true")]
+    pub paths: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for PiiScrubConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            names: Vec::new(),
+            emails: true,
+            keys: true,
+            paths: true,
+        }
+    }
+}
+
+/// Category of a scrubbed value; determines the placeholder prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PiiKind {
+    Name,
+    Email,
+    Key,
+    Path,
+}
+
+impl PiiKind {
+    fn label(self) -> &'static str {
+        match self {
+            PiiKind::Name => "NAME",
+            PiiKind::Email => "EMAIL",
+            PiiKind::Key => "KEY",
+            PiiKind::Path => "PATH",
+        }
+    }
+}
+
+/// Number of replacements made, by category.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScrubCounts {
+    pub names: usize,
+    pub emails: usize,
+    pub keys: usize,
+    pub paths: usize,
+}
+
+impl ScrubCounts {
+    pub fn total(&self) -> usize {
+        self.names + self.emails + self.keys + self.paths
+    }
+
+    fn add(&mut self, kind: PiiKind) {
+        match kind {
+            PiiKind::Name => self.names += 1,
+            PiiKind::Email => self.emails += 1,
+            PiiKind::Key => self.keys += 1,
+            PiiKind::Path => self.paths += 1,
+        }
+    }
+}
+
+/// Reversible placeholder mapping, persisted as `pii_mapping.json`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PiiMapping {
+    /// Placeholder → original value
+    #[serde(default)]
+    pub entries: BTreeMap<String, String>,
+    #[serde(skip)]
+    index: HashMap<String, String>,
+    #[serde(skip)]
+    next: HashMap<PiiKind, usize>,
+}
+
+impl PiiMapping {
+    /// Load the mapping, treating a missing file as empty.
+    pub async fn load(exports_dir: &Path) -> Result<Self> {
+        let path = exports_dir.join(PII_MAPPING_FILE);
+        let mut mapping: Self = match tokio::fs::read_to_string(&path).await {
+            Ok(json) => serde_json::from_str(&json)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e.into()),
+        };
+        mapping.rebuild_index();
+        Ok(mapping)
+    }
+
+    pub async fn save(&self, exports_dir: &Path) -> Result<()> {
+        write_json_atomic(&exports_dir.join(PII_MAPPING_FILE), self).await
+    }
+
+    fn rebuild_index(&mut self) {
+        self.index.clear();
+        self.next.clear();
+        for (placeholder, original) in &self.entries {
+            self.index.insert(original.clone(), placeholder.clone());
+            for kind in [PiiKind::Name, PiiKind::Email, PiiKind::Key, PiiKind::Path] {
+                let n = placeholder
+                    .strip_prefix('[')
+                    .and_then(|p| p.strip_prefix(kind.label()))
+                    .and_then(|p| p.strip_prefix('_'))
+                    .and_then(|p| p.strip_suffix(']'))
+                    .and_then(|n| n.parse::<usize>().ok());
+                if let Some(n) = n {
+                    let next = self.next.entry(kind).or_insert(1);
+                    *next = (*next).max(n + 1);
+                }
+            }
+        }
+    }
+
+    /// Placeholder for `original`, allocating a new one on first sight.
+    fn placeholder(&mut self, kind: PiiKind, original: &str) -> String {
+        if let Some(existing) = self.index.get(original) {
+            return existing.clone();
+        }
+        let next = self.next.entry(kind).or_insert(1);
+        let placeholder = format!("[{}_{}]", kind.label(), next);
+        *next += 1;
+        self.index.insert(original.to_string(), placeholder.clone());
+        self.entries
+            .insert(placeholder.clone(), original.to_string());
+        placeholder
+    }
+
+    /// Replace known placeholders in `text` with their original values.
+    pub fn restore(&self, text: &str) -> String {
+        PLACEHOLDER_RE
+            .replace_all(text, |caps: &regex::Captures| {
+                let placeholder = &caps[0];
+                self.entries
+                    .get(placeholder)
+                    .cloned()
+                    .unwrap_or_else(|| placeholder.to_string())
+            })
+            .into_owned()
+    }
+}
+
+/// Scrub counts per conversation, keyed by export (`atif`, `sft`, `dpo`).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PiiReport {
+    #[serde(default)]
+    pub conversations: BTreeMap<String, BTreeMap<String, ScrubCounts>>,
+}
+
+impl PiiReport {
+    pub async fn load(exports_dir: &Path) -> Result<Self> {
+        let path = exports_dir.join(PII_REPORT_FILE);
+        match tokio::fs::read_to_string(&path).await {
+            Ok(json) => serde_json::from_str(&json)
+                .with_context(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn save(&self, exports_dir: &Path) -> Result<()> {
+        write_json_atomic(&exports_dir.join(PII_REPORT_FILE), self).await
+    }
+
+    /// Record the counts for one export of a conversation, replacing the
+    /// previous entry (exports are deduplicated the same way).
+    pub fn record(&mut self, conversation_id: &str, export: &str, counts: ScrubCounts) {
+        self.conversations
+            .entry(conversation_id.to_string())
+            .or_default()
+            .insert(export.to_string(), counts);
+    }
+}
+
+/// Replaces PII in strings using a shared [`PiiMapping`].
+pub struct PiiScrubber {
+    config: PiiScrubConfig,
+    names_re: Option<Regex>,
+    mapping: PiiMapping,
+}
+
+impl PiiScrubber {
+    pub fn new(config: &PiiScrubConfig, mapping: PiiMapping) -> Self {
+        let mut names: Vec<&str> = config
+            .names
+            .iter()
+            .map(|n| n.trim())
+            .filter(|n| !n.is_empty())
+            .collect();
+        // Longest first so "Ada Lovelace" wins over "Ada"
+        names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+        let names_re = (!names.is_empty()).then(|| {
+            let alternatives = names
+                .iter()
+                .map(|n| regex::escape(n))
+                .collect::<Vec<_>>()
+                .join("|");
+            Regex::new(&format!(r"(?i)\b(?:{alternatives})\b")).unwrap()
+        });
+        Self {
+            config: config.clone(),
+            names_re,
+            mapping,
+        }
+    }
+
+    pub fn into_mapping(self) -> PiiMapping {
+        self.mapping
+    }
+
+    /// Scrub a single string, adding replacements to `counts`.
+    pub fn scrub_text(&mut self, text: &str, counts: &mut ScrubCounts) -> String {
+        let mut text = text.to_string();
+        if self.config.keys {
+            text = self.replace(&KEY_RE, PiiKind::Key, &text, counts);
+        }
+        if self.config.emails {
+            text = self.replace(&EMAIL_RE, PiiKind::Email, &text, counts);
+        }
+        if self.config.paths {
+            text = self.replace(&PATH_RE, PiiKind::Path, &text, counts);
+        }
+        if let Some(names_re) = self.names_re.clone() {
+            text = self.replace(&names_re, PiiKind::Name, &text, counts);
+        }
+        text
+    }
+
+    /// Scrub every string in a JSON value in place.
+    pub fn scrub_value(&mut self, value: &mut serde_json::Value) -> ScrubCounts {
+        let mut counts = ScrubCounts::default();
+        self.scrub_value_into(value, &mut counts);
+        counts
+    }
+
+    fn scrub_value_into(&mut self, value: &mut serde_json::Value, counts: &mut ScrubCounts) {
+        match value {
+            serde_json::Value::String(s) => *s = self.scrub_text(s, counts),
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    self.scrub_value_into(item, counts);
+                }
+            }
+            serde_json::Value::Object(map) => {
+                for item in map.values_mut() {
+                    self.scrub_value_into(item, counts);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn replace(
+        &mut self,
+        re: &Regex,
+        kind: PiiKind,
+        text: &str,
+        counts: &mut ScrubCounts,
+    ) -> String {
+        if !re.is_match(text) {
+            return text.to_string();
+        }
+        re.replace_all(text, |caps: &regex::Captures| {
+            counts.add(kind);
+            self.mapping.placeholder(kind, &caps[0])
+        })
+        .into_owned()
+    }
+}
+
+/// Scrub the values of one export in place, updating the mapping and report
+/// files in `exports_dir`. Returns the combined counts.
+pub async fn scrub_export(
+    exports_dir: &Path,
+    config: &PiiScrubConfig,
+    conversation_id: &str,
+    export: &str,
+    values: &mut [serde_json::Value],
+) -> Result<ScrubCounts> {
+    let _guard = EXPORT_LOCK.lock().await;
+    tokio::fs::create_dir_all(exports_dir).await?;
+
+    let mut scrubber = PiiScrubber::new(config, PiiMapping::load(exports_dir).await?);
+    let mut counts = ScrubCounts::default();
+    for value in values.iter_mut() {
+        scrubber.scrub_value_into(value, &mut counts);
+    }
+    scrubber.into_mapping().save(exports_dir).await?;
+
+    let mut report = PiiReport::load(exports_dir).await?;
+    report.record(conversation_id, export, counts);
+    report.save(exports_dir).await?;
+    Ok(counts)
+}
+
+async fn write_json_atomic(path: &Path, value: &impl Serialize) -> Result<()> {
+    let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    tokio::fs::write(&temp_path, serde_json::to_string_pretty(value)?).await?;
+    tokio::fs::rename(&temp_path, path).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(names: &[&str]) -> PiiScrubConfig {
+        PiiScrubConfig {
+            enabled: true,
+            names: names.iter().map(|n| n.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scrubs_each_category_with_stable_placeholders() {
+        let mut scrubber = PiiScrubber::new(&config(&["Ada Lovelace", "Ada"]), Default::default());
+        let mut counts = ScrubCounts::default();
+        let out = scrubber.scrub_text(
+            "Ada Lovelace <ada@example.com> ran /home/ada/proj/main.rs with \
+             sk-abcdefghijklmnopqrstuvwx; mail ada@example.com, ask ada",
+            &mut counts,
+        );
+        assert_eq!(
+            out,
+            "[NAME_1] <[EMAIL_1]> ran [PATH_1]/proj/main.rs with [KEY_1]; \
+             mail [EMAIL_1], ask [NAME_2]"
+        );
+        assert_eq!(
+            counts,
+            ScrubCounts {
+                names: 2,
+                emails: 2,
+                keys: 1,
+                paths: 1
+            }
+        );
+    }
+
+    #[test]
+    fn disabled_categories_are_left_alone() {
+        let cfg = PiiScrubConfig {
+            emails: false,
+            ..config(&[])
+        };
+        let mut scrubber = PiiScrubber::new(&cfg, Default::default());
+        let mut counts = ScrubCounts::default();
+        let out = scrubber.scrub_text("bob@example.com C:\\Users\\bob\\x", &mut counts);
+        assert_eq!(out, "bob@example.com [PATH_1]\\x");
+        assert_eq!(counts.total(), 1);
+    }
+
+    #[test]
+    fn scrub_value_walks_nested_json_and_restores() {
+        let mut scrubber = PiiScrubber::new(&config(&[]), Default::default());
+        let mut value = serde_json::json!({
+            "messages": [{"role": "user", "content": "I'm at a@b.io"}],
+            "_conversation_id": "c1",
+            "count": 3
+        });
+        let counts = scrubber.scrub_value(&mut value);
+        assert_eq!(counts.emails, 1);
+        assert_eq!(value["messages"][0]["content"], "I'm at [EMAIL_1]");
+        let mapping = scrubber.into_mapping();
+        assert_eq!(
+            mapping.restore("I'm at [EMAIL_1], [EMAIL_9]"),
+            "I'm at a@b.io, [EMAIL_9]"
+        );
+    }
+
+    #[tokio::test]
+    async fn scrub_export_persists_mapping_and_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let cfg = config(&[]);
+        let mut first = vec![serde_json::json!({"content": "x@y.com"})];
+        scrub_export(dir.path(), &cfg, "c1", "sft", &mut first)
+            .await
+            .unwrap();
+        let mut second = vec![serde_json::json!({"content": "z@y.com and x@y.com"})];
+        let counts = scrub_export(dir.path(), &cfg, "c2", "sft", &mut second)
+            .await
+            .unwrap();
+        assert_eq!(counts.emails, 2);
+        // Placeholders continue numbering across exports and reuse known values
+        assert_eq!(second[0]["content"], "[EMAIL_2] and [EMAIL_1]");
+
+        let mapping = PiiMapping::load(dir.path()).await.unwrap();
+        assert_eq!(mapping.entries.len(), 2);
+        let report = PiiReport::load(dir.path()).await.unwrap();
+        assert_eq!(report.conversations["c1"]["sft"].emails, 1);
+        assert_eq!(report.conversations["c2"]["sft"].total(), 2);
+    }
+}
@@ -0,0 +1,645 @@
+//! Project export: the code files a conversation produced, written out as a
+//! directory tree.
+//!
+//! Files come from two places: successful `write_file`/`apply_diff`/`move_file`
+//! tool calls, and fenced code blocks in the assistant's answers. A block is
+//! placed at the path named in its info string (```` ```rust src/main.rs ````),
+//! on the line just above it (`` `src/main.rs`: ``, `### src/main.rs`) or in a
+//! first-line comment (`// src/main.rs`). Blocks without a path land under
+//! `snippets/`. When a path is produced more than once the latest version
+//! wins, and a tool-written file beats a code block from the same turn.
+//!
+//! A README summarizing the conversation is written next to the files, and
+//! the directory can optionally be turned into a git repository.
+
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use rig_core::completion::Message;
+use rig_core::completion::message::AssistantContent;
+
+use super::handoff::{indent, truncate};
+use crate::models::message_types::{SystemTrace, ToolCallState, TraceItem};
+use crate::repositories::ConversationData;
+use crate::services::message_orchestrator::extract_user_text;
+
+/// Directory unnamed code blocks are written to.
+pub const SNIPPETS_DIR: &str = "snippets";
+
+/// Characters of each user request kept in the README.
+pub const REQUEST_CHARS: usize = 300;
+
+/// Code block languages that are program output rather than source.
+const OUTPUT_LANGUAGES: &[&str] = &["", "text", "txt", "plaintext", "console", "output", "log"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FileSource {
+    /// Written to disk by a filesystem tool; the file's current contents are
+    /// exported when it still exists.
+    Tool { name: String, origin: PathBuf },
+    /// A fenced code block in an answer.
+    CodeBlock,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectFile {
+    /// Relative to the export directory.
+    pub path: PathBuf,
+    /// Contents as seen in the conversation; `None` for tool calls that did
+    /// not carry the full file (diffs, moves).
+    pub content: Option<String>,
+    pub source: FileSource,
+    /// The user turn (1-based) the file was produced in.
+    pub turn: usize,
+}
+
+impl ProjectFile {
+    fn describe(&self) -> String {
+        match &self.source {
+            FileSource::Tool { name, .. } => format!("`{name}`, turn {}", self.turn),
+            FileSource::CodeBlock => format!("code block, turn {}", self.turn),
+        }
+    }
+}
+
+/// What [`ProjectExport::write`] did with the git repository.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GitOutcome {
+    Committed,
+    /// `git init` worked but the initial commit did not (e.g. no
+    /// `user.email` configured).
+    InitializedOnly(String),
+    Failed(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectWriteSummary {
+    pub files_written: usize,
+    /// Tool-written files that were gone from disk and had no recorded
+    /// contents.
+    pub skipped: Vec<PathBuf>,
+    pub readme: PathBuf,
+    pub git: Option<GitOutcome>,
+}
+
+/// A conversation's generated files, ready to be written out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectExport {
+    pub title: String,
+    /// The user's requests, oldest first.
+    pub requests: Vec<String>,
+    pub files: Vec<ProjectFile>,
+}
+
+impl ProjectExport {
+    pub fn from_conversation(data: &ConversationData) -> Result<Self> {
+        let history: Vec<Message> = serde_json::from_str(&data.message_history)
+            .context("Failed to parse message_history")?;
+        let traces: Vec<Option<serde_json::Value>> =
+            serde_json::from_str(&data.system_traces).unwrap_or_default();
+        let working_dir = data.working_dir.as_deref().map(Path::new);
+
+        let mut requests = Vec::new();
+        let mut files: Vec<ProjectFile> = Vec::new();
+        let mut add_file = |file: ProjectFile| {
+            files.retain(|existing| existing.path != file.path);
+            files.push(file);
+        };
+        let mut turn = 0;
+        for (ix, message) in history.iter().enumerate() {
+            match message {
+                Message::User { content } => {
+                    let text = extract_user_text(&content.iter().cloned().collect::<Vec<_>>());
+                    if !text.trim().is_empty() {
+                        turn += 1;
+                        requests.push(text.trim().to_string());
+                    }
+                }
+                Message::Assistant { content, .. } => {
+                    let text = content
+                        .iter()
+                        .filter_map(|c| match c {
+                            AssistantContent::Text(t) => Some(t.text.as_str()),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n\n");
+                    let mut unnamed = 0;
+                    for block in code_blocks(&text) {
+                        let path = match block.path.as_deref().and_then(|p| project_path(p, None)) {
+                            Some(path) => path,
+                            None if OUTPUT_LANGUAGES.contains(&block.language.as_str()) => {
+                                continue;
+                            }
+                            None => {
+                                unnamed += 1;
+                                PathBuf::from(SNIPPETS_DIR).join(format!(
+                                    "turn-{turn}-{unnamed}.{}",
+                                    extension_for(&block.language)
+                                ))
+                            }
+                        };
+                        add_file(ProjectFile {
+                            path,
+                            content: Some(block.code),
+                            source: FileSource::CodeBlock,
+                            turn,
+                        });
+                    }
+
+                    let Some(trace) = traces
+                        .get(ix)
+                        .cloned()
+                        .flatten()
+                        .and_then(|value| serde_json::from_value::<SystemTrace>(value).ok())
+                    else {
+                        continue;
+                    };
+                    for item in &trace.items {
+                        if let TraceItem::ToolCall(call) = item
+                            && call.state == ToolCallState::Success
+                            && let Some((origin, content)) =
+                                written_file(&call.tool_name, &call.input)
+                            && let Some(path) = project_path(&origin, working_dir)
+                        {
+                            let origin = match working_dir {
+                                Some(dir) if Path::new(&origin).is_relative() => dir.join(&origin),
+                                _ => PathBuf::from(&origin),
+                            };
+                            add_file(ProjectFile {
+                                path,
+                                content,
+                                source: FileSource::Tool {
+                                    name: call.tool_name.clone(),
+                                    origin,
+                                },
+                                turn,
+                            });
+                        }
+                    }
+                }
+                Message::System { .. } => {}
+            }
+        }
+
+        Ok(Self {
+            title: data.title.clone(),
+            requests,
+            files,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Name of the summary file: `README.md`, or `CONVERSATION.md` when the
+    /// conversation produced a README of its own.
+    pub fn readme_name(&self) -> &'static str {
+        let has_readme = self.files.iter().any(|file| {
+            file.path
+                .to_str()
+                .is_some_and(|p| p.eq_ignore_ascii_case("README.md"))
+        });
+        if has_readme {
+            "CONVERSATION.md"
+        } else {
+            "README.md"
+        }
+    }
+
+    /// The summary README.
+    pub fn readme(&self) -> String {
+        let mut md = format!(
+            "# {}\n\nGenerated in a Chatty conversation. The files below were written by \
+             tools or taken from code blocks in the answers.\n",
+            self.title
+        );
+        if !self.requests.is_empty() {
+            md.push_str("\n## Requests\n\n");
+            for (ix, text) in self.requests.iter().enumerate() {
+                md.push_str(&format!(
+                    "{}. {}\n",
+                    ix + 1,
+                    indent(&truncate(text, REQUEST_CHARS), "   ")
+                ));
+            }
+        }
+        if !self.files.is_empty() {
+            md.push_str("\n## Files\n\n");
+            for file in &self.files {
+                md.push_str(&format!(
+                    "- `{}` ({})\n",
+                    file.path.to_string_lossy(),
+                    file.describe()
+                ));
+            }
+        }
+        md
+    }
+
+    /// Write the files and the README into `dest`, which must be empty or
+    /// not exist yet, and optionally `git init` it with an initial commit.
+    pub async fn write(&self, dest: &Path, git_init: bool) -> Result<ProjectWriteSummary> {
+        if let Ok(mut entries) = tokio::fs::read_dir(dest).await
+            && entries.next_entry().await?.is_some()
+        {
+            bail!("{} is not empty", dest.display());
+        }
+        tokio::fs::create_dir_all(dest)
+            .await
+            .with_context(|| format!("Failed to create {}", dest.display()))?;
+
+        let mut files_written = 0;
+        let mut skipped = Vec::new();
+        for file in &self.files {
+            let on_disk = match &file.source {
+                FileSource::Tool { origin, .. } => tokio::fs::read(origin).await.ok(),
+                FileSource::CodeBlock => None,
+            };
+            let Some(contents) = on_disk.or_else(|| file.content.clone().map(String::into_bytes))
+            else {
+                skipped.push(file.path.clone());
+                continue;
+            };
+            let target = dest.join(&file.path);
+            if let Some(parent) = target.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            tokio::fs::write(&target, contents)
+                .await
+                .with_context(|| format!("Failed to write {}", target.display()))?;
+            files_written += 1;
+        }
+
+        let readme = dest.join(self.readme_name());
+        tokio::fs::write(&readme, self.readme())
+            .await
+            .with_context(|| format!("Failed to write {}", readme.display()))?;
+
+        let git = if git_init {
+            Some(init_repository(dest).await)
+        } else {
+            None
+        };
+        Ok(ProjectWriteSummary {
+            files_written,
+            skipped,
+            readme,
+            git,
+        })
+    }
+}
+
+async fn init_repository(dir: &Path) -> GitOutcome {
+    if let Err(err) = run_git(dir, &["init"]).await {
+        return GitOutcome::Failed(err);
+    }
+    let commit = async {
+        run_git(dir, &["add", "-A"]).await?;
+        run_git(dir, &["commit", "-m", "Initial commit"]).await
+    };
+    match commit.await {
+        Ok(()) => GitOutcome::Committed,
+        Err(err) => GitOutcome::InitializedOnly(err),
+    }
+}
+
+async fn run_git(dir: &Path, args: &[&str]) -> std::result::Result<(), String> {
+    let output = tokio::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .map_err(|e| format!("git {}: {e}", args[0]))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "git {}: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// The file a successful filesystem tool call wrote, with the contents the
+/// call carried when it wrote the whole file.
+fn written_file(tool_name: &str, input: &str) -> Option<(String, Option<String>)> {
+    let key = match tool_name {
+        "write_file" | "apply_diff" => "path",
+        "move_file" => "destination",
+        _ => return None,
+    };
+    let args: serde_json::Value = serde_json::from_str(input).ok()?;
+    let path = args.get(key)?.as_str()?.trim();
+    let content = (tool_name == "write_file")
+        .then(|| args.get("content").and_then(|c| c.as_str()))
+        .flatten()
+        .map(str::to_string);
+    (!path.is_empty()).then(|| (path.to_string(), content))
+}
+
+/// A path safe to create under the export directory: relative to
+/// `working_dir` when it lies inside it, just the file name for other
+/// absolute paths, and `None` for paths that climb out with `..`.
+fn project_path(raw: &str, working_dir: Option<&Path>) -> Option<PathBuf> {
+    let raw = Path::new(raw.trim());
+    let relative = if raw.is_absolute() {
+        match working_dir.and_then(|dir| raw.strip_prefix(dir).ok()) {
+            Some(rel) => rel,
+            None => Path::new(raw.file_name()?),
+        }
+    } else {
+        raw
+    };
+    let mut path = PathBuf::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    (!path.as_os_str().is_empty()).then_some(path)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct CodeBlock {
+    language: String,
+    path: Option<String>,
+    code: String,
+}
+
+/// Fenced code blocks in `text`, with the path each one names, if any.
+fn code_blocks(text: &str) -> Vec<CodeBlock> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut blocks = Vec::new();
+    let mut ix = 0;
+    while ix < lines.len() {
+        let line = lines[ix].trim_start();
+        let Some(fence) = fence_of(line) else {
+            ix += 1;
+            continue;
+        };
+        let info = line[fence.len()..].trim();
+        let marker = fence.chars().next().unwrap_or('`');
+        let close = (ix + 1..lines.len()).find(|&j| {
+            let candidate = lines[j].trim();
+            candidate.starts_with(fence) && candidate.trim_start_matches(marker).is_empty()
+        });
+        let end = close.unwrap_or(lines.len());
+        let body = &lines[ix + 1..end];
+
+        let mut words = info.split_whitespace();
+        let first = words.next().unwrap_or("");
+        let (language, inline_path) = match first.split_once(':') {
+            Some((lang, path)) if looks_like_path(path) => (lang, Some(path.to_string())),
+            _ if looks_like_path(first) => ("", Some(first.to_string())),
+            _ => (first, None),
+        };
+        let path = inline_path
+            .or_else(|| {
+                words.find_map(|word| {
+                    path_from_attribute(word)
+                        .or_else(|| looks_like_path(word).then(|| word.to_string()))
+                })
+            })
+            .or_else(|| {
+                lines[..ix]
+                    .iter()
+                    .rev()
+                    .find(|l| !l.trim().is_empty())
+                    .and_then(|l| path_from_heading(l))
+            })
+            .or_else(|| body.first().and_then(|l| path_from_comment(l)));
+
+        let mut code = body.join("\n");
+        if !code.is_empty() {
+            code.push('\n');
+        }
+        blocks.push(CodeBlock {
+            language: language.to_ascii_lowercase(),
+            path,
+            code,
+        });
+        ix = end + 1;
+    }
+    blocks
+}
+
+fn fence_of(line: &str) -> Option<&str> {
+    let marker = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = line.chars().take_while(|c| *c == marker).count();
+    (len >= 3).then(|| &line[..len])
+}
+
+/// `title="src/main.rs"`, `file=src/main.rs` and the like in an info string.
+fn path_from_attribute(word: &str) -> Option<String> {
+    let (key, value) = word.split_once('=')?;
+    if !matches!(key, "title" | "file" | "filename" | "path") {
+        return None;
+    }
+    let value = value.trim_matches(|c| c == '"' || c == '\'');
+    looks_like_path(value).then(|| value.to_string())
+}
+
+/// A path on the line above a fence: `` `src/main.rs` ``, `**src/main.rs**:`,
+/// `### src/main.rs` or `File: src/main.rs`.
+fn path_from_heading(line: &str) -> Option<String> {
+    let mut line = line
+        .trim()
+        .trim_start_matches('#')
+        .trim()
+        .trim_end_matches(':')
+        .trim();
+    for prefix in ["File:", "file:", "Filename:", "filename:", "Path:", "path:"] {
+        if let Some(rest) = line.strip_prefix(prefix) {
+            line = rest.trim();
+        }
+    }
+    let candidate = line
+        .trim_matches(|c| c == '`' || c == '*' || c == '_')
+        .trim_end_matches(':');
+    looks_like_path(candidate).then(|| candidate.to_string())
+}
+
+/// A path in a first-line comment: `// src/main.rs`, `# app.py`,
+/// `<!-- index.html -->`, optionally with a `file:` prefix.
+fn path_from_comment(line: &str) -> Option<String> {
+    let line = line.trim();
+    let rest = ["//", "#", "--", "<!--", "/*"]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))?;
+    let mut rest = rest
+        .trim()
+        .trim_end_matches("-->")
+        .trim_end_matches("*/")
+        .trim();
+    for prefix in ["file:", "File:", "filename:", "Filename:"] {
+        if let Some(stripped) = rest.strip_prefix(prefix) {
+            rest = stripped.trim();
+        }
+    }
+    looks_like_path(rest).then(|| rest.to_string())
+}
+
+/// One token with a file extension (or a well-known extensionless name such
+/// as `Makefile`).
+fn looks_like_path(candidate: &str) -> bool {
+    if candidate.is_empty() || candidate.contains(char::is_whitespace) || candidate.contains("://")
+    {
+        return false;
+    }
+    let name = candidate.rsplit('/').next().unwrap_or(candidate);
+    if matches!(name, "Makefile" | "Dockerfile" | "Justfile" | "Procfile") {
+        return true;
+    }
+    match name.rsplit_once('.') {
+        Some((stem, ext)) => {
+            !stem.is_empty()
+                && (1..=10).contains(&ext.len())
+                && ext.chars().all(|c| c.is_ascii_alphanumeric())
+                && ext.chars().any(|c| c.is_ascii_alphabetic())
+        }
+        None => false,
+    }
+}
+
+fn extension_for(language: &str) -> &str {
+    match language {
+        "rust" | "rs" => "rs",
+        "python" | "py" => "py",
+        "javascript" | "js" | "jsx" => "js",
+        "typescript" | "ts" | "tsx" => "ts",
+        "bash" | "sh" | "shell" | "zsh" => "sh",
+        "golang" | "go" => "go",
+        "c++" | "cpp" => "cpp",
+        "c#" | "csharp" => "cs",
+        "kotlin" => "kt",
+        "ruby" => "rb",
+        "yaml" | "yml" => "yaml",
+        "markdown" | "md" => "md",
+        "dockerfile" => "dockerfile",
+        lang if !lang.is_empty() && lang.chars().all(|c| c.is_ascii_alphanumeric()) => lang,
+        _ => "txt",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{ConversationDataBuilder, tool_call, trace};
+
+    #[test]
+    fn finds_paths_in_info_strings_headings_and_comments() {
+        let text = "Here is the layout.\n\n\
+                    ```rust src/main.rs\nfn main() {}\n```\n\n\
+                    **`Cargo.toml`**:\n\n```toml\n[package]\n```\n\n\
+                    ```python\n# tools/gen.py\nprint(1)\n```\n\n\
+                    ```js title=\"web/app.js\"\nrun();\n```\n\n\
+                    ```\n$ cargo run\n```\n";
+        let blocks = code_blocks(text);
+        let paths: Vec<_> = blocks.iter().map(|b| b.path.as_deref()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Some("src/main.rs"),
+                Some("Cargo.toml"),
+                Some("tools/gen.py"),
+                Some("web/app.js"),
+                None,
+            ]
+        );
+        assert_eq!(blocks[0].code, "fn main() {}\n");
+        assert_eq!(blocks[1].language, "toml");
+    }
+
+    #[test]
+    fn rejects_unsafe_and_non_file_paths() {
+        assert_eq!(
+            project_path("/work/src/lib.rs", Some(Path::new("/work"))),
+            Some(PathBuf::from("src/lib.rs"))
+        );
+        assert_eq!(
+            project_path("/etc/hosts.conf", Some(Path::new("/work"))),
+            Some(PathBuf::from("hosts.conf"))
+        );
+        assert_eq!(
+            project_path("./a/b.rs", None),
+            Some(PathBuf::from("a/b.rs"))
+        );
+        assert_eq!(project_path("../secret.txt", None), None);
+        assert!(!looks_like_path("e.g. this"));
+        assert!(!looks_like_path("1.5"));
+        assert!(!looks_like_path("https://example.com/a.js"));
+        assert!(looks_like_path("Makefile"));
+    }
+
+    #[test]
+    fn collects_latest_version_with_tool_writes_winning() {
+        let data = ConversationDataBuilder::new("conv-1")
+            .title("Todo CLI")
+            .working_dir("/work")
+            .user("Write a todo CLI")
+            .assistant("```rust src/main.rs\nfn main() {}\n```\n\n```sh\ncargo run\n```")
+            .trace(trace([
+                tool_call(
+                    "write_file",
+                    serde_json::json!({"path": "/work/src/main.rs", "content": "fn main() { run() }\n"}),
+                    ToolCallState::Success,
+                ),
+                tool_call(
+                    "write_file",
+                    serde_json::json!({"path": "/work/denied.rs", "content": ""}),
+                    ToolCallState::Error("denied".to_string()),
+                ),
+            ]))
+            .user("Add a README")
+            .assistant("### README.md\n```markdown\n# Todo\n```")
+            .build();
+        let export = ProjectExport::from_conversation(&data).unwrap();
+
+        let paths: Vec<_> = export.files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("snippets/turn-1-1.sh"),
+                PathBuf::from("src/main.rs"),
+                PathBuf::from("README.md"),
+            ]
+        );
+        assert_eq!(
+            export.files[1].content.as_deref(),
+            Some("fn main() { run() }\n")
+        );
+        assert!(matches!(export.files[1].source, FileSource::Tool { .. }));
+        assert_eq!(export.files[2].turn, 2);
+        assert_eq!(export.readme_name(), "CONVERSATION.md");
+        assert!(export.readme().contains("1. Write a todo CLI"));
+    }
+
+    #[tokio::test]
+    async fn writes_files_and_refuses_non_empty_directories() {
+        let data = ConversationDataBuilder::new("conv-1")
+            .title("Todo CLI")
+            .working_dir("/work")
+            .user("Write a script")
+            .assistant("`bin/run.sh`\n```sh\necho hi\n```")
+            .build();
+        let export = ProjectExport::from_conversation(&data).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("out");
+
+        let summary = export.write(&dest, false).await.unwrap();
+        assert_eq!(summary.files_written, 1);
+        assert_eq!(
+            std::fs::read_to_string(dest.join("bin/run.sh")).unwrap(),
+            "echo hi\n"
+        );
+        assert!(dest.join("README.md").exists());
+        assert!(export.write(&dest, false).await.is_err());
+    }
+}
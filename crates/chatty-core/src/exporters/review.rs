@@ -0,0 +1,318 @@
+//! Review state for exported training examples.
+//!
+//! Reviewers mark examples in `sft.jsonl` as approved or rejected, with an
+//! optional comment. Decisions live in `review_state.json` next to the exports
+//! so the directory can be shared with (or re-opened by) another reviewer;
+//! the export files themselves are never rewritten by a review.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// File holding review decisions, keyed by `_conversation_id`.
+pub const REVIEW_STATE_FILE: &str = "review_state.json";
+/// Exported SFT examples the reviewer works through.
+pub const SFT_FILE: &str = "sft.jsonl";
+/// Approved-only dataset written by [`write_approved_dataset`].
+pub const APPROVED_SFT_FILE: &str = "sft.approved.jsonl";
+
+/// Maximum characters of the first user message shown as a preview.
+const PREVIEW_CHARS: usize = 160;
+
+/// Review decision for one exported example.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewStatus {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// A reviewer's decision, comment and attribution.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReviewRecord {
+    pub status: ReviewStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewer: Option<String>,
+    /// Unix timestamp (seconds) of the last change
+    pub reviewed_at: i64,
+}
+
+/// All review decisions for an exports directory.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ReviewState {
+    #[serde(default)]
+    pub records: BTreeMap<String, ReviewRecord>,
+}
+
+impl ReviewState {
+    /// Load the review state, treating a missing file as empty.
+    pub async fn load(exports_dir: &Path) -> Result<Self> {
+        let path = exports_dir.join(REVIEW_STATE_FILE);
+        match tokio::fs::read_to_string(&path).await {
+            Ok(json) => serde_json::from_str(&json)
+                .with_context(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the review state atomically (temp file + rename).
+    pub async fn save(&self, exports_dir: &Path) -> Result<()> {
+        tokio::fs::create_dir_all(exports_dir).await?;
+        let path = exports_dir.join(REVIEW_STATE_FILE);
+        let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        tokio::fs::write(&temp_path, serde_json::to_string_pretty(self)?).await?;
+        tokio::fs::rename(&temp_path, &path).await?;
+        Ok(())
+    }
+
+    /// Record a decision. Blank comments are dropped, and a pending example
+    /// without a comment has no record at all.
+    pub fn set(
+        &mut self,
+        id: &str,
+        status: ReviewStatus,
+        comment: Option<String>,
+        reviewer: Option<String>,
+    ) {
+        let clean = |s: Option<String>| s.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        let comment = clean(comment);
+        if status == ReviewStatus::Pending && comment.is_none() {
+            self.records.remove(id);
+            return;
+        }
+        self.records.insert(
+            id.to_string(),
+            ReviewRecord {
+                status,
+                comment,
+                reviewer: clean(reviewer),
+                reviewed_at: chrono::Utc::now().timestamp(),
+            },
+        );
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ReviewRecord> {
+        self.records.get(id)
+    }
+
+    pub fn status(&self, id: &str) -> ReviewStatus {
+        self.get(id).map(|r| r.status).unwrap_or_default()
+    }
+}
+
+/// One exported SFT example as listed in the dataset browser.
+#[derive(Clone, Debug)]
+pub struct DatasetExample {
+    pub id: String,
+    /// Start of the first user message
+    pub preview: String,
+    pub message_count: usize,
+    /// Whether the example carries `_reviewer` annotations (reactions/notes)
+    pub annotated: bool,
+    /// Overall judge score (1–5) from `_quality`, if the conversation was scored
+    pub quality: Option<f32>,
+    pub review: Option<ReviewRecord>,
+}
+
+/// Default exports directory (`<config>/chatty/exports`).
+pub fn default_exports_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|config| config.join("chatty").join("exports"))
+}
+
+/// List the examples in `sft.jsonl` together with their review state.
+pub async fn list_examples(exports_dir: &Path) -> Result<Vec<DatasetExample>> {
+    let state = ReviewState::load(exports_dir).await?;
+    let content = match tokio::fs::read_to_string(exports_dir.join(SFT_FILE)).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(parse_examples(&content, &state))
+}
+
+/// Write `sft.approved.jsonl` with the approved examples, each carrying its
+/// review record under `_review`. With `min_quality`, approved examples whose
+/// overall quality score is lower (or missing) are left out. Returns the
+/// number of examples written.
+pub async fn write_approved_dataset(exports_dir: &Path, min_quality: Option<f32>) -> Result<usize> {
+    let state = ReviewState::load(exports_dir).await?;
+    let content = match tokio::fs::read_to_string(exports_dir.join(SFT_FILE)).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let lines = approved_lines(&content, &state, min_quality)?;
+
+    let path = exports_dir.join(APPROVED_SFT_FILE);
+    let temp_path = path.with_extension(format!("jsonl.{}.tmp", std::process::id()));
+    let mut output = lines.join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    tokio::fs::write(&temp_path, output).await?;
+    tokio::fs::rename(&temp_path, &path).await?;
+    Ok(lines.len())
+}
+
+fn parse_examples(content: &str, state: &ReviewState) -> Vec<DatasetExample> {
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|val| {
+            let id = val.get("_conversation_id")?.as_str()?.to_string();
+            let messages = val.get("messages").and_then(|m| m.as_array());
+            let preview = messages
+                .and_then(|m| m.iter().find(|msg| msg["role"] == "user"))
+                .and_then(|msg| msg["content"].as_str())
+                .map(|text| {
+                    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                    match text.char_indices().nth(PREVIEW_CHARS) {
+                        Some((cut, _)) => format!("{}…", &text[..cut]),
+                        None => text,
+                    }
+                })
+                .unwrap_or_default();
+            Some(DatasetExample {
+                review: state.get(&id).cloned(),
+                id,
+                preview,
+                message_count: messages.map(|m| m.len()).unwrap_or(0),
+                annotated: val.get("_reviewer").is_some(),
+                quality: quality_of(&val),
+            })
+        })
+        .collect()
+}
+
+fn quality_of(val: &serde_json::Value) -> Option<f32> {
+    val.get("_quality")?
+        .get("overall")?
+        .as_f64()
+        .map(|overall| overall as f32)
+}
+
+fn approved_lines(
+    content: &str,
+    state: &ReviewState,
+    min_quality: Option<f32>,
+) -> Result<Vec<String>> {
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let Ok(mut val) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        let Some(record) = val
+            .get("_conversation_id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| state.get(id))
+            .filter(|r| r.status == ReviewStatus::Approved)
+            .cloned()
+        else {
+            continue;
+        };
+        if let Some(min) = min_quality
+            && quality_of(&val).is_none_or(|quality| quality < min)
+        {
+            continue;
+        }
+        val["_review"] = serde_json::to_value(record)?;
+        lines.push(serde_json::to_string(&val)?);
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SFT: &str = concat!(
+        r#"{"messages":[{"role":"user","content":"What is  Rust?"},{"role":"assistant","content":"A language"}],"_conversation_id":"a"}"#,
+        "\n",
+        r#"{"messages":[{"role":"user","content":"Hi"}],"_conversation_id":"b","_reviewer":[{"message_index":0,"note":"x"}]}"#,
+        "\n",
+        "not json\n",
+    );
+
+    #[test]
+    fn parse_examples_reads_preview_and_review() {
+        let mut state = ReviewState::default();
+        state.set(
+            "a",
+            ReviewStatus::Approved,
+            Some("  clean ".into()),
+            Some("sam".into()),
+        );
+        let examples = parse_examples(SFT, &state);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].preview, "What is Rust?");
+        assert_eq!(examples[0].message_count, 2);
+        let review = examples[0].review.as_ref().unwrap();
+        assert_eq!(review.status, ReviewStatus::Approved);
+        assert_eq!(review.comment.as_deref(), Some("clean"));
+        assert!(examples[1].annotated);
+        assert!(examples[1].review.is_none());
+    }
+
+    #[test]
+    fn pending_clears_record() {
+        let mut state = ReviewState::default();
+        state.set("a", ReviewStatus::Rejected, None, None);
+        assert_eq!(state.status("a"), ReviewStatus::Rejected);
+        state.set("a", ReviewStatus::Pending, None, None);
+        assert!(state.get("a").is_none());
+        assert_eq!(state.status("a"), ReviewStatus::Pending);
+    }
+
+    #[test]
+    fn approved_lines_keep_only_approved_with_review() {
+        let mut state = ReviewState::default();
+        state.set("a", ReviewStatus::Approved, None, Some("sam".into()));
+        state.set("b", ReviewStatus::Rejected, Some("off-topic".into()), None);
+        let lines = approved_lines(SFT, &state, None).unwrap();
+        assert_eq!(lines.len(), 1);
+        let val: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(val["_conversation_id"], "a");
+        assert_eq!(val["_review"]["status"], "approved");
+        assert_eq!(val["_review"]["reviewer"], "sam");
+    }
+
+    #[test]
+    fn approved_lines_filter_by_min_quality() {
+        let sft = concat!(
+            r#"{"messages":[],"_conversation_id":"a","_quality":{"overall":4.5}}"#,
+            "\n",
+            r#"{"messages":[],"_conversation_id":"b","_quality":{"overall":2.0}}"#,
+            "\n",
+            r#"{"messages":[],"_conversation_id":"c"}"#,
+            "\n",
+        );
+        let mut state = ReviewState::default();
+        for id in ["a", "b", "c"] {
+            state.set(id, ReviewStatus::Approved, None, None);
+        }
+        assert_eq!(parse_examples(sft, &state)[0].quality, Some(4.5));
+        assert_eq!(approved_lines(sft, &state, None).unwrap().len(), 3);
+        let lines = approved_lines(sft, &state, Some(3.0)).unwrap();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains(r#""_conversation_id":"a""#));
+    }
+
+    #[tokio::test]
+    async fn state_roundtrips_through_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = ReviewState::default();
+        state.set("a", ReviewStatus::Approved, Some("ok".into()), None);
+        state.save(dir.path()).await.unwrap();
+        let loaded = ReviewState::load(dir.path()).await.unwrap();
+        assert_eq!(loaded.get("a"), state.get("a"));
+        let missing = ReviewState::load(&dir.path().join("nope")).await.unwrap();
+        assert!(missing.records.is_empty());
+    }
+}
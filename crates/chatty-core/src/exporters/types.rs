@@ -124,13 +124,15 @@ pub struct AtifFinalMetrics {
     pub total_steps: Option<u32>,
 }
 
-/// Custom extra block for Chatty-specific data (feedback, regenerations).
-/// The ATIF spec allows arbitrary data in `extra` fields.
+/// Custom extra block for Chatty-specific data (feedback, regenerations,
+/// reviewer annotations). The ATIF spec allows arbitrary data in `extra` fields.
 #[derive(Debug, Serialize)]
 pub struct AtifExtra {
     pub feedback: Vec<Option<String>>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub regenerations: Vec<AtifRegeneration>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<ReviewerAnnotation>,
 }
 
 #[derive(Debug, Serialize)]
@@ -139,3 +141,31 @@ pub struct AtifRegeneration {
     pub original_text: String,
     pub timestamp: i64,
 }
+
+/// Reviewer metadata for one message: emoji reactions and a private note.
+/// Shared by the ATIF `extra` block and the SFT `_reviewer` field.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ReviewerAnnotation {
+    pub message_index: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl ReviewerAnnotation {
+    /// Zip the per-message reaction and note arrays, keeping annotated messages only.
+    pub fn collect(reactions: &[Vec<String>], notes: &[Option<String>]) -> Vec<Self> {
+        (0..reactions.len().max(notes.len()))
+            .filter_map(|message_index| {
+                let reactions = reactions.get(message_index).cloned().unwrap_or_default();
+                let note = notes.get(message_index).cloned().flatten();
+                (!reactions.is_empty() || note.is_some()).then_some(Self {
+                    message_index,
+                    reactions,
+                    note,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,251 @@
+//! Per-turn token usage export to CSV, for expense reporting.
+//!
+//! Every assistant response with recorded token usage becomes one row. The
+//! n-th usage entry of a conversation belongs to its n-th assistant message,
+//! the same pairing `Conversation::delete_message` relies on. Latency is the
+//! time between the prompt and the finished response, so it has one-second
+//! resolution and is empty when either timestamp is missing.
+
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone};
+use rig_core::completion::Message;
+
+use crate::models::token_usage::ConversationTokenUsage;
+use crate::repositories::ConversationData;
+
+const HEADER: &str = "timestamp,conversation_id,conversation_title,turn,model,\
+                      input_tokens,output_tokens,api_calls,latency_seconds,cost_usd";
+
+/// Which responses an export covers, by when they finished.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UsageExportRange {
+    All,
+    /// The last `n` days, counted back from now
+    LastDays(i64),
+    /// Since the first day of the current calendar month
+    ThisMonth,
+}
+
+impl UsageExportRange {
+    /// Earliest Unix timestamp included, or `None` for no lower bound.
+    pub fn since<Tz: TimeZone>(self, now: &DateTime<Tz>) -> Option<i64> {
+        match self {
+            Self::All => None,
+            Self::LastDays(days) => Some((now.clone() - Duration::days(days)).timestamp()),
+            Self::ThisMonth => {
+                let first = now.date_naive().with_day(1)?.and_hms_opt(0, 0, 0)?;
+                Some(
+                    first
+                        .and_local_timezone(now.timezone())
+                        .earliest()?
+                        .timestamp(),
+                )
+            }
+        }
+    }
+
+    /// [`Self::since`] in local time.
+    pub fn since_now(self) -> Option<i64> {
+        self.since(&Local::now())
+    }
+
+    /// Suggested file name for the export.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::All => "chatty-usage.csv",
+            Self::LastDays(_) => "chatty-usage-recent.csv",
+            Self::ThisMonth => "chatty-usage-this-month.csv",
+        }
+    }
+}
+
+/// Token usage of one assistant response.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UsageRow {
+    /// Unix timestamp of the response, if recorded
+    pub timestamp: Option<i64>,
+    pub conversation_id: String,
+    pub conversation_title: String,
+    /// 1-based position of the response in the conversation
+    pub turn: usize,
+    pub model: String,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub api_calls: u32,
+    pub latency_seconds: Option<i64>,
+    pub cost_usd: Option<f64>,
+}
+
+/// Usage rows of one conversation. Responses without a timestamp fall back
+/// to the conversation's `updated_at` when checked against `since`.
+pub fn usage_rows(data: &ConversationData, since: Option<i64>) -> Vec<UsageRow> {
+    let Ok(usage) = serde_json::from_str::<ConversationTokenUsage>(&data.token_usage) else {
+        return Vec::new();
+    };
+    let messages: Vec<Message> = serde_json::from_str(&data.message_history).unwrap_or_default();
+    let timestamps: Vec<Option<i64>> =
+        serde_json::from_str(&data.message_timestamps).unwrap_or_default();
+    let timestamp_of = |ix: usize| timestamps.get(ix).copied().flatten();
+
+    let mut prompted_at = None;
+    let mut responses = Vec::new();
+    for (ix, message) in messages.iter().enumerate() {
+        match message {
+            Message::User { .. } => prompted_at = timestamp_of(ix),
+            Message::Assistant { .. } => {
+                let finished_at = timestamp_of(ix);
+                let latency = finished_at.zip(prompted_at).map(|(end, start)| end - start);
+                responses.push((finished_at, latency));
+            }
+            _ => {}
+        }
+    }
+
+    usage
+        .message_usages
+        .iter()
+        .zip(responses)
+        .enumerate()
+        .filter(|(_, (_, (finished_at, _)))| {
+            since.is_none_or(|since| finished_at.unwrap_or(data.updated_at) >= since)
+        })
+        .map(|(ix, (turn, (finished_at, latency)))| UsageRow {
+            timestamp: finished_at,
+            conversation_id: data.id.clone(),
+            conversation_title: data.title.clone(),
+            turn: ix + 1,
+            model: data.model_id.clone(),
+            input_tokens: turn.input_tokens,
+            output_tokens: turn.output_tokens,
+            api_calls: turn.api_turn_count,
+            latency_seconds: latency.filter(|secs| *secs >= 0),
+            cost_usd: turn.estimated_cost_usd,
+        })
+        .collect()
+}
+
+/// Render rows as CSV with a header line. Timestamps are written in UTC
+/// (RFC 3339), costs in US dollars.
+pub fn usage_csv(rows: &[UsageRow]) -> String {
+    let mut csv = format!("{HEADER}\n");
+    for row in rows {
+        let timestamp = row
+            .timestamp
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+            .map(|ts| ts.to_rfc3339())
+            .unwrap_or_default();
+        let fields = [
+            timestamp,
+            csv_field(&row.conversation_id),
+            csv_field(&row.conversation_title),
+            row.turn.to_string(),
+            csv_field(&row.model),
+            row.input_tokens.to_string(),
+            row.output_tokens.to_string(),
+            row.api_calls.to_string(),
+            row.latency_seconds
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+            row.cost_usd.map(|c| format!("{c:.6}")).unwrap_or_default(),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quote a text field when needed. A leading `=`, `+`, `-` or `@` gets a `'`
+/// prefix so spreadsheets don't evaluate titles as formulas.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::token_usage::TokenUsage;
+    use crate::testing::ConversationDataBuilder;
+    use chrono::Utc;
+
+    fn conversation(title: &str, timestamps: &[Option<i64>]) -> ConversationData {
+        let mut usage = ConversationTokenUsage::new();
+        for (input, output, cost) in [(1200, 300, Some(0.0042)), (1600, 40, None)] {
+            let mut turn = TokenUsage::with_turn_count(input, output, 2);
+            turn.estimated_cost_usd = cost;
+            usage.add_usage(turn);
+        }
+        let mut data = ConversationDataBuilder::new("conv-1")
+            .title(title)
+            .model_id("gpt-4o")
+            .updated_at(1700000100)
+            .user("Summarize the report")
+            .assistant("Here is the summary.")
+            .user("Shorter please")
+            .assistant("Done.")
+            .build();
+        data.token_usage = serde_json::to_string(&usage).unwrap();
+        data.message_timestamps = serde_json::to_string(timestamps).unwrap();
+        data
+    }
+
+    #[test]
+    fn pairs_usage_with_responses_and_measures_latency() {
+        let data = conversation(
+            "Report",
+            &[Some(1700000000), Some(1700000007), Some(1700000050), None],
+        );
+        let rows = usage_rows(&data, None);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].turn, 1);
+        assert_eq!(rows[0].timestamp, Some(1700000007));
+        assert_eq!(rows[0].latency_seconds, Some(7));
+        assert_eq!(rows[0].api_calls, 2);
+        assert_eq!(rows[1].input_tokens, 1600);
+        assert_eq!(rows[1].latency_seconds, None);
+
+        // Only the second response (falling back to updated_at) is recent enough
+        let recent = usage_rows(&data, Some(1700000050));
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].turn, 2);
+    }
+
+    #[test]
+    fn writes_quoted_csv() {
+        let data = conversation("=SUM(A1), \"draft\"", &[Some(1700000000), Some(1700000007)]);
+        let csv = usage_csv(&usage_rows(&data, None));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], HEADER);
+        assert_eq!(
+            lines[1],
+            "2023-11-14T22:13:27+00:00,conv-1,\"'=SUM(A1), \"\"draft\"\"\",1,gpt-4o,\
+             1200,300,2,7,0.004200"
+        );
+        assert_eq!(
+            lines[2],
+            ",conv-1,\"'=SUM(A1), \"\"draft\"\"\",2,gpt-4o,1600,40,2,,"
+        );
+    }
+
+    #[test]
+    fn this_month_starts_on_the_first() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 18, 15, 30, 0).unwrap();
+        let first = Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            UsageExportRange::ThisMonth.since(&now),
+            Some(first.timestamp())
+        );
+        assert_eq!(
+            UsageExportRange::LastDays(30).since(&now),
+            Some(now.timestamp() - 30 * 86_400)
+        );
+        assert_eq!(UsageExportRange::All.since(&now), None);
+    }
+}
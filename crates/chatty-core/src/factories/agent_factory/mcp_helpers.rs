@@ -1,5 +1,10 @@
 use std::collections::HashSet;
 
+use rig_core::tool::ToolDyn;
+
+use crate::services::mcp_service::McpToolSet;
+use crate::tools::McpServerTool;
+
 /// MCP listing tool, always enabled when MCP servers are configured.
 pub(super) struct McpTools {
     pub list: Option<crate::tools::ListMcpTool>,
@@ -22,13 +27,13 @@ impl McpTools {
 /// LLM providers require unique tool names, so this function
 /// deduplicates by keeping the first occurrence of each tool name and logging skipped duplicates.
 pub(super) fn deduplicate_mcp_tools(
-    mcp_tools: Vec<(String, Vec<rmcp::model::Tool>, rmcp::service::ServerSink)>,
+    mcp_tools: McpToolSet,
     reserved_tool_names: &HashSet<String>,
-) -> Vec<(String, Vec<rmcp::model::Tool>, rmcp::service::ServerSink)> {
+) -> McpToolSet {
     let mut seen_tool_names = reserved_tool_names.clone();
     let mut result = Vec::new();
 
-    for (server_name, tools, sink) in mcp_tools {
+    for (server_name, tools, service) in mcp_tools {
         let mut deduped_tools = Vec::new();
         let mut skipped_count = 0;
         let total_tools = tools.len();
@@ -57,7 +62,7 @@ pub(super) fn deduplicate_mcp_tools(
         }
 
         if !deduped_tools.is_empty() {
-            result.push((server_name, deduped_tools, sink));
+            result.push((server_name, deduped_tools, service));
         }
     }
 
@@ -130,13 +135,11 @@ fn strip_format_from_schema(schema: &mut serde_json::Map<String, serde_json::Val
 /// strict-mode function calling rejects.
 ///
 /// TODO(#127): Remove once rig-core's `sanitize_schema()` strips `"format"` (not fixed as of v0.32).
-pub(super) fn sanitize_mcp_tools_for_openai(
-    mcp_tools: Option<Vec<(String, Vec<rmcp::model::Tool>, rmcp::service::ServerSink)>>,
-) -> Option<Vec<(String, Vec<rmcp::model::Tool>, rmcp::service::ServerSink)>> {
+pub(super) fn sanitize_mcp_tools_for_openai(mcp_tools: Option<McpToolSet>) -> Option<McpToolSet> {
     mcp_tools.map(|servers| {
         servers
             .into_iter()
-            .map(|(name, tools, sink)| {
+            .map(|(name, tools, service)| {
                 let sanitized_tools = tools
                     .into_iter()
                     .map(|mut tool| {
@@ -146,40 +149,35 @@ pub(super) fn sanitize_mcp_tools_for_openai(
                         tool
                     })
                     .collect();
-                (name, sanitized_tools, sink)
+                (name, sanitized_tools, service)
             })
             .collect()
     })
 }
 
-macro_rules! build_with_mcp_tools {
-    ($builder:expr, $mcp_tools:expr, $reserved_tool_names:expr) => {{
-        match $mcp_tools {
-            Some(tools_list) => {
-                let deduped = $crate::factories::agent_factory::mcp_helpers::deduplicate_mcp_tools(
-                    tools_list,
-                    $reserved_tool_names,
-                );
-                let mut iter = deduped
-                    .into_iter()
-                    .filter(|(_name, t, _sink)| !t.is_empty());
-                if let Some((_first_name, first_tools, first_sink)) = iter.next() {
-                    let mut b = $builder.rmcp_tools(first_tools, first_sink);
-                    for (_name, tools, sink) in iter {
-                        b = b.rmcp_tools(tools, sink);
-                    }
-                    b.build()
-                } else {
-                    $builder.build()
-                }
-            }
-            None => $builder.build(),
-        }
-    }};
+/// Deduplicate MCP tools and wrap each as a [`McpServerTool`], so calls go
+/// through `McpService` and reconnect servers stopped for being idle.
+pub(super) fn mcp_tool_dyns(
+    mcp_tools: Option<McpToolSet>,
+    reserved_tool_names: &HashSet<String>,
+) -> Vec<Box<dyn ToolDyn>> {
+    let Some(mcp_tools) = mcp_tools else {
+        return Vec::new();
+    };
+    deduplicate_mcp_tools(mcp_tools, reserved_tool_names)
+        .into_iter()
+        .flat_map(|(server_name, tools, service)| {
+            tools.into_iter().map(move |tool| {
+                Box::new(McpServerTool::new(
+                    service.clone(),
+                    server_name.clone(),
+                    tool,
+                )) as Box<dyn ToolDyn>
+            })
+        })
+        .collect()
 }
 
-pub(super) use build_with_mcp_tools;
-
 #[cfg(test)]
 mod tests {
     use super::*;
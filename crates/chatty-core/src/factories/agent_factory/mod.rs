@@ -6,29 +6,39 @@ mod tool_registry;
 
 use anyhow::Result;
 use rig_core::agent::Agent;
-use rig_core::completion::Prompt;
+use rig_core::completion::{Prompt, Usage};
 
 use crate::sandbox::{SandboxConfig, SandboxManager};
+use crate::services::citation_service::CitationService;
+use crate::services::docker_service::{DockerResources, DockerService};
 use crate::services::filesystem_service::FileSystemService;
 use crate::services::git_service::GitService;
+use crate::services::kubernetes_service::{KubernetesScope, KubernetesService};
 use crate::services::memory_service::MemoryService;
+use crate::services::notes_service::NotesService;
+use crate::services::prompt_template::{PromptVariables, render_prompt_template};
 use crate::services::search_service::CodeSearchService;
 use crate::services::shell_service::ShellSession;
 use crate::services::skill_service::SkillService;
+use crate::services::ssh_service::SshService;
+use crate::services::workspace_rules::WorkspaceRules;
 use crate::settings::models::models_store::ModelConfig;
 use crate::settings::models::providers_store::ProviderConfig;
 #[cfg(feature = "math-render")]
 use crate::tools::CompileTypstTool;
 use crate::tools::{
-    AddAttachmentTool, ApplyDiffTool, BrowserUseTool, CreateChartTool, CreateDirectoryTool,
-    DaytonaTool, DeleteFileTool, DocRetrieverTool, ExecuteCodeTool, FetchTool, FinalAnswerTool,
-    FindDefinitionTool, FindFilesTool, GitAddTool, GitCommitTool, GitCreateBranchTool, GitDiffTool,
-    GitLogTool, GitStatusTool, GitSwitchBranchTool, GlobSearchTool, InvokeAgentTool,
-    ListAgentsTool, ListDirectoryTool, ListMcpTool, ListToolsTool, LocalModuleAgentSummary,
-    MoveFileTool, PendingArtifacts, PublishModuleTool, ReadBinaryTool, ReadFileTool, ReadSkillTool,
-    RememberTool, SaveSkillTool, SearchCodeTool, SearchMemoryTool, SearchWebTool, ShellCdTool,
-    ShellExecuteTool, ShellSetEnvTool, ShellStatusTool, SubAgentTool, UpdateTodoTool,
-    VerifyCompletionTool, WriteFileTool, WriteTodosTool,
+    AddAttachmentTool, ApplyDiffTool, AskUserTool, BrowserUseTool, CitationTool,
+    ConversationVariables, CreateChartTool, CreateDirectoryTool, CreatePlanTool, CustomHttpTool,
+    DaytonaTool, DeleteFileTool, DocRetrieverTool, DockerTool, ExecuteCodeTool, FetchTool,
+    FinalAnswerTool, FindDefinitionTool, FindFilesTool, GetVariableTool, GitAddTool, GitCommitTool,
+    GitCreateBranchTool, GitDiffTool, GitLogTool, GitStatusTool, GitSwitchBranchTool,
+    GlobSearchTool, GraphQlTool, InvokeAgentTool, K8sActionTool, K8sDescribeTool, K8sEventsTool,
+    K8sGetTool, K8sLogsTool, ListAgentsTool, ListDirectoryTool, ListMcpTool, ListToolsTool,
+    LocalModuleAgentSummary, MoveFileTool, NotesAppendTool, NotesReadTool, NotesSearchTool,
+    PendingArtifacts, PublishModuleTool, ReadBinaryTool, ReadFileTool, ReadSkillTool, RememberTool,
+    SaveSkillTool, SearchCodeTool, SearchMemoryTool, SearchWebTool, SetVariableTool, SharedPlan,
+    ShellCdTool, ShellExecuteTool, ShellSetEnvTool, ShellStatusTool, SshExecuteTool, SubAgentTool,
+    UpdatePlanTool, UpdateTodoTool, VerifyCompletionTool, WriteFileTool, WriteTodosTool,
 };
 #[cfg(feature = "duckdb")]
 use crate::tools::{DescribeDataTool, FileStructureTool, ProfileDataTool, QueryDataTool};
@@ -46,6 +56,7 @@ use preamble_builder::build_preamble;
 use tool_collector::*;
 use tool_registry::active_native_tool_names;
 
+pub(crate) use provider_builder::{normalize_azure_endpoint, resolve_azure_auth};
 pub use tool_registry::ToolAvailability;
 
 fn doc_retriever_enabled() -> bool {
@@ -58,8 +69,10 @@ fn doc_retriever_enabled() -> bool {
 ///
 /// Groups the many optional services and settings needed by
 /// `AgentClient::from_model_config_with_tools()` and `Conversation::new/from_data()`.
+/// `Default` builds an agent without any tools.
+#[derive(Default)]
 pub struct AgentBuildContext {
-    pub mcp_tools: Option<Vec<(String, Vec<rmcp::model::Tool>, rmcp::service::ServerSink)>>,
+    pub mcp_tools: Option<crate::services::mcp_service::McpToolSet>,
     pub exec_settings: Option<crate::settings::models::ExecutionSettingsModel>,
     pub pending_approvals: Option<crate::models::execution_approval_store::PendingApprovals>,
     pub pending_write_approvals: Option<crate::models::write_approval_store::PendingWriteApprovals>,
@@ -76,6 +89,18 @@ pub struct AgentBuildContext {
     pub gateway_port: Option<u16>,
     pub remote_agents: Vec<crate::settings::models::a2a_store::A2aAgentConfig>,
     pub available_model_ids: Vec<String>,
+    /// Conversation variable scratchpad; enables `get_variable`/`set_variable` when set.
+    pub conversation_variables: Option<ConversationVariables>,
+    /// Conversation plan; enables `create_plan`/`update_plan` when set and
+    /// planner mode is on in the execution settings.
+    pub execution_plan: Option<SharedPlan>,
+    /// Conversation's confidential flag, consulted by network guardrails at call time.
+    pub confidential: Option<crate::services::ConfidentialFlag>,
+    /// Compose stacks started in this conversation, cleaned up on deletion
+    /// according to `docker_cleanup`.
+    pub docker_resources: Option<DockerResources>,
+    /// Tools (native or MCP, by name) switched off for this conversation.
+    pub disabled_tools: std::collections::BTreeSet<String>,
 }
 
 /// Enum-based agent wrapper for multi-provider support
@@ -84,14 +109,17 @@ pub enum AgentClient {
     OpenRouter {
         agent: Agent<rig_core::providers::openrouter::CompletionModel>,
         task_controller: crate::services::AgentTaskController,
+        tool_cancellation: crate::services::ToolCancellation,
     },
     Ollama {
         agent: Agent<rig_core::providers::ollama::CompletionModel>,
         task_controller: crate::services::AgentTaskController,
+        tool_cancellation: crate::services::ToolCancellation,
     },
     AzureOpenAI {
         agent: Agent<rig_core::providers::azure::CompletionModel>,
         task_controller: crate::services::AgentTaskController,
+        tool_cancellation: crate::services::ToolCancellation,
     },
 }
 
@@ -109,6 +137,21 @@ impl AgentClient {
         }
     }
 
+    /// Like [`AgentClient::prompt`], but also returns the provider-reported
+    /// token usage.
+    pub async fn prompt_with_usage(&self, prompt: &str) -> Result<(String, Usage)> {
+        let response = match self {
+            AgentClient::OpenRouter { agent, .. } => {
+                agent.prompt(prompt).extended_details().await?
+            }
+            AgentClient::Ollama { agent, .. } => agent.prompt(prompt).extended_details().await?,
+            AgentClient::AzureOpenAI { agent, .. } => {
+                agent.prompt(prompt).extended_details().await?
+            }
+        };
+        Ok((response.output, response.usage))
+    }
+
     pub fn task_controller(&self) -> crate::services::AgentTaskController {
         match self {
             AgentClient::OpenRouter {
@@ -123,6 +166,22 @@ impl AgentClient {
         }
     }
 
+    /// Handle the long-running tools of this agent watch for the Stop button.
+    /// Install each stream's cancel flag into it before streaming.
+    pub fn tool_cancellation(&self) -> crate::services::ToolCancellation {
+        match self {
+            AgentClient::OpenRouter {
+                tool_cancellation, ..
+            }
+            | AgentClient::Ollama {
+                tool_cancellation, ..
+            }
+            | AgentClient::AzureOpenAI {
+                tool_cancellation, ..
+            } => tool_cancellation.clone(),
+        }
+    }
+
     /// Create AgentClient from ModelConfig, ProviderConfig and build context
     pub async fn from_model_config_with_tools(
         model_config: &ModelConfig,
@@ -152,10 +211,31 @@ impl AgentClient {
             gateway_port,
             remote_agents,
             available_model_ids,
+            conversation_variables,
+            execution_plan,
+            confidential,
+            docker_resources,
+            disabled_tools,
         } = ctx;
 
+        // Tool groups blocked for the user's managed role never reach the registry
+        let exec_settings = exec_settings.map(|mut settings| {
+            crate::services::managed_config_service::restrict_tools(&mut settings);
+            settings
+        });
+
         // Extract secret key names before user_secrets is moved into ShellSession.
         let secret_key_names: Vec<String> = user_secrets.iter().map(|(k, _)| k.clone()).collect();
+        // Custom HTTP and GraphQL tools resolve `{{secret:KEY}}` header references
+        // from the same secrets.
+        let custom_tool_secrets = if exec_settings
+            .as_ref()
+            .is_some_and(|s| !s.custom_http_tools.is_empty() || !s.graphql_endpoints.is_empty())
+        {
+            user_secrets.clone()
+        } else {
+            Vec::new()
+        };
 
         // Ensure shell session exists when execution is enabled (factory-level fallback).
         let shell_session = if shell_session.is_some() {
@@ -187,6 +267,10 @@ impl AgentClient {
 
         let shell_session_out = shell_session.clone();
 
+        // Lets long-running tools (shell, fetch, code execution) stop when the
+        // user presses Stop; frontends install each stream's cancel flag.
+        let tool_cancellation = crate::services::ToolCancellation::new();
+
         // Create shell session tools if execution is enabled and a session is provided
         let shell_tools: Option<ShellTools> =
             if let (Some(session), Some(settings), Some(approvals)) =
@@ -195,7 +279,8 @@ impl AgentClient {
                 if settings.enabled {
                     tracing::info!("Shell session tools enabled");
                     Some((
-                        ShellExecuteTool::new(session.clone(), settings.clone(), approvals.clone()),
+                        ShellExecuteTool::new(session.clone(), settings.clone(), approvals.clone())
+                            .with_cancellation(tool_cancellation.clone()),
                         ShellSetEnvTool::new(session.clone(), settings.clone()),
                         ShellCdTool::new(session.clone(), settings.clone()),
                         ShellStatusTool::new(session.clone()),
@@ -233,6 +318,59 @@ impl AgentClient {
             None
         };
 
+        // Start the kubectl availability check early, like the git service above.
+        let kubernetes_service_handle = exec_settings
+            .as_ref()
+            .filter(|s| s.kubernetes_enabled)
+            .map(|s| {
+                let scope = KubernetesScope {
+                    contexts: s.kubernetes_contexts.clone(),
+                    namespaces: s.kubernetes_namespaces.clone(),
+                };
+                let timeout = std::time::Duration::from_secs(s.timeout_seconds.max(1) as u64);
+                let max_output_bytes = s.max_output_bytes;
+                tokio::spawn(async move {
+                    KubernetesService::new(scope, timeout, max_output_bytes).await
+                })
+            });
+
+        // Connect to the Docker daemon early for the docker tool.
+        let docker_service_handle = exec_settings
+            .as_ref()
+            .filter(|s| s.docker_tools_enabled)
+            .map(|s| {
+                let docker_host = s.docker_host.clone();
+                let workspace_dir = s.workspace_dir.as_ref().map(std::path::PathBuf::from);
+                let timeout = std::time::Duration::from_secs(s.timeout_seconds.max(1) as u64);
+                let max_output_bytes = s.max_output_bytes;
+                tokio::spawn(async move {
+                    DockerService::new(docker_host, workspace_dir, timeout, max_output_bytes).await
+                })
+            });
+
+        // Check for the ssh client early; only enabled, valid host profiles are kept.
+        let ssh_service_handle = exec_settings
+            .as_ref()
+            .filter(|s| s.ssh_enabled && !s.ssh_hosts.is_empty())
+            .map(|s| {
+                let hosts = s.ssh_hosts.clone();
+                let timeout = std::time::Duration::from_secs(s.timeout_seconds.max(1) as u64);
+                let max_output_bytes = s.max_output_bytes;
+                tokio::spawn(async move { SshService::new(hosts, timeout, max_output_bytes).await })
+            });
+
+        // Load the BibTeX file or probe the Zotero local API early for the citations tool.
+        let citation_service_handle = exec_settings
+            .as_ref()
+            .filter(|s| s.citations_enabled)
+            .and_then(|s| {
+                CitationService::source_from_settings(
+                    s.bibtex_path.as_deref(),
+                    s.zotero_api_url.as_deref(),
+                )
+            })
+            .map(|source| tokio::spawn(async move { CitationService::new(source).await }));
+
         // Create filesystem tools if a workspace directory is configured
         let mut add_attachment_tool: Option<AddAttachmentTool> = None;
         #[cfg(feature = "pdf")]
@@ -425,7 +563,7 @@ impl AgentClient {
                 );
                 tools_list
                     .iter()
-                    .flat_map(|(server_name, tools, _sink)| {
+                    .flat_map(|(server_name, tools, _service)| {
                         tracing::info!(
                             server = %server_name,
                             tool_count = tools.len(),
@@ -473,7 +611,7 @@ impl AgentClient {
                 .and_then(|s| s.workspace_dir.as_ref())
                 .map(std::path::PathBuf::from);
             tracing::info!(?workspace, "Fetch tool enabled");
-            Some(FetchTool::new(workspace))
+            Some(FetchTool::new(workspace).with_cancellation(tool_cancellation.clone()))
         } else {
             tracing::info!("Fetch tool disabled by execution settings");
             None
@@ -631,6 +769,137 @@ impl AgentClient {
             None
         };
 
+        // Kubernetes tools: read-only by default; k8s_action only when mutations are
+        // allowed and an approval UI is available (every action asks the user), so
+        // it is not offered to sub-agents.
+        let (kubernetes_tools, kubernetes_action_tool): (
+            Option<KubernetesTools>,
+            Option<K8sActionTool>,
+        ) = match kubernetes_service_handle {
+            Some(handle) => match handle.await {
+                Ok(Ok(service)) => {
+                    let service = std::sync::Arc::new(service);
+                    let allow_mutations = exec_settings
+                        .as_ref()
+                        .is_some_and(|s| s.kubernetes_allow_mutations);
+                    let action_tool = pending_approvals
+                        .clone()
+                        .filter(|_| allow_mutations && allow_sub_agent)
+                        .map(|approvals| K8sActionTool::new(service.clone(), approvals));
+                    tracing::info!(actions = action_tool.is_some(), "Kubernetes tools enabled");
+                    (
+                        Some((
+                            K8sGetTool::new(service.clone()),
+                            K8sDescribeTool::new(service.clone()),
+                            K8sLogsTool::new(service.clone()),
+                            K8sEventsTool::new(service),
+                        )),
+                        action_tool,
+                    )
+                }
+                Ok(Err(e)) => {
+                    tracing::warn!(error = ?e, "Failed to initialize Kubernetes tools");
+                    (None, None)
+                }
+                Err(e) => {
+                    tracing::warn!(error = ?e, "Kubernetes service init task panicked");
+                    (None, None)
+                }
+            },
+            None => (None, None),
+        };
+
+        // Docker service; the tool itself is built once the invoke_agent progress
+        // slot exists, since it streams output through it.
+        let docker_service: Option<std::sync::Arc<DockerService>> = match docker_service_handle {
+            Some(handle) => match handle.await {
+                Ok(Ok(service)) => Some(std::sync::Arc::new(service)),
+                Ok(Err(e)) => {
+                    tracing::warn!(error = ?e, "Failed to initialize Docker tool");
+                    None
+                }
+                Err(e) => {
+                    tracing::warn!(error = ?e, "Docker service init task panicked");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        // SSH service; like k8s_action, ssh_execute asks for every command, so it
+        // needs the approval UI and is not offered to sub-agents.
+        let ssh_service: Option<std::sync::Arc<SshService>> = match ssh_service_handle {
+            Some(handle) if allow_sub_agent && pending_approvals.is_some() => match handle.await {
+                Ok(Ok(service)) => Some(std::sync::Arc::new(service)),
+                Ok(Err(e)) => {
+                    tracing::warn!(error = ?e, "Failed to initialize SSH tool");
+                    None
+                }
+                Err(e) => {
+                    tracing::warn!(error = ?e, "SSH service init task panicked");
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        let citation_tool: Option<CitationTool> = match citation_service_handle {
+            Some(handle) => match handle.await {
+                Ok(Ok(service)) => {
+                    tracing::info!("Citations tool enabled");
+                    Some(CitationTool::new(
+                        std::sync::Arc::new(service),
+                        exec_settings
+                            .as_ref()
+                            .map(|s| s.citation_style)
+                            .unwrap_or_default(),
+                    ))
+                }
+                Ok(Err(e)) => {
+                    tracing::warn!(error = ?e, "Failed to initialize citations tool");
+                    None
+                }
+                Err(e) => {
+                    tracing::warn!(error = ?e, "Citation service init task panicked");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        // Notes tools for the configured Markdown vault.
+        let notes_tools: Option<NotesTools> = match exec_settings
+            .as_ref()
+            .filter(|s| s.notes_enabled)
+            .and_then(|s| s.notes_vault_dir.as_ref())
+        {
+            Some(vault_dir) => match NotesService::new(vault_dir) {
+                Ok(service) => {
+                    let service = std::sync::Arc::new(service);
+                    let approval_mode = exec_settings
+                        .as_ref()
+                        .map(|s| s.approval_mode.clone())
+                        .unwrap_or_default();
+                    let approvals = pending_approvals.clone().unwrap_or_else(|| {
+                        std::sync::Arc::new(parking_lot::Mutex::new(
+                            std::collections::HashMap::new(),
+                        ))
+                    });
+                    tracing::info!(vault = %vault_dir, "Notes tools enabled");
+                    Some((
+                        NotesSearchTool::new(service.clone()),
+                        NotesReadTool::new(service.clone()),
+                        NotesAppendTool::new(service, approval_mode, approvals),
+                    ))
+                }
+                Err(e) => {
+                    tracing::warn!(error = ?e, "Failed to initialize notes tools");
+                    None
+                }
+            },
+            None => None,
+        };
+
         // Memory tools
         let (remember_tool, save_skill_tool, search_memory_tool): (
             Option<RememberTool>,
@@ -657,6 +926,10 @@ impl AgentClient {
                     embedding_service.clone(),
                     skill_service.clone(),
                     workspace_skills_dir,
+                    exec_settings
+                        .as_ref()
+                        .map(|s| s.hybrid_keyword_weight)
+                        .unwrap_or(0.5),
                 )),
             )
         } else {
@@ -664,6 +937,28 @@ impl AgentClient {
             (None, None, None)
         };
 
+        // Conversation variable tools (only when the caller provides a scratchpad)
+        let variable_tools: Option<(GetVariableTool, SetVariableTool)> = conversation_variables
+            .map(|vars| {
+                (
+                    GetVariableTool::new(vars.clone()),
+                    SetVariableTool::new(vars),
+                )
+            });
+
+        // Planner-mode tools (opt-in setting, and only when the caller provides a plan handle)
+        let planner_mode = exec_settings.as_ref().is_some_and(|s| s.planner_mode);
+        let plan_tools: Option<(CreatePlanTool, UpdatePlanTool)> = execution_plan
+            .filter(|_| planner_mode)
+            .map(|plan| (CreatePlanTool::new(plan.clone()), UpdatePlanTool::new(plan)));
+
+        // ask_user tool — needs a UI to answer through the approval channel, so it is
+        // not offered to sub-agents
+        let ask_user_tool: Option<AskUserTool> = pending_approvals
+            .clone()
+            .filter(|_| allow_sub_agent)
+            .map(AskUserTool::new);
+
         // read_skill tool — always available
         let read_skill_tool = ReadSkillTool::new(
             exec_settings
@@ -722,7 +1017,7 @@ impl AgentClient {
                 ..SandboxConfig::default()
             };
             let manager = std::sync::Arc::new(SandboxManager::new(sandbox_config));
-            Some(ExecuteCodeTool::new(manager))
+            Some(ExecuteCodeTool::new(manager).with_cancellation(tool_cancellation.clone()))
         } else {
             tracing::info!("Code execution tool disabled by execution settings");
             None
@@ -764,6 +1059,12 @@ impl AgentClient {
             fetch: fetch_tool.is_some(),
             shell: shell_tools.is_some(),
             git: git_tools.is_some(),
+            kubernetes: kubernetes_tools.is_some(),
+            kubernetes_actions: kubernetes_action_tool.is_some(),
+            docker: docker_service.is_some(),
+            ssh: ssh_service.is_some(),
+            notes: notes_tools.is_some(),
+            citations: citation_tool.is_some(),
             search: search_tools.is_some(),
             add_attachment: add_attachment_tool.is_some(),
             excel_read: {
@@ -883,9 +1184,48 @@ impl AgentClient {
             browser_use: browser_use_tool.is_some(),
             daytona: daytona_tool.is_some(),
             publish_module: false, // set below after publish_module_tool is created
+            conversation_variables: variable_tools.is_some(),
+            planner: plan_tools.is_some(),
+            ask_user: ask_user_tool.is_some(),
         };
 
-        let native_tool_names = active_native_tool_names(&tool_availability);
+        let mut native_tool_names = active_native_tool_names(&tool_availability);
+
+        // User-defined REST tools. Their names are reserved like native tool
+        // names so MCP tools cannot shadow them.
+        let custom_http_tools = match exec_settings.as_ref() {
+            Some(settings) if !settings.custom_http_tools.is_empty() => {
+                CustomHttpTool::from_configs(
+                    &settings.custom_http_tools,
+                    &native_tool_names,
+                    &custom_tool_secrets,
+                    pending_approvals.clone().unwrap_or_default(),
+                    settings.approval_mode.clone(),
+                )
+            }
+            _ => Vec::new(),
+        };
+        let mut user_network_tool_names: Vec<String> = custom_http_tools
+            .iter()
+            .map(rig_core::tool::ToolDyn::name)
+            .collect();
+        native_tool_names.extend(user_network_tool_names.iter().cloned());
+
+        // User-configured GraphQL endpoints, reserved the same way.
+        let graphql_tools = match exec_settings.as_ref() {
+            Some(settings) if !settings.graphql_endpoints.is_empty() => GraphQlTool::from_configs(
+                &settings.graphql_endpoints,
+                &native_tool_names,
+                &custom_tool_secrets,
+            ),
+            _ => Vec::new(),
+        };
+        let graphql_tool_names: Vec<String> = graphql_tools
+            .iter()
+            .map(rig_core::tool::ToolDyn::name)
+            .collect();
+        native_tool_names.extend(graphql_tool_names.iter().cloned());
+        user_network_tool_names.extend(graphql_tool_names);
         let mcp_tool_info = filter_mcp_tool_info(mcp_tool_info, &native_tool_names);
 
         // Create list_tools tool (always available)
@@ -905,18 +1245,42 @@ impl AgentClient {
         let invoke_agent_tool = InvokeAgentTool::new(remote_agents, module_agents, gateway_port);
         let invoke_agent_progress_slot = invoke_agent_tool.progress_slot();
 
+        // Docker tool streams logs/exec/compose output into the same progress trace.
+        let docker_tool: Option<DockerTool> = docker_service.map(|service| {
+            tracing::info!("Docker tool enabled");
+            DockerTool::new(
+                service,
+                pending_approvals.clone().unwrap_or_else(|| {
+                    std::sync::Arc::new(parking_lot::Mutex::new(std::collections::HashMap::new()))
+                }),
+                exec_settings
+                    .as_ref()
+                    .map(|s| s.approval_mode.clone())
+                    .unwrap_or_default(),
+                docker_resources.unwrap_or_default(),
+                invoke_agent_progress_slot.clone(),
+            )
+        });
+
+        let ssh_execute_tool: Option<SshExecuteTool> = ssh_service
+            .zip(pending_approvals.clone())
+            .map(|(service, approvals)| {
+                tracing::info!(hosts = service.hosts().len(), "SSH tool enabled");
+                SshExecuteTool::new(service, approvals, invoke_agent_progress_slot.clone())
+            });
+
         // Publish module tool (if an MCP server exposes `publish_module`)
         let publish_module_tool: Option<PublishModuleTool> = mcp_tools.as_ref().and_then(|servers| {
-            for (_name, tools, sink) in servers {
+            for (name, tools, service) in servers {
                 if tools.iter().any(|t| &*t.name == "publish_module") {
                     let ws = exec_settings
                         .as_ref()
                         .and_then(|s| s.workspace_dir.clone());
                     tracing::info!(
-                        server = %_name,
+                        server = %name,
                         "Creating publish_wasm_module composite tool (backed by MCP publish_module)"
                     );
-                    return Some(PublishModuleTool::new(sink.clone(), ws));
+                    return Some(PublishModuleTool::new(service.clone(), name.clone(), ws));
                 }
             }
             None
@@ -928,9 +1292,14 @@ impl AgentClient {
             ..tool_availability
         };
 
-        // Build the augmented preamble
-        let preamble = build_preamble(
-            &model_config.preamble,
+        // Build the augmented preamble, resolving {{variables}} in the model's prompt
+        let prompt_variables = PromptVariables::current(
+            exec_settings
+                .as_ref()
+                .and_then(|s| s.workspace_dir.as_deref()),
+        );
+        let mut preamble = build_preamble(
+            &render_prompt_template(&model_config.preamble, &prompt_variables),
             &model_config.provider_type,
             &tool_availability,
             &search_settings,
@@ -939,8 +1308,18 @@ impl AgentClient {
             &secret_key_names,
         );
 
+        // Standing instructions from the workspace's .chatty/rules.md or AGENTS.md
+        if let Some(rules) = exec_settings
+            .as_ref()
+            .and_then(|s| s.workspace_dir.as_deref())
+            .and_then(|dir| WorkspaceRules::load(std::path::Path::new(dir)))
+        {
+            tracing::info!(path = %rules.path.display(), truncated = rules.truncated, "Workspace rules loaded");
+            preamble.push_str(&rules.preamble_section());
+        }
+
         // Build native tools once (all providers use the same set)
-        let tool_vec = native_tools!(
+        let mut tool_vec = native_tools!(
             list_tools: list_tools,
             write_todos_tool: write_todos_tool,
             update_todo_tool: update_todo_tool,
@@ -956,6 +1335,12 @@ impl AgentClient {
             fetch_tool: fetch_tool,
             shell_tools: shell_tools,
             git_tools: git_tools,
+            kubernetes_tools: kubernetes_tools,
+            kubernetes_action_tool: kubernetes_action_tool,
+            docker_tool: docker_tool,
+            ssh_execute_tool: ssh_execute_tool,
+            notes_tools: notes_tools,
+            citation_tool: citation_tool,
             search_tools: search_tools,
             excel_read: excel_read_tool,
             excel_write: excel_write_tools,
@@ -968,6 +1353,9 @@ impl AgentClient {
             typst_tool: typst_tool,
             execute_code_tool: execute_code_tool,
             remember_tool: remember_tool,
+            variable_tools: variable_tools,
+            plan_tools: plan_tools,
+            ask_user_tool: ask_user_tool,
             save_skill_tool: save_skill_tool,
             search_memory_tool: search_memory_tool,
             read_skill_tool: read_skill_tool,
@@ -980,6 +1368,66 @@ impl AgentClient {
             publish_module_tool: publish_module_tool,
         )
         .into_tool_vec();
+        tool_vec.extend(
+            custom_http_tools
+                .into_iter()
+                .map(|tool| Box::new(tool) as Box<dyn rig_core::tool::ToolDyn>),
+        );
+        tool_vec.extend(
+            graphql_tools
+                .into_iter()
+                .map(|tool| Box::new(tool) as Box<dyn rig_core::tool::ToolDyn>),
+        );
+
+        // Tools switched off for this conversation never reach the registry
+        let mcp_tools = if disabled_tools.is_empty() {
+            mcp_tools
+        } else {
+            tool_vec.retain(|tool| !disabled_tools.contains(&tool.name()));
+            mcp_tools.map(|servers| {
+                servers
+                    .into_iter()
+                    .map(|(server, mut tools, service)| {
+                        tools.retain(|tool| !disabled_tools.contains(tool.name.as_ref()));
+                        (server, tools, service)
+                    })
+                    .collect()
+            })
+        };
+
+        // Recorded for the tool reference panel, as the model will see them
+        crate::services::tool_reference::record(
+            crate::services::tool_reference::describe_tools(
+                &tool_vec,
+                &user_network_tool_names,
+                mcp_tools.as_ref(),
+            )
+            .await,
+        );
+
+        // Guardrails run before each tool, native or MCP (and so before its
+        // approval prompt). Managed (team) rules are enforced on top of the
+        // user's own.
+        let mut guardrail_rules =
+            crate::services::managed_config_service::enforced_guardrail_rules();
+        if let Some(settings) = exec_settings.as_ref() {
+            guardrail_rules.extend(settings.guardrail_rules.iter().cloned());
+        }
+        let tool_guard = crate::services::ToolGuard::new(
+            &guardrail_rules,
+            user_network_tool_names,
+            exec_settings
+                .as_ref()
+                .and_then(|settings| settings.workspace_dir.as_ref())
+                .map(std::path::PathBuf::from),
+            exec_settings
+                .as_ref()
+                .filter(|settings| settings.notes_enabled)
+                .and_then(|settings| settings.notes_vault_dir.as_ref())
+                .map(std::path::PathBuf::from),
+            confidential,
+        );
+        let tool_vec = tool_guard.wrap(tool_vec);
 
         let agent = provider_builder::build_provider_agent(
             model_config,
@@ -988,7 +1436,9 @@ impl AgentClient {
             tool_vec,
             mcp_tools,
             &native_tool_names,
+            &tool_guard,
             agent_task_controller,
+            tool_cancellation,
         )
         .await?;
 
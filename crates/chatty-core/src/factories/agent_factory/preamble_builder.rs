@@ -77,6 +77,47 @@ pub(super) fn build_preamble(
                 .to_string(),
         );
     }
+    if tools.kubernetes {
+        let mut k8s = String::from(
+            "- **k8s_get / k8s_describe / k8s_logs / k8s_events** (read-only cluster inspection; \
+             start with events and describe when debugging a failing workload)",
+        );
+        if tools.kubernetes_actions {
+            k8s.push_str(
+                "\n- **k8s_action** (delete, rollout restart, scale, cordon/uncordon; \
+                 every call asks the user — only propose after inspecting)",
+            );
+        }
+        tool_sections.push(k8s);
+    }
+    if tools.docker {
+        tool_sections.push(
+            "- **docker** (list containers/images, logs; exec and compose up/down in the \
+             workspace ask for approval)"
+                .to_string(),
+        );
+    }
+    if tools.ssh {
+        tool_sections.push(
+            "- **ssh_execute** (run a command on a configured remote host; every command asks \
+             the user — start with read-only diagnostics)"
+                .to_string(),
+        );
+    }
+    if tools.notes {
+        tool_sections.push(
+            "- **notes_search**, **notes_read**, **notes_append** (the user's Markdown notes \
+             vault; notes_read accepts [[wiki link]] names, appends ask for approval)"
+                .to_string(),
+        );
+    }
+    if tools.citations {
+        tool_sections.push(
+            "- **citations** (search the user's BibTeX/Zotero library and format references; \
+             cite with the returned marker, e.g. [@smith2020], and never invent citekeys)"
+                .to_string(),
+        );
+    }
     if tools.add_attachment {
         tool_sections.push("- **add_attachment** (display image or PDF inline)".to_string());
     }
@@ -160,6 +201,24 @@ immediately switch to shell_execute: write a `/tmp/solve.py` script and run it t
                 .to_string(),
         );
     }
+    if tools.conversation_variables {
+        tool_sections.push(
+            "- **get_variable** / **set_variable** (per-conversation scratchpad that persists across turns)"
+                .to_string(),
+        );
+    }
+    if tools.planner {
+        tool_sections.push(
+            "- **create_plan** / **update_plan** (planner mode: visible plan checklist tracked across turns)"
+                .to_string(),
+        );
+    }
+    if tools.ask_user {
+        tool_sections.push(
+            "- **ask_user** (pause and ask the user a clarifying question; use sparingly)"
+                .to_string(),
+        );
+    }
     if tools.sub_agent {
         tool_sections.push(
             "- **sub_agent** (delegate tasks to an independent sub-agent with the same tools)"
@@ -269,6 +328,20 @@ immediately switch to shell_execute: write a `/tmp/solve.py` script and run it t
         ""
     };
 
+    // Planner-mode instructions — only if the plan tools are available.
+    let planner_instructions = if tools.planner {
+        "\n\n## Planner Mode\n\
+             Planner mode is on. Before doing any work on a new task, call `create_plan` with \
+             the goal and an ordered list of concrete steps; the user sees the plan as a live \
+             checklist. Then work through the steps in order: call `update_plan` to mark a step \
+             `in_progress` before starting it and `done`, `skipped` or `failed` when it is \
+             finished. The plan persists across turns, so on follow-up messages continue \
+             updating the existing plan instead of creating a new one unless the task changed. \
+             Write your final reply only after every step is finished."
+    } else {
+        ""
+    };
+
     // Skills instructions — always injected because read_skill is always available.
     let skills_instructions = "\n\n## Skills\
          Use `search_memory` to discover relevant skills when a task might benefit from a \
@@ -284,6 +357,7 @@ immediately switch to shell_execute: write a `/tmp/solve.py` script and run it t
     p.push_str(&tool_summary);
     p.push_str(formatting_guide);
     p.push_str(memory_instructions);
+    p.push_str(planner_instructions);
     p.push_str(skills_instructions);
     if !secret_key_names.is_empty() {
         p.push_str(&format!(
@@ -544,6 +618,28 @@ mod tests {
         assert!(!result.contains("## Memory"));
     }
 
+    #[test]
+    fn planner_section_only_in_planner_mode() {
+        let mut tools = ToolAvailability::default();
+        let build = |tools: &ToolAvailability| {
+            build_preamble(
+                "",
+                &ProviderType::OpenRouter,
+                tools,
+                &None,
+                &McpTools::none(),
+                &[],
+                &[],
+            )
+        };
+        assert!(!build(&tools).contains("## Planner Mode"));
+
+        tools.planner = true;
+        let result = build(&tools);
+        assert!(result.contains("## Planner Mode"));
+        assert!(result.contains("update_plan"));
+    }
+
     #[test]
     fn secret_keys_appended() {
         let tools = ToolAvailability::default();
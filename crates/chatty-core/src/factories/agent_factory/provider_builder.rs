@@ -1,8 +1,9 @@
 //! Provider-specific agent construction.
 //!
 //! Encapsulates the logic that differs between LLM providers: client creation,
-//! builder configuration (temperature, reasoning hints, max tokens), and any
-//! provider-specific schema sanitization (e.g. OpenAI `"format"` stripping).
+//! builder configuration (temperature, reasoning hints, max tokens, privacy
+//! opt-outs, network timeouts), and any provider-specific schema sanitization
+//! (e.g. OpenAI `"format"` stripping).
 
 use std::collections::HashSet;
 use std::sync::OnceLock;
@@ -12,29 +13,34 @@ use rig_core::client::CompletionClient;
 use rig_core::tool::ToolDyn;
 
 use crate::auth::{AzureTokenCache, azure_auth};
-use crate::services::AgentTaskController;
+use crate::models::turn_overrides::{REASONING_EFFORT_PARAM, ReasoningEffort};
+use crate::services::mcp_service::McpToolSet;
+use crate::services::{AgentTaskController, ToolCancellation, ToolGuard};
 use crate::settings::models::models_store::{AZURE_DEFAULT_API_VERSION, ModelConfig};
 use crate::settings::models::providers_store::{AzureAuthMethod, ProviderConfig, ProviderType};
 
 use super::AgentClient;
-use super::mcp_helpers::{build_with_mcp_tools, sanitize_mcp_tools_for_openai};
+use super::mcp_helpers::{mcp_tool_dyns, sanitize_mcp_tools_for_openai};
 
 static AZURE_TOKEN_CACHE: OnceLock<Option<AzureTokenCache>> = OnceLock::new();
 
-type McpToolSet = Vec<(String, Vec<rmcp::model::Tool>, rmcp::service::ServerSink)>;
-
 /// Build a provider-specific `AgentClient` from pre-collected native tools.
 ///
 /// All tool construction is done before this function — it only handles
 /// provider client creation, builder configuration, and MCP attachment.
+/// `tool_vec` is already guarded; MCP tools are wrapped with `tool_guard`
+/// as they are attached.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn build_provider_agent(
     model_config: &ModelConfig,
     provider_config: &ProviderConfig,
     preamble: &str,
-    tool_vec: Vec<Box<dyn ToolDyn>>,
+    mut tool_vec: Vec<Box<dyn ToolDyn>>,
     mcp_tools: Option<McpToolSet>,
     native_tool_names: &HashSet<String>,
+    tool_guard: &ToolGuard,
     task_controller: AgentTaskController,
+    tool_cancellation: ToolCancellation,
 ) -> Result<AgentClient> {
     let api_key = provider_config.api_key.clone();
     let base_url = provider_config.base_url.clone();
@@ -44,14 +50,13 @@ pub(super) async fn build_provider_agent(
             let key =
                 api_key.ok_or_else(|| anyhow!("API key not configured for OpenRouter provider"))?;
 
-            let client = if let Some(ref url) = base_url {
-                rig_core::providers::openrouter::Client::builder()
-                    .api_key(&key)
-                    .base_url(url)
-                    .build()?
-            } else {
-                rig_core::providers::openrouter::Client::new(&key)?
-            };
+            let mut client_builder = rig_core::providers::openrouter::Client::builder()
+                .api_key(&key)
+                .http_client(http_client(provider_config));
+            if let Some(ref url) = base_url {
+                client_builder = client_builder.base_url(url);
+            }
+            let client = client_builder.build()?;
 
             let mut builder = client
                 .agent(&model_config.model_identifier)
@@ -65,12 +70,17 @@ pub(super) async fn build_provider_agent(
                 builder = builder.max_tokens(max_tokens as u64);
             }
 
+            if let Some(params) = request_params(model_config, provider_config) {
+                builder = builder.additional_params(params);
+            }
+
             let mcp_tools = sanitize_mcp_tools_for_openai(mcp_tools);
-            let agent =
-                build_with_mcp_tools!(builder.tools(tool_vec), mcp_tools, native_tool_names);
+            tool_vec.extend(tool_guard.wrap(mcp_tool_dyns(mcp_tools, native_tool_names)));
+            let agent = builder.tools(tool_vec).build();
             Ok(AgentClient::OpenRouter {
                 agent,
                 task_controller,
+                tool_cancellation,
             })
         }
         ProviderType::Ollama => {
@@ -79,6 +89,7 @@ pub(super) async fn build_provider_agent(
             let client = rig_core::providers::ollama::Client::builder()
                 .api_key(rig_core::client::Nothing)
                 .base_url(&url)
+                .http_client(http_client(provider_config))
                 .build()?;
 
             let builder = client
@@ -86,11 +97,12 @@ pub(super) async fn build_provider_agent(
                 .preamble(preamble)
                 .temperature(model_config.temperature as f64);
 
-            let agent =
-                build_with_mcp_tools!(builder.tools(tool_vec), mcp_tools, native_tool_names);
+            tool_vec.extend(tool_guard.wrap(mcp_tool_dyns(mcp_tools, native_tool_names)));
+            let agent = builder.tools(tool_vec).build();
             Ok(AgentClient::Ollama {
                 agent,
                 task_controller,
+                tool_cancellation,
             })
         }
         ProviderType::AzureOpenAI => {
@@ -101,7 +113,9 @@ pub(super) async fn build_provider_agent(
                 tool_vec,
                 mcp_tools,
                 native_tool_names,
+                tool_guard,
                 task_controller,
+                tool_cancellation,
                 api_key,
                 base_url,
             )
@@ -110,6 +124,40 @@ pub(super) async fn build_provider_agent(
     }
 }
 
+/// HTTP client applying the provider's connect and read timeouts.
+fn http_client(provider_config: &ProviderConfig) -> reqwest::Client {
+    let timeouts = provider_config.timeouts();
+    crate::services::http_client::provider_client(timeouts.connect(), timeouts.read())
+}
+
+/// Extra request body fields: the provider's privacy opt-outs plus the
+/// reasoning effort, when the model config sets one. Ollama takes neither.
+fn request_params(
+    model_config: &ModelConfig,
+    provider_config: &ProviderConfig,
+) -> Option<serde_json::Value> {
+    let effort = model_config
+        .extra_params
+        .get(REASONING_EFFORT_PARAM)
+        .and_then(|value| ReasoningEffort::parse(value));
+    let reasoning = match (&provider_config.provider_type, effort) {
+        (ProviderType::OpenRouter, Some(effort)) => {
+            Some(serde_json::json!({ "reasoning": { "effort": effort.as_str() } }))
+        }
+        (ProviderType::AzureOpenAI, Some(effort)) => {
+            Some(serde_json::json!({ "reasoning_effort": effort.as_str() }))
+        }
+        _ => None,
+    };
+    match (provider_config.privacy_request_params(), reasoning) {
+        (Some(serde_json::Value::Object(mut privacy)), Some(serde_json::Value::Object(extra))) => {
+            privacy.extend(extra);
+            Some(serde_json::Value::Object(privacy))
+        }
+        (privacy, reasoning) => privacy.or(reasoning),
+    }
+}
+
 /// Azure OpenAI has more complex setup (endpoint normalization, Entra ID auth),
 /// so it gets its own function.
 #[allow(clippy::too_many_arguments)]
@@ -117,10 +165,12 @@ async fn build_azure_agent(
     model_config: &ModelConfig,
     provider_config: &ProviderConfig,
     preamble: &str,
-    tool_vec: Vec<Box<dyn ToolDyn>>,
+    mut tool_vec: Vec<Box<dyn ToolDyn>>,
     mcp_tools: Option<McpToolSet>,
     native_tool_names: &HashSet<String>,
+    tool_guard: &ToolGuard,
     task_controller: AgentTaskController,
+    tool_cancellation: ToolCancellation,
     api_key: Option<String>,
     base_url: Option<String>,
 ) -> Result<AgentClient> {
@@ -142,42 +192,7 @@ async fn build_azure_agent(
         ));
     }
 
-    let auth = match provider_config.azure_auth_method() {
-        AzureAuthMethod::EntraId => {
-            tracing::info!("Using Entra ID authentication with token cache");
-
-            let cache = AZURE_TOKEN_CACHE.get_or_init(|| match AzureTokenCache::new() {
-                Ok(cache) => Some(cache),
-                Err(e) => {
-                    tracing::warn!(
-                        error = ?e,
-                        "Failed to create Azure token cache, will fetch tokens directly each time"
-                    );
-                    None
-                }
-            });
-
-            let token = if let Some(cache) = cache {
-                cache
-                    .get_token()
-                    .await
-                    .context("Failed to get cached Entra ID token")?
-            } else {
-                tracing::debug!("Using direct token fetch (cache unavailable)");
-                azure_auth::fetch_entra_id_token()
-                    .await
-                    .context("Failed to fetch Entra ID token")?
-            };
-
-            rig_core::providers::azure::AzureOpenAIAuth::Token(token)
-        }
-        AzureAuthMethod::ApiKey => {
-            tracing::info!("Using API Key authentication for Azure OpenAI");
-            let key = api_key
-                .ok_or_else(|| anyhow!("API key not configured for Azure OpenAI provider"))?;
-            rig_core::providers::azure::AzureOpenAIAuth::ApiKey(key)
-        }
-    };
+    let auth = resolve_azure_auth(provider_config, api_key).await?;
 
     tracing::info!(
         endpoint = %endpoint,
@@ -191,6 +206,7 @@ async fn build_azure_agent(
         .api_key(auth)
         .azure_endpoint(endpoint.clone())
         .api_version(api_version)
+        .http_client(http_client(provider_config))
         .build()
         .map_err(|e| {
             anyhow!(
@@ -212,19 +228,70 @@ async fn build_azure_agent(
         builder = builder.max_tokens(max_tokens as u64);
     }
 
+    if let Some(params) = request_params(model_config, provider_config) {
+        builder = builder.additional_params(params);
+    }
+
     let mcp_tools = sanitize_mcp_tools_for_openai(mcp_tools);
-    let agent = build_with_mcp_tools!(builder.tools(tool_vec), mcp_tools, native_tool_names);
+    tool_vec.extend(tool_guard.wrap(mcp_tool_dyns(mcp_tools, native_tool_names)));
+    let agent = builder.tools(tool_vec).build();
     Ok(AgentClient::AzureOpenAI {
         agent,
         task_controller,
+        tool_cancellation,
     })
 }
 
+/// Resolve Azure OpenAI credentials for `provider_config`: a (cached) Entra
+/// ID token or the configured API key.
+pub(crate) async fn resolve_azure_auth(
+    provider_config: &ProviderConfig,
+    api_key: Option<String>,
+) -> Result<rig_core::providers::azure::AzureOpenAIAuth> {
+    let auth = match provider_config.azure_auth_method() {
+        AzureAuthMethod::EntraId => {
+            tracing::info!("Using Entra ID authentication with token cache");
+
+            let cache = AZURE_TOKEN_CACHE.get_or_init(|| match AzureTokenCache::new() {
+                Ok(cache) => Some(cache),
+                Err(e) => {
+                    tracing::warn!(
+                        error = ?e,
+                        "Failed to create Azure token cache, will fetch tokens directly each time"
+                    );
+                    None
+                }
+            });
+
+            let token = if let Some(cache) = cache {
+                cache
+                    .get_token()
+                    .await
+                    .context("Failed to get cached Entra ID token")?
+            } else {
+                tracing::debug!("Using direct token fetch (cache unavailable)");
+                azure_auth::fetch_entra_id_token()
+                    .await
+                    .context("Failed to fetch Entra ID token")?
+            };
+
+            rig_core::providers::azure::AzureOpenAIAuth::Token(token)
+        }
+        AzureAuthMethod::ApiKey => {
+            tracing::info!("Using API Key authentication for Azure OpenAI");
+            let key = api_key
+                .ok_or_else(|| anyhow!("API key not configured for Azure OpenAI provider"))?;
+            rig_core::providers::azure::AzureOpenAIAuth::ApiKey(key)
+        }
+    };
+    Ok(auth)
+}
+
 /// Normalize Azure endpoint URL:
 /// 1. Strip trailing slashes
 /// 2. Add https:// if missing
 /// 3. Extract base URL if user provided full path (e.g., .../openai/deployments/...)
-fn normalize_azure_endpoint(raw_endpoint: &str) -> String {
+pub(crate) fn normalize_azure_endpoint(raw_endpoint: &str) -> String {
     let raw_endpoint = raw_endpoint.trim_end_matches('/').to_string();
     let mut endpoint =
         if raw_endpoint.starts_with("http://") || raw_endpoint.starts_with("https://") {
@@ -251,6 +318,36 @@ fn normalize_azure_endpoint(raw_endpoint: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::settings::models::providers_store::ProviderPrivacy;
+
+    #[test]
+    fn test_request_params_merge_privacy_and_reasoning_effort() {
+        let mut provider = ProviderConfig::new("test".to_string(), ProviderType::AzureOpenAI);
+        let mut model = ModelConfig::new(
+            "o3".to_string(),
+            "o3".to_string(),
+            ProviderType::AzureOpenAI,
+            "o3".to_string(),
+        );
+        assert!(request_params(&model, &provider).is_none());
+
+        model
+            .extra_params
+            .insert(REASONING_EFFORT_PARAM.to_string(), "high".to_string());
+        assert_eq!(
+            request_params(&model, &provider),
+            Some(serde_json::json!({"reasoning_effort": "high"}))
+        );
+
+        provider.set_privacy(ProviderPrivacy {
+            disable_storage: true,
+            zero_data_retention: false,
+        });
+        assert_eq!(
+            request_params(&model, &provider),
+            Some(serde_json::json!({"store": false, "reasoning_effort": "high"}))
+        );
+    }
 
     #[test]
     fn test_azure_url_normalization_basic() {
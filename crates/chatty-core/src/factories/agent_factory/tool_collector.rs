@@ -3,15 +3,17 @@ use rig_core::tool::ToolDyn;
 #[cfg(feature = "math-render")]
 use crate::tools::CompileTypstTool;
 use crate::tools::{
-    AddAttachmentTool, ApplyDiffTool, BrowserUseTool, CreateChartTool, CreateDirectoryTool,
-    DaytonaTool, DeleteFileTool, DocRetrieverTool, ExecuteCodeTool, FetchTool, FinalAnswerTool,
-    FindDefinitionTool, FindFilesTool, GitAddTool, GitCommitTool, GitCreateBranchTool, GitDiffTool,
-    GitLogTool, GitStatusTool, GitSwitchBranchTool, GlobSearchTool, InvokeAgentTool,
-    ListAgentsTool, ListDirectoryTool, ListToolsTool, MoveFileTool, PublishModuleTool,
-    ReadBinaryTool, ReadFileTool, ReadSkillTool, RememberTool, SaveSkillTool, SearchCodeTool,
-    SearchMemoryTool, SearchWebTool, ShellCdTool, ShellExecuteTool, ShellSetEnvTool,
-    ShellStatusTool, SubAgentTool, UpdateTodoTool, VerifyCompletionTool, WriteFileTool,
-    WriteTodosTool,
+    AddAttachmentTool, ApplyDiffTool, AskUserTool, BrowserUseTool, CitationTool, CreateChartTool,
+    CreateDirectoryTool, CreatePlanTool, DaytonaTool, DeleteFileTool, DocRetrieverTool, DockerTool,
+    ExecuteCodeTool, FetchTool, FinalAnswerTool, FindDefinitionTool, FindFilesTool,
+    GetVariableTool, GitAddTool, GitCommitTool, GitCreateBranchTool, GitDiffTool, GitLogTool,
+    GitStatusTool, GitSwitchBranchTool, GlobSearchTool, InvokeAgentTool, K8sActionTool,
+    K8sDescribeTool, K8sEventsTool, K8sGetTool, K8sLogsTool, ListAgentsTool, ListDirectoryTool,
+    ListToolsTool, MoveFileTool, NotesAppendTool, NotesReadTool, NotesSearchTool,
+    PublishModuleTool, ReadBinaryTool, ReadFileTool, ReadSkillTool, RememberTool, SaveSkillTool,
+    SearchCodeTool, SearchMemoryTool, SearchWebTool, SetVariableTool, ShellCdTool,
+    ShellExecuteTool, ShellSetEnvTool, ShellStatusTool, SshExecuteTool, SubAgentTool,
+    UpdatePlanTool, UpdateTodoTool, VerifyCompletionTool, WriteFileTool, WriteTodosTool,
 };
 #[cfg(feature = "duckdb")]
 use crate::tools::{DescribeDataTool, FileStructureTool, ProfileDataTool, QueryDataTool};
@@ -63,9 +65,21 @@ pub(super) type GitTools = (
     GitCommitTool,
 );
 
+/// Read-only Kubernetes tool set (get, describe, logs, events)
+pub(super) type KubernetesTools = (K8sGetTool, K8sDescribeTool, K8sLogsTool, K8sEventsTool);
+
+/// Notes vault tool set (search, read, append)
+pub(super) type NotesTools = (NotesSearchTool, NotesReadTool, NotesAppendTool);
+
 /// Code search tool set (search_code, find_files, find_definition)
 pub(super) type SearchTools = (SearchCodeTool, FindFilesTool, FindDefinitionTool);
 
+/// Conversation variable tools (get_variable, set_variable)
+pub(super) type VariableTools = (GetVariableTool, SetVariableTool);
+
+/// Planner-mode tools (create_plan, update_plan)
+pub(super) type PlanTools = (CreatePlanTool, UpdatePlanTool);
+
 /// Excel tool sets (gated on filesystem read/write settings)
 #[cfg(feature = "excel")]
 pub(super) type ExcelWriteTools = (WriteExcelTool, EditExcelTool);
@@ -103,6 +117,12 @@ pub(super) struct NativeTools {
     pub fetch_tool: Option<FetchTool>,
     pub shell_tools: Option<ShellTools>,
     pub git_tools: Option<GitTools>,
+    pub kubernetes_tools: Option<KubernetesTools>,
+    pub kubernetes_action_tool: Option<K8sActionTool>,
+    pub docker_tool: Option<DockerTool>,
+    pub ssh_execute_tool: Option<SshExecuteTool>,
+    pub notes_tools: Option<NotesTools>,
+    pub citation_tool: Option<CitationTool>,
     pub search_tools: Option<SearchTools>,
     #[cfg(feature = "excel")]
     pub excel_read: Option<ReadExcelTool>,
@@ -123,6 +143,9 @@ pub(super) struct NativeTools {
     pub typst_tool: Option<CompileTypstTool>,
     pub execute_code_tool: Option<ExecuteCodeTool>,
     pub remember_tool: Option<RememberTool>,
+    pub variable_tools: Option<VariableTools>,
+    pub plan_tools: Option<PlanTools>,
+    pub ask_user_tool: Option<AskUserTool>,
     pub save_skill_tool: Option<SaveSkillTool>,
     pub search_memory_tool: Option<SearchMemoryTool>,
     pub read_skill_tool: ReadSkillTool,
@@ -200,6 +223,29 @@ impl NativeTools {
             tools.push(Box::new(switch_branch));
             tools.push(Box::new(commit));
         }
+        if let Some((get, describe, logs, events)) = self.kubernetes_tools {
+            tools.push(Box::new(get));
+            tools.push(Box::new(describe));
+            tools.push(Box::new(logs));
+            tools.push(Box::new(events));
+        }
+        if let Some(t) = self.kubernetes_action_tool {
+            tools.push(Box::new(t));
+        }
+        if let Some(t) = self.docker_tool {
+            tools.push(Box::new(t));
+        }
+        if let Some(t) = self.ssh_execute_tool {
+            tools.push(Box::new(t));
+        }
+        if let Some((search, read, append)) = self.notes_tools {
+            tools.push(Box::new(search));
+            tools.push(Box::new(read));
+            tools.push(Box::new(append));
+        }
+        if let Some(t) = self.citation_tool {
+            tools.push(Box::new(t));
+        }
         if let Some((sc, ff, fd)) = self.search_tools {
             tools.push(Box::new(sc));
             tools.push(Box::new(ff));
@@ -250,6 +296,17 @@ impl NativeTools {
         if let Some(t) = self.remember_tool {
             tools.push(Box::new(t));
         }
+        if let Some((get, set)) = self.variable_tools {
+            tools.push(Box::new(get));
+            tools.push(Box::new(set));
+        }
+        if let Some((create, update)) = self.plan_tools {
+            tools.push(Box::new(create));
+            tools.push(Box::new(update));
+        }
+        if let Some(t) = self.ask_user_tool {
+            tools.push(Box::new(t));
+        }
         if let Some(t) = self.save_skill_tool {
             tools.push(Box::new(t));
         }
@@ -297,6 +354,12 @@ macro_rules! native_tools {
         fetch_tool: $fetch_tool:expr,
         shell_tools: $shell_tools:expr,
         git_tools: $git_tools:expr,
+        kubernetes_tools: $kubernetes_tools:expr,
+        kubernetes_action_tool: $kubernetes_action_tool:expr,
+        docker_tool: $docker_tool:expr,
+        ssh_execute_tool: $ssh_execute_tool:expr,
+        notes_tools: $notes_tools:expr,
+        citation_tool: $citation_tool:expr,
         search_tools: $search_tools:expr,
         excel_read: $excel_read:expr,
         excel_write: $excel_write:expr,
@@ -309,6 +372,9 @@ macro_rules! native_tools {
         typst_tool: $typst_tool:expr,
         execute_code_tool: $execute_code_tool:expr,
         remember_tool: $remember_tool:expr,
+        variable_tools: $variable_tools:expr,
+        plan_tools: $plan_tools:expr,
+        ask_user_tool: $ask_user_tool:expr,
         save_skill_tool: $save_skill_tool:expr,
         search_memory_tool: $search_memory_tool:expr,
         read_skill_tool: $read_skill_tool:expr,
@@ -339,6 +405,12 @@ macro_rules! native_tools {
             fetch_tool: $fetch_tool,
             shell_tools: $shell_tools,
             git_tools: $git_tools,
+            kubernetes_tools: $kubernetes_tools,
+            kubernetes_action_tool: $kubernetes_action_tool,
+            docker_tool: $docker_tool,
+            ssh_execute_tool: $ssh_execute_tool,
+            notes_tools: $notes_tools,
+            citation_tool: $citation_tool,
             search_tools: $search_tools,
             #[cfg(feature = "excel")]
             excel_read: $excel_read,
@@ -359,6 +431,9 @@ macro_rules! native_tools {
             typst_tool: $typst_tool,
             execute_code_tool: $execute_code_tool,
             remember_tool: $remember_tool,
+            variable_tools: $variable_tools,
+            plan_tools: $plan_tools,
+            ask_user_tool: $ask_user_tool,
             save_skill_tool: $save_skill_tool,
             search_memory_tool: $search_memory_tool,
             read_skill_tool: $read_skill_tool,
@@ -13,6 +13,12 @@ pub struct ToolAvailability {
     pub fetch: bool,
     pub shell: bool,
     pub git: bool,
+    pub kubernetes: bool,
+    pub kubernetes_actions: bool,
+    pub docker: bool,
+    pub ssh: bool,
+    pub notes: bool,
+    pub citations: bool,
     pub search: bool,
     pub add_attachment: bool,
     pub excel_read: bool,
@@ -33,6 +39,9 @@ pub struct ToolAvailability {
     pub browser_use: bool,
     pub daytona: bool,
     pub publish_module: bool,
+    pub conversation_variables: bool,
+    pub planner: bool,
+    pub ask_user: bool,
 }
 
 pub(super) fn active_native_tool_names(tools: &ToolAvailability) -> HashSet<String> {
@@ -98,6 +107,32 @@ pub(super) fn active_native_tool_names(tools: &ToolAvailability) -> HashSet<Stri
             .map(String::from),
         );
     }
+    if tools.kubernetes {
+        names.extend(
+            ["k8s_get", "k8s_describe", "k8s_logs", "k8s_events"]
+                .into_iter()
+                .map(String::from),
+        );
+    }
+    if tools.kubernetes_actions {
+        names.insert(String::from("k8s_action"));
+    }
+    if tools.docker {
+        names.insert(String::from("docker"));
+    }
+    if tools.ssh {
+        names.insert(String::from("ssh_execute"));
+    }
+    if tools.notes {
+        names.extend(
+            ["notes_search", "notes_read", "notes_append"]
+                .into_iter()
+                .map(String::from),
+        );
+    }
+    if tools.citations {
+        names.insert(String::from("citations"));
+    }
     if tools.search {
         names.extend(
             ["search_code", "find_files", "find_definition"]
@@ -175,6 +210,19 @@ pub(super) fn active_native_tool_names(tools: &ToolAvailability) -> HashSet<Stri
     if tools.publish_module {
         names.insert(String::from("publish_wasm_module"));
     }
+    if tools.conversation_variables {
+        names.extend(
+            ["get_variable", "set_variable"]
+                .into_iter()
+                .map(String::from),
+        );
+    }
+    if tools.planner {
+        names.extend(["create_plan", "update_plan"].into_iter().map(String::from));
+    }
+    if tools.ask_user {
+        names.insert(String::from("ask_user"));
+    }
 
     names
 }
@@ -262,6 +310,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn includes_kubernetes_tools_without_actions_by_default() {
+        let names = active_native_tool_names(&ToolAvailability {
+            kubernetes: true,
+            ..Default::default()
+        });
+        for tool in ["k8s_get", "k8s_describe", "k8s_logs", "k8s_events"] {
+            assert!(names.contains(tool), "{tool} missing for kubernetes");
+        }
+        assert!(!names.contains("k8s_action"));
+    }
+
+    #[test]
+    fn includes_notes_tools() {
+        let names = active_native_tool_names(&ToolAvailability {
+            notes: true,
+            ..Default::default()
+        });
+        for tool in ["notes_search", "notes_read", "notes_append"] {
+            assert!(names.contains(tool), "{tool} missing for notes");
+        }
+    }
+
     #[test]
     fn includes_search_tools() {
         let names = active_native_tool_names(&ToolAvailability {
@@ -364,6 +435,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn includes_conversation_variable_tools() {
+        let names = active_native_tool_names(&ToolAvailability {
+            conversation_variables: true,
+            ..Default::default()
+        });
+        for tool in ["get_variable", "set_variable"] {
+            assert!(
+                names.contains(tool),
+                "{tool} missing for conversation_variables"
+            );
+        }
+    }
+
+    #[test]
+    fn includes_plan_tools_only_in_planner_mode() {
+        let names = active_native_tool_names(&ToolAvailability {
+            planner: true,
+            ..Default::default()
+        });
+        assert!(names.contains("create_plan"));
+        assert!(names.contains("update_plan"));
+    }
+
     #[test]
     fn includes_single_flag_tools() {
         // Tools that are a single flag → single tool name
@@ -377,6 +472,11 @@ mod tests {
             ("browser_use", "browser_use"),
             ("daytona", "daytona_run"),
             ("publish_module", "publish_wasm_module"),
+            ("ask_user", "ask_user"),
+            ("kubernetes_actions", "k8s_action"),
+            ("docker", "docker"),
+            ("ssh", "ssh_execute"),
+            ("citations", "citations"),
         ];
 
         for (flag, expected_tool) in cases {
@@ -391,6 +491,11 @@ mod tests {
                 "browser_use" => tools.browser_use = true,
                 "daytona" => tools.daytona = true,
                 "publish_module" => tools.publish_module = true,
+                "ask_user" => tools.ask_user = true,
+                "kubernetes_actions" => tools.kubernetes_actions = true,
+                "docker" => tools.docker = true,
+                "ssh" => tools.ssh = true,
+                "citations" => tools.citations = true,
                 _ => unreachable!(),
             }
             let names = active_native_tool_names(&tools);
@@ -411,6 +516,12 @@ mod tests {
             fetch: true,
             shell: true,
             git: true,
+            kubernetes: true,
+            kubernetes_actions: true,
+            docker: true,
+            ssh: true,
+            notes: true,
+            citations: true,
             search: true,
             add_attachment: true,
             excel_read: true,
@@ -431,6 +542,9 @@ mod tests {
             browser_use: true,
             daytona: true,
             publish_module: true,
+            conversation_variables: true,
+            planner: true,
+            ask_user: true,
         };
         let names = active_native_tool_names(&all);
         // Every individual flag's tools should be present
@@ -453,6 +567,12 @@ mod tests {
             "final_answer",
             "shell_execute",
             "git_status",
+            "k8s_get",
+            "k8s_action",
+            "docker",
+            "ssh_execute",
+            "notes_search",
+            "citations",
             "search_code",
             "read_excel",
             "pdf_info",
@@ -464,6 +584,11 @@ mod tests {
             "daytona_run",
             "execute_code",
             "compile_typst",
+            "get_variable",
+            "set_variable",
+            "create_plan",
+            "update_plan",
+            "ask_user",
         ] {
             assert!(
                 !names.contains(tool),
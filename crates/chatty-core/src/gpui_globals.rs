@@ -8,6 +8,7 @@ use gpui::Global;
 // ── Settings models ──────────────────────────────────────────────────────────
 impl Global for crate::settings::models::GeneralSettingsModel {}
 impl Global for crate::settings::models::ModelsModel {}
+impl Global for crate::settings::models::EmbeddingModelsModel {}
 impl Global for crate::settings::models::ProviderModel {}
 impl Global for crate::settings::models::McpServersModel {}
 impl Global for crate::settings::models::A2aAgentsModel {}
@@ -16,14 +17,19 @@ impl Global for crate::settings::models::TrainingSettingsModel {}
 impl Global for crate::settings::models::SearchSettingsModel {}
 impl Global for crate::settings::models::TokenTrackingSettings {}
 impl Global for crate::settings::models::UserSecretsModel {}
+impl Global for crate::settings::models::SnippetsModel {}
+impl Global for crate::settings::models::PromptPacksModel {}
 impl Global for crate::settings::models::ModuleSettingsModel {}
 impl Global for crate::settings::models::HiveSettingsModel {}
+impl Global for crate::settings::models::ManagedConfigModel {}
 impl Global for crate::settings::models::ExtensionsModel {}
+impl Global for crate::settings::models::KeybindingsModel {}
 
 // ── Chatty models ────────────────────────────────────────────────────────────
 impl Global for crate::models::ConversationsStore {}
 impl Global for crate::models::ErrorStore {}
 impl Global for crate::models::ExecutionApprovalStore {}
+impl Global for crate::models::JobQueue {}
 impl Global for crate::models::WriteApprovalStore {}
 
 // ── Services ─────────────────────────────────────────────────────────────────
@@ -32,6 +38,7 @@ impl Global for crate::services::MathRendererService {}
 impl Global for crate::services::McpService {}
 #[cfg(feature = "mermaid")]
 impl Global for crate::services::MermaidRendererService {}
+impl Global for crate::services::SpellChecker {}
 
 // ── Memory ───────────────────────────────────────────────────────────────────
 impl Global for crate::services::MemoryService {}
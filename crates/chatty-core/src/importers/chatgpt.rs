@@ -0,0 +1,153 @@
+//! ChatGPT's `conversations.json`.
+//!
+//! Each conversation stores its messages as a tree in `mapping`, since
+//! editing a message or regenerating an answer starts a new branch. The
+//! branch shown last ends at `current_node`; walking its parents gives the
+//! transcript. System prompts, tool messages, hidden messages and non-text
+//! parts (images, code interpreter input) are left out.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use super::{ImportedConversation, ImportedMessage, ImportedRole};
+
+pub(super) fn parse(items: &[Value]) -> Vec<ImportedConversation> {
+    items.iter().map(parse_conversation).collect()
+}
+
+fn parse_conversation(item: &Value) -> ImportedConversation {
+    let mapping = item.get("mapping").and_then(Value::as_object);
+    let mut branch = Vec::new();
+    let mut visited = HashSet::new();
+    let mut node_id = item
+        .get("current_node")
+        .and_then(Value::as_str)
+        .or_else(|| last_leaf(mapping?));
+    while let Some(id) = node_id {
+        // Guard against a malformed export with a parent cycle
+        if !visited.insert(id) {
+            break;
+        }
+        let Some(node) = mapping.and_then(|m| m.get(id)) else {
+            break;
+        };
+        branch.extend(node.get("message").and_then(parse_message));
+        node_id = node.get("parent").and_then(Value::as_str);
+    }
+    branch.reverse();
+
+    ImportedConversation::new(
+        item.get("title").and_then(Value::as_str),
+        seconds(item.get("create_time")),
+        seconds(item.get("update_time")),
+        branch,
+    )
+}
+
+/// A node without children, for exports that lack `current_node`.
+fn last_leaf(mapping: &serde_json::Map<String, Value>) -> Option<&str> {
+    mapping
+        .iter()
+        .filter(|(_, node)| {
+            node.get("children")
+                .and_then(Value::as_array)
+                .is_none_or(|children| children.is_empty())
+        })
+        .map(|(id, _)| id.as_str())
+        .next_back()
+}
+
+fn parse_message(message: &Value) -> Option<ImportedMessage> {
+    let role = match message.pointer("/author/role")?.as_str()? {
+        "user" => ImportedRole::User,
+        "assistant" => ImportedRole::Assistant,
+        _ => return None,
+    };
+    if message
+        .pointer("/metadata/is_visually_hidden_from_conversation")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    let content = message.get("content")?;
+    match content.get("content_type")?.as_str()? {
+        "text" | "multimodal_text" => {}
+        _ => return None,
+    }
+    let text = content
+        .get("parts")?
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_str)
+        .filter(|part| !part.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Some(ImportedMessage {
+        role,
+        text,
+        timestamp: seconds(message.get("create_time")),
+    })
+}
+
+/// ChatGPT timestamps are fractional Unix seconds, or null.
+fn seconds(value: Option<&Value>) -> Option<i64> {
+    value?.as_f64().map(|secs| secs as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ImportSource, parse_export};
+    use super::*;
+
+    const EXPORT: &str = r#"[{
+        "title": "Sorting",
+        "create_time": 1700000000.25,
+        "update_time": 1700000300.5,
+        "current_node": "a2",
+        "mapping": {
+            "root": {"id": "root", "message": null, "parent": null, "children": ["sys"]},
+            "sys": {"id": "sys", "parent": "root", "children": ["u1"], "message": {
+                "author": {"role": "system"},
+                "content": {"content_type": "text", "parts": [""]},
+                "metadata": {"is_visually_hidden_from_conversation": true}}},
+            "u1": {"id": "u1", "parent": "sys", "children": ["a1", "a2"], "message": {
+                "author": {"role": "user"}, "create_time": 1700000100.0,
+                "content": {"content_type": "multimodal_text",
+                            "parts": [{"asset_pointer": "file-service://x"}, "How do I sort?"]}}},
+            "a1": {"id": "a1", "parent": "u1", "children": [], "message": {
+                "author": {"role": "assistant"},
+                "content": {"content_type": "text", "parts": ["First try"]}}},
+            "a2": {"id": "a2", "parent": "u1", "children": [], "message": {
+                "author": {"role": "assistant"}, "create_time": 1700000200.0,
+                "content": {"content_type": "text", "parts": ["Use `sort`."]}}}
+        }
+    }]"#;
+
+    #[test]
+    fn follows_the_current_branch() {
+        let (source, conversations) = parse_export(EXPORT).unwrap();
+        assert_eq!(source, ImportSource::ChatGpt);
+
+        let conversation = &conversations[0];
+        assert_eq!(conversation.title, "Sorting");
+        assert_eq!(conversation.created_at, 1_700_000_000);
+        assert_eq!(conversation.updated_at, 1_700_000_300);
+        assert_eq!(
+            conversation.messages,
+            vec![
+                ImportedMessage {
+                    role: ImportedRole::User,
+                    text: "How do I sort?".to_string(),
+                    timestamp: Some(1_700_000_100),
+                },
+                ImportedMessage {
+                    role: ImportedRole::Assistant,
+                    text: "Use `sort`.".to_string(),
+                    timestamp: Some(1_700_000_200),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Claude's `conversations.json`.
+//!
+//! Each conversation lists its `chat_messages` in order, sent by `human` or
+//! `assistant`. The text is read from the `text` blocks of `content`, or the
+//! flat `text` field in older exports. The extracted text of attached
+//! documents is appended to the message it came with; other files are
+//! named only, as the export doesn't include them.
+
+use serde_json::Value;
+
+use super::{ImportedConversation, ImportedMessage, ImportedRole};
+
+pub(super) fn parse(items: &[Value]) -> Vec<ImportedConversation> {
+    items.iter().map(parse_conversation).collect()
+}
+
+fn parse_conversation(item: &Value) -> ImportedConversation {
+    let messages: Vec<_> = item
+        .get("chat_messages")
+        .and_then(Value::as_array)
+        .map(|messages| messages.iter().filter_map(parse_message).collect())
+        .unwrap_or_default();
+
+    ImportedConversation::new(
+        item.get("name").and_then(Value::as_str),
+        timestamp(item.get("created_at")),
+        timestamp(item.get("updated_at")),
+        messages,
+    )
+}
+
+fn parse_message(message: &Value) -> Option<ImportedMessage> {
+    let role = match message.get("sender")?.as_str()? {
+        "human" => ImportedRole::User,
+        "assistant" => ImportedRole::Assistant,
+        _ => return None,
+    };
+
+    let blocks: Vec<&str> = message
+        .get("content")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|block| block.get("type").and_then(Value::as_str) == Some("text"))
+        .filter_map(|block| block.get("text").and_then(Value::as_str))
+        .collect();
+    let mut text = if blocks.is_empty() {
+        message
+            .get("text")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    } else {
+        blocks.join("\n\n")
+    };
+
+    for attachment in message
+        .get("attachments")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let name = attachment
+            .get("file_name")
+            .and_then(Value::as_str)
+            .unwrap_or("attachment");
+        let content = attachment
+            .get("extracted_content")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        text.push_str(&format!("\n\n[Attachment: {name}]\n{content}"));
+    }
+    for file in message
+        .get("files")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        if let Some(name) = file.get("file_name").and_then(Value::as_str) {
+            text.push_str(&format!("\n\n[File: {name}]"));
+        }
+    }
+
+    Some(ImportedMessage {
+        role,
+        text: text.trim().to_string(),
+        timestamp: timestamp(message.get("created_at")),
+    })
+}
+
+/// Claude timestamps are RFC 3339 strings.
+fn timestamp(value: Option<&Value>) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(value?.as_str()?)
+        .ok()
+        .map(|dt| dt.timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ImportSource, parse_export};
+    use super::*;
+
+    const EXPORT: &str = r#"[{
+        "uuid": "c1",
+        "name": "",
+        "created_at": "2024-05-01T10:00:00.000000+00:00",
+        "updated_at": "2024-05-01T10:05:00.000000+00:00",
+        "chat_messages": [
+            {"sender": "human", "text": "Summarize this", "created_at": "2024-05-01T10:00:00Z",
+             "content": [{"type": "text", "text": "Summarize this"}],
+             "attachments": [{"file_name": "notes.txt", "extracted_content": "Buy milk"}],
+             "files": [{"file_name": "photo.png"}]},
+            {"sender": "assistant", "text": "Old text", "created_at": "2024-05-01T10:01:00Z",
+             "content": [{"type": "tool_use", "name": "x"}, {"type": "text", "text": "It's a shopping list."}]}
+        ]
+    }]"#;
+
+    #[test]
+    fn reads_messages_and_attachments() {
+        let (source, conversations) = parse_export(EXPORT).unwrap();
+        assert_eq!(source, ImportSource::Claude);
+
+        let conversation = &conversations[0];
+        assert_eq!(conversation.title, "Summarize this");
+        assert_eq!(conversation.created_at, 1_714_557_600);
+        assert_eq!(conversation.updated_at, 1_714_557_900);
+        assert_eq!(conversation.messages.len(), 2);
+        assert_eq!(
+            conversation.messages[0].text,
+            "Summarize this\n\n[Attachment: notes.txt]\nBuy milk\n\n[File: photo.png]"
+        );
+        assert_eq!(conversation.messages[0].role, ImportedRole::User);
+        assert_eq!(conversation.messages[1].text, "It's a shopping list.");
+        assert_eq!(conversation.messages[1].timestamp, Some(1_714_557_660));
+    }
+}
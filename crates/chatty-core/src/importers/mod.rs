@@ -0,0 +1,343 @@
+//! Import of conversations exported from the ChatGPT and Claude web apps.
+//!
+//! Both apps export a `conversations.json` holding every conversation.
+//! [`parse_export`] recognises the format and turns each conversation into
+//! an [`ImportedConversation`] — the user and assistant turns of the branch
+//! that was shown last, with their timestamps. [`import_conversations`]
+//! saves them through a [`ConversationRepository`].
+//!
+//! Conversations are identified by a hash of their messages: the id is
+//! derived from it, so importing the same export twice, or a newer export
+//! that repeats old conversations, skips the ones already there.
+
+mod chatgpt;
+mod claude;
+
+use std::collections::HashSet;
+
+use anyhow::{Result, bail};
+use rig_core::completion::Message;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::repositories::{ConversationData, ConversationRepository};
+
+/// Title for conversations exported without one.
+const FALLBACK_TITLE: &str = "Imported conversation";
+
+/// Characters of the first message used as a title when there is none.
+const TITLE_CHARS: usize = 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportSource {
+    ChatGpt,
+    Claude,
+}
+
+impl ImportSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ChatGpt => "ChatGPT",
+            Self::Claude => "Claude",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportedRole {
+    User,
+    Assistant,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedMessage {
+    pub role: ImportedRole,
+    pub text: String,
+    /// Unix timestamp (seconds), when the export has one
+    pub timestamp: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedConversation {
+    pub title: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub messages: Vec<ImportedMessage>,
+}
+
+impl ImportedConversation {
+    /// Build a conversation from its turns, merging consecutive messages of
+    /// the same role (tool steps split an answer in the ChatGPT export) and
+    /// filling in a missing title or timestamps.
+    fn new(
+        title: Option<&str>,
+        created_at: Option<i64>,
+        updated_at: Option<i64>,
+        turns: impl IntoIterator<Item = ImportedMessage>,
+    ) -> Self {
+        let mut messages: Vec<ImportedMessage> = Vec::new();
+        for turn in turns {
+            if turn.text.trim().is_empty() {
+                continue;
+            }
+            match messages.last_mut() {
+                Some(last) if last.role == turn.role => {
+                    last.text.push_str("\n\n");
+                    last.text.push_str(&turn.text);
+                }
+                _ => messages.push(turn),
+            }
+        }
+
+        let title = title
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .or_else(|| {
+                let first = messages.first()?.text.lines().next()?.trim();
+                Some(first.chars().take(TITLE_CHARS).collect())
+            })
+            .unwrap_or_else(|| FALLBACK_TITLE.to_string());
+        let first_timestamp = messages.iter().find_map(|m| m.timestamp);
+        let last_timestamp = messages.iter().rev().find_map(|m| m.timestamp);
+        let created_at = created_at.or(first_timestamp).unwrap_or_default();
+        let updated_at = updated_at.or(last_timestamp).unwrap_or(created_at);
+        Self {
+            title,
+            created_at,
+            updated_at,
+            messages,
+        }
+    }
+
+    /// SHA-256 over the roles and texts of the messages; title and
+    /// timestamps are left out, so a renamed conversation still matches.
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        for message in &self.messages {
+            let role: &[u8] = match message.role {
+                ImportedRole::User => b"user",
+                ImportedRole::Assistant => b"assistant",
+            };
+            hasher.update(role);
+            hasher.update([0]);
+            hasher.update(message.text.as_bytes());
+            hasher.update([0]);
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Conversation id derived from [`content_hash`](Self::content_hash),
+    /// shaped like the UUIDs of conversations created in the app.
+    pub fn id(&self) -> String {
+        let hash = self.content_hash();
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hash[0..8],
+            &hash[8..12],
+            &hash[12..16],
+            &hash[16..20],
+            &hash[20..32]
+        )
+    }
+
+    /// Persistable data for the conversation, to be continued with
+    /// `model_id`.
+    pub fn to_data(&self, model_id: &str) -> ConversationData {
+        let history: Vec<Message> = self
+            .messages
+            .iter()
+            .map(|m| match m.role {
+                ImportedRole::User => Message::user(m.text.clone()),
+                ImportedRole::Assistant => Message::assistant(m.text.clone()),
+            })
+            .collect();
+        let timestamps: Vec<Option<i64>> = self.messages.iter().map(|m| m.timestamp).collect();
+        ConversationData {
+            id: self.id(),
+            title: self.title.clone(),
+            model_id: model_id.to_string(),
+            message_history: serde_json::to_string(&history).unwrap_or_else(|_| "[]".to_string()),
+            system_traces: "[]".to_string(),
+            token_usage: "{}".to_string(),
+            attachment_paths: "[]".to_string(),
+            message_timestamps: serde_json::to_string(&timestamps)
+                .unwrap_or_else(|_| "[]".to_string()),
+            message_feedback: "[]".to_string(),
+            message_reactions: "[]".to_string(),
+            message_notes: "[]".to_string(),
+            message_errors: "[]".to_string(),
+            regeneration_records: "[]".to_string(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            working_dir: None,
+            agent_task_snapshot: None,
+            pinned_items: "[]".to_string(),
+            variables: "{}".to_string(),
+            execution_plan: None,
+            confidential: false,
+            history_policy: r#"{"mode":"full"}"#.to_string(),
+            disabled_mcp_servers: "[]".to_string(),
+            disabled_tools: "[]".to_string(),
+            label: "{}".to_string(),
+            tags: "[]".to_string(),
+            folder: None,
+            history_edits: "[]".to_string(),
+            compaction_records: "[]".to_string(),
+            locked: false,
+            quality_score: None,
+        }
+    }
+}
+
+/// Parse a `conversations.json` exported from ChatGPT or Claude.
+/// Conversations without any user or assistant text are left out.
+pub fn parse_export(json: &str) -> Result<(ImportSource, Vec<ImportedConversation>)> {
+    let value: Value = serde_json::from_str(json)?;
+    let Some(items) = value.as_array() else {
+        bail!("Expected a list of conversations (conversations.json)");
+    };
+    let source = match items.first() {
+        None => bail!("The export contains no conversations"),
+        Some(first) if first.get("mapping").is_some() => ImportSource::ChatGpt,
+        Some(first) if first.get("chat_messages").is_some() => ImportSource::Claude,
+        Some(_) => bail!("Not a ChatGPT or Claude conversations export"),
+    };
+    let conversations = match source {
+        ImportSource::ChatGpt => chatgpt::parse(items),
+        ImportSource::Claude => claude::parse(items),
+    };
+    Ok((
+        source,
+        conversations
+            .into_iter()
+            .filter(|c| !c.messages.is_empty())
+            .collect(),
+    ))
+}
+
+/// Outcome of [`import_conversations`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Ids of the conversations saved
+    pub imported: Vec<String>,
+    /// Conversations already in the repository or repeated in the export
+    pub duplicates: usize,
+    /// Conversations that failed to save
+    pub failed: usize,
+}
+
+/// Save `conversations` to `repo`, skipping those whose content hash is
+/// already there.
+pub async fn import_conversations(
+    repo: &dyn ConversationRepository,
+    conversations: &[ImportedConversation],
+    model_id: &str,
+) -> ImportSummary {
+    let mut summary = ImportSummary::default();
+    let mut seen = HashSet::new();
+    for conversation in conversations {
+        let id = conversation.id();
+        if !seen.insert(id.clone()) || matches!(repo.load_one(&id).await, Ok(Some(_))) {
+            summary.duplicates += 1;
+            continue;
+        }
+        match repo.save(&id, conversation.to_data(model_id)).await {
+            Ok(()) => summary.imported.push(id),
+            Err(e) => {
+                tracing::warn!(error = ?e, conv_id = %id, "Failed to save imported conversation");
+                summary.failed += 1;
+            }
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::InMemoryConversationRepository;
+
+    fn message(role: ImportedRole, text: &str) -> ImportedMessage {
+        ImportedMessage {
+            role,
+            text: text.to_string(),
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn merges_turns_and_fills_in_title() {
+        let conversation = ImportedConversation::new(
+            Some("  "),
+            None,
+            None,
+            [
+                message(ImportedRole::User, "How do I sort a Vec?\nThanks"),
+                message(ImportedRole::Assistant, "Use `sort`."),
+                message(ImportedRole::Assistant, " "),
+                message(ImportedRole::Assistant, "Or `sort_by_key`."),
+            ],
+        );
+
+        assert_eq!(conversation.title, "How do I sort a Vec?");
+        assert_eq!(conversation.messages.len(), 2);
+        assert_eq!(
+            conversation.messages[1].text,
+            "Use `sort`.\n\nOr `sort_by_key`."
+        );
+    }
+
+    #[test]
+    fn id_depends_on_messages_only() {
+        let turns = [
+            message(ImportedRole::User, "Hi"),
+            message(ImportedRole::Assistant, "Hello"),
+        ];
+        let a = ImportedConversation::new(Some("A"), Some(1), Some(2), turns.clone());
+        let b = ImportedConversation::new(Some("B"), Some(3), Some(4), turns);
+        let c =
+            ImportedConversation::new(Some("A"), None, None, [message(ImportedRole::User, "Hi")]);
+
+        assert_eq!(a.id(), b.id());
+        assert_ne!(a.id(), c.id());
+        assert_eq!(a.id().len(), 36);
+    }
+
+    #[test]
+    fn rejects_unknown_formats() {
+        assert!(parse_export("{}").is_err());
+        assert!(parse_export("[]").is_err());
+        assert!(parse_export(r#"[{"title": "x"}]"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn import_skips_conversations_already_saved() {
+        let repo = InMemoryConversationRepository::new();
+        let conversation = ImportedConversation::new(
+            Some("Greeting"),
+            Some(1_700_000_000),
+            None,
+            [
+                message(ImportedRole::User, "Hi"),
+                message(ImportedRole::Assistant, "Hello"),
+            ],
+        );
+        let batch = vec![conversation.clone(), conversation.clone()];
+
+        let first = import_conversations(&repo, &batch, "model").await;
+        assert_eq!(first.imported, vec![conversation.id()]);
+        assert_eq!(first.duplicates, 1);
+
+        let again = import_conversations(&repo, &batch, "model").await;
+        assert!(again.imported.is_empty());
+        assert_eq!(again.duplicates, 2);
+
+        let data = repo.load_one(&conversation.id()).await.unwrap().unwrap();
+        assert_eq!(data.title, "Greeting");
+        assert_eq!(data.model_id, "model");
+        assert_eq!(data.updated_at, 1_700_000_000);
+        let history: Vec<Message> = serde_json::from_str(&data.message_history).unwrap();
+        assert_eq!(history.len(), 2);
+    }
+}
@@ -176,7 +176,9 @@ pub fn uninstall_extension(id: &str, extensions: &mut ExtensionsModel) -> Result
 /// Check whether an update is available for a Hive-sourced extension.
 pub fn needs_update(ext: &InstalledExtension, latest_version: &str) -> bool {
     match &ext.source {
-        ExtensionSource::Hive { version, .. } => version != latest_version,
+        ExtensionSource::Hive { version, .. } | ExtensionSource::McpCatalog { version, .. } => {
+            version != latest_version
+        }
         ExtensionSource::Custom => false,
     }
 }
@@ -462,6 +464,9 @@ pub fn ensure_curated_mcp_servers(
             api_key: None,
             enabled: false,
             is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
         };
 
         extensions.add(InstalledExtension {
@@ -507,6 +512,9 @@ pub fn ensure_default_hive_mcp(
         api_key: None,
         enabled: false,
         is_module: false,
+        result_cache_ttl_secs: 0,
+        cached_tools: Vec::new(),
+        launch: None,
     };
 
     extensions.add(InstalledExtension {
@@ -21,6 +21,11 @@
 // - `AZURE_TOKEN_CACHE`          — factories/agent_factory/provider_builder.rs (OnceLock)
 // - `MCP_WRITE_LOCK`             — settings/models/mcp_store.rs (LazyLock<Mutex>)
 // - `PATH_AUGMENTED`             — auth/azure_auth.rs (OnceLock)
+// - `KNOWN_REFERENCES`           — services/citation_service.rs (LazyLock<RwLock>)
+// - `TOOL_REFERENCE`             — services/tool_reference.rs (LazyLock<RwLock>)
+// - `MANAGED_CONFIG`             — services/managed_config_service.rs (tokio OnceCell)
+// - `TELEMETRY`                  — services/telemetry_service.rs (OnceLock)
+// - `PROCESS_START`, `PHASES`    — services/startup_profile.rs (OnceLock, Mutex)
 //
 // Design rationale: domain-local singletons stay near their usage to avoid
 // coupling unrelated modules through a central registry. Service and repository
@@ -35,12 +40,16 @@ pub mod curated_mcp;
 pub mod exporters;
 pub mod factories;
 pub use hive_client as hive;
+pub mod importers;
 pub mod install;
+pub mod mcp_catalog;
 pub mod models;
+pub mod prompt_packs;
 pub mod repositories;
 pub mod sandbox;
 pub mod services;
 pub mod settings;
+pub mod testing;
 pub mod token_budget;
 pub mod tools;
 
@@ -69,15 +78,21 @@ pub struct RepositoryRegistry {
     pub providers: Arc<dyn settings::repositories::ProviderRepository>,
     pub general_settings: Arc<dyn settings::repositories::GeneralSettingsRepository>,
     pub models: Arc<dyn settings::repositories::ModelsRepository>,
+    pub embedding_models: Arc<dyn settings::repositories::EmbeddingModelsRepository>,
     pub mcp: Arc<dyn settings::repositories::McpRepository>,
     pub a2a: Arc<dyn settings::repositories::A2aRepository>,
     pub execution_settings: Arc<dyn settings::repositories::ExecutionSettingsRepository>,
     pub search_settings: Arc<dyn settings::repositories::SearchSettingsRepository>,
     pub training_settings: Arc<dyn settings::repositories::TrainingSettingsRepository>,
     pub user_secrets: Arc<dyn settings::repositories::UserSecretsRepository>,
+    pub snippets: Arc<dyn settings::repositories::SnippetsRepository>,
+    pub prompt_packs: Arc<dyn settings::repositories::PromptPacksRepository>,
+    pub managed_config_cache: Arc<dyn settings::repositories::ManagedConfigCacheRepository>,
+    pub mcp_tool_cache: Arc<dyn settings::repositories::McpToolCacheRepository>,
     pub module_settings: Arc<dyn settings::repositories::ModuleSettingsRepository>,
     pub hive_settings: Arc<dyn settings::repositories::HiveSettingsRepository>,
     pub extensions: Arc<dyn settings::repositories::ExtensionsRepository>,
+    pub keybindings: Arc<dyn settings::repositories::KeybindingsRepository>,
 }
 
 static REPOSITORY_REGISTRY: OnceLock<RepositoryRegistry> = OnceLock::new();
@@ -93,15 +108,21 @@ pub fn init_repositories() -> anyhow::Result<()> {
         providers: Arc::new(JsonFileRepository::new()?),
         general_settings: Arc::new(GeneralSettingsJsonRepository::new()?),
         models: Arc::new(JsonModelsRepository::new()?),
+        embedding_models: Arc::new(JsonEmbeddingModelsRepository::new()?),
         mcp: Arc::new(JsonMcpRepository::new()?),
         a2a: Arc::new(A2aJsonRepository::new()?),
         execution_settings: Arc::new(ExecutionSettingsJsonRepository::new()?),
         search_settings: Arc::new(SearchSettingsJsonRepository::new()?),
         training_settings: Arc::new(TrainingSettingsJsonRepository::new()?),
         user_secrets: Arc::new(UserSecretsJsonRepository::new()?),
+        snippets: Arc::new(SnippetsJsonRepository::new()?),
+        prompt_packs: Arc::new(PromptPacksJsonRepository::new()?),
+        managed_config_cache: Arc::new(ManagedConfigCacheJsonRepository::new()?),
+        mcp_tool_cache: Arc::new(McpToolCacheJsonRepository::new()?),
         module_settings: Arc::new(ModuleSettingsJsonRepository::new()?),
         hive_settings: Arc::new(HiveSettingsJsonRepository::new()?),
         extensions: Arc::new(ExtensionsJsonRepository::new()?),
+        keybindings: Arc::new(KeybindingsJsonRepository::new()?),
     };
     REPOSITORY_REGISTRY.set(registry).ok();
 
@@ -138,6 +159,11 @@ pub fn models_repository() -> Arc<dyn settings::repositories::ModelsRepository>
     registry().models.clone()
 }
 
+/// Returns a cloned Arc to the embedding models repository.
+pub fn embedding_models_repository() -> Arc<dyn settings::repositories::EmbeddingModelsRepository> {
+    registry().embedding_models.clone()
+}
+
 /// Returns a cloned Arc to the MCP repository.
 pub fn mcp_repository() -> Arc<dyn settings::repositories::McpRepository> {
     registry().mcp.clone()
@@ -170,6 +196,27 @@ pub fn user_secrets_repository() -> Arc<dyn settings::repositories::UserSecretsR
     registry().user_secrets.clone()
 }
 
+/// Returns a cloned Arc to the snippets repository.
+pub fn snippets_repository() -> Arc<dyn settings::repositories::SnippetsRepository> {
+    registry().snippets.clone()
+}
+
+/// Returns a cloned Arc to the installed prompt packs repository.
+pub fn prompt_packs_repository() -> Arc<dyn settings::repositories::PromptPacksRepository> {
+    registry().prompt_packs.clone()
+}
+
+/// Returns a cloned Arc to the managed config cache repository.
+pub fn managed_config_cache_repository()
+-> Arc<dyn settings::repositories::ManagedConfigCacheRepository> {
+    registry().managed_config_cache.clone()
+}
+
+/// Returns a cloned Arc to the MCP tool cache repository.
+pub fn mcp_tool_cache_repository() -> Arc<dyn settings::repositories::McpToolCacheRepository> {
+    registry().mcp_tool_cache.clone()
+}
+
 /// Returns a cloned Arc to the module settings repository.
 pub fn module_settings_repository() -> Arc<dyn settings::repositories::ModuleSettingsRepository> {
     registry().module_settings.clone()
@@ -185,6 +232,11 @@ pub fn extensions_repository() -> Arc<dyn settings::repositories::ExtensionsRepo
     registry().extensions.clone()
 }
 
+/// Returns a cloned Arc to the keybindings repository.
+pub fn keybindings_repository() -> Arc<dyn settings::repositories::KeybindingsRepository> {
+    registry().keybindings.clone()
+}
+
 // ── Pre-warming ──────────────────────────────────────────────────────────────
 
 /// Force-initialize expensive lazy statics so the cost is paid in the background
@@ -0,0 +1,444 @@
+//! Browsable catalog of popular MCP servers.
+//!
+//! Unlike the [curated catalog](crate::curated_mcp), which seeds a few hosted
+//! endpoints into the Extensions list, entries here are installed on demand.
+//! An entry either names a hosted endpoint (`url`) or a command Chatty
+//! launches itself (`command`, `args`, `env`). Templates may contain `${ID}`
+//! placeholders for the entry's `inputs`, which the user fills in at install
+//! time. Secret inputs are stored as user secrets and referenced as
+//! `{{secret:ID}}`, so the values never land in `mcp_servers.json`.
+//!
+//! The index ships with the app (`mcp_catalog.json`) and can be refreshed
+//! from [`DEFAULT_CATALOG_URL`]. Each entry carries a version; installed
+//! entries whose catalog version is newer are offered an update.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::models::extensions_store::{
+    ExtensionKind, ExtensionSource, ExtensionsModel, InstalledExtension,
+};
+use crate::settings::models::mcp_store::{McpLaunchConfig, McpServerConfig};
+
+/// Where the latest index is published.
+pub const DEFAULT_CATALOG_URL: &str = "https://raw.githubusercontent.com/boersmamarcel/chatty2/main/crates/chatty-core/src/mcp_catalog.json";
+
+/// Environment variable that points catalog refreshes at another index.
+pub const CATALOG_URL_ENV: &str = "CHATTY_MCP_CATALOG_URL";
+
+const BUNDLED_INDEX: &str = include_str!("mcp_catalog.json");
+
+#[derive(Debug, thiserror::Error)]
+pub enum McpCatalogError {
+    #[error("'{0}' is required")]
+    MissingInput(String),
+    #[error("An MCP server named '{0}' is already configured")]
+    NameTaken(String),
+    #[error("Failed to fetch the MCP catalog: {0}")]
+    Fetch(#[from] reqwest::Error),
+    #[error("Invalid MCP catalog: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct McpCatalogIndex {
+    #[serde(default)]
+    pub servers: Vec<McpCatalogEntry>,
+}
+
+/// One installable server.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct McpCatalogEntry {
+    /// Stable slug, used as the MCP server name.
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Revision of the entry; bump it when the install settings change.
+    pub version: String,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    /// Endpoint of a hosted server.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Bearer token template for a hosted server.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Command that starts a local server.
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Values asked for at install time.
+    #[serde(default)]
+    pub inputs: Vec<McpCatalogInput>,
+}
+
+/// A value the user supplies when installing an entry.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct McpCatalogInput {
+    /// Placeholder name; also the user secret key for secret inputs.
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub secret: bool,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl McpCatalogEntry {
+    /// Extension id of the installed entry.
+    pub fn extension_id(&self) -> String {
+        format!("mcp-{}", self.id)
+    }
+
+    /// Whether Chatty starts the server itself.
+    pub fn is_launched(&self) -> bool {
+        self.command.is_some()
+    }
+
+    /// Build the server config from the user's inputs. `known_secrets` are
+    /// user secret keys that already exist, so secret inputs left blank on
+    /// reinstall keep their stored value.
+    pub fn to_config(
+        &self,
+        inputs: &HashMap<String, String>,
+        known_secrets: &HashSet<String>,
+    ) -> Result<McpServerConfig, McpCatalogError> {
+        // Placeholder → replacement, for every input that has a value
+        let mut values = HashMap::new();
+        for input in &self.inputs {
+            let value = inputs.get(&input.id).map(|v| v.trim()).unwrap_or_default();
+            let replacement =
+                if input.secret && (!value.is_empty() || known_secrets.contains(&input.id)) {
+                    format!("{{{{secret:{}}}}}", input.id)
+                } else if !input.secret && !value.is_empty() {
+                    value.to_string()
+                } else if input.required {
+                    return Err(McpCatalogError::MissingInput(input.label.clone()));
+                } else {
+                    continue;
+                };
+            values.insert(input.id.as_str(), replacement);
+        }
+        let fill = |template: &str| fill_template(template, &values);
+
+        let launch = self.command.as_ref().map(|command| McpLaunchConfig {
+            command: command.clone(),
+            args: self.args.iter().filter_map(|arg| fill(arg)).collect(),
+            env: self
+                .env
+                .iter()
+                .filter_map(|(key, value)| Some((key.clone(), fill(value)?)))
+                .collect(),
+        });
+
+        Ok(McpServerConfig {
+            name: self.id.clone(),
+            url: self.url.clone().unwrap_or_default(),
+            api_key: self.api_key.as_deref().and_then(fill),
+            enabled: true,
+            is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch,
+        })
+    }
+
+    /// Secret inputs with a value, as `(user secret key, value)` pairs.
+    pub fn secret_values(&self, inputs: &HashMap<String, String>) -> Vec<(String, String)> {
+        self.inputs
+            .iter()
+            .filter(|input| input.secret)
+            .filter_map(|input| {
+                let value = inputs.get(&input.id)?.trim();
+                (!value.is_empty()).then(|| (input.id.clone(), value.to_string()))
+            })
+            .collect()
+    }
+}
+
+/// Replace `${ID}` placeholders. Returns `None` when a placeholder has no
+/// value, so optional settings left blank are omitted.
+fn fill_template(template: &str, values: &HashMap<&str, String>) -> Option<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find('}')?;
+        out.push_str(values.get(&after[..end])?);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+/// The index that ships with the app.
+pub fn bundled_catalog() -> Vec<McpCatalogEntry> {
+    parse_index(BUNDLED_INDEX).expect("bundled MCP catalog is valid")
+}
+
+fn parse_index(json: &str) -> Result<Vec<McpCatalogEntry>, McpCatalogError> {
+    let mut servers = serde_json::from_str::<McpCatalogIndex>(json)?.servers;
+    servers.sort_by_key(|e| e.name.to_lowercase());
+    Ok(servers)
+}
+
+/// Download the latest index and merge it over the bundled one.
+pub async fn fetch_catalog() -> Result<Vec<McpCatalogEntry>, McpCatalogError> {
+    let url = std::env::var(CATALOG_URL_ENV).unwrap_or_else(|_| DEFAULT_CATALOG_URL.to_string());
+    let body = crate::services::http_client::default_client(30)
+        .get(&url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    Ok(merge(bundled_catalog(), parse_index(&body)?))
+}
+
+/// Combine two indexes, keeping the newer version of entries in both.
+pub fn merge(
+    mut entries: Vec<McpCatalogEntry>,
+    remote: Vec<McpCatalogEntry>,
+) -> Vec<McpCatalogEntry> {
+    for entry in remote {
+        match entries.iter_mut().find(|e| e.id == entry.id) {
+            Some(existing) if is_newer(&entry.version, &existing.version) => *existing = entry,
+            Some(_) => {}
+            None => entries.push(entry),
+        }
+    }
+    entries.sort_by_key(|e| e.name.to_lowercase());
+    entries
+}
+
+/// Whether `candidate` is a newer version than `current`. Versions that
+/// aren't semver count as newer whenever they differ.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    match (
+        semver::Version::parse(candidate),
+        semver::Version::parse(current),
+    ) {
+        (Ok(candidate), Ok(current)) => candidate > current,
+        _ => candidate != current,
+    }
+}
+
+/// The catalog entry offering an update for an installed extension, if any.
+pub fn available_update<'a>(
+    ext: &InstalledExtension,
+    catalog: &'a [McpCatalogEntry],
+) -> Option<&'a McpCatalogEntry> {
+    let ExtensionSource::McpCatalog { entry_id, version } = &ext.source else {
+        return None;
+    };
+    catalog
+        .iter()
+        .find(|entry| &entry.id == entry_id && is_newer(&entry.version, version))
+}
+
+/// Add `entry` to the extensions model and the legacy server list, or update
+/// it in place when installed from the catalog before. An update keeps the
+/// enabled state and cache TTL. Returns the installed config and whether the
+/// extension is enabled.
+pub fn install_entry(
+    entry: &McpCatalogEntry,
+    inputs: &HashMap<String, String>,
+    known_secrets: &HashSet<String>,
+    extensions: &mut ExtensionsModel,
+    servers: &mut Vec<McpServerConfig>,
+) -> Result<(McpServerConfig, bool), McpCatalogError> {
+    let mut config = entry.to_config(inputs, known_secrets)?;
+    let id = entry.extension_id();
+    let source = ExtensionSource::McpCatalog {
+        entry_id: entry.id.clone(),
+        version: entry.version.clone(),
+    };
+
+    let enabled = match extensions.find_mut(&id) {
+        Some(ext) if matches!(ext.source, ExtensionSource::McpCatalog { .. }) => {
+            if let ExtensionKind::McpServer(previous) = &ext.kind {
+                config.result_cache_ttl_secs = previous.result_cache_ttl_secs;
+                config.cached_tools = previous.cached_tools.clone();
+            }
+            config.enabled = ext.enabled;
+            ext.description = entry.description.clone();
+            ext.kind = ExtensionKind::McpServer(config.clone());
+            ext.source = source;
+            ext.enabled
+        }
+        Some(_) => return Err(McpCatalogError::NameTaken(entry.id.clone())),
+        None => {
+            if extensions.find_mcp_by_name(&entry.id).is_some() {
+                return Err(McpCatalogError::NameTaken(entry.id.clone()));
+            }
+            extensions.add(InstalledExtension {
+                id,
+                display_name: entry.name.clone(),
+                description: entry.description.clone(),
+                kind: ExtensionKind::McpServer(config.clone()),
+                source,
+                pricing_model: None,
+                enabled: true,
+            });
+            true
+        }
+    };
+
+    servers.retain(|s| s.name != config.name);
+    servers.push(config.clone());
+    Ok((config, enabled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str) -> McpCatalogEntry {
+        bundled_catalog().into_iter().find(|e| e.id == id).unwrap()
+    }
+
+    fn inputs(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn bundled_entries_are_valid_and_unique() {
+        let catalog = bundled_catalog();
+        let mut ids: Vec<_> = catalog.iter().map(|e| e.id.as_str()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), catalog.len(), "duplicate ids in catalog");
+        for entry in &catalog {
+            assert!(
+                entry.url.is_some() != entry.command.is_some(),
+                "{} needs exactly one of url and command",
+                entry.id
+            );
+            assert!(
+                semver::Version::parse(&entry.version).is_ok(),
+                "{}",
+                entry.id
+            );
+        }
+    }
+
+    #[test]
+    fn to_config_fills_plain_inputs_into_args() {
+        let config = entry("filesystem")
+            .to_config(&inputs(&[("ALLOWED_DIR", "/tmp/docs")]), &HashSet::new())
+            .unwrap();
+        let launch = config.launch.unwrap();
+        assert_eq!(launch.command, "npx");
+        assert_eq!(launch.args.last().unwrap(), "/tmp/docs");
+    }
+
+    #[test]
+    fn to_config_references_secrets_instead_of_storing_them() {
+        let github = entry("github");
+        let values = inputs(&[("GITHUB_PERSONAL_ACCESS_TOKEN", "ghp_x")]);
+        let config = github.to_config(&values, &HashSet::new()).unwrap();
+        assert_eq!(
+            config.api_key.as_deref(),
+            Some("{{secret:GITHUB_PERSONAL_ACCESS_TOKEN}}")
+        );
+        assert_eq!(
+            github.secret_values(&values),
+            vec![(
+                "GITHUB_PERSONAL_ACCESS_TOKEN".to_string(),
+                "ghp_x".to_string()
+            )]
+        );
+
+        let known = HashSet::from(["GITHUB_PERSONAL_ACCESS_TOKEN".to_string()]);
+        assert!(github.to_config(&HashMap::new(), &known).is_ok());
+        assert!(matches!(
+            github.to_config(&HashMap::new(), &HashSet::new()),
+            Err(McpCatalogError::MissingInput(_))
+        ));
+    }
+
+    #[test]
+    fn to_config_omits_blank_optional_inputs() {
+        let config = entry("context7")
+            .to_config(&HashMap::new(), &HashSet::new())
+            .unwrap();
+        assert_eq!(config.api_key, None);
+        assert!(config.launch.is_none());
+    }
+
+    #[test]
+    fn update_is_offered_only_for_newer_versions() {
+        let mut extensions = ExtensionsModel::default();
+        let mut servers = Vec::new();
+        let mut time = entry("time");
+        install_entry(
+            &time,
+            &HashMap::new(),
+            &HashSet::new(),
+            &mut extensions,
+            &mut servers,
+        )
+        .unwrap();
+        let ext = extensions.find("mcp-time").unwrap().clone();
+        assert!(available_update(&ext, std::slice::from_ref(&time)).is_none());
+
+        time.version = "1.1.0".to_string();
+        assert!(available_update(&ext, std::slice::from_ref(&time)).is_some());
+        assert!(!is_newer("0.9.0", "1.0.0"));
+    }
+
+    #[test]
+    fn reinstall_keeps_enabled_state_and_rejects_name_clashes() {
+        let mut extensions = ExtensionsModel::default();
+        let mut servers = Vec::new();
+        let time = entry("time");
+        install_entry(
+            &time,
+            &HashMap::new(),
+            &HashSet::new(),
+            &mut extensions,
+            &mut servers,
+        )
+        .unwrap();
+        extensions.find_mut("mcp-time").unwrap().enabled = false;
+
+        let (_, enabled) = install_entry(
+            &time,
+            &HashMap::new(),
+            &HashSet::new(),
+            &mut extensions,
+            &mut servers,
+        )
+        .unwrap();
+        assert!(!enabled);
+        assert_eq!(servers.len(), 1);
+
+        let mut custom = extensions.find("mcp-time").unwrap().clone();
+        custom.id = "custom-time".to_string();
+        custom.source = ExtensionSource::Custom;
+        extensions.remove("mcp-time");
+        extensions.add(custom);
+        assert!(matches!(
+            install_entry(
+                &time,
+                &HashMap::new(),
+                &HashSet::new(),
+                &mut extensions,
+                &mut servers
+            ),
+            Err(McpCatalogError::NameTaken(_))
+        ));
+    }
+}
@@ -0,0 +1,183 @@
+//! Messages per day, for the activity heatmap on the usage page.
+//!
+//! Every message with a recorded timestamp counts towards the local day it
+//! was sent or received on. Each day also remembers which conversations
+//! were active, so selecting a day can filter the sidebar to them.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, TimeZone};
+
+use crate::repositories::ConversationData;
+
+/// Activity on one day.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DayActivity {
+    pub messages: usize,
+    /// Conversations with a message that day, in no particular order
+    pub conversation_ids: Vec<String>,
+}
+
+/// One cell of the heatmap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeatmapDay {
+    pub date: NaiveDate,
+    pub messages: usize,
+    /// Shade from 0 (no messages) to 4, relative to the busiest day
+    pub level: u8,
+}
+
+impl HeatmapDay {
+    /// e.g. `Fri, Mar 1 2024`
+    pub fn label(&self) -> String {
+        self.date.format("%a, %b %-d %Y").to_string()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ActivityCalendar {
+    days: BTreeMap<NaiveDate, DayActivity>,
+}
+
+impl ActivityCalendar {
+    /// Count the timestamped messages of `conversations` per day in `tz`.
+    pub fn from_conversations<Tz: TimeZone>(conversations: &[ConversationData], tz: &Tz) -> Self {
+        let mut calendar = Self::default();
+        for data in conversations {
+            let timestamps: Vec<Option<i64>> =
+                serde_json::from_str(&data.message_timestamps).unwrap_or_default();
+            calendar.add(&data.id, &timestamps, tz);
+        }
+        calendar
+    }
+
+    /// [`Self::from_conversations`] in local time.
+    pub fn local(conversations: &[ConversationData]) -> Self {
+        Self::from_conversations(conversations, &Local)
+    }
+
+    /// Count the messages of one conversation, given their Unix timestamps.
+    pub fn add<Tz: TimeZone>(
+        &mut self,
+        conversation_id: &str,
+        timestamps: &[Option<i64>],
+        tz: &Tz,
+    ) {
+        for timestamp in timestamps.iter().flatten() {
+            let Some(time) = DateTime::from_timestamp(*timestamp, 0) else {
+                continue;
+            };
+            let day = self
+                .days
+                .entry(time.with_timezone(tz).date_naive())
+                .or_default();
+            day.messages += 1;
+            if !day.conversation_ids.iter().any(|id| id == conversation_id) {
+                day.conversation_ids.push(conversation_id.to_string());
+            }
+        }
+    }
+
+    pub fn day(&self, date: NaiveDate) -> Option<&DayActivity> {
+        self.days.get(&date)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+
+    /// Messages on the busiest day.
+    pub fn max_messages(&self) -> usize {
+        self.days.values().map(|d| d.messages).max().unwrap_or(0)
+    }
+
+    /// Messages in `[from, to]`, both inclusive.
+    pub fn total_messages(&self, from: NaiveDate, to: NaiveDate) -> usize {
+        self.days.range(from..=to).map(|(_, d)| d.messages).sum()
+    }
+
+    /// Shade of a day from 0 (no messages) to 4, relative to the busiest day.
+    pub fn level(&self, date: NaiveDate) -> u8 {
+        let messages = self.day(date).map_or(0, |d| d.messages);
+        let max = self.max_messages();
+        if messages == 0 || max == 0 {
+            return 0;
+        }
+        (messages * 4).div_ceil(max).clamp(1, 4) as u8
+    }
+
+    /// Columns of the heatmap: `weeks` weeks, Monday first, ending with the
+    /// week of `today`. Days after `today` are `None`.
+    pub fn heatmap(&self, today: NaiveDate, weeks: usize) -> Vec<[Option<HeatmapDay>; 7]> {
+        let monday = today - Days::new(today.weekday().num_days_from_monday() as u64);
+        let first = monday - Days::new(7 * weeks.saturating_sub(1) as u64);
+        (0..weeks)
+            .map(|week| {
+                std::array::from_fn(|weekday| {
+                    let date = first + Days::new((week * 7 + weekday) as u64);
+                    (date <= today).then(|| HeatmapDay {
+                        date,
+                        messages: self.day(date).map_or(0, |d| d.messages),
+                        level: self.level(date),
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// [`Self::heatmap`] up to today in local time.
+    pub fn heatmap_to_today(&self, weeks: usize) -> Vec<[Option<HeatmapDay>; 7]> {
+        self.heatmap(Local::now().date_naive(), weeks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn counts_messages_and_conversations_per_day() {
+        // 2024-03-01 10:00 and 21:00 UTC, then 2024-03-02 08:00 UTC
+        let mut calendar = ActivityCalendar::default();
+        calendar.add("a", &[Some(1_709_287_200), None, Some(1_709_326_800)], &Utc);
+        calendar.add("b", &[Some(1_709_366_400)], &Utc);
+        calendar.add("c", &[Some(1_709_287_200)], &Utc);
+
+        let first = calendar.day(date(2024, 3, 1)).unwrap();
+        assert_eq!(first.messages, 3);
+        assert_eq!(first.conversation_ids, vec!["a", "c"]);
+        assert_eq!(calendar.day(date(2024, 3, 2)).unwrap().messages, 1);
+        assert_eq!(
+            calendar.total_messages(date(2024, 3, 1), date(2024, 3, 2)),
+            4
+        );
+
+        assert_eq!(calendar.level(date(2024, 3, 1)), 4);
+        assert_eq!(calendar.level(date(2024, 3, 2)), 2);
+        assert_eq!(calendar.level(date(2024, 3, 3)), 0);
+    }
+
+    #[test]
+    fn weeks_start_on_monday_and_stop_today() {
+        let mut calendar = ActivityCalendar::default();
+        calendar.add("a", &[Some(1_709_287_200)], &Utc);
+
+        // A Wednesday
+        let weeks = calendar.heatmap(date(2024, 3, 6), 2);
+        let date_of = |week: usize, weekday: usize| weeks[week][weekday].as_ref().map(|d| d.date);
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(date_of(0, 0), Some(date(2024, 2, 26)));
+        assert_eq!(date_of(1, 0), Some(date(2024, 3, 4)));
+        assert_eq!(date_of(1, 2), Some(date(2024, 3, 6)));
+        assert_eq!(date_of(1, 3), None);
+
+        let friday = weeks[0][4].as_ref().unwrap();
+        assert_eq!((friday.messages, friday.level), (1, 4));
+        assert_eq!(friday.label(), "Fri, Mar 1 2024");
+    }
+}
@@ -9,6 +9,9 @@ pub const MAX_FILE_SIZE: u64 = 5_242_880; // 5MB
 pub const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "svg", "bmp"];
 pub const PDF_EXTENSION: &str = "pdf";
 pub const OFFICE_EXTENSIONS: &[&str] = &["docx", "pptx", "xlsx"];
+/// Short clips sent to vision models as sampled frames (see `video_frames`)
+pub const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "m4v", "webm", "mkv", "avi"];
+pub const MAX_VIDEO_FILE_SIZE: u64 = 104_857_600; // 100MB
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ValidationError {
@@ -34,18 +37,21 @@ pub async fn validate_attachment_async(path: &Path) -> Result<(), ValidationErro
 
 /// Shared validation logic for both sync and async variants
 fn validate_attachment_metadata(path: &Path, size: u64) -> Result<(), ValidationError> {
-    if size > MAX_FILE_SIZE {
-        return Err(ValidationError::FileTooLarge {
-            size,
-            max: MAX_FILE_SIZE,
-        });
-    }
-
     let ext = path
         .extension()
-        .ok_or(ValidationError::NoExtension)?
-        .to_string_lossy()
-        .to_lowercase();
+        .map(|ext| ext.to_string_lossy().to_lowercase());
+
+    // Videos are never sent whole, only their sampled frames, so they may be larger
+    let max = if ext.as_deref().is_some_and(is_video_extension) {
+        MAX_VIDEO_FILE_SIZE
+    } else {
+        MAX_FILE_SIZE
+    };
+    if size > max {
+        return Err(ValidationError::FileTooLarge { size, max });
+    }
+
+    let ext = ext.ok_or(ValidationError::NoExtension)?;
 
     if !is_supported_extension(&ext) {
         return Err(ValidationError::UnsupportedExtension(ext.to_string()));
@@ -60,6 +66,7 @@ pub fn is_supported_extension(ext: &str) -> bool {
     IMAGE_EXTENSIONS.contains(&ext_lower.as_str())
         || ext_lower == PDF_EXTENSION
         || OFFICE_EXTENSIONS.contains(&ext_lower.as_str())
+        || VIDEO_EXTENSIONS.contains(&ext_lower.as_str())
 }
 
 /// Check if a file is an image based on extension
@@ -67,6 +74,11 @@ pub fn is_image_extension(ext: &str) -> bool {
     IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str())
 }
 
+/// Check if a file is a video based on extension
+pub fn is_video_extension(ext: &str) -> bool {
+    VIDEO_EXTENSIONS.contains(&ext.to_lowercase().as_str())
+}
+
 /// Check if a file is a PDF based on extension
 #[allow(dead_code)]
 pub fn is_pdf_extension(ext: &str) -> bool {
@@ -138,6 +150,27 @@ mod tests {
         let _ = fs::remove_file(&max_path);
     }
 
+    #[test]
+    fn test_validate_video_allows_larger_files() {
+        let temp_dir = std::env::temp_dir();
+        let video_path = temp_dir.join("test_clip.mp4");
+
+        create_test_file(&video_path, MAX_FILE_SIZE + 1).expect("Failed to create test file");
+
+        let result = validate_attachment(&video_path);
+        assert!(result.is_ok(), "Video over the image limit should pass");
+
+        let _ = fs::remove_file(&video_path);
+    }
+
+    #[test]
+    fn test_is_video_extension() {
+        assert!(is_video_extension("mp4"));
+        assert!(is_video_extension("MOV"));
+        assert!(!is_video_extension("png"));
+        assert!(is_supported_extension("webm"));
+    }
+
     #[test]
     fn test_validate_unsupported_extension() {
         let temp_dir = std::env::temp_dir();
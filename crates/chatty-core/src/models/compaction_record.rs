@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// What started a compaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactionTrigger {
+    /// The user asked for it (`/compact`)
+    Manual,
+    /// The history neared the model's context window
+    Automatic,
+}
+
+/// Audit record of older messages being replaced with a summary, kept with
+/// the conversation so exports show what the model no longer sees verbatim.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompactionRecord {
+    pub trigger: CompactionTrigger,
+    /// Number of messages replaced by the summary
+    pub messages_compacted: usize,
+    /// Approximate tokens freed, as reported by the summarizer
+    pub estimated_tokens_freed: usize,
+    /// The summary that replaced the messages
+    pub summary: String,
+    /// Unix timestamp (seconds) of the compaction
+    pub compacted_at: i64,
+}
+
+impl CompactionRecord {
+    /// One-line description, e.g. `Automatically compacted 12 messages (~3400 tokens freed)`.
+    pub fn description(&self) -> String {
+        let how = match self.trigger {
+            CompactionTrigger::Manual => "Compacted",
+            CompactionTrigger::Automatic => "Automatically compacted",
+        };
+        format!(
+            "{how} {} messages (~{} tokens freed)",
+            self.messages_compacted, self.estimated_tokens_freed
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_describes() {
+        let record = CompactionRecord {
+            trigger: CompactionTrigger::Automatic,
+            messages_compacted: 12,
+            estimated_tokens_freed: 3400,
+            summary: "- Set up the project".to_string(),
+            compacted_at: 1,
+        };
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.contains(r#""trigger":"automatic""#));
+        assert_eq!(
+            serde_json::from_str::<CompactionRecord>(&json).unwrap(),
+            record
+        );
+        assert_eq!(
+            record.description(),
+            "Automatically compacted 12 messages (~3400 tokens freed)"
+        );
+    }
+}
@@ -1,23 +1,35 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
+use std::sync::atomic::Ordering;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tracing::trace;
 
 use rig_core::OneOrMany;
 use rig_core::completion::Message;
-use rig_core::completion::message::{AssistantContent, Text};
+use rig_core::completion::message::{AssistantContent, Text, UserContent};
 
 use crate::factories::AgentClient;
 use crate::factories::agent_factory::AgentBuildContext;
+use crate::models::compaction_record::{CompactionRecord, CompactionTrigger};
+use crate::models::conversation_label::ConversationLabel;
+use crate::models::conversation_tags;
+use crate::models::history_edit::{HistoryEdit, HistoryEditKind};
+use crate::models::history_policy::HistoryPolicy;
 use crate::models::message_types::{SystemTrace, ToolSource};
+use crate::models::pinned_context::PinnedItem;
+use crate::models::quality_score::QualityScore;
+use crate::models::session_budget::BudgetTracker;
 use crate::models::token_usage::{ConversationTokenUsage, TokenUsage};
 use crate::repositories::ConversationData;
-use crate::services::AgentTaskSnapshot;
+use crate::services::docker_service::DockerResources;
 use crate::services::shell_service::ShellSession;
+use crate::services::{AgentTaskSnapshot, ConfidentialFlag};
 use crate::settings::models::models_store::ModelConfig;
 use crate::settings::models::providers_store::ProviderConfig;
-use crate::tools::PendingArtifacts;
+use crate::token_budget::summarizer::SummarizationResult;
+use crate::tools::{ConversationVariables, ExecutionPlan, PendingArtifacts, SharedPlan};
 
 /// User feedback signal for an individual assistant message
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -26,6 +38,16 @@ pub enum MessageFeedback {
     ThumbsDown,
 }
 
+/// Why a turn failed, recorded on the user message whose response stream
+/// errored. Cleared when the turn is retried.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TurnError {
+    /// Error reported by the provider or the stream
+    pub message: String,
+    /// Unix timestamp (seconds) when the stream failed
+    pub failed_at: i64,
+}
+
 /// Record of a regenerated assistant response, capturing the original text
 /// for DPO (Direct Preference Optimization) preference pair training data.
 /// The original text is the "rejected" response; the replacement is the "chosen" response.
@@ -54,6 +76,46 @@ pub struct MessageEntry {
     pub attachment_paths: Vec<PathBuf>,
     pub timestamp: Option<i64>,
     pub feedback: Option<MessageFeedback>,
+    /// Emoji reactions, in the order they were added.
+    pub reactions: Vec<String>,
+    /// Private reviewer note; never sent to the model.
+    pub note: Option<String>,
+    /// Failure of the response stream to this (user) message.
+    pub error: Option<TurnError>,
+}
+
+/// Shared state a conversation hands to its agent's tools. Restored from
+/// persisted data before the agent is built so both sides hold the same
+/// handles.
+struct ToolHandles {
+    pending_artifacts: PendingArtifacts,
+    variables: ConversationVariables,
+    execution_plan: SharedPlan,
+    confidential: ConfidentialFlag,
+    docker_resources: DockerResources,
+    disabled_tools: BTreeSet<String>,
+}
+
+impl ToolHandles {
+    fn from_data(data: &ConversationData) -> Self {
+        Self {
+            pending_artifacts: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
+            variables: std::sync::Arc::new(parking_lot::Mutex::new(
+                Conversation::deserialize_variables(&data.variables).unwrap_or_default(),
+            )),
+            execution_plan: std::sync::Arc::new(parking_lot::Mutex::new(
+                data.execution_plan
+                    .as_deref()
+                    .and_then(|json| Conversation::deserialize_execution_plan(json).ok()),
+            )),
+            confidential: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(
+                data.confidential,
+            )),
+            docker_resources: DockerResources::default(),
+            disabled_tools: Conversation::deserialize_disabled_tools(&data.disabled_tools)
+                .unwrap_or_default(),
+        }
+    }
 }
 
 /// A single conversation with an AI agent
@@ -85,10 +147,42 @@ pub struct Conversation {
     working_dir: Option<PathBuf>,
     /// Latest persisted agent todo panel snapshot for this conversation.
     agent_task_snapshot: Option<AgentTaskSnapshot>,
+    /// User-pinned snippets injected verbatim into every turn.
+    pinned_items: Vec<PinnedItem>,
+    /// Key-value scratchpad shared with the agent's get_variable/set_variable tools.
+    variables: ConversationVariables,
+    /// Planner-mode plan shared with the agent's create_plan/update_plan tools.
+    execution_plan: SharedPlan,
+    /// Confidential conversations block network tools (see `GuardrailService`).
+    confidential: ConfidentialFlag,
+    /// How much of the stored history is sent to the model each turn.
+    history_policy: HistoryPolicy,
+    /// Globally enabled MCP servers whose tools are left out of this conversation.
+    disabled_mcp_servers: BTreeSet<String>,
+    /// Tools (native or MCP, by name) left out of this conversation's agent.
+    disabled_tools: BTreeSet<String>,
+    /// Color and emoji shown in the sidebar.
+    label: ConversationLabel,
+    /// Tags for grouping in the sidebar, in the order they were added.
+    tags: Vec<String>,
+    /// Sidebar folder, a `/`-separated path.
+    folder: Option<String>,
+    /// Manual deletions from the history, oldest first.
+    history_edits: Vec<HistoryEdit>,
+    /// Older messages replaced with a summary, oldest first.
+    compaction_records: Vec<CompactionRecord>,
+    /// Read-only: no new messages, deletions or regenerations.
+    locked: bool,
+    /// Latest judge-model rating, used to filter training exports.
+    quality_score: Option<QualityScore>,
+    /// Compose stacks the docker tool started, cleaned up when the conversation is deleted.
+    docker_resources: DockerResources,
     /// Effective workspace directory the current agent was built with.
     agent_workspace_dir: Option<PathBuf>,
     /// Progress slot for the invoke_agent tool in this conversation's agent.
     invoke_agent_progress_slot: crate::tools::invoke_agent_tool::InvokeAgentProgressSlot,
+    /// `/timebox` budget of the current or next agentic task; not persisted.
+    session_budget: Option<BudgetTracker>,
 }
 
 impl Conversation {
@@ -122,9 +216,18 @@ impl Conversation {
         // Shell session is created inside the factory when execution is enabled.
         // The factory returns it so we can store it on the Conversation for reuse
         // across agent rebuilds (MCP changes, model switches).
+        let variables = ConversationVariables::default();
+        let execution_plan = SharedPlan::default();
+        let confidential = ConfidentialFlag::default();
+        let docker_resources = DockerResources::default();
+
         let ctx = AgentBuildContext {
             pending_artifacts: Some(pending_artifacts.clone()),
             shell_session: None, // Factory creates session on-demand when execution is enabled
+            conversation_variables: Some(variables.clone()),
+            execution_plan: Some(execution_plan.clone()),
+            confidential: Some(confidential.clone()),
+            docker_resources: Some(docker_resources.clone()),
             ..ctx
         };
         let (agent, shell_session, invoke_agent_progress_slot) =
@@ -151,6 +254,22 @@ impl Conversation {
             shell_session,
             working_dir: None,
             agent_task_snapshot: None,
+            pinned_items: Vec::new(),
+            variables,
+            execution_plan,
+            confidential,
+            history_policy: HistoryPolicy::default(),
+            disabled_mcp_servers: BTreeSet::new(),
+            disabled_tools: BTreeSet::new(),
+            label: ConversationLabel::default(),
+            tags: Vec::new(),
+            folder: None,
+            history_edits: Vec::new(),
+            compaction_records: Vec::new(),
+            locked: false,
+            quality_score: None,
+            session_budget: None,
+            docker_resources,
             agent_workspace_dir,
             invoke_agent_progress_slot,
         })
@@ -174,8 +293,7 @@ impl Conversation {
             data.id, provider_config.provider_type, url_info, model_config.model_identifier
         );
 
-        let pending_artifacts: PendingArtifacts =
-            std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handles = ToolHandles::from_data(&data);
         let agent_workspace_dir = ctx
             .exec_settings
             .as_ref()
@@ -184,8 +302,13 @@ impl Conversation {
 
         // Reconstruct agent; factory creates shell session on-demand when execution is enabled
         let ctx = AgentBuildContext {
-            pending_artifacts: Some(pending_artifacts.clone()),
+            pending_artifacts: Some(handles.pending_artifacts.clone()),
             shell_session: None, // Factory creates session on-demand
+            conversation_variables: Some(handles.variables.clone()),
+            execution_plan: Some(handles.execution_plan.clone()),
+            confidential: Some(handles.confidential.clone()),
+            docker_resources: Some(handles.docker_resources.clone()),
+            disabled_tools: handles.disabled_tools.clone(),
             ..ctx
         };
         let (agent, shell_session, invoke_agent_progress_slot) =
@@ -193,6 +316,24 @@ impl Conversation {
                 .await
                 .context("Failed to create agent from config")?;
 
+        let mut conversation = Self::restore(data, agent, handles)?;
+        conversation.shell_session = shell_session;
+        conversation.agent_workspace_dir = agent_workspace_dir;
+        conversation.invoke_agent_progress_slot = invoke_agent_progress_slot;
+        Ok(conversation)
+    }
+
+    /// Restore a conversation from persisted data around an agent that was
+    /// built elsewhere. The agent's tools do not share the conversation's
+    /// variables or plan, so this suits agents without tools, such as the
+    /// offline agent of `testing::FakeAgentClient`.
+    pub fn from_data_with_agent(data: ConversationData, agent: AgentClient) -> Result<Self> {
+        let handles = ToolHandles::from_data(&data);
+        Self::restore(data, agent, handles)
+    }
+
+    /// Deserialize `data` into a conversation driven by `agent`.
+    fn restore(data: ConversationData, agent: AgentClient, handles: ToolHandles) -> Result<Self> {
         // Deserialize message history
         let history = Self::deserialize_history(&data.message_history)
             .context("Failed to deserialize message history")?;
@@ -217,6 +358,12 @@ impl Conversation {
             Self::deserialize_message_timestamps(&data.message_timestamps).unwrap_or_default();
         let message_feedback =
             Self::deserialize_message_feedback(&data.message_feedback).unwrap_or_default();
+        let message_reactions =
+            Self::deserialize_message_reactions(&data.message_reactions).unwrap_or_default();
+        let message_notes =
+            Self::deserialize_message_notes(&data.message_notes).unwrap_or_default();
+        let message_errors =
+            Self::deserialize_message_errors(&data.message_errors).unwrap_or_default();
 
         // Zip the deserialized arrays into MessageEntry structs
         let entries: Vec<MessageEntry> = history
@@ -228,6 +375,9 @@ impl Conversation {
                 attachment_paths: attachment_paths.get(i).cloned().unwrap_or_default(),
                 timestamp: message_timestamps.get(i).copied().flatten(),
                 feedback: message_feedback.get(i).cloned().flatten(),
+                reactions: message_reactions.get(i).cloned().unwrap_or_default(),
+                note: message_notes.get(i).cloned().flatten(),
+                error: message_errors.get(i).cloned().flatten(),
             })
             .collect();
 
@@ -242,6 +392,19 @@ impl Conversation {
             .agent_task_snapshot
             .as_deref()
             .and_then(|json| Self::deserialize_agent_task_snapshot(json).ok());
+        let pinned_items = Self::deserialize_pinned_items(&data.pinned_items).unwrap_or_default();
+        let history_policy =
+            Self::deserialize_history_policy(&data.history_policy).unwrap_or_default();
+        let disabled_mcp_servers =
+            Self::deserialize_disabled_mcp_servers(&data.disabled_mcp_servers).unwrap_or_default();
+        let history_edits =
+            Self::deserialize_history_edits(&data.history_edits).unwrap_or_default();
+        let compaction_records =
+            Self::deserialize_compaction_records(&data.compaction_records).unwrap_or_default();
+        let quality_score = data
+            .quality_score
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok());
 
         // Convert Unix timestamps to SystemTime
         let created_at = UNIX_EPOCH + Duration::from_secs(data.created_at as u64);
@@ -260,12 +423,28 @@ impl Conversation {
             streaming_message: None, // Always start fresh, streaming state is transient
             streaming_trace: None,
             streaming_sub_agent_trace: None,
-            pending_artifacts,
-            shell_session,
+            pending_artifacts: handles.pending_artifacts,
+            shell_session: None,
             working_dir: data.working_dir.map(PathBuf::from),
             agent_task_snapshot,
-            agent_workspace_dir,
-            invoke_agent_progress_slot,
+            pinned_items,
+            variables: handles.variables,
+            execution_plan: handles.execution_plan,
+            confidential: handles.confidential,
+            history_policy,
+            disabled_mcp_servers,
+            disabled_tools: handles.disabled_tools,
+            label: ConversationLabel::from_json(&data.label),
+            tags: conversation_tags::tags_from_json(&data.tags),
+            folder: data.folder,
+            history_edits,
+            compaction_records,
+            locked: data.locked,
+            quality_score,
+            docker_resources: handles.docker_resources,
+            agent_workspace_dir: None,
+            invoke_agent_progress_slot: Default::default(),
+            session_budget: None,
         })
     }
 
@@ -283,6 +462,9 @@ impl Conversation {
             attachment_paths: attachments,
             timestamp: Some(timestamp),
             feedback: None,
+            reactions: Vec::new(),
+            note: None,
+            error: None,
         });
         self.updated_at = now;
     }
@@ -331,6 +513,9 @@ impl Conversation {
             attachment_paths: attachments,
             timestamp: Some(timestamp),
             feedback: None,
+            reactions: Vec::new(),
+            note: None,
+            error: None,
         });
         self.updated_at = now;
     }
@@ -384,6 +569,82 @@ impl Conversation {
 
     // ── Serialization (backward-compatible with ConversationData format) ─────
 
+    /// Snapshot the conversation for persistence or export, stamped with
+    /// `updated_at` (Unix seconds). Fails only if the history or traces
+    /// cannot be serialized; other fields fall back to their empty form.
+    pub fn to_data(&self, updated_at: i64) -> Result<ConversationData> {
+        Ok(ConversationData {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            model_id: self.model_id.clone(),
+            message_history: self.serialize_history()?,
+            system_traces: self.serialize_traces()?,
+            token_usage: self
+                .serialize_token_usage()
+                .unwrap_or_else(|_| "{}".to_string()),
+            attachment_paths: self
+                .serialize_attachment_paths()
+                .unwrap_or_else(|_| "[]".to_string()),
+            message_timestamps: self
+                .serialize_message_timestamps()
+                .unwrap_or_else(|_| "[]".to_string()),
+            message_feedback: self
+                .serialize_message_feedback()
+                .unwrap_or_else(|_| "[]".to_string()),
+            message_reactions: self
+                .serialize_message_reactions()
+                .unwrap_or_else(|_| "[]".to_string()),
+            message_notes: self
+                .serialize_message_notes()
+                .unwrap_or_else(|_| "[]".to_string()),
+            message_errors: self
+                .serialize_message_errors()
+                .unwrap_or_else(|_| "[]".to_string()),
+            regeneration_records: self
+                .serialize_regeneration_records()
+                .unwrap_or_else(|_| "[]".to_string()),
+            created_at: self
+                .created_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs() as i64,
+            updated_at,
+            working_dir: self
+                .working_dir
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
+            agent_task_snapshot: self.serialize_agent_task_snapshot().unwrap_or(None),
+            pinned_items: self
+                .serialize_pinned_items()
+                .unwrap_or_else(|_| "[]".to_string()),
+            variables: self
+                .serialize_variables()
+                .unwrap_or_else(|_| "{}".to_string()),
+            execution_plan: self.serialize_execution_plan().unwrap_or(None),
+            confidential: self.is_confidential(),
+            history_policy: self
+                .serialize_history_policy()
+                .unwrap_or_else(|_| r#"{"mode":"full"}"#.to_string()),
+            disabled_mcp_servers: self
+                .serialize_disabled_mcp_servers()
+                .unwrap_or_else(|_| "[]".to_string()),
+            disabled_tools: self
+                .serialize_disabled_tools()
+                .unwrap_or_else(|_| "[]".to_string()),
+            label: self.label.to_json(),
+            tags: conversation_tags::tags_to_json(&self.tags),
+            folder: self.folder.clone(),
+            history_edits: self
+                .serialize_history_edits()
+                .unwrap_or_else(|_| "[]".to_string()),
+            compaction_records: self
+                .serialize_compaction_records()
+                .unwrap_or_else(|_| "[]".to_string()),
+            locked: self.locked,
+            quality_score: self.serialize_quality_score().unwrap_or(None),
+        })
+    }
+
     /// Serialize message history to JSON string
     pub fn serialize_history(&self) -> Result<String> {
         let messages: Vec<&Message> = self.entries.iter().map(|e| &e.message).collect();
@@ -452,6 +713,94 @@ impl Conversation {
         serde_json::from_str(json).context("Failed to deserialize message feedback")
     }
 
+    /// Toggle an emoji reaction on a message. Returns whether the reaction is now set.
+    pub fn toggle_message_reaction(&mut self, index: usize, emoji: &str) -> bool {
+        let Some(entry) = self.entries.get_mut(index) else {
+            return false;
+        };
+        let added = match entry.reactions.iter().position(|r| r == emoji) {
+            Some(pos) => {
+                entry.reactions.remove(pos);
+                false
+            }
+            None => {
+                entry.reactions.push(emoji.to_string());
+                true
+            }
+        };
+        self.updated_at = SystemTime::now();
+        added
+    }
+
+    /// Set or clear the private annotation note on a message (blank clears it).
+    pub fn set_message_note(&mut self, index: usize, note: Option<String>) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+            self.updated_at = SystemTime::now();
+        }
+    }
+
+    /// Record that the response stream to the last message failed. Returns
+    /// the message's index, or `None` when the history does not end with a
+    /// user message.
+    pub fn record_turn_error(&mut self, message: String, failed_at: i64) -> Option<usize> {
+        let index = self.entries.len().checked_sub(1)?;
+        let entry = &mut self.entries[index];
+        if !matches!(entry.message, Message::User { .. }) {
+            return None;
+        }
+        entry.error = Some(TurnError { message, failed_at });
+        self.updated_at = SystemTime::now();
+        Some(index)
+    }
+
+    /// Clear the failure recorded on the message at `index`, e.g. before
+    /// retrying it. Returns `false` when there was none.
+    pub fn clear_turn_error(&mut self, index: usize) -> bool {
+        let cleared = self
+            .entries
+            .get_mut(index)
+            .is_some_and(|entry| entry.error.take().is_some());
+        if cleared {
+            self.updated_at = SystemTime::now();
+        }
+        cleared
+    }
+
+    /// Serialize message reactions to JSON string
+    pub fn serialize_message_reactions(&self) -> Result<String> {
+        let reactions: Vec<&Vec<String>> = self.entries.iter().map(|e| &e.reactions).collect();
+        serde_json::to_string(&reactions).context("Failed to serialize message reactions")
+    }
+
+    /// Deserialize message reactions from JSON string
+    pub fn deserialize_message_reactions(json: &str) -> Result<Vec<Vec<String>>> {
+        serde_json::from_str(json).context("Failed to deserialize message reactions")
+    }
+
+    /// Serialize message annotation notes to JSON string
+    pub fn serialize_message_notes(&self) -> Result<String> {
+        let notes: Vec<Option<&String>> = self.entries.iter().map(|e| e.note.as_ref()).collect();
+        serde_json::to_string(&notes).context("Failed to serialize message notes")
+    }
+
+    /// Deserialize message annotation notes from JSON string
+    pub fn deserialize_message_notes(json: &str) -> Result<Vec<Option<String>>> {
+        serde_json::from_str(json).context("Failed to deserialize message notes")
+    }
+
+    /// Serialize per-message turn errors to JSON string
+    pub fn serialize_message_errors(&self) -> Result<String> {
+        let errors: Vec<Option<&TurnError>> =
+            self.entries.iter().map(|e| e.error.as_ref()).collect();
+        serde_json::to_string(&errors).context("Failed to serialize message errors")
+    }
+
+    /// Deserialize per-message turn errors from JSON string
+    pub fn deserialize_message_errors(json: &str) -> Result<Vec<Option<TurnError>>> {
+        serde_json::from_str(json).context("Failed to deserialize message errors")
+    }
+
     /// Serialize the persisted agent task snapshot to JSON.
     pub fn serialize_agent_task_snapshot(&self) -> Result<Option<String>> {
         self.agent_task_snapshot
@@ -466,6 +815,72 @@ impl Conversation {
         serde_json::from_str(json).context("Failed to deserialize agent task snapshot")
     }
 
+    /// Serialize pinned context items to JSON string
+    pub fn serialize_pinned_items(&self) -> Result<String> {
+        serde_json::to_string(&self.pinned_items).context("Failed to serialize pinned items")
+    }
+
+    /// Deserialize pinned context items from JSON string
+    pub fn deserialize_pinned_items(json: &str) -> Result<Vec<PinnedItem>> {
+        serde_json::from_str(json).context("Failed to deserialize pinned items")
+    }
+
+    /// Serialize conversation variables to JSON string
+    pub fn serialize_variables(&self) -> Result<String> {
+        serde_json::to_string(&*self.variables.lock()).context("Failed to serialize variables")
+    }
+
+    /// Deserialize conversation variables from JSON string
+    pub fn deserialize_variables(json: &str) -> Result<BTreeMap<String, String>> {
+        serde_json::from_str(json).context("Failed to deserialize variables")
+    }
+
+    /// Serialize the history policy to JSON string
+    pub fn serialize_history_policy(&self) -> Result<String> {
+        serde_json::to_string(&self.history_policy).context("Failed to serialize history policy")
+    }
+
+    /// Deserialize the history policy from JSON string
+    pub fn deserialize_history_policy(json: &str) -> Result<HistoryPolicy> {
+        serde_json::from_str(json).context("Failed to deserialize history policy")
+    }
+
+    /// Serialize the MCP servers disabled for this conversation to JSON string
+    pub fn serialize_disabled_mcp_servers(&self) -> Result<String> {
+        serde_json::to_string(&self.disabled_mcp_servers)
+            .context("Failed to serialize disabled MCP servers")
+    }
+
+    /// Deserialize the MCP servers disabled for a conversation from JSON string
+    pub fn deserialize_disabled_mcp_servers(json: &str) -> Result<BTreeSet<String>> {
+        serde_json::from_str(json).context("Failed to deserialize disabled MCP servers")
+    }
+
+    /// Serialize the tools disabled for this conversation to JSON string
+    pub fn serialize_disabled_tools(&self) -> Result<String> {
+        serde_json::to_string(&self.disabled_tools).context("Failed to serialize disabled tools")
+    }
+
+    /// Deserialize the tools disabled for a conversation from JSON string
+    pub fn deserialize_disabled_tools(json: &str) -> Result<BTreeSet<String>> {
+        serde_json::from_str(json).context("Failed to deserialize disabled tools")
+    }
+
+    /// Serialize the planner-mode plan to JSON (`None` when no plan exists).
+    pub fn serialize_execution_plan(&self) -> Result<Option<String>> {
+        self.execution_plan
+            .lock()
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .context("Failed to serialize execution plan")
+    }
+
+    /// Deserialize a planner-mode plan from JSON.
+    pub fn deserialize_execution_plan(json: &str) -> Result<ExecutionPlan> {
+        serde_json::from_str(json).context("Failed to deserialize execution plan")
+    }
+
     /// Get regeneration records for this conversation
     #[allow(dead_code)]
     pub fn regeneration_records(&self) -> &[RegenerationRecord] {
@@ -533,6 +948,8 @@ impl Conversation {
         let timestamp = last.timestamp;
 
         self.entries.pop();
+        // The judge rated the answer that was just removed
+        self.quality_score = None;
         self.updated_at = SystemTime::now();
         Some((text, timestamp))
     }
@@ -556,6 +973,9 @@ impl Conversation {
                 attachment_paths: vec![],
                 timestamp: None,
                 feedback: None,
+                reactions: Vec::new(),
+                note: None,
+                error: None,
             });
         }
 
@@ -571,6 +991,9 @@ impl Conversation {
                 attachment_paths: old_entry.attachment_paths.clone(),
                 timestamp: old_entry.timestamp,
                 feedback: old_entry.feedback.clone(),
+                reactions: old_entry.reactions.clone(),
+                note: old_entry.note.clone(),
+                error: old_entry.error.clone(),
             });
         }
 
@@ -578,6 +1001,150 @@ impl Conversation {
         self.updated_at = SystemTime::now();
     }
 
+    /// Replace the first `result.messages_summarized` messages with the
+    /// summary from `summarize_oldest_half()` and record the compaction.
+    ///
+    /// Unlike [`Self::replace_history`], every stored message after the
+    /// summarized ones is kept, so messages added since the history was
+    /// captured for summarizing are not lost. Returns `None` when the
+    /// conversation has fewer messages than were summarized.
+    pub fn compact_history(
+        &mut self,
+        result: &SummarizationResult,
+        trigger: CompactionTrigger,
+    ) -> Option<CompactionRecord> {
+        let compacted = result.messages_summarized;
+        let summary_message = result.new_history.first()?.clone();
+        if compacted == 0 || compacted > self.entries.len() {
+            return None;
+        }
+        let summary = role_and_text(&summary_message)
+            .map(|(_, text)| text)
+            .unwrap_or_default();
+
+        self.entries.splice(
+            ..compacted,
+            [MessageEntry {
+                message: summary_message,
+                system_trace: None,
+                attachment_paths: vec![],
+                timestamp: None,
+                feedback: None,
+                reactions: Vec::new(),
+                note: None,
+                error: None,
+            }],
+        );
+        // Regenerations of summarized answers are gone with them
+        self.regeneration_records
+            .retain(|record| record.message_index >= compacted);
+        for record in &mut self.regeneration_records {
+            record.message_index -= compacted - 1;
+        }
+        // So are edits of summarized messages; deletions and merge boundaries
+        // among them stay in the log, pointing at the summary
+        self.history_edits.retain(|edit| {
+            edit.message_index >= compacted
+                || matches!(
+                    edit.kind,
+                    HistoryEditKind::MessageDeleted | HistoryEditKind::ConversationMerged
+                )
+        });
+        for edit in &mut self.history_edits {
+            edit.message_index = edit.message_index.saturating_sub(compacted - 1);
+        }
+
+        let now = SystemTime::now();
+        let record = CompactionRecord {
+            trigger,
+            messages_compacted: compacted,
+            estimated_tokens_freed: result.estimated_tokens_freed,
+            summary,
+            compacted_at: now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+        };
+        self.compaction_records.push(record.clone());
+        self.updated_at = now;
+        Some(record)
+    }
+
+    pub fn compaction_records(&self) -> &[CompactionRecord] {
+        &self.compaction_records
+    }
+
+    /// Serialize the compaction log to JSON string
+    pub fn serialize_compaction_records(&self) -> Result<String> {
+        serde_json::to_string(&self.compaction_records)
+            .context("Failed to serialize compaction records")
+    }
+
+    /// Deserialize the compaction log from JSON string
+    pub fn deserialize_compaction_records(json: &str) -> Result<Vec<CompactionRecord>> {
+        serde_json::from_str(json).context("Failed to deserialize compaction records")
+    }
+
+    /// Append `source`'s history and record where it starts as a history
+    /// edit naming it. The boundary is a record rather than a message so the
+    /// history keeps alternating between user and assistant turns, and
+    /// reviewer notes stay untouched. Each message keeps
+    /// its metadata; regenerations and history edits move with their
+    /// messages, token usage is added up, and pins and tags this conversation
+    /// lacks are taken over. Returns the number of messages added.
+    pub fn merge_from(&mut self, source: Conversation) -> usize {
+        let now = SystemTime::now();
+        // Index of the first merged message
+        let offset = self.entries.len();
+        let added = source.entries.len();
+
+        self.entries.extend(source.entries);
+
+        self.regeneration_records
+            .extend(
+                source
+                    .regeneration_records
+                    .into_iter()
+                    .map(|record| RegenerationRecord {
+                        message_index: record.message_index + offset,
+                        ..record
+                    }),
+            );
+        self.history_edits
+            .extend(source.history_edits.into_iter().map(|edit| HistoryEdit {
+                message_index: edit.message_index + offset,
+                ..edit
+            }));
+        self.history_edits.sort_by_key(|edit| edit.edited_at);
+        if added > 0 {
+            self.record_history_edit(
+                HistoryEditKind::ConversationMerged,
+                offset,
+                "",
+                &source.title,
+            );
+        }
+        self.compaction_records.extend(source.compaction_records);
+        self.compaction_records
+            .sort_by_key(|record| record.compacted_at);
+        for usage in source.token_usage.message_usages {
+            self.token_usage.add_usage(usage);
+        }
+
+        for item in source.pinned_items {
+            if !self.pinned_items.iter().any(|pinned| pinned.id == item.id) {
+                self.pinned_items.push(item);
+            }
+        }
+        for tag in source.tags {
+            if !conversation_tags::has_tag(&self.tags, &tag) {
+                self.tags.push(tag);
+            }
+        }
+
+        // The judge rated an answer that is no longer the latest
+        self.quality_score = None;
+        self.updated_at = now;
+        added
+    }
+
     /// Get the agent
     pub fn agent(&self) -> &AgentClient {
         &self.agent
@@ -641,6 +1208,367 @@ impl Conversation {
         self.updated_at = SystemTime::now();
     }
 
+    /// Get the items pinned as "always in context" for this conversation.
+    pub fn pinned_items(&self) -> &[PinnedItem] {
+        &self.pinned_items
+    }
+
+    /// Pin an item so it is included ahead of the user's message every turn.
+    pub fn pin_item(&mut self, item: PinnedItem) {
+        self.pinned_items.push(item);
+        self.updated_at = SystemTime::now();
+    }
+
+    /// Pin the text of the message at `index` in the history.
+    ///
+    /// Returns `false` when the index is out of range or the message has no text.
+    pub fn pin_message(&mut self, index: usize) -> bool {
+        let Some((role, text)) = self
+            .entries
+            .get(index)
+            .and_then(|e| role_and_text(&e.message))
+        else {
+            return false;
+        };
+        if text.trim().is_empty() {
+            return false;
+        }
+        self.pin_item(PinnedItem::message(role, text));
+        true
+    }
+
+    /// Delete the message at `index` with all its metadata and record the
+    /// edit. A deleted response's token usage is dropped from the totals, and
+    /// regeneration records and earlier edits follow the shifted indices.
+    pub fn delete_message(&mut self, index: usize) -> Option<HistoryEdit> {
+        let entry = self.entries.get(index)?;
+        let (role, text) = role_and_text(&entry.message).unwrap_or(("System", String::new()));
+        if matches!(entry.message, Message::Assistant { .. }) {
+            let turn = self.entries[..index]
+                .iter()
+                .filter(|e| matches!(e.message, Message::Assistant { .. }))
+                .count();
+            self.token_usage.remove_usage(turn);
+        }
+        self.entries.remove(index);
+
+        self.regeneration_records
+            .retain(|record| record.message_index != index);
+        for record in &mut self.regeneration_records {
+            if record.message_index > index {
+                record.message_index -= 1;
+            }
+        }
+        // Earlier edits of this message are superseded by its deletion; past
+        // deletions and merge boundaries stay in the log
+        self.history_edits.retain(|edit| {
+            edit.message_index != index
+                || matches!(
+                    edit.kind,
+                    HistoryEditKind::MessageDeleted | HistoryEditKind::ConversationMerged
+                )
+        });
+        for edit in &mut self.history_edits {
+            if edit.message_index > index {
+                edit.message_index -= 1;
+            }
+        }
+
+        Some(self.record_history_edit(HistoryEditKind::MessageDeleted, index, role, &text))
+    }
+
+    /// Remove the trace (thinking blocks and tool calls) of the message at
+    /// `index`, keeping its text, and record the edit.
+    pub fn delete_trace(&mut self, index: usize) -> Option<HistoryEdit> {
+        let entry = self.entries.get_mut(index)?;
+        entry.system_trace.take()?;
+        let (role, text) = role_and_text(&entry.message).unwrap_or(("System", String::new()));
+        Some(self.record_history_edit(HistoryEditKind::TraceDeleted, index, role, &text))
+    }
+
+    /// Replace the text of the user message at `index` with `new_text` and
+    /// drop every later message, so the conversation can be re-run from it.
+    /// Attachments of the message are kept. The original text goes into the
+    /// edit log; usage, regenerations and edit records of the dropped
+    /// messages are removed along with the quality score.
+    pub fn edit_user_message(&mut self, index: usize, new_text: String) -> Option<HistoryEdit> {
+        let entry = self.entries.get_mut(index)?;
+        let Message::User { content } = &entry.message else {
+            return None;
+        };
+
+        // The typed text is the first text part; attachments (including the
+        // text parts describing videos) follow it and are kept as they are
+        let mut parts: Vec<UserContent> = content.iter().cloned().collect();
+        let typed = parts.iter_mut().find_map(|part| match part {
+            UserContent::Text(text) => Some(text),
+            _ => None,
+        });
+        let original = match typed {
+            Some(text) => std::mem::replace(&mut text.text, new_text),
+            None => {
+                parts.insert(0, UserContent::text(new_text));
+                String::new()
+            }
+        };
+        entry.message = Message::User {
+            content: OneOrMany::many(parts).ok()?,
+        };
+        entry.error = None;
+
+        let responses_kept = self.entries[..index]
+            .iter()
+            .filter(|e| matches!(e.message, Message::Assistant { .. }))
+            .count();
+        self.token_usage.message_usages.truncate(responses_kept);
+        self.token_usage.recalculate_totals();
+        let removed = self.entries.len() - index - 1;
+        self.entries.truncate(index + 1);
+        self.regeneration_records
+            .retain(|record| record.message_index < index);
+        // Edits of the removed messages would point past the history
+        self.history_edits
+            .retain(|edit| edit.message_index <= index);
+        // The judge rated answers that are gone
+        self.quality_score = None;
+
+        let now = SystemTime::now();
+        let timestamp = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+        let edit = HistoryEdit::edited(index, &original, removed, timestamp);
+        self.history_edits.push(edit.clone());
+        self.updated_at = now;
+        Some(edit)
+    }
+
+    fn record_history_edit(
+        &mut self,
+        kind: HistoryEditKind,
+        index: usize,
+        role: &str,
+        text: &str,
+    ) -> HistoryEdit {
+        let now = SystemTime::now();
+        let timestamp = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+        let edit = HistoryEdit::new(kind, index, role, text, timestamp);
+        self.history_edits.push(edit.clone());
+        self.updated_at = now;
+        edit
+    }
+
+    pub fn history_edits(&self) -> &[HistoryEdit] {
+        &self.history_edits
+    }
+
+    /// Serialize the history edit log to JSON string
+    pub fn serialize_history_edits(&self) -> Result<String> {
+        serde_json::to_string(&self.history_edits).context("Failed to serialize history edits")
+    }
+
+    /// Deserialize the history edit log from JSON string
+    pub fn deserialize_history_edits(json: &str) -> Result<Vec<HistoryEdit>> {
+        serde_json::from_str(json).context("Failed to deserialize history edits")
+    }
+
+    /// Remove a pinned item by ID. Returns `true` if an item was removed.
+    pub fn unpin_item(&mut self, id: &str) -> bool {
+        let initial_len = self.pinned_items.len();
+        self.pinned_items.retain(|item| item.id != id);
+        let removed = self.pinned_items.len() < initial_len;
+        if removed {
+            self.updated_at = SystemTime::now();
+        }
+        removed
+    }
+
+    /// Shared handle to the variable scratchpad (passed to the agent factory on rebuilds).
+    pub fn variables(&self) -> ConversationVariables {
+        self.variables.clone()
+    }
+
+    /// Snapshot of the current variables, sorted by name.
+    pub fn variables_snapshot(&self) -> BTreeMap<String, String> {
+        self.variables.lock().clone()
+    }
+
+    /// Delete a variable (e.g. from the variables panel). Returns `true` if it existed.
+    pub fn remove_variable(&mut self, name: &str) -> bool {
+        let removed = self.variables.lock().remove(name).is_some();
+        if removed {
+            self.updated_at = SystemTime::now();
+        }
+        removed
+    }
+
+    /// Shared handle to the planner-mode plan (passed to the agent factory on rebuilds).
+    pub fn execution_plan(&self) -> SharedPlan {
+        self.execution_plan.clone()
+    }
+
+    /// Snapshot of the current plan, if the agent has created one.
+    pub fn execution_plan_snapshot(&self) -> Option<ExecutionPlan> {
+        self.execution_plan.lock().clone()
+    }
+
+    /// Discard the current plan (e.g. dismissed from the checklist).
+    pub fn clear_execution_plan(&mut self) {
+        if self.execution_plan.lock().take().is_some() {
+            self.updated_at = SystemTime::now();
+        }
+    }
+
+    /// Shared confidential flag (passed to the agent factory on rebuilds).
+    pub fn confidential_flag(&self) -> ConfidentialFlag {
+        self.confidential.clone()
+    }
+
+    /// Docker resources tracked for this conversation (passed to the agent
+    /// factory on rebuilds and consulted on deletion).
+    pub fn docker_resources(&self) -> DockerResources {
+        self.docker_resources.clone()
+    }
+
+    /// Whether network tools are blocked for this conversation.
+    pub fn is_confidential(&self) -> bool {
+        self.confidential.load(Ordering::Relaxed)
+    }
+
+    /// Mark the conversation confidential (or not). Takes effect on the next
+    /// tool call without rebuilding the agent.
+    pub fn set_confidential(&mut self, confidential: bool) {
+        if self.confidential.swap(confidential, Ordering::Relaxed) != confidential {
+            self.updated_at = SystemTime::now();
+        }
+    }
+
+    /// How much of the stored history is sent to the model each turn.
+    pub fn history_policy(&self) -> HistoryPolicy {
+        self.history_policy
+    }
+
+    /// Change the history policy. Applies from the next turn; the stored
+    /// history is not modified.
+    pub fn set_history_policy(&mut self, policy: HistoryPolicy) {
+        if self.history_policy != policy {
+            self.history_policy = policy;
+            self.updated_at = SystemTime::now();
+        }
+    }
+
+    /// MCP servers switched off for this conversation only.
+    pub fn disabled_mcp_servers(&self) -> &BTreeSet<String> {
+        &self.disabled_mcp_servers
+    }
+
+    /// Whether the tools of the MCP server `name` are offered in this
+    /// conversation (provided the server is enabled globally).
+    pub fn is_mcp_server_enabled(&self, name: &str) -> bool {
+        !self.disabled_mcp_servers.contains(name)
+    }
+
+    /// Switch an MCP server on or off for this conversation. Takes effect
+    /// once the agent is rebuilt.
+    pub fn set_mcp_server_enabled(&mut self, name: &str, enabled: bool) {
+        let changed = if enabled {
+            self.disabled_mcp_servers.remove(name)
+        } else {
+            self.disabled_mcp_servers.insert(name.to_string())
+        };
+        if changed {
+            self.updated_at = SystemTime::now();
+        }
+    }
+
+    /// Tools switched off for this conversation only (passed to the agent
+    /// factory on rebuilds).
+    pub fn disabled_tools(&self) -> &BTreeSet<String> {
+        &self.disabled_tools
+    }
+
+    /// Switch a tool on or off for this conversation. Takes effect once the
+    /// agent is rebuilt.
+    pub fn set_tool_enabled(&mut self, name: &str, enabled: bool) {
+        let changed = if enabled {
+            self.disabled_tools.remove(name)
+        } else {
+            self.disabled_tools.insert(name.to_string())
+        };
+        if changed {
+            self.updated_at = SystemTime::now();
+        }
+    }
+
+    pub fn label(&self) -> &ConversationLabel {
+        &self.label
+    }
+
+    /// Set the sidebar label. Leaves `updated_at` alone so the conversation
+    /// keeps its place in the list.
+    pub fn set_label(&mut self, label: ConversationLabel) {
+        self.label = label;
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Lock the conversation against new messages, deletions and
+    /// regenerations, or unlock it. Like labels, this leaves `updated_at` alone.
+    pub fn set_locked(&mut self, locked: bool) {
+        self.locked = locked;
+    }
+
+    pub fn session_budget(&self) -> Option<&BudgetTracker> {
+        self.session_budget.as_ref()
+    }
+
+    /// Set or clear the budget of the next agentic task.
+    pub fn set_session_budget(&mut self, budget: Option<BudgetTracker>) {
+        self.session_budget = budget;
+    }
+
+    /// Take the budget for the stream that is starting; the stream puts it
+    /// back if the task continues with a follow-up.
+    pub fn take_session_budget(&mut self) -> Option<BudgetTracker> {
+        self.session_budget.take()
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Replace the tags. Like labels, this leaves `updated_at` alone.
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+    }
+
+    pub fn folder(&self) -> Option<&str> {
+        self.folder.as_deref()
+    }
+
+    /// Move the conversation to another folder (None = no folder). Like
+    /// labels, this leaves `updated_at` alone.
+    pub fn set_folder(&mut self, folder: Option<String>) {
+        self.folder = folder;
+    }
+
+    pub fn quality_score(&self) -> Option<&QualityScore> {
+        self.quality_score.as_ref()
+    }
+
+    /// Store a judge rating. Like labels, this leaves `updated_at` alone.
+    pub fn set_quality_score(&mut self, score: QualityScore) {
+        self.quality_score = Some(score);
+    }
+
+    /// Serialize the quality score to JSON.
+    pub fn serialize_quality_score(&self) -> Result<Option<String>> {
+        self.quality_score
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .context("Failed to serialize quality score")
+    }
+
     /// Set the agent and model ID synchronously (for model switching without blocking)
     pub fn set_agent(
         &mut self,
@@ -754,6 +1682,35 @@ impl Conversation {
     }
 }
 
+/// Role label and text content of a user or assistant message.
+pub(crate) fn role_and_text(message: &Message) -> Option<(&'static str, String)> {
+    match message {
+        Message::User { content } => Some((
+            "User",
+            content
+                .iter()
+                .filter_map(|c| match c {
+                    rig_core::message::UserContent::Text(t) => Some(t.text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )),
+        Message::Assistant { content, .. } => Some((
+            "Assistant",
+            content
+                .iter()
+                .filter_map(|ac| match ac {
+                    AssistantContent::Text(t) => Some(t.text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join(""),
+        )),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -837,6 +1794,184 @@ mod tests {
         assert_eq!(deserialized[1].original_text, "Attempt 2");
     }
 
+    #[test]
+    fn delete_message_shifts_later_records() {
+        use crate::testing::{ConversationDataBuilder, FakeAgentClient};
+
+        let mut conv = ConversationDataBuilder::new("c")
+            .user("First question")
+            .assistant("First answer")
+            .user("Second question")
+            .assistant("Second answer")
+            .build_conversation(&FakeAgentClient::new())
+            .unwrap();
+        conv.record_regeneration(1, "Old first answer".to_string(), 0);
+        conv.record_regeneration(3, "Old second answer".to_string(), 0);
+        conv.record_history_edit(
+            HistoryEditKind::TraceDeleted,
+            1,
+            "Assistant",
+            "First answer",
+        );
+        conv.record_history_edit(
+            HistoryEditKind::TraceDeleted,
+            3,
+            "Assistant",
+            "Second answer",
+        );
+
+        let edit = conv.delete_message(1).unwrap();
+        assert_eq!(edit.kind, HistoryEditKind::MessageDeleted);
+        assert_eq!(conv.message_count(), 3);
+
+        let regenerated: Vec<_> = conv
+            .regeneration_records()
+            .iter()
+            .map(|record| (record.message_index, record.original_text.as_str()))
+            .collect();
+        assert_eq!(regenerated, [(2, "Old second answer")]);
+
+        let edits: Vec<_> = conv
+            .history_edits()
+            .iter()
+            .map(|edit| (edit.kind, edit.message_index))
+            .collect();
+        assert_eq!(
+            edits,
+            [
+                (HistoryEditKind::TraceDeleted, 2),
+                (HistoryEditKind::MessageDeleted, 1)
+            ]
+        );
+
+        // A second deletion at the same position keeps the first one's record
+        conv.delete_message(1).unwrap();
+        let edits: Vec<_> = conv
+            .history_edits()
+            .iter()
+            .map(|edit| (edit.kind, edit.message_index))
+            .collect();
+        assert_eq!(
+            edits,
+            [
+                (HistoryEditKind::TraceDeleted, 1),
+                (HistoryEditKind::MessageDeleted, 1),
+                (HistoryEditKind::MessageDeleted, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn edit_user_message_drops_records_of_removed_messages() {
+        use crate::models::quality_score::QualityScore;
+        use crate::testing::{ConversationDataBuilder, FakeAgentClient};
+
+        let mut conv = ConversationDataBuilder::new("c")
+            .user("First question")
+            .assistant("First answer")
+            .user("Second question")
+            .assistant("Second answer")
+            .build_conversation(&FakeAgentClient::new())
+            .unwrap();
+        conv.record_history_edit(
+            HistoryEditKind::TraceDeleted,
+            1,
+            "Assistant",
+            "First answer",
+        );
+        conv.record_history_edit(
+            HistoryEditKind::TraceDeleted,
+            3,
+            "Assistant",
+            "Second answer",
+        );
+        conv.set_quality_score(QualityScore {
+            helpfulness: 4,
+            tool_efficiency: 4,
+            resolution: 5,
+            rationale: None,
+            judge_model_id: "judge".to_string(),
+            message_count: 4,
+            scored_at: 0,
+        });
+
+        let edit = conv
+            .edit_user_message(2, "Second question, reworded".to_string())
+            .unwrap();
+        assert_eq!(edit.messages_removed, 1);
+        assert_eq!(conv.message_count(), 3);
+        assert!(conv.quality_score().is_none());
+
+        let edits: Vec<_> = conv
+            .history_edits()
+            .iter()
+            .map(|edit| (edit.kind, edit.message_index))
+            .collect();
+        assert_eq!(
+            edits,
+            [
+                (HistoryEditKind::TraceDeleted, 1),
+                (HistoryEditKind::MessageEdited, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn compact_history_shifts_history_edits() {
+        use crate::testing::{ConversationDataBuilder, FakeAgentClient};
+        use crate::token_budget::summarizer::SummarizationResult;
+
+        let client = FakeAgentClient::new();
+        let mut conv = ConversationDataBuilder::new("c")
+            .user("First question")
+            .assistant("First answer")
+            .build_conversation(&client)
+            .unwrap();
+        let source = ConversationDataBuilder::new("s")
+            .user("Merged question")
+            .assistant("Merged answer")
+            .user("Later question")
+            .assistant("Later answer")
+            .build_conversation(&client)
+            .unwrap();
+        conv.record_history_edit(
+            HistoryEditKind::TraceDeleted,
+            1,
+            "Assistant",
+            "First answer",
+        );
+        conv.merge_from(source);
+        conv.record_history_edit(
+            HistoryEditKind::TraceDeleted,
+            5,
+            "Assistant",
+            "Later answer",
+        );
+
+        // Summarize the first three messages, across the merge boundary at 2
+        let result = SummarizationResult {
+            new_history: vec![Message::user("Summary")],
+            messages_summarized: 3,
+            estimated_tokens_freed: 10,
+        };
+        conv.compact_history(&result, CompactionTrigger::Manual)
+            .unwrap();
+        assert_eq!(conv.message_count(), 4);
+
+        let edits: Vec<_> = conv
+            .history_edits()
+            .iter()
+            .map(|edit| (edit.kind, edit.message_index))
+            .collect();
+        assert_eq!(
+            edits,
+            [
+                (HistoryEditKind::ConversationMerged, 0),
+                (HistoryEditKind::TraceDeleted, 3)
+            ]
+        );
+    }
+
     #[test]
     fn deserialize_traces_preserves_running_sub_agent_source() {
         let mut trace = SystemTrace::new_sub_agent("review this thread", ToolSource::HiveCloud);
@@ -0,0 +1,147 @@
+//! Date grouping and ordering for the conversation list.
+//!
+//! The sidebar buckets conversations by `updated_at` into Today / Yesterday /
+//! This week / Older. A streaming conversation bumps its `updated_at` on
+//! every save, so [`keep_positions`] holds streaming entries where they were
+//! until the stream ends instead of letting them jump to the top.
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Order of the conversation list in the sidebar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversationSort {
+    /// Most recently changed first
+    #[default]
+    Updated,
+    /// Most recently opened first
+    Viewed,
+    /// Newest first
+    Created,
+}
+
+impl ConversationSort {
+    pub const ALL: [ConversationSort; 3] = [Self::Updated, Self::Viewed, Self::Created];
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DateGroup {
+    Today,
+    Yesterday,
+    ThisWeek,
+    Older,
+}
+
+impl DateGroup {
+    /// Group for a Unix timestamp, by local calendar day relative to `now`.
+    pub fn of(updated_at: i64, now: i64) -> Self {
+        let (Some(date), Some(today)) = (local_date(updated_at), local_date(now)) else {
+            return Self::Older;
+        };
+        match (today - date).num_days() {
+            ..=0 => Self::Today,
+            1 => Self::Yesterday,
+            2..=6 => Self::ThisWeek,
+            _ => Self::Older,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Today => "Today",
+            Self::Yesterday => "Yesterday",
+            Self::ThisWeek => "This week",
+            Self::Older => "Older",
+        }
+    }
+}
+
+fn local_date(timestamp: i64) -> Option<NaiveDate> {
+    DateTime::from_timestamp(timestamp, 0).map(|utc| utc.with_timezone(&Local).date_naive())
+}
+
+/// "just now" / "5 min ago" / "3 h ago" / "2 days ago" / "4 weeks ago".
+pub fn relative_time(updated_at: i64, now: i64) -> String {
+    let minutes = (now - updated_at).max(0) / 60;
+    match minutes {
+        0 => "just now".to_string(),
+        m if m < 60 => format!("{m} min ago"),
+        m if m < 60 * 24 => format!("{} h ago", m / 60),
+        m if m < 60 * 24 * 2 => "1 day ago".to_string(),
+        m if m < 60 * 24 * 14 => format!("{} days ago", m / (60 * 24)),
+        m => format!("{} weeks ago", m / (60 * 24 * 7)),
+    }
+}
+
+/// Reorder `next` so that entries for which `hold` returns true stay at the
+/// index they had in `previous`. Everything else keeps the order of `next`.
+pub fn keep_positions<T>(
+    previous: &[String],
+    next: Vec<T>,
+    id_of: impl Fn(&T) -> &str,
+    hold: impl Fn(&str) -> bool,
+) -> Vec<T> {
+    let mut held = Vec::new();
+    let mut rest = Vec::with_capacity(next.len());
+    for item in next {
+        match previous.iter().position(|id| id == id_of(&item)) {
+            Some(index) if hold(id_of(&item)) => held.push((index, item)),
+            _ => rest.push(item),
+        }
+    }
+    held.sort_by_key(|(index, _)| *index);
+    for (index, item) in held {
+        let index = index.min(rest.len());
+        rest.insert(index, item);
+    }
+    rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn local_noon(date: NaiveDate) -> i64 {
+        Local
+            .from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap())
+            .unwrap()
+            .timestamp()
+    }
+
+    #[test]
+    fn groups_by_local_day() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap();
+        let now = local_noon(today);
+        let days_ago = |days| local_noon(today - Duration::days(days));
+        assert_eq!(DateGroup::of(now, now), DateGroup::Today);
+        assert_eq!(DateGroup::of(days_ago(1), now), DateGroup::Yesterday);
+        assert_eq!(DateGroup::of(days_ago(6), now), DateGroup::ThisWeek);
+        assert_eq!(DateGroup::of(days_ago(7), now), DateGroup::Older);
+    }
+
+    #[test]
+    fn relative_time_labels() {
+        let now = 1_000_000;
+        assert_eq!(relative_time(now - 30, now), "just now");
+        assert_eq!(relative_time(now - 5 * 60, now), "5 min ago");
+        assert_eq!(relative_time(now - 3 * 3600, now), "3 h ago");
+        assert_eq!(relative_time(now - 30 * 3600, now), "1 day ago");
+        assert_eq!(relative_time(now - 21 * 86400, now), "3 weeks ago");
+    }
+
+    #[test]
+    fn held_entries_keep_their_index() {
+        let previous: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        // "c" was updated by a stream and sorted to the top.
+        let next = vec!["c", "a", "b"];
+        let ordered = keep_positions(&previous, next, |id: &&str| *id, |id| id == "c");
+        assert_eq!(ordered, vec!["a", "b", "c"]);
+
+        // Once the stream ends it moves up.
+        let next = vec!["c", "a", "b"];
+        let ordered = keep_positions(&previous, next, |id: &&str| *id, |_| false);
+        assert_eq!(ordered, vec!["c", "a", "b"]);
+    }
+}
@@ -0,0 +1,101 @@
+//! Color and emoji label a user can give a conversation to tell work streams
+//! apart in the sidebar. Stored as JSON in the `label` column and carried in
+//! the sidebar metadata, so it is available without loading the conversation.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelColor {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+    Gray,
+}
+
+impl LabelColor {
+    pub const ALL: [LabelColor; 7] = [
+        Self::Red,
+        Self::Orange,
+        Self::Yellow,
+        Self::Green,
+        Self::Blue,
+        Self::Purple,
+        Self::Gray,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Red => "Red",
+            Self::Orange => "Orange",
+            Self::Yellow => "Yellow",
+            Self::Green => "Green",
+            Self::Blue => "Blue",
+            Self::Purple => "Purple",
+            Self::Gray => "Gray",
+        }
+    }
+
+    /// RGB value used to draw the label.
+    pub fn rgb(self) -> u32 {
+        match self {
+            Self::Red => 0xef4444,
+            Self::Orange => 0xf97316,
+            Self::Yellow => 0xeab308,
+            Self::Green => 0x22c55e,
+            Self::Blue => 0x3b82f6,
+            Self::Purple => 0xa855f7,
+            Self::Gray => 0x6b7280,
+        }
+    }
+}
+
+/// Emoji offered by the label picker.
+pub const LABEL_ICONS: &[&str] = &["💼", "🏠", "🧪", "🐛", "📚", "✍️", "💡", "🚀", "⭐", "🔒"];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationLabel {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<LabelColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+}
+
+impl ConversationLabel {
+    pub fn is_empty(&self) -> bool {
+        self.color.is_none() && self.icon.is_none()
+    }
+
+    /// Parse the stored JSON; anything unreadable counts as no label.
+    pub fn from_json(json: &str) -> Self {
+        serde_json::from_str(json).unwrap_or_default()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let label = ConversationLabel {
+            color: Some(LabelColor::Blue),
+            icon: Some("🚀".to_string()),
+        };
+        assert_eq!(ConversationLabel::from_json(&label.to_json()), label);
+        assert_eq!(ConversationLabel::default().to_json(), "{}");
+    }
+
+    #[test]
+    fn unreadable_json_is_no_label() {
+        assert!(ConversationLabel::from_json("not json").is_empty());
+        assert!(ConversationLabel::from_json(r#"{"color":"teal"}"#).is_empty());
+    }
+}
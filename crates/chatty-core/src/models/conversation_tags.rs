@@ -0,0 +1,126 @@
+//! Tags and folders for organizing conversations. A conversation has any
+//! number of tags and at most one folder, a `/`-separated path such as
+//! `clients/acme`. Both are stored in their own columns and carried in the
+//! sidebar metadata, like the label.
+
+/// Clean up a tag typed by the user: surrounding whitespace and a leading
+/// `#` are dropped and inner whitespace collapses to single spaces. Tags
+/// compare case-insensitively but keep the case they were first given in.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#');
+    let tag = tag.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Clean up a folder path: segments are trimmed and empty ones dropped, so
+/// `" /clients// acme/"` becomes `clients/acme`. `None` for an empty path.
+pub fn normalize_folder(path: &str) -> Option<String> {
+    let folder = path
+        .split('/')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    (!folder.is_empty()).then_some(folder)
+}
+
+/// Parse a comma-separated tag list as typed in the tags dialog, dropping
+/// empty entries and repeats.
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').filter_map(normalize_tag) {
+        if !has_tag(&tags, &tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Whether `tags` contains `tag`, ignoring case.
+pub fn has_tag(tags: &[String], tag: &str) -> bool {
+    tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+}
+
+/// Whether a conversation in `folder` is shown when filtering by `filter`:
+/// the folder itself or any folder nested in it.
+pub fn in_folder(folder: Option<&str>, filter: &str) -> bool {
+    folder.is_some_and(|folder| {
+        folder == filter
+            || folder
+                .strip_prefix(filter)
+                .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+/// Every folder path and its parents, sorted, e.g. `clients` and
+/// `clients/acme` for a conversation in `clients/acme`.
+pub fn folder_tree<'a>(folders: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut tree: Vec<String> = Vec::new();
+    for folder in folders {
+        let mut end = 0;
+        for segment in folder.split('/') {
+            end += segment.len();
+            tree.push(folder[..end].to_string());
+            end += 1;
+        }
+    }
+    tree.sort();
+    tree.dedup();
+    tree
+}
+
+/// Parse the stored JSON tag list; anything unreadable counts as no tags.
+pub fn tags_from_json(json: &str) -> Vec<String> {
+    serde_json::from_str(json).unwrap_or_default()
+}
+
+pub fn tags_to_json(tags: &[String]) -> String {
+    serde_json::to_string(tags).unwrap_or_else(|_| "[]".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_tags_and_folders() {
+        assert_eq!(
+            normalize_tag("  #client   work "),
+            Some("client work".into())
+        );
+        assert_eq!(normalize_tag(" # "), None);
+        assert_eq!(
+            normalize_folder(" /clients// acme/"),
+            Some("clients/acme".into())
+        );
+        assert_eq!(normalize_folder("/ /"), None);
+        assert_eq!(
+            parse_tags("rust, #Rust,, client work "),
+            ["rust", "client work"]
+        );
+    }
+
+    #[test]
+    fn folder_filter_includes_subfolders_only() {
+        assert!(in_folder(Some("clients"), "clients"));
+        assert!(in_folder(Some("clients/acme"), "clients"));
+        assert!(!in_folder(Some("clientsfoo"), "clients"));
+        assert!(!in_folder(None, "clients"));
+    }
+
+    #[test]
+    fn folder_tree_lists_parents_once() {
+        assert_eq!(
+            folder_tree(["clients/acme", "clients/globex", "home"]),
+            ["clients", "clients/acme", "clients/globex", "home"]
+        );
+    }
+
+    #[test]
+    fn tags_round_trip_through_json() {
+        let tags = vec!["rust".to_string(), "Client Work".to_string()];
+        assert_eq!(tags_from_json(&tags_to_json(&tags)), tags);
+        assert!(tags_from_json("not json").is_empty());
+        assert!(has_tag(&tags, "client work"));
+    }
+}
@@ -1,23 +1,41 @@
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::UNIX_EPOCH;
 
-use crate::repositories::ConversationMetadata;
+use anyhow::{Result, bail};
+
+use crate::repositories::{ConversationMetadata, MessageSearchHit};
 
 use super::conversation::Conversation;
+use super::conversation_groups::ConversationSort;
+use super::conversation_label::ConversationLabel;
+use super::conversation_tags;
 
 /// Maximum number of full conversation objects kept in memory.
 /// When the cache exceeds this limit, the least recently used conversations are evicted.
 /// They will be re-loaded from SQLite on demand if the user navigates back.
 const MAX_CACHED_CONVERSATIONS: usize = 10;
 
+/// A conversation found by [`ConversationsStore::search`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversationMatch {
+    pub id: String,
+    pub title: String,
+    /// History index of the best matching message, to jump to when opened
+    pub history_index: Option<usize>,
+    /// Excerpt of that message
+    pub snippet: Option<String>,
+}
+
 /// Global store for all conversations.
 ///
 /// Two-layer design:
 /// - `metadata`: always loaded at startup (lightweight — just id/title/cost)
 /// - `conversations`: lazily populated when a conversation is selected, with LRU eviction
 pub struct ConversationsStore {
-    /// Lightweight metadata list, sorted by updated_at descending.
+    /// Lightweight metadata list, sorted by `sort` (most recent first).
     /// This is the source of truth for the sidebar and navigation.
     metadata: Vec<ConversationMetadata>,
+    sort: ConversationSort,
     /// Full conversation data, populated on demand when the user selects a conversation.
     /// Bounded to `MAX_CACHED_CONVERSATIONS` entries via LRU eviction.
     conversations: HashMap<String, Conversation>,
@@ -33,6 +51,7 @@ impl ConversationsStore {
     pub fn new() -> Self {
         Self {
             metadata: Vec::new(),
+            sort: ConversationSort::default(),
             conversations: HashMap::new(),
             access_order: VecDeque::new(),
             active_conversation_id: None,
@@ -45,6 +64,25 @@ impl ConversationsStore {
     /// Replace the metadata list (called once at startup after `load_metadata()`).
     pub fn set_metadata(&mut self, metadata: Vec<ConversationMetadata>) {
         self.metadata = metadata;
+        self.resort();
+    }
+
+    /// Current order of the metadata list.
+    pub fn sort(&self) -> ConversationSort {
+        self.sort
+    }
+
+    /// Change the order of the metadata list.
+    pub fn set_sort(&mut self, sort: ConversationSort) {
+        self.sort = sort;
+        self.resort();
+    }
+
+    /// Sort most recent first by the current order; ties keep their place.
+    fn resort(&mut self) {
+        let sort = self.sort;
+        self.metadata
+            .sort_by_key(|m| std::cmp::Reverse(m.sort_time(sort)));
     }
 
     /// Total number of conversations (based on metadata, not the in-memory cache).
@@ -61,12 +99,103 @@ impl ConversationsStore {
             .collect()
     }
 
-    /// All conversation IDs sorted by updated_at descending (for keyboard navigation).
+    /// All conversation IDs in sidebar order (for keyboard navigation).
     pub fn all_metadata_ids(&self) -> Vec<String> {
         self.metadata.iter().map(|m| m.id.clone()).collect()
     }
 
-    /// Insert or update a single metadata entry and re-sort by updated_at descending.
+    /// Last update time (Unix seconds) of a conversation, from metadata.
+    pub fn updated_at(&self, id: &str) -> Option<i64> {
+        self.metadata
+            .iter()
+            .find(|m| m.id == id)
+            .map(|m| m.updated_at)
+    }
+
+    /// Time a conversation is ordered and date-grouped by under the current sort.
+    pub fn sort_time(&self, id: &str) -> Option<i64> {
+        self.metadata
+            .iter()
+            .find(|m| m.id == id)
+            .map(|m| m.sort_time(self.sort))
+    }
+
+    /// Record that a conversation was opened at `viewed_at` (Unix seconds).
+    pub fn set_last_viewed(&mut self, id: &str, viewed_at: i64) {
+        if let Some(entry) = self.metadata.iter_mut().find(|m| m.id == id) {
+            entry.last_viewed_at = Some(viewed_at);
+            if self.sort == ConversationSort::Viewed {
+                self.resort();
+            }
+        }
+    }
+
+    /// Up to `limit` conversations that were opened before, most recently
+    /// viewed first, as (id, title). `exclude` is left out (the open one).
+    pub fn recently_viewed(&self, limit: usize, exclude: Option<&str>) -> Vec<(String, String)> {
+        let mut viewed: Vec<&ConversationMetadata> = self
+            .metadata
+            .iter()
+            .filter(|m| m.last_viewed_at.is_some() && Some(m.id.as_str()) != exclude)
+            .collect();
+        viewed.sort_by_key(|m| std::cmp::Reverse(m.last_viewed_at));
+        viewed
+            .into_iter()
+            .take(limit)
+            .map(|m| (m.id.clone(), m.title.clone()))
+            .collect()
+    }
+
+    /// Conversations matching `query`: those whose title contains every word
+    /// (in sidebar order) first, then those with a matching message in
+    /// `hits` (from `ConversationRepository::search_messages`, best first).
+    /// Each conversation appears once, with its best message hit if any.
+    pub fn search(&self, query: &str, hits: &[MessageSearchHit]) -> Vec<ConversationMatch> {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        if words.is_empty() {
+            return Vec::new();
+        }
+        let best_hit = |id: &str| hits.iter().find(|hit| hit.conversation_id == id);
+        let to_match =
+            |m: &ConversationMetadata, hit: Option<&MessageSearchHit>| ConversationMatch {
+                id: m.id.clone(),
+                title: m.title.clone(),
+                history_index: hit.map(|h| h.history_index),
+                snippet: hit.map(|h| h.snippet.clone()),
+            };
+
+        let mut matches: Vec<ConversationMatch> = self
+            .metadata
+            .iter()
+            .filter(|m| {
+                let title = m.title.to_lowercase();
+                words.iter().all(|w| title.contains(w.as_str()))
+            })
+            .map(|m| to_match(m, best_hit(&m.id)))
+            .collect();
+
+        for hit in hits {
+            if matches.iter().any(|m| m.id == hit.conversation_id) {
+                continue;
+            }
+            // Hits can outlive a conversation deleted since the search ran
+            if let Some(m) = self.metadata.iter().find(|m| m.id == hit.conversation_id) {
+                matches.push(to_match(m, Some(hit)));
+            }
+        }
+        matches
+    }
+
+    /// Title of a conversation, from metadata.
+    pub fn title(&self, id: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|m| m.id == id)
+            .map(|m| m.title.as_str())
+    }
+
+    /// Insert or update a single metadata entry and re-sort. A new entry is
+    /// created at `updated_at`.
     pub fn upsert_metadata(&mut self, id: &str, title: &str, total_cost: f64, updated_at: i64) {
         if let Some(entry) = self.metadata.iter_mut().find(|m| m.id == id) {
             entry.title = title.to_string();
@@ -78,11 +207,174 @@ impl ConversationsStore {
                 title: title.to_string(),
                 total_cost,
                 updated_at,
+                created_at: updated_at,
+                last_viewed_at: None,
+                label: ConversationLabel::default(),
+                tags: Vec::new(),
+                folder: None,
+                locked: false,
             });
         }
-        // Keep sorted: most recently updated first
+        self.resort();
+    }
+
+    /// Label of a conversation, from metadata.
+    pub fn label(&self, id: &str) -> Option<&ConversationLabel> {
+        self.metadata.iter().find(|m| m.id == id).map(|m| &m.label)
+    }
+
+    /// Set the label in metadata and, if loaded, on the conversation itself.
+    /// Does not change `updated_at`, so the conversation keeps its place.
+    pub fn set_label(&mut self, id: &str, label: ConversationLabel) {
+        if let Some(conversation) = self.conversations.get_mut(id) {
+            conversation.set_label(label.clone());
+        }
+        if let Some(entry) = self.metadata.iter_mut().find(|m| m.id == id) {
+            entry.label = label;
+        }
+    }
+
+    /// Whether a conversation is locked, from metadata.
+    pub fn is_locked(&self, id: &str) -> bool {
+        self.metadata.iter().any(|m| m.id == id && m.locked)
+    }
+
+    /// Lock or unlock a conversation in metadata and, if loaded, on the
+    /// conversation itself. Like labels, this does not change `updated_at`.
+    pub fn set_locked(&mut self, id: &str, locked: bool) {
+        if let Some(conversation) = self.conversations.get_mut(id) {
+            conversation.set_locked(locked);
+        }
+        if let Some(entry) = self.metadata.iter_mut().find(|m| m.id == id) {
+            entry.locked = locked;
+        }
+    }
+
+    /// Tags of a conversation, from metadata.
+    pub fn tags(&self, id: &str) -> &[String] {
         self.metadata
-            .sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+            .iter()
+            .find(|m| m.id == id)
+            .map_or(&[], |m| m.tags.as_slice())
+    }
+
+    /// Folder of a conversation, from metadata.
+    pub fn folder(&self, id: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|m| m.id == id)
+            .and_then(|m| m.folder.as_deref())
+    }
+
+    /// Every tag in use with the number of conversations carrying it, sorted
+    /// by name. Tags differing only in case are counted together under the
+    /// spelling seen first.
+    pub fn all_tags(&self) -> Vec<(String, usize)> {
+        let mut tags: Vec<(String, usize)> = Vec::new();
+        for tag in self.metadata.iter().flat_map(|m| &m.tags) {
+            match tags.iter_mut().find(|(t, _)| t.eq_ignore_ascii_case(tag)) {
+                Some((_, count)) => *count += 1,
+                None => tags.push((tag.clone(), 1)),
+            }
+        }
+        tags.sort_by_key(|(tag, _)| tag.to_lowercase());
+        tags
+    }
+
+    /// Every folder in use and their parent folders, sorted.
+    pub fn all_folders(&self) -> Vec<String> {
+        conversation_tags::folder_tree(self.metadata.iter().filter_map(|m| m.folder.as_deref()))
+    }
+
+    /// Conversations with `tag` in `folder` (or a subfolder), as sidebar
+    /// tuples (id, title, cost) in sidebar order. `None` matches anything.
+    pub fn list_matching(
+        &self,
+        tag: Option<&str>,
+        folder: Option<&str>,
+    ) -> Vec<(String, String, Option<f64>)> {
+        self.metadata
+            .iter()
+            .filter(|m| tag.is_none_or(|tag| conversation_tags::has_tag(&m.tags, tag)))
+            .filter(|m| {
+                folder
+                    .is_none_or(|folder| conversation_tags::in_folder(m.folder.as_deref(), folder))
+            })
+            .map(|m| (m.id.clone(), m.title.clone(), Some(m.total_cost)))
+            .collect()
+    }
+
+    /// Replace the tags of a conversation in metadata and, if loaded, on the
+    /// conversation itself. Like labels, this does not change `updated_at`.
+    pub fn set_tags(&mut self, id: &str, tags: Vec<String>) {
+        if let Some(conversation) = self.conversations.get_mut(id) {
+            conversation.set_tags(tags.clone());
+        }
+        if let Some(entry) = self.metadata.iter_mut().find(|m| m.id == id) {
+            entry.tags = tags;
+        }
+    }
+
+    /// Add `tag` to every conversation in `ids` that lacks it. Returns the
+    /// IDs that changed, for saving.
+    pub fn add_tag(&mut self, ids: &[String], tag: &str) -> Vec<String> {
+        let Some(tag) = conversation_tags::normalize_tag(tag) else {
+            return Vec::new();
+        };
+        self.update_tags(ids, |tags| {
+            if conversation_tags::has_tag(tags, &tag) {
+                return false;
+            }
+            tags.push(tag.clone());
+            true
+        })
+    }
+
+    /// Remove `tag` (ignoring case) from every conversation in `ids`.
+    /// Returns the IDs that changed, for saving.
+    pub fn remove_tag(&mut self, ids: &[String], tag: &str) -> Vec<String> {
+        self.update_tags(ids, |tags| {
+            let before = tags.len();
+            tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+            tags.len() != before
+        })
+    }
+
+    fn update_tags(
+        &mut self,
+        ids: &[String],
+        mut update: impl FnMut(&mut Vec<String>) -> bool,
+    ) -> Vec<String> {
+        let mut changed = Vec::new();
+        for id in ids {
+            let mut tags = self.tags(id).to_vec();
+            if update(&mut tags) {
+                self.set_tags(id, tags);
+                changed.push(id.clone());
+            }
+        }
+        changed
+    }
+
+    /// Move every conversation in `ids` to `folder` (None = out of any
+    /// folder). Returns the IDs that changed, for saving.
+    pub fn set_folder(&mut self, ids: &[String], folder: Option<&str>) -> Vec<String> {
+        let folder = folder.and_then(conversation_tags::normalize_folder);
+        let mut changed = Vec::new();
+        for id in ids {
+            let Some(entry) = self.metadata.iter_mut().find(|m| &m.id == id) else {
+                continue;
+            };
+            if entry.folder == folder {
+                continue;
+            }
+            entry.folder = folder.clone();
+            if let Some(conversation) = self.conversations.get_mut(id) {
+                conversation.set_folder(folder.clone());
+            }
+            changed.push(id.clone());
+        }
+        changed
     }
 
     /// Remove a conversation from the metadata list.
@@ -152,6 +444,11 @@ impl ConversationsStore {
         self.streaming_ids.remove(id);
     }
 
+    /// Whether a conversation has an active stream.
+    pub fn is_streaming(&self, id: &str) -> bool {
+        self.streaming_ids.contains(id)
+    }
+
     /// Number of full conversations currently cached in memory.
     pub fn cached_count(&self) -> usize {
         self.conversations.len()
@@ -183,6 +480,67 @@ impl ConversationsStore {
         in_cache || in_metadata
     }
 
+    /// Append the history of `source_id` to `target_id` (see
+    /// [`Conversation::merge_from`]) and delete the source. Both must be
+    /// loaded, idle and unlocked. Only in-memory state changes: the caller
+    /// saves the target and deletes the source from disk, and must delete the
+    /// source only after the target's save succeeded, or a failed save loses
+    /// the merged messages. Returns the number of messages added to the
+    /// target.
+    pub fn merge(&mut self, source_id: &str, target_id: &str) -> Result<usize> {
+        if source_id == target_id {
+            bail!("A conversation cannot be merged into itself");
+        }
+        if self.is_streaming(source_id) || self.is_streaming(target_id) {
+            bail!("Wait for the response to finish before merging");
+        }
+        match self.conversations.get(target_id) {
+            Some(target) if target.is_locked() => {
+                bail!(
+                    "\"{}\" is locked; unlock it to merge into it",
+                    target.title()
+                )
+            }
+            Some(_) => {}
+            None => bail!("Conversation {target_id} is not loaded"),
+        }
+        if self.is_locked(source_id)
+            || self
+                .conversations
+                .get(source_id)
+                .is_some_and(Conversation::is_locked)
+        {
+            bail!(
+                "\"{}\" is locked; unlock it to merge it",
+                self.title(source_id).unwrap_or_default()
+            );
+        }
+        let Some(source) = self.conversations.remove(source_id) else {
+            bail!("Conversation {source_id} is not loaded");
+        };
+        let was_active = self.active_conversation_id.as_deref() == Some(source_id);
+        self.delete_conversation(source_id);
+
+        let Some(target) = self.conversations.get_mut(target_id) else {
+            bail!("Conversation {target_id} is not loaded");
+        };
+        let added = target.merge_from(source);
+        let title = target.title().to_string();
+        let cost = target.token_usage().total_estimated_cost_usd;
+        let tags = target.tags().to_vec();
+        let updated_at = target
+            .updated_at()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        self.upsert_metadata(target_id, &title, cost, updated_at);
+        self.set_tags(target_id, tags);
+        if was_active {
+            self.set_active_by_id(target_id.to_string());
+        }
+        Ok(added)
+    }
+
     // ── Active conversation ───────────────────────────────────────────────────
 
     /// Set the active conversation ID unconditionally (does not validate against metadata).
@@ -222,6 +580,16 @@ impl ConversationsStore {
 
     // ── Legacy helpers (kept for compatibility) ───────────────────────────────
 
+    /// Shell sessions of the loaded conversations, for shutting them down on quit.
+    pub fn shell_sessions(
+        &self,
+    ) -> Vec<std::sync::Arc<crate::services::shell_service::ShellSession>> {
+        self.conversations
+            .values()
+            .filter_map(|conv| conv.shell_session())
+            .collect()
+    }
+
     /// List the N most recent conversations from the in-memory cache.
     /// Prefer `list_recent_metadata()` for sidebar display.
     ///
@@ -305,6 +673,132 @@ mod tests {
         assert_eq!(all[0].0, "conv-0"); // conv-0 is now first (most recent)
     }
 
+    #[test]
+    fn set_label_keeps_label_across_upserts_and_order() {
+        let mut store = make_store_with_n_entries(3);
+        let label = ConversationLabel {
+            color: Some(crate::models::LabelColor::Green),
+            icon: None,
+        };
+        store.set_label("conv-0", label.clone());
+        assert_eq!(store.all_metadata_ids()[2], "conv-0");
+
+        store.upsert_metadata("conv-0", "Renamed", 0.0, 0);
+        assert_eq!(store.label("conv-0"), Some(&label));
+    }
+
+    #[test]
+    fn set_locked_survives_upserts() {
+        let mut store = make_store_with_n_entries(2);
+        store.set_locked("conv-1", true);
+        store.upsert_metadata("conv-1", "Renamed", 0.0, 0);
+
+        assert!(store.is_locked("conv-1"));
+        assert!(!store.is_locked("conv-0"));
+        store.set_locked("conv-1", false);
+        assert!(!store.is_locked("conv-1"));
+    }
+
+    #[test]
+    fn bulk_tag_operations_report_changed_conversations() {
+        let mut store = make_store_with_n_entries(3);
+        let ids = store.all_metadata_ids();
+        store.set_tags("conv-0", vec!["Rust".to_string()]);
+
+        assert_eq!(store.add_tag(&ids, " #rust "), ["conv-2", "conv-1"]);
+        assert_eq!(store.tags("conv-0"), ["Rust"]);
+        assert_eq!(store.all_tags(), [("rust".to_string(), 3)]);
+        assert!(store.add_tag(&ids, "#").is_empty());
+
+        assert_eq!(store.remove_tag(&ids[..1], "RUST"), ["conv-2"]);
+        assert_eq!(store.list_matching(Some("rust"), None).len(), 2);
+    }
+
+    #[test]
+    fn folder_moves_and_filters_include_subfolders() {
+        let mut store = make_store_with_n_entries(3);
+        let moved = store.set_folder(
+            &["conv-0".to_string(), "conv-1".to_string()],
+            Some("clients/ acme/"),
+        );
+        assert_eq!(moved, ["conv-0", "conv-1"]);
+        store.set_folder(&["conv-2".to_string()], Some("clients"));
+        assert!(
+            store
+                .set_folder(&["conv-2".to_string()], Some("clients"))
+                .is_empty()
+        );
+
+        assert_eq!(store.folder("conv-0"), Some("clients/acme"));
+        assert_eq!(store.all_folders(), ["clients", "clients/acme"]);
+        let in_clients: Vec<String> = store
+            .list_matching(None, Some("clients"))
+            .into_iter()
+            .map(|(id, _, _)| id)
+            .collect();
+        assert_eq!(in_clients, ["conv-2", "conv-1", "conv-0"]);
+        assert_eq!(store.list_matching(None, Some("clients/acme")).len(), 2);
+
+        assert_eq!(store.set_folder(&["conv-0".to_string()], None), ["conv-0"]);
+        assert_eq!(store.folder("conv-0"), None);
+    }
+
+    #[test]
+    fn sorts_by_viewed_or_created_time() {
+        let mut store = make_store_with_n_entries(3);
+        // conv-2 was created last, conv-0 was updated last, conv-1 was opened
+        store.upsert_metadata("conv-0", "Title 0", 0.0, 10);
+        store.set_last_viewed("conv-1", 20);
+
+        assert_eq!(store.all_metadata_ids(), ["conv-0", "conv-2", "conv-1"]);
+        store.set_sort(ConversationSort::Viewed);
+        assert_eq!(store.all_metadata_ids(), ["conv-1", "conv-0", "conv-2"]);
+        store.set_sort(ConversationSort::Created);
+        assert_eq!(store.all_metadata_ids(), ["conv-2", "conv-1", "conv-0"]);
+    }
+
+    #[test]
+    fn recently_viewed_skips_unviewed_and_excluded() {
+        let mut store = make_store_with_n_entries(4);
+        store.set_last_viewed("conv-0", 30);
+        store.set_last_viewed("conv-2", 40);
+        store.set_last_viewed("conv-3", 50);
+
+        let ids =
+            |list: Vec<(String, String)>| list.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+        assert_eq!(ids(store.recently_viewed(2, None)), ["conv-3", "conv-2"]);
+        assert_eq!(
+            ids(store.recently_viewed(5, Some("conv-3"))),
+            ["conv-2", "conv-0"]
+        );
+    }
+
+    #[test]
+    fn search_lists_title_matches_before_message_matches() {
+        let mut store = make_store_with_n_entries(3);
+        store.upsert_metadata("conv-1", "Deploy keys", 0.0, 1);
+        let hit = |id: &str, history_index: usize| MessageSearchHit {
+            conversation_id: id.to_string(),
+            history_index,
+            snippet: format!("deploy in {id}"),
+        };
+        let hits = [
+            hit("conv-2", 4),
+            hit("conv-1", 2),
+            hit("conv-2", 7),
+            hit("gone", 0),
+        ];
+
+        let matches = store.search("DEPLOY", &hits);
+        let found: Vec<(&str, Option<usize>)> = matches
+            .iter()
+            .map(|m| (m.id.as_str(), m.history_index))
+            .collect();
+        assert_eq!(found, [("conv-1", Some(2)), ("conv-2", Some(4))]);
+        assert_eq!(matches[1].title, "Title 2");
+        assert!(store.search(" ", &hits).is_empty());
+    }
+
     #[test]
     fn all_metadata_ids_returns_all_ids_most_recent_first() {
         let store = make_store_with_n_entries(1000);
@@ -390,6 +884,69 @@ mod tests {
         assert_eq!(store.find_lru_evictable(), Some("b".to_string()));
     }
 
+    #[test]
+    fn merge_appends_source_history_and_deletes_source() {
+        use crate::models::HistoryEditKind;
+        use crate::testing::{ConversationDataBuilder, FakeAgentClient};
+
+        let agent = FakeAgentClient::new();
+        let mut store = make_store_with_n_entries(0);
+        let target = ConversationDataBuilder::new("target")
+            .title("Trip plan")
+            .tags(&["travel"])
+            .user("Where should we go?")
+            .assistant("Lisbon")
+            .build_conversation(&agent)
+            .unwrap();
+        let mut source = ConversationDataBuilder::new("source")
+            .title("Flights")
+            .tags(&["Travel", "budget"])
+            .user("Cheapest flight?")
+            .assistant("Tuesday")
+            .build_conversation(&agent)
+            .unwrap();
+        source.record_regeneration(1, "Monday".to_string(), 0);
+        for conv in [target, source] {
+            store.upsert_metadata(conv.id(), conv.title(), 0.0, 0);
+            store.insert_loaded(conv);
+        }
+        store.set_active_by_id("source".to_string());
+
+        assert!(store.merge("target", "target").is_err());
+        store.mark_streaming("source");
+        assert!(store.merge("source", "target").is_err());
+        store.unmark_streaming("source");
+        store.set_locked("source", true);
+        assert!(store.merge("source", "target").is_err());
+        assert!(store.is_loaded("source"));
+        store.set_locked("source", false);
+
+        assert_eq!(store.merge("source", "target").unwrap(), 2);
+        assert!(!store.is_loaded("source"));
+        assert_eq!(store.all_metadata_ids(), vec!["target"]);
+        assert_eq!(store.active_id().map(String::as_str), Some("target"));
+        assert_eq!(store.tags("target"), ["travel", "budget"]);
+
+        let merged = store.get_conversation("target").unwrap();
+        assert_eq!(merged.message_count(), 4);
+        // The boundary is a history edit, so user and assistant turns still alternate
+        let roles: Vec<_> = merged
+            .messages()
+            .iter()
+            .filter_map(|m| crate::models::conversation::role_and_text(m).map(|(role, _)| role))
+            .collect();
+        assert_eq!(roles, ["User", "Assistant", "User", "Assistant"]);
+        assert_eq!(merged.entries()[2].note, None);
+        let boundary = merged.history_edits().last().unwrap();
+        assert_eq!(boundary.kind, HistoryEditKind::ConversationMerged);
+        assert_eq!(boundary.message_index, 2);
+        assert_eq!(
+            boundary.summary(),
+            "Merged conversation \"Flights\" in from message #3"
+        );
+        assert_eq!(merged.regeneration_records()[0].message_index, 3);
+    }
+
     #[test]
     fn max_cached_conversations_constant_is_reasonable() {
         // Guard: keep the constant between 5 and 50 to prevent accidental extremes
@@ -35,6 +35,15 @@ pub struct ErrorEntry {
     pub fields: HashMap<String, String>,
 }
 
+impl ErrorEntry {
+    /// Whether the event was logged with `key = value` for every pair.
+    pub fn has_fields(&self, fields: &[(&str, &str)]) -> bool {
+        fields
+            .iter()
+            .all(|(key, value)| self.fields.get(*key).is_some_and(|v| v == value))
+    }
+}
+
 pub struct ErrorStore {
     entries: Arc<Mutex<VecDeque<ErrorEntry>>>,
     max_entries: usize,
@@ -79,6 +88,12 @@ impl ErrorStore {
             .count()
     }
 
+    /// Whether an entry logged with all of `fields` is still in the store.
+    pub fn contains_fields(&self, fields: &[(&str, &str)]) -> bool {
+        let entries = self.entries.lock();
+        entries.iter().any(|e| e.has_fields(fields))
+    }
+
     pub fn clear(&mut self) {
         let mut entries = self.entries.lock();
         entries.clear();
@@ -216,6 +231,19 @@ mod tests {
         assert_eq!(entries[0].fields.get("key2").unwrap(), "value2");
     }
 
+    #[test]
+    fn test_contains_fields_matches_every_pair() {
+        let store = ErrorStore::new(10);
+        let mut entry = make_entry(ErrorLevel::Error, "Stream error");
+        entry.fields.insert("conv_id".to_string(), "c1".to_string());
+        entry.fields.insert("error".to_string(), "429".to_string());
+        store.add_entry(entry);
+
+        assert!(store.contains_fields(&[("conv_id", "c1"), ("error", "429")]));
+        assert!(!store.contains_fields(&[("conv_id", "c1"), ("error", "500")]));
+        assert!(!store.contains_fields(&[("conv_id", "c2")]));
+    }
+
     #[test]
     fn test_max_entries_of_one() {
         let store = ErrorStore::new(1);
@@ -4,6 +4,7 @@ use std::time::SystemTime;
 use tokio::sync::{mpsc, oneshot};
 
 use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 
 // Global notification senders (set once per message send, cleared between messages)
 static GLOBAL_APPROVAL_NOTIFIER: OnceLock<
@@ -20,15 +21,32 @@ pub fn set_global_approval_notifier(tx: mpsc::UnboundedSender<ApprovalNotificati
 
 /// Notify via global channel (called by shell tools)
 pub fn notify_approval_via_global(id: String, command: String, is_sandboxed: bool) {
+    send_global_notification(ApprovalNotification {
+        id,
+        command,
+        is_sandboxed,
+        question: None,
+    });
+}
+
+/// Notify via global channel that the agent is asking the user a question
+/// (called by the ask_user tool).
+pub fn notify_question_via_global(id: String, question: UserQuestion) {
+    send_global_notification(ApprovalNotification {
+        id,
+        command: question.question.clone(),
+        is_sandboxed: true,
+        question: Some(question),
+    });
+}
+
+fn send_global_notification(notification: ApprovalNotification) {
     use tracing::{debug, warn};
 
+    let id = notification.id.clone();
     if let Some(guard) = GLOBAL_APPROVAL_NOTIFIER.get() {
         if let Some(tx) = guard.lock().as_ref() {
-            match tx.send(ApprovalNotification {
-                id: id.clone(),
-                command,
-                is_sandboxed,
-            }) {
+            match tx.send(notification) {
                 Ok(_) => {
                     debug!(id = %id, "Successfully sent approval notification via global channel");
                 }
@@ -49,14 +67,56 @@ pub fn notify_approval_via_global(id: String, command: String, is_sandboxed: boo
 pub enum ApprovalDecision {
     Approved,
     Denied,
+    /// Free-form reply to a question asked through `request_user_input`.
+    Answered(String),
+}
+
+/// A question the agent asks the user mid-turn (see the ask_user tool).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UserQuestion {
+    pub question: String,
+    /// Suggested answers rendered as buttons; empty for a free-text question.
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// Whether the user may type an answer instead of picking an option.
+    #[serde(default = "default_allow_free_text")]
+    pub allow_free_text: bool,
+}
+
+fn default_allow_free_text() -> bool {
+    true
+}
+
+impl UserQuestion {
+    /// Map text typed by the user to an answer: a 1-based option number or an
+    /// option's text (case-insensitive) selects that option; anything else is
+    /// accepted verbatim only when free text is allowed.
+    pub fn resolve_typed_answer(&self, text: &str) -> Option<String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        if let Ok(index) = text.parse::<usize>()
+            && let Some(option) = index.checked_sub(1).and_then(|i| self.options.get(i))
+        {
+            return Some(option.clone());
+        }
+        if let Some(option) = self.options.iter().find(|o| o.eq_ignore_ascii_case(text)) {
+            return Some(option.clone());
+        }
+        self.allow_free_text.then(|| text.to_string())
+    }
 }
 
 /// Notification that an approval request was created
 #[derive(Clone, Debug)]
 pub struct ApprovalNotification {
     pub id: String,
+    /// Command label, or the question text for user-input requests
     pub command: String,
     pub is_sandboxed: bool,
+    /// Set when the request asks the user for input rather than a yes/no decision
+    pub question: Option<UserQuestion>,
 }
 
 /// Notification that an approval was resolved
@@ -64,6 +124,8 @@ pub struct ApprovalNotification {
 pub struct ApprovalResolution {
     pub id: String,
     pub approved: bool,
+    /// The user's reply when the request was a question
+    pub answer: Option<String>,
 }
 
 /// Request for user approval to execute a command
@@ -109,6 +171,32 @@ pub async fn request_execution_approval(
         _ => {}
     }
 
+    let (request_id, rx) = register_request(pending, label, is_sandboxed);
+    notify_approval_via_global(request_id.clone(), label.to_string(), is_sandboxed);
+
+    match tokio::time::timeout(APPROVAL_TIMEOUT, rx).await {
+        Ok(Ok(ApprovalDecision::Approved)) => Ok(true),
+        Ok(Ok(ApprovalDecision::Denied)) => Ok(false),
+        Ok(Ok(ApprovalDecision::Answered(_))) => Ok(true),
+        Ok(Err(_)) => Err(anyhow::anyhow!("Approval channel closed")),
+        Err(_) => {
+            let mut store = pending.lock();
+            store.remove(&request_id);
+            Err(anyhow::anyhow!("Approval timeout (5 minutes)"))
+        }
+    }
+}
+
+/// How long a tool waits for an approve/deny decision.
+const APPROVAL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+/// How long `ask_user` waits for an answer; questions usually need more thought.
+const USER_QUESTION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(900);
+
+fn register_request(
+    pending: &PendingApprovals,
+    label: &str,
+    is_sandboxed: bool,
+) -> (String, oneshot::Receiver<ApprovalDecision>) {
     let (tx, rx) = oneshot::channel();
     let request_id = uuid::Uuid::new_v4().to_string();
 
@@ -119,22 +207,31 @@ pub async fn request_execution_approval(
         created_at: SystemTime::now(),
         responder: tx,
     };
+    pending.lock().insert(request_id.clone(), request);
 
-    {
-        let mut store = pending.lock();
-        store.insert(request_id.clone(), request);
-    }
+    (request_id, rx)
+}
 
-    notify_approval_via_global(request_id.clone(), label.to_string(), is_sandboxed);
+/// Ask the user a question through the approval channel and wait for the reply.
+///
+/// Unlike `request_execution_approval` this is never auto-approved. Returns
+/// `Ok(Some(answer))` when the user answered, `Ok(None)` when they dismissed
+/// the question, or an error on timeout / channel failure.
+pub async fn request_user_input(
+    pending: &PendingApprovals,
+    question: UserQuestion,
+) -> anyhow::Result<Option<String>> {
+    let (request_id, rx) = register_request(pending, &question.question, true);
+    notify_question_via_global(request_id.clone(), question);
 
-    match tokio::time::timeout(std::time::Duration::from_secs(300), rx).await {
-        Ok(Ok(ApprovalDecision::Approved)) => Ok(true),
-        Ok(Ok(ApprovalDecision::Denied)) => Ok(false),
-        Ok(Err(_)) => Err(anyhow::anyhow!("Approval channel closed")),
+    match tokio::time::timeout(USER_QUESTION_TIMEOUT, rx).await {
+        Ok(Ok(ApprovalDecision::Answered(answer))) => Ok(Some(answer)),
+        Ok(Ok(ApprovalDecision::Approved)) => Ok(Some(String::new())),
+        Ok(Ok(ApprovalDecision::Denied)) => Ok(None),
+        Ok(Err(_)) => Err(anyhow::anyhow!("Question channel closed")),
         Err(_) => {
-            let mut store = pending.lock();
-            store.remove(&request_id);
-            Err(anyhow::anyhow!("Approval timeout (5 minutes)"))
+            pending.lock().remove(&request_id);
+            Err(anyhow::anyhow!("No answer from the user (15 minutes)"))
         }
     }
 }
@@ -178,7 +275,11 @@ impl ExecutionApprovalStore {
     pub fn resolve(&self, id: &str, decision: ApprovalDecision) -> bool {
         let mut pending = self.pending_requests.lock();
         if let Some(request) = pending.remove(id) {
-            let approved = matches!(decision, ApprovalDecision::Approved);
+            let approved = !matches!(decision, ApprovalDecision::Denied);
+            let answer = match &decision {
+                ApprovalDecision::Answered(answer) => Some(answer.clone()),
+                _ => None,
+            };
             let _ = request.responder.send(decision);
 
             // Notify stream that approval was resolved
@@ -186,6 +287,7 @@ impl ExecutionApprovalStore {
                 let _ = tx.send(ApprovalResolution {
                     id: id.to_string(),
                     approved,
+                    answer,
                 });
             }
 
@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+
+/// Maximum characters of the removed message kept in an edit record.
+const PREVIEW_CHARS: usize = 80;
+
+/// What a manual history edit changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryEditKind {
+    /// The message and all its metadata
+    MessageDeleted,
+    /// Only the message's trace (thinking blocks and tool calls)
+    TraceDeleted,
+    /// A user message was rewritten and the conversation re-run from it
+    MessageEdited,
+    /// Another conversation's history was appended, starting at the message
+    ConversationMerged,
+}
+
+/// Audit record of a manual edit to a conversation's history, kept with the
+/// conversation so exports show that it was cleaned up.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEdit {
+    pub kind: HistoryEditKind,
+    /// Index of the message in the history; follows later deletions
+    pub message_index: usize,
+    /// "User" or "Assistant"; empty for merges
+    pub role: String,
+    /// Start of the message text, or the merged conversation's title
+    pub preview: String,
+    /// Unix timestamp (seconds) of the edit
+    pub edited_at: i64,
+    /// Full text before the edit, for edited messages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_text: Option<String>,
+    /// Later messages dropped when an edited message was re-run
+    #[serde(default)]
+    pub messages_removed: usize,
+}
+
+impl HistoryEdit {
+    pub fn new(
+        kind: HistoryEditKind,
+        message_index: usize,
+        role: &str,
+        text: &str,
+        edited_at: i64,
+    ) -> Self {
+        let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let preview = match line.char_indices().nth(PREVIEW_CHARS) {
+            Some((end, _)) => format!("{}…", &line[..end]),
+            None => line,
+        };
+        Self {
+            kind,
+            message_index,
+            role: role.to_string(),
+            preview,
+            edited_at,
+            original_text: None,
+            messages_removed: 0,
+        }
+    }
+
+    /// Revision record of the user message at `message_index` being
+    /// rewritten; `original` is its text before the edit.
+    pub fn edited(
+        message_index: usize,
+        original: &str,
+        messages_removed: usize,
+        edited_at: i64,
+    ) -> Self {
+        Self {
+            original_text: Some(original.to_string()),
+            messages_removed,
+            ..Self::new(
+                HistoryEditKind::MessageEdited,
+                message_index,
+                "User",
+                original,
+                edited_at,
+            )
+        }
+    }
+
+    /// One-line description, e.g. `Deleted assistant message #4: "Sure, …"`.
+    pub fn summary(&self) -> String {
+        let role = self.role.to_lowercase();
+        let number = self.message_index + 1;
+        match self.kind {
+            HistoryEditKind::MessageDeleted => {
+                format!("Deleted {role} message #{number}: \"{}\"", self.preview)
+            }
+            HistoryEditKind::TraceDeleted => format!(
+                "Deleted {role} tool trace of message #{number}: \"{}\"",
+                self.preview
+            ),
+            HistoryEditKind::MessageEdited => format!(
+                "Edited {role} message #{number} and re-ran from it \
+                 ({} later messages removed), was: \"{}\"",
+                self.messages_removed, self.preview
+            ),
+            HistoryEditKind::ConversationMerged => format!(
+                "Merged conversation \"{}\" in from message #{number}",
+                self.preview
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_is_one_line_and_truncated() {
+        let text = format!("first\nsecond {}", "x".repeat(100));
+        let edit = HistoryEdit::new(HistoryEditKind::MessageDeleted, 3, "User", &text, 0);
+        assert!(edit.preview.starts_with("first second x"));
+        assert_eq!(edit.preview.chars().count(), PREVIEW_CHARS + 1);
+        assert!(
+            edit.summary()
+                .starts_with("Deleted user message #4: \"first")
+        );
+    }
+
+    #[test]
+    fn kind_serializes_as_snake_case() {
+        let edit = HistoryEdit::new(HistoryEditKind::TraceDeleted, 0, "Assistant", "ok", 1);
+        let json = serde_json::to_string(&edit).unwrap();
+        assert!(json.contains(r#""kind":"trace_deleted""#));
+        assert_eq!(serde_json::from_str::<HistoryEdit>(&json).unwrap(), edit);
+        assert!(!json.contains("original_text"));
+    }
+
+    #[test]
+    fn edit_keeps_the_original_text() {
+        let original = format!("Explain {}", "lifetimes ".repeat(20));
+        let edit = HistoryEdit::edited(2, &original, 3, 5);
+        assert_eq!(edit.kind, HistoryEditKind::MessageEdited);
+        assert_eq!(edit.original_text.as_deref(), Some(original.as_str()));
+        assert!(
+            edit.summary().starts_with(
+                "Edited user message #3 and re-ran from it (3 later messages removed)"
+            )
+        );
+
+        let json = serde_json::to_string(&edit).unwrap();
+        assert!(json.contains(r#""kind":"message_edited""#));
+        assert_eq!(serde_json::from_str::<HistoryEdit>(&json).unwrap(), edit);
+    }
+}
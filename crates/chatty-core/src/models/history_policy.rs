@@ -0,0 +1,156 @@
+use rig_core::completion::Message;
+use rig_core::message::UserContent;
+use serde::{Deserialize, Serialize};
+
+/// Turn counts offered in the history policy picker.
+pub const LAST_TURNS_PRESETS: &[usize] = &[3, 5, 10, 20];
+
+/// How much of a conversation's stored history is sent to the model each turn.
+///
+/// The policy only shapes the request; the stored conversation is never
+/// modified.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum HistoryPolicy {
+    /// Send the whole history (the context shaper still trims oversized turns).
+    #[default]
+    Full,
+    /// Send only the most recent `turns` user turns and everything after them.
+    LastTurns { turns: usize },
+    /// Send the whole history but let the context shaper summarize older
+    /// messages with the conversation's model once it grows large.
+    Summarized,
+    /// Send the whole history until it nears the model's context limit, then
+    /// leave out the middle turns least related to the current question.
+    Relevant,
+}
+
+/// A turn the relevance policy left out of a request, listed in the context
+/// breakdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrunedTurn {
+    /// Start of the turn's user message
+    pub preview: String,
+    /// Stored messages in the turn (the prompt, tool calls and answers)
+    pub messages: usize,
+    /// Similarity to the current question, in percent
+    pub relevance_pct: u8,
+}
+
+impl HistoryPolicy {
+    /// Short label for the parameters popover and the context bar.
+    pub fn label(&self) -> String {
+        match self {
+            HistoryPolicy::Full => "Full history".to_string(),
+            HistoryPolicy::LastTurns { turns: 1 } => "Last turn".to_string(),
+            HistoryPolicy::LastTurns { turns } => format!("Last {turns} turns"),
+            HistoryPolicy::Summarized => "Summarized".to_string(),
+            HistoryPolicy::Relevant => "Relevant turns".to_string(),
+        }
+    }
+
+    /// Whether the context shaper may call the model to summarize history.
+    pub fn allows_summarization(&self) -> bool {
+        matches!(self, HistoryPolicy::Summarized)
+    }
+
+    /// Whether low-relevance turns may be left out near the context limit.
+    pub fn prunes_by_relevance(&self) -> bool {
+        matches!(self, HistoryPolicy::Relevant)
+    }
+
+    /// Apply the policy to `history`, returning the messages to send and the
+    /// number of stored messages left out.
+    ///
+    /// A turn starts at a user message that carries typed content (not just
+    /// tool results), so tool calls are never separated from their results.
+    pub fn apply(&self, history: Vec<Message>) -> (Vec<Message>, usize) {
+        let HistoryPolicy::LastTurns { turns } = *self else {
+            return (history, 0);
+        };
+
+        let turn_starts: Vec<usize> = history
+            .iter()
+            .enumerate()
+            .filter(|(_, message)| starts_turn(message))
+            .map(|(ix, _)| ix)
+            .collect();
+        if turn_starts.len() <= turns {
+            return (history, 0);
+        }
+
+        let cut = if turns == 0 {
+            history.len()
+        } else {
+            turn_starts[turn_starts.len() - turns]
+        };
+        let mut history = history;
+        let kept = history.split_off(cut);
+        (kept, cut)
+    }
+}
+
+pub(crate) fn starts_turn(message: &Message) -> bool {
+    match message {
+        Message::User { content } => content
+            .iter()
+            .any(|item| !matches!(item, UserContent::ToolResult(_))),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig_core::OneOrMany;
+    use rig_core::completion::message::{Text, ToolResult, ToolResultContent};
+
+    fn tool_result() -> Message {
+        Message::User {
+            content: OneOrMany::one(UserContent::ToolResult(ToolResult {
+                id: "call".into(),
+                call_id: None,
+                content: OneOrMany::one(ToolResultContent::Text(Text { text: "ok".into() })),
+            })),
+        }
+    }
+
+    #[test]
+    fn last_turns_keeps_whole_turns() {
+        let history = vec![
+            Message::user("first"),
+            Message::assistant("one"),
+            Message::user("second"),
+            Message::assistant("calling a tool"),
+            tool_result(),
+            Message::assistant("two"),
+            Message::user("third"),
+            Message::assistant("three"),
+        ];
+
+        let (kept, dropped) = HistoryPolicy::LastTurns { turns: 2 }.apply(history.clone());
+        assert_eq!(dropped, 2);
+        assert_eq!(kept, history[2..].to_vec());
+
+        let (kept, dropped) = HistoryPolicy::LastTurns { turns: 5 }.apply(history.clone());
+        assert_eq!(dropped, 0);
+        assert_eq!(kept.len(), history.len());
+
+        let (kept, dropped) = HistoryPolicy::Summarized.apply(history.clone());
+        assert_eq!((kept.len(), dropped), (history.len(), 0));
+    }
+
+    #[test]
+    fn serializes_with_mode_tag() {
+        let json = serde_json::to_string(&HistoryPolicy::LastTurns { turns: 10 }).unwrap();
+        assert_eq!(json, r#"{"mode":"last_turns","turns":10}"#);
+        assert_eq!(
+            serde_json::from_str::<HistoryPolicy>(r#"{"mode":"full"}"#).unwrap(),
+            HistoryPolicy::Full
+        );
+        assert_eq!(
+            serde_json::from_str::<HistoryPolicy>(r#"{"mode":"relevant"}"#).unwrap(),
+            HistoryPolicy::Relevant
+        );
+    }
+}
@@ -0,0 +1,415 @@
+//! Persisted queue of background work with retry and backoff.
+//!
+//! Work that runs after a turn without the user waiting for it (title
+//! generation, auto-exports, auto-summaries, topic-shift embeddings) is
+//! recorded here before it starts. A failed attempt is retried with exponential backoff, and a job
+//! that keeps failing stays in the queue as `Failed` so the jobs panel can
+//! show it and the user can retry it by hand. The queue is saved to
+//! `jobs.json` in the config directory; jobs interrupted by quitting run
+//! again on the next start.
+//!
+//! The queue is bounded: at most [`MAX_CONCURRENT_JOBS`] run at once, and
+//! no more than [`MAX_QUEUED_JOBS`] wait or run, so a burst of turns cannot
+//! pile up work faster than it drains. Jobs queued beyond that are kept as
+//! `Failed`, so the jobs panel shows them and they can be retried.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Attempts made automatically before a job is marked `Failed`.
+pub const MAX_ATTEMPTS: u32 = 4;
+
+/// Jobs running at the same time; further due jobs wait for a free slot.
+pub const MAX_CONCURRENT_JOBS: usize = 2;
+
+/// Jobs waiting or running; new jobs beyond this are recorded as failed.
+pub const MAX_QUEUED_JOBS: usize = 64;
+
+/// Error recorded on jobs queued while the queue was full.
+pub const QUEUE_FULL_ERROR: &str = "Not started: too many background jobs were waiting";
+
+/// Delay before the first retry; doubles with every further attempt.
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(15);
+
+/// Longest delay between two attempts.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(10 * 60);
+
+/// What a job does. Every kind works on one conversation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    GenerateTitle,
+    ExportAtif,
+    ExportJsonl,
+    Summarize,
+    DetectTopicShift,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    /// Waiting for `next_attempt_at`
+    Pending,
+    Running,
+    /// Gave up after `MAX_ATTEMPTS`; only a manual retry runs it again
+    Failed,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BackgroundJob {
+    pub id: u64,
+    pub kind: JobKind,
+    pub conversation_id: String,
+    pub state: JobState,
+    /// Failed attempts so far
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// Unix seconds
+    pub created_at: i64,
+    /// Unix seconds; a pending job is due once this has passed
+    pub next_attempt_at: i64,
+    /// Queued again while running; runs once more after it finishes
+    #[serde(default)]
+    pub rerun: bool,
+}
+
+impl BackgroundJob {
+    /// Whether the job is waiting and its next attempt time has passed.
+    pub fn is_due(&self, now: i64) -> bool {
+        self.state == JobState::Pending && self.next_attempt_at <= now
+    }
+}
+
+/// Delay before retrying a job that has failed `attempts` times.
+pub fn retry_delay(attempts: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+    BASE_RETRY_DELAY.saturating_mul(factor).min(MAX_RETRY_DELAY)
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct JobQueue {
+    jobs: Vec<BackgroundJob>,
+    next_id: u64,
+}
+
+impl JobQueue {
+    /// Default location of the persisted queue.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("chatty").join("jobs.json"))
+    }
+
+    /// Read the queue saved at `path`. A missing or unreadable file gives an
+    /// empty queue; jobs that were running when the app quit are due again.
+    pub fn load(path: &Path) -> Self {
+        let mut queue: Self = match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                warn!(error = ?e, path = %path.display(), "Ignoring unreadable job queue");
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+        for job in &mut queue.jobs {
+            if job.state == JobState::Running {
+                job.state = JobState::Pending;
+            }
+        }
+        queue
+    }
+
+    /// Write the queue to `path` atomically.
+    ///
+    /// # Errors
+    /// Returns an error if the directory or file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        std::fs::write(&temp_path, json)
+            .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+        std::fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    /// All jobs, oldest first.
+    pub fn jobs(&self) -> &[BackgroundJob] {
+        &self.jobs
+    }
+
+    pub fn get(&self, id: u64) -> Option<&BackgroundJob> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    /// Number of jobs waiting or running, and of jobs that gave up.
+    pub fn counts(&self) -> (usize, usize) {
+        let failed = self
+            .jobs
+            .iter()
+            .filter(|job| job.state == JobState::Failed)
+            .count();
+        (self.jobs.len() - failed, failed)
+    }
+
+    /// Jobs currently running.
+    pub fn running(&self) -> usize {
+        self.jobs
+            .iter()
+            .filter(|job| job.state == JobState::Running)
+            .count()
+    }
+
+    /// Queue `kind` for `conversation_id`, due immediately. An existing job
+    /// of the same kind for the conversation is reused: made due again, or
+    /// run once more after it finishes if it is running. While
+    /// [`MAX_QUEUED_JOBS`] are already waiting or running, the job is kept as
+    /// `Failed` instead, for the user to retry. Returns the job's id.
+    pub fn enqueue(&mut self, kind: JobKind, conversation_id: &str, now: i64) -> u64 {
+        let full = self.counts().0 >= MAX_QUEUED_JOBS;
+        if let Some(job) = self
+            .jobs
+            .iter_mut()
+            .find(|job| job.kind == kind && job.conversation_id == conversation_id)
+        {
+            if job.state == JobState::Running {
+                job.rerun = true;
+            } else if job.state == JobState::Pending || !full {
+                job.state = JobState::Pending;
+                job.attempts = 0;
+                job.next_attempt_at = now;
+            }
+            return job.id;
+        }
+        let (state, last_error) = if full {
+            warn!(kind = ?kind, conversation_id, "Job queue is full, keeping job as failed");
+            (JobState::Failed, Some(QUEUE_FULL_ERROR.to_string()))
+        } else {
+            (JobState::Pending, None)
+        };
+        self.next_id += 1;
+        self.jobs.push(BackgroundJob {
+            id: self.next_id,
+            kind,
+            conversation_id: conversation_id.to_string(),
+            state,
+            attempts: 0,
+            last_error,
+            created_at: now,
+            next_attempt_at: now,
+            rerun: false,
+        });
+        self.next_id
+    }
+
+    /// Mark the jobs `ids` as running and return them, oldest first and no
+    /// more than the free slots of [`MAX_CONCURRENT_JOBS`]. Jobs that are not
+    /// pending are skipped.
+    pub fn start(&mut self, ids: &[u64]) -> Vec<BackgroundJob> {
+        let free = MAX_CONCURRENT_JOBS.saturating_sub(self.running());
+        self.jobs
+            .iter_mut()
+            .filter(|job| job.state == JobState::Pending && ids.contains(&job.id))
+            .take(free)
+            .map(|job| {
+                job.state = JobState::Running;
+                job.clone()
+            })
+            .collect()
+    }
+
+    /// Seconds from `now` until the next pending job that is not due yet
+    /// becomes due (a retry waiting out its backoff).
+    pub fn next_due_in(&self, now: i64) -> Option<u64> {
+        self.jobs
+            .iter()
+            .filter(|job| job.state == JobState::Pending && job.next_attempt_at > now)
+            .map(|job| (job.next_attempt_at - now) as u64)
+            .min()
+    }
+
+    /// The job finished; drop it from the queue, or make it due again if it
+    /// was queued while running.
+    pub fn complete(&mut self, id: u64) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id && job.rerun) {
+            job.rerun = false;
+            job.state = JobState::Pending;
+            job.attempts = 0;
+            job.last_error = None;
+            return;
+        }
+        self.jobs.retain(|job| job.id != id);
+    }
+
+    /// Record a failed attempt: schedule a retry with backoff, or mark the
+    /// job `Failed` after `MAX_ATTEMPTS`.
+    pub fn fail(&mut self, id: u64, error: String, now: i64) {
+        let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) else {
+            return;
+        };
+        job.attempts += 1;
+        job.last_error = Some(error);
+        if job.attempts >= MAX_ATTEMPTS {
+            job.state = JobState::Failed;
+        } else {
+            job.state = JobState::Pending;
+            job.next_attempt_at = now + retry_delay(job.attempts).as_secs() as i64;
+        }
+    }
+
+    /// Run a waiting or failed job now, with a fresh set of attempts.
+    pub fn retry(&mut self, id: u64, now: i64) {
+        if let Some(job) = self
+            .jobs
+            .iter_mut()
+            .find(|job| job.id == id && job.state != JobState::Running)
+        {
+            job.state = JobState::Pending;
+            job.attempts = 0;
+            job.next_attempt_at = now;
+        }
+    }
+
+    /// Drop a job that is not running.
+    pub fn dismiss(&mut self, id: u64) {
+        self.jobs
+            .retain(|job| job.id != id || job.state == JobState::Running);
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn take_due(queue: &mut JobQueue, now: i64) -> Vec<BackgroundJob> {
+        let ids: Vec<u64> = queue
+            .jobs()
+            .iter()
+            .filter(|job| job.is_due(now))
+            .map(|job| job.id)
+            .collect();
+        queue.start(&ids)
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        assert_eq!(retry_delay(1), Duration::from_secs(15));
+        assert_eq!(retry_delay(2), Duration::from_secs(30));
+        assert_eq!(retry_delay(3), Duration::from_secs(60));
+        assert_eq!(retry_delay(40), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn failed_job_is_retried_then_given_up() {
+        let mut queue = JobQueue::default();
+        let id = queue.enqueue(JobKind::GenerateTitle, "c1", 100);
+        assert_eq!(take_due(&mut queue, 100).len(), 1);
+        assert!(
+            take_due(&mut queue, 100).is_empty(),
+            "running jobs are not due"
+        );
+
+        queue.fail(id, "timeout".into(), 100);
+        assert_eq!(queue.next_due_in(100), Some(15));
+        assert!(take_due(&mut queue, 110).is_empty());
+        assert_eq!(take_due(&mut queue, 115).len(), 1);
+
+        for _ in 1..MAX_ATTEMPTS {
+            queue.fail(id, "timeout".into(), 200);
+        }
+        let job = queue.get(id).unwrap();
+        assert_eq!(job.state, JobState::Failed);
+        assert_eq!(queue.counts(), (0, 1));
+        assert!(take_due(&mut queue, i64::MAX).is_empty());
+
+        queue.retry(id, 300);
+        assert_eq!(take_due(&mut queue, 300).len(), 1);
+        queue.complete(id);
+        assert!(queue.jobs().is_empty());
+    }
+
+    #[test]
+    fn enqueue_reuses_job_for_same_conversation() {
+        let mut queue = JobQueue::default();
+        let first = queue.enqueue(JobKind::ExportAtif, "c1", 0);
+        take_due(&mut queue, 0);
+        queue.fail(first, "disk full".into(), 0);
+        assert_eq!(queue.enqueue(JobKind::ExportAtif, "c1", 5), first);
+        assert_eq!(queue.get(first).unwrap().next_attempt_at, 5);
+        assert_ne!(queue.enqueue(JobKind::ExportJsonl, "c1", 5), first);
+        assert_eq!(queue.jobs().len(), 2);
+    }
+
+    #[test]
+    fn job_queued_while_running_runs_again() {
+        let mut queue = JobQueue::default();
+        let id = queue.enqueue(JobKind::ExportJsonl, "c1", 0);
+        take_due(&mut queue, 0);
+        assert_eq!(queue.enqueue(JobKind::ExportJsonl, "c1", 1), id);
+        assert!(take_due(&mut queue, 1).is_empty());
+
+        queue.complete(id);
+        assert_eq!(take_due(&mut queue, 2).len(), 1);
+        queue.complete(id);
+        assert!(queue.jobs().is_empty());
+    }
+
+    #[test]
+    fn concurrency_and_queue_length_are_bounded() {
+        let mut queue = JobQueue::default();
+        let ids: Vec<u64> = (0..MAX_QUEUED_JOBS)
+            .map(|i| queue.enqueue(JobKind::Summarize, &format!("c{i}"), 0))
+            .collect();
+        // A job beyond the bound is kept, as failed, for the user to retry
+        let overflow = queue.enqueue(JobKind::Summarize, "one-too-many", 0);
+        let job = queue.get(overflow).unwrap();
+        assert_eq!(job.state, JobState::Failed);
+        assert_eq!(job.last_error.as_deref(), Some(QUEUE_FULL_ERROR));
+        assert_eq!(queue.counts(), (MAX_QUEUED_JOBS, 1));
+        // Queuing it again while full leaves it failed
+        assert_eq!(
+            queue.enqueue(JobKind::Summarize, "one-too-many", 0),
+            overflow
+        );
+        assert_eq!(queue.get(overflow).unwrap().state, JobState::Failed);
+        // A job already queued is still made due again
+        assert_eq!(queue.enqueue(JobKind::Summarize, "c0", 0), ids[0]);
+
+        let started = take_due(&mut queue, 0);
+        assert_eq!(started.len(), MAX_CONCURRENT_JOBS);
+        assert_eq!(started[0].id, ids[0]);
+        assert!(take_due(&mut queue, 0).is_empty());
+
+        queue.complete(ids[0]);
+        let next = take_due(&mut queue, 0);
+        assert_eq!(next.len(), 1);
+        assert_eq!(next[0].id, ids[MAX_CONCURRENT_JOBS]);
+        let room = queue.enqueue(JobKind::Summarize, "room-again", 0);
+        assert_eq!(queue.get(room).unwrap().state, JobState::Pending);
+    }
+
+    #[test]
+    fn interrupted_jobs_run_again_after_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jobs.json");
+        let mut queue = JobQueue::default();
+        queue.enqueue(JobKind::Summarize, "c1", 0);
+        take_due(&mut queue, 0);
+        queue.save(&path).unwrap();
+
+        let mut loaded = JobQueue::load(&path);
+        assert_eq!(take_due(&mut loaded, 0).len(), 1);
+        assert!(
+            JobQueue::load(&dir.path().join("missing.json"))
+                .jobs()
+                .is_empty()
+        );
+    }
+}
@@ -3,7 +3,10 @@ use serde_json::json;
 use std::time::Duration;
 use std::time::SystemTime;
 
+use crate::models::execution_approval_store::UserQuestion;
+use crate::models::turn_overrides::TurnOverrides;
 use crate::sandbox::MontySandbox;
+use crate::services::model_router::RoutingDecision;
 
 /// User message content
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -40,6 +43,20 @@ pub struct SystemTrace {
     pub total_duration: Option<Duration>,
     /// Track which tool is currently executing (by index)
     pub active_tool_index: Option<usize>,
+    /// Model routing decision for this turn, when the router is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routing: Option<RoutingDecision>,
+    /// Provider privacy policy applied to this turn's requests
+    /// (see `ProviderConfig::privacy_summary`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_privacy: Option<String>,
+    /// Parameters chosen for this turn only, in place of the conversation's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<TurnOverrides>,
+    /// Set when a `/timebox` budget ended the turn
+    /// (see `BudgetTracker::marker`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_reached: Option<String>,
 }
 
 /// Individual items in the system trace
@@ -51,6 +68,8 @@ pub enum TraceItem {
     ToolCall(ToolCallBlock),
     /// An execution approval prompt
     ApprovalPrompt(ApprovalBlock),
+    /// A self-check that rejected an earlier attempt at this answer
+    Reflection(ReflectionBlock),
 }
 
 /// Events emitted by SystemTraceView when trace state changes
@@ -198,6 +217,27 @@ pub struct ApprovalBlock {
     pub state: ApprovalState,
     /// When the approval was requested
     pub created_at: std::time::SystemTime,
+    /// Set when this entry is a question from the ask_user tool rather than
+    /// an approve/deny prompt
+    #[serde(default)]
+    pub question: Option<UserQuestion>,
+    /// The user's answer to `question`
+    #[serde(default)]
+    pub answer: Option<String>,
+}
+
+/// The agent's critique of its first answer, kept on the trace of the
+/// retried answer so both attempts stay visible.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReflectionBlock {
+    /// Text of the rejected first attempt
+    pub first_attempt: String,
+    /// What the critique found wrong with it
+    pub critique: String,
+    /// Confidence score the critique gave (1–5)
+    pub score: u8,
+    /// Scores below this triggered the retry
+    pub threshold: u8,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -222,6 +262,10 @@ impl SystemTrace {
             items: Vec::new(),
             total_duration: None,
             active_tool_index: None,
+            routing: None,
+            request_privacy: None,
+            overrides: None,
+            budget_reached: None,
         }
     }
 
@@ -265,7 +309,19 @@ impl SystemTrace {
         }
     }
 
-    /// Transition all Running tool calls to Error("Cancelled").
+    /// Record the user's answer to an ask_user question by approval ID
+    pub fn set_approval_answer(&mut self, id: &str, answer: String) {
+        for item in &mut self.items {
+            if let TraceItem::ApprovalPrompt(approval) = item
+                && approval.id == id
+            {
+                approval.answer = Some(answer);
+                break;
+            }
+        }
+    }
+
+    /// Transition all Running tool calls to Error("Cancelled by user").
     /// Called when a stream is cancelled to prevent tool calls from staying stuck
     /// in the Running state permanently.
     pub fn cancel_running_tool_calls(&mut self) {
@@ -273,7 +329,7 @@ impl SystemTrace {
             if let TraceItem::ToolCall(tc) = item
                 && matches!(tc.state, ToolCallState::Running)
             {
-                tc.state = ToolCallState::Error("Cancelled".to_string());
+                tc.state = ToolCallState::Error(crate::services::CANCELLED_BY_USER.to_string());
             }
         }
     }
@@ -412,6 +468,18 @@ pub fn is_denial_result(result: &str) -> bool {
     lower.contains("denied by user") || lower.contains("execution denied")
 }
 
+/// First line of a tool result answered from the MCP result cache.
+pub const CACHED_RESULT_PREFIX: &str = "[cached result]";
+
+/// Whether a tool result was answered from the MCP result cache. Tolerates
+/// results that arrive JSON-quoted.
+pub fn is_cached_result(result: &str) -> bool {
+    result
+        .strip_prefix('"')
+        .unwrap_or(result)
+        .starts_with(CACHED_RESULT_PREFIX)
+}
+
 /// Classify a built-in tool call by name into a [`ToolSource`] for source badges.
 pub fn classify_tool_source(tool_name: &str) -> ToolSource {
     match tool_name {
@@ -794,14 +862,14 @@ mod tests {
         // Running calls should be cancelled
         assert!(matches!(
             &tool_calls[0].state,
-            ToolCallState::Error(msg) if msg == "Cancelled"
+            ToolCallState::Error(msg) if msg == "Cancelled by user"
         ));
         // Already-completed call should be unchanged
         assert!(matches!(&tool_calls[1].state, ToolCallState::Success));
         // Running calls should be cancelled
         assert!(matches!(
             &tool_calls[2].state,
-            ToolCallState::Error(msg) if msg == "Cancelled"
+            ToolCallState::Error(msg) if msg == "Cancelled by user"
         ));
     }
 
@@ -901,6 +969,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn detects_cached_results_plain_and_quoted() {
+        assert!(is_cached_result("[cached result]\nhello"));
+        assert!(is_cached_result("\"[cached result]\\nhello\""));
+        assert!(!is_cached_result("hello [cached result]"));
+    }
+
     #[test]
     fn predicts_execute_code_engine_from_input() {
         let monty = r#"{"language":"python","code":"print(1 + 1)"}"#;
@@ -920,4 +995,31 @@ mod tests {
             Some(ExecutionEngine::Docker)
         );
     }
+
+    #[test]
+    fn question_answer_is_recorded_on_approval_block() {
+        let mut trace = SystemTrace::new();
+        trace.add_approval(ApprovalBlock {
+            id: "q1".to_string(),
+            command: "Which DB?".to_string(),
+            is_sandboxed: true,
+            state: ApprovalState::Pending,
+            created_at: SystemTime::now(),
+            question: Some(UserQuestion {
+                question: "Which DB?".to_string(),
+                options: vec!["sqlite".to_string()],
+                allow_free_text: true,
+            }),
+            answer: None,
+        });
+
+        trace.set_approval_answer("q1", "sqlite".to_string());
+        trace.update_approval_state("q1", ApprovalState::Approved);
+
+        let TraceItem::ApprovalPrompt(approval) = &trace.items[0] else {
+            panic!("expected approval prompt");
+        };
+        assert_eq!(approval.answer.as_deref(), Some("sqlite"));
+        assert_eq!(approval.state, ApprovalState::Approved);
+    }
 }
@@ -1,16 +1,41 @@
+pub mod activity_calendar;
 pub mod attachment_validation;
+pub mod compaction_record;
 pub mod conversation;
+pub mod conversation_groups;
+pub mod conversation_label;
+pub mod conversation_tags;
 pub mod conversations_store;
 pub mod error_store;
 pub mod execution_approval_store;
+pub mod history_edit;
+pub mod history_policy;
+pub mod job_queue;
 pub mod message_types;
+pub mod pinned_context;
+pub mod quality_score;
+pub mod session_budget;
+pub mod text_direction;
 pub mod token_usage;
+pub mod turn_overrides;
 pub mod write_approval_store;
 
 #[allow(unused_imports)]
 pub use conversation::RegenerationRecord;
-pub use conversation::{Conversation, MessageEntry, MessageFeedback};
-pub use conversations_store::ConversationsStore;
+pub use activity_calendar::{ActivityCalendar, DayActivity, HeatmapDay};
+pub use compaction_record::{CompactionRecord, CompactionTrigger};
+pub use conversation::{Conversation, MessageEntry, MessageFeedback, TurnError};
+pub use conversation_groups::ConversationSort;
+pub use conversation_label::{ConversationLabel, LabelColor};
+pub use conversations_store::{ConversationMatch, ConversationsStore};
 pub use error_store::ErrorStore;
 pub use execution_approval_store::ExecutionApprovalStore;
+pub use history_edit::{HistoryEdit, HistoryEditKind};
+pub use history_policy::{HistoryPolicy, PrunedTurn};
+pub use job_queue::{BackgroundJob, JobKind, JobQueue, JobState};
+pub use pinned_context::{PinnedItem, PinnedItemKind, build_pinned_context_block};
+pub use quality_score::QualityScore;
+pub use session_budget::{BudgetAction, BudgetTracker, SessionBudget};
+pub use text_direction::{LayoutDirection, TextDirection};
+pub use turn_overrides::{ReasoningEffort, TurnOverrides};
 pub use write_approval_store::WriteApprovalStore;
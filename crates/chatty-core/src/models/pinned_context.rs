@@ -0,0 +1,154 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// What a pinned context item was created from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PinnedItemKind {
+    /// A message copied out of the conversation history.
+    Message,
+    /// The contents of a file on disk, captured when it was pinned.
+    File,
+    /// Free-form text entered by the user.
+    Note,
+}
+
+impl PinnedItemKind {
+    /// Short label used in the context drawer and the injected block.
+    pub fn label(&self) -> &'static str {
+        match self {
+            PinnedItemKind::Message => "Message",
+            PinnedItemKind::File => "File",
+            PinnedItemKind::Note => "Note",
+        }
+    }
+}
+
+/// A snippet the user pinned as "always in context" for a conversation.
+///
+/// Pinned items are sent verbatim ahead of the user's message on every turn,
+/// so they survive context shaping and summarization of the history.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PinnedItem {
+    pub id: String,
+    pub kind: PinnedItemKind,
+    /// Human-readable name (file name, message role, or note preview).
+    pub label: String,
+    /// Exact text injected into the prompt.
+    pub content: String,
+    /// Unix timestamp (seconds) when the item was pinned.
+    pub pinned_at: i64,
+}
+
+/// Maximum characters of a note shown as its label.
+const NOTE_LABEL_CHARS: usize = 40;
+
+impl PinnedItem {
+    fn new(kind: PinnedItemKind, label: String, content: String) -> Self {
+        let pinned_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            label,
+            content,
+            pinned_at,
+        }
+    }
+
+    /// Pin the text of a conversation message. `role` is used as the label.
+    pub fn message(role: &str, content: String) -> Self {
+        Self::new(PinnedItemKind::Message, format!("{role} message"), content)
+    }
+
+    /// Pin a file's contents, labelled with its file name.
+    pub fn file(path: &Path, content: String) -> Self {
+        let label = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        Self::new(PinnedItemKind::File, label, content)
+    }
+
+    /// Pin a free-form note, labelled with its first line.
+    pub fn note(content: String) -> Self {
+        let first_line = content.lines().next().unwrap_or_default().trim();
+        let label = if first_line.chars().count() > NOTE_LABEL_CHARS {
+            let truncated: String = first_line.chars().take(NOTE_LABEL_CHARS).collect();
+            format!("{truncated}…")
+        } else {
+            first_line.to_string()
+        };
+        Self::new(PinnedItemKind::Note, label, content)
+    }
+}
+
+/// Build the context block injected ahead of the user's message.
+///
+/// Returns `None` when nothing is pinned.
+pub fn build_pinned_context_block(items: &[PinnedItem]) -> Option<String> {
+    if items.is_empty() {
+        return None;
+    }
+
+    let mut block = String::from("[Pinned context — always included by the user]\n");
+    for item in items {
+        block.push_str(&format!("--- {}: {} ---\n", item.kind.label(), item.label));
+        block.push_str(&item.content);
+        if !item.content.ends_with('\n') {
+            block.push('\n');
+        }
+    }
+    block.push_str("[End of pinned context]\n\n");
+    Some(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_pins_produce_no_block() {
+        assert!(build_pinned_context_block(&[]).is_none());
+    }
+
+    #[test]
+    fn block_includes_content_verbatim_in_pin_order() {
+        let items = vec![
+            PinnedItem::note("Always answer in British English".to_string()),
+            PinnedItem::file(
+                Path::new("/tmp/project/schema.sql"),
+                "CREATE TABLE users (id INTEGER);".to_string(),
+            ),
+        ];
+
+        let block = build_pinned_context_block(&items).unwrap();
+
+        let note_pos = block.find("Always answer in British English").unwrap();
+        let file_pos = block.find("CREATE TABLE users (id INTEGER);\n").unwrap();
+        assert!(note_pos < file_pos);
+        assert!(block.contains("--- File: schema.sql ---"));
+        assert!(block.ends_with("[End of pinned context]\n\n"));
+    }
+
+    #[test]
+    fn note_label_is_truncated_first_line() {
+        let item = PinnedItem::note(format!("{}\nsecond line", "x".repeat(60)));
+        assert_eq!(item.label.chars().count(), NOTE_LABEL_CHARS + 1);
+        assert!(item.label.ends_with('…'));
+        assert_eq!(item.kind, PinnedItemKind::Note);
+    }
+
+    #[test]
+    fn pinned_items_roundtrip_through_json() {
+        let items = vec![PinnedItem::message("Assistant", "Use port 8080".into())];
+        let json = serde_json::to_string(&items).unwrap();
+        assert!(json.contains("\"kind\":\"message\""));
+        let restored: Vec<PinnedItem> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, items);
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Lowest and highest rating a judge can give on each axis.
+pub const MIN_RATING: u8 = 1;
+pub const MAX_RATING: u8 = 5;
+
+/// A judge model's rating of a completed conversation, used to filter
+/// exported training data.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QualityScore {
+    /// How useful the assistant's answers were (1–5)
+    pub helpfulness: u8,
+    /// Whether tools were used where needed and without wasted calls (1–5)
+    pub tool_efficiency: u8,
+    /// Whether the user's request was resolved by the end (1–5)
+    pub resolution: u8,
+    /// Short reason given by the judge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+    /// ID of the model that judged the conversation
+    pub judge_model_id: String,
+    /// Number of messages the rating covers
+    pub message_count: usize,
+    /// Unix timestamp (seconds) of the rating
+    pub scored_at: i64,
+}
+
+impl QualityScore {
+    /// Mean of the three ratings.
+    pub fn overall(&self) -> f32 {
+        (self.helpfulness + self.tool_efficiency + self.resolution) as f32 / 3.0
+    }
+}
@@ -0,0 +1,326 @@
+//! Wall-clock and cost budget for one agentic task.
+//!
+//! A budget set with `/timebox` covers the next message and the follow-ups the
+//! agent is sent on its own. [`BudgetTracker`] measures the task against it:
+//! once [`WRAP_UP_AT`] of either limit is used the agent is told to wrap up,
+//! and when a limit is reached the stream is stopped and its trace marked.
+//! Cost while streaming is estimated from the text received, and replaced
+//! by the provider's token counts when they arrive.
+
+use std::time::{Duration, Instant};
+
+/// Share of the budget after which the agent is told to wrap up.
+pub const WRAP_UP_AT: f64 = 0.8;
+
+/// Longest duration `/timebox` accepts.
+pub const MAX_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Rough characters per token, to estimate output cost while streaming.
+const CHARS_PER_TOKEN: f64 = 4.0;
+
+/// Instruction sent when the budget nears exhaustion.
+pub const WRAP_UP_PROMPT: &str = "You are close to the time or cost budget for this task. \
+    Stop starting new work: do not call any more tools unless one is needed to save work \
+    in progress. Wrap up now with a short summary of what you did, what is left to do and \
+    how to continue.";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SessionBudget {
+    pub max_duration: Option<Duration>,
+    pub max_cost_usd: Option<f64>,
+}
+
+impl SessionBudget {
+    /// Parse `10m`, `$0.50`, `90s`, `1h`, or both, e.g. `10m $0.50`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut budget = Self::default();
+        for part in text.split_whitespace() {
+            if let Some(cost) = part.strip_prefix('$') {
+                let cost: f64 = cost
+                    .parse()
+                    .ok()
+                    .filter(|c: &f64| c.is_finite() && *c > 0.0)
+                    .ok_or_else(|| format!("'{part}' is not a cost like $0.50"))?;
+                budget.max_cost_usd = Some(cost);
+                continue;
+            }
+            let split = part
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(part.len());
+            let (amount, unit) = part.split_at(split);
+            let seconds = match (amount.parse::<u64>(), unit) {
+                (Ok(n), "s") => Some(n),
+                (Ok(n), "m") => n.checked_mul(60),
+                (Ok(n), "h") => n.checked_mul(60 * 60),
+                _ => return Err(format!("'{part}' is not a duration like 10m, 90s or 1h")),
+            };
+            let duration = seconds.map(Duration::from_secs);
+            if duration.is_none_or(|d| d > MAX_DURATION) {
+                return Err(format!("'{part}' is longer than the 24h limit"));
+            }
+            if seconds == Some(0) {
+                return Err(format!("'{part}' is not a duration like 10m, 90s or 1h"));
+            }
+            budget.max_duration = duration;
+        }
+        if budget.max_duration.is_none() && budget.max_cost_usd.is_none() {
+            return Err("Give a duration, a cost or both, e.g. 10m $0.50".to_string());
+        }
+        Ok(budget)
+    }
+
+    /// e.g. `10m 0s / $0.50`
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = self
+            .max_duration
+            .map(format_duration)
+            .into_iter()
+            .chain(self.max_cost_usd.map(|c| format!("${c:.2}")))
+            .collect();
+        parts.join(" / ")
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else {
+        format!("{}m {}s", secs / 60, secs % 60)
+    }
+}
+
+/// What the stream should do about the budget.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BudgetAction {
+    /// Interrupt the stream and send [`WRAP_UP_PROMPT`]
+    WrapUp,
+    /// Stop the stream; the string is the budget-reached marker
+    Stop(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct BudgetTracker {
+    budget: SessionBudget,
+    /// Set when the first stream of the task starts
+    started_at: Option<Instant>,
+    /// Cost reported by the provider for finished requests
+    spent_usd: f64,
+    /// Estimated cost of the text received since the last report
+    estimated_usd: f64,
+    wrap_up_sent: bool,
+    stopped: bool,
+}
+
+impl BudgetTracker {
+    pub fn new(budget: SessionBudget) -> Self {
+        Self {
+            budget,
+            started_at: None,
+            spent_usd: 0.0,
+            estimated_usd: 0.0,
+            wrap_up_sent: false,
+            stopped: false,
+        }
+    }
+
+    pub fn budget(&self) -> SessionBudget {
+        self.budget
+    }
+
+    /// Start the clock, unless an earlier stream of the task already did.
+    pub fn start(&mut self, now: Instant) {
+        self.started_at.get_or_insert(now);
+    }
+
+    /// Whether the agent was already told to wrap up.
+    pub fn wrap_up_sent(&self) -> bool {
+        self.wrap_up_sent
+    }
+
+    fn elapsed(&self, now: Instant) -> Duration {
+        self.started_at
+            .map(|start| now.saturating_duration_since(start))
+            .unwrap_or_default()
+    }
+
+    fn spent(&self) -> f64 {
+        self.spent_usd + self.estimated_usd
+    }
+
+    /// Record `chars` of streamed text at `cost_per_million_output` USD.
+    pub fn on_text(&mut self, chars: usize, cost_per_million_output: Option<f64>) {
+        if let Some(price) = cost_per_million_output {
+            self.estimated_usd += chars as f64 / CHARS_PER_TOKEN / 1_000_000.0 * price;
+        }
+    }
+
+    /// Replace the estimate with the cost the provider reported.
+    pub fn on_usage(&mut self, cost_usd: Option<f64>) {
+        if let Some(cost) = cost_usd {
+            self.spent_usd += cost;
+            self.estimated_usd = 0.0;
+        }
+    }
+
+    /// When `share` of the time limit is used; `None` without one, or when
+    /// it lies beyond what the clock can represent.
+    fn deadline(&self, share: f64) -> Option<Instant> {
+        let max = self.budget.max_duration?;
+        let offset = Duration::try_from_secs_f64(max.as_secs_f64() * share).ok()?;
+        self.started_at?.checked_add(offset)
+    }
+
+    /// Whether `share` of either limit is used.
+    fn reached(&self, share: f64, now: Instant) -> bool {
+        let time = self.deadline(share).is_some_and(|deadline| now >= deadline);
+        let cost = self
+            .budget
+            .max_cost_usd
+            .is_some_and(|max| self.spent() >= max * share);
+        time || cost
+    }
+
+    /// What to do now, each action at most once. A task that blows through
+    /// its whole budget at once is still told to wrap up first.
+    pub fn poll(&mut self, now: Instant) -> Option<BudgetAction> {
+        if self.stopped {
+            return None;
+        }
+        if !self.wrap_up_sent && self.reached(WRAP_UP_AT, now) {
+            self.wrap_up_sent = true;
+            return Some(BudgetAction::WrapUp);
+        }
+        if self.wrap_up_sent && self.reached(1.0, now) {
+            self.stopped = true;
+            return Some(BudgetAction::Stop(self.marker(now)));
+        }
+        None
+    }
+
+    /// When [`Self::poll`] next has something to do because of time alone.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        if self.stopped {
+            return None;
+        }
+        let share = if self.wrap_up_sent { 1.0 } else { WRAP_UP_AT };
+        self.deadline(share)
+    }
+
+    /// e.g. `Budget reached after 9m 58s and $0.41 (budget 10m 0s / $0.50)`
+    pub fn marker(&self, now: Instant) -> String {
+        let mut used = format!(
+            "Budget reached after {}",
+            format_duration(self.elapsed(now))
+        );
+        if self.budget.max_cost_usd.is_some() {
+            used.push_str(&format!(" and ${:.2}", self.spent()));
+        }
+        format!("{used} (budget {})", self.budget.summary())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations_and_costs() {
+        let budget = SessionBudget::parse("10m $0.50").unwrap();
+        assert_eq!(budget.max_duration, Some(Duration::from_secs(600)));
+        assert_eq!(budget.max_cost_usd, Some(0.5));
+        assert_eq!(budget.summary(), "10m 0s / $0.50");
+        assert_eq!(
+            SessionBudget::parse("90s").unwrap().max_duration,
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(SessionBudget::parse("$2").unwrap().summary(), "$2.00");
+        assert!(SessionBudget::parse("").is_err());
+        assert!(SessionBudget::parse("10 minutes").is_err());
+        assert!(SessionBudget::parse("$-1").is_err());
+        assert!(SessionBudget::parse("0m").is_err());
+    }
+
+    #[test]
+    fn rejects_huge_durations() {
+        assert_eq!(
+            SessionBudget::parse("24h").unwrap().max_duration,
+            Some(MAX_DURATION)
+        );
+        for text in [
+            "25h",
+            "1441m",
+            "18446744073709551615s",
+            "18446744073709551615h",
+        ] {
+            assert!(
+                SessionBudget::parse(text).is_err(),
+                "{text} should be rejected"
+            );
+        }
+        // A tracker built around the parser never overflows the clock either.
+        let mut tracker = BudgetTracker::new(SessionBudget {
+            max_duration: Some(Duration::MAX),
+            max_cost_usd: None,
+        });
+        let now = Instant::now();
+        tracker.start(now);
+        assert_eq!(tracker.poll(now), None);
+        assert_eq!(tracker.next_deadline(), None);
+    }
+
+    #[test]
+    fn wraps_up_then_stops_on_time() {
+        let start = Instant::now();
+        let mut tracker = BudgetTracker::new(SessionBudget::parse("10m").unwrap());
+        tracker.start(start);
+        assert_eq!(tracker.poll(start), None);
+        assert_eq!(
+            tracker.next_deadline(),
+            Some(start + Duration::from_secs(480))
+        );
+
+        let wrap_up = start + Duration::from_secs(480);
+        assert_eq!(tracker.poll(wrap_up), Some(BudgetAction::WrapUp));
+        assert_eq!(tracker.poll(wrap_up), None);
+        // A later stream of the same task keeps the original start
+        tracker.start(wrap_up);
+        assert_eq!(
+            tracker.next_deadline(),
+            Some(start + Duration::from_secs(600))
+        );
+
+        let end = start + Duration::from_secs(600);
+        assert_eq!(
+            tracker.poll(end),
+            Some(BudgetAction::Stop(
+                "Budget reached after 10m 0s (budget 10m 0s)".to_string()
+            ))
+        );
+        assert_eq!(tracker.poll(end), None);
+        assert_eq!(tracker.next_deadline(), None);
+    }
+
+    #[test]
+    fn cost_is_estimated_until_reported() {
+        let now = Instant::now();
+        let mut tracker = BudgetTracker::new(SessionBudget::parse("$1").unwrap());
+        tracker.start(now);
+        assert_eq!(tracker.next_deadline(), None);
+
+        // 3.2M chars ≈ 800k tokens at $1 per million output tokens
+        tracker.on_text(3_200_000, Some(1.0));
+        assert_eq!(tracker.poll(now), Some(BudgetAction::WrapUp));
+
+        // The provider reports less than estimated
+        tracker.on_usage(Some(0.5));
+        assert_eq!(tracker.poll(now), None);
+        tracker.on_usage(Some(0.5));
+        assert_eq!(
+            tracker.poll(now),
+            Some(BudgetAction::Stop(
+                "Budget reached after 0s and $1.00 (budget $1.00)".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Writing direction detection for message text.
+//!
+//! A paragraph's direction follows its first strong character, as in the
+//! Unicode bidi algorithm's paragraph level rule (P2/P3): Hebrew, Arabic and
+//! the other right-to-left scripts make it right-to-left, any other letter
+//! makes it left-to-right. Digits, punctuation and markdown syntax are
+//! neutral, so `1. שלום` is right-to-left. Paragraphs without a strong
+//! character take the direction of the paragraph before them.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl TextDirection {
+    pub fn is_rtl(self) -> bool {
+        self == TextDirection::RightToLeft
+    }
+}
+
+/// Direction of the app layout (sidebar side, default text alignment).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutDirection {
+    /// Follow the UI language
+    #[default]
+    Auto,
+    LeftToRight,
+    /// Mirrored: sidebar on the right, text aligned right
+    RightToLeft,
+}
+
+impl LayoutDirection {
+    pub const ALL: [LayoutDirection; 3] = [Self::Auto, Self::LeftToRight, Self::RightToLeft];
+
+    /// Resolve against whether the UI language is written right to left.
+    pub fn resolve(self, rtl_locale: bool) -> TextDirection {
+        match self {
+            LayoutDirection::Auto if rtl_locale => TextDirection::RightToLeft,
+            LayoutDirection::Auto | LayoutDirection::LeftToRight => TextDirection::LeftToRight,
+            LayoutDirection::RightToLeft => TextDirection::RightToLeft,
+        }
+    }
+}
+
+/// Languages written right to left, by ISO 639-1 code.
+pub const RTL_LANGUAGES: &[&str] = &["ar", "dv", "fa", "he", "ps", "sd", "ug", "ur", "yi"];
+
+/// Whether `c` belongs to a right-to-left script.
+pub fn is_rtl_char(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF | 0x10800..=0x10FFF | 0x1E800..=0x1EFFF
+    )
+}
+
+/// Direction of the first strong character in `text`, if any.
+pub fn detect(text: &str) -> Option<TextDirection> {
+    text.chars().find_map(|c| {
+        if is_rtl_char(c) {
+            Some(TextDirection::RightToLeft)
+        } else if c.is_alphabetic() {
+            Some(TextDirection::LeftToRight)
+        } else {
+            None
+        }
+    })
+}
+
+/// Whether `text` contains any right-to-left character. Cheap check used to
+/// skip paragraph splitting for the common all-LTR case.
+pub fn contains_rtl(text: &str) -> bool {
+    text.chars().any(is_rtl_char)
+}
+
+/// Split `text` at blank lines into runs of consecutive paragraphs with the
+/// same direction. `base` is used until the first strong character.
+pub fn split_by_direction(text: &str, base: TextDirection) -> Vec<(TextDirection, String)> {
+    let mut runs: Vec<(TextDirection, String)> = Vec::new();
+    let mut direction = base;
+
+    for paragraph in text.split("\n\n") {
+        direction = detect(paragraph).unwrap_or(direction);
+        match runs.last_mut() {
+            Some((run_direction, run)) if *run_direction == direction => {
+                run.push_str("\n\n");
+                run.push_str(paragraph);
+            }
+            _ => runs.push((direction, paragraph.to_string())),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_direction_from_first_strong_character() {
+        assert_eq!(detect("1. שלום world"), Some(TextDirection::RightToLeft));
+        assert_eq!(detect("**Hello** مرحبا"), Some(TextDirection::LeftToRight));
+        assert_eq!(detect("42 — ?"), None);
+        assert!(contains_rtl("see مرحبا"));
+        assert!(!contains_rtl("plain text"));
+    }
+
+    #[test]
+    fn groups_paragraphs_into_direction_runs() {
+        let text = "Intro\n\nשלום\n\n123\n\nEnd";
+        let runs = split_by_direction(text, TextDirection::LeftToRight);
+        assert_eq!(
+            runs,
+            vec![
+                (TextDirection::LeftToRight, "Intro".to_string()),
+                (TextDirection::RightToLeft, "שלום\n\n123".to_string()),
+                (TextDirection::LeftToRight, "End".to_string()),
+            ]
+        );
+        assert_eq!(
+            LayoutDirection::Auto.resolve(true),
+            TextDirection::RightToLeft
+        );
+    }
+}
@@ -133,8 +133,18 @@ impl ConversationTokenUsage {
         self.message_usages.push(usage);
     }
 
+    /// Drop the usage of the `turn`-th response (0-based) and recalculate
+    /// the totals. Returns `false` when there is no usage for that turn.
+    pub fn remove_usage(&mut self, turn: usize) -> bool {
+        if turn >= self.message_usages.len() {
+            return false;
+        }
+        self.message_usages.remove(turn);
+        self.recalculate_totals();
+        true
+    }
+
     /// Recalculate totals from per-message usages
-    #[allow(dead_code)]
     pub fn recalculate_totals(&mut self) {
         self.total_input_tokens = self.message_usages.iter().map(|u| u.input_tokens).sum();
         self.total_output_tokens = self.message_usages.iter().map(|u| u.output_tokens).sum();
@@ -145,3 +155,25 @@ impl ConversationTokenUsage {
             .sum();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removing_a_usage_recalculates_totals() {
+        let mut usage = ConversationTokenUsage::new();
+        for (input, output, cost) in [(100, 10, 0.5), (200, 20, 1.0)] {
+            let mut turn = TokenUsage::new(input, output);
+            turn.estimated_cost_usd = Some(cost);
+            usage.add_usage(turn);
+        }
+
+        assert!(usage.remove_usage(0));
+        assert_eq!(usage.message_usages.len(), 1);
+        assert_eq!(usage.total_input_tokens, 200);
+        assert_eq!(usage.total_output_tokens, 20);
+        assert_eq!(usage.total_estimated_cost_usd, 1.0);
+        assert!(!usage.remove_usage(1));
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+use crate::settings::models::models_store::ModelConfig;
+
+/// Temperatures offered in the send button's parameters popover.
+pub const TEMPERATURE_PRESETS: &[f32] = &[0.0, 0.3, 0.7, 1.0, 1.5];
+
+/// `ModelConfig::extra_params` key the provider builder reads the reasoning
+/// effort from.
+pub const REASONING_EFFORT_PARAM: &str = "reasoning_effort";
+
+/// How much reasoning a reasoning model should do before answering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+impl ReasoningEffort {
+    pub const ALL: [ReasoningEffort; 3] = [Self::Low, Self::Medium, Self::High];
+
+    /// Value sent to the provider.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasoningEffort::Low => "low",
+            ReasoningEffort::Medium => "medium",
+            ReasoningEffort::High => "high",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|effort| effort.as_str() == value)
+    }
+}
+
+/// Parameters chosen for a single turn in place of the conversation's model
+/// defaults. The conversation itself is left unchanged; the overrides are
+/// recorded on the turn's trace.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TurnOverrides {
+    /// Model used instead of the conversation's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_id: Option<String>,
+    /// Display name of `model_id`, kept so the trace stays readable if the
+    /// model is later removed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+}
+
+impl TurnOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.model_id.is_none() && self.temperature.is_none() && self.reasoning_effort.is_none()
+    }
+
+    /// `base` with the temperature and reasoning effort overrides applied.
+    /// The model override is resolved by the caller, which passes the
+    /// overriding model as `base`.
+    pub fn apply(&self, base: &ModelConfig) -> ModelConfig {
+        let mut model = base.clone();
+        if let Some(temperature) = self.temperature {
+            model.temperature = temperature;
+        }
+        if let Some(effort) = self.reasoning_effort {
+            model.extra_params.insert(
+                REASONING_EFFORT_PARAM.to_string(),
+                effort.as_str().to_string(),
+            );
+        }
+        model
+    }
+
+    /// One-line description for the trace header, e.g.
+    /// `GPT-4o · temperature 0.2 · reasoning high`.
+    pub fn summary(&self) -> String {
+        let model = self.model_name.clone().or_else(|| self.model_id.clone());
+        let temperature = self.temperature.map(|t| format!("temperature {t}"));
+        let effort = self
+            .reasoning_effort
+            .map(|effort| format!("reasoning {}", effort.as_str()));
+        [model, temperature, effort]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::models::providers_store::ProviderType;
+
+    #[test]
+    fn applies_parameters_and_summarizes() {
+        let base = ModelConfig::new(
+            "gpt".into(),
+            "GPT".into(),
+            ProviderType::OpenRouter,
+            "openai/gpt".into(),
+        );
+        let overrides = TurnOverrides {
+            model_id: Some("o3".into()),
+            model_name: Some("o3".into()),
+            temperature: Some(0.2),
+            reasoning_effort: Some(ReasoningEffort::High),
+        };
+        let model = overrides.apply(&base);
+        assert_eq!(model.temperature, 0.2);
+        assert_eq!(
+            model
+                .extra_params
+                .get(REASONING_EFFORT_PARAM)
+                .map(String::as_str),
+            Some("high")
+        );
+        assert_eq!(overrides.summary(), "o3 · temperature 0.2 · reasoning high");
+        assert!(TurnOverrides::default().is_empty());
+        assert_eq!(
+            ReasoningEffort::parse("medium"),
+            Some(ReasoningEffort::Medium)
+        );
+    }
+}
@@ -0,0 +1,845 @@
+//! Shareable packs of prompt templates, agent profiles and MCP tools.
+//!
+//! A pack is a JSON envelope imported from a URL or a file:
+//!
+//! ```json
+//! { "format": "chatty-prompt-pack",
+//!   "public_key": "<hex Ed25519 public key>",
+//!   "signature": "<Base64 Ed25519 signature>",
+//!   "manifest": "<PromptPackManifest as a JSON string>" }
+//! ```
+//!
+//! The signature covers the hex SHA-256 of the `manifest` string, the same
+//! scheme Hive modules and the managed config use. Packs are trusted on first
+//! use: the user sees the key's fingerprint in the preview, and updates must
+//! be signed with the key the installed version was signed with.
+//!
+//! Templates install as snippets, profiles as models (a provider model with
+//! a system prompt), and tools as entries of the [MCP catalog](crate::mcp_catalog).
+//! Tools that need no settings are installed right away; the rest show up in
+//! the catalog to be installed with their settings. The installed pack
+//! records what it created, so updates and removal never touch the user's
+//! own snippets, models or servers.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::mcp_catalog::{self, McpCatalogEntry};
+use crate::settings::models::extensions_store::{
+    ExtensionSource, ExtensionsModel, InstalledExtension,
+};
+use crate::settings::models::mcp_store::McpServerConfig;
+use crate::settings::models::models_store::{ModelConfig, ModelsModel};
+use crate::settings::models::prompt_packs_store::{
+    InstalledPromptPack, PromptPackSource, PromptPacksModel,
+};
+use crate::settings::models::providers_store::ProviderType;
+use crate::settings::models::snippets_store::SnippetsModel;
+
+/// Value of the envelope's `format` field.
+pub const PACK_FORMAT: &str = "chatty-prompt-pack";
+
+#[derive(Debug, thiserror::Error)]
+pub enum PromptPackError {
+    #[error("Failed to read the pack: {0}")]
+    Read(#[from] std::io::Error),
+    #[error("Failed to download the pack: {0}")]
+    Fetch(#[from] reqwest::Error),
+    #[error("Packs can only be downloaded over HTTPS")]
+    InsecureUrl,
+    #[error("Invalid pack: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("Not a Chatty prompt pack (format '{0}')")]
+    Format(String),
+    #[error("Pack signature invalid: {0}")]
+    Signature(String),
+    #[error("'{0}' is not a valid name (use letters, digits, '-' and '_')")]
+    InvalidName(String),
+    #[error("Pack '{0}' is signed with a different key than the installed version")]
+    PublisherChanged(String),
+}
+
+/// The signed file as published.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PromptPackEnvelope {
+    pub format: String,
+    pub public_key: String,
+    pub signature: String,
+    /// The manifest as JSON text, exactly as signed.
+    pub manifest: String,
+}
+
+/// What a pack contains.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PromptPackManifest {
+    /// Stable slug; prefixes the ids of the models the pack creates.
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Revision of the pack; updates are offered when it increases.
+    pub version: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    /// Where newer versions are published, when that differs from the URL
+    /// the pack is imported from.
+    #[serde(default)]
+    pub update_url: Option<String>,
+    #[serde(default)]
+    pub templates: Vec<PackTemplate>,
+    #[serde(default)]
+    pub profiles: Vec<PackProfile>,
+    #[serde(default)]
+    pub tools: Vec<McpCatalogEntry>,
+}
+
+/// A prompt template, installed as a snippet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PackTemplate {
+    /// Snippet name typed after `/`.
+    pub name: String,
+    pub content: String,
+}
+
+/// An agent profile: a provider model with its own system prompt.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PackProfile {
+    pub id: String,
+    pub name: String,
+    pub provider_type: ProviderType,
+    pub model_identifier: String,
+    #[serde(default)]
+    pub preamble: String,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+impl PackProfile {
+    /// The model this profile installs as, for pack `pack_id`.
+    pub fn to_model(&self, pack_id: &str) -> ModelConfig {
+        let mut model = ModelConfig::new(
+            profile_model_id(pack_id, &self.id),
+            self.name.clone(),
+            self.provider_type.clone(),
+            self.model_identifier.clone(),
+        );
+        let (supports_images, supports_pdf) = self.provider_type.default_capabilities();
+        model.supports_images = supports_images;
+        model.supports_pdf = supports_pdf;
+        model.preamble = self.preamble.clone();
+        if let Some(temperature) = self.temperature {
+            model.temperature = temperature;
+        }
+        model.aliases = self.aliases.clone();
+        model
+    }
+}
+
+/// Model id for profile `profile_id` of pack `pack_id`.
+pub fn profile_model_id(pack_id: &str, profile_id: &str) -> String {
+    format!("pack-{pack_id}-{profile_id}")
+}
+
+/// A pack whose signature checked out, ready to preview and install.
+#[derive(Clone, Debug)]
+pub struct VerifiedPromptPack {
+    pub manifest: PromptPackManifest,
+    pub public_key: String,
+    pub source: PromptPackSource,
+}
+
+impl VerifiedPromptPack {
+    /// Short form of the publisher key for the user to compare.
+    pub fn fingerprint(&self) -> String {
+        fingerprint(&self.public_key)
+    }
+}
+
+/// The first 16 hex digits of the SHA-256 of `public_key`, in groups of four.
+pub fn fingerprint(public_key: &str) -> String {
+    let hash = hex::encode(Sha256::digest(public_key.trim().to_lowercase().as_bytes()));
+    hash.as_bytes()[..16]
+        .chunks(4)
+        .map(|group| String::from_utf8_lossy(group).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Check an envelope's signature and parse its manifest.
+pub fn verify(json: &str) -> Result<(PromptPackManifest, String), PromptPackError> {
+    let envelope: PromptPackEnvelope = serde_json::from_str(json)?;
+    if envelope.format != PACK_FORMAT {
+        return Err(PromptPackError::Format(envelope.format));
+    }
+    crate::hive::verify::verify_module(&crate::hive::verify::VerifyInput {
+        wasm_hash: hex::encode(Sha256::digest(envelope.manifest.as_bytes())),
+        signature: envelope.signature.trim().to_string(),
+        publisher_public_key: envelope.public_key.trim().to_string(),
+    })
+    .map_err(|e| PromptPackError::Signature(e.to_string()))?;
+
+    let manifest: PromptPackManifest = serde_json::from_str(&envelope.manifest)?;
+    let names = std::iter::once(&manifest.id)
+        .chain(manifest.templates.iter().map(|t| &t.name))
+        .chain(manifest.profiles.iter().map(|p| &p.id));
+    for name in names {
+        if !SnippetsModel::is_valid_name(name) {
+            return Err(PromptPackError::InvalidName(name.clone()));
+        }
+    }
+    Ok((manifest, envelope.public_key.trim().to_lowercase()))
+}
+
+/// Read or download a pack and verify it.
+pub async fn load(source: PromptPackSource) -> Result<VerifiedPromptPack, PromptPackError> {
+    let json = match &source {
+        PromptPackSource::File { path } => tokio::fs::read_to_string(path).await?,
+        PromptPackSource::Url { url } => download(url).await?,
+    };
+    let (manifest, public_key) = verify(&json)?;
+    Ok(VerifiedPromptPack {
+        manifest,
+        public_key,
+        source,
+    })
+}
+
+async fn download(url: &str) -> Result<String, PromptPackError> {
+    if !url.starts_with("https://") {
+        return Err(PromptPackError::InsecureUrl);
+    }
+    let response = crate::services::http_client::default_client(30)
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?;
+    // Redirects are followed, but never down to plain HTTP.
+    if response.url().scheme() != "https" {
+        return Err(PromptPackError::InsecureUrl);
+    }
+    Ok(response.text().await?)
+}
+
+// ── Preview ────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PackItemKind {
+    Template,
+    Profile,
+    Tool,
+}
+
+/// What installing the pack does to one item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PackItemChange {
+    Added,
+    Updated,
+    Unchanged,
+    /// The user already has an item with this name; theirs is kept.
+    Conflict,
+    /// A tool that needs settings; it is added to the MCP catalog instead.
+    NeedsSetup,
+    /// Installed by the previous version and dropped from this one.
+    Removed,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackPreviewItem {
+    pub kind: PackItemKind,
+    pub name: String,
+    /// Template text, profile model or tool description.
+    pub detail: String,
+    pub change: PackItemChange,
+}
+
+/// Everything the user needs to decide whether to install a pack.
+#[derive(Clone, Debug)]
+pub struct PromptPackPreview {
+    pub pack: VerifiedPromptPack,
+    /// Version already installed, when this is an update.
+    pub installed_version: Option<String>,
+    /// The installed version was signed with another key; installing fails.
+    pub publisher_changed: bool,
+    pub items: Vec<PackPreviewItem>,
+}
+
+/// Compare `pack` with what is installed.
+pub fn preview(
+    pack: VerifiedPromptPack,
+    packs: &PromptPacksModel,
+    snippets: &SnippetsModel,
+    models: &ModelsModel,
+    extensions: &ExtensionsModel,
+) -> PromptPackPreview {
+    let manifest = &pack.manifest;
+    let installed = packs.find(&manifest.id);
+    let owns_snippet = |name: &str| {
+        installed.is_some_and(|p| p.snippets.iter().any(|s| s.eq_ignore_ascii_case(name)))
+    };
+    let owns_extension = |id: &str| installed.is_some_and(|p| p.extensions.iter().any(|e| e == id));
+    let mut items = Vec::new();
+
+    for template in &manifest.templates {
+        let change = match snippets.find(&template.name) {
+            None => PackItemChange::Added,
+            Some(_) if !owns_snippet(&template.name) => PackItemChange::Conflict,
+            Some(existing) if existing.content == template.content => PackItemChange::Unchanged,
+            Some(_) => PackItemChange::Updated,
+        };
+        items.push(PackPreviewItem {
+            kind: PackItemKind::Template,
+            name: format!("/{}", template.name),
+            detail: template.content.clone(),
+            change,
+        });
+    }
+
+    for profile in &manifest.profiles {
+        let model = profile.to_model(&manifest.id);
+        let change = match models.get_model(&model.id) {
+            None => PackItemChange::Added,
+            Some(existing) if same_profile(existing, &model) => PackItemChange::Unchanged,
+            Some(_) => PackItemChange::Updated,
+        };
+        items.push(PackPreviewItem {
+            kind: PackItemKind::Profile,
+            name: profile.name.clone(),
+            detail: format!("{:?} · {}", profile.provider_type, profile.model_identifier),
+            change,
+        });
+    }
+
+    for tool in &manifest.tools {
+        let id = tool.extension_id();
+        let change = match extensions.find(&id) {
+            Some(ext) if owns_extension(&id) => {
+                match mcp_catalog::available_update(ext, std::slice::from_ref(tool)) {
+                    Some(_) => PackItemChange::Updated,
+                    None => PackItemChange::Unchanged,
+                }
+            }
+            // Set up by the user from the catalog; its updates are offered there
+            Some(ext) if installed_from_catalog(ext, tool) => PackItemChange::Unchanged,
+            Some(_) => PackItemChange::Conflict,
+            None if extensions.find_mcp_by_name(&tool.id).is_some() => PackItemChange::Conflict,
+            None if needs_setup(tool) => PackItemChange::NeedsSetup,
+            None => PackItemChange::Added,
+        };
+        items.push(PackPreviewItem {
+            kind: PackItemKind::Tool,
+            name: tool.name.clone(),
+            detail: tool.description.clone(),
+            change,
+        });
+    }
+
+    if let Some(installed) = installed {
+        items.extend(removed_items(installed, manifest));
+    }
+
+    PromptPackPreview {
+        installed_version: installed.map(|p| p.manifest.version.clone()),
+        publisher_changed: installed.is_some_and(|p| p.publisher_key != pack.public_key),
+        pack,
+        items,
+    }
+}
+
+fn same_profile(a: &ModelConfig, b: &ModelConfig) -> bool {
+    a.name == b.name
+        && a.provider_type == b.provider_type
+        && a.model_identifier == b.model_identifier
+        && a.preamble == b.preamble
+        && a.temperature == b.temperature
+        && a.aliases == b.aliases
+}
+
+fn installed_from_catalog(ext: &InstalledExtension, tool: &McpCatalogEntry) -> bool {
+    matches!(&ext.source, ExtensionSource::McpCatalog { entry_id, .. } if entry_id == &tool.id)
+}
+
+/// Whether installing `tool` needs values from the user.
+fn needs_setup(tool: &McpCatalogEntry) -> bool {
+    tool.inputs.iter().any(|input| input.required)
+}
+
+/// Items the installed version created that `manifest` no longer has.
+fn removed_items(
+    installed: &InstalledPromptPack,
+    manifest: &PromptPackManifest,
+) -> Vec<PackPreviewItem> {
+    let old = &installed.manifest;
+    let mut items = Vec::new();
+    for template in &old.templates {
+        let owned = installed
+            .snippets
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(&template.name));
+        if owned && !manifest.templates.iter().any(|t| t.name == template.name) {
+            items.push(PackPreviewItem {
+                kind: PackItemKind::Template,
+                name: format!("/{}", template.name),
+                detail: template.content.clone(),
+                change: PackItemChange::Removed,
+            });
+        }
+    }
+    for profile in &old.profiles {
+        if !manifest.profiles.iter().any(|p| p.id == profile.id) {
+            items.push(PackPreviewItem {
+                kind: PackItemKind::Profile,
+                name: profile.name.clone(),
+                detail: profile.model_identifier.clone(),
+                change: PackItemChange::Removed,
+            });
+        }
+    }
+    for tool in &old.tools {
+        let owned = installed.extensions.contains(&tool.extension_id());
+        if owned && !manifest.tools.iter().any(|t| t.id == tool.id) {
+            items.push(PackPreviewItem {
+                kind: PackItemKind::Tool,
+                name: tool.name.clone(),
+                detail: tool.description.clone(),
+                change: PackItemChange::Removed,
+            });
+        }
+    }
+    items
+}
+
+// ── Install / remove ───────────────────────────────────────────────────────
+
+/// The local settings a pack installs into.
+pub struct PackTargets<'a> {
+    pub packs: &'a mut PromptPacksModel,
+    pub snippets: &'a mut SnippetsModel,
+    pub models: &'a mut ModelsModel,
+    pub extensions: &'a mut ExtensionsModel,
+    /// The legacy MCP server list, kept in step with the extensions.
+    pub servers: &'a mut Vec<McpServerConfig>,
+}
+
+/// MCP servers whose connection has to change after an install or removal.
+#[derive(Clone, Debug, Default)]
+pub struct PackChanges {
+    /// Servers installed or updated by the pack; reconnect the enabled ones.
+    pub connect: Vec<McpServerConfig>,
+    /// Servers the pack removed.
+    pub disconnect: Vec<String>,
+}
+
+/// Install `pack`, or update the installed version in place. Items whose
+/// names the user already uses are skipped; items dropped since the
+/// installed version are removed.
+pub fn install(
+    pack: &VerifiedPromptPack,
+    installed_at: i64,
+    mut targets: PackTargets<'_>,
+) -> Result<PackChanges, PromptPackError> {
+    let manifest = &pack.manifest;
+    let previous = targets.packs.find(&manifest.id).cloned();
+    if let Some(previous) = &previous
+        && previous.publisher_key != pack.public_key
+    {
+        return Err(PromptPackError::PublisherChanged(manifest.id.clone()));
+    }
+    let owned = |list: fn(&InstalledPromptPack) -> &Vec<String>, item: &str| {
+        previous
+            .as_ref()
+            .is_some_and(|p| list(p).iter().any(|i| i.eq_ignore_ascii_case(item)))
+    };
+
+    let mut record = InstalledPromptPack {
+        manifest: manifest.clone(),
+        publisher_key: pack.public_key.clone(),
+        source: pack.source.clone(),
+        installed_at,
+        snippets: Vec::new(),
+        models: Vec::new(),
+        extensions: Vec::new(),
+    };
+    let mut changes = PackChanges::default();
+
+    for template in &manifest.templates {
+        if targets.snippets.find(&template.name).is_some()
+            && !owned(|p| &p.snippets, &template.name)
+        {
+            continue;
+        }
+        targets
+            .snippets
+            .upsert(&template.name, template.content.clone());
+        record.snippets.push(template.name.clone());
+    }
+
+    for profile in &manifest.profiles {
+        let model = profile.to_model(&manifest.id);
+        record.models.push(model.id.clone());
+        match targets.models.get_model(&model.id) {
+            // Keep what the user tuned outside the profile (costs, limits, rules)
+            Some(existing) => {
+                let mut updated = existing.clone();
+                updated.name = model.name;
+                updated.provider_type = model.provider_type;
+                updated.model_identifier = model.model_identifier;
+                updated.preamble = model.preamble;
+                updated.temperature = model.temperature;
+                updated.aliases = model.aliases;
+                targets.models.update_model(updated);
+            }
+            None => targets.models.add_model(model),
+        }
+    }
+
+    let no_inputs = HashMap::new();
+    let no_secrets = HashSet::new();
+    for tool in manifest.tools.iter().filter(|tool| !needs_setup(tool)) {
+        let id = tool.extension_id();
+        let taken = targets.extensions.is_installed(&id)
+            || targets.extensions.find_mcp_by_name(&tool.id).is_some();
+        if taken && !owned(|p| &p.extensions, &id) {
+            continue;
+        }
+        match mcp_catalog::install_entry(
+            tool,
+            &no_inputs,
+            &no_secrets,
+            targets.extensions,
+            targets.servers,
+        ) {
+            Ok((config, enabled)) => {
+                record.extensions.push(id);
+                if enabled {
+                    changes.connect.push(config);
+                }
+            }
+            Err(e) => warn!(tool = %tool.id, error = %e, "Skipped pack tool"),
+        }
+    }
+
+    if let Some(previous) = &previous {
+        changes.disconnect = remove_owned(previous, Some(&record), &mut targets);
+    }
+    targets.packs.upsert(record);
+    Ok(changes)
+}
+
+/// Remove pack `id` and everything it installed. Returns `None` when no such
+/// pack is installed.
+pub fn uninstall(id: &str, mut targets: PackTargets<'_>) -> Option<PackChanges> {
+    let pack = targets.packs.remove(id)?;
+    Some(PackChanges {
+        connect: Vec::new(),
+        disconnect: remove_owned(&pack, None, &mut targets),
+    })
+}
+
+/// Remove the items `pack` created, except those `keep` still owns. Returns
+/// the names of the removed MCP servers.
+fn remove_owned(
+    pack: &InstalledPromptPack,
+    keep: Option<&InstalledPromptPack>,
+    targets: &mut PackTargets<'_>,
+) -> Vec<String> {
+    let kept = |list: fn(&InstalledPromptPack) -> &Vec<String>, item: &str| {
+        keep.is_some_and(|k| list(k).iter().any(|i| i.eq_ignore_ascii_case(item)))
+    };
+    for name in &pack.snippets {
+        if !kept(|p| &p.snippets, name) {
+            targets.snippets.remove(name);
+        }
+    }
+    for id in &pack.models {
+        if !kept(|p| &p.models, id) {
+            targets.models.delete_model(id);
+        }
+    }
+    let mut disconnect = Vec::new();
+    for id in &pack.extensions {
+        if kept(|p| &p.extensions, id) {
+            continue;
+        }
+        let Some(name) = pack
+            .manifest
+            .tools
+            .iter()
+            .find(|tool| &tool.extension_id() == id)
+            .map(|tool| tool.id.clone())
+        else {
+            continue;
+        };
+        targets.extensions.remove(id);
+        targets.servers.retain(|s| s.name != name);
+        disconnect.push(name);
+    }
+    disconnect
+}
+
+/// Tools of all installed packs, for the MCP catalog.
+pub fn catalog_entries(packs: &PromptPacksModel) -> Vec<McpCatalogEntry> {
+    packs
+        .packs
+        .iter()
+        .flat_map(|pack| pack.manifest.tools.iter().cloned())
+        .collect()
+}
+
+/// Download the published version of every installed pack that has an
+/// update URL, and return those that are newer and signed by the same key.
+pub async fn check_updates(packs: &[InstalledPromptPack]) -> Vec<VerifiedPromptPack> {
+    let mut updates = Vec::new();
+    for installed in packs {
+        let Some(url) = installed.update_url() else {
+            continue;
+        };
+        let source = PromptPackSource::Url {
+            url: url.to_string(),
+        };
+        match load(source).await {
+            Ok(pack) if pack.public_key != installed.publisher_key => warn!(
+                pack = %installed.manifest.id,
+                "Published pack is signed with a different key; ignoring it"
+            ),
+            Ok(pack) if pack.manifest.id != installed.manifest.id => warn!(
+                pack = %installed.manifest.id,
+                published = %pack.manifest.id,
+                "Update URL serves a different pack; ignoring it"
+            ),
+            Ok(pack)
+                if mcp_catalog::is_newer(&pack.manifest.version, &installed.manifest.version) =>
+            {
+                updates.push(pack)
+            }
+            Ok(_) => {}
+            Err(e) => warn!(pack = %installed.manifest.id, error = %e, "Pack update check failed"),
+        }
+    }
+    updates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn envelope(key: &SigningKey, manifest: &serde_json::Value) -> String {
+        let manifest = manifest.to_string();
+        let hash = hex::encode(Sha256::digest(manifest.as_bytes()));
+        serde_json::json!({
+            "format": PACK_FORMAT,
+            "public_key": hex::encode(key.verifying_key().to_bytes()),
+            "signature": BASE64.encode(key.sign(hash.as_bytes()).to_bytes()),
+            "manifest": manifest,
+        })
+        .to_string()
+    }
+
+    fn manifest(version: &str, templates: &[(&str, &str)]) -> serde_json::Value {
+        serde_json::json!({
+            "id": "writing",
+            "name": "Writing Pack",
+            "version": version,
+            "templates": templates
+                .iter()
+                .map(|(name, content)| serde_json::json!({"name": name, "content": content}))
+                .collect::<Vec<_>>(),
+            "profiles": [{
+                "id": "editor",
+                "name": "Copy Editor",
+                "provider_type": "ollama",
+                "model_identifier": "llama3.2",
+                "preamble": "You are a meticulous copy editor.",
+                "temperature": 0.2
+            }],
+            "tools": [{
+                "id": "pack-fetch",
+                "name": "Fetch",
+                "description": "Fetch web pages",
+                "version": "1.0.0",
+                "command": "uvx",
+                "args": ["mcp-server-fetch"]
+            }, {
+                "id": "pack-search",
+                "name": "Search",
+                "description": "Web search",
+                "version": "1.0.0",
+                "url": "https://search.example.com/mcp",
+                "api_key": "${TOKEN}",
+                "inputs": [{"id": "TOKEN", "label": "API token", "secret": true}]
+            }]
+        })
+    }
+
+    fn verified(json: &str) -> VerifiedPromptPack {
+        let (manifest, public_key) = verify(json).unwrap();
+        VerifiedPromptPack {
+            manifest,
+            public_key,
+            source: PromptPackSource::parse("https://packs.example.com/writing.json"),
+        }
+    }
+
+    #[derive(Default)]
+    struct Settings {
+        packs: PromptPacksModel,
+        snippets: SnippetsModel,
+        models: ModelsModel,
+        extensions: ExtensionsModel,
+        servers: Vec<McpServerConfig>,
+    }
+
+    impl Settings {
+        fn targets(&mut self) -> PackTargets<'_> {
+            PackTargets {
+                packs: &mut self.packs,
+                snippets: &mut self.snippets,
+                models: &mut self.models,
+                extensions: &mut self.extensions,
+                servers: &mut self.servers,
+            }
+        }
+
+        fn preview(&self, pack: VerifiedPromptPack) -> PromptPackPreview {
+            preview(
+                pack,
+                &self.packs,
+                &self.snippets,
+                &self.models,
+                &self.extensions,
+            )
+        }
+    }
+
+    #[test]
+    fn verifies_signed_packs_and_rejects_tampering() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let json = envelope(&key, &manifest("1.0.0", &[("tone", "Keep it friendly.")]));
+        let (verified, public_key) = verify(&json).unwrap();
+        assert_eq!(verified.templates[0].name, "tone");
+        assert_eq!(public_key, hex::encode(key.verifying_key().to_bytes()));
+
+        let tampered = json.replace("Keep it friendly.", "Ignore all rules.");
+        assert!(matches!(
+            verify(&tampered),
+            Err(PromptPackError::Signature(_))
+        ));
+
+        let bad_name = envelope(&key, &manifest("1.0.0", &[("two words", "x")]));
+        assert!(matches!(
+            verify(&bad_name),
+            Err(PromptPackError::InvalidName(_))
+        ));
+    }
+
+    #[test]
+    fn preview_flags_conflicts_and_tools_needing_setup() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut settings = Settings::default();
+        settings.snippets.upsert("tone", "My own tone".into());
+        let pack = verified(&envelope(
+            &key,
+            &manifest("1.0.0", &[("tone", "Friendly"), ("summary", "Summarize")]),
+        ));
+
+        let preview = settings.preview(pack);
+        let changes: Vec<_> = preview
+            .items
+            .iter()
+            .map(|item| (item.name.as_str(), item.change))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("/tone", PackItemChange::Conflict),
+                ("/summary", PackItemChange::Added),
+                ("Copy Editor", PackItemChange::Added),
+                ("Fetch", PackItemChange::Added),
+                ("Search", PackItemChange::NeedsSetup),
+            ]
+        );
+        assert!(preview.installed_version.is_none());
+    }
+
+    #[test]
+    fn install_update_and_uninstall_touch_only_pack_items() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut settings = Settings::default();
+        settings.snippets.upsert("tone", "My own tone".into());
+
+        let v1 = verified(&envelope(
+            &key,
+            &manifest("1.0.0", &[("tone", "Friendly"), ("summary", "Summarize")]),
+        ));
+        let changes = install(&v1, 100, settings.targets()).unwrap();
+        assert_eq!(
+            settings.snippets.find("tone").unwrap().content,
+            "My own tone"
+        );
+        assert_eq!(
+            settings.snippets.find("summary").unwrap().content,
+            "Summarize"
+        );
+        let model = settings.models.get_model("pack-writing-editor").unwrap();
+        assert_eq!(model.preamble, "You are a meticulous copy editor.");
+        assert!(settings.extensions.is_installed("mcp-pack-fetch"));
+        assert!(!settings.extensions.is_installed("mcp-pack-search"));
+        assert_eq!(changes.connect.len(), 1);
+        assert_eq!(catalog_entries(&settings.packs).len(), 2);
+
+        // v2 drops the summary template and renames the other one
+        let v2 = verified(&envelope(
+            &key,
+            &manifest("1.1.0", &[("outline", "Outline")]),
+        ));
+        let preview = settings.preview(v2.clone());
+        assert_eq!(preview.installed_version.as_deref(), Some("1.0.0"));
+        assert!(
+            preview
+                .items
+                .iter()
+                .any(|i| i.name == "/summary" && i.change == PackItemChange::Removed)
+        );
+        install(&v2, 200, settings.targets()).unwrap();
+        assert!(settings.snippets.find("summary").is_none());
+        assert!(settings.snippets.find("outline").is_some());
+        assert!(settings.snippets.find("tone").is_some());
+
+        // Updates signed with another key are refused
+        let other = SigningKey::from_bytes(&[8u8; 32]);
+        let forged = verified(&envelope(&other, &manifest("2.0.0", &[])));
+        assert!(settings.preview(forged.clone()).publisher_changed);
+        assert!(matches!(
+            install(&forged, 300, settings.targets()),
+            Err(PromptPackError::PublisherChanged(_))
+        ));
+
+        let changes = uninstall("writing", settings.targets()).unwrap();
+        assert_eq!(changes.disconnect, vec!["pack-fetch"]);
+        assert!(settings.packs.packs.is_empty());
+        assert!(settings.snippets.find("outline").is_none());
+        assert_eq!(
+            settings.snippets.find("tone").unwrap().content,
+            "My own tone"
+        );
+        assert!(settings.models.get_model("pack-writing-editor").is_none());
+        assert!(!settings.extensions.is_installed("mcp-pack-fetch"));
+        assert!(settings.servers.is_empty());
+    }
+
+    #[test]
+    fn fingerprints_are_grouped_and_stable() {
+        let fp = fingerprint("ABCDEF");
+        assert_eq!(fp, fingerprint("abcdef"));
+        assert_eq!(fp.len(), 19);
+        assert_eq!(fp.split(' ').count(), 4);
+    }
+}
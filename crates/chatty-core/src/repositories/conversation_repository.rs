@@ -3,7 +3,9 @@ use std::pin::Pin;
 
 use serde::{Deserialize, Serialize};
 
+use super::conversation_search::MessageSearchHit;
 use super::error::RepositoryResult;
+use crate::models::{ConversationLabel, ConversationSort};
 
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
@@ -27,6 +29,14 @@ fn default_empty_feedback() -> String {
     "[]".to_string()
 }
 
+fn default_empty_reactions() -> String {
+    "[]".to_string()
+}
+
+fn default_empty_notes() -> String {
+    "[]".to_string()
+}
+
 /// Default empty regeneration records for backward compatibility
 fn default_empty_regeneration_records() -> String {
     "[]".to_string()
@@ -42,6 +52,53 @@ fn default_none_agent_task_snapshot() -> Option<String> {
     None
 }
 
+/// Default empty pinned items for backward compatibility
+fn default_empty_pinned_items() -> String {
+    "[]".to_string()
+}
+
+/// Default empty conversation variables for backward compatibility
+fn default_none_execution_plan() -> Option<String> {
+    None
+}
+
+fn default_empty_variables() -> String {
+    "{}".to_string()
+}
+
+/// Default full-history policy for backward compatibility
+fn default_full_history_policy() -> String {
+    r#"{"mode":"full"}"#.to_string()
+}
+
+fn default_empty_disabled_mcp_servers() -> String {
+    "[]".to_string()
+}
+
+fn default_empty_disabled_tools() -> String {
+    "[]".to_string()
+}
+
+fn default_empty_label() -> String {
+    "{}".to_string()
+}
+
+fn default_empty_tags() -> String {
+    "[]".to_string()
+}
+
+fn default_empty_history_edits() -> String {
+    "[]".to_string()
+}
+
+fn default_empty_compaction_records() -> String {
+    "[]".to_string()
+}
+
+fn default_empty_message_errors() -> String {
+    "[]".to_string()
+}
+
 /// Lightweight conversation metadata used for the sidebar.
 /// Loaded at startup without deserializing full message history.
 #[derive(Debug, Clone)]
@@ -50,6 +107,27 @@ pub struct ConversationMetadata {
     pub title: String,
     pub total_cost: f64,
     pub updated_at: i64,
+    pub created_at: i64,
+    /// When the conversation was last opened (None = not since tracking began)
+    pub last_viewed_at: Option<i64>,
+    pub label: ConversationLabel,
+    pub tags: Vec<String>,
+    pub folder: Option<String>,
+    /// Read-only conversations are shown with a lock in the sidebar
+    pub locked: bool,
+}
+
+impl ConversationMetadata {
+    /// Timestamp the conversation is ordered and date-grouped by under `sort`.
+    /// Conversations never opened since views were tracked fall back to
+    /// their update time.
+    pub fn sort_time(&self, sort: ConversationSort) -> i64 {
+        match sort {
+            ConversationSort::Updated => self.updated_at,
+            ConversationSort::Viewed => self.last_viewed_at.unwrap_or(self.updated_at),
+            ConversationSort::Created => self.created_at,
+        }
+    }
 }
 
 /// Serializable conversation data for persistence
@@ -68,6 +146,12 @@ pub struct ConversationData {
     pub message_timestamps: String, // JSON-serialized Vec<Option<i64>> (per-message Unix timestamps)
     #[serde(default = "default_empty_feedback")]
     pub message_feedback: String, // JSON-serialized Vec<Option<MessageFeedback>> (per-message feedback)
+    #[serde(default = "default_empty_reactions")]
+    pub message_reactions: String, // JSON-serialized Vec<Vec<String>> (per-message emoji reactions)
+    #[serde(default = "default_empty_notes")]
+    pub message_notes: String, // JSON-serialized Vec<Option<String>> (per-message reviewer notes)
+    #[serde(default = "default_empty_message_errors")]
+    pub message_errors: String, // JSON-serialized Vec<Option<TurnError>> (per-message stream failures)
     #[serde(default = "default_empty_regeneration_records")]
     pub regeneration_records: String, // JSON-serialized Vec<RegenerationRecord> (DPO preference pairs)
     pub created_at: i64, // Unix timestamp
@@ -76,6 +160,34 @@ pub struct ConversationData {
     pub working_dir: Option<String>, // Per-conversation working directory override
     #[serde(default = "default_none_agent_task_snapshot")]
     pub agent_task_snapshot: Option<String>, // JSON-serialized AgentTaskSnapshot
+    #[serde(default = "default_empty_pinned_items")]
+    pub pinned_items: String, // JSON-serialized Vec<PinnedItem> (always-in-context snippets)
+    #[serde(default = "default_empty_variables")]
+    pub variables: String, // JSON-serialized BTreeMap<String, String> (agent scratchpad)
+    #[serde(default = "default_none_execution_plan")]
+    pub execution_plan: Option<String>, // JSON-serialized ExecutionPlan (planner mode)
+    #[serde(default)]
+    pub confidential: bool, // Network tools blocked by guardrails
+    #[serde(default = "default_full_history_policy")]
+    pub history_policy: String, // JSON-serialized HistoryPolicy (what history is sent each turn)
+    #[serde(default = "default_empty_disabled_mcp_servers")]
+    pub disabled_mcp_servers: String, // JSON-serialized Vec<String> (MCP servers off in this conversation)
+    #[serde(default = "default_empty_disabled_tools")]
+    pub disabled_tools: String, // JSON-serialized Vec<String> (tools off in this conversation)
+    #[serde(default = "default_empty_label")]
+    pub label: String, // JSON-serialized ConversationLabel (sidebar color and emoji)
+    #[serde(default = "default_empty_tags")]
+    pub tags: String, // JSON-serialized Vec<String> (sidebar tags)
+    #[serde(default)]
+    pub folder: Option<String>, // Sidebar folder path, e.g. "clients/acme"
+    #[serde(default = "default_empty_history_edits")]
+    pub history_edits: String, // JSON-serialized Vec<HistoryEdit> (manual deletions, for auditing)
+    #[serde(default = "default_empty_compaction_records")]
+    pub compaction_records: String, // JSON-serialized Vec<CompactionRecord> (summarized history, for auditing)
+    #[serde(default)]
+    pub locked: bool, // Read-only: no new messages, deletions or regenerations
+    #[serde(default)]
+    pub quality_score: Option<String>, // JSON-serialized QualityScore (judge model rating)
 }
 
 impl ConversationData {
@@ -105,4 +217,39 @@ pub trait ConversationRepository: Send + Sync + 'static {
 
     /// Delete a conversation from storage
     fn delete(&self, id: &str) -> BoxFuture<'static, RepositoryResult<()>>;
+
+    /// Update only the label of a conversation, without loading or rewriting it
+    fn save_label(
+        &self,
+        id: &str,
+        label: ConversationLabel,
+    ) -> BoxFuture<'static, RepositoryResult<()>>;
+
+    /// Update only the tags of a conversation, without loading or rewriting it
+    fn save_tags(&self, id: &str, tags: Vec<String>) -> BoxFuture<'static, RepositoryResult<()>>;
+
+    /// Update only the locked state of a conversation, without loading or rewriting it
+    fn save_locked(&self, id: &str, locked: bool) -> BoxFuture<'static, RepositoryResult<()>>;
+
+    /// Update only the folder of a conversation, without loading or rewriting it
+    fn save_folder(
+        &self,
+        id: &str,
+        folder: Option<String>,
+    ) -> BoxFuture<'static, RepositoryResult<()>>;
+
+    /// Record when a conversation was opened, leaving `updated_at` alone
+    fn save_last_viewed(
+        &self,
+        id: &str,
+        viewed_at: i64,
+    ) -> BoxFuture<'static, RepositoryResult<()>>;
+
+    /// Find messages whose text contains every word of `query`, best matches
+    /// first. An empty query finds nothing.
+    fn search_messages(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> BoxFuture<'static, RepositoryResult<Vec<MessageSearchHit>>>;
 }
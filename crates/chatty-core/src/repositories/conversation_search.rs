@@ -0,0 +1,123 @@
+use rig_core::completion::Message;
+
+use crate::models::conversation::role_and_text;
+
+/// Characters of context kept on each side of a match in a snippet.
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+/// A message whose text matches a full-text search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageSearchHit {
+    pub conversation_id: String,
+    /// Index of the message in the conversation's history
+    pub history_index: usize,
+    /// Excerpt of the message around the match
+    pub snippet: String,
+}
+
+/// Searchable text of the user and assistant messages in a serialized
+/// history, keyed by history index. Tool calls, tool results and empty
+/// messages are left out.
+pub(crate) fn indexable_messages(message_history: &str) -> Vec<(usize, String)> {
+    let Ok(messages) = serde_json::from_str::<Vec<Message>>(message_history) else {
+        return Vec::new();
+    };
+    messages
+        .iter()
+        .enumerate()
+        .filter_map(|(ix, message)| {
+            let (_, text) = role_and_text(message)?;
+            (!text.trim().is_empty()).then_some((ix, text))
+        })
+        .collect()
+}
+
+/// Turn what the user typed into an FTS5 query: every word must appear,
+/// and the last word also matches as a prefix so results show while typing.
+/// Words are quoted so FTS5 operators in the input are taken literally.
+pub(crate) fn fts_query(input: &str) -> Option<String> {
+    let words: Vec<String> = input
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", words.join(" ")))
+}
+
+/// Excerpt of `text` around the first word of `query`, if every word of the
+/// query occurs in it (ignoring case). Used where no FTS5 index is available.
+pub(crate) fn match_snippet(text: &str, query: &str) -> Option<String> {
+    let haystack = text.to_lowercase();
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if words.is_empty() || !words.iter().all(|w| haystack.contains(w.as_str())) {
+        return None;
+    }
+
+    let byte_start = haystack.find(words[0].as_str())?;
+    // Lowercasing can change byte lengths, so map back through char counts
+    let chars: Vec<char> = text.chars().collect();
+    let match_char = haystack[..byte_start].chars().count().min(chars.len());
+    let start = match_char.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let end = (match_char + words[0].chars().count() + SNIPPET_CONTEXT_CHARS).min(chars.len());
+
+    let mut snippet: String = chars[start..end].iter().collect();
+    snippet = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    Some(snippet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_user_and_assistant_text_by_history_index() {
+        let history = serde_json::to_string(&vec![
+            Message::user("How do I rotate logs?"),
+            Message::assistant("Use logrotate."),
+            Message::user("   "),
+        ])
+        .unwrap();
+
+        let messages = indexable_messages(&history);
+        assert_eq!(
+            messages,
+            vec![
+                (0, "How do I rotate logs?".to_string()),
+                (1, "Use logrotate.".to_string()),
+            ]
+        );
+        assert!(indexable_messages("not json").is_empty());
+    }
+
+    #[test]
+    fn fts_query_quotes_words_and_prefixes_the_last() {
+        assert_eq!(fts_query("  "), None);
+        assert_eq!(fts_query("rust"), Some("\"rust\"*".to_string()));
+        assert_eq!(
+            fts_query("say \"hi\" OR"),
+            Some("\"say\" \"\"\"hi\"\"\" \"OR\"*".to_string())
+        );
+    }
+
+    #[test]
+    fn snippet_requires_every_word_and_trims_long_text() {
+        let text = format!(
+            "{} the Deploy key was rotated {}",
+            "a ".repeat(40),
+            "b ".repeat(40)
+        );
+        let snippet = match_snippet(&text, "deploy ROTATED").unwrap();
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert!(snippet.contains("the Deploy key was rotated"));
+        assert!(match_snippet(&text, "deploy missing").is_none());
+        assert_eq!(match_snippet("short note", "note").unwrap(), "short note");
+    }
+}
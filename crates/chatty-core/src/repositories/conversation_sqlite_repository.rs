@@ -1,13 +1,18 @@
 use std::path::PathBuf;
 
 use sqlx::Row;
-use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePool, SqlitePoolOptions,
+};
 use tracing::info;
 
 use super::conversation_repository::{
     BoxFuture, ConversationData, ConversationMetadata, ConversationRepository,
 };
+use super::conversation_search::{MessageSearchHit, fts_query, indexable_messages};
 use super::error::{RepositoryError, RepositoryResult};
+use crate::models::ConversationLabel;
+use crate::models::conversation_tags;
 
 /// Migrations applied in order. Each entry is (version, sql).
 /// To add a new migration: append a tuple with the next version number and its SQL.
@@ -38,8 +43,87 @@ const MIGRATIONS: &[(i64, &str)] = &[
         3,
         "ALTER TABLE conversations ADD COLUMN agent_task_snapshot TEXT;",
     ),
+    (
+        4,
+        "ALTER TABLE conversations ADD COLUMN pinned_items TEXT NOT NULL DEFAULT '[]';",
+    ),
+    (
+        5,
+        "ALTER TABLE conversations ADD COLUMN variables TEXT NOT NULL DEFAULT '{}';",
+    ),
+    (
+        6,
+        "ALTER TABLE conversations ADD COLUMN execution_plan TEXT;",
+    ),
+    (
+        7,
+        "ALTER TABLE conversations ADD COLUMN confidential INTEGER NOT NULL DEFAULT 0;",
+    ),
+    (
+        8,
+        "ALTER TABLE conversations ADD COLUMN message_reactions TEXT NOT NULL DEFAULT '[]';
+         ALTER TABLE conversations ADD COLUMN message_notes TEXT NOT NULL DEFAULT '[]';",
+    ),
+    (
+        9,
+        "ALTER TABLE conversations ADD COLUMN history_policy TEXT NOT NULL DEFAULT '{\"mode\":\"full\"}';",
+    ),
+    (
+        10,
+        "ALTER TABLE conversations ADD COLUMN disabled_mcp_servers TEXT NOT NULL DEFAULT '[]';",
+    ),
+    (
+        11,
+        "ALTER TABLE conversations ADD COLUMN disabled_tools TEXT NOT NULL DEFAULT '[]';",
+    ),
+    (
+        12,
+        "ALTER TABLE conversations ADD COLUMN label TEXT NOT NULL DEFAULT '{}';",
+    ),
+    (
+        13,
+        "ALTER TABLE conversations ADD COLUMN history_edits TEXT NOT NULL DEFAULT '[]';",
+    ),
+    (
+        14,
+        "ALTER TABLE conversations ADD COLUMN quality_score TEXT;",
+    ),
+    (
+        15,
+        "ALTER TABLE conversations ADD COLUMN last_viewed_at INTEGER;",
+    ),
+    (
+        SEARCH_INDEX_VERSION,
+        "CREATE VIRTUAL TABLE IF NOT EXISTS message_fts USING fts5(
+        content,
+        conversation_id UNINDEXED,
+        history_index   UNINDEXED,
+        tokenize = 'unicode61 remove_diacritics 2'
+    );",
+    ),
+    (
+        17,
+        "ALTER TABLE conversations ADD COLUMN message_errors TEXT NOT NULL DEFAULT '[]';",
+    ),
+    (
+        18,
+        "ALTER TABLE conversations ADD COLUMN tags TEXT NOT NULL DEFAULT '[]';
+         ALTER TABLE conversations ADD COLUMN folder TEXT;",
+    ),
+    (
+        19,
+        "ALTER TABLE conversations ADD COLUMN compaction_records TEXT NOT NULL DEFAULT '[]';",
+    ),
+    (
+        20,
+        "ALTER TABLE conversations ADD COLUMN locked INTEGER NOT NULL DEFAULT 0;",
+    ),
 ];
 
+/// Migration that adds the `message_fts` full-text index. Databases older
+/// than this have their existing messages indexed once after migrating.
+const SEARCH_INDEX_VERSION: i64 = 16;
+
 /// SQLite-backed repository for conversations.
 ///
 /// Uses WAL journal mode for concurrent reads during background saves.
@@ -67,7 +151,10 @@ impl ConversationSqliteRepository {
             .connect_with(options)
             .await?;
 
-        Self::run_migrations(&pool).await?;
+        let previous_version = Self::run_migrations(&pool).await?;
+        if previous_version < SEARCH_INDEX_VERSION {
+            Self::rebuild_search_index(&pool).await?;
+        }
 
         info!(path = %db_path.display(), "Opened SQLite conversation database");
 
@@ -75,7 +162,8 @@ impl ConversationSqliteRepository {
     }
 
     /// Create the schema_version table if absent, then apply any pending migrations.
-    async fn run_migrations(pool: &SqlitePool) -> RepositoryResult<()> {
+    /// Returns the schema version the database had before migrating.
+    async fn run_migrations(pool: &SqlitePool) -> RepositoryResult<i64> {
         sqlx::query(
             "CREATE TABLE IF NOT EXISTS schema_version (
                 version INTEGER NOT NULL
@@ -111,6 +199,52 @@ impl ConversationSqliteRepository {
             }
         }
 
+        Ok(current)
+    }
+
+    /// Index the messages of every stored conversation from scratch.
+    async fn rebuild_search_index(pool: &SqlitePool) -> RepositoryResult<()> {
+        let rows = sqlx::query("SELECT id, message_history FROM conversations")
+            .fetch_all(pool)
+            .await?;
+
+        let mut tx = pool.begin().await?;
+        sqlx::query("DELETE FROM message_fts")
+            .execute(&mut *tx)
+            .await?;
+        for row in &rows {
+            Self::index_messages(&mut tx, row.get("id"), row.get("message_history")).await?;
+        }
+        tx.commit().await?;
+
+        info!(
+            conversations = rows.len(),
+            "Built full-text message search index"
+        );
+        Ok(())
+    }
+
+    /// Replace the indexed messages of conversation `id`.
+    async fn index_messages(
+        conn: &mut SqliteConnection,
+        id: &str,
+        message_history: &str,
+    ) -> RepositoryResult<()> {
+        sqlx::query("DELETE FROM message_fts WHERE conversation_id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+        for (history_index, text) in indexable_messages(message_history) {
+            sqlx::query(
+                "INSERT INTO message_fts (content, conversation_id, history_index)
+                 VALUES (?, ?, ?)",
+            )
+            .bind(text)
+            .bind(id)
+            .bind(history_index as i64)
+            .execute(&mut *conn)
+            .await?;
+        }
         Ok(())
     }
 
@@ -136,7 +270,8 @@ impl ConversationRepository for ConversationSqliteRepository {
         let pool = self.pool.clone();
         Box::pin(async move {
             let rows = sqlx::query(
-                "SELECT id, title, total_cost, updated_at
+                "SELECT id, title, total_cost, created_at, updated_at, last_viewed_at, label,
+                        tags, folder, locked
                  FROM conversations
                  ORDER BY updated_at DESC",
             )
@@ -150,6 +285,12 @@ impl ConversationRepository for ConversationSqliteRepository {
                     title: row.get("title"),
                     total_cost: row.get("total_cost"),
                     updated_at: row.get("updated_at"),
+                    created_at: row.get("created_at"),
+                    last_viewed_at: row.get("last_viewed_at"),
+                    label: ConversationLabel::from_json(row.get("label")),
+                    tags: conversation_tags::tags_from_json(row.get("tags")),
+                    folder: row.get("folder"),
+                    locked: row.get("locked"),
                 })
                 .collect();
 
@@ -164,7 +305,11 @@ impl ConversationRepository for ConversationSqliteRepository {
             let row = sqlx::query(
                 "SELECT id, title, model_id, message_history, system_traces, token_usage,
                         attachment_paths, message_timestamps, message_feedback,
-                        regeneration_records, created_at, updated_at, working_dir, agent_task_snapshot
+                        regeneration_records, created_at, updated_at, working_dir, agent_task_snapshot,
+                        pinned_items, variables, execution_plan, confidential,
+                        message_reactions, message_notes, history_policy, disabled_mcp_servers,
+                        disabled_tools, label, history_edits, quality_score, message_errors,
+                        tags, folder, compaction_records, locked
                  FROM conversations
                  WHERE id = ?",
             )
@@ -187,6 +332,23 @@ impl ConversationRepository for ConversationSqliteRepository {
                 updated_at: r.get("updated_at"),
                 working_dir: r.get("working_dir"),
                 agent_task_snapshot: r.get("agent_task_snapshot"),
+                pinned_items: r.get("pinned_items"),
+                variables: r.get("variables"),
+                execution_plan: r.get("execution_plan"),
+                confidential: r.get("confidential"),
+                message_reactions: r.get("message_reactions"),
+                message_notes: r.get("message_notes"),
+                message_errors: r.get("message_errors"),
+                history_policy: r.get("history_policy"),
+                disabled_mcp_servers: r.get("disabled_mcp_servers"),
+                disabled_tools: r.get("disabled_tools"),
+                label: r.get("label"),
+                tags: r.get("tags"),
+                folder: r.get("folder"),
+                history_edits: r.get("history_edits"),
+                compaction_records: r.get("compaction_records"),
+                locked: r.get("locked"),
+                quality_score: r.get("quality_score"),
             }))
         })
     }
@@ -197,7 +359,11 @@ impl ConversationRepository for ConversationSqliteRepository {
             let rows = sqlx::query(
                 "SELECT id, title, model_id, message_history, system_traces, token_usage,
                         attachment_paths, message_timestamps, message_feedback,
-                        regeneration_records, created_at, updated_at, working_dir, agent_task_snapshot
+                        regeneration_records, created_at, updated_at, working_dir, agent_task_snapshot,
+                        pinned_items, variables, execution_plan, confidential,
+                        message_reactions, message_notes, history_policy, disabled_mcp_servers,
+                        disabled_tools, label, history_edits, quality_score, message_errors,
+                        tags, folder, compaction_records, locked
                  FROM conversations
                  ORDER BY updated_at DESC",
             )
@@ -221,6 +387,23 @@ impl ConversationRepository for ConversationSqliteRepository {
                     updated_at: r.get("updated_at"),
                     working_dir: r.get("working_dir"),
                     agent_task_snapshot: r.get("agent_task_snapshot"),
+                    pinned_items: r.get("pinned_items"),
+                    variables: r.get("variables"),
+                    execution_plan: r.get("execution_plan"),
+                    confidential: r.get("confidential"),
+                    message_reactions: r.get("message_reactions"),
+                    message_notes: r.get("message_notes"),
+                    message_errors: r.get("message_errors"),
+                    history_policy: r.get("history_policy"),
+                    disabled_mcp_servers: r.get("disabled_mcp_servers"),
+                    disabled_tools: r.get("disabled_tools"),
+                    label: r.get("label"),
+                    tags: r.get("tags"),
+                    folder: r.get("folder"),
+                    history_edits: r.get("history_edits"),
+                    compaction_records: r.get("compaction_records"),
+                    locked: r.get("locked"),
+                    quality_score: r.get("quality_score"),
                 })
                 .collect())
         })
@@ -229,13 +412,22 @@ impl ConversationRepository for ConversationSqliteRepository {
     fn save(&self, _id: &str, data: ConversationData) -> BoxFuture<'static, RepositoryResult<()>> {
         let pool = self.pool.clone();
         let total_cost = data.total_cost();
+        let write = crate::services::pending_writes::begin();
         Box::pin(async move {
+            let _write = write;
+            // The row and its search index entries are written together
+            let mut tx = pool.begin().await?;
             sqlx::query(
                 "INSERT INTO conversations
                     (id, title, model_id, message_history, system_traces, token_usage,
                      attachment_paths, message_timestamps, message_feedback,
-                     regeneration_records, total_cost, created_at, updated_at, working_dir, agent_task_snapshot)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+                     regeneration_records, total_cost, created_at, updated_at, working_dir, agent_task_snapshot,
+                     pinned_items, variables, execution_plan, confidential,
+                     message_reactions, message_notes, history_policy, disabled_mcp_servers,
+                     disabled_tools, label, history_edits, quality_score, message_errors,
+                     tags, folder, compaction_records, locked)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19,
+                         ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32)
                  ON CONFLICT(id) DO UPDATE SET
                     title                = excluded.title,
                     model_id             = excluded.model_id,
@@ -249,7 +441,24 @@ impl ConversationRepository for ConversationSqliteRepository {
                     total_cost           = excluded.total_cost,
                     updated_at           = excluded.updated_at,
                     working_dir          = excluded.working_dir,
-                    agent_task_snapshot  = excluded.agent_task_snapshot",
+                    agent_task_snapshot  = excluded.agent_task_snapshot,
+                    pinned_items         = excluded.pinned_items,
+                    variables            = excluded.variables,
+                    execution_plan       = excluded.execution_plan,
+                    confidential         = excluded.confidential,
+                    message_reactions    = excluded.message_reactions,
+                    message_notes        = excluded.message_notes,
+                    history_policy       = excluded.history_policy,
+                    disabled_mcp_servers = excluded.disabled_mcp_servers,
+                    disabled_tools       = excluded.disabled_tools,
+                    label                = excluded.label,
+                    history_edits        = excluded.history_edits,
+                    quality_score        = excluded.quality_score,
+                    message_errors       = excluded.message_errors,
+                    tags                 = excluded.tags,
+                    folder               = excluded.folder,
+                    compaction_records   = excluded.compaction_records,
+                    locked               = excluded.locked",
             )
             .bind(&data.id)
             .bind(&data.title)
@@ -266,9 +475,29 @@ impl ConversationRepository for ConversationSqliteRepository {
             .bind(data.updated_at)
             .bind(&data.working_dir)
             .bind(&data.agent_task_snapshot)
-            .execute(&pool)
+            .bind(&data.pinned_items)
+            .bind(&data.variables)
+            .bind(&data.execution_plan)
+            .bind(data.confidential)
+            .bind(&data.message_reactions)
+            .bind(&data.message_notes)
+            .bind(&data.history_policy)
+            .bind(&data.disabled_mcp_servers)
+            .bind(&data.disabled_tools)
+            .bind(&data.label)
+            .bind(&data.history_edits)
+            .bind(&data.quality_score)
+            .bind(&data.message_errors)
+            .bind(&data.tags)
+            .bind(&data.folder)
+            .bind(&data.compaction_records)
+            .bind(data.locked)
+            .execute(&mut *tx)
             .await?;
 
+            Self::index_messages(&mut tx, &data.id, &data.message_history).await?;
+            tx.commit().await?;
+
             Ok(())
         })
     }
@@ -276,12 +505,142 @@ impl ConversationRepository for ConversationSqliteRepository {
     fn delete(&self, id: &str) -> BoxFuture<'static, RepositoryResult<()>> {
         let pool = self.pool.clone();
         let id = id.to_string();
+        let write = crate::services::pending_writes::begin();
         Box::pin(async move {
+            let _write = write;
+            let mut tx = pool.begin().await?;
             sqlx::query("DELETE FROM conversations WHERE id = ?")
+                .bind(&id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM message_fts WHERE conversation_id = ?")
+                .bind(&id)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            Ok(())
+        })
+    }
+
+    fn save_label(
+        &self,
+        id: &str,
+        label: ConversationLabel,
+    ) -> BoxFuture<'static, RepositoryResult<()>> {
+        let pool = self.pool.clone();
+        let id = id.to_string();
+        let write = crate::services::pending_writes::begin();
+        Box::pin(async move {
+            let _write = write;
+            sqlx::query("UPDATE conversations SET label = ? WHERE id = ?")
+                .bind(label.to_json())
+                .bind(&id)
+                .execute(&pool)
+                .await?;
+            Ok(())
+        })
+    }
+
+    fn save_locked(&self, id: &str, locked: bool) -> BoxFuture<'static, RepositoryResult<()>> {
+        let pool = self.pool.clone();
+        let id = id.to_string();
+        let write = crate::services::pending_writes::begin();
+        Box::pin(async move {
+            let _write = write;
+            sqlx::query("UPDATE conversations SET locked = ? WHERE id = ?")
+                .bind(locked)
                 .bind(&id)
                 .execute(&pool)
                 .await?;
             Ok(())
         })
     }
+
+    fn save_tags(&self, id: &str, tags: Vec<String>) -> BoxFuture<'static, RepositoryResult<()>> {
+        let pool = self.pool.clone();
+        let id = id.to_string();
+        let write = crate::services::pending_writes::begin();
+        Box::pin(async move {
+            let _write = write;
+            sqlx::query("UPDATE conversations SET tags = ? WHERE id = ?")
+                .bind(conversation_tags::tags_to_json(&tags))
+                .bind(&id)
+                .execute(&pool)
+                .await?;
+            Ok(())
+        })
+    }
+
+    fn save_folder(
+        &self,
+        id: &str,
+        folder: Option<String>,
+    ) -> BoxFuture<'static, RepositoryResult<()>> {
+        let pool = self.pool.clone();
+        let id = id.to_string();
+        let write = crate::services::pending_writes::begin();
+        Box::pin(async move {
+            let _write = write;
+            sqlx::query("UPDATE conversations SET folder = ? WHERE id = ?")
+                .bind(&folder)
+                .bind(&id)
+                .execute(&pool)
+                .await?;
+            Ok(())
+        })
+    }
+
+    fn save_last_viewed(
+        &self,
+        id: &str,
+        viewed_at: i64,
+    ) -> BoxFuture<'static, RepositoryResult<()>> {
+        let pool = self.pool.clone();
+        let id = id.to_string();
+        let write = crate::services::pending_writes::begin();
+        Box::pin(async move {
+            let _write = write;
+            sqlx::query("UPDATE conversations SET last_viewed_at = ? WHERE id = ?")
+                .bind(viewed_at)
+                .bind(&id)
+                .execute(&pool)
+                .await?;
+            Ok(())
+        })
+    }
+
+    fn search_messages(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> BoxFuture<'static, RepositoryResult<Vec<MessageSearchHit>>> {
+        let pool = self.pool.clone();
+        let query = fts_query(query);
+        Box::pin(async move {
+            let Some(query) = query else {
+                return Ok(Vec::new());
+            };
+            let rows = sqlx::query(
+                "SELECT conversation_id, history_index,
+                        snippet(message_fts, 0, '', '', '…', 12) AS snippet
+                 FROM message_fts
+                 WHERE message_fts MATCH ?
+                 ORDER BY rank
+                 LIMIT ?",
+            )
+            .bind(&query)
+            .bind(limit as i64)
+            .fetch_all(&pool)
+            .await?;
+
+            Ok(rows
+                .iter()
+                .map(|row| MessageSearchHit {
+                    conversation_id: row.get("conversation_id"),
+                    history_index: row.get::<i64, _>("history_index") as usize,
+                    snippet: row.get("snippet"),
+                })
+                .collect())
+        })
+    }
 }
@@ -1,22 +1,65 @@
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
-use super::conversation_repository::{BoxFuture, ConversationData, ConversationRepository};
-use super::error::{RepositoryError, RepositoryResult};
+use parking_lot::Mutex;
+
+use super::conversation_repository::{
+    BoxFuture, ConversationData, ConversationMetadata, ConversationRepository,
+};
+use super::conversation_search::{MessageSearchHit, indexable_messages, match_snippet};
+use super::error::RepositoryResult;
+use crate::models::ConversationLabel;
+use crate::models::conversation_tags;
 
 /// In-memory repository for conversations
-/// Useful for testing and development
+/// Useful for testing and development; see `crate::testing` for the
+/// builders and fakes that go with it.
 #[derive(Clone)]
 pub struct InMemoryConversationRepository {
     conversations: Arc<Mutex<HashMap<String, ConversationData>>>,
+    saves: Arc<Mutex<Vec<String>>>,
+    /// Last-viewed times, kept apart from the data like the SQLite column
+    viewed: Arc<Mutex<HashMap<String, i64>>>,
 }
 
 impl InMemoryConversationRepository {
     pub fn new() -> Self {
         Self {
             conversations: Arc::new(Mutex::new(HashMap::new())),
+            saves: Arc::new(Mutex::new(Vec::new())),
+            viewed: Arc::new(Mutex::new(HashMap::new())),
         }
     }
+
+    /// Repository that already holds `conversations`.
+    pub fn with_conversations(conversations: impl IntoIterator<Item = ConversationData>) -> Self {
+        let repo = Self::new();
+        repo.conversations.lock().extend(
+            conversations
+                .into_iter()
+                .map(|data| (data.id.clone(), data)),
+        );
+        repo
+    }
+
+    /// Stored data of conversation `id`, without going through a future.
+    pub fn get(&self, id: &str) -> Option<ConversationData> {
+        self.conversations.lock().get(id).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.conversations.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conversations.lock().is_empty()
+    }
+
+    /// Ids passed to `save`, in call order, so tests can check that a code
+    /// path persisted a conversation (and how often).
+    pub fn saved_ids(&self) -> Vec<String> {
+        self.saves.lock().clone()
+    }
 }
 
 impl Default for InMemoryConversationRepository {
@@ -26,17 +69,49 @@ impl Default for InMemoryConversationRepository {
 }
 
 impl ConversationRepository for InMemoryConversationRepository {
-    fn load_all(&self) -> BoxFuture<'static, RepositoryResult<Vec<ConversationData>>> {
+    fn load_metadata(&self) -> BoxFuture<'static, RepositoryResult<Vec<ConversationMetadata>>> {
         let conversations = self.conversations.clone();
+        let viewed = self.viewed.clone();
 
         Box::pin(async move {
-            let store = conversations
+            let viewed = viewed.lock();
+            let mut metadata: Vec<ConversationMetadata> = conversations
                 .lock()
-                .map_err(|e| RepositoryError::InvalidData {
-                    message: format!("Failed to lock conversations: {}", e),
-                })?;
+                .values()
+                .map(|data| ConversationMetadata {
+                    id: data.id.clone(),
+                    title: data.title.clone(),
+                    total_cost: data.total_cost(),
+                    updated_at: data.updated_at,
+                    created_at: data.created_at,
+                    last_viewed_at: viewed.get(&data.id).copied(),
+                    label: ConversationLabel::from_json(&data.label),
+                    tags: conversation_tags::tags_from_json(&data.tags),
+                    folder: data.folder.clone(),
+                    locked: data.locked,
+                })
+                .collect();
+
+            // Sort by updated_at descending, like the SQLite repository
+            metadata.sort_by_key(|c| std::cmp::Reverse(c.updated_at));
+
+            Ok(metadata)
+        })
+    }
+
+    fn load_one(&self, id: &str) -> BoxFuture<'static, RepositoryResult<Option<ConversationData>>> {
+        let conversations = self.conversations.clone();
+        let id = id.to_string();
+
+        Box::pin(async move { Ok(conversations.lock().get(&id).cloned()) })
+    }
 
-            let mut result: Vec<ConversationData> = store.values().cloned().collect();
+    fn load_all(&self) -> BoxFuture<'static, RepositoryResult<Vec<ConversationData>>> {
+        let conversations = self.conversations.clone();
+
+        Box::pin(async move {
+            let mut result: Vec<ConversationData> =
+                conversations.lock().values().cloned().collect();
 
             // Sort by updated_at descending
             result.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
@@ -47,16 +122,12 @@ impl ConversationRepository for InMemoryConversationRepository {
 
     fn save(&self, id: &str, data: ConversationData) -> BoxFuture<'static, RepositoryResult<()>> {
         let conversations = self.conversations.clone();
+        let saves = self.saves.clone();
         let id = id.to_string();
 
         Box::pin(async move {
-            let mut store = conversations
-                .lock()
-                .map_err(|e| RepositoryError::InvalidData {
-                    message: format!("Failed to lock conversations: {}", e),
-                })?;
-
-            store.insert(id, data);
+            saves.lock().push(id.clone());
+            conversations.lock().insert(id, data);
 
             Ok(())
         })
@@ -67,115 +138,178 @@ impl ConversationRepository for InMemoryConversationRepository {
         let id = id.to_string();
 
         Box::pin(async move {
-            let mut store = conversations
-                .lock()
-                .map_err(|e| RepositoryError::InvalidData {
-                    message: format!("Failed to lock conversations: {}", e),
-                })?;
+            conversations.lock().remove(&id);
 
-            store.remove(&id);
+            Ok(())
+        })
+    }
+
+    fn save_label(
+        &self,
+        id: &str,
+        label: ConversationLabel,
+    ) -> BoxFuture<'static, RepositoryResult<()>> {
+        let conversations = self.conversations.clone();
+        let id = id.to_string();
+
+        Box::pin(async move {
+            if let Some(data) = conversations.lock().get_mut(&id) {
+                data.label = label.to_json();
+            }
 
             Ok(())
         })
     }
+
+    fn save_locked(&self, id: &str, locked: bool) -> BoxFuture<'static, RepositoryResult<()>> {
+        let conversations = self.conversations.clone();
+        let id = id.to_string();
+
+        Box::pin(async move {
+            if let Some(data) = conversations.lock().get_mut(&id) {
+                data.locked = locked;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn save_tags(&self, id: &str, tags: Vec<String>) -> BoxFuture<'static, RepositoryResult<()>> {
+        let conversations = self.conversations.clone();
+        let id = id.to_string();
+
+        Box::pin(async move {
+            if let Some(data) = conversations.lock().get_mut(&id) {
+                data.tags = conversation_tags::tags_to_json(&tags);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn save_folder(
+        &self,
+        id: &str,
+        folder: Option<String>,
+    ) -> BoxFuture<'static, RepositoryResult<()>> {
+        let conversations = self.conversations.clone();
+        let id = id.to_string();
+
+        Box::pin(async move {
+            if let Some(data) = conversations.lock().get_mut(&id) {
+                data.folder = folder;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn save_last_viewed(
+        &self,
+        id: &str,
+        viewed_at: i64,
+    ) -> BoxFuture<'static, RepositoryResult<()>> {
+        let conversations = self.conversations.clone();
+        let viewed = self.viewed.clone();
+        let id = id.to_string();
+
+        Box::pin(async move {
+            if conversations.lock().contains_key(&id) {
+                viewed.lock().insert(id, viewed_at);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn search_messages(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> BoxFuture<'static, RepositoryResult<Vec<MessageSearchHit>>> {
+        let conversations = self.conversations.clone();
+        let query = query.to_string();
+
+        Box::pin(async move {
+            let conversations = conversations.lock();
+            let mut recent: Vec<&ConversationData> = conversations.values().collect();
+            recent.sort_by_key(|c| std::cmp::Reverse(c.updated_at));
+
+            // Plain substring matching stands in for the SQLite FTS5 index
+            let hits = recent
+                .into_iter()
+                .flat_map(|data| {
+                    indexable_messages(&data.message_history)
+                        .into_iter()
+                        .filter_map(|(history_index, text)| {
+                            Some(MessageSearchHit {
+                                conversation_id: data.id.clone(),
+                                history_index,
+                                snippet: match_snippet(&text, &query)?,
+                            })
+                        })
+                })
+                .take(limit)
+                .collect();
+
+            Ok(hits)
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::LabelColor;
+    use crate::testing::ConversationDataBuilder;
 
     #[tokio::test]
     async fn test_save_and_load() {
         let repo = InMemoryConversationRepository::new();
 
-        let data = ConversationData {
-            id: "test-1".to_string(),
-            title: "Test Conversation".to_string(),
-            model_id: "model-1".to_string(),
-            message_history: "[]".to_string(),
-            system_traces: "[]".to_string(),
-            token_usage: "{}".to_string(),
-            attachment_paths: "[]".to_string(),
-            message_timestamps: "[]".to_string(),
-            message_feedback: "[]".to_string(),
-            regeneration_records: "[]".to_string(),
-            created_at: 1000,
-            updated_at: 1000,
-            working_dir: None,
-            agent_task_snapshot: None,
-        };
+        let data = ConversationDataBuilder::new("test-1")
+            .title("Test Conversation")
+            .updated_at(1000)
+            .build();
+
+        repo.save("test-1", data).await.unwrap();
 
         let loaded = repo.load_all().await.unwrap();
         assert_eq!(loaded.len(), 1);
         assert_eq!(loaded[0].id, "test-1");
         assert_eq!(loaded[0].title, "Test Conversation");
+        assert_eq!(repo.saved_ids(), vec!["test-1"]);
     }
 
     #[tokio::test]
     async fn test_delete() {
         let repo = InMemoryConversationRepository::new();
 
-        let data = ConversationData {
-            id: "test-1".to_string(),
-            title: "Test Conversation".to_string(),
-            model_id: "model-1".to_string(),
-            message_history: "[]".to_string(),
-            system_traces: "[]".to_string(),
-            token_usage: "{}".to_string(),
-            attachment_paths: "[]".to_string(),
-            message_timestamps: "[]".to_string(),
-            message_feedback: "[]".to_string(),
-            regeneration_records: "[]".to_string(),
-            created_at: 1000,
-            updated_at: 1000,
-            working_dir: None,
-            agent_task_snapshot: None,
-        };
+        let data = ConversationDataBuilder::new("test-1")
+            .title("Test Conversation")
+            .updated_at(1000)
+            .build();
 
         repo.save("test-1", data).await.unwrap();
         repo.delete("test-1").await.unwrap();
 
         let loaded = repo.load_all().await.unwrap();
         assert_eq!(loaded.len(), 0);
+        assert!(repo.load_one("test-1").await.unwrap().is_none());
     }
 
     #[tokio::test]
     async fn test_sorting_by_updated_at() {
         let repo = InMemoryConversationRepository::new();
 
-        let data1 = ConversationData {
-            id: "test-1".to_string(),
-            title: "Older".to_string(),
-            model_id: "model-1".to_string(),
-            message_history: "[]".to_string(),
-            system_traces: "[]".to_string(),
-            token_usage: "{}".to_string(),
-            attachment_paths: "[]".to_string(),
-            message_timestamps: "[]".to_string(),
-            message_feedback: "[]".to_string(),
-            regeneration_records: "[]".to_string(),
-            created_at: 1000,
-            updated_at: 1000,
-            working_dir: None,
-            agent_task_snapshot: None,
-        };
-
-        let data2 = ConversationData {
-            id: "test-2".to_string(),
-            title: "Newer".to_string(),
-            model_id: "model-1".to_string(),
-            message_history: "[]".to_string(),
-            system_traces: "[]".to_string(),
-            token_usage: "{}".to_string(),
-            attachment_paths: "[]".to_string(),
-            message_timestamps: "[]".to_string(),
-            message_feedback: "[]".to_string(),
-            regeneration_records: "[]".to_string(),
-            created_at: 2000,
-            updated_at: 2000,
-            working_dir: None,
-            agent_task_snapshot: None,
-        };
+        let data1 = ConversationDataBuilder::new("test-1")
+            .title("Older")
+            .updated_at(1000)
+            .build();
+        let data2 = ConversationDataBuilder::new("test-2")
+            .title("Newer")
+            .updated_at(2000)
+            .build();
 
         repo.save("test-1", data1).await.unwrap();
         repo.save("test-2", data2).await.unwrap();
@@ -184,6 +318,78 @@ mod tests {
         assert_eq!(loaded.len(), 2);
         assert_eq!(loaded[0].title, "Newer");
         assert_eq!(loaded[1].title, "Older");
+
+        let metadata = repo.load_metadata().await.unwrap();
+        assert_eq!(metadata[0].id, "test-2");
+        assert_eq!(metadata[1].id, "test-1");
+    }
+
+    #[tokio::test]
+    async fn test_save_label_updates_metadata() {
+        let data = ConversationDataBuilder::new("test-1").build();
+        let repo = InMemoryConversationRepository::with_conversations([data]);
+        let label = ConversationLabel {
+            color: Some(LabelColor::Green),
+            icon: None,
+        };
+
+        repo.save_label("test-1", label.clone()).await.unwrap();
+
+        let metadata = repo.load_metadata().await.unwrap();
+        assert_eq!(metadata[0].label, label);
+        assert!(repo.saved_ids().is_empty(), "labels skip the full save");
+    }
+
+    #[tokio::test]
+    async fn test_save_tags_and_folder_update_metadata() {
+        let data = ConversationDataBuilder::new("test-1").build();
+        let repo = InMemoryConversationRepository::with_conversations([data]);
+
+        repo.save_tags("test-1", vec!["rust".to_string()])
+            .await
+            .unwrap();
+        repo.save_folder("test-1", Some("clients/acme".to_string()))
+            .await
+            .unwrap();
+
+        let metadata = repo.load_metadata().await.unwrap();
+        assert_eq!(metadata[0].tags, ["rust"]);
+        assert_eq!(metadata[0].folder.as_deref(), Some("clients/acme"));
+        assert!(repo.saved_ids().is_empty(), "tags skip the full save");
+    }
+
+    #[tokio::test]
+    async fn test_save_last_viewed_keeps_updated_at() {
+        let data = ConversationDataBuilder::new("test-1")
+            .updated_at(1000)
+            .build();
+        let repo = InMemoryConversationRepository::with_conversations([data]);
+
+        repo.save_last_viewed("test-1", 5000).await.unwrap();
+
+        let metadata = repo.load_metadata().await.unwrap();
+        assert_eq!(metadata[0].last_viewed_at, Some(5000));
+        assert_eq!(metadata[0].updated_at, 1000);
+        assert!(repo.saved_ids().is_empty(), "views skip the full save");
+    }
+
+    #[tokio::test]
+    async fn test_search_messages_finds_message_text() {
+        let repo = InMemoryConversationRepository::with_conversations([
+            ConversationDataBuilder::new("test-1")
+                .user("How do I rotate the deploy key?")
+                .assistant("Generate a new key first.")
+                .build(),
+            ConversationDataBuilder::new("test-2")
+                .user("Unrelated question")
+                .build(),
+        ]);
+
+        let hits = repo.search_messages("new KEY", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].conversation_id, "test-1");
+        assert_eq!(hits[0].history_index, 1);
+        assert!(repo.search_messages("  ", 10).await.unwrap().is_empty());
     }
 
     #[test]
@@ -1,6 +1,10 @@
 pub mod conversation_repository;
+pub mod conversation_search;
 pub mod conversation_sqlite_repository;
 pub mod error;
+pub mod in_memory_repository;
 
 pub use conversation_repository::{ConversationData, ConversationMetadata, ConversationRepository};
+pub use conversation_search::MessageSearchHit;
 pub use conversation_sqlite_repository::ConversationSqliteRepository;
+pub use in_memory_repository::InMemoryConversationRepository;
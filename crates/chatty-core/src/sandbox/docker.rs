@@ -52,7 +52,7 @@ async fn try_socket(path: &str) -> Result<Docker, String> {
 /// 1. If `docker_host` is Some, use that explicitly
 /// 2. Try Bollard's `connect_with_local_defaults()` (checks DOCKER_HOST env + platform default)
 /// 3. Try common fallback socket paths (rootless Docker, Docker Desktop, etc.)
-async fn connect_docker(docker_host: Option<&str>) -> Result<Docker> {
+pub(crate) async fn connect_docker(docker_host: Option<&str>) -> Result<Docker> {
     // Strategy 1: User-configured docker host
     if let Some(host) = docker_host {
         let path = host.strip_prefix("unix://").unwrap_or(host);
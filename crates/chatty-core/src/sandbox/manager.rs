@@ -183,6 +183,16 @@ impl SandboxManager {
         guard[language].execute(code, language).await
     }
 
+    /// Destroy the container for one language, dropping its state. Used when
+    /// a run is cancelled mid-way.
+    pub async fn discard(&self, language: &Language) -> Result<()> {
+        let sandbox = self.sandboxes.lock().await.remove(language);
+        match sandbox {
+            Some(sandbox) => sandbox.destroy().await,
+            None => Ok(()),
+        }
+    }
+
     /// Destroy all sandbox containers. Call when the conversation ends.
     #[allow(dead_code)]
     pub async fn destroy(&self) -> Result<()> {
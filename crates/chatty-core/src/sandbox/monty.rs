@@ -244,6 +244,8 @@ impl SandboxBackend for MontySandbox {
             .env_clear()
             .env("PATH", "/usr/bin:/bin:/usr/local/bin")
             .env("LANG", "en_US.UTF-8")
+            // Cancelling the tool drops this future; take the child with it.
+            .kill_on_drop(true)
             .spawn()
             .context("Failed to spawn python3. Is Python 3 installed?")?;
 
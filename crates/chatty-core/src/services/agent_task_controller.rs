@@ -281,7 +281,14 @@ impl AgentTaskController {
     pub fn observe_tool_result(&self, tool_name: &str) -> Option<String> {
         if matches!(
             tool_name,
-            "write_todos" | "update_todo" | "verify_completion"
+            "write_todos"
+                | "update_todo"
+                | "verify_completion"
+                | "get_variable"
+                | "set_variable"
+                | "create_plan"
+                | "update_plan"
+                | "ask_user"
         ) {
             return None;
         }
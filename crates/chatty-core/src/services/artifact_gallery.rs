@@ -0,0 +1,383 @@
+//! Files a conversation's tools produced, for the artifact gallery.
+//!
+//! Artifacts are collected from two places in the message history: the
+//! attachments queued on assistant messages by `add_attachment`, and the
+//! outputs of successful tool calls that write files (`pdf_to_image` pages,
+//! Daytona downloads, DOCX/XLSX/PPTX/Typst documents, `write_file`). Each
+//! artifact carries the turn it was produced in so the gallery can filter by
+//! turn as well as by kind, and the filtered set can be copied to a folder
+//! in one go.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use rig_core::completion::Message;
+use rig_core::completion::message::UserContent;
+
+use crate::models::MessageEntry;
+use crate::models::message_types::{SystemTrace, ToolCallState, TraceItem};
+
+/// Tools whose successful output names files they produced, and the output
+/// field holding the path (a string) or paths (an array of strings).
+const ARTIFACT_TOOLS: &[(&str, &str)] = &[
+    ("add_attachment", "path"),
+    ("pdf_to_image", "images"),
+    ("daytona_run", "downloaded_files"),
+    ("compile_typst", "saved_path"),
+    ("write_docx", "path"),
+    ("write_excel", "path"),
+    ("edit_excel", "path"),
+    ("write_pptx", "path"),
+    ("write_file", "path"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ArtifactKind {
+    Image,
+    /// PDFs, office documents and Markdown/HTML reports
+    Document,
+    /// Spreadsheets and structured data files
+    Data,
+    Other,
+}
+
+impl ArtifactKind {
+    pub const ALL: [ArtifactKind; 4] = [Self::Image, Self::Document, Self::Data, Self::Other];
+
+    pub fn from_path(path: &Path) -> Self {
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match ext.as_str() {
+            "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" | "bmp" => Self::Image,
+            "pdf" | "docx" | "pptx" | "odt" | "md" | "html" | "htm" | "txt" | "typ" => {
+                Self::Document
+            }
+            "csv" | "tsv" | "xlsx" | "xls" | "ods" | "json" | "jsonl" | "parquet" => Self::Data,
+            _ => Self::Other,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Image => "Images",
+            Self::Document => "Documents",
+            Self::Data => "Data",
+            Self::Other => "Other",
+        }
+    }
+}
+
+/// One file produced during the conversation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Artifact {
+    pub path: PathBuf,
+    pub kind: ArtifactKind,
+    /// 1-based number of the user prompt whose answer produced the file
+    pub turn: usize,
+    /// Tool that produced the file
+    pub tool: String,
+}
+
+impl Artifact {
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+}
+
+/// Narrow the gallery to one kind and/or one turn (`None` = all).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArtifactFilter {
+    pub kind: Option<ArtifactKind>,
+    pub turn: Option<usize>,
+}
+
+impl ArtifactFilter {
+    pub fn matches(&self, artifact: &Artifact) -> bool {
+        self.kind.is_none_or(|kind| artifact.kind == kind)
+            && self.turn.is_none_or(|turn| artifact.turn == turn)
+    }
+
+    pub fn apply<'a>(&self, artifacts: &'a [Artifact]) -> Vec<&'a Artifact> {
+        artifacts.iter().filter(|a| self.matches(a)).collect()
+    }
+}
+
+/// Outcome of [`export_artifacts`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExportSummary {
+    pub copied: Vec<PathBuf>,
+    /// Artifacts whose file no longer exists
+    pub missing: Vec<PathBuf>,
+}
+
+/// Every artifact in `entries`, in the order produced. A file written in
+/// several turns (e.g. a spreadsheet edited twice) is listed once, at its
+/// latest turn. Relative paths are resolved against `base_dir`.
+pub fn collect_artifacts(entries: &[MessageEntry], base_dir: Option<&Path>) -> Vec<Artifact> {
+    let mut artifacts: Vec<Artifact> = Vec::new();
+    let mut turn = 0;
+
+    let push = |path: &str, tool: &str, turn: usize, artifacts: &mut Vec<Artifact>| {
+        let path = PathBuf::from(path);
+        let path = match base_dir {
+            Some(base) if path.is_relative() => base.join(path),
+            _ => path,
+        };
+        artifacts.retain(|a| a.path != path);
+        artifacts.push(Artifact {
+            kind: ArtifactKind::from_path(&path),
+            path,
+            turn,
+            tool: tool.to_string(),
+        });
+    };
+
+    for entry in entries {
+        match &entry.message {
+            Message::User { content } => {
+                if content.iter().any(|c| matches!(c, UserContent::Text(_))) {
+                    turn += 1;
+                }
+            }
+            Message::Assistant { .. } => {
+                let turn = turn.max(1);
+                if let Some(trace) = entry
+                    .system_trace
+                    .as_ref()
+                    .and_then(|value| serde_json::from_value::<SystemTrace>(value.clone()).ok())
+                {
+                    for item in &trace.items {
+                        let TraceItem::ToolCall(call) = item else {
+                            continue;
+                        };
+                        if call.state != ToolCallState::Success {
+                            continue;
+                        }
+                        for path in tool_output_paths(&call.tool_name, call.output.as_deref()) {
+                            push(&path, &call.tool_name, turn, &mut artifacts);
+                        }
+                    }
+                }
+                for path in &entry.attachment_paths {
+                    push(
+                        &path.to_string_lossy(),
+                        "add_attachment",
+                        turn,
+                        &mut artifacts,
+                    );
+                }
+            }
+            Message::System { .. } => {}
+        }
+    }
+    artifacts
+}
+
+/// Paths named in a successful artifact tool's JSON output.
+fn tool_output_paths(tool_name: &str, output: Option<&str>) -> Vec<String> {
+    let Some((_, field)) = ARTIFACT_TOOLS.iter().find(|(name, _)| *name == tool_name) else {
+        return Vec::new();
+    };
+    let Some(json) = output.and_then(|o| serde_json::from_str::<serde_json::Value>(o).ok()) else {
+        return Vec::new();
+    };
+    match json.get(field) {
+        Some(serde_json::Value::String(path)) => vec![path.clone()],
+        Some(serde_json::Value::Array(paths)) => paths
+            .iter()
+            .filter_map(|p| p.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Copy `artifacts` into `dest_dir`, renaming on clashes (`chart.png`,
+/// `chart-2.png`, ...). Files that no longer exist are skipped and reported.
+pub fn export_artifacts(artifacts: &[Artifact], dest_dir: &Path) -> Result<ExportSummary> {
+    std::fs::create_dir_all(dest_dir)
+        .with_context(|| format!("Failed to create {}", dest_dir.display()))?;
+
+    let mut summary = ExportSummary::default();
+    let mut taken = HashSet::new();
+    for artifact in artifacts {
+        if !artifact.path.is_file() {
+            summary.missing.push(artifact.path.clone());
+            continue;
+        }
+        let dest = unique_destination(dest_dir, &artifact.path, &mut taken);
+        std::fs::copy(&artifact.path, &dest).with_context(|| {
+            format!(
+                "Failed to copy {} to {}",
+                artifact.path.display(),
+                dest.display()
+            )
+        })?;
+        summary.copied.push(dest);
+    }
+    Ok(summary)
+}
+
+/// First free `<stem>[-n].<ext>` in `dir`, considering both files already
+/// there and names used earlier in the same export.
+fn unique_destination(dir: &Path, source: &Path, taken: &mut HashSet<PathBuf>) -> PathBuf {
+    let stem = source
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "artifact".to_string());
+    let ext = source
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+
+    let mut n = 1;
+    loop {
+        let name = if n == 1 {
+            format!("{stem}{ext}")
+        } else {
+            format!("{stem}-{n}{ext}")
+        };
+        let candidate = dir.join(name);
+        if !candidate.exists() && taken.insert(candidate.clone()) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::message_types::ToolCallBlock;
+    use crate::testing::{ConversationDataBuilder, FakeAgentClient, tool_call, trace};
+
+    /// A successful call of `name` that returned `output`.
+    fn produced(name: &str, output: serde_json::Value) -> ToolCallBlock {
+        ToolCallBlock {
+            output: Some(output.to_string()),
+            ..tool_call(name, serde_json::json!({}), ToolCallState::Success)
+        }
+    }
+
+    fn history() -> ConversationDataBuilder {
+        ConversationDataBuilder::new("conv-1")
+            .user("render the report")
+            .assistant("done")
+            .trace(trace([
+                produced(
+                    "pdf_to_image",
+                    serde_json::json!({"images": ["/out/page0.png", "/out/page1.png"]}),
+                ),
+                produced("read_file", serde_json::json!({"path": "/src/lib.rs"})),
+            ]))
+            .attachments(&["/out/chart.png"])
+            .user("now the spreadsheet")
+            .assistant("done")
+            .trace(trace([produced(
+                "write_excel",
+                serde_json::json!({"path": "sales.xlsx"}),
+            )]))
+    }
+
+    fn entries(history: ConversationDataBuilder) -> Vec<MessageEntry> {
+        history
+            .build_conversation(&FakeAgentClient::new())
+            .unwrap()
+            .entries()
+            .to_vec()
+    }
+
+    #[test]
+    fn collects_tool_outputs_and_attachments_by_turn() {
+        let artifacts = collect_artifacts(&entries(history()), Some(Path::new("/work")));
+        let summary: Vec<_> = artifacts
+            .iter()
+            .map(|a| (a.path.to_str().unwrap(), a.kind, a.turn))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("/out/page0.png", ArtifactKind::Image, 1),
+                ("/out/page1.png", ArtifactKind::Image, 1),
+                ("/out/chart.png", ArtifactKind::Image, 1),
+                ("/work/sales.xlsx", ArtifactKind::Data, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn rewritten_file_is_listed_once_at_latest_turn() {
+        let entries = entries(
+            history()
+                .user("add a column")
+                .assistant("done")
+                .trace(trace([produced(
+                    "edit_excel",
+                    serde_json::json!({"path": "/work/sales.xlsx"}),
+                )])),
+        );
+        let artifacts = collect_artifacts(&entries, Some(Path::new("/work")));
+        let sales: Vec<_> = artifacts
+            .iter()
+            .filter(|a| a.path.ends_with("sales.xlsx"))
+            .collect();
+        assert_eq!(sales.len(), 1);
+        assert_eq!(sales[0].turn, 3);
+        assert_eq!(sales[0].tool, "edit_excel");
+    }
+
+    #[test]
+    fn filter_by_kind_and_turn() {
+        let artifacts = collect_artifacts(&entries(history()), None);
+        let images = ArtifactFilter {
+            kind: Some(ArtifactKind::Image),
+            turn: None,
+        };
+        assert_eq!(images.apply(&artifacts).len(), 3);
+        let second_turn = ArtifactFilter {
+            kind: None,
+            turn: Some(2),
+        };
+        assert_eq!(second_turn.apply(&artifacts).len(), 1);
+        assert_eq!(ArtifactFilter::default().apply(&artifacts).len(), 4);
+    }
+
+    #[test]
+    fn export_renames_clashes_and_reports_missing() {
+        let src = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        std::fs::create_dir(src.path().join("a")).unwrap();
+        std::fs::write(src.path().join("chart.png"), b"one").unwrap();
+        std::fs::write(src.path().join("a/chart.png"), b"two").unwrap();
+        let artifact = |path: PathBuf| Artifact {
+            kind: ArtifactKind::from_path(&path),
+            path,
+            turn: 1,
+            tool: "add_attachment".to_string(),
+        };
+        let artifacts = vec![
+            artifact(src.path().join("chart.png")),
+            artifact(src.path().join("a/chart.png")),
+            artifact(src.path().join("gone.csv")),
+        ];
+
+        let summary = export_artifacts(&artifacts, dest.path()).unwrap();
+        assert_eq!(
+            summary.copied,
+            vec![
+                dest.path().join("chart.png"),
+                dest.path().join("chart-2.png")
+            ]
+        );
+        assert_eq!(summary.missing, vec![src.path().join("gone.csv")]);
+        assert_eq!(
+            std::fs::read(dest.path().join("chart-2.png")).unwrap(),
+            b"two"
+        );
+    }
+}
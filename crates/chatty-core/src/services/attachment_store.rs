@@ -0,0 +1,326 @@
+//! Content-addressed storage for attachments and tool artifacts.
+//!
+//! Files attached to a message, and files tools queue for inline display,
+//! are copied into `<data_dir>/chatty/attachments/` under the SHA-256 of
+//! their content. The conversation then keeps them even if the original is
+//! moved or edited, and a file attached many times is stored once.
+//! Conversations reference stored files by path in their attachment lists;
+//! a stored file no conversation references is an orphan, which
+//! [`AttachmentStore::remove_orphans`] deletes once it is older than
+//! [`ORPHAN_GRACE_SECS`].
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::repositories::ConversationData;
+
+/// Orphans younger than this are kept: the message referencing them may not
+/// have been saved yet.
+pub const ORPHAN_GRACE_SECS: u64 = 60 * 60;
+
+/// Layout: `<dirs::data_dir>/chatty/attachments/`
+pub fn store_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("chatty").join("attachments"))
+}
+
+/// A file in the store.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoredBlob {
+    pub path: PathBuf,
+    pub bytes: u64,
+    /// Number of attachment lists entries pointing at this file
+    pub references: usize,
+    pub modified: SystemTime,
+}
+
+/// What the store holds, for the storage page.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StorageUsage {
+    pub blobs: Vec<StoredBlob>,
+}
+
+impl StorageUsage {
+    pub fn total_bytes(&self) -> u64 {
+        self.blobs.iter().map(|b| b.bytes).sum()
+    }
+
+    /// Files no conversation references.
+    pub fn orphans(&self) -> impl Iterator<Item = &StoredBlob> {
+        self.blobs.iter().filter(|b| b.references == 0)
+    }
+
+    pub fn orphaned_bytes(&self) -> u64 {
+        self.orphans().map(|b| b.bytes).sum()
+    }
+
+    /// Bytes not stored again because the same file was attached more than once.
+    pub fn deduplicated_bytes(&self) -> u64 {
+        self.blobs
+            .iter()
+            .map(|b| b.bytes * b.references.saturating_sub(1) as u64)
+            .sum()
+    }
+}
+
+/// Result of [`AttachmentStore::remove_orphans`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CleanupReport {
+    pub removed: usize,
+    pub freed_bytes: u64,
+    /// Orphans kept because they are younger than [`ORPHAN_GRACE_SECS`]
+    pub kept_recent: usize,
+}
+
+pub struct AttachmentStore {
+    dir: PathBuf,
+}
+
+impl AttachmentStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The store in the user's data directory.
+    pub fn open_default() -> Option<Self> {
+        store_dir().map(Self::new)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Whether `path` is a file of this store.
+    pub fn contains(&self, path: &Path) -> bool {
+        path.parent() == Some(self.dir.as_path())
+            && path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(is_blob_name)
+    }
+
+    /// Copy `path` into the store and return the stored path. A file with the
+    /// same content and extension is stored only once.
+    pub async fn store(&self, path: &Path) -> Result<PathBuf> {
+        if self.contains(path) {
+            return Ok(path.to_path_buf());
+        }
+        let bytes = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("Failed to read '{}'", path.display()))?;
+        let hash = hex::encode(Sha256::digest(&bytes));
+        let name = match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) => format!("{hash}.{}", ext.to_lowercase()),
+            None => hash,
+        };
+        let stored = self.dir.join(&name);
+        if tokio::fs::try_exists(&stored).await? {
+            // Restart the grace period: the file may have been an orphan
+            // about to be cleaned up.
+            std::fs::File::options()
+                .write(true)
+                .open(&stored)?
+                .set_modified(SystemTime::now())?;
+            return Ok(stored);
+        }
+
+        // Write under a temporary name first so a crash never leaves a
+        // truncated file behind the content's hash.
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let partial = self
+            .dir
+            .join(format!(".{name}.{}.partial", uuid::Uuid::new_v4()));
+        tokio::fs::write(&partial, &bytes).await?;
+        if let Err(e) = tokio::fs::rename(&partial, &stored).await {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(e).context("Failed to move attachment into the store");
+        }
+        info!(path = %path.display(), stored = %stored.display(), "Attachment stored");
+        Ok(stored)
+    }
+
+    /// [`Self::store`] for each path, keeping the original path of any file
+    /// that could not be stored.
+    pub async fn store_all(&self, paths: Vec<PathBuf>) -> Vec<PathBuf> {
+        let mut stored = Vec::with_capacity(paths.len());
+        for path in paths {
+            match self.store(&path).await {
+                Ok(blob) => stored.push(blob),
+                Err(e) => {
+                    warn!(path = %path.display(), error = ?e, "Failed to store attachment, keeping original path");
+                    stored.push(path);
+                }
+            }
+        }
+        stored
+    }
+
+    /// The stored files, with how often `references` (from
+    /// [`reference_counts`]) points at each.
+    pub async fn usage(&self, references: &HashMap<PathBuf, usize>) -> Result<StorageUsage> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(StorageUsage::default());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut blobs = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if !self.contains(&path) {
+                continue;
+            }
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+            blobs.push(StoredBlob {
+                references: references.get(&path).copied().unwrap_or(0),
+                bytes: metadata.len(),
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                path,
+            });
+        }
+        blobs.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.path.cmp(&b.path)));
+        Ok(StorageUsage { blobs })
+    }
+
+    /// Delete the stored files no conversation references, except those
+    /// modified within [`ORPHAN_GRACE_SECS`] of `now`. `references` must cover
+    /// every saved conversation, or referenced files would be deleted.
+    pub async fn remove_orphans(
+        &self,
+        references: &HashMap<PathBuf, usize>,
+        now: SystemTime,
+    ) -> Result<CleanupReport> {
+        let grace = Duration::from_secs(ORPHAN_GRACE_SECS);
+        let mut report = CleanupReport::default();
+        for blob in self.usage(references).await?.orphans() {
+            let age = now.duration_since(blob.modified).unwrap_or_default();
+            if age < grace {
+                report.kept_recent += 1;
+                continue;
+            }
+            match tokio::fs::remove_file(&blob.path).await {
+                Ok(()) => {
+                    report.removed += 1;
+                    report.freed_bytes += blob.bytes;
+                }
+                Err(e) => {
+                    warn!(path = %blob.path.display(), error = ?e, "Failed to remove orphaned attachment")
+                }
+            }
+        }
+        info!(
+            removed = report.removed,
+            freed_bytes = report.freed_bytes,
+            kept_recent = report.kept_recent,
+            "Orphaned attachments removed"
+        );
+        Ok(report)
+    }
+}
+
+/// `<64 hex digits>` with an optional extension.
+fn is_blob_name(name: &str) -> bool {
+    let hash = name.split_once('.').map_or(name, |(hash, _)| hash);
+    hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// How many attachment list entries of `conversations` point at each path.
+pub fn reference_counts(conversations: &[ConversationData]) -> HashMap<PathBuf, usize> {
+    count_references(conversations.iter().map(|c| c.attachment_paths.as_str()))
+}
+
+/// Count the paths in serialized per-message attachment lists.
+fn count_references<'a>(
+    attachment_lists: impl IntoIterator<Item = &'a str>,
+) -> HashMap<PathBuf, usize> {
+    let mut counts = HashMap::new();
+    for json in attachment_lists {
+        let lists: Vec<Vec<PathBuf>> = serde_json::from_str(json).unwrap_or_default();
+        for path in lists.into_iter().flatten() {
+            *counts.entry(path).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: Duration = Duration::from_secs(60 * 60);
+
+    #[tokio::test]
+    async fn identical_files_are_stored_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = AttachmentStore::new(tmp.path().join("attachments"));
+        let (a, b) = (tmp.path().join("a.PNG"), tmp.path().join("b.png"));
+        std::fs::write(&a, "pixels").unwrap();
+        std::fs::write(&b, "pixels").unwrap();
+
+        let stored_a = store.store(&a).await.unwrap();
+        let stored_b = store.store(&b).await.unwrap();
+        assert_eq!(stored_a, stored_b);
+        assert!(store.contains(&stored_a));
+        assert_eq!(stored_a.extension().unwrap(), "png");
+        assert_eq!(std::fs::read_to_string(&stored_a).unwrap(), "pixels");
+        assert_eq!(store.store(&stored_a).await.unwrap(), stored_a);
+
+        let missing = tmp.path().join("missing.pdf");
+        let kept = store.store_all(vec![a, missing.clone()]).await;
+        assert_eq!(kept, vec![stored_a.clone(), missing]);
+
+        let references = HashMap::from([(stored_a, 2)]);
+        let usage = store.usage(&references).await.unwrap();
+        assert_eq!(usage.blobs.len(), 1);
+        assert_eq!(usage.total_bytes(), 6);
+        assert_eq!(usage.deduplicated_bytes(), 6);
+        assert_eq!(usage.orphans().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn only_old_unreferenced_files_are_removed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = AttachmentStore::new(tmp.path());
+        let (kept, orphan) = (tmp.path().join("kept.txt"), tmp.path().join("orphan.txt"));
+        std::fs::write(&kept, "kept").unwrap();
+        std::fs::write(&orphan, "orphan").unwrap();
+        let kept = store.store(&kept).await.unwrap();
+        let orphan = store.store(&orphan).await.unwrap();
+        let references = HashMap::from([(kept.clone(), 1)]);
+
+        let report = store
+            .remove_orphans(&references, SystemTime::now())
+            .await
+            .unwrap();
+        assert_eq!((report.removed, report.kept_recent), (0, 1));
+        assert!(orphan.exists());
+
+        let later = SystemTime::now() + 2 * HOUR;
+        let report = store.remove_orphans(&references, later).await.unwrap();
+        assert_eq!((report.removed, report.freed_bytes), (1, 6));
+        assert!(!orphan.exists());
+        assert!(kept.exists());
+        // Files that are not blobs are never touched
+        assert!(tmp.path().join("kept.txt").exists());
+    }
+
+    #[test]
+    fn counts_references_across_conversations() {
+        let counts = count_references([
+            r#"[["/s/a.png"], [], ["/s/a.png", "/s/b.pdf"]]"#,
+            r#"[["/s/a.png"]]"#,
+            "not json",
+        ]);
+        assert_eq!(counts[Path::new("/s/a.png")], 3);
+        assert_eq!(counts[Path::new("/s/b.pdf")], 1);
+        assert_eq!(counts.len(), 2);
+    }
+}
@@ -0,0 +1,467 @@
+//! Provider batch endpoints for non-interactive prompt runs.
+//!
+//! Instead of one request per prompt, the whole input file is uploaded as a
+//! JSONL job, the provider processes it within its completion window at a
+//! discount, and the results are merged back into [`BatchResult`] rows by
+//! row index. Azure OpenAI (with a Global Batch deployment) is the only
+//! configured provider with a batch endpoint; OpenRouter and Ollama keep
+//! using the real-time runner.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::{debug, info};
+
+use crate::factories::agent_factory::{normalize_azure_endpoint, resolve_azure_auth};
+use crate::models::token_usage::TokenUsage;
+use crate::services::batch_runner::{BatchControl, BatchInput, BatchResult};
+use crate::settings::models::models_store::{AZURE_DEFAULT_API_VERSION, ModelConfig};
+use crate::settings::models::providers_store::{ProviderConfig, ProviderType};
+
+/// Price multiplier providers apply to batch requests.
+pub const BATCH_COST_FACTOR: f64 = 0.5;
+
+/// How often a submitted job is polled.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Time the provider has to finish a job.
+const COMPLETION_WINDOW: &str = "24h";
+
+/// Batch API path every job line targets.
+const CHAT_COMPLETIONS_URL: &str = "/chat/completions";
+
+const MULTIPART_BOUNDARY: &str = "chatty-batch-upload";
+
+/// Lifecycle states reported by the provider.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchJobStatus {
+    Validating,
+    InProgress,
+    Finalizing,
+    Completed,
+    Failed,
+    Expired,
+    Cancelling,
+    Cancelled,
+}
+
+impl BatchJobStatus {
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            Self::Completed | Self::Failed | Self::Expired | Self::Cancelled
+        )
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Validating => "Validating",
+            Self::InProgress => "In progress",
+            Self::Finalizing => "Finalizing",
+            Self::Completed => "Completed",
+            Self::Failed => "Failed",
+            Self::Expired => "Expired",
+            Self::Cancelling => "Cancelling",
+            Self::Cancelled => "Cancelled",
+        }
+    }
+}
+
+/// Request counts of a job, as reported while it runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct BatchRequestCounts {
+    #[serde(default)]
+    pub total: usize,
+    #[serde(default)]
+    pub completed: usize,
+    #[serde(default)]
+    pub failed: usize,
+}
+
+/// A submitted batch job.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BatchJob {
+    pub id: String,
+    pub status: BatchJobStatus,
+    #[serde(default)]
+    pub output_file_id: Option<String>,
+    #[serde(default)]
+    pub error_file_id: Option<String>,
+    #[serde(default)]
+    pub request_counts: BatchRequestCounts,
+}
+
+#[derive(Deserialize)]
+struct UploadedFile {
+    id: String,
+}
+
+/// Client for a provider's file and batch endpoints.
+pub struct BatchJobService {
+    client: reqwest::Client,
+    endpoint: String,
+    api_version: String,
+    auth_header: (&'static str, String),
+}
+
+impl BatchJobService {
+    /// Whether `provider_type` has a batch endpoint.
+    pub fn supports(provider_type: &ProviderType) -> bool {
+        matches!(provider_type, ProviderType::AzureOpenAI)
+    }
+
+    /// Connect to the batch endpoint of `provider` for `model`.
+    ///
+    /// # Errors
+    /// Returns an error if the provider has no batch endpoint or its endpoint
+    /// or credentials are not configured.
+    pub async fn for_provider(model: &ModelConfig, provider: &ProviderConfig) -> Result<Self> {
+        if !Self::supports(&provider.provider_type) {
+            bail!(
+                "{} has no batch endpoint",
+                provider.provider_type.display_name()
+            );
+        }
+        let endpoint = provider
+            .base_url
+            .as_deref()
+            .map(normalize_azure_endpoint)
+            .ok_or_else(|| anyhow!("Endpoint URL not configured for Azure OpenAI provider"))?;
+        let api_version = model
+            .extra_params
+            .get("api_version")
+            .cloned()
+            .unwrap_or_else(|| AZURE_DEFAULT_API_VERSION.to_string());
+        let auth_header = match resolve_azure_auth(provider, provider.api_key.clone()).await? {
+            rig_core::providers::azure::AzureOpenAIAuth::ApiKey(key) => ("api-key", key),
+            rig_core::providers::azure::AzureOpenAIAuth::Token(token) => {
+                ("Authorization", format!("Bearer {token}"))
+            }
+        };
+
+        Ok(Self {
+            client: crate::services::http_client::default_client(300),
+            endpoint,
+            api_version,
+            auth_header,
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}/openai/{path}?api-version={}",
+            self.endpoint, self.api_version
+        )
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let response = request
+            .header(self.auth_header.0, &self.auth_header.1)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("Batch API returned {status}: {body}");
+        }
+        Ok(response)
+    }
+
+    /// Upload `inputs` as a job file and start a batch job for it.
+    pub async fn submit(&self, model: &ModelConfig, inputs: &[BatchInput]) -> Result<BatchJob> {
+        let file = build_job_file(model, inputs);
+        let body = multipart_body(&file);
+        let uploaded: UploadedFile = self
+            .send(
+                self.client
+                    .post(self.url("files"))
+                    .header(
+                        "Content-Type",
+                        format!("multipart/form-data; boundary={MULTIPART_BOUNDARY}"),
+                    )
+                    .body(body),
+            )
+            .await
+            .context("Failed to upload batch input file")?
+            .json()
+            .await?;
+        debug!(file_id = %uploaded.id, "Uploaded batch input file");
+
+        let job: BatchJob = self
+            .send(self.client.post(self.url("batches")).json(&json!({
+                "input_file_id": uploaded.id,
+                "endpoint": CHAT_COMPLETIONS_URL,
+                "completion_window": COMPLETION_WINDOW,
+            })))
+            .await
+            .context("Failed to create batch job")?
+            .json()
+            .await?;
+        info!(job_id = %job.id, requests = inputs.len(), "Submitted batch job");
+        Ok(job)
+    }
+
+    pub async fn status(&self, job_id: &str) -> Result<BatchJob> {
+        Ok(self
+            .send(self.client.get(self.url(&format!("batches/{job_id}"))))
+            .await?
+            .json()
+            .await?)
+    }
+
+    pub async fn cancel(&self, job_id: &str) -> Result<BatchJob> {
+        Ok(self
+            .send(
+                self.client
+                    .post(self.url(&format!("batches/{job_id}/cancel"))),
+            )
+            .await?
+            .json()
+            .await?)
+    }
+
+    /// Poll `job_id` until it reaches a terminal state, calling `on_status`
+    /// after every poll. Cancelling `control` cancels the job; pausing only
+    /// stops polling, since the provider keeps processing.
+    pub async fn wait(
+        &self,
+        job_id: &str,
+        control: &BatchControl,
+        mut on_status: impl FnMut(&BatchJob),
+    ) -> Result<BatchJob> {
+        let mut cancel_sent = false;
+        loop {
+            if control.is_cancelled() && !cancel_sent {
+                self.cancel(job_id).await?;
+                cancel_sent = true;
+            }
+            let job = self.status(job_id).await?;
+            on_status(&job);
+            if job.status.is_terminal() {
+                return Ok(job);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn file_content(&self, file_id: &str) -> Result<String> {
+        Ok(self
+            .send(
+                self.client
+                    .get(self.url(&format!("files/{file_id}/content"))),
+            )
+            .await?
+            .text()
+            .await?)
+    }
+
+    /// Download the output and error files of a finished job.
+    pub async fn fetch_results(&self, job: &BatchJob) -> Result<String> {
+        let mut lines = String::new();
+        for file_id in [&job.output_file_id, &job.error_file_id]
+            .into_iter()
+            .flatten()
+        {
+            lines.push_str(&self.file_content(file_id).await?);
+            lines.push('\n');
+        }
+        Ok(lines)
+    }
+}
+
+/// JSONL job file with one chat completion request per input. Rows are
+/// keyed by index because input ids need not be unique.
+pub fn build_job_file(model: &ModelConfig, inputs: &[BatchInput]) -> String {
+    inputs
+        .iter()
+        .enumerate()
+        .map(|(ix, input)| {
+            let mut messages = Vec::new();
+            if !model.preamble.trim().is_empty() {
+                messages.push(json!({"role": "system", "content": model.preamble}));
+            }
+            messages.push(json!({"role": "user", "content": input.prompt}));
+            let mut body = json!({
+                "model": model.model_identifier,
+                "messages": messages,
+            });
+            if model.supports_temperature {
+                body["temperature"] = json!(model.temperature);
+            }
+            if let Some(max_tokens) = model.max_tokens {
+                body["max_tokens"] = json!(max_tokens);
+            }
+            let line = json!({
+                "custom_id": ix.to_string(),
+                "method": "POST",
+                "url": CHAT_COMPLETIONS_URL,
+                "body": body,
+            });
+            format!("{line}\n")
+        })
+        .collect()
+}
+
+fn multipart_body(file: &str) -> Vec<u8> {
+    format!(
+        "--{MULTIPART_BOUNDARY}\r\n\
+         Content-Disposition: form-data; name=\"purpose\"\r\n\r\n\
+         batch\r\n\
+         --{MULTIPART_BOUNDARY}\r\n\
+         Content-Disposition: form-data; name=\"file\"; filename=\"batch.jsonl\"\r\n\
+         Content-Type: application/jsonl\r\n\r\n\
+         {file}\r\n\
+         --{MULTIPART_BOUNDARY}--\r\n"
+    )
+    .into_bytes()
+}
+
+#[derive(Deserialize)]
+struct ResultLine {
+    custom_id: String,
+    #[serde(default)]
+    response: Option<ResultResponse>,
+    #[serde(default)]
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ResultResponse {
+    status_code: u16,
+    #[serde(default)]
+    body: serde_json::Value,
+}
+
+/// Merge the provider's result lines back into one [`BatchResult`] per
+/// input, in input order. Inputs without a result line are reported as
+/// failed. Costs are discounted by [`BATCH_COST_FACTOR`].
+pub fn merge_results(
+    model: &ModelConfig,
+    inputs: Vec<BatchInput>,
+    lines: &str,
+) -> Vec<BatchResult> {
+    let mut by_row: HashMap<usize, ResultLine> = lines
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<ResultLine>(line).ok())
+        .filter_map(|line| Some((line.custom_id.parse().ok()?, line)))
+        .collect();
+
+    inputs
+        .into_iter()
+        .enumerate()
+        .map(|(ix, input)| {
+            let (response, error, usage) = match by_row.remove(&ix) {
+                Some(line) => parse_result_line(model, line),
+                None => (
+                    None,
+                    Some("No result returned for this row".to_string()),
+                    TokenUsage::default(),
+                ),
+            };
+            BatchResult {
+                id: input.id,
+                prompt: input.prompt,
+                model_id: model.id.clone(),
+                response,
+                error,
+                usage,
+                duration_ms: 0,
+            }
+        })
+        .collect()
+}
+
+fn parse_result_line(
+    model: &ModelConfig,
+    line: ResultLine,
+) -> (Option<String>, Option<String>, TokenUsage) {
+    let Some(response) = line.response.filter(|r| r.status_code < 400) else {
+        let error = line
+            .error
+            .as_ref()
+            .and_then(|e| e.get("message").and_then(|m| m.as_str()))
+            .map(str::to_string)
+            .or_else(|| line.error.as_ref().map(|e| e.to_string()))
+            .unwrap_or_else(|| "Request failed".to_string());
+        return (None, Some(error), TokenUsage::default());
+    };
+
+    let body = &response.body;
+    let text = body["choices"][0]["message"]["content"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    let mut usage = TokenUsage::new(
+        body["usage"]["prompt_tokens"].as_u64().unwrap_or(0) as u32,
+        body["usage"]["completion_tokens"].as_u64().unwrap_or(0) as u32,
+    );
+    if let (Some(input_cost), Some(output_cost)) = (
+        model.cost_per_million_input_tokens,
+        model.cost_per_million_output_tokens,
+    ) {
+        usage.calculate_cost(
+            input_cost * BATCH_COST_FACTOR,
+            output_cost * BATCH_COST_FACTOR,
+        );
+    }
+    (Some(text), None, usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> ModelConfig {
+        let mut m = ModelConfig::new(
+            "m".into(),
+            "Batch".into(),
+            ProviderType::AzureOpenAI,
+            "gpt-4o-batch".into(),
+        );
+        m.cost_per_million_input_tokens = Some(2.0);
+        m.cost_per_million_output_tokens = Some(8.0);
+        m
+    }
+
+    fn input(id: &str, prompt: &str) -> BatchInput {
+        BatchInput {
+            id: id.into(),
+            prompt: prompt.into(),
+        }
+    }
+
+    #[test]
+    fn job_file_keys_rows_by_index() {
+        let file = build_job_file(&model(), &[input("a", "one"), input("a", "two")]);
+        let lines: Vec<serde_json::Value> = file
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["custom_id"], "1");
+        assert_eq!(lines[1]["url"], CHAT_COMPLETIONS_URL);
+        assert_eq!(lines[1]["body"]["model"], "gpt-4o-batch");
+        assert_eq!(lines[1]["body"]["messages"][0]["content"], "two");
+    }
+
+    #[test]
+    fn merges_results_with_errors_and_missing_rows() {
+        let lines = r#"{"custom_id":"1","response":{"status_code":400,"body":{}},"error":{"message":"bad request"}}
+{"custom_id":"0","response":{"status_code":200,"body":{"choices":[{"message":{"content":"hi"}}],"usage":{"prompt_tokens":1000000,"completion_tokens":0}}}}"#;
+        let results = merge_results(
+            &model(),
+            vec![input("a", "one"), input("b", "two"), input("c", "three")],
+            lines,
+        );
+        assert_eq!(results[0].response.as_deref(), Some("hi"));
+        assert_eq!(results[0].usage.estimated_cost_usd, Some(1.0));
+        assert_eq!(results[1].error.as_deref(), Some("bad request"));
+        assert_eq!(results[2].id, "c");
+        assert!(results[2].error.is_some());
+    }
+}
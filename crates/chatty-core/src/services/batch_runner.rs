@@ -0,0 +1,471 @@
+//! Batch prompt runs over a CSV or JSONL file of inputs.
+//!
+//! Each input is sent as a fresh single-turn prompt to one model, with at
+//! most `concurrency` requests in flight. Results are appended to a JSONL
+//! output file as they complete, so a cancelled run keeps its finished rows.
+//! Runs can be paused between prompts; in-flight requests finish first.
+//!
+//! For providers with a batch endpoint, [`run_provider_batch`] submits the
+//! whole file as one discounted job through
+//! [`BatchJobService`](super::batch_job_service::BatchJobService) and writes
+//! the merged results to the same output format.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use anyhow::{Context, Result, anyhow, bail};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Notify;
+use tracing::warn;
+
+use crate::factories::AgentClient;
+use crate::models::token_usage::TokenUsage;
+use crate::services::batch_job_service::{self, BatchJobService, BatchJobStatus};
+use crate::settings::models::models_store::ModelConfig;
+
+/// Column (CSV) or field (JSONL) holding the prompt text.
+pub const PROMPT_FIELD: &str = "prompt";
+/// Optional column or field used as the row identifier.
+pub const ID_FIELD: &str = "id";
+
+/// One prompt to run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchInput {
+    pub id: String,
+    pub prompt: String,
+}
+
+/// Outcome of one prompt, written as a line of the output file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BatchResult {
+    pub id: String,
+    pub prompt: String,
+    pub model_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub usage: TokenUsage,
+    pub duration_ms: u64,
+}
+
+/// Running totals reported after every completed prompt.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BatchProgress {
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl BatchProgress {
+    pub fn is_finished(&self) -> bool {
+        self.completed >= self.total
+    }
+
+    fn record(&mut self, result: &BatchResult) {
+        self.completed += 1;
+        if result.error.is_some() {
+            self.failed += 1;
+        }
+        self.input_tokens += result.usage.input_tokens as u64;
+        self.output_tokens += result.usage.output_tokens as u64;
+        self.cost_usd += result.usage.estimated_cost_usd.unwrap_or(0.0);
+    }
+}
+
+/// How a run sends its prompts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BatchMode {
+    /// One request per prompt, `concurrency` at a time.
+    #[default]
+    Realtime,
+    /// One job on the provider's batch endpoint, at a discount but with
+    /// results arriving within the provider's completion window.
+    ProviderBatch,
+}
+
+/// Pause, resume and cancel handle shared between a run and its UI.
+#[derive(Clone, Default)]
+pub struct BatchControl {
+    paused: Arc<AtomicBool>,
+    cancelled: Arc<AtomicBool>,
+    resumed: Arc<Notify>,
+}
+
+impl BatchControl {
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+        self.resumed.notify_waiters();
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.resumed.notify_waiters();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wait while paused. Returns false when the run was cancelled.
+    async fn proceed(&self) -> bool {
+        while self.is_paused() && !self.is_cancelled() {
+            let resumed = self.resumed.notified();
+            if !self.is_paused() || self.is_cancelled() {
+                break;
+            }
+            resumed.await;
+        }
+        !self.is_cancelled()
+    }
+}
+
+/// Directory batch results are written to (`<config>/chatty/exports/batch`).
+pub fn default_batch_dir() -> Option<PathBuf> {
+    crate::exporters::review::default_exports_dir().map(|dir| dir.join("batch"))
+}
+
+/// Output path for a run of `input` against `model_id`.
+pub fn output_path(dir: &Path, input: &Path, model_id: &str) -> PathBuf {
+    let stem = input
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("batch");
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let model: String = model_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("{stem}.{model}.{stamp}.jsonl"))
+}
+
+/// Read batch inputs from a `.csv` or `.jsonl` file.
+pub async fn load_inputs(path: &Path) -> Result<Vec<BatchInput>> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match ext.as_deref() {
+        Some("csv") => parse_csv_inputs(&content),
+        Some("jsonl") | Some("ndjson") => parse_jsonl_inputs(&content),
+        _ => bail!("Unsupported input file; use .csv or .jsonl"),
+    }
+}
+
+/// Parse CSV with a header row containing a `prompt` column and optionally
+/// an `id` column. Rows without an id are numbered from 1.
+pub fn parse_csv_inputs(content: &str) -> Result<Vec<BatchInput>> {
+    let mut rows = parse_csv(content).into_iter();
+    let header = rows.next().ok_or_else(|| anyhow!("CSV file is empty"))?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let prompt_col =
+        column(PROMPT_FIELD).ok_or_else(|| anyhow!("CSV header has no \"prompt\" column"))?;
+    let id_col = column(ID_FIELD);
+
+    Ok(rows
+        .enumerate()
+        .filter_map(|(ix, row)| {
+            let prompt = row.get(prompt_col)?.trim().to_string();
+            if prompt.is_empty() {
+                return None;
+            }
+            let id = id_col
+                .and_then(|c| row.get(c))
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty())
+                .unwrap_or_else(|| (ix + 1).to_string());
+            Some(BatchInput { id, prompt })
+        })
+        .collect())
+}
+
+/// Parse JSONL where each line is an object with a `prompt` field and an
+/// optional `id` field (string or number).
+pub fn parse_jsonl_inputs(content: &str) -> Result<Vec<BatchInput>> {
+    let mut inputs = Vec::new();
+    for (ix, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value: serde_json::Value = serde_json::from_str(line)
+            .with_context(|| format!("Invalid JSON on line {}", ix + 1))?;
+        let prompt = value
+            .get(PROMPT_FIELD)
+            .and_then(|p| p.as_str())
+            .ok_or_else(|| anyhow!("Line {} has no \"prompt\" string", ix + 1))?;
+        let id = match value.get(ID_FIELD) {
+            Some(serde_json::Value::String(id)) => id.clone(),
+            Some(serde_json::Value::Number(n)) => n.to_string(),
+            _ => (inputs.len() + 1).to_string(),
+        };
+        inputs.push(BatchInput {
+            id,
+            prompt: prompt.to_string(),
+        });
+    }
+    Ok(inputs)
+}
+
+/// Minimal RFC 4180 reader: quoted fields may contain commas, newlines and
+/// doubled quotes.
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|r| r.iter().any(|f| !f.trim().is_empty()));
+    rows
+}
+
+async fn run_one(agent: &AgentClient, model: &ModelConfig, input: BatchInput) -> BatchResult {
+    let started = Instant::now();
+    let (response, error, usage) = match agent.prompt_with_usage(&input.prompt).await {
+        Ok((text, usage)) => {
+            let mut usage = TokenUsage::new(usage.input_tokens as u32, usage.output_tokens as u32);
+            if let (Some(input_cost), Some(output_cost)) = (
+                model.cost_per_million_input_tokens,
+                model.cost_per_million_output_tokens,
+            ) {
+                usage.calculate_cost(input_cost, output_cost);
+            }
+            (Some(text), None, usage)
+        }
+        Err(e) => (None, Some(e.to_string()), TokenUsage::default()),
+    };
+    BatchResult {
+        id: input.id,
+        prompt: input.prompt,
+        model_id: model.id.clone(),
+        response,
+        error,
+        usage,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+async fn open_output(output: &Path) -> Result<tokio::fs::File> {
+    if let Some(parent) = output.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(output)
+        .await
+        .with_context(|| format!("Failed to open {}", output.display()))
+}
+
+async fn write_result(file: &mut tokio::fs::File, result: &BatchResult) -> Result<()> {
+    let mut line = serde_json::to_string(result)?;
+    line.push('\n');
+    file.write_all(line.as_bytes()).await?;
+    Ok(())
+}
+
+/// Run `inputs` against `agent`, appending each result to `output`.
+///
+/// `on_progress` is called after every completed prompt. Returns the final
+/// totals; a cancelled run returns the totals so far.
+pub async fn run_batch(
+    agent: AgentClient,
+    model: ModelConfig,
+    inputs: Vec<BatchInput>,
+    concurrency: usize,
+    output: &Path,
+    control: BatchControl,
+    mut on_progress: impl FnMut(&BatchProgress),
+) -> Result<BatchProgress> {
+    let mut file = open_output(output).await?;
+
+    let mut progress = BatchProgress {
+        total: inputs.len(),
+        ..Default::default()
+    };
+    on_progress(&progress);
+
+    let agent = &agent;
+    let model = &model;
+    let control_ref = &control;
+    let mut results = futures::stream::iter(inputs)
+        .map(|input| async move {
+            if !control_ref.proceed().await {
+                return None;
+            }
+            Some(run_one(agent, model, input).await)
+        })
+        .buffer_unordered(concurrency.max(1));
+
+    while let Some(result) = results.next().await {
+        let Some(result) = result else { continue };
+        if let Some(error) = &result.error {
+            warn!(id = %result.id, error = %error, "Batch prompt failed");
+        }
+        write_result(&mut file, &result).await?;
+        progress.record(&result);
+        on_progress(&progress);
+    }
+    file.flush().await?;
+
+    Ok(progress)
+}
+
+/// Submit `inputs` as one job on the provider's batch endpoint, wait for it
+/// and write the merged results to `output`.
+///
+/// `on_progress` is called after every status poll. Cancelling `control`
+/// cancels the job; rows the provider finished before that are still
+/// written.
+pub async fn run_provider_batch(
+    service: BatchJobService,
+    model: ModelConfig,
+    inputs: Vec<BatchInput>,
+    output: &Path,
+    control: BatchControl,
+    mut on_progress: impl FnMut(&BatchProgress),
+) -> Result<BatchProgress> {
+    let mut file = open_output(output).await?;
+    let mut progress = BatchProgress {
+        total: inputs.len(),
+        ..Default::default()
+    };
+    on_progress(&progress);
+
+    let job = service.submit(&model, &inputs).await?;
+    let job = service
+        .wait(&job.id, &control, |job| {
+            let counts = job.request_counts;
+            progress.completed = counts.completed + counts.failed;
+            progress.failed = counts.failed;
+            on_progress(&progress);
+        })
+        .await?;
+    if job.status == BatchJobStatus::Failed
+        && job.output_file_id.is_none()
+        && job.error_file_id.is_none()
+    {
+        bail!("Batch job {} failed", job.id);
+    }
+
+    let lines = service.fetch_results(&job).await?;
+    let mut progress = BatchProgress {
+        total: inputs.len(),
+        ..Default::default()
+    };
+    for result in batch_job_service::merge_results(&model, inputs, &lines) {
+        if let Some(error) = &result.error {
+            warn!(id = %result.id, error = %error, "Batch prompt failed");
+        }
+        write_result(&mut file, &result).await?;
+        progress.record(&result);
+    }
+    file.flush().await?;
+    on_progress(&progress);
+
+    Ok(progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_csv_with_quotes_and_optional_ids() {
+        let csv = "id,prompt,label\r\n\
+                   a1,\"Hello, world\",x\r\n\
+                   ,\"Say \"\"hi\"\"\nplease\",y\r\n\
+                   a3,,z\r\n";
+        let inputs = parse_csv_inputs(csv).unwrap();
+        assert_eq!(
+            inputs,
+            vec![
+                BatchInput {
+                    id: "a1".into(),
+                    prompt: "Hello, world".into()
+                },
+                BatchInput {
+                    id: "2".into(),
+                    prompt: "Say \"hi\"\nplease".into()
+                },
+            ]
+        );
+        assert!(parse_csv_inputs("question\nwhat?\n").is_err());
+    }
+
+    #[test]
+    fn parses_jsonl_inputs() {
+        let jsonl = "{\"id\": 7, \"prompt\": \"one\"}\n\n{\"prompt\": \"two\"}\n";
+        let inputs = parse_jsonl_inputs(jsonl).unwrap();
+        assert_eq!(inputs[0].id, "7");
+        assert_eq!(inputs[1].id, "2");
+        assert_eq!(inputs[1].prompt, "two");
+        assert!(parse_jsonl_inputs("{\"text\": \"x\"}").is_err());
+    }
+
+    #[tokio::test]
+    async fn paused_control_waits_until_resumed_or_cancelled() {
+        let control = BatchControl::default();
+        control.pause();
+        let waiter = tokio::spawn({
+            let control = control.clone();
+            async move { control.proceed().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+        control.resume();
+        assert!(waiter.await.unwrap());
+
+        control.pause();
+        control.cancel();
+        assert!(!control.proceed().await);
+    }
+}
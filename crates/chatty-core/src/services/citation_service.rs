@@ -0,0 +1,745 @@
+use anyhow::{Context, Result, anyhow};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tracing::{debug, info};
+
+/// References seen in this process, keyed by citekey. Filled when a BibTeX
+/// library is loaded or Zotero results are returned, and read by the UI to
+/// turn `@citekey` in answers into reference cards.
+static KNOWN_REFERENCES: LazyLock<RwLock<HashMap<String, Reference>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// `[@key]`, `[@a; @b]` and bare `@key` preceded by whitespace or punctuation.
+static CITEKEY_RE: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"(^|[\s\[(;,])@([A-Za-z0-9][A-Za-z0-9_:.\-]*[A-Za-z0-9])").unwrap()
+});
+
+/// A bibliographic reference from a BibTeX file or a Zotero library.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Reference {
+    /// Citekey used as `@key` in answers.
+    pub key: String,
+    /// Entry type, e.g. `article`, `book`, `inproceedings`.
+    pub kind: String,
+    pub title: String,
+    /// Authors as "Last, First".
+    pub authors: Vec<String>,
+    #[serde(default)]
+    pub year: Option<String>,
+    /// Journal, book or proceedings title.
+    #[serde(default)]
+    pub container: Option<String>,
+    #[serde(default)]
+    pub volume: Option<String>,
+    #[serde(default)]
+    pub pages: Option<String>,
+    #[serde(default)]
+    pub publisher: Option<String>,
+    #[serde(default)]
+    pub doi: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Bibliography style for formatted references.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CitationStyle {
+    #[default]
+    Apa,
+    Mla,
+    Chicago,
+    Ieee,
+}
+
+impl Reference {
+    fn last_names(&self) -> Vec<&str> {
+        self.authors
+            .iter()
+            .map(|a| a.split(',').next().unwrap_or(a).trim())
+            .collect()
+    }
+
+    /// Author-year label, e.g. "Smith & Lee, 2020" or "Smith et al., 2020".
+    pub fn in_text(&self) -> String {
+        let names = self.last_names();
+        let who = match names.as_slice() {
+            [] => self.title.clone(),
+            [one] => one.to_string(),
+            [a, b] => format!("{a} & {b}"),
+            [a, ..] => format!("{a} et al."),
+        };
+        format!("{}, {}", who, self.year.as_deref().unwrap_or("n.d."))
+    }
+
+    /// Link to the reference: DOI when known, otherwise its URL.
+    pub fn link(&self) -> Option<String> {
+        self.doi
+            .as_ref()
+            .map(|doi| {
+                format!(
+                    "https://doi.org/{}",
+                    doi.trim_start_matches("https://doi.org/")
+                )
+            })
+            .or_else(|| self.url.clone())
+    }
+
+    /// Full bibliography entry in `style`.
+    pub fn format(&self, style: CitationStyle) -> String {
+        let year = self.year.as_deref().unwrap_or("n.d.");
+        let mut out = match style {
+            CitationStyle::Apa => {
+                let mut s = format!(
+                    "{} ({}). {}.",
+                    join_authors(&self.authors, " & "),
+                    year,
+                    self.title
+                );
+                if let Some(container) = &self.container {
+                    s.push_str(&format!(" *{}*", container));
+                    if let Some(volume) = &self.volume {
+                        s.push_str(&format!(", {}", volume));
+                    }
+                    if let Some(pages) = &self.pages {
+                        s.push_str(&format!(", {}", pages));
+                    }
+                    s.push('.');
+                } else if let Some(publisher) = &self.publisher {
+                    s.push_str(&format!(" {}.", publisher));
+                }
+                s
+            }
+            CitationStyle::Mla => {
+                let mut s = format!(
+                    "{}. \"{}.\"",
+                    join_authors(&self.authors, ", and "),
+                    self.title
+                );
+                if let Some(container) = self.container.as_ref().or(self.publisher.as_ref()) {
+                    s.push_str(&format!(" *{}*,", container));
+                }
+                if let Some(volume) = &self.volume {
+                    s.push_str(&format!(" vol. {},", volume));
+                }
+                s.push_str(&format!(" {}", year));
+                if let Some(pages) = &self.pages {
+                    s.push_str(&format!(", pp. {}", pages));
+                }
+                s.push('.');
+                s
+            }
+            CitationStyle::Chicago => {
+                let mut s = format!(
+                    "{}. {}. \"{}.\"",
+                    join_authors(&self.authors, ", and "),
+                    year,
+                    self.title
+                );
+                if let Some(container) = self.container.as_ref().or(self.publisher.as_ref()) {
+                    s.push_str(&format!(" *{}*", container));
+                    if let Some(volume) = &self.volume {
+                        s.push_str(&format!(" {}", volume));
+                    }
+                    if let Some(pages) = &self.pages {
+                        s.push_str(&format!(": {}", pages));
+                    }
+                    s.push('.');
+                }
+                s
+            }
+            CitationStyle::Ieee => {
+                let authors: Vec<String> = self.authors.iter().map(|a| initials_first(a)).collect();
+                let mut s = format!("{}, \"{},\"", authors.join(", "), self.title);
+                if let Some(container) = self.container.as_ref().or(self.publisher.as_ref()) {
+                    s.push_str(&format!(" *{}*,", container));
+                }
+                if let Some(volume) = &self.volume {
+                    s.push_str(&format!(" vol. {},", volume));
+                }
+                if let Some(pages) = &self.pages {
+                    s.push_str(&format!(" pp. {},", pages));
+                }
+                s.push_str(&format!(" {}.", year));
+                s
+            }
+        };
+        if let Some(link) = self.link() {
+            out.push_str(&format!(" {}", link));
+        }
+        out
+    }
+}
+
+fn join_authors(authors: &[String], last_sep: &str) -> String {
+    match authors {
+        [] => "Anon.".to_string(),
+        [one] => one.clone(),
+        [rest @ .., last] => format!("{}{}{}", rest.join(", "), last_sep, last),
+    }
+}
+
+/// "Smith, John Paul" → "J. P. Smith".
+fn initials_first(author: &str) -> String {
+    match author.split_once(',') {
+        Some((last, first)) => {
+            let initials: Vec<String> = first
+                .split_whitespace()
+                .filter_map(|n| n.chars().next())
+                .map(|c| format!("{c}."))
+                .collect();
+            format!("{} {}", initials.join(" "), last.trim())
+                .trim()
+                .to_string()
+        }
+        None => author.to_string(),
+    }
+}
+
+/// Where references come from.
+#[derive(Debug, Clone)]
+pub enum CitationSource {
+    /// A `.bib` file, parsed once when the service is created.
+    Bibtex(PathBuf),
+    /// Zotero's local API (Zotero 7, "Allow other applications to communicate
+    /// with Zotero"), e.g. `http://localhost:23119/api`.
+    Zotero(String),
+}
+
+/// Looks up references in a BibTeX file or a local Zotero library.
+#[derive(Debug)]
+pub struct CitationService {
+    source: CitationSource,
+    entries: Vec<Reference>,
+    client: reqwest::Client,
+}
+
+impl CitationService {
+    pub async fn new(source: CitationSource) -> Result<Self> {
+        let client = crate::services::http_client::default_client(10);
+        let entries = match &source {
+            CitationSource::Bibtex(path) => {
+                let text = tokio::fs::read_to_string(path)
+                    .await
+                    .with_context(|| format!("Failed to read BibTeX file '{}'", path.display()))?;
+                let entries = parse_bibtex(&text);
+                info!(path = %path.display(), entries = entries.len(), "BibTeX library loaded");
+                remember(&entries);
+                entries
+            }
+            CitationSource::Zotero(url) => {
+                client
+                    .get(format!("{}/users/0/items", url.trim_end_matches('/')))
+                    .query(&[("limit", "1")])
+                    .send()
+                    .await
+                    .and_then(|r| r.error_for_status())
+                    .with_context(|| format!("Zotero local API not reachable at {url}"))?;
+                info!(url = %url, "Zotero local API connected");
+                Vec::new()
+            }
+        };
+        Ok(Self {
+            source,
+            entries,
+            client,
+        })
+    }
+
+    /// Load a service from the citation settings, if a source is configured.
+    pub fn source_from_settings(
+        bibtex_path: Option<&str>,
+        zotero_api_url: Option<&str>,
+    ) -> Option<CitationSource> {
+        bibtex_path
+            .filter(|p| !p.trim().is_empty())
+            .map(|p| CitationSource::Bibtex(Path::new(p.trim()).to_path_buf()))
+            .or_else(|| {
+                zotero_api_url
+                    .filter(|u| !u.trim().is_empty())
+                    .map(|u| CitationSource::Zotero(u.trim().to_string()))
+            })
+    }
+
+    /// Search by citekey, title, author, year or venue.
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<Reference>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(anyhow!("Search query must not be empty"));
+        }
+        let results = match &self.source {
+            CitationSource::Bibtex(_) => search_entries(&self.entries, query, limit),
+            CitationSource::Zotero(url) => {
+                let results = self.search_zotero(url, query, limit).await?;
+                remember(&results);
+                results
+            }
+        };
+        debug!(query, results = results.len(), "Citation search");
+        Ok(results)
+    }
+
+    /// Find references by citekey.
+    pub fn get(&self, key: &str) -> Option<Reference> {
+        self.entries
+            .iter()
+            .find(|r| r.key == key)
+            .cloned()
+            .or_else(|| lookup(key))
+    }
+
+    async fn search_zotero(&self, url: &str, query: &str, limit: usize) -> Result<Vec<Reference>> {
+        let limit = limit.to_string();
+        let body = self
+            .client
+            .get(format!("{}/users/0/items", url.trim_end_matches('/')))
+            .query(&[
+                ("q", query),
+                ("qmode", "titleCreatorYear"),
+                ("itemType", "-attachment"),
+                ("limit", limit.as_str()),
+            ])
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .context("Zotero search failed")?
+            .text()
+            .await?;
+        parse_zotero_items(&body)
+    }
+}
+
+fn search_entries(entries: &[Reference], query: &str, limit: usize) -> Vec<Reference> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    entries
+        .iter()
+        .filter(|r| {
+            let haystack = format!(
+                "{} {} {} {} {}",
+                r.key,
+                r.title,
+                r.authors.join(" "),
+                r.year.as_deref().unwrap_or_default(),
+                r.container.as_deref().unwrap_or_default()
+            )
+            .to_lowercase();
+            terms.iter().all(|t| haystack.contains(t))
+        })
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
+/// Add references to the process-wide lookup used for rendering.
+pub fn remember(references: &[Reference]) {
+    let mut known = KNOWN_REFERENCES.write();
+    for reference in references {
+        known.insert(reference.key.clone(), reference.clone());
+    }
+}
+
+/// Look up a reference seen earlier in this process.
+pub fn lookup(key: &str) -> Option<Reference> {
+    KNOWN_REFERENCES.read().get(key).cloned()
+}
+
+/// Known references cited as `@key` in `text`, in order of first use.
+pub fn cited_references(text: &str) -> Vec<Reference> {
+    let known = KNOWN_REFERENCES.read();
+    if known.is_empty() {
+        return Vec::new();
+    }
+    let mut cited: Vec<Reference> = Vec::new();
+    for caps in CITEKEY_RE.captures_iter(text) {
+        if let Some(reference) = known.get(&caps[2])
+            && !cited.iter().any(|r| r.key == reference.key)
+        {
+            cited.push(reference.clone());
+        }
+    }
+    cited
+}
+
+/// Replace known `@key` citations with author-year links, e.g.
+/// `[@smith2020]` → `[(Smith, 2020)](https://doi.org/…)`. Unknown keys and
+/// e-mail addresses are left alone.
+pub fn resolve_citekeys(text: &str) -> Cow<'_, str> {
+    let known = KNOWN_REFERENCES.read();
+    if known.is_empty() || !text.contains('@') {
+        return Cow::Borrowed(text);
+    }
+    let replaced = CITEKEY_RE.replace_all(text, |caps: &regex::Captures| {
+        let prefix = &caps[1];
+        match known.get(&caps[2]) {
+            Some(reference) => {
+                let label = format!("({})", reference.in_text());
+                match reference.link() {
+                    Some(link) => format!("{prefix}[{label}]({link})"),
+                    None => format!("{prefix}{label}"),
+                }
+            }
+            None => caps[0].to_string(),
+        }
+    });
+    match replaced {
+        Cow::Borrowed(_) => Cow::Borrowed(text),
+        // Drop the brackets of `[@key]` groups that are now fully resolved.
+        Cow::Owned(s) => Cow::Owned(strip_citation_brackets(&s)),
+    }
+}
+
+/// `[[(Smith, 2020)](…)]` → `[(Smith, 2020)](…)`, `[[(A)](…); [(B)](…)]` → `[(A)](…); [(B)](…)`.
+fn strip_citation_brackets(text: &str) -> String {
+    static GROUP_RE: LazyLock<regex::Regex> = LazyLock::new(|| {
+        regex::Regex::new(r"\[((?:\[\([^\]]+\)\]\([^)\s]+\)|\([^\[\]()]+\))(?:[;,]\s*(?:\[\([^\]]+\)\]\([^)\s]+\)|\([^\[\]()]+\)))*)\]").unwrap()
+    });
+    GROUP_RE.replace_all(text, "$1").into_owned()
+}
+
+// ── BibTeX ──────────────────────────────────────────────────────────────────
+
+/// Parse the entries of a BibTeX file. `@string`, `@comment` and `@preamble`
+/// blocks and malformed entries are skipped.
+pub fn parse_bibtex(text: &str) -> Vec<Reference> {
+    let mut entries = Vec::new();
+    let mut rest = text;
+    while let Some(at) = rest.find('@') {
+        rest = &rest[at + 1..];
+        let Some(open) = rest.find(['{', '(']) else {
+            break;
+        };
+        let kind = rest[..open].trim().to_lowercase();
+        // An `@` in free text between entries (e.g. an e-mail address).
+        if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphabetic()) {
+            continue;
+        }
+        let Some(body_len) = balanced_len(&rest[open..]) else {
+            break;
+        };
+        let body = &rest[open + 1..open + body_len - 1];
+        rest = &rest[open + body_len..];
+        if matches!(kind.as_str(), "string" | "comment" | "preamble") {
+            continue;
+        }
+        if let Some(reference) = parse_bibtex_entry(&kind, body) {
+            entries.push(reference);
+        }
+    }
+    entries
+}
+
+/// Length of the bracketed group starting at `text[0]`, including both brackets.
+fn balanced_len(text: &str) -> Option<usize> {
+    let (open, close) = if text.starts_with('(') {
+        ('(', ')')
+    } else {
+        ('{', '}')
+    };
+    let mut depth = 0usize;
+    for (ix, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth = depth.checked_sub(1)?;
+            if depth == 0 {
+                return Some(ix + 1);
+            }
+        }
+    }
+    None
+}
+
+fn parse_bibtex_entry(kind: &str, body: &str) -> Option<Reference> {
+    let (key, fields) = body.split_once(',')?;
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+    let fields = parse_bibtex_fields(fields);
+    let field = |name: &str| fields.get(name).cloned().filter(|v| !v.is_empty());
+    Some(Reference {
+        key: key.to_string(),
+        kind: kind.to_string(),
+        title: field("title").unwrap_or_default(),
+        authors: field("author")
+            .or_else(|| field("editor"))
+            .map(|a| {
+                a.split(" and ")
+                    .map(|name| normalize_author(name.trim()))
+                    .filter(|n| !n.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        year: field("year").or_else(|| field("date").map(|d| d.chars().take(4).collect())),
+        container: field("journal")
+            .or_else(|| field("journaltitle"))
+            .or_else(|| field("booktitle")),
+        volume: field("volume"),
+        pages: field("pages").map(|p| p.replace("--", "–")),
+        publisher: field("publisher").or_else(|| field("institution")),
+        doi: field("doi"),
+        url: field("url"),
+    })
+}
+
+/// `name = {value}`, `name = "value"` and `name = 2020` pairs, lower-cased names.
+fn parse_bibtex_fields(text: &str) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+    let mut rest = text;
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq]
+            .trim()
+            .trim_start_matches(',')
+            .trim()
+            .to_lowercase();
+        let value_text = rest[eq + 1..].trim_start();
+        let (value, consumed) = if value_text.starts_with('{') {
+            match balanced_len(value_text) {
+                Some(len) => (&value_text[1..len - 1], len),
+                None => break,
+            }
+        } else if let Some(quoted) = value_text.strip_prefix('"') {
+            match quoted.find('"') {
+                Some(end) => (&quoted[..end], end + 2),
+                None => break,
+            }
+        } else {
+            let end = value_text.find(',').unwrap_or(value_text.len());
+            (value_text[..end].trim(), end)
+        };
+        fields.insert(name, clean_bibtex_value(value));
+        rest = &value_text[consumed..];
+    }
+    fields
+}
+
+/// Drop braces and collapse whitespace.
+fn clean_bibtex_value(value: &str) -> String {
+    value
+        .replace(['{', '}'], "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// "John Smith" → "Smith, John"; "Smith, John" stays as is.
+fn normalize_author(name: &str) -> String {
+    if name.contains(',') {
+        return name.to_string();
+    }
+    match name.rsplit_once(' ') {
+        Some((first, last)) => format!("{}, {}", last, first),
+        None => name.to_string(),
+    }
+}
+
+// ── Zotero ──────────────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct ZoteroItem {
+    data: ZoteroData,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct ZoteroData {
+    key: String,
+    item_type: String,
+    title: String,
+    creators: Vec<ZoteroCreator>,
+    date: String,
+    publication_title: String,
+    book_title: String,
+    proceedings_title: String,
+    volume: String,
+    pages: String,
+    publisher: String,
+    #[serde(rename = "DOI")]
+    doi: String,
+    url: String,
+    extra: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct ZoteroCreator {
+    first_name: String,
+    last_name: String,
+    name: String,
+}
+
+/// Convert a Zotero API `items` response into references. The citekey comes
+/// from a Better BibTeX `Citation Key:` line in Extra when present, otherwise
+/// it is derived as `<lastname><year><firstword>`.
+fn parse_zotero_items(body: &str) -> Result<Vec<Reference>> {
+    let items: Vec<ZoteroItem> =
+        serde_json::from_str(body).context("Unexpected Zotero API response")?;
+    Ok(items
+        .into_iter()
+        .map(|item| item.data)
+        .filter(|d| !matches!(d.item_type.as_str(), "attachment" | "note" | "annotation"))
+        .map(|d| {
+            let authors: Vec<String> = d
+                .creators
+                .iter()
+                .map(|c| {
+                    if c.last_name.is_empty() {
+                        c.name.clone()
+                    } else if c.first_name.is_empty() {
+                        c.last_name.clone()
+                    } else {
+                        format!("{}, {}", c.last_name, c.first_name)
+                    }
+                })
+                .filter(|a| !a.is_empty())
+                .collect();
+            let year = regex_year(&d.date);
+            let key = d
+                .extra
+                .lines()
+                .find_map(|l| l.strip_prefix("Citation Key:"))
+                .map(|k| k.trim().to_string())
+                .unwrap_or_else(|| derived_key(&authors, year.as_deref(), &d.title, &d.key));
+            let non_empty = |s: String| (!s.is_empty()).then_some(s);
+            Reference {
+                key,
+                kind: d.item_type,
+                title: d.title,
+                authors,
+                year,
+                container: non_empty(d.publication_title)
+                    .or_else(|| non_empty(d.book_title))
+                    .or_else(|| non_empty(d.proceedings_title)),
+                volume: non_empty(d.volume),
+                pages: non_empty(d.pages),
+                publisher: non_empty(d.publisher),
+                doi: non_empty(d.doi),
+                url: non_empty(d.url),
+            }
+        })
+        .collect())
+}
+
+fn regex_year(date: &str) -> Option<String> {
+    static YEAR_RE: LazyLock<regex::Regex> =
+        LazyLock::new(|| regex::Regex::new(r"\b(1[5-9]\d\d|20\d\d)\b").unwrap());
+    YEAR_RE.captures(date).map(|c| c[1].to_string())
+}
+
+fn derived_key(authors: &[String], year: Option<&str>, title: &str, fallback: &str) -> String {
+    let last = authors
+        .first()
+        .map(|a| a.split(',').next().unwrap_or(a))
+        .unwrap_or_default();
+    let word = title
+        .split_whitespace()
+        .find(|w| w.len() > 3)
+        .unwrap_or_default();
+    let key: String = format!("{}{}{}", last, year.unwrap_or_default(), word)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    if key.is_empty() {
+        fallback.to_string()
+    } else {
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BIB: &str = r#"
+@string{acm = "ACM"}
+@article{vaswani2017attention,
+  title = {Attention Is All You {Need}},
+  author = {Vaswani, Ashish and Shazeer, Noam and Parmar, Niki},
+  journal = "Advances in Neural Information Processing Systems",
+  volume = 30,
+  pages = {5998--6008},
+  year = {2017},
+  doi = {10.48550/arXiv.1706.03762}
+}
+@book{knuth1984,
+  author = {Donald E. Knuth},
+  title = {The {\TeX}book},
+  publisher = {Addison-Wesley},
+  year = 1984,
+}
+"#;
+
+    #[test]
+    fn parses_bibtex_entries_and_fields() {
+        let entries = parse_bibtex(BIB);
+        assert_eq!(entries.len(), 2);
+        let attention = &entries[0];
+        assert_eq!(attention.key, "vaswani2017attention");
+        assert_eq!(attention.title, "Attention Is All You Need");
+        assert_eq!(attention.authors.len(), 3);
+        assert_eq!(attention.volume.as_deref(), Some("30"));
+        assert_eq!(attention.pages.as_deref(), Some("5998–6008"));
+        assert_eq!(entries[1].authors, vec!["Knuth, Donald E."]);
+        assert_eq!(entries[1].year.as_deref(), Some("1984"));
+    }
+
+    #[test]
+    fn formats_styles() {
+        let entries = parse_bibtex(BIB);
+        let attention = &entries[0];
+        assert_eq!(attention.in_text(), "Vaswani et al., 2017");
+        let apa = attention.format(CitationStyle::Apa);
+        assert!(apa.starts_with("Vaswani, Ashish, Shazeer, Noam & Parmar, Niki (2017). Attention"));
+        assert!(apa.ends_with("https://doi.org/10.48550/arXiv.1706.03762"));
+        let ieee = entries[1].format(CitationStyle::Ieee);
+        assert!(ieee.starts_with("D. E. Knuth, \"The \\TeXbook,\""));
+    }
+
+    #[test]
+    fn search_matches_all_terms() {
+        let entries = parse_bibtex(BIB);
+        assert_eq!(search_entries(&entries, "attention 2017", 10).len(), 1);
+        assert_eq!(search_entries(&entries, "knuth", 10)[0].key, "knuth1984");
+        assert!(search_entries(&entries, "attention 1984", 10).is_empty());
+    }
+
+    #[test]
+    fn resolves_known_citekeys_only() {
+        remember(&parse_bibtex(BIB));
+        let text = "Transformers [@vaswani2017attention] changed NLP; see @knuth1984. Mail me@example.com or @unknownkey.";
+        let resolved = resolve_citekeys(text);
+        assert!(resolved.contains(
+            "Transformers [(Vaswani et al., 2017)](https://doi.org/10.48550/arXiv.1706.03762) changed"
+        ));
+        assert!(resolved.contains("see (Knuth, 1984)."));
+        assert!(resolved.contains("me@example.com"));
+        assert!(resolved.contains("@unknownkey"));
+
+        let cited: Vec<String> = cited_references(text).into_iter().map(|r| r.key).collect();
+        assert_eq!(cited, vec!["vaswani2017attention", "knuth1984"]);
+    }
+
+    #[test]
+    fn parses_zotero_items_with_citation_keys() {
+        let body = r#"[
+            {"key": "ABCD1234", "data": {"key": "ABCD1234", "itemType": "journalArticle",
+              "title": "Deep Residual Learning", "date": "June 2016",
+              "creators": [{"creatorType": "author", "firstName": "Kaiming", "lastName": "He"}],
+              "publicationTitle": "CVPR", "DOI": "10.1109/CVPR.2016.90", "extra": ""}},
+            {"key": "EFGH5678", "data": {"key": "EFGH5678", "itemType": "book", "title": "SICP",
+              "date": "1985", "creators": [{"name": "MIT"}], "extra": "Citation Key: sicp"}},
+            {"key": "NOTE0001", "data": {"key": "NOTE0001", "itemType": "note"}}
+        ]"#;
+        let refs = parse_zotero_items(body).unwrap();
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].key, "he2016deep");
+        assert_eq!(refs[0].authors, vec!["He, Kaiming"]);
+        assert_eq!(refs[0].container.as_deref(), Some("CVPR"));
+        assert_eq!(refs[1].key, "sicp");
+    }
+}
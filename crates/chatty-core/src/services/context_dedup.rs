@@ -0,0 +1,257 @@
+//! Semantic deduplication and re-ranking of retrieved context.
+//!
+//! Memory search merges keyword (BM25) and vector hits, which frequently
+//! returns several overlapping chunks that say the same thing. Injecting all of
+//! them wastes context tokens, so before hits reach the model this module:
+//!
+//! 1. Drops near-duplicates whose similarity to an already selected chunk is
+//!    above [`ContextDedupSettings::duplicate_threshold`].
+//! 2. Re-ranks the remainder with Maximal Marginal Relevance (MMR), trading
+//!    off relevance to the query against redundancy with earlier picks.
+//!
+//! Similarity uses cosine distance between embeddings when they are available
+//! and falls back to word-set Jaccard overlap otherwise, so the pipeline still
+//! works for keyword-only (no embedding provider) setups.
+
+use std::collections::HashSet;
+
+use crate::services::memory_service::MemoryHit;
+
+/// Tunables for [`dedup_and_rerank`].
+#[derive(Debug, Clone, Copy)]
+pub struct ContextDedupSettings {
+    /// MMR trade-off: `1.0` ranks purely by relevance, `0.0` purely by novelty.
+    pub lambda: f32,
+    /// Chunks at or above this similarity to a selected chunk are dropped.
+    pub duplicate_threshold: f32,
+}
+
+impl Default for ContextDedupSettings {
+    fn default() -> Self {
+        Self {
+            lambda: 0.7,
+            duplicate_threshold: 0.9,
+        }
+    }
+}
+
+/// Result of deduplicating a set of retrieved chunks.
+#[derive(Debug, Clone)]
+pub struct DedupOutcome {
+    /// Selected hits in MMR order. `score` keeps the original relevance score.
+    pub selected: Vec<MemoryHit>,
+    /// Number of candidates dropped as near-duplicates.
+    pub duplicates_removed: usize,
+}
+
+/// Cosine similarity between two vectors. Returns `0.0` for mismatched or zero vectors.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let mut dot = 0.0f32;
+    let mut norm_a = 0.0f32;
+    let mut norm_b = 0.0f32;
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Jaccard overlap of the lowercase alphanumeric word sets of two texts.
+pub fn lexical_similarity(a: &str, b: &str) -> f32 {
+    let words_a = word_set(a);
+    let words_b = word_set(b);
+    if words_a.is_empty() && words_b.is_empty() {
+        return 1.0;
+    }
+    let intersection = words_a.intersection(&words_b).count();
+    let union = words_a.union(&words_b).count();
+    intersection as f32 / union as f32
+}
+
+fn word_set(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// Deduplicate and MMR re-rank retrieved hits, returning at most `limit` of them.
+///
+/// `embeddings`, when provided, must be parallel to `hits`; `query_embedding`
+/// is used to compute relevance. Without embeddings, relevance is the hit's
+/// own score normalised to `[0, 1]` and similarity is lexical.
+pub fn dedup_and_rerank(
+    hits: Vec<MemoryHit>,
+    embeddings: Option<&[Vec<f32>]>,
+    query_embedding: Option<&[f32]>,
+    limit: usize,
+    settings: &ContextDedupSettings,
+) -> DedupOutcome {
+    let embeddings = embeddings.filter(|e| e.len() == hits.len());
+
+    let max_score = hits.iter().map(|h| h.score).fold(0.0f32, f32::max);
+    let relevance: Vec<f32> = hits
+        .iter()
+        .enumerate()
+        .map(|(i, hit)| match (embeddings, query_embedding) {
+            (Some(embs), Some(query)) => cosine_similarity(&embs[i], query),
+            _ if max_score > 0.0 => hit.score / max_score,
+            _ => 0.0,
+        })
+        .collect();
+
+    let similarity = |i: usize, j: usize| -> f32 {
+        match embeddings {
+            Some(embs) => cosine_similarity(&embs[i], &embs[j]),
+            None => lexical_similarity(&hits[i].text, &hits[j].text),
+        }
+    };
+
+    let mut remaining: Vec<usize> = (0..hits.len()).collect();
+    let mut selected: Vec<usize> = Vec::with_capacity(limit.min(hits.len()));
+    let mut duplicates_removed = 0;
+
+    while selected.len() < limit && !remaining.is_empty() {
+        // Drop candidates that are near-duplicates of anything already picked.
+        remaining.retain(|&i| {
+            let is_duplicate = selected
+                .iter()
+                .any(|&j| similarity(i, j) >= settings.duplicate_threshold);
+            if is_duplicate {
+                duplicates_removed += 1;
+            }
+            !is_duplicate
+        });
+
+        let best = remaining
+            .iter()
+            .enumerate()
+            .map(|(pos, &i)| {
+                let redundancy = selected
+                    .iter()
+                    .map(|&j| similarity(i, j))
+                    .fold(0.0f32, f32::max);
+                let mmr = settings.lambda * relevance[i] - (1.0 - settings.lambda) * redundancy;
+                (pos, mmr)
+            })
+            // On equal scores keep the earlier (higher-ranked) candidate;
+            // `max_by` alone would return the last of them.
+            .max_by(|a, b| {
+                a.1.partial_cmp(&b.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| b.0.cmp(&a.0))
+            });
+
+        let Some((pos, _)) = best else {
+            break;
+        };
+        selected.push(remaining.remove(pos));
+    }
+
+    let mut slots: Vec<Option<MemoryHit>> = hits.into_iter().map(Some).collect();
+    let selected = selected
+        .into_iter()
+        .filter_map(|i| slots[i].take())
+        .collect();
+
+    DedupOutcome {
+        selected,
+        duplicates_removed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::memory_service::MemoryHitSource;
+
+    fn hit(text: &str, score: f32) -> MemoryHit {
+        MemoryHit {
+            text: text.to_string(),
+            title: None,
+            score,
+            source: Some(MemoryHitSource::Memory),
+            frame_id: None,
+        }
+    }
+
+    #[test]
+    fn cosine_similarity_handles_identical_and_orthogonal() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[1.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn lexical_dedup_drops_overlapping_chunks() {
+        let hits = vec![
+            hit("The project uses PostgreSQL for the database", 0.9),
+            hit("the project uses postgresql for the database!", 0.8),
+            hit("Deployments run on Fly.io every Friday", 0.5),
+        ];
+
+        let outcome = dedup_and_rerank(hits, None, None, 3, &ContextDedupSettings::default());
+
+        assert_eq!(outcome.duplicates_removed, 1);
+        assert_eq!(outcome.selected.len(), 2);
+        assert!(outcome.selected[0].text.contains("PostgreSQL"));
+        assert!(outcome.selected[1].text.contains("Fly.io"));
+    }
+
+    #[test]
+    fn mmr_prefers_novel_chunk_over_redundant_one() {
+        let hits = vec![hit("a", 1.0), hit("b", 0.95), hit("c", 0.8)];
+        // b is close to a (but below the duplicate threshold); c is orthogonal.
+        let embeddings = vec![vec![1.0, 0.0], vec![0.8, 0.6], vec![0.0, 1.0]];
+        let query = [1.0, 0.1];
+
+        let outcome = dedup_and_rerank(
+            hits,
+            Some(&embeddings),
+            Some(&query),
+            2,
+            &ContextDedupSettings {
+                lambda: 0.5,
+                duplicate_threshold: 0.95,
+            },
+        );
+
+        let texts: Vec<_> = outcome.selected.iter().map(|h| h.text.as_str()).collect();
+        assert_eq!(texts, vec!["a", "c"]);
+        assert_eq!(outcome.duplicates_removed, 0);
+    }
+
+    #[test]
+    fn equal_scores_keep_the_original_order() {
+        let hits = vec![hit("alpha", 0.5), hit("beta", 0.5), hit("gamma", 0.5)];
+
+        let outcome = dedup_and_rerank(hits, None, None, 3, &ContextDedupSettings::default());
+
+        let texts: Vec<_> = outcome.selected.iter().map(|h| h.text.as_str()).collect();
+        assert_eq!(texts, vec!["alpha", "beta", "gamma"]);
+    }
+
+    #[test]
+    fn mismatched_embeddings_fall_back_to_lexical() {
+        let hits = vec![hit("alpha beta", 1.0), hit("alpha beta", 0.5)];
+        let embeddings = vec![vec![1.0, 0.0]];
+
+        let outcome = dedup_and_rerank(
+            hits,
+            Some(&embeddings),
+            None,
+            5,
+            &ContextDedupSettings::default(),
+        );
+
+        assert_eq!(outcome.selected.len(), 1);
+        assert_eq!(outcome.duplicates_removed, 1);
+    }
+}
@@ -0,0 +1,465 @@
+use anyhow::{Context, Result, anyhow};
+use bollard::Docker;
+use bollard::container::LogOutput;
+use bollard::exec::{CreateExecOptions, StartExecResults};
+use bollard::query_parameters::{ListContainersOptions, ListImagesOptions, LogsOptions};
+use futures::StreamExt;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tracing::{debug, info, warn};
+
+use crate::sandbox::docker::connect_docker;
+use crate::settings::models::execution_settings::DockerCleanup;
+
+/// Upper bound for `logs` tail lines.
+pub const MAX_LOG_LINES: u32 = 2000;
+
+/// Compose stacks started by the docker tool in one conversation.
+///
+/// Owned by the conversation so it survives agent rebuilds; consulted when the
+/// conversation is deleted (see [`cleanup_docker_resources`]). Kept in memory
+/// only, so stacks started before a restart are left alone.
+#[derive(Debug, Default)]
+pub struct TrackedDockerResources {
+    /// Workspace directories `docker compose up` was run in.
+    pub compose_dirs: Vec<PathBuf>,
+}
+
+/// Shared handle to a conversation's tracked Docker resources.
+pub type DockerResources = Arc<parking_lot::Mutex<TrackedDockerResources>>;
+
+/// One row of `list_containers`.
+#[derive(Debug, Serialize)]
+pub struct ContainerInfo {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    pub state: String,
+    pub status: String,
+}
+
+/// One row of `list_images`.
+#[derive(Debug, Serialize)]
+pub struct ImageInfo {
+    pub id: String,
+    pub tags: Vec<String>,
+    pub size_mb: f64,
+}
+
+/// Captured output of logs, exec or compose.
+#[derive(Debug, Serialize)]
+pub struct DockerCommandOutput {
+    pub output: String,
+    pub truncated: bool,
+    /// Exit code for exec and compose (None for logs)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i64>,
+}
+
+/// Accumulates streamed output up to a byte limit.
+struct OutputBuffer {
+    text: String,
+    max_bytes: usize,
+    truncated: bool,
+}
+
+impl OutputBuffer {
+    fn new(max_bytes: usize) -> Self {
+        Self {
+            text: String::new(),
+            max_bytes,
+            truncated: false,
+        }
+    }
+
+    fn push(&mut self, chunk: &str) {
+        let remaining = self.max_bytes.saturating_sub(self.text.len());
+        if chunk.len() <= remaining {
+            self.text.push_str(chunk);
+            return;
+        }
+        let mut end = remaining;
+        while !chunk.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.text.push_str(&chunk[..end]);
+        self.truncated = true;
+    }
+
+    fn finish(self, exit_code: Option<i64>) -> DockerCommandOutput {
+        DockerCommandOutput {
+            output: self.text,
+            truncated: self.truncated,
+            exit_code,
+        }
+    }
+}
+
+/// Docker daemon access for the docker tool.
+///
+/// Containers, images, logs and exec go through the Docker API (bollard);
+/// Compose has no API, so `compose_up`/`compose_down` run the `docker compose`
+/// CLI in the workspace directory against the same daemon.
+pub struct DockerService {
+    docker: Docker,
+    docker_host: Option<String>,
+    workspace_dir: Option<PathBuf>,
+    timeout: Duration,
+    max_output_bytes: usize,
+}
+
+impl std::fmt::Debug for DockerService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DockerService")
+            .field("docker_host", &self.docker_host)
+            .field("workspace_dir", &self.workspace_dir)
+            .finish()
+    }
+}
+
+impl DockerService {
+    /// Connect to the Docker daemon (see `connect_docker` for the lookup order).
+    pub async fn new(
+        docker_host: Option<String>,
+        workspace_dir: Option<PathBuf>,
+        timeout: Duration,
+        max_output_bytes: usize,
+    ) -> Result<Self> {
+        let docker = connect_docker(docker_host.as_deref()).await?;
+        info!(docker_host = ?docker_host, "Docker service initialized");
+        Ok(Self {
+            docker,
+            docker_host,
+            workspace_dir,
+            timeout,
+            max_output_bytes,
+        })
+    }
+
+    pub fn workspace_dir(&self) -> Option<&Path> {
+        self.workspace_dir.as_deref()
+    }
+
+    /// List containers (running only unless `all`).
+    pub async fn list_containers(&self, all: bool) -> Result<Vec<ContainerInfo>> {
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions {
+                all,
+                ..Default::default()
+            }))
+            .await
+            .context("Failed to list containers")?;
+
+        Ok(containers
+            .into_iter()
+            .map(|c| ContainerInfo {
+                id: c.id.unwrap_or_default().chars().take(12).collect(),
+                name: c
+                    .names
+                    .and_then(|names| names.into_iter().next())
+                    .map(|n| n.trim_start_matches('/').to_string())
+                    .unwrap_or_default(),
+                image: c.image.unwrap_or_default(),
+                state: c.state.map(|s| s.to_string()).unwrap_or_default(),
+                status: c.status.unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// List local images.
+    pub async fn list_images(&self) -> Result<Vec<ImageInfo>> {
+        let images = self
+            .docker
+            .list_images(Some(ListImagesOptions::default()))
+            .await
+            .context("Failed to list images")?;
+
+        Ok(images
+            .into_iter()
+            .map(|image| ImageInfo {
+                id: image
+                    .id
+                    .trim_start_matches("sha256:")
+                    .chars()
+                    .take(12)
+                    .collect(),
+                tags: image.repo_tags,
+                size_mb: (image.size as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
+            })
+            .collect())
+    }
+
+    /// Fetch the last `tail` log lines of a container, passing each chunk to
+    /// `on_chunk` as it arrives.
+    pub async fn logs(
+        &self,
+        container: &str,
+        tail: u32,
+        mut on_chunk: impl FnMut(&str),
+    ) -> Result<DockerCommandOutput> {
+        validate_identifier(container, "container")?;
+        let mut buffer = OutputBuffer::new(self.max_output_bytes);
+        let mut stream = self.docker.logs(
+            container,
+            Some(LogsOptions {
+                stdout: true,
+                stderr: true,
+                tail: tail.clamp(1, MAX_LOG_LINES).to_string(),
+                ..Default::default()
+            }),
+        );
+
+        let collect = async {
+            while let Some(chunk) = stream.next().await {
+                let text = log_text(chunk.context("Failed to read container logs")?);
+                on_chunk(&text);
+                buffer.push(&text);
+            }
+            Ok::<(), anyhow::Error>(())
+        };
+        tokio::time::timeout(self.timeout, collect)
+            .await
+            .map_err(|_| anyhow!("Reading logs timed out after {:?}", self.timeout))??;
+
+        Ok(buffer.finish(None))
+    }
+
+    /// Run a command inside a running container.
+    pub async fn exec(
+        &self,
+        container: &str,
+        command: Vec<String>,
+        mut on_chunk: impl FnMut(&str),
+    ) -> Result<DockerCommandOutput> {
+        validate_identifier(container, "container")?;
+        if command.is_empty() {
+            return Err(anyhow!("exec requires a command"));
+        }
+
+        let exec = self
+            .docker
+            .create_exec(
+                container,
+                CreateExecOptions {
+                    cmd: Some(command),
+                    attach_stdout: Some(true),
+                    attach_stderr: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await
+            .with_context(|| format!("Failed to create exec in container '{}'", container))?;
+
+        let mut buffer = OutputBuffer::new(self.max_output_bytes);
+        let collect = async {
+            if let StartExecResults::Attached { mut output, .. } =
+                self.docker.start_exec(&exec.id, None).await?
+            {
+                while let Some(chunk) = output.next().await {
+                    let text = log_text(chunk?);
+                    on_chunk(&text);
+                    buffer.push(&text);
+                }
+            }
+            Ok::<(), anyhow::Error>(())
+        };
+        tokio::time::timeout(self.timeout, collect)
+            .await
+            .map_err(|_| anyhow!("exec timed out after {:?}", self.timeout))??;
+
+        let exit_code = self.docker.inspect_exec(&exec.id).await?.exit_code;
+        Ok(buffer.finish(exit_code))
+    }
+
+    /// Run `docker compose <args>` in the workspace directory, streaming
+    /// stdout and stderr lines to `on_chunk`.
+    pub async fn compose(
+        &self,
+        args: Vec<String>,
+        mut on_chunk: impl FnMut(&str),
+    ) -> Result<DockerCommandOutput> {
+        let dir = self
+            .workspace_dir
+            .as_deref()
+            .ok_or_else(|| anyhow!("docker compose requires a workspace directory"))?;
+        debug!(args = ?args, dir = %dir.display(), "Running docker compose");
+
+        let mut child = compose_command(dir, self.docker_host.as_deref(), &args)
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .context("Failed to run docker compose (is the Docker CLI installed?)")?;
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+        if let Some(stdout) = child.stdout.take() {
+            forward_lines(stdout, tx.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            forward_lines(stderr, tx.clone());
+        }
+        drop(tx);
+
+        let mut buffer = OutputBuffer::new(self.max_output_bytes);
+        let collect = async {
+            while let Some(line) = rx.recv().await {
+                let line = format!("{line}\n");
+                on_chunk(&line);
+                buffer.push(&line);
+            }
+            child.wait().await
+        };
+        let status = tokio::time::timeout(self.timeout, collect)
+            .await
+            .map_err(|_| anyhow!("docker compose timed out after {:?}", self.timeout))?
+            .context("docker compose failed")?;
+
+        Ok(buffer.finish(status.code().map(i64::from)))
+    }
+}
+
+/// Forward lines from a child process pipe into `tx` until EOF.
+fn forward_lines<R>(reader: R, tx: tokio::sync::mpsc::UnboundedSender<String>)
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Build a `docker compose` command bound to the configured daemon.
+fn compose_command(
+    dir: &Path,
+    docker_host: Option<&str>,
+    args: &[String],
+) -> tokio::process::Command {
+    let mut command = tokio::process::Command::new("docker");
+    command
+        .arg("compose")
+        .args(args)
+        .current_dir(dir)
+        .kill_on_drop(true);
+    if let Some(host) = docker_host {
+        command.env("DOCKER_HOST", docker_host_uri(host));
+    }
+    command
+}
+
+/// The Docker CLI expects a URI in `DOCKER_HOST`; settings may hold a bare socket path.
+fn docker_host_uri(host: &str) -> String {
+    if host.contains("://") {
+        host.to_string()
+    } else {
+        format!("unix://{host}")
+    }
+}
+
+fn log_text(output: LogOutput) -> String {
+    match output {
+        LogOutput::StdOut { message }
+        | LogOutput::StdErr { message }
+        | LogOutput::Console { message } => String::from_utf8_lossy(&message).into_owned(),
+        LogOutput::StdIn { .. } => String::new(),
+    }
+}
+
+/// Validate a container or compose service name/ID so it cannot be read as a flag.
+pub fn validate_identifier(value: &str, what: &str) -> Result<()> {
+    if value.is_empty()
+        || value.starts_with(['-', '.'])
+        || !value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+    {
+        return Err(anyhow!("Invalid {} name '{}'", what, value));
+    }
+    Ok(())
+}
+
+/// Apply the configured cleanup to compose stacks a deleted conversation started.
+pub async fn cleanup_docker_resources(
+    resources: &DockerResources,
+    policy: &DockerCleanup,
+    docker_host: Option<&str>,
+) {
+    let verb = match policy {
+        DockerCleanup::Keep => return,
+        DockerCleanup::Stop => "stop",
+        DockerCleanup::Remove => "down",
+    };
+    let dirs = std::mem::take(&mut resources.lock().compose_dirs);
+    for dir in dirs {
+        info!(dir = %dir.display(), verb, "Cleaning up compose stack for deleted conversation");
+        match compose_command(&dir, docker_host, &[verb.to_string()])
+            .output()
+            .await
+        {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => warn!(
+                dir = %dir.display(),
+                stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+                "docker compose cleanup failed"
+            ),
+            Err(e) => {
+                warn!(error = ?e, dir = %dir.display(), "Failed to run docker compose cleanup")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_buffer_truncates_on_char_boundary() {
+        let mut buffer = OutputBuffer::new(4);
+        buffer.push("ab");
+        buffer.push("cé!");
+        let output = buffer.finish(Some(0));
+        assert_eq!(output.output, "abc");
+        assert!(output.truncated);
+        assert_eq!(output.exit_code, Some(0));
+    }
+
+    #[test]
+    fn identifiers_reject_flags_and_separators() {
+        assert!(validate_identifier("web-1", "container").is_ok());
+        assert!(validate_identifier("3f2a9c1b", "container").is_ok());
+        assert!(validate_identifier("--privileged", "container").is_err());
+        assert!(validate_identifier("web;rm", "container").is_err());
+        assert!(validate_identifier("", "container").is_err());
+    }
+
+    #[test]
+    fn docker_host_uri_adds_unix_scheme_for_paths() {
+        assert_eq!(
+            docker_host_uri("/run/user/1000/docker.sock"),
+            "unix:///run/user/1000/docker.sock"
+        );
+        assert_eq!(
+            docker_host_uri("tcp://127.0.0.1:2375"),
+            "tcp://127.0.0.1:2375"
+        );
+    }
+
+    #[tokio::test]
+    async fn keep_policy_leaves_tracked_stacks_alone() {
+        let resources = DockerResources::default();
+        resources
+            .lock()
+            .compose_dirs
+            .push(PathBuf::from("/tmp/app"));
+        cleanup_docker_resources(&resources, &DockerCleanup::Keep, None).await;
+        assert_eq!(resources.lock().compose_dirs.len(), 1);
+    }
+}
@@ -1,9 +1,16 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use anyhow::{Result, anyhow};
 use rig_core::client::EmbeddingsClient;
 use rig_core::embeddings::EmbeddingModel;
 use tracing::{info, warn};
 
-use crate::settings::models::providers_store::ProviderType;
+use crate::settings::models::embedding_models_store::EmbeddingModelConfig;
+use crate::settings::models::providers_store::{AzureAuthMethod, ProviderConfig, ProviderType};
+
+/// Texts per request when no batch size has been configured.
+const DEFAULT_BATCH_SIZE: usize = 32;
 
 /// Service for computing text embeddings using rig-core providers.
 ///
@@ -17,6 +24,11 @@ pub struct EmbeddingService {
     inner: EmbeddingServiceInner,
     provider_type: ProviderType,
     model_name: String,
+    batch_size: usize,
+    /// Minimum spacing between provider requests (derived from requests/minute).
+    min_request_interval: Option<Duration>,
+    /// Time of the most recent provider request, shared across clones.
+    last_request: Arc<tokio::sync::Mutex<Option<Instant>>>,
 }
 
 /// Concrete embedding model, one variant per supported provider.
@@ -99,11 +111,53 @@ impl EmbeddingService {
             inner,
             provider_type: provider_type.clone(),
             model_name: model_name.to_string(),
+            batch_size: DEFAULT_BATCH_SIZE,
+            min_request_interval: None,
+            last_request: Arc::new(tokio::sync::Mutex::new(None)),
         })
     }
 
+    /// Create an EmbeddingService from a registered embedding model, applying
+    /// its batch/rate controls.
+    pub fn from_config(
+        config: &EmbeddingModelConfig,
+        api_key: Option<&str>,
+        base_url: Option<&str>,
+        azure_token: Option<String>,
+    ) -> Result<Self> {
+        let mut service = Self::new(
+            &config.provider_type,
+            &config.model_identifier,
+            api_key,
+            base_url,
+            azure_token,
+        )?;
+        service.batch_size = config.batch_size.max(1);
+        service.min_request_interval = config
+            .requests_per_minute
+            .filter(|&rpm| rpm > 0)
+            .map(|rpm| Duration::from_secs_f64(60.0 / rpm as f64));
+        Ok(service)
+    }
+
+    /// Wait until the configured requests-per-minute budget allows another request.
+    async fn throttle(&self) {
+        let Some(interval) = self.min_request_interval else {
+            return;
+        };
+        let mut last = self.last_request.lock().await;
+        if let Some(prev) = *last {
+            let elapsed = prev.elapsed();
+            if elapsed < interval {
+                tokio::time::sleep(interval - elapsed).await;
+            }
+        }
+        *last = Some(Instant::now());
+    }
+
     /// Compute a single text embedding, returning `Vec<f32>` for memvid-core.
     pub async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.throttle().await;
         let embedding = match &self.inner {
             EmbeddingServiceInner::OpenRouter(m) => m
                 .embed_text(text)
@@ -123,6 +177,41 @@ impl EmbeddingService {
         Ok(embedding.vec.iter().map(|&v| v as f32).collect())
     }
 
+    /// Compute embeddings for several texts, `batch_size` texts per provider request.
+    ///
+    /// Requests are spaced according to the configured rate limit. Results are
+    /// returned in the same order as `texts`.
+    pub async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut results = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(self.batch_size) {
+            self.throttle().await;
+            results.extend(self.embed_chunk(chunk.to_vec()).await?);
+        }
+        Ok(results)
+    }
+
+    async fn embed_chunk(&self, owned: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let embeddings = match &self.inner {
+            EmbeddingServiceInner::OpenRouter(m) => m
+                .embed_texts(owned)
+                .await
+                .map_err(|e| anyhow!("OpenRouter batch embedding failed: {e}"))?,
+            EmbeddingServiceInner::Ollama(m) => m
+                .embed_texts(owned)
+                .await
+                .map_err(|e| anyhow!("Ollama batch embedding failed: {e}"))?,
+            EmbeddingServiceInner::AzureOpenAI(m) => m
+                .embed_texts(owned)
+                .await
+                .map_err(|e| anyhow!("Azure OpenAI batch embedding failed: {e}"))?,
+        };
+
+        Ok(embeddings
+            .into_iter()
+            .map(|e| e.vec.iter().map(|&v| v as f32).collect())
+            .collect())
+    }
+
     /// Returns a stable identifier for memvid-core's `set_vec_model()`.
     ///
     /// Format: "provider:model" (e.g. "OpenRouter:openai/text-embedding-3-small").
@@ -147,6 +236,58 @@ impl EmbeddingService {
     }
 }
 
+/// Try to create an EmbeddingService from a registered embedding model,
+/// logging warnings on failure.
+pub fn try_create_embedding_service_from_config(
+    config: &EmbeddingModelConfig,
+    api_key: Option<&str>,
+    base_url: Option<&str>,
+    azure_token: Option<String>,
+) -> Option<EmbeddingService> {
+    match EmbeddingService::from_config(config, api_key, base_url, azure_token) {
+        Ok(service) => Some(service),
+        Err(e) => {
+            warn!(
+                error = ?e,
+                provider = config.provider_type.display_name(),
+                model = %config.model_identifier,
+                "Failed to create EmbeddingService, falling back to BM25-only search"
+            );
+            None
+        }
+    }
+}
+
+/// Create an EmbeddingService for a registered embedding model, taking the
+/// credentials from the matching provider (fetching an Entra ID token for
+/// Azure OpenAI when configured). Logs warnings and returns `None` on failure.
+pub async fn create_embedding_service_with_providers(
+    config: &EmbeddingModelConfig,
+    providers: &[ProviderConfig],
+) -> Option<EmbeddingService> {
+    let provider = providers
+        .iter()
+        .find(|p| p.provider_type == config.provider_type);
+    let base_url = provider.and_then(|p| p.base_url.as_deref());
+
+    if config.provider_type == ProviderType::AzureOpenAI
+        && provider.map(|p| p.azure_auth_method()) == Some(AzureAuthMethod::EntraId)
+    {
+        let azure_token = match crate::auth::azure_auth::fetch_entra_id_token().await {
+            Ok(token) => Some(token),
+            Err(e) => {
+                warn!(error = ?e, "Failed to fetch Entra ID token for Azure OpenAI embeddings");
+                None
+            }
+        };
+        // api_key is None for Entra ID; the bearer token handles auth
+        return try_create_embedding_service_from_config(config, None, base_url, azure_token);
+    }
+
+    let api_key = provider.and_then(|p| p.api_key.as_deref());
+    try_create_embedding_service_from_config(config, api_key, base_url, None)
+}
+
 /// Try to create an EmbeddingService, logging warnings on failure.
 /// Returns `None` if the service cannot be initialized.
 ///
@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
 use super::path_validator::PathValidator;
+use super::workspace_trash::{self, TrashEntry};
 
 /// Result of applying a diff
 #[derive(Debug, Serialize)]
@@ -371,8 +372,27 @@ impl FileSystemService {
         Ok(())
     }
 
-    /// Move/rename a file or directory.
-    pub async fn move_file(&self, source: &str, destination: &str) -> Result<()> {
+    /// Move a file into the workspace trash, from where it can be restored
+    /// within the undo window.
+    pub async fn trash_file(&self, path: &str) -> Result<TrashEntry> {
+        let canonical = self.validator.validate(path).await?;
+
+        if !canonical.is_file() {
+            return Err(anyhow!("'{}' is not a file or does not exist", path));
+        }
+
+        debug!(path = %canonical.display(), "Moving file to the trash");
+        workspace_trash::trash_file(
+            self.workspace_root(),
+            &canonical,
+            chrono::Utc::now().timestamp(),
+        )
+        .await
+    }
+
+    /// Move/rename a file or directory. The move is recorded in the
+    /// workspace trash so it can be undone; `None` if recording it failed.
+    pub async fn move_file(&self, source: &str, destination: &str) -> Result<Option<TrashEntry>> {
         let src_canonical = self.validator.validate(source).await?;
         let dst_resolved = self.validator.validate_new_path(destination).await?;
 
@@ -398,7 +418,16 @@ impl FileSystemService {
             to = %dst_resolved.display(),
             "File moved successfully"
         );
-        Ok(())
+        let recorded = workspace_trash::record_move(
+            self.workspace_root(),
+            &src_canonical,
+            &dst_resolved,
+            chrono::Utc::now().timestamp(),
+        )
+        .await;
+        Ok(recorded
+            .map_err(|e| warn!(error = ?e, "Failed to record move for undo"))
+            .ok())
     }
 
     /// Apply a unified diff to a file.
@@ -817,6 +846,24 @@ mod tests {
         assert_eq!(content, "content");
     }
 
+    #[tokio::test]
+    async fn test_trash_and_restore_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join("draft.md"), "keep me").unwrap();
+
+        let service = FileSystemService::new(tmp.path().to_str().unwrap())
+            .await
+            .unwrap();
+        let entry = service.trash_file("draft.md").await.unwrap();
+        assert!(!tmp.path().join("draft.md").exists());
+
+        workspace_trash::restore(&entry.id, entry.trashed_at)
+            .await
+            .unwrap();
+        let content = fs::read_to_string(tmp.path().join("draft.md")).unwrap();
+        assert_eq!(content, "keep me");
+    }
+
     #[tokio::test]
     async fn test_move_file_destination_exists() {
         let tmp = tempfile::tempdir().unwrap();
@@ -0,0 +1,377 @@
+//! Record and replay of provider responses.
+//!
+//! In record mode every streamed turn — text, tool calls with their results,
+//! approvals, token usage and errors — is appended to a JSON fixture file as
+//! it finishes. In replay mode `llm_service::stream_prompt` never contacts the
+//! provider: each turn yields the next recorded turn instead, so a session can
+//! be re-run deterministically to reproduce a bug. The same fixture files feed
+//! regression tests through `testing::FakeAgentClient::from_recording`.
+//!
+//! The mode is chosen once at startup with [`init`], usually from the
+//! `CHATTY_RECORD_FLOW` / `CHATTY_REPLAY_FLOW` environment variables
+//! ([`mode_from_env`]).
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{Context, Result, anyhow};
+use futures::StreamExt;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::llm_service::{ResponseStream, StreamChunk};
+
+/// Current fixture format.
+pub const FLOW_RECORDING_VERSION: u32 = 1;
+
+/// Environment variable naming the file to record into.
+pub const RECORD_ENV_VAR: &str = "CHATTY_RECORD_FLOW";
+/// Environment variable naming the file to replay from.
+pub const REPLAY_ENV_VAR: &str = "CHATTY_REPLAY_FLOW";
+
+static FLOW_RECORDER: OnceLock<FlowRecorder> = OnceLock::new();
+
+/// One streamed turn as the provider delivered it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedTurn {
+    /// Text of the user message that started the turn. Replay only uses it
+    /// to warn when a session diverges from the recording.
+    pub prompt: String,
+    /// Number of history messages sent with the turn.
+    #[serde(default)]
+    pub history_len: usize,
+    pub chunks: Vec<StreamChunk>,
+}
+
+/// A recorded session: its turns in the order they were streamed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlowRecording {
+    pub version: u32,
+    #[serde(default)]
+    pub turns: Vec<RecordedTurn>,
+}
+
+impl Default for FlowRecording {
+    fn default() -> Self {
+        Self {
+            version: FLOW_RECORDING_VERSION,
+            turns: Vec::new(),
+        }
+    }
+}
+
+impl FlowRecording {
+    /// Load a fixture file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, is not a recording, or
+    /// was written by a newer format version.
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read flow recording {}", path.display()))?;
+        let recording: Self = serde_json::from_str(&json)
+            .with_context(|| format!("Invalid flow recording {}", path.display()))?;
+        if recording.version > FLOW_RECORDING_VERSION {
+            return Err(anyhow!(
+                "Flow recording {} has version {}, newest supported is {}",
+                path.display(),
+                recording.version,
+                FLOW_RECORDING_VERSION
+            ));
+        }
+        Ok(recording)
+    }
+
+    /// Write the recording to `path`, replacing the file atomically.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        std::fs::write(&temp_path, json).and_then(|()| std::fs::rename(&temp_path, path))?;
+        Ok(())
+    }
+}
+
+/// What the recorder does with streamed turns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FlowMode {
+    /// Append each turn to the fixture at this path.
+    Record(PathBuf),
+    /// Serve turns from the fixture at this path instead of the provider.
+    Replay(PathBuf),
+}
+
+/// The mode requested through the environment, if any. Replay wins when
+/// both variables are set, so a recording is never overwritten by accident.
+pub fn mode_from_env() -> Option<FlowMode> {
+    let path = |var: &str| {
+        std::env::var_os(var)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    path(REPLAY_ENV_VAR)
+        .map(FlowMode::Replay)
+        .or_else(|| path(RECORD_ENV_VAR).map(FlowMode::Record))
+}
+
+enum Inner {
+    Record {
+        path: PathBuf,
+        recording: Mutex<FlowRecording>,
+    },
+    Replay {
+        turns: Mutex<VecDeque<RecordedTurn>>,
+    },
+}
+
+/// Records streamed turns to a fixture, or replays them from one. Clones
+/// share the same recording.
+#[derive(Clone)]
+pub struct FlowRecorder {
+    inner: Arc<Inner>,
+}
+
+impl FlowRecorder {
+    /// A recorder that writes every finished turn to `path`. The file is
+    /// replaced, not appended to.
+    pub fn recording(path: PathBuf) -> Self {
+        Self {
+            inner: Arc::new(Inner::Record {
+                path,
+                recording: Mutex::new(FlowRecording::default()),
+            }),
+        }
+    }
+
+    /// A recorder that serves the turns of `recording` in order.
+    pub fn replaying(recording: FlowRecording) -> Self {
+        Self {
+            inner: Arc::new(Inner::Replay {
+                turns: Mutex::new(recording.turns.into()),
+            }),
+        }
+    }
+
+    /// Build the recorder for `mode`.
+    ///
+    /// # Errors
+    /// Returns an error if a replay fixture cannot be loaded.
+    pub fn from_mode(mode: FlowMode) -> Result<Self> {
+        match mode {
+            FlowMode::Record(path) => Ok(Self::recording(path)),
+            FlowMode::Replay(path) => FlowRecording::load(&path).map(Self::replaying),
+        }
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        matches!(*self.inner, Inner::Replay { .. })
+    }
+
+    /// Turns recorded so far, or not yet replayed.
+    pub fn turn_count(&self) -> usize {
+        match &*self.inner {
+            Inner::Record { recording, .. } => recording.lock().turns.len(),
+            Inner::Replay { turns } => turns.lock().len(),
+        }
+    }
+
+    /// The next recorded turn as a response stream. `None` in record mode.
+    ///
+    /// # Errors
+    /// Returns an error when every recorded turn has been replayed.
+    pub fn replay(&self, prompt: &str) -> Option<Result<ResponseStream>> {
+        let Inner::Replay { turns } = &*self.inner else {
+            return None;
+        };
+        let Some(turn) = turns.lock().pop_front() else {
+            return Some(Err(anyhow!(
+                "Flow recording has no turns left to replay (prompt: {prompt:?})"
+            )));
+        };
+        if turn.prompt != prompt {
+            warn!(
+                recorded = %turn.prompt,
+                sent = %prompt,
+                "Replayed turn was recorded for a different prompt"
+            );
+        }
+        let stream: ResponseStream =
+            Box::pin(futures::stream::iter(turn.chunks.into_iter().map(Ok)));
+        Some(Ok(stream))
+    }
+
+    /// Pass `stream` through, recording its chunks. The turn is saved when
+    /// the stream ends or is dropped, so cancelled turns are kept as far as
+    /// they got. Replay mode returns `stream` untouched.
+    pub fn record(
+        &self,
+        stream: ResponseStream,
+        prompt: &str,
+        history_len: usize,
+    ) -> ResponseStream {
+        if self.is_replaying() {
+            return stream;
+        }
+        let mut turn = TurnGuard {
+            recorder: self.clone(),
+            turn: Some(RecordedTurn {
+                prompt: prompt.to_string(),
+                history_len,
+                chunks: Vec::new(),
+            }),
+        };
+        let mut stream = stream;
+        Box::pin(async_stream::stream! {
+            while let Some(item) = stream.next().await {
+                turn.push(match &item {
+                    Ok(chunk) => chunk.clone(),
+                    Err(e) => StreamChunk::Error(e.to_string()),
+                });
+                yield item;
+            }
+            drop(turn);
+        })
+    }
+
+    fn commit(&self, turn: RecordedTurn) {
+        let Inner::Record { path, recording } = &*self.inner else {
+            return;
+        };
+        let mut recording = recording.lock();
+        recording.turns.push(turn);
+        if let Err(e) = recording.save(path) {
+            warn!(error = ?e, path = %path.display(), "Failed to save flow recording");
+        }
+    }
+}
+
+/// Collects one turn's chunks and hands them to the recorder when dropped.
+struct TurnGuard {
+    recorder: FlowRecorder,
+    turn: Option<RecordedTurn>,
+}
+
+impl TurnGuard {
+    fn push(&mut self, chunk: StreamChunk) {
+        if let Some(turn) = &mut self.turn {
+            turn.chunks.push(chunk);
+        }
+    }
+}
+
+impl Drop for TurnGuard {
+    fn drop(&mut self) {
+        if let Some(turn) = self.turn.take() {
+            self.recorder.commit(turn);
+        }
+    }
+}
+
+/// Start recording or replaying for the rest of the process. Later calls
+/// keep the first recorder. `None` leaves streaming untouched.
+///
+/// # Errors
+/// Returns an error if a replay fixture cannot be loaded.
+pub fn init(mode: Option<FlowMode>) -> Result<()> {
+    let Some(mode) = mode else {
+        return Ok(());
+    };
+    let recorder = FlowRecorder::from_mode(mode.clone())?;
+    match &mode {
+        FlowMode::Record(path) => info!(path = %path.display(), "Recording conversation flow"),
+        FlowMode::Replay(path) => info!(
+            path = %path.display(),
+            turns = recorder.turn_count(),
+            "Replaying conversation flow"
+        ),
+    }
+    let _ = FLOW_RECORDER.set(recorder);
+    Ok(())
+}
+
+/// The process-wide recorder, once [`init`] has set one.
+pub fn flow_recorder() -> Option<&'static FlowRecorder> {
+    FLOW_RECORDER.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn chunks(stream: ResponseStream) -> Vec<StreamChunk> {
+        stream.map(|item| item.unwrap()).collect().await
+    }
+
+    fn reply(text: &str) -> ResponseStream {
+        Box::pin(futures::stream::iter(vec![
+            Ok(StreamChunk::Text(text.to_string())),
+            Ok(StreamChunk::ToolCallStarted {
+                id: "call-1".to_string(),
+                name: "fetch".to_string(),
+            }),
+            Ok(StreamChunk::ToolCallResult {
+                id: "call-1".to_string(),
+                result: "ok".to_string(),
+            }),
+            Ok(StreamChunk::Done),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn recorded_turns_replay_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flow.json");
+
+        let recorder = FlowRecorder::recording(path.clone());
+        let first = chunks(recorder.record(reply("one"), "first", 0)).await;
+        chunks(recorder.record(reply("two"), "second", 2)).await;
+        assert_eq!(recorder.turn_count(), 2);
+
+        let replay = FlowRecorder::from_mode(FlowMode::Replay(path)).unwrap();
+        assert!(replay.is_replaying());
+        let replayed = chunks(replay.replay("first").unwrap().unwrap()).await;
+        assert_eq!(format!("{replayed:?}"), format!("{first:?}"));
+        assert!(matches!(
+            &chunks(replay.replay("second").unwrap().unwrap()).await[0],
+            StreamChunk::Text(text) if text == "two"
+        ));
+        assert!(replay.replay("third").unwrap().is_err());
+    }
+
+    #[tokio::test]
+    async fn dropped_stream_keeps_partial_turn() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flow.json");
+        let recorder = FlowRecorder::recording(path.clone());
+
+        let mut stream = recorder.record(reply("partial"), "cancel me", 0);
+        stream.next().await;
+        drop(stream);
+
+        let recording = FlowRecording::load(&path).unwrap();
+        assert_eq!(recording.turns.len(), 1);
+        assert_eq!(recording.turns[0].prompt, "cancel me");
+        assert_eq!(recording.turns[0].chunks.len(), 1);
+    }
+
+    #[test]
+    fn record_mode_does_not_replay() {
+        let recorder = FlowRecorder::recording(PathBuf::from("unused.json"));
+        assert!(recorder.replay("hi").is_none());
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flow.json");
+        std::fs::write(&path, r#"{"version": 99, "turns": []}"#).unwrap();
+        assert!(FlowRecording::load(&path).is_err());
+    }
+}
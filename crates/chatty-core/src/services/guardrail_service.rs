@@ -0,0 +1,635 @@
+//! Guardrail policies evaluated against every tool call.
+//!
+//! Users define rules in the execution settings (e.g. "no `rm -rf`", "only
+//! write under `src/`", "no network in confidential conversations"). The agent
+//! factory wraps each tool in a [`GuardedTool`], which evaluates the
+//! rules *before* the tool runs — and therefore before any approval prompt is
+//! shown. A violating call is denied without executing and the tool result
+//! becomes an `Error: Blocked by guardrail …` message, which the stream marks
+//! as a failed tool call so the explanation shows up in the trace.
+//!
+//! MCP tools are wrapped by the provider builder with the same [`ToolGuard`].
+//! Command and path rules only recognise the built-in tools' arguments, so
+//! for MCP tools the "block tool" rule is the one that applies.
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::{ToolDyn, ToolError};
+use rig_core::wasm_compat::WasmBoxedFuture;
+use serde::{Deserialize, Serialize};
+
+/// Shared per-conversation "confidential" flag. Read at call time, so toggling
+/// it takes effect without rebuilding the agent.
+pub type ConfidentialFlag = Arc<AtomicBool>;
+
+/// Tools whose arguments carry a command or script, and the argument holding
+/// it: a string, or a list of words such as docker's `exec` command.
+pub(crate) const COMMAND_ARGS: &[(&str, &str)] = &[
+    ("shell_execute", "command"),
+    ("execute_code", "code"),
+    ("daytona_run", "code"),
+    ("docker", "command"),
+    ("ssh_execute", "command"),
+];
+
+/// Tools that write to the filesystem, and the arguments holding target paths.
+pub(crate) const WRITE_PATH_ARGS: &[(&str, &[&str])] = &[
+    ("write_file", &["path"]),
+    ("apply_diff", &["path"]),
+    ("create_directory", &["path"]),
+    ("delete_file", &["path"]),
+    ("move_file", &["source", "destination"]),
+    ("final_answer", &["output_path"]),
+    ("write_excel", &["path"]),
+    ("edit_excel", &["path", "output_path"]),
+    ("write_docx", &["path"]),
+    ("write_pptx", &["path"]),
+    ("notes_append", &["note"]),
+];
+
+/// Tools in [`WRITE_PATH_ARGS`] whose paths are relative to the notes vault
+/// rather than the workspace.
+const VAULT_PATH_TOOLS: &[&str] = &["notes_append"];
+
+/// Tools that send data to, or fetch data from, the network.
+pub(crate) const NETWORK_TOOLS: &[&str] = &[
+    "fetch",
+    "search_web",
+    "browser_use",
+    "daytona_run",
+    "docker",
+    "ssh_execute",
+    "k8s_get",
+    "k8s_describe",
+    "k8s_logs",
+    "k8s_events",
+    "k8s_action",
+];
+
+/// What a guardrail rule checks.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GuardrailRuleKind {
+    /// Deny shell/code execution whose command contains `pattern`
+    /// (whitespace-insensitive substring match).
+    BlockCommand { pattern: String },
+    /// Deny filesystem writes outside the listed directories. Relative
+    /// directories are resolved against the workspace directory.
+    RestrictWrites { allowed_dirs: Vec<String> },
+    /// Deny network tools while the conversation is marked confidential.
+    BlockNetworkWhenConfidential,
+    /// Deny a tool outright.
+    BlockTool { tool_name: String },
+}
+
+impl GuardrailRuleKind {
+    /// One-line description shown in the settings list.
+    pub fn summary(&self) -> String {
+        match self {
+            Self::BlockCommand { pattern } => format!("Block commands containing `{pattern}`"),
+            Self::RestrictWrites { allowed_dirs } => {
+                format!("Only write inside: {}", allowed_dirs.join(", "))
+            }
+            Self::BlockNetworkWhenConfidential => {
+                "Block network tools in confidential conversations".to_string()
+            }
+            Self::BlockTool { tool_name } => format!("Block the `{tool_name}` tool"),
+        }
+    }
+}
+
+/// A user-defined guardrail rule.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GuardrailRule {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub kind: GuardrailRuleKind,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl GuardrailRule {
+    pub fn new(name: impl Into<String>, kind: GuardrailRuleKind) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.into(),
+            enabled: true,
+            kind,
+        }
+    }
+}
+
+/// Rules seeded into fresh execution settings.
+pub fn default_guardrail_rules() -> Vec<GuardrailRule> {
+    vec![
+        GuardrailRule {
+            id: "default-no-rm-rf".to_string(),
+            name: "No rm -rf".to_string(),
+            enabled: true,
+            kind: GuardrailRuleKind::BlockCommand {
+                pattern: "rm -rf".to_string(),
+            },
+        },
+        GuardrailRule {
+            id: "default-confidential-network".to_string(),
+            name: "No network in confidential conversations".to_string(),
+            enabled: true,
+            kind: GuardrailRuleKind::BlockNetworkWhenConfidential,
+        },
+        GuardrailRule {
+            id: "default-writes-in-src".to_string(),
+            name: "Writes only under src/".to_string(),
+            enabled: false,
+            kind: GuardrailRuleKind::RestrictWrites {
+                allowed_dirs: vec!["src".to_string()],
+            },
+        },
+    ]
+}
+
+/// Per-call facts the rules are evaluated against.
+#[derive(Clone, Debug, Default)]
+pub struct GuardrailContext {
+    pub workspace_dir: Option<PathBuf>,
+    /// Notes vault that `notes_append` paths are relative to.
+    pub notes_dir: Option<PathBuf>,
+    pub confidential: bool,
+}
+
+/// A rule that denied a tool call.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GuardrailViolation {
+    pub rule_name: String,
+    pub reason: String,
+}
+
+impl GuardrailViolation {
+    /// Tool result returned to the model in place of the real output. The
+    /// `Error:` prefix makes the stream record the call as failed.
+    pub fn tool_result(&self, tool_name: &str) -> String {
+        format!(
+            "Error: Blocked by guardrail \"{}\": {}. The {tool_name} call was denied \
+             before execution. Do not retry it; choose an approach that satisfies the rule \
+             or ask the user to change their guardrail settings.",
+            self.rule_name, self.reason
+        )
+    }
+}
+
+/// Evaluates the enabled guardrail rules against tool calls.
+#[derive(Clone, Debug, Default)]
+pub struct GuardrailService {
+    rules: Vec<GuardrailRule>,
+    /// Tools treated as network access in addition to the built-in ones
+    /// (e.g. user-defined REST tools).
+    network_tools: Vec<String>,
+}
+
+impl GuardrailService {
+    /// Keeps only the enabled rules.
+    pub fn new(rules: &[GuardrailRule]) -> Self {
+        Self {
+            rules: rules.iter().filter(|r| r.enabled).cloned().collect(),
+            network_tools: Vec::new(),
+        }
+    }
+
+    /// Treat the named tools as network access.
+    pub fn with_network_tools(mut self, names: impl IntoIterator<Item = String>) -> Self {
+        self.network_tools.extend(names);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Return the first rule the call violates, if any.
+    pub fn evaluate(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Value,
+        ctx: &GuardrailContext,
+    ) -> Option<GuardrailViolation> {
+        let is_network = NETWORK_TOOLS.contains(&tool_name)
+            || self.network_tools.iter().any(|name| name == tool_name);
+        self.rules.iter().find_map(|rule| {
+            check_rule(&rule.kind, tool_name, is_network, args, ctx).map(|reason| {
+                GuardrailViolation {
+                    rule_name: rule.name.clone(),
+                    reason,
+                }
+            })
+        })
+    }
+}
+
+fn check_rule(
+    kind: &GuardrailRuleKind,
+    tool_name: &str,
+    is_network: bool,
+    args: &serde_json::Value,
+    ctx: &GuardrailContext,
+) -> Option<String> {
+    match kind {
+        GuardrailRuleKind::BlockCommand { pattern } => {
+            let pattern = collapse_whitespace(pattern);
+            if pattern.is_empty() {
+                return None;
+            }
+            let (_, arg) = COMMAND_ARGS.iter().find(|(name, _)| *name == tool_name)?;
+            let command = match args.get(*arg)? {
+                serde_json::Value::String(command) => collapse_whitespace(command),
+                serde_json::Value::Array(words) => collapse_whitespace(
+                    &words
+                        .iter()
+                        .filter_map(|word| word.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                _ => return None,
+            };
+            command
+                .contains(&pattern)
+                .then(|| format!("the command contains `{pattern}`"))
+        }
+        GuardrailRuleKind::RestrictWrites { allowed_dirs } => {
+            let (_, path_args) = WRITE_PATH_ARGS
+                .iter()
+                .find(|(name, _)| *name == tool_name)?;
+            let workspace = ctx.workspace_dir.as_deref();
+            let base = if VAULT_PATH_TOOLS.contains(&tool_name) {
+                ctx.notes_dir.as_deref()
+            } else {
+                workspace
+            };
+            let allowed: Vec<PathBuf> = allowed_dirs
+                .iter()
+                .filter(|d| !d.trim().is_empty())
+                .map(|d| resolve(Path::new(d.trim()), workspace))
+                .collect();
+            path_args
+                .iter()
+                .filter_map(|arg| args.get(*arg)?.as_str())
+                .find(|path| {
+                    let target = resolve(Path::new(path), base);
+                    !allowed.iter().any(|dir| target.starts_with(dir))
+                })
+                .map(|path| {
+                    format!(
+                        "`{path}` is outside the allowed directories ({})",
+                        allowed_dirs.join(", ")
+                    )
+                })
+        }
+        GuardrailRuleKind::BlockNetworkWhenConfidential => (ctx.confidential && is_network)
+            .then(|| "network access is disabled in confidential conversations".to_string()),
+        GuardrailRuleKind::BlockTool { tool_name: blocked } => {
+            (blocked.trim() == tool_name).then(|| format!("the `{tool_name}` tool is not allowed"))
+        }
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Resolve `path` against the workspace and normalize `.`/`..` lexically, so
+/// `src/../secrets` is not mistaken for a path under `src`.
+fn resolve(path: &Path, workspace: Option<&Path>) -> PathBuf {
+    let joined = match workspace {
+        Some(base) if path.is_relative() => base.join(path),
+        _ => path.to_path_buf(),
+    };
+    let mut out = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push("..");
+                }
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+/// Wraps a tool so guardrails are checked before it runs.
+pub struct GuardedTool {
+    inner: Box<dyn ToolDyn>,
+    service: Arc<GuardrailService>,
+    workspace_dir: Option<PathBuf>,
+    notes_dir: Option<PathBuf>,
+    confidential: Option<ConfidentialFlag>,
+}
+
+impl ToolDyn for GuardedTool {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn definition<'a>(&'a self, prompt: String) -> WasmBoxedFuture<'a, ToolDefinition> {
+        self.inner.definition(prompt)
+    }
+
+    fn call<'a>(&'a self, args: String) -> WasmBoxedFuture<'a, Result<String, ToolError>> {
+        let name = self.inner.name();
+        let parsed = serde_json::from_str(&args).unwrap_or(serde_json::Value::Null);
+        let ctx = GuardrailContext {
+            workspace_dir: self.workspace_dir.clone(),
+            notes_dir: self.notes_dir.clone(),
+            confidential: self
+                .confidential
+                .as_ref()
+                .is_some_and(|flag| flag.load(Ordering::Relaxed)),
+        };
+        match self.service.evaluate(&name, &parsed, &ctx) {
+            Some(violation) => {
+                tracing::warn!(
+                    tool = %name,
+                    rule = %violation.rule_name,
+                    reason = %violation.reason,
+                    "Tool call denied by guardrail"
+                );
+                Box::pin(async move { Ok(violation.tool_result(&name)) })
+            }
+            None => self.inner.call(args),
+        }
+    }
+}
+
+/// Wraps tools in [`GuardedTool`]s that share one set of rules, for native
+/// and MCP tools alike.
+#[derive(Clone, Default)]
+pub struct ToolGuard {
+    /// `None` when no rule is enabled
+    service: Option<Arc<GuardrailService>>,
+    workspace_dir: Option<PathBuf>,
+    notes_dir: Option<PathBuf>,
+    confidential: Option<ConfidentialFlag>,
+}
+
+impl ToolGuard {
+    pub fn new(
+        rules: &[GuardrailRule],
+        network_tools: Vec<String>,
+        workspace_dir: Option<PathBuf>,
+        notes_dir: Option<PathBuf>,
+        confidential: Option<ConfidentialFlag>,
+    ) -> Self {
+        let service = GuardrailService::new(rules).with_network_tools(network_tools);
+        Self {
+            service: (!service.is_empty()).then(|| Arc::new(service)),
+            workspace_dir,
+            notes_dir,
+            confidential,
+        }
+    }
+
+    /// Wrap every tool. Returns the tools unchanged when no rule is enabled.
+    pub fn wrap(&self, tools: Vec<Box<dyn ToolDyn>>) -> Vec<Box<dyn ToolDyn>> {
+        let Some(service) = &self.service else {
+            return tools;
+        };
+        tools
+            .into_iter()
+            .map(|inner| {
+                Box::new(GuardedTool {
+                    inner,
+                    service: service.clone(),
+                    workspace_dir: self.workspace_dir.clone(),
+                    notes_dir: self.notes_dir.clone(),
+                    confidential: self.confidential.clone(),
+                }) as Box<dyn ToolDyn>
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ctx(confidential: bool) -> GuardrailContext {
+        GuardrailContext {
+            workspace_dir: Some(PathBuf::from("/work")),
+            notes_dir: Some(PathBuf::from("/notes")),
+            confidential,
+        }
+    }
+
+    fn service(kind: GuardrailRuleKind) -> GuardrailService {
+        GuardrailService::new(&[GuardrailRule::new("rule", kind)])
+    }
+
+    #[test]
+    fn block_command_matches_ignoring_whitespace() {
+        let svc = service(GuardrailRuleKind::BlockCommand {
+            pattern: "rm -rf".into(),
+        });
+        let hit = svc.evaluate(
+            "shell_execute",
+            &json!({"command": "cd /tmp &&  rm   -rf build"}),
+            &ctx(false),
+        );
+        assert_eq!(hit.unwrap().rule_name, "rule");
+        assert!(
+            svc.evaluate(
+                "shell_execute",
+                &json!({"command": "rm -r build"}),
+                &ctx(false)
+            )
+            .is_none()
+        );
+        // Container commands are given as a list of words
+        let exec = json!({"action": "exec", "container": "web", "command": ["rm", "-rf", "/"]});
+        assert!(svc.evaluate("docker", &exec, &ctx(false)).is_some());
+        let remote = json!({"host": "prod", "command": "rm -rf /var/www"});
+        assert!(svc.evaluate("ssh_execute", &remote, &ctx(false)).is_some());
+        // Only command-carrying tools are checked.
+        assert!(
+            svc.evaluate("write_file", &json!({"path": "rm -rf"}), &ctx(false))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn restrict_writes_resolves_relative_and_parent_paths() {
+        let svc = service(GuardrailRuleKind::RestrictWrites {
+            allowed_dirs: vec!["src".into()],
+        });
+        let allowed = json!({"path": "src/lib.rs", "content": ""});
+        assert!(svc.evaluate("write_file", &allowed, &ctx(false)).is_none());
+        assert!(
+            svc.evaluate(
+                "write_file",
+                &json!({"path": "/work/src/a.rs"}),
+                &ctx(false)
+            )
+            .is_none()
+        );
+
+        for path in ["Cargo.toml", "src/../Cargo.toml", "/etc/passwd", "srcx/a"] {
+            assert!(
+                svc.evaluate("write_file", &json!({"path": path}), &ctx(false))
+                    .is_some(),
+                "{path} should be blocked"
+            );
+        }
+
+        let moved_out = json!({"source": "src/a.rs", "destination": "a.rs"});
+        assert!(svc.evaluate("move_file", &moved_out, &ctx(false)).is_some());
+        // Note paths are relative to the vault, not the workspace.
+        let note = json!({"note": "src/Ideas", "text": "x"});
+        assert!(svc.evaluate("notes_append", &note, &ctx(false)).is_some());
+        let vault = service(GuardrailRuleKind::RestrictWrites {
+            allowed_dirs: vec!["/notes/Inbox".into()],
+        });
+        let inbox = json!({"note": "Inbox/Ideas.md", "text": "x"});
+        assert!(
+            vault
+                .evaluate("notes_append", &inbox, &ctx(false))
+                .is_none()
+        );
+        // Reads are never restricted.
+        assert!(
+            svc.evaluate("read_file", &json!({"path": "/etc/passwd"}), &ctx(false))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn network_rule_applies_only_to_confidential_conversations() {
+        let svc = service(GuardrailRuleKind::BlockNetworkWhenConfidential);
+        let args = json!({"url": "https://example.com"});
+        assert!(svc.evaluate("fetch", &args, &ctx(false)).is_none());
+        assert!(svc.evaluate("fetch", &args, &ctx(true)).is_some());
+        assert!(svc.evaluate("read_file", &args, &ctx(true)).is_none());
+        let ps = json!({"action": "list_containers"});
+        assert!(svc.evaluate("docker", &ps, &ctx(true)).is_some());
+        let uptime = json!({"host": "prod", "command": "uptime"});
+        assert!(svc.evaluate("ssh_execute", &uptime, &ctx(true)).is_some());
+        // Cluster tools reach a remote API server
+        assert!(
+            svc.evaluate("k8s_logs", &json!({"pod": "api"}), &ctx(true))
+                .is_some()
+        );
+
+        let svc = svc.with_network_tools(["github_issues".to_string()]);
+        assert!(svc.evaluate("github_issues", &args, &ctx(true)).is_some());
+    }
+
+    #[test]
+    fn disabled_rules_are_ignored() {
+        let mut rule = GuardrailRule::new(
+            "off",
+            GuardrailRuleKind::BlockTool {
+                tool_name: "fetch".into(),
+            },
+        );
+        rule.enabled = false;
+        let svc = GuardrailService::new(&[rule]);
+        assert!(svc.is_empty());
+        assert!(svc.evaluate("fetch", &json!({}), &ctx(false)).is_none());
+    }
+
+    #[test]
+    fn rules_round_trip_through_json() {
+        let rules = default_guardrail_rules();
+        let json = serde_json::to_string(&rules).unwrap();
+        assert!(json.contains("\"kind\":\"block_command\""));
+        let back: Vec<GuardrailRule> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, rules);
+    }
+
+    /// Returns its arguments, named like the tool it stands in for.
+    struct EchoTool(&'static str);
+
+    impl ToolDyn for EchoTool {
+        fn name(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn definition<'a>(&'a self, _prompt: String) -> WasmBoxedFuture<'a, ToolDefinition> {
+            Box::pin(async {
+                ToolDefinition {
+                    name: self.0.to_string(),
+                    description: String::new(),
+                    parameters: json!({}),
+                }
+            })
+        }
+
+        fn call<'a>(&'a self, args: String) -> WasmBoxedFuture<'a, Result<String, ToolError>> {
+            Box::pin(async move { Ok(args) })
+        }
+    }
+
+    #[tokio::test]
+    async fn guarded_tool_denies_without_calling_inner_tool() {
+        let flag: ConfidentialFlag = Arc::new(AtomicBool::new(false));
+        let tools = ToolGuard::new(
+            &default_guardrail_rules(),
+            Vec::new(),
+            None,
+            None,
+            Some(flag.clone()),
+        )
+        .wrap(vec![Box::new(EchoTool("fetch"))]);
+        let tool = &tools[0];
+        let args = r#"{"url":"https://example.com"}"#.to_string();
+
+        assert_eq!(tool.call(args.clone()).await.unwrap(), args);
+
+        flag.store(true, Ordering::Relaxed);
+        let denied = tool.call(args).await.unwrap();
+        assert!(denied.starts_with("Error: Blocked by guardrail"));
+        assert!(denied.contains("No network in confidential conversations"));
+    }
+
+    #[tokio::test]
+    async fn guarded_notes_append_is_restricted_within_the_vault() {
+        let rules = [GuardrailRule::new(
+            "inbox only",
+            GuardrailRuleKind::RestrictWrites {
+                allowed_dirs: vec!["/notes/Inbox".into()],
+            },
+        )];
+        let tools = ToolGuard::new(
+            &rules,
+            Vec::new(),
+            Some(PathBuf::from("/work")),
+            Some(PathBuf::from("/notes")),
+            None,
+        )
+        .wrap(vec![Box::new(EchoTool("notes_append"))]);
+        let tool = &tools[0];
+
+        let inbox = r#"{"note":"Inbox/Ideas.md","text":"x"}"#.to_string();
+        assert_eq!(tool.call(inbox.clone()).await.unwrap(), inbox);
+
+        for note in [
+            "Projects/Plan.md",
+            "Inbox/../Journal.md",
+            "/work/Inbox/a.md",
+        ] {
+            let args = json!({"note": note, "text": "x"}).to_string();
+            let denied = tool.call(args).await.unwrap();
+            assert!(
+                denied.starts_with("Error: Blocked by guardrail \"inbox only\""),
+                "{note}: {denied}"
+            );
+        }
+    }
+}
@@ -35,6 +35,24 @@ pub fn no_redirect_client(timeout_secs: u64) -> reqwest::Client {
         .expect("Failed to initialize HTTP client (TLS backend error)")
 }
 
+/// Build the HTTP client for LLM provider requests.
+///
+/// There is no overall timeout, as a streamed response may run for minutes;
+/// `connect` bounds establishing the connection and `read` each wait for
+/// more data instead. `None` means no limit.
+pub fn provider_client(connect: Option<Duration>, read: Option<Duration>) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder();
+    if let Some(connect) = connect {
+        builder = builder.connect_timeout(connect);
+    }
+    if let Some(read) = read {
+        builder = builder.read_timeout(read);
+    }
+    builder
+        .build()
+        .expect("Failed to initialize HTTP client (TLS backend error)")
+}
+
 /// Build a minimal HTTP client (no custom user-agent) for probing endpoints.
 ///
 /// Used for short-lived metadata requests where a branded user-agent is not
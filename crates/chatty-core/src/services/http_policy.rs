@@ -0,0 +1,514 @@
+//! Shared policy for outbound HTTP requests made on behalf of the agent.
+//!
+//! Every agent-initiated request (the `fetch` tool, user-defined REST tools)
+//! goes through [`HttpPolicy`], which enforces the URL scheme, blocks private
+//! and internal hosts (SSRF protection), re-validates every redirect hop and
+//! caps the amount of response body read into memory.
+
+use std::net::IpAddr;
+use tracing::{info, warn};
+
+/// Maximum number of redirects followed before giving up.
+pub const MAX_REDIRECTS: usize = 10;
+
+/// Errors raised by the HTTP policy layer.
+#[derive(Debug, thiserror::Error)]
+pub enum HttpPolicyError {
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("{0}")]
+    Blocked(String),
+
+    #[error("Request failed: {0}")]
+    Request(String),
+}
+
+/// An outbound request, described independently of the client.
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    pub method: reqwest::Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+    /// Names of headers whose values came from the user's secrets. Like
+    /// [`CREDENTIAL_HEADERS`], they are not sent to another origin.
+    pub secret_headers: Vec<String>,
+}
+
+impl HttpRequest {
+    pub fn get(url: impl Into<String>) -> Self {
+        Self {
+            method: reqwest::Method::GET,
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+            secret_headers: Vec::new(),
+        }
+    }
+}
+
+/// Headers dropped when a redirect leads to a different scheme, host or port.
+pub const CREDENTIAL_HEADERS: &[&str] = &["authorization", "cookie", "proxy-authorization"];
+
+/// A response whose body was read as text, capped at the policy limit.
+#[derive(Clone, Debug)]
+pub struct HttpTextResponse {
+    pub status: u16,
+    pub content_type: String,
+    pub body: String,
+    pub truncated: bool,
+}
+
+/// Rules applied to an agent-initiated HTTP request.
+#[derive(Clone, Debug)]
+pub struct HttpPolicy {
+    /// Allow requests to loopback, private and link-local hosts. Only enabled
+    /// for user-defined tools that explicitly target a local service.
+    pub allow_private_hosts: bool,
+    /// Maximum number of body bytes read by [`HttpPolicy::send_text`].
+    pub max_response_bytes: usize,
+}
+
+impl Default for HttpPolicy {
+    fn default() -> Self {
+        Self {
+            allow_private_hosts: false,
+            max_response_bytes: 1024 * 1024,
+        }
+    }
+}
+
+impl HttpPolicy {
+    /// Check the scheme and host of `url` against the policy.
+    pub fn check_url(&self, url: &str) -> Result<(), HttpPolicyError> {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(HttpPolicyError::InvalidUrl(
+                "URL must start with http:// or https://".to_string(),
+            ));
+        }
+        if self.allow_private_hosts {
+            return reqwest::Url::parse(url)
+                .map(|_| ())
+                .map_err(|e| HttpPolicyError::InvalidUrl(e.to_string()));
+        }
+        validate_url_host(url)
+    }
+
+    /// Send `request` with `client`, following redirects manually so each hop
+    /// is checked against the policy. `client` must be built with redirects
+    /// disabled (see [`crate::services::http_client::no_redirect_client`]).
+    ///
+    /// Credential and secret headers are dropped once a redirect leaves the
+    /// original origin.
+    ///
+    /// Returns the final response and the URL it was served from.
+    pub async fn send(
+        &self,
+        client: &reqwest::Client,
+        request: HttpRequest,
+    ) -> Result<(reqwest::Response, String), HttpPolicyError> {
+        self.check_url(&request.url)?;
+
+        let HttpRequest {
+            mut method,
+            url,
+            mut headers,
+            mut body,
+            secret_headers,
+        } = request;
+        let mut current_url = url;
+
+        for hop in 0..=MAX_REDIRECTS {
+            let mut builder = client.request(method.clone(), &current_url);
+            for (name, value) in &headers {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+            if let Some(body) = &body {
+                builder = builder.body(body.clone());
+            }
+            let response = builder.send().await.map_err(|e| {
+                HttpPolicyError::Request(if hop == 0 {
+                    e.to_string()
+                } else {
+                    format!("redirect to {current_url} failed: {e}")
+                })
+            })?;
+
+            if !response.status().is_redirection() || hop == MAX_REDIRECTS {
+                return Ok((response, current_url));
+            }
+
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| {
+                    HttpPolicyError::Request("Redirect response missing Location header".into())
+                })?;
+            let base = reqwest::Url::parse(&current_url)
+                .map_err(|e| HttpPolicyError::InvalidUrl(e.to_string()))?;
+            let next = base
+                .join(location)
+                .map_err(|e| HttpPolicyError::InvalidUrl(format!("redirect target: {e}")))?;
+            let next_url = next.to_string();
+            self.check_url(&next_url)?;
+
+            if next.origin() != base.origin() {
+                let before = headers.len();
+                headers.retain(|(name, _)| !is_sensitive_header(name, &secret_headers));
+                if headers.len() < before {
+                    info!(to = %next_url, "Dropping credentials on cross-origin redirect");
+                }
+            }
+
+            // 307/308 preserve the method and body; other redirects become a GET.
+            let status = response.status();
+            if status != reqwest::StatusCode::TEMPORARY_REDIRECT
+                && status != reqwest::StatusCode::PERMANENT_REDIRECT
+            {
+                method = reqwest::Method::GET;
+                body = None;
+            }
+
+            info!(from = %current_url, to = %next_url, "Following redirect");
+            current_url = next_url;
+        }
+
+        unreachable!("the last hop always returns")
+    }
+
+    /// Like [`HttpPolicy::send`], but reads the body as text, stopping after
+    /// `max_response_bytes`.
+    pub async fn send_text(
+        &self,
+        client: &reqwest::Client,
+        request: HttpRequest,
+    ) -> Result<HttpTextResponse, HttpPolicyError> {
+        let (mut response, _) = self.send(client, request).await?;
+        let status = response.status().as_u16();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("unknown")
+            .to_string();
+
+        let mut bytes = Vec::new();
+        let mut truncated = false;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| HttpPolicyError::Request(format!("Failed to read response body: {e}")))?
+        {
+            let remaining = self.max_response_bytes.saturating_sub(bytes.len());
+            if chunk.len() > remaining {
+                bytes.extend_from_slice(&chunk[..remaining]);
+                truncated = true;
+                break;
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        Ok(HttpTextResponse {
+            status,
+            content_type,
+            body: String::from_utf8_lossy(&bytes).into_owned(),
+            truncated,
+        })
+    }
+}
+
+/// Whether a header must not follow a redirect to another origin.
+fn is_sensitive_header(name: &str, secret_headers: &[String]) -> bool {
+    let name = name.trim();
+    CREDENTIAL_HEADERS
+        .iter()
+        .any(|header| header.eq_ignore_ascii_case(name))
+        || secret_headers
+            .iter()
+            .any(|header| header.trim().eq_ignore_ascii_case(name))
+}
+
+/// Validate that a URL does not target private, internal, or reserved network hosts.
+///
+/// Blocks loopback (127.x.x.x, ::1), RFC-1918 private ranges (10.x, 172.16-31.x, 192.168.x),
+/// link-local (169.254.x.x, fe80::), cloud metadata endpoints (169.254.169.254), and other
+/// reserved addresses to prevent SSRF attacks.
+pub fn validate_url_host(url: &str) -> Result<(), HttpPolicyError> {
+    let parsed =
+        reqwest::Url::parse(url).map_err(|e| HttpPolicyError::InvalidUrl(e.to_string()))?;
+
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| HttpPolicyError::InvalidUrl("URL has no host".to_string()))?;
+
+    // Check hostname-based blocklist first (catches localhost even without DNS)
+    if is_blocked_hostname(host) {
+        return Err(HttpPolicyError::Blocked(format!(
+            "Access denied: requests to '{}' are blocked for security (SSRF protection)",
+            host
+        )));
+    }
+
+    // Try to parse as IP address directly.
+    // Strip brackets for IPv6: host_str() returns "[::1]" but IpAddr expects "::1".
+    let ip_str = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = ip_str.parse::<IpAddr>()
+        && is_private_ip(&ip)
+    {
+        return Err(HttpPolicyError::Blocked(format!(
+            "Access denied: requests to private/internal IP '{}' are blocked for security (SSRF protection)",
+            ip
+        )));
+    }
+
+    // For hostnames, resolve to IP and check the resolved address.
+    // This catches DNS rebinding / split-horizon attacks where a public hostname
+    // resolves to a private IP.
+    if ip_str.parse::<IpAddr>().is_err() {
+        // Use std::net for synchronous resolution (sufficient for validation)
+        if let Ok(addrs) = std::net::ToSocketAddrs::to_socket_addrs(&(host, 80)) {
+            for addr in addrs {
+                if is_private_ip(&addr.ip()) {
+                    warn!(
+                        host = %host,
+                        resolved_ip = %addr.ip(),
+                        "Blocked DNS-resolved private IP"
+                    );
+                    return Err(HttpPolicyError::Blocked(format!(
+                        "Access denied: '{}' resolves to private/internal IP {} (SSRF protection)",
+                        host,
+                        addr.ip()
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check if a hostname string is a known-blocked name (case-insensitive).
+fn is_blocked_hostname(host: &str) -> bool {
+    let h = host.to_lowercase();
+    h == "localhost"
+        || h == "metadata.google.internal"  // GCP metadata
+        || h.ends_with(".internal")
+        || h.ends_with(".local")
+}
+
+/// Check if an IP address belongs to a private, loopback, link-local, or otherwise
+/// reserved network range that should not be accessible from agent tools.
+fn is_private_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let octets = v4.octets();
+            // 127.0.0.0/8 — loopback
+            octets[0] == 127
+            // 10.0.0.0/8 — RFC-1918 private
+            || octets[0] == 10
+            // 172.16.0.0/12 — RFC-1918 private
+            || (octets[0] == 172 && (16..=31).contains(&octets[1]))
+            // 192.168.0.0/16 — RFC-1918 private
+            || (octets[0] == 192 && octets[1] == 168)
+            // 169.254.0.0/16 — link-local (includes AWS/GCP/Azure metadata at 169.254.169.254)
+            || (octets[0] == 169 && octets[1] == 254)
+            // 0.0.0.0/8 — "this" network
+            || octets[0] == 0
+            // 100.64.0.0/10 — shared address space (CGN, often internal)
+            || (octets[0] == 100 && (64..=127).contains(&octets[1]))
+            // 198.18.0.0/15 — benchmarking
+            || (octets[0] == 198 && (18..=19).contains(&octets[1]))
+            // 224.0.0.0/4 — multicast
+            || octets[0] >= 224
+        }
+        IpAddr::V6(v6) => {
+            // ::1 — loopback
+            v6.is_loopback()
+            // fe80::/10 — link-local
+            || (v6.segments()[0] & 0xffc0) == 0xfe80
+            // fc00::/7 — unique local (ULA, RFC-4193)
+            || (v6.segments()[0] & 0xfe00) == 0xfc00
+            // :: — unspecified
+            || v6.is_unspecified()
+            // ::ffff:x.x.x.x — IPv4-mapped, check the embedded v4 address
+            || v6.to_ipv4_mapped().map(|v4| is_private_ip(&IpAddr::V4(v4))).unwrap_or(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_private_ip_loopback() {
+        assert!(is_private_ip(&"127.0.0.1".parse().unwrap()));
+        assert!(is_private_ip(&"127.0.0.2".parse().unwrap()));
+        assert!(is_private_ip(&"::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_is_private_ip_rfc1918() {
+        assert!(is_private_ip(&"10.0.0.1".parse().unwrap()));
+        assert!(is_private_ip(&"10.255.255.255".parse().unwrap()));
+        assert!(is_private_ip(&"172.16.0.1".parse().unwrap()));
+        assert!(is_private_ip(&"172.31.255.255".parse().unwrap()));
+        assert!(is_private_ip(&"192.168.0.1".parse().unwrap()));
+        assert!(is_private_ip(&"192.168.255.255".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_is_private_ip_link_local_and_metadata() {
+        // AWS/GCP/Azure metadata endpoint
+        assert!(is_private_ip(&"169.254.169.254".parse().unwrap()));
+        assert!(is_private_ip(&"169.254.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_is_private_ip_public() {
+        assert!(!is_private_ip(&"8.8.8.8".parse().unwrap()));
+        assert!(!is_private_ip(&"1.1.1.1".parse().unwrap()));
+        assert!(!is_private_ip(&"93.184.216.34".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_is_private_ip_other_reserved() {
+        assert!(is_private_ip(&"0.0.0.0".parse().unwrap()));
+        assert!(is_private_ip(&"100.64.0.1".parse().unwrap())); // CGN
+        assert!(is_private_ip(&"224.0.0.1".parse().unwrap())); // multicast
+        assert!(is_private_ip(&"255.255.255.255".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_is_private_ip_v6() {
+        // ULA
+        assert!(is_private_ip(&"fd00::1".parse().unwrap()));
+        // Link-local
+        assert!(is_private_ip(&"fe80::1".parse().unwrap()));
+        // Unspecified
+        assert!(is_private_ip(&"::".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_is_private_ip_v4_mapped_v6() {
+        // ::ffff:127.0.0.1 should be blocked
+        assert!(is_private_ip(&"::ffff:127.0.0.1".parse().unwrap()));
+        // ::ffff:169.254.169.254 (metadata via v6)
+        assert!(is_private_ip(&"::ffff:169.254.169.254".parse().unwrap()));
+        // ::ffff:8.8.8.8 should be allowed
+        assert!(!is_private_ip(&"::ffff:8.8.8.8".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_is_blocked_hostname() {
+        assert!(is_blocked_hostname("localhost"));
+        assert!(is_blocked_hostname("LOCALHOST"));
+        assert!(is_blocked_hostname("metadata.google.internal"));
+        assert!(is_blocked_hostname("foo.internal"));
+        assert!(is_blocked_hostname("printer.local"));
+        assert!(!is_blocked_hostname("example.com"));
+        assert!(!is_blocked_hostname("my-internal-api.com")); // "internal" in domain name is fine
+    }
+
+    #[test]
+    fn test_validate_url_host_blocks_private() {
+        assert!(validate_url_host("http://127.0.0.1/secret").is_err());
+        assert!(validate_url_host("http://localhost:8080/admin").is_err());
+        assert!(validate_url_host("http://169.254.169.254/latest/meta-data/").is_err());
+        assert!(validate_url_host("http://10.0.0.1/internal").is_err());
+        assert!(validate_url_host("http://192.168.1.1/router").is_err());
+        assert!(validate_url_host("http://172.16.0.5/service").is_err());
+        assert!(validate_url_host("http://[::1]/secret").is_err());
+        assert!(validate_url_host("http://metadata.google.internal/computeMetadata/v1/").is_err());
+    }
+
+    #[test]
+    fn test_validate_url_host_allows_public() {
+        assert!(validate_url_host("https://example.com").is_ok());
+        assert!(validate_url_host("https://docs.rs/rig-core/latest").is_ok());
+    }
+
+    #[test]
+    fn test_check_url_scheme_and_private_override() {
+        let policy = HttpPolicy::default();
+        assert!(policy.check_url("ftp://example.com").is_err());
+        assert!(policy.check_url("http://localhost:3000/api").is_err());
+
+        let local = HttpPolicy {
+            allow_private_hosts: true,
+            ..HttpPolicy::default()
+        };
+        assert!(local.check_url("http://localhost:3000/api").is_ok());
+        assert!(local.check_url("file:///etc/passwd").is_err());
+    }
+
+    /// Accept one connection, answer it with `response` and return the
+    /// request head, lowercased.
+    async fn serve_once(listener: tokio::net::TcpListener, response: String) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = vec![0u8; 8192];
+        let n = stream.read(&mut buf).await.unwrap();
+        stream.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&buf[..n]).to_lowercase()
+    }
+
+    #[tokio::test]
+    async fn cross_origin_redirect_drops_credentials_and_secret_headers() {
+        let first = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let second = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let start = format!("http://{}/start", first.local_addr().unwrap());
+        // Same host, different port: another origin
+        let target = format!("http://{}/next", second.local_addr().unwrap());
+        let redirect = tokio::spawn(serve_once(
+            first,
+            format!("HTTP/1.1 302 Found\r\nLocation: {target}\r\nContent-Length: 0\r\n\r\n"),
+        ));
+        let landing = tokio::spawn(serve_once(
+            second,
+            "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok".to_string(),
+        ));
+
+        let policy = HttpPolicy {
+            allow_private_hosts: true,
+            ..HttpPolicy::default()
+        };
+        let request = HttpRequest {
+            headers: [
+                ("Authorization", "Bearer t0ken"),
+                ("Cookie", "session=s3ssion"),
+                ("Proxy-Authorization", "Basic cHJveHk="),
+                ("X-Api-Key", "k3y"),
+                ("Accept", "application/json"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .to_vec(),
+            secret_headers: vec!["X-Api-Key".to_string()],
+            ..HttpRequest::get(start)
+        };
+        let client = crate::services::http_client::no_redirect_client(5);
+        let response = policy.send_text(&client, request).await.unwrap();
+        assert_eq!(response.body, "ok");
+
+        let first_hop = redirect.await.unwrap();
+        assert!(
+            first_hop.contains("authorization: bearer t0ken"),
+            "{first_hop}"
+        );
+        assert!(first_hop.contains("x-api-key: k3y"), "{first_hop}");
+        let second_hop = landing.await.unwrap();
+        for header in ["authorization", "cookie", "x-api-key"] {
+            assert!(
+                !second_hop.contains(header),
+                "{header} leaked: {second_hop}"
+            );
+        }
+        assert!(
+            second_hop.contains("accept: application/json"),
+            "{second_hop}"
+        );
+    }
+}
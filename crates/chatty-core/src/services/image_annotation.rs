@@ -0,0 +1,348 @@
+//! Annotations applied to staged image attachments before they are sent.
+//!
+//! The editor records shapes in normalized coordinates (fractions of the
+//! image's width and height), so it doesn't matter how large the preview was
+//! drawn. [`annotate_image`] burns them into a copy of the source image:
+//! boxes and arrows are stroked, blur regions are pixelated and blurred so the
+//! original content can't be read back, and the last crop (if any) is applied
+//! after everything else. The source file is never modified.
+
+use std::path::{Path, PathBuf};
+
+use image::{Rgba, RgbaImage, imageops};
+
+/// Stroke colour for boxes and arrows.
+const STROKE_COLOR: Rgba<u8> = Rgba([230, 57, 70, 255]);
+/// Rects smaller than this fraction of the image on either axis are ignored.
+const MIN_RECT_FRACTION: f32 = 0.01;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ImageAnnotationError {
+    #[error("Failed to read image: {0}")]
+    Read(String),
+
+    #[error("Failed to write image: {0}")]
+    Write(String),
+
+    #[error("No directory to store annotated images in")]
+    NoOutputDir,
+}
+
+/// A point in normalized image coordinates (`0.0..=1.0` on both axes).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NormPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl NormPoint {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self {
+            x: x.clamp(0.0, 1.0),
+            y: y.clamp(0.0, 1.0),
+        }
+    }
+
+    fn to_pixels(self, width: u32, height: u32) -> (f32, f32) {
+        (self.x * width as f32, self.y * height as f32)
+    }
+}
+
+/// An axis-aligned rectangle in normalized image coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NormRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl NormRect {
+    /// The rectangle spanned by two opposite corners, in either order.
+    pub fn from_corners(a: NormPoint, b: NormPoint) -> Self {
+        Self {
+            x: a.x.min(b.x),
+            y: a.y.min(b.y),
+            width: (a.x - b.x).abs(),
+            height: (a.y - b.y).abs(),
+        }
+    }
+
+    /// Whether the rect is too small to be intentional (e.g. a plain click).
+    pub fn is_degenerate(&self) -> bool {
+        self.width < MIN_RECT_FRACTION || self.height < MIN_RECT_FRACTION
+    }
+
+    /// Pixel `(x, y, width, height)` within a `width` × `height` image, at
+    /// least one pixel in each dimension.
+    fn to_pixels(self, width: u32, height: u32) -> (u32, u32, u32, u32) {
+        let x = ((self.x * width as f32) as u32).min(width.saturating_sub(1));
+        let y = ((self.y * height as f32) as u32).min(height.saturating_sub(1));
+        let w = ((self.width * width as f32).round() as u32).clamp(1, width - x);
+        let h = ((self.height * height as f32).round() as u32).clamp(1, height - y);
+        (x, y, w, h)
+    }
+}
+
+/// One edit made in the annotation editor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Annotation {
+    /// Keep only this region of the image.
+    Crop(NormRect),
+    /// Outline a region.
+    Box(NormRect),
+    /// Arrow pointing from `from` to `to`.
+    Arrow { from: NormPoint, to: NormPoint },
+    /// Mask a region so its content can't be read.
+    Blur(NormRect),
+}
+
+/// Pixel dimensions of the image at `path`, read from its header.
+pub fn image_size(path: &Path) -> Result<(u32, u32), ImageAnnotationError> {
+    image::image_dimensions(path).map_err(|e| ImageAnnotationError::Read(e.to_string()))
+}
+
+/// Apply `annotations` to a copy of `source` and save it as a PNG in the
+/// annotated-images directory. Returns the new file's path.
+pub fn annotate_image(
+    source: &Path,
+    annotations: &[Annotation],
+) -> Result<PathBuf, ImageAnnotationError> {
+    let image = image::open(source)
+        .map_err(|e| ImageAnnotationError::Read(e.to_string()))?
+        .to_rgba8();
+    let annotated = apply_annotations(image, annotations);
+
+    let dir = annotated_images_dir().ok_or(ImageAnnotationError::NoOutputDir)?;
+    std::fs::create_dir_all(&dir).map_err(|e| ImageAnnotationError::Write(e.to_string()))?;
+
+    let stem = source
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "image".to_string());
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    let path = dir.join(format!("{}_annotated_{}.png", stem, &id[..8]));
+    annotated
+        .save_with_format(&path, image::ImageFormat::Png)
+        .map_err(|e| ImageAnnotationError::Write(e.to_string()))?;
+    Ok(path)
+}
+
+/// Burn `annotations` into `image`, in order, then apply the last crop.
+pub fn apply_annotations(mut image: RgbaImage, annotations: &[Annotation]) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    if width == 0 || height == 0 {
+        return image;
+    }
+    let stroke = (width.min(height) / 200).max(2);
+
+    for annotation in annotations {
+        match *annotation {
+            Annotation::Box(rect) => draw_box(&mut image, rect, stroke),
+            Annotation::Arrow { from, to } => draw_arrow(&mut image, from, to, stroke),
+            Annotation::Blur(rect) => blur_region(&mut image, rect),
+            Annotation::Crop(_) => {}
+        }
+    }
+
+    let crop = annotations
+        .iter()
+        .rev()
+        .find_map(|annotation| match annotation {
+            Annotation::Crop(rect) => Some(*rect),
+            _ => None,
+        });
+    match crop {
+        Some(rect) => {
+            let (x, y, w, h) = rect.to_pixels(width, height);
+            imageops::crop_imm(&image, x, y, w, h).to_image()
+        }
+        None => image,
+    }
+}
+
+/// Where annotated copies are written. They live next to the app's other
+/// data (not in a temp dir) because sent messages keep referring to them.
+fn annotated_images_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("chatty").join("annotated_images"))
+}
+
+fn draw_box(image: &mut RgbaImage, rect: NormRect, stroke: u32) {
+    let (width, height) = image.dimensions();
+    let (x, y, w, h) = rect.to_pixels(width, height);
+    let (left, top) = (x as f32, y as f32);
+    let (right, bottom) = ((x + w - 1) as f32, (y + h - 1) as f32);
+    draw_line(image, (left, top), (right, top), stroke);
+    draw_line(image, (right, top), (right, bottom), stroke);
+    draw_line(image, (right, bottom), (left, bottom), stroke);
+    draw_line(image, (left, bottom), (left, top), stroke);
+}
+
+fn draw_arrow(image: &mut RgbaImage, from: NormPoint, to: NormPoint, stroke: u32) {
+    let (width, height) = image.dimensions();
+    let start = from.to_pixels(width, height);
+    let end = to.to_pixels(width, height);
+    draw_line(image, start, end, stroke);
+
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length < 1.0 {
+        return;
+    }
+    // Two barbs at ±25° from the shaft, pointing back towards the start.
+    let head = (stroke as f32 * 5.0).max(12.0).min(length / 2.0);
+    let angle = dy.atan2(dx);
+    for barb in [angle + 2.7, angle - 2.7] {
+        let tip = (end.0 + head * barb.cos(), end.1 + head * barb.sin());
+        draw_line(image, end, tip, stroke);
+    }
+}
+
+/// Stroke a line by stamping `stroke`-sized squares along it.
+fn draw_line(image: &mut RgbaImage, from: (f32, f32), to: (f32, f32), stroke: u32) {
+    let (width, height) = image.dimensions();
+    let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).ceil() as u32;
+    let half = stroke as i64 / 2;
+    for step in 0..=steps {
+        let t = if steps == 0 {
+            0.0
+        } else {
+            step as f32 / steps as f32
+        };
+        let cx = (from.0 + (to.0 - from.0) * t).round() as i64;
+        let cy = (from.1 + (to.1 - from.1) * t).round() as i64;
+        for py in (cy - half)..(cy - half + stroke as i64) {
+            for px in (cx - half)..(cx - half + stroke as i64) {
+                if px >= 0 && py >= 0 && (px as u32) < width && (py as u32) < height {
+                    image.put_pixel(px as u32, py as u32, STROKE_COLOR);
+                }
+            }
+        }
+    }
+}
+
+/// Pixelate, then blur, a region. Pixelating first throws the detail away so
+/// text under the mask can't be recovered by sharpening the blur.
+fn blur_region(image: &mut RgbaImage, rect: NormRect) {
+    let (width, height) = image.dimensions();
+    let (x, y, w, h) = rect.to_pixels(width, height);
+    let block = (w.min(h) / 8).max(8);
+
+    let region = imageops::crop_imm(image, x, y, w, h).to_image();
+    let small = imageops::resize(
+        &region,
+        (w / block).max(1),
+        (h / block).max(1),
+        imageops::FilterType::Triangle,
+    );
+    let pixelated = imageops::resize(&small, w, h, imageops::FilterType::Nearest);
+    let blurred = imageops::blur(&pixelated, block as f32 / 2.0);
+    imageops::replace(image, &blurred, x as i64, y as i64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| {
+            if (x + y) % 2 == 0 {
+                Rgba([0, 0, 0, 255])
+            } else {
+                Rgba([255, 255, 255, 255])
+            }
+        })
+    }
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> NormRect {
+        NormRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn from_corners_normalizes_order() {
+        let r = NormRect::from_corners(NormPoint::new(0.8, 0.6), NormPoint::new(0.2, 0.1));
+        assert!((r.x - 0.2).abs() < 1e-6);
+        assert!((r.y - 0.1).abs() < 1e-6);
+        assert!((r.width - 0.6).abs() < 1e-6);
+        assert!((r.height - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn click_without_drag_is_degenerate() {
+        let p = NormPoint::new(0.5, 0.5);
+        assert!(NormRect::from_corners(p, p).is_degenerate());
+    }
+
+    #[test]
+    fn crop_keeps_only_the_region() {
+        let image = checkerboard(200, 100);
+        let out = apply_annotations(image, &[Annotation::Crop(rect(0.25, 0.5, 0.5, 0.5))]);
+        assert_eq!(out.dimensions(), (100, 50));
+    }
+
+    #[test]
+    fn last_crop_wins() {
+        let image = checkerboard(200, 100);
+        let out = apply_annotations(
+            image,
+            &[
+                Annotation::Crop(rect(0.0, 0.0, 0.5, 0.5)),
+                Annotation::Crop(rect(0.0, 0.0, 1.0, 0.2)),
+            ],
+        );
+        assert_eq!(out.dimensions(), (200, 20));
+    }
+
+    #[test]
+    fn box_strokes_its_edges_only() {
+        let image = RgbaImage::from_pixel(100, 100, Rgba([255, 255, 255, 255]));
+        let out = apply_annotations(image, &[Annotation::Box(rect(0.2, 0.2, 0.6, 0.6))]);
+        assert_eq!(*out.get_pixel(20, 50), STROKE_COLOR);
+        assert_eq!(*out.get_pixel(50, 20), STROKE_COLOR);
+        assert_eq!(*out.get_pixel(50, 50), Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn arrow_strokes_its_shaft() {
+        let image = RgbaImage::from_pixel(100, 100, Rgba([255, 255, 255, 255]));
+        let out = apply_annotations(
+            image,
+            &[Annotation::Arrow {
+                from: NormPoint::new(0.1, 0.5),
+                to: NormPoint::new(0.9, 0.5),
+            }],
+        );
+        assert_eq!(*out.get_pixel(50, 50), STROKE_COLOR);
+        assert_eq!(*out.get_pixel(50, 10), Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn blur_removes_detail_inside_region_only() {
+        let image = checkerboard(64, 64);
+        let out = apply_annotations(image, &[Annotation::Blur(rect(0.0, 0.0, 0.5, 0.5))]);
+        // The checkerboard averages out to grey inside the region...
+        let inside = out.get_pixel(16, 16);
+        assert!(inside[0] > 40 && inside[0] < 215, "got {:?}", inside);
+        // ...and is untouched outside it.
+        assert_eq!(*out.get_pixel(48, 48), Rgba([0, 0, 0, 255]));
+        assert_eq!(*out.get_pixel(49, 48), Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn annotations_outside_the_image_are_clamped() {
+        let image = checkerboard(50, 50);
+        let out = apply_annotations(
+            image,
+            &[
+                Annotation::Box(rect(0.9, 0.9, 0.5, 0.5)),
+                Annotation::Blur(rect(0.95, 0.95, 0.5, 0.5)),
+            ],
+        );
+        assert_eq!(out.dimensions(), (50, 50));
+    }
+}
@@ -0,0 +1,650 @@
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Resource kinds that are never read or modified through the kubectl tools.
+const BLOCKED_KINDS: &[&str] = &["secret", "secrets"];
+
+/// Kinds that support `kubectl rollout restart`.
+const ROLLOUT_KINDS: &[&str] = &[
+    "deployment",
+    "deployments",
+    "deploy",
+    "statefulset",
+    "statefulsets",
+    "sts",
+    "daemonset",
+    "daemonsets",
+    "ds",
+];
+
+/// Kinds that support `kubectl scale`.
+const SCALE_KINDS: &[&str] = &[
+    "deployment",
+    "deployments",
+    "deploy",
+    "statefulset",
+    "statefulsets",
+    "sts",
+    "replicaset",
+    "replicasets",
+    "rs",
+];
+
+/// Upper bound for `kubectl logs --tail`.
+pub const MAX_LOG_LINES: u32 = 2000;
+
+/// Output of a single kubectl invocation.
+#[derive(Debug, Serialize)]
+pub struct KubectlOutput {
+    /// The command that was run, for transparency in the transcript
+    pub command: String,
+    /// Captured stdout (truncated to the configured output limit)
+    pub output: String,
+    /// Whether `output` was cut off
+    pub truncated: bool,
+}
+
+/// Mutating operation offered by `k8s_action`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KubernetesAction {
+    Delete {
+        kind: String,
+        name: String,
+    },
+    RolloutRestart {
+        kind: String,
+        name: String,
+    },
+    Scale {
+        kind: String,
+        name: String,
+        replicas: u32,
+    },
+    Cordon {
+        node: String,
+    },
+    Uncordon {
+        node: String,
+    },
+}
+
+/// Contexts and namespaces the tools may touch.
+///
+/// An empty context list restricts the tools to the kubeconfig's current
+/// context; an empty namespace list allows any namespace.
+#[derive(Debug, Clone, Default)]
+pub struct KubernetesScope {
+    pub contexts: Vec<String>,
+    pub namespaces: Vec<String>,
+}
+
+impl KubernetesScope {
+    /// Resolve the requested context against the allowlist, defaulting to the
+    /// first allowed context.
+    pub fn resolve_context(&self, requested: Option<&str>) -> Result<Option<String>> {
+        match (requested.filter(|c| !c.is_empty()), self.contexts.first()) {
+            (None, first) => Ok(first.cloned()),
+            (Some(context), None) => Err(anyhow!(
+                "Context '{}' is not allowed; only the current kubeconfig context is configured",
+                context
+            )),
+            (Some(context), Some(_)) => {
+                if self.contexts.iter().any(|c| c == context) {
+                    Ok(Some(context.to_string()))
+                } else {
+                    Err(anyhow!(
+                        "Context '{}' is not allowed (allowed: {})",
+                        context,
+                        self.contexts.join(", ")
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Resolve the requested namespace against the allowlist, defaulting to
+    /// the first allowed namespace.
+    pub fn resolve_namespace(&self, requested: Option<&str>) -> Result<Option<String>> {
+        match requested.filter(|n| !n.is_empty()) {
+            None => Ok(self.namespaces.first().cloned()),
+            Some(namespace) => {
+                validate_name(namespace, "namespace")?;
+                if self.namespaces.is_empty() || self.namespaces.iter().any(|n| n == namespace) {
+                    Ok(Some(namespace.to_string()))
+                } else {
+                    Err(anyhow!(
+                        "Namespace '{}' is not allowed (allowed: {})",
+                        namespace,
+                        self.namespaces.join(", ")
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Global flags (`--context`, `--namespace` / `--all-namespaces`) for a
+    /// namespaced command.
+    pub fn scope_args(
+        &self,
+        context: Option<&str>,
+        namespace: Option<&str>,
+        all_namespaces: bool,
+    ) -> Result<Vec<String>> {
+        let mut args = Vec::new();
+        if let Some(context) = self.resolve_context(context)? {
+            args.push(format!("--context={context}"));
+        }
+        if all_namespaces {
+            if !self.namespaces.is_empty() {
+                return Err(anyhow!(
+                    "all_namespaces is not allowed when namespaces are restricted (allowed: {})",
+                    self.namespaces.join(", ")
+                ));
+            }
+            args.push("--all-namespaces".to_string());
+        } else if let Some(namespace) = self.resolve_namespace(namespace)? {
+            args.push(format!("--namespace={namespace}"));
+        }
+        Ok(args)
+    }
+
+    /// Arguments for `kubectl get`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_args(
+        &self,
+        kind: &str,
+        name: Option<&str>,
+        selector: Option<&str>,
+        output: Option<&str>,
+        context: Option<&str>,
+        namespace: Option<&str>,
+        all_namespaces: bool,
+    ) -> Result<Vec<String>> {
+        validate_kind(kind)?;
+        let mut args = vec!["get".to_string(), kind.to_string()];
+        if let Some(name) = name.filter(|n| !n.is_empty()) {
+            validate_name(name, "resource name")?;
+            args.push(name.to_string());
+        }
+        if let Some(selector) = selector.filter(|s| !s.is_empty()) {
+            validate_selector(selector)?;
+            args.push(format!("--selector={selector}"));
+        }
+        let output = output.unwrap_or("wide");
+        if !matches!(output, "wide" | "yaml" | "json" | "name") {
+            return Err(anyhow!(
+                "Unsupported output format '{}' (use wide, yaml, json or name)",
+                output
+            ));
+        }
+        args.push(format!("--output={output}"));
+        args.extend(self.scope_args(context, namespace, all_namespaces)?);
+        Ok(args)
+    }
+
+    /// Arguments for `kubectl describe`.
+    pub fn describe_args(
+        &self,
+        kind: &str,
+        name: &str,
+        context: Option<&str>,
+        namespace: Option<&str>,
+    ) -> Result<Vec<String>> {
+        validate_kind(kind)?;
+        validate_name(name, "resource name")?;
+        let mut args = vec!["describe".to_string(), kind.to_string(), name.to_string()];
+        args.extend(self.scope_args(context, namespace, false)?);
+        Ok(args)
+    }
+
+    /// Arguments for `kubectl logs`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn logs_args(
+        &self,
+        pod: &str,
+        container: Option<&str>,
+        tail_lines: u32,
+        previous: bool,
+        since: Option<&str>,
+        context: Option<&str>,
+        namespace: Option<&str>,
+    ) -> Result<Vec<String>> {
+        validate_name(pod, "pod name")?;
+        let mut args = vec!["logs".to_string(), pod.to_string()];
+        if let Some(container) = container.filter(|c| !c.is_empty()) {
+            validate_name(container, "container name")?;
+            args.push(format!("--container={container}"));
+        }
+        args.push(format!("--tail={}", tail_lines.clamp(1, MAX_LOG_LINES)));
+        if previous {
+            args.push("--previous".to_string());
+        }
+        if let Some(since) = since.filter(|s| !s.is_empty()) {
+            validate_duration(since)?;
+            args.push(format!("--since={since}"));
+        }
+        args.extend(self.scope_args(context, namespace, false)?);
+        Ok(args)
+    }
+
+    /// Arguments for `kubectl get events`, newest last.
+    pub fn events_args(
+        &self,
+        involved_object: Option<&str>,
+        context: Option<&str>,
+        namespace: Option<&str>,
+        all_namespaces: bool,
+    ) -> Result<Vec<String>> {
+        let mut args = vec![
+            "get".to_string(),
+            "events".to_string(),
+            "--sort-by=.lastTimestamp".to_string(),
+        ];
+        if let Some(name) = involved_object.filter(|n| !n.is_empty()) {
+            validate_name(name, "object name")?;
+            args.push(format!("--field-selector=involvedObject.name={name}"));
+        }
+        args.extend(self.scope_args(context, namespace, all_namespaces)?);
+        Ok(args)
+    }
+
+    /// Arguments for a mutating action.
+    pub fn action_args(
+        &self,
+        action: &KubernetesAction,
+        context: Option<&str>,
+        namespace: Option<&str>,
+    ) -> Result<Vec<String>> {
+        let mut args = match action {
+            KubernetesAction::Delete { kind, name } => {
+                validate_kind(kind)?;
+                validate_name(name, "resource name")?;
+                vec!["delete".to_string(), kind.clone(), name.clone()]
+            }
+            KubernetesAction::RolloutRestart { kind, name } => {
+                validate_kind_in(kind, ROLLOUT_KINDS, "rollout restart")?;
+                validate_name(name, "resource name")?;
+                vec![
+                    "rollout".to_string(),
+                    "restart".to_string(),
+                    format!("{kind}/{name}"),
+                ]
+            }
+            KubernetesAction::Scale {
+                kind,
+                name,
+                replicas,
+            } => {
+                validate_kind_in(kind, SCALE_KINDS, "scale")?;
+                validate_name(name, "resource name")?;
+                vec![
+                    "scale".to_string(),
+                    format!("{kind}/{name}"),
+                    format!("--replicas={replicas}"),
+                ]
+            }
+            // Nodes are cluster-scoped, so no namespace flag is added below.
+            KubernetesAction::Cordon { node } | KubernetesAction::Uncordon { node } => {
+                validate_name(node, "node name")?;
+                let verb = if matches!(action, KubernetesAction::Cordon { .. }) {
+                    "cordon"
+                } else {
+                    "uncordon"
+                };
+                let mut args = vec![verb.to_string(), node.clone()];
+                if let Some(context) = self.resolve_context(context)? {
+                    args.push(format!("--context={context}"));
+                }
+                return Ok(args);
+            }
+        };
+        args.extend(self.scope_args(context, namespace, false)?);
+        Ok(args)
+    }
+}
+
+/// kubectl-backed Kubernetes inspection service.
+///
+/// Every command is built from validated arguments (never a shell string) and
+/// scoped to the contexts and namespaces allowed in execution settings.
+/// Secrets are never read.
+#[derive(Debug)]
+pub struct KubernetesService {
+    scope: KubernetesScope,
+    timeout: Duration,
+    max_output_bytes: usize,
+}
+
+impl KubernetesService {
+    /// Create a new service, verifying that kubectl is installed.
+    pub async fn new(
+        scope: KubernetesScope,
+        timeout: Duration,
+        max_output_bytes: usize,
+    ) -> Result<Self> {
+        let check = tokio::process::Command::new("kubectl")
+            .args(["version", "--client"])
+            .output()
+            .await
+            .map_err(|e| anyhow!("kubectl is not installed or not in PATH: {}", e))?;
+
+        if !check.status.success() {
+            return Err(anyhow!("kubectl is not available on this system"));
+        }
+
+        info!(
+            contexts = ?scope.contexts,
+            namespaces = ?scope.namespaces,
+            "Kubernetes service initialized"
+        );
+
+        Ok(Self {
+            scope,
+            timeout,
+            max_output_bytes,
+        })
+    }
+
+    pub fn scope(&self) -> &KubernetesScope {
+        &self.scope
+    }
+
+    /// Run kubectl with pre-validated arguments.
+    pub async fn run(&self, args: Vec<String>) -> Result<KubectlOutput> {
+        let command = format!("kubectl {}", args.join(" "));
+        debug!(command = %command, "Running kubectl command");
+
+        let output = tokio::time::timeout(
+            self.timeout,
+            tokio::process::Command::new("kubectl")
+                .args(&args)
+                .kill_on_drop(true)
+                .output(),
+        )
+        .await
+        .map_err(|_| anyhow!("{} timed out after {:?}", command, self.timeout))?
+        .map_err(|e| anyhow!("Failed to execute kubectl: {}", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("{} failed: {}", command, stderr.trim()));
+        }
+
+        let (output, truncated) = truncate_output(&output.stdout, self.max_output_bytes);
+        Ok(KubectlOutput {
+            command,
+            output,
+            truncated,
+        })
+    }
+}
+
+/// Decode stdout, cutting it at `max_bytes` on a char boundary.
+fn truncate_output(stdout: &[u8], max_bytes: usize) -> (String, bool) {
+    let text = String::from_utf8_lossy(stdout);
+    if text.len() <= max_bytes {
+        return (text.into_owned(), false);
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    (text[..end].to_string(), true)
+}
+
+/// Validate a resource kind such as `pods` or `deployments.apps`.
+fn validate_kind(kind: &str) -> Result<()> {
+    if kind.is_empty()
+        || kind.starts_with(['-', '.'])
+        || !kind
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    {
+        return Err(anyhow!("Invalid resource kind '{}'", kind));
+    }
+    let base = kind.split('.').next().unwrap_or(kind).to_ascii_lowercase();
+    if BLOCKED_KINDS.contains(&base.as_str()) {
+        return Err(anyhow!("Access to Kubernetes secrets is not allowed"));
+    }
+    Ok(())
+}
+
+fn validate_kind_in(kind: &str, allowed: &[&str], verb: &str) -> Result<()> {
+    validate_kind(kind)?;
+    if !allowed.contains(&kind.to_ascii_lowercase().as_str()) {
+        return Err(anyhow!("Cannot {} resources of kind '{}'", verb, kind));
+    }
+    Ok(())
+}
+
+/// Validate an object name (DNS-1123 subdomain characters).
+fn validate_name(name: &str, what: &str) -> Result<()> {
+    if name.is_empty()
+        || name.len() > 253
+        || name.starts_with(['-', '.'])
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.')
+    {
+        return Err(anyhow!("Invalid {} '{}'", what, name));
+    }
+    Ok(())
+}
+
+/// Validate a label selector such as `app=web,tier!=cache`.
+fn validate_selector(selector: &str) -> Result<()> {
+    if !selector.chars().all(|c| {
+        c.is_ascii_alphanumeric()
+            || matches!(c, '=' | '!' | ',' | '-' | '_' | '.' | '/' | ' ' | '(' | ')')
+    }) {
+        return Err(anyhow!("Invalid label selector '{}'", selector));
+    }
+    Ok(())
+}
+
+/// Validate a relative duration such as `15m` or `2h`.
+fn validate_duration(since: &str) -> Result<()> {
+    let digits = since.trim_end_matches(['s', 'm', 'h']);
+    if digits.is_empty()
+        || digits.len() == since.len()
+        || !digits.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(anyhow!(
+            "Invalid duration '{}' (use e.g. 30s, 15m, 2h)",
+            since
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope() -> KubernetesScope {
+        KubernetesScope {
+            contexts: vec!["prod".to_string(), "staging".to_string()],
+            namespaces: vec!["web".to_string(), "jobs".to_string()],
+        }
+    }
+
+    #[test]
+    fn empty_scope_uses_current_context_and_any_namespace() {
+        let scope = KubernetesScope::default();
+        assert_eq!(scope.resolve_context(None).unwrap(), None);
+        assert!(scope.resolve_context(Some("prod")).is_err());
+        assert_eq!(
+            scope.resolve_namespace(Some("kube-system")).unwrap(),
+            Some("kube-system".to_string())
+        );
+        assert_eq!(
+            scope.scope_args(None, None, true).unwrap(),
+            vec!["--all-namespaces"]
+        );
+    }
+
+    #[test]
+    fn restricted_scope_defaults_to_first_entries() {
+        let args = scope().scope_args(None, None, false).unwrap();
+        assert_eq!(args, vec!["--context=prod", "--namespace=web"]);
+        let args = scope()
+            .scope_args(Some("staging"), Some("jobs"), false)
+            .unwrap();
+        assert_eq!(args, vec!["--context=staging", "--namespace=jobs"]);
+    }
+
+    #[test]
+    fn restricted_scope_rejects_other_contexts_and_namespaces() {
+        assert!(scope().scope_args(Some("dev"), None, false).is_err());
+        assert!(
+            scope()
+                .scope_args(None, Some("kube-system"), false)
+                .is_err()
+        );
+        assert!(scope().scope_args(None, None, true).is_err());
+    }
+
+    #[test]
+    fn get_args_builds_selector_and_output() {
+        let args = scope()
+            .get_args("pods", None, Some("app=web"), None, None, None, false)
+            .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "get",
+                "pods",
+                "--selector=app=web",
+                "--output=wide",
+                "--context=prod",
+                "--namespace=web"
+            ]
+        );
+        assert!(
+            scope()
+                .get_args("pods", None, None, Some("go-template"), None, None, false)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn secrets_are_blocked() {
+        for kind in ["secret", "secrets", "Secrets", "secrets.v1"] {
+            assert!(
+                scope()
+                    .get_args(kind, None, None, None, None, None, false)
+                    .is_err(),
+                "{kind} should be blocked"
+            );
+        }
+        assert!(scope().describe_args("secret", "db", None, None).is_err());
+        assert!(
+            scope()
+                .get_args("pods,secrets", None, None, None, None, None, false)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn names_cannot_smuggle_flags() {
+        assert!(scope().describe_args("pod", "--raw", None, None).is_err());
+        assert!(scope().describe_args("-f", "x", None, None).is_err());
+        assert!(
+            scope()
+                .logs_args("web-1", Some("--all"), 100, false, None, None, None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn logs_args_clamp_tail_and_validate_since() {
+        let args = scope()
+            .logs_args("web-1", Some("app"), 50_000, true, Some("15m"), None, None)
+            .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "logs",
+                "web-1",
+                "--container=app",
+                "--tail=2000",
+                "--previous",
+                "--since=15m",
+                "--context=prod",
+                "--namespace=web"
+            ]
+        );
+        assert!(
+            scope()
+                .logs_args("web-1", None, 10, false, Some("yesterday"), None, None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn events_args_filter_by_object() {
+        let args = scope()
+            .events_args(Some("web-1"), None, Some("jobs"), false)
+            .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "get",
+                "events",
+                "--sort-by=.lastTimestamp",
+                "--field-selector=involvedObject.name=web-1",
+                "--context=prod",
+                "--namespace=jobs"
+            ]
+        );
+    }
+
+    #[test]
+    fn action_args_restrict_kinds() {
+        let restart = KubernetesAction::RolloutRestart {
+            kind: "deployment".to_string(),
+            name: "web".to_string(),
+        };
+        assert_eq!(
+            scope().action_args(&restart, None, None).unwrap(),
+            vec![
+                "rollout",
+                "restart",
+                "deployment/web",
+                "--context=prod",
+                "--namespace=web"
+            ]
+        );
+        let bad_restart = KubernetesAction::RolloutRestart {
+            kind: "pod".to_string(),
+            name: "web".to_string(),
+        };
+        assert!(scope().action_args(&bad_restart, None, None).is_err());
+
+        let cordon = KubernetesAction::Cordon {
+            node: "node-1".to_string(),
+        };
+        assert_eq!(
+            scope().action_args(&cordon, Some("staging"), None).unwrap(),
+            vec!["cordon", "node-1", "--context=staging"]
+        );
+
+        let delete_secret = KubernetesAction::Delete {
+            kind: "secret".to_string(),
+            name: "db".to_string(),
+        };
+        assert!(scope().action_args(&delete_secret, None, None).is_err());
+    }
+
+    #[test]
+    fn truncate_output_respects_char_boundaries() {
+        let (text, truncated) = truncate_output("héllo".as_bytes(), 2);
+        assert_eq!(text, "h");
+        assert!(truncated);
+        let (text, truncated) = truncate_output(b"ok", 10);
+        assert_eq!(text, "ok");
+        assert!(!truncated);
+    }
+}
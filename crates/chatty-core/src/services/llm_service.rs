@@ -5,13 +5,19 @@ use rig_core::OneOrMany;
 use rig_core::completion::Message;
 use rig_core::message::UserContent;
 use rig_core::streaming::StreamingPrompt;
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 use crate::factories::AgentClient;
-use crate::models::execution_approval_store::{ApprovalNotification, ApprovalResolution};
+use crate::models::execution_approval_store::{
+    ApprovalNotification, ApprovalResolution, UserQuestion,
+};
 
 /// Stream chunks emitted during responses
-#[derive(Debug, Clone)]
+///
+/// Serializable so `flow_recorder` can store and replay whole turns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum StreamChunk {
     Text(String),
     ToolCallStarted {
@@ -34,10 +40,13 @@ pub enum StreamChunk {
         id: String,
         command: String,
         is_sandboxed: bool,
+        /// Set when the agent asks the user a question (ask_user tool)
+        question: Option<UserQuestion>,
     },
     ApprovalResolved {
         id: String,
         approved: bool,
+        answer: Option<String>,
     },
     TokenUsage {
         input_tokens: u32,
@@ -287,6 +296,7 @@ macro_rules! process_agent_stream_with_approvals {
                             id: approval.id,
                             command: approval.command,
                             is_sandboxed: approval.is_sandboxed,
+                            question: approval.question,
                         });
                     }
 
@@ -301,6 +311,7 @@ macro_rules! process_agent_stream_with_approvals {
                         yield Ok(StreamChunk::ApprovalResolved {
                             id: resolution.id,
                             approved: resolution.approved,
+                            answer: resolution.answer,
                         });
                     }
                 }
@@ -328,10 +339,19 @@ pub async fn stream_prompt(
     resolution_rx: Option<mpsc::UnboundedReceiver<ApprovalResolution>>,
     max_agent_turns: usize,
 ) -> Result<(ResponseStream, Message)> {
+    let recorder = super::flow_recorder::flow_recorder();
+    let prompt = recorder.map(|_| super::message_orchestrator::extract_user_text(&contents));
     let user_message = Message::User {
         content: OneOrMany::many(contents).context("Failed to create message from contents")?,
     };
 
+    // Replay mode: serve the recorded turn without contacting the provider
+    if let (Some(recorder), Some(prompt)) = (recorder, prompt.as_deref())
+        && let Some(stream) = recorder.replay(prompt)
+    {
+        return Ok((stream?, user_message));
+    }
+
     let history_snapshot = history.to_vec();
 
     let stream: ResponseStream = match agent {
@@ -376,5 +396,10 @@ pub async fn stream_prompt(
         }
     };
 
+    let stream = match (recorder, prompt.as_deref()) {
+        (Some(recorder), Some(prompt)) => recorder.record(stream, prompt, history.len()),
+        _ => stream,
+    };
+
     Ok((stream, user_message))
 }
@@ -0,0 +1,202 @@
+//! Admin-provided ("managed") team configuration.
+//!
+//! When `CHATTY_MANAGED_CONFIG_URL` is set, the JSON document at that HTTPS
+//! URL supplies recommended providers, models, MCP servers and guardrail
+//! policies. The response must carry an `X-Chatty-Signature` header: a
+//! Base64 Ed25519 signature over the hex SHA-256 of the body, made with the
+//! key whose public half is in `CHATTY_MANAGED_CONFIG_PUBLIC_KEY` (the same
+//! scheme Hive uses for modules). Unsigned or tampered documents are rejected.
+//!
+//! The document may also define roles that block native tool groups or limit
+//! writes to some directories; `CHATTY_MANAGED_ROLE` selects the user's role.
+//! Blocked groups are switched off before the agent's tool registry is built,
+//! so the model never sees those tools.
+//!
+//! The last verified document is cached so the managed layer still applies
+//! offline. It is loaded once per process; the host merges it over local
+//! settings and shows it read-only in the settings window.
+
+use sha2::{Digest, Sha256};
+use tokio::sync::OnceCell;
+use tracing::{info, warn};
+
+use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use crate::settings::models::managed_config::{
+    MANAGED_ROLE_ENV, ManagedConfig, ManagedConfigCache, ManagedConfigModel, ManagedConfigSource,
+    ManagedConfigStatus,
+};
+
+/// Response header carrying the Base64 Ed25519 signature.
+pub const SIGNATURE_HEADER: &str = "x-chatty-signature";
+
+static MANAGED_CONFIG: OnceCell<ManagedConfigModel> = OnceCell::const_new();
+
+/// Load the managed layer, fetching it on first call. Later calls return the
+/// same result.
+pub async fn load() -> &'static ManagedConfigModel {
+    MANAGED_CONFIG.get_or_init(resolve).await
+}
+
+/// The managed layer if [`load`] has completed.
+pub fn current() -> Option<&'static ManagedConfigModel> {
+    MANAGED_CONFIG.get()
+}
+
+/// Guardrail rules the managed layer enforces on every agent.
+pub fn enforced_guardrail_rules() -> Vec<crate::services::GuardrailRule> {
+    current()
+        .map(ManagedConfigModel::enforced_guardrail_rules)
+        .unwrap_or_default()
+}
+
+/// Turn off the tool groups blocked for the user's managed role.
+pub fn restrict_tools(settings: &mut ExecutionSettingsModel) {
+    if let Some(model) = current() {
+        model.restrict_tools(settings);
+    }
+}
+
+async fn resolve() -> ManagedConfigModel {
+    let source = match ManagedConfigSource::from_env() {
+        None => return ManagedConfigModel::default(),
+        Some(Ok(source)) => source,
+        Some(Err(e)) => {
+            warn!(error = %e, "Managed config source is invalid");
+            return ManagedConfigModel {
+                status: ManagedConfigStatus::Failed(e),
+                ..Default::default()
+            };
+        }
+    };
+
+    let requested_role = std::env::var(MANAGED_ROLE_ENV)
+        .ok()
+        .filter(|role| !role.trim().is_empty());
+    let repo = crate::managed_config_cache_repository();
+    let error = match fetch(&source).await {
+        Ok((config, cache)) => {
+            info!(url = %source.url, "Managed config fetched and verified");
+            let fetched_at = cache.fetched_at;
+            if let Err(e) = repo.save(cache).await {
+                warn!(error = ?e, "Failed to cache managed config");
+            }
+            return ManagedConfigModel {
+                source_url: Some(source.url),
+                status: ManagedConfigStatus::Verified { fetched_at },
+                config,
+                requested_role,
+                overridden: Vec::new(),
+            };
+        }
+        Err(e) => e,
+    };
+
+    warn!(url = %source.url, error = %error, "Managed config fetch failed");
+    let cached = repo
+        .load()
+        .await
+        .ok()
+        .filter(|cache| cache.url == source.url)
+        .and_then(|cache| {
+            verify_and_parse(&cache.body, &cache.signature, &source.public_key)
+                .ok()
+                .map(|config| (config, cache.fetched_at))
+        });
+    let (config, status) = match cached {
+        Some((config, fetched_at)) => (config, ManagedConfigStatus::Cached { fetched_at, error }),
+        None => (ManagedConfig::default(), ManagedConfigStatus::Failed(error)),
+    };
+    ManagedConfigModel {
+        source_url: Some(source.url),
+        status,
+        config,
+        requested_role,
+        overridden: Vec::new(),
+    }
+}
+
+async fn fetch(
+    source: &ManagedConfigSource,
+) -> Result<(ManagedConfig, ManagedConfigCache), String> {
+    let response = crate::services::http_client::default_client(15)
+        .get(&source.url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?;
+    // Redirects are followed, but never down to plain HTTP.
+    if response.url().scheme() != "https" {
+        return Err(format!("Redirected to a non-HTTPS URL: {}", response.url()));
+    }
+    let signature = response
+        .headers()
+        .get(SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+        .ok_or_else(|| format!("Response has no {SIGNATURE_HEADER} header"))?;
+    let body = response.text().await.map_err(|e| e.to_string())?;
+
+    let config = verify_and_parse(&body, &signature, &source.public_key)?;
+    let fetched_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    Ok((
+        config,
+        ManagedConfigCache {
+            url: source.url.clone(),
+            body,
+            signature,
+            fetched_at,
+        },
+    ))
+}
+
+/// Check the signature over `body` and parse it as a [`ManagedConfig`].
+pub fn verify_and_parse(
+    body: &str,
+    signature: &str,
+    public_key: &str,
+) -> Result<ManagedConfig, String> {
+    crate::hive::verify::verify_module(&crate::hive::verify::VerifyInput {
+        wasm_hash: hex::encode(Sha256::digest(body.as_bytes())),
+        signature: signature.trim().to_string(),
+        publisher_public_key: public_key.to_string(),
+    })
+    .map_err(|e| format!("Managed config signature invalid: {e}"))?;
+    serde_json::from_str(body).map_err(|e| format!("Managed config is not valid: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn sign(key: &SigningKey, body: &str) -> String {
+        let hash = hex::encode(Sha256::digest(body.as_bytes()));
+        BASE64.encode(key.sign(hash.as_bytes()).to_bytes())
+    }
+
+    #[test]
+    fn verifies_signed_config_and_rejects_tampering() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = hex::encode(key.verifying_key().to_bytes());
+        let body = r#"{"mcp_servers":[{"name":"team-docs","url":"https://mcp.example.com/mcp"}]}"#;
+        let signature = sign(&key, body);
+
+        let config = verify_and_parse(body, &signature, &public_key).unwrap();
+        assert_eq!(config.mcp_servers[0].name, "team-docs");
+
+        let tampered = body.replace("team-docs", "evil-docs");
+        assert!(verify_and_parse(&tampered, &signature, &public_key).is_err());
+
+        let other_key = hex::encode(
+            SigningKey::from_bytes(&[8u8; 32])
+                .verifying_key()
+                .to_bytes(),
+        );
+        assert!(verify_and_parse(body, &signature, &other_key).is_err());
+    }
+}
@@ -1,14 +1,20 @@
 use anyhow::{Context, Result};
+use rmcp::model::{CallToolRequestParams, CallToolResult};
+use rmcp::service::ServerSink;
 use rmcp::service::ServiceExt;
-use rmcp::transport::StreamableHttpClientTransport;
 use rmcp::transport::streamable_http_client::StreamableHttpClientTransportConfig;
+use rmcp::transport::{StreamableHttpClientTransport, TokioChildProcess};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{RwLock, watch};
 use tracing::{debug, error, info, warn};
 
 use crate::services::mcp_token_store::FileCredentialStore;
-use crate::settings::models::mcp_store::McpServerConfig;
+use crate::settings::models::mcp_store::{
+    McpCachedTools, McpLaunchConfig, McpServerConfig, McpToolCache,
+};
+use crate::tools::custom_http_tool::substitute_secrets;
 
 /// Represents an active MCP server connection
 pub struct McpConnection {
@@ -23,14 +29,20 @@ pub struct McpConnection {
 }
 
 impl McpConnection {
-    /// Connect to an already-running MCP server via its HTTP endpoint.
+    /// Connect to an already-running MCP server via its HTTP endpoint, or
+    /// start it when it has a launch command.
     ///
     /// If the server requires OAuth authentication, an interactive browser-based
     /// flow is initiated automatically.
     pub async fn connect(config: McpServerConfig) -> Result<Self> {
+        let mut config = Self::resolve_secrets(config).await?;
         let name = config.name.clone();
         let url = config.url.clone();
 
+        if let Some(launch) = config.launch.take() {
+            return Self::launch(name, launch).await;
+        }
+
         info!(
             server = %name,
             url = %url,
@@ -613,6 +625,53 @@ impl McpConnection {
         Ok(())
     }
 
+    /// Fill `{{secret:KEY}}` references in the config from the user's secrets
+    async fn resolve_secrets(mut config: McpServerConfig) -> Result<McpServerConfig> {
+        let secrets: HashMap<String, String> = if crate::is_initialized() {
+            crate::user_secrets_repository()
+                .load()
+                .await
+                .map(|model| model.as_env_pairs().into_iter().collect())
+                .unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+        let resolve = |value: &mut String| -> Result<()> {
+            *value = substitute_secrets(value, &secrets).map_err(anyhow::Error::msg)?;
+            Ok(())
+        };
+
+        if let Some(key) = config.api_key.as_mut() {
+            resolve(key)?;
+        }
+        if let Some(launch) = config.launch.as_mut() {
+            launch.args.iter_mut().try_for_each(resolve)?;
+            launch.env.values_mut().try_for_each(resolve)?;
+        }
+        Ok(config)
+    }
+
+    /// Start a stdio MCP server process and connect to it.
+    async fn launch(name: String, launch: McpLaunchConfig) -> Result<Self> {
+        info!(server = %name, command = %launch.command, "Launching MCP server");
+
+        let mut command = tokio::process::Command::new(&launch.command);
+        command.args(&launch.args).envs(&launch.env);
+        let transport = TokioChildProcess::new(command)
+            .with_context(|| format!("Failed to start MCP server command: {}", launch.command))?;
+        let service = ()
+            .serve(transport)
+            .await
+            .with_context(|| format!("MCP server did not initialize: {name}"))?;
+
+        info!(server = %name, info = ?service.peer_info(), "MCP server connected");
+        Ok(Self {
+            name,
+            service,
+            cached_tools: None,
+        })
+    }
+
     /// List available tools from this MCP server, using cache when available
     pub async fn list_tools(&mut self) -> Result<Vec<rmcp::model::Tool>> {
         if let Some(ref cached) = self.cached_tools {
@@ -643,11 +702,58 @@ impl McpConnection {
     }
 }
 
-/// Global service for managing MCP server connections
+/// Tools per MCP server, each with the service used to call them.
+pub type McpToolSet = Vec<(String, Vec<rmcp::model::Tool>, McpService)>;
+
+/// A tool result kept for servers with a result cache TTL.
+#[derive(Clone)]
+struct CachedResult {
+    stored: Instant,
+    result: CallToolResult,
+}
+
+/// Lifecycle of a registered MCP server connection.
+#[derive(Clone, Debug, PartialEq)]
+pub enum McpServerState {
+    /// Registered but not connected; connects on the next tool use.
+    Stopped,
+    Starting,
+    Running,
+    Failed(String),
+}
+
+/// Global service for managing MCP server connections.
+///
+/// Servers are registered at launch and connected lazily: when an agent needs
+/// a tool list that is not cached from an earlier connection, or when one of
+/// the server's tools is called. Callers wait for the connection
+/// transparently. [`Self::stop_idle`] disconnects servers nobody is using;
+/// they reconnect on their next tool call.
 #[derive(Clone)]
 pub struct McpService {
     /// Active connections keyed by server name
     connections: Arc<RwLock<HashMap<String, McpConnection>>>,
+
+    /// Enabled server configs, connected on demand
+    configs: Arc<RwLock<HashMap<String, McpServerConfig>>>,
+
+    /// Last known tool list per server, kept while the server is stopped
+    known_tools: Arc<RwLock<HashMap<String, McpCachedTools>>>,
+
+    /// One lock per server so concurrent callers share a single connect
+    start_locks: Arc<parking_lot::Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>,
+
+    /// When each server's tools were last listed or called
+    last_used: Arc<parking_lot::Mutex<HashMap<String, Instant>>>,
+
+    /// Tool calls currently running per server; busy servers are never idle
+    in_flight: Arc<parking_lot::Mutex<HashMap<String, usize>>>,
+
+    /// State of every registered server, for status indicators
+    states: Arc<watch::Sender<HashMap<String, McpServerState>>>,
+
+    /// Tool results per server, keyed by tool name and arguments
+    result_cache: Arc<parking_lot::Mutex<HashMap<String, HashMap<String, CachedResult>>>>,
 }
 
 impl McpService {
@@ -655,12 +761,105 @@ impl McpService {
     pub fn new() -> Self {
         Self {
             connections: Arc::new(RwLock::new(HashMap::new())),
+            configs: Arc::new(RwLock::new(HashMap::new())),
+            known_tools: Arc::new(RwLock::new(HashMap::new())),
+            start_locks: Arc::default(),
+            last_used: Arc::default(),
+            in_flight: Arc::default(),
+            states: Arc::new(watch::channel(HashMap::new()).0),
+            result_cache: Arc::default(),
         }
     }
 
-    /// Connect to a single MCP server by URL.
+    /// Current state of every registered server.
+    pub fn states(&self) -> HashMap<String, McpServerState> {
+        self.states.borrow().clone()
+    }
+
+    /// Receive a notification whenever a server changes state.
+    pub fn subscribe_states(&self) -> watch::Receiver<HashMap<String, McpServerState>> {
+        self.states.subscribe()
+    }
+
+    fn set_state(&self, name: &str, state: McpServerState) {
+        self.states.send_modify(|states| {
+            states.insert(name.to_string(), state);
+        });
+    }
+
+    fn touch(&self, name: &str) {
+        self.last_used
+            .lock()
+            .insert(name.to_string(), Instant::now());
+    }
+
+    /// Count a call to `name` as running until the guard is dropped.
+    fn begin_call(&self, name: &str) -> InFlightCall {
+        *self.in_flight.lock().entry(name.to_string()).or_default() += 1;
+        InFlightCall {
+            counts: self.in_flight.clone(),
+            server: name.to_string(),
+        }
+    }
+
+    fn start_lock(&self, name: &str) -> Arc<tokio::sync::Mutex<()>> {
+        self.start_locks
+            .lock()
+            .entry(name.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Register the enabled servers without connecting to them. Tool lists
+    /// cached by an earlier launch are reused, so those servers stay stopped
+    /// until one of their tools is called.
+    pub async fn register_all(&self, configs: Vec<McpServerConfig>) {
+        let cache = if crate::is_initialized() {
+            crate::mcp_tool_cache_repository()
+                .load()
+                .await
+                .unwrap_or_else(|e| {
+                    warn!(error = ?e, "Failed to load MCP tool cache");
+                    McpToolCache::default()
+                })
+        } else {
+            McpToolCache::default()
+        };
+
+        let mut registered = self.configs.write().await;
+        let mut known = self.known_tools.write().await;
+        for config in configs {
+            if !config.enabled {
+                debug!(server = %config.name, "Skipping disabled MCP server");
+                continue;
+            }
+            if let Some(cached) = cache.servers.get(&config.name)
+                && cached.url == config.url
+            {
+                known
+                    .entry(config.name.clone())
+                    .or_insert_with(|| cached.clone());
+            }
+            let has_state = self.states.borrow().contains_key(&config.name);
+            if !has_state {
+                self.set_state(&config.name, McpServerState::Stopped);
+            }
+            registered.insert(config.name.clone(), config);
+        }
+        info!(count = registered.len(), "MCP servers registered");
+    }
+
+    /// Connect to a single MCP server by URL and register it for reconnects.
     pub async fn connect_server(&self, config: McpServerConfig) -> Result<()> {
         let name = config.name.clone();
+        let previous = self
+            .configs
+            .write()
+            .await
+            .insert(name.clone(), config.clone());
+        if previous.is_some_and(|p| p.url != config.url) {
+            self.invalidate_cached_results(Some(&name));
+        }
 
         // Check if already connected
         {
@@ -671,17 +870,75 @@ impl McpService {
             }
         }
 
-        let connection = McpConnection::connect(config).await?;
+        self.set_state(&name, McpServerState::Starting);
+        let mut connection = match McpConnection::connect(config.clone()).await {
+            Ok(connection) => connection,
+            Err(e) => {
+                self.set_state(&name, McpServerState::Failed(format!("{e:#}")));
+                return Err(e);
+            }
+        };
+
+        // Refresh the known tool list while connected
+        match connection.list_tools().await {
+            Ok(tools) => self.remember_tools(&name, &config.url, tools).await,
+            Err(e) => warn!(server = %name, error = ?e, "Failed to list tools after connecting"),
+        }
 
         {
             let mut connections = self.connections.write().await;
             connections.insert(name.clone(), connection);
         }
+        self.touch(&name);
+        self.set_state(&name, McpServerState::Running);
 
         info!(server = %name, "MCP server connected successfully");
         Ok(())
     }
 
+    /// Store a server's tool list in memory and in the on-disk cache.
+    async fn remember_tools(&self, name: &str, url: &str, tools: Vec<rmcp::model::Tool>) {
+        let cache = {
+            let mut known = self.known_tools.write().await;
+            known.insert(
+                name.to_string(),
+                McpCachedTools {
+                    url: url.to_string(),
+                    tools,
+                },
+            );
+            McpToolCache {
+                servers: known.clone(),
+            }
+        };
+        if crate::is_initialized()
+            && let Err(e) = crate::mcp_tool_cache_repository().save(cache).await
+        {
+            warn!(error = ?e, "Failed to save MCP tool cache");
+        }
+    }
+
+    /// Register `config` with a known tool list without connecting, for the
+    /// test fixtures. The list is not written to the tool cache.
+    pub(crate) async fn register_with_tools(
+        &self,
+        config: McpServerConfig,
+        tools: Vec<rmcp::model::Tool>,
+    ) {
+        self.known_tools.write().await.insert(
+            config.name.clone(),
+            McpCachedTools {
+                url: config.url.clone(),
+                tools,
+            },
+        );
+        self.set_state(&config.name, McpServerState::Stopped);
+        self.configs
+            .write()
+            .await
+            .insert(config.name.clone(), config);
+    }
+
     /// Delete stored OAuth credentials for a server.
     /// Call this when a server is removed from settings.
     pub async fn delete_server_credentials(server_name: &str) {
@@ -693,8 +950,15 @@ impl McpService {
         FileCredentialStore::has_credentials(server_name)
     }
 
-    /// Disconnect from a single MCP server.
+    /// Disconnect from a single MCP server and unregister it, so it is not
+    /// reconnected on demand.
     pub async fn disconnect_server(&self, name: &str) -> Result<()> {
+        self.configs.write().await.remove(name);
+        self.invalidate_cached_results(Some(name));
+        self.states.send_modify(|states| {
+            states.remove(name);
+        });
+
         let connection = {
             let mut connections = self.connections.write().await;
             connections.remove(name)
@@ -710,6 +974,200 @@ impl McpService {
         Ok(())
     }
 
+    /// Disconnect a server but keep it registered; its next tool call reconnects.
+    async fn stop_server(&self, name: &str) -> Result<()> {
+        let connection = {
+            let mut connections = self.connections.write().await;
+            connections.remove(name)
+        };
+        self.set_state(name, McpServerState::Stopped);
+        if let Some(connection) = connection {
+            connection.disconnect().await?;
+        }
+        Ok(())
+    }
+
+    /// Disconnect servers whose tools have not been listed or called within
+    /// `idle` and that have no call in flight. Returns the names of the
+    /// servers stopped.
+    pub async fn stop_idle(&self, idle: Duration) -> Vec<String> {
+        let connected: Vec<String> = {
+            let connections = self.connections.read().await;
+            connections.keys().cloned().collect()
+        };
+        let mut stopped = Vec::new();
+        for name in self.idle_among(connected, idle) {
+            // Don't race a caller that is reconnecting the server
+            let lock = self.start_lock(&name);
+            let _guard = lock.lock().await;
+            // A call may have started while waiting for the lock
+            if self.idle_among(vec![name.clone()], idle).is_empty() {
+                continue;
+            }
+            info!(server = %name, "Stopping idle MCP server");
+            if let Err(e) = self.stop_server(&name).await {
+                warn!(server = %name, error = ?e, "Failed to stop idle MCP server");
+            }
+            stopped.push(name);
+        }
+        stopped
+    }
+
+    /// The servers in `names` unused for `idle` and with no call in flight.
+    fn idle_among(&self, names: Vec<String>, idle: Duration) -> Vec<String> {
+        let last_used = self.last_used.lock();
+        let in_flight = self.in_flight.lock();
+        names
+            .into_iter()
+            .filter(|name| last_used.get(name).is_none_or(|t| t.elapsed() >= idle))
+            .filter(|name| !in_flight.contains_key(name))
+            .collect()
+    }
+
+    async fn running_sink(&self, name: &str) -> Option<ServerSink> {
+        let connections = self.connections.read().await;
+        connections
+            .get(name)
+            .map(|connection| connection.service.peer().clone())
+    }
+
+    /// Peer for a registered server, connecting it first if it is stopped.
+    /// Concurrent callers wait for the same connection attempt.
+    pub async fn ensure_running(&self, name: &str) -> Result<ServerSink> {
+        if let Some(sink) = self.running_sink(name).await {
+            self.touch(name);
+            return Ok(sink);
+        }
+
+        let lock = self.start_lock(name);
+        let _guard = lock.lock().await;
+        if let Some(sink) = self.running_sink(name).await {
+            self.touch(name);
+            return Ok(sink);
+        }
+
+        let config = self
+            .configs
+            .read()
+            .await
+            .get(name)
+            .cloned()
+            .with_context(|| format!("MCP server is not enabled: {name}"))?;
+        info!(server = %name, "Starting MCP server on first use");
+        self.connect_server(config).await?;
+        self.running_sink(name)
+            .await
+            .with_context(|| format!("MCP server disconnected during startup: {name}"))
+    }
+
+    /// Call a tool on `server`, connecting the server first if needed.
+    pub async fn call_tool(
+        &self,
+        server: &str,
+        params: CallToolRequestParams,
+    ) -> Result<CallToolResult> {
+        let _call = self.begin_call(server);
+        let sink = self.ensure_running(server).await?;
+        let result = sink
+            .call_tool(params)
+            .await
+            .with_context(|| format!("MCP call_tool failed on server: {server}"));
+        self.touch(server);
+        result
+    }
+
+    /// Call a tool like [`Self::call_tool`], answering from the server's result
+    /// cache when its `result_cache_ttl_secs` is set. Returns the result and
+    /// whether it came from the cache.
+    ///
+    /// Error results are never cached. Only tools the server annotates as
+    /// read-only, or that the user listed in `cached_tools`, are answered from
+    /// the cache; other tools always reach the server and clear its cache,
+    /// since they may change what the other tools return.
+    pub async fn call_tool_cached(
+        &self,
+        server: &str,
+        params: CallToolRequestParams,
+    ) -> Result<(CallToolResult, bool)> {
+        let (ttl, opted_in) = self
+            .configs
+            .read()
+            .await
+            .get(server)
+            .map(|c| {
+                (
+                    Duration::from_secs(c.result_cache_ttl_secs),
+                    c.cached_tools
+                        .iter()
+                        .any(|t| t.as_str() == params.name.as_ref()),
+                )
+            })
+            .unwrap_or_default();
+        if ttl.is_zero() {
+            return Ok((self.call_tool(server, params).await?, false));
+        }
+
+        let cacheable = opted_in || {
+            let known = self.known_tools.read().await;
+            known
+                .get(server)
+                .and_then(|k| k.tools.iter().find(|t| t.name == params.name))
+                .is_some_and(is_cacheable)
+        };
+        if !cacheable {
+            let result = self.call_tool(server, params).await?;
+            self.invalidate_cached_results(Some(server));
+            return Ok((result, false));
+        }
+
+        let key = result_cache_key(&params);
+        let hit = self
+            .result_cache
+            .lock()
+            .get(server)
+            .and_then(|entries| entries.get(&key))
+            .filter(|cached| cached.stored.elapsed() < ttl)
+            .map(|cached| cached.result.clone());
+        if let Some(result) = hit {
+            debug!(server = %server, tool = %params.name, "Using cached MCP tool result");
+            return Ok((result, true));
+        }
+
+        let result = self.call_tool(server, params).await?;
+        if !result.is_error.unwrap_or(false) {
+            let mut cache = self.result_cache.lock();
+            let entries = cache.entry(server.to_string()).or_default();
+            entries.retain(|_, cached| cached.stored.elapsed() < ttl);
+            entries.insert(
+                key,
+                CachedResult {
+                    stored: Instant::now(),
+                    result: result.clone(),
+                },
+            );
+        }
+        Ok((result, false))
+    }
+
+    /// Change a registered server's result cache TTL and drop its cached results.
+    pub async fn set_result_cache_ttl(&self, name: &str, secs: u64) {
+        if let Some(config) = self.configs.write().await.get_mut(name) {
+            config.result_cache_ttl_secs = secs;
+        }
+        self.invalidate_cached_results(Some(name));
+    }
+
+    /// Drop cached tool results for `server`, or for every server when `None`.
+    pub fn invalidate_cached_results(&self, server: Option<&str>) {
+        let mut cache = self.result_cache.lock();
+        match server {
+            Some(name) => {
+                cache.remove(name);
+            }
+            None => cache.clear(),
+        }
+    }
+
     /// Connect to all enabled servers from the given configurations concurrently.
     pub async fn connect_all(&self, configs: Vec<McpServerConfig>) -> Result<()> {
         let _ = self.connect_all_with_status(configs).await;
@@ -762,7 +1220,7 @@ impl McpService {
         results
     }
 
-    /// Disconnect from all connected servers
+    /// Disconnect from all connected servers. They stay registered.
     pub async fn disconnect_all(&self) -> Result<()> {
         let server_names: Vec<String> = {
             let connections = self.connections.read().await;
@@ -775,7 +1233,7 @@ impl McpService {
         );
 
         for name in server_names {
-            if let Err(e) = self.disconnect_server(&name).await {
+            if let Err(e) = self.stop_server(&name).await {
                 error!(
                     server = %name,
                     error = ?e,
@@ -787,47 +1245,86 @@ impl McpService {
         Ok(())
     }
 
-    /// Get all tools from all active servers, grouped by server with their ServerSinks.
+    /// Get the tools of all registered servers, each with the service used to
+    /// call them.
     ///
-    /// Tool lists are cached after the first successful fetch per server.
-    pub async fn get_all_tools_with_sinks(
-        &self,
-    ) -> Result<Vec<(String, Vec<rmcp::model::Tool>, rmcp::service::ServerSink)>> {
-        let mut connections = self.connections.write().await;
+    /// Servers without a known tool list are connected first (concurrently);
+    /// the others stay stopped until one of their tools is called.
+    pub async fn get_all_tools(&self) -> Result<McpToolSet> {
+        let mut configs: Vec<McpServerConfig> = {
+            let configs = self.configs.read().await;
+            configs.values().cloned().collect()
+        };
+        configs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let unknown: Vec<String> = {
+            let known = self.known_tools.read().await;
+            configs
+                .iter()
+                .filter(|c| known.get(&c.name).is_none_or(|k| k.url != c.url))
+                .map(|c| c.name.clone())
+                .collect()
+        };
+        futures::future::join_all(unknown.iter().map(|name| async move {
+            if let Err(e) = self.load_tools(name).await {
+                error!(
+                    server = %name,
+                    error = ?e,
+                    "Failed to list tools from MCP server"
+                );
+            }
+        }))
+        .await;
+
+        let known = self.known_tools.read().await;
         let mut result = Vec::new();
+        for config in configs {
+            if let Some(cached) = known.get(&config.name) {
+                info!(
+                    server = %config.name,
+                    tool_count = cached.tools.len(),
+                    "Retrieved tools from MCP server"
+                );
+                result.push((config.name, cached.tools.clone(), self.clone()));
+            }
+        }
 
-        for (name, connection) in connections.iter_mut() {
-            match connection.list_tools().await {
-                Ok(tools) => {
-                    let server_sink = connection.service.peer().clone();
-                    let tool_count = tools.len();
+        Ok(result)
+    }
 
-                    for tool in &tools {
-                        debug!(
-                            server = %name,
-                            tool_name = %tool.name,
-                            "Retrieved tool from MCP server"
-                        );
-                    }
+    /// Connect `name` if needed and record its tool list.
+    async fn load_tools(&self, name: &str) -> Result<()> {
+        self.ensure_running(name).await?;
+        let tools = {
+            let mut connections = self.connections.write().await;
+            let connection = connections
+                .get_mut(name)
+                .with_context(|| format!("MCP server disconnected: {name}"))?;
+            connection.list_tools().await?
+        };
+        let url = self.configs.read().await.get(name).map(|c| c.url.clone());
+        if let Some(url) = url {
+            self.remember_tools(name, &url, tools).await;
+        }
+        Ok(())
+    }
+}
 
-                    result.push((name.clone(), tools, server_sink));
-                    info!(
-                        server = %name,
-                        tool_count = tool_count,
-                        "Retrieved tools with ServerSink"
-                    );
-                }
-                Err(e) => {
-                    error!(
-                        server = %name,
-                        error = ?e,
-                        "Failed to list tools from MCP server"
-                    );
-                }
+/// Marks a tool call as running; see [`McpService::stop_idle`].
+struct InFlightCall {
+    counts: Arc<parking_lot::Mutex<HashMap<String, usize>>>,
+    server: String,
+}
+
+impl Drop for InFlightCall {
+    fn drop(&mut self) {
+        let mut counts = self.counts.lock();
+        if let Some(count) = counts.get_mut(&self.server) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&self.server);
             }
         }
-
-        Ok(result)
     }
 }
 
@@ -837,6 +1334,22 @@ impl Default for McpService {
     }
 }
 
+/// Whether a tool's results may be reused, judged by its annotations. Only
+/// tools the server marks read-only qualify; unannotated tools may have side
+/// effects and must be opted in by the user.
+fn is_cacheable(tool: &rmcp::model::Tool) -> bool {
+    tool.annotations.as_ref().is_some_and(|annotations| {
+        annotations.read_only_hint == Some(true) && annotations.destructive_hint != Some(true)
+    })
+}
+
+/// Cache key of a call. `serde_json` maps are sorted, so argument order
+/// doesn't matter.
+fn result_cache_key(params: &CallToolRequestParams) -> String {
+    let arguments = serde_json::to_string(&params.arguments).unwrap_or_default();
+    format!("{}\n{arguments}", params.name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -849,6 +1362,9 @@ mod tests {
             api_key: None,
             enabled: false,
             is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
         }
     }
 
@@ -858,7 +1374,7 @@ mod tests {
     fn test_new_service_has_no_connections() {
         let svc = McpService::new();
         // A freshly created service should have no active connections
-        // (verified by get_all_tools_with_sinks returning empty in async tests)
+        // (verified by get_all_tools returning empty in async tests)
         let _ = svc.connections.try_read().is_ok();
     }
 
@@ -898,12 +1414,12 @@ mod tests {
         assert!(result.is_ok());
     }
 
-    // --- get_all_tools_with_sinks with no connections ---
+    // --- get_all_tools with no connections ---
 
     #[tokio::test]
     async fn test_get_all_tools_no_connections_returns_empty() {
         let svc = McpService::new();
-        let result = svc.get_all_tools_with_sinks().await;
+        let result = svc.get_all_tools().await;
         assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
     }
@@ -929,6 +1445,9 @@ mod tests {
                 api_key: None,
                 enabled: true,
                 is_module: false,
+                result_cache_ttl_secs: 0,
+                cached_tools: Vec::new(),
+                launch: None,
             },
             McpServerConfig {
                 name: "bad-2".to_string(),
@@ -936,6 +1455,9 @@ mod tests {
                 api_key: None,
                 enabled: true,
                 is_module: false,
+                result_cache_ttl_secs: 0,
+                cached_tools: Vec::new(),
+                launch: None,
             },
         ];
 
@@ -944,13 +1466,163 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    // --- Lazy start ---
+
+    #[tokio::test]
+    async fn test_register_all_does_not_connect() {
+        let svc = McpService::new();
+        let lazy = McpServerConfig {
+            enabled: true,
+            ..disabled_config("lazy")
+        };
+        svc.register_all(vec![lazy, disabled_config("off")]).await;
+
+        assert!(svc.connections.read().await.is_empty());
+        let states = svc.states();
+        assert_eq!(states.get("lazy"), Some(&McpServerState::Stopped));
+        assert!(!states.contains_key("off"));
+
+        svc.disconnect_server("lazy").await.unwrap();
+        assert!(svc.states().is_empty());
+        assert!(svc.ensure_running("lazy").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stop_idle_without_connections_is_noop() {
+        let svc = McpService::new();
+        assert!(svc.stop_idle(Duration::ZERO).await.is_empty());
+    }
+
+    #[test]
+    fn test_servers_with_calls_in_flight_are_not_idle() {
+        let svc = McpService::new();
+        let names = || vec!["slow".to_string(), "quiet".to_string()];
+
+        let call = svc.begin_call("slow");
+        let nested = svc.begin_call("slow");
+        assert_eq!(svc.idle_among(names(), Duration::ZERO), vec!["quiet"]);
+        drop(call);
+        assert_eq!(svc.idle_among(names(), Duration::ZERO), vec!["quiet"]);
+        drop(nested);
+        assert_eq!(svc.idle_among(names(), Duration::ZERO), names());
+    }
+
+    // --- Result cache ---
+
+    fn tool(annotations: serde_json::Value) -> rmcp::model::Tool {
+        serde_json::from_value(serde_json::json!({
+            "name": "read_docs",
+            "inputSchema": { "type": "object" },
+            "annotations": annotations,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_is_cacheable_respects_annotations() {
+        assert!(!is_cacheable(&tool(serde_json::json!({}))));
+        assert!(is_cacheable(&tool(
+            serde_json::json!({ "readOnlyHint": true })
+        )));
+        assert!(!is_cacheable(&tool(
+            serde_json::json!({ "readOnlyHint": false })
+        )));
+        assert!(!is_cacheable(&tool(
+            serde_json::json!({ "destructiveHint": true })
+        )));
+    }
+
+    #[test]
+    fn test_result_cache_key_ignores_argument_order() {
+        let args = |json: serde_json::Value| {
+            CallToolRequestParams::new("read_docs")
+                .with_arguments(json.as_object().unwrap().clone())
+        };
+        assert_eq!(
+            result_cache_key(&args(serde_json::json!({ "a": 1, "b": 2 }))),
+            result_cache_key(&args(serde_json::json!({ "b": 2, "a": 1 }))),
+        );
+        assert_ne!(
+            result_cache_key(&args(serde_json::json!({ "a": 1 }))),
+            result_cache_key(&args(serde_json::json!({ "a": 2 }))),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_call_tool_cached_uses_fresh_entry_without_connecting() {
+        let svc = McpService::new();
+        let config = McpServerConfig {
+            enabled: true,
+            result_cache_ttl_secs: 60,
+            cached_tools: vec!["read_docs".to_string()],
+            ..disabled_config("docs")
+        };
+        svc.register_all(vec![config]).await;
+
+        let params = CallToolRequestParams::new("read_docs");
+        svc.result_cache
+            .lock()
+            .entry("docs".to_string())
+            .or_default()
+            .insert(
+                result_cache_key(&params),
+                CachedResult {
+                    stored: Instant::now(),
+                    result: CallToolResult::success(vec![rmcp::model::Content::text("cached")]),
+                },
+            );
+
+        let (result, cached) = svc.call_tool_cached("docs", params).await.unwrap();
+        assert!(cached);
+        assert_eq!(result.is_error, Some(false));
+        assert!(svc.connections.read().await.is_empty());
+
+        svc.invalidate_cached_results(Some("docs"));
+        assert!(svc.result_cache.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_call_tool_cached_skips_cache_for_unannotated_tools() {
+        let svc = McpService::new();
+        let config = McpServerConfig {
+            enabled: true,
+            url: "http://127.0.0.1:1/mcp".to_string(),
+            result_cache_ttl_secs: 60,
+            ..disabled_config("docs")
+        };
+        svc.register_all(vec![config]).await;
+        svc.known_tools.write().await.insert(
+            "docs".to_string(),
+            McpCachedTools {
+                url: "http://127.0.0.1:1/mcp".to_string(),
+                tools: vec![tool(serde_json::json!(null))],
+            },
+        );
+
+        let params = CallToolRequestParams::new("read_docs");
+        svc.result_cache
+            .lock()
+            .entry("docs".to_string())
+            .or_default()
+            .insert(
+                result_cache_key(&params),
+                CachedResult {
+                    stored: Instant::now(),
+                    result: CallToolResult::success(vec![rmcp::model::Content::text("cached")]),
+                },
+            );
+
+        // The call goes to the (unreachable) server instead of the cache
+        assert!(svc.call_tool_cached("docs", params).await.is_err());
+    }
+
     // --- Tool cache: get_all_tools idempotent with no connections ---
 
     #[tokio::test]
     async fn test_get_all_tools_idempotent_no_connections() {
         let svc = McpService::new();
-        let r1 = svc.get_all_tools_with_sinks().await.unwrap();
-        let r2 = svc.get_all_tools_with_sinks().await.unwrap();
+        let r1 = svc.get_all_tools().await.unwrap();
+        let r2 = svc.get_all_tools().await.unwrap();
         assert_eq!(r1.len(), r2.len());
     }
 }
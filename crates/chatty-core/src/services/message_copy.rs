@@ -0,0 +1,819 @@
+//! Clipboard text for assistant messages.
+//!
+//! [`format_for_copy`] turns a message's Markdown into the [`CopyFormat`]
+//! picked in the copy menu or the general settings. Plain text and HTML come
+//! from a small converter that covers what models write — headings,
+//! paragraphs, nested lists, block quotes, fenced code, tables, emphasis,
+//! code spans, links and images — rather than the whole CommonMark spec.
+
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+use crate::services::citation_service::resolve_citekeys;
+use crate::settings::models::CopyFormat;
+
+static THINKING_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)<think>.*?</think>|<thinking>.*?</thinking>|<thought>.*?</thought>").unwrap()
+});
+
+/// Pandoc-style citation groups: `[@smith2020]`, `[see @a; @b, p. 4]`.
+static CITEKEY_GROUP_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[ \t]?\[[^\[\]]*@[A-Za-z0-9][^\[\]]*\]").unwrap());
+
+/// Numeric source markers as written after web-search answers: `[1]`, `[1, 3]`, `[2][4]`.
+static NUMERIC_CITATION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(^|[\s.,;:!?)])(?:\[\d+(?:\s*[,–-]\s*\d+)*\])+").unwrap());
+
+static FOOTNOTE_REF_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[\^[\w-]+\]").unwrap());
+
+static FOOTNOTE_DEF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^\[\^[\w-]+\]:.*(?:\n[ \t]+.*)*\n?").unwrap());
+
+/// Render `content` (an assistant message's Markdown) for the clipboard.
+pub fn format_for_copy(content: &str, format: CopyFormat) -> String {
+    match format {
+        CopyFormat::Markdown => content.to_string(),
+        CopyFormat::Clean => strip_citations_and_traces(content),
+        CopyFormat::PlainText => {
+            let source = resolve_citekeys(&strip_thinking(content)).into_owned();
+            clipboard_safe(&render_plain(&parse_blocks(&lines_of(&source))))
+        }
+        CopyFormat::Html => {
+            let source = resolve_citekeys(&strip_thinking(content)).into_owned();
+            render_html(&parse_blocks(&lines_of(&source)))
+        }
+    }
+}
+
+fn strip_thinking(content: &str) -> String {
+    THINKING_RE.replace_all(content, "").trim().to_string()
+}
+
+/// The Markdown source without thinking blocks, footnotes and citation
+/// markers. Fenced code and code spans are left alone, so `[1]` in a code
+/// sample survives.
+fn strip_citations_and_traces(content: &str) -> String {
+    let without_thinking = strip_thinking(content);
+    let mut out = String::new();
+    let mut prose = String::new();
+    let mut fence: Option<String> = None;
+    for line in without_thinking.split_inclusive('\n') {
+        match &fence {
+            Some(open) => {
+                out.push_str(line);
+                if closes_fence(line, open) {
+                    fence = None;
+                }
+            }
+            None => match fence_marker(line) {
+                Some(marker) => {
+                    out.push_str(&strip_citations(&std::mem::take(&mut prose)));
+                    out.push_str(line);
+                    fence = Some(marker);
+                }
+                None => prose.push_str(line),
+            },
+        }
+    }
+    out.push_str(&strip_citations(&prose));
+    out.trim().to_string()
+}
+
+/// Remove citation markers from prose, skipping `code spans`.
+fn strip_citations(prose: &str) -> String {
+    let prose = FOOTNOTE_DEF_RE.replace_all(prose, "");
+    prose
+        .split('`')
+        .enumerate()
+        .map(|(ix, part)| {
+            if ix % 2 == 1 {
+                return part.to_string();
+            }
+            let part = FOOTNOTE_REF_RE.replace_all(part, "");
+            let part = CITEKEY_GROUP_RE.replace_all(&part, "");
+            NUMERIC_CITATION_RE
+                .replace_all(&part, |caps: &Captures| {
+                    let whole = caps.get(0).unwrap();
+                    // `[1](url)` is a link and `[1]: url` a reference definition.
+                    if part[whole.end()..].starts_with(['(', ':']) {
+                        return whole.as_str().to_string();
+                    }
+                    let lead = &caps[1];
+                    if lead.trim().is_empty() {
+                        String::new()
+                    } else {
+                        lead.to_string()
+                    }
+                })
+                .into_owned()
+        })
+        .collect::<Vec<_>>()
+        .join("`")
+}
+
+/// Drop characters that paste badly: zero-width marks, byte order marks and
+/// non-breaking spaces.
+fn clipboard_safe(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .chars()
+        .filter(|c| {
+            !matches!(
+                c,
+                '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}'
+            )
+        })
+        .map(|c| {
+            if matches!(c, '\u{00A0}' | '\u{202F}') {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+// ── Blocks ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, PartialEq)]
+enum Block {
+    Heading(usize, String),
+    Paragraph(Vec<String>),
+    Code {
+        language: String,
+        code: String,
+    },
+    Quote(Vec<Block>),
+    List {
+        /// Start number of an ordered list.
+        start: Option<u64>,
+        items: Vec<Vec<Block>>,
+    },
+    Table {
+        header: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    Rule,
+}
+
+struct ListMarker {
+    indent: usize,
+    /// Start number for ordered items.
+    number: Option<u64>,
+    /// Column where the item's text starts.
+    content_offset: usize,
+}
+
+fn lines_of(text: &str) -> Vec<String> {
+    text.lines().map(|l| l.replace('\t', "    ")).collect()
+}
+
+fn leading_spaces(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn fence_marker(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if leading_spaces(line) > 3 {
+        return None;
+    }
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|c| *c == marker).count();
+    (len >= 3).then(|| marker.to_string().repeat(len))
+}
+
+fn closes_fence(line: &str, open: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with(open) && trimmed.chars().all(|c| open.starts_with(c))
+}
+
+fn heading_of(line: &str) -> Option<(usize, String)> {
+    if leading_spaces(line) > 3 {
+        return None;
+    }
+    let trimmed = line.trim();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    let rest = &trimmed[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    Some((
+        level,
+        rest.trim().trim_end_matches('#').trim_end().to_string(),
+    ))
+}
+
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && leading_spaces(line) <= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|m| compact.chars().all(|c| c == *m))
+}
+
+fn list_marker(line: &str) -> Option<ListMarker> {
+    let indent = leading_spaces(line);
+    let rest = &line[indent..];
+    let (number, marker_len) = if rest.starts_with(['-', '*', '+']) {
+        (None, 1)
+    } else {
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        if !(1..=9).contains(&digits) || !rest[digits..].starts_with(['.', ')']) {
+            return None;
+        }
+        (rest[..digits].parse().ok(), digits + 1)
+    };
+    let after = &rest[marker_len..];
+    if !(after.is_empty() || after.starts_with(' ')) {
+        return None;
+    }
+    let gap = (after.len() - after.trim_start().len()).clamp(1, 4);
+    Some(ListMarker {
+        indent,
+        number,
+        content_offset: indent + marker_len + gap,
+    })
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let trimmed = line.trim().trim_matches('|');
+    !trimmed.is_empty()
+        && trimmed.split('|').all(|cell| {
+            let cell = cell.trim().trim_start_matches(':').trim_end_matches(':');
+            !cell.is_empty() && cell.chars().all(|c| c == '-')
+        })
+}
+
+fn table_cells(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let trimmed = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let trimmed = trimmed.strip_suffix('|').unwrap_or(trimmed);
+    trimmed.split('|').map(|c| c.trim().to_string()).collect()
+}
+
+/// Does `line` start a block other than a paragraph?
+fn starts_block(line: &str) -> bool {
+    fence_marker(line).is_some()
+        || heading_of(line).is_some()
+        || line.trim_start().starts_with('>')
+        || is_rule(line)
+        || list_marker(line).is_some()
+}
+
+fn parse_blocks(lines: &[String]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut ix = 0;
+    while ix < lines.len() {
+        let line = &lines[ix];
+        if line.trim().is_empty() {
+            ix += 1;
+        } else if let Some(open) = fence_marker(line) {
+            let language = line.trim_start()[open.len()..]
+                .split_whitespace()
+                .next()
+                .unwrap_or("")
+                .to_string();
+            let indent = leading_spaces(line);
+            let mut code = Vec::new();
+            ix += 1;
+            while ix < lines.len() && !closes_fence(&lines[ix], &open) {
+                let l = &lines[ix];
+                code.push(&l[leading_spaces(l).min(indent)..]);
+                ix += 1;
+            }
+            ix += 1;
+            blocks.push(Block::Code {
+                language,
+                code: code.join("\n"),
+            });
+        } else if let Some((level, text)) = heading_of(line) {
+            blocks.push(Block::Heading(level, text));
+            ix += 1;
+        } else if is_rule(line) {
+            blocks.push(Block::Rule);
+            ix += 1;
+        } else if line.trim_start().starts_with('>') {
+            let mut inner = Vec::new();
+            while ix < lines.len() && lines[ix].trim_start().starts_with('>') {
+                let rest = &lines[ix].trim_start()[1..];
+                inner.push(rest.strip_prefix(' ').unwrap_or(rest).to_string());
+                ix += 1;
+            }
+            blocks.push(Block::Quote(parse_blocks(&inner)));
+        } else if let Some(marker) = list_marker(line) {
+            let (list, next) = parse_list(lines, ix, &marker);
+            blocks.push(list);
+            ix = next;
+        } else if line.contains('|') && lines.get(ix + 1).is_some_and(|l| is_table_separator(l)) {
+            let header = table_cells(line);
+            ix += 2;
+            let mut rows = Vec::new();
+            while ix < lines.len() && lines[ix].contains('|') && !lines[ix].trim().is_empty() {
+                rows.push(table_cells(&lines[ix]));
+                ix += 1;
+            }
+            blocks.push(Block::Table { header, rows });
+        } else {
+            let mut text = vec![line.trim().to_string()];
+            ix += 1;
+            while ix < lines.len() && !lines[ix].trim().is_empty() && !starts_block(&lines[ix]) {
+                let next = lines[ix].trim();
+                // Setext heading underline.
+                if next.chars().all(|c| c == '=') {
+                    ix += 1;
+                    blocks.push(Block::Heading(1, text.join(" ")));
+                    text.clear();
+                    break;
+                }
+                text.push(next.to_string());
+                ix += 1;
+            }
+            if ix < lines.len() && !text.is_empty() && lines[ix].trim().starts_with("--") {
+                let underline = lines[ix].trim();
+                if underline.chars().all(|c| c == '-') {
+                    ix += 1;
+                    blocks.push(Block::Heading(2, text.join(" ")));
+                    text.clear();
+                }
+            }
+            if !text.is_empty() {
+                blocks.push(Block::Paragraph(text));
+            }
+        }
+    }
+    blocks
+}
+
+/// Parse the list starting at `lines[start]`; returns it and the index of
+/// the first line after it.
+fn parse_list(lines: &[String], start: usize, first: &ListMarker) -> (Block, usize) {
+    let ordered = first.number.is_some();
+    let same_list = |line: &str| {
+        list_marker(line)
+            .is_some_and(|m| m.number.is_some() == ordered && m.indent <= first.indent + 1)
+    };
+    let mut items = Vec::new();
+    let mut ix = start;
+    while ix < lines.len() && same_list(&lines[ix]) {
+        let marker = list_marker(&lines[ix]).unwrap();
+        let first_line = &lines[ix];
+        let mut item = vec![
+            first_line
+                .get(marker.content_offset.min(first_line.len())..)
+                .unwrap_or("")
+                .to_string(),
+        ];
+        ix += 1;
+        while ix < lines.len() {
+            let line = &lines[ix];
+            if line.trim().is_empty() {
+                let continues = lines[ix + 1..]
+                    .iter()
+                    .find(|l| !l.trim().is_empty())
+                    .is_some_and(|l| leading_spaces(l) > first.indent + 1);
+                if !continues {
+                    break;
+                }
+                item.push(String::new());
+            } else if leading_spaces(line) > first.indent + 1 {
+                item.push(line[leading_spaces(line).min(marker.content_offset)..].to_string());
+            } else if starts_block(line) {
+                break;
+            } else {
+                item.push(line.trim().to_string());
+            }
+            ix += 1;
+        }
+        items.push(parse_blocks(&item));
+
+        // A blank line between items keeps the list going.
+        let next = (ix..lines.len()).find(|&j| !lines[j].trim().is_empty());
+        match next {
+            Some(j) if same_list(&lines[j]) => ix = j,
+            _ => break,
+        }
+    }
+    (
+        Block::List {
+            start: first.number,
+            items,
+        },
+        ix,
+    )
+}
+
+// ── Plain text ─────────────────────────────────────────────────────────────
+
+fn render_plain(blocks: &[Block]) -> String {
+    blocks
+        .iter()
+        .map(plain_block)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn plain_block(block: &Block) -> String {
+    match block {
+        Block::Heading(_, text) => inline(text, false),
+        Block::Paragraph(lines) => lines
+            .iter()
+            .map(|l| inline(l, false))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Block::Code { code, .. } => code.clone(),
+        Block::Quote(inner) => prefix_lines(&render_plain(inner), "> ", "> "),
+        Block::List { start, items } => items
+            .iter()
+            .enumerate()
+            .map(|(ix, item)| {
+                let bullet = match start {
+                    Some(n) => format!("{}. ", n + ix as u64),
+                    None => "- ".to_string(),
+                };
+                let body = render_plain(item).replace("\n\n", "\n");
+                prefix_lines(&body, &bullet, &" ".repeat(bullet.len()))
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Block::Table { header, rows } => std::iter::once(header)
+            .chain(rows)
+            .map(|row| {
+                row.iter()
+                    .map(|cell| inline(cell, false))
+                    .collect::<Vec<_>>()
+                    .join("\t")
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Block::Rule => String::new(),
+    }
+}
+
+fn prefix_lines(text: &str, first: &str, rest: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(ix, line)| {
+            let prefix = if ix == 0 { first } else { rest };
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{prefix}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ── HTML ───────────────────────────────────────────────────────────────────
+
+fn render_html(blocks: &[Block]) -> String {
+    blocks.iter().map(html_block).collect::<Vec<_>>().join("\n")
+}
+
+fn html_block(block: &Block) -> String {
+    match block {
+        Block::Heading(level, text) => format!("<h{level}>{}</h{level}>", inline(text, true)),
+        Block::Paragraph(lines) => format!(
+            "<p>{}</p>",
+            lines
+                .iter()
+                .map(|l| inline(l, true))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+        Block::Code { language, code } => {
+            let class = if language.is_empty() {
+                String::new()
+            } else {
+                format!(" class=\"language-{}\"", escape_html(language))
+            };
+            format!("<pre><code{class}>{}</code></pre>", escape_html(code))
+        }
+        Block::Quote(inner) => format!("<blockquote>\n{}\n</blockquote>", render_html(inner)),
+        Block::List { start, items } => {
+            let items = items
+                .iter()
+                .map(|item| match item.as_slice() {
+                    [Block::Paragraph(lines)] => format!(
+                        "<li>{}</li>",
+                        lines
+                            .iter()
+                            .map(|l| inline(l, true))
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    ),
+                    blocks => format!("<li>\n{}\n</li>", render_html(blocks)),
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            match start {
+                Some(1) => format!("<ol>\n{items}\n</ol>"),
+                Some(n) => format!("<ol start=\"{n}\">\n{items}\n</ol>"),
+                None => format!("<ul>\n{items}\n</ul>"),
+            }
+        }
+        Block::Table { header, rows } => {
+            let row_html = |cells: &[String], tag: &str| {
+                let cells = cells
+                    .iter()
+                    .map(|cell| format!("<{tag}>{}</{tag}>", inline(cell, true)))
+                    .collect::<String>();
+                format!("<tr>{cells}</tr>")
+            };
+            let body = rows
+                .iter()
+                .map(|row| row_html(row, "td"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!(
+                "<table>\n<thead>\n{}\n</thead>\n<tbody>\n{body}\n</tbody>\n</table>",
+                row_html(header, "th")
+            )
+        }
+        Block::Rule => "<hr>".to_string(),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// ── Inline ─────────────────────────────────────────────────────────────────
+
+/// Render one line of inline Markdown as HTML (`html`) or plain text.
+fn inline(text: &str, html: bool) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let push_text = |out: &mut String, s: &str| {
+        if html {
+            out.push_str(&escape_html(s));
+        } else {
+            out.push_str(s);
+        }
+    };
+    let mut ix = 0;
+    while ix < chars.len() {
+        let c = chars[ix];
+        let run = chars[ix..].iter().take_while(|&&d| d == c).count();
+        match c {
+            '\\' if chars.get(ix + 1).is_some_and(|n| n.is_ascii_punctuation()) => {
+                push_text(&mut out, &chars[ix + 1].to_string());
+                ix += 2;
+            }
+            '`' => match find_run(&chars, ix + run, '`', run) {
+                Some(end) => {
+                    let code: String = chars[ix + run..end].iter().collect();
+                    let code = code.strip_prefix(' ').unwrap_or(&code);
+                    let code = code.strip_suffix(' ').unwrap_or(code);
+                    if html {
+                        out.push_str(&format!("<code>{}</code>", escape_html(code)));
+                    } else {
+                        out.push_str(code);
+                    }
+                    ix = end + run;
+                }
+                None => {
+                    push_text(&mut out, &"`".repeat(run));
+                    ix += run;
+                }
+            },
+            '!' if chars.get(ix + 1) == Some(&'[') => match link_at(&chars, ix + 1) {
+                Some((alt, url, end)) => {
+                    if html {
+                        out.push_str(&format!(
+                            "<img src=\"{}\" alt=\"{}\">",
+                            escape_html(&url),
+                            escape_html(&inline(&alt, false))
+                        ));
+                    } else if alt.is_empty() {
+                        out.push_str(&url);
+                    } else {
+                        out.push_str(&inline(&alt, false));
+                    }
+                    ix = end;
+                }
+                None => {
+                    push_text(&mut out, "!");
+                    ix += 1;
+                }
+            },
+            '[' => match link_at(&chars, ix) {
+                Some((label, url, end)) => {
+                    if html {
+                        out.push_str(&format!(
+                            "<a href=\"{}\">{}</a>",
+                            escape_html(&url),
+                            inline(&label, true)
+                        ));
+                    } else {
+                        let label = inline(&label, false);
+                        if url.is_empty() || url == label || url.starts_with('#') {
+                            out.push_str(&label);
+                        } else {
+                            out.push_str(&format!("{label} ({url})"));
+                        }
+                    }
+                    ix = end;
+                }
+                None => {
+                    push_text(&mut out, "[");
+                    ix += 1;
+                }
+            },
+            '*' | '_' | '~' if opens_emphasis(&chars, ix, run) => {
+                let (len, tags): (usize, &[&str]) = match (c, run) {
+                    ('~', _) => (2, &["del"]),
+                    (_, 1) => (1, &["em"]),
+                    (_, 2) => (2, &["strong"]),
+                    _ => (3, &["strong", "em"]),
+                };
+                match find_run(&chars, ix + len, c, len) {
+                    Some(end) if end > ix + len => {
+                        let inner: String = chars[ix + len..end].iter().collect();
+                        let inner = inline(&inner, html);
+                        if html {
+                            let open: String = tags.iter().map(|t| format!("<{t}>")).collect();
+                            let close: String =
+                                tags.iter().rev().map(|t| format!("</{t}>")).collect();
+                            out.push_str(&format!("{open}{inner}{close}"));
+                        } else {
+                            out.push_str(&inner);
+                        }
+                        ix = end + len;
+                    }
+                    _ => {
+                        push_text(&mut out, &c.to_string().repeat(run));
+                        ix += run;
+                    }
+                }
+            }
+            _ => {
+                push_text(&mut out, &c.to_string());
+                ix += 1;
+            }
+        }
+    }
+    out
+}
+
+/// A delimiter run that can open emphasis: followed by a non-space, and for
+/// `_` not inside a word (`snake_case` stays as is). `~` needs exactly two.
+fn opens_emphasis(chars: &[char], ix: usize, run: usize) -> bool {
+    let c = chars[ix];
+    if c == '~' && run != 2 {
+        return false;
+    }
+    let followed = chars.get(ix + run).is_some_and(|n| !n.is_whitespace());
+    let intraword = c == '_' && ix > 0 && chars[ix - 1].is_alphanumeric();
+    followed && !intraword
+}
+
+/// Index of the next run of exactly `len` × `c` at or after `from` that is not
+/// preceded by whitespace, skipping code spans.
+fn find_run(chars: &[char], from: usize, c: char, len: usize) -> Option<usize> {
+    let mut ix = from;
+    while ix < chars.len() {
+        let d = chars[ix];
+        let run = chars[ix..].iter().take_while(|&&e| e == d).count();
+        if d == c && run == len && (c == '`' || !chars[ix - 1].is_whitespace()) {
+            let closes_word = c != '_' || chars.get(ix + run).is_none_or(|n| !n.is_alphanumeric());
+            if closes_word {
+                return Some(ix);
+            }
+        }
+        if d == '`'
+            && c != '`'
+            && let Some(end) = find_run(chars, ix + run, '`', run)
+        {
+            ix = end + run;
+            continue;
+        }
+        ix += run;
+    }
+    None
+}
+
+/// `[label](url "title")` starting at `chars[open]`; returns the label, the
+/// URL and the index after the closing parenthesis.
+fn link_at(chars: &[char], open: usize) -> Option<(String, String, usize)> {
+    let mut depth = 0;
+    let mut close = None;
+    for (ix, c) in chars.iter().enumerate().skip(open) {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(ix);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let close = close?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let mut depth = 0;
+    let mut end = None;
+    for (ix, c) in chars.iter().enumerate().skip(close + 1) {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    end = Some(ix);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let end = end?;
+    let label: String = chars[open + 1..close].iter().collect();
+    let target: String = chars[close + 2..end].iter().collect();
+    let url = target
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_string();
+    Some((label, url, end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWER: &str = "<think>Check the docs first.</think>\n\
+        ## Setup\n\n\
+        Install **ripgrep** with `cargo install`, see [the docs](https://example.com) [1].\n\n\
+        1. Clone the repo\n\
+        2. Run `make`:\n\
+        \x20  ```sh\n\
+        \x20  make -j4\n\
+        \x20  ```\n\n\
+        - snake_case names stay\n\
+        - *emphasis* and ~~old~~ text\n\n\
+        | Tool | Speed |\n\
+        |------|------:|\n\
+        | rg | fast |\n\n\
+        > Quoted advice[^1]\n\n\
+        [^1]: From the manual.";
+
+    #[test]
+    fn markdown_is_the_source_unchanged() {
+        assert_eq!(format_for_copy(ANSWER, CopyFormat::Markdown), ANSWER);
+    }
+
+    #[test]
+    fn plain_text_drops_markdown_syntax() {
+        let plain = format_for_copy(ANSWER, CopyFormat::PlainText);
+        assert!(plain.starts_with(
+            "Setup\n\nInstall ripgrep with cargo install, see the docs (https://example.com) [1]."
+        ));
+        assert!(plain.contains("1. Clone the repo\n2. Run make:\n   make -j4"));
+        assert!(plain.contains("- snake_case names stay\n- emphasis and old text"));
+        assert!(plain.contains("Tool\tSpeed\nrg\tfast"));
+        assert!(plain.contains("> Quoted advice[^1]"));
+        assert!(!plain.contains("Check the docs"));
+    }
+
+    #[test]
+    fn html_renders_blocks_and_inline_markup() {
+        let html = format_for_copy(ANSWER, CopyFormat::Html);
+        assert!(html.starts_with("<h2>Setup</h2>\n<p>Install <strong>ripgrep</strong> with <code>cargo install</code>, see <a href=\"https://example.com\">the docs</a> [1].</p>"));
+        assert!(html.contains("<ol>\n<li>Clone the repo</li>\n<li>\n<p>Run <code>make</code>:</p>\n<pre><code class=\"language-sh\">make -j4</code></pre>\n</li>\n</ol>"));
+        assert!(html.contains("<li><em>emphasis</em> and <del>old</del> text</li>"));
+        assert!(html.contains("<thead>\n<tr><th>Tool</th><th>Speed</th></tr>"));
+        assert!(html.contains("<blockquote>\n<p>Quoted advice[^1]</p>\n</blockquote>"));
+    }
+
+    #[test]
+    fn clean_strips_thinking_citations_and_footnotes_outside_code() {
+        let text = "<thinking>plan</thinking>Rust is fast [1][2] and safe.[3] \
+            Ownership prevents races [@klabnik2019; @jung2017].\nSee note[^a].\n\n\
+            ```python\nxs = [1, 2]\n```\n\nIndex `xs[1]` or read [1](https://a.example).\n\n\
+            [^a]: A footnote.\n  continued";
+        assert_eq!(
+            format_for_copy(text, CopyFormat::Clean),
+            "Rust is fast and safe. Ownership prevents races.\nSee note.\n\n\
+             ```python\nxs = [1, 2]\n```\n\nIndex `xs[1]` or read [1](https://a.example)."
+        );
+    }
+
+    #[test]
+    fn plain_text_is_clipboard_safe() {
+        assert_eq!(
+            format_for_copy("a\u{00A0}b\u{200B}c\r\nd", CopyFormat::PlainText),
+            "a bc\nd"
+        );
+    }
+}
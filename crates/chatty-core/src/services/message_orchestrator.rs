@@ -1,6 +1,9 @@
 use rig_core::message::UserContent;
+use std::collections::BTreeSet;
 use tracing::info;
 
+use crate::services::mcp_service::McpToolSet;
+
 /// Extract the text portion of user contents for memory query.
 ///
 /// This filters out non-text content (images, PDFs) and joins text fragments.
@@ -19,13 +22,13 @@ pub fn extract_user_text(contents: &[UserContent]) -> String {
 /// Gather MCP tools from the service, returning `None` when no tools are available.
 ///
 /// This wraps the common pattern used by both frontends:
-/// - Call `get_all_tools_with_sinks()`
+/// - Call `get_all_tools()`
 /// - Log the count
 /// - Return `None` for empty tool sets or errors
 pub async fn gather_mcp_tools(
     mcp_service: &crate::services::mcp_service::McpService,
-) -> Option<Vec<(String, Vec<rmcp::model::Tool>, rmcp::service::ServerSink)>> {
-    match mcp_service.get_all_tools_with_sinks().await {
+) -> Option<crate::services::mcp_service::McpToolSet> {
+    match mcp_service.get_all_tools().await {
         Ok(tools) if !tools.is_empty() => {
             info!(count = tools.len(), "MCP tools loaded");
             Some(tools)
@@ -38,9 +41,51 @@ pub async fn gather_mcp_tools(
     }
 }
 
+/// Drop the tools of MCP servers switched off for one conversation,
+/// returning `None` when no server is left.
+pub fn filter_mcp_tools(
+    mcp_tools: Option<McpToolSet>,
+    disabled_servers: &BTreeSet<String>,
+) -> Option<McpToolSet> {
+    if disabled_servers.is_empty() {
+        return mcp_tools;
+    }
+    let tools: McpToolSet = mcp_tools?
+        .into_iter()
+        .filter(|(server, _, _)| !disabled_servers.contains(server))
+        .collect();
+    (!tools.is_empty()).then_some(tools)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::mcp_service::McpService;
+
+    fn tool_set(servers: &[&str]) -> McpToolSet {
+        servers
+            .iter()
+            .map(|name| (name.to_string(), Vec::new(), McpService::new()))
+            .collect()
+    }
+
+    #[test]
+    fn filter_mcp_tools_drops_disabled_servers() {
+        let disabled = BTreeSet::from(["filesystem".to_string()]);
+        let filtered = filter_mcp_tools(Some(tool_set(&["filesystem", "fetch"])), &disabled);
+        let names: Vec<_> = filtered
+            .unwrap()
+            .into_iter()
+            .map(|(name, _, _)| name)
+            .collect();
+        assert_eq!(names, vec!["fetch"]);
+    }
+
+    #[test]
+    fn filter_mcp_tools_none_when_all_disabled() {
+        let disabled = BTreeSet::from(["fetch".to_string()]);
+        assert!(filter_mcp_tools(Some(tool_set(&["fetch"])), &disabled).is_none());
+    }
 
     #[test]
     fn extract_user_text_joins_fragments() {
@@ -5,13 +5,55 @@
 //!
 //! - **External integrations**: LLM streaming (`llm_service`), MCP connections
 //!   (`mcp_service`), A2A protocol (`a2a_client`), search engines (`search_service`).
-//! - **Orchestration**: Stream lifecycle (`stream_processor`) and title generation (`title_generator`).
+//! - **Orchestration**: Stream lifecycle (`stream_processor`), title generation (`title_generator`),
+//!   batch prompt runs over CSV/JSONL inputs (`batch_runner`), discounted provider batch
+//!   jobs (`batch_job_service`), Stop-button cancellation of long-running tools
+//!   (`tool_cancellation`) and detection of streams that stopped sending
+//!   (`stall_detector`).
 //! - **System operations**: Shell execution (`shell_service`), filesystem access
-//!   (`filesystem_service`), path validation (`path_validator`), git operations (`git_service`).
+//!   (`filesystem_service`), path validation (`path_validator`), git operations (`git_service`),
+//!   read-mostly cluster inspection via kubectl (`kubernetes_service`), containers and
+//!   compose stacks via the Docker API (`docker_service`), commands on allowlisted
+//!   remote hosts (`ssh_service`), the workspace trash that makes agent deletions
+//!   and moves undoable (`workspace_trash`), the deduplicating store for
+//!   attachments and tool artifacts (`attachment_store`).
+//! - **Notes**: Markdown/Obsidian vault search, reads, appends and wiki-link
+//!   resolution (`notes_service`).
+//! - **References**: BibTeX / Zotero lookup and citation formatting (`citation_service`).
+//! - **Policy**: User-defined guardrails checked before tool calls (`guardrail_service`),
+//!   SSRF and redirect checks for agent-initiated HTTP requests (`http_policy`),
+//!   signed admin-provided team configuration (`managed_config_service`).
+//! - **Tool generation**: OpenAPI 3.x specs imported as REST tools (`openapi_import`).
 //! - **Rendering**: Math/LaTeX (`math_renderer_service`), Mermaid diagrams
 //!   (`mermaid_renderer_service`), chart SVGs (`chart_svg_renderer`), PDF thumbnails
-//!   (`pdf_thumbnail`).
-//! - **Memory & context**: Agent memory (`memory_service`), skill persistence (`skill_service`).
+//!   (`pdf_thumbnail`) and page renders for the inline PDF viewer (`pdf_viewer`),
+//!   crops, arrows, boxes and blurs burned into staged images (`image_annotation`),
+//!   ffmpeg frame sampling and transcripts for video attachments (`video_frames`),
+//!   answers converted to plain text, HTML or citation-free Markdown for the
+//!   clipboard (`message_copy`).
+//! - **Voice**: Turn detection, transcription and read-aloud for hands-free
+//!   voice conversations (`speech_mode`).
+//! - **Prompts**: `{{variable}}` substitution in per-model system prompts (`prompt_template`),
+//!   per-turn model selection by prompt complexity (`model_router`), per-workspace
+//!   instruction files (`workspace_rules`), Hunspell spell checking of drafts
+//!   (`spell_checker`) and model rewrites of drafts (`prompt_polisher`).
+//! - **Diagnostics**: Opt-in, local-only usage and crash counts (`telemetry_service`),
+//!   startup phase timings (`startup_profile`), per-tool call statistics
+//!   (`tool_stats_service`), the schemas of the tools offered to the model
+//!   (`tool_reference`), record/replay of provider responses for
+//!   regression tests and bug reports (`flow_recorder`). The counters are
+//!   kept in memory and written back through `persisted_json`.
+//! - **Lifecycle**: In-flight persistence writes awaited on quit (`pending_writes`).
+//! - **Artifacts**: Files a conversation's tools produced, filtered by kind and turn
+//!   and copied out in bulk (`artifact_gallery`).
+//! - **Memory & context**: Agent memory (`memory_service`), skill persistence (`skill_service`),
+//!   retrieved-context deduplication (`context_dedup`), topic-shift detection for
+//!   splitting conversations (`topic_shift`), relevance pruning of long
+//!   histories (`relevance_pruner`).
+//! - **Answer checks**: Self-critique of completed answers with one retry on low
+//!   confidence (`self_reflection`).
+//! - **Training data**: Judge-model quality ratings of completed conversations
+//!   (`quality_scorer`).
 //!
 //! ## When to use services vs tools vs repositories
 //!
@@ -24,14 +66,28 @@
 pub mod a2a_client;
 pub mod agent_loop_guard;
 pub mod agent_task_controller;
+pub mod artifact_gallery;
+pub mod attachment_store;
+pub mod batch_job_service;
+pub mod batch_runner;
 pub mod chart_svg_renderer;
+pub mod citation_service;
+pub mod context_dedup;
 pub mod context_shaper;
+pub mod docker_service;
 pub mod embedding_service;
 pub mod error_collector_layer;
 pub mod filesystem_service;
+pub mod flow_recorder;
 pub mod git_service;
+pub mod guardrail_service;
 pub mod http_client;
+pub mod http_policy;
+#[cfg(feature = "image-annotation")]
+pub mod image_annotation;
+pub mod kubernetes_service;
 pub mod llm_service;
+pub mod managed_config_service;
 #[cfg(feature = "math-render")]
 pub mod math_renderer_service;
 pub mod mcp_service;
@@ -40,28 +96,61 @@ pub mod memory_query;
 pub mod memory_service;
 #[cfg(feature = "mermaid")]
 pub mod mermaid_renderer_service;
+pub mod message_copy;
 pub mod message_orchestrator;
+pub mod model_router;
+pub mod notes_service;
+pub mod openapi_import;
 pub mod path_validator;
 #[cfg(feature = "pdf")]
 pub mod pdf_thumbnail;
 #[cfg(feature = "pdf")]
+pub mod pdf_viewer;
+#[cfg(feature = "pdf")]
 pub mod pdfium_utils;
+pub mod pending_writes;
+pub mod persisted_json;
+pub mod prompt_polisher;
+pub mod prompt_template;
+pub mod quality_scorer;
+pub mod relevance_pruner;
 pub mod search_service;
+pub mod self_reflection;
 pub mod shell_service;
 pub mod skill_service;
+pub mod speech_mode;
+pub mod spell_checker;
+pub mod ssh_service;
+pub mod stall_detector;
+pub mod startup_profile;
 pub mod stream_processor;
+pub mod telemetry_service;
+pub mod text_reveal;
 pub mod title_generator;
+pub mod tool_cancellation;
+pub mod tool_reference;
+pub mod tool_stats_service;
+pub mod topic_shift;
 #[cfg(feature = "math-render")]
 pub mod typst_compiler_service;
+pub mod video_frames;
+pub mod workspace_file_index;
+pub mod workspace_rules;
+pub mod workspace_trash;
 
 pub use a2a_client::{A2aClient, A2aStreamEvent};
 pub use agent_loop_guard::AgentLoopGuard;
 pub use agent_task_controller::{
     AgentTaskController, AgentTaskResponse, AgentTaskSnapshot, AgentTodo, AgentTodoStatus,
 };
+pub use context_dedup::{ContextDedupSettings, DedupOutcome, dedup_and_rerank};
 pub use context_shaper::{ContextShaperSettings, ShapedContext, shape_context};
 pub use embedding_service::EmbeddingService;
 pub use error_collector_layer::ErrorCollectorLayer;
+pub use flow_recorder::{FlowMode, FlowRecorder, FlowRecording};
+pub use guardrail_service::{
+    ConfidentialFlag, GuardrailRule, GuardrailRuleKind, GuardrailService, ToolGuard,
+};
 pub use llm_service::{StreamChunk, stream_prompt};
 #[cfg(feature = "math-render")]
 pub use math_renderer_service::MathRendererService;
@@ -70,11 +159,15 @@ pub use memory_query::simplify_memory_query;
 pub use memory_service::MemoryService;
 #[cfg(feature = "mermaid")]
 pub use mermaid_renderer_service::MermaidRendererService;
-pub use message_orchestrator::{extract_user_text, gather_mcp_tools};
+pub use message_orchestrator::{extract_user_text, filter_mcp_tools, gather_mcp_tools};
 #[cfg(feature = "pdf")]
 pub use pdf_thumbnail::cleanup_thumbnails;
 pub use skill_service::SkillService;
+pub use spell_checker::SpellChecker;
+pub use stall_detector::StallDetector;
 pub use stream_processor::{
     ChunkAction, StreamChunkHandler, install_progress_channel, run_stream_loop,
 };
 pub use title_generator::generate_title;
+pub use tool_cancellation::{CANCELLED_BY_USER, ToolCancellation};
+pub use workspace_rules::{RulesFingerprint, WorkspaceRules};
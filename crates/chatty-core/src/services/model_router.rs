@@ -0,0 +1,256 @@
+//! Per-turn model routing based on prompt complexity.
+//!
+//! Each user message is classified as a short factual question, a longer
+//! agentic task, or a vision request. Classification uses a small local
+//! Ollama model when one is configured and falls back to a keyword/length
+//! heuristic otherwise. The router then picks the cheapest configured model
+//! suited to that class; the decision is recorded on the turn's trace.
+
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use rig_core::client::CompletionClient;
+use rig_core::completion::Prompt;
+use serde::{Deserialize, Serialize};
+
+use crate::settings::models::models_store::{AttachmentKind, ModelConfig};
+use crate::settings::models::providers_store::ProviderType;
+
+const CLASSIFIER_PREAMBLE: &str = "You route chat messages. Reply with exactly one word: \
+FACTUAL for short questions with a direct answer, AGENTIC for multi-step tasks such as \
+writing or changing code, research or planning, VISION for questions about images.";
+
+/// Longest prefix of a message sent to the classifier model.
+const MAX_CLASSIFIER_INPUT_CHARS: usize = 2_000;
+
+/// Messages longer than this are treated as agentic by the heuristic.
+const AGENTIC_LENGTH_THRESHOLD: usize = 600;
+
+const AGENTIC_KEYWORDS: &[&str] = &[
+    "implement",
+    "refactor",
+    "debug",
+    "step by step",
+    "write a",
+    "create a",
+    "build a",
+    "set up",
+    "fix the",
+    "analyze",
+    "analyse",
+    "investigate",
+    "plan ",
+    "```",
+];
+
+/// Kind of work a user message asks for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptClass {
+    Factual,
+    Agentic,
+    Vision,
+}
+
+impl PromptClass {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Factual => "factual",
+            Self::Agentic => "agentic",
+            Self::Vision => "vision",
+        }
+    }
+
+    /// Parse a classifier reply, tolerating punctuation and extra words.
+    pub fn parse(reply: &str) -> Option<Self> {
+        let reply = reply.to_ascii_lowercase();
+        [Self::Vision, Self::Agentic, Self::Factual]
+            .into_iter()
+            .find(|class| reply.contains(class.label()))
+    }
+}
+
+/// Outcome of routing one user message, stored on the turn's system trace.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RoutingDecision {
+    pub class: PromptClass,
+    pub model_id: String,
+    pub model_name: String,
+    /// Classifier model identifier, or "heuristic"
+    pub classifier: String,
+    /// Whether the turn went to a model other than the conversation's
+    pub switched: bool,
+}
+
+impl RoutingDecision {
+    /// One-line summary for traces and exports.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} → {} (via {})",
+            self.class.label(),
+            self.model_name,
+            self.classifier
+        )
+    }
+}
+
+/// Classify a message without calling a model.
+pub fn classify_heuristic(text: &str, attachments: &[PathBuf]) -> PromptClass {
+    if attachments
+        .iter()
+        .any(|path| AttachmentKind::from_path(path) == Some(AttachmentKind::Image))
+    {
+        return PromptClass::Vision;
+    }
+
+    let lower = text.to_lowercase();
+    if text.chars().count() > AGENTIC_LENGTH_THRESHOLD
+        || text.lines().count() > 8
+        || AGENTIC_KEYWORDS.iter().any(|kw| lower.contains(kw))
+    {
+        PromptClass::Agentic
+    } else {
+        PromptClass::Factual
+    }
+}
+
+/// Classify a message with a local Ollama model.
+///
+/// Image attachments short-circuit to [`PromptClass::Vision`] without a
+/// model call.
+pub async fn classify_with_ollama(
+    base_url: &str,
+    model_identifier: &str,
+    text: &str,
+    attachments: &[PathBuf],
+) -> Result<PromptClass> {
+    if classify_heuristic("", attachments) == PromptClass::Vision {
+        return Ok(PromptClass::Vision);
+    }
+
+    let client = rig_core::providers::ollama::Client::builder()
+        .api_key(rig_core::client::Nothing)
+        .base_url(base_url)
+        .build()?;
+    let agent = client
+        .agent(model_identifier)
+        .preamble(CLASSIFIER_PREAMBLE)
+        .temperature(0.0)
+        .build();
+
+    let input: String = text.chars().take(MAX_CLASSIFIER_INPUT_CHARS).collect();
+    let reply = agent.prompt(input.as_str()).await?;
+    PromptClass::parse(&reply)
+        .ok_or_else(|| anyhow!("Unrecognized classifier reply: {}", reply.trim()))
+}
+
+/// Blended price per million tokens. Local models are free; models without
+/// a configured price rank after every priced model.
+fn blended_cost(model: &ModelConfig) -> f64 {
+    match (
+        model.cost_per_million_input_tokens,
+        model.cost_per_million_output_tokens,
+    ) {
+        (None, None) if model.provider_type == ProviderType::Ollama => 0.0,
+        (None, None) => f64::INFINITY,
+        (input, output) => input.unwrap_or(0.0) + output.unwrap_or(0.0),
+    }
+}
+
+/// Pick the most cost-effective model for `class`.
+///
+/// Models carrying the class name as an alias ("factual", "agentic",
+/// "vision") are preferred. Without such aliases, factual messages go to the
+/// cheapest model, vision messages to the cheapest image-capable model, and
+/// agentic messages stay on the current model. Ties keep the current model.
+pub fn choose_model<'a>(
+    class: PromptClass,
+    models: &'a [ModelConfig],
+    current: Option<&str>,
+) -> Option<&'a ModelConfig> {
+    let mut candidates: Vec<&ModelConfig> = models
+        .iter()
+        .filter(|m| m.has_alias(class.label()))
+        .filter(|m| class != PromptClass::Vision || m.supports_images)
+        .collect();
+
+    if candidates.is_empty() {
+        candidates = match class {
+            PromptClass::Factual => models.iter().collect(),
+            PromptClass::Vision => models.iter().filter(|m| m.supports_images).collect(),
+            PromptClass::Agentic => {
+                return current
+                    .and_then(|id| models.iter().find(|m| m.id == id))
+                    .or_else(|| models.first());
+            }
+        };
+    }
+
+    let is_current = |m: &ModelConfig| current == Some(m.id.as_str());
+    candidates.into_iter().min_by(|a, b| {
+        blended_cost(a)
+            .total_cmp(&blended_cost(b))
+            .then_with(|| is_current(b).cmp(&is_current(a)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str, provider: ProviderType, cost: Option<f64>) -> ModelConfig {
+        let mut m = ModelConfig::new(id.into(), id.into(), provider, id.into());
+        m.cost_per_million_input_tokens = cost;
+        m.cost_per_million_output_tokens = cost;
+        m
+    }
+
+    #[test]
+    fn heuristic_classifies_by_attachments_keywords_and_length() {
+        assert_eq!(
+            classify_heuristic("What is the capital of France?", &[]),
+            PromptClass::Factual
+        );
+        assert_eq!(
+            classify_heuristic("Please refactor the parser module", &[]),
+            PromptClass::Agentic
+        );
+        assert_eq!(
+            classify_heuristic(&"word ".repeat(200), &[]),
+            PromptClass::Agentic
+        );
+        assert_eq!(
+            classify_heuristic("What is this?", &[PathBuf::from("shot.PNG")]),
+            PromptClass::Vision
+        );
+        assert_eq!(PromptClass::parse("Agentic."), Some(PromptClass::Agentic));
+        assert_eq!(PromptClass::parse("no idea"), None);
+    }
+
+    #[test]
+    fn chooses_cheapest_suitable_model() {
+        let mut vision = model("vision", ProviderType::AzureOpenAI, Some(5.0));
+        vision.supports_images = true;
+        let models = vec![
+            model("big", ProviderType::OpenRouter, Some(15.0)),
+            model("unpriced", ProviderType::AzureOpenAI, None),
+            model("local", ProviderType::Ollama, None),
+            vision,
+        ];
+
+        let pick = |class, current| choose_model(class, &models, current).map(|m| m.id.as_str());
+        assert_eq!(pick(PromptClass::Factual, Some("big")), Some("local"));
+        assert_eq!(pick(PromptClass::Vision, Some("big")), Some("vision"));
+        assert_eq!(pick(PromptClass::Agentic, Some("big")), Some("big"));
+    }
+
+    #[test]
+    fn class_aliases_take_precedence() {
+        let mut smart = model("smart", ProviderType::OpenRouter, Some(15.0));
+        smart.aliases = vec!["agentic".into()];
+        let models = vec![model("local", ProviderType::Ollama, None), smart];
+
+        let pick = choose_model(PromptClass::Agentic, &models, Some("local"));
+        assert_eq!(pick.map(|m| m.id.as_str()), Some("smart"));
+    }
+}
@@ -0,0 +1,462 @@
+use anyhow::{Context, Result, anyhow};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
+use tracing::info;
+
+/// Largest note returned by `read` before the content is cut.
+const MAX_NOTE_BYTES: usize = 64 * 1024;
+
+/// Notes larger than this are skipped by `search`.
+const MAX_SEARCH_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Most wiki-linked notes appended by `expand_wiki_links`.
+const MAX_LINKED_NOTES: usize = 10;
+
+/// Per-note cap for linked notes appended by `expand_wiki_links`.
+const MAX_LINKED_NOTE_BYTES: usize = 16 * 1024;
+
+/// Vault folder that "Export answer to note" writes into.
+pub const EXPORT_FOLDER: &str = "Chatty";
+
+/// `[[target]]`, `[[target|alias]]`, `[[target#heading]]` and `![[embed]]`.
+static WIKI_LINK_RE: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"!?\[\[([^\[\]|#^]+)(?:[#^][^\[\]|]*)?(?:\|[^\[\]]*)?\]\]").unwrap()
+});
+
+/// A line in a note that matched a search.
+#[derive(Debug, Serialize)]
+pub struct NoteMatch {
+    /// Path relative to the vault root.
+    pub path: String,
+    pub line: usize,
+    pub snippet: String,
+}
+
+/// A note read from the vault.
+#[derive(Debug, Serialize)]
+pub struct NoteContent {
+    pub path: String,
+    pub content: String,
+    pub truncated: bool,
+    /// Targets of the `[[wiki links]]` in the note.
+    pub links: Vec<String>,
+}
+
+/// Reads and writes Markdown notes in an Obsidian-style vault.
+///
+/// Notes are addressed by their path relative to the vault root, with or
+/// without the `.md` extension. Paths that escape the vault are rejected, and
+/// hidden folders such as `.obsidian` and `.trash` are never searched.
+#[derive(Debug, Clone)]
+pub struct NotesService {
+    vault: PathBuf,
+}
+
+impl NotesService {
+    pub fn new(vault_dir: impl AsRef<Path>) -> Result<Self> {
+        let vault_dir = vault_dir.as_ref();
+        let vault = std::fs::canonicalize(vault_dir)
+            .with_context(|| format!("Notes vault '{}' not found", vault_dir.display()))?;
+        if !vault.is_dir() {
+            return Err(anyhow!(
+                "Notes vault '{}' is not a directory",
+                vault.display()
+            ));
+        }
+        info!(vault = %vault.display(), "Notes service initialized");
+        Ok(Self { vault })
+    }
+
+    pub fn vault(&self) -> &Path {
+        &self.vault
+    }
+
+    /// Whether `path` is a Markdown note inside the vault.
+    pub fn contains(&self, path: &Path) -> bool {
+        is_note(path) && std::fs::canonicalize(path).is_ok_and(|p| p.starts_with(&self.vault))
+    }
+
+    /// Case-insensitive search over note names and contents.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<NoteMatch>> {
+        let needle = query.trim().to_lowercase();
+        if needle.is_empty() {
+            return Err(anyhow!("Search query must not be empty"));
+        }
+
+        let mut matches = Vec::new();
+        for path in self.list_notes() {
+            if matches.len() >= limit {
+                break;
+            }
+            let relative = self.relative(&path);
+            if relative.to_lowercase().contains(&needle) {
+                matches.push(NoteMatch {
+                    path: relative.clone(),
+                    line: 0,
+                    snippet: String::new(),
+                });
+            }
+            if std::fs::metadata(&path).is_ok_and(|m| m.len() > MAX_SEARCH_FILE_BYTES) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            for (ix, line) in content.lines().enumerate() {
+                if matches.len() >= limit {
+                    break;
+                }
+                if line.to_lowercase().contains(&needle) {
+                    matches.push(NoteMatch {
+                        path: relative.clone(),
+                        line: ix + 1,
+                        snippet: line.trim().chars().take(200).collect(),
+                    });
+                }
+            }
+        }
+        Ok(matches)
+    }
+
+    /// Read a note by vault-relative path, or by name as a wiki link would.
+    pub fn read(&self, note: &str) -> Result<NoteContent> {
+        let path = match self.note_path(note) {
+            Ok(path) if path.is_file() => path,
+            _ => self
+                .resolve_link(note)
+                .ok_or_else(|| anyhow!("Note '{}' not found in the vault", note))?,
+        };
+        let mut content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read note '{}'", note))?;
+        let links = wiki_links(&content);
+        let truncated = truncate_at_char_boundary(&mut content, MAX_NOTE_BYTES);
+        Ok(NoteContent {
+            path: self.relative(&path),
+            content,
+            truncated,
+            links,
+        })
+    }
+
+    /// Append `text` to a note, creating it (and its folders) if needed.
+    pub fn append(&self, note: &str, text: &str) -> Result<String> {
+        let path = self.note_path(note)?;
+        if let Some(parent) = path.parent() {
+            // A symlinked folder could point outside the vault, so check the
+            // deepest existing ancestor before creating anything under it.
+            let existing = parent
+                .ancestors()
+                .find(|dir| dir.exists())
+                .unwrap_or(&self.vault);
+            if !std::fs::canonicalize(existing)?.starts_with(&self.vault) {
+                return Err(anyhow!("Note '{}' is outside the vault", note));
+            }
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create folder for '{}'", note))?;
+        }
+
+        let needs_separator = std::fs::read(&path)
+            .ok()
+            .is_some_and(|bytes| !bytes.is_empty() && !bytes.ends_with(b"\n"));
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open note '{}'", note))?;
+        if needs_separator {
+            file.write_all(b"\n")?;
+        }
+        file.write_all(text.as_bytes())?;
+        if !text.ends_with('\n') {
+            file.write_all(b"\n")?;
+        }
+        Ok(self.relative(&path))
+    }
+
+    /// Write `content` to a new note in [`EXPORT_FOLDER`], named after its
+    /// first line. Existing notes are never overwritten.
+    pub fn export(&self, content: &str) -> Result<String> {
+        let title = note_title(content);
+        let date = chrono::Local::now().format("%Y-%m-%d");
+        let mut name = format!("{EXPORT_FOLDER}/{date} {title}");
+        let mut suffix = 2;
+        while self.note_path(&name)?.exists() {
+            name = format!("{EXPORT_FOLDER}/{date} {title} {suffix}");
+            suffix += 1;
+        }
+        let body = format!(
+            "---\ncreated: {}\nsource: chatty\n---\n\n{}",
+            chrono::Local::now().format("%Y-%m-%dT%H:%M:%S"),
+            content
+        );
+        self.append(&name, &body)
+    }
+
+    /// Find the note a wiki link points to. Targets are matched like Obsidian
+    /// does: by vault-relative path first, then by note name (case-insensitive,
+    /// shortest path wins).
+    pub fn resolve_link(&self, target: &str) -> Option<PathBuf> {
+        let target = target.trim().trim_end_matches(".md");
+        if target.is_empty() {
+            return None;
+        }
+        if let Ok(path) = self.note_path(target)
+            && path.is_file()
+        {
+            return Some(path);
+        }
+
+        let wanted = target.rsplit('/').next()?.to_lowercase();
+        self.list_notes()
+            .into_iter()
+            .filter(|p| {
+                p.file_stem()
+                    .is_some_and(|s| s.to_string_lossy().to_lowercase() == wanted)
+            })
+            .min_by_key(|p| p.components().count())
+    }
+
+    /// Append the notes referenced by `[[wiki links]]` in `content`, so a note
+    /// attached as context carries the notes it links to. Only direct links are
+    /// followed; unresolved links are left as they are.
+    pub fn expand_wiki_links(&self, content: &str, source: Option<&Path>) -> String {
+        let source = source.and_then(|p| std::fs::canonicalize(p).ok());
+        let mut seen: HashSet<PathBuf> = source.into_iter().collect();
+        let mut expanded = content.to_string();
+
+        for target in wiki_links(content) {
+            if seen.len() > MAX_LINKED_NOTES {
+                break;
+            }
+            let Some(path) = self.resolve_link(&target) else {
+                continue;
+            };
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+            let Ok(mut linked) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            if truncate_at_char_boundary(&mut linked, MAX_LINKED_NOTE_BYTES) {
+                linked.push_str("\n…");
+            }
+            if !expanded.ends_with('\n') {
+                expanded.push('\n');
+            }
+            expanded.push_str(&format!(
+                "\n--- Linked note [[{}]] ({}) ---\n{}",
+                target,
+                self.relative(&path),
+                linked
+            ));
+        }
+        expanded
+    }
+
+    /// Resolve a vault-relative note path, adding `.md` when missing.
+    fn note_path(&self, note: &str) -> Result<PathBuf> {
+        let note = note.trim();
+        let relative = Path::new(note);
+        if note.is_empty()
+            || !relative
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(anyhow!(
+                "Note path '{}' must be relative to the vault without '..'",
+                note
+            ));
+        }
+        let mut path = self.vault.join(relative);
+        if !is_note(&path) {
+            let mut name = path.file_name().unwrap_or_default().to_os_string();
+            name.push(".md");
+            path.set_file_name(name);
+        }
+        if path.exists() && !std::fs::canonicalize(&path)?.starts_with(&self.vault) {
+            return Err(anyhow!("Note '{}' is outside the vault", note));
+        }
+        Ok(path)
+    }
+
+    /// All Markdown notes in the vault, skipping hidden folders.
+    fn list_notes(&self) -> Vec<PathBuf> {
+        let mut notes = Vec::new();
+        let mut dirs = vec![self.vault.clone()];
+        while let Some(dir) = dirs.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                if entry.file_name().to_string_lossy().starts_with('.') {
+                    continue;
+                }
+                let path = entry.path();
+                match entry.file_type() {
+                    Ok(t) if t.is_dir() => dirs.push(path),
+                    Ok(t) if t.is_file() && is_note(&path) => notes.push(path),
+                    _ => {}
+                }
+            }
+        }
+        notes.sort();
+        notes
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        path.strip_prefix(&self.vault)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+fn is_note(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+}
+
+/// Unique `[[wiki link]]` targets in `content`, in order of appearance.
+pub fn wiki_links(content: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    WIKI_LINK_RE
+        .captures_iter(content)
+        .map(|c| c[1].trim().to_string())
+        .filter(|target| !target.is_empty() && seen.insert(target.clone()))
+        .collect()
+}
+
+/// File-name-safe title from the first non-empty line of `content`.
+fn note_title(content: &str) -> String {
+    let first_line = content
+        .lines()
+        .map(|l| l.trim().trim_start_matches('#').trim())
+        .find(|l| !l.is_empty())
+        .unwrap_or_default();
+    let title: String = first_line
+        .chars()
+        .filter(|c| {
+            !matches!(
+                c,
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']'
+            )
+        })
+        .take(60)
+        .collect();
+    let title = title.trim().trim_start_matches('.').trim();
+    if title.is_empty() {
+        "Chatty answer".to_string()
+    } else {
+        title.to_string()
+    }
+}
+
+/// Cut `text` to at most `max_bytes`, returning whether anything was removed.
+fn truncate_at_char_boundary(text: &mut String, max_bytes: usize) -> bool {
+    if text.len() <= max_bytes {
+        return false;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault() -> (tempfile::TempDir, NotesService) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("Projects")).unwrap();
+        std::fs::create_dir_all(dir.path().join(".obsidian")).unwrap();
+        std::fs::write(
+            dir.path().join("Index.md"),
+            "See [[Roadmap|the roadmap]] and [[Projects/Chatty#Goals]].\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("Projects/Roadmap.md"), "Ship notes in Q3\n").unwrap();
+        std::fs::write(dir.path().join("Projects/Chatty.md"), "## Goals\nFast UI\n").unwrap();
+        std::fs::write(dir.path().join(".obsidian/Roadmap.md"), "hidden").unwrap();
+        let service = NotesService::new(dir.path()).unwrap();
+        (dir, service)
+    }
+
+    #[test]
+    fn wiki_links_strip_aliases_and_headings() {
+        assert_eq!(
+            wiki_links("[[A]] [[B|alias]] ![[C#Heading]] [[A]] [[D^block]]"),
+            vec!["A", "B", "C", "D"]
+        );
+    }
+
+    #[test]
+    fn resolve_link_by_name_and_path_skipping_hidden_folders() {
+        let (_dir, service) = vault();
+        let by_name = service.resolve_link("roadmap").unwrap();
+        assert!(by_name.ends_with("Projects/Roadmap.md"));
+        let by_path = service.resolve_link("Projects/Chatty").unwrap();
+        assert!(by_path.ends_with("Projects/Chatty.md"));
+        assert!(service.resolve_link("Missing").is_none());
+    }
+
+    #[test]
+    fn expand_wiki_links_appends_linked_notes_once() {
+        let (dir, service) = vault();
+        let index = dir.path().join("Index.md");
+        let content = std::fs::read_to_string(&index).unwrap();
+        let expanded = service.expand_wiki_links(&content, Some(&index));
+        assert!(expanded.starts_with(&content));
+        assert!(expanded.contains("--- Linked note [[Roadmap]] (Projects/Roadmap.md) ---"));
+        assert!(expanded.contains("Ship notes in Q3"));
+        assert!(expanded.contains("Fast UI"));
+        assert_eq!(expanded.matches("Ship notes in Q3").count(), 1);
+    }
+
+    #[test]
+    fn read_search_and_append_stay_inside_vault() {
+        let (_dir, service) = vault();
+        assert_eq!(service.read("Roadmap").unwrap().path, "Projects/Roadmap.md");
+        assert!(service.read("../etc/passwd").is_err());
+        assert!(service.append("../outside", "x").is_err());
+
+        let path = service.append("Inbox/Today", "- call Bob").unwrap();
+        assert_eq!(path, "Inbox/Today.md");
+        service.append("Inbox/Today", "- write docs").unwrap();
+        let today = service.read("Inbox/Today.md").unwrap();
+        assert_eq!(today.content, "- call Bob\n- write docs\n");
+
+        let hits = service.search("ship", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, "Projects/Roadmap.md");
+        assert_eq!(hits[0].line, 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn append_creates_nothing_through_a_symlink_out_of_the_vault() {
+        let (dir, service) = vault();
+        let outside = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("Escape")).unwrap();
+
+        assert!(service.append("Escape/Deep/Note", "x").is_err());
+        assert!(!outside.path().join("Deep").exists());
+    }
+
+    #[test]
+    fn export_never_overwrites() {
+        let (_dir, service) = vault();
+        let first = service.export("# Deploy steps\n1. build").unwrap();
+        let second = service.export("# Deploy steps\n1. build").unwrap();
+        assert!(first.starts_with("Chatty/") && first.ends_with(" Deploy steps.md"));
+        assert!(second.ends_with(" Deploy steps 2.md"));
+        let note = service.read(&first).unwrap();
+        assert!(note.content.starts_with("---\ncreated: "));
+        assert!(note.content.contains("1. build"));
+    }
+}
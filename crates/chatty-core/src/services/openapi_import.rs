@@ -0,0 +1,717 @@
+//! Import of OpenAPI 3.x specifications as user-defined REST tools.
+//!
+//! [`parse_openapi_spec`] reads a JSON or YAML spec and lists its operations
+//! with a parameter schema derived from the path, query and header parameters
+//! and the JSON request body (local `$ref`s are inlined), noting where each
+//! argument is sent. The user picks operations
+//! and [`OpenApiSpec::tool_configs`] turns them into
+//! [`CustomHttpToolConfig`]s, which then behave like hand-written REST tools.
+//! Authentication is wired through a user secret referenced from a header.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::{Map, Value, json};
+
+use crate::tools::custom_http_tool::{
+    ArgumentLocation, CustomHttpHeader, CustomHttpMethod, CustomHttpToolConfig,
+};
+
+/// Maximum depth when inlining nested `$ref`s; deeper (or recursive)
+/// schemas are replaced with an untyped placeholder.
+const MAX_REF_DEPTH: usize = 8;
+
+/// Maximum length of a generated tool description.
+const MAX_DESCRIPTION_LEN: usize = 1024;
+
+/// How the API expects credentials, as declared in `components.securitySchemes`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OpenApiAuth {
+    /// `Authorization: Bearer <secret>` (HTTP bearer, OAuth2 and OpenID Connect).
+    Bearer,
+    /// `Authorization: Basic <secret>`; the secret holds the encoded credentials.
+    Basic,
+    /// API key sent in the named header.
+    ApiKeyHeader { header: String },
+}
+
+impl OpenApiAuth {
+    /// Header carrying the credential stored in the user secret `secret_key`.
+    pub fn header(&self, secret_key: &str) -> CustomHttpHeader {
+        let secret = format!("{{{{secret:{}}}}}", secret_key.trim());
+        match self {
+            OpenApiAuth::Bearer => CustomHttpHeader {
+                name: "Authorization".to_string(),
+                value: format!("Bearer {secret}"),
+            },
+            OpenApiAuth::Basic => CustomHttpHeader {
+                name: "Authorization".to_string(),
+                value: format!("Basic {secret}"),
+            },
+            OpenApiAuth::ApiKeyHeader { header } => CustomHttpHeader {
+                name: header.clone(),
+                value: secret,
+            },
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            OpenApiAuth::Bearer => "Bearer token".to_string(),
+            OpenApiAuth::Basic => "HTTP basic".to_string(),
+            OpenApiAuth::ApiKeyHeader { header } => format!("API key in header {header}"),
+        }
+    }
+}
+
+/// A single importable operation.
+#[derive(Clone, Debug)]
+pub struct OpenApiOperation {
+    /// Tool name derived from the `operationId` (or method and path).
+    pub tool_name: String,
+    pub method: CustomHttpMethod,
+    pub path: String,
+    pub description: String,
+    /// JSON object schema for the tool arguments.
+    pub parameters: Value,
+    /// Whether each argument is a path, query or header parameter or a body field.
+    pub argument_locations: BTreeMap<String, ArgumentLocation>,
+}
+
+/// The parts of a spec needed to generate tools.
+#[derive(Clone, Debug)]
+pub struct OpenApiSpec {
+    pub title: String,
+    /// First entry of `servers`, with variables replaced by their defaults.
+    pub base_url: Option<String>,
+    pub auth: Option<OpenApiAuth>,
+    pub operations: Vec<OpenApiOperation>,
+    /// Operations that cannot be expressed as a tool, with the reason.
+    pub skipped: Vec<String>,
+}
+
+impl OpenApiSpec {
+    /// Build tool configs for the operations at `selected` (indices into
+    /// [`OpenApiSpec::operations`]). `secret_key` names the user secret that
+    /// holds the credential for [`OpenApiSpec::auth`].
+    pub fn tool_configs(
+        &self,
+        selected: &[usize],
+        base_url: &str,
+        secret_key: Option<&str>,
+    ) -> Result<Vec<CustomHttpToolConfig>> {
+        let base_url = base_url.trim().trim_end_matches('/');
+        if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+            bail!("Base URL must start with http:// or https://");
+        }
+        let auth_header = match (&self.auth, secret_key.map(str::trim)) {
+            (Some(auth), Some(key)) if !key.is_empty() => Some(auth.header(key)),
+            _ => None,
+        };
+
+        selected
+            .iter()
+            .map(|&ix| {
+                let op = self
+                    .operations
+                    .get(ix)
+                    .ok_or_else(|| anyhow!("No operation at index {ix}"))?;
+                let mut config = CustomHttpToolConfig::new(
+                    op.tool_name.clone(),
+                    op.method,
+                    format!("{base_url}{}", op.path),
+                );
+                config.description = op.description.clone();
+                config.parameters = op.parameters.clone();
+                config.argument_locations = op.argument_locations.clone();
+                config.headers.extend(auth_header.clone());
+                config.source = Some(self.title.clone());
+                config
+                    .validate()
+                    .map_err(|e| anyhow!("{}: {e}", op.tool_name))?;
+                Ok(config)
+            })
+            .collect()
+    }
+}
+
+/// Parse an OpenAPI 3.x document (JSON or YAML).
+pub fn parse_openapi_spec(text: &str) -> Result<OpenApiSpec> {
+    let doc: Value = if text.trim_start().starts_with('{') {
+        serde_json::from_str(text).context("Spec is not valid JSON")?
+    } else {
+        // Go through serde_yaml_ng's own value type so unquoted non-string keys
+        // (e.g. `200:` under `responses`) become JSON strings.
+        let yaml: serde_yaml_ng::Value =
+            serde_yaml_ng::from_str(text).context("Spec is not valid YAML")?;
+        serde_json::to_value(yaml).context("Spec cannot be represented as JSON")?
+    };
+
+    // An unquoted `openapi: 3.0` is read from YAML as a number.
+    let version = match doc.get("openapi") {
+        Some(Value::String(v)) => v.clone(),
+        Some(Value::Number(v)) => v.to_string(),
+        _ => String::new(),
+    };
+    if !version.starts_with("3.") {
+        if doc.get("swagger").is_some() {
+            bail!("Swagger 2.0 specs are not supported; convert the spec to OpenAPI 3.x first");
+        }
+        bail!("Not an OpenAPI 3.x document (missing \"openapi: 3.x\")");
+    }
+
+    let title = doc
+        .pointer("/info/title")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .unwrap_or("API")
+        .to_string();
+
+    let mut spec = OpenApiSpec {
+        title,
+        base_url: server_url(&doc),
+        auth: security_scheme(&doc),
+        operations: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    let paths = doc
+        .get("paths")
+        .and_then(Value::as_object)
+        .ok_or_else(|| anyhow!("Spec has no paths"))?;
+    for (path, item) in paths {
+        let item = resolve(&doc, item);
+        let shared_params = item
+            .get("parameters")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        for method in CustomHttpMethod::ALL {
+            let Some(op) = item.get(method.as_str().to_lowercase()) else {
+                continue;
+            };
+            match build_operation(&doc, path, method, op, &shared_params) {
+                Ok(mut operation) => {
+                    operation.tool_name = unique_name(&operation.tool_name, &spec.operations);
+                    spec.operations.push(operation);
+                }
+                Err(reason) => spec
+                    .skipped
+                    .push(format!("{} {path}: {reason}", method.as_str())),
+            }
+        }
+    }
+
+    if spec.operations.is_empty() {
+        bail!("Spec contains no importable operations");
+    }
+    Ok(spec)
+}
+
+/// Add imported tools to `existing`. A tool already imported from the same
+/// source for the same method and URL is replaced (keeping its ID, name and
+/// enabled state); other name clashes get a numeric suffix. Returns the number
+/// of tools added or replaced.
+pub fn merge_imported_tools(
+    existing: &mut Vec<CustomHttpToolConfig>,
+    imported: Vec<CustomHttpToolConfig>,
+) -> usize {
+    let count = imported.len();
+    for mut tool in imported {
+        if let Some(current) = existing.iter_mut().find(|t| {
+            t.source == tool.source
+                && t.method == tool.method
+                && t.url_template == tool.url_template
+        }) {
+            tool.id = current.id.clone();
+            tool.name = current.name.clone();
+            tool.enabled = current.enabled;
+            *current = tool;
+            continue;
+        }
+        let base = tool.name.clone();
+        let mut n = 2;
+        while existing.iter().any(|t| t.name == tool.name) {
+            let suffix = format!("_{n}");
+            let keep = base.len().min(64 - suffix.len());
+            tool.name = format!("{}{suffix}", &base[..keep]);
+            n += 1;
+        }
+        existing.push(tool);
+    }
+    count
+}
+
+fn build_operation(
+    doc: &Value,
+    path: &str,
+    method: CustomHttpMethod,
+    op: &Value,
+    shared_params: &[Value],
+) -> std::result::Result<OpenApiOperation, String> {
+    let mut properties = Map::new();
+    let mut required: Vec<String> = Vec::new();
+    let mut locations = BTreeMap::new();
+
+    // Operation-level parameters override path-level ones with the same name.
+    let op_params = op
+        .get("parameters")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for param in shared_params.iter().chain(op_params.iter()) {
+        let param = resolve(doc, param);
+        let Some(name) = param.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let location = match param.get("in").and_then(Value::as_str) {
+            Some("path") => ArgumentLocation::Path,
+            Some("query") => ArgumentLocation::Query,
+            // The spec says these three are ignored as header parameters;
+            // the tool sets them itself.
+            Some("header")
+                if !["Accept", "Content-Type", "Authorization"]
+                    .iter()
+                    .any(|reserved| reserved.eq_ignore_ascii_case(name)) =>
+            {
+                ArgumentLocation::Header
+            }
+            _ => continue,
+        };
+        let mut schema = param
+            .get("schema")
+            .map(|s| inline_refs(doc, s, 0))
+            .unwrap_or_else(|| json!({ "type": "string" }));
+        if let (Some(description), Some(obj)) = (
+            param.get("description").and_then(Value::as_str),
+            schema.as_object_mut(),
+        ) {
+            obj.entry("description")
+                .or_insert_with(|| Value::String(description.to_string()));
+        }
+        properties.insert(name.to_string(), schema);
+        locations.insert(name.to_string(), location);
+        let is_required = location == ArgumentLocation::Path
+            || param.get("required").and_then(Value::as_bool) == Some(true);
+        if is_required && !required.iter().any(|r| r == name) {
+            required.push(name.to_string());
+        }
+    }
+
+    // Path placeholders the spec forgot to declare are still required strings.
+    for placeholder in path_placeholders(path) {
+        if !properties.contains_key(&placeholder) {
+            properties.insert(placeholder.clone(), json!({ "type": "string" }));
+            locations.insert(placeholder.clone(), ArgumentLocation::Path);
+            required.push(placeholder);
+        }
+    }
+
+    if let Some(body) = op.get("requestBody") {
+        let body = resolve(doc, body);
+        let schema = body
+            .pointer("/content/application~1json/schema")
+            .ok_or("request body is not JSON")?;
+        let schema = inline_refs(doc, schema, 0);
+        let body_props = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .ok_or("request body is not a JSON object")?;
+        for (name, prop) in body_props {
+            if properties.contains_key(name) {
+                return Err(format!(
+                    "body field '{name}' clashes with a path, query or header parameter"
+                ));
+            }
+            properties.insert(name.clone(), prop.clone());
+            locations.insert(name.clone(), ArgumentLocation::Body);
+        }
+        if let Some(body_required) = schema.get("required").and_then(Value::as_array) {
+            required.extend(
+                body_required
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string),
+            );
+        }
+    }
+
+    let summary = op
+        .get("summary")
+        .or_else(|| op.get("description"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("");
+    let mut description = if summary.is_empty() {
+        format!("{} {path}", method.as_str())
+    } else {
+        format!("{summary} ({} {path})", method.as_str())
+    };
+    if description.len() > MAX_DESCRIPTION_LEN {
+        let mut end = MAX_DESCRIPTION_LEN;
+        while !description.is_char_boundary(end) {
+            end -= 1;
+        }
+        description.truncate(end);
+    }
+
+    let raw_name = op
+        .get("operationId")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}_{path}", method.as_str().to_lowercase()));
+
+    Ok(OpenApiOperation {
+        tool_name: sanitize_tool_name(&raw_name),
+        method,
+        path: path.to_string(),
+        description,
+        parameters: json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        }),
+        argument_locations: locations,
+    })
+}
+
+/// Follow a chain of local `$ref`s to the referenced value.
+fn resolve<'a>(doc: &'a Value, value: &'a Value) -> &'a Value {
+    let mut current = value;
+    for _ in 0..MAX_REF_DEPTH {
+        let Some(target) = current
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| r.strip_prefix('#'))
+            .and_then(|pointer| doc.pointer(pointer))
+        else {
+            break;
+        };
+        current = target;
+    }
+    current
+}
+
+/// Copy `value`, replacing every local `$ref` with the referenced schema.
+fn inline_refs(doc: &Value, value: &Value, depth: usize) -> Value {
+    if depth > MAX_REF_DEPTH {
+        return json!({});
+    }
+    match value {
+        Value::Object(map) if map.contains_key("$ref") => {
+            let target = resolve(doc, value);
+            if std::ptr::eq(target, value) {
+                // Unresolvable (e.g. external) reference.
+                json!({})
+            } else {
+                inline_refs(doc, target, depth + 1)
+            }
+        }
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), inline_refs(doc, v, depth)))
+                .collect(),
+        ),
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|v| inline_refs(doc, v, depth)).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+fn server_url(doc: &Value) -> Option<String> {
+    let server = doc.pointer("/servers/0")?;
+    let mut url = server.get("url")?.as_str()?.to_string();
+    if let Some(variables) = server.get("variables").and_then(Value::as_object) {
+        for (name, variable) in variables {
+            if let Some(default) = variable.get("default").and_then(Value::as_str) {
+                url = url.replace(&format!("{{{name}}}"), default);
+            }
+        }
+    }
+    Some(url.trim_end_matches('/').to_string())
+}
+
+/// Pick the scheme named by the top-level `security` requirement, falling
+/// back to the first declared scheme that can be sent as a header.
+fn security_scheme(doc: &Value) -> Option<OpenApiAuth> {
+    let schemes = doc
+        .pointer("/components/securitySchemes")
+        .and_then(Value::as_object)?;
+    let preferred = doc
+        .pointer("/security/0")
+        .and_then(Value::as_object)
+        .and_then(|req| req.keys().next())
+        .and_then(|name| schemes.get(name));
+    preferred
+        .into_iter()
+        .chain(schemes.values())
+        .find_map(|scheme| auth_from_scheme(resolve(doc, scheme)))
+}
+
+fn auth_from_scheme(scheme: &Value) -> Option<OpenApiAuth> {
+    match scheme.get("type").and_then(Value::as_str)? {
+        "http" => match scheme
+            .get("scheme")
+            .and_then(Value::as_str)?
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "bearer" => Some(OpenApiAuth::Bearer),
+            "basic" => Some(OpenApiAuth::Basic),
+            _ => None,
+        },
+        "oauth2" | "openIdConnect" => Some(OpenApiAuth::Bearer),
+        "apiKey" if scheme.get("in").and_then(Value::as_str) == Some("header") => {
+            Some(OpenApiAuth::ApiKeyHeader {
+                header: scheme.get("name")?.as_str()?.to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn path_placeholders(path: &str) -> Vec<String> {
+    CustomHttpToolConfig::new("", CustomHttpMethod::Get, path).url_placeholders()
+}
+
+/// Map an operation ID to a valid tool name (`[A-Za-z0-9_-]`, at most 64 chars).
+fn sanitize_tool_name(raw: &str) -> String {
+    let mut name = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        let c = if c.is_ascii_alphanumeric() || c == '-' {
+            c
+        } else {
+            '_'
+        };
+        if c == '_' && name.ends_with('_') {
+            continue;
+        }
+        name.push(c);
+    }
+    let name = name.trim_matches('_');
+    let name = if name.is_empty() { "operation" } else { name };
+    name.chars().take(64).collect()
+}
+
+fn unique_name(name: &str, operations: &[OpenApiOperation]) -> String {
+    let mut candidate = name.to_string();
+    let mut n = 2;
+    while operations.iter().any(|op| op.tool_name == candidate) {
+        let suffix = format!("_{n}");
+        let keep = name.len().min(64 - suffix.len());
+        candidate = format!("{}{suffix}", &name[..keep]);
+        n += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = r##"
+openapi: 3.0
+info:
+  title: Petstore
+servers:
+  - url: https://{region}.petstore.example/v1/
+    variables:
+      region:
+        default: eu
+security:
+  - apiKey: []
+components:
+  securitySchemes:
+    bearer:
+      type: http
+      scheme: bearer
+    apiKey:
+      type: apiKey
+      in: header
+      name: X-Api-Key
+  parameters:
+    PetId:
+      name: petId
+      in: path
+      required: true
+      schema:
+        type: integer
+  schemas:
+    NewPet:
+      type: object
+      required: [name]
+      properties:
+        name:
+          type: string
+        tag:
+          $ref: '#/components/schemas/Tag'
+    Tag:
+      type: string
+      enum: [cat, dog]
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      summary: List pets
+      parameters:
+        - name: limit
+          in: query
+          description: Page size
+          schema:
+            type: integer
+        - name: X-Trace
+          in: header
+          schema:
+            type: string
+    post:
+      operationId: createPet
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/NewPet'
+  /pets/{petId}:
+    parameters:
+      - $ref: '#/components/parameters/PetId'
+    get:
+      summary: Get a pet
+      responses:
+        200:
+          description: A pet
+    put:
+      operationId: uploadPhoto
+      requestBody:
+        content:
+          image/png:
+            schema:
+              type: string
+              format: binary
+"##;
+
+    #[test]
+    fn parses_operations_servers_and_auth() {
+        let spec = parse_openapi_spec(PETSTORE).unwrap();
+        assert_eq!(spec.title, "Petstore");
+        assert_eq!(
+            spec.base_url.as_deref(),
+            Some("https://eu.petstore.example/v1")
+        );
+        assert_eq!(
+            spec.auth,
+            Some(OpenApiAuth::ApiKeyHeader {
+                header: "X-Api-Key".into()
+            })
+        );
+
+        let names: Vec<&str> = spec
+            .operations
+            .iter()
+            .map(|op| op.tool_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["listPets", "createPet", "get_pets_petId"]);
+        assert_eq!(spec.skipped.len(), 1);
+        assert!(spec.skipped[0].contains("not JSON"), "{:?}", spec.skipped);
+    }
+
+    #[test]
+    fn derives_parameter_schemas() {
+        let spec = parse_openapi_spec(PETSTORE).unwrap();
+
+        let list = &spec.operations[0].parameters;
+        assert_eq!(list["properties"]["limit"]["type"], "integer");
+        assert_eq!(list["properties"]["limit"]["description"], "Page size");
+        assert_eq!(list["properties"]["X-Trace"]["type"], "string");
+        assert_eq!(list["required"], json!([]));
+        let locations = &spec.operations[0].argument_locations;
+        assert_eq!(locations["limit"], ArgumentLocation::Query);
+        assert_eq!(locations["X-Trace"], ArgumentLocation::Header);
+
+        let create = &spec.operations[1].parameters;
+        assert_eq!(create["properties"]["tag"]["enum"], json!(["cat", "dog"]));
+        assert_eq!(create["required"], json!(["name"]));
+        assert_eq!(
+            spec.operations[1].argument_locations["tag"],
+            ArgumentLocation::Body
+        );
+
+        let get = &spec.operations[2].parameters;
+        assert_eq!(get["properties"]["petId"]["type"], "integer");
+        assert_eq!(get["required"], json!(["petId"]));
+    }
+
+    #[test]
+    fn tool_configs_carry_base_url_auth_and_source() {
+        let spec = parse_openapi_spec(PETSTORE).unwrap();
+        let tools = spec
+            .tool_configs(
+                &[2],
+                "https://eu.petstore.example/v1/",
+                Some("PETSTORE_KEY"),
+            )
+            .unwrap();
+        assert_eq!(tools.len(), 1);
+        let tool = &tools[0];
+        assert_eq!(
+            tool.url_template,
+            "https://eu.petstore.example/v1/pets/{petId}"
+        );
+        assert_eq!(tool.method, CustomHttpMethod::Get);
+        assert_eq!(tool.headers[0].name, "X-Api-Key");
+        assert_eq!(tool.headers[0].value, "{{secret:PETSTORE_KEY}}");
+        assert_eq!(tool.source.as_deref(), Some("Petstore"));
+        assert!(tool.validate().is_ok());
+
+        assert!(spec.tool_configs(&[0], "/relative", None).is_err());
+    }
+
+    #[test]
+    fn rejects_swagger_2_and_garbage() {
+        assert!(
+            parse_openapi_spec(r#"{"swagger": "2.0", "paths": {}}"#)
+                .unwrap_err()
+                .to_string()
+                .contains("Swagger 2.0")
+        );
+        assert!(parse_openapi_spec("not: [valid").is_err());
+    }
+
+    #[test]
+    fn sanitizes_and_dedupes_names() {
+        assert_eq!(sanitize_tool_name("get /users/{id}"), "get_users_id");
+        assert_eq!(sanitize_tool_name("///"), "operation");
+        assert_eq!(sanitize_tool_name(&"x".repeat(80)).len(), 64);
+    }
+
+    #[test]
+    fn merge_replaces_same_source_and_renames_clashes() {
+        let spec = parse_openapi_spec(PETSTORE).unwrap();
+        let base = "https://eu.petstore.example/v1";
+        let mut existing = vec![CustomHttpToolConfig::new(
+            "listPets",
+            CustomHttpMethod::Get,
+            "https://other.example/pets",
+        )];
+
+        let added = merge_imported_tools(
+            &mut existing,
+            spec.tool_configs(&[0, 1], base, None).unwrap(),
+        );
+        assert_eq!(added, 2);
+        let names: Vec<&str> = existing.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["listPets", "listPets_2", "createPet"]);
+
+        // Re-importing replaces the tools from the same spec in place.
+        existing[2].enabled = false;
+        let id = existing[2].id.clone();
+        merge_imported_tools(
+            &mut existing,
+            spec.tool_configs(&[0, 1], base, None).unwrap(),
+        );
+        assert_eq!(existing.len(), 3);
+        assert_eq!(existing[1].name, "listPets_2");
+        assert_eq!(existing[2].id, id);
+        assert!(!existing[2].enabled);
+    }
+}
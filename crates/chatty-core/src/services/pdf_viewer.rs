@@ -0,0 +1,143 @@
+//! Page renders for the inline PDF viewer.
+//!
+//! Each page is rendered once per (file, modification time, width) and
+//! cached as a PNG in the session thumbnail directory, so flipping back and
+//! forth between pages or zoom levels doesn't re-rasterize. The page text is
+//! extracted alongside the render so the viewer can show it as selectable
+//! text and attach the same image to an "ask about this page" prompt.
+
+use pdfium_render::prelude::*;
+use std::path::{Path, PathBuf};
+
+use super::pdf_thumbnail::{PdfThumbnailError, get_thumbnail_dir};
+use super::pdfium_utils::create_pdfium;
+
+/// Rendered page width at 100% zoom, in pixels.
+pub const BASE_PAGE_WIDTH: u32 = 720;
+
+/// Zoom steps offered by the viewer, in ascending order.
+pub const ZOOM_LEVELS: &[f32] = &[0.5, 0.75, 1.0, 1.25, 1.5, 2.0, 3.0];
+
+/// One rendered page of a PDF.
+#[derive(Clone, Debug)]
+pub struct RenderedPage {
+    /// Zero-based page index.
+    pub index: u32,
+    /// Total number of pages in the document.
+    pub page_count: u32,
+    /// PNG render of the page.
+    pub image: PathBuf,
+    /// Text extracted from the page (may be empty for scanned PDFs).
+    pub text: String,
+}
+
+/// Step `current` one zoom level up (`step > 0`) or down (`step < 0`),
+/// clamping at either end of [`ZOOM_LEVELS`].
+pub fn step_zoom(current: f32, step: i32) -> f32 {
+    let index = ZOOM_LEVELS
+        .iter()
+        .position(|level| (level - current).abs() < f32::EPSILON)
+        .unwrap_or_else(|| {
+            ZOOM_LEVELS
+                .iter()
+                .position(|level| *level >= current)
+                .unwrap_or(ZOOM_LEVELS.len() - 1)
+        });
+    let next = (index as i32 + step).clamp(0, ZOOM_LEVELS.len() as i32 - 1);
+    ZOOM_LEVELS[next as usize]
+}
+
+/// Pixel width of a page render at `zoom`.
+pub fn page_width(zoom: f32) -> u32 {
+    (BASE_PAGE_WIDTH as f32 * zoom).round() as u32
+}
+
+/// Render page `index` of `pdf_path` at `width` pixels wide.
+///
+/// Out-of-range indices are clamped to the last page.
+pub fn render_page(
+    pdf_path: &Path,
+    index: u32,
+    width: u32,
+) -> Result<RenderedPage, PdfThumbnailError> {
+    let pdfium = create_pdfium()
+        .map_err(|e| PdfThumbnailError::Pdfium(format!("Failed to bind pdfium: {}", e)))?;
+
+    let document = pdfium.load_pdf_from_file(pdf_path, None)?;
+    let page_count = document.pages().len() as u32;
+    if page_count == 0 {
+        return Err(PdfThumbnailError::Pdfium("PDF has no pages".to_string()));
+    }
+    let index = index.min(page_count - 1);
+    let page = document.pages().get(index as i32)?;
+    let text = page.text().map(|t| t.all()).unwrap_or_default();
+
+    let image_path = get_thumbnail_dir()?.join(cache_file_name(pdf_path, index, width));
+    if !image_path.exists() {
+        let render_config = PdfRenderConfig::new().set_target_width(width as i32);
+        let bitmap = page.render_with_config(&render_config)?;
+        bitmap
+            .as_image()?
+            .save_with_format(&image_path, image::ImageFormat::Png)?;
+    }
+
+    Ok(RenderedPage {
+        index,
+        page_count,
+        image: image_path,
+        text,
+    })
+}
+
+/// Cache key for a page render. Includes the file's modification time so an
+/// edited PDF isn't served stale pages.
+fn cache_file_name(pdf_path: &Path, index: u32, width: u32) -> String {
+    use sha2::{Digest, Sha256};
+
+    let modified = std::fs::metadata(pdf_path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+
+    let mut hasher = Sha256::new();
+    hasher.update(pdf_path.to_string_lossy().as_bytes());
+    hasher.update(modified.to_le_bytes());
+    format!(
+        "page_{}_{}_{}.png",
+        hex::encode(hasher.finalize()),
+        index,
+        width
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_zoom_moves_between_levels() {
+        assert_eq!(step_zoom(1.0, 1), 1.25);
+        assert_eq!(step_zoom(1.0, -1), 0.75);
+    }
+
+    #[test]
+    fn step_zoom_clamps_at_ends() {
+        assert_eq!(step_zoom(0.5, -1), 0.5);
+        assert_eq!(step_zoom(3.0, 1), 3.0);
+    }
+
+    #[test]
+    fn step_zoom_snaps_unknown_levels() {
+        assert_eq!(step_zoom(1.1, 0), 1.25);
+        assert_eq!(step_zoom(10.0, 0), 3.0);
+    }
+
+    #[test]
+    fn cache_name_differs_by_page_and_width() {
+        let path = Path::new("/tmp/does-not-exist.pdf");
+        assert_ne!(cache_file_name(path, 0, 720), cache_file_name(path, 1, 720));
+        assert_ne!(cache_file_name(path, 0, 720), cache_file_name(path, 0, 900));
+    }
+}
@@ -0,0 +1,67 @@
+//! Count of persistence writes still in flight.
+//!
+//! Conversation saves run on detached tasks, so quitting right after a change
+//! could cut one off. Repositories hold a [`WriteGuard`] for the duration of
+//! each write and the shutdown path waits with [`wait_idle`] before exiting.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+/// How often [`wait_idle`] re-checks the count.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Marks one write as in flight until dropped.
+#[must_use = "the write only counts while the guard is alive"]
+pub struct WriteGuard(());
+
+impl Drop for WriteGuard {
+    fn drop(&mut self) {
+        IN_FLIGHT.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Start counting a write. Take the guard when the write is requested (not
+/// when its future first runs) so a queued write is already counted.
+pub fn begin() -> WriteGuard {
+    IN_FLIGHT.fetch_add(1, Ordering::AcqRel);
+    WriteGuard(())
+}
+
+pub fn in_flight() -> usize {
+    IN_FLIGHT.load(Ordering::Acquire)
+}
+
+/// Wait until no write is in flight, at most `timeout`. Returns `false` if
+/// writes were still running when it gave up.
+pub async fn wait_idle(timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while in_flight() > 0 {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The counter is process-wide; repository tests may bump it concurrently,
+    // so only assert what holds regardless.
+    #[tokio::test]
+    async fn waits_for_guards_to_drop() {
+        let guard = begin();
+        assert!(!wait_idle(Duration::from_millis(30)).await);
+
+        let release = async {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            drop(guard);
+        };
+        let (idle, ()) = tokio::join!(wait_idle(Duration::from_secs(5)), release);
+        assert!(idle);
+    }
+}
@@ -0,0 +1,142 @@
+//! A value kept in memory and written to a JSON file in the config directory.
+//!
+//! For the process-wide aggregates (`telemetry_service`, `tool_stats_service`)
+//! that are updated on hot paths and written back periodically. Loading and
+//! saving are synchronous, unlike the settings repositories, so a panic hook
+//! can still save before the process exits.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use parking_lot::{Mutex, MutexGuard};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tracing::warn;
+
+pub struct PersistedJson<T> {
+    /// `None` keeps the value in memory only (tests, missing config dir).
+    path: Option<PathBuf>,
+    /// What the file holds, for log messages
+    what: &'static str,
+    value: Mutex<T>,
+    dirty: AtomicBool,
+}
+
+/// `~/.config/chatty/<file_name>`, or `None` without a config directory.
+pub fn config_path(file_name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("chatty").join(file_name))
+}
+
+impl<T: Serialize + DeserializeOwned + Default> PersistedJson<T> {
+    /// Read the value from `path`. A missing or unreadable file starts from
+    /// `T::default()`.
+    pub fn load(path: Option<PathBuf>, what: &'static str) -> Self {
+        let value = path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            what,
+            value: Mutex::new(value),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        self.value.lock()
+    }
+
+    /// The value, or `None` when it is locked right now.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        self.value.try_lock()
+    }
+
+    /// Note a change for the next [`flush`](Self::flush).
+    pub fn mark_dirty(&self) {
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Write the value if it changed since the last write.
+    pub fn flush(&self) {
+        if self.dirty.load(Ordering::Relaxed) {
+            self.save();
+        }
+    }
+
+    /// Write the value now, atomically (temp file + rename).
+    pub fn save(&self) {
+        let json = serde_json::to_string_pretty(&*self.value.lock());
+        self.write(json);
+    }
+
+    /// Like [`save`](Self::save), but skips the write instead of waiting when
+    /// the value is locked. For panic hooks, which may run on a thread that
+    /// already holds the lock.
+    pub fn try_save(&self) {
+        let Some(value) = self.value.try_lock() else {
+            warn!(what = self.what, "Value is locked, not saving");
+            return;
+        };
+        let json = serde_json::to_string_pretty(&*value);
+        drop(value);
+        self.write(json);
+    }
+
+    fn write(&self, json: serde_json::Result<String>) {
+        self.dirty.store(false, Ordering::Relaxed);
+        let Some(path) = &self.path else {
+            return;
+        };
+        let json = match json {
+            Ok(json) => json,
+            Err(e) => {
+                warn!(error = ?e, what = self.what, "Failed to serialize");
+                return;
+            }
+        };
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        if let Err(e) =
+            std::fs::write(&temp_path, json).and_then(|()| std::fs::rename(&temp_path, path))
+        {
+            warn!(error = ?e, what = self.what, "Failed to save");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn flush_writes_only_after_a_change_and_load_reads_it_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("counts.json");
+
+        let counts = PersistedJson::<BTreeMap<String, u64>>::load(Some(path.clone()), "counts");
+        counts.flush();
+        assert!(!path.exists());
+
+        counts.lock().insert("fetch".to_string(), 2);
+        counts.mark_dirty();
+        counts.flush();
+
+        let reloaded = PersistedJson::<BTreeMap<String, u64>>::load(Some(path), "counts");
+        assert_eq!(reloaded.lock().get("fetch"), Some(&2));
+    }
+
+    #[test]
+    fn unreadable_file_starts_from_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("counts.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let counts = PersistedJson::<BTreeMap<String, u64>>::load(Some(path), "counts");
+        assert!(counts.lock().is_empty());
+    }
+}
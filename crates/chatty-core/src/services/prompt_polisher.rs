@@ -0,0 +1,81 @@
+//! "Polish my prompt": have a model fix the spelling, grammar and clarity of
+//! a draft before it is sent.
+//!
+//! The rewrite keeps the draft's language, meaning and formatting (code,
+//! lists, `@` mentions and `/` commands are left alone) and is put back in
+//! the input for the user to review; nothing is sent automatically.
+
+use anyhow::{Result, anyhow};
+use tracing::debug;
+
+use crate::factories::AgentClient;
+
+/// Longest draft sent for polishing, in characters.
+const MAX_DRAFT_CHARS: usize = 8_000;
+
+fn polish_instruction(draft: &str) -> String {
+    format!(
+        "Rewrite the following chat message so it has correct spelling and grammar and \
+         reads clearly. Keep its language, meaning, tone and formatting. Do not answer \
+         it, do not add anything, and leave code, file paths, @mentions and /commands \
+         unchanged. Output ONLY the rewritten message.\n\n\
+         <message>\n{draft}\n</message>"
+    )
+}
+
+/// Strip wrappers models sometimes add around the rewrite.
+pub fn clean_polished(raw: &str) -> String {
+    let text = raw.trim();
+    let text = text
+        .strip_prefix("<message>")
+        .and_then(|t| t.strip_suffix("</message>"))
+        .unwrap_or(text)
+        .trim();
+    // Quotes around the whole rewrite, but not a message that merely starts
+    // and ends with a quotation
+    let text = match text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        Some(inner) if !inner.contains('"') => inner,
+        _ => text,
+    };
+    text.to_string()
+}
+
+/// Return `draft` with its spelling, grammar and clarity improved.
+///
+/// # Errors
+/// Returns an error if the draft is empty or too long, the LLM call fails,
+/// or the model returns nothing.
+pub async fn polish_prompt(agent: &AgentClient, draft: &str) -> Result<String> {
+    if draft.trim().is_empty() {
+        return Err(anyhow!("Nothing to polish"));
+    }
+    if draft.chars().count() > MAX_DRAFT_CHARS {
+        return Err(anyhow!(
+            "Message is too long to polish ({MAX_DRAFT_CHARS} characters at most)"
+        ));
+    }
+
+    debug!(draft_len = draft.len(), "Polishing prompt");
+    let response = agent.prompt(&polish_instruction(draft)).await?;
+    let polished = clean_polished(&response);
+    if polished.is_empty() {
+        return Err(anyhow!("The model returned an empty rewrite"));
+    }
+    Ok(polished)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_wrappers_from_rewrite() {
+        assert_eq!(clean_polished("  Fixed text.\n"), "Fixed text.");
+        assert_eq!(
+            clean_polished("<message>\nFixed text.\n</message>"),
+            "Fixed text."
+        );
+        assert_eq!(clean_polished("\"Fixed text.\""), "Fixed text.");
+        assert_eq!(clean_polished("\"a\" and \"b\""), "\"a\" and \"b\"");
+    }
+}
@@ -0,0 +1,106 @@
+//! `{{variable}}` substitution for per-model system prompts.
+//!
+//! Variables are resolved when an agent is built, so a preamble such as
+//! "Today is {{date}} and you are running on {{os}}" reflects the moment the
+//! conversation's agent was created. Unknown variables are left untouched.
+
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+static VARIABLE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap());
+
+/// Supported variables and their descriptions, for help text in the UI.
+pub const TEMPLATE_VARIABLES: &[(&str, &str)] = &[
+    ("date", "Current local date (YYYY-MM-DD)"),
+    ("os", "Operating system (macos, linux, windows)"),
+    ("workspace", "Configured workspace directory"),
+    ("user_name", "Name of the logged-in user"),
+];
+
+/// Values substituted into a prompt template.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PromptVariables {
+    pub date: String,
+    pub os: String,
+    pub workspace: Option<String>,
+    pub user_name: Option<String>,
+}
+
+impl PromptVariables {
+    /// Resolve the variables for the current machine and moment.
+    pub fn current(workspace: Option<&str>) -> Self {
+        let user_name = ["USER", "USERNAME"]
+            .iter()
+            .find_map(|key| std::env::var(key).ok())
+            .filter(|name| !name.trim().is_empty());
+        Self {
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            os: std::env::consts::OS.to_string(),
+            workspace: workspace.map(str::to_string),
+            user_name,
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "date" => Some(&self.date),
+            "os" => Some(&self.os),
+            "workspace" => Some(self.workspace.as_deref().unwrap_or("(no workspace)")),
+            "user_name" => Some(self.user_name.as_deref().unwrap_or("the user")),
+            _ => None,
+        }
+    }
+}
+
+/// Replace `{{name}}` placeholders in `template` with their values.
+pub fn render_prompt_template<'a>(template: &'a str, vars: &PromptVariables) -> Cow<'a, str> {
+    if !template.contains("{{") {
+        return Cow::Borrowed(template);
+    }
+    VARIABLE_RE.replace_all(template, |caps: &regex::Captures| {
+        vars.get(&caps[1])
+            .map(str::to_string)
+            .unwrap_or_else(|| caps[0].to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> PromptVariables {
+        PromptVariables {
+            date: "2026-03-01".into(),
+            os: "linux".into(),
+            workspace: Some("/srv/project".into()),
+            user_name: None,
+        }
+    }
+
+    #[test]
+    fn substitutes_known_variables() {
+        let out = render_prompt_template(
+            "Today is {{date}} on {{ os }} in {{workspace}}. Help {{user_name}}.",
+            &vars(),
+        );
+        assert_eq!(
+            out,
+            "Today is 2026-03-01 on linux in /srv/project. Help the user."
+        );
+    }
+
+    #[test]
+    fn leaves_unknown_variables_and_plain_text_alone() {
+        assert_eq!(
+            render_prompt_template("{{unknown}} and {{date", &vars()),
+            "{{unknown}} and {{date"
+        );
+        assert!(matches!(
+            render_prompt_template("no variables", &vars()),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -0,0 +1,151 @@
+//! Judge-model scoring of completed conversations.
+//!
+//! A judge model reads the transcript (with the tools each turn called) and
+//! rates helpfulness, tool efficiency and resolution from 1 to 5. The result
+//! is stored on the conversation and used by the dataset review to keep
+//! low-quality conversations out of training data.
+
+use anyhow::{Result, anyhow};
+use rig_core::completion::Message;
+use rig_core::completion::message::AssistantContent;
+use serde::Deserialize;
+
+use crate::factories::AgentClient;
+use crate::models::quality_score::{MAX_RATING, MIN_RATING, QualityScore};
+use crate::token_budget::summarizer::build_transcript;
+
+/// Longest rationale kept from the judge's reply.
+const MAX_RATIONALE_CHARS: usize = 300;
+
+#[derive(Deserialize)]
+struct JudgeReply {
+    helpfulness: u8,
+    tool_efficiency: u8,
+    resolution: u8,
+    #[serde(default)]
+    rationale: Option<String>,
+}
+
+/// Transcript for the judge: the conversation text plus the tools each
+/// assistant turn called, so tool efficiency can be rated.
+fn judge_transcript(history: &[Message]) -> String {
+    history
+        .iter()
+        .map(|message| {
+            let text = build_transcript(std::slice::from_ref(message));
+            let Message::Assistant { content, .. } = message else {
+                return text;
+            };
+            let tools: Vec<&str> = content
+                .iter()
+                .filter_map(|c| match c {
+                    AssistantContent::ToolCall(call) => Some(call.function.name.as_str()),
+                    _ => None,
+                })
+                .collect();
+            if tools.is_empty() {
+                text
+            } else {
+                format!("{text}\n[Tool calls: {}]", tools.join(", "))
+            }
+        })
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Parse the judge's JSON reply, tolerating text around the object.
+pub fn parse_judge_reply(reply: &str) -> Result<(u8, u8, u8, Option<String>)> {
+    let start = reply
+        .find('{')
+        .ok_or_else(|| anyhow!("Judge reply has no JSON"))?;
+    let end = reply
+        .rfind('}')
+        .ok_or_else(|| anyhow!("Judge reply has no JSON"))?;
+    let parsed: JudgeReply = serde_json::from_str(reply.get(start..=end).unwrap_or_default())?;
+    for rating in [
+        parsed.helpfulness,
+        parsed.tool_efficiency,
+        parsed.resolution,
+    ] {
+        if !(MIN_RATING..=MAX_RATING).contains(&rating) {
+            return Err(anyhow!("Judge rating {rating} is outside 1–5"));
+        }
+    }
+    let rationale = parsed
+        .rationale
+        .map(|r| {
+            r.trim()
+                .chars()
+                .take(MAX_RATIONALE_CHARS)
+                .collect::<String>()
+        })
+        .filter(|r| !r.is_empty());
+    Ok((
+        parsed.helpfulness,
+        parsed.tool_efficiency,
+        parsed.resolution,
+        rationale,
+    ))
+}
+
+/// Ask `judge` to rate `history`.
+///
+/// # Errors
+/// Returns an error if the conversation is empty, the LLM call fails or the
+/// reply cannot be parsed.
+pub async fn score_conversation(
+    judge: &AgentClient,
+    judge_model_id: &str,
+    history: &[Message],
+) -> Result<QualityScore> {
+    if history.is_empty() {
+        return Err(anyhow!("Nothing to score"));
+    }
+    let prompt = format!(
+        "You are reviewing a conversation between a user and an AI assistant for use \
+         as training data. Rate it from 1 (poor) to 5 (excellent) on:\n\
+         - helpfulness: how useful and correct the assistant's answers were\n\
+         - tool_efficiency: whether tools were used when needed, without redundant or \
+           failed calls (5 when no tools were needed and none were used)\n\
+         - resolution: whether the user's request was resolved by the end\n\
+         \n\
+         Reply with ONLY a JSON object: {{\"helpfulness\": n, \"tool_efficiency\": n, \
+         \"resolution\": n, \"rationale\": \"one sentence\"}}\n\
+         \n\
+         --- CONVERSATION ---\n\
+         {}",
+        judge_transcript(history)
+    );
+    let reply = judge.prompt(&prompt).await?;
+    let (helpfulness, tool_efficiency, resolution, rationale) = parse_judge_reply(&reply)?;
+    Ok(QualityScore {
+        helpfulness,
+        tool_efficiency,
+        resolution,
+        rationale,
+        judge_model_id: judge_model_id.to_string(),
+        message_count: history.len(),
+        scored_at: chrono::Utc::now().timestamp(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_reply_wrapped_in_text_and_rejects_out_of_range() {
+        let reply = "Here you go:\n```json\n{\"helpfulness\": 4, \"tool_efficiency\": 5, \
+                     \"resolution\": 3, \"rationale\": \" Mostly solved. \"}\n```";
+        assert_eq!(
+            parse_judge_reply(reply).unwrap(),
+            (4, 5, 3, Some("Mostly solved.".to_string()))
+        );
+        assert!(
+            parse_judge_reply(r#"{"helpfulness": 0, "tool_efficiency": 5, "resolution": 3}"#)
+                .is_err()
+        );
+        assert!(parse_judge_reply("no json here").is_err());
+    }
+}
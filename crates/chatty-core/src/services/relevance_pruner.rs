@@ -0,0 +1,275 @@
+//! Relevance pruning of long conversation histories.
+//!
+//! Used by the `Relevant` history policy. Once a history nears the model's
+//! context limit, each middle turn is embedded and compared with the current
+//! question. The least related turns are left out, lowest similarity first,
+//! until the history fits again. The first turn (which usually sets up the
+//! task) and the most recent turns are always kept. A single marker message
+//! lists the questions of the pruned turns so the model knows they existed.
+
+use std::ops::Range;
+
+use anyhow::{Result, anyhow};
+use rig_core::OneOrMany;
+use rig_core::completion::Message;
+use rig_core::completion::message::Text;
+use rig_core::message::UserContent;
+
+use crate::models::history_policy::{PrunedTurn, starts_turn};
+use crate::services::embedding_service::EmbeddingService;
+use crate::services::topic_shift::cosine_distance;
+use crate::token_budget::summarizer::{build_transcript, extract_user_text};
+
+/// Fraction of the model's context window the history may fill before
+/// pruning starts.
+const PRUNE_AT_FRACTION: f64 = 0.75;
+
+/// Rough characters per token, used to turn the context limit into a budget.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Budget used when the model's context window is unknown (matches the
+/// context shaper's snip threshold).
+const DEFAULT_BUDGET_CHARS: usize = 80_000;
+
+/// Turns always kept at the start and end of the history.
+const KEEP_HEAD_TURNS: usize = 1;
+const KEEP_RECENT_TURNS: usize = 2;
+
+/// Longest turn transcript embedded for scoring.
+const MAX_EMBED_CHARS: usize = 2_000;
+
+/// Longest question preview kept per pruned turn.
+const PREVIEW_CHARS: usize = 80;
+
+/// Result of a pruning pass.
+#[derive(Debug, Clone, Default)]
+pub struct PrunedHistory {
+    /// The history to send, with a marker in place of the pruned turns
+    pub messages: Vec<Message>,
+    /// Turns left out, in conversation order
+    pub pruned: Vec<PrunedTurn>,
+}
+
+/// History size, in characters, above which pruning starts for a model with
+/// `context_limit` tokens.
+pub fn prune_budget_chars(context_limit: Option<usize>) -> usize {
+    context_limit
+        .map(|tokens| (tokens as f64 * CHARS_PER_TOKEN as f64 * PRUNE_AT_FRACTION) as usize)
+        .unwrap_or(DEFAULT_BUDGET_CHARS)
+}
+
+/// Approximate size of `messages` as sent (serialized JSON length).
+fn history_chars(messages: &[Message]) -> usize {
+    messages
+        .iter()
+        .map(|message| serde_json::to_string(message).map_or(0, |json| json.len()))
+        .sum()
+}
+
+/// Message ranges of the turns that may be pruned: everything but the first
+/// and the most recent turns. Messages before the first user prompt count as
+/// part of the first turn.
+pub fn middle_turns(history: &[Message]) -> Vec<Range<usize>> {
+    let starts: Vec<usize> = history
+        .iter()
+        .enumerate()
+        .filter(|(_, message)| starts_turn(message))
+        .map(|(ix, _)| ix)
+        .collect();
+    if starts.len() <= KEEP_HEAD_TURNS + KEEP_RECENT_TURNS {
+        return Vec::new();
+    }
+    let last = starts.len() - KEEP_RECENT_TURNS;
+    (KEEP_HEAD_TURNS..last)
+        .map(|ix| starts[ix]..starts[ix + 1])
+        .collect()
+}
+
+/// Leave out the middle turns of `history` with the lowest `relevance`
+/// (aligned with [`middle_turns`]) until it fits in `budget_chars`.
+pub fn prune_scored(
+    history: Vec<Message>,
+    relevance: &[f32],
+    budget_chars: usize,
+) -> PrunedHistory {
+    let turns = middle_turns(&history);
+    let mut excess = history_chars(&history).saturating_sub(budget_chars);
+    if excess == 0 || turns.is_empty() || turns.len() != relevance.len() {
+        return PrunedHistory {
+            messages: history,
+            pruned: Vec::new(),
+        };
+    }
+
+    let mut order: Vec<usize> = (0..turns.len()).collect();
+    order.sort_by(|&a, &b| relevance[a].total_cmp(&relevance[b]));
+    let mut drop = vec![false; turns.len()];
+    for ix in order {
+        if excess == 0 {
+            break;
+        }
+        drop[ix] = true;
+        excess = excess.saturating_sub(history_chars(&history[turns[ix].clone()]));
+    }
+
+    let pruned: Vec<PrunedTurn> = turns
+        .iter()
+        .zip(relevance)
+        .zip(&drop)
+        .filter(|(_, dropped)| **dropped)
+        .map(|((range, score), _)| PrunedTurn {
+            preview: turn_preview(&history[range.start]),
+            messages: range.len(),
+            relevance_pct: (score.clamp(0.0, 1.0) * 100.0).round() as u8,
+        })
+        .collect();
+
+    let marker = Message::User {
+        content: OneOrMany::one(UserContent::Text(Text {
+            text: format!(
+                "[CONTEXT PRUNED: {} earlier turns unrelated to the current question were left out. \
+                 They asked:\n{}]",
+                pruned.len(),
+                pruned
+                    .iter()
+                    .map(|turn| format!("- {}", turn.preview))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ),
+        })),
+    };
+
+    let marker_at = turns
+        .iter()
+        .zip(&drop)
+        .find_map(|(range, dropped)| dropped.then_some(range.start));
+    let mut messages = Vec::with_capacity(history.len() + 1);
+    for (ix, message) in history.into_iter().enumerate() {
+        if Some(ix) == marker_at {
+            messages.push(marker.clone());
+        }
+        let in_dropped_turn = turns
+            .iter()
+            .zip(&drop)
+            .any(|(range, dropped)| *dropped && range.contains(&ix));
+        if !in_dropped_turn {
+            messages.push(message);
+        }
+    }
+
+    PrunedHistory { messages, pruned }
+}
+
+/// Score the middle turns of `history` against `query` and prune the least
+/// related ones when the history exceeds `budget_chars`.
+///
+/// # Errors
+/// Returns an error if the embedding request fails.
+pub async fn prune_by_relevance(
+    embeddings: &EmbeddingService,
+    history: Vec<Message>,
+    query: &str,
+    budget_chars: usize,
+) -> Result<PrunedHistory> {
+    let turns = middle_turns(&history);
+    if turns.is_empty() || query.trim().is_empty() || history_chars(&history) <= budget_chars {
+        return Ok(PrunedHistory {
+            messages: history,
+            pruned: Vec::new(),
+        });
+    }
+
+    let mut texts = Vec::with_capacity(turns.len() + 1);
+    texts.push(query.chars().take(MAX_EMBED_CHARS).collect::<String>());
+    texts.extend(turns.iter().map(|range| {
+        build_transcript(&history[range.clone()])
+            .chars()
+            .take(MAX_EMBED_CHARS)
+            .collect::<String>()
+    }));
+    let vectors = embeddings.embed_batch(&texts).await?;
+    let Some((query_vector, turn_vectors)) = vectors.split_first() else {
+        return Err(anyhow!("Embedding service returned no vectors"));
+    };
+    if turn_vectors.len() != turns.len() {
+        return Err(anyhow!(
+            "Expected {} turn embeddings, got {}",
+            turns.len(),
+            turn_vectors.len()
+        ));
+    }
+    let relevance: Vec<f32> = turn_vectors
+        .iter()
+        .map(|vector| cosine_distance(query_vector, vector).map_or(0.0, |distance| 1.0 - distance))
+        .collect();
+
+    Ok(prune_scored(history, &relevance, budget_chars))
+}
+
+fn turn_preview(message: &Message) -> String {
+    let text = match message {
+        Message::User { content } => extract_user_text(content),
+        _ => String::new(),
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > PREVIEW_CHARS {
+        format!("{}…", text.chars().take(PREVIEW_CHARS).collect::<String>())
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> Vec<Message> {
+        let filler = "x".repeat(400);
+        let mut history = Vec::new();
+        for question in [
+            "set up the project",
+            "weather today",
+            "fix the build",
+            "run tests",
+        ] {
+            history.push(Message::user(question));
+            history.push(Message::assistant(filler.as_str()));
+        }
+        history
+    }
+
+    #[test]
+    fn prunes_least_relevant_middle_turn_first() {
+        let history = history();
+        assert_eq!(middle_turns(&history), vec![2..4]);
+
+        let history = [history.clone(), history[2..4].to_vec()].concat();
+        let turns = middle_turns(&history);
+        assert_eq!(turns, vec![2..4, 4..6]);
+
+        // Slightly over budget: only the less relevant turn goes
+        let budget = history_chars(&history) - 100;
+        let pruned = prune_scored(history.clone(), &[0.9, 0.1], budget);
+        assert_eq!(pruned.pruned.len(), 1);
+        assert_eq!(pruned.pruned[0].preview, "fix the build");
+        assert_eq!(pruned.pruned[0].relevance_pct, 10);
+        assert_eq!(pruned.messages.len(), history.len() - 1);
+        assert_eq!(pruned.messages[2], history[2]);
+        assert_eq!(pruned.messages[3], history[3]);
+        let Message::User { content } = &pruned.messages[4] else {
+            panic!("expected the pruned-turns marker");
+        };
+        assert!(extract_user_text(content).starts_with("[CONTEXT PRUNED: 1 earlier turns"));
+
+        // Under budget: nothing changes
+        let kept = prune_scored(history.clone(), &[0.9, 0.1], usize::MAX);
+        assert!(kept.pruned.is_empty());
+        assert_eq!(kept.messages, history);
+    }
+
+    #[test]
+    fn budget_follows_context_limit() {
+        assert_eq!(prune_budget_chars(Some(10_000)), 30_000);
+        assert_eq!(prune_budget_chars(None), DEFAULT_BUDGET_CHARS);
+    }
+}
@@ -0,0 +1,162 @@
+//! Self-reflection on completed answers.
+//!
+//! After a turn completes, the agent critiques its own answer against the
+//! original question and the context its tools retrieved, and rates its
+//! confidence from 1 to 5. A score below the configured threshold triggers a
+//! single retry with the critique appended to the question.
+
+use anyhow::{Result, anyhow};
+use rig_core::completion::Message;
+use rig_core::completion::message::AssistantContent;
+use serde::Deserialize;
+
+use crate::factories::AgentClient;
+use crate::models::message_types::{SystemTrace, ToolCallState, TraceItem};
+use crate::token_budget::summarizer::extract_user_text;
+
+/// Scores below this trigger a retry unless the user picks another threshold.
+pub const DEFAULT_REFLECTION_THRESHOLD: u8 = 3;
+
+/// Longest tool output included per call, and overall.
+const MAX_OUTPUT_CHARS: usize = 1_500;
+const MAX_CONTEXT_CHARS: usize = 6_000;
+
+/// The agent's verdict on its own answer.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Critique {
+    /// Confidence that the answer is correct and complete (1–5)
+    pub score: u8,
+    /// What is wrong or missing; empty when nothing is
+    #[serde(default)]
+    pub critique: String,
+}
+
+impl Critique {
+    pub fn passes(&self, threshold: u8) -> bool {
+        self.score >= threshold
+    }
+}
+
+/// Text of the most recent user message in `history`.
+pub fn last_question(history: &[Message]) -> Option<String> {
+    history.iter().rev().find_map(|message| match message {
+        Message::User { content } => {
+            Some(extract_user_text(content)).filter(|text| !text.trim().is_empty())
+        }
+        _ => None,
+    })
+}
+
+/// Text of the final assistant message in `history`, if it ends with one.
+pub fn last_answer(history: &[Message]) -> Option<String> {
+    let Message::Assistant { content, .. } = history.last()? else {
+        return None;
+    };
+    let text = content
+        .iter()
+        .filter_map(|c| match c {
+            AssistantContent::Text(t) => Some(t.text.as_str()),
+            _ => None,
+        })
+        .collect::<String>();
+    (!text.trim().is_empty()).then_some(text)
+}
+
+/// Successful tool outputs from a turn's trace, the context the answer was
+/// based on.
+pub fn retrieved_context(trace: &SystemTrace) -> String {
+    let mut context = String::new();
+    for item in &trace.items {
+        let TraceItem::ToolCall(call) = item else {
+            continue;
+        };
+        if call.state != ToolCallState::Success {
+            continue;
+        }
+        let Some(output) = call.output.as_deref().or(call.output_preview.as_deref()) else {
+            continue;
+        };
+        let output: String = output.chars().take(MAX_OUTPUT_CHARS).collect();
+        context.push_str(&format!("[{}]\n{}\n\n", call.display_name, output.trim()));
+        if context.chars().count() >= MAX_CONTEXT_CHARS {
+            break;
+        }
+    }
+    context.chars().take(MAX_CONTEXT_CHARS).collect()
+}
+
+/// Parse the critique JSON, tolerating text around the object.
+pub fn parse_critique(reply: &str) -> Result<Critique> {
+    let start = reply
+        .find('{')
+        .ok_or_else(|| anyhow!("Critique has no JSON"))?;
+    let end = reply
+        .rfind('}')
+        .ok_or_else(|| anyhow!("Critique has no JSON"))?;
+    let mut critique: Critique = serde_json::from_str(reply.get(start..=end).unwrap_or_default())?;
+    if !(1..=5).contains(&critique.score) {
+        return Err(anyhow!("Critique score {} is outside 1–5", critique.score));
+    }
+    critique.critique = critique.critique.trim().to_string();
+    Ok(critique)
+}
+
+/// Have `agent` critique `answer` to `question`, given the tool output the
+/// answer was based on.
+///
+/// # Errors
+/// Returns an error if the LLM call fails or the reply cannot be parsed.
+pub async fn critique_answer(
+    agent: &AgentClient,
+    question: &str,
+    answer: &str,
+    context: &str,
+) -> Result<Critique> {
+    let context = if context.is_empty() {
+        "(no tools were called)"
+    } else {
+        context
+    };
+    let prompt = format!(
+        "Check the answer below before it is shown to the user. Compare it with the \
+         question and with the retrieved context. Look for factual errors, claims the \
+         context does not support, and parts of the question left unanswered.\n\
+         \n\
+         Reply with ONLY a JSON object: {{\"score\": n, \"critique\": \"...\"}} where \
+         score is your confidence from 1 (wrong or incomplete) to 5 (correct and complete) \
+         and critique names the concrete problems to fix (empty if none). Do not call tools.\n\
+         \n\
+         --- QUESTION ---\n{question}\n\n\
+         --- RETRIEVED CONTEXT ---\n{context}\n\n\
+         --- ANSWER ---\n{answer}"
+    );
+    let reply = agent.prompt(&prompt).await?;
+    parse_critique(&reply)
+}
+
+/// Text appended to the question when retrying after a failed critique.
+pub fn retry_instruction(critique: &str) -> String {
+    format!(
+        "\n\n[A review of your previous answer to this message found these problems: \
+         {critique}\nAnswer the message again, fixing them.]"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_critique_and_applies_threshold() {
+        let critique = parse_critique(
+            "Sure.\n{\"score\": 2, \"critique\": \" Misses the second question. \"}",
+        )
+        .unwrap();
+        assert_eq!(critique.score, 2);
+        assert_eq!(critique.critique, "Misses the second question.");
+        assert!(!critique.passes(DEFAULT_REFLECTION_THRESHOLD));
+        assert!(parse_critique(r#"{"score": 4}"#).unwrap().passes(3));
+        assert!(parse_critique(r#"{"score": 9}"#).is_err());
+        assert!(parse_critique("looks fine").is_err());
+    }
+}
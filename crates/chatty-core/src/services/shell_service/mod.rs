@@ -508,11 +508,36 @@ impl ShellSession {
         Ok(escaped)
     }
 
+    /// Spawn the bash process now instead of on the first command, so the
+    /// first command doesn't wait for the sandbox to start. Does nothing if
+    /// it is already running.
+    pub async fn start(&self) -> Result<()> {
+        let mut process = self.process.lock().await;
+        Self::ensure_started(
+            &mut process,
+            &self.workspace_dir,
+            self.network_isolation,
+            &self.startup_env_vars,
+        )
+        .await
+    }
+
     /// Execute a command in the persistent shell session.
     ///
     /// The command's stdout and stderr are merged (stderr redirected to stdout).
     /// Returns the combined output and exit code.
     pub async fn execute(&self, command: &str) -> Result<ShellOutput> {
+        self.execute_until(command, std::future::pending()).await
+    }
+
+    /// Like [`execute`](Self::execute), but kills the session as soon as
+    /// `cancelled` resolves (the next call respawns it), so the running command
+    /// stops instead of finishing in the background.
+    pub async fn execute_until(
+        &self,
+        command: &str,
+        cancelled: impl std::future::Future<Output = ()>,
+    ) -> Result<ShellOutput> {
         let mut process = self.process.lock().await;
         Self::ensure_started(
             &mut process,
@@ -551,7 +576,7 @@ impl ShellSession {
         let mut output = String::new();
         let timeout_duration = tokio::time::Duration::from_secs(self.timeout_seconds as u64);
 
-        let read_result = tokio::time::timeout(timeout_duration, async {
+        let read = tokio::time::timeout(timeout_duration, async {
             loop {
                 let mut line = Vec::new();
                 let bytes_read = proc
@@ -593,8 +618,17 @@ impl ShellSession {
 
                 output.push_str(&line);
             }
-        })
-        .await;
+        });
+        let read_result = tokio::select! {
+            result = read => result,
+            () = cancelled => {
+                info!("Shell command cancelled, killing session");
+                if let Some(mut proc) = process.take() {
+                    let _ = proc.child.kill().await;
+                }
+                return Err(anyhow!(crate::services::CANCELLED_BY_USER));
+            }
+        };
 
         match read_result {
             Ok(Ok(result)) => {
@@ -183,6 +183,20 @@ async fn test_shutdown() {
     assert!(!session.is_running().await);
 }
 
+#[tokio::test]
+async fn test_start_spawns_before_first_command() {
+    let session = ShellSession::with_secrets(None, 30, 51200, false, vec![]);
+    assert!(!session.is_running().await);
+
+    session.start().await.unwrap();
+    assert!(session.is_running().await);
+    // Starting again keeps the same process and its state
+    session.set_env("WARM_VAR", "kept").await.unwrap();
+    session.start().await.unwrap();
+    let output = session.execute("echo $WARM_VAR").await.unwrap();
+    assert!(output.stdout.contains("kept"));
+}
+
 #[tokio::test]
 async fn test_special_characters_in_env_value() {
     let session = ShellSession::with_secrets(None, 30, 51200, false, vec![]);
@@ -60,15 +60,22 @@ fn fnv1a_hash(s: &str) -> String {
     format!("{:016x}", hash)
 }
 
+/// Fingerprint of `content` embedded with `model`, so switching the skills
+/// embedding model invalidates the cache too.
+fn embedding_fingerprint(model: &str, content: &str) -> String {
+    fnv1a_hash(&format!("{model}\n{content}"))
+}
+
 /// Load a cached skill embedding from `skill_dir` if the content fingerprint matches.
 ///
 /// Cache layout (both files live inside the skill's own subdirectory):
 /// - `SKILL.embedding`      — raw `f32` values, little-endian
-/// - `SKILL.embedding.hash` — FNV-1a hex of the skill content that was embedded
+/// - `SKILL.embedding.hash` — FNV-1a hex of the embedding model and the skill
+///   content that was embedded
 ///
 /// Returns `None` when the cache is missing, unreadable, or stale.
-async fn load_cached_embedding(skill_dir: &Path, content: &str) -> Option<Vec<f32>> {
-    let expected = fnv1a_hash(content);
+async fn load_cached_embedding(skill_dir: &Path, model: &str, content: &str) -> Option<Vec<f32>> {
+    let expected = embedding_fingerprint(model, content);
     let stored = tokio::fs::read_to_string(skill_dir.join("SKILL.embedding.hash"))
         .await
         .ok()?;
@@ -90,13 +97,13 @@ async fn load_cached_embedding(skill_dir: &Path, content: &str) -> Option<Vec<f3
 }
 
 /// Write `embedding` alongside the skill file so future loads skip the API call.
-async fn save_cached_embedding(skill_dir: &Path, content: &str, embedding: &[f32]) {
+async fn save_cached_embedding(skill_dir: &Path, model: &str, content: &str, embedding: &[f32]) {
     let bytes: Vec<u8> = embedding.iter().flat_map(|f| f.to_le_bytes()).collect();
     if let Err(e) = tokio::fs::write(skill_dir.join("SKILL.embedding"), &bytes).await {
         warn!(error = ?e, "Failed to write skill embedding cache");
         return;
     }
-    let hash = fnv1a_hash(content);
+    let hash = embedding_fingerprint(model, content);
     if let Err(e) = tokio::fs::write(skill_dir.join("SKILL.embedding.hash"), hash).await {
         warn!(error = ?e, "Failed to write skill embedding hash");
     }
@@ -226,6 +233,14 @@ impl SkillService {
         }
     }
 
+    /// Embedding service skills are indexed with, if any.
+    ///
+    /// May use a different model than the memory store, so a query embedding
+    /// passed to [`Self::load_hits`] must come from this service.
+    pub fn embedding_service(&self) -> Option<&EmbeddingService> {
+        self.embedding_service.as_ref()
+    }
+
     /// Return the path to the global skills directory.
     pub fn global_skills_dir(&self) -> &Path {
         &self.global_skills_dir
@@ -264,6 +279,7 @@ impl SkillService {
     /// Load skill hits from both the workspace and global directories.
     ///
     /// Skills are scored by cosine similarity (cached) or keyword overlap.
+    /// `query_embedding` must be computed with [`Self::embedding_service`].
     /// The caller should sort and truncate the returned hits together with
     /// any persisted memory hits before injecting them into context.
     pub async fn load_hits(
@@ -334,26 +350,26 @@ impl SkillService {
                 .and_then(|n| n.to_str())
                 .unwrap_or("unknown");
 
-            let score = if let Some(query_emb) = query_embedding {
+            let score = if let (Some(query_emb), Some(svc)) =
+                (query_embedding, &self.embedding_service)
+            {
                 // Resolve skill embedding: cached → compute+cache → keyword fallback
-                let skill_emb = match load_cached_embedding(&path, &content).await {
+                let model = svc.model_identifier();
+                let skill_emb = match load_cached_embedding(&path, &model, &content).await {
                     Some(emb) => Some(emb),
-                    None => match &self.embedding_service {
-                        Some(svc) => match svc.embed(&content).await {
-                            Ok(emb) => {
-                                save_cached_embedding(&path, &content, &emb).await;
-                                Some(emb)
-                            }
-                            Err(e) => {
-                                warn!(
-                                    error = ?e,
-                                    skill = %skill_name,
-                                    "Failed to embed local skill, using keyword score"
-                                );
-                                None
-                            }
-                        },
-                        None => None,
+                    None => match svc.embed(&content).await {
+                        Ok(emb) => {
+                            save_cached_embedding(&path, &model, &content, &emb).await;
+                            Some(emb)
+                        }
+                        Err(e) => {
+                            warn!(
+                                error = ?e,
+                                skill = %skill_name,
+                                "Failed to embed local skill, using keyword score"
+                            );
+                            None
+                        }
                     },
                 };
                 skill_emb
@@ -432,6 +448,32 @@ mod tests {
         assert!(extract_frontmatter_description(content).is_none());
     }
 
+    #[tokio::test]
+    async fn cached_embedding_is_stale_for_another_model() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content = "# Skill";
+        save_cached_embedding(tmp.path(), "Ollama:nomic-embed-text", content, &[0.5, 1.0]).await;
+
+        assert_eq!(
+            load_cached_embedding(tmp.path(), "Ollama:nomic-embed-text", content).await,
+            Some(vec![0.5, 1.0])
+        );
+        assert!(
+            load_cached_embedding(
+                tmp.path(),
+                "OpenRouter:openai/text-embedding-3-small",
+                content
+            )
+            .await
+            .is_none()
+        );
+        assert!(
+            load_cached_embedding(tmp.path(), "Ollama:nomic-embed-text", "# Changed")
+                .await
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn load_hits_returns_description_not_full_content() {
         let tmp = tempfile::tempdir().unwrap();
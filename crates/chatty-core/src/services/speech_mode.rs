@@ -0,0 +1,519 @@
+//! Hands-free voice conversation.
+//!
+//! Speech mode chains three user-configured shell commands: a recorder that
+//! streams the microphone as raw 16 kHz mono 16-bit PCM on stdout, a
+//! transcription command that prints the text of a WAV file (`{audio}` is
+//! replaced with its path), and a text-to-speech command that reads the reply
+//! from stdin.
+//!
+//! Turn taking is decided here by a small energy-based voice activity
+//! detector: an utterance starts after a short run of loud frames and ends
+//! after enough trailing silence. Speech that starts while a reply is being
+//! spoken is a barge-in, which needs a longer run of loud frames so the
+//! speaker's own output is less likely to interrupt itself.
+//!
+//! Transcripts are sent as ordinary user messages, so the spoken exchange
+//! stays in the conversation history like any typed one.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
+
+use anyhow::{Context, Result, anyhow, bail};
+use regex::Regex;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Replaced with the path of the WAV file in a transcription command (also
+/// used for the audio track of video attachments).
+pub const AUDIO_PLACEHOLDER: &str = "{audio}";
+/// Sample rate the recorder command must produce.
+pub const SAMPLE_RATE: u32 = 16_000;
+/// Samples per VAD frame (30 ms).
+const FRAME_SAMPLES: usize = 480;
+
+/// Names the WAV files of utterances transcribed this session.
+static UTTERANCE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Where a speech-mode turn stands, shown next to the chat input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpeechPhase {
+    /// Waiting for the user to speak (or hearing them)
+    Listening,
+    /// Turning the last utterance into text
+    Transcribing,
+    /// The reply is streaming
+    Thinking,
+    /// The reply is being read aloud
+    Speaking,
+}
+
+/// Thresholds of the voice activity detector, in 30 ms frames.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VadConfig {
+    /// RMS level (0.0–1.0 of full scale) above which a frame counts as voiced
+    pub threshold: f32,
+    /// Voiced frames in a row that start an utterance
+    pub start_frames: usize,
+    /// Voiced frames in a row that start an utterance while a reply plays
+    pub barge_in_frames: usize,
+    /// Silent frames in a row that end an utterance
+    pub end_frames: usize,
+    /// Voiced frames an utterance needs to be transcribed at all
+    pub min_speech_frames: usize,
+    /// Frames kept from before the start so the first syllable isn't cut
+    pub pre_roll_frames: usize,
+    /// Frames after which an utterance is ended regardless of silence
+    pub max_frames: usize,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.02,
+            start_frames: 3,
+            barge_in_frames: 10,
+            end_frames: 27,
+            min_speech_frames: 8,
+            pre_roll_frames: 10,
+            max_frames: 2_000,
+        }
+    }
+}
+
+/// Turn-taking events found in the microphone stream.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VadEvent {
+    /// The user started talking
+    SpeechStarted,
+    /// The user stopped talking; the utterance's samples
+    SpeechEnded(Vec<i16>),
+    /// What sounded like speech was too short to transcribe
+    SpeechDiscarded,
+}
+
+/// Energy-based voice activity detector over 16 kHz mono samples.
+pub struct VoiceActivityDetector {
+    config: VadConfig,
+    /// Samples not yet making up a whole frame
+    partial: Vec<i16>,
+    pre_roll: VecDeque<Vec<i16>>,
+    in_speech: bool,
+    voiced_run: usize,
+    silent_run: usize,
+    speech_frames: usize,
+    frames: usize,
+    utterance: Vec<i16>,
+}
+
+impl VoiceActivityDetector {
+    pub fn new(config: VadConfig) -> Self {
+        Self {
+            config,
+            partial: Vec::with_capacity(FRAME_SAMPLES),
+            pre_roll: VecDeque::new(),
+            in_speech: false,
+            voiced_run: 0,
+            silent_run: 0,
+            speech_frames: 0,
+            frames: 0,
+            utterance: Vec::new(),
+        }
+    }
+
+    /// Feed `samples`; `barge_in` is set while a reply is being spoken.
+    pub fn push(&mut self, samples: &[i16], barge_in: bool) -> Vec<VadEvent> {
+        let mut events = Vec::new();
+        for &sample in samples {
+            self.partial.push(sample);
+            if self.partial.len() == FRAME_SAMPLES {
+                let frame = std::mem::replace(&mut self.partial, Vec::with_capacity(FRAME_SAMPLES));
+                events.extend(self.push_frame(frame, barge_in));
+            }
+        }
+        events
+    }
+
+    fn push_frame(&mut self, frame: Vec<i16>, barge_in: bool) -> Option<VadEvent> {
+        let voiced = frame_level(&frame) >= self.config.threshold;
+        if !self.in_speech {
+            self.voiced_run = if voiced { self.voiced_run + 1 } else { 0 };
+            self.pre_roll.push_back(frame);
+            while self.pre_roll.len() > self.config.pre_roll_frames.max(self.voiced_run) {
+                self.pre_roll.pop_front();
+            }
+            let needed = if barge_in {
+                self.config.barge_in_frames
+            } else {
+                self.config.start_frames
+            };
+            if self.voiced_run < needed.max(1) {
+                return None;
+            }
+            self.in_speech = true;
+            self.speech_frames = self.voiced_run;
+            self.frames = self.pre_roll.len();
+            self.silent_run = 0;
+            self.utterance = self.pre_roll.drain(..).flatten().collect();
+            return Some(VadEvent::SpeechStarted);
+        }
+
+        self.utterance.extend_from_slice(&frame);
+        self.frames += 1;
+        if voiced {
+            self.speech_frames += 1;
+            self.silent_run = 0;
+        } else {
+            self.silent_run += 1;
+        }
+        if self.silent_run < self.config.end_frames && self.frames < self.config.max_frames {
+            return None;
+        }
+        self.in_speech = false;
+        self.voiced_run = 0;
+        let utterance = std::mem::take(&mut self.utterance);
+        if self.speech_frames < self.config.min_speech_frames {
+            Some(VadEvent::SpeechDiscarded)
+        } else {
+            Some(VadEvent::SpeechEnded(utterance))
+        }
+    }
+}
+
+/// RMS level of a frame as a fraction of full scale.
+fn frame_level(frame: &[i16]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = frame.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    ((sum / frame.len() as f64).sqrt() / i16::MAX as f64) as f32
+}
+
+/// The running recorder command and the detector listening to it. The
+/// recorder is killed when the listener is dropped.
+pub struct SpeechListener {
+    child: tokio::process::Child,
+    stdout: tokio::process::ChildStdout,
+    vad: VoiceActivityDetector,
+    barge_in: Arc<AtomicBool>,
+    /// Events found in the last read that haven't been returned yet
+    pending: VecDeque<VadEvent>,
+    /// Odd trailing byte of the last read
+    carry: Option<u8>,
+}
+
+impl SpeechListener {
+    /// Start `record_command`, which must write raw 16 kHz mono signed
+    /// 16-bit little-endian PCM to stdout until it is killed.
+    pub fn start(record_command: &str, config: VadConfig) -> Result<Self> {
+        let mut child = shell_command(record_command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .context("Failed to start the recording command")?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("Recording command has no stdout"))?;
+        Ok(Self {
+            child,
+            stdout,
+            vad: VoiceActivityDetector::new(config),
+            barge_in: Arc::new(AtomicBool::new(false)),
+            pending: VecDeque::new(),
+            carry: None,
+        })
+    }
+
+    /// Flag shared with the playback side: set while a reply is spoken so
+    /// speech has to last longer before it counts as an interruption.
+    pub fn barge_in_flag(&self) -> Arc<AtomicBool> {
+        self.barge_in.clone()
+    }
+
+    /// The next turn-taking event, or `None` once the recorder has exited.
+    pub async fn next_event(&mut self) -> Option<VadEvent> {
+        let mut buf = vec![0u8; 4096];
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+            let read = match self.stdout.read(&mut buf).await {
+                Ok(0) | Err(_) => {
+                    let _ = self.child.start_kill();
+                    return None;
+                }
+                Ok(read) => read,
+            };
+            let mut bytes = Vec::with_capacity(read + 1);
+            bytes.extend(self.carry.take());
+            bytes.extend_from_slice(&buf[..read]);
+            if bytes.len() % 2 == 1 {
+                self.carry = bytes.pop();
+            }
+            let samples: Vec<i16> = bytes
+                .chunks_exact(2)
+                .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+                .collect();
+            let barge_in = self.barge_in.load(Ordering::Relaxed);
+            self.pending.extend(self.vad.push(&samples, barge_in));
+        }
+    }
+}
+
+/// `samples` as the bytes of a 16 kHz mono 16-bit WAV file.
+pub fn wav_bytes(samples: &[i16]) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut out = Vec::with_capacity(44 + data_len as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    out.extend_from_slice(&1u16.to_le_bytes()); // mono
+    out.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    out.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    out.extend_from_slice(&2u16.to_le_bytes());
+    out.extend_from_slice(&16u16.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        out.extend_from_slice(&sample.to_le_bytes());
+    }
+    out
+}
+
+/// Transcribe an utterance with `command`; its stdout is the transcript.
+pub async fn transcribe_utterance(samples: &[i16], command: &str) -> Result<String> {
+    let dir = std::env::temp_dir().join("chatty-speech");
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .context("Failed to create the speech temp directory")?;
+    let audio: PathBuf = dir.join(format!(
+        "utterance-{}-{}.wav",
+        std::process::id(),
+        UTTERANCE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    tokio::fs::write(&audio, wav_bytes(samples))
+        .await
+        .context("Failed to write the utterance")?;
+
+    let output = shell_command(&build_transcribe_command(command, &audio))
+        .output()
+        .await
+        .context("Failed to run the transcription command");
+    let _ = tokio::fs::remove_file(&audio).await;
+    let output = output?;
+    if !output.status.success() {
+        bail!(
+            "Transcription command failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Read `text` aloud with `command`, which gets the text on stdin. Dropping
+/// the returned future stops the playback.
+pub async fn speak(text: &str, command: &str) -> Result<()> {
+    let mut child = shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .context("Failed to start the text-to-speech command")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .await
+            .context("Failed to pass the reply to the text-to-speech command")?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .context("Failed to run the text-to-speech command")?;
+    if !output.status.success() {
+        bail!(
+            "Text-to-speech command failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Substitute the quoted audio path for [`AUDIO_PLACEHOLDER`], or append it
+/// when the command has no placeholder.
+pub(crate) fn build_transcribe_command(command: &str, audio: &Path) -> String {
+    let quoted = format!("\"{}\"", audio.to_string_lossy().replace('"', "\\\""));
+    if command.contains(AUDIO_PLACEHOLDER) {
+        command.replace(AUDIO_PLACEHOLDER, &quoted)
+    } else {
+        format!("{} {}", command.trim(), quoted)
+    }
+}
+
+/// Run `command_line` through the platform shell.
+pub(crate) fn shell_command(command_line: &str) -> tokio::process::Command {
+    #[cfg(windows)]
+    {
+        let mut command = tokio::process::Command::new("cmd");
+        command.args(["/C", command_line]);
+        command
+    }
+    #[cfg(not(windows))]
+    {
+        let mut command = tokio::process::Command::new("sh");
+        command.args(["-c", command_line]);
+        command
+    }
+}
+
+static RE_THINKING: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)<(?:think|thinking|thought)>.*?</(?:think|thinking|thought)>").unwrap()
+});
+static RE_LINK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"!?\[([^\]]*)\]\([^)]*\)").unwrap());
+static RE_CITATION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s*\[\d+(?:,\s*\d+)*\]").unwrap());
+static RE_EMPHASIS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\*\*|__|~~|[*`]").unwrap());
+
+/// The part of an assistant message worth reading aloud: prose without
+/// Markdown syntax, thinking blocks or citation markers, with code blocks
+/// replaced by a short note.
+pub fn speakable_text(content: &str) -> String {
+    let content = RE_THINKING.replace_all(content, "");
+    let mut prose = String::new();
+    let mut in_fence = false;
+    for line in content.lines() {
+        let line = line.trim_start();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            if !in_fence {
+                prose.push_str("(code block omitted)\n");
+            }
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let line = line.trim_start_matches(['#', '>']).trim_start();
+        let line = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|bullet| line.strip_prefix(bullet))
+            .unwrap_or(line);
+        let line = RE_LINK.replace_all(line, "$1");
+        let line = RE_CITATION.replace_all(&line, "");
+        prose.push_str(&RE_EMPHASIS.replace_all(&line, ""));
+        prose.push('\n');
+    }
+    prose.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(frames: usize, amplitude: i16) -> Vec<i16> {
+        (0..frames * FRAME_SAMPLES)
+            .map(|i| if i % 2 == 0 { amplitude } else { -amplitude })
+            .collect()
+    }
+
+    fn silence(frames: usize) -> Vec<i16> {
+        vec![0; frames * FRAME_SAMPLES]
+    }
+
+    #[test]
+    fn utterance_starts_and_ends_on_silence() {
+        let config = VadConfig::default();
+        let mut vad = VoiceActivityDetector::new(config);
+        assert!(vad.push(&silence(5), false).is_empty());
+        assert_eq!(
+            vad.push(&tone(config.start_frames, 8_000), false),
+            vec![VadEvent::SpeechStarted]
+        );
+        assert!(vad.push(&tone(20, 8_000), false).is_empty());
+        let events = vad.push(&silence(config.end_frames), false);
+        let [VadEvent::SpeechEnded(samples)] = events.as_slice() else {
+            panic!("expected the utterance to end, got {:?}", events);
+        };
+        // Pre-roll, speech and the trailing silence are all kept
+        let expected = (5 + config.start_frames + 20 + config.end_frames) * FRAME_SAMPLES;
+        assert_eq!(samples.len(), expected);
+    }
+
+    #[test]
+    fn short_noise_is_discarded() {
+        let config = VadConfig::default();
+        let mut vad = VoiceActivityDetector::new(config);
+        assert_eq!(
+            vad.push(&tone(config.start_frames, 8_000), false),
+            vec![VadEvent::SpeechStarted]
+        );
+        assert_eq!(
+            vad.push(&silence(config.end_frames), false),
+            vec![VadEvent::SpeechDiscarded]
+        );
+        assert!(vad.push(&silence(3), false).is_empty());
+    }
+
+    #[test]
+    fn barge_in_needs_a_longer_run() {
+        let config = VadConfig::default();
+        let mut vad = VoiceActivityDetector::new(config);
+        assert!(vad.push(&tone(config.start_frames, 8_000), true).is_empty());
+        let remaining = config.barge_in_frames - config.start_frames;
+        assert_eq!(
+            vad.push(&tone(remaining, 8_000), true),
+            vec![VadEvent::SpeechStarted]
+        );
+    }
+
+    #[test]
+    fn quiet_input_never_starts_speech() {
+        let mut vad = VoiceActivityDetector::new(VadConfig::default());
+        assert!(vad.push(&tone(100, 200), false).is_empty());
+    }
+
+    #[test]
+    fn wav_header_describes_the_samples() {
+        let bytes = wav_bytes(&[1, -1, 2]);
+        assert_eq!(bytes.len(), 44 + 6);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(&bytes[24..28], &SAMPLE_RATE.to_le_bytes());
+        assert_eq!(&bytes[40..44], &6u32.to_le_bytes());
+        assert_eq!(&bytes[44..46], &1i16.to_le_bytes());
+    }
+
+    #[test]
+    fn transcribe_command_gets_the_quoted_audio_path() {
+        let audio = Path::new("/tmp/utterance 1.wav");
+        assert_eq!(
+            build_transcribe_command("whisper-cli -nt -f {audio}", audio),
+            "whisper-cli -nt -f \"/tmp/utterance 1.wav\""
+        );
+        assert_eq!(
+            build_transcribe_command("transcribe ", audio),
+            "transcribe \"/tmp/utterance 1.wav\""
+        );
+    }
+
+    #[test]
+    fn speakable_text_drops_markup_and_code() {
+        let text = speakable_text(
+            "<think>plan</think>## Result\n\nUse **this** [1], see [docs](https://example.com).\n\n```rust\nfn main() {}\n```\n\n- Done.",
+        );
+        assert!(text.contains("Result"));
+        assert!(text.contains("Use this"));
+        assert!(text.contains("(code block omitted)"));
+        assert!(!text.contains("fn main"));
+        assert!(!text.contains("plan"));
+        assert!(!text.contains("**"));
+        assert!(!text.contains("[1]"));
+        assert!(text.contains("Use this, see docs."));
+        assert!(text.ends_with("\nDone."));
+    }
+}
@@ -0,0 +1,599 @@
+//! Spell checking with Hunspell dictionaries.
+//!
+//! Reads the `.aff`/`.dic` pairs shipped with LibreOffice, Firefox and most
+//! Linux distributions (`/usr/share/hunspell`), the macOS `~/Library/Spelling`
+//! folder, or `<data dir>/chatty/dictionaries` for user-installed ones.
+//!
+//! Only the part of the Hunspell format needed for checking prose is
+//! implemented: dictionary stems with affix flags, single prefix or suffix
+//! rules with their conditions, the `FLAG` encodings and the `TRY` alphabet
+//! used for suggestions. Compounding and two-level affixes are not applied,
+//! so a handful of rare forms may be flagged.
+//!
+//! [`SpellChecker::misspellings`] skips what usually is not prose: fenced and
+//! inline code, URLs, paths, mentions, identifiers, acronyms and words with
+//! digits.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+
+/// Most suggestions offered per word.
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// Words longer than this are not given suggestions (too many candidates).
+const MAX_SUGGEST_LEN: usize = 30;
+
+type Flag = u32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FlagMode {
+    /// One character per flag (default)
+    Char,
+    /// Two characters per flag (`FLAG long`)
+    Long,
+    /// Comma-separated numbers (`FLAG num`)
+    Num,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum CondElem {
+    Any,
+    Char(char),
+    Set { negated: bool, chars: Vec<char> },
+}
+
+impl CondElem {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CondElem::Any => true,
+            CondElem::Char(expected) => *expected == c,
+            CondElem::Set { negated, chars } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct AffixRule {
+    flag: Flag,
+    /// Removed from the stem before `add` is attached
+    strip: String,
+    /// Condition on the end (suffix) or start (prefix) of the stem
+    condition: Vec<CondElem>,
+}
+
+/// A misspelled word in checked text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Misspelling {
+    /// Byte range of the word in the checked text
+    pub range: Range<usize>,
+    pub word: String,
+}
+
+/// A loaded Hunspell dictionary.
+pub struct SpellChecker {
+    /// Dictionary code, e.g. `en_US`
+    language: String,
+    words: HashMap<String, Vec<Flag>>,
+    /// Suffix rules keyed by the text they add
+    suffixes: HashMap<String, Vec<AffixRule>>,
+    /// Prefix rules keyed by the text they add
+    prefixes: HashMap<String, Vec<AffixRule>>,
+    /// Characters tried when building suggestions
+    try_chars: Vec<char>,
+}
+
+impl SpellChecker {
+    /// Load the dictionary `language` (e.g. `en_US`) from the first
+    /// dictionary directory that has it.
+    ///
+    /// # Errors
+    /// Returns an error if no such dictionary is installed or it cannot be read.
+    pub fn load(language: &str) -> Result<Self> {
+        let dir = dictionary_dirs()
+            .into_iter()
+            .find(|dir| dictionary_files(dir, language).is_some())
+            .ok_or_else(|| anyhow!("No Hunspell dictionary installed for {language}"))?;
+        let (aff_path, dic_path) =
+            dictionary_files(&dir, language).expect("checked by the find above");
+        let aff = read_dictionary_file(&aff_path)?;
+        let dic = read_dictionary_file(&dic_path)?;
+        Ok(Self::from_parts(language, &aff, &dic))
+    }
+
+    /// Build a checker from the contents of an `.aff` and a `.dic` file.
+    pub fn from_parts(language: &str, aff: &str, dic: &str) -> Self {
+        let mut flag_mode = FlagMode::Char;
+        let mut suffixes: HashMap<String, Vec<AffixRule>> = HashMap::new();
+        let mut prefixes: HashMap<String, Vec<AffixRule>> = HashMap::new();
+        let mut try_chars = Vec::new();
+
+        for line in aff.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["FLAG", mode, ..] => {
+                    flag_mode = match *mode {
+                        "long" => FlagMode::Long,
+                        "num" => FlagMode::Num,
+                        _ => FlagMode::Char,
+                    };
+                }
+                ["TRY", chars, ..] => try_chars = chars.chars().collect(),
+                // Rule lines have five or more fields; the header has four
+                [kind @ ("SFX" | "PFX"), flag, strip, add, condition, ..] => {
+                    let Some(flag) = parse_flags(flag, flag_mode).first().copied() else {
+                        continue;
+                    };
+                    let add = add.split('/').next().unwrap_or_default();
+                    let rule = AffixRule {
+                        flag,
+                        strip: if *strip == "0" {
+                            String::new()
+                        } else {
+                            strip.to_string()
+                        },
+                        condition: parse_condition(condition),
+                    };
+                    let add = if add == "0" { "" } else { add };
+                    let rules = if *kind == "SFX" {
+                        &mut suffixes
+                    } else {
+                        &mut prefixes
+                    };
+                    rules.entry(add.to_string()).or_default().push(rule);
+                }
+                _ => {}
+            }
+        }
+
+        let mut words: HashMap<String, Vec<Flag>> = HashMap::new();
+        // The first line is the word count
+        for line in dic.lines().skip(1) {
+            let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+            if entry.is_empty() {
+                continue;
+            }
+            let (word, flags) = match entry.split_once('/') {
+                Some((word, flags)) => (word, parse_flags(flags, flag_mode)),
+                None => (entry, Vec::new()),
+            };
+            words.entry(word.to_string()).or_default().extend(flags);
+        }
+
+        if try_chars.is_empty() {
+            try_chars = ('a'..='z').collect();
+        }
+
+        Self {
+            language: language.to_string(),
+            words,
+            suffixes,
+            prefixes,
+            try_chars,
+        }
+    }
+
+    /// Code of the loaded dictionary, e.g. `en_US`.
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Whether `word` is spelled correctly. A capitalized word is also
+    /// accepted in lower case (sentence starts).
+    pub fn check(&self, word: &str) -> bool {
+        let word = &word.replace('’', "'");
+        if word.is_empty() || self.check_exact(word) {
+            return true;
+        }
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) if first.is_uppercase() => {
+                let lowered: String = first.to_lowercase().chain(chars).collect();
+                self.check_exact(&lowered)
+            }
+            _ => false,
+        }
+    }
+
+    fn check_exact(&self, word: &str) -> bool {
+        let word = word.trim_end_matches('\'');
+        let word = word.strip_suffix("'s").unwrap_or(word);
+        if self.words.contains_key(word) {
+            return true;
+        }
+
+        // word = stem - strip + add
+        for (end, _) in word
+            .char_indices()
+            .chain(std::iter::once((word.len(), ' ')))
+        {
+            let (base, add) = word.split_at(end);
+            if let Some(rules) = self.suffixes.get(add) {
+                for rule in rules {
+                    let stem = format!("{base}{}", rule.strip);
+                    if self.has_flag(&stem, rule.flag) && matches_end(&stem, &rule.condition) {
+                        return true;
+                    }
+                }
+            }
+            let (add, rest) = word.split_at(end);
+            if let Some(rules) = self.prefixes.get(add) {
+                for rule in rules {
+                    let stem = format!("{}{rest}", rule.strip);
+                    if self.has_flag(&stem, rule.flag) && matches_start(&stem, &rule.condition) {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    fn has_flag(&self, stem: &str, flag: Flag) -> bool {
+        self.words
+            .get(stem)
+            .is_some_and(|flags| flags.contains(&flag))
+    }
+
+    /// Correctly spelled words one edit away from `word`, best first.
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let chars: Vec<char> = word.chars().collect();
+        if chars.is_empty() || chars.len() > MAX_SUGGEST_LEN {
+            return Vec::new();
+        }
+
+        let mut candidates: Vec<String> = Vec::new();
+        // Swapped neighbours and dropped letters are the most common typos
+        for i in 0..chars.len().saturating_sub(1) {
+            let mut swapped = chars.clone();
+            swapped.swap(i, i + 1);
+            candidates.push(swapped.into_iter().collect());
+        }
+        for i in 0..chars.len() {
+            for &c in &self.try_chars {
+                let mut replaced = chars.clone();
+                replaced[i] = c;
+                candidates.push(replaced.into_iter().collect());
+            }
+        }
+        for i in 0..=chars.len() {
+            for &c in &self.try_chars {
+                let mut inserted = chars.clone();
+                inserted.insert(i, c);
+                candidates.push(inserted.into_iter().collect());
+            }
+        }
+        for i in 0..chars.len() {
+            let mut removed = chars.clone();
+            removed.remove(i);
+            candidates.push(removed.into_iter().collect());
+        }
+        // Missing space between two words
+        for i in 1..chars.len() {
+            let (left, right) = chars.split_at(i);
+            let (left, right): (String, String) = (left.iter().collect(), right.iter().collect());
+            if left.chars().count() > 1 && self.check(&left) && self.check(&right) {
+                candidates.push(format!("{left} {right}"));
+            }
+        }
+
+        let mut seen = HashSet::new();
+        candidates
+            .into_iter()
+            .filter(|candidate| candidate != word)
+            .filter(|candidate| candidate.contains(' ') || self.check(candidate))
+            .filter(|candidate| seen.insert(candidate.clone()))
+            .take(MAX_SUGGESTIONS)
+            .collect()
+    }
+
+    /// Misspelled words in `text`, in order.
+    pub fn misspellings(&self, text: &str) -> Vec<Misspelling> {
+        prose_words(text)
+            .filter(|(_, word)| !self.check(word))
+            .map(|(start, word)| Misspelling {
+                range: start..start + word.len(),
+                word: word.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Words of `text` worth spell checking, with their byte offsets.
+fn prose_words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut words = Vec::new();
+    let mut in_fence = false;
+    let mut line_start = 0;
+
+    for line in text.split_inclusive('\n') {
+        let offset = line_start;
+        line_start += line.len();
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        // Drop inline code spans: every other backtick-separated piece
+        let mut piece_start = offset;
+        for (ix, piece) in line.split('`').enumerate() {
+            if ix % 2 == 0 {
+                collect_chunk_words(piece, piece_start, &mut words);
+            }
+            piece_start += piece.len() + 1;
+        }
+    }
+    words.into_iter()
+}
+
+fn collect_chunk_words<'a>(piece: &'a str, offset: usize, words: &mut Vec<(usize, &'a str)>) {
+    let mut chunk_start = 0;
+    for chunk in piece.split_inclusive(char::is_whitespace) {
+        let start = chunk_start;
+        chunk_start += chunk.len();
+        let chunk_trimmed = chunk.trim_end();
+        if chunk_trimmed.contains("://")
+            || chunk_trimmed.starts_with("www.")
+            || chunk_trimmed.contains(['@', '/', '\\', '_', '<', '>', '{', '}', '=', '$'])
+            || chunk_trimmed.chars().any(|c| c.is_ascii_digit())
+        {
+            continue;
+        }
+        // file.rs, foo.bar(): dots between letters mean code, not prose
+        let dotted = chunk_trimmed
+            .trim_end_matches(|c: char| !c.is_alphanumeric())
+            .contains('.');
+        if dotted {
+            continue;
+        }
+
+        let mut word_start = None;
+        let push = |from: usize, to: usize, words: &mut Vec<(usize, &'a str)>| {
+            let word = chunk[from..to].trim_end_matches(['\'', '’']);
+            // Skip single letters, ACRONYMS and camelCase identifiers
+            let upper_after_first = word.chars().skip(1).any(char::is_uppercase);
+            if word.chars().count() > 1 && !upper_after_first {
+                words.push((offset + start + from, word));
+            }
+        };
+        for (ix, c) in chunk.char_indices() {
+            let part_of_word =
+                c.is_alphabetic() || (matches!(c, '\'' | '’') && word_start.is_some());
+            match (part_of_word, word_start) {
+                (true, None) => word_start = Some(ix),
+                (false, Some(from)) => {
+                    push(from, ix, words);
+                    word_start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(from) = word_start {
+            push(from, chunk.len(), words);
+        }
+    }
+}
+
+fn parse_flags(flags: &str, mode: FlagMode) -> Vec<Flag> {
+    match mode {
+        FlagMode::Char => flags.chars().map(|c| c as Flag).collect(),
+        FlagMode::Long => flags
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .map(|pair| pair.iter().fold(0, |acc, &c| (acc << 16) | c as Flag))
+            .collect(),
+        FlagMode::Num => flags
+            .split(',')
+            .filter_map(|n| n.trim().parse().ok())
+            .collect(),
+    }
+}
+
+fn parse_condition(condition: &str) -> Vec<CondElem> {
+    if condition == "." {
+        return Vec::new();
+    }
+    let mut elems = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => elems.push(CondElem::Any),
+            '[' => {
+                let mut set: Vec<char> = chars.by_ref().take_while(|&c| c != ']').collect();
+                let negated = set.first() == Some(&'^');
+                if negated {
+                    set.remove(0);
+                }
+                elems.push(CondElem::Set {
+                    negated,
+                    chars: set,
+                });
+            }
+            c => elems.push(CondElem::Char(c)),
+        }
+    }
+    elems
+}
+
+fn matches_end(stem: &str, condition: &[CondElem]) -> bool {
+    let tail: Vec<char> = stem.chars().rev().take(condition.len()).collect();
+    tail.len() == condition.len()
+        && condition
+            .iter()
+            .rev()
+            .zip(&tail)
+            .all(|(elem, &c)| elem.matches(c))
+}
+
+fn matches_start(stem: &str, condition: &[CondElem]) -> bool {
+    let head: Vec<char> = stem.chars().take(condition.len()).collect();
+    head.len() == condition.len()
+        && condition
+            .iter()
+            .zip(&head)
+            .all(|(elem, &c)| elem.matches(c))
+}
+
+/// Directories searched for dictionaries, user-installed first.
+pub fn dictionary_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(data) = dirs::data_dir() {
+        dirs.push(data.join("chatty").join("dictionaries"));
+    }
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join("Library").join("Spelling"));
+    }
+    for dir in [
+        "/usr/share/hunspell",
+        "/usr/share/myspell",
+        "/usr/share/myspell/dicts",
+        "/usr/local/share/hunspell",
+        "/Library/Spelling",
+    ] {
+        dirs.push(PathBuf::from(dir));
+    }
+    dirs
+}
+
+fn dictionary_files(dir: &Path, language: &str) -> Option<(PathBuf, PathBuf)> {
+    let aff = dir.join(format!("{language}.aff"));
+    let dic = dir.join(format!("{language}.dic"));
+    (aff.is_file() && dic.is_file()).then_some((aff, dic))
+}
+
+/// Codes of the installed dictionaries (e.g. `de_DE`, `en_US`), sorted.
+pub fn available_dictionaries() -> Vec<String> {
+    let mut codes: Vec<String> = dictionary_dirs()
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let code = path.file_stem()?.to_str()?.to_string();
+            (path.extension()? == "dic" && path.with_extension("aff").is_file()).then_some(code)
+        })
+        .collect();
+    codes.sort();
+    codes.dedup();
+    codes
+}
+
+/// The dictionary to use when none is chosen: the main variant for the UI
+/// language (`de_DE` for `de`, `en_US` for `en`), any variant of it, then
+/// US English, then any.
+pub fn preferred_dictionary(available: &[String], ui_language: &str) -> Option<String> {
+    let for_language = |language: &str| {
+        let main = match language {
+            "en" => "en_US".to_string(),
+            _ => format!("{language}_{}", language.to_uppercase()),
+        };
+        available.iter().find(|code| **code == main).or_else(|| {
+            available
+                .iter()
+                .find(|code| code.split(['_', '-']).next() == Some(language))
+        })
+    };
+    for_language(ui_language)
+        .or_else(|| for_language("en"))
+        .or_else(|| available.first())
+        .cloned()
+}
+
+/// Read a dictionary file, decoding Latin-1 when the file is not UTF-8
+/// (older dictionaries declare `SET ISO8859-1`).
+fn read_dictionary_file(path: &Path) -> Result<String> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(match String::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(e) => e.into_bytes().into_iter().map(char::from).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "SET UTF-8\n\
+        TRY esianrtolcdugmphbyfvkwz\n\
+        SFX S Y 3\n\
+        SFX S   y     ies        [^aeiou]y\n\
+        SFX S   0     s          [aeiou]y\n\
+        SFX S   0     s          [^sy]\n\
+        PFX U Y 1\n\
+        PFX U   0     un         .\n";
+    const DIC: &str = "5\nhello\nworld/S\ncity/S\nhappy/U\nspell/S\n";
+
+    fn checker() -> SpellChecker {
+        SpellChecker::from_parts("en_TEST", AFF, DIC)
+    }
+
+    #[test]
+    fn checks_stems_and_affixes() {
+        let checker = checker();
+        assert!(checker.check("hello"));
+        assert!(checker.check("Hello"));
+        assert!(checker.check("worlds"));
+        assert!(checker.check("cities"));
+        assert!(!checker.check("citys"));
+        assert!(checker.check("unhappy"));
+        assert!(!checker.check("unworld"));
+        assert!(checker.check("world's"));
+        assert!(!checker.check("helo"));
+    }
+
+    #[test]
+    fn suggests_single_edits() {
+        let checker = checker();
+        assert_eq!(
+            checker.suggest("helo").first().map(String::as_str),
+            Some("hello")
+        );
+        assert!(checker.suggest("wrold").contains(&"world".to_string()));
+        assert!(
+            checker
+                .suggest("helloworld")
+                .contains(&"hello world".to_string())
+        );
+    }
+
+    #[test]
+    fn skips_code_urls_and_identifiers() {
+        let checker = checker();
+        let text = "helo `wrold` see https://exmaple.com and main.rs, fooBar, NASA\n\
+            ```\nwrold\n```\nspel";
+        let found: Vec<_> = checker
+            .misspellings(text)
+            .into_iter()
+            .map(|m| m.word)
+            .collect();
+        assert_eq!(found, vec!["helo", "see", "and", "spel"]);
+        let first = &checker.misspellings(text)[0];
+        assert_eq!(&text[first.range.clone()], "helo");
+    }
+
+    #[test]
+    fn prefers_dictionary_for_ui_language() {
+        let available = vec![
+            "de_AT".to_string(),
+            "de_DE".to_string(),
+            "en_GB".to_string(),
+            "en_US".to_string(),
+        ];
+        assert_eq!(
+            preferred_dictionary(&available, "de").as_deref(),
+            Some("de_DE")
+        );
+        assert_eq!(
+            preferred_dictionary(&available, "fr").as_deref(),
+            Some("en_US")
+        );
+        assert_eq!(preferred_dictionary(&[], "en"), None);
+    }
+}
@@ -0,0 +1,327 @@
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tracing::{debug, info};
+
+/// A remote machine the agent may run commands on, stored in the execution settings.
+///
+/// The agent only ever refers to a profile by `name`; hostname, user and key
+/// come from the profile, so commands cannot reach hosts that are not listed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SshHostProfile {
+    pub id: String,
+    /// Name the agent uses to pick this host (letters, digits, `_` and `-`).
+    pub name: String,
+    pub hostname: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    pub user: String,
+    /// Private key to authenticate with. When empty, keys from the running
+    /// ssh-agent (`SSH_AUTH_SOCK`) are used.
+    #[serde(default)]
+    pub identity_file: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_port() -> u16 {
+    22
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl SshHostProfile {
+    pub fn new(
+        name: impl Into<String>,
+        hostname: impl Into<String>,
+        user: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.into(),
+            hostname: hostname.into(),
+            port: default_port(),
+            user: user.into(),
+            identity_file: None,
+            enabled: true,
+        }
+    }
+
+    /// Check the profile before it is saved or used.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty()
+            || self.name.len() > 64
+            || !self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(
+                "Host name must be 1-64 characters of letters, digits, '_' or '-'".to_string(),
+            );
+        }
+        if self.hostname.is_empty()
+            || self.hostname.starts_with('-')
+            || !self
+                .hostname
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':'))
+        {
+            return Err("Hostname must be a DNS name or IP address".to_string());
+        }
+        if self.user.is_empty()
+            || self.user.starts_with('-')
+            || !self
+                .user
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        {
+            return Err("User must be letters, digits, '.', '_' or '-'".to_string());
+        }
+        if self.port == 0 {
+            return Err("Port must be between 1 and 65535".to_string());
+        }
+        Ok(())
+    }
+
+    /// `user@hostname` label for approvals and traces.
+    pub fn target(&self) -> String {
+        format!("{}@{}", self.user, self.hostname)
+    }
+}
+
+/// Result of one remote command.
+#[derive(Debug, Serialize)]
+pub struct SshOutput {
+    pub host: String,
+    pub exit_code: Option<i32>,
+    pub output: String,
+    pub truncated: bool,
+}
+
+/// Runs commands on allowlisted hosts through the system `ssh` client.
+///
+/// The user's ssh config is ignored (`-F /dev/null`) so aliases, proxy
+/// commands and forwarding cannot widen what a profile reaches, and host keys
+/// must already be in `known_hosts` (`StrictHostKeyChecking=yes`).
+#[derive(Debug)]
+pub struct SshService {
+    hosts: Vec<SshHostProfile>,
+    timeout: Duration,
+    max_output_bytes: usize,
+}
+
+impl SshService {
+    /// Check that `ssh` is available and keep the enabled, valid profiles.
+    pub async fn new(
+        hosts: Vec<SshHostProfile>,
+        timeout: Duration,
+        max_output_bytes: usize,
+    ) -> Result<Self> {
+        let status = tokio::process::Command::new("ssh")
+            .arg("-V")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+            .context("ssh not found on PATH")?;
+        if !status.success() {
+            return Err(anyhow!("ssh -V failed"));
+        }
+
+        let hosts: Vec<SshHostProfile> = hosts
+            .into_iter()
+            .filter(|h| h.enabled && h.validate().is_ok())
+            .collect();
+        if hosts.is_empty() {
+            return Err(anyhow!("No enabled SSH host profiles"));
+        }
+        info!(hosts = hosts.len(), "SSH service initialized");
+        Ok(Self {
+            hosts,
+            timeout,
+            max_output_bytes,
+        })
+    }
+
+    pub fn hosts(&self) -> &[SshHostProfile] {
+        &self.hosts
+    }
+
+    /// Look up an allowlisted profile by name.
+    pub fn host(&self, name: &str) -> Result<&SshHostProfile> {
+        self.hosts.iter().find(|h| h.name == name).ok_or_else(|| {
+            anyhow!(
+                "Unknown host '{}'. Allowed hosts: {}",
+                name,
+                self.hosts
+                    .iter()
+                    .map(|h| h.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+    }
+
+    /// Run `command` on `host`, passing output lines to `on_chunk` as they arrive.
+    pub async fn execute(
+        &self,
+        host: &str,
+        command: &str,
+        mut on_chunk: impl FnMut(&str),
+    ) -> Result<SshOutput> {
+        let profile = self.host(host)?;
+        let args = ssh_args(profile, command)?;
+        debug!(host = %profile.name, "Running ssh command");
+
+        let mut child = tokio::process::Command::new("ssh")
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("Failed to run ssh")?;
+        let mut stdout = BufReader::new(child.stdout.take().context("ssh stdout")?).lines();
+        let mut stderr = BufReader::new(child.stderr.take().context("ssh stderr")?).lines();
+
+        let mut output = String::new();
+        let mut truncated = false;
+        let max_output_bytes = self.max_output_bytes;
+        let mut push = |line: String| {
+            let line = format!("{line}\n");
+            on_chunk(&line);
+            if output.len() + line.len() <= max_output_bytes {
+                output.push_str(&line);
+            } else {
+                truncated = true;
+            }
+        };
+
+        let collect = async {
+            let (mut stdout_done, mut stderr_done) = (false, false);
+            while !(stdout_done && stderr_done) {
+                tokio::select! {
+                    line = stdout.next_line(), if !stdout_done => match line? {
+                        Some(line) => push(line),
+                        None => stdout_done = true,
+                    },
+                    line = stderr.next_line(), if !stderr_done => match line? {
+                        Some(line) => push(line),
+                        None => stderr_done = true,
+                    },
+                }
+            }
+            Ok::<_, anyhow::Error>(child.wait().await?)
+        };
+        let status = tokio::time::timeout(self.timeout, collect)
+            .await
+            .map_err(|_| anyhow!("ssh command timed out after {:?}", self.timeout))??;
+
+        Ok(SshOutput {
+            host: profile.name.clone(),
+            exit_code: status.code(),
+            output,
+            truncated,
+        })
+    }
+}
+
+/// Build the `ssh` argument list for one command on `profile`.
+fn ssh_args(profile: &SshHostProfile, command: &str) -> Result<Vec<String>> {
+    profile.validate().map_err(|e| anyhow!(e))?;
+    if command.trim().is_empty() {
+        return Err(anyhow!("Command must not be empty"));
+    }
+
+    let mut args: Vec<String> = [
+        "-F",
+        "/dev/null",
+        "-o",
+        "BatchMode=yes",
+        "-o",
+        "StrictHostKeyChecking=yes",
+        "-o",
+        "ForwardAgent=no",
+        "-o",
+        "ClearAllForwardings=yes",
+        "-o",
+        "ConnectTimeout=10",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+    if let Some(identity_file) = profile.identity_file.as_deref().filter(|p| !p.is_empty()) {
+        args.extend([
+            "-o".to_string(),
+            "IdentitiesOnly=yes".to_string(),
+            "-i".to_string(),
+            identity_file.to_string(),
+        ]);
+    }
+    args.extend([
+        "-p".to_string(),
+        profile.port.to_string(),
+        "-l".to_string(),
+        profile.user.clone(),
+        "--".to_string(),
+        profile.hostname.clone(),
+        command.to_string(),
+    ]);
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_validation_rejects_option_injection() {
+        assert!(
+            SshHostProfile::new("web", "web.internal", "deploy")
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            SshHostProfile::new("web", "-oProxyCommand=x", "deploy")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            SshHostProfile::new("web", "web.internal", "-l root")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            SshHostProfile::new("web server", "web.internal", "deploy")
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn ssh_args_pin_options_and_end_with_host_and_command() {
+        let mut profile = SshHostProfile::new("web", "10.0.0.5", "deploy");
+        profile.port = 2222;
+        profile.identity_file = Some("/home/me/.ssh/id_web".to_string());
+        let args = ssh_args(&profile, "uptime").unwrap();
+
+        assert_eq!(&args[..2], ["-F", "/dev/null"]);
+        assert!(args.contains(&"StrictHostKeyChecking=yes".to_string()));
+        assert!(args.windows(2).any(|w| w == ["-i", "/home/me/.ssh/id_web"]));
+        assert!(args.windows(2).any(|w| w == ["-p", "2222"]));
+        assert_eq!(&args[args.len() - 3..], ["--", "10.0.0.5", "uptime"]);
+    }
+
+    #[test]
+    fn ssh_args_use_agent_without_identity_file() {
+        let profile = SshHostProfile::new("web", "web.internal", "deploy");
+        let args = ssh_args(&profile, "uptime").unwrap();
+        assert!(!args.contains(&"-i".to_string()));
+        assert!(ssh_args(&profile, "  ").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Detection of response streams that stopped making progress.
+//!
+//! A provider can keep the connection open without sending anything, which
+//! would leave the turn spinning forever. [`StallDetector`] watches the
+//! chunks of a stream and gives the moment it counts as stalled: the read
+//! timeout after the last chunk. While a tool runs or an approval is
+//! pending the provider is not expected to send anything, so the detector
+//! waits until they are done.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use super::llm_service::StreamChunk;
+
+pub struct StallDetector {
+    timeout: Option<Duration>,
+    last_activity: Instant,
+    running_tools: HashSet<String>,
+    pending_approvals: HashSet<String>,
+}
+
+impl StallDetector {
+    /// `timeout` is the provider's read timeout; `None` never stalls.
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout,
+            last_activity: Instant::now(),
+            running_tools: HashSet::new(),
+            pending_approvals: HashSet::new(),
+        }
+    }
+
+    /// Record a chunk received from the stream.
+    pub fn observe(&mut self, chunk: &StreamChunk) {
+        self.last_activity = Instant::now();
+        match chunk {
+            StreamChunk::ToolCallStarted { id, .. } => {
+                self.running_tools.insert(id.clone());
+            }
+            StreamChunk::ToolCallResult { id, .. } | StreamChunk::ToolCallError { id, .. } => {
+                self.running_tools.remove(id);
+            }
+            StreamChunk::ApprovalRequested { id, .. } => {
+                self.pending_approvals.insert(id.clone());
+            }
+            StreamChunk::ApprovalResolved { id, .. } => {
+                self.pending_approvals.remove(id);
+            }
+            _ => {}
+        }
+    }
+
+    /// Record progress seen outside the stream, e.g. sub-agent output.
+    pub fn touch(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// When the stream counts as stalled if nothing arrives before then.
+    /// `None` while waiting on a tool or an approval, or without a timeout.
+    pub fn deadline(&self) -> Option<Instant> {
+        if !self.running_tools.is_empty() || !self.pending_approvals.is_empty() {
+            return None;
+        }
+        self.timeout.map(|timeout| self.last_activity + timeout)
+    }
+
+    /// Error reported when the stream stalled.
+    pub fn error_message(&self) -> String {
+        format!(
+            "Stream stalled after {} seconds: the provider stopped sending a response.",
+            self.timeout.unwrap_or_default().as_secs()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn deadline_follows_the_last_chunk() {
+        let mut detector = StallDetector::new(Some(TIMEOUT));
+        let first = detector.deadline().unwrap();
+
+        detector.observe(&StreamChunk::Text("Hello".to_string()));
+        let second = detector.deadline().unwrap();
+        assert!(second >= first);
+        assert!(second <= Instant::now() + TIMEOUT);
+    }
+
+    #[test]
+    fn no_deadline_while_a_tool_runs_or_awaits_approval() {
+        let mut detector = StallDetector::new(Some(TIMEOUT));
+        detector.observe(&StreamChunk::ToolCallStarted {
+            id: "call-1".to_string(),
+            name: "shell_execute".to_string(),
+        });
+        detector.observe(&StreamChunk::ApprovalRequested {
+            id: "approval-1".to_string(),
+            command: "ls".to_string(),
+            is_sandboxed: false,
+            question: None,
+        });
+        assert!(detector.deadline().is_none());
+
+        detector.observe(&StreamChunk::ApprovalResolved {
+            id: "approval-1".to_string(),
+            approved: true,
+            answer: None,
+        });
+        assert!(detector.deadline().is_none());
+
+        detector.observe(&StreamChunk::ToolCallError {
+            id: "call-1".to_string(),
+            error: "failed".to_string(),
+        });
+        assert!(detector.deadline().is_some());
+    }
+
+    #[test]
+    fn never_stalls_without_a_timeout() {
+        let detector = StallDetector::new(None);
+        assert!(detector.deadline().is_none());
+        assert_eq!(
+            StallDetector::new(Some(TIMEOUT)).error_message(),
+            "Stream stalled after 30 seconds: the provider stopped sending a response."
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Timings of the application's startup phases.
+//!
+//! Hosts wrap each phase (repository init, settings loads, MCP connections,
+//! conversation restore, ...) in a [`phase`] guard, which records its offset
+//! from process start and its duration when dropped. Guards can be moved into
+//! async tasks, so phases that finish on the executor are timed the same way.
+//! The recorded phases are logged and shown on the diagnostics settings page
+//! when `CHATTY_DIAGNOSTICS` is set.
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use tracing::info;
+
+/// Environment variable that reveals the diagnostics settings page.
+pub const DIAGNOSTICS_ENV: &str = "CHATTY_DIAGNOSTICS";
+
+static PROCESS_START: OnceLock<Instant> = OnceLock::new();
+static PHASES: Mutex<Vec<StartupPhase>> = Mutex::new(Vec::new());
+
+/// One timed phase. Milestones recorded with [`mark`] have a zero duration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StartupPhase {
+    pub name: &'static str,
+    /// Offset of the phase start from process start.
+    pub started_at: Duration,
+    pub duration: Duration,
+}
+
+/// Records the phase when dropped.
+#[must_use = "the phase is recorded when the guard is dropped"]
+pub struct PhaseGuard {
+    name: &'static str,
+    started: Instant,
+}
+
+impl Drop for PhaseGuard {
+    fn drop(&mut self) {
+        push(self.name, self.started, self.started.elapsed());
+    }
+}
+
+/// Anchor offsets at the current instant. Call first thing in `main`; later
+/// calls have no effect.
+pub fn mark_process_start() {
+    process_start();
+}
+
+/// Start timing `name` until the returned guard is dropped.
+pub fn phase(name: &'static str) -> PhaseGuard {
+    process_start();
+    PhaseGuard {
+        name,
+        started: Instant::now(),
+    }
+}
+
+/// Record a milestone (e.g. first paint) at the current instant.
+pub fn mark(name: &'static str) {
+    push(name, Instant::now(), Duration::ZERO);
+}
+
+/// All phases recorded so far, ordered by start offset.
+pub fn phases() -> Vec<StartupPhase> {
+    let mut phases = PHASES.lock().clone();
+    phases.sort_by_key(|p| p.started_at);
+    phases
+}
+
+/// Whether the diagnostics page should be shown.
+pub fn diagnostics_enabled() -> bool {
+    std::env::var_os(DIAGNOSTICS_ENV).is_some_and(|v| !v.is_empty() && v != "0")
+}
+
+fn process_start() -> Instant {
+    *PROCESS_START.get_or_init(Instant::now)
+}
+
+fn push(name: &'static str, started: Instant, duration: Duration) {
+    let started_at = started.saturating_duration_since(process_start());
+    info!(
+        phase = name,
+        started_at_ms = started_at.as_millis() as u64,
+        duration_ms = duration.as_millis() as u64,
+        "Startup phase"
+    );
+    PHASES.lock().push(StartupPhase {
+        name,
+        started_at,
+        duration,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guards_record_phases_in_start_order() {
+        mark_process_start();
+        let outer = phase("test_outer");
+        std::thread::sleep(Duration::from_millis(1));
+        {
+            let _inner = phase("test_inner");
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        drop(outer);
+        mark("test_mark");
+
+        let recorded: Vec<_> = phases()
+            .into_iter()
+            .filter(|p| p.name.starts_with("test_"))
+            .collect();
+        let names: Vec<_> = recorded.iter().map(|p| p.name).collect();
+        assert_eq!(names, ["test_outer", "test_inner", "test_mark"]);
+        assert!(recorded[0].duration >= recorded[1].duration);
+        assert!(recorded[1].duration >= Duration::from_millis(2));
+        assert_eq!(recorded[2].duration, Duration::ZERO);
+    }
+}
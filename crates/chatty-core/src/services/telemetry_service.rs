@@ -0,0 +1,223 @@
+//! Opt-in, local-only usage metrics.
+//!
+//! Nothing is collected until the user turns telemetry on in the settings, and
+//! nothing leaves the machine: counts are aggregated in
+//! `~/.config/chatty/telemetry.json` and can be exported as JSON for the user
+//! to share themselves. Only fixed feature names (`&'static str`, see
+//! [`features`]) and a crash count are recorded — never message text, file
+//! paths, tool arguments or model output.
+//!
+//! Setting `CHATTY_TELEMETRY_DISABLED` is a kill switch: collection stays off
+//! whatever the setting says, e.g. for managed deployments.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use super::persisted_json::{PersistedJson, config_path};
+
+/// Environment variable that forces telemetry off.
+pub const KILL_SWITCH_ENV: &str = "CHATTY_TELEMETRY_DISABLED";
+
+/// Feature names the host applications record.
+pub mod features {
+    pub const MESSAGE_SENT: &str = "message_sent";
+    pub const CONVERSATION_CREATED: &str = "conversation_created";
+    pub const TOOL_CALL: &str = "tool_call";
+    pub const SLASH_COMMAND: &str = "slash_command";
+    pub const CONVERSATION_EXPORTED: &str = "conversation_exported";
+}
+
+static TELEMETRY: OnceLock<TelemetryService> = OnceLock::new();
+
+/// Aggregated counts, as persisted and exported.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryData {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Unix timestamp (seconds) when collection was turned on.
+    #[serde(default)]
+    pub collecting_since: Option<i64>,
+    #[serde(default)]
+    pub feature_counts: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub crash_count: u64,
+}
+
+/// Holds the local aggregate and writes it to disk.
+pub struct TelemetryService {
+    data: PersistedJson<TelemetryData>,
+}
+
+/// Load the persisted aggregate and make the service available. Later calls
+/// return the same instance.
+pub fn init() -> &'static TelemetryService {
+    TELEMETRY.get_or_init(|| TelemetryService::load(config_path("telemetry.json")))
+}
+
+/// The service, once [`init`] has run.
+pub fn telemetry() -> Option<&'static TelemetryService> {
+    TELEMETRY.get()
+}
+
+/// Count one use of `feature` when telemetry is on. No-op before [`init`].
+pub fn record(feature: &'static str) {
+    if let Some(service) = telemetry() {
+        service.record(feature);
+    }
+}
+
+/// Chain a panic hook that counts the crash and saves immediately, since the
+/// process may not survive to the next flush.
+pub fn install_panic_hook() {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(service) = telemetry() {
+            service.record_crash();
+        }
+        previous_hook(info);
+    }));
+}
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+impl TelemetryService {
+    fn load(path: Option<PathBuf>) -> Self {
+        Self {
+            data: PersistedJson::load(path, "telemetry"),
+        }
+    }
+
+    /// Whether the kill switch environment variable is set.
+    pub fn kill_switch_active() -> bool {
+        std::env::var_os(KILL_SWITCH_ENV).is_some_and(|v| !v.is_empty() && v != "0")
+    }
+
+    /// Whether the user opted in (regardless of the kill switch).
+    pub fn is_enabled(&self) -> bool {
+        self.data.lock().enabled
+    }
+
+    pub fn is_collecting(&self) -> bool {
+        self.is_enabled() && !Self::kill_switch_active()
+    }
+
+    /// Opt in or out. Opting out deletes everything collected so far.
+    pub fn set_enabled(&self, enabled: bool) {
+        {
+            let mut data = self.data.lock();
+            if enabled {
+                data.enabled = true;
+                data.collecting_since.get_or_insert_with(now_secs);
+            } else {
+                *data = TelemetryData::default();
+            }
+        }
+        self.data.save();
+    }
+
+    pub fn record(&self, feature: &'static str) {
+        if !self.is_collecting() {
+            return;
+        }
+        *self
+            .data
+            .lock()
+            .feature_counts
+            .entry(feature.to_string())
+            .or_default() += 1;
+        self.data.mark_dirty();
+    }
+
+    /// Count a crash and save right away. Runs in the panic hook, possibly
+    /// on a thread that holds the lock, so a busy lock drops the crash
+    /// instead of deadlocking.
+    pub fn record_crash(&self) {
+        let Some(mut data) = self.data.try_lock() else {
+            return;
+        };
+        if !data.enabled || Self::kill_switch_active() {
+            return;
+        }
+        data.crash_count += 1;
+        drop(data);
+        self.data.try_save();
+    }
+
+    /// Reset the counts but stay opted in.
+    pub fn clear(&self) {
+        {
+            let mut data = self.data.lock();
+            let enabled = data.enabled;
+            *data = TelemetryData::default();
+            if enabled {
+                data.enabled = true;
+                data.collecting_since = Some(now_secs());
+            }
+        }
+        self.data.save();
+    }
+
+    pub fn snapshot(&self) -> TelemetryData {
+        self.data.lock().clone()
+    }
+
+    /// The aggregate as pretty JSON for self-reporting.
+    pub fn export_json(&self) -> String {
+        let data = self.snapshot();
+        let report = serde_json::json!({
+            "app_version": env!("CARGO_PKG_VERSION"),
+            "os": std::env::consts::OS,
+            "exported_at": now_secs(),
+            "collecting_since": data.collecting_since,
+            "feature_counts": data.feature_counts,
+            "crash_count": data.crash_count,
+        });
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+
+    /// Write the aggregate if anything was recorded since the last write.
+    pub fn flush(&self) {
+        self.data.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_only_while_opted_in_and_opt_out_deletes() {
+        let service = TelemetryService::load(None);
+        service.record(features::MESSAGE_SENT);
+        assert!(service.snapshot().feature_counts.is_empty());
+
+        service.set_enabled(true);
+        service.record(features::MESSAGE_SENT);
+        service.record(features::MESSAGE_SENT);
+        service.record_crash();
+        {
+            // A crash while the data is locked is dropped, not a deadlock
+            let _guard = service.data.lock();
+            service.record_crash();
+        }
+        let data = service.snapshot();
+        assert_eq!(data.feature_counts[features::MESSAGE_SENT], 2);
+        assert_eq!(data.crash_count, 1);
+        assert!(data.collecting_since.is_some());
+
+        let export: serde_json::Value = serde_json::from_str(&service.export_json()).unwrap();
+        assert_eq!(export["feature_counts"][features::MESSAGE_SENT], 2);
+        assert_eq!(export["crash_count"], 1);
+
+        service.set_enabled(false);
+        assert_eq!(service.snapshot(), TelemetryData::default());
+    }
+}
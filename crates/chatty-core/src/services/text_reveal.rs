@@ -0,0 +1,128 @@
+//! Paced reveal of streamed response text.
+//!
+//! Providers deliver text in bursts: nothing for a while, then a few hundred
+//! characters at once. `TextReveal` buffers what arrived and hands it out at
+//! a steady number of characters per second, so the chat grows like typing
+//! instead of jumping a paragraph per chunk. It never lags more than
+//! [`MAX_LAG`] behind the network: when a burst would take longer than that
+//! to type out, the rest of it is revealed faster.
+
+use std::time::Duration;
+
+/// Longest a buffered character waits before it is shown.
+pub const MAX_LAG: Duration = Duration::from_secs(1);
+
+/// Buffer between received and displayed text.
+#[derive(Clone, Debug)]
+pub struct TextReveal {
+    pending: String,
+    /// Characters (not bytes) in `pending`
+    pending_chars: usize,
+    chars_per_second: f64,
+    /// Current speed: `chars_per_second`, or faster while catching up
+    speed: f64,
+    /// Fraction of a character carried over from the previous tick
+    carry: f64,
+}
+
+impl TextReveal {
+    pub fn new(chars_per_second: u32) -> Self {
+        Self {
+            pending: String::new(),
+            pending_chars: 0,
+            chars_per_second: f64::from(chars_per_second.max(1)),
+            speed: f64::from(chars_per_second.max(1)),
+            carry: 0.0,
+        }
+    }
+
+    /// Queue received text behind what is still pending.
+    pub fn push(&mut self, text: &str) {
+        self.pending.push_str(text);
+        self.pending_chars += text.chars().count();
+        let catch_up = self.pending_chars as f64 / MAX_LAG.as_secs_f64();
+        self.speed = self.speed.max(catch_up);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// The text due after `elapsed` more time, cut at a character boundary.
+    pub fn take(&mut self, elapsed: Duration) -> String {
+        if self.pending.is_empty() {
+            return String::new();
+        }
+
+        let budget = self.speed * elapsed.as_secs_f64() + self.carry;
+        let count = budget.floor() as usize;
+        if count >= self.pending_chars {
+            return self.take_all();
+        }
+        self.carry = budget - count as f64;
+
+        let split = self
+            .pending
+            .char_indices()
+            .nth(count)
+            .map_or(self.pending.len(), |(ix, _)| ix);
+        let rest = self.pending.split_off(split);
+        self.pending_chars -= count;
+        std::mem::replace(&mut self.pending, rest)
+    }
+
+    /// Everything still pending, e.g. when the stream ends.
+    pub fn take_all(&mut self) -> String {
+        self.pending_chars = 0;
+        self.speed = self.chars_per_second;
+        self.carry = 0.0;
+        std::mem::take(&mut self.pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reveals_at_the_configured_rate() {
+        let mut reveal = TextReveal::new(100);
+        reveal.push("abcdefghij");
+
+        assert_eq!(reveal.take(Duration::from_millis(30)), "abc");
+        // Fractions carry over: 15ms = 1.5 chars, then another 1.5
+        assert_eq!(reveal.take(Duration::from_millis(15)), "d");
+        assert_eq!(reveal.take(Duration::from_millis(15)), "ef");
+        assert_eq!(reveal.take(Duration::from_secs(1)), "ghij");
+        assert!(reveal.is_empty());
+        assert_eq!(reveal.take(Duration::from_secs(1)), "");
+    }
+
+    #[test]
+    fn large_backlog_drains_within_max_lag() {
+        let mut reveal = TextReveal::new(10);
+        reveal.push(&"x".repeat(1000));
+
+        // Drained within a second instead of the 100 seconds 10/s would take
+        for _ in 0..10 {
+            assert!(!reveal.is_empty());
+            assert_eq!(reveal.take(Duration::from_millis(100)).len(), 100);
+        }
+        assert!(reveal.is_empty());
+
+        // Back to the configured rate once caught up
+        reveal.push("abc");
+        assert_eq!(reveal.take(Duration::from_millis(100)), "a");
+    }
+
+    #[test]
+    fn splits_on_character_boundaries() {
+        let mut reveal = TextReveal::new(100);
+        reveal.push("héllo wörld");
+
+        assert_eq!(reveal.take(Duration::from_millis(20)), "hé");
+        assert_eq!(reveal.take(Duration::from_millis(50)), "llo w");
+        assert_eq!(reveal.take_all(), "örld");
+        assert!(reveal.is_empty());
+    }
+}
@@ -0,0 +1,100 @@
+//! Stop-button cancellation for long-running tools.
+//!
+//! The stream loop only checks its cancel flag between chunks, so a tool that
+//! is busy (a shell command, an HTTP request, a code run) would otherwise keep
+//! going until it finishes on its own. Each agent build creates one
+//! [`ToolCancellation`], hands clones to those tools, and the frontends install
+//! the cancel flag of every stream they start. Tools race their work against
+//! [`ToolCancellation::cancelled`] and stop the underlying process or request
+//! as soon as the user presses Stop.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+/// Tool result text recorded when the user stopped the stream mid-call.
+pub const CANCELLED_BY_USER: &str = "Cancelled by user";
+
+/// How often a waiting tool re-checks the stream's cancel flag.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Shared handle to the cancel flag of the stream currently driving the agent.
+#[derive(Clone, Debug, Default)]
+pub struct ToolCancellation {
+    flag: Arc<Mutex<Option<Arc<AtomicBool>>>>,
+}
+
+impl ToolCancellation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Point the handle at the cancel flag of the stream about to run.
+    pub fn install(&self, cancel_flag: Arc<AtomicBool>) {
+        *self.flag.lock() = Some(cancel_flag);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.flag
+            .lock()
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    /// Resolves once the current stream is cancelled. Never resolves while no
+    /// stream has installed a flag.
+    pub async fn cancelled(&self) {
+        while !self.is_cancelled() {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Run `work` until it finishes or the stream is cancelled, whichever
+    /// comes first. `work` is dropped on cancellation, so anything it owns
+    /// must stop on drop (e.g. `kill_on_drop` children, reqwest futures).
+    pub async fn run<F: Future>(&self, work: F) -> Option<F::Output> {
+        tokio::select! {
+            output = work => Some(output),
+            () = self.cancelled() => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn run_finishes_when_not_cancelled() {
+        let cancellation = ToolCancellation::new();
+        cancellation.install(Arc::new(AtomicBool::new(false)));
+        assert_eq!(cancellation.run(async { 42 }).await, Some(42));
+    }
+
+    #[tokio::test]
+    async fn run_stops_pending_work_on_cancel() {
+        let cancellation = ToolCancellation::new();
+        let flag = Arc::new(AtomicBool::new(false));
+        cancellation.install(flag.clone());
+
+        let cancel = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            flag.store(true, Ordering::Relaxed);
+        };
+        let (output, ()) = tokio::join!(cancellation.run(std::future::pending::<()>()), cancel);
+        assert_eq!(output, None);
+    }
+
+    #[test]
+    fn a_new_stream_replaces_the_previous_flag() {
+        let cancellation = ToolCancellation::new();
+        assert!(!cancellation.is_cancelled());
+        cancellation.install(Arc::new(AtomicBool::new(true)));
+        assert!(cancellation.is_cancelled());
+        cancellation.install(Arc::new(AtomicBool::new(false)));
+        assert!(!cancellation.is_cancelled());
+    }
+}
@@ -0,0 +1,339 @@
+//! Reference of the tools offered to the model, for debugging tool calls.
+//!
+//! Each time an agent is built its tool definitions — the name, description
+//! and JSON schema the model sees — are recorded here, together with where
+//! the tool comes from and what it may do. The UI lists them in the "Tool
+//! reference" panel and opens the entry of a tool from its trace header, so
+//! a malformed call can be compared against the schema it was given.
+
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use parking_lot::RwLock;
+use rig_core::tool::ToolDyn;
+use serde_json::{Map, Value};
+
+use super::guardrail_service::{COMMAND_ARGS, NETWORK_TOOLS, WRITE_PATH_ARGS};
+use super::mcp_service::McpToolSet;
+
+/// Tool definitions seen in this process, keyed by tool name. Filled when an
+/// agent is built; a later definition of the same name replaces the earlier.
+static TOOL_REFERENCE: LazyLock<RwLock<BTreeMap<String, ToolReference>>> =
+    LazyLock::new(|| RwLock::new(BTreeMap::new()));
+
+/// Where a tool is implemented.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ToolOrigin {
+    BuiltIn,
+    /// Custom HTTP or GraphQL tool from the user's settings
+    Custom,
+    Mcp {
+        server: String,
+    },
+}
+
+impl ToolOrigin {
+    pub fn label(&self) -> String {
+        match self {
+            Self::BuiltIn => "Built-in".to_string(),
+            Self::Custom => "Custom".to_string(),
+            Self::Mcp { server } => format!("MCP: {server}"),
+        }
+    }
+}
+
+/// What a tool may do when called, from the same lists the guardrails use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PermissionClass {
+    ReadOnly,
+    WritesFiles,
+    RunsCode,
+    Network,
+    /// Runs in an MCP server; what it does is up to the server
+    External,
+}
+
+impl PermissionClass {
+    fn of(name: &str, origin: &ToolOrigin) -> Self {
+        if matches!(origin, ToolOrigin::Mcp { .. }) {
+            Self::External
+        } else if COMMAND_ARGS.iter().any(|(tool, _)| *tool == name) {
+            Self::RunsCode
+        } else if WRITE_PATH_ARGS.iter().any(|(tool, _)| *tool == name) {
+            Self::WritesFiles
+        } else if NETWORK_TOOLS.contains(&name) || *origin == ToolOrigin::Custom {
+            Self::Network
+        } else {
+            Self::ReadOnly
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "Read-only",
+            Self::WritesFiles => "Writes files",
+            Self::RunsCode => "Runs code",
+            Self::Network => "Network",
+            Self::External => "External",
+        }
+    }
+}
+
+/// A tool definition as offered to the model.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToolReference {
+    pub name: String,
+    pub description: String,
+    /// JSON schema of the arguments
+    pub parameters: Value,
+    pub origin: ToolOrigin,
+    pub permission: PermissionClass,
+}
+
+impl ToolReference {
+    pub fn new(name: String, description: String, parameters: Value, origin: ToolOrigin) -> Self {
+        let permission = PermissionClass::of(&name, &origin);
+        Self {
+            name,
+            description,
+            parameters,
+            origin,
+            permission,
+        }
+    }
+
+    /// Example arguments derived from the schema: one call with only the
+    /// required arguments and, when there are optional ones, one with all.
+    /// Values come from the schema's `examples`, `default` or `enum` where
+    /// given, and are placeholders of the right type otherwise.
+    pub fn example_invocations(&self) -> Vec<Value> {
+        let properties = self
+            .parameters
+            .get("properties")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        let required: Vec<&str> = self
+            .parameters
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+
+        let arguments = |all: bool| -> Map<String, Value> {
+            properties
+                .iter()
+                .filter(|(name, _)| all || required.contains(&name.as_str()))
+                .map(|(name, schema)| (name.clone(), example_value(name, schema)))
+                .collect()
+        };
+        let minimal = arguments(false);
+        let full = arguments(true);
+        if full.len() > minimal.len() {
+            vec![Value::Object(minimal), Value::Object(full)]
+        } else {
+            vec![Value::Object(minimal)]
+        }
+    }
+
+    /// Case-insensitive match of `query` against name, description and origin.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.name.to_lowercase().contains(&query)
+            || self.description.to_lowercase().contains(&query)
+            || self.origin.label().to_lowercase().contains(&query)
+    }
+}
+
+fn example_value(name: &str, schema: &Value) -> Value {
+    if let Some(example) = schema
+        .get("examples")
+        .and_then(Value::as_array)
+        .and_then(|examples| examples.first())
+        .or_else(|| schema.get("default"))
+        .or_else(|| {
+            schema
+                .get("enum")
+                .and_then(Value::as_array)
+                .and_then(|values| values.first())
+        })
+    {
+        return example.clone();
+    }
+    let kind = match schema.get("type") {
+        Some(Value::Array(kinds)) => kinds
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|kind| *kind != "null"),
+        Some(kind) => kind.as_str(),
+        None => None,
+    };
+    match kind {
+        Some("integer") => Value::from(0),
+        Some("number") => Value::from(0.0),
+        Some("boolean") => Value::Bool(false),
+        Some("array") => Value::Array(Vec::new()),
+        Some("object") => Value::Object(Map::new()),
+        _ => Value::String(format!("<{name}>")),
+    }
+}
+
+/// Definitions of the tools an agent is built with. `custom_tools` names
+/// the user's HTTP and GraphQL tools among `tools`.
+pub async fn describe_tools(
+    tools: &[Box<dyn ToolDyn>],
+    custom_tools: &[String],
+    mcp_tools: Option<&McpToolSet>,
+) -> Vec<ToolReference> {
+    let mut references = Vec::new();
+    for tool in tools {
+        let definition = tool.definition(String::new()).await;
+        let origin = if custom_tools.contains(&definition.name) {
+            ToolOrigin::Custom
+        } else {
+            ToolOrigin::BuiltIn
+        };
+        references.push(ToolReference::new(
+            definition.name,
+            definition.description,
+            definition.parameters,
+            origin,
+        ));
+    }
+    for (server, server_tools, _) in mcp_tools.into_iter().flatten() {
+        references.extend(server_tools.iter().map(|tool| {
+            ToolReference::new(
+                tool.name.to_string(),
+                tool.description.as_deref().unwrap_or_default().to_string(),
+                Value::Object((*tool.input_schema).clone()),
+                ToolOrigin::Mcp {
+                    server: server.clone(),
+                },
+            )
+        }));
+    }
+    references
+}
+
+/// Remember tool definitions so the UI can show them.
+pub fn record(references: Vec<ToolReference>) {
+    let mut known = TOOL_REFERENCE.write();
+    for reference in references {
+        known.insert(reference.name.clone(), reference);
+    }
+}
+
+/// Every tool recorded in this process, sorted by name.
+pub fn all() -> Vec<ToolReference> {
+    TOOL_REFERENCE.read().values().cloned().collect()
+}
+
+/// Look up a tool recorded earlier in this process.
+pub fn lookup(name: &str) -> Option<ToolReference> {
+    TOOL_REFERENCE.read().get(name).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn reference(name: &str, parameters: Value) -> ToolReference {
+        ToolReference::new(
+            name.to_string(),
+            String::new(),
+            parameters,
+            ToolOrigin::BuiltIn,
+        )
+    }
+
+    #[test]
+    fn classifies_permissions() {
+        assert_eq!(
+            reference("shell_execute", json!({})).permission,
+            PermissionClass::RunsCode
+        );
+        assert_eq!(
+            reference("write_file", json!({})).permission,
+            PermissionClass::WritesFiles
+        );
+        assert_eq!(
+            reference("fetch", json!({})).permission,
+            PermissionClass::Network
+        );
+        assert_eq!(
+            reference("read_file", json!({})).permission,
+            PermissionClass::ReadOnly
+        );
+        let custom = ToolReference::new(
+            "github_issues".to_string(),
+            String::new(),
+            json!({}),
+            ToolOrigin::Custom,
+        );
+        assert_eq!(custom.permission, PermissionClass::Network);
+        let mcp = ToolReference::new(
+            "read_file".to_string(),
+            String::new(),
+            json!({}),
+            ToolOrigin::Mcp {
+                server: "fs".to_string(),
+            },
+        );
+        assert_eq!(mcp.permission, PermissionClass::External);
+    }
+
+    #[test]
+    fn examples_cover_required_then_all_arguments() {
+        let tool = reference(
+            "search",
+            json!({
+                "type": "object",
+                "properties": {
+                    "query": {"type": "string"},
+                    "limit": {"type": "integer", "default": 10},
+                    "mode": {"type": ["string", "null"], "enum": ["fast", "deep"]},
+                    "tags": {"type": "array"}
+                },
+                "required": ["query"]
+            }),
+        );
+
+        assert_eq!(
+            tool.example_invocations(),
+            vec![
+                json!({"query": "<query>"}),
+                json!({"query": "<query>", "limit": 10, "mode": "fast", "tags": []}),
+            ]
+        );
+    }
+
+    #[test]
+    fn single_example_when_all_arguments_are_required() {
+        let tool = reference(
+            "read_file",
+            json!({"properties": {"path": {"type": "string"}}, "required": ["path"]}),
+        );
+        assert_eq!(tool.example_invocations(), vec![json!({"path": "<path>"})]);
+        assert_eq!(
+            reference("list_tools", json!({})).example_invocations(),
+            vec![json!({})]
+        );
+    }
+
+    #[test]
+    fn records_and_searches() {
+        let mut tool = reference("tool_reference_test_tool", json!({}));
+        tool.description = "Fetches the weather".to_string();
+        record(vec![tool.clone()]);
+
+        assert_eq!(lookup("tool_reference_test_tool"), Some(tool.clone()));
+        assert!(all().contains(&tool));
+        assert!(tool.matches("WEATHER"));
+        assert!(tool.matches("built-in"));
+        assert!(!tool.matches("shell"));
+    }
+}
@@ -0,0 +1,237 @@
+//! Local per-tool usage statistics.
+//!
+//! Every tool call is counted by name with its outcome and latency, across
+//! all conversations, in `~/.config/chatty/tool_stats.json`. Unlike
+//! [`telemetry_service`](super::telemetry_service) this is always on: it only
+//! stores tool names and numbers, never arguments or results, and is used by
+//! the app itself to suggest switching off tools that keep failing.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use super::persisted_json::{PersistedJson, config_path};
+
+/// Calls needed before a tool's failure rate is trusted.
+const MIN_CALLS_FOR_SUGGESTION: u64 = 5;
+/// Failure rate from which a tool counts as consistently failing.
+const FAILING_RATE: f64 = 0.8;
+/// Failures in a row that count as consistently failing regardless of history.
+const FAILING_STREAK: u64 = 3;
+
+static TOOL_STATS: OnceLock<ToolStatsService> = OnceLock::new();
+
+/// Aggregate for one tool.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolStats {
+    #[serde(default)]
+    pub calls: u64,
+    #[serde(default)]
+    pub failures: u64,
+    /// Sum of call durations, for the average latency.
+    #[serde(default)]
+    pub total_duration_ms: u64,
+    /// Failures since the last success.
+    #[serde(default)]
+    pub failure_streak: u64,
+    /// Unix timestamp (seconds) of the last call.
+    #[serde(default)]
+    pub last_used: Option<i64>,
+}
+
+impl ToolStats {
+    /// Share of calls that succeeded, from 0.0 to 1.0.
+    pub fn success_rate(&self) -> f64 {
+        if self.calls == 0 {
+            return 1.0;
+        }
+        (self.calls - self.failures) as f64 / self.calls as f64
+    }
+
+    pub fn average_latency(&self) -> Duration {
+        Duration::from_millis(self.total_duration_ms.checked_div(self.calls).unwrap_or(0))
+    }
+
+    /// Whether the tool fails often enough that disabling it is worth suggesting.
+    pub fn is_consistently_failing(&self) -> bool {
+        self.failure_streak >= FAILING_STREAK
+            || (self.calls >= MIN_CALLS_FOR_SUGGESTION && 1.0 - self.success_rate() >= FAILING_RATE)
+    }
+}
+
+/// All tool aggregates, as persisted.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolStatsData {
+    /// Unix timestamp (seconds) when counting started or was last reset.
+    #[serde(default)]
+    pub since: Option<i64>,
+    #[serde(default)]
+    pub tools: BTreeMap<String, ToolStats>,
+}
+
+impl ToolStatsData {
+    /// Tools sorted by call count, most used first.
+    pub fn by_usage(&self) -> Vec<(String, ToolStats)> {
+        let mut tools: Vec<_> = self
+            .tools
+            .iter()
+            .map(|(name, stats)| (name.clone(), stats.clone()))
+            .collect();
+        tools.sort_by(|a, b| b.1.calls.cmp(&a.1.calls).then_with(|| a.0.cmp(&b.0)));
+        tools
+    }
+
+    /// Names of the tools that keep failing.
+    pub fn failing_tools(&self) -> Vec<String> {
+        self.tools
+            .iter()
+            .filter(|(_, stats)| stats.is_consistently_failing())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+/// Holds the aggregate and the calls still in flight, and writes to disk.
+pub struct ToolStatsService {
+    data: PersistedJson<ToolStatsData>,
+    /// Tool call id → (tool name, start time).
+    in_flight: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+/// Load the persisted aggregate and make the service available. Later calls
+/// return the same instance.
+pub fn init() -> &'static ToolStatsService {
+    TOOL_STATS.get_or_init(|| ToolStatsService::load(config_path("tool_stats.json")))
+}
+
+/// The service, once [`init`] has run.
+pub fn tool_stats() -> Option<&'static ToolStatsService> {
+    TOOL_STATS.get()
+}
+
+/// Note that the tool call `id` started. No-op before [`init`].
+pub fn record_started(id: &str, name: &str) {
+    if let Some(service) = tool_stats() {
+        service.record_started(id, name);
+    }
+}
+
+/// Count the tool call `id` as finished. No-op before [`init`] or for an
+/// unknown id.
+pub fn record_finished(id: &str, success: bool) {
+    if let Some(service) = tool_stats() {
+        service.record_finished(id, success);
+    }
+}
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+impl ToolStatsService {
+    fn load(path: Option<PathBuf>) -> Self {
+        Self {
+            data: PersistedJson::load(path, "tool statistics"),
+            in_flight: Mutex::default(),
+        }
+    }
+
+    pub fn record_started(&self, id: &str, name: &str) {
+        self.in_flight
+            .lock()
+            .insert(id.to_string(), (name.to_string(), Instant::now()));
+    }
+
+    pub fn record_finished(&self, id: &str, success: bool) {
+        let Some((name, started)) = self.in_flight.lock().remove(id) else {
+            return;
+        };
+        self.record(&name, success, started.elapsed());
+    }
+
+    /// Count one finished call of `name`.
+    pub fn record(&self, name: &str, success: bool, duration: Duration) {
+        {
+            let mut data = self.data.lock();
+            data.since.get_or_insert_with(now_secs);
+            let stats = data.tools.entry(name.to_string()).or_default();
+            stats.calls += 1;
+            stats.total_duration_ms += duration.as_millis() as u64;
+            stats.last_used = Some(now_secs());
+            if success {
+                stats.failure_streak = 0;
+            } else {
+                stats.failures += 1;
+                stats.failure_streak += 1;
+            }
+        }
+        self.data.mark_dirty();
+    }
+
+    /// Forget all statistics.
+    pub fn clear(&self) {
+        *self.data.lock() = ToolStatsData::default();
+        self.data.save();
+    }
+
+    pub fn snapshot(&self) -> ToolStatsData {
+        self.data.lock().clone()
+    }
+
+    /// Write the aggregate if anything was recorded since the last write.
+    pub fn flush(&self) {
+        self.data.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_counts_failures_and_latency() {
+        let service = ToolStatsService::load(None);
+        service.record("fetch", true, Duration::from_millis(100));
+        service.record("fetch", false, Duration::from_millis(300));
+
+        let stats = &service.snapshot().tools["fetch"];
+        assert_eq!(stats.calls, 2);
+        assert_eq!(stats.failures, 1);
+        assert_eq!(stats.success_rate(), 0.5);
+        assert_eq!(stats.average_latency(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn finished_calls_are_matched_to_their_start() {
+        let service = ToolStatsService::load(None);
+        service.record_started("call-1", "shell");
+        service.record_finished("call-1", false);
+        service.record_finished("unknown", true);
+
+        let data = service.snapshot();
+        assert_eq!(data.tools.len(), 1);
+        assert_eq!(data.tools["shell"].failures, 1);
+    }
+
+    #[test]
+    fn suggests_tools_that_keep_failing() {
+        let service = ToolStatsService::load(None);
+        for _ in 0..FAILING_STREAK {
+            service.record("broken", false, Duration::ZERO);
+        }
+        service.record("flaky", false, Duration::ZERO);
+        service.record("flaky", true, Duration::ZERO);
+        assert_eq!(service.snapshot().failing_tools(), vec!["broken"]);
+
+        // A success ends the streak
+        service.record("broken", true, Duration::ZERO);
+        assert!(service.snapshot().failing_tools().is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+//! Topic-shift detection for suggesting conversation splits.
+//!
+//! After a turn completes, the newest user prompt is embedded and compared
+//! with the user prompts that came before it. When the cosine distance is
+//! above [`TOPIC_SHIFT_THRESHOLD`] the conversation has most likely moved on
+//! to an unrelated subject, and the UI offers to continue in a new
+//! conversation seeded with a summary of this one.
+
+use anyhow::{Result, anyhow};
+use rig_core::completion::Message;
+use tracing::debug;
+
+use crate::factories::AgentClient;
+use crate::services::embedding_service::EmbeddingService;
+use crate::token_budget::summarizer::{build_transcript, extract_user_text};
+
+/// Cosine distance above which two prompts count as different topics.
+pub const TOPIC_SHIFT_THRESHOLD: f32 = 0.6;
+
+/// Earlier user prompts the newest prompt is compared with.
+const CONTEXT_PROMPTS: usize = 3;
+
+/// Fewest earlier user prompts before a shift is considered; a single
+/// exchange has no established topic to drift away from.
+const MIN_EARLIER_PROMPTS: usize = 2;
+
+/// Cosine distance (`1 - cosine similarity`) between two embeddings.
+///
+/// Returns `None` for empty, mismatched or zero-length vectors.
+pub fn cosine_distance(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.is_empty() || a.len() != b.len() {
+        return None;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return None;
+    }
+    Some(1.0 - dot / (norm_a * norm_b))
+}
+
+/// Split `history` into the text of the earlier user prompts (most recent
+/// [`CONTEXT_PROMPTS`], oldest first) and the newest user prompt.
+///
+/// Returns `None` while the conversation is too short to have a topic.
+pub fn topic_shift_inputs(history: &[Message]) -> Option<(String, String)> {
+    let mut prompts: Vec<String> = history
+        .iter()
+        .filter_map(|message| match message {
+            Message::User { content } => {
+                let text = extract_user_text(content);
+                let text = text.trim();
+                (!text.is_empty()).then(|| text.to_string())
+            }
+            _ => None,
+        })
+        .collect();
+    let latest = prompts.pop()?;
+    if prompts.len() < MIN_EARLIER_PROMPTS {
+        return None;
+    }
+    let earlier = prompts[prompts.len().saturating_sub(CONTEXT_PROMPTS)..].join("\n\n");
+    Some((earlier, latest))
+}
+
+/// Embed the newest prompt and the ones before it, returning the distance
+/// when it exceeds [`TOPIC_SHIFT_THRESHOLD`].
+///
+/// # Errors
+/// Returns an error if the embedding request fails.
+pub async fn detect_topic_shift(
+    embeddings: &EmbeddingService,
+    history: &[Message],
+) -> Result<Option<f32>> {
+    let Some((earlier, latest)) = topic_shift_inputs(history) else {
+        return Ok(None);
+    };
+    let vectors = embeddings.embed_batch(&[earlier, latest]).await?;
+    let [earlier, latest] = vectors.as_slice() else {
+        return Err(anyhow!("Expected 2 embeddings, got {}", vectors.len()));
+    };
+    let distance = cosine_distance(earlier, latest);
+    debug!(?distance, "Topic shift distance");
+    Ok(distance.filter(|distance| *distance > TOPIC_SHIFT_THRESHOLD))
+}
+
+/// Summarize a conversation whose newest exchange started a new topic, so a
+/// new conversation can continue that topic with the useful background.
+///
+/// The summary covers the earlier conversation in brief and the newest
+/// exchange in more detail.
+///
+/// # Errors
+/// Returns an error if there is nothing before the newest exchange or the
+/// LLM call fails.
+pub async fn summarize_for_split(agent: &AgentClient, history: &[Message]) -> Result<String> {
+    let latest = history
+        .iter()
+        .rposition(|message| matches!(message, Message::User { .. }))
+        .filter(|latest| *latest > 0)
+        .ok_or_else(|| anyhow!("Nothing to summarize before the newest message"))?;
+
+    let prompt = format!(
+        "The last exchange of this conversation started a new topic, which the user \
+         will continue in a fresh conversation. Write a summary for that conversation \
+         with two sections:\n\
+         \n\
+         Background: a few short bullet points with the decisions, facts and open \
+         questions from the earlier conversation that may still matter.\n\
+         Current topic: the user's latest request and the answer so far.\n\
+         \n\
+         Output ONLY the summary.\n\
+         \n\
+         --- EARLIER CONVERSATION ---\n\
+         {}\n\
+         \n\
+         --- LATEST EXCHANGE ---\n\
+         {}",
+        build_transcript(&history[..latest]),
+        build_transcript(&history[latest..])
+    );
+    let summary = agent.prompt(&prompt).await?;
+    Ok(summary.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cosine_distance_of_parallel_and_orthogonal_vectors() {
+        assert!(cosine_distance(&[1.0, 2.0], &[2.0, 4.0]).unwrap().abs() < 1e-6);
+        assert!((cosine_distance(&[1.0, 0.0], &[0.0, 3.0]).unwrap() - 1.0).abs() < 1e-6);
+        assert!(cosine_distance(&[1.0], &[1.0, 0.0]).is_none());
+        assert!(cosine_distance(&[0.0, 0.0], &[1.0, 0.0]).is_none());
+    }
+
+    #[test]
+    fn inputs_need_two_earlier_prompts_and_keep_the_most_recent() {
+        let short = [
+            Message::user("a"),
+            Message::assistant("x"),
+            Message::user("b"),
+        ];
+        assert!(topic_shift_inputs(&short).is_none());
+
+        let history = [
+            Message::user("one"),
+            Message::assistant("x"),
+            Message::user("two"),
+            Message::assistant("x"),
+            Message::user("three"),
+            Message::assistant("x"),
+            Message::user("four"),
+            Message::assistant("x"),
+            Message::user("five"),
+            Message::assistant("x"),
+        ];
+        let (earlier, latest) = topic_shift_inputs(&history).unwrap();
+        assert_eq!(earlier, "two\n\nthree\n\nfour");
+        assert_eq!(latest, "five");
+    }
+}
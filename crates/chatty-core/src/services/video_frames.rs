@@ -0,0 +1,367 @@
+//! Frame sampling for video attachments.
+//!
+//! Vision models take images, not video, so a staged clip is sent as a
+//! handful of evenly spaced frames extracted with `ffmpeg`, plus a short
+//! text part giving the clip's length and each frame's timestamp. When a
+//! transcription command is configured the audio track is transcribed too
+//! and the transcript is sent alongside the frames.
+//!
+//! Samples are cached under `<data_dir>/chatty/video_frames/<hash>/`, keyed
+//! by the video's path and modification time, with a `sample.json` manifest.
+//! They live outside the temp dir because sent messages keep showing the
+//! frames as a preview strip.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result, anyhow, bail};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::services::speech_mode::{build_transcribe_command, shell_command};
+
+/// Frames sampled when the model doesn't set its own cap.
+pub const DEFAULT_VIDEO_FRAMES: u32 = 8;
+/// Upper bound on frames per clip, whatever the model allows.
+pub const MAX_VIDEO_FRAMES: u32 = 32;
+/// Width frames are scaled to (height follows the aspect ratio).
+const FRAME_WIDTH: u32 = 768;
+
+const MANIFEST_FILE: &str = "sample.json";
+
+/// Samples already read this session, so the message list doesn't re-read
+/// manifests on every frame.
+static SAMPLES: LazyLock<Mutex<HashMap<PathBuf, VideoSample>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// One extracted frame.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VideoFrame {
+    pub path: PathBuf,
+    pub timestamp_secs: f64,
+}
+
+/// Frames (and optionally a transcript) sampled from a video.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VideoSample {
+    pub source: PathBuf,
+    pub duration_secs: f64,
+    pub frames: Vec<VideoFrame>,
+    #[serde(default)]
+    pub transcript: Option<String>,
+}
+
+impl VideoSample {
+    /// Up to `cap` frames, evenly spread over the whole sample.
+    pub fn select_frames(&self, cap: u32) -> Vec<&VideoFrame> {
+        let cap = cap.max(1) as usize;
+        let count = self.frames.len();
+        if count <= cap {
+            return self.frames.iter().collect();
+        }
+        (0..cap)
+            .map(|i| &self.frames[(i * 2 + 1) * count / (cap * 2)])
+            .collect()
+    }
+
+    /// Text part sent ahead of the frames so the model knows what it's
+    /// looking at.
+    pub fn describe(&self, frames: &[&VideoFrame]) -> String {
+        let name = self
+            .source
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "video".to_string());
+        let timestamps = frames
+            .iter()
+            .map(|frame| format_timestamp(frame.timestamp_secs))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut text = format!(
+            "[Video: {} ({}). The {} images that follow are frames taken at {}.]",
+            name,
+            format_timestamp(self.duration_secs),
+            frames.len(),
+            timestamps
+        );
+        if let Some(transcript) = self.transcript.as_deref().filter(|t| !t.is_empty()) {
+            text.push_str("\n\nAudio transcript:\n");
+            text.push_str(transcript);
+        }
+        text
+    }
+}
+
+/// Effective frame cap for a model's optional `max_video_frames` setting.
+pub fn frame_cap(model_cap: Option<u32>) -> u32 {
+    model_cap
+        .unwrap_or(DEFAULT_VIDEO_FRAMES)
+        .clamp(1, MAX_VIDEO_FRAMES)
+}
+
+/// Timestamps of `count` frames spread over `duration_secs`, each in the
+/// middle of its slice so the first and last frames avoid black fades.
+pub fn frame_timestamps(duration_secs: f64, count: u32) -> Vec<f64> {
+    let count = count.max(1);
+    (0..count)
+        .map(|i| duration_secs * (i as f64 + 0.5) / count as f64)
+        .collect()
+}
+
+/// `m:ss` (or `h:mm:ss`) for a position in the clip.
+pub fn format_timestamp(secs: f64) -> String {
+    let total = secs.max(0.0).round() as u64;
+    let (hours, minutes, seconds) = (total / 3600, (total % 3600) / 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// The sample for `path` already on disk, if any. Cheap after the first
+/// call, so it can be used while rendering.
+pub fn cached_sample(path: &Path) -> Option<VideoSample> {
+    if let Some(sample) = SAMPLES.lock().get(path) {
+        return Some(sample.clone());
+    }
+    let manifest = sample_dir(path)?.join(MANIFEST_FILE);
+    let sample: VideoSample = serde_json::from_slice(&std::fs::read(manifest).ok()?).ok()?;
+    SAMPLES.lock().insert(path.to_path_buf(), sample.clone());
+    Some(sample)
+}
+
+/// Sample `frame_count` frames from the video at `path`, and transcribe its
+/// audio with `transcribe_command` when one is given. Reuses the cached
+/// sample when it already has enough frames (and a transcript, if asked for).
+pub async fn sample_video(
+    path: &Path,
+    frame_count: u32,
+    transcribe_command: Option<&str>,
+) -> Result<VideoSample> {
+    let frame_count = frame_count.clamp(1, MAX_VIDEO_FRAMES);
+    let wants_transcript = transcribe_command.is_some_and(|c| !c.trim().is_empty());
+
+    if let Some(sample) = cached_sample(path)
+        && sample.frames.len() >= frame_count as usize
+        && (!wants_transcript || sample.transcript.is_some())
+    {
+        return Ok(sample);
+    }
+
+    let dir = sample_dir(path).ok_or_else(|| anyhow!("No directory to store video frames in"))?;
+    tokio::fs::create_dir_all(&dir).await?;
+
+    let duration_secs = probe_duration(path).await?;
+    let mut frames = Vec::new();
+    for (i, timestamp_secs) in frame_timestamps(duration_secs, frame_count)
+        .into_iter()
+        .enumerate()
+    {
+        let frame_path = dir.join(format!("frame_{}_{:02}.jpg", frame_count, i));
+        extract_frame(path, timestamp_secs, &frame_path).await?;
+        frames.push(VideoFrame {
+            path: frame_path,
+            timestamp_secs,
+        });
+    }
+
+    let transcript = match transcribe_command.filter(|_| wants_transcript) {
+        Some(command) => match transcribe(path, command, &dir).await {
+            Ok(transcript) => Some(transcript),
+            Err(e) => {
+                warn!(?path, error = ?e, "Video transcription failed; sending frames only");
+                None
+            }
+        },
+        None => None,
+    };
+
+    let sample = VideoSample {
+        source: path.to_path_buf(),
+        duration_secs,
+        frames,
+        transcript,
+    };
+    tokio::fs::write(dir.join(MANIFEST_FILE), serde_json::to_vec_pretty(&sample)?).await?;
+    SAMPLES.lock().insert(path.to_path_buf(), sample.clone());
+    debug!(?path, frames = sample.frames.len(), "Sampled video frames");
+    Ok(sample)
+}
+
+/// Cache directory for `path`'s frames; changes when the file is modified.
+fn sample_dir(path: &Path) -> Option<PathBuf> {
+    use sha2::{Digest, Sha256};
+
+    let modified = std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(path.to_string_lossy().as_bytes());
+    hasher.update(modified.to_le_bytes());
+    let hash = hex::encode(hasher.finalize());
+
+    dirs::data_dir().map(|d| d.join("chatty").join("video_frames").join(&hash[..16]))
+}
+
+async fn probe_duration(path: &Path) -> Result<f64> {
+    let output = tokio::process::Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "format=duration",
+            "-of",
+            "default=noprint_wrappers=1:nokey=1",
+        ])
+        .arg(path)
+        .output()
+        .await
+        .context("Failed to run ffprobe (is ffmpeg installed?)")?;
+    if !output.status.success() {
+        bail!(
+            "ffprobe failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|d| d.is_finite() && *d > 0.0)
+        .ok_or_else(|| anyhow!("Could not read the video's duration"))
+}
+
+async fn extract_frame(path: &Path, timestamp_secs: f64, out: &Path) -> Result<()> {
+    let output = tokio::process::Command::new("ffmpeg")
+        .args([
+            "-v",
+            "error",
+            "-y",
+            "-ss",
+            &format!("{:.3}", timestamp_secs),
+            "-i",
+        ])
+        .arg(path)
+        .args([
+            "-frames:v",
+            "1",
+            "-vf",
+            &format!("scale='min({},iw)':-2", FRAME_WIDTH),
+            "-q:v",
+            "3",
+        ])
+        .arg(out)
+        .output()
+        .await
+        .context("Failed to run ffmpeg (is ffmpeg installed?)")?;
+    if !output.status.success() || !out.exists() {
+        bail!(
+            "ffmpeg failed to extract a frame at {:.1}s: {}",
+            timestamp_secs,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Extract the audio track as 16 kHz mono WAV and run `command` on it. The
+/// command's stdout is the transcript.
+async fn transcribe(path: &Path, command: &str, dir: &Path) -> Result<String> {
+    let audio = dir.join("audio.wav");
+    let output = tokio::process::Command::new("ffmpeg")
+        .args(["-v", "error", "-y", "-i"])
+        .arg(path)
+        .args(["-vn", "-ac", "1", "-ar", "16000"])
+        .arg(&audio)
+        .output()
+        .await
+        .context("Failed to run ffmpeg (is ffmpeg installed?)")?;
+    if !output.status.success() {
+        bail!(
+            "ffmpeg failed to extract audio: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let command_line = build_transcribe_command(command, &audio);
+    let output = shell_command(&command_line)
+        .output()
+        .await
+        .context("Failed to run the transcription command")?;
+    let _ = tokio::fs::remove_file(&audio).await;
+    if !output.status.success() {
+        bail!(
+            "Transcription command failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_with_frames(count: usize) -> VideoSample {
+        VideoSample {
+            source: PathBuf::from("/videos/demo.mp4"),
+            duration_secs: 90.0,
+            frames: (0..count)
+                .map(|i| VideoFrame {
+                    path: PathBuf::from(format!("/frames/{}.jpg", i)),
+                    timestamp_secs: i as f64 * 10.0,
+                })
+                .collect(),
+            transcript: None,
+        }
+    }
+
+    #[test]
+    fn timestamps_sit_in_the_middle_of_each_slice() {
+        assert_eq!(frame_timestamps(80.0, 4), vec![10.0, 30.0, 50.0, 70.0]);
+        assert_eq!(frame_timestamps(10.0, 0), vec![5.0]);
+    }
+
+    #[test]
+    fn frame_cap_defaults_and_clamps() {
+        assert_eq!(frame_cap(None), DEFAULT_VIDEO_FRAMES);
+        assert_eq!(frame_cap(Some(0)), 1);
+        assert_eq!(frame_cap(Some(500)), MAX_VIDEO_FRAMES);
+        assert_eq!(frame_cap(Some(4)), 4);
+    }
+
+    #[test]
+    fn select_frames_spreads_over_the_sample() {
+        let sample = sample_with_frames(8);
+        let picked: Vec<f64> = sample
+            .select_frames(2)
+            .iter()
+            .map(|f| f.timestamp_secs)
+            .collect();
+        assert_eq!(picked, vec![20.0, 60.0]);
+        assert_eq!(sample.select_frames(20).len(), 8);
+    }
+
+    #[test]
+    fn describe_lists_timestamps_and_transcript() {
+        let mut sample = sample_with_frames(2);
+        sample.transcript = Some("hello there".to_string());
+        let frames = sample.select_frames(2);
+        let text = sample.describe(&frames);
+        assert!(text.starts_with("[Video: demo.mp4 (1:30). The 2 images"));
+        assert!(text.contains("0:00, 0:10"));
+        assert!(text.ends_with("Audio transcript:\nhello there"));
+    }
+
+    #[test]
+    fn format_timestamp_handles_hours() {
+        assert_eq!(format_timestamp(5.4), "0:05");
+        assert_eq!(format_timestamp(3725.0), "1:02:05");
+    }
+}
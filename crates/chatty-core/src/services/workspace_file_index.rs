@@ -0,0 +1,214 @@
+//! Index of the files in a workspace, for path autocompletion.
+//!
+//! The workspace is walked once (skipping hidden entries, dependency and
+//! build directories, and symlinks) and the files are kept as relative
+//! paths with `/` separators, the form the filesystem tools accept.
+//! [`WorkspaceFileIndex::complete`] ranks them against a typed fragment with
+//! a fuzzy subsequence match.
+
+use std::path::{Path, PathBuf};
+
+/// Directories never descended into.
+const EXCLUDED_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "__pycache__",
+    "dist",
+    "build",
+    ".git",
+];
+
+/// Stop indexing after this many files so huge trees stay responsive.
+pub const MAX_INDEXED_FILES: usize = 20_000;
+
+/// Files nested deeper than this are left out.
+const MAX_DEPTH: usize = 16;
+
+/// Relative paths of the files in a workspace.
+#[derive(Clone, Debug, Default)]
+pub struct WorkspaceFileIndex {
+    root: PathBuf,
+    paths: Vec<String>,
+}
+
+impl WorkspaceFileIndex {
+    /// Walk `root` and collect its files, sorted. Unreadable directories are skipped.
+    pub fn build(root: &Path) -> Self {
+        let mut paths = Vec::new();
+        let mut pending = vec![(root.to_path_buf(), 0)];
+        while let Some((dir, depth)) = pending.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.filter_map(|e| e.ok()) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with('.') {
+                    continue;
+                }
+                // `DirEntry::file_type` does not follow symlinks, so links
+                // (which may point outside the workspace) are never listed
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                let path = entry.path();
+                if file_type.is_dir() {
+                    if depth < MAX_DEPTH && !EXCLUDED_DIRS.contains(&name.as_str()) {
+                        pending.push((path, depth + 1));
+                    }
+                } else if file_type.is_file()
+                    && let Some(relative) = relative_path(root, &path)
+                {
+                    paths.push(relative);
+                    if paths.len() >= MAX_INDEXED_FILES {
+                        pending.clear();
+                        break;
+                    }
+                }
+            }
+        }
+        paths.sort_unstable();
+        Self {
+            root: root.to_path_buf(),
+            paths,
+        }
+    }
+
+    /// Directory the paths are relative to.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Up to `limit` indexed paths matching `fragment`, best match first.
+    pub fn complete(&self, fragment: &str, limit: usize) -> Vec<&str> {
+        let fragment = fragment.strip_prefix("./").unwrap_or(fragment);
+        let mut scored: Vec<(i64, &str)> = self
+            .paths
+            .iter()
+            .filter_map(|path| Some((fuzzy_score(path, fragment)?, path.as_str())))
+            .collect();
+        // Higher scores first; shorter paths break ties
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.len().cmp(&b.1.len())));
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, path)| path)
+            .collect()
+    }
+
+    /// Whether `path` is still an indexed file that exists inside the root.
+    /// Guards against inserting a path deleted or replaced since indexing.
+    pub fn validate(&self, path: &str) -> bool {
+        if self
+            .paths
+            .binary_search_by(|p| p.as_str().cmp(path))
+            .is_err()
+        {
+            return false;
+        }
+        let (Ok(root), Ok(file)) = (
+            std::fs::canonicalize(&self.root),
+            std::fs::canonicalize(self.root.join(path)),
+        ) else {
+            return false;
+        };
+        file.starts_with(root) && file.is_file()
+    }
+}
+
+/// `path` relative to `root` with `/` separators, if it is valid UTF-8.
+fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let parts: Option<Vec<&str>> = relative.iter().map(|part| part.to_str()).collect();
+    Some(parts?.join("/"))
+}
+
+/// Score how well `path` matches `fragment` as a case-insensitive
+/// subsequence, or `None` if it does not match. Consecutive characters,
+/// matches at the start of a path segment or word, and matches in the file
+/// name score higher.
+pub fn fuzzy_score(path: &str, fragment: &str) -> Option<i64> {
+    let path_chars: Vec<char> = path.chars().flat_map(char::to_lowercase).collect();
+    let name_start = path_chars
+        .iter()
+        .rposition(|&c| c == '/')
+        .map_or(0, |ix| ix + 1);
+
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in fragment.chars().flat_map(char::to_lowercase) {
+        let ix = next + path_chars[next..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == ix) {
+            score += 5;
+        }
+        if ix == 0 || matches!(path_chars[ix - 1], '/' | '_' | '-' | '.' | ' ') {
+            score += 3;
+        }
+        if ix >= name_start {
+            score += 2;
+        }
+        previous = Some(ix);
+        next = ix + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(files: &[&str]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for file in files {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn indexes_files_and_skips_hidden_and_build_dirs() {
+        let dir = workspace(&[
+            "src/main.rs",
+            "src/views/chat.rs",
+            ".git/config",
+            ".env",
+            "target/debug/app",
+            "node_modules/pkg/index.js",
+        ]);
+        let index = WorkspaceFileIndex::build(dir.path());
+        assert_eq!(index.paths, ["src/main.rs", "src/views/chat.rs"]);
+    }
+
+    #[test]
+    fn completes_fuzzy_fragments_best_first() {
+        let dir = workspace(&["src/main.rs", "src/chat_view.rs", "docs/chat.md"]);
+        let index = WorkspaceFileIndex::build(dir.path());
+        assert_eq!(index.complete("chatv", 5), ["src/chat_view.rs"]);
+        assert_eq!(index.complete("./src/m", 5)[0], "src/main.rs");
+        assert_eq!(index.complete("chat", 5)[0], "docs/chat.md");
+        assert!(index.complete("xyz", 5).is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_unknown_and_deleted_paths() {
+        let dir = workspace(&["src/main.rs", "notes.txt"]);
+        let index = WorkspaceFileIndex::build(dir.path());
+        assert!(index.validate("src/main.rs"));
+        assert!(!index.validate("src/other.rs"));
+        assert!(!index.validate("../notes.txt"));
+
+        std::fs::remove_file(dir.path().join("notes.txt")).unwrap();
+        assert!(!index.validate("notes.txt"));
+    }
+}
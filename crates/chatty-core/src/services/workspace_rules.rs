@@ -0,0 +1,150 @@
+//! Per-workspace instruction files.
+//!
+//! A workspace can carry standing instructions for the agent in
+//! `.chatty/rules.md` (or, failing that, an `AGENTS.md` at its root). The file
+//! is read when a conversation's agent is built for that workspace and appended
+//! to the system prompt. [`RulesFingerprint`] lets callers notice edits cheaply
+//! so the agent can be rebuilt with the new rules.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Rule files looked up relative to the workspace root, in priority order.
+pub const RULES_FILE_CANDIDATES: &[&str] = &[".chatty/rules.md", "AGENTS.md"];
+
+/// Rules longer than this are cut so one file cannot crowd out the conversation.
+const MAX_RULES_BYTES: usize = 32 * 1024;
+
+/// Instructions loaded from a workspace rules file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkspaceRules {
+    /// File the rules were read from
+    pub path: PathBuf,
+    pub content: String,
+    /// True when the file exceeded [`MAX_RULES_BYTES`] and was cut
+    pub truncated: bool,
+}
+
+impl WorkspaceRules {
+    /// Load the first non-empty rules file in `workspace_dir`, if any.
+    pub fn load(workspace_dir: &Path) -> Option<Self> {
+        RULES_FILE_CANDIDATES.iter().find_map(|name| {
+            let path = workspace_dir.join(name);
+            let content = std::fs::read_to_string(&path).ok()?;
+            let content = content.trim();
+            if content.is_empty() {
+                return None;
+            }
+            let (content, truncated) = truncate_to_char_boundary(content, MAX_RULES_BYTES);
+            Some(Self {
+                path,
+                content: content.to_string(),
+                truncated,
+            })
+        })
+    }
+
+    /// Section appended to the agent's system prompt.
+    pub fn preamble_section(&self) -> String {
+        let file_name = self
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let note = if self.truncated {
+            "\n\n[rules truncated — the file is longer than the rules limit]"
+        } else {
+            ""
+        };
+        format!(
+            "\n\n## Workspace rules ({file_name})\n\
+             The user keeps these instructions in the workspace. Follow them for \
+             all work in this workspace unless the user says otherwise.\n\n{}{note}",
+            self.content
+        )
+    }
+}
+
+/// Identity of the rules file currently in effect for a workspace.
+///
+/// Two fingerprints differ when a rules file appears, disappears, or is
+/// modified, which is all a watcher needs to decide whether to reload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RulesFingerprint {
+    pub path: PathBuf,
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl RulesFingerprint {
+    /// Fingerprint the rules file `WorkspaceRules::load` would pick, without
+    /// reading it. Empty files are skipped just like in `load`.
+    pub fn of(workspace_dir: &Path) -> Option<Self> {
+        RULES_FILE_CANDIDATES.iter().find_map(|name| {
+            let path = workspace_dir.join(name);
+            let metadata = std::fs::metadata(&path).ok()?;
+            if !metadata.is_file() || metadata.len() == 0 {
+                return None;
+            }
+            Some(Self {
+                path,
+                modified: metadata.modified().ok(),
+                len: metadata.len(),
+            })
+        })
+    }
+}
+
+fn truncate_to_char_boundary(s: &str, max_bytes: usize) -> (&str, bool) {
+    if s.len() <= max_bytes {
+        return (s, false);
+    }
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    (&s[..end], true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chatty_rules_take_priority_over_agents_md() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("AGENTS.md"), "Use tabs.").unwrap();
+        assert_eq!(
+            WorkspaceRules::load(dir.path()).unwrap().path,
+            dir.path().join("AGENTS.md")
+        );
+
+        std::fs::create_dir(dir.path().join(".chatty")).unwrap();
+        std::fs::write(dir.path().join(".chatty/rules.md"), "").unwrap();
+        // Empty rules files are ignored
+        assert_eq!(
+            RulesFingerprint::of(dir.path()).unwrap().path,
+            dir.path().join("AGENTS.md")
+        );
+
+        std::fs::write(dir.path().join(".chatty/rules.md"), "Always write tests.").unwrap();
+        let rules = WorkspaceRules::load(dir.path()).unwrap();
+        assert_eq!(rules.path, dir.path().join(".chatty/rules.md"));
+        assert!(rules.preamble_section().contains("Always write tests."));
+        assert!(!rules.truncated);
+    }
+
+    #[test]
+    fn missing_rules_load_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(WorkspaceRules::load(dir.path()).is_none());
+        assert!(RulesFingerprint::of(dir.path()).is_none());
+    }
+
+    #[test]
+    fn oversized_rules_are_truncated() {
+        let (cut, truncated) = truncate_to_char_boundary("héllo", 2);
+        assert_eq!((cut, truncated), ("h", true));
+        assert_eq!(truncate_to_char_boundary("abc", 10), ("abc", false));
+    }
+}
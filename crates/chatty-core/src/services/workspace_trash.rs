@@ -0,0 +1,315 @@
+//! Workspace-local trash, so agent file deletions and moves can be undone.
+//!
+//! `delete_file` moves the file into `.chatty/trash/` of the workspace
+//! instead of removing it, and `move_file` records where the file went.
+//! Each change gets an id, returned in the tool output, which the trace of
+//! the call uses to offer "Restore". Changes can be restored for
+//! [`UNDO_WINDOW_SECS`]; older trashed files are removed the next time something
+//! is trashed. Only changes made in this process are offered for restore.
+//! The trash ignores itself in git, so trashed files are never committed.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Result, anyhow};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// Trash directory, relative to the workspace root.
+pub const TRASH_DIR: &str = ".chatty/trash";
+
+/// How long a deletion or move can be restored, in seconds.
+pub const UNDO_WINDOW_SECS: i64 = 24 * 60 * 60;
+
+/// Workspace root and time of each change that can be restored, by id.
+static RESTORABLE: LazyLock<RwLock<HashMap<String, (PathBuf, i64)>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// A change recorded in the trash. Paths are relative to the workspace root.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TrashedChange {
+    Deleted {
+        path: PathBuf,
+    },
+    Moved {
+        source: PathBuf,
+        destination: PathBuf,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    pub change: TrashedChange,
+    /// Unix timestamp (seconds) of the change
+    pub trashed_at: i64,
+}
+
+impl TrashEntry {
+    /// e.g. `Restored notes/todo.md`
+    pub fn restored_message(&self) -> String {
+        match &self.change {
+            TrashedChange::Deleted { path } => format!("Restored {}", path.display()),
+            TrashedChange::Moved {
+                source,
+                destination,
+            } => format!(
+                "Moved {} back to {}",
+                destination.display(),
+                source.display()
+            ),
+        }
+    }
+}
+
+fn trash_dir(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(TRASH_DIR)
+}
+
+/// Create the trash directory, with a `.gitignore` that ignores everything
+/// in it.
+async fn create_trash_dir(workspace_root: &Path) -> Result<PathBuf> {
+    let dir = trash_dir(workspace_root);
+    tokio::fs::create_dir_all(&dir).await?;
+    let gitignore = dir.join(".gitignore");
+    if !tokio::fs::try_exists(&gitignore).await.unwrap_or(false) {
+        tokio::fs::write(&gitignore, "*\n").await?;
+    }
+    Ok(dir)
+}
+
+fn relative(workspace_root: &Path, path: &Path) -> Result<PathBuf> {
+    path.strip_prefix(workspace_root)
+        .map(Path::to_path_buf)
+        .map_err(|_| anyhow!("'{}' is outside the workspace", path.display()))
+}
+
+/// Record `change` in the trash and remember it for restoring.
+async fn record(workspace_root: &Path, change: TrashedChange, now: i64) -> Result<TrashEntry> {
+    let entry = TrashEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        change,
+        trashed_at: now,
+    };
+    let dir = create_trash_dir(workspace_root).await?;
+    tokio::fs::write(
+        dir.join(format!("{}.json", entry.id)),
+        serde_json::to_string_pretty(&entry)?,
+    )
+    .await?;
+    RESTORABLE
+        .write()
+        .insert(entry.id.clone(), (workspace_root.to_path_buf(), now));
+    Ok(entry)
+}
+
+/// Move `file` (an absolute path inside `workspace_root`) into the trash.
+pub async fn trash_file(workspace_root: &Path, file: &Path, now: i64) -> Result<TrashEntry> {
+    let path = relative(workspace_root, file)?;
+    if let Err(e) = purge_expired(workspace_root, now).await {
+        warn!(error = ?e, "Failed to empty expired trash entries");
+    }
+    let entry = record(workspace_root, TrashedChange::Deleted { path }, now).await?;
+    let trashed = trash_dir(workspace_root).join(&entry.id);
+    if let Err(e) = tokio::fs::rename(file, &trashed).await {
+        forget(workspace_root, &entry.id).await;
+        return Err(anyhow!(
+            "Failed to move '{}' to the trash: {}",
+            file.display(),
+            e
+        ));
+    }
+    info!(id = %entry.id, path = %file.display(), "File moved to the workspace trash");
+    Ok(entry)
+}
+
+/// Record that `source` was moved to `destination` (absolute paths inside
+/// `workspace_root`), so the move can be undone.
+pub async fn record_move(
+    workspace_root: &Path,
+    source: &Path,
+    destination: &Path,
+    now: i64,
+) -> Result<TrashEntry> {
+    let change = TrashedChange::Moved {
+        source: relative(workspace_root, source)?,
+        destination: relative(workspace_root, destination)?,
+    };
+    record(workspace_root, change, now).await
+}
+
+/// Whether the change `id` was made in this process and can still be restored.
+pub fn is_restorable(id: &str, now: i64) -> bool {
+    RESTORABLE
+        .read()
+        .get(id)
+        .is_some_and(|(_, trashed_at)| now - trashed_at < UNDO_WINDOW_SECS)
+}
+
+/// Undo the change `id`: put a deleted file back, or move a moved file back
+/// to where it was. Refuses to overwrite a file that took its place.
+pub async fn restore(id: &str, now: i64) -> Result<TrashEntry> {
+    if !is_restorable(id, now) {
+        return Err(anyhow!("This change can no longer be restored"));
+    }
+    let Some((workspace_root, _)) = RESTORABLE.read().get(id).cloned() else {
+        return Err(anyhow!("This change can no longer be restored"));
+    };
+    let dir = trash_dir(&workspace_root);
+    let entry: TrashEntry =
+        serde_json::from_str(&tokio::fs::read_to_string(dir.join(format!("{id}.json"))).await?)?;
+
+    let (from, to) = match &entry.change {
+        TrashedChange::Deleted { path } => (dir.join(id), workspace_root.join(path)),
+        TrashedChange::Moved {
+            source,
+            destination,
+        } => (
+            workspace_root.join(destination),
+            workspace_root.join(source),
+        ),
+    };
+    if tokio::fs::try_exists(&to).await? {
+        return Err(anyhow!(
+            "'{}' exists again; move it away before restoring",
+            to.display()
+        ));
+    }
+    if let Some(parent) = to.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::rename(&from, &to)
+        .await
+        .map_err(|e| anyhow!("Failed to restore '{}': {}", to.display(), e))?;
+
+    forget(&workspace_root, id).await;
+    info!(id, path = %to.display(), "Restored from the workspace trash");
+    Ok(entry)
+}
+
+/// Drop the record of change `id`.
+async fn forget(workspace_root: &Path, id: &str) {
+    RESTORABLE.write().remove(id);
+    let _ = tokio::fs::remove_file(trash_dir(workspace_root).join(format!("{id}.json"))).await;
+}
+
+/// Permanently remove trashed files and records older than the undo window.
+/// Returns how many changes were removed.
+pub async fn purge_expired(workspace_root: &Path, now: i64) -> Result<usize> {
+    let dir = trash_dir(workspace_root);
+    let mut entries = match tokio::fs::read_dir(&dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let mut removed = 0;
+    while let Some(file) = entries.next_entry().await? {
+        let path = file.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Ok(entry) = serde_json::from_str::<TrashEntry>(
+            &tokio::fs::read_to_string(&path).await.unwrap_or_default(),
+        ) else {
+            continue;
+        };
+        if now - entry.trashed_at < UNDO_WINDOW_SECS {
+            continue;
+        }
+        let trashed = dir.join(&entry.id);
+        if trashed.is_dir() {
+            tokio::fs::remove_dir_all(&trashed).await.ok();
+        } else {
+            tokio::fs::remove_file(&trashed).await.ok();
+        }
+        forget(workspace_root, &entry.id).await;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[tokio::test]
+    async fn deleted_file_is_restored() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        std::fs::create_dir(root.join("notes")).unwrap();
+        let file = root.join("notes/todo.md");
+        std::fs::write(&file, "buy milk").unwrap();
+
+        let entry = trash_file(&root, &file, NOW).await.unwrap();
+        assert!(!file.exists());
+        assert!(is_restorable(&entry.id, NOW));
+        assert_eq!(
+            entry.change,
+            TrashedChange::Deleted {
+                path: PathBuf::from("notes/todo.md")
+            }
+        );
+
+        let restored = restore(&entry.id, NOW + 60).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "buy milk");
+        assert_eq!(restored.restored_message(), "Restored notes/todo.md");
+        assert!(!is_restorable(&entry.id, NOW));
+        assert!(restore(&entry.id, NOW).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn trash_ignores_itself_in_git() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let file = root.join("secret.env");
+        std::fs::write(&file, "TOKEN=1").unwrap();
+
+        trash_file(&root, &file, NOW).await.unwrap();
+        let gitignore = root.join(TRASH_DIR).join(".gitignore");
+        assert_eq!(std::fs::read_to_string(&gitignore).unwrap(), "*\n");
+        // Purging leaves it in place
+        assert_eq!(
+            purge_expired(&root, NOW + UNDO_WINDOW_SECS).await.unwrap(),
+            1
+        );
+        assert!(gitignore.exists());
+    }
+
+    #[tokio::test]
+    async fn move_is_undone_unless_the_source_was_reused() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let (source, destination) = (root.join("a.txt"), root.join("b.txt"));
+        std::fs::write(&destination, "moved").unwrap();
+
+        let entry = record_move(&root, &source, &destination, NOW)
+            .await
+            .unwrap();
+        std::fs::write(&source, "new").unwrap();
+        assert!(restore(&entry.id, NOW).await.is_err());
+
+        std::fs::remove_file(&source).unwrap();
+        restore(&entry.id, NOW).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&source).unwrap(), "moved");
+        assert!(!destination.exists());
+    }
+
+    #[tokio::test]
+    async fn expired_changes_are_purged() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        let file = root.join("old.log");
+        std::fs::write(&file, "").unwrap();
+        let entry = trash_file(&root, &file, NOW).await.unwrap();
+
+        let later = NOW + UNDO_WINDOW_SECS;
+        assert!(!is_restorable(&entry.id, later));
+        assert_eq!(purge_expired(&root, later).await.unwrap(), 1);
+        assert!(!root.join(TRASH_DIR).join(&entry.id).exists());
+    }
+}
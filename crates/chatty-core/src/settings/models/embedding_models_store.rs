@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+
+use super::execution_settings::ExecutionSettingsModel;
+use super::providers_store::ProviderType;
+
+/// A registered embedding model, used for vector search over memory and skills.
+///
+/// Embedding models are configured separately from chat models because they
+/// have different metadata (vector dimensions, batch limits) and are never
+/// used for completions.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EmbeddingModelConfig {
+    pub id: String,
+    pub name: String,
+    pub provider_type: ProviderType,
+    pub model_identifier: String,
+    /// Output vector dimensions, shown in the registry. `None` means unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<usize>,
+    /// Maximum number of texts sent in a single embedding request.
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    /// Maximum embedding requests per minute (`None` = unlimited).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<u32>,
+}
+
+fn default_batch_size() -> usize {
+    32
+}
+
+/// A store searched by vector similarity, each with its own embedding model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KnowledgeBase {
+    /// Agent memory (memvid vector index)
+    Memory,
+    /// Skill files, scored against their cached embeddings
+    Skills,
+}
+
+impl EmbeddingModelConfig {
+    pub fn new(
+        id: String,
+        name: String,
+        provider_type: ProviderType,
+        model_identifier: String,
+    ) -> Self {
+        let dimensions = known_dimensions(&model_identifier);
+        Self {
+            id,
+            name,
+            provider_type,
+            model_identifier,
+            dimensions,
+            batch_size: default_batch_size(),
+            requests_per_minute: None,
+        }
+    }
+}
+
+/// Vector dimensions of well-known embedding models.
+///
+/// Matches on the final path segment so provider-prefixed identifiers
+/// (e.g. `openai/text-embedding-3-small` on OpenRouter) resolve too.
+pub fn known_dimensions(model_identifier: &str) -> Option<usize> {
+    let base = model_identifier
+        .rsplit('/')
+        .next()
+        .unwrap_or(model_identifier);
+    let base = base.split(':').next().unwrap_or(base);
+    match base {
+        "text-embedding-3-small" | "text-embedding-ada-002" => Some(1536),
+        "text-embedding-3-large" => Some(3072),
+        "nomic-embed-text" => Some(768),
+        "mxbai-embed-large" => Some(1024),
+        "all-minilm" => Some(384),
+        "text-embedding-004" | "gemini-embedding-001" => Some(768),
+        _ => None,
+    }
+}
+
+/// Global store for registered embedding models.
+#[derive(Clone, Default)]
+pub struct EmbeddingModelsModel {
+    models: Vec<EmbeddingModelConfig>,
+}
+
+impl EmbeddingModelsModel {
+    pub fn new() -> Self {
+        Self { models: Vec::new() }
+    }
+
+    pub fn add_model(&mut self, config: EmbeddingModelConfig) {
+        self.models.push(config);
+    }
+
+    pub fn update_model(&mut self, updated_config: EmbeddingModelConfig) -> bool {
+        if let Some(model) = self.models.iter_mut().find(|m| m.id == updated_config.id) {
+            *model = updated_config;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn delete_model(&mut self, id: &str) -> bool {
+        let initial_len = self.models.len();
+        self.models.retain(|m| m.id != id);
+        self.models.len() < initial_len
+    }
+
+    pub fn get_model(&self, id: &str) -> Option<&EmbeddingModelConfig> {
+        self.models.iter().find(|m| m.id == id)
+    }
+
+    pub fn models(&self) -> &[EmbeddingModelConfig] {
+        &self.models
+    }
+
+    /// Replace all models (used when loading from disk)
+    pub fn replace_all(&mut self, models: Vec<EmbeddingModelConfig>) {
+        self.models = models;
+    }
+
+    /// Resolve the embedding model for `knowledge_base`.
+    ///
+    /// The skills index uses the registered model selected via
+    /// `skills_embedding_model_id`, or the memory store's model when none is
+    /// selected (or the selected one was deleted).
+    pub fn resolve_for(
+        &self,
+        knowledge_base: KnowledgeBase,
+        settings: &ExecutionSettingsModel,
+    ) -> Option<EmbeddingModelConfig> {
+        let selected = match knowledge_base {
+            KnowledgeBase::Memory => None,
+            KnowledgeBase::Skills => settings.skills_embedding_model_id.as_deref(),
+        };
+        match selected.and_then(|id| self.get_model(id)) {
+            Some(config) => Some(config.clone()),
+            None => self.resolve(settings),
+        }
+    }
+
+    /// Resolve the embedding model to use for the memory vector store.
+    ///
+    /// Prefers the registered model selected via `embedding_model_id`; falls
+    /// back to an ad-hoc config built from the legacy `embedding_provider` /
+    /// `embedding_model` fields so existing settings keep working.
+    pub fn resolve(&self, settings: &ExecutionSettingsModel) -> Option<EmbeddingModelConfig> {
+        if let Some(config) = settings
+            .embedding_model_id
+            .as_deref()
+            .and_then(|id| self.get_model(id))
+        {
+            return Some(config.clone());
+        }
+
+        let provider_type = settings.embedding_provider.clone()?;
+        let model_name = settings.embedding_model.clone()?;
+        Some(EmbeddingModelConfig::new(
+            String::new(),
+            model_name.clone(),
+            provider_type,
+            model_name,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_dimensions_strips_provider_prefix_and_tag() {
+        assert_eq!(
+            known_dimensions("openai/text-embedding-3-small"),
+            Some(1536)
+        );
+        assert_eq!(known_dimensions("nomic-embed-text:latest"), Some(768));
+        assert_eq!(known_dimensions("google/gemini-embedding-001"), Some(768));
+        assert_eq!(known_dimensions("custom-embedder"), None);
+    }
+
+    #[test]
+    fn resolve_prefers_registered_model_over_legacy_fields() {
+        let mut store = EmbeddingModelsModel::new();
+        store.add_model(EmbeddingModelConfig::new(
+            "nomic".into(),
+            "Nomic".into(),
+            ProviderType::Ollama,
+            "nomic-embed-text".into(),
+        ));
+
+        let mut settings = ExecutionSettingsModel {
+            embedding_provider: Some(ProviderType::OpenRouter),
+            embedding_model: Some("openai/text-embedding-3-small".into()),
+            ..Default::default()
+        };
+        let legacy = store.resolve(&settings).unwrap();
+        assert_eq!(legacy.provider_type, ProviderType::OpenRouter);
+        assert_eq!(legacy.dimensions, Some(1536));
+
+        settings.embedding_model_id = Some("nomic".into());
+        let selected = store.resolve(&settings).unwrap();
+        assert_eq!(selected.model_identifier, "nomic-embed-text");
+
+        settings.embedding_model_id = Some("deleted".into());
+        settings.embedding_provider = None;
+        assert!(store.resolve(&settings).is_none());
+    }
+
+    #[test]
+    fn skills_fall_back_to_the_memory_model() {
+        let mut store = EmbeddingModelsModel::new();
+        store.add_model(EmbeddingModelConfig::new(
+            "nomic".into(),
+            "Nomic".into(),
+            ProviderType::Ollama,
+            "nomic-embed-text".into(),
+        ));
+        store.add_model(EmbeddingModelConfig::new(
+            "small".into(),
+            "Small".into(),
+            ProviderType::OpenRouter,
+            "openai/text-embedding-3-small".into(),
+        ));
+
+        let mut settings = ExecutionSettingsModel {
+            embedding_model_id: Some("nomic".into()),
+            ..Default::default()
+        };
+        let skills = store.resolve_for(KnowledgeBase::Skills, &settings).unwrap();
+        assert_eq!(skills.id, "nomic");
+
+        settings.skills_embedding_model_id = Some("small".into());
+        let skills = store.resolve_for(KnowledgeBase::Skills, &settings).unwrap();
+        assert_eq!(skills.id, "small");
+        let memory = store.resolve_for(KnowledgeBase::Memory, &settings).unwrap();
+        assert_eq!(memory.id, "nomic");
+
+        settings.skills_embedding_model_id = Some("deleted".into());
+        let skills = store.resolve_for(KnowledgeBase::Skills, &settings).unwrap();
+        assert_eq!(skills.id, "nomic");
+    }
+
+    #[test]
+    fn missing_rate_fields_use_defaults() {
+        let json = r#"{
+            "id": "e1",
+            "name": "Nomic",
+            "provider_type": "ollama",
+            "model_identifier": "nomic-embed-text"
+        }"#;
+        let config: EmbeddingModelConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.batch_size, 32);
+        assert_eq!(config.requests_per_minute, None);
+        assert_eq!(config.dimensions, None);
+    }
+}
@@ -1,4 +1,10 @@
+use crate::services::citation_service::CitationStyle;
+use crate::services::guardrail_service::{GuardrailRule, default_guardrail_rules};
+use crate::services::self_reflection::DEFAULT_REFLECTION_THRESHOLD;
+use crate::services::ssh_service::SshHostProfile;
 use crate::settings::models::providers_store::ProviderType;
+use crate::tools::custom_http_tool::CustomHttpToolConfig;
+use crate::tools::graphql_tool::GraphQlEndpointConfig;
 use serde::{Deserialize, Serialize};
 
 /// Approval mode for code execution requests
@@ -13,6 +19,19 @@ pub enum ApprovalMode {
     AutoApproveAll,
 }
 
+/// What happens to Docker Compose stacks started by the docker tool when
+/// their conversation is deleted.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum DockerCleanup {
+    /// Leave the containers running (default).
+    #[default]
+    Keep,
+    /// Stop the containers (`docker compose stop`).
+    Stop,
+    /// Stop and remove the containers (`docker compose down`).
+    Remove,
+}
+
 /// Settings for code execution tool
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ExecutionSettingsModel {
@@ -36,6 +55,20 @@ pub struct ExecutionSettingsModel {
     /// Requires workspace_dir to be set and the workspace to be a git repository.
     #[serde(default)]
     pub git_enabled: bool,
+    /// Enable kubectl-backed Kubernetes inspection tools (get, describe, logs, events).
+    /// Requires kubectl on PATH and a kubeconfig.
+    #[serde(default)]
+    pub kubernetes_enabled: bool,
+    /// kubeconfig contexts the Kubernetes tools may use. Empty = current context only.
+    #[serde(default)]
+    pub kubernetes_contexts: Vec<String>,
+    /// Namespaces the Kubernetes tools may use. Empty = any namespace.
+    #[serde(default)]
+    pub kubernetes_namespaces: Vec<String>,
+    /// Offer the k8s_action tool (delete, rollout restart, scale, cordon).
+    /// Every action still requires explicit approval.
+    #[serde(default)]
+    pub kubernetes_allow_mutations: bool,
     /// Expose the execute_code tool to the model.
     /// Python may run via Monty; other languages require Docker fallback.
     #[serde(default)]
@@ -49,6 +82,13 @@ pub struct ExecutionSettingsModel {
     /// or "unix:///path/to/docker.sock"). When None, the app tries common default locations.
     #[serde(default)]
     pub docker_host: Option<String>,
+    /// Expose the docker tool (list containers/images, logs, exec, compose up/down).
+    /// Exec and compose commands require approval.
+    #[serde(default)]
+    pub docker_tools_enabled: bool,
+    /// Cleanup applied to compose stacks a conversation started when it is deleted.
+    #[serde(default)]
+    pub docker_cleanup: DockerCleanup,
     /// Maximum execution time in seconds
     pub timeout_seconds: u32,
     /// Maximum output size in bytes (prevents memory exhaustion)
@@ -74,6 +114,76 @@ pub struct ExecutionSettingsModel {
     /// Embedding model identifier (e.g., "text-embedding-3-small").
     #[serde(default)]
     pub embedding_model: Option<String>,
+    /// ID of a registered embedding model (see `EmbeddingModelsModel`) used for
+    /// the memory vector store. Takes precedence over `embedding_provider` /
+    /// `embedding_model` when set.
+    #[serde(default)]
+    pub embedding_model_id: Option<String>,
+    /// ID of a registered embedding model used for the skills index
+    /// (None = same model as the memory store).
+    #[serde(default)]
+    pub skills_embedding_model_id: Option<String>,
+    /// Weight of keyword (BM25) ranks vs. vector ranks when fusing hybrid
+    /// memory search results (0.0 = vector only, 1.0 = keyword only).
+    #[serde(default = "default_hybrid_keyword_weight")]
+    pub hybrid_keyword_weight: f32,
+    /// Planner mode: the agent records a structured plan (create_plan) before
+    /// working and updates step statuses (update_plan) as it progresses.
+    #[serde(default)]
+    pub planner_mode: bool,
+    /// Self-reflection: after each answer the agent critiques it against the
+    /// question and retrieved context, and retries once when the critique
+    /// scores below `reflection_threshold`.
+    #[serde(default)]
+    pub self_reflection: bool,
+    /// Critique score (1–5) an answer needs to pass without a retry.
+    #[serde(default = "default_reflection_threshold")]
+    pub reflection_threshold: u8,
+    /// Guardrail rules evaluated against every native tool call before the
+    /// approval flow (see `GuardrailService`).
+    #[serde(default = "default_guardrail_rules")]
+    pub guardrail_rules: Vec<GuardrailRule>,
+
+    /// User-defined REST tools exposed to the agent (see `CustomHttpTool`).
+    #[serde(default)]
+    pub custom_http_tools: Vec<CustomHttpToolConfig>,
+
+    /// GraphQL endpoints exposed to the agent as read-only query tools
+    /// (see `GraphQlTool`).
+    #[serde(default)]
+    pub graphql_endpoints: Vec<GraphQlEndpointConfig>,
+
+    /// Enable the ssh_execute tool for the hosts in `ssh_hosts`.
+    #[serde(default)]
+    pub ssh_enabled: bool,
+    /// Remote hosts the agent may reach over SSH; nothing else is reachable.
+    #[serde(default)]
+    pub ssh_hosts: Vec<SshHostProfile>,
+
+    /// Markdown notes vault (e.g. an Obsidian vault). Used by the notes tools,
+    /// "Export answer to note" and wiki-link resolution for pinned notes.
+    #[serde(default)]
+    pub notes_vault_dir: Option<String>,
+    /// Expose notes_search / notes_read / notes_append for the vault.
+    #[serde(default)]
+    pub notes_enabled: bool,
+
+    /// Expose the citations tool for the reference library below.
+    #[serde(default)]
+    pub citations_enabled: bool,
+    /// BibTeX file used as the reference library. Takes precedence over Zotero.
+    #[serde(default)]
+    pub bibtex_path: Option<String>,
+    /// Zotero local API base URL (e.g. `http://localhost:23119/api`).
+    #[serde(default)]
+    pub zotero_api_url: Option<String>,
+    /// Bibliography style used when the agent does not ask for one.
+    #[serde(default)]
+    pub citation_style: CitationStyle,
+    /// Disconnect MCP servers whose tools have not been used for this many
+    /// minutes; they reconnect on the next tool call. 0 keeps them connected.
+    #[serde(default = "default_mcp_idle_shutdown_minutes")]
+    pub mcp_idle_shutdown_minutes: u32,
 }
 
 fn default_true() -> bool {
@@ -84,6 +194,18 @@ fn default_max_agent_turns() -> u32 {
     10
 }
 
+fn default_hybrid_keyword_weight() -> f32 {
+    0.5
+}
+
+fn default_reflection_threshold() -> u8 {
+    DEFAULT_REFLECTION_THRESHOLD
+}
+
+fn default_mcp_idle_shutdown_minutes() -> u32 {
+    10
+}
+
 impl Default for ExecutionSettingsModel {
     fn default() -> Self {
         Self {
@@ -94,9 +216,15 @@ impl Default for ExecutionSettingsModel {
             filesystem_write_enabled: true, // Enabled by default when workspace is set
             fetch_enabled: true,           // Enabled by default for zero-config web access
             git_enabled: false,            // Opt-in: requires workspace with git repo
-            execute_code_enabled: false,   // Opt-in: exposes execute_code to the model
+            kubernetes_enabled: false,     // Opt-in: requires kubectl and a kubeconfig
+            kubernetes_contexts: Vec::new(),
+            kubernetes_namespaces: Vec::new(),
+            kubernetes_allow_mutations: false, // Read-only unless explicitly allowed
+            execute_code_enabled: false,       // Opt-in: exposes execute_code to the model
             docker_code_execution_enabled: false, // Opt-in: requires Docker
             docker_host: None,
+            docker_tools_enabled: false, // Opt-in: requires Docker
+            docker_cleanup: DockerCleanup::Keep,
             timeout_seconds: 30,
             max_output_bytes: 51200, // 50KB
             network_isolation: false,
@@ -105,6 +233,56 @@ impl Default for ExecutionSettingsModel {
             embedding_enabled: false, // Opt-in: requires embedding provider
             embedding_provider: None,
             embedding_model: None,
+            embedding_model_id: None,
+            skills_embedding_model_id: None,
+            hybrid_keyword_weight: default_hybrid_keyword_weight(),
+            planner_mode: false,    // Opt-in: adds a plan checklist to every task
+            self_reflection: false, // Opt-in: adds a critique request to every answer
+            reflection_threshold: default_reflection_threshold(),
+            guardrail_rules: default_guardrail_rules(),
+            custom_http_tools: Vec::new(),
+            graphql_endpoints: Vec::new(),
+            ssh_enabled: false, // Opt-in: requires ssh and host profiles
+            ssh_hosts: Vec::new(),
+            notes_vault_dir: None,
+            notes_enabled: false,     // Opt-in: requires a vault directory
+            citations_enabled: false, // Opt-in: requires a BibTeX file or Zotero
+            bibtex_path: None,
+            zotero_api_url: None,
+            citation_style: CitationStyle::Apa,
+            mcp_idle_shutdown_minutes: default_mcp_idle_shutdown_minutes(),
+        }
+    }
+}
+
+/// Tools imported from one OpenAPI spec, as listed in the tools panel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomToolSource {
+    pub name: String,
+    pub tool_count: usize,
+    pub enabled_count: usize,
+}
+
+impl ExecutionSettingsModel {
+    /// OpenAPI specs the custom HTTP tools were imported from, in first-seen order.
+    pub fn custom_tool_sources(&self) -> Vec<CustomToolSource> {
+        let mut sources: Vec<CustomToolSource> = Vec::new();
+        for tool in &self.custom_http_tools {
+            let Some(name) = &tool.source else { continue };
+            let index = match sources.iter().position(|s| &s.name == name) {
+                Some(index) => index,
+                None => {
+                    sources.push(CustomToolSource {
+                        name: name.clone(),
+                        tool_count: 0,
+                        enabled_count: 0,
+                    });
+                    sources.len() - 1
+                }
+            };
+            sources[index].tool_count += 1;
+            sources[index].enabled_count += usize::from(tool.enabled);
         }
+        sources
     }
 }
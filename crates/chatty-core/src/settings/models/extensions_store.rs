@@ -20,6 +20,9 @@ pub enum ExtensionSource {
     /// Manually configured by the user.
     #[serde(rename = "custom")]
     Custom,
+    /// Installed from the MCP server catalog.
+    #[serde(rename = "mcp_catalog")]
+    McpCatalog { entry_id: String, version: String },
 }
 
 // ── Extension kind ─────────────────────────────────────────────────────────
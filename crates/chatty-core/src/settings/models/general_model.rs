@@ -1,10 +1,123 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::conversation_groups::ConversationSort;
+use crate::models::text_direction::LayoutDirection;
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct GeneralSettingsModel {
     pub font_size: f32,
     pub theme_name: Option<String>,
     pub dark_mode: Option<bool>,
+    /// Shell command that streams the microphone as raw 16 kHz mono 16-bit
+    /// PCM on stdout, for speech mode (None = speech mode unavailable)
+    #[serde(default)]
+    pub speech_record_command: Option<String>,
+    /// Shell command that prints the transcript of a WAV file; `{audio}` is
+    /// replaced with its path (None = speech mode unavailable)
+    #[serde(default)]
+    pub speech_transcribe_command: Option<String>,
+    /// Shell command that reads text from stdin aloud, for speech mode
+    /// (None = replies are not spoken)
+    #[serde(default)]
+    pub speech_tts_command: Option<String>,
+    /// Pick a model per message based on prompt complexity
+    #[serde(default)]
+    pub model_routing_enabled: bool,
+    /// Local model that classifies messages for routing (None = heuristic)
+    #[serde(default)]
+    pub router_model_id: Option<String>,
+    /// Reopen the window, sidebar and conversation from `last_session` on startup
+    #[serde(default = "default_restore_session")]
+    pub restore_session: bool,
+    /// What was on screen when the app last quit
+    #[serde(default)]
+    pub last_session: SessionState,
+    /// Include thinking summaries and tool calls in "Copy as context"
+    #[serde(default)]
+    pub copy_context_include_traces: bool,
+    /// Suggest moving to a new conversation when a prompt changes the topic
+    #[serde(default)]
+    pub topic_split_suggestions: bool,
+    /// UI language code, e.g. "de" (None = follow the system locale)
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Mirror the layout for right-to-left languages
+    #[serde(default)]
+    pub layout_direction: LayoutDirection,
+    /// Underline misspelled words in the chat input
+    #[serde(default = "default_spell_check_enabled")]
+    pub spell_check_enabled: bool,
+    /// Hunspell dictionary code, e.g. "en_GB" (None = match the UI language)
+    #[serde(default)]
+    pub spell_check_dictionary: Option<String>,
+    /// Warn in the chat input when a message alone is estimated above this
+    /// many tokens (0 = never warn)
+    #[serde(default = "default_draft_token_warning")]
+    pub draft_token_warning: usize,
+    /// Key that sends a message; the other Enter variants insert a newline
+    #[serde(default)]
+    pub send_shortcut: SendShortcut,
+    /// Show the chat input as a tall multi-line composer
+    #[serde(default)]
+    pub composer_expanded: bool,
+    /// Show a rendered Markdown preview of the draft in the expanded composer
+    #[serde(default)]
+    pub composer_preview: bool,
+    /// Shell command that transcribes a video's audio track; `{audio}` is
+    /// replaced with the path of an extracted WAV file (None = no transcript)
+    #[serde(default)]
+    pub video_transcribe_command: Option<String>,
+    /// What the copy button on assistant messages puts on the clipboard
+    #[serde(default)]
+    pub default_copy_format: CopyFormat,
+    /// Print thinking summaries and tool calls along with the messages
+    #[serde(default)]
+    pub print_include_traces: bool,
+    /// Print image, PDF and video attachments as thumbnails (false = names only)
+    #[serde(default = "default_print_attachment_thumbnails")]
+    pub print_attachment_thumbnails: bool,
+    /// Repeat the conversation title and date at the top of every printed page
+    #[serde(default = "default_print_page_header")]
+    pub print_page_header: bool,
+    /// Order of the status footer indicators and which ones are hidden
+    #[serde(default)]
+    pub footer_layout: FooterLayout,
+    /// The first-run provider setup was finished or skipped
+    #[serde(default)]
+    pub onboarding_completed: bool,
+    /// Order of the conversation list in the sidebar
+    #[serde(default)]
+    pub conversation_sort: ConversationSort,
+    /// Reveal streamed responses at a steady pace instead of chunk by chunk
+    #[serde(default)]
+    pub smooth_streaming: bool,
+    /// Characters per second revealed when `smooth_streaming` is on
+    #[serde(default = "default_smooth_streaming_rate")]
+    pub smooth_streaming_rate: u32,
+}
+
+fn default_restore_session() -> bool {
+    true
+}
+
+fn default_spell_check_enabled() -> bool {
+    true
+}
+
+fn default_draft_token_warning() -> usize {
+    8_000
+}
+
+fn default_smooth_streaming_rate() -> u32 {
+    400
+}
+
+fn default_print_attachment_thumbnails() -> bool {
+    true
+}
+
+fn default_print_page_header() -> bool {
+    true
 }
 
 impl Default for GeneralSettingsModel {
@@ -13,6 +126,295 @@ impl Default for GeneralSettingsModel {
             font_size: 14.0,
             theme_name: None,
             dark_mode: None,
+            speech_record_command: None,
+            speech_transcribe_command: None,
+            speech_tts_command: None,
+            model_routing_enabled: false,
+            router_model_id: None,
+            restore_session: default_restore_session(),
+            last_session: SessionState::default(),
+            copy_context_include_traces: false,
+            topic_split_suggestions: false,
+            language: None,
+            layout_direction: LayoutDirection::default(),
+            spell_check_enabled: default_spell_check_enabled(),
+            spell_check_dictionary: None,
+            draft_token_warning: default_draft_token_warning(),
+            send_shortcut: SendShortcut::default(),
+            composer_expanded: false,
+            composer_preview: false,
+            video_transcribe_command: None,
+            default_copy_format: CopyFormat::default(),
+            print_include_traces: false,
+            print_attachment_thumbnails: default_print_attachment_thumbnails(),
+            print_page_header: default_print_page_header(),
+            footer_layout: FooterLayout::default(),
+            onboarding_completed: false,
+            conversation_sort: ConversationSort::default(),
+            smooth_streaming: false,
+            smooth_streaming_rate: default_smooth_streaming_rate(),
+        }
+    }
+}
+
+/// Which key sends a message from the chat input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SendShortcut {
+    /// Enter sends, Shift+Enter inserts a newline
+    #[default]
+    Enter,
+    /// Ctrl+Enter (Cmd+Enter on macOS) sends, Enter inserts a newline
+    CtrlEnter,
+}
+
+impl SendShortcut {
+    pub const ALL: [SendShortcut; 2] = [Self::Enter, Self::CtrlEnter];
+}
+
+/// How an assistant message is put on the clipboard.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyFormat {
+    /// The Markdown source as the model wrote it
+    #[default]
+    Markdown,
+    /// Rendered text without Markdown syntax
+    PlainText,
+    /// Rendered HTML
+    Html,
+    /// Markdown without thinking blocks, citation markers and footnotes
+    Clean,
+}
+
+impl CopyFormat {
+    pub const ALL: [CopyFormat; 4] = [Self::Markdown, Self::PlainText, Self::Html, Self::Clean];
+}
+
+/// An indicator in the status footer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FooterIndicator {
+    /// Error and warning count, opens the error log
+    Errors,
+    /// Background jobs
+    Jobs,
+    /// Prompt pack updates
+    PackUpdates,
+    /// Auto-updater status
+    AutoUpdate,
+    /// Context window fill and conversation cost
+    Tokens,
+    /// Models loaded by a local Ollama server and their GPU/RAM use
+    LocalModels,
+    /// Fetch tool online/offline toggle
+    Fetch,
+    /// Sandbox network isolation toggle
+    Network,
+    /// Built-in tool toggles
+    Tools,
+    /// MCP servers
+    Mcp,
+    /// Sub-agents
+    Agent,
+}
+
+impl FooterIndicator {
+    /// Every indicator in its default order.
+    pub const ALL: [FooterIndicator; 11] = [
+        Self::Errors,
+        Self::Jobs,
+        Self::PackUpdates,
+        Self::AutoUpdate,
+        Self::Tokens,
+        Self::LocalModels,
+        Self::Fetch,
+        Self::Network,
+        Self::Tools,
+        Self::Mcp,
+        Self::Agent,
+    ];
+
+    /// App-wide status sits on the left of the footer, conversation and tool
+    /// state on the right.
+    pub fn is_left(self) -> bool {
+        matches!(
+            self,
+            Self::Errors | Self::Jobs | Self::PackUpdates | Self::AutoUpdate
+        )
+    }
+
+    /// Kept in the footer of a narrow window; the others move to the
+    /// overflow menu.
+    pub fn is_essential(self) -> bool {
+        matches!(self, Self::Errors | Self::Tokens)
+    }
+}
+
+/// Which status footer indicators are shown, and in what order.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FooterLayout {
+    /// Chosen order; indicators missing from it follow in their default order
+    #[serde(default)]
+    pub order: Vec<FooterIndicator>,
+    /// Indicators moved to the overflow menu
+    #[serde(default)]
+    pub hidden: Vec<FooterIndicator>,
+}
+
+impl FooterLayout {
+    /// Every indicator, in display order.
+    pub fn ordered(&self) -> Vec<FooterIndicator> {
+        let mut ordered: Vec<FooterIndicator> = Vec::with_capacity(FooterIndicator::ALL.len());
+        for indicator in self.order.iter().chain(FooterIndicator::ALL.iter()) {
+            if !ordered.contains(indicator) {
+                ordered.push(*indicator);
+            }
+        }
+        ordered
+    }
+
+    /// Indicators shown in the footer, in display order.
+    pub fn visible(&self) -> Vec<FooterIndicator> {
+        self.ordered()
+            .into_iter()
+            .filter(|indicator| !self.is_hidden(*indicator))
+            .collect()
+    }
+
+    /// Indicators in the overflow menu, in display order.
+    pub fn overflow(&self) -> Vec<FooterIndicator> {
+        self.ordered()
+            .into_iter()
+            .filter(|indicator| self.is_hidden(*indicator))
+            .collect()
+    }
+
+    /// Indicators shown and in the overflow menu in a narrow window: only
+    /// the essential ones stay in the footer, if they are not hidden.
+    pub fn compact(&self) -> (Vec<FooterIndicator>, Vec<FooterIndicator>) {
+        self.ordered()
+            .into_iter()
+            .partition(|indicator| indicator.is_essential() && !self.is_hidden(*indicator))
+    }
+
+    pub fn is_hidden(&self, indicator: FooterIndicator) -> bool {
+        self.hidden.contains(&indicator)
+    }
+
+    pub fn set_hidden(&mut self, indicator: FooterIndicator, hidden: bool) {
+        self.hidden.retain(|i| *i != indicator);
+        if hidden {
+            self.hidden.push(indicator);
         }
     }
+
+    /// Swap `indicator` with its neighbour `offset` places away (-1 = earlier).
+    /// Does nothing at either end of the list.
+    pub fn move_by(&mut self, indicator: FooterIndicator, offset: isize) {
+        let mut ordered = self.ordered();
+        let Some(from) = ordered.iter().position(|i| *i == indicator) else {
+            return;
+        };
+        let Some(to) = from
+            .checked_add_signed(offset)
+            .filter(|to| *to < ordered.len())
+        else {
+            return;
+        };
+        ordered.swap(from, to);
+        self.order = ordered;
+    }
+}
+
+/// Main window placement in logical pixels.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+/// Window layout and position in the chat, captured on quit.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionState {
+    #[serde(default)]
+    pub window: Option<WindowGeometry>,
+    #[serde(default)]
+    pub sidebar_collapsed: bool,
+    #[serde(default)]
+    pub active_conversation_id: Option<String>,
+    /// Vertical offset of the message list (0 at the top, negative further down)
+    #[serde(default)]
+    pub scroll_offset: Option<f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn footer_layout_fills_in_missing_indicators() {
+        let layout = FooterLayout {
+            order: vec![
+                FooterIndicator::Mcp,
+                FooterIndicator::Errors,
+                FooterIndicator::Mcp,
+            ],
+            hidden: vec![FooterIndicator::Jobs],
+        };
+
+        let ordered = layout.ordered();
+        assert_eq!(ordered.len(), FooterIndicator::ALL.len());
+        assert_eq!(
+            &ordered[..3],
+            &[
+                FooterIndicator::Mcp,
+                FooterIndicator::Errors,
+                FooterIndicator::Jobs
+            ]
+        );
+        assert!(!layout.visible().contains(&FooterIndicator::Jobs));
+        assert_eq!(layout.overflow(), vec![FooterIndicator::Jobs]);
+    }
+
+    #[test]
+    fn footer_layout_moves_and_hides() {
+        let mut layout = FooterLayout::default();
+        layout.move_by(FooterIndicator::Errors, -1);
+        assert_eq!(layout.ordered(), FooterIndicator::ALL.to_vec());
+
+        layout.move_by(FooterIndicator::Jobs, -1);
+        assert_eq!(
+            &layout.ordered()[..2],
+            &[FooterIndicator::Jobs, FooterIndicator::Errors]
+        );
+
+        layout.set_hidden(FooterIndicator::Tokens, true);
+        layout.set_hidden(FooterIndicator::Tokens, true);
+        assert_eq!(layout.hidden, vec![FooterIndicator::Tokens]);
+        layout.set_hidden(FooterIndicator::Tokens, false);
+        assert!(layout.hidden.is_empty());
+    }
+
+    #[test]
+    fn compact_footer_keeps_essential_indicators() {
+        let mut layout = FooterLayout::default();
+        layout.move_by(FooterIndicator::Tokens, -1);
+        let (visible, overflow) = layout.compact();
+        assert_eq!(
+            visible,
+            vec![FooterIndicator::Errors, FooterIndicator::Tokens]
+        );
+        assert_eq!(overflow.len(), FooterIndicator::ALL.len() - 2);
+        assert!(overflow.contains(&FooterIndicator::Mcp));
+
+        layout.set_hidden(FooterIndicator::Errors, true);
+        let (visible, overflow) = layout.compact();
+        assert_eq!(visible, vec![FooterIndicator::Tokens]);
+        assert!(overflow.contains(&FooterIndicator::Errors));
+    }
 }
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Keyboard actions on the selected message in the chat view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MessageAction {
+    NextMessage,
+    PreviousMessage,
+    ExtendSelectionDown,
+    ExtendSelectionUp,
+    CopyMessage,
+    CopyAsContext,
+    EditMessage,
+    RegenerateMessage,
+    BookmarkMessage,
+    DeleteMessage,
+    DeleteTrace,
+    ToggleShortcuts,
+}
+
+impl MessageAction {
+    pub const ALL: [MessageAction; 12] = [
+        Self::NextMessage,
+        Self::PreviousMessage,
+        Self::ExtendSelectionDown,
+        Self::ExtendSelectionUp,
+        Self::CopyMessage,
+        Self::CopyAsContext,
+        Self::EditMessage,
+        Self::RegenerateMessage,
+        Self::BookmarkMessage,
+        Self::DeleteMessage,
+        Self::DeleteTrace,
+        Self::ToggleShortcuts,
+    ];
+
+    /// Key used for the action in `keybindings.json`.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::NextMessage => "next_message",
+            Self::PreviousMessage => "previous_message",
+            Self::ExtendSelectionDown => "extend_selection_down",
+            Self::ExtendSelectionUp => "extend_selection_up",
+            Self::CopyMessage => "copy_message",
+            Self::CopyAsContext => "copy_as_context",
+            Self::EditMessage => "edit_message",
+            Self::RegenerateMessage => "regenerate_message",
+            Self::BookmarkMessage => "bookmark_message",
+            Self::DeleteMessage => "delete_message",
+            Self::DeleteTrace => "delete_trace",
+            Self::ToggleShortcuts => "toggle_shortcuts",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::NextMessage => "Next message",
+            Self::PreviousMessage => "Previous message",
+            Self::ExtendSelectionDown => "Extend selection down",
+            Self::ExtendSelectionUp => "Extend selection up",
+            Self::CopyMessage => "Copy message",
+            Self::CopyAsContext => "Copy selection as context",
+            Self::EditMessage => "Edit message in the input",
+            Self::RegenerateMessage => "Regenerate response",
+            Self::BookmarkMessage => "Bookmark message",
+            Self::DeleteMessage => "Delete message from history",
+            Self::DeleteTrace => "Delete tool trace from history",
+            Self::ToggleShortcuts => "Show or hide this cheat sheet",
+        }
+    }
+
+    pub fn default_keystroke(self) -> &'static str {
+        match self {
+            Self::NextMessage => "j",
+            Self::PreviousMessage => "k",
+            Self::ExtendSelectionDown => "shift-j",
+            Self::ExtendSelectionUp => "shift-k",
+            Self::CopyMessage => "y",
+            Self::CopyAsContext => "c",
+            Self::EditMessage => "e",
+            Self::RegenerateMessage => "r",
+            Self::BookmarkMessage => "b",
+            Self::DeleteMessage => "x",
+            Self::DeleteTrace => "shift-x",
+            Self::ToggleShortcuts => "?",
+        }
+    }
+}
+
+/// Key bindings for message navigation in the chat view.
+///
+/// Persisted to `~/.config/chatty/keybindings.json` and read at startup.
+/// Only overrides are stored, keyed by [`MessageAction::id`], e.g.
+/// `{"bindings": {"copy_message": "c"}}`. An empty keystroke unbinds the action.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct KeybindingsModel {
+    #[serde(default)]
+    pub bindings: BTreeMap<String, String>,
+}
+
+impl KeybindingsModel {
+    /// Keystroke bound to `action`, or `None` when it has been unbound.
+    pub fn keystroke(&self, action: MessageAction) -> Option<&str> {
+        let keystroke = self
+            .bindings
+            .get(action.id())
+            .map(|k| k.trim())
+            .unwrap_or(action.default_keystroke());
+        (!keystroke.is_empty()).then_some(keystroke)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_apply_without_overrides() {
+        let model = KeybindingsModel::default();
+        assert_eq!(model.keystroke(MessageAction::NextMessage), Some("j"));
+        assert_eq!(model.keystroke(MessageAction::ToggleShortcuts), Some("?"));
+    }
+
+    #[test]
+    fn overrides_replace_or_unbind() {
+        let model: KeybindingsModel = serde_json::from_str(
+            r#"{"bindings": {"copy_message": "c", "bookmark_message": "", "unknown": "x"}}"#,
+        )
+        .unwrap();
+        assert_eq!(model.keystroke(MessageAction::CopyMessage), Some("c"));
+        assert_eq!(model.keystroke(MessageAction::BookmarkMessage), None);
+        assert_eq!(model.keystroke(MessageAction::EditMessage), Some("e"));
+    }
+}
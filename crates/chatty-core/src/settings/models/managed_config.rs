@@ -0,0 +1,439 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::guardrail_service::{GuardrailRule, GuardrailRuleKind};
+use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use crate::settings::models::mcp_store::McpServerConfig;
+use crate::settings::models::models_store::ModelConfig;
+use crate::settings::models::providers_store::ProviderConfig;
+
+/// Environment variable holding the admin-provided managed config URL.
+pub const MANAGED_CONFIG_URL_ENV: &str = "CHATTY_MANAGED_CONFIG_URL";
+/// Environment variable holding the hex-encoded Ed25519 key that signs it.
+pub const MANAGED_CONFIG_KEY_ENV: &str = "CHATTY_MANAGED_CONFIG_PUBLIC_KEY";
+/// Environment variable naming the user's role in the managed config.
+pub const MANAGED_ROLE_ENV: &str = "CHATTY_MANAGED_ROLE";
+
+/// Where the managed ("team") configuration comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManagedConfigSource {
+    /// HTTPS URL serving the config JSON.
+    pub url: String,
+    /// Hex-encoded Ed25519 verifying key of the administrator.
+    pub public_key: String,
+}
+
+impl ManagedConfigSource {
+    /// Read the source from the environment. Returns `None` when no URL is
+    /// configured, and an error when the URL or key is unusable.
+    pub fn from_env() -> Option<Result<Self, String>> {
+        let url = std::env::var(MANAGED_CONFIG_URL_ENV)
+            .ok()
+            .filter(|v| !v.trim().is_empty())?;
+        let public_key = std::env::var(MANAGED_CONFIG_KEY_ENV).unwrap_or_default();
+        Some(Self::new(url.trim(), public_key.trim()))
+    }
+
+    pub fn new(url: &str, public_key: &str) -> Result<Self, String> {
+        if !url.starts_with("https://") {
+            return Err(format!("Managed config URL must use HTTPS: {url}"));
+        }
+        if public_key.is_empty() {
+            return Err(format!(
+                "{MANAGED_CONFIG_KEY_ENV} must be set to verify the managed config"
+            ));
+        }
+        Ok(Self {
+            url: url.to_string(),
+            public_key: public_key.to_string(),
+        })
+    }
+}
+
+/// Native tool groups an administrator can block for a role. The names match
+/// chatty-tui's `--disable` groups.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ToolGroup {
+    Shell,
+    FsRead,
+    FsWrite,
+    Fetch,
+    Git,
+    K8s,
+    Docker,
+    Ssh,
+    Notes,
+    Citations,
+    CodeExec,
+    DockerExec,
+}
+
+impl ToolGroup {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Shell => "Shell",
+            Self::FsRead => "Filesystem Read",
+            Self::FsWrite => "Filesystem Write",
+            Self::Fetch => "Fetch",
+            Self::Git => "Git",
+            Self::K8s => "Kubernetes",
+            Self::Docker => "Docker",
+            Self::Ssh => "SSH",
+            Self::Notes => "Notes",
+            Self::Citations => "Citations",
+            Self::CodeExec => "Code Execution",
+            Self::DockerExec => "Docker Fallback",
+        }
+    }
+
+    /// Turn the group off in `settings`, which keeps its tools out of the
+    /// agent's tool registry.
+    pub fn disable(self, settings: &mut ExecutionSettingsModel) {
+        match self {
+            Self::Shell => settings.enabled = false,
+            Self::FsRead => settings.filesystem_read_enabled = false,
+            Self::FsWrite => settings.filesystem_write_enabled = false,
+            Self::Fetch => settings.fetch_enabled = false,
+            Self::Git => settings.git_enabled = false,
+            Self::K8s => settings.kubernetes_enabled = false,
+            Self::Docker => settings.docker_tools_enabled = false,
+            Self::Ssh => settings.ssh_enabled = false,
+            Self::Notes => settings.notes_enabled = false,
+            Self::Citations => settings.citations_enabled = false,
+            Self::CodeExec => settings.execute_code_enabled = false,
+            Self::DockerExec => settings.docker_code_execution_enabled = false,
+        }
+    }
+}
+
+/// Tool restrictions for everyone assigned a managed role.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ManagedRole {
+    #[serde(default)]
+    pub blocked_tools: Vec<ToolGroup>,
+    /// When non-empty, filesystem writes are only allowed inside these
+    /// directories (relative ones resolve against the workspace).
+    #[serde(default)]
+    pub allowed_write_dirs: Vec<String>,
+}
+
+/// Recommended providers, models, MCP servers and guardrail policies
+/// published by an administrator, plus per-role tool restrictions.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ManagedConfig {
+    #[serde(default)]
+    pub providers: Vec<ProviderConfig>,
+    #[serde(default)]
+    pub models: Vec<ModelConfig>,
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServerConfig>,
+    #[serde(default)]
+    pub guardrail_rules: Vec<GuardrailRule>,
+    #[serde(default)]
+    pub roles: BTreeMap<String, ManagedRole>,
+    /// Role for users whose `CHATTY_MANAGED_ROLE` is unset or unknown.
+    #[serde(default)]
+    pub default_role: Option<String>,
+}
+
+impl ManagedConfig {
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+            && self.models.is_empty()
+            && self.mcp_servers.is_empty()
+            && self.guardrail_rules.is_empty()
+            && self.roles.is_empty()
+    }
+
+    /// Merge managed providers over local ones (matched by provider type).
+    /// A managed provider without an API key keeps the user's key. Returns the
+    /// merged list and the provider types whose local entry was overridden.
+    pub fn apply_to_providers(
+        &self,
+        local: Vec<ProviderConfig>,
+    ) -> (Vec<ProviderConfig>, Vec<String>) {
+        let managed: Vec<ProviderConfig> = self
+            .providers
+            .iter()
+            .map(|m| {
+                let mut m = m.clone();
+                if m.api_key.is_none() {
+                    m.api_key = local
+                        .iter()
+                        .find(|l| l.provider_type == m.provider_type)
+                        .and_then(|l| l.api_key.clone());
+                }
+                m
+            })
+            .collect();
+        merge_by_key(local, &managed, |p| {
+            p.provider_type.display_name().to_string()
+        })
+    }
+
+    /// Merge managed models over local ones (matched by id).
+    pub fn apply_to_models(&self, local: Vec<ModelConfig>) -> (Vec<ModelConfig>, Vec<String>) {
+        merge_by_key(local, &self.models, |m| m.id.clone())
+    }
+
+    /// Merge managed MCP servers over local ones (matched by name).
+    pub fn apply_to_mcp_servers(
+        &self,
+        local: Vec<McpServerConfig>,
+    ) -> (Vec<McpServerConfig>, Vec<String>) {
+        merge_by_key(local, &self.mcp_servers, |s| s.name.clone())
+    }
+
+    /// Managed guardrail rules are always enforced, whatever their `enabled` flag.
+    pub fn enforced_guardrail_rules(&self) -> Vec<GuardrailRule> {
+        self.guardrail_rules
+            .iter()
+            .cloned()
+            .map(|mut rule| {
+                rule.enabled = true;
+                rule
+            })
+            .collect()
+    }
+}
+
+/// Replace local entries that share a key with a managed entry and append the
+/// rest. Local entries identical to their managed counterpart (e.g. a managed
+/// entry persisted by an earlier save) are not reported as overridden.
+fn merge_by_key<T: Clone + Serialize>(
+    local: Vec<T>,
+    managed: &[T],
+    key: impl Fn(&T) -> String,
+) -> (Vec<T>, Vec<String>) {
+    let mut overridden = Vec::new();
+    let mut merged: Vec<T> = local
+        .into_iter()
+        .filter(|l| match managed.iter().find(|m| key(m) == key(l)) {
+            Some(m) => {
+                if serde_json::to_value(m).ok() != serde_json::to_value(l).ok() {
+                    overridden.push(key(l));
+                }
+                false
+            }
+            None => true,
+        })
+        .collect();
+    merged.extend(managed.iter().cloned());
+    (merged, overridden)
+}
+
+/// Last verified managed config, persisted to
+/// `~/.config/chatty/managed_config_cache.json` so the managed layer still
+/// applies when the URL is unreachable. The signature is re-checked on load.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ManagedConfigCache {
+    #[serde(default)]
+    pub url: String,
+    /// Raw response body exactly as signed.
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub signature: String,
+    /// Unix timestamp (seconds) of the successful fetch.
+    #[serde(default)]
+    pub fetched_at: i64,
+}
+
+/// Whether the managed layer is active and where it came from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum ManagedConfigStatus {
+    /// No managed config URL is configured.
+    #[default]
+    Disabled,
+    /// Fetched and verified at `fetched_at`.
+    Verified { fetched_at: i64 },
+    /// The fetch failed; the last verified copy from `fetched_at` is in use.
+    Cached { fetched_at: i64, error: String },
+    /// The fetch failed and no verified copy exists; nothing is applied.
+    Failed(String),
+}
+
+/// Global, read-only view of the managed layer for the settings UI.
+#[derive(Clone, Debug, Default)]
+pub struct ManagedConfigModel {
+    pub source_url: Option<String>,
+    pub status: ManagedConfigStatus,
+    pub config: ManagedConfig,
+    /// Role requested through `CHATTY_MANAGED_ROLE`.
+    pub requested_role: Option<String>,
+    /// Local entries replaced by managed ones, as `"<kind>: <key>"`.
+    pub overridden: Vec<String>,
+}
+
+impl ManagedConfigModel {
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.status,
+            ManagedConfigStatus::Verified { .. } | ManagedConfigStatus::Cached { .. }
+        )
+    }
+
+    /// The role whose restrictions apply: the requested one if the config
+    /// defines it, otherwise the config's default role.
+    pub fn active_role(&self) -> Option<(&str, &ManagedRole)> {
+        if !self.is_active() {
+            return None;
+        }
+        let roles = &self.config.roles;
+        self.requested_role
+            .as_deref()
+            .and_then(|name| roles.get_key_value(name))
+            .or_else(|| {
+                self.config
+                    .default_role
+                    .as_deref()
+                    .and_then(|name| roles.get_key_value(name))
+            })
+            .map(|(name, role)| (name.as_str(), role))
+    }
+
+    /// Why `group` is unavailable, if the active role blocks it.
+    pub fn blocked_reason(&self, group: ToolGroup) -> Option<String> {
+        self.active_role()
+            .filter(|(_, role)| role.blocked_tools.contains(&group))
+            .map(|(name, _)| format!("Blocked by your administrator for the \"{name}\" role"))
+    }
+
+    /// Turn off every tool group the active role blocks.
+    pub fn restrict_tools(&self, settings: &mut ExecutionSettingsModel) {
+        if let Some((_, role)) = self.active_role() {
+            for group in &role.blocked_tools {
+                group.disable(settings);
+            }
+        }
+    }
+
+    /// Managed guardrail rules plus the active role's write restriction.
+    pub fn enforced_guardrail_rules(&self) -> Vec<GuardrailRule> {
+        if !self.is_active() {
+            return Vec::new();
+        }
+        let mut rules = self.config.enforced_guardrail_rules();
+        if let Some((name, role)) = self.active_role()
+            && !role.allowed_write_dirs.is_empty()
+        {
+            rules.push(GuardrailRule {
+                id: format!("managed-role-{name}-writes"),
+                name: format!("Role \"{name}\": writes only in allowed directories"),
+                enabled: true,
+                kind: GuardrailRuleKind::RestrictWrites {
+                    allowed_dirs: role.allowed_write_dirs.clone(),
+                },
+            });
+        }
+        rules
+    }
+
+    /// Record local entries of `kind` that the managed layer overrode.
+    pub fn record_overridden(&mut self, kind: &str, keys: Vec<String>) {
+        self.overridden
+            .extend(keys.into_iter().map(|key| format!("{kind}: {key}")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::models::providers_store::ProviderType;
+
+    #[test]
+    fn managed_providers_override_by_type_and_keep_user_key() {
+        let local = vec![
+            ProviderConfig::new("OpenRouter".into(), ProviderType::OpenRouter)
+                .with_api_key("sk-user".into()),
+            ProviderConfig::new("Ollama".into(), ProviderType::Ollama),
+        ];
+        let mut team_router =
+            ProviderConfig::new("Team OpenRouter".into(), ProviderType::OpenRouter);
+        team_router.base_url = Some("https://llm.example.com/v1".into());
+        let managed = ManagedConfig {
+            providers: vec![team_router],
+            ..Default::default()
+        };
+
+        let (merged, overridden) = managed.apply_to_providers(local);
+        assert_eq!(merged.len(), 2);
+        let router = merged
+            .iter()
+            .find(|p| p.provider_type == ProviderType::OpenRouter)
+            .unwrap();
+        assert_eq!(router.name, "Team OpenRouter");
+        assert_eq!(router.api_key.as_deref(), Some("sk-user"));
+        assert_eq!(overridden, ["OpenRouter"]);
+
+        // Merging again over the already-merged list reports nothing new.
+        let (_, overridden) = managed.apply_to_providers(merged);
+        assert!(overridden.is_empty());
+    }
+
+    #[test]
+    fn managed_rules_are_always_enabled() {
+        let mut rule = GuardrailRule::new(
+            "No fetch",
+            GuardrailRuleKind::BlockTool {
+                tool_name: "fetch".into(),
+            },
+        );
+        rule.enabled = false;
+        let managed = ManagedConfig {
+            guardrail_rules: vec![rule],
+            ..Default::default()
+        };
+        assert!(managed.enforced_guardrail_rules()[0].enabled);
+    }
+
+    #[test]
+    fn source_requires_https_and_key() {
+        assert!(ManagedConfigSource::new("http://config.example.com", "ab").is_err());
+        assert!(ManagedConfigSource::new("https://config.example.com", "").is_err());
+        assert!(ManagedConfigSource::new("https://config.example.com", "ab").is_ok());
+    }
+
+    #[test]
+    fn role_blocks_tools_and_limits_writes() {
+        let config: ManagedConfig = serde_json::from_str(
+            r#"{
+                "roles": {
+                    "analyst": {"blocked_tools": ["shell", "code-exec"], "allowed_write_dirs": ["reports"]},
+                    "engineer": {}
+                },
+                "default_role": "analyst"
+            }"#,
+        )
+        .unwrap();
+        let mut model = ManagedConfigModel {
+            status: ManagedConfigStatus::Verified { fetched_at: 0 },
+            config,
+            requested_role: Some("unknown".into()),
+            ..Default::default()
+        };
+
+        // Unknown roles fall back to the default role.
+        assert_eq!(model.active_role().unwrap().0, "analyst");
+        let mut settings = ExecutionSettingsModel {
+            enabled: true,
+            execute_code_enabled: true,
+            git_enabled: true,
+            ..Default::default()
+        };
+        model.restrict_tools(&mut settings);
+        assert!(!settings.enabled && !settings.execute_code_enabled && settings.git_enabled);
+        assert!(model.blocked_reason(ToolGroup::Shell).is_some());
+        assert!(model.blocked_reason(ToolGroup::Git).is_none());
+        assert!(matches!(
+            &model.enforced_guardrail_rules()[0].kind,
+            GuardrailRuleKind::RestrictWrites { allowed_dirs } if allowed_dirs == &["reports"]
+        ));
+
+        model.requested_role = Some("engineer".into());
+        assert!(model.blocked_reason(ToolGroup::Shell).is_none());
+        assert!(model.enforced_guardrail_rules().is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Shared write lock for all MCP tool operations (add, delete, edit).
 ///
@@ -32,10 +32,10 @@ pub enum McpAuthStatus {
 
 /// Configuration for a single MCP server.
 ///
-/// The app connects to servers that are already running — either locally or
-/// remotely. It is the user's responsibility to start the server before adding
-/// it here. The `url` field must point to the server's MCP endpoint (e.g.
-/// `http://localhost:3000/mcp` for a streamable-HTTP server).
+/// Usually the app connects to a server that is already running — either
+/// locally or remotely — and `url` points to its MCP endpoint (e.g.
+/// `http://localhost:3000/mcp` for a streamable-HTTP server). Servers with a
+/// [`McpLaunchConfig`] are started by the app instead and spoken to over stdio.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct McpServerConfig {
     /// Unique name identifier for the MCP server
@@ -60,12 +60,41 @@ pub struct McpServerConfig {
     /// the corresponding module disappears.
     #[serde(default, skip_serializing_if = "is_false")]
     pub is_module: bool,
+
+    /// Seconds an identical tool call (same tool and arguments) is answered
+    /// from the previous result instead of the server. 0 disables caching.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub result_cache_ttl_secs: u64,
+
+    /// Tools whose results may be cached although the server does not
+    /// annotate them as read-only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cached_tools: Vec<String>,
+
+    /// Command that starts the server; when set, `url` is unused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launch: Option<McpLaunchConfig>,
+}
+
+/// How to start a stdio MCP server. Arguments and environment values may
+/// reference user secrets as `{{secret:KEY}}`; they are filled in at launch.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct McpLaunchConfig {
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
 }
 
 fn is_false(v: &bool) -> bool {
     !v
 }
 
+fn is_zero(v: &u64) -> bool {
+    *v == 0
+}
+
 fn default_enabled() -> bool {
     true
 }
@@ -75,6 +104,28 @@ impl McpServerConfig {
     pub fn has_api_key(&self) -> bool {
         self.api_key.as_deref().is_some_and(|k| !k.is_empty())
     }
+
+    /// Whether the server runs on this machine: launched by the app or
+    /// listening on a loopback address.
+    pub fn is_local(&self) -> bool {
+        self.launch.is_some() || self.url.contains("localhost") || self.url.contains("127.0.0.1")
+    }
+}
+
+/// A server's tool list as of its last connection.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct McpCachedTools {
+    /// URL the list was fetched from; the entry is ignored if the server moves.
+    pub url: String,
+    pub tools: Vec<rmcp::model::Tool>,
+}
+
+/// Tool lists persisted across launches so agents can be built before a
+/// server is started, keyed by server name.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct McpToolCache {
+    #[serde(default)]
+    pub servers: HashMap<String, McpCachedTools>,
 }
 
 /// Global store for MCP server configurations
@@ -148,6 +199,9 @@ mod tests {
             api_key: None,
             enabled: true,
             is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
         };
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("\"name\":\"test-server\""));
@@ -165,6 +219,9 @@ mod tests {
             api_key: Some("sk-secret-token".to_string()),
             enabled: true,
             is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
         };
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("\"api_key\":\"sk-secret-token\""));
@@ -202,6 +259,9 @@ mod tests {
             api_key: Some("token".to_string()),
             enabled: true,
             is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
         };
         assert!(with_key.has_api_key());
 
@@ -211,6 +271,9 @@ mod tests {
             api_key: None,
             enabled: true,
             is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
         };
         assert!(!without_key.has_api_key());
 
@@ -220,6 +283,9 @@ mod tests {
             api_key: Some("".to_string()),
             enabled: true,
             is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
         };
         assert!(!empty_key.has_api_key());
     }
@@ -234,6 +300,9 @@ mod tests {
                 api_key: None,
                 enabled: true,
                 is_module: false,
+                result_cache_ttl_secs: 0,
+                cached_tools: Vec::new(),
+                launch: None,
             },
             McpServerConfig {
                 name: "b".to_string(),
@@ -241,6 +310,9 @@ mod tests {
                 api_key: None,
                 enabled: false,
                 is_module: false,
+                result_cache_ttl_secs: 0,
+                cached_tools: Vec::new(),
+                launch: None,
             },
             McpServerConfig {
                 name: "c".to_string(),
@@ -248,6 +320,9 @@ mod tests {
                 api_key: Some("token".to_string()),
                 enabled: true,
                 is_module: false,
+                result_cache_ttl_secs: 0,
+                cached_tools: Vec::new(),
+                launch: None,
             },
         ]);
         assert_eq!(model.enabled_count(), 2);
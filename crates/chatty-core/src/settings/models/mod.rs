@@ -1,27 +1,40 @@
 pub mod a2a_store;
+pub mod embedding_models_store;
 pub mod execution_settings;
 pub mod extensions_store;
 pub mod general_model;
 pub mod hive_settings;
+pub mod keybindings;
+pub mod managed_config;
 pub mod mcp_store;
 pub mod models_store;
 pub mod module_settings;
+pub mod prompt_packs_store;
 pub mod providers_store;
 pub mod search_settings;
+pub mod snippets_store;
 pub mod token_tracking_settings;
 pub mod training_settings;
 pub mod user_secrets_store;
 
 pub use a2a_store::A2aAgentsModel;
+pub use embedding_models_store::EmbeddingModelsModel;
 pub use execution_settings::ExecutionSettingsModel;
 pub use extensions_store::ExtensionsModel;
-pub use general_model::GeneralSettingsModel;
+pub use general_model::{
+    CopyFormat, FooterIndicator, FooterLayout, GeneralSettingsModel, SendShortcut, SessionState,
+    WindowGeometry,
+};
 pub use hive_settings::HiveSettingsModel;
+pub use keybindings::{KeybindingsModel, MessageAction};
+pub use managed_config::ManagedConfigModel;
 pub use mcp_store::McpServersModel;
 pub use models_store::ModelsModel;
 pub use module_settings::ModuleSettingsModel;
+pub use prompt_packs_store::PromptPacksModel;
 pub use providers_store::ProviderModel;
 pub use search_settings::SearchSettingsModel;
+pub use snippets_store::SnippetsModel;
 pub use token_tracking_settings::TokenTrackingSettings;
 pub use training_settings::TrainingSettingsModel;
 pub use user_secrets_store::UserSecretsModel;
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use super::providers_store::ProviderType;
 
@@ -41,6 +42,18 @@ pub struct ModelConfig {
     /// Max context window in tokens (used for the footer fill indicator)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_context_window: Option<i32>,
+    /// Most frames sent per video attachment (None = the default in `video_frames`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_video_frames: Option<u32>,
+    /// Short names such as "fast" or "cheap" that resolve to this model
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Contexts in which this model becomes the default for a new conversation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_rules: Vec<ModelRule>,
+    /// Training provenance when this is a fine-tuned model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lineage: Option<ModelLineage>,
 }
 
 fn default_temperature() -> f32 {
@@ -74,6 +87,245 @@ impl ModelConfig {
             supports_pdf: false,
             supports_temperature: true,
             max_context_window: None,
+            max_video_frames: None,
+            aliases: Vec::new(),
+            default_rules: Vec::new(),
+            lineage: None,
+        }
+    }
+
+    /// Whether `alias` is one of this model's aliases (case-insensitive).
+    pub fn has_alias(&self, alias: &str) -> bool {
+        self.aliases.iter().any(|a| a.eq_ignore_ascii_case(alias))
+    }
+}
+
+/// Where a fine-tuned model comes from: an OpenAI `ft:` model, an Azure
+/// fine-tuned deployment or an Ollama model built with an `ADAPTER`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelLineage {
+    /// Model the fine-tune started from, e.g. `gpt-4o-mini-2024-07-18`
+    pub base_model: String,
+    /// Hash of the training dataset, e.g. the sha256 of the JSONL file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataset_hash: Option<String>,
+    /// Day training finished, as `YYYY-MM-DD`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trained_on: Option<String>,
+}
+
+impl ModelLineage {
+    /// Build lineage from the model dialog fields.
+    ///
+    /// Returns `Ok(None)` when all fields are blank. An OpenAI
+    /// `ft:<base>:<org>:<suffix>:<id>` identifier fills in a blank base model.
+    pub fn from_fields(
+        model_identifier: &str,
+        base_model: &str,
+        dataset_hash: &str,
+        trained_on: &str,
+    ) -> Result<Option<Self>, String> {
+        let optional = |value: &str| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        let dataset_hash = optional(dataset_hash);
+        let trained_on = optional(trained_on);
+        let base_model =
+            optional(base_model).or_else(|| Self::base_from_identifier(model_identifier));
+
+        let Some(base_model) = base_model else {
+            if dataset_hash.is_some() || trained_on.is_some() {
+                return Err("Fine-tuned models need a base model".to_string());
+            }
+            return Ok(None);
+        };
+        if let Some(date) = &trained_on
+            && chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err()
+        {
+            return Err(format!(
+                "Invalid training date '{date}': expected YYYY-MM-DD"
+            ));
+        }
+        Ok(Some(Self {
+            base_model,
+            dataset_hash,
+            trained_on,
+        }))
+    }
+
+    /// Base model of an OpenAI fine-tuned model id (`ft:<base>:...`).
+    pub fn base_from_identifier(model_identifier: &str) -> Option<String> {
+        let base = model_identifier
+            .trim()
+            .strip_prefix("ft:")?
+            .split(':')
+            .next()?;
+        (!base.is_empty()).then(|| base.to_string())
+    }
+
+    /// One-line summary for the models list.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!("Fine-tuned from {}", self.base_model)];
+        if let Some(hash) = &self.dataset_hash {
+            parts.push(format!("dataset {}", hash.get(..12).unwrap_or(hash)));
+        }
+        if let Some(date) = &self.trained_on {
+            parts.push(format!("trained {date}"));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Kind of attachment a default-model rule can match on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    Image,
+    Pdf,
+}
+
+impl AttachmentKind {
+    /// Classify an attachment by its file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "png" | "jpg" | "jpeg" | "gif" | "webp" => Some(Self::Image),
+            "pdf" => Some(Self::Pdf),
+            _ => None,
+        }
+    }
+}
+
+/// Condition under which a model becomes the default for a conversation.
+///
+/// Written as `tag:<name>`, `project:<path>` or `attachment:image|pdf` in the
+/// model dialog.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum ModelRule {
+    /// The first message contains `#<name>`
+    Tag(String),
+    /// The conversation's working directory is inside this path
+    Project(String),
+    /// The first message carries an attachment of this kind
+    Attachment(AttachmentKind),
+}
+
+impl ModelRule {
+    /// More specific rules win when several models match.
+    fn specificity(&self) -> u8 {
+        match self {
+            ModelRule::Tag(_) => 3,
+            ModelRule::Project(_) => 2,
+            ModelRule::Attachment(_) => 1,
+        }
+    }
+
+    fn matches(&self, context: &ModelRuleContext) -> bool {
+        match self {
+            ModelRule::Tag(tag) => context.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+            ModelRule::Project(path) => context
+                .project
+                .as_deref()
+                .is_some_and(|project| project.starts_with(expand_home(path))),
+            ModelRule::Attachment(kind) => context.attachments.contains(kind),
+        }
+    }
+
+    /// Parse a comma-separated rule list, e.g. `tag:code, attachment:image`.
+    pub fn parse_list(input: &str) -> Result<Vec<ModelRule>, String> {
+        input
+            .split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse())
+            .collect()
+    }
+
+    /// Format a rule list in the syntax accepted by [`ModelRule::parse_list`].
+    pub fn format_list(rules: &[ModelRule]) -> String {
+        rules
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl std::fmt::Display for ModelRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelRule::Tag(tag) => write!(f, "tag:{tag}"),
+            ModelRule::Project(path) => write!(f, "project:{path}"),
+            ModelRule::Attachment(AttachmentKind::Image) => write!(f, "attachment:image"),
+            ModelRule::Attachment(AttachmentKind::Pdf) => write!(f, "attachment:pdf"),
+        }
+    }
+}
+
+impl std::str::FromStr for ModelRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = s
+            .split_once(':')
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .ok_or_else(|| format!("Invalid rule '{s}': expected <kind>:<value>"))?;
+        if value.is_empty() {
+            return Err(format!("Invalid rule '{s}': missing value"));
+        }
+        match kind.to_ascii_lowercase().as_str() {
+            "tag" => Ok(ModelRule::Tag(value.trim_start_matches('#').to_string())),
+            "project" => Ok(ModelRule::Project(value.to_string())),
+            "attachment" => match value.to_ascii_lowercase().as_str() {
+                "image" => Ok(ModelRule::Attachment(AttachmentKind::Image)),
+                "pdf" => Ok(ModelRule::Attachment(AttachmentKind::Pdf)),
+                other => Err(format!(
+                    "Unknown attachment kind '{other}': expected image or pdf"
+                )),
+            },
+            other => Err(format!(
+                "Unknown rule kind '{other}': expected tag, project or attachment"
+            )),
+        }
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| PathBuf::from(path)),
+        None => PathBuf::from(path),
+    }
+}
+
+/// What is known about a conversation when its default model is chosen.
+#[derive(Clone, Debug, Default)]
+pub struct ModelRuleContext {
+    pub tags: Vec<String>,
+    pub project: Option<PathBuf>,
+    pub attachments: Vec<AttachmentKind>,
+}
+
+impl ModelRuleContext {
+    /// Build the context for a first message: `#tags` in its text, the
+    /// conversation's working directory and the attachment kinds.
+    pub fn for_message(text: &str, project: Option<PathBuf>, attachments: &[PathBuf]) -> Self {
+        let tags = text
+            .split_whitespace()
+            .filter_map(|word| word.strip_prefix('#'))
+            .map(|tag| tag.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '-' && c != '_'))
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect();
+        let mut kinds: Vec<AttachmentKind> = attachments
+            .iter()
+            .filter_map(|path| AttachmentKind::from_path(path))
+            .collect();
+        kinds.dedup();
+        Self {
+            tags,
+            project,
+            attachments: kinds,
         }
     }
 }
@@ -111,6 +363,46 @@ impl ModelsModel {
         self.models.iter().find(|m| m.id == id)
     }
 
+    /// Resolve a user-typed model reference: exact id, then alias, then
+    /// case-insensitive name, then a partial match on the model identifier.
+    pub fn resolve(&self, query: &str) -> Option<&ModelConfig> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        self.get_model(query)
+            .or_else(|| self.models.iter().find(|m| m.has_alias(query)))
+            .or_else(|| {
+                self.models
+                    .iter()
+                    .find(|m| m.name.eq_ignore_ascii_case(query))
+            })
+            .or_else(|| {
+                self.models
+                    .iter()
+                    .find(|m| m.model_identifier.contains(query))
+            })
+    }
+
+    /// Pick the default model for `context` from the models' rules.
+    ///
+    /// The most specific matching rule wins (tag, then project, then
+    /// attachment); ties go to the model listed first.
+    pub fn default_model_for(
+        &self,
+        context: &ModelRuleContext,
+    ) -> Option<(&ModelConfig, &ModelRule)> {
+        let mut best: Option<(&ModelConfig, &ModelRule)> = None;
+        for model in &self.models {
+            for rule in model.default_rules.iter().filter(|r| r.matches(context)) {
+                if best.is_none_or(|(_, current)| rule.specificity() > current.specificity()) {
+                    best = Some((model, rule));
+                }
+            }
+        }
+        best
+    }
+
     pub fn models(&self) -> &[ModelConfig] {
         &self.models
     }
@@ -122,6 +414,17 @@ impl ModelsModel {
             .collect()
     }
 
+    /// Move `id` to the front of the list, where new conversations take
+    /// their model from. Returns false when no such model exists.
+    pub fn make_default(&mut self, id: &str) -> bool {
+        let Some(ix) = self.models.iter().position(|m| m.id == id) else {
+            return false;
+        };
+        let model = self.models.remove(ix);
+        self.models.insert(0, model);
+        true
+    }
+
     /// Replace all models (used when loading from disk)
     pub fn replace_all(&mut self, models: Vec<ModelConfig>) {
         self.models = models;
@@ -133,3 +436,97 @@ impl Default for ModelsModel {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str, aliases: &[&str], rules: &str) -> ModelConfig {
+        let mut config = ModelConfig::new(
+            id.to_string(),
+            id.to_uppercase(),
+            ProviderType::OpenRouter,
+            format!("vendor/{id}-v1"),
+        );
+        config.aliases = aliases.iter().map(|a| a.to_string()).collect();
+        config.default_rules = ModelRule::parse_list(rules).unwrap();
+        config
+    }
+
+    fn store() -> ModelsModel {
+        let mut store = ModelsModel::new();
+        store.add_model(model("mini", &["fast", "cheap"], "attachment:image"));
+        store.add_model(model(
+            "large",
+            &["smart"],
+            "tag:code, project:/work/api, attachment:pdf",
+        ));
+        store
+    }
+
+    #[test]
+    fn resolve_prefers_id_then_alias_then_name() {
+        let store = store();
+        assert_eq!(store.resolve("large").unwrap().id, "large");
+        assert_eq!(store.resolve("FAST").unwrap().id, "mini");
+        assert_eq!(store.resolve("smart").unwrap().id, "large");
+        assert_eq!(store.resolve("Mini").unwrap().id, "mini");
+        assert_eq!(store.resolve("large-v1").unwrap().id, "large");
+        assert!(store.resolve("unknown").is_none());
+    }
+
+    #[test]
+    fn rules_round_trip_and_reject_bad_input() {
+        let rules = ModelRule::parse_list("tag:#code, project:~/src , attachment:PDF").unwrap();
+        assert_eq!(
+            ModelRule::format_list(&rules),
+            "tag:code, project:~/src, attachment:pdf"
+        );
+        assert!(ModelRule::parse_list("colour:blue").is_err());
+        assert!(ModelRule::parse_list("attachment:video").is_err());
+        assert!(ModelRule::parse_list("tag:").is_err());
+    }
+
+    #[test]
+    fn lineage_from_fields_fills_base_from_ft_ids() {
+        let lineage = ModelLineage::from_fields(
+            "ft:gpt-4o-mini-2024-07-18:acme::9abc",
+            "",
+            " 3f2a ",
+            "2026-03-01",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(lineage.base_model, "gpt-4o-mini-2024-07-18");
+        assert_eq!(lineage.dataset_hash.as_deref(), Some("3f2a"));
+        assert_eq!(
+            lineage.summary(),
+            "Fine-tuned from gpt-4o-mini-2024-07-18 · dataset 3f2a · trained 2026-03-01"
+        );
+
+        assert_eq!(ModelLineage::from_fields("llama3", "", "", ""), Ok(None));
+        assert!(ModelLineage::from_fields("llama3", "", "3f2a", "").is_err());
+        assert!(ModelLineage::from_fields("llama3", "llama3", "", "March").is_err());
+    }
+
+    #[test]
+    fn most_specific_rule_wins() {
+        let store = store();
+        let image = [PathBuf::from("shot.PNG")];
+
+        let ctx = ModelRuleContext::for_message("what is this?", None, &image);
+        assert_eq!(store.default_model_for(&ctx).unwrap().0.id, "mini");
+
+        // A tag beats an attachment rule on another model
+        let ctx = ModelRuleContext::for_message("#code: review this", None, &image);
+        let (model, rule) = store.default_model_for(&ctx).unwrap();
+        assert_eq!(model.id, "large");
+        assert_eq!(rule, &ModelRule::Tag("code".into()));
+
+        let ctx = ModelRuleContext::for_message("hi", Some(PathBuf::from("/work/api/src")), &[]);
+        assert_eq!(store.default_model_for(&ctx).unwrap().0.id, "large");
+
+        let ctx = ModelRuleContext::for_message("hi", Some(PathBuf::from("/work/web")), &[]);
+        assert!(store.default_model_for(&ctx).is_none());
+    }
+}
@@ -0,0 +1,104 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::prompt_packs::PromptPackManifest;
+
+/// Where a pack was imported from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromptPackSource {
+    Url { url: String },
+    File { path: PathBuf },
+}
+
+impl PromptPackSource {
+    /// Read what the user typed into the import field: an `http(s)://` URL,
+    /// otherwise a file path.
+    pub fn parse(input: &str) -> Self {
+        let input = input.trim();
+        if input.starts_with("https://") || input.starts_with("http://") {
+            Self::Url {
+                url: input.to_string(),
+            }
+        } else {
+            Self::File {
+                path: PathBuf::from(input),
+            }
+        }
+    }
+
+    pub fn display(&self) -> String {
+        match self {
+            Self::Url { url } => url.clone(),
+            Self::File { path } => path.display().to_string(),
+        }
+    }
+}
+
+/// A pack installed into the local settings, with the items it owns so an
+/// update or removal only touches what the pack added.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InstalledPromptPack {
+    pub manifest: PromptPackManifest,
+    /// Hex Ed25519 key that signed the installed version. Updates must be
+    /// signed with the same key.
+    pub publisher_key: String,
+    pub source: PromptPackSource,
+    /// Unix timestamp (seconds) of the install or last update.
+    pub installed_at: i64,
+    /// Snippet names created by the pack.
+    #[serde(default)]
+    pub snippets: Vec<String>,
+    /// Model ids created from the pack's profiles.
+    #[serde(default)]
+    pub models: Vec<String>,
+    /// Extension ids of the MCP servers the pack installed.
+    #[serde(default)]
+    pub extensions: Vec<String>,
+}
+
+impl InstalledPromptPack {
+    /// Where to look for a newer version: the manifest's update URL, else the
+    /// URL the pack was imported from. Packs imported from a file without an
+    /// update URL are never checked.
+    pub fn update_url(&self) -> Option<&str> {
+        self.manifest.update_url.as_deref().or(match &self.source {
+            PromptPackSource::Url { url } => Some(url.as_str()),
+            PromptPackSource::File { .. } => None,
+        })
+    }
+}
+
+/// Global store for installed prompt packs.
+///
+/// Persisted to `~/.config/chatty/prompt_packs.json`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PromptPacksModel {
+    #[serde(default)]
+    pub packs: Vec<InstalledPromptPack>,
+}
+
+impl PromptPacksModel {
+    pub fn find(&self, id: &str) -> Option<&InstalledPromptPack> {
+        self.packs.iter().find(|p| p.manifest.id == id)
+    }
+
+    /// Insert `pack`, replacing an installed pack with the same id.
+    pub fn upsert(&mut self, pack: InstalledPromptPack) {
+        match self
+            .packs
+            .iter_mut()
+            .find(|p| p.manifest.id == pack.manifest.id)
+        {
+            Some(existing) => *existing = pack,
+            None => self.packs.push(pack),
+        }
+    }
+
+    /// Remove a pack record. Returns the removed record.
+    pub fn remove(&mut self, id: &str) -> Option<InstalledPromptPack> {
+        let index = self.packs.iter().position(|p| p.manifest.id == id)?;
+        Some(self.packs.remove(index))
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -9,6 +10,36 @@ pub enum AzureAuthMethod {
     EntraId,
 }
 
+/// Provider-side data retention opt-outs, sent with every request.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProviderPrivacy {
+    /// Ask the provider not to store prompts and completions
+    /// (OpenRouter: deny data collection, Azure OpenAI: `store: false`)
+    pub disable_storage: bool,
+    /// Only route to endpoints with zero data retention (OpenRouter only)
+    pub zero_data_retention: bool,
+}
+
+/// Network timeouts for a provider's requests, in seconds (0 = no limit).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProviderTimeouts {
+    /// Time allowed to connect to the provider
+    pub connect_secs: u64,
+    /// Time allowed without receiving any data once the request is sent;
+    /// a streamed response that goes quiet this long counts as stalled
+    pub read_secs: u64,
+}
+
+impl ProviderTimeouts {
+    pub fn connect(&self) -> Option<Duration> {
+        (self.connect_secs > 0).then(|| Duration::from_secs(self.connect_secs))
+    }
+
+    pub fn read(&self) -> Option<Duration> {
+        (self.read_secs > 0).then(|| Duration::from_secs(self.read_secs))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[allow(clippy::upper_case_acronyms)]
@@ -37,6 +68,21 @@ impl ProviderType {
         }
     }
 
+    /// Timeouts used until the user changes them. Local Ollama models may
+    /// take minutes to load before the first token, so they get longer.
+    pub fn default_timeouts(&self) -> ProviderTimeouts {
+        match self {
+            ProviderType::Ollama => ProviderTimeouts {
+                connect_secs: 10,
+                read_secs: 300,
+            },
+            ProviderType::OpenRouter | ProviderType::AzureOpenAI => ProviderTimeouts {
+                connect_secs: 30,
+                read_secs: 120,
+            },
+        }
+    }
+
     /// Returns default (supports_images, supports_pdf) based on provider capabilities
     pub fn default_capabilities(&self) -> (bool, bool) {
         match self {
@@ -102,6 +148,127 @@ impl ProviderConfig {
         self.extra_config
             .insert("auth_method".to_string(), value.to_string());
     }
+
+    /// Date the API key stops working, as entered by the user (YYYY-MM-DD).
+    /// `None` when unset or not a valid date.
+    pub fn key_expires_on(&self) -> Option<chrono::NaiveDate> {
+        let value = self.extra_config.get("key_expires_on")?;
+        chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok()
+    }
+
+    /// Store the key expiry date as typed; an empty value clears it.
+    pub fn set_key_expires_on(&mut self, value: &str) {
+        if value.trim().is_empty() {
+            self.extra_config.remove("key_expires_on");
+        } else {
+            self.extra_config
+                .insert("key_expires_on".to_string(), value.trim().to_string());
+        }
+    }
+
+    /// Network timeouts from extra_config, falling back to the provider
+    /// type's defaults.
+    pub fn timeouts(&self) -> ProviderTimeouts {
+        let defaults = self.provider_type.default_timeouts();
+        let secs = |key: &str, default: u64| {
+            self.extra_config
+                .get(key)
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default)
+        };
+        ProviderTimeouts {
+            connect_secs: secs("connect_timeout_secs", defaults.connect_secs),
+            read_secs: secs("read_timeout_secs", defaults.read_secs),
+        }
+    }
+
+    /// Set the network timeouts; values equal to the defaults are not stored.
+    pub fn set_timeouts(&mut self, timeouts: ProviderTimeouts) {
+        let defaults = self.provider_type.default_timeouts();
+        for (key, value, default) in [
+            (
+                "connect_timeout_secs",
+                timeouts.connect_secs,
+                defaults.connect_secs,
+            ),
+            ("read_timeout_secs", timeouts.read_secs, defaults.read_secs),
+        ] {
+            if value == default {
+                self.extra_config.remove(key);
+            } else {
+                self.extra_config.insert(key.to_string(), value.to_string());
+            }
+        }
+    }
+
+    /// Get the privacy opt-outs from extra_config
+    pub fn privacy(&self) -> ProviderPrivacy {
+        let flag = |key: &str| self.extra_config.get(key).is_some_and(|v| v == "true");
+        ProviderPrivacy {
+            disable_storage: flag("disable_storage"),
+            zero_data_retention: flag("zero_data_retention"),
+        }
+    }
+
+    /// Set the privacy opt-outs
+    pub fn set_privacy(&mut self, privacy: ProviderPrivacy) {
+        for (key, enabled) in [
+            ("disable_storage", privacy.disable_storage),
+            ("zero_data_retention", privacy.zero_data_retention),
+        ] {
+            if enabled {
+                self.extra_config
+                    .insert(key.to_string(), "true".to_string());
+            } else {
+                self.extra_config.remove(key);
+            }
+        }
+    }
+
+    /// Request body fields that apply the privacy opt-outs, merged into every
+    /// completion request. `None` when nothing is opted out or the provider
+    /// has no such option.
+    pub fn privacy_request_params(&self) -> Option<serde_json::Value> {
+        let privacy = self.privacy();
+        match self.provider_type {
+            ProviderType::OpenRouter => {
+                let mut preferences = serde_json::Map::new();
+                if privacy.disable_storage {
+                    preferences.insert("data_collection".to_string(), "deny".into());
+                }
+                if privacy.zero_data_retention {
+                    preferences.insert("zdr".to_string(), true.into());
+                }
+                (!preferences.is_empty()).then(|| serde_json::json!({ "provider": preferences }))
+            }
+            ProviderType::AzureOpenAI => privacy
+                .disable_storage
+                .then(|| serde_json::json!({ "store": false })),
+            ProviderType::Ollama => None,
+        }
+    }
+
+    /// Short description of the active privacy policy for the request
+    /// inspector, e.g. `data collection denied, zero data retention`.
+    pub fn privacy_summary(&self) -> Option<String> {
+        let privacy = self.privacy();
+        let parts: Vec<&str> = match self.provider_type {
+            ProviderType::OpenRouter => [
+                privacy.disable_storage.then_some("data collection denied"),
+                privacy.zero_data_retention.then_some("zero data retention"),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            ProviderType::AzureOpenAI => privacy
+                .disable_storage
+                .then_some("store: false")
+                .into_iter()
+                .collect(),
+            ProviderType::Ollama => vec!["local, nothing leaves this machine"],
+        };
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
 }
 
 #[derive(Clone)]
@@ -226,6 +393,64 @@ mod tests {
         assert_eq!(provider.azure_auth_method(), AzureAuthMethod::EntraId);
     }
 
+    #[test]
+    fn test_privacy_round_trips_through_extra_config() {
+        let mut provider = ProviderConfig::new("test".to_string(), ProviderType::OpenRouter);
+        assert_eq!(provider.privacy(), ProviderPrivacy::default());
+        assert!(provider.privacy_request_params().is_none());
+        assert!(provider.privacy_summary().is_none());
+
+        let privacy = ProviderPrivacy {
+            disable_storage: true,
+            zero_data_retention: true,
+        };
+        provider.set_privacy(privacy);
+        assert_eq!(provider.privacy(), privacy);
+        assert_eq!(
+            provider.privacy_request_params(),
+            Some(serde_json::json!({"provider": {"data_collection": "deny", "zdr": true}}))
+        );
+        assert_eq!(
+            provider.privacy_summary().as_deref(),
+            Some("data collection denied, zero data retention")
+        );
+
+        provider.set_privacy(ProviderPrivacy::default());
+        assert!(provider.extra_config.is_empty());
+    }
+
+    #[test]
+    fn test_azure_privacy_disables_stored_completions() {
+        let mut provider = ProviderConfig::new("test".to_string(), ProviderType::AzureOpenAI);
+        provider.set_privacy(ProviderPrivacy {
+            disable_storage: true,
+            zero_data_retention: true,
+        });
+        assert_eq!(
+            provider.privacy_request_params(),
+            Some(serde_json::json!({"store": false}))
+        );
+        assert_eq!(provider.privacy_summary().as_deref(), Some("store: false"));
+    }
+
+    #[test]
+    fn test_timeouts_round_trip_through_extra_config() {
+        let mut provider = ProviderConfig::new("test".to_string(), ProviderType::Ollama);
+        assert_eq!(provider.timeouts(), ProviderType::Ollama.default_timeouts());
+
+        let timeouts = ProviderTimeouts {
+            connect_secs: 5,
+            read_secs: 0,
+        };
+        provider.set_timeouts(timeouts);
+        assert_eq!(provider.timeouts(), timeouts);
+        assert_eq!(timeouts.connect(), Some(Duration::from_secs(5)));
+        assert_eq!(timeouts.read(), None);
+
+        provider.set_timeouts(ProviderType::Ollama.default_timeouts());
+        assert!(provider.extra_config.is_empty());
+    }
+
     #[test]
     fn test_configured_providers_azure_with_api_key() {
         let mut model = ProviderModel::new();
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+
+/// A named block of text or code the user pastes often.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snippet {
+    /// Slash-menu name (letters, digits, `-` and `_`), unique case-insensitively.
+    pub name: String,
+    pub content: String,
+    /// How many times the snippet has been inserted into the chat input.
+    #[serde(default)]
+    pub use_count: u64,
+}
+
+impl Snippet {
+    /// First non-empty line of the content, for one-line previews.
+    pub fn preview(&self) -> &str {
+        self.content
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("")
+    }
+}
+
+/// Global store for the snippet library.
+///
+/// Persisted to `~/.config/chatty/snippets.json`. Snippets are offered in the
+/// chat input's `/` picker, most used first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SnippetsModel {
+    #[serde(default)]
+    pub snippets: Vec<Snippet>,
+}
+
+impl SnippetsModel {
+    /// Whether `name` can be typed after `/` without closing the picker.
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Snippet> {
+        self.snippets
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Create a snippet or replace the content of an existing one (keeping its
+    /// usage count). Returns `true` when a new snippet was created.
+    pub fn upsert(&mut self, name: &str, content: String) -> bool {
+        match self
+            .snippets
+            .iter_mut()
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+        {
+            Some(existing) => {
+                existing.content = content;
+                false
+            }
+            None => {
+                self.snippets.push(Snippet {
+                    name: name.to_string(),
+                    content,
+                    use_count: 0,
+                });
+                true
+            }
+        }
+    }
+
+    /// Rename a snippet, keeping its content and usage count. Does nothing if
+    /// `new_name` is taken by another snippet.
+    pub fn rename(&mut self, name: &str, new_name: &str) -> bool {
+        if !name.eq_ignore_ascii_case(new_name) && self.find(new_name).is_some() {
+            return false;
+        }
+        match self
+            .snippets
+            .iter_mut()
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+        {
+            Some(snippet) => {
+                snippet.name = new_name.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a snippet by name. Returns `true` when one was removed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.snippets.len();
+        self.snippets.retain(|s| !s.name.eq_ignore_ascii_case(name));
+        self.snippets.len() != before
+    }
+
+    /// Count one insertion of the named snippet.
+    pub fn record_use(&mut self, name: &str) {
+        if let Some(snippet) = self
+            .snippets
+            .iter_mut()
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+        {
+            snippet.use_count += 1;
+        }
+    }
+
+    /// Snippets ordered most used first, then by name.
+    pub fn by_usage(&self) -> Vec<Snippet> {
+        let mut snippets = self.snippets.clone();
+        snippets.sort_by(|a, b| {
+            b.use_count
+                .cmp(&a.use_count)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        snippets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upsert_keeps_usage_and_orders_by_use() {
+        let mut model = SnippetsModel::default();
+        assert!(model.upsert("sql-style", "Use lowercase keywords.".into()));
+        assert!(model.upsert("bug-template", "Steps to reproduce:".into()));
+        model.record_use("SQL-STYLE");
+        model.record_use("sql-style");
+
+        assert!(!model.upsert("sql-style", "Use uppercase keywords.".into()));
+        let snippet = model.find("sql-style").unwrap();
+        assert_eq!(snippet.use_count, 2);
+        assert_eq!(snippet.content, "Use uppercase keywords.");
+
+        let names: Vec<_> = model.by_usage().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, ["sql-style", "bug-template"]);
+
+        assert!(model.remove("Bug-Template"));
+        assert!(model.find("bug-template").is_none());
+    }
+
+    #[test]
+    fn names_must_fit_the_slash_picker() {
+        assert!(SnippetsModel::is_valid_name("pr_review-2"));
+        assert!(!SnippetsModel::is_valid_name("two words"));
+        assert!(!SnippetsModel::is_valid_name(""));
+        assert_eq!(
+            Snippet {
+                name: "x".into(),
+                content: "\n  first line\nsecond".into(),
+                use_count: 0,
+            }
+            .preview(),
+            "first line"
+        );
+    }
+}
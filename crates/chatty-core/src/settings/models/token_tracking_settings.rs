@@ -144,6 +144,18 @@ impl TokenTrackingSettings {
     pub fn is_critical(&self, utilization: f64) -> bool {
         utilization >= self.critical_threshold
     }
+
+    /// Return `true` if a history of `history_tokens` should be compacted
+    /// before the next request to a model with `context_window` tokens.
+    ///
+    /// The history plus the response reserve must stay below the critical
+    /// threshold, so the request still fits with room for the answer.
+    pub fn should_compact(&self, history_tokens: usize, context_window: usize) -> bool {
+        context_window > 0
+            && self.is_critical(
+                (history_tokens + self.response_reserve) as f64 / context_window as f64,
+            )
+    }
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -152,6 +164,14 @@ impl TokenTrackingSettings {
 mod tests {
     use super::*;
 
+    #[test]
+    fn compacts_when_history_nears_the_context_window() {
+        let s = TokenTrackingSettings::default();
+        assert!(!s.should_compact(50_000, 128_000));
+        assert!(s.should_compact(112_000, 128_000));
+        assert!(!s.should_compact(112_000, 0));
+    }
+
     #[test]
     fn default_values_are_sensible() {
         let s = TokenTrackingSettings::default();
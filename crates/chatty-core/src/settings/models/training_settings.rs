@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::exporters::pii::PiiScrubConfig;
+
 /// Settings for training data collection and export
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct TrainingSettingsModel {
@@ -11,4 +13,21 @@ pub struct TrainingSettingsModel {
     /// Opt-in: disabled by default.
     #[serde(default)]
     pub jsonl_auto_export: bool,
+    /// Name recorded with review decisions in the dataset browser.
+    #[serde(default)]
+    pub reviewer_name: Option<String>,
+    /// PII scrubbing applied to ATIF and JSONL exports.
+    #[serde(default)]
+    pub pii_scrub: PiiScrubConfig,
+    /// Rate each completed conversation with a judge model.
+    /// Opt-in: disabled by default.
+    #[serde(default)]
+    pub quality_scoring: bool,
+    /// Model used as judge; scoring is skipped while unset.
+    #[serde(default)]
+    pub judge_model_id: Option<String>,
+    /// Minimum overall score (1–5) for approved examples to be written to
+    /// the approved dataset. `None` keeps every approved example.
+    #[serde(default)]
+    pub min_quality_score: Option<f32>,
 }
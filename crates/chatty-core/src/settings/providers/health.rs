@@ -0,0 +1,191 @@
+use chrono::NaiveDate;
+use tracing::debug;
+
+use crate::auth::azure_auth;
+use crate::settings::models::providers_store::{AzureAuthMethod, ProviderConfig, ProviderType};
+
+use super::validation::{CheckError, ProviderCheck, openrouter_key_info, probe_azure};
+
+/// Warn this many days before a key's recorded expiry date.
+pub const EXPIRY_WARNING_DAYS: i64 = 7;
+
+/// Warn when an OpenRouter key has less than this many dollars left.
+pub const LOW_CREDIT_USD: f64 = 1.0;
+
+/// A provider problem found before a send runs into it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthWarning {
+    pub provider: ProviderType,
+    pub problem: String,
+    pub fix: String,
+}
+
+impl HealthWarning {
+    fn new(provider: &ProviderType, problem: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            provider: provider.clone(),
+            problem: problem.into(),
+            fix: fix.into(),
+        }
+    }
+
+    fn from_check(provider: &ProviderType, check: ProviderCheck) -> Option<Self> {
+        match check {
+            ProviderCheck::Passed { .. } => None,
+            ProviderCheck::Failed { problem, fix } => Some(Self::new(provider, problem, fix)),
+        }
+    }
+}
+
+/// Warn when the key's recorded expiry date has passed or is less than
+/// [`EXPIRY_WARNING_DAYS`] away.
+pub fn expiry_warning(provider: &ProviderConfig, today: NaiveDate) -> Option<HealthWarning> {
+    let expires_on = provider.key_expires_on()?;
+    let name = provider.provider_type.display_name();
+    let days_left = (expires_on - today).num_days();
+    let fix = "Create a new key, paste it in Settings → Providers and update the expiry date.";
+    if days_left < 0 {
+        Some(HealthWarning::new(
+            &provider.provider_type,
+            format!("The {name} key expired on {expires_on}."),
+            fix,
+        ))
+    } else if days_left < EXPIRY_WARNING_DAYS {
+        let when = match days_left {
+            0 => "today".to_string(),
+            1 => "tomorrow".to_string(),
+            n => format!("in {n} days"),
+        };
+        Some(HealthWarning::new(
+            &provider.provider_type,
+            format!("The {name} key expires {when} ({expires_on})."),
+            fix,
+        ))
+    } else {
+        None
+    }
+}
+
+/// Warn when an OpenRouter key's spending limit is nearly used up.
+pub fn low_credit_warning(limit_remaining: Option<f64>) -> Option<HealthWarning> {
+    let remaining = limit_remaining?;
+    let provider = ProviderType::OpenRouter;
+    if remaining <= 0.0 {
+        Some(HealthWarning::new(
+            &provider,
+            "The OpenRouter key has used up its credit limit.",
+            "Add credits or raise the key's limit at openrouter.ai/settings/keys.",
+        ))
+    } else if remaining < LOW_CREDIT_USD {
+        Some(HealthWarning::new(
+            &provider,
+            format!("The OpenRouter key has only ${remaining:.2} of its limit left."),
+            "Add credits or raise the key's limit at openrouter.ai/settings/keys.",
+        ))
+    } else {
+        None
+    }
+}
+
+/// Check a configured provider's credentials without sending a prompt.
+///
+/// Providers that cannot be reached are skipped: the network indicator
+/// already covers being offline, and a warning would only add noise.
+pub async fn check_provider_health(provider: &ProviderConfig) -> Vec<HealthWarning> {
+    let today = chrono::Local::now().date_naive();
+    let mut warnings: Vec<HealthWarning> = expiry_warning(provider, today).into_iter().collect();
+
+    let provider_type = &provider.provider_type;
+    let api_key = provider.api_key.as_deref().unwrap_or_default();
+    let result = match provider_type {
+        ProviderType::OpenRouter => openrouter_key_info(api_key).await.map(|info| {
+            warnings.extend(low_credit_warning(info.limit_remaining));
+        }),
+        ProviderType::AzureOpenAI => match provider.azure_auth_method() {
+            AzureAuthMethod::ApiKey => {
+                let endpoint = provider.base_url.as_deref().unwrap_or_default();
+                probe_azure(endpoint, api_key).await
+            }
+            AzureAuthMethod::EntraId => azure_auth::fetch_entra_id_token()
+                .await
+                .map(|_| ())
+                .map_err(|e| {
+                    debug!(error = ?e, "Entra ID token check failed");
+                    CheckError::Rejected(ProviderCheck::failed(
+                        "Could not get an Entra ID token for Azure OpenAI.",
+                        "Run `az login` again, or renew the service principal's client secret.",
+                    ))
+                }),
+        },
+        // Local models have no credentials to expire
+        ProviderType::Ollama => Ok(()),
+    };
+
+    match result {
+        Ok(()) => {}
+        Err(CheckError::Rejected(check)) => {
+            warnings.extend(HealthWarning::from_check(provider_type, check));
+        }
+        Err(CheckError::Unreachable(_)) => {
+            debug!(
+                provider = provider_type.display_name(),
+                "Provider unreachable, skipping health check"
+            );
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider_expiring(date: &str) -> ProviderConfig {
+        let mut provider = ProviderConfig::new("OpenRouter".to_string(), ProviderType::OpenRouter);
+        provider.set_key_expires_on(date);
+        provider
+    }
+
+    fn day(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn warns_within_a_week_of_key_expiry() {
+        let provider = provider_expiring("2025-03-10");
+        assert!(expiry_warning(&provider, day("2025-03-01")).is_none());
+        let soon = expiry_warning(&provider, day("2025-03-05")).unwrap();
+        assert!(soon.problem.contains("in 5 days"));
+        let today = expiry_warning(&provider, day("2025-03-10")).unwrap();
+        assert!(today.problem.contains("today"));
+        let expired = expiry_warning(&provider, day("2025-03-11")).unwrap();
+        assert!(expired.problem.contains("expired"));
+    }
+
+    #[test]
+    fn ignores_missing_or_malformed_expiry_dates() {
+        let today = day("2025-03-01");
+        let provider = ProviderConfig::new("OpenRouter".to_string(), ProviderType::OpenRouter);
+        assert!(expiry_warning(&provider, today).is_none());
+        assert!(expiry_warning(&provider_expiring("next week"), today).is_none());
+        assert!(expiry_warning(&provider_expiring(" 2025-03-02 "), today).is_some());
+    }
+
+    #[test]
+    fn warns_when_credit_limit_runs_low() {
+        assert!(low_credit_warning(None).is_none());
+        assert!(low_credit_warning(Some(12.5)).is_none());
+        assert!(
+            low_credit_warning(Some(0.4))
+                .unwrap()
+                .problem
+                .contains("$0.40")
+        );
+        assert!(
+            low_credit_warning(Some(0.0))
+                .unwrap()
+                .problem
+                .contains("used up")
+        );
+    }
+}
@@ -1,2 +1,4 @@
+pub mod health;
 pub mod ollama;
 pub mod openrouter;
+pub mod validation;
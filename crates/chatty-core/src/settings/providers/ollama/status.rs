@@ -0,0 +1,202 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// How long a status request may take before Ollama is treated as unreachable.
+const STATUS_TIMEOUT_SECS: u64 = 5;
+
+#[derive(Debug, Deserialize)]
+struct OllamaPsResponse {
+    #[serde(default)]
+    models: Vec<OllamaPsModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaPsModel {
+    name: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    size_vram: u64,
+    #[serde(default)]
+    expires_at: Option<String>,
+    #[serde(default)]
+    details: OllamaPsDetails,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OllamaPsDetails {
+    #[serde(default)]
+    parameter_size: Option<String>,
+    #[serde(default)]
+    quantization_level: Option<String>,
+}
+
+/// A model Ollama currently holds in memory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadedModel {
+    /// Model name as Ollama reports it, e.g. "llama3.2:latest"
+    pub name: String,
+    /// Total memory the model occupies, in bytes
+    pub size_bytes: u64,
+    /// Part of `size_bytes` held in GPU memory
+    pub vram_bytes: u64,
+    /// When Ollama will unload the model if it stays idle (RFC 3339)
+    pub expires_at: Option<String>,
+    /// e.g. "8.0B"
+    pub parameter_size: Option<String>,
+    /// e.g. "Q4_K_M"
+    pub quantization: Option<String>,
+}
+
+impl LoadedModel {
+    /// Part of the model held in system RAM.
+    pub fn ram_bytes(&self) -> u64 {
+        self.size_bytes.saturating_sub(self.vram_bytes)
+    }
+
+    /// Share of the model on the GPU, 0–100.
+    pub fn gpu_percent(&self) -> u8 {
+        if self.size_bytes == 0 {
+            return 0;
+        }
+        ((self.vram_bytes.min(self.size_bytes) * 100) / self.size_bytes) as u8
+    }
+
+    /// Minutes until Ollama unloads the idle model. `None` without an expiry
+    /// or when it is more than a day away (`keep_alive` set to forever).
+    pub fn unloads_in_minutes(&self) -> Option<i64> {
+        self.unloads_in_minutes_at(Utc::now())
+    }
+
+    fn unloads_in_minutes_at(&self, now: DateTime<Utc>) -> Option<i64> {
+        let expires = DateTime::parse_from_rfc3339(self.expires_at.as_deref()?).ok()?;
+        let minutes = (expires.with_timezone(&Utc) - now).num_minutes();
+        (minutes <= 24 * 60).then_some(minutes.max(0))
+    }
+}
+
+/// What a local Ollama server has loaded right now.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OllamaStatus {
+    pub loaded: Vec<LoadedModel>,
+}
+
+impl OllamaStatus {
+    pub fn total_vram_bytes(&self) -> u64 {
+        self.loaded.iter().map(|m| m.vram_bytes).sum()
+    }
+
+    pub fn total_ram_bytes(&self) -> u64 {
+        self.loaded.iter().map(LoadedModel::ram_bytes).sum()
+    }
+
+    /// Whether `identifier` (a model name as used in requests, tag optional)
+    /// is loaded. Ollama fills in the `latest` tag when none is given.
+    pub fn is_loaded(&self, identifier: &str) -> bool {
+        let wanted = with_default_tag(identifier);
+        self.loaded
+            .iter()
+            .any(|m| with_default_tag(&m.name) == wanted)
+    }
+}
+
+fn with_default_tag(name: &str) -> String {
+    if name.contains(':') {
+        name.to_string()
+    } else {
+        format!("{name}:latest")
+    }
+}
+
+/// Parse the body of `GET /api/ps`.
+pub fn parse_ps_response(body: &str) -> Result<OllamaStatus> {
+    let response: OllamaPsResponse = serde_json::from_str(body)?;
+    let loaded = response
+        .models
+        .into_iter()
+        .map(|m| LoadedModel {
+            name: m.name,
+            size_bytes: m.size,
+            vram_bytes: m.size_vram,
+            expires_at: m.expires_at,
+            parameter_size: m.details.parameter_size.filter(|s| !s.is_empty()),
+            quantization: m.details.quantization_level.filter(|s| !s.is_empty()),
+        })
+        .collect();
+    Ok(OllamaStatus { loaded })
+}
+
+/// Ask Ollama which models it has in memory and how they are split between
+/// GPU and system RAM.
+///
+/// # Errors
+/// Returns an error if Ollama is unreachable, answers with a non-success
+/// status, or sends a body that is not a `/api/ps` response.
+pub async fn fetch_ollama_status(base_url: &str) -> Result<OllamaStatus> {
+    let url = format!("{}/api/ps", base_url.trim_end_matches('/'));
+    let response = crate::services::http_client::default_client(STATUS_TIMEOUT_SECS)
+        .get(&url)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Ollama API returned status: {}",
+            response.status()
+        ));
+    }
+
+    parse_ps_response(&response.text().await?)
+}
+
+/// Memory size for display, e.g. "4.7 GB" or "512 MB".
+pub fn format_memory(bytes: u64) -> String {
+    const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+    const MB: f64 = 1024.0 * 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= GB {
+        format!("{:.1} GB", bytes / GB)
+    } else {
+        format!("{:.0} MB", bytes / MB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PS_BODY: &str = r#"{"models":[{"name":"llama3.2:latest","model":"llama3.2:latest","size":4294967296,"digest":"a80c","details":{"format":"gguf","family":"llama","parameter_size":"3.2B","quantization_level":"Q4_K_M"},"expires_at":"2026-10-18T12:05:00Z","size_vram":3221225472}]}"#;
+
+    #[test]
+    fn parses_loaded_models_and_memory_split() {
+        let status = parse_ps_response(PS_BODY).unwrap();
+        assert_eq!(status.loaded.len(), 1);
+
+        let model = &status.loaded[0];
+        assert_eq!(model.parameter_size.as_deref(), Some("3.2B"));
+        assert_eq!(model.quantization.as_deref(), Some("Q4_K_M"));
+        assert_eq!(model.gpu_percent(), 75);
+        assert_eq!(format_memory(status.total_vram_bytes()), "3.0 GB");
+        assert_eq!(format_memory(status.total_ram_bytes()), "1.0 GB");
+        assert_eq!(format_memory(512 * 1024 * 1024), "512 MB");
+
+        let now = DateTime::parse_from_rfc3339("2026-10-18T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(model.unloads_in_minutes_at(now), Some(5));
+    }
+
+    #[test]
+    fn matches_names_with_and_without_tag() {
+        let status = parse_ps_response(PS_BODY).unwrap();
+        assert!(status.is_loaded("llama3.2"));
+        assert!(status.is_loaded("llama3.2:latest"));
+        assert!(!status.is_loaded("llama3.2:70b"));
+        assert!(
+            !parse_ps_response(r#"{"models":[]}"#)
+                .unwrap()
+                .is_loaded("llama3.2")
+        );
+    }
+}
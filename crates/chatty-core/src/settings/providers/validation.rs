@@ -0,0 +1,345 @@
+use std::cmp::Reverse;
+
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::factories::agent_factory::normalize_azure_endpoint;
+use crate::settings::models::models_store::{AZURE_DEFAULT_API_VERSION, ModelConfig};
+use crate::settings::models::providers_store::ProviderType;
+
+use super::ollama::discovery::discover_ollama_models;
+
+/// How long a validation request may take before the provider counts as unreachable.
+const CHECK_TIMEOUT_SECS: u64 = 15;
+
+/// Outcome of testing a provider's credentials.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProviderCheck {
+    /// The provider accepted the credentials
+    Passed { detail: String },
+    /// What went wrong and what the user can do about it
+    Failed { problem: String, fix: String },
+}
+
+impl ProviderCheck {
+    pub(crate) fn failed(problem: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self::Failed {
+            problem: problem.into(),
+            fix: fix.into(),
+        }
+    }
+
+    pub fn is_passed(&self) -> bool {
+        matches!(self, Self::Passed { .. })
+    }
+}
+
+/// Why a validation request did not confirm the credentials.
+pub(crate) enum CheckError {
+    /// The provider refused them, or they cannot work as entered
+    Rejected(ProviderCheck),
+    /// No answer at all: offline, DNS failure or timeout
+    Unreachable(ProviderCheck),
+}
+
+impl CheckError {
+    pub(crate) fn into_check(self) -> ProviderCheck {
+        match self {
+            Self::Rejected(check) | Self::Unreachable(check) => check,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenRouterKeyResponse {
+    data: OpenRouterKeyInfo,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct OpenRouterKeyInfo {
+    /// Dollars left before the key's own spending limit (None = no limit)
+    #[serde(default)]
+    pub(crate) limit_remaining: Option<f64>,
+    #[serde(default)]
+    pub(crate) is_free_tier: bool,
+}
+
+/// Catch keys that cannot work before sending anything, e.g. an OpenAI key
+/// pasted into the OpenRouter field.
+pub fn key_format_problem(provider: &ProviderType, api_key: &str) -> Option<ProviderCheck> {
+    let api_key = api_key.trim();
+    if api_key.is_empty() {
+        return Some(ProviderCheck::failed(
+            "No API key entered.",
+            "Paste the key from your provider account.",
+        ));
+    }
+    if api_key.chars().any(char::is_whitespace) {
+        return Some(ProviderCheck::failed(
+            "The key contains spaces or line breaks.",
+            "Copy the key again; it is a single word without spaces.",
+        ));
+    }
+    if *provider == ProviderType::OpenRouter
+        && api_key.starts_with("sk-")
+        && !api_key.starts_with("sk-or-")
+    {
+        return Some(ProviderCheck::failed(
+            "This looks like an OpenAI or Anthropic key, not an OpenRouter key.",
+            "Create an OpenRouter key at https://openrouter.ai/keys; it starts with \"sk-or-\".",
+        ));
+    }
+    None
+}
+
+/// Turn an unsuccessful HTTP status from a validation request into an
+/// explanation and a next step.
+pub fn explain_http_status(provider: &ProviderType, status: u16) -> ProviderCheck {
+    let name = provider.display_name();
+    match (provider, status) {
+        (ProviderType::OpenRouter, 401 | 403) => ProviderCheck::failed(
+            "OpenRouter rejected the API key.",
+            "Check that the key was copied completely and has not been deleted at https://openrouter.ai/keys.",
+        ),
+        (ProviderType::OpenRouter, 402) => ProviderCheck::failed(
+            "The OpenRouter account has no credits left.",
+            "Add credits at https://openrouter.ai/settings/credits or pick a free model.",
+        ),
+        (ProviderType::AzureOpenAI, 401) => ProviderCheck::failed(
+            "The Azure resource rejected the API key.",
+            "Use KEY 1 or KEY 2 from \"Keys and Endpoint\" of this resource in the Azure portal.",
+        ),
+        (ProviderType::AzureOpenAI, 403) => ProviderCheck::failed(
+            "The Azure resource refused access.",
+            "Check the resource's network rules allow your IP address, or sign in with Entra ID.",
+        ),
+        (ProviderType::AzureOpenAI, 404) => ProviderCheck::failed(
+            "No Azure OpenAI API was found at this endpoint.",
+            "Use the resource URL, e.g. https://my-resource.openai.azure.com.",
+        ),
+        (_, 429) => ProviderCheck::failed(
+            format!("{name} is rate limiting requests."),
+            "Wait a minute and test again.",
+        ),
+        (_, 500..=599) => ProviderCheck::failed(
+            format!("{name} had a server error (HTTP {status})."),
+            "Try again later; your settings may be fine.",
+        ),
+        _ => ProviderCheck::failed(
+            format!("{name} answered with HTTP {status}."),
+            "Check the settings and test again.",
+        ),
+    }
+}
+
+/// Explain a request that got no HTTP answer at all.
+fn explain_request_error(provider: &ProviderType, error: &reqwest::Error) -> ProviderCheck {
+    let name = provider.display_name();
+    if error.is_timeout() {
+        return ProviderCheck::failed(
+            format!("{name} did not answer within {CHECK_TIMEOUT_SECS} seconds."),
+            "Check your internet connection or proxy and test again.",
+        );
+    }
+    let fix = match provider {
+        ProviderType::AzureOpenAI => "Check the spelling of the endpoint URL.",
+        _ => "Check your internet connection or proxy settings.",
+    };
+    ProviderCheck::failed(format!("Could not connect to {name}."), fix)
+}
+
+/// Send `request`, turning a missing or unsuccessful answer into a [`CheckError`].
+async fn send_check(
+    provider: &ProviderType,
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, CheckError> {
+    let response = request.send().await.map_err(|e| {
+        debug!(provider = provider.display_name(), error = ?e, "Provider check failed");
+        CheckError::Unreachable(explain_request_error(provider, &e))
+    })?;
+    if !response.status().is_success() {
+        return Err(CheckError::Rejected(explain_http_status(
+            provider,
+            response.status().as_u16(),
+        )));
+    }
+    Ok(response)
+}
+
+/// Ask OpenRouter about `api_key` through the key-info endpoint, which costs nothing.
+pub(crate) async fn openrouter_key_info(api_key: &str) -> Result<OpenRouterKeyInfo, CheckError> {
+    let provider = ProviderType::OpenRouter;
+    if let Some(problem) = key_format_problem(&provider, api_key) {
+        return Err(CheckError::Rejected(problem));
+    }
+
+    let request = crate::services::http_client::default_client(CHECK_TIMEOUT_SECS)
+        .get("https://openrouter.ai/api/v1/key")
+        .bearer_auth(api_key.trim());
+    let response = send_check(&provider, request).await?;
+    // The key was accepted even if the body has an unexpected shape
+    Ok(response
+        .json::<OpenRouterKeyResponse>()
+        .await
+        .map(|r| r.data)
+        .unwrap_or_default())
+}
+
+/// List the Azure resource's models, which costs nothing, to test the endpoint and key.
+pub(crate) async fn probe_azure(endpoint: &str, api_key: &str) -> Result<(), CheckError> {
+    let provider = ProviderType::AzureOpenAI;
+    if endpoint.trim().is_empty() {
+        return Err(CheckError::Rejected(ProviderCheck::failed(
+            "No endpoint URL entered.",
+            "Copy the endpoint from \"Keys and Endpoint\" of the resource in the Azure portal.",
+        )));
+    }
+    if let Some(problem) = key_format_problem(&provider, api_key) {
+        return Err(CheckError::Rejected(problem));
+    }
+
+    let url = format!(
+        "{}/openai/models?api-version={AZURE_DEFAULT_API_VERSION}",
+        normalize_azure_endpoint(endpoint.trim())
+    );
+    let request = crate::services::http_client::default_client(CHECK_TIMEOUT_SECS)
+        .get(&url)
+        .header("api-key", api_key.trim());
+    send_check(&provider, request).await.map(|_| ())
+}
+
+/// Test an OpenRouter key.
+pub async fn check_openrouter(api_key: &str) -> ProviderCheck {
+    let info = match openrouter_key_info(api_key).await {
+        Ok(info) => info,
+        Err(e) => return e.into_check(),
+    };
+    let detail = if info.is_free_tier {
+        "Key accepted (free tier: free models only until credits are added).".to_string()
+    } else if let Some(remaining) = info.limit_remaining {
+        format!("Key accepted (${remaining:.2} of its limit left).")
+    } else {
+        "Key accepted.".to_string()
+    };
+    ProviderCheck::Passed { detail }
+}
+
+/// Test an Azure OpenAI endpoint and key.
+pub async fn check_azure(endpoint: &str, api_key: &str) -> ProviderCheck {
+    match probe_azure(endpoint, api_key).await {
+        Ok(()) => ProviderCheck::Passed {
+            detail: "Endpoint and key accepted.".to_string(),
+        },
+        Err(e) => e.into_check(),
+    }
+}
+
+/// Check that Ollama is running at `base_url` and has at least one model.
+pub async fn check_ollama(base_url: &str) -> ProviderCheck {
+    match discover_ollama_models(base_url).await {
+        Ok(models) if models.is_empty() => ProviderCheck::failed(
+            "Ollama is running but has no models.",
+            "Download one in a terminal, e.g. `ollama pull llama3.2`, then test again.",
+        ),
+        Ok(models) => ProviderCheck::Passed {
+            detail: format!("Ollama is running with {} models.", models.len()),
+        },
+        Err(e) => {
+            debug!(url = %base_url, error = ?e, "Ollama check failed");
+            ProviderCheck::failed(
+                format!("Ollama is not reachable at {base_url}."),
+                "Start Ollama (`ollama serve`) or correct the URL.",
+            )
+        }
+    }
+}
+
+/// Suggest which of `provider`'s models new conversations should use:
+/// models that accept images first, then the largest context window, with
+/// ties going to the model listed first. Embedding models are never suggested.
+pub fn suggest_default_model<'a>(
+    models: &'a [ModelConfig],
+    provider: &ProviderType,
+) -> Option<&'a ModelConfig> {
+    models
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| &m.provider_type == provider && !m.model_identifier.contains("embed"))
+        .max_by_key(|(ix, m)| {
+            (
+                m.supports_images,
+                m.max_context_window.unwrap_or(0),
+                Reverse(*ix),
+            )
+        })
+        .map(|(_, m)| m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str, provider: ProviderType, images: bool, context: Option<i32>) -> ModelConfig {
+        let mut config = ModelConfig::new(id.to_string(), id.to_string(), provider, id.to_string());
+        config.supports_images = images;
+        config.max_context_window = context;
+        config
+    }
+
+    #[test]
+    fn rejects_keys_for_the_wrong_provider_before_sending() {
+        let provider = ProviderType::OpenRouter;
+        assert!(key_format_problem(&provider, "sk-or-v1-abc").is_none());
+        assert!(key_format_problem(&provider, "  sk-or-v1-abc\n").is_none());
+        assert!(key_format_problem(&provider, "sk-proj-abc").is_some());
+        assert!(key_format_problem(&provider, "sk-or-v1 abc").is_some());
+        assert!(key_format_problem(&provider, "").is_some());
+        assert!(key_format_problem(&ProviderType::AzureOpenAI, "sk-proj-abc").is_none());
+    }
+
+    #[test]
+    fn explains_statuses_per_provider() {
+        let ProviderCheck::Failed { fix, .. } = explain_http_status(&ProviderType::OpenRouter, 402)
+        else {
+            panic!("402 is a failure");
+        };
+        assert!(fix.contains("credits"));
+
+        let ProviderCheck::Failed { fix, .. } =
+            explain_http_status(&ProviderType::AzureOpenAI, 404)
+        else {
+            panic!("404 is a failure");
+        };
+        assert!(fix.contains("openai.azure.com"));
+
+        let ProviderCheck::Failed { problem, .. } =
+            explain_http_status(&ProviderType::AzureOpenAI, 503)
+        else {
+            panic!("503 is a failure");
+        };
+        assert!(problem.contains("Azure OpenAI"));
+    }
+
+    #[test]
+    fn suggests_multimodal_model_with_largest_context() {
+        use ProviderType::{AzureOpenAI, Ollama, OpenRouter};
+
+        let models = vec![
+            model("ollama-llama", Ollama, false, None),
+            model("text-only", OpenRouter, false, Some(1_000_000)),
+            model("vision-small", OpenRouter, true, Some(128_000)),
+            model("vision-large", OpenRouter, true, Some(200_000)),
+            model("vision-large-2", OpenRouter, true, Some(200_000)),
+        ];
+        let suggested = suggest_default_model(&models, &OpenRouter).unwrap();
+        assert_eq!(suggested.id, "vision-large");
+
+        let local = vec![
+            model("nomic-embed-text", Ollama, false, None),
+            model("llama3.2", Ollama, false, None),
+        ];
+        let suggested = suggest_default_model(&local, &Ollama).unwrap();
+        assert_eq!(suggested.id, "llama3.2");
+        assert!(suggest_default_model(&local, &AzureOpenAI).is_none());
+    }
+}
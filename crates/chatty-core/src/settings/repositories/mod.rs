@@ -178,6 +178,34 @@ impl UserSecretsJsonRepository {
     }
 }
 
+define_single_json_repository!(
+    trait SnippetsRepository,
+    struct SnippetsJsonRepository,
+    model = crate::settings::models::snippets_store::SnippetsModel,
+    filename = "snippets.json",
+);
+
+define_single_json_repository!(
+    trait PromptPacksRepository,
+    struct PromptPacksJsonRepository,
+    model = crate::settings::models::prompt_packs_store::PromptPacksModel,
+    filename = "prompt_packs.json",
+);
+
+define_single_json_repository!(
+    trait ManagedConfigCacheRepository,
+    struct ManagedConfigCacheJsonRepository,
+    model = crate::settings::models::managed_config::ManagedConfigCache,
+    filename = "managed_config_cache.json",
+);
+
+define_single_json_repository!(
+    trait McpToolCacheRepository,
+    struct McpToolCacheJsonRepository,
+    model = crate::settings::models::mcp_store::McpToolCache,
+    filename = "mcp_tool_cache.json",
+);
+
 define_single_json_repository!(
     trait HiveSettingsRepository,
     struct HiveSettingsJsonRepository,
@@ -192,6 +220,13 @@ define_single_json_repository!(
     filename = "extensions.json",
 );
 
+define_single_json_repository!(
+    trait KeybindingsRepository,
+    struct KeybindingsJsonRepository,
+    model = crate::settings::models::keybindings::KeybindingsModel,
+    filename = "keybindings.json",
+);
+
 // ── List-based repositories (load_all/save_all) ─────────────────────────────
 
 define_list_json_repository!(
@@ -208,6 +243,13 @@ define_list_json_repository!(
     filename = "models.json",
 );
 
+define_list_json_repository!(
+    trait EmbeddingModelsRepository,
+    struct JsonEmbeddingModelsRepository,
+    model = crate::settings::models::embedding_models_store::EmbeddingModelConfig,
+    filename = "embedding_models.json",
+);
+
 define_list_json_repository!(
     trait McpRepository,
     struct JsonMcpRepository,
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use rig_core::completion::Message;
+
+use crate::models::message_types::{
+    SystemTrace, ToolCallBlock, ToolCallState, ToolSource, TraceItem,
+};
+use crate::models::{Conversation, ConversationLabel, conversation_tags};
+use crate::repositories::ConversationData;
+use crate::tools::ExecutionPlan;
+
+use super::FakeAgentClient;
+
+/// Builds a `ConversationData` the way the repositories store it. Fields that
+/// are not set hold the values a new, empty conversation is saved with.
+#[derive(Clone, Debug)]
+pub struct ConversationDataBuilder {
+    data: ConversationData,
+    messages: Vec<Message>,
+    /// Trace and attachments of each message, in history order
+    traces: Vec<Option<serde_json::Value>>,
+    attachments: Vec<Vec<String>>,
+}
+
+impl ConversationDataBuilder {
+    pub fn new(id: &str) -> Self {
+        Self {
+            data: ConversationData {
+                id: id.to_string(),
+                title: "New Chat".to_string(),
+                model_id: "fake-model".to_string(),
+                message_history: "[]".to_string(),
+                system_traces: "[]".to_string(),
+                token_usage: "{}".to_string(),
+                attachment_paths: "[]".to_string(),
+                message_timestamps: "[]".to_string(),
+                message_feedback: "[]".to_string(),
+                message_reactions: "[]".to_string(),
+                message_notes: "[]".to_string(),
+                message_errors: "[]".to_string(),
+                regeneration_records: "[]".to_string(),
+                created_at: 0,
+                updated_at: 0,
+                working_dir: None,
+                agent_task_snapshot: None,
+                pinned_items: "[]".to_string(),
+                variables: "{}".to_string(),
+                execution_plan: None,
+                confidential: false,
+                history_policy: r#"{"mode":"full"}"#.to_string(),
+                disabled_mcp_servers: "[]".to_string(),
+                disabled_tools: "[]".to_string(),
+                label: ConversationLabel::default().to_json(),
+                tags: "[]".to_string(),
+                folder: None,
+                history_edits: "[]".to_string(),
+                compaction_records: "[]".to_string(),
+                locked: false,
+                quality_score: None,
+            },
+            messages: Vec::new(),
+            traces: Vec::new(),
+            attachments: Vec::new(),
+        }
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.data.title = title.to_string();
+        self
+    }
+
+    pub fn model_id(mut self, model_id: &str) -> Self {
+        self.data.model_id = model_id.to_string();
+        self
+    }
+
+    /// Last-update time in Unix seconds; orders the sidebar and `load_all`.
+    pub fn updated_at(mut self, updated_at: i64) -> Self {
+        self.data.updated_at = updated_at;
+        self
+    }
+
+    pub fn label(mut self, label: ConversationLabel) -> Self {
+        self.data.label = label.to_json();
+        self
+    }
+
+    pub fn tags(mut self, tags: &[&str]) -> Self {
+        let tags: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+        self.data.tags = conversation_tags::tags_to_json(&tags);
+        self
+    }
+
+    pub fn folder(mut self, folder: &str) -> Self {
+        self.data.folder = Some(folder.to_string());
+        self
+    }
+
+    pub fn confidential(mut self, confidential: bool) -> Self {
+        self.data.confidential = confidential;
+        self
+    }
+
+    pub fn working_dir(mut self, dir: &str) -> Self {
+        self.data.working_dir = Some(dir.to_string());
+        self
+    }
+
+    pub fn execution_plan(mut self, plan: &ExecutionPlan) -> Self {
+        self.data.execution_plan =
+            Some(serde_json::to_string(plan).expect("plan serializes to JSON"));
+        self
+    }
+
+    /// Append a user message to the history.
+    pub fn user(mut self, text: &str) -> Self {
+        self.messages.push(Message::user(text));
+        self
+    }
+
+    /// Append an assistant reply to the history.
+    pub fn assistant(mut self, text: &str) -> Self {
+        self.messages.push(Message::assistant(text));
+        self
+    }
+
+    /// Attach `trace` to the last message added.
+    pub fn trace(mut self, trace: SystemTrace) -> Self {
+        let index = self.last_index();
+        self.traces.resize(index + 1, None);
+        self.traces[index] = Some(serde_json::to_value(trace).expect("trace serializes to JSON"));
+        self
+    }
+
+    /// Attach files to the last message added.
+    pub fn attachments(mut self, paths: &[&str]) -> Self {
+        let index = self.last_index();
+        self.attachments.resize(index + 1, Vec::new());
+        self.attachments[index] = paths.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    fn last_index(&self) -> usize {
+        self.messages
+            .len()
+            .checked_sub(1)
+            .expect("add a message before attaching to it")
+    }
+
+    pub fn build(self) -> ConversationData {
+        let mut data = self.data;
+        data.message_history =
+            serde_json::to_string(&self.messages).expect("messages serialize to JSON");
+        data.system_traces = serde_json::to_string(&self.traces).expect("traces serialize to JSON");
+        data.attachment_paths =
+            serde_json::to_string(&self.attachments).expect("attachments serialize to JSON");
+        data
+    }
+
+    /// Restore the built data into a `Conversation` driven by `agent`'s
+    /// offline agent client.
+    pub fn build_conversation(self, agent: &FakeAgentClient) -> Result<Conversation> {
+        Conversation::from_data_with_agent(self.build(), agent.agent_client()?)
+    }
+}
+
+/// A local tool call with no output yet.
+pub fn tool_call(name: &str, input: serde_json::Value, state: ToolCallState) -> ToolCallBlock {
+    ToolCallBlock {
+        id: String::new(),
+        tool_name: name.to_string(),
+        display_name: name.to_string(),
+        input: input.to_string(),
+        output: None,
+        output_preview: None,
+        state,
+        duration: None,
+        text_before: String::new(),
+        source: ToolSource::Local,
+        execution_engine: None,
+    }
+}
+
+/// A trace made of `calls`, in order.
+pub fn trace(calls: impl IntoIterator<Item = ToolCallBlock>) -> SystemTrace {
+    let mut trace = SystemTrace::new();
+    trace.items = calls.into_iter().map(TraceItem::ToolCall).collect();
+    trace
+}
@@ -0,0 +1,153 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use anyhow::{Context, Result, anyhow};
+use parking_lot::Mutex;
+use rig_core::OneOrMany;
+use rig_core::client::CompletionClient;
+use rig_core::completion::Message;
+use rig_core::message::UserContent;
+
+use crate::factories::AgentClient;
+use crate::services::flow_recorder::FlowRecording;
+use crate::services::llm_service::{ResponseStream, StreamChunk};
+use crate::services::{AgentTaskController, ToolCancellation};
+
+/// Address of the offline agent. Nothing listens on the discard port, so a
+/// call that reaches the network by mistake fails fast.
+const OFFLINE_URL: &str = "http://127.0.0.1:9";
+
+/// What one streamed turn was sent.
+#[derive(Clone, Debug)]
+pub struct FakeRequest {
+    pub history: Vec<Message>,
+    pub user_message: Message,
+}
+
+/// Replays scripted turns in place of `llm_service::stream_prompt`.
+///
+/// Every [`Self::stream_prompt`] call takes the next scripted turn and yields
+/// its chunks in order. Clones share the script and the recorded requests, so
+/// a test can keep one handle while the code under test holds another.
+#[derive(Clone, Default)]
+pub struct FakeAgentClient {
+    turns: Arc<Mutex<VecDeque<Vec<StreamChunk>>>>,
+    requests: Arc<Mutex<Vec<FakeRequest>>>,
+}
+
+impl FakeAgentClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Script every turn of a session captured with `flow_recorder`, so a
+    /// recorded bug report can run as a regression test.
+    pub fn from_recording(recording: FlowRecording) -> Self {
+        let agent = Self::new();
+        for turn in recording.turns {
+            agent.push_turn(turn.chunks);
+        }
+        agent
+    }
+
+    /// Script a turn that yields `chunks` exactly as given.
+    pub fn push_turn(&self, chunks: Vec<StreamChunk>) {
+        self.turns.lock().push_back(chunks);
+    }
+
+    /// Script a plain reply streamed word by word, ending like a provider
+    /// stream does: token usage (one output token per chunk), then `Done`.
+    pub fn push_reply(&self, text: &str) {
+        let mut chunks: Vec<StreamChunk> = text
+            .split_inclusive(' ')
+            .map(|word| StreamChunk::Text(word.to_string()))
+            .collect();
+        chunks.push(StreamChunk::TokenUsage {
+            input_tokens: 0,
+            output_tokens: chunks.len() as u32,
+        });
+        chunks.push(StreamChunk::Done);
+        self.push_turn(chunks);
+    }
+
+    /// Script a turn that calls tool `name` with `arguments`, gets `result`
+    /// back, and then replies with `reply`.
+    pub fn push_tool_reply(&self, name: &str, arguments: &str, result: &str, reply: &str) {
+        let id = format!("call-{name}");
+        self.push_turn(vec![
+            StreamChunk::ToolCallStarted {
+                id: id.clone(),
+                name: name.to_string(),
+            },
+            StreamChunk::ToolCallInput {
+                id: id.clone(),
+                arguments: arguments.to_string(),
+            },
+            StreamChunk::ToolCallResult {
+                id,
+                result: result.to_string(),
+            },
+            StreamChunk::Text(reply.to_string()),
+            StreamChunk::Done,
+        ]);
+    }
+
+    /// Script a turn that fails the way a provider error surfaces: an
+    /// `Error` chunk with no `Done` after it.
+    pub fn push_error(&self, message: &str) {
+        self.push_turn(vec![StreamChunk::Error(message.to_string())]);
+    }
+
+    /// Scripted turns not streamed yet.
+    pub fn remaining_turns(&self) -> usize {
+        self.turns.lock().len()
+    }
+
+    /// Requests streamed so far, oldest first.
+    pub fn requests(&self) -> Vec<FakeRequest> {
+        self.requests.lock().clone()
+    }
+
+    /// Same contract as `llm_service::stream_prompt`: returns the response
+    /// stream and the user message built from `contents`. Approvals are not
+    /// simulated; script `ApprovalRequested` chunks instead.
+    ///
+    /// # Errors
+    /// Returns an error if `contents` is empty or no scripted turn is left.
+    pub async fn stream_prompt(
+        &self,
+        history: &[Message],
+        contents: Vec<UserContent>,
+    ) -> Result<(ResponseStream, Message)> {
+        let user_message = Message::User {
+            content: OneOrMany::many(contents).context("Failed to create message from contents")?,
+        };
+        self.requests.lock().push(FakeRequest {
+            history: history.to_vec(),
+            user_message: user_message.clone(),
+        });
+        let chunks = self
+            .turns
+            .lock()
+            .pop_front()
+            .ok_or_else(|| anyhow!("FakeAgentClient has no scripted turn left"))?;
+        let stream: ResponseStream = Box::pin(futures::stream::iter(chunks.into_iter().map(Ok)));
+        Ok((stream, user_message))
+    }
+
+    /// An `AgentClient` without tools for the `Conversation` under test.
+    /// It points at an address nothing listens on and is never contacted
+    /// while turns go through [`Self::stream_prompt`].
+    pub fn agent_client(&self) -> Result<AgentClient> {
+        let client = rig_core::providers::ollama::Client::builder()
+            .api_key(rig_core::client::Nothing)
+            .base_url(OFFLINE_URL)
+            .build()?;
+        let agent = client.agent("fake-model").build();
+        Ok(AgentClient::Ollama {
+            agent,
+            task_controller: AgentTaskController::new(),
+            tool_cancellation: ToolCancellation::new(),
+        })
+    }
+}
@@ -0,0 +1,47 @@
+use crate::services::McpService;
+use crate::settings::models::mcp_store::McpServerConfig;
+
+/// An `McpService` whose servers are registered with a fixed tool list, as
+/// if an earlier launch had cached it. Listing tools never connects; calling
+/// one tries the server's unreachable URL and fails.
+#[derive(Clone, Default)]
+pub struct FakeMcpService {
+    service: McpService,
+}
+
+impl FakeMcpService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register server `name` offering tools named `tools`.
+    pub async fn add_server(&self, name: &str, tools: &[&str]) {
+        let config = McpServerConfig {
+            name: name.to_string(),
+            url: format!("http://127.0.0.1:9/{name}/mcp"),
+            api_key: None,
+            enabled: true,
+            is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
+        };
+        let tools = tools.iter().map(|tool| fake_tool(tool)).collect();
+        self.service.register_with_tools(config, tools).await;
+    }
+
+    /// The service to hand to the code under test, e.g. `gather_mcp_tools`.
+    pub fn service(&self) -> McpService {
+        self.service.clone()
+    }
+}
+
+/// A tool definition named `name` that takes an empty object.
+pub fn fake_tool(name: &str) -> rmcp::model::Tool {
+    serde_json::from_value(serde_json::json!({
+        "name": name,
+        "description": format!("Fake tool {name}"),
+        "inputSchema": { "type": "object" },
+    }))
+    .expect("tool definition is valid")
+}
@@ -0,0 +1,22 @@
+//! Test fixtures for code built on chatty-core.
+//!
+//! Integration tests can walk the same steps the frontends' controllers take
+//! (stream a reply, finalize it, regenerate it, persist the conversation)
+//! without a provider, an MCP server or a database:
+//!
+//! - [`FakeAgentClient`] replays scripted stream chunks in place of
+//!   `llm_service::stream_prompt` and records what each turn was sent.
+//! - [`FakeMcpService`] registers MCP servers with a fixed tool list, so
+//!   listing tools never connects.
+//! - [`ConversationDataBuilder`] builds conversations as the repositories
+//!   store them and restores them around the fake agent.
+//!
+//! Persist through `repositories::InMemoryConversationRepository`.
+
+pub mod builders;
+pub mod fake_agent;
+pub mod fake_mcp;
+
+pub use builders::{ConversationDataBuilder, tool_call, trace};
+pub use fake_agent::{FakeAgentClient, FakeRequest};
+pub use fake_mcp::{FakeMcpService, fake_tool};
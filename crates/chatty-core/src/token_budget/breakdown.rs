@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+
+use rig_core::completion::Message;
+use rig_core::completion::message::{AssistantContent, ToolResult, ToolResultContent};
+use rig_core::message::UserContent;
+
+use super::counter::TokenCounter;
+use crate::models::history_policy::{HistoryPolicy, PrunedTurn};
+
+// ── Source classification ─────────────────────────────────────────────────────
+
+/// Tools whose results are recalled long-term memory.
+const MEMORY_TOOLS: &[&str] = &["search_memory", "read_skill"];
+
+/// Tools whose results are retrieved document chunks.
+const RETRIEVAL_TOOLS: &[&str] = &[
+    "doc_retriever",
+    "notes_search",
+    "notes_read",
+    "citations",
+    "search_web",
+    "fetch",
+];
+
+/// Prefixes the context shaper writes into tool results it shortened.
+const TRUNCATED_RESULT_PREFIXES: &[&str] = &["[tool result truncated", "[compacted] "];
+
+/// Prefixes the context shaper, summarizer and relevance pruner write in place
+/// of dropped messages.
+const DROPPED_HISTORY_PREFIXES: &[&str] = &[
+    "[CONTEXT SHAPER:",
+    "[CONVERSATION SUMMARY",
+    "[CONTEXT PRUNED:",
+];
+
+// ── ContextComponent ──────────────────────────────────────────────────────────
+
+/// Token count for one source of prompt content.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ContextComponent {
+    pub tokens: usize,
+    /// True when the context shaper shortened or dropped some of this content
+    pub truncated: bool,
+}
+
+impl ContextComponent {
+    fn add(&mut self, tokens: usize, truncated: bool) {
+        self.tokens += tokens;
+        self.truncated |= truncated;
+    }
+}
+
+// ── ContextBreakdown ──────────────────────────────────────────────────────────
+
+/// What the conversation history sent with a turn is made of.
+///
+/// `memory`, `retrieval`, `attachments` and `conversation` partition the
+/// history count; `attachments` additionally includes files attached to the
+/// latest user message.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContextBreakdown {
+    /// Results of memory tools (`search_memory`, `read_skill`)
+    pub memory: ContextComponent,
+    /// Results of retrieval tools (documents, notes, citations, web)
+    pub retrieval: ContextComponent,
+    /// Images, PDFs and other non-text user content
+    pub attachments: ContextComponent,
+    /// Everything else: user and assistant text, tool calls, other tool results
+    pub conversation: ContextComponent,
+    /// History policy the turn was sent with
+    pub history_policy: HistoryPolicy,
+    /// Stored messages the history policy left out of the request
+    pub dropped_messages: usize,
+    /// Turns the relevance policy left out of the request
+    pub pruned_turns: Vec<PrunedTurn>,
+}
+
+impl ContextBreakdown {
+    /// Split `history` (and the latest message's attachments) by source.
+    ///
+    /// Tool results are attributed through the tool call that requested them.
+    /// Counts use the same JSON serialisation as [`TokenCounter::count_history`]
+    /// so the parts add up to the history total.
+    pub fn compute(
+        counter: &TokenCounter,
+        history: &[Message],
+        latest_attachments: &[UserContent],
+    ) -> Self {
+        let tool_names: HashMap<&str, &str> = history
+            .iter()
+            .filter_map(|message| match message {
+                Message::Assistant { content, .. } => Some(content.iter()),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|item| match item {
+                AssistantContent::ToolCall(call) => {
+                    Some((call.id.as_str(), call.function.name.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut breakdown = Self::default();
+        let mut history_tokens = 0usize;
+        let mut attributed = 0usize;
+
+        for message in history {
+            history_tokens += counter.count_message(message);
+            let Message::User { content } = message else {
+                continue;
+            };
+            for item in content.iter() {
+                match item {
+                    UserContent::ToolResult(result) => {
+                        let truncated = result_is_truncated(result);
+                        let target = match tool_names.get(result.id.as_str()) {
+                            Some(name) if MEMORY_TOOLS.contains(name) => &mut breakdown.memory,
+                            Some(name) if RETRIEVAL_TOOLS.contains(name) => {
+                                &mut breakdown.retrieval
+                            }
+                            _ => {
+                                breakdown.conversation.truncated |= truncated;
+                                continue;
+                            }
+                        };
+                        let tokens = count_json(counter, result);
+                        target.add(tokens, truncated);
+                        attributed += tokens;
+                    }
+                    UserContent::Text(text)
+                        if DROPPED_HISTORY_PREFIXES
+                            .iter()
+                            .any(|prefix| text.text.starts_with(prefix)) =>
+                    {
+                        breakdown.conversation.truncated = true;
+                    }
+                    other if is_attachment(other) => {
+                        let tokens = count_json(counter, other);
+                        breakdown.attachments.add(tokens, false);
+                        attributed += tokens;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        breakdown.conversation.tokens = history_tokens.saturating_sub(attributed);
+        for item in latest_attachments {
+            breakdown.attachments.add(count_json(counter, item), false);
+        }
+        breakdown
+    }
+
+    /// Record the history policy that produced the counted history. Messages
+    /// it left out mark the conversation history as truncated.
+    pub fn with_history_policy(mut self, policy: HistoryPolicy, dropped_messages: usize) -> Self {
+        self.history_policy = policy;
+        self.dropped_messages = dropped_messages;
+        self.conversation.truncated |= dropped_messages > 0;
+        self
+    }
+
+    /// Record the turns the relevance policy pruned from the counted history.
+    pub fn with_pruned_turns(mut self, pruned_turns: Vec<PrunedTurn>) -> Self {
+        self.conversation.truncated |= !pruned_turns.is_empty();
+        self.pruned_turns = pruned_turns;
+        self
+    }
+}
+
+/// Whether a user content item is an attached file rather than text.
+pub fn is_attachment(content: &UserContent) -> bool {
+    matches!(
+        content,
+        UserContent::Image(_)
+            | UserContent::Audio(_)
+            | UserContent::Video(_)
+            | UserContent::Document(_)
+    )
+}
+
+fn result_is_truncated(result: &ToolResult) -> bool {
+    result.content.iter().any(|item| match item {
+        ToolResultContent::Text(text) => TRUNCATED_RESULT_PREFIXES
+            .iter()
+            .any(|prefix| text.text.starts_with(prefix)),
+        _ => false,
+    })
+}
+
+fn count_json(counter: &TokenCounter, value: &impl serde::Serialize) -> usize {
+    serde_json::to_string(value)
+        .map(|json| counter.count(&json))
+        .unwrap_or(0)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig_core::OneOrMany;
+    use rig_core::completion::message::{Text, ToolCall, ToolFunction};
+
+    fn tool_call(id: &str, name: &str) -> Message {
+        Message::Assistant {
+            id: None,
+            content: OneOrMany::one(AssistantContent::ToolCall(ToolCall::new(
+                id.into(),
+                ToolFunction {
+                    name: name.into(),
+                    arguments: serde_json::json!({}),
+                },
+            ))),
+        }
+    }
+
+    fn tool_result(id: &str, text: &str) -> Message {
+        Message::User {
+            content: OneOrMany::one(UserContent::ToolResult(ToolResult {
+                id: id.into(),
+                call_id: None,
+                content: OneOrMany::one(ToolResultContent::Text(Text { text: text.into() })),
+            })),
+        }
+    }
+
+    #[test]
+    fn attributes_tool_results_by_tool_name() {
+        let counter = TokenCounter::for_model("gpt-4");
+        let history = vec![
+            Message::user("What did we decide about the schema?"),
+            tool_call("m1", "search_memory"),
+            tool_result("m1", "The schema uses UUID primary keys."),
+            tool_call("r1", "doc_retriever"),
+            tool_result("r1", "[tool result truncated — 9000 chars]\nSchema docs…"),
+            tool_call("s1", "shell_execute"),
+            tool_result("s1", "ok"),
+        ];
+
+        let breakdown = ContextBreakdown::compute(&counter, &history, &[]);
+
+        assert!(breakdown.memory.tokens > 0);
+        assert!(!breakdown.memory.truncated);
+        assert!(breakdown.retrieval.tokens > 0);
+        assert!(breakdown.retrieval.truncated);
+        assert_eq!(breakdown.attachments, ContextComponent::default());
+        assert_eq!(
+            breakdown.memory.tokens + breakdown.retrieval.tokens + breakdown.conversation.tokens,
+            counter.count_history(&history)
+        );
+    }
+
+    #[test]
+    fn snipped_history_is_marked_truncated() {
+        let counter = TokenCounter::for_model("gpt-4");
+        let history = vec![
+            Message::user("[CONTEXT SHAPER: 12 messages snipped to reduce context size]"),
+            Message::assistant("Continuing."),
+        ];
+
+        let breakdown = ContextBreakdown::compute(&counter, &history, &[]);
+
+        assert!(breakdown.conversation.truncated);
+        assert_eq!(
+            breakdown.conversation.tokens,
+            counter.count_history(&history)
+        );
+    }
+}
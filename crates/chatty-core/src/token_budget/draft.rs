@@ -0,0 +1,94 @@
+//! Token estimate for a message that has not been sent yet.
+//!
+//! The chat input shows it live while the user types. Text is counted with
+//! the model's BPE approximation (`TokenCounter`). Attachments cannot be
+//! tokenized before the provider sees them, so they get fixed costs:
+//! providers bill images by resolution after downscaling them (about 1,600
+//! tokens at the usual ~1.15 megapixel cap), PDFs per page and videos per
+//! sampled frame.
+
+use std::path::Path;
+
+use crate::models::attachment_validation::is_video_extension;
+use crate::services::video_frames::DEFAULT_VIDEO_FRAMES;
+
+/// Estimated cost of one image attachment.
+pub const IMAGE_TOKENS: usize = 1_600;
+
+/// Estimated cost of one PDF page (text plus the rendered page image).
+pub const PDF_PAGE_TOKENS: usize = 1_500;
+
+/// Estimated tokens of the attachment at `path`. PDFs are read to count
+/// their pages, so call this off the UI thread; videos cost their default
+/// number of sampled frames, and anything else the chat input accepts is an
+/// image.
+pub fn estimate_attachment_tokens(path: &Path) -> usize {
+    let ext = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if is_video_extension(&ext) {
+        return DEFAULT_VIDEO_FRAMES as usize * IMAGE_TOKENS;
+    }
+    let is_pdf = ext == "pdf";
+    if !is_pdf {
+        return IMAGE_TOKENS;
+    }
+    let pages = std::fs::read(path)
+        .map(|data| pdf_page_count(&data))
+        .unwrap_or(1);
+    pages * PDF_PAGE_TOKENS
+}
+
+/// Number of pages in a PDF, counted from its `/Type /Page` objects.
+/// Returns 1 when none are found (e.g. compressed object streams).
+pub fn pdf_page_count(data: &[u8]) -> usize {
+    const MARKER: &[u8] = b"/Type";
+    let mut pages = 0;
+    let mut rest = data;
+    while let Some(pos) = rest.windows(MARKER.len()).position(|w| w == MARKER) {
+        rest = &rest[pos + MARKER.len()..];
+        let value = rest.trim_ascii_start();
+        if value.starts_with(b"/Page") && !value.starts_with(b"/Pages") {
+            pages += 1;
+        }
+    }
+    pages.max(1)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_pdf_page_objects() {
+        let pdf = b"1 0 obj << /Type /Pages /Count 2 >> endobj \
+                    2 0 obj << /Type /Page /Parent 1 0 R >> endobj \
+                    3 0 obj <</Type/Page/Parent 1 0 R>> endobj \
+                    4 0 obj << /Type /Catalog >> endobj";
+        assert_eq!(pdf_page_count(pdf), 2);
+        assert_eq!(pdf_page_count(b"%PDF-1.7 compressed"), 1);
+    }
+
+    #[test]
+    fn images_use_fixed_estimate() {
+        assert_eq!(
+            estimate_attachment_tokens(Path::new("/nonexistent/photo.PNG")),
+            IMAGE_TOKENS
+        );
+        assert_eq!(
+            estimate_attachment_tokens(Path::new("/nonexistent/paper.pdf")),
+            PDF_PAGE_TOKENS
+        );
+    }
+
+    #[test]
+    fn videos_cost_their_sampled_frames() {
+        assert_eq!(
+            estimate_attachment_tokens(Path::new("/nonexistent/clip.mp4")),
+            DEFAULT_VIDEO_FRAMES as usize * IMAGE_TOKENS
+        );
+    }
+}
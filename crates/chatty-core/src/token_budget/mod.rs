@@ -1,9 +1,12 @@
 //! Token budget tracking for the context window.
 
+pub mod breakdown;
 pub mod cache;
 pub mod counter;
+pub mod draft;
 pub mod snapshot;
 pub mod summarizer;
 
+pub use breakdown::{ContextBreakdown, ContextComponent};
 pub use snapshot::{ContextStatus, TokenBudgetSnapshot};
 pub use summarizer::summarize_oldest_half;
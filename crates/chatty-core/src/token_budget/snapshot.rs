@@ -1,3 +1,5 @@
+use super::breakdown::ContextBreakdown;
+
 /// A point-in-time view of how tokens are distributed across context components.
 ///
 /// Computed once before each prompt is sent (in a background thread via `tokio::spawn_blocking`),
@@ -24,6 +26,11 @@ pub struct TokenBudgetSnapshot {
     pub conversation_history_tokens: usize,
     /// Tokens consumed by the new user message being sent
     pub latest_user_message_tokens: usize,
+    /// Tokens consumed by pinned context items injected ahead of the user message
+    pub pinned_context_tokens: usize,
+    /// Where the history tokens came from (memory, retrieval, attachments) and
+    /// whether the context shaper truncated them
+    pub breakdown: ContextBreakdown,
 
     // ── Post-response actuals (populated after each turn via provider Usage) ─
     /// Raw input token count reported by the provider API for the last turn.
@@ -51,6 +58,7 @@ impl TokenBudgetSnapshot {
             + self.tool_definitions_tokens
             + self.conversation_history_tokens
             + self.latest_user_message_tokens
+            + self.pinned_context_tokens
     }
 
     /// Tokens remaining before the effective budget is exhausted.
@@ -81,6 +89,7 @@ impl TokenBudgetSnapshot {
             tools: (self.tool_definitions_tokens as f64 / budget).clamp(0.0, 1.0),
             history: (self.conversation_history_tokens as f64 / budget).clamp(0.0, 1.0),
             user_msg: (self.latest_user_message_tokens as f64 / budget).clamp(0.0, 1.0),
+            pinned: (self.pinned_context_tokens as f64 / budget).clamp(0.0, 1.0),
         }
     }
 
@@ -124,19 +133,26 @@ pub struct ComponentFractions {
     pub history: f64,
     /// Fraction used by the latest user message
     pub user_msg: f64,
+    /// Fraction used by pinned context items
+    pub pinned: f64,
 }
 
 impl ComponentFractions {
     /// Fraction of the budget that is still free (remaining headroom).
     /// Clamped to `0.0–1.0` — may be 0.0 when over budget.
     pub fn remaining(&self) -> f64 {
-        (1.0 - self.preamble - self.tools - self.history - self.user_msg).clamp(0.0, 1.0)
+        (1.0 - self.preamble - self.tools - self.history - self.user_msg - self.pinned)
+            .clamp(0.0, 1.0)
     }
 
     /// True when all components are zero (e.g. snapshot has not been computed yet).
     #[allow(dead_code)]
     pub fn is_empty(&self) -> bool {
-        self.preamble == 0.0 && self.tools == 0.0 && self.history == 0.0 && self.user_msg == 0.0
+        self.preamble == 0.0
+            && self.tools == 0.0
+            && self.history == 0.0
+            && self.user_msg == 0.0
+            && self.pinned == 0.0
     }
 }
 
@@ -248,6 +264,8 @@ mod tests {
             tool_definitions_tokens: tools,
             conversation_history_tokens: history,
             latest_user_message_tokens: user_msg,
+            pinned_context_tokens: 0,
+            breakdown: ContextBreakdown::default(),
             actual_input_tokens: None,
             actual_output_tokens: None,
             conversation_id: "test".to_string(),
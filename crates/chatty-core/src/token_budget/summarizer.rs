@@ -29,9 +29,12 @@ pub struct SummarizationResult {
 /// history is preserved verbatim so no recent context is lost.
 ///
 /// # When to call
-/// Triggered either automatically (when `TokenTrackingSettings.auto_summarize` is
-/// true and a `CriticalPressure` event fires) or manually (user clicks the
-/// "Summarize" button in the context bar popover).
+/// Triggered automatically before a request whose history nears the model's
+/// context window (see `TokenTrackingSettings::should_compact`), when
+/// `TokenTrackingSettings.auto_summarize` is true and a `CriticalPressure`
+/// event fires, or manually (`/compact` or the "Summarize" button in the
+/// context bar popover). Apply the result with `Conversation::compact_history`
+/// so the compaction is recorded.
 ///
 /// # Errors
 /// Returns an error if:
@@ -162,7 +165,7 @@ pub async fn summarize_with_model(
 /// Non-text content (images, PDFs, tool calls, tool results) is represented
 /// by a placeholder so the LLM understands what was present without receiving
 /// binary data.
-fn build_transcript(messages: &[Message]) -> String {
+pub(crate) fn build_transcript(messages: &[Message]) -> String {
     let mut parts = Vec::with_capacity(messages.len());
 
     for message in messages {
@@ -195,7 +198,7 @@ fn build_transcript(messages: &[Message]) -> String {
 }
 
 /// Extract plain text from `UserContent`, joining multiple text parts with a space.
-fn extract_user_text(content: &OneOrMany<UserContent>) -> String {
+pub(crate) fn extract_user_text(content: &OneOrMany<UserContent>) -> String {
     content
         .iter()
         .filter_map(|c| match c {
@@ -0,0 +1,218 @@
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::Tool;
+use serde::{Deserialize, Serialize};
+
+use crate::models::execution_approval_store::{PendingApprovals, UserQuestion, request_user_input};
+use crate::tools::ToolError;
+
+/// Maximum number of suggested answers shown to the user.
+pub const MAX_OPTIONS: usize = 8;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AskUserArgs {
+    pub question: String,
+    #[serde(default)]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub allow_free_text: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AskUserOutput {
+    pub answered: bool,
+    pub answer: Option<String>,
+    pub message: String,
+}
+
+/// Pause the agent turn and ask the user a question. The question is routed
+/// through the execution-approval channel, so the UI renders it inline and the
+/// answer resumes the same turn.
+#[derive(Clone)]
+pub struct AskUserTool {
+    pending_approvals: PendingApprovals,
+}
+
+impl AskUserTool {
+    pub fn new(pending_approvals: PendingApprovals) -> Self {
+        Self { pending_approvals }
+    }
+}
+
+fn build_question(args: AskUserArgs) -> Result<UserQuestion, ToolError> {
+    let question = args.question.trim().to_string();
+    if question.is_empty() {
+        return Err(ToolError::OperationFailed(
+            "question must not be empty".to_string(),
+        ));
+    }
+
+    let mut options: Vec<String> = Vec::with_capacity(args.options.len());
+    for option in args.options {
+        let option = option.trim();
+        if !option.is_empty() && !options.iter().any(|o| o == option) {
+            options.push(option.to_string());
+        }
+    }
+    if options.len() > MAX_OPTIONS {
+        return Err(ToolError::OperationFailed(format!(
+            "at most {MAX_OPTIONS} options are allowed"
+        )));
+    }
+
+    // A question without options can only be answered with free text.
+    let allow_free_text = options.is_empty() || args.allow_free_text.unwrap_or(true);
+    Ok(UserQuestion {
+        question,
+        options,
+        allow_free_text,
+    })
+}
+
+impl Tool for AskUserTool {
+    const NAME: &'static str = "ask_user";
+    type Error = ToolError;
+    type Args = AskUserArgs;
+    type Output = AskUserOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Ask the user a clarifying question and wait for the answer before \
+                          continuing. Use it only when you cannot proceed without a decision \
+                          or information that only the user has. Offer options for \
+                          multiple-choice questions."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "question": {
+                        "type": "string",
+                        "description": "The question to show the user."
+                    },
+                    "options": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": format!("Optional suggested answers (at most {MAX_OPTIONS}).")
+                    },
+                    "allow_free_text": {
+                        "type": "boolean",
+                        "description": "Whether the user may type their own answer instead of picking an option. Defaults to true."
+                    }
+                },
+                "required": ["question"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let question = build_question(args)?;
+        match request_user_input(&self.pending_approvals, question).await? {
+            Some(answer) => Ok(AskUserOutput {
+                answered: true,
+                message: "The user answered. Continue the task using their answer.".to_string(),
+                answer: Some(answer),
+            }),
+            None => Ok(AskUserOutput {
+                answered: false,
+                answer: None,
+                message: "The user dismissed the question. Proceed with your best judgement \
+                          and state the assumption you made."
+                    .to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExecutionApprovalStore;
+    use crate::models::execution_approval_store::ApprovalDecision;
+
+    fn args(question: &str, options: &[&str]) -> AskUserArgs {
+        AskUserArgs {
+            question: question.into(),
+            options: options.iter().map(|o| o.to_string()).collect(),
+            allow_free_text: Some(false),
+        }
+    }
+
+    #[test]
+    fn build_question_trims_and_dedupes_options() {
+        let question =
+            build_question(args(" Which DB? ", &["sqlite", " sqlite ", "", "postgres"])).unwrap();
+        assert_eq!(question.question, "Which DB?");
+        assert_eq!(question.options, vec!["sqlite", "postgres"]);
+        assert!(!question.allow_free_text);
+    }
+
+    #[test]
+    fn build_question_without_options_forces_free_text() {
+        let question = build_question(args("Name?", &[])).unwrap();
+        assert!(question.allow_free_text);
+    }
+
+    #[test]
+    fn build_question_rejects_empty_and_too_many_options() {
+        assert!(build_question(args("  ", &[])).is_err());
+        let many: Vec<String> = (0..=MAX_OPTIONS).map(|i| format!("o{i}")).collect();
+        let many: Vec<&str> = many.iter().map(String::as_str).collect();
+        assert!(build_question(args("Pick", &many)).is_err());
+    }
+
+    #[test]
+    fn typed_answer_maps_numbers_and_option_text() {
+        let question = build_question(args("Which DB?", &["sqlite", "postgres"])).unwrap();
+        assert_eq!(
+            question.resolve_typed_answer("2").as_deref(),
+            Some("postgres")
+        );
+        assert_eq!(
+            question.resolve_typed_answer(" SQLite ").as_deref(),
+            Some("sqlite")
+        );
+        assert_eq!(question.resolve_typed_answer("mysql"), None);
+
+        let free = UserQuestion {
+            allow_free_text: true,
+            ..question
+        };
+        assert_eq!(free.resolve_typed_answer("mysql").as_deref(), Some("mysql"));
+        assert_eq!(free.resolve_typed_answer("9").as_deref(), Some("9"));
+    }
+
+    async fn answer_first_pending(store: &ExecutionApprovalStore, decision: ApprovalDecision) {
+        let pending = store.get_pending_approvals();
+        let id = loop {
+            if let Some(id) = pending.lock().keys().next().cloned() {
+                break id;
+            }
+            tokio::task::yield_now().await;
+        };
+        assert!(store.resolve(&id, decision));
+    }
+
+    #[tokio::test]
+    async fn answer_resumes_the_tool_call() {
+        let store = ExecutionApprovalStore::new();
+        let tool = AskUserTool::new(store.get_pending_approvals());
+        let call = tokio::spawn(async move { tool.call(args("Which DB?", &["sqlite"])).await });
+
+        answer_first_pending(&store, ApprovalDecision::Answered("sqlite".into())).await;
+        let output = call.await.unwrap().unwrap();
+        assert!(output.answered);
+        assert_eq!(output.answer.as_deref(), Some("sqlite"));
+    }
+
+    #[tokio::test]
+    async fn dismissing_returns_unanswered() {
+        let store = ExecutionApprovalStore::new();
+        let tool = AskUserTool::new(store.get_pending_approvals());
+        let call = tokio::spawn(async move { tool.call(args("Name?", &[])).await });
+
+        answer_first_pending(&store, ApprovalDecision::Denied).await;
+        let output = call.await.unwrap().unwrap();
+        assert!(!output.answered);
+        assert!(output.answer.is_none());
+    }
+}
@@ -0,0 +1,163 @@
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::Tool;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::services::citation_service::{CitationService, CitationStyle, Reference, remember};
+use crate::tools::ToolError;
+
+/// Default and maximum number of references returned by `search`.
+const DEFAULT_SEARCH_LIMIT: usize = 10;
+const MAX_SEARCH_LIMIT: usize = 50;
+
+#[derive(Deserialize, Serialize)]
+pub struct CitationArgs {
+    /// search or format.
+    pub action: String,
+    /// Title, author, year or citekey words (search).
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Citekeys to format (format).
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// Bibliography style; defaults to the configured style.
+    #[serde(default)]
+    pub style: Option<CitationStyle>,
+    /// Maximum number of results (search).
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// One reference as returned to the model.
+#[derive(Debug, Serialize)]
+pub struct CitationEntry {
+    pub key: String,
+    /// Marker to put in the answer; the UI turns it into a reference card.
+    pub marker: String,
+    pub in_text: String,
+    pub reference: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CitationOutput {
+    pub references: Vec<CitationEntry>,
+    /// Keys passed to `format` that are not in the library.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unknown_keys: Vec<String>,
+}
+
+/// Search the user's BibTeX / Zotero library and format citations.
+#[derive(Clone)]
+pub struct CitationTool {
+    service: Arc<CitationService>,
+    default_style: CitationStyle,
+}
+
+impl CitationTool {
+    pub fn new(service: Arc<CitationService>, default_style: CitationStyle) -> Self {
+        Self {
+            service,
+            default_style,
+        }
+    }
+
+    fn entry(&self, reference: &Reference, style: CitationStyle) -> CitationEntry {
+        CitationEntry {
+            key: reference.key.clone(),
+            marker: format!("[@{}]", reference.key),
+            in_text: reference.in_text(),
+            reference: reference.format(style),
+        }
+    }
+}
+
+impl Tool for CitationTool {
+    const NAME: &'static str = "citations";
+    type Error = ToolError;
+    type Args = CitationArgs;
+    type Output = CitationOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "citations".to_string(),
+            description: "Look up references in the user's reference library (BibTeX file or \
+                         Zotero). Actions: search (find references by title, author, year or \
+                         citekey) and format (full bibliography entries for citekeys). Cite a \
+                         reference in your answer by writing its marker, e.g. [@smith2020]; the \
+                         app renders it as a reference card. Only cite keys returned by this tool."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["search", "format"],
+                        "description": "The operation to perform"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Words from the title, author names, year or citekey (search)"
+                    },
+                    "keys": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Citekeys to format (format)"
+                    },
+                    "style": {
+                        "type": "string",
+                        "enum": ["apa", "mla", "chicago", "ieee"],
+                        "description": "Bibliography style. Defaults to the user's setting."
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of results (search). Default: 10"
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let style = args.style.unwrap_or(self.default_style);
+        match args.action.as_str() {
+            "search" => {
+                let query = args.query.as_deref().unwrap_or_default();
+                let limit = args
+                    .limit
+                    .unwrap_or(DEFAULT_SEARCH_LIMIT)
+                    .clamp(1, MAX_SEARCH_LIMIT);
+                let results = self.service.search(query, limit).await?;
+                Ok(CitationOutput {
+                    references: results.iter().map(|r| self.entry(r, style)).collect(),
+                    unknown_keys: Vec::new(),
+                })
+            }
+            "format" => {
+                if args.keys.is_empty() {
+                    return Err(ToolError::OperationFailed(
+                        "format requires 'keys'".to_string(),
+                    ));
+                }
+                let mut references = Vec::new();
+                let mut unknown_keys = Vec::new();
+                for key in &args.keys {
+                    let key = key.trim().trim_start_matches('@');
+                    match self.service.get(key) {
+                        Some(reference) => references.push(reference),
+                        None => unknown_keys.push(key.to_string()),
+                    }
+                }
+                remember(&references);
+                Ok(CitationOutput {
+                    references: references.iter().map(|r| self.entry(r, style)).collect(),
+                    unknown_keys,
+                })
+            }
+            other => Err(ToolError::OperationFailed(format!(
+                "Unknown citations action '{}'",
+                other
+            ))),
+        }
+    }
+}
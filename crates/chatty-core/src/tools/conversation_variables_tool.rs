@@ -0,0 +1,308 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::Tool;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::ToolError;
+
+/// Per-conversation key-value scratchpad shared between the conversation
+/// (which persists it) and the variable tools of its agent.
+pub type ConversationVariables = Arc<Mutex<BTreeMap<String, String>>>;
+
+/// Maximum number of variables a single conversation may hold.
+pub const MAX_VARIABLES: usize = 64;
+/// Maximum length of a variable name.
+pub const MAX_NAME_LEN: usize = 64;
+/// Maximum length (in characters) of a single variable value.
+pub const MAX_VALUE_CHARS: usize = 8_000;
+
+#[derive(Debug, Serialize)]
+pub struct VariablesOutput {
+    pub message: String,
+    /// The requested variable, or every variable when no name was given.
+    pub variables: BTreeMap<String, String>,
+}
+
+fn validate_name(name: &str) -> Result<String, ToolError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ToolError::OperationFailed(
+            "variable name must not be empty".to_string(),
+        ));
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(ToolError::OperationFailed(format!(
+            "variable name must be at most {MAX_NAME_LEN} characters"
+        )));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        return Err(ToolError::OperationFailed(format!(
+            "invalid variable name '{name}': use letters, digits, '_', '-' or '.'"
+        )));
+    }
+    Ok(name.to_string())
+}
+
+// ── get_variable ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GetVariableArgs {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct GetVariableTool {
+    variables: ConversationVariables,
+}
+
+impl GetVariableTool {
+    pub fn new(variables: ConversationVariables) -> Self {
+        Self { variables }
+    }
+}
+
+impl Tool for GetVariableTool {
+    const NAME: &'static str = "get_variable";
+    type Error = ToolError;
+    type Args = GetVariableArgs;
+    type Output = VariablesOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Read a conversation variable saved earlier with set_variable. \
+                          Omit name to list every variable. Variables survive across turns \
+                          even when older messages are summarized or trimmed."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Variable name. Omit to return all variables."
+                    }
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let variables = self.variables.lock();
+        let Some(name) = args.name.filter(|n| !n.trim().is_empty()) else {
+            return Ok(VariablesOutput {
+                message: format!("{} variable(s) set.", variables.len()),
+                variables: variables.clone(),
+            });
+        };
+
+        let name = validate_name(&name)?;
+        match variables.get(&name) {
+            Some(value) => Ok(VariablesOutput {
+                message: format!("Variable '{name}' found."),
+                variables: BTreeMap::from([(name, value.clone())]),
+            }),
+            None => Ok(VariablesOutput {
+                message: format!(
+                    "Variable '{name}' is not set. Existing variables are listed below."
+                ),
+                variables: variables.clone(),
+            }),
+        }
+    }
+}
+
+// ── set_variable ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SetVariableArgs {
+    pub name: String,
+    /// New value; `None` removes the variable.
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct SetVariableTool {
+    variables: ConversationVariables,
+}
+
+impl SetVariableTool {
+    pub fn new(variables: ConversationVariables) -> Self {
+        Self { variables }
+    }
+}
+
+impl Tool for SetVariableTool {
+    const NAME: &'static str = "set_variable";
+    type Error = ToolError;
+    type Args = SetVariableArgs;
+    type Output = VariablesOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Save a value in this conversation's variable scratchpad so it can be \
+                          read back with get_variable on later turns. Use it for plan state, \
+                          counters, chosen options, or intermediate results. Omit value to \
+                          delete the variable. The user can see these variables."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Variable name (letters, digits, '_', '-', '.')."
+                    },
+                    "value": {
+                        "type": "string",
+                        "description": "Value to store. Omit to delete the variable."
+                    }
+                },
+                "required": ["name"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let name = validate_name(&args.name)?;
+        let mut variables = self.variables.lock();
+
+        let message = match args.value {
+            Some(value) => {
+                if value.chars().count() > MAX_VALUE_CHARS {
+                    return Err(ToolError::OperationFailed(format!(
+                        "value for '{name}' exceeds {MAX_VALUE_CHARS} characters"
+                    )));
+                }
+                if !variables.contains_key(&name) && variables.len() >= MAX_VARIABLES {
+                    return Err(ToolError::OperationFailed(format!(
+                        "conversation already has {MAX_VARIABLES} variables; delete one first"
+                    )));
+                }
+                variables.insert(name.clone(), value);
+                format!("Variable '{name}' saved.")
+            }
+            None => {
+                if variables.remove(&name).is_some() {
+                    format!("Variable '{name}' deleted.")
+                } else {
+                    format!("Variable '{name}' was not set.")
+                }
+            }
+        };
+
+        Ok(VariablesOutput {
+            message,
+            variables: variables.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tools() -> (GetVariableTool, SetVariableTool, ConversationVariables) {
+        let variables = ConversationVariables::default();
+        (
+            GetVariableTool::new(variables.clone()),
+            SetVariableTool::new(variables.clone()),
+            variables,
+        )
+    }
+
+    #[tokio::test]
+    async fn set_then_get_roundtrips_value() {
+        let (get, set, shared) = tools();
+        set.call(SetVariableArgs {
+            name: " current_step ".into(),
+            value: Some("3".into()),
+        })
+        .await
+        .unwrap();
+
+        let output = get
+            .call(GetVariableArgs {
+                name: Some("current_step".into()),
+            })
+            .await
+            .unwrap();
+        assert_eq!(output.variables.get("current_step").unwrap(), "3");
+        assert_eq!(shared.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn omitted_value_deletes_variable() {
+        let (get, set, _) = tools();
+        set.call(SetVariableArgs {
+            name: "draft".into(),
+            value: Some("v1".into()),
+        })
+        .await
+        .unwrap();
+        set.call(SetVariableArgs {
+            name: "draft".into(),
+            value: None,
+        })
+        .await
+        .unwrap();
+
+        let output = get.call(GetVariableArgs { name: None }).await.unwrap();
+        assert!(output.variables.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_names_and_oversized_values() {
+        let (_, set, _) = tools();
+        assert!(
+            set.call(SetVariableArgs {
+                name: "has space".into(),
+                value: Some("x".into()),
+            })
+            .await
+            .is_err()
+        );
+        assert!(
+            set.call(SetVariableArgs {
+                name: "big".into(),
+                value: Some("x".repeat(MAX_VALUE_CHARS + 1)),
+            })
+            .await
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn enforces_variable_limit_but_allows_overwrite() {
+        let (_, set, shared) = tools();
+        {
+            let mut vars = shared.lock();
+            for i in 0..MAX_VARIABLES {
+                vars.insert(format!("v{i}"), "x".into());
+            }
+        }
+        assert!(
+            set.call(SetVariableArgs {
+                name: "one_more".into(),
+                value: Some("x".into()),
+            })
+            .await
+            .is_err()
+        );
+        assert!(
+            set.call(SetVariableArgs {
+                name: "v0".into(),
+                value: Some("updated".into()),
+            })
+            .await
+            .is_ok()
+        );
+    }
+}
@@ -0,0 +1,837 @@
+//! User-defined REST tools.
+//!
+//! A [`CustomHttpToolConfig`] declares a tool entirely in settings: a name,
+//! an HTTP method, a URL template with `{param}` placeholders, headers that
+//! may reference user secrets as `{{secret:KEY}}`, and a JSON schema for the
+//! parameters. [`CustomHttpTool`] turns a config into an agent tool whose
+//! requests go through the shared [`HttpPolicy`].
+//!
+//! Arguments that fill a URL placeholder are percent-encoded into the path;
+//! the rest become query parameters (GET/DELETE) or a JSON body
+//! (POST/PUT/PATCH), unless the config records another location for them
+//! (as tools imported from OpenAPI do). Requests that can modify remote state
+//! ask for approval like other side-effecting tools.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::ToolDyn;
+use rig_core::wasm_compat::WasmBoxedFuture;
+use serde::{Deserialize, Serialize};
+
+use crate::models::execution_approval_store::{PendingApprovals, request_execution_approval};
+use crate::services::http_policy::{HttpPolicy, HttpRequest};
+use crate::settings::models::execution_settings::ApprovalMode;
+use crate::tools::ToolError;
+
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Maximum response body size returned to the model.
+const MAX_RESPONSE_BYTES: usize = 100_000;
+
+/// Prefix/suffix of a secret reference in a header value.
+const SECRET_REF_OPEN: &str = "{{secret:";
+const SECRET_REF_CLOSE: &str = "}}";
+
+/// HTTP method of a user-defined tool.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CustomHttpMethod {
+    #[default]
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl CustomHttpMethod {
+    pub const ALL: [CustomHttpMethod; 5] = [
+        CustomHttpMethod::Get,
+        CustomHttpMethod::Post,
+        CustomHttpMethod::Put,
+        CustomHttpMethod::Patch,
+        CustomHttpMethod::Delete,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CustomHttpMethod::Get => "GET",
+            CustomHttpMethod::Post => "POST",
+            CustomHttpMethod::Put => "PUT",
+            CustomHttpMethod::Patch => "PATCH",
+            CustomHttpMethod::Delete => "DELETE",
+        }
+    }
+
+    /// Parse a method name case-insensitively.
+    pub fn parse(method: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|m| m.as_str().eq_ignore_ascii_case(method.trim()))
+    }
+
+    /// Whether leftover arguments are sent as a JSON body (instead of a query string).
+    pub fn sends_body(self) -> bool {
+        matches!(
+            self,
+            CustomHttpMethod::Post | CustomHttpMethod::Put | CustomHttpMethod::Patch
+        )
+    }
+
+    /// Whether a call may change remote state and therefore needs approval.
+    pub fn is_mutating(self) -> bool {
+        self != CustomHttpMethod::Get
+    }
+
+    fn to_reqwest(self) -> reqwest::Method {
+        match self {
+            CustomHttpMethod::Get => reqwest::Method::GET,
+            CustomHttpMethod::Post => reqwest::Method::POST,
+            CustomHttpMethod::Put => reqwest::Method::PUT,
+            CustomHttpMethod::Patch => reqwest::Method::PATCH,
+            CustomHttpMethod::Delete => reqwest::Method::DELETE,
+        }
+    }
+}
+
+/// A request header; the value may contain `{{secret:KEY}}` references.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomHttpHeader {
+    pub name: String,
+    pub value: String,
+}
+
+impl CustomHttpHeader {
+    /// Whether the value references a secret.
+    pub fn has_secret(&self) -> bool {
+        self.value.contains(SECRET_REF_OPEN)
+    }
+}
+
+/// Where a tool argument is sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArgumentLocation {
+    /// Fills the URL placeholder of the same name
+    Path,
+    Query,
+    Header,
+    /// A field of the JSON body
+    Body,
+}
+
+/// Declarative definition of a user-defined REST tool, stored in the
+/// execution settings.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomHttpToolConfig {
+    pub id: String,
+    /// Tool name shown to the model (letters, digits, `_` and `-`).
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub method: CustomHttpMethod,
+    /// URL with `{param}` placeholders, e.g. `https://api.github.com/repos/{owner}/{repo}/issues`.
+    pub url_template: String,
+    #[serde(default)]
+    pub headers: Vec<CustomHttpHeader>,
+    /// JSON schema (`"type": "object"`) describing the tool arguments.
+    #[serde(default = "empty_parameters_schema")]
+    pub parameters: serde_json::Value,
+    /// Where each argument goes, for tools imported from an OpenAPI spec.
+    /// Arguments not listed here fill URL placeholders, or go to the query
+    /// string (GET/DELETE) or JSON body (POST/PUT/PATCH).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub argument_locations: BTreeMap<String, ArgumentLocation>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Allow requests to localhost and private networks (e.g. a local dev server).
+    #[serde(default)]
+    pub allow_private_network: bool,
+    /// Title of the OpenAPI spec this tool was imported from; `None` for
+    /// tools defined by hand.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+pub(crate) fn default_true() -> bool {
+    true
+}
+
+pub fn empty_parameters_schema() -> serde_json::Value {
+    serde_json::json!({ "type": "object", "properties": {} })
+}
+
+impl CustomHttpToolConfig {
+    pub fn new(
+        name: impl Into<String>,
+        method: CustomHttpMethod,
+        url_template: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.into(),
+            description: String::new(),
+            method,
+            url_template: url_template.into(),
+            headers: Vec::new(),
+            parameters: empty_parameters_schema(),
+            argument_locations: BTreeMap::new(),
+            enabled: true,
+            allow_private_network: false,
+            source: None,
+        }
+    }
+
+    /// Placeholder names used in the URL template, in order of appearance.
+    pub fn url_placeholders(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut rest = self.url_template.as_str();
+        while let Some(start) = rest.find('{') {
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('}') else { break };
+            let name = after[..end].trim();
+            if !name.is_empty() && !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+            rest = &after[end + 1..];
+        }
+        names
+    }
+
+    /// Where the argument `name` goes when it does not fill a URL placeholder.
+    pub fn argument_location(&self, name: &str) -> ArgumentLocation {
+        match self.argument_locations.get(name) {
+            Some(location) if *location != ArgumentLocation::Path => *location,
+            _ if self.method.sends_body() => ArgumentLocation::Body,
+            _ => ArgumentLocation::Query,
+        }
+    }
+
+    /// Check the definition before it is saved or turned into a tool.
+    pub fn validate(&self) -> Result<(), String> {
+        validate_http_tool(&self.name, "URL", &self.url_template, &self.headers)?;
+        if self.parameters.get("type").and_then(|t| t.as_str()) != Some("object") {
+            return Err(
+                "Parameter schema must be a JSON object schema (\"type\": \"object\")".into(),
+            );
+        }
+        let properties = self.parameters.get("properties");
+        for placeholder in self.url_placeholders() {
+            if properties.and_then(|p| p.get(&placeholder)).is_none() {
+                return Err(format!(
+                    "URL placeholder {{{placeholder}}} is not declared in the parameter schema"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Object schema with one required string property per URL placeholder,
+    /// used when the user does not provide a schema.
+    pub fn placeholder_schema(&self) -> serde_json::Value {
+        let placeholders = self.url_placeholders();
+        let properties: serde_json::Map<String, serde_json::Value> = placeholders
+            .iter()
+            .map(|name| (name.clone(), serde_json::json!({ "type": "string" })))
+            .collect();
+        serde_json::json!({
+            "type": "object",
+            "properties": properties,
+            "required": placeholders,
+        })
+    }
+
+    /// Secret keys referenced by the header values.
+    pub fn referenced_secrets(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+        for header in &self.headers {
+            let mut rest = header.value.as_str();
+            while let Some(start) = rest.find(SECRET_REF_OPEN) {
+                let after = &rest[start + SECRET_REF_OPEN.len()..];
+                let Some(end) = after.find(SECRET_REF_CLOSE) else {
+                    break;
+                };
+                keys.push(after[..end].trim().to_string());
+                rest = &after[end + SECRET_REF_CLOSE.len()..];
+            }
+        }
+        keys
+    }
+}
+
+/// Checks shared by the user-defined HTTP tools (this one and
+/// `graphql_tool`): a name the model accepts, an http(s) `url` (called
+/// `what` in the error) and non-empty header names.
+pub(crate) fn validate_http_tool(
+    name: &str,
+    what: &str,
+    url: &str,
+    headers: &[CustomHttpHeader],
+) -> Result<(), String> {
+    if name.is_empty()
+        || name.len() > 64
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err("Tool name must be 1-64 characters of letters, digits, '_' or '-'".to_string());
+    }
+    let url = url.trim();
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(format!("{what} must start with http:// or https://"));
+    }
+    if headers.iter().any(|h| h.name.trim().is_empty()) {
+        return Err("Header names must not be empty".to_string());
+    }
+    Ok(())
+}
+
+/// Parse `Name: value` lines, as entered in the settings UI, into headers.
+pub fn parse_header_lines(text: &str) -> Result<Vec<CustomHttpHeader>, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("Header line '{line}' must look like 'Name: value'"))?;
+            Ok(CustomHttpHeader {
+                name: name.trim().to_string(),
+                value: value.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Inverse of [`parse_header_lines`].
+pub fn format_header_lines(headers: &[CustomHttpHeader]) -> String {
+    headers
+        .iter()
+        .map(|h| format!("{}: {}", h.name, h.value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Result returned to the model.
+#[derive(Debug, Serialize)]
+struct CustomHttpOutput {
+    status: u16,
+    url: String,
+    /// Parsed JSON when the response is JSON, otherwise the raw text.
+    body: serde_json::Value,
+    truncated: bool,
+}
+
+/// Agent tool backed by a [`CustomHttpToolConfig`].
+#[derive(Clone)]
+pub struct CustomHttpTool {
+    config: CustomHttpToolConfig,
+    secrets: HashMap<String, String>,
+    client: reqwest::Client,
+    pending_approvals: PendingApprovals,
+    approval_mode: ApprovalMode,
+}
+
+impl CustomHttpTool {
+    pub fn new(
+        config: CustomHttpToolConfig,
+        secrets: HashMap<String, String>,
+        pending_approvals: PendingApprovals,
+        approval_mode: ApprovalMode,
+    ) -> Self {
+        Self {
+            config,
+            secrets,
+            client: crate::services::http_client::no_redirect_client(REQUEST_TIMEOUT_SECS),
+            pending_approvals,
+            approval_mode,
+        }
+    }
+
+    /// Turn the enabled, valid configs into tools. Definitions whose name
+    /// collides with `reserved_names` or an earlier definition are skipped.
+    pub fn from_configs(
+        configs: &[CustomHttpToolConfig],
+        reserved_names: &HashSet<String>,
+        secrets: &[(String, String)],
+        pending_approvals: PendingApprovals,
+        approval_mode: ApprovalMode,
+    ) -> Vec<CustomHttpTool> {
+        let secrets: HashMap<String, String> = secrets.iter().cloned().collect();
+        let mut seen = HashSet::new();
+        configs
+            .iter()
+            .filter(|config| config.enabled)
+            .filter(|config| match config.validate() {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::warn!(tool = %config.name, error = %e, "Skipping invalid custom HTTP tool");
+                    false
+                }
+            })
+            .filter(|config| {
+                let unique =
+                    !reserved_names.contains(&config.name) && seen.insert(config.name.clone());
+                if !unique {
+                    tracing::warn!(tool = %config.name, "Skipping custom HTTP tool with a duplicate name");
+                }
+                unique
+            })
+            .map(|config| {
+                CustomHttpTool::new(
+                    config.clone(),
+                    secrets.clone(),
+                    pending_approvals.clone(),
+                    approval_mode.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Build the request for `args` without sending it.
+    fn build_request(&self, args: &serde_json::Value) -> Result<HttpRequest, ToolError> {
+        let mut remaining = match args {
+            serde_json::Value::Object(map) => map.clone(),
+            serde_json::Value::Null => serde_json::Map::new(),
+            _ => {
+                return Err(ToolError::OperationFailed(
+                    "Arguments must be a JSON object".to_string(),
+                ));
+            }
+        };
+
+        let mut url = self.config.url_template.trim().to_string();
+        for placeholder in self.config.url_placeholders() {
+            let value = remaining.remove(&placeholder).ok_or_else(|| {
+                ToolError::OperationFailed(format!("Missing required argument '{placeholder}'"))
+            })?;
+            url = url.replace(
+                &format!("{{{placeholder}}}"),
+                &encode_path_value(&value_to_string(&value)),
+            );
+        }
+        remaining.retain(|_, v| !v.is_null());
+
+        let mut headers = Vec::with_capacity(self.config.headers.len() + 1);
+        let mut secret_headers = Vec::new();
+        for header in &self.config.headers {
+            let value = substitute_secrets(&header.value, &self.secrets)
+                .map_err(ToolError::OperationFailed)?;
+            if header.has_secret() {
+                secret_headers.push(header.name.trim().to_string());
+            }
+            headers.push((header.name.trim().to_string(), value));
+        }
+
+        let mut query = serde_json::Map::new();
+        let mut fields = serde_json::Map::new();
+        for (name, value) in remaining {
+            match self.config.argument_location(&name) {
+                ArgumentLocation::Header => headers.push((name, value_to_string(&value))),
+                ArgumentLocation::Body => {
+                    fields.insert(name, value);
+                }
+                ArgumentLocation::Path | ArgumentLocation::Query => {
+                    query.insert(name, value);
+                }
+            }
+        }
+
+        let mut body = None;
+        if !fields.is_empty() {
+            if !headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            {
+                headers.push(("Content-Type".to_string(), "application/json".to_string()));
+            }
+            body = Some(serde_json::Value::Object(fields).to_string());
+        }
+        if !query.is_empty() {
+            let mut parsed = reqwest::Url::parse(&url)
+                .map_err(|e| ToolError::OperationFailed(format!("Invalid URL: {e}")))?;
+            {
+                let mut pairs = parsed.query_pairs_mut();
+                for (key, value) in &query {
+                    match value {
+                        serde_json::Value::Array(items) => {
+                            for item in items {
+                                pairs.append_pair(key, &value_to_string(item));
+                            }
+                        }
+                        other => {
+                            pairs.append_pair(key, &value_to_string(other));
+                        }
+                    }
+                }
+            }
+            url = parsed.to_string();
+        }
+
+        Ok(HttpRequest {
+            method: self.config.method.to_reqwest(),
+            url,
+            headers,
+            body,
+            secret_headers,
+        })
+    }
+
+    async fn execute(&self, args: String) -> Result<CustomHttpOutput, ToolError> {
+        let args: serde_json::Value = if args.trim().is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::from_str(&args)
+                .map_err(|e| ToolError::OperationFailed(format!("Invalid arguments: {e}")))?
+        };
+        let request = self.build_request(&args)?;
+        let url = request.url.clone();
+
+        if self.config.method.is_mutating() {
+            let approved = request_execution_approval(
+                &self.pending_approvals,
+                &self.approval_mode,
+                &format!(
+                    "[{}] {} {}",
+                    self.config.name,
+                    self.config.method.as_str(),
+                    url
+                ),
+                false,
+            )
+            .await?;
+            if !approved {
+                return Err(ToolError::OperationFailed(
+                    "Request denied by user".to_string(),
+                ));
+            }
+        }
+
+        tracing::info!(tool = %self.config.name, method = self.config.method.as_str(), url = %url, "Calling custom HTTP tool");
+        let policy = HttpPolicy {
+            allow_private_hosts: self.config.allow_private_network,
+            max_response_bytes: MAX_RESPONSE_BYTES,
+        };
+        let response = policy
+            .send_text(&self.client, request)
+            .await
+            .map_err(|e| ToolError::OperationFailed(e.to_string()))?;
+
+        let body = if response.content_type.contains("json") && !response.truncated {
+            serde_json::from_str(&response.body).unwrap_or(serde_json::Value::String(response.body))
+        } else {
+            serde_json::Value::String(response.body)
+        };
+        Ok(CustomHttpOutput {
+            status: response.status,
+            url,
+            body,
+            truncated: response.truncated,
+        })
+    }
+}
+
+impl ToolDyn for CustomHttpTool {
+    fn name(&self) -> String {
+        self.config.name.clone()
+    }
+
+    fn definition<'a>(&'a self, _prompt: String) -> WasmBoxedFuture<'a, ToolDefinition> {
+        Box::pin(async move {
+            let description = if self.config.description.trim().is_empty() {
+                format!(
+                    "Call {} {}",
+                    self.config.method.as_str(),
+                    self.config.url_template
+                )
+            } else {
+                self.config.description.clone()
+            };
+            ToolDefinition {
+                name: self.config.name.clone(),
+                description,
+                parameters: self.config.parameters.clone(),
+            }
+        })
+    }
+
+    fn call<'a>(
+        &'a self,
+        args: String,
+    ) -> WasmBoxedFuture<'a, Result<String, rig_core::tool::ToolError>> {
+        Box::pin(async move {
+            let output = self
+                .execute(args)
+                .await
+                .map_err(|e| rig_core::tool::ToolError::ToolCallError(Box::new(e)))?;
+            serde_json::to_string(&output).map_err(rig_core::tool::ToolError::JsonError)
+        })
+    }
+}
+
+/// Replace `{{secret:KEY}}` references with the secret values.
+pub(crate) fn substitute_secrets(
+    value: &str,
+    secrets: &HashMap<String, String>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find(SECRET_REF_OPEN) {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + SECRET_REF_OPEN.len()..];
+        let end = after
+            .find(SECRET_REF_CLOSE)
+            .ok_or_else(|| "Unterminated {{secret:...}} reference in header".to_string())?;
+        let key = after[..end].trim();
+        let secret = secrets
+            .get(key)
+            .ok_or_else(|| format!("Secret '{key}' is not defined in Settings → Secrets"))?;
+        out.push_str(secret);
+        rest = &after[end + SECRET_REF_CLOSE.len()..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn value_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters.
+fn encode_path_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExecutionApprovalStore;
+    use serde_json::json;
+
+    fn issues_config(method: CustomHttpMethod) -> CustomHttpToolConfig {
+        let mut config = CustomHttpToolConfig::new(
+            "github_issues",
+            method,
+            "https://api.github.com/repos/{owner}/{repo}/issues",
+        );
+        config.headers.push(CustomHttpHeader {
+            name: "Authorization".into(),
+            value: "Bearer {{secret:GITHUB_TOKEN}}".into(),
+        });
+        config.parameters = json!({
+            "type": "object",
+            "properties": {
+                "owner": { "type": "string" },
+                "repo": { "type": "string" },
+                "state": { "type": "string" },
+                "labels": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["owner", "repo"]
+        });
+        config
+    }
+
+    fn tool(config: CustomHttpToolConfig) -> CustomHttpTool {
+        let secrets = HashMap::from([("GITHUB_TOKEN".to_string(), "t0ken".to_string())]);
+        CustomHttpTool::new(
+            config,
+            secrets,
+            ExecutionApprovalStore::new().get_pending_approvals(),
+            ApprovalMode::AutoApproveAll,
+        )
+    }
+
+    #[test]
+    fn validate_checks_name_url_and_placeholders() {
+        assert!(issues_config(CustomHttpMethod::Get).validate().is_ok());
+
+        let mut bad = issues_config(CustomHttpMethod::Get);
+        bad.name = "has space".into();
+        assert!(bad.validate().is_err());
+
+        let mut bad = issues_config(CustomHttpMethod::Get);
+        bad.url_template = "ftp://example.com".into();
+        assert!(bad.validate().is_err());
+
+        let mut bad = issues_config(CustomHttpMethod::Get);
+        bad.parameters = empty_parameters_schema();
+        let err = bad.validate().unwrap_err();
+        assert!(err.contains("{owner}"), "{err}");
+    }
+
+    #[test]
+    fn get_fills_path_and_moves_rest_to_query() {
+        let request = tool(issues_config(CustomHttpMethod::Get))
+            .build_request(&json!({
+                "owner": "rust lang",
+                "repo": "rust",
+                "state": "open",
+                "labels": ["bug", "A-io"]
+            }))
+            .unwrap();
+        assert_eq!(request.method, reqwest::Method::GET);
+        let url = reqwest::Url::parse(&request.url).unwrap();
+        assert_eq!(url.path(), "/repos/rust%20lang/rust/issues");
+        let mut query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        query.sort();
+        let expected = [("labels", "A-io"), ("labels", "bug"), ("state", "open")];
+        assert_eq!(query, expected.map(|(k, v)| (k.to_string(), v.to_string())));
+        assert_eq!(
+            request.headers,
+            vec![("Authorization".to_string(), "Bearer t0ken".to_string())]
+        );
+        assert_eq!(request.secret_headers, vec!["Authorization"]);
+        assert!(request.body.is_none());
+    }
+
+    #[test]
+    fn post_sends_remaining_args_as_json_body() {
+        let request = tool(issues_config(CustomHttpMethod::Post))
+            .build_request(&json!({"owner": "o", "repo": "r", "state": "closed"}))
+            .unwrap();
+        assert_eq!(request.url, "https://api.github.com/repos/o/r/issues");
+        assert_eq!(request.body.as_deref(), Some(r#"{"state":"closed"}"#));
+        assert!(
+            request
+                .headers
+                .contains(&("Content-Type".to_string(), "application/json".to_string()))
+        );
+    }
+
+    #[test]
+    fn imported_post_sends_query_and_header_arguments_outside_the_body() {
+        let spec = crate::services::openapi_import::parse_openapi_spec(
+            r#"{
+                "openapi": "3.0.0",
+                "info": { "title": "Notes" },
+                "paths": { "/notes/{folder}": { "post": {
+                    "operationId": "createNote",
+                    "parameters": [
+                        { "name": "folder", "in": "path", "required": true },
+                        { "name": "notify", "in": "query", "schema": { "type": "boolean" } },
+                        { "name": "X-Request-Id", "in": "header" }
+                    ],
+                    "requestBody": { "content": { "application/json": { "schema": {
+                        "type": "object",
+                        "properties": { "text": { "type": "string" } }
+                    } } } }
+                } } }
+            }"#,
+        )
+        .unwrap();
+        let config = spec
+            .tool_configs(&[0], "https://notes.example", None)
+            .unwrap()
+            .remove(0);
+
+        let request = tool(config)
+            .build_request(&json!({
+                "folder": "inbox",
+                "notify": true,
+                "X-Request-Id": "r1",
+                "text": "Buy milk"
+            }))
+            .unwrap();
+        assert_eq!(request.method, reqwest::Method::POST);
+        assert_eq!(request.url, "https://notes.example/notes/inbox?notify=true");
+        assert_eq!(request.body.as_deref(), Some(r#"{"text":"Buy milk"}"#));
+        assert!(
+            request
+                .headers
+                .contains(&("X-Request-Id".to_string(), "r1".to_string()))
+        );
+    }
+
+    #[test]
+    fn missing_placeholder_and_unknown_secret_are_errors() {
+        let tool_ = tool(issues_config(CustomHttpMethod::Get));
+        assert!(tool_.build_request(&json!({"owner": "o"})).is_err());
+
+        let mut config = issues_config(CustomHttpMethod::Get);
+        config.headers[0].value = "{{secret:MISSING}}".into();
+        let err = tool(config)
+            .build_request(&json!({"owner": "o", "repo": "r"}))
+            .unwrap_err();
+        assert!(err.to_string().contains("MISSING"));
+    }
+
+    #[test]
+    fn from_configs_skips_disabled_invalid_and_reserved() {
+        let mut disabled = issues_config(CustomHttpMethod::Get);
+        disabled.name = "disabled_tool".into();
+        disabled.enabled = false;
+        let mut reserved = issues_config(CustomHttpMethod::Get);
+        reserved.name = "fetch".into();
+        let mut invalid = issues_config(CustomHttpMethod::Get);
+        invalid.name = String::new();
+        let configs = vec![
+            issues_config(CustomHttpMethod::Get),
+            issues_config(CustomHttpMethod::Get),
+            disabled,
+            reserved,
+            invalid,
+        ];
+
+        let tools = CustomHttpTool::from_configs(
+            &configs,
+            &HashSet::from(["fetch".to_string()]),
+            &[],
+            ExecutionApprovalStore::new().get_pending_approvals(),
+            ApprovalMode::AlwaysAsk,
+        );
+        let names: Vec<String> = tools.iter().map(ToolDyn::name).collect();
+        assert_eq!(names, vec!["github_issues"]);
+    }
+
+    #[test]
+    fn referenced_secrets_and_method_parsing() {
+        let config = issues_config(CustomHttpMethod::Get);
+        assert_eq!(config.referenced_secrets(), vec!["GITHUB_TOKEN"]);
+        assert_eq!(
+            CustomHttpMethod::parse("patch"),
+            Some(CustomHttpMethod::Patch)
+        );
+        assert_eq!(CustomHttpMethod::parse("TRACE"), None);
+    }
+
+    #[test]
+    fn header_lines_round_trip_and_placeholder_schema() {
+        let headers =
+            parse_header_lines("Authorization: Bearer {{secret:TOKEN}}\n\nAccept: a:b").unwrap();
+        assert_eq!(headers[1].value, "a:b");
+        assert_eq!(
+            parse_header_lines(&format_header_lines(&headers)).unwrap(),
+            headers
+        );
+        assert!(parse_header_lines("no colon").is_err());
+
+        let mut config = issues_config(CustomHttpMethod::Get);
+        config.parameters = config.placeholder_schema();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.parameters["required"], json!(["owner", "repo"]));
+    }
+
+    #[tokio::test]
+    async fn private_hosts_are_blocked_unless_allowed() {
+        let config = CustomHttpToolConfig::new(
+            "local_api",
+            CustomHttpMethod::Get,
+            "http://169.254.169.254/latest/meta-data/",
+        );
+        let err = tool(config).execute("{}".into()).await.unwrap_err();
+        assert!(err.to_string().contains("SSRF"), "{err}");
+    }
+}
@@ -0,0 +1,283 @@
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::Tool;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::models::execution_approval_store::{PendingApprovals, request_execution_approval};
+use crate::models::message_types::ToolSource;
+use crate::services::docker_service::{
+    ContainerInfo, DockerCommandOutput, DockerResources, DockerService, ImageInfo,
+    validate_identifier,
+};
+use crate::settings::models::execution_settings::ApprovalMode;
+use crate::tools::ToolError;
+use crate::tools::invoke_agent_tool::{InvokeAgentProgress, InvokeAgentProgressSlot};
+
+/// Default number of log lines returned by `logs`.
+const DEFAULT_LOG_TAIL: u32 = 200;
+
+#[derive(Deserialize, Serialize)]
+pub struct DockerArgs {
+    /// list_containers, list_images, logs, exec, compose_up or compose_down.
+    pub action: String,
+    /// Container name or ID (logs, exec).
+    #[serde(default)]
+    pub container: Option<String>,
+    /// Command and arguments to run (exec).
+    #[serde(default)]
+    pub command: Vec<String>,
+    /// Number of log lines (logs).
+    #[serde(default)]
+    pub tail: Option<u32>,
+    /// Include stopped containers (list_containers).
+    #[serde(default)]
+    pub all: bool,
+    /// Compose services to start or stop; empty means the whole stack.
+    #[serde(default)]
+    pub services: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum DockerOutput {
+    Containers { containers: Vec<ContainerInfo> },
+    Images { images: Vec<ImageInfo> },
+    Command(DockerCommandOutput),
+}
+
+/// Inspect and drive the local Docker daemon.
+///
+/// Listing and logs run without prompting; `exec` and compose actions go
+/// through the execution approval flow. Output of logs, exec and compose is
+/// streamed into the trace via the shared progress slot, and compose stacks
+/// that are started are recorded so conversation cleanup can stop them.
+#[derive(Clone)]
+pub struct DockerTool {
+    service: Arc<DockerService>,
+    pending_approvals: PendingApprovals,
+    approval_mode: ApprovalMode,
+    resources: DockerResources,
+    progress_slot: InvokeAgentProgressSlot,
+}
+
+impl DockerTool {
+    pub fn new(
+        service: Arc<DockerService>,
+        pending_approvals: PendingApprovals,
+        approval_mode: ApprovalMode,
+        resources: DockerResources,
+        progress_slot: InvokeAgentProgressSlot,
+    ) -> Self {
+        Self {
+            service,
+            pending_approvals,
+            approval_mode,
+            resources,
+            progress_slot,
+        }
+    }
+
+    fn send_progress(&self, event: InvokeAgentProgress) {
+        if let Some(tx) = self.progress_slot.lock().as_ref() {
+            let _ = tx.send(event);
+        }
+    }
+
+    async fn approve(&self, label: String) -> Result<(), ToolError> {
+        let approved =
+            request_execution_approval(&self.pending_approvals, &self.approval_mode, &label, false)
+                .await?;
+        if !approved {
+            return Err(ToolError::OperationFailed(
+                "Docker command denied by user".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Run a streaming operation, wrapping its output in Started/Text/Finished
+    /// progress events so the UI renders it as a live trace.
+    async fn streamed<F, Fut>(&self, summary: String, run: F) -> Result<DockerOutput, ToolError>
+    where
+        F: FnOnce(Box<dyn FnMut(&str) + Send>) -> Fut,
+        Fut: std::future::Future<Output = anyhow::Result<DockerCommandOutput>>,
+    {
+        self.send_progress(InvokeAgentProgress::Started {
+            agent_name: "docker".to_string(),
+            prompt: summary,
+            source: ToolSource::Local,
+        });
+        let slot = self.progress_slot.clone();
+        let on_chunk = Box::new(move |chunk: &str| {
+            if let Some(tx) = slot.lock().as_ref() {
+                let _ = tx.send(InvokeAgentProgress::Text(chunk.to_string()));
+            }
+        });
+        let result = run(on_chunk).await;
+        self.send_progress(InvokeAgentProgress::Finished {
+            success: matches!(&result, Ok(out) if out.exit_code.is_none_or(|code| code == 0)),
+            result: result.as_ref().err().map(|e| e.to_string()),
+        });
+        Ok(DockerOutput::Command(result?))
+    }
+}
+
+impl Tool for DockerTool {
+    const NAME: &'static str = "docker";
+    type Error = ToolError;
+    type Args = DockerArgs;
+    type Output = DockerOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "docker".to_string(),
+            description: "Work with the local Docker daemon. Actions: list_containers, \
+                         list_images, logs (container output), exec (run a command in a running \
+                         container, requires approval), compose_up / compose_down (docker compose \
+                         in the workspace directory, requires approval)."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["list_containers", "list_images", "logs", "exec", "compose_up", "compose_down"],
+                        "description": "The operation to perform"
+                    },
+                    "container": {
+                        "type": "string",
+                        "description": "Container name or ID (logs, exec)"
+                    },
+                    "command": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Command and arguments for exec, e.g. [\"ls\", \"-la\", \"/app\"]"
+                    },
+                    "tail": {
+                        "type": "integer",
+                        "description": "Number of log lines to return (logs). Default: 200"
+                    },
+                    "all": {
+                        "type": "boolean",
+                        "description": "Include stopped containers (list_containers)"
+                    },
+                    "services": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Compose services to act on. Omit for the whole stack."
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        match args.action.as_str() {
+            "list_containers" => Ok(DockerOutput::Containers {
+                containers: self.service.list_containers(args.all).await?,
+            }),
+            "list_images" => Ok(DockerOutput::Images {
+                images: self.service.list_images().await?,
+            }),
+            "logs" => {
+                let container = required_container(&args)?;
+                let tail = args.tail.unwrap_or(DEFAULT_LOG_TAIL);
+                self.streamed(format!("logs {container} (tail {tail})"), |on_chunk| {
+                    self.service.logs(&container, tail, on_chunk)
+                })
+                .await
+            }
+            "exec" => {
+                let container = required_container(&args)?;
+                if args.command.is_empty() {
+                    return Err(ToolError::OperationFailed(
+                        "exec requires a 'command'".to_string(),
+                    ));
+                }
+                let command_line = args.command.join(" ");
+                self.approve(format!("[docker exec {container}] {command_line}"))
+                    .await?;
+                self.streamed(format!("exec {container}: {command_line}"), |on_chunk| {
+                    self.service.exec(&container, args.command, on_chunk)
+                })
+                .await
+            }
+            "compose_up" | "compose_down" => {
+                let dir = self.service.workspace_dir().map(|d| d.to_path_buf());
+                let compose_args = compose_args(&args.action, &args.services)?;
+                self.approve(format!("[docker] compose {}", compose_args.join(" ")))
+                    .await?;
+                let output = self
+                    .streamed(format!("compose {}", compose_args.join(" ")), |on_chunk| {
+                        self.service.compose(compose_args.clone(), on_chunk)
+                    })
+                    .await?;
+                if let Some(dir) = dir {
+                    let mut resources = self.resources.lock();
+                    if args.action == "compose_up" {
+                        if !resources.compose_dirs.contains(&dir) {
+                            resources.compose_dirs.push(dir);
+                        }
+                    } else if args.services.is_empty() {
+                        resources.compose_dirs.retain(|d| d != &dir);
+                    }
+                }
+                Ok(output)
+            }
+            other => Err(ToolError::OperationFailed(format!(
+                "Unknown docker action '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+fn required_container(args: &DockerArgs) -> Result<String, ToolError> {
+    let container = args.container.clone().ok_or_else(|| {
+        ToolError::OperationFailed(format!("{} requires a 'container'", args.action))
+    })?;
+    validate_identifier(&container, "container")?;
+    Ok(container)
+}
+
+/// Build `docker compose` arguments for compose_up / compose_down.
+fn compose_args(action: &str, services: &[String]) -> Result<Vec<String>, ToolError> {
+    for service in services {
+        validate_identifier(service, "service")?;
+    }
+    let mut args: Vec<String> = if action == "compose_up" {
+        vec!["up".into(), "-d".into()]
+    } else if services.is_empty() {
+        vec!["down".into()]
+    } else {
+        // `down` works on the whole project; stop + remove individual services instead.
+        vec!["rm".into(), "-s".into(), "-f".into()]
+    };
+    args.extend(services.iter().cloned());
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compose_args_for_up_and_down() {
+        assert_eq!(compose_args("compose_up", &[]).unwrap(), vec!["up", "-d"]);
+        assert_eq!(
+            compose_args("compose_up", &["db".to_string()]).unwrap(),
+            vec!["up", "-d", "db"]
+        );
+        assert_eq!(compose_args("compose_down", &[]).unwrap(), vec!["down"]);
+        assert_eq!(
+            compose_args("compose_down", &["db".to_string()]).unwrap(),
+            vec!["rm", "-s", "-f", "db"]
+        );
+    }
+
+    #[test]
+    fn compose_args_reject_flag_services() {
+        assert!(compose_args("compose_up", &["--build".to_string()]).is_err());
+    }
+}
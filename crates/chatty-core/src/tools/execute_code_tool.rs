@@ -6,6 +6,7 @@ use std::sync::Arc;
 use crate::models::message_types::ExecutionEngine;
 use crate::sandbox::backend::Language;
 use crate::sandbox::manager::SandboxManager;
+use crate::services::{CANCELLED_BY_USER, ToolCancellation};
 use crate::tools::ToolError;
 
 // ── Args / Output ────────────────────────────────────────────────────────────
@@ -46,11 +47,21 @@ pub struct ExecuteCodeOutput {
 #[derive(Clone)]
 pub struct ExecuteCodeTool {
     manager: Arc<SandboxManager>,
+    cancellation: ToolCancellation,
 }
 
 impl ExecuteCodeTool {
     pub fn new(manager: Arc<SandboxManager>) -> Self {
-        Self { manager }
+        Self {
+            manager,
+            cancellation: ToolCancellation::default(),
+        }
+    }
+
+    /// Stop the running code when the user stops the stream.
+    pub fn with_cancellation(mut self, cancellation: ToolCancellation) -> Self {
+        self.cancellation = cancellation;
+        self
     }
 }
 
@@ -89,10 +100,22 @@ impl Tool for ExecuteCodeTool {
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
         let language = Language::parse(&args.language);
-        let result = self
-            .manager
-            .execute(&args.code, &language, args.expose_port)
-            .await?;
+        let Some(result) = self
+            .cancellation
+            .run(
+                self.manager
+                    .execute(&args.code, &language, args.expose_port),
+            )
+            .await
+        else {
+            // A Docker exec keeps running after its future is dropped, so the
+            // container goes too; the next run starts a fresh one.
+            if let Err(e) = self.manager.discard(&language).await {
+                tracing::warn!(error = ?e, "Failed to remove sandbox after cancellation");
+            }
+            return Err(ToolError::OperationFailed(CANCELLED_BY_USER.to_string()));
+        };
+        let result = result?;
 
         Ok(ExecuteCodeOutput {
             stdout: result.stdout,
@@ -1,10 +1,11 @@
 use rig_core::completion::ToolDefinition;
 use rig_core::tool::Tool;
 use serde::{Deserialize, Serialize};
-use std::net::IpAddr;
 use std::path::PathBuf;
 use tracing::{info, warn};
 
+use crate::services::http_policy::{HttpPolicy, HttpRequest};
+use crate::services::{CANCELLED_BY_USER, ToolCancellation};
 use crate::tools::ToolError;
 
 /// Default maximum response length in characters
@@ -54,6 +55,8 @@ pub struct FetchTool {
     /// Optional workspace directory for saving downloaded binary files.
     /// When None, binary content returns an error asking the user to configure a workspace.
     workspace_dir: Option<PathBuf>,
+    /// Aborts the in-flight request when the user stops the stream.
+    cancellation: ToolCancellation,
 }
 
 impl FetchTool {
@@ -62,8 +65,14 @@ impl FetchTool {
         Self {
             client,
             workspace_dir,
+            cancellation: ToolCancellation::default(),
         }
     }
+
+    pub fn with_cancellation(mut self, cancellation: ToolCancellation) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
 }
 
 impl Tool for FetchTool {
@@ -99,74 +108,27 @@ impl Tool for FetchTool {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        self.cancellation
+            .run(self.fetch(args))
+            .await
+            .unwrap_or_else(|| Err(ToolError::OperationFailed(CANCELLED_BY_USER.to_string())))
+    }
+}
+
+impl FetchTool {
+    /// Run the request; dropping the future aborts it.
+    async fn fetch(&self, args: FetchToolArgs) -> Result<FetchToolOutput, ToolError> {
         let url = args.url.trim().to_string();
         let max_length = args.max_length.unwrap_or(DEFAULT_MAX_LENGTH);
 
-        // Validate URL scheme
-        if !url.starts_with("http://") && !url.starts_with("https://") {
-            return Err(ToolError::OperationFailed(
-                "URL must start with http:// or https://".to_string(),
-            ));
-        }
-
-        // SSRF protection: block requests to private/internal networks
-        validate_url_host(&url)?;
-
         info!(url = %url, max_length = max_length, "Fetching URL");
 
-        // Perform GET request, following redirects manually (max 10 hops)
-        // to validate each redirect target against the private-host denylist.
-        let mut current_url = url.clone();
-        let mut response = self
-            .client
-            .get(&current_url)
-            .send()
+        // The shared HTTP policy validates the scheme and blocks private/internal
+        // hosts (SSRF protection), re-checking every redirect hop.
+        let (response, _) = HttpPolicy::default()
+            .send(&self.client, HttpRequest::get(url.clone()))
             .await
-            .map_err(|e| ToolError::OperationFailed(format!("Request failed: {}", e)))?;
-
-        for _ in 0..10 {
-            if !response.status().is_redirection() {
-                break;
-            }
-            let location = response
-                .headers()
-                .get(reqwest::header::LOCATION)
-                .and_then(|v| v.to_str().ok())
-                .ok_or_else(|| {
-                    ToolError::OperationFailed(
-                        "Redirect response missing Location header".to_string(),
-                    )
-                })?
-                .to_string();
-
-            // Resolve relative redirects against the current URL
-            let next_url = if location.starts_with("http://") || location.starts_with("https://") {
-                location
-            } else {
-                // Relative URL — resolve against current
-                let base = reqwest::Url::parse(&current_url).map_err(|e| {
-                    ToolError::OperationFailed(format!("Invalid base URL for redirect: {}", e))
-                })?;
-                base.join(&location)
-                    .map_err(|e| {
-                        ToolError::OperationFailed(format!("Invalid redirect URL: {}", e))
-                    })?
-                    .to_string()
-            };
-
-            // SSRF protection: validate redirect target
-            validate_url_host(&next_url)?;
-
-            info!(from = %current_url, to = %next_url, "Following redirect");
-            current_url = next_url;
-
-            response = self
-                .client
-                .get(&current_url)
-                .send()
-                .await
-                .map_err(|e| ToolError::OperationFailed(format!("Redirect failed: {}", e)))?;
-        }
+            .map_err(|e| ToolError::OperationFailed(e.to_string()))?;
 
         let status = response.status().as_u16();
         let content_type = response
@@ -235,9 +197,7 @@ impl Tool for FetchTool {
             saved_to: None,
         })
     }
-}
 
-impl FetchTool {
     /// Handle binary responses by saving them to the workspace directory.
     async fn handle_binary_response(
         &self,
@@ -390,114 +350,6 @@ fn unique_path(path: PathBuf) -> PathBuf {
     parent.join(format!("{}-{}.{}", stem, uuid::Uuid::new_v4(), ext))
 }
 
-/// Validate that a URL does not target private, internal, or reserved network hosts.
-///
-/// Blocks loopback (127.x.x.x, ::1), RFC-1918 private ranges (10.x, 172.16-31.x, 192.168.x),
-/// link-local (169.254.x.x, fe80::), cloud metadata endpoints (169.254.169.254), and other
-/// reserved addresses to prevent SSRF attacks.
-fn validate_url_host(url: &str) -> Result<(), ToolError> {
-    let parsed = reqwest::Url::parse(url)
-        .map_err(|e| ToolError::OperationFailed(format!("Invalid URL: {}", e)))?;
-
-    let host = parsed
-        .host_str()
-        .ok_or_else(|| ToolError::OperationFailed("URL has no host".to_string()))?;
-
-    // Check hostname-based blocklist first (catches localhost even without DNS)
-    if is_blocked_hostname(host) {
-        return Err(ToolError::OperationFailed(format!(
-            "Access denied: requests to '{}' are blocked for security (SSRF protection)",
-            host
-        )));
-    }
-
-    // Try to parse as IP address directly.
-    // Strip brackets for IPv6: host_str() returns "[::1]" but IpAddr expects "::1".
-    let ip_str = host.trim_start_matches('[').trim_end_matches(']');
-    if let Ok(ip) = ip_str.parse::<IpAddr>()
-        && is_private_ip(&ip)
-    {
-        return Err(ToolError::OperationFailed(format!(
-            "Access denied: requests to private/internal IP '{}' are blocked for security (SSRF protection)",
-            ip
-        )));
-    }
-
-    // For hostnames, resolve to IP and check the resolved address.
-    // This catches DNS rebinding / split-horizon attacks where a public hostname
-    // resolves to a private IP.
-    if ip_str.parse::<IpAddr>().is_err() {
-        // Use std::net for synchronous resolution (sufficient for validation)
-        if let Ok(addrs) = std::net::ToSocketAddrs::to_socket_addrs(&(host, 80)) {
-            for addr in addrs {
-                if is_private_ip(&addr.ip()) {
-                    warn!(
-                        host = %host,
-                        resolved_ip = %addr.ip(),
-                        "Blocked DNS-resolved private IP"
-                    );
-                    return Err(ToolError::OperationFailed(format!(
-                        "Access denied: '{}' resolves to private/internal IP {} (SSRF protection)",
-                        host,
-                        addr.ip()
-                    )));
-                }
-            }
-        }
-    }
-
-    Ok(())
-}
-
-/// Check if a hostname string is a known-blocked name (case-insensitive).
-fn is_blocked_hostname(host: &str) -> bool {
-    let h = host.to_lowercase();
-    h == "localhost"
-        || h == "metadata.google.internal"  // GCP metadata
-        || h.ends_with(".internal")
-        || h.ends_with(".local")
-}
-
-/// Check if an IP address belongs to a private, loopback, link-local, or otherwise
-/// reserved network range that should not be accessible from the fetch tool.
-fn is_private_ip(ip: &IpAddr) -> bool {
-    match ip {
-        IpAddr::V4(v4) => {
-            let octets = v4.octets();
-            // 127.0.0.0/8 — loopback
-            octets[0] == 127
-            // 10.0.0.0/8 — RFC-1918 private
-            || octets[0] == 10
-            // 172.16.0.0/12 — RFC-1918 private
-            || (octets[0] == 172 && (16..=31).contains(&octets[1]))
-            // 192.168.0.0/16 — RFC-1918 private
-            || (octets[0] == 192 && octets[1] == 168)
-            // 169.254.0.0/16 — link-local (includes AWS/GCP/Azure metadata at 169.254.169.254)
-            || (octets[0] == 169 && octets[1] == 254)
-            // 0.0.0.0/8 — "this" network
-            || octets[0] == 0
-            // 100.64.0.0/10 — shared address space (CGN, often internal)
-            || (octets[0] == 100 && (64..=127).contains(&octets[1]))
-            // 198.18.0.0/15 — benchmarking
-            || (octets[0] == 198 && (18..=19).contains(&octets[1]))
-            // 224.0.0.0/4 — multicast
-            || octets[0] >= 224
-        }
-        IpAddr::V6(v6) => {
-            // ::1 — loopback
-            v6.is_loopback()
-            // fe80::/10 — link-local
-            || (v6.segments()[0] & 0xffc0) == 0xfe80
-            // fc00::/7 — unique local (ULA, RFC-4193)
-            || (v6.segments()[0] & 0xfe00) == 0xfc00
-            // :: — unspecified
-            || v6.is_unspecified()
-            // ::ffff:x.x.x.x — IPv4-mapped, check the embedded v4 address
-            || v6.to_ipv4_mapped().map(|v4| is_private_ip(&IpAddr::V4(v4))).unwrap_or(false)
-        }
-    }
-}
-
 /// Simple heuristic to detect HTML content when content-type is missing or ambiguous
 fn looks_like_html(body: &str) -> bool {
     let trimmed = body.trim_start();
@@ -775,96 +627,6 @@ mod tests {
         assert_eq!(unique_path(path.clone()), path);
     }
 
-    // --- SSRF protection tests ---
-
-    #[test]
-    fn test_is_private_ip_loopback() {
-        assert!(is_private_ip(&"127.0.0.1".parse().unwrap()));
-        assert!(is_private_ip(&"127.0.0.2".parse().unwrap()));
-        assert!(is_private_ip(&"::1".parse().unwrap()));
-    }
-
-    #[test]
-    fn test_is_private_ip_rfc1918() {
-        assert!(is_private_ip(&"10.0.0.1".parse().unwrap()));
-        assert!(is_private_ip(&"10.255.255.255".parse().unwrap()));
-        assert!(is_private_ip(&"172.16.0.1".parse().unwrap()));
-        assert!(is_private_ip(&"172.31.255.255".parse().unwrap()));
-        assert!(is_private_ip(&"192.168.0.1".parse().unwrap()));
-        assert!(is_private_ip(&"192.168.255.255".parse().unwrap()));
-    }
-
-    #[test]
-    fn test_is_private_ip_link_local_and_metadata() {
-        // AWS/GCP/Azure metadata endpoint
-        assert!(is_private_ip(&"169.254.169.254".parse().unwrap()));
-        assert!(is_private_ip(&"169.254.0.1".parse().unwrap()));
-    }
-
-    #[test]
-    fn test_is_private_ip_public() {
-        assert!(!is_private_ip(&"8.8.8.8".parse().unwrap()));
-        assert!(!is_private_ip(&"1.1.1.1".parse().unwrap()));
-        assert!(!is_private_ip(&"93.184.216.34".parse().unwrap()));
-    }
-
-    #[test]
-    fn test_is_private_ip_other_reserved() {
-        assert!(is_private_ip(&"0.0.0.0".parse().unwrap()));
-        assert!(is_private_ip(&"100.64.0.1".parse().unwrap())); // CGN
-        assert!(is_private_ip(&"224.0.0.1".parse().unwrap())); // multicast
-        assert!(is_private_ip(&"255.255.255.255".parse().unwrap()));
-    }
-
-    #[test]
-    fn test_is_private_ip_v6() {
-        // ULA
-        assert!(is_private_ip(&"fd00::1".parse().unwrap()));
-        // Link-local
-        assert!(is_private_ip(&"fe80::1".parse().unwrap()));
-        // Unspecified
-        assert!(is_private_ip(&"::".parse().unwrap()));
-    }
-
-    #[test]
-    fn test_is_private_ip_v4_mapped_v6() {
-        // ::ffff:127.0.0.1 should be blocked
-        assert!(is_private_ip(&"::ffff:127.0.0.1".parse().unwrap()));
-        // ::ffff:169.254.169.254 (metadata via v6)
-        assert!(is_private_ip(&"::ffff:169.254.169.254".parse().unwrap()));
-        // ::ffff:8.8.8.8 should be allowed
-        assert!(!is_private_ip(&"::ffff:8.8.8.8".parse().unwrap()));
-    }
-
-    #[test]
-    fn test_is_blocked_hostname() {
-        assert!(is_blocked_hostname("localhost"));
-        assert!(is_blocked_hostname("LOCALHOST"));
-        assert!(is_blocked_hostname("metadata.google.internal"));
-        assert!(is_blocked_hostname("foo.internal"));
-        assert!(is_blocked_hostname("printer.local"));
-        assert!(!is_blocked_hostname("example.com"));
-        assert!(!is_blocked_hostname("my-internal-api.com")); // "internal" in domain name is fine
-    }
-
-    #[test]
-    fn test_validate_url_host_blocks_private() {
-        assert!(validate_url_host("http://127.0.0.1/secret").is_err());
-        assert!(validate_url_host("http://localhost:8080/admin").is_err());
-        assert!(validate_url_host("http://169.254.169.254/latest/meta-data/").is_err());
-        assert!(validate_url_host("http://10.0.0.1/internal").is_err());
-        assert!(validate_url_host("http://192.168.1.1/router").is_err());
-        assert!(validate_url_host("http://172.16.0.5/service").is_err());
-        assert!(validate_url_host("http://[::1]/secret").is_err());
-        assert!(validate_url_host("http://metadata.google.internal/computeMetadata/v1/").is_err());
-    }
-
-    #[test]
-    fn test_validate_url_host_allows_public() {
-        assert!(validate_url_host("https://example.com").is_ok());
-        assert!(validate_url_host("https://docs.rs/rig-core/latest").is_ok());
-    }
-
     #[tokio::test]
     async fn test_fetch_tool_blocks_ssrf() {
         let tool = FetchTool::new(None);
@@ -523,6 +523,8 @@ pub struct DeleteFileArgs {
 pub struct DeleteFileOutput {
     pub path: String,
     pub deleted: bool,
+    /// Workspace trash entry the file can be restored from
+    pub trash_id: String,
 }
 
 #[derive(Clone)]
@@ -551,7 +553,8 @@ impl Tool for DeleteFileTool {
             name: "delete_file".to_string(),
             description: "Delete a file within the workspace. \
                          Requires user confirmation before deleting. \
-                         This operation is irreversible.\n\
+                         The file is moved to the workspace trash, from where \
+                         the user can restore it for 24 hours.\n\
                          \n\
                          Examples:\n\
                          - Delete file: {\"path\": \"temp/output.log\"}\n\
@@ -582,11 +585,12 @@ impl Tool for DeleteFileTool {
             ));
         }
 
-        self.service.delete_file(&args.path).await?;
+        let entry = self.service.trash_file(&args.path).await?;
 
         Ok(DeleteFileOutput {
             path: args.path,
             deleted: true,
+            trash_id: entry.id,
         })
     }
 }
@@ -603,6 +607,9 @@ pub struct MoveFileArgs {
 pub struct MoveFileOutput {
     pub source: String,
     pub destination: String,
+    /// Workspace trash entry the move can be undone from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trash_id: Option<String>,
 }
 
 #[derive(Clone)]
@@ -667,13 +674,15 @@ impl Tool for MoveFileTool {
             ));
         }
 
-        self.service
+        let entry = self
+            .service
             .move_file(&args.source, &args.destination)
             .await?;
 
         Ok(MoveFileOutput {
             source: args.source,
             destination: args.destination,
+            trash_id: entry.map(|entry| entry.id),
         })
     }
 }
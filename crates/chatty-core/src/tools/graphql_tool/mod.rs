@@ -0,0 +1,457 @@
+//! `graphql_tool` — read-only access to user-configured GraphQL endpoints.
+//!
+//! Each [`GraphQlEndpointConfig`] in the execution settings becomes one tool.
+//! On first use the tool introspects the endpoint's schema and keeps it for
+//! the lifetime of the agent. The model can ask for a description of the
+//! schema (or of a single type) and run queries; every query is parsed and
+//! validated against the schema before anything is sent, so typos come back
+//! as precise errors instead of a round trip. Mutations and subscriptions
+//! are rejected.
+//!
+//! # What lives here
+//!
+//! - The `GraphQlTool` rig-core tool implementation and its settings type.
+//! - A minimal parser for executable documents (`parser`).
+//! - Introspection, schema description and query validation (`schema`).
+//!
+//! # What does NOT live here
+//!
+//! - SSRF and redirect checks — `services::http_policy`.
+//! - Secret resolution for headers and config validation — shared with
+//!   `custom_http_tool`.
+//! - Rendering results as a tree — the GPUI trace components.
+
+mod parser;
+mod schema;
+
+use std::collections::{HashMap, HashSet};
+
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::ToolDyn;
+use rig_core::wasm_compat::WasmBoxedFuture;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::sync::OnceCell;
+
+use crate::services::http_policy::{HttpPolicy, HttpRequest};
+use crate::tools::ToolError;
+use crate::tools::custom_http_tool::{
+    CustomHttpHeader, default_true, substitute_secrets, validate_http_tool,
+};
+use parser::parse_document;
+use schema::{GraphQlSchema, INTROSPECTION_QUERY};
+
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Maximum size of a query response returned to the model.
+const MAX_RESPONSE_BYTES: usize = 100_000;
+
+/// Maximum size of the introspection response; large public schemas
+/// (GitHub, Shopify) are a few megabytes.
+const MAX_INTROSPECTION_BYTES: usize = 8 * 1024 * 1024;
+
+/// Maximum length of a query document.
+const MAX_QUERY_CHARS: usize = 20_000;
+
+/// A GraphQL endpoint exposed to the agent, stored in the execution settings.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GraphQlEndpointConfig {
+    pub id: String,
+    /// Tool name shown to the model, as for custom HTTP tools.
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub endpoint: String,
+    /// Sent with every request; values may contain `{{secret:KEY}}` references.
+    #[serde(default)]
+    pub headers: Vec<CustomHttpHeader>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Allow requests to localhost and private networks, as for custom HTTP tools.
+    #[serde(default)]
+    pub allow_private_network: bool,
+}
+
+impl GraphQlEndpointConfig {
+    pub fn new(name: impl Into<String>, endpoint: impl Into<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.into(),
+            description: String::new(),
+            endpoint: endpoint.into(),
+            headers: Vec::new(),
+            enabled: true,
+            allow_private_network: false,
+        }
+    }
+
+    /// Check the definition before it is saved or turned into a tool.
+    pub fn validate(&self) -> Result<(), String> {
+        validate_http_tool(&self.name, "Endpoint", &self.endpoint, &self.headers)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum GraphQlAction {
+    Schema,
+    Query,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlArgs {
+    action: GraphQlAction,
+    #[serde(default)]
+    type_name: Option<String>,
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    variables: Option<Value>,
+    #[serde(default)]
+    operation_name: Option<String>,
+}
+
+/// Agent tool backed by a [`GraphQlEndpointConfig`].
+pub struct GraphQlTool {
+    config: GraphQlEndpointConfig,
+    secrets: HashMap<String, String>,
+    client: reqwest::Client,
+    schema: OnceCell<GraphQlSchema>,
+}
+
+impl GraphQlTool {
+    pub fn new(config: GraphQlEndpointConfig, secrets: HashMap<String, String>) -> Self {
+        Self {
+            config,
+            secrets,
+            client: crate::services::http_client::no_redirect_client(REQUEST_TIMEOUT_SECS),
+            schema: OnceCell::new(),
+        }
+    }
+
+    /// Turn the enabled, valid configs into tools. Definitions whose name
+    /// collides with `reserved_names` or an earlier definition are skipped.
+    pub fn from_configs(
+        configs: &[GraphQlEndpointConfig],
+        reserved_names: &HashSet<String>,
+        secrets: &[(String, String)],
+    ) -> Vec<GraphQlTool> {
+        let secrets: HashMap<String, String> = secrets.iter().cloned().collect();
+        let mut seen = HashSet::new();
+        configs
+            .iter()
+            .filter(|config| config.enabled)
+            .filter(|config| match config.validate() {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::warn!(tool = %config.name, error = %e, "Skipping invalid GraphQL endpoint");
+                    false
+                }
+            })
+            .filter(|config| {
+                let unique =
+                    !reserved_names.contains(&config.name) && seen.insert(config.name.clone());
+                if !unique {
+                    tracing::warn!(tool = %config.name, "Skipping GraphQL endpoint with a duplicate name");
+                }
+                unique
+            })
+            .map(|config| GraphQlTool::new(config.clone(), secrets.clone()))
+            .collect()
+    }
+
+    /// POST a GraphQL request body and return the parsed JSON response.
+    async fn post(&self, body: Value, max_response_bytes: usize) -> Result<Value, ToolError> {
+        let mut headers = vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("Accept".to_string(), "application/json".to_string()),
+        ];
+        let mut secret_headers = Vec::new();
+        for header in &self.config.headers {
+            let value = substitute_secrets(&header.value, &self.secrets)
+                .map_err(ToolError::OperationFailed)?;
+            if header.has_secret() {
+                secret_headers.push(header.name.trim().to_string());
+            }
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case(header.name.trim()));
+            headers.push((header.name.trim().to_string(), value));
+        }
+
+        let policy = HttpPolicy {
+            allow_private_hosts: self.config.allow_private_network,
+            max_response_bytes,
+        };
+        let response = policy
+            .send_text(
+                &self.client,
+                HttpRequest {
+                    method: reqwest::Method::POST,
+                    url: self.config.endpoint.trim().to_string(),
+                    headers,
+                    body: Some(body.to_string()),
+                    secret_headers,
+                },
+            )
+            .await
+            .map_err(|e| ToolError::OperationFailed(e.to_string()))?;
+
+        if response.truncated {
+            return Err(ToolError::OperationFailed(format!(
+                "Response exceeded {max_response_bytes} bytes; select fewer fields or paginate"
+            )));
+        }
+        // GraphQL servers often report errors with a 4xx status and a JSON body.
+        match serde_json::from_str::<Value>(&response.body) {
+            Ok(value) if value.get("data").is_some() || value.get("errors").is_some() => Ok(value),
+            _ => Err(ToolError::OperationFailed(format!(
+                "Endpoint returned HTTP {} without a GraphQL response: {}",
+                response.status,
+                response.body.chars().take(500).collect::<String>()
+            ))),
+        }
+    }
+
+    /// Introspect the endpoint once and keep the schema.
+    async fn schema(&self) -> Result<&GraphQlSchema, ToolError> {
+        self.schema
+            .get_or_try_init(|| async {
+                tracing::info!(tool = %self.config.name, endpoint = %self.config.endpoint, "Introspecting GraphQL schema");
+                let response = self
+                    .post(json!({ "query": INTROSPECTION_QUERY }), MAX_INTROSPECTION_BYTES)
+                    .await?;
+                GraphQlSchema::from_introspection(&response).map_err(ToolError::OperationFailed)
+            })
+            .await
+    }
+
+    async fn execute(&self, args: String) -> Result<Value, ToolError> {
+        let args: GraphQlArgs = serde_json::from_str(&args)
+            .map_err(|e| ToolError::OperationFailed(format!("Invalid arguments: {e}")))?;
+        let schema = self.schema().await?;
+
+        match args.action {
+            GraphQlAction::Schema => schema
+                .describe(args.type_name.as_deref().map(str::trim))
+                .map(Value::String)
+                .map_err(ToolError::OperationFailed),
+            GraphQlAction::Query => {
+                let query = args
+                    .query
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|q| !q.is_empty())
+                    .ok_or_else(|| {
+                        ToolError::OperationFailed("'query' is required for action 'query'".into())
+                    })?;
+                if query.chars().count() > MAX_QUERY_CHARS {
+                    return Err(ToolError::OperationFailed(format!(
+                        "Query is longer than {MAX_QUERY_CHARS} characters"
+                    )));
+                }
+                let variables = match args.variables {
+                    None | Some(Value::Null) => None,
+                    Some(Value::Object(map)) => Some(Value::Object(map)),
+                    Some(_) => {
+                        return Err(ToolError::OperationFailed(
+                            "'variables' must be a JSON object".to_string(),
+                        ));
+                    }
+                };
+
+                let document = parse_document(query).map_err(ToolError::OperationFailed)?;
+                schema
+                    .validate(&document, args.operation_name.as_deref())
+                    .map_err(|errors| {
+                        ToolError::OperationFailed(format!(
+                            "Query does not match the schema:\n- {}",
+                            errors.join("\n- ")
+                        ))
+                    })?;
+
+                let mut body = json!({ "query": query });
+                if let Some(variables) = variables {
+                    body["variables"] = variables;
+                }
+                if let Some(name) = args.operation_name {
+                    body["operationName"] = Value::String(name);
+                }
+                tracing::info!(tool = %self.config.name, "Running GraphQL query");
+                self.post(body, MAX_RESPONSE_BYTES).await
+            }
+        }
+    }
+}
+
+impl ToolDyn for GraphQlTool {
+    fn name(&self) -> String {
+        self.config.name.clone()
+    }
+
+    fn definition<'a>(&'a self, _prompt: String) -> WasmBoxedFuture<'a, ToolDefinition> {
+        Box::pin(async move {
+            let mut description = if self.config.description.trim().is_empty() {
+                format!("Query the GraphQL API at {}.", self.config.endpoint.trim())
+            } else {
+                self.config.description.trim().to_string()
+            };
+            description.push_str(
+                " Call with action \"schema\" first to see the query root (and with type_name \
+                 for the fields of a type), then action \"query\". Queries are validated \
+                 against the schema before they are sent; mutations and subscriptions are \
+                 not allowed.",
+            );
+            ToolDefinition {
+                name: self.config.name.clone(),
+                description,
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "action": {
+                            "type": "string",
+                            "enum": ["schema", "query"],
+                            "description": "\"schema\" describes types, \"query\" runs a query"
+                        },
+                        "type_name": {
+                            "type": "string",
+                            "description": "For action \"schema\": type to describe (default: the query root)"
+                        },
+                        "query": {
+                            "type": "string",
+                            "description": "For action \"query\": the GraphQL query document"
+                        },
+                        "variables": {
+                            "type": "object",
+                            "description": "Values for the query's $variables"
+                        },
+                        "operation_name": {
+                            "type": "string",
+                            "description": "Operation to run when the document has several"
+                        }
+                    },
+                    "required": ["action"]
+                }),
+            }
+        })
+    }
+
+    fn call<'a>(
+        &'a self,
+        args: String,
+    ) -> WasmBoxedFuture<'a, Result<String, rig_core::tool::ToolError>> {
+        Box::pin(async move {
+            let output = self
+                .execute(args)
+                .await
+                .map_err(|e| rig_core::tool::ToolError::ToolCallError(Box::new(e)))?;
+            match output {
+                Value::String(text) => Ok(text),
+                other => {
+                    serde_json::to_string(&other).map_err(rig_core::tool::ToolError::JsonError)
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_with_schema() -> GraphQlTool {
+        let tool = GraphQlTool::new(
+            GraphQlEndpointConfig::new("blog", "https://api.example.com/graphql"),
+            HashMap::new(),
+        );
+        let schema =
+            GraphQlSchema::from_introspection(&schema::tests::blog_introspection()).unwrap();
+        tool.schema.set(schema).unwrap();
+        tool
+    }
+
+    #[test]
+    fn validate_checks_name_and_endpoint() {
+        assert!(
+            GraphQlEndpointConfig::new("github", "https://api.github.com/graphql")
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            GraphQlEndpointConfig::new("bad name", "https://x.dev")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            GraphQlEndpointConfig::new("ok", "ftp://x.dev")
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn from_configs_skips_disabled_invalid_and_reserved() {
+        let mut disabled = GraphQlEndpointConfig::new("disabled", "https://x.dev/graphql");
+        disabled.enabled = false;
+        let configs = vec![
+            GraphQlEndpointConfig::new("blog", "https://x.dev/graphql"),
+            GraphQlEndpointConfig::new("blog", "https://y.dev/graphql"),
+            GraphQlEndpointConfig::new("fetch", "https://x.dev/graphql"),
+            GraphQlEndpointConfig::new("broken", "not a url"),
+            disabled,
+        ];
+        let reserved = HashSet::from(["fetch".to_string()]);
+        let tools = GraphQlTool::from_configs(&configs, &reserved, &[]);
+        let names: Vec<String> = tools.iter().map(ToolDyn::name).collect();
+        assert_eq!(names, vec!["blog"]);
+    }
+
+    #[tokio::test]
+    async fn schema_action_uses_cached_schema() {
+        let tool = tool_with_schema();
+        let out = tool
+            .call(r#"{"action": "schema", "type_name": "User"}"#.to_string())
+            .await
+            .unwrap();
+        assert!(out.starts_with("type User {"), "{out}");
+    }
+
+    #[tokio::test]
+    async fn invalid_queries_fail_before_sending() {
+        let tool = tool_with_schema();
+        for (args, expected) in [
+            (r#"{"action": "query"}"#, "'query' is required"),
+            (
+                r#"{"action": "query", "query": "{ user(id: 1) { nme } }"}"#,
+                "field 'nme' does not exist on type 'User'",
+            ),
+            (
+                r#"{"action": "query", "query": "mutation { deletePost }"}"#,
+                "Only queries are allowed",
+            ),
+            (
+                r#"{"action": "query", "query": "{ posts {"}"#,
+                "Syntax error",
+            ),
+            (
+                r#"{"action": "query", "query": "{ posts { title } }", "variables": [1]}"#,
+                "must be a JSON object",
+            ),
+        ] {
+            let err = tool.call(args.to_string()).await.unwrap_err().to_string();
+            assert!(err.contains(expected), "{args}: {err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn private_endpoints_are_blocked_unless_allowed() {
+        let tool = GraphQlTool::new(
+            GraphQlEndpointConfig::new("local", "http://127.0.0.1:4000/graphql"),
+            HashMap::new(),
+        );
+        let err = tool
+            .call(r#"{"action": "schema"}"#.to_string())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("SSRF"), "{err}");
+    }
+}
@@ -0,0 +1,574 @@
+//! Minimal parser for GraphQL executable documents.
+//!
+//! Only the structure needed for validation is kept: operations, fragments,
+//! selection sets, field names/aliases and argument names. Values, variable
+//! definitions and directives are parsed for syntax and then dropped.
+
+/// Maximum nesting of selection sets, lists and objects while parsing.
+const MAX_NESTING: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum OperationKind {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl OperationKind {
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            OperationKind::Query => "query",
+            OperationKind::Mutation => "mutation",
+            OperationKind::Subscription => "subscription",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Document {
+    pub operations: Vec<Operation>,
+    pub fragments: Vec<Fragment>,
+}
+
+#[derive(Debug)]
+pub(super) struct Operation {
+    pub kind: OperationKind,
+    pub name: Option<String>,
+    pub selection: Vec<Selection>,
+}
+
+#[derive(Debug)]
+pub(super) struct Fragment {
+    pub name: String,
+    pub type_condition: String,
+    pub selection: Vec<Selection>,
+}
+
+#[derive(Debug)]
+pub(super) enum Selection {
+    Field(Field),
+    FragmentSpread(String),
+    InlineFragment {
+        type_condition: Option<String>,
+        selection: Vec<Selection>,
+    },
+}
+
+#[derive(Debug)]
+pub(super) struct Field {
+    pub alias: Option<String>,
+    pub name: String,
+    pub arguments: Vec<String>,
+    pub selection: Vec<Selection>,
+}
+
+impl Field {
+    /// Key the field appears under in the response.
+    pub(super) fn response_key(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Punct(char),
+    Spread,
+    Name(String),
+    /// Int, float or string literal; the content is not needed.
+    Literal,
+}
+
+fn describe(token: Option<&Token>) -> String {
+    match token {
+        Some(Token::Punct(c)) => format!("'{c}'"),
+        Some(Token::Spread) => "'...'".to_string(),
+        Some(Token::Name(name)) => format!("'{name}'"),
+        Some(Token::Literal) => "a literal".to_string(),
+        None => "end of document".to_string(),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() || c == ',' || c == '\u{feff}' => i += 1,
+            '#' => {
+                while i < chars.len() && chars[i] != '\n' && chars[i] != '\r' {
+                    i += 1;
+                }
+            }
+            '!' | '$' | '&' | '(' | ')' | ':' | '=' | '@' | '[' | ']' | '{' | '|' | '}' => {
+                tokens.push(Token::Punct(c));
+                i += 1;
+            }
+            '.' => {
+                if chars.get(i + 1) == Some(&'.') && chars.get(i + 2) == Some(&'.') {
+                    tokens.push(Token::Spread);
+                    i += 3;
+                } else {
+                    return Err("Unexpected '.'; did you mean '...'?".to_string());
+                }
+            }
+            '"' => {
+                if chars.get(i + 1) == Some(&'"') && chars.get(i + 2) == Some(&'"') {
+                    i += 3;
+                    loop {
+                        match chars.get(i) {
+                            None => return Err("Unterminated block string".to_string()),
+                            Some('\\')
+                                if chars.get(i + 1) == Some(&'"')
+                                    && chars.get(i + 2) == Some(&'"')
+                                    && chars.get(i + 3) == Some(&'"') =>
+                            {
+                                i += 4
+                            }
+                            Some('"')
+                                if chars.get(i + 1) == Some(&'"')
+                                    && chars.get(i + 2) == Some(&'"') =>
+                            {
+                                i += 3;
+                                break;
+                            }
+                            Some(_) => i += 1,
+                        }
+                    }
+                } else {
+                    i += 1;
+                    loop {
+                        match chars.get(i) {
+                            None | Some('\n') | Some('\r') => {
+                                return Err("Unterminated string".to_string());
+                            }
+                            Some('\\') => i += 2,
+                            Some('"') => {
+                                i += 1;
+                                break;
+                            }
+                            Some(_) => i += 1,
+                        }
+                    }
+                }
+                tokens.push(Token::Literal);
+            }
+            c if c == '-' || c.is_ascii_digit() => {
+                i += 1;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '.' | '+' | '-'))
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Literal);
+            }
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                let start = i;
+                while i < chars.len() && (chars[i] == '_' || chars[i].is_ascii_alphanumeric()) {
+                    i += 1;
+                }
+                tokens.push(Token::Name(chars[start..i].iter().collect()));
+            }
+            other => return Err(format!("Unexpected character '{other}'")),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_is(&self, punct: char) -> bool {
+        self.peek() == Some(&Token::Punct(punct))
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, punct: char) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Punct(c)) if c == punct => Ok(()),
+            other => Err(format!(
+                "Expected '{punct}', found {}",
+                describe(other.as_ref())
+            )),
+        }
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Name(name)) => Ok(name),
+            other => Err(format!(
+                "Expected a name, found {}",
+                describe(other.as_ref())
+            )),
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING {
+            return Err("Document is nested too deeply".to_string());
+        }
+        Ok(())
+    }
+
+    fn document(&mut self) -> Result<Document, String> {
+        let mut document = Document::default();
+        while let Some(token) = self.peek() {
+            match token {
+                Token::Punct('{') => document.operations.push(Operation {
+                    kind: OperationKind::Query,
+                    name: None,
+                    selection: self.selection_set()?,
+                }),
+                Token::Name(keyword) if keyword == "fragment" => {
+                    self.pos += 1;
+                    let name = self.name()?;
+                    if name == "on" {
+                        return Err("A fragment cannot be named 'on'".to_string());
+                    }
+                    match self.next() {
+                        Some(Token::Name(on)) if on == "on" => {}
+                        other => {
+                            return Err(format!(
+                                "Expected 'on' after fragment name, found {}",
+                                describe(other.as_ref())
+                            ));
+                        }
+                    }
+                    let type_condition = self.name()?;
+                    self.directives()?;
+                    let selection = self.selection_set()?;
+                    document.fragments.push(Fragment {
+                        name,
+                        type_condition,
+                        selection,
+                    });
+                }
+                Token::Name(keyword) => {
+                    let kind = match keyword.as_str() {
+                        "query" => OperationKind::Query,
+                        "mutation" => OperationKind::Mutation,
+                        "subscription" => OperationKind::Subscription,
+                        other => {
+                            return Err(format!(
+                                "Expected 'query', 'mutation', 'subscription' or 'fragment', found '{other}'"
+                            ));
+                        }
+                    };
+                    self.pos += 1;
+                    let name = match self.peek() {
+                        Some(Token::Name(_)) => Some(self.name()?),
+                        _ => None,
+                    };
+                    if self.peek_is('(') {
+                        self.variable_definitions()?;
+                    }
+                    self.directives()?;
+                    let selection = self.selection_set()?;
+                    document.operations.push(Operation {
+                        kind,
+                        name,
+                        selection,
+                    });
+                }
+                other => {
+                    return Err(format!(
+                        "Expected an operation or fragment, found {}",
+                        describe(Some(other))
+                    ));
+                }
+            }
+        }
+        if document.operations.is_empty() {
+            return Err("Document contains no operation".to_string());
+        }
+        Ok(document)
+    }
+
+    fn variable_definitions(&mut self) -> Result<(), String> {
+        self.expect('(')?;
+        loop {
+            self.expect('$')?;
+            self.name()?;
+            self.expect(':')?;
+            self.type_ref()?;
+            if self.peek_is('=') {
+                self.pos += 1;
+                self.value()?;
+            }
+            self.directives()?;
+            if self.peek_is(')') {
+                self.pos += 1;
+                return Ok(());
+            }
+        }
+    }
+
+    fn type_ref(&mut self) -> Result<(), String> {
+        if self.peek_is('[') {
+            self.enter()?;
+            self.pos += 1;
+            self.type_ref()?;
+            self.expect(']')?;
+            self.depth -= 1;
+        } else {
+            self.name()?;
+        }
+        if self.peek_is('!') {
+            self.pos += 1;
+        }
+        Ok(())
+    }
+
+    fn directives(&mut self) -> Result<(), String> {
+        while self.peek_is('@') {
+            self.pos += 1;
+            self.name()?;
+            if self.peek_is('(') {
+                self.arguments()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse `(name: value, ...)` and return the argument names.
+    fn arguments(&mut self) -> Result<Vec<String>, String> {
+        self.expect('(')?;
+        let mut names = Vec::new();
+        loop {
+            names.push(self.name()?);
+            self.expect(':')?;
+            self.value()?;
+            if self.peek_is(')') {
+                self.pos += 1;
+                return Ok(names);
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Punct('$')) => {
+                self.name()?;
+            }
+            Some(Token::Literal) | Some(Token::Name(_)) => {}
+            Some(Token::Punct('[')) => {
+                self.enter()?;
+                while !self.peek_is(']') {
+                    self.value()?;
+                }
+                self.pos += 1;
+                self.depth -= 1;
+            }
+            Some(Token::Punct('{')) => {
+                self.enter()?;
+                while !self.peek_is('}') {
+                    self.name()?;
+                    self.expect(':')?;
+                    self.value()?;
+                }
+                self.pos += 1;
+                self.depth -= 1;
+            }
+            other => {
+                return Err(format!(
+                    "Expected a value, found {}",
+                    describe(other.as_ref())
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn selection_set(&mut self) -> Result<Vec<Selection>, String> {
+        self.expect('{')?;
+        self.enter()?;
+        let mut selection = Vec::new();
+        while !self.peek_is('}') {
+            if self.peek().is_none() {
+                return Err("Unterminated selection set".to_string());
+            }
+            selection.push(self.selection()?);
+        }
+        self.pos += 1;
+        self.depth -= 1;
+        if selection.is_empty() {
+            return Err("Selection sets must not be empty".to_string());
+        }
+        Ok(selection)
+    }
+
+    fn selection(&mut self) -> Result<Selection, String> {
+        if self.peek() == Some(&Token::Spread) {
+            self.pos += 1;
+            return match self.peek() {
+                Some(Token::Name(name)) if name == "on" => {
+                    self.pos += 1;
+                    let type_condition = Some(self.name()?);
+                    self.directives()?;
+                    Ok(Selection::InlineFragment {
+                        type_condition,
+                        selection: self.selection_set()?,
+                    })
+                }
+                Some(Token::Name(_)) => {
+                    let name = self.name()?;
+                    self.directives()?;
+                    Ok(Selection::FragmentSpread(name))
+                }
+                _ => {
+                    self.directives()?;
+                    Ok(Selection::InlineFragment {
+                        type_condition: None,
+                        selection: self.selection_set()?,
+                    })
+                }
+            };
+        }
+
+        let first = self.name()?;
+        let (alias, name) = if self.peek_is(':') {
+            self.pos += 1;
+            (Some(first), self.name()?)
+        } else {
+            (None, first)
+        };
+        let arguments = if self.peek_is('(') {
+            self.arguments()?
+        } else {
+            Vec::new()
+        };
+        self.directives()?;
+        let selection = if self.peek_is('{') {
+            self.selection_set()?
+        } else {
+            Vec::new()
+        };
+        Ok(Selection::Field(Field {
+            alias,
+            name,
+            arguments,
+            selection,
+        }))
+    }
+}
+
+/// Parse a GraphQL executable document.
+pub(super) fn parse_document(source: &str) -> Result<Document, String> {
+    let tokens = tokenize(source).map_err(|e| format!("Syntax error: {e}"))?;
+    Parser {
+        tokens,
+        pos: 0,
+        depth: 0,
+    }
+    .document()
+    .map_err(|e| format!("Syntax error: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(selection: &Selection) -> &Field {
+        match selection {
+            Selection::Field(field) => field,
+            other => panic!("expected a field, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_operations_fragments_and_arguments() {
+        let doc = parse_document(
+            r#"
+            # comment
+            query Repo($owner: String!, $ids: [ID!] = ["a"]) @cached {
+              repository(owner: $owner, name: "chatty", filter: {first: 10, tags: [A, B]}) {
+                issueCount: issues { totalCount }
+                ...RepoFields
+                ... on Repository @include(if: true) { stars }
+                ... { id }
+              }
+            }
+            fragment RepoFields on Repository { description(format: """multi
+            line""") }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(doc.operations.len(), 1);
+        let op = &doc.operations[0];
+        assert_eq!(op.kind, OperationKind::Query);
+        assert_eq!(op.name.as_deref(), Some("Repo"));
+
+        let repository = field(&op.selection[0]);
+        assert_eq!(repository.name, "repository");
+        assert_eq!(repository.arguments, vec!["owner", "name", "filter"]);
+
+        let issues = field(&repository.selection[0]);
+        assert_eq!(issues.alias.as_deref(), Some("issueCount"));
+        assert_eq!(issues.response_key(), "issueCount");
+        assert_eq!(issues.name, "issues");
+        assert!(
+            matches!(&repository.selection[1], Selection::FragmentSpread(n) if n == "RepoFields")
+        );
+        assert!(matches!(
+            &repository.selection[2],
+            Selection::InlineFragment { type_condition: Some(t), .. } if t == "Repository"
+        ));
+        assert!(matches!(
+            &repository.selection[3],
+            Selection::InlineFragment {
+                type_condition: None,
+                ..
+            }
+        ));
+
+        assert_eq!(doc.fragments.len(), 1);
+        assert_eq!(doc.fragments[0].type_condition, "Repository");
+    }
+
+    #[test]
+    fn anonymous_query_and_mutation_kinds() {
+        let doc = parse_document("{ viewer { login } } mutation M { star(id: 1) { ok } }").unwrap();
+        assert_eq!(doc.operations[0].kind, OperationKind::Query);
+        assert_eq!(doc.operations[0].name, None);
+        assert_eq!(doc.operations[1].kind, OperationKind::Mutation);
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        for source in [
+            "",
+            "{ viewer { login }",
+            "{ }",
+            "query { user(id: ) { id } }",
+            "{ a(b: \"unterminated) }",
+            "fragment F User { id }",
+            "{ a.b }",
+        ] {
+            let err = parse_document(source).unwrap_err();
+            assert!(err.starts_with("Syntax error"), "{source}: {err}");
+        }
+    }
+
+    #[test]
+    fn rejects_excessive_nesting() {
+        let source = format!("{}{}", "{ a ".repeat(100), "}".repeat(100));
+        let err = parse_document(&source).unwrap_err();
+        assert!(err.contains("nested too deeply"), "{err}");
+    }
+}
@@ -0,0 +1,837 @@
+//! GraphQL schema loaded through introspection, and validation of query
+//! documents against it.
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+use super::parser::{Document, OperationKind, Selection};
+
+/// Introspection query sent once per tool instance. Type references are
+/// unwrapped seven levels deep, which covers types like `[[Foo!]!]!`.
+pub(super) const INTROSPECTION_QUERY: &str = r#"
+query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    types {
+      kind
+      name
+      description
+      fields(includeDeprecated: true) {
+        name
+        description
+        args { name type { ...TypeRef } defaultValue }
+        type { ...TypeRef }
+      }
+      inputFields { name type { ...TypeRef } defaultValue }
+      enumValues(includeDeprecated: true) { name }
+      possibleTypes { name }
+    }
+  }
+}
+fragment TypeRef on __Type {
+  kind name
+  ofType { kind name ofType { kind name ofType { kind name ofType { kind name
+    ofType { kind name ofType { kind name ofType { kind name } } } } } } }
+}
+"#;
+
+/// Maximum depth of nested field selections in a query.
+pub(super) const MAX_QUERY_DEPTH: usize = 12;
+
+/// Maximum size of a type description returned to the model.
+const MAX_DESCRIBE_CHARS: usize = 20_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+}
+
+impl TypeKind {
+    fn parse(kind: &str) -> Option<Self> {
+        Some(match kind {
+            "SCALAR" => TypeKind::Scalar,
+            "OBJECT" => TypeKind::Object,
+            "INTERFACE" => TypeKind::Interface,
+            "UNION" => TypeKind::Union,
+            "ENUM" => TypeKind::Enum,
+            "INPUT_OBJECT" => TypeKind::InputObject,
+            _ => return None,
+        })
+    }
+
+    fn is_composite(self) -> bool {
+        matches!(
+            self,
+            TypeKind::Object | TypeKind::Interface | TypeKind::Union
+        )
+    }
+}
+
+/// A type reference such as `[User!]!`.
+#[derive(Clone, Debug, PartialEq)]
+struct TypeRef {
+    /// SDL notation.
+    display: String,
+    /// Innermost named type.
+    named: String,
+    non_null: bool,
+}
+
+impl TypeRef {
+    fn from_json(value: &Value) -> Result<Self, String> {
+        let kind = value.get("kind").and_then(Value::as_str).unwrap_or("");
+        match kind {
+            "NON_NULL" | "LIST" => {
+                let inner = Self::from_json(
+                    value
+                        .get("ofType")
+                        .filter(|v| !v.is_null())
+                        .ok_or_else(|| format!("{kind} type reference without ofType"))?,
+                )?;
+                Ok(if kind == "NON_NULL" {
+                    TypeRef {
+                        display: format!("{}!", inner.display),
+                        named: inner.named,
+                        non_null: true,
+                    }
+                } else {
+                    TypeRef {
+                        display: format!("[{}]", inner.display),
+                        named: inner.named,
+                        non_null: false,
+                    }
+                })
+            }
+            _ => {
+                let name = value
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or("Type reference without a name")?;
+                Ok(TypeRef {
+                    display: name.to_string(),
+                    named: name.to_string(),
+                    non_null: false,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct InputValue {
+    name: String,
+    ty: TypeRef,
+    has_default: bool,
+}
+
+impl InputValue {
+    fn from_json(value: &Value) -> Result<Self, String> {
+        Ok(InputValue {
+            name: string_field(value, "name")?,
+            ty: TypeRef::from_json(value.get("type").unwrap_or(&Value::Null))?,
+            has_default: value.get("defaultValue").is_some_and(|d| !d.is_null()),
+        })
+    }
+
+    fn is_required(&self) -> bool {
+        self.ty.non_null && !self.has_default
+    }
+}
+
+#[derive(Clone, Debug)]
+struct FieldDef {
+    name: String,
+    description: Option<String>,
+    args: Vec<InputValue>,
+    ty: TypeRef,
+}
+
+#[derive(Clone, Debug)]
+struct TypeDef {
+    kind: TypeKind,
+    name: String,
+    description: Option<String>,
+    fields: Vec<FieldDef>,
+    input_fields: Vec<InputValue>,
+    enum_values: Vec<String>,
+    possible_types: Vec<String>,
+}
+
+impl TypeDef {
+    fn field(&self, name: &str) -> Option<&FieldDef> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+/// The parts of a schema needed to describe types and validate queries.
+#[derive(Clone, Debug)]
+pub(super) struct GraphQlSchema {
+    query_type: String,
+    mutation_type: Option<String>,
+    types: HashMap<String, TypeDef>,
+}
+
+fn string_field(value: &Value, key: &str) -> Result<String, String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| format!("Introspection result is missing '{key}'"))
+}
+
+fn description(value: &Value) -> Option<String> {
+    value
+        .get("description")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(str::to_string)
+}
+
+fn list<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+impl GraphQlSchema {
+    /// Build the schema from an introspection response (`{"data": {"__schema": ...}}`).
+    pub(super) fn from_introspection(response: &Value) -> Result<Self, String> {
+        let schema = response
+            .pointer("/data/__schema")
+            .or_else(|| response.get("__schema"))
+            .ok_or_else(|| {
+                match response
+                    .pointer("/errors/0/message")
+                    .and_then(Value::as_str)
+                {
+                    Some(message) => format!("Introspection failed: {message}"),
+                    None => "Endpoint did not return an introspection result".to_string(),
+                }
+            })?;
+
+        let query_type = schema
+            .pointer("/queryType/name")
+            .and_then(Value::as_str)
+            .ok_or("Schema has no query type")?
+            .to_string();
+        let mutation_type = schema
+            .pointer("/mutationType/name")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        let mut types = HashMap::new();
+        for ty in list(schema, "types") {
+            let name = string_field(ty, "name")?;
+            let Some(kind) = ty
+                .get("kind")
+                .and_then(Value::as_str)
+                .and_then(TypeKind::parse)
+            else {
+                continue;
+            };
+            let fields = list(ty, "fields")
+                .map(|field| {
+                    Ok(FieldDef {
+                        name: string_field(field, "name")?,
+                        description: description(field),
+                        args: list(field, "args")
+                            .map(InputValue::from_json)
+                            .collect::<Result<_, String>>()?,
+                        ty: TypeRef::from_json(field.get("type").unwrap_or(&Value::Null))?,
+                    })
+                })
+                .collect::<Result<_, String>>()?;
+            let input_fields = list(ty, "inputFields")
+                .map(InputValue::from_json)
+                .collect::<Result<_, String>>()?;
+            let enum_values = list(ty, "enumValues")
+                .filter_map(|v| v.get("name").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect();
+            let possible_types = list(ty, "possibleTypes")
+                .filter_map(|v| v.get("name").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect();
+            types.insert(
+                name.clone(),
+                TypeDef {
+                    kind,
+                    name,
+                    description: description(ty),
+                    fields,
+                    input_fields,
+                    enum_values,
+                    possible_types,
+                },
+            );
+        }
+
+        if !types.contains_key(&query_type) {
+            return Err(format!("Query type '{query_type}' is not defined"));
+        }
+        Ok(GraphQlSchema {
+            query_type,
+            mutation_type,
+            types,
+        })
+    }
+
+    /// SDL-style description of `type_name`, or of the query root followed
+    /// by an index of the other types when `type_name` is `None`.
+    pub(super) fn describe(&self, type_name: Option<&str>) -> Result<String, String> {
+        let mut out = match type_name {
+            Some(name) => {
+                let ty = self
+                    .types
+                    .get(name)
+                    .ok_or_else(|| format!("Type '{name}' does not exist in the schema"))?;
+                render_type(ty)
+            }
+            None => {
+                let mut out = render_type(&self.types[&self.query_type]);
+                let mut others: Vec<&TypeDef> = self
+                    .types
+                    .values()
+                    .filter(|t| t.name != self.query_type && !t.name.starts_with("__"))
+                    .filter(|t| Some(&t.name) != self.mutation_type.as_ref())
+                    .filter(|t| t.kind != TypeKind::Scalar)
+                    .collect();
+                others.sort_by(|a, b| a.name.cmp(&b.name));
+                out.push_str("\n# Other types (pass one as type_name for its fields):\n");
+                for ty in others {
+                    out.push_str(&format!("# {} {}\n", keyword(ty.kind), ty.name));
+                }
+                out
+            }
+        };
+        if out.len() > MAX_DESCRIBE_CHARS {
+            let cut = (0..=MAX_DESCRIBE_CHARS)
+                .rev()
+                .find(|i| out.is_char_boundary(*i))
+                .unwrap_or(0);
+            out.truncate(cut);
+            out.push_str("\n# ... truncated");
+        }
+        Ok(out)
+    }
+
+    /// Check `document` against the schema. Only query operations are
+    /// accepted; the returned errors name the offending path.
+    pub(super) fn validate(
+        &self,
+        document: &Document,
+        operation_name: Option<&str>,
+    ) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        for op in &document.operations {
+            if op.kind != OperationKind::Query {
+                errors.push(format!(
+                    "Only queries are allowed; found a {} operation{}",
+                    op.kind.as_str(),
+                    op.name
+                        .as_ref()
+                        .map(|n| format!(" '{n}'"))
+                        .unwrap_or_default()
+                ));
+            }
+        }
+        match operation_name {
+            Some(name)
+                if !document
+                    .operations
+                    .iter()
+                    .any(|op| op.name.as_deref() == Some(name)) =>
+            {
+                errors.push(format!("No operation named '{name}' in the document"));
+            }
+            None if document.operations.len() > 1 => errors.push(
+                "The document has several operations; pass operation_name to pick one".to_string(),
+            ),
+            _ => {}
+        }
+
+        let fragments: HashMap<&str, &super::parser::Fragment> = document
+            .fragments
+            .iter()
+            .map(|f| (f.name.as_str(), f))
+            .collect();
+        if fragments.len() != document.fragments.len() {
+            errors.push("Fragment names must be unique".to_string());
+        }
+
+        let mut used_fragments = HashSet::new();
+        for op in document
+            .operations
+            .iter()
+            .filter(|op| op.kind == OperationKind::Query)
+        {
+            let mut ctx = ValidationContext {
+                schema: self,
+                fragments: &fragments,
+                used_fragments: &mut used_fragments,
+                visiting: Vec::new(),
+                errors: &mut errors,
+            };
+            ctx.selection_set(&self.query_type, &op.selection, &self.query_type, 1);
+        }
+        for fragment in &document.fragments {
+            if !used_fragments.contains(fragment.name.as_str()) {
+                errors.push(format!("Fragment '{}' is never used", fragment.name));
+            }
+        }
+
+        let mut seen = HashSet::new();
+        errors.retain(|e| seen.insert(e.clone()));
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+struct ValidationContext<'a, 'd> {
+    schema: &'a GraphQlSchema,
+    fragments: &'a HashMap<&'d str, &'d super::parser::Fragment>,
+    used_fragments: &'a mut HashSet<&'d str>,
+    /// Fragment spreads currently being expanded, to detect cycles.
+    visiting: Vec<&'d str>,
+    errors: &'a mut Vec<String>,
+}
+
+impl<'d> ValidationContext<'_, 'd> {
+    fn selection_set(
+        &mut self,
+        parent: &str,
+        selection: &'d [Selection],
+        path: &str,
+        depth: usize,
+    ) {
+        if depth > MAX_QUERY_DEPTH {
+            self.errors.push(format!(
+                "{path}: query is nested deeper than {MAX_QUERY_DEPTH} levels"
+            ));
+            return;
+        }
+        let schema = self.schema;
+        let Some(parent_ty) = schema.types.get(parent) else {
+            return;
+        };
+
+        for item in selection {
+            match item {
+                Selection::Field(field) => {
+                    let field_path = format!("{path}.{}", field.response_key());
+                    if field.name == "__typename" {
+                        continue;
+                    }
+                    if field.name.starts_with("__") {
+                        self.errors.push(format!(
+                            "{field_path}: introspection fields are not allowed; use the schema action"
+                        ));
+                        continue;
+                    }
+                    if parent_ty.kind == TypeKind::Union {
+                        self.errors.push(format!(
+                            "{field_path}: '{parent}' is a union; select its fields through \
+                             inline fragments (... on Member {{ ... }})"
+                        ));
+                        continue;
+                    }
+                    let Some(def) = parent_ty.field(&field.name) else {
+                        self.errors.push(format!(
+                            "{field_path}: field '{}' does not exist on type '{parent}'",
+                            field.name
+                        ));
+                        continue;
+                    };
+
+                    for arg in &field.arguments {
+                        if !def.args.iter().any(|a| &a.name == arg) {
+                            self.errors.push(format!(
+                                "{field_path}: unknown argument '{arg}' on field '{}'",
+                                field.name
+                            ));
+                        }
+                    }
+                    for arg in def.args.iter().filter(|a| a.is_required()) {
+                        if !field.arguments.contains(&arg.name) {
+                            self.errors.push(format!(
+                                "{field_path}: missing required argument '{}: {}'",
+                                arg.name, arg.ty.display
+                            ));
+                        }
+                    }
+
+                    let is_composite = schema
+                        .types
+                        .get(&def.ty.named)
+                        .is_some_and(|t| t.kind.is_composite());
+                    match (is_composite, field.selection.is_empty()) {
+                        (true, true) => self.errors.push(format!(
+                            "{field_path}: field of type '{}' needs a selection of subfields",
+                            def.ty.display
+                        )),
+                        (false, false) => self.errors.push(format!(
+                            "{field_path}: field of type '{}' cannot have subfields",
+                            def.ty.display
+                        )),
+                        (true, false) => {
+                            self.selection_set(
+                                &def.ty.named,
+                                &field.selection,
+                                &field_path,
+                                depth + 1,
+                            );
+                        }
+                        (false, true) => {}
+                    }
+                }
+                Selection::InlineFragment {
+                    type_condition,
+                    selection,
+                } => {
+                    let target = type_condition.as_deref().unwrap_or(parent);
+                    if self.check_fragment_type(target, path) {
+                        self.selection_set(target, selection, path, depth);
+                    }
+                }
+                Selection::FragmentSpread(name) => {
+                    let Some(fragment) = self.fragments.get(name.as_str()).copied() else {
+                        self.errors
+                            .push(format!("{path}: fragment '{name}' is not defined"));
+                        continue;
+                    };
+                    self.used_fragments.insert(fragment.name.as_str());
+                    if self.visiting.contains(&fragment.name.as_str()) {
+                        self.errors
+                            .push(format!("{path}: fragment '{name}' spreads itself"));
+                        continue;
+                    }
+                    if self.check_fragment_type(&fragment.type_condition, path) {
+                        self.visiting.push(fragment.name.as_str());
+                        self.selection_set(
+                            &fragment.type_condition,
+                            &fragment.selection,
+                            path,
+                            depth,
+                        );
+                        self.visiting.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_fragment_type(&mut self, type_name: &str, path: &str) -> bool {
+        match self.schema.types.get(type_name) {
+            Some(ty) if ty.kind.is_composite() => true,
+            Some(_) => {
+                self.errors.push(format!(
+                    "{path}: fragments can only be on object, interface or union types, not '{type_name}'"
+                ));
+                false
+            }
+            None => {
+                self.errors.push(format!(
+                    "{path}: type '{type_name}' does not exist in the schema"
+                ));
+                false
+            }
+        }
+    }
+}
+
+fn keyword(kind: TypeKind) -> &'static str {
+    match kind {
+        TypeKind::Scalar => "scalar",
+        TypeKind::Object => "type",
+        TypeKind::Interface => "interface",
+        TypeKind::Union => "union",
+        TypeKind::Enum => "enum",
+        TypeKind::InputObject => "input",
+    }
+}
+
+fn render_args(args: &[InputValue]) -> String {
+    if args.is_empty() {
+        return String::new();
+    }
+    let args: Vec<String> = args
+        .iter()
+        .map(|a| {
+            format!(
+                "{}: {}{}",
+                a.name,
+                a.ty.display,
+                if a.has_default { " = …" } else { "" }
+            )
+        })
+        .collect();
+    format!("({})", args.join(", "))
+}
+
+fn render_type(ty: &TypeDef) -> String {
+    let mut out = String::new();
+    if let Some(description) = &ty.description {
+        for line in description.lines() {
+            out.push_str(&format!("# {line}\n"));
+        }
+    }
+    let kw = keyword(ty.kind);
+    match ty.kind {
+        TypeKind::Scalar => out.push_str(&format!("{kw} {}\n", ty.name)),
+        TypeKind::Union => out.push_str(&format!(
+            "{kw} {} = {}\n",
+            ty.name,
+            ty.possible_types.join(" | ")
+        )),
+        TypeKind::Enum => {
+            out.push_str(&format!("{kw} {} {{\n", ty.name));
+            for value in &ty.enum_values {
+                out.push_str(&format!("  {value}\n"));
+            }
+            out.push_str("}\n");
+        }
+        TypeKind::InputObject => {
+            out.push_str(&format!("{kw} {} {{\n", ty.name));
+            for field in &ty.input_fields {
+                out.push_str(&format!("  {}: {}\n", field.name, field.ty.display));
+            }
+            out.push_str("}\n");
+        }
+        TypeKind::Object | TypeKind::Interface => {
+            out.push_str(&format!("{kw} {} {{\n", ty.name));
+            for field in &ty.fields {
+                if let Some(description) = &field.description {
+                    let first = description.lines().next().unwrap_or_default();
+                    out.push_str(&format!("  # {first}\n"));
+                }
+                out.push_str(&format!(
+                    "  {}{}: {}\n",
+                    field.name,
+                    render_args(&field.args),
+                    field.ty.display
+                ));
+            }
+            out.push_str("}\n");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::super::parser::parse_document;
+    use super::*;
+    use serde_json::json;
+
+    fn named(kind: &str, name: &str) -> Value {
+        json!({ "kind": kind, "name": name, "ofType": null })
+    }
+
+    fn non_null(inner: Value) -> Value {
+        json!({ "kind": "NON_NULL", "name": null, "ofType": inner })
+    }
+
+    fn list_of(inner: Value) -> Value {
+        json!({ "kind": "LIST", "name": null, "ofType": inner })
+    }
+
+    fn field(name: &str, ty: Value, args: Value) -> Value {
+        json!({ "name": name, "description": null, "args": args, "type": ty })
+    }
+
+    /// Introspection result for a small blog schema.
+    pub(crate) fn blog_introspection() -> Value {
+        json!({ "data": { "__schema": {
+            "queryType": { "name": "Query" },
+            "mutationType": { "name": "Mutation" },
+            "types": [
+                { "kind": "OBJECT", "name": "Query", "description": "Entry points",
+                  "fields": [
+                    field("user", named("OBJECT", "User"), json!([
+                        { "name": "id", "type": non_null(named("SCALAR", "ID")), "defaultValue": null }
+                    ])),
+                    field("posts", non_null(list_of(non_null(named("OBJECT", "Post")))), json!([
+                        { "name": "first", "type": named("SCALAR", "Int"), "defaultValue": "10" }
+                    ])),
+                    field("search", list_of(named("UNION", "SearchResult")), json!([
+                        { "name": "term", "type": non_null(named("SCALAR", "String")), "defaultValue": null }
+                    ])),
+                  ],
+                  "inputFields": null, "enumValues": null, "possibleTypes": null },
+                { "kind": "OBJECT", "name": "Mutation", "description": null,
+                  "fields": [ field("deletePost", named("SCALAR", "Boolean"), json!([])) ],
+                  "inputFields": null, "enumValues": null, "possibleTypes": null },
+                { "kind": "OBJECT", "name": "User", "description": null,
+                  "fields": [
+                    field("id", non_null(named("SCALAR", "ID")), json!([])),
+                    field("name", named("SCALAR", "String"), json!([])),
+                    field("role", named("ENUM", "Role"), json!([])),
+                    field("posts", list_of(named("OBJECT", "Post")), json!([])),
+                  ],
+                  "inputFields": null, "enumValues": null, "possibleTypes": null },
+                { "kind": "OBJECT", "name": "Post", "description": null,
+                  "fields": [
+                    field("title", named("SCALAR", "String"), json!([])),
+                    field("author", named("OBJECT", "User"), json!([])),
+                  ],
+                  "inputFields": null, "enumValues": null, "possibleTypes": null },
+                { "kind": "UNION", "name": "SearchResult", "description": null,
+                  "fields": null, "inputFields": null, "enumValues": null,
+                  "possibleTypes": [ { "name": "User" }, { "name": "Post" } ] },
+                { "kind": "ENUM", "name": "Role", "description": null, "fields": null,
+                  "inputFields": null, "enumValues": [ { "name": "ADMIN" }, { "name": "MEMBER" } ],
+                  "possibleTypes": null },
+                { "kind": "SCALAR", "name": "ID", "description": null, "fields": null,
+                  "inputFields": null, "enumValues": null, "possibleTypes": null },
+                { "kind": "SCALAR", "name": "String", "description": null, "fields": null,
+                  "inputFields": null, "enumValues": null, "possibleTypes": null },
+                { "kind": "SCALAR", "name": "Int", "description": null, "fields": null,
+                  "inputFields": null, "enumValues": null, "possibleTypes": null },
+                { "kind": "SCALAR", "name": "Boolean", "description": null, "fields": null,
+                  "inputFields": null, "enumValues": null, "possibleTypes": null },
+            ]
+        } } })
+    }
+
+    fn schema() -> GraphQlSchema {
+        GraphQlSchema::from_introspection(&blog_introspection()).unwrap()
+    }
+
+    fn validate(query: &str) -> Result<(), Vec<String>> {
+        schema().validate(&parse_document(query).unwrap(), None)
+    }
+
+    #[test]
+    fn describes_root_and_named_types() {
+        let schema = schema();
+        let root = schema.describe(None).unwrap();
+        assert!(root.contains("# Entry points"), "{root}");
+        assert!(root.contains("user(id: ID!): User"), "{root}");
+        assert!(root.contains("posts(first: Int = …): [Post!]!"), "{root}");
+        assert!(root.contains("# union SearchResult"), "{root}");
+        assert!(!root.contains("Mutation"), "{root}");
+
+        assert!(schema.describe(Some("Role")).unwrap().contains("ADMIN"));
+        assert!(
+            schema
+                .describe(Some("SearchResult"))
+                .unwrap()
+                .contains("union SearchResult = User | Post")
+        );
+        assert!(schema.describe(Some("Nope")).is_err());
+    }
+
+    #[test]
+    fn accepts_valid_queries() {
+        validate(
+            r#"
+            query Feed($id: ID!) {
+              me: user(id: $id) { __typename name role posts { ...PostFields } }
+              posts { title }
+              search(term: "rust") { ... on User { name } ... on Post { title } }
+            }
+            fragment PostFields on Post { title author { id } }
+            "#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_fields_arguments_and_bad_selections() {
+        let errors = validate(
+            r#"{
+              user { nickname role { x } posts }
+              posts(limit: 5) { title }
+              search(term: "a") { name }
+            }"#,
+        )
+        .unwrap_err();
+        let all = errors.join("\n");
+        assert!(
+            all.contains("Query.user: missing required argument 'id: ID!'"),
+            "{all}"
+        );
+        assert!(
+            all.contains("field 'nickname' does not exist on type 'User'"),
+            "{all}"
+        );
+        assert!(
+            all.contains("Query.user.role: field of type 'Role' cannot have subfields"),
+            "{all}"
+        );
+        assert!(
+            all.contains("Query.user.posts: field of type '[Post]' needs a selection"),
+            "{all}"
+        );
+        assert!(all.contains("unknown argument 'limit'"), "{all}");
+        assert!(all.contains("'SearchResult' is a union"), "{all}");
+    }
+
+    #[test]
+    fn rejects_mutations_and_ambiguous_documents() {
+        let errors = validate("mutation { deletePost }").unwrap_err();
+        assert!(errors[0].contains("Only queries are allowed"), "{errors:?}");
+
+        let errors =
+            validate("query A { posts { title } } query B { posts { title } }").unwrap_err();
+        assert!(errors[0].contains("operation_name"), "{errors:?}");
+        let doc =
+            parse_document("query A { posts { title } } query B { posts { title } }").unwrap();
+        schema().validate(&doc, Some("B")).unwrap();
+        assert!(schema().validate(&doc, Some("C")).is_err());
+    }
+
+    #[test]
+    fn rejects_fragment_problems_and_deep_queries() {
+        let all = validate(
+            "{ posts { ...Missing ...Loop ... on Role { x } } } \
+             fragment Loop on Post { author { posts { ...Loop } } } \
+             fragment Unused on Post { title }",
+        )
+        .unwrap_err()
+        .join("\n");
+        assert!(all.contains("fragment 'Missing' is not defined"), "{all}");
+        assert!(all.contains("fragment 'Loop' spreads itself"), "{all}");
+        assert!(all.contains("not 'Role'"), "{all}");
+        assert!(all.contains("Fragment 'Unused' is never used"), "{all}");
+
+        let deep = format!(
+            "{{ posts {{ {} title {} }} }}",
+            "author { posts { ".repeat(6),
+            "} } ".repeat(6)
+        );
+        let all = validate(&deep).unwrap_err().join("\n");
+        assert!(all.contains("nested deeper than"), "{all}");
+
+        let all = validate("{ __schema { types { name } } }")
+            .unwrap_err()
+            .join("\n");
+        assert!(
+            all.contains("introspection fields are not allowed"),
+            "{all}"
+        );
+    }
+
+    #[test]
+    fn reports_introspection_errors() {
+        let err = GraphQlSchema::from_introspection(
+            &json!({ "errors": [ { "message": "introspection disabled" } ] }),
+        )
+        .unwrap_err();
+        assert!(err.contains("introspection disabled"), "{err}");
+    }
+}
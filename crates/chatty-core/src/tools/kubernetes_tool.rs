@@ -0,0 +1,551 @@
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::Tool;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::models::execution_approval_store::{PendingApprovals, request_execution_approval};
+use crate::services::kubernetes_service::{
+    KubectlOutput, KubernetesAction, KubernetesService, MAX_LOG_LINES,
+};
+use crate::settings::models::execution_settings::ApprovalMode;
+use crate::tools::ToolError;
+
+/// JSON schema properties shared by every kubectl tool.
+fn scope_properties() -> serde_json::Value {
+    serde_json::json!({
+        "context": {
+            "type": "string",
+            "description": "kubeconfig context to use. Omit for the default allowed context."
+        },
+        "namespace": {
+            "type": "string",
+            "description": "Namespace to use. Omit for the default allowed namespace."
+        }
+    })
+}
+
+/// Merge tool-specific properties into the shared scope properties.
+fn parameters(properties: serde_json::Value, required: &[&str]) -> serde_json::Value {
+    let mut merged = scope_properties();
+    if let (Some(merged), Some(extra)) = (merged.as_object_mut(), properties.as_object()) {
+        merged.extend(extra.clone());
+    }
+    serde_json::json!({
+        "type": "object",
+        "properties": merged,
+        "required": required,
+    })
+}
+
+// ── K8sGetTool ──────────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Serialize)]
+pub struct K8sGetArgs {
+    /// Resource kind, e.g. "pods", "deployments", "nodes".
+    pub kind: String,
+    /// Optional resource name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Optional label selector, e.g. "app=web".
+    #[serde(default)]
+    pub selector: Option<String>,
+    /// Output format: wide (default), yaml, json or name.
+    #[serde(default)]
+    pub output: Option<String>,
+    #[serde(default)]
+    pub all_namespaces: bool,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+/// List or fetch Kubernetes resources (`kubectl get`).
+#[derive(Clone)]
+pub struct K8sGetTool {
+    service: Arc<KubernetesService>,
+}
+
+impl K8sGetTool {
+    pub fn new(service: Arc<KubernetesService>) -> Self {
+        Self { service }
+    }
+}
+
+impl Tool for K8sGetTool {
+    const NAME: &'static str = "k8s_get";
+    type Error = ToolError;
+    type Args = K8sGetArgs;
+    type Output = KubectlOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "k8s_get".to_string(),
+            description: "List or fetch Kubernetes resources (kubectl get). Read-only. Use to \
+                         check pod status, restarts, deployments, nodes and other resources. \
+                         Secrets cannot be read."
+                .to_string(),
+            parameters: parameters(
+                serde_json::json!({
+                    "kind": {
+                        "type": "string",
+                        "description": "Resource kind, e.g. pods, deployments, services, nodes"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Optional resource name"
+                    },
+                    "selector": {
+                        "type": "string",
+                        "description": "Optional label selector, e.g. app=web"
+                    },
+                    "output": {
+                        "type": "string",
+                        "enum": ["wide", "yaml", "json", "name"],
+                        "description": "Output format. Default: wide"
+                    },
+                    "all_namespaces": {
+                        "type": "boolean",
+                        "description": "List across all namespaces (only when namespaces are unrestricted)"
+                    }
+                }),
+                &["kind"],
+            ),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let kubectl_args = self.service.scope().get_args(
+            &args.kind,
+            args.name.as_deref(),
+            args.selector.as_deref(),
+            args.output.as_deref(),
+            args.context.as_deref(),
+            args.namespace.as_deref(),
+            args.all_namespaces,
+        )?;
+        Ok(self.service.run(kubectl_args).await?)
+    }
+}
+
+// ── K8sDescribeTool ─────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Serialize)]
+pub struct K8sDescribeArgs {
+    pub kind: String,
+    pub name: String,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+/// Show detailed state of a Kubernetes resource (`kubectl describe`).
+#[derive(Clone)]
+pub struct K8sDescribeTool {
+    service: Arc<KubernetesService>,
+}
+
+impl K8sDescribeTool {
+    pub fn new(service: Arc<KubernetesService>) -> Self {
+        Self { service }
+    }
+}
+
+impl Tool for K8sDescribeTool {
+    const NAME: &'static str = "k8s_describe";
+    type Error = ToolError;
+    type Args = K8sDescribeArgs;
+    type Output = KubectlOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "k8s_describe".to_string(),
+            description: "Show detailed state of one Kubernetes resource (kubectl describe), \
+                         including conditions and recent events. Read-only. Useful for \
+                         diagnosing CrashLoopBackOff, scheduling or image pull problems."
+                .to_string(),
+            parameters: parameters(
+                serde_json::json!({
+                    "kind": {
+                        "type": "string",
+                        "description": "Resource kind, e.g. pod, deployment, node"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Resource name"
+                    }
+                }),
+                &["kind", "name"],
+            ),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let kubectl_args = self.service.scope().describe_args(
+            &args.kind,
+            &args.name,
+            args.context.as_deref(),
+            args.namespace.as_deref(),
+        )?;
+        Ok(self.service.run(kubectl_args).await?)
+    }
+}
+
+// ── K8sLogsTool ─────────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Serialize)]
+pub struct K8sLogsArgs {
+    pub pod: String,
+    #[serde(default)]
+    pub container: Option<String>,
+    /// Number of trailing lines to return. Defaults to 200.
+    #[serde(default = "default_tail_lines")]
+    pub tail_lines: u32,
+    /// Return logs of the previous (crashed) container instance.
+    #[serde(default)]
+    pub previous: bool,
+    /// Only return logs newer than this relative duration, e.g. "15m".
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+fn default_tail_lines() -> u32 {
+    200
+}
+
+/// Fetch container logs from a pod (`kubectl logs`).
+#[derive(Clone)]
+pub struct K8sLogsTool {
+    service: Arc<KubernetesService>,
+}
+
+impl K8sLogsTool {
+    pub fn new(service: Arc<KubernetesService>) -> Self {
+        Self { service }
+    }
+}
+
+impl Tool for K8sLogsTool {
+    const NAME: &'static str = "k8s_logs";
+    type Error = ToolError;
+    type Args = K8sLogsArgs;
+    type Output = KubectlOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "k8s_logs".to_string(),
+            description: format!(
+                "Fetch the last lines of a pod's container logs (kubectl logs). Read-only. \
+                 Set 'previous' to read the logs of a crashed container. At most {} lines.",
+                MAX_LOG_LINES
+            ),
+            parameters: parameters(
+                serde_json::json!({
+                    "pod": {
+                        "type": "string",
+                        "description": "Pod name"
+                    },
+                    "container": {
+                        "type": "string",
+                        "description": "Container name (required for multi-container pods)"
+                    },
+                    "tail_lines": {
+                        "type": "integer",
+                        "description": "Number of trailing lines. Default: 200"
+                    },
+                    "previous": {
+                        "type": "boolean",
+                        "description": "Logs of the previous container instance. Default: false"
+                    },
+                    "since": {
+                        "type": "string",
+                        "description": "Only logs newer than this duration, e.g. 30s, 15m, 2h"
+                    }
+                }),
+                &["pod"],
+            ),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let kubectl_args = self.service.scope().logs_args(
+            &args.pod,
+            args.container.as_deref(),
+            args.tail_lines,
+            args.previous,
+            args.since.as_deref(),
+            args.context.as_deref(),
+            args.namespace.as_deref(),
+        )?;
+        Ok(self.service.run(kubectl_args).await?)
+    }
+}
+
+// ── K8sEventsTool ───────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Serialize)]
+pub struct K8sEventsArgs {
+    /// Only events about the object with this name.
+    #[serde(default)]
+    pub object: Option<String>,
+    #[serde(default)]
+    pub all_namespaces: bool,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+/// List cluster events, oldest first (`kubectl get events`).
+#[derive(Clone)]
+pub struct K8sEventsTool {
+    service: Arc<KubernetesService>,
+}
+
+impl K8sEventsTool {
+    pub fn new(service: Arc<KubernetesService>) -> Self {
+        Self { service }
+    }
+}
+
+impl Tool for K8sEventsTool {
+    const NAME: &'static str = "k8s_events";
+    type Error = ToolError;
+    type Args = K8sEventsArgs;
+    type Output = KubectlOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "k8s_events".to_string(),
+            description: "List Kubernetes events sorted by time, newest last (kubectl get \
+                         events). Read-only. Optionally restrict to events about one object."
+                .to_string(),
+            parameters: parameters(
+                serde_json::json!({
+                    "object": {
+                        "type": "string",
+                        "description": "Only events about the object with this name"
+                    },
+                    "all_namespaces": {
+                        "type": "boolean",
+                        "description": "List across all namespaces (only when namespaces are unrestricted)"
+                    }
+                }),
+                &[],
+            ),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let kubectl_args = self.service.scope().events_args(
+            args.object.as_deref(),
+            args.context.as_deref(),
+            args.namespace.as_deref(),
+            args.all_namespaces,
+        )?;
+        Ok(self.service.run(kubectl_args).await?)
+    }
+}
+
+// ── K8sActionTool ───────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Serialize)]
+pub struct K8sActionArgs {
+    /// One of: delete, rollout_restart, scale, cordon, uncordon.
+    pub action: String,
+    /// Resource kind (not used for cordon/uncordon).
+    #[serde(default)]
+    pub kind: Option<String>,
+    /// Resource or node name.
+    pub name: String,
+    /// Replica count for scale.
+    #[serde(default)]
+    pub replicas: Option<u32>,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+impl K8sActionArgs {
+    fn into_action(self) -> Result<KubernetesAction, ToolError> {
+        let kind = || {
+            self.kind.clone().ok_or_else(|| {
+                ToolError::OperationFailed(format!("'kind' is required for {}", self.action))
+            })
+        };
+        match self.action.as_str() {
+            "delete" => Ok(KubernetesAction::Delete {
+                kind: kind()?,
+                name: self.name,
+            }),
+            "rollout_restart" => Ok(KubernetesAction::RolloutRestart {
+                kind: kind()?,
+                name: self.name,
+            }),
+            "scale" => Ok(KubernetesAction::Scale {
+                kind: kind()?,
+                replicas: self.replicas.ok_or_else(|| {
+                    ToolError::OperationFailed("'replicas' is required for scale".to_string())
+                })?,
+                name: self.name,
+            }),
+            "cordon" => Ok(KubernetesAction::Cordon { node: self.name }),
+            "uncordon" => Ok(KubernetesAction::Uncordon { node: self.name }),
+            other => Err(ToolError::OperationFailed(format!(
+                "Unknown action '{}' (use delete, rollout_restart, scale, cordon or uncordon)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Run a mutating kubectl command. Always asks the user first, regardless of
+/// the configured approval mode.
+#[derive(Clone)]
+pub struct K8sActionTool {
+    service: Arc<KubernetesService>,
+    pending_approvals: PendingApprovals,
+}
+
+impl K8sActionTool {
+    pub fn new(service: Arc<KubernetesService>, pending_approvals: PendingApprovals) -> Self {
+        Self {
+            service,
+            pending_approvals,
+        }
+    }
+}
+
+impl Tool for K8sActionTool {
+    const NAME: &'static str = "k8s_action";
+    type Error = ToolError;
+    type Args = K8sActionArgs;
+    type Output = KubectlOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "k8s_action".to_string(),
+            description: "Change cluster state: delete a resource, restart a rollout, scale a \
+                         workload, or cordon/uncordon a node. Every call requires explicit \
+                         user approval. Inspect with k8s_get/k8s_describe first and explain \
+                         why the action is needed."
+                .to_string(),
+            parameters: parameters(
+                serde_json::json!({
+                    "action": {
+                        "type": "string",
+                        "enum": ["delete", "rollout_restart", "scale", "cordon", "uncordon"],
+                        "description": "The action to perform"
+                    },
+                    "kind": {
+                        "type": "string",
+                        "description": "Resource kind, e.g. pod or deployment (not used for cordon/uncordon)"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Resource name, or node name for cordon/uncordon"
+                    },
+                    "replicas": {
+                        "type": "integer",
+                        "description": "Desired replica count (scale only)"
+                    }
+                }),
+                &["action", "name"],
+            ),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let context = args.context.clone();
+        let namespace = args.namespace.clone();
+        let action = args.into_action()?;
+        let kubectl_args =
+            self.service
+                .scope()
+                .action_args(&action, context.as_deref(), namespace.as_deref())?;
+
+        let approved = request_execution_approval(
+            &self.pending_approvals,
+            &ApprovalMode::AlwaysAsk,
+            &format!("[kubectl] {}", kubectl_args.join(" ")),
+            false,
+        )
+        .await?;
+
+        if !approved {
+            return Err(ToolError::OperationFailed(
+                "Kubernetes action denied by user".to_string(),
+            ));
+        }
+
+        tracing::info!(action = ?action, "Running kubectl action");
+        Ok(self.service.run(kubectl_args).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action_args(action: &str, kind: Option<&str>, replicas: Option<u32>) -> K8sActionArgs {
+        K8sActionArgs {
+            action: action.to_string(),
+            kind: kind.map(str::to_string),
+            name: "web".to_string(),
+            replicas,
+            context: None,
+            namespace: None,
+        }
+    }
+
+    #[test]
+    fn action_args_map_to_actions() {
+        assert_eq!(
+            action_args("scale", Some("deployment"), Some(3))
+                .into_action()
+                .unwrap(),
+            KubernetesAction::Scale {
+                kind: "deployment".to_string(),
+                name: "web".to_string(),
+                replicas: 3,
+            }
+        );
+        assert_eq!(
+            action_args("cordon", None, None).into_action().unwrap(),
+            KubernetesAction::Cordon {
+                node: "web".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn action_args_require_kind_and_replicas() {
+        assert!(action_args("delete", None, None).into_action().is_err());
+        assert!(
+            action_args("scale", Some("deployment"), None)
+                .into_action()
+                .is_err()
+        );
+        assert!(
+            action_args("exec", Some("pod"), None)
+                .into_action()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parameters_include_scope_properties() {
+        let params = parameters(serde_json::json!({"pod": {"type": "string"}}), &["pod"]);
+        let properties = params["properties"].as_object().unwrap();
+        assert!(properties.contains_key("context"));
+        assert!(properties.contains_key("namespace"));
+        assert!(properties.contains_key("pod"));
+        assert_eq!(params["required"], serde_json::json!(["pod"]));
+    }
+}
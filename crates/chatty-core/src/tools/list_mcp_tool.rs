@@ -115,6 +115,9 @@ mod tests {
             api_key: None,
             enabled: true,
             is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
         }
     }
 
@@ -158,6 +161,9 @@ mod tests {
             api_key: Some("sk-super-secret".to_string()),
             enabled: true,
             is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
         };
         let repo = Arc::new(MockMcpRepository::with_servers(vec![server]));
         let tool = ListMcpTool::new(repo);
@@ -209,6 +215,9 @@ mod tests {
             api_key: None,
             enabled: false,
             is_module: false,
+            result_cache_ttl_secs: 0,
+            cached_tools: Vec::new(),
+            launch: None,
         };
         let repo = Arc::new(MockMcpRepository::with_servers(vec![server]));
         let tool = ListMcpTool::new(repo);
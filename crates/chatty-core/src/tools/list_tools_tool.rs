@@ -214,6 +214,86 @@ impl ListToolsTool {
             ]);
         }
 
+        if tools.kubernetes {
+            native_tools.extend(vec![
+                ToolInfo {
+                    name: "k8s_get".to_string(),
+                    description: "List or fetch Kubernetes resources (kubectl get); secrets are never read".to_string(),
+                    source: "native".to_string(),
+                },
+                ToolInfo {
+                    name: "k8s_describe".to_string(),
+                    description: "Show detailed state and recent events of one Kubernetes resource".to_string(),
+                    source: "native".to_string(),
+                },
+                ToolInfo {
+                    name: "k8s_logs".to_string(),
+                    description: "Fetch the last lines of a pod's container logs, optionally from the previous instance".to_string(),
+                    source: "native".to_string(),
+                },
+                ToolInfo {
+                    name: "k8s_events".to_string(),
+                    description: "List Kubernetes events sorted by time, optionally for one object".to_string(),
+                    source: "native".to_string(),
+                },
+            ]);
+        }
+
+        if tools.kubernetes_actions {
+            native_tools.push(ToolInfo {
+                name: "k8s_action".to_string(),
+                description: "Delete, rollout-restart, scale, or cordon/uncordon (always requires user approval)".to_string(),
+                source: "native".to_string(),
+            });
+        }
+
+        if tools.docker {
+            native_tools.push(ToolInfo {
+                name: "docker".to_string(),
+                description: "List containers and images, read container logs, exec in a container or run docker compose up/down in the workspace (exec and compose require approval)".to_string(),
+                source: "native".to_string(),
+            });
+        }
+
+        if tools.ssh {
+            native_tools.push(ToolInfo {
+                name: "ssh_execute".to_string(),
+                description: "Run a shell command on a configured remote host over SSH (always requires user approval)".to_string(),
+                source: "native".to_string(),
+            });
+        }
+
+        if tools.notes {
+            native_tools.extend(vec![
+                ToolInfo {
+                    name: "notes_search".to_string(),
+                    description: "Search note names and contents in the Markdown notes vault"
+                        .to_string(),
+                    source: "native".to_string(),
+                },
+                ToolInfo {
+                    name: "notes_read".to_string(),
+                    description: "Read a note by vault path or [[wiki link]] name".to_string(),
+                    source: "native".to_string(),
+                },
+                ToolInfo {
+                    name: "notes_append".to_string(),
+                    description:
+                        "Append Markdown to a note, creating it if needed (requires approval)"
+                            .to_string(),
+                    source: "native".to_string(),
+                },
+            ]);
+        }
+
+        if tools.citations {
+            native_tools.push(ToolInfo {
+                name: "citations".to_string(),
+                description: "Search the BibTeX/Zotero reference library and format citations (APA, MLA, Chicago, IEEE)".to_string(),
+                source: "native".to_string(),
+            });
+        }
+
         if tools.search {
             native_tools.extend(vec![
                 ToolInfo {
@@ -366,6 +446,44 @@ impl ListToolsTool {
             ]);
         }
 
+        if tools.conversation_variables {
+            native_tools.extend(vec![
+                ToolInfo {
+                    name: "get_variable".to_string(),
+                    description: "Read a conversation variable (or list all of them). Variables persist across turns of this conversation.".to_string(),
+                    source: "native".to_string(),
+                },
+                ToolInfo {
+                    name: "set_variable".to_string(),
+                    description: "Save or delete a conversation variable to keep plan state, counters, or intermediate results outside the message history.".to_string(),
+                    source: "native".to_string(),
+                },
+            ]);
+        }
+
+        if tools.planner {
+            native_tools.extend(vec![
+                ToolInfo {
+                    name: "create_plan".to_string(),
+                    description: "Record the structured plan for a task; shown to the user as a live checklist.".to_string(),
+                    source: "native".to_string(),
+                },
+                ToolInfo {
+                    name: "update_plan".to_string(),
+                    description: "Update the status (pending, in_progress, done, skipped, failed) of plan steps.".to_string(),
+                    source: "native".to_string(),
+                },
+            ]);
+        }
+
+        if tools.ask_user {
+            native_tools.push(ToolInfo {
+                name: "ask_user".to_string(),
+                description: "Ask the user a clarifying question (free text or multiple choice) and wait for the answer.".to_string(),
+                source: "native".to_string(),
+            });
+        }
+
         if tools.search_web {
             native_tools.push(ToolInfo {
                 name: "search_web".to_string(),
@@ -445,6 +563,11 @@ impl Tool for ListToolsTool {
                          - Agent todo tools: write_todos, update_todo, verify_completion for multi-step task tracking\n\
                          - Filesystem tools: read_file, final_answer, write_file, list_directory, optional doc_retriever, etc.\n\
                          - Git tools: git_status, git_diff, git_log, git_add, git_create_branch, git_switch_branch, git_commit\n\
+                         - Kubernetes tools: k8s_get, k8s_describe, k8s_logs, k8s_events, optional k8s_action\n\
+                         - docker: Containers, images, logs, exec and compose in the workspace\n\
+                         - ssh_execute: Run commands on allowlisted remote hosts\n\
+                         - Notes tools: notes_search, notes_read, notes_append (Markdown/Obsidian vault)\n\
+                         - citations: Search the reference library and format citations\n\
                          - add_attachment: Display images or PDFs inline in chat responses\n\
                          - PDF tools: pdf_info, pdf_extract_text, pdf_to_image\n\
                          - Data query tools: file_structure_detector, profile_data, query_data, describe_data (workspace/data map and SQL/profile CSV/JSON/Parquet via DuckDB)\n\
@@ -519,6 +642,12 @@ mod tests {
             fetch: false,
             shell: false,
             git: false,
+            kubernetes: false,
+            kubernetes_actions: false,
+            docker: false,
+            ssh: false,
+            notes: false,
+            citations: false,
             search: false,
             add_attachment: false,
             excel_read: false,
@@ -539,6 +668,9 @@ mod tests {
             browser_use: false,
             daytona: false,
             publish_module: false,
+            conversation_variables: false,
+            planner: false,
+            ask_user: false,
         }
     }
 
@@ -551,6 +683,12 @@ mod tests {
             fetch: true,
             shell: true,
             git: true,
+            kubernetes: true,
+            kubernetes_actions: true,
+            docker: true,
+            ssh: true,
+            notes: true,
+            citations: true,
             search: true,
             add_attachment: true,
             excel_read: true,
@@ -571,6 +709,9 @@ mod tests {
             browser_use: true,
             daytona: true,
             publish_module: true,
+            conversation_variables: true,
+            planner: true,
+            ask_user: true,
         }
     }
 
@@ -676,6 +817,17 @@ mod tests {
             "git_create_branch",
             "git_switch_branch",
             "git_commit",
+            "k8s_get",
+            "k8s_describe",
+            "k8s_logs",
+            "k8s_events",
+            "k8s_action",
+            "docker",
+            "ssh_execute",
+            "notes_search",
+            "notes_read",
+            "notes_append",
+            "citations",
             "search_code",
             "find_files",
             "find_definition",
@@ -701,6 +853,11 @@ mod tests {
             "sub_agent",
             "browser_use",
             "daytona_run",
+            "get_variable",
+            "set_variable",
+            "create_plan",
+            "update_plan",
+            "ask_user",
         ];
         for name in &expected {
             assert!(names.contains(&name.to_string()), "missing {name}");
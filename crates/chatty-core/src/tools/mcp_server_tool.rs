@@ -0,0 +1,87 @@
+//! A tool exposed by an MCP server, called through [`McpService`].
+//!
+//! Unlike rig's built-in MCP tool, which holds the peer of one connection,
+//! this resolves the server on every call, so it keeps working after the
+//! server was stopped for being idle: the call waits while it reconnects.
+//! Results answered from the server's result cache start with
+//! [`CACHED_RESULT_PREFIX`] so the trace and the model can tell.
+
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::{ToolDyn, ToolError};
+use rig_core::wasm_compat::WasmBoxedFuture;
+use rmcp::model::{CallToolRequestParams, RawContent};
+
+use crate::models::message_types::CACHED_RESULT_PREFIX;
+use crate::services::McpService;
+
+pub struct McpServerTool {
+    service: McpService,
+    server: String,
+    tool: rmcp::model::Tool,
+}
+
+impl McpServerTool {
+    pub fn new(service: McpService, server: String, tool: rmcp::model::Tool) -> Self {
+        Self {
+            service,
+            server,
+            tool,
+        }
+    }
+}
+
+impl ToolDyn for McpServerTool {
+    fn name(&self) -> String {
+        self.tool.name.to_string()
+    }
+
+    fn definition<'a>(&'a self, _prompt: String) -> WasmBoxedFuture<'a, ToolDefinition> {
+        Box::pin(async move {
+            ToolDefinition {
+                name: self.tool.name.to_string(),
+                description: self
+                    .tool
+                    .description
+                    .as_deref()
+                    .unwrap_or_default()
+                    .to_string(),
+                parameters: serde_json::Value::Object((*self.tool.input_schema).clone()),
+            }
+        })
+    }
+
+    fn call<'a>(&'a self, args: String) -> WasmBoxedFuture<'a, Result<String, ToolError>> {
+        Box::pin(async move {
+            let mut params = CallToolRequestParams::new(self.tool.name.clone());
+            if !args.trim().is_empty() {
+                let arguments: serde_json::Map<String, serde_json::Value> =
+                    serde_json::from_str(&args).map_err(ToolError::JsonError)?;
+                params = params.with_arguments(arguments);
+            }
+
+            let (result, cached) = self
+                .service
+                .call_tool_cached(&self.server, params)
+                .await
+                .map_err(|e| ToolError::ToolCallError(e.into()))?;
+
+            let output = result
+                .content
+                .iter()
+                .map(|content| match &content.raw {
+                    RawContent::Text(text) => text.text.clone(),
+                    other => serde_json::to_string(other).unwrap_or_default(),
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            if result.is_error.unwrap_or(false) {
+                return Err(ToolError::ToolCallError(output.into()));
+            }
+            if cached {
+                return Ok(format!("{CACHED_RESULT_PREFIX}\n{output}"));
+            }
+            Ok(output)
+        })
+    }
+}
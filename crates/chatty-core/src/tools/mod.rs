@@ -14,12 +14,17 @@ impl From<anyhow::Error> for ToolError {
 
 pub mod add_attachment_tool;
 pub mod agent_todo_tool;
+pub mod ask_user_tool;
 pub mod browser_use_tool;
 pub mod chart_tool;
+pub mod citation_tool;
+pub mod conversation_variables_tool;
+pub mod custom_http_tool;
 #[cfg(feature = "duckdb")]
 pub mod data_query_tool;
 pub mod daytona_tool;
 pub mod doc_retriever_tool;
+pub mod docker_tool;
 #[cfg(feature = "docx")]
 pub mod docx_tool;
 #[cfg(feature = "excel")]
@@ -31,10 +36,14 @@ pub mod file_structure_tool;
 pub mod filesystem_tool;
 pub mod filesystem_write_tool;
 pub mod git_tool;
+pub mod graphql_tool;
 pub mod invoke_agent_tool;
+pub mod kubernetes_tool;
 pub mod list_agents_tool;
 pub mod list_mcp_tool;
 pub mod list_tools_tool;
+pub mod mcp_server_tool;
+pub mod notes_tool;
 mod path_utils;
 #[cfg(feature = "pdf")]
 pub mod pdf_extract_text_tool;
@@ -42,6 +51,7 @@ pub mod pdf_extract_text_tool;
 pub mod pdf_info_tool;
 #[cfg(feature = "pdf")]
 pub mod pdf_to_image_tool;
+pub mod plan_tool;
 #[cfg(feature = "pptx")]
 pub mod pptx_tool;
 pub mod publish_module_tool;
@@ -52,6 +62,7 @@ pub mod search_memory_tool;
 pub mod search_tool;
 pub mod search_web_tool;
 pub mod shell_tool;
+pub mod ssh_tool;
 pub mod sub_agent_tool;
 #[cfg(test)]
 pub mod test_helpers;
@@ -60,12 +71,17 @@ pub mod typst_tool;
 
 pub use add_attachment_tool::{AddAttachmentTool, PendingArtifacts};
 pub use agent_todo_tool::{UpdateTodoTool, VerifyCompletionTool, WriteTodosTool};
+pub use ask_user_tool::AskUserTool;
 pub use browser_use_tool::BrowserUseTool;
 pub use chart_tool::CreateChartTool;
+pub use citation_tool::CitationTool;
+pub use conversation_variables_tool::{ConversationVariables, GetVariableTool, SetVariableTool};
+pub use custom_http_tool::{CustomHttpMethod, CustomHttpTool, CustomHttpToolConfig};
 #[cfg(feature = "duckdb")]
 pub use data_query_tool::{DescribeDataTool, ProfileDataTool, QueryDataTool};
 pub use daytona_tool::DaytonaTool;
 pub use doc_retriever_tool::DocRetrieverTool;
+pub use docker_tool::DockerTool;
 #[cfg(feature = "docx")]
 pub use docx_tool::{ReadDocxTool, WriteDocxTool};
 #[cfg(feature = "excel")]
@@ -83,16 +99,23 @@ pub use git_tool::{
     GitAddTool, GitCommitTool, GitCreateBranchTool, GitDiffTool, GitLogTool, GitStatusTool,
     GitSwitchBranchTool,
 };
+pub use graphql_tool::{GraphQlEndpointConfig, GraphQlTool};
 pub use invoke_agent_tool::InvokeAgentTool;
+pub use kubernetes_tool::{K8sActionTool, K8sDescribeTool, K8sEventsTool, K8sGetTool, K8sLogsTool};
 pub use list_agents_tool::{ListAgentsTool, LocalModuleAgentSummary};
 pub use list_mcp_tool::ListMcpTool;
 pub use list_tools_tool::ListToolsTool;
+pub use mcp_server_tool::McpServerTool;
+pub use notes_tool::{NotesAppendTool, NotesReadTool, NotesSearchTool};
 #[cfg(feature = "pdf")]
 pub use pdf_extract_text_tool::PdfExtractTextTool;
 #[cfg(feature = "pdf")]
 pub use pdf_info_tool::PdfInfoTool;
 #[cfg(feature = "pdf")]
 pub use pdf_to_image_tool::PdfToImageTool;
+pub use plan_tool::{
+    CreatePlanTool, ExecutionPlan, PlanStep, PlanStepStatus, SharedPlan, UpdatePlanTool,
+};
 #[cfg(feature = "pptx")]
 pub use pptx_tool::{ReadPptxTool, WritePptxTool};
 pub use publish_module_tool::PublishModuleTool;
@@ -100,11 +123,13 @@ pub use read_skill_tool::ReadSkillTool;
 pub use remember_tool::RememberTool;
 pub use save_skill_tool::{SKILL_TITLE_PREFIX, SaveSkillTool};
 pub use search_memory_tool::{
-    SearchMemoryTool, build_memory_context_block, merge_search_results, select_context_hits,
+    SearchMemoryTool, build_memory_context_block, fuse_search_results_rrf, merge_search_results,
+    select_context_hits,
 };
 pub use search_tool::{FindDefinitionTool, FindFilesTool, SearchCodeTool};
 pub use search_web_tool::SearchWebTool;
 pub use shell_tool::{ShellCdTool, ShellExecuteTool, ShellSetEnvTool, ShellStatusTool};
+pub use ssh_tool::SshExecuteTool;
 pub use sub_agent_tool::SubAgentTool;
 #[cfg(feature = "math-render")]
 pub use typst_tool::CompileTypstTool;
@@ -0,0 +1,235 @@
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::Tool;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::models::execution_approval_store::{PendingApprovals, request_execution_approval};
+use crate::services::notes_service::{NoteContent, NoteMatch, NotesService};
+use crate::settings::models::execution_settings::ApprovalMode;
+use crate::tools::ToolError;
+
+/// Default and maximum number of matches returned by `notes_search`.
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 100;
+
+/// Run a blocking vault operation off the async runtime.
+async fn blocking<T, F>(f: F) -> Result<T, ToolError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| ToolError::OperationFailed(format!("Notes task failed: {e}")))?
+        .map_err(ToolError::from)
+}
+
+// ── NotesSearchTool ─────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Serialize)]
+pub struct NotesSearchArgs {
+    /// Text to look for in note names and contents.
+    pub query: String,
+    /// Maximum number of matches.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotesSearchOutput {
+    pub matches: Vec<NoteMatch>,
+}
+
+/// Search the notes vault by name and content.
+#[derive(Clone)]
+pub struct NotesSearchTool {
+    service: Arc<NotesService>,
+}
+
+impl NotesSearchTool {
+    pub fn new(service: Arc<NotesService>) -> Self {
+        Self { service }
+    }
+}
+
+impl Tool for NotesSearchTool {
+    const NAME: &'static str = "notes_search";
+    type Error = ToolError;
+    type Args = NotesSearchArgs;
+    type Output = NotesSearchOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "notes_search".to_string(),
+            description: "Search the user's Markdown notes vault (e.g. Obsidian) for a word or \
+                         phrase. Matches note names and lines (case-insensitive) and returns the \
+                         note path, line number and matching line. Use notes_read to open a note."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Text to search for"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of matches. Default: 20"
+                    }
+                },
+                "required": ["query"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let limit = args
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT);
+        let service = self.service.clone();
+        let matches = blocking(move || service.search(&args.query, limit)).await?;
+        Ok(NotesSearchOutput { matches })
+    }
+}
+
+// ── NotesReadTool ───────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Serialize)]
+pub struct NotesReadArgs {
+    /// Vault-relative path or note name, as in a `[[wiki link]]`.
+    pub note: String,
+}
+
+/// Read a note from the vault.
+#[derive(Clone)]
+pub struct NotesReadTool {
+    service: Arc<NotesService>,
+}
+
+impl NotesReadTool {
+    pub fn new(service: Arc<NotesService>) -> Self {
+        Self { service }
+    }
+}
+
+impl Tool for NotesReadTool {
+    const NAME: &'static str = "notes_read";
+    type Error = ToolError;
+    type Args = NotesReadArgs;
+    type Output = NoteContent;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "notes_read".to_string(),
+            description: "Read a note from the user's Markdown notes vault. Accepts a path \
+                         relative to the vault (\"Projects/Roadmap.md\") or a note name as used \
+                         in a [[wiki link]] (\"Roadmap\"). Returns the content and the wiki links \
+                         it contains, which can be read the same way."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "note": {
+                        "type": "string",
+                        "description": "Vault-relative path or note name"
+                    }
+                },
+                "required": ["note"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let service = self.service.clone();
+        blocking(move || service.read(&args.note)).await
+    }
+}
+
+// ── NotesAppendTool ─────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Serialize)]
+pub struct NotesAppendArgs {
+    /// Vault-relative path of the note; created if it does not exist.
+    pub note: String,
+    /// Markdown to append.
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotesAppendOutput {
+    pub path: String,
+}
+
+/// Append Markdown to a note, creating it if needed.
+#[derive(Clone)]
+pub struct NotesAppendTool {
+    service: Arc<NotesService>,
+    approval_mode: ApprovalMode,
+    pending_approvals: PendingApprovals,
+}
+
+impl NotesAppendTool {
+    pub fn new(
+        service: Arc<NotesService>,
+        approval_mode: ApprovalMode,
+        pending_approvals: PendingApprovals,
+    ) -> Self {
+        Self {
+            service,
+            approval_mode,
+            pending_approvals,
+        }
+    }
+}
+
+impl Tool for NotesAppendTool {
+    const NAME: &'static str = "notes_append";
+    type Error = ToolError;
+    type Args = NotesAppendArgs;
+    type Output = NotesAppendOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: "notes_append".to_string(),
+            description: "Append Markdown text to the end of a note in the user's notes vault, \
+                         creating the note (and its folders) if it does not exist. Existing \
+                         content is never modified. Requires approval."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "note": {
+                        "type": "string",
+                        "description": "Vault-relative path of the note, e.g. \"Inbox/Ideas.md\""
+                    },
+                    "text": {
+                        "type": "string",
+                        "description": "Markdown to append"
+                    }
+                },
+                "required": ["note", "text"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let approved = request_execution_approval(
+            &self.pending_approvals,
+            &self.approval_mode,
+            &format!("[notes] append to {}:\n{}", args.note, args.text),
+            false,
+        )
+        .await?;
+        if !approved {
+            return Err(ToolError::OperationFailed(
+                "Note append denied by user".to_string(),
+            ));
+        }
+
+        tracing::debug!(note = %args.note, "Appending to note");
+        let service = self.service.clone();
+        let path = blocking(move || service.append(&args.note, &args.text)).await?;
+        Ok(NotesAppendOutput { path })
+    }
+}
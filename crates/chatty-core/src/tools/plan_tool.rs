@@ -0,0 +1,415 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::Tool;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::ToolError;
+
+/// Maximum number of steps in a single plan.
+pub const MAX_PLAN_STEPS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanStepStatus {
+    Pending,
+    InProgress,
+    Done,
+    Skipped,
+    Failed,
+}
+
+impl PlanStepStatus {
+    /// Whether the step needs no further work.
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Done | Self::Skipped | Self::Failed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanStep {
+    pub id: String,
+    pub title: String,
+    pub status: PlanStepStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// A structured plan produced in planner mode and tracked across turns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionPlan {
+    pub goal: String,
+    pub steps: Vec<PlanStep>,
+}
+
+impl ExecutionPlan {
+    /// Number of finished steps (done, skipped or failed).
+    pub fn finished_count(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.status.is_finished())
+            .count()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.steps.iter().all(|step| step.status.is_finished())
+    }
+}
+
+/// Conversation-owned plan shared with the agent's plan tools. `None` until
+/// the agent calls `create_plan`.
+pub type SharedPlan = Arc<Mutex<Option<ExecutionPlan>>>;
+
+#[derive(Debug, Serialize)]
+pub struct PlanOutput {
+    pub message: String,
+    pub plan: ExecutionPlan,
+}
+
+// ── create_plan ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PlanStepInput {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreatePlanArgs {
+    pub goal: String,
+    pub steps: Vec<PlanStepInput>,
+}
+
+#[derive(Clone)]
+pub struct CreatePlanTool {
+    plan: SharedPlan,
+}
+
+impl CreatePlanTool {
+    pub fn new(plan: SharedPlan) -> Self {
+        Self { plan }
+    }
+}
+
+impl Tool for CreatePlanTool {
+    const NAME: &'static str = "create_plan";
+    type Error = ToolError;
+    type Args = CreatePlanArgs;
+    type Output = PlanOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Record the structured plan for the user's task before doing any work. \
+                          The plan is shown to the user as a live checklist and persists across \
+                          turns. Calling it again replaces the current plan."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "goal": {
+                        "type": "string",
+                        "description": "One sentence describing the desired end state."
+                    },
+                    "steps": {
+                        "type": "array",
+                        "description": format!("Ordered list of at most {MAX_PLAN_STEPS} concrete steps."),
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "id": {
+                                    "type": "string",
+                                    "description": "Stable short id such as s1."
+                                },
+                                "title": {
+                                    "type": "string",
+                                    "description": "Short action-oriented title."
+                                }
+                            },
+                            "required": ["id", "title"]
+                        }
+                    }
+                },
+                "required": ["goal", "steps"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let goal = args.goal.trim().to_string();
+        if goal.is_empty() {
+            return Err(ToolError::OperationFailed(
+                "goal must not be empty".to_string(),
+            ));
+        }
+        if args.steps.is_empty() || args.steps.len() > MAX_PLAN_STEPS {
+            return Err(ToolError::OperationFailed(format!(
+                "steps must contain between 1 and {MAX_PLAN_STEPS} items"
+            )));
+        }
+
+        let mut ids = HashSet::new();
+        let mut steps = Vec::with_capacity(args.steps.len());
+        for step in args.steps {
+            let id = step.id.trim().to_string();
+            let title = step.title.trim().to_string();
+            if id.is_empty() || title.is_empty() {
+                return Err(ToolError::OperationFailed(
+                    "step id and title must not be empty".to_string(),
+                ));
+            }
+            if !ids.insert(id.clone()) {
+                return Err(ToolError::OperationFailed(format!(
+                    "duplicate step id: {id}"
+                )));
+            }
+            steps.push(PlanStep {
+                id,
+                title,
+                status: PlanStepStatus::Pending,
+                note: None,
+            });
+        }
+
+        let plan = ExecutionPlan { goal, steps };
+        *self.plan.lock() = Some(plan.clone());
+        Ok(PlanOutput {
+            message: "Plan recorded. Mark the first step in_progress with update_plan before \
+                      starting it."
+                .to_string(),
+            plan,
+        })
+    }
+}
+
+// ── update_plan ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PlanStepUpdate {
+    pub id: String,
+    pub status: PlanStepStatus,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdatePlanArgs {
+    pub updates: Vec<PlanStepUpdate>,
+}
+
+#[derive(Clone)]
+pub struct UpdatePlanTool {
+    plan: SharedPlan,
+}
+
+impl UpdatePlanTool {
+    pub fn new(plan: SharedPlan) -> Self {
+        Self { plan }
+    }
+}
+
+impl Tool for UpdatePlanTool {
+    const NAME: &'static str = "update_plan";
+    type Error = ToolError;
+    type Args = UpdatePlanArgs;
+    type Output = PlanOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Update the status of one or more steps of the current plan created \
+                          with create_plan. Mark a step in_progress before working on it and \
+                          done, skipped or failed afterwards; add a short note when useful."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "updates": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "id": {
+                                    "type": "string",
+                                    "description": "Step id from create_plan."
+                                },
+                                "status": {
+                                    "type": "string",
+                                    "enum": ["pending", "in_progress", "done", "skipped", "failed"]
+                                },
+                                "note": {
+                                    "type": "string",
+                                    "description": "Optional short note shown next to the step."
+                                }
+                            },
+                            "required": ["id", "status"]
+                        }
+                    }
+                },
+                "required": ["updates"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let mut guard = self.plan.lock();
+        let Some(plan) = guard.as_mut() else {
+            return Err(ToolError::OperationFailed(
+                "no plan exists yet; call create_plan first".to_string(),
+            ));
+        };
+
+        // Validate every id before applying anything so a bad batch is a no-op.
+        if let Some(unknown) = args
+            .updates
+            .iter()
+            .find(|update| !plan.steps.iter().any(|step| step.id == update.id.trim()))
+        {
+            return Err(ToolError::OperationFailed(format!(
+                "unknown step id: {}",
+                unknown.id
+            )));
+        }
+
+        for update in args.updates {
+            if let Some(step) = plan
+                .steps
+                .iter_mut()
+                .find(|step| step.id == update.id.trim())
+            {
+                step.status = update.status;
+                if let Some(note) = update.note {
+                    let note = note.trim();
+                    step.note = (!note.is_empty()).then(|| note.to_string());
+                }
+            }
+        }
+
+        let message = if plan.is_complete() {
+            "All steps are finished. Give the user the final answer.".to_string()
+        } else {
+            format!(
+                "{}/{} steps finished.",
+                plan.finished_count(),
+                plan.steps.len()
+            )
+        };
+        Ok(PlanOutput {
+            message,
+            plan: plan.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(id: &str, title: &str) -> PlanStepInput {
+        PlanStepInput {
+            id: id.into(),
+            title: title.into(),
+        }
+    }
+
+    async fn created_plan() -> (CreatePlanTool, UpdatePlanTool, SharedPlan) {
+        let shared = SharedPlan::default();
+        let create = CreatePlanTool::new(shared.clone());
+        let update = UpdatePlanTool::new(shared.clone());
+        create
+            .call(CreatePlanArgs {
+                goal: "Ship the release".into(),
+                steps: vec![step("s1", "Bump version"), step("s2", "Tag release")],
+            })
+            .await
+            .unwrap();
+        (create, update, shared)
+    }
+
+    #[tokio::test]
+    async fn create_plan_starts_all_steps_pending() {
+        let (_, _, shared) = created_plan().await;
+        let plan = shared.lock().clone().unwrap();
+        assert_eq!(plan.steps.len(), 2);
+        assert!(
+            plan.steps
+                .iter()
+                .all(|step| step.status == PlanStepStatus::Pending)
+        );
+    }
+
+    #[tokio::test]
+    async fn update_plan_applies_batch_and_reports_completion() {
+        let (_, update, _) = created_plan().await;
+        let output = update
+            .call(UpdatePlanArgs {
+                updates: vec![
+                    PlanStepUpdate {
+                        id: "s1".into(),
+                        status: PlanStepStatus::Done,
+                        note: Some("1.4.0".into()),
+                    },
+                    PlanStepUpdate {
+                        id: "s2".into(),
+                        status: PlanStepStatus::Skipped,
+                        note: None,
+                    },
+                ],
+            })
+            .await
+            .unwrap();
+        assert!(output.plan.is_complete());
+        assert_eq!(output.plan.steps[0].note.as_deref(), Some("1.4.0"));
+    }
+
+    #[tokio::test]
+    async fn update_with_unknown_id_leaves_plan_untouched() {
+        let (_, update, shared) = created_plan().await;
+        let result = update
+            .call(UpdatePlanArgs {
+                updates: vec![
+                    PlanStepUpdate {
+                        id: "s1".into(),
+                        status: PlanStepStatus::Done,
+                        note: None,
+                    },
+                    PlanStepUpdate {
+                        id: "nope".into(),
+                        status: PlanStepStatus::Done,
+                        note: None,
+                    },
+                ],
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(
+            shared.lock().as_ref().unwrap().steps[0].status,
+            PlanStepStatus::Pending
+        );
+    }
+
+    #[tokio::test]
+    async fn update_without_plan_and_duplicate_ids_are_rejected() {
+        let shared = SharedPlan::default();
+        let update = UpdatePlanTool::new(shared.clone());
+        assert!(
+            update
+                .call(UpdatePlanArgs { updates: vec![] })
+                .await
+                .is_err()
+        );
+
+        let create = CreatePlanTool::new(shared);
+        assert!(
+            create
+                .call(CreatePlanArgs {
+                    goal: "x".into(),
+                    steps: vec![step("s1", "a"), step("s1", "b")],
+                })
+                .await
+                .is_err()
+        );
+    }
+}
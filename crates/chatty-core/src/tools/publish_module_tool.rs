@@ -3,8 +3,8 @@ use rig_core::completion::ToolDefinition;
 use rig_core::tool::Tool;
 use rmcp::model::CallToolRequestParams;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
 
+use crate::services::McpService;
 use crate::tools::ToolError;
 
 #[derive(Deserialize, Serialize)]
@@ -27,14 +27,17 @@ pub struct PublishModuleOutput {
 /// This avoids shuttling large base64 blobs through the LLM context window.
 #[derive(Clone)]
 pub struct PublishModuleTool {
-    server_sink: Arc<rmcp::service::ServerSink>,
+    service: McpService,
+    /// MCP server exposing `publish_module`
+    server: String,
     workspace_dir: Option<String>,
 }
 
 impl PublishModuleTool {
-    pub fn new(server_sink: rmcp::service::ServerSink, workspace_dir: Option<String>) -> Self {
+    pub fn new(service: McpService, server: String, workspace_dir: Option<String>) -> Self {
         Self {
-            server_sink: Arc::new(server_sink),
+            service,
+            server,
             workspace_dir,
         }
     }
@@ -158,12 +161,8 @@ impl Tool for PublishModuleTool {
 
         let params = CallToolRequestParams::new("publish_module").with_arguments(arguments);
 
-        // Call publish_module via MCP ServerSink
-        let result = self
-            .server_sink
-            .call_tool(params)
-            .await
-            .map_err(|e| anyhow::anyhow!("MCP call_tool failed: {}", e))?;
+        // Call publish_module via the MCP server (reconnecting it if idle)
+        let result = self.service.call_tool(&self.server, params).await?;
 
         // Extract text from result
         let text = result
@@ -6,11 +6,20 @@ use std::path::PathBuf;
 use tracing::warn;
 
 use super::save_skill_tool::SKILL_TITLE_PREFIX;
+use crate::services::context_dedup::{ContextDedupSettings, dedup_and_rerank};
 use crate::services::embedding_service::EmbeddingService;
 use crate::services::memory_service::{MemoryHit, MemoryHitSource, MemoryService};
 use crate::services::skill_service::SkillService;
 use crate::tools::ToolError;
 
+/// Rank-smoothing constant for reciprocal rank fusion (the standard value from
+/// Cormack et al.). Larger values flatten the contribution of top ranks.
+const RRF_K: f32 = 60.0;
+
+/// Memory candidates fetched per requested result, giving deduplication room
+/// to drop overlapping chunks without returning fewer hits than asked for.
+const CANDIDATE_MULTIPLIER: usize = 2;
+
 /// Arguments for the search_memory tool
 #[derive(Deserialize, Serialize)]
 pub struct SearchMemoryToolArgs {
@@ -25,6 +34,13 @@ pub struct SearchMemoryToolArgs {
 #[derive(Debug, Serialize)]
 pub struct SearchMemoryToolOutput {
     pub results: Vec<MemoryHit>,
+    /// Number of retrieved chunks dropped as near-duplicates of selected ones.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub duplicates_removed: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
 }
 
 /// Tool that allows the agent to search its persistent memory and filesystem skills.
@@ -39,6 +55,8 @@ pub struct SearchMemoryTool {
     /// Optional skill service for filesystem skill discovery.
     skill_service: Option<SkillService>,
     workspace_skills_dir: Option<PathBuf>,
+    /// Weight of keyword ranks vs. vector ranks in reciprocal rank fusion (0.0–1.0).
+    keyword_weight: f32,
 }
 
 impl SearchMemoryTool {
@@ -47,12 +65,14 @@ impl SearchMemoryTool {
         embedding_service: Option<EmbeddingService>,
         skill_service: Option<SkillService>,
         workspace_skills_dir: Option<PathBuf>,
+        keyword_weight: f32,
     ) -> Self {
         Self {
             memory_service,
             embedding_service,
             skill_service,
             workspace_skills_dir,
+            keyword_weight: keyword_weight.clamp(0.0, 1.0),
         }
     }
 }
@@ -111,11 +131,12 @@ impl Tool for SearchMemoryTool {
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
         let top_k = args.top_k.unwrap_or(5);
+        let candidate_k = top_k * CANDIDATE_MULTIPLIER;
 
         // BM25 lexical search (always runs)
         let lex_results = self
             .memory_service
-            .search(&args.query, Some(top_k))
+            .search(&args.query, Some(candidate_k))
             .await
             .map_err(|e| ToolError::OperationFailed(e.to_string()))?;
 
@@ -126,7 +147,7 @@ impl Tool for SearchMemoryTool {
                 Ok(embedding) => {
                     query_embedding_opt = Some(embedding.clone());
                     self.memory_service
-                        .search_vec(embedding, Some(top_k))
+                        .search_vec(embedding, Some(candidate_k))
                         .await
                         .unwrap_or_else(|e| {
                             warn!(error = ?e, "Vector search failed, using BM25 only");
@@ -142,14 +163,58 @@ impl Tool for SearchMemoryTool {
             Vec::new()
         };
 
-        let memory_results = merge_search_results(lex_results, vec_results, top_k);
+        let candidates =
+            fuse_search_results_rrf(lex_results, vec_results, self.keyword_weight, candidate_k);
+
+        // Embed candidates so near-duplicates are detected semantically; falls
+        // back to lexical overlap when embeddings are unavailable.
+        let candidate_embeddings = match (&self.embedding_service, &query_embedding_opt) {
+            (Some(embed_svc), Some(_)) if candidates.len() > 1 => {
+                let texts: Vec<String> = candidates.iter().map(|h| h.text.clone()).collect();
+                embed_svc
+                    .embed_batch(&texts)
+                    .await
+                    .inspect_err(
+                        |e| warn!(error = ?e, "Candidate embedding failed, using lexical dedup"),
+                    )
+                    .ok()
+            }
+            _ => None,
+        };
+
+        let outcome = dedup_and_rerank(
+            candidates,
+            candidate_embeddings.as_deref(),
+            query_embedding_opt.as_deref(),
+            top_k,
+            &ContextDedupSettings::default(),
+        );
+        let memory_results = outcome.selected;
 
         // Scan filesystem skills (if skill service is available)
         let skill_results: Vec<MemoryHit> = if let Some(ref skill_svc) = self.skill_service {
+            // The skills index may use its own embedding model; embed the
+            // query again with it in that case
+            let memory_model = self
+                .embedding_service
+                .as_ref()
+                .map(|s| s.model_identifier());
+            let skill_query_embedding = match skill_svc.embedding_service() {
+                Some(skill_embed) if memory_model != Some(skill_embed.model_identifier()) => {
+                    skill_embed
+                        .embed(&args.query)
+                        .await
+                        .inspect_err(
+                            |e| warn!(error = ?e, "Skill query embedding failed, using keywords"),
+                        )
+                        .ok()
+                }
+                _ => query_embedding_opt,
+            };
             skill_svc
                 .load_hits(
                     &args.query,
-                    query_embedding_opt.as_deref(),
+                    skill_query_embedding.as_deref(),
                     self.workspace_skills_dir.as_deref(),
                 )
                 .await
@@ -159,7 +224,10 @@ impl Tool for SearchMemoryTool {
 
         let results = select_context_hits(memory_results, skill_results, top_k);
 
-        Ok(SearchMemoryToolOutput { results })
+        Ok(SearchMemoryToolOutput {
+            results,
+            duplicates_removed: outcome.duplicates_removed,
+        })
     }
 }
 
@@ -298,6 +366,52 @@ pub fn merge_search_results(
     merged
 }
 
+/// Fuse keyword (BM25) and vector results with weighted reciprocal rank fusion.
+///
+/// Each hit scores `w / (k + rank)` from the keyword list plus
+/// `(1 - w) / (k + rank)` from the vector list, where `w` is `keyword_weight`.
+/// Ranks rather than raw scores are fused because BM25 and vector similarity
+/// live on incomparable scales; this lets exact identifier matches that only
+/// BM25 finds compete fairly with semantic matches. Hits are deduplicated by
+/// text and the fused score replaces `score`.
+pub fn fuse_search_results_rrf(
+    lex_results: Vec<MemoryHit>,
+    vec_results: Vec<MemoryHit>,
+    keyword_weight: f32,
+    limit: usize,
+) -> Vec<MemoryHit> {
+    let keyword_weight = keyword_weight.clamp(0.0, 1.0);
+    let mut fused: Vec<MemoryHit> = Vec::with_capacity(lex_results.len() + vec_results.len());
+
+    let lists = [
+        (lex_results, keyword_weight),
+        (vec_results, 1.0 - keyword_weight),
+    ];
+    for (hits, weight) in lists {
+        for (rank, mut hit) in hits.into_iter().enumerate() {
+            let contribution = weight / (RRF_K + rank as f32 + 1.0);
+            // Use first 200 chars as dedup key to avoid expensive full-text comparison
+            let key: String = hit.text.chars().take(200).collect();
+            if let Some(existing) = fused
+                .iter_mut()
+                .find(|h| h.text.chars().take(200).collect::<String>() == key)
+            {
+                existing.score += contribution;
+                if existing.source.is_none() {
+                    existing.source = hit.source;
+                }
+            } else {
+                hit.score = contribution;
+                fused.push(hit);
+            }
+        }
+    }
+
+    sort_by_score_desc(&mut fused);
+    fused.truncate(limit);
+    fused
+}
+
 fn is_skill_hit(hit: &MemoryHit) -> bool {
     hit.title
         .as_deref()
@@ -417,6 +531,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rrf_rewards_hits_found_by_both_retrievers() {
+        let lex = vec![fact("A", 12.0), fact("B", 9.0)];
+        let vec = vec![fact("C", 0.9), fact("B", 0.8)];
+
+        let fused = fuse_search_results_rrf(lex, vec, 0.5, 3);
+
+        let titles: Vec<_> = fused.iter().filter_map(|h| h.title.as_deref()).collect();
+        assert_eq!(titles, vec!["B", "A", "C"]);
+    }
+
+    #[test]
+    fn rrf_weight_shifts_ranking_toward_keyword_hits() {
+        let lex = vec![fact("exact_symbol", 3.0)];
+        let vec = vec![fact("semantic", 0.9)];
+
+        let keyword_heavy = fuse_search_results_rrf(lex.clone(), vec.clone(), 0.8, 2);
+        assert_eq!(keyword_heavy[0].title.as_deref(), Some("exact_symbol"));
+
+        let vector_heavy = fuse_search_results_rrf(lex, vec, 0.2, 2);
+        assert_eq!(vector_heavy[0].title.as_deref(), Some("semantic"));
+    }
+
     #[test]
     fn build_memory_context_block_includes_skill_source_hints() {
         let block =
@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use crate::models::execution_approval_store::{PendingApprovals, request_execution_approval};
 use crate::models::message_types::ExecutionEngine;
+use crate::services::ToolCancellation;
 use crate::services::shell_service::{ShellOutput, ShellSession, ShellStatus};
 use crate::settings::models::execution_settings::ExecutionSettingsModel;
 use crate::tools::ToolError;
@@ -41,6 +42,7 @@ pub struct ShellExecuteTool {
     session: Arc<ShellSession>,
     settings: ExecutionSettingsModel,
     pending_approvals: PendingApprovals,
+    cancellation: ToolCancellation,
 }
 
 impl ShellExecuteTool {
@@ -53,9 +55,16 @@ impl ShellExecuteTool {
             session,
             settings,
             pending_approvals,
+            cancellation: ToolCancellation::default(),
         }
     }
 
+    /// Kill the running command when the user stops the stream.
+    pub fn with_cancellation(mut self, cancellation: ToolCancellation) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
     async fn request_approval(&self, command: &str) -> anyhow::Result<bool> {
         let is_sandboxed = self.session.is_sandboxed().await;
         request_execution_approval(
@@ -126,7 +135,10 @@ impl Tool for ShellExecuteTool {
         }
 
         tracing::debug!(command = %args.command, "Executing in shell session");
-        let output = self.session.execute(&args.command).await?;
+        let output = self
+            .session
+            .execute_until(&args.command, self.cancellation.cancelled())
+            .await?;
         Ok(output.into())
     }
 }
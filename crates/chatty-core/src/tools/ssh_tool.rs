@@ -0,0 +1,128 @@
+use rig_core::completion::ToolDefinition;
+use rig_core::tool::Tool;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::models::execution_approval_store::{PendingApprovals, request_execution_approval};
+use crate::models::message_types::ToolSource;
+use crate::services::ssh_service::{SshOutput, SshService};
+use crate::settings::models::execution_settings::ApprovalMode;
+use crate::tools::ToolError;
+use crate::tools::invoke_agent_tool::{InvokeAgentProgress, InvokeAgentProgressSlot};
+
+#[derive(Deserialize, Serialize)]
+pub struct SshExecuteArgs {
+    /// Name of a configured host profile.
+    pub host: String,
+    /// Shell command to run on the remote host.
+    pub command: String,
+}
+
+/// Run a command on an allowlisted remote host over SSH.
+///
+/// Every command asks the user, whatever the approval mode, since it runs
+/// outside the local sandbox on another machine. Output streams into the
+/// trace through the shared progress slot while the command runs.
+#[derive(Clone)]
+pub struct SshExecuteTool {
+    service: Arc<SshService>,
+    pending_approvals: PendingApprovals,
+    progress_slot: InvokeAgentProgressSlot,
+}
+
+impl SshExecuteTool {
+    pub fn new(
+        service: Arc<SshService>,
+        pending_approvals: PendingApprovals,
+        progress_slot: InvokeAgentProgressSlot,
+    ) -> Self {
+        Self {
+            service,
+            pending_approvals,
+            progress_slot,
+        }
+    }
+
+    fn send_progress(&self, event: InvokeAgentProgress) {
+        if let Some(tx) = self.progress_slot.lock().as_ref() {
+            let _ = tx.send(event);
+        }
+    }
+}
+
+impl Tool for SshExecuteTool {
+    const NAME: &'static str = "ssh_execute";
+    type Error = ToolError;
+    type Args = SshExecuteArgs;
+    type Output = SshOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        let hosts: Vec<&str> = self
+            .service
+            .hosts()
+            .iter()
+            .map(|h| h.name.as_str())
+            .collect();
+        ToolDefinition {
+            name: "ssh_execute".to_string(),
+            description: format!(
+                "Run a shell command on a remote machine over SSH. Only these configured \
+                 hosts are reachable: {}. Every command requires user approval; prefer \
+                 read-only diagnostics and explain what a command changes before running it.",
+                hosts.join(", ")
+            ),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "host": {
+                        "type": "string",
+                        "enum": hosts,
+                        "description": "Name of the host profile to connect to"
+                    },
+                    "command": {
+                        "type": "string",
+                        "description": "Shell command to run on the remote host"
+                    }
+                },
+                "required": ["host", "command"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let target = self.service.host(&args.host)?.target();
+
+        let approved = request_execution_approval(
+            &self.pending_approvals,
+            &ApprovalMode::AlwaysAsk,
+            &format!("[ssh {}] {}", target, args.command),
+            false,
+        )
+        .await?;
+        if !approved {
+            return Err(ToolError::OperationFailed(
+                "SSH command denied by user".to_string(),
+            ));
+        }
+
+        tracing::info!(host = %args.host, "Running ssh_execute");
+        self.send_progress(InvokeAgentProgress::Started {
+            agent_name: format!("ssh {}", args.host),
+            prompt: args.command.clone(),
+            source: ToolSource::ExternalService {
+                name: target.clone(),
+            },
+        });
+        let result = self
+            .service
+            .execute(&args.host, &args.command, |chunk| {
+                self.send_progress(InvokeAgentProgress::Text(chunk.to_string()))
+            })
+            .await;
+        self.send_progress(InvokeAgentProgress::Finished {
+            success: matches!(&result, Ok(out) if out.exit_code == Some(0)),
+            result: result.as_ref().err().map(|e| e.to_string()),
+        });
+        Ok(result?)
+    }
+}
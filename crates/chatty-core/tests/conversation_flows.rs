@@ -0,0 +1,383 @@
+//! Integration tests for the turn lifecycle the frontends' controllers run:
+//! stream a reply, finalize it into the conversation, regenerate it, roll
+//! back a cancelled turn, and persist the result.
+//!
+//! Providers, MCP servers and the database are replaced by the fixtures in
+//! `chatty_core::testing` and the in-memory conversation repository, so these
+//! tests need no network.
+//!
+//! Tests are grouped by the path they cover:
+//! - Stream finalization: chunks → `finalize_response` → repository
+//! - Regeneration: DPO record of the replaced reply survives a reload
+//! - Cancellation and errors: no dangling user message, nothing finalized,
+//!   failed turns annotated
+//! - MCP tool listing: tools gathered and filtered per conversation
+//! - Recorded flows: fixtures from `flow_recorder` replayed as regression tests
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use anyhow::Result;
+use chatty_core::models::Conversation;
+use chatty_core::repositories::{ConversationRepository, InMemoryConversationRepository};
+use chatty_core::services::flow_recorder::{FlowRecorder, FlowRecording};
+use chatty_core::services::llm_service::{ResponseStream, StreamChunk};
+use chatty_core::services::{
+    ChunkAction, StreamChunkHandler, filter_mcp_tools, gather_mcp_tools, run_stream_loop,
+};
+use chatty_core::testing::{ConversationDataBuilder, FakeAgentClient, FakeMcpService};
+use chatty_core::tools::invoke_agent_tool::InvokeAgentProgress;
+use rig_core::completion::Message;
+use rig_core::completion::message::AssistantContent;
+use rig_core::message::UserContent;
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+/// Collects a streamed turn the way the controllers do before finalizing.
+#[derive(Default)]
+struct TurnCollector {
+    text: String,
+    tool_calls: Vec<String>,
+    output_tokens: u32,
+    error: Option<String>,
+    cancelled: bool,
+}
+
+impl StreamChunkHandler for TurnCollector {
+    fn on_stream_started(&mut self) {}
+
+    fn on_chunk(&mut self, chunk: Result<StreamChunk>) -> Result<ChunkAction> {
+        match chunk? {
+            StreamChunk::Text(text) => self.text.push_str(&text),
+            StreamChunk::ToolCallStarted { name, .. } => self.tool_calls.push(name),
+            StreamChunk::TokenUsage { output_tokens, .. } => self.output_tokens += output_tokens,
+            StreamChunk::Error(error) => {
+                self.error = Some(error);
+                return Ok(ChunkAction::Break);
+            }
+            StreamChunk::Done => return Ok(ChunkAction::Break),
+            _ => {}
+        }
+        Ok(ChunkAction::Continue)
+    }
+
+    fn on_progress(&mut self, _progress: InvokeAgentProgress) {}
+
+    fn on_cancelled(&mut self) {
+        self.cancelled = true;
+    }
+
+    fn on_stream_ended(&mut self) {}
+}
+
+async fn collect(mut stream: ResponseStream, cancelled: bool) -> TurnCollector {
+    let (_progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let cancel_flag = Arc::new(AtomicBool::new(cancelled));
+    let mut collector = TurnCollector::default();
+    run_stream_loop(&mut stream, &mut progress_rx, &cancel_flag, &mut collector)
+        .await
+        .unwrap();
+    collector
+}
+
+/// Send `text` as a new user turn: stream it, then add the user message to
+/// the conversation, as the controllers do.
+async fn send(agent: &FakeAgentClient, conv: &mut Conversation, text: &str) -> ResponseStream {
+    let (stream, user_message) = agent
+        .stream_prompt(&conv.messages(), vec![UserContent::text(text)])
+        .await
+        .unwrap();
+    conv.add_user_message_with_attachments(user_message, Vec::new());
+    stream
+}
+
+fn assistant_text(message: &Message) -> Option<String> {
+    match message {
+        Message::Assistant { content, .. } => Some(
+            content
+                .iter()
+                .filter_map(|c| match c {
+                    AssistantContent::Text(t) => Some(t.text.as_str()),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+async fn reload(
+    repo: &InMemoryConversationRepository,
+    agent: &FakeAgentClient,
+    id: &str,
+) -> Conversation {
+    let data = repo
+        .load_one(id)
+        .await
+        .unwrap()
+        .expect("conversation saved");
+    Conversation::from_data_with_agent(data, agent.agent_client().unwrap()).unwrap()
+}
+
+// ── Stream finalization ───────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn streamed_reply_is_finalized_and_persisted() {
+    let agent = FakeAgentClient::new();
+    let repo = InMemoryConversationRepository::new();
+    let mut conv = ConversationDataBuilder::new("conv-1")
+        .title("Greeting")
+        .build_conversation(&agent)
+        .unwrap();
+
+    agent.push_reply("Hello there, how can I help?");
+    let stream = send(&agent, &mut conv, "Hi").await;
+    let turn = collect(stream, false).await;
+    assert_eq!(turn.text, "Hello there, how can I help?");
+    assert_eq!(turn.output_tokens, 6);
+
+    conv.finalize_response(turn.text, Vec::new(), None);
+    repo.save(conv.id(), conv.to_data(100).unwrap())
+        .await
+        .unwrap();
+
+    let restored = reload(&repo, &agent, "conv-1").await;
+    assert_eq!(restored.title(), "Greeting");
+    assert_eq!(restored.message_count(), 2);
+    assert_eq!(
+        assistant_text(&restored.messages()[1]).as_deref(),
+        Some("Hello there, how can I help?")
+    );
+    assert_eq!(repo.saved_ids(), vec!["conv-1"]);
+
+    let requests = agent.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].history.is_empty());
+}
+
+#[tokio::test]
+async fn follow_up_turn_sends_previous_history() {
+    let agent = FakeAgentClient::new();
+    let mut conv = ConversationDataBuilder::new("conv-1")
+        .user("What is 2 + 2?")
+        .assistant("4")
+        .build_conversation(&agent)
+        .unwrap();
+
+    agent.push_tool_reply("calculator", r#"{"expr":"4*3"}"#, "12", "That makes 12.");
+    let stream = send(&agent, &mut conv, "And times 3?").await;
+    let turn = collect(stream, false).await;
+    conv.finalize_response(turn.text, Vec::new(), None);
+
+    assert_eq!(turn.tool_calls, vec!["calculator"]);
+    assert_eq!(agent.requests()[0].history.len(), 2);
+    assert_eq!(conv.message_count(), 4);
+    assert_eq!(agent.remaining_turns(), 0);
+}
+
+// ── Regeneration ──────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn regeneration_record_survives_reload() {
+    let agent = FakeAgentClient::new();
+    let repo = InMemoryConversationRepository::new();
+    let mut conv = ConversationDataBuilder::new("conv-1")
+        .user("Name a color")
+        .assistant("Blue")
+        .build_conversation(&agent)
+        .unwrap();
+
+    // Same steps as the regenerate handler: drop the reply, record it,
+    // stream the last turn again without re-adding the user message.
+    let (original, timestamp) = conv.remove_last_assistant_message().unwrap();
+    conv.record_regeneration(1, original, timestamp.unwrap_or(0));
+
+    agent.push_reply("Green");
+    let history = conv.messages();
+    let (stream, _) = agent
+        .stream_prompt(
+            &history[..history.len() - 1],
+            vec![UserContent::text("Name a color")],
+        )
+        .await
+        .unwrap();
+    let turn = collect(stream, false).await;
+    conv.finalize_response(turn.text, Vec::new(), None);
+    repo.save(conv.id(), conv.to_data(200).unwrap())
+        .await
+        .unwrap();
+
+    let restored = reload(&repo, &agent, "conv-1").await;
+    assert_eq!(restored.message_count(), 2);
+    assert_eq!(
+        assistant_text(&restored.messages()[1]).as_deref(),
+        Some("Green")
+    );
+    let records = restored.regeneration_records();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].message_index, 1);
+    assert_eq!(records[0].original_text, "Blue");
+}
+
+// ── Cancellation and errors ───────────────────────────────────────────────────
+
+#[tokio::test]
+async fn cancelled_turn_rolls_back_user_message() {
+    let agent = FakeAgentClient::new();
+    let repo = InMemoryConversationRepository::new();
+    let mut conv = ConversationDataBuilder::new("conv-1")
+        .build_conversation(&agent)
+        .unwrap();
+
+    agent.push_reply("never seen");
+    let stream = send(&agent, &mut conv, "Hi").await;
+    let turn = collect(stream, true).await;
+    assert!(turn.cancelled);
+    assert!(turn.text.is_empty());
+
+    // Nothing arrived, so the user message goes too
+    assert!(conv.remove_last_user_message());
+    repo.save(conv.id(), conv.to_data(300).unwrap())
+        .await
+        .unwrap();
+
+    let restored = reload(&repo, &agent, "conv-1").await;
+    assert_eq!(restored.message_count(), 0);
+}
+
+#[tokio::test]
+async fn provider_error_ends_turn_without_reply() {
+    let agent = FakeAgentClient::new();
+    let mut conv = ConversationDataBuilder::new("conv-1")
+        .build_conversation(&agent)
+        .unwrap();
+
+    agent.push_error("429 Too Many Requests");
+    let stream = send(&agent, &mut conv, "Hi").await;
+    let turn = collect(stream, false).await;
+
+    assert_eq!(turn.error.as_deref(), Some("429 Too Many Requests"));
+    assert!(turn.text.is_empty());
+    assert!(
+        agent
+            .stream_prompt(&[], vec![UserContent::text("again")])
+            .await
+            .is_err(),
+        "the script is exhausted"
+    );
+}
+
+#[tokio::test]
+async fn provider_error_is_recorded_on_the_failed_turn() {
+    let agent = FakeAgentClient::new();
+    let repo = InMemoryConversationRepository::new();
+    let mut conv = ConversationDataBuilder::new("conv-1")
+        .build_conversation(&agent)
+        .unwrap();
+
+    agent.push_error("429 Too Many Requests");
+    let stream = send(&agent, &mut conv, "Hi").await;
+    let turn = collect(stream, false).await;
+
+    // Same steps as the stream-error handler: annotate the prompt, keep it
+    let index = conv.record_turn_error(turn.error.unwrap(), 400);
+    assert_eq!(index, Some(0));
+    repo.save(conv.id(), conv.to_data(400).unwrap())
+        .await
+        .unwrap();
+
+    let mut restored = reload(&repo, &agent, "conv-1").await;
+    let error = restored.entries()[0].error.clone().unwrap();
+    assert_eq!(error.message, "429 Too Many Requests");
+    assert_eq!(error.failed_at, 400);
+
+    // Retrying clears it; a reply is never annotated
+    assert!(restored.clear_turn_error(0));
+    assert!(!restored.clear_turn_error(0));
+    restored.finalize_response("Hello".to_string(), Vec::new(), None);
+    assert_eq!(restored.record_turn_error("late".to_string(), 500), None);
+}
+
+// ── MCP tool listing ──────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn fake_mcp_tools_are_gathered_and_filtered() {
+    let mcp = FakeMcpService::new();
+    mcp.add_server("docs", &["search_docs"]).await;
+    mcp.add_server("files", &["read_file", "write_file"]).await;
+
+    let tools = gather_mcp_tools(&mcp.service()).await.unwrap();
+    let servers: Vec<_> = tools
+        .iter()
+        .map(|(name, tools, _)| (name.as_str(), tools.len()))
+        .collect();
+    assert_eq!(servers, vec![("docs", 1), ("files", 2)]);
+
+    let disabled = BTreeSet::from(["files".to_string()]);
+    let filtered = filter_mcp_tools(Some(tools), &disabled).unwrap();
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].1[0].name, "search_docs");
+}
+
+// ── Recorded flows ────────────────────────────────────────────────────────────
+
+fn fixture(name: &str) -> FlowRecording {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/flows")
+        .join(name);
+    FlowRecording::load(&path).unwrap()
+}
+
+#[tokio::test]
+async fn recorded_session_replays_deterministically() {
+    let recording = fixture("weather_tool_session.json");
+    let prompts: Vec<_> = recording.turns.iter().map(|t| t.prompt.clone()).collect();
+    let agent = FakeAgentClient::from_recording(recording);
+    let mut conv = ConversationDataBuilder::new("conv-1")
+        .build_conversation(&agent)
+        .unwrap();
+
+    let stream = send(&agent, &mut conv, &prompts[0]).await;
+    let turn = collect(stream, false).await;
+    assert_eq!(turn.tool_calls, vec!["fetch"]);
+    assert_eq!(turn.text, "It's 12°C in Utrecht with light showers.");
+    assert_eq!(turn.output_tokens, 9);
+    conv.finalize_response(turn.text, Vec::new(), None);
+
+    // The recorded failure comes back the same way on every run
+    let stream = send(&agent, &mut conv, &prompts[1]).await;
+    let turn = collect(stream, false).await;
+    assert_eq!(
+        turn.error.as_deref(),
+        Some("Provider stream ended unexpectedly")
+    );
+    assert!(turn.text.is_empty());
+    assert_eq!(agent.requests()[1].history.len(), 2);
+    assert_eq!(agent.remaining_turns(), 0);
+}
+
+#[tokio::test]
+async fn recorded_turns_round_trip_through_a_fixture() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("flow.json");
+    let live = FakeAgentClient::new();
+    live.push_tool_reply("calculator", r#"{"expr":"6*7"}"#, "42", "The answer is 42.");
+    let recorder = FlowRecorder::recording(path.clone());
+
+    let (stream, _) = live
+        .stream_prompt(&[], vec![UserContent::text("6 times 7?")])
+        .await
+        .unwrap();
+    let original = collect(recorder.record(stream, "6 times 7?", 0), false).await;
+
+    let replayed = FakeAgentClient::from_recording(FlowRecording::load(&path).unwrap());
+    let (stream, _) = replayed
+        .stream_prompt(&[], vec![UserContent::text("6 times 7?")])
+        .await
+        .unwrap();
+    let turn = collect(stream, false).await;
+    assert_eq!(turn.text, original.text);
+    assert_eq!(turn.tool_calls, original.tool_calls);
+}
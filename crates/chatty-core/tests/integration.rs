@@ -33,6 +33,8 @@ fn make_snapshot(used_tokens: usize, model_limit: usize, reserve: usize) -> Toke
         tool_definitions_tokens: quarter,
         conversation_history_tokens: quarter,
         latest_user_message_tokens: used_tokens - quarter * 3, // absorb rounding
+        pinned_context_tokens: 0,
+        breakdown: Default::default(),
         actual_input_tokens: None,
         actual_output_tokens: None,
         conversation_id: "test-conv-id".to_string(),
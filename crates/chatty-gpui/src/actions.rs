@@ -20,6 +20,7 @@ pub(crate) fn register_actions(cx: &mut App) {
         KeyBinding::new("cmd-down", NextConversation, None),
         KeyBinding::new("alt-backspace", DeleteActiveConversation, None),
         KeyBinding::new("cmd-backspace", DeleteActiveConversation, None),
+        KeyBinding::new("cmd-f", chatty::views::chat_view::FindInConversation, None),
     ]);
 
     #[cfg(not(target_os = "macos"))]
@@ -31,49 +32,23 @@ pub(crate) fn register_actions(cx: &mut App) {
         KeyBinding::new("ctrl-up", PreviousConversation, None),
         KeyBinding::new("ctrl-down", NextConversation, None),
         KeyBinding::new("ctrl-backspace", DeleteActiveConversation, None),
+        KeyBinding::new("ctrl-f", chatty::views::chat_view::FindInConversation, None),
     ]);
+
+    // Message navigation keys, from keybindings.json (loaded before this runs)
+    let keybindings = cx
+        .try_global::<settings::models::KeybindingsModel>()
+        .cloned()
+        .unwrap_or_default();
+    cx.bind_keys(chatty::views::chat_view::message_key_bindings(&keybindings));
+
     cx.on_action(|_: &OpenSettings, cx: &mut App| {
         debug!("Action triggered");
         SettingsView::open_or_focus_settings_window(cx);
     });
     cx.on_action(|_: &Quit, cx: &mut App| {
         debug!("Quit action triggered");
-        chatty::services::cleanup_thumbnails();
-
-        // Stop all active streams gracefully
-        if let Some(manager) = cx
-            .try_global::<chatty::models::GlobalStreamManager>()
-            .and_then(|g| g.get())
-        {
-            manager.update(cx, |mgr, cx| {
-                mgr.stop_all(cx);
-            });
-        }
-
-        // Disconnect from all MCP servers on quit.
-        let mcp_service = cx.global::<chatty::services::McpService>().clone();
-        cx.spawn(|_cx: &mut AsyncApp| async move {
-            if let Err(e) = mcp_service.disconnect_all().await {
-                error!(error = ?e, "Failed to disconnect MCP servers during shutdown");
-            }
-        })
-        .detach();
-
-        // Mandatory auto-update: if an update has been downloaded and is ready,
-        // install it silently before quitting so the next launch runs the new
-        // version. install_on_quit() does NOT relaunch — the user intended to
-        // quit, so we respect that while still ensuring the update is applied.
-        if let Some(updater) = cx.try_global::<AutoUpdater>()
-            && matches!(updater.status(), auto_updater::AutoUpdateStatus::Ready(..))
-        {
-            info!("Pending update found on quit — installing before exit");
-            cx.update_global::<AutoUpdater, _>(|updater, cx| {
-                updater.install_on_quit(cx);
-            });
-            return;
-        }
-
-        cx.quit();
+        shutdown::begin_shutdown(cx);
     });
     cx.on_action(|_: &ToggleSidebar, cx: &mut App| {
         debug!("Toggle sidebar action triggered");
@@ -52,6 +52,12 @@ pub enum CustomIcon {
     Ollama,        // ollama.svg - Ollama provider badge
     OpenRouter,    // openrouter.svg - OpenRouter provider badge
     Azure,         // azure.svg - Azure provider badge
+    Pin,           // pin.svg - Pin message to context
+    FilePlus,      // file-plus-corner.svg - Export answer to note
+    SmilePlus,     // smile-plus.svg - Add emoji reaction
+    MessageSquare, // message-square.svg - Annotation note
+    Printer,       // printer.svg - Conversation print action
+    Cpu,           // cpu.svg - Local model memory indicator
 }
 
 impl IconNamed for CustomIcon {
@@ -80,6 +86,12 @@ impl IconNamed for CustomIcon {
             CustomIcon::Ollama => "icons/ollama.svg",
             CustomIcon::OpenRouter => "icons/openrouter.svg",
             CustomIcon::Azure => "icons/azure.svg",
+            CustomIcon::Pin => "icons/pin.svg",
+            CustomIcon::FilePlus => "icons/file-plus-corner.svg",
+            CustomIcon::SmilePlus => "icons/smile-plus.svg",
+            CustomIcon::MessageSquare => "icons/message-square.svg",
+            CustomIcon::Printer => "icons/printer.svg",
+            CustomIcon::Cpu => "icons/cpu.svg",
         }
         .into()
     }
@@ -67,7 +67,10 @@ impl ChattyApp {
         // point, the existing conversation will retain its original tool set. Open a new
         // conversation to pick up updated tool registrations.
         let mcp_tools = chatty_core::services::gather_mcp_tools(mcp_service).await;
-        ctx.mcp_tools = mcp_tools;
+        let disabled_mcp_servers =
+            Conversation::deserialize_disabled_mcp_servers(&data.disabled_mcp_servers)
+                .unwrap_or_default();
+        ctx.mcp_tools = chatty_core::services::filter_mcp_tools(mcp_tools, &disabled_mcp_servers);
 
         // Restore conversation using factory method (bash tool will be created in agent_factory if enabled)
         Conversation::from_data(data, model_config, provider_config, ctx).await
@@ -83,6 +86,7 @@ impl ChattyApp {
         let chat_view = self.chat_view.clone();
 
         cx.spawn(async move |weak, cx| {
+            let _phase = startup_profile::phase("conversation_restore");
             match repo.load_metadata().await {
                 Ok(metadata) => {
                     let count = metadata.len();
@@ -142,6 +146,26 @@ impl ChattyApp {
                             let _: Result<(), _> = app.update(cx, |app, cx| {
                                 app.is_ready = true;
                                 info!("App is now ready (metadata loaded, conversations loaded on demand)");
+
+                                // Reopen the conversation that was active at the last quit
+                                let restore_id = app
+                                    .pending_session
+                                    .as_ref()
+                                    .and_then(|session| session.active_conversation_id.clone())
+                                    .filter(|id| {
+                                        cx.global::<ConversationsStore>()
+                                            .all_metadata_ids()
+                                            .contains(id)
+                                    });
+                                match restore_id {
+                                    Some(id) => {
+                                        info!(conv_id = %id, "Restoring previous session");
+                                        app.load_conversation(&id, cx);
+                                    }
+                                    None => app.pending_session = None,
+                                }
+                                // Resume background jobs left over from the last session
+                                app.run_due_jobs(cx);
                                 cx.notify();
                             });
                         }
@@ -185,8 +209,19 @@ impl ChattyApp {
     pub fn create_new_conversation(
         &mut self,
         cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<String>> {
+        self.create_conversation(false, cx)
+    }
+
+    /// Create a new conversation, keeping the text and attachments in the
+    /// chat input when `keep_draft` is set (pre-warming while the user types).
+    pub(super) fn create_conversation(
+        &mut self,
+        keep_draft: bool,
+        cx: &mut Context<Self>,
     ) -> Task<anyhow::Result<String>> {
         info!("Creating new conversation");
+        telemetry_service::record(features::CONVERSATION_CREATED);
 
         // Use the selected model from chat input, falling back to first available
         let selected_model_id = self
@@ -203,6 +238,12 @@ impl ChattyApp {
             .read(cx)
             .working_dir()
             .map(|path| normalize_workspace_path(path));
+        let selected_history_policy = self
+            .chat_view
+            .read(cx)
+            .chat_input_state()
+            .read(cx)
+            .history_policy();
 
         let models = cx.global::<ModelsModel>();
         let providers = cx.global::<ProviderModel>();
@@ -242,6 +283,7 @@ impl ChattyApp {
                         .iter()
                         .map(|m| {
                             ModelOption::new(m.id.clone(), m.name.clone(), m.provider_type.clone())
+                                .with_aliases(m.aliases.clone())
                         })
                         .collect();
 
@@ -253,8 +295,12 @@ impl ChattyApp {
                         );
                         // Reset streaming state for new conversation (Bug Fix #1)
                         state.set_streaming(false, cx);
-                        // Clear input text field for new conversation (Bug Fix #3)
-                        state.mark_for_clear();
+                        if !keep_draft {
+                            // Queued messages belong to the previous conversation
+                            state.restore_queued_to_draft(cx);
+                            // Clear input text field for new conversation (Bug Fix #3)
+                            state.mark_for_clear();
+                        }
                     });
                 });
 
@@ -389,10 +435,16 @@ impl ChattyApp {
                             gateway_port,
                             remote_agents,
                             available_model_ids,
+                            conversation_variables: None, // set inside Conversation::new
+                            execution_plan: None,          // set inside Conversation::new
+                            confidential: None, // set inside Conversation::new
+                            docker_resources: None, // set inside Conversation::new
+                            disabled_tools: Default::default(),
                         },
                     )
                     .await?;
                     conversation.set_working_dir(selected_working_dir.clone());
+                    conversation.set_history_policy(selected_history_policy);
 
                     // PHASE 3: Add to global store and refresh sidebar with real data
                     cx.update_global::<ConversationsStore, _>(|store, _cx| {
@@ -430,6 +482,9 @@ impl ChattyApp {
                         attachment_paths: "[]".to_string(),
                         message_timestamps: "[]".to_string(),
                         message_feedback: "[]".to_string(),
+                        message_reactions: "[]".to_string(),
+                        message_notes: "[]".to_string(),
+                        message_errors: "[]".to_string(),
                         regeneration_records: "[]".to_string(),
                         created_at: now,
                         updated_at: now,
@@ -437,6 +492,21 @@ impl ChattyApp {
                             .as_ref()
                             .map(|path| path.to_string_lossy().to_string()),
                         agent_task_snapshot: None,
+                        pinned_items: "[]".to_string(),
+                        variables: "{}".to_string(),
+                        execution_plan: None,
+                        confidential: false,
+                        history_policy: serde_json::to_string(&selected_history_policy)
+                            .unwrap_or_else(|_| r#"{"mode":"full"}"#.to_string()),
+                        disabled_mcp_servers: "[]".to_string(),
+                        disabled_tools: "[]".to_string(),
+                        label: "{}".to_string(),
+                        tags: "[]".to_string(),
+                        folder: None,
+                        history_edits: "[]".to_string(),
+                        compaction_records: "[]".to_string(),
+                        locked: false,
+                        quality_score: None,
                     };
 
                     repo.save(&conv_id, data)
@@ -448,16 +518,25 @@ impl ChattyApp {
             } else {
                 let err_msg = "No provider found for model";
                 error!("{}", err_msg);
+                self.open_provider_setup(cx);
                 Task::ready(Err(anyhow::anyhow!(err_msg)))
             }
         } else {
             let err_msg = "No models configured";
             error!("{}", err_msg);
-            // TODO: Show error in UI
+            self.open_provider_setup(cx);
             Task::ready(Err(anyhow::anyhow!(err_msg)))
         }
     }
 
+    /// Show the provider setup wizard after a conversation could not be
+    /// created. At startup only on first run; a failed send always shows it.
+    fn open_provider_setup(&self, cx: &mut Context<Self>) {
+        if self.is_ready || !cx.global::<GeneralSettingsModel>().onboarding_completed {
+            OnboardingState::open(cx);
+        }
+    }
+
     /// Load a conversation by ID.
     ///
     /// Fast path: if the conversation is already in memory, display it immediately.
@@ -477,10 +556,25 @@ impl ChattyApp {
             sidebar.set_active_conversation(Some(conv_id.clone()), cx);
         });
 
-        // Mark as active in the store regardless of whether it is loaded
+        // Mark as active in the store regardless of whether it is loaded, and
+        // record the view. Only the view column is written, so `updated_at`
+        // and the default sidebar order stay as they were.
+        let viewed_at = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
         cx.update_global::<ConversationsStore, _>(|store, _| {
             store.set_active_by_id(conv_id.clone());
+            store.set_last_viewed(&conv_id, viewed_at);
         });
+        let save = self.conversation_repo.save_last_viewed(&conv_id, viewed_at);
+        cx.spawn(async move |_, _cx| {
+            if let Err(e) = save.await {
+                warn!(error = ?e, "Failed to save conversation view time");
+            }
+        })
+        .detach();
+        self.refresh_sidebar(cx);
 
         if cx.global::<ConversationsStore>().is_loaded(id) {
             // Fast path: full data already in memory
@@ -540,6 +634,11 @@ impl ChattyApp {
                                 gateway_port,
                                 remote_agents,
                                 available_model_ids,
+                                conversation_variables: None, // set inside Conversation::from_data
+                                execution_plan: None,          // set inside Conversation::from_data
+                                confidential: None, // set inside Conversation::from_data
+                                docker_resources: None, // set inside Conversation::from_data
+                                disabled_tools: Default::default(), // set inside Conversation::from_data
                             },
                         )
                         .await
@@ -583,14 +682,39 @@ impl ChattyApp {
         }
     }
 
+    /// Open a conversation found by search, jumping to the matching message
+    /// at `history_index` when there is one.
+    pub fn open_search_result(
+        &mut self,
+        id: &str,
+        history_index: Option<usize>,
+        cx: &mut Context<Self>,
+    ) {
+        self.pending_reveal = history_index.map(|ix| (id.to_string(), ix));
+        self.load_conversation(id, cx);
+    }
+
     /// Display a conversation that is already loaded in the ConversationsStore.
     fn display_loaded_conversation(&mut self, id: &str, cx: &mut Context<Self>) {
         // Clear stale invoke_agent IDs from the previous conversation to
         // prevent suppressing ToolCallBlocks that happen to share an ID.
         self.active_invoke_agent_ids.clear();
 
+        // Jobs waiting for this conversation to be loaded can run now
+        self.run_due_jobs(cx);
+
         let conv_id = id.to_string();
         let chat_view = self.chat_view.clone();
+        let restored_scroll_offset = self
+            .pending_session
+            .take()
+            .filter(|session| session.active_conversation_id.as_deref() == Some(id))
+            .and_then(|session| session.scroll_offset);
+        let reveal = self
+            .pending_reveal
+            .take()
+            .filter(|(reveal_id, _)| reveal_id == id)
+            .map(|(_, history_index)| history_index);
 
         let minimal_data = cx
             .global::<ConversationsStore>()
@@ -602,7 +726,11 @@ impl ChattyApp {
                     conv.streaming_trace().cloned(),
                     conv.streaming_sub_agent_trace().cloned(),
                     conv.working_dir().cloned(),
+                    conv.history_policy(),
                     conv.agent_task_snapshot().cloned(),
+                    conv.pinned_items().to_vec(),
+                    conv.variables_snapshot(),
+                    conv.execution_plan_snapshot(),
                 )
             });
 
@@ -612,7 +740,11 @@ impl ChattyApp {
             streaming_trace,
             streaming_sub_agent_trace,
             conversation_working_dir,
+            history_policy,
             agent_task_snapshot,
+            pinned_items,
+            variables,
+            execution_plan,
         )) = minimal_data
         {
             // Check if this conversation has an active stream via StreamManager
@@ -645,9 +777,18 @@ impl ChattyApp {
                 if let Some(entries) = entries {
                     view.load_history(&entries, cx);
                 }
+                if let Some(offset) = restored_scroll_offset {
+                    view.restore_scroll_offset(offset, cx);
+                }
+                if let Some(history_index) = reveal {
+                    view.reveal_history_message(history_index, cx);
+                }
                 if let Some(snapshot) = agent_task_snapshot.clone() {
                     view.set_agent_task_snapshot(snapshot, cx);
                 }
+                view.set_pinned_items(pinned_items, cx);
+                view.set_variables(variables, cx);
+                view.set_execution_plan(execution_plan, cx);
 
                 // Update the selected model and capabilities in the chat input
                 view.chat_input_state().update(cx, |state, cx| {
@@ -657,10 +798,13 @@ impl ChattyApp {
                     // Restore streaming state if conversation has active stream
                     // Set this BEFORE restoring the message so the UI is in correct state
                     state.set_streaming(has_active_stream, cx);
+                    // Never send queued messages into a different conversation
+                    state.restore_queued_to_draft(cx);
 
                     // Restore the per-conversation working directory override without emitting
                     // a WorkingDirChanged event (which would trigger an unnecessary agent rebuild)
                     state.set_working_dir_silent(conversation_working_dir.clone());
+                    state.set_history_policy_silent(history_policy);
                 });
 
                 // Restore in-progress state from Conversation model if it exists.
@@ -8,12 +8,16 @@
 //! - Changing the active conversation's model or working directory at
 //!   runtime (rebuilding the agent in-place).
 //! - Persisting a single conversation to disk.
+//! - Locking conversations read-only.
 //!
 //! # What does NOT live here
 //!
 //! - Conversation creation / loading / restore — `conversation_ops.rs`.
 //! - The persistence layer itself — `chatty_core::repositories::conversation_*`.
 
+use chatty_core::repositories::conversation_repository::BoxFuture;
+use chatty_core::repositories::error::RepositoryResult;
+
 use super::*;
 
 impl ChattyApp {
@@ -107,6 +111,11 @@ impl ChattyApp {
             .map(|s| s.to_string());
 
         let Some(conv_id) = conv_id else { return };
+        // Locked conversations take no new turns, so their agent can wait
+        // until they are unlocked
+        if cx.global::<ConversationsStore>().is_locked(&conv_id) {
+            return;
+        }
 
         cx.spawn(async move |_weak, cx| -> anyhow::Result<()> {
             rebuild_conversation_agent(&conv_id, cx).await
@@ -115,6 +124,16 @@ impl ChattyApp {
     }
 
     pub(super) fn change_conversation_model(&mut self, model_id: String, cx: &mut Context<Self>) {
+        self.switch_conversation_model(model_id, cx).detach();
+    }
+
+    /// Rebuild the active conversation's agent for `model_id`. The returned
+    /// task completes once the new agent is in place.
+    pub(super) fn switch_conversation_model(
+        &mut self,
+        model_id: String,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
         debug!(model_id = %model_id, "Changing to model");
 
         // Get the active conversation ID
@@ -141,15 +160,29 @@ impl ChattyApp {
                     debug!("Found model and provider config");
 
                     // Update the conversation model
-                    cx.spawn(async move |_weak, cx| -> anyhow::Result<()> {
+                    return cx.spawn(async move |_weak, cx| -> anyhow::Result<()> {
                         // Get MCP service
                         let mcp_service = cx
                             .update(|cx| cx.global::<crate::chatty::services::McpService>().clone())
                             .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
-                        // Get MCP tools from active servers
-                        let mcp_tools =
-                            chatty_core::services::gather_mcp_tools(&mcp_service).await;
+                        // Get MCP tools from active servers, minus those switched
+                        // off for this conversation
+                        let (disabled_mcp_servers, disabled_tools) = cx
+                            .update(|cx| {
+                                cx.global::<ConversationsStore>()
+                                    .get_conversation(&conv_id)
+                                    .map(|c| {
+                                        (c.disabled_mcp_servers().clone(), c.disabled_tools().clone())
+                                    })
+                            })
+                            .ok()
+                            .flatten()
+                            .unwrap_or_default();
+                        let mcp_tools = chatty_core::services::filter_mcp_tools(
+                            chatty_core::services::gather_mcp_tools(&mcp_service).await,
+                            &disabled_mcp_servers,
+                        );
 
                         debug!(
                             has_mcp_tools = mcp_tools.is_some(),
@@ -162,6 +195,10 @@ impl ChattyApp {
                             pending_approvals,
                             pending_write_approvals,
                             pending_artifacts,
+                            conversation_variables,
+                            execution_plan,
+                            confidential,
+                            docker_resources,
                             shell_session,
                             user_secrets,
                             theme_colors,
@@ -192,6 +229,10 @@ impl ChattyApp {
                                     .as_ref()
                                     .map(|dir| normalize_workspace_path(Path::new(dir)));
                                 let artifacts = conv.map(|c| c.pending_artifacts());
+                                let variables = conv.map(|c| c.variables());
+                                let plan = conv.map(|c| c.execution_plan());
+                                let confidential = conv.map(|c| c.confidential_flag());
+                                let docker_resources = conv.map(|c| c.docker_resources());
                                 let session = conv.and_then(|c| c.shell_session());
                                 let secrets = cx
                                     .global::<crate::settings::models::UserSecretsModel>()
@@ -205,6 +246,10 @@ impl ChattyApp {
                                     Some(approvals),
                                     Some(write_approvals),
                                     artifacts,
+                                    variables,
+                                    plan,
+                                    confidential,
+                                    docker_resources,
                                     session,
                                     secrets,
                                     Some(colors),
@@ -268,6 +313,11 @@ impl ChattyApp {
                                     gateway_port,
                                     remote_agents,
                                     available_model_ids,
+                                    conversation_variables,
+                                    execution_plan,
+                                    confidential,
+                                    docker_resources,
+                                    disabled_tools,
                                 },
                             )
                             .await?;
@@ -299,51 +349,9 @@ impl ChattyApp {
                         // Save to disk
                         let conv_data_res =
                             cx.update_global::<ConversationsStore, _>(|store, _cx| {
-                                store.get_conversation(&conv_id).and_then(|conv| {
-                                    let history = conv.serialize_history().ok()?;
-                                    let traces = conv.serialize_traces().ok()?;
-                                    let now = SystemTime::now()
-                                        .duration_since(SystemTime::UNIX_EPOCH)
-                                        .unwrap_or_default()
-                                        .as_secs()
-                                        as i64;
-
-                                    Some(ConversationData {
-                                        id: conv.id().to_string(),
-                                        title: conv.title().to_string(),
-                                        model_id: conv.model_id().to_string(),
-                                        message_history: history,
-                                        system_traces: traces,
-                                        token_usage: conv
-                                            .serialize_token_usage()
-                                            .unwrap_or_else(|_| "{}".to_string()),
-                                        attachment_paths: conv
-                                            .serialize_attachment_paths()
-                                            .unwrap_or_else(|_| "[]".to_string()),
-                                        message_timestamps: conv
-                                            .serialize_message_timestamps()
-                                            .unwrap_or_else(|_| "[]".to_string()),
-                                        message_feedback: conv
-                                            .serialize_message_feedback()
-                                            .unwrap_or_else(|_| "[]".to_string()),
-                                        regeneration_records: conv
-                                            .serialize_regeneration_records()
-                                            .unwrap_or_else(|_| "[]".to_string()),
-                                        created_at: conv
-                                            .created_at()
-                                            .duration_since(SystemTime::UNIX_EPOCH)
-                                            .unwrap_or_default()
-                                            .as_secs()
-                                            as i64,
-                                        updated_at: now,
-                                        working_dir: conv
-                                            .working_dir()
-                                            .map(|p| p.to_string_lossy().to_string()),
-                                        agent_task_snapshot: conv
-                                            .serialize_agent_task_snapshot()
-                                            .unwrap_or(None),
-                                    })
-                                })
+                                store
+                                    .get_conversation(&conv_id)
+                                    .and_then(build_conversation_data)
                             });
 
                         if let Ok(Some(conv_data)) = conv_data_res {
@@ -354,8 +362,7 @@ impl ChattyApp {
                         }
 
                         Ok(())
-                    })
-                    .detach();
+                    });
                 } else {
                     error!("Provider not found");
                 }
@@ -363,6 +370,181 @@ impl ChattyApp {
                 error!("Model not found");
             }
         }
+        Task::ready(Ok(()))
+    }
+
+    /// Select `model_id` in the chat input's model picker and update the
+    /// attachment capabilities to match.
+    pub(super) fn select_model_in_input(&self, model_id: &str, cx: &mut Context<Self>) {
+        let capabilities = cx
+            .global::<ModelsModel>()
+            .get_model(model_id)
+            .map(|m| (m.supports_images, m.supports_pdf))
+            .unwrap_or((false, false));
+        let model_id = model_id.to_string();
+        self.chat_view.update(cx, |view, cx| {
+            view.chat_input_state().update(cx, |state, cx| {
+                state.set_selected_model_id(model_id);
+                state.set_capabilities(capabilities.0, capabilities.1);
+                cx.notify();
+            });
+        });
+    }
+
+    /// Apply the models' default rules before the first message of a
+    /// conversation. Returns a task to await when the active conversation's
+    /// model is being switched, so the message goes to the new agent.
+    pub(super) fn apply_default_model_rules(
+        &mut self,
+        message: &str,
+        attachments: &[PathBuf],
+        cx: &mut Context<Self>,
+    ) -> Option<Task<anyhow::Result<()>>> {
+        let store = cx.global::<ConversationsStore>();
+        let active = store.active_id().and_then(|id| store.get_conversation(id));
+        if active.is_some_and(|conv| conv.message_count() > 0) {
+            return None;
+        }
+
+        let project = active
+            .and_then(|conv| conv.working_dir().cloned())
+            .or_else(|| {
+                self.chat_view
+                    .read(cx)
+                    .chat_input_state()
+                    .read(cx)
+                    .working_dir()
+                    .cloned()
+            })
+            .or_else(|| {
+                cx.try_global::<ExecutionSettingsModel>()
+                    .and_then(|s| s.workspace_dir.clone())
+                    .map(PathBuf::from)
+            })
+            .map(|dir| normalize_workspace_path(&dir));
+        let context = ModelRuleContext::for_message(message, project, attachments);
+
+        let (model_id, rule) = cx
+            .global::<ModelsModel>()
+            .default_model_for(&context)
+            .map(|(model, rule)| (model.id.clone(), rule.to_string()))?;
+        let current = active.map(|conv| conv.model_id().to_string()).or_else(|| {
+            self.chat_view
+                .read(cx)
+                .chat_input_state()
+                .read(cx)
+                .selected_model_id()
+                .cloned()
+        });
+        if current.as_deref() == Some(model_id.as_str()) {
+            return None;
+        }
+
+        info!(model_id = %model_id, rule = %rule, "Default model rule matched");
+        let has_active = active.is_some();
+        self.select_model_in_input(&model_id, cx);
+        // Without an active conversation, the one created on send picks up
+        // the model selected in the chat input.
+        has_active.then(|| self.switch_conversation_model(model_id, cx))
+    }
+
+    /// Classify `message` and send it to the most cost-effective model when
+    /// model routing is enabled. The route is a one-turn model override: the
+    /// conversation keeps its configured model. The returned task resolves
+    /// once the route is chosen; the decision is attached to the next
+    /// response's trace.
+    pub(super) fn route_message(
+        &mut self,
+        message: &str,
+        attachments: &[PathBuf],
+        cx: &mut Context<Self>,
+    ) -> Option<Task<()>> {
+        let settings = cx.global::<GeneralSettingsModel>();
+        if !settings.model_routing_enabled {
+            return None;
+        }
+
+        let classifier = settings
+            .router_model_id
+            .as_ref()
+            .and_then(|id| cx.global::<ModelsModel>().get_model(id))
+            .filter(|m| m.provider_type == ProviderType::Ollama)
+            .map(|m| {
+                let base_url = cx
+                    .global::<ProviderModel>()
+                    .providers()
+                    .iter()
+                    .find(|p| p.provider_type == ProviderType::Ollama)
+                    .and_then(|p| p.base_url.clone())
+                    .unwrap_or_else(|| "http://localhost:11434".to_string());
+                (m.model_identifier.clone(), base_url)
+            });
+
+        let store = cx.global::<ConversationsStore>();
+        let active = store.active_id().and_then(|id| store.get_conversation(id));
+        let current = active.map(|conv| conv.model_id().to_string()).or_else(|| {
+            self.chat_view
+                .read(cx)
+                .chat_input_state()
+                .read(cx)
+                .selected_model_id()
+                .cloned()
+        });
+
+        let message = message.to_string();
+        let attachments = attachments.to_vec();
+        Some(cx.spawn(async move |app, cx| {
+            let heuristic = || {
+                (
+                    classify_heuristic(&message, &attachments),
+                    "heuristic".to_string(),
+                )
+            };
+            let (class, classifier) = match classifier {
+                Some((model_identifier, base_url)) => {
+                    match classify_with_ollama(&base_url, &model_identifier, &message, &attachments)
+                        .await
+                    {
+                        Ok(class) => (class, model_identifier),
+                        Err(e) => {
+                            warn!(error = ?e, "Router model failed, using heuristic");
+                            heuristic()
+                        }
+                    }
+                }
+                None => heuristic(),
+            };
+
+            app.update(cx, |app, cx| {
+                let Some(chosen) = choose_model(
+                    class,
+                    cx.global::<ModelsModel>().models(),
+                    current.as_deref(),
+                ) else {
+                    return;
+                };
+                let decision = RoutingDecision {
+                    class,
+                    model_id: chosen.id.clone(),
+                    model_name: chosen.name.clone(),
+                    classifier,
+                    switched: current.as_deref() != Some(chosen.id.as_str()),
+                };
+                info!(
+                    class = decision.class.label(),
+                    model_id = %decision.model_id,
+                    classifier = %decision.classifier,
+                    "Routed message"
+                );
+                if decision.switched {
+                    let overrides = app.pending_overrides.get_or_insert_default();
+                    overrides.model_id = Some(decision.model_id.clone());
+                    overrides.model_name = Some(decision.model_name.clone());
+                }
+                app.pending_routing = Some(decision);
+            })
+            .ok();
+        }))
     }
 
     /// Change the working directory for the active conversation
@@ -397,6 +579,90 @@ impl ChattyApp {
         self.refresh_chat_input_skills(dir.as_deref(), cx);
     }
 
+    /// Change which part of the stored history the active conversation sends
+    /// to the model. Takes effect on the next turn without rebuilding the agent.
+    pub(super) fn change_conversation_history_policy(
+        &mut self,
+        policy: chatty_core::models::HistoryPolicy,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(conv_id) = cx
+            .global::<ConversationsStore>()
+            .active_id()
+            .map(|s| s.to_string())
+        else {
+            return;
+        };
+
+        info!(conv_id = %conv_id, policy = ?policy, "Changing conversation history policy");
+
+        cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            if let Some(conv) = store.get_conversation_mut(&conv_id) {
+                conv.set_history_policy(policy);
+            }
+        });
+
+        self.persist_conversation(&conv_id, cx);
+    }
+
+    /// Switch an MCP server on or off for the active conversation only, then
+    /// rebuild its agent so the server's tools are added or removed.
+    pub fn set_conversation_mcp_server_enabled(
+        &mut self,
+        server: &str,
+        enabled: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(conv_id) = cx
+            .global::<ConversationsStore>()
+            .active_id()
+            .map(|s| s.to_string())
+        else {
+            return;
+        };
+
+        info!(conv_id = %conv_id, server = %server, enabled, "Changing conversation MCP server");
+
+        cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            if let Some(conv) = store.get_conversation_mut(&conv_id) {
+                conv.set_mcp_server_enabled(server, enabled);
+            }
+        });
+
+        self.persist_conversation(&conv_id, cx);
+        self.rebuild_active_agent(cx);
+        cx.notify();
+    }
+
+    /// Switch a tool on or off for the active conversation only, then rebuild
+    /// its agent so the tool is added or removed.
+    pub fn set_conversation_tool_enabled(
+        &mut self,
+        tool: &str,
+        enabled: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(conv_id) = cx
+            .global::<ConversationsStore>()
+            .active_id()
+            .map(|s| s.to_string())
+        else {
+            return;
+        };
+
+        info!(conv_id = %conv_id, tool = %tool, enabled, "Changing conversation tool");
+
+        cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            if let Some(conv) = store.get_conversation_mut(&conv_id) {
+                conv.set_tool_enabled(tool, enabled);
+            }
+        });
+
+        self.persist_conversation(&conv_id, cx);
+        self.rebuild_active_agent(cx);
+        cx.notify();
+    }
+
     /// Delete a conversation
     pub(super) fn delete_conversation(&mut self, id: &str, cx: &mut Context<Self>) {
         let conv_id = id.to_string();
@@ -404,6 +670,26 @@ impl ChattyApp {
         let sidebar = self.sidebar_view.clone();
         let chat_view = self.chat_view.clone();
 
+        // Apply the Docker cleanup policy to compose stacks this conversation started.
+        let settings = cx.global::<crate::settings::models::ExecutionSettingsModel>();
+        let docker_cleanup = settings.docker_cleanup.clone();
+        let docker_host = settings.docker_host.clone();
+        if docker_cleanup != crate::settings::models::execution_settings::DockerCleanup::Keep
+            && let Some(resources) = cx
+                .global::<ConversationsStore>()
+                .get_conversation(&conv_id)
+                .map(|conv| conv.docker_resources())
+        {
+            tokio::spawn(async move {
+                chatty_core::services::docker_service::cleanup_docker_resources(
+                    &resources,
+                    &docker_cleanup,
+                    docker_host.as_deref(),
+                )
+                .await;
+            });
+        }
+
         // Remove from global store
         cx.update_global::<ConversationsStore, _>(|store, _cx| {
             store.delete_conversation(&conv_id);
@@ -421,6 +707,7 @@ impl ChattyApp {
                 .active_id()
                 .map(|s| s.to_string());
 
+            sidebar.mark_read(&conv_id, cx);
             sidebar.set_conversations(convs, cx);
             sidebar.set_active_conversation(active_id.clone(), cx);
         });
@@ -449,49 +736,172 @@ impl ChattyApp {
         .detach();
     }
 
+    /// Give a conversation a color and emoji label. Only the label column is
+    /// written, so this works for conversations that are not loaded.
+    pub(super) fn set_conversation_label(
+        &mut self,
+        conv_id: &str,
+        label: chatty_core::models::ConversationLabel,
+        cx: &mut Context<Self>,
+    ) {
+        cx.update_global::<ConversationsStore, _>(|store, _| {
+            store.set_label(conv_id, label.clone());
+        });
+
+        let conv_id = conv_id.to_string();
+        let save = self.conversation_repo.save_label(&conv_id, label);
+        cx.spawn(async move |_, _cx| {
+            if let Err(e) = save.await {
+                warn!(error = ?e, conv_id = %conv_id, "Failed to save conversation label");
+            }
+        })
+        .detach();
+
+        self.sidebar_view.update(cx, |_, cx| cx.notify());
+        cx.notify();
+    }
+
+    /// Lock a conversation read-only, or unlock it. Only the locked column is
+    /// written, so this works for conversations that are not loaded.
+    pub(super) fn set_conversation_locked(
+        &mut self,
+        conv_id: &str,
+        locked: bool,
+        cx: &mut Context<Self>,
+    ) {
+        cx.update_global::<ConversationsStore, _>(|store, _| {
+            store.set_locked(conv_id, locked);
+        });
+
+        let conv_id = conv_id.to_string();
+        let save = self.conversation_repo.save_locked(&conv_id, locked);
+        cx.spawn(async move |_, _cx| {
+            if let Err(e) = save.await {
+                warn!(error = ?e, conv_id = %conv_id, "Failed to save conversation lock");
+            }
+        })
+        .detach();
+
+        self.sidebar_view.update(cx, |_, cx| cx.notify());
+        self.chat_view.update(cx, |_, cx| cx.notify());
+        cx.notify();
+    }
+
+    /// Whether `conv_id` is locked. Tells the user so in the chat when it is,
+    /// for actions that would change the conversation.
+    pub(super) fn refuse_if_locked(&self, conv_id: &str, cx: &mut Context<Self>) -> bool {
+        if !cx.global::<ConversationsStore>().is_locked(conv_id) {
+            return false;
+        }
+        debug!(conv_id = %conv_id, "Conversation is locked, ignoring change");
+        self.chat_view.update(cx, |view, cx| {
+            view.add_info_message(
+                "This conversation is locked. Unlock it to send, delete or regenerate messages."
+                    .to_string(),
+                cx,
+            );
+        });
+        true
+    }
+
+    /// Apply a tag or folder change from the sidebar to the store, then save
+    /// the conversations it changed.
+    pub(super) fn organize_conversations(
+        &mut self,
+        change: impl FnOnce(&mut ConversationsStore) -> Vec<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let changed = cx.update_global::<ConversationsStore, _>(|store, _| change(store));
+        if changed.is_empty() {
+            return;
+        }
+
+        let saves: Vec<_> = {
+            let store = cx.global::<ConversationsStore>();
+            changed
+                .into_iter()
+                .map(|conv_id| {
+                    let tags = self
+                        .conversation_repo
+                        .save_tags(&conv_id, store.tags(&conv_id).to_vec());
+                    let folder = self
+                        .conversation_repo
+                        .save_folder(&conv_id, store.folder(&conv_id).map(str::to_string));
+                    (conv_id, tags, folder)
+                })
+                .collect()
+        };
+        cx.spawn(async move |_, _cx| {
+            for (conv_id, tags, folder) in saves {
+                if let Err(e) = tags.await {
+                    warn!(error = ?e, conv_id = %conv_id, "Failed to save conversation tags");
+                }
+                if let Err(e) = folder.await {
+                    warn!(error = ?e, conv_id = %conv_id, "Failed to save conversation folder");
+                }
+            }
+        })
+        .detach();
+
+        self.sidebar_view.update(cx, |_, cx| cx.notify());
+        cx.notify();
+    }
+
     /// Persist a conversation to disk asynchronously.
     /// Also updates the metadata store so the sidebar reflects the latest title and cost.
     pub(super) fn persist_conversation(&self, conv_id: &str, cx: &mut Context<Self>) {
+        let Some(save) = self.start_conversation_save(conv_id, cx) else {
+            return;
+        };
         let conv_id = conv_id.to_string();
-        let repo = self.conversation_repo.clone();
+        cx.spawn(async move |_, _cx| {
+            if let Err(e) = save.await {
+                warn!(error = ?e, conv_id = %conv_id, "Failed to save conversation to disk");
+            } else {
+                debug!(conv_id = %conv_id, "Conversation saved to disk");
+            }
+            Ok::<_, anyhow::Error>(())
+        })
+        .detach();
+    }
 
+    /// Update the sidebar metadata of `conv_id` and start writing it to disk.
+    /// The returned save resolves once the write finished; None when the
+    /// conversation could not be serialized.
+    pub(super) fn start_conversation_save(
+        &self,
+        conv_id: &str,
+        cx: &mut Context<Self>,
+    ) -> Option<BoxFuture<'static, RepositoryResult<()>>> {
         let conv_data_opt = cx.update_global::<ConversationsStore, _>(|store, _cx| {
             store
-                .get_conversation(&conv_id)
+                .get_conversation(conv_id)
                 .and_then(build_conversation_data)
         });
 
-        if let Some(conv_data) = conv_data_opt {
-            // Update metadata so title and cost changes are reflected in the sidebar
-            let total_cost = conv_data.total_cost();
-            cx.update_global::<ConversationsStore, _>(|store, _| {
-                store.upsert_metadata(
-                    &conv_data.id,
-                    &conv_data.title,
-                    total_cost,
-                    conv_data.updated_at,
-                );
-            });
-
-            debug!(
-                conv_id = %conv_id,
-                traces_json_len = conv_data.system_traces.len(),
-                history_json_len = conv_data.message_history.len(),
-                "Persisting conversation data"
+        let Some(conv_data) = conv_data_opt else {
+            error!(conv_id = %conv_id, "Failed to build conversation data for persistence (serialization failed)");
+            return None;
+        };
+        // Update metadata so title and cost changes are reflected in the sidebar
+        let total_cost = conv_data.total_cost();
+        cx.update_global::<ConversationsStore, _>(|store, _| {
+            store.upsert_metadata(
+                &conv_data.id,
+                &conv_data.title,
+                total_cost,
+                conv_data.updated_at,
             );
+        });
 
-            let conv_id_for_save = conv_id.clone();
-            cx.spawn(async move |_, _cx| {
-                if let Err(e) = repo.save(&conv_id_for_save, conv_data).await {
-                    warn!(error = ?e, conv_id = %conv_id_for_save, "Failed to save conversation to disk");
-                } else {
-                    debug!(conv_id = %conv_id_for_save, "Conversation saved to disk");
-                }
-                Ok::<_, anyhow::Error>(())
-            })
-            .detach();
-        } else {
-            error!(conv_id = %conv_id, "Failed to build conversation data for persistence (serialization failed)");
-        }
+        debug!(
+            conv_id = %conv_id,
+            traces_json_len = conv_data.system_traces.len(),
+            history_json_len = conv_data.message_history.len(),
+            "Persisting conversation data"
+        );
+
+        // Start the write now so it counts as pending even before the task runs.
+        Some(self.conversation_repo.save(conv_id, conv_data))
     }
 }
@@ -0,0 +1,123 @@
+//! Conversation import for `ChattyApp`.
+//!
+//! # What lives here
+//!
+//! - `import_conversations` — pick a `conversations.json` exported from
+//!   ChatGPT or Claude, save its conversations and list them in the sidebar.
+//!
+//! Parsing and deduplication live in `chatty_core::importers`.
+
+use chatty_core::importers::{import_conversations, parse_export};
+
+use super::*;
+
+impl ChattyApp {
+    /// Import the conversations of a ChatGPT or Claude export, reporting the
+    /// outcome in the chat view. Imported conversations continue with the
+    /// model selected in the chat input (or the first one configured);
+    /// conversations imported before are skipped.
+    pub(super) fn import_conversations(&self, cx: &mut Context<Self>) {
+        let selected_model_id = self
+            .chat_view
+            .read(cx)
+            .chat_input_state()
+            .read(cx)
+            .selected_model_id()
+            .cloned();
+        let models = cx.global::<ModelsModel>();
+        let Some(model_id) = selected_model_id
+            .filter(|id| models.get_model(id).is_some())
+            .or_else(|| models.models().first().map(|m| m.id.clone()))
+        else {
+            warn!("Cannot import conversations: no model configured");
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message(
+                    "Add a model before importing conversations, so they can be continued."
+                        .to_string(),
+                    cx,
+                )
+            });
+            return;
+        };
+
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Import conversations".into()),
+        });
+        let repo = self.conversation_repo.clone();
+        cx.spawn(async move |weak, cx| {
+            let Ok(Ok(Some(paths))) = receiver.await else {
+                return;
+            };
+            let Some(path) = paths.into_iter().next() else {
+                return;
+            };
+            let parsed = match tokio::fs::read_to_string(&path).await {
+                Ok(json) => parse_export(&json),
+                Err(e) => Err(anyhow::anyhow!(e)),
+            };
+            let message = match parsed {
+                Ok((source, conversations)) => {
+                    let summary =
+                        import_conversations(repo.as_ref(), &conversations, &model_id).await;
+                    info!(
+                        source = source.label(),
+                        imported = summary.imported.len(),
+                        duplicates = summary.duplicates,
+                        failed = summary.failed,
+                        "Imported conversations"
+                    );
+
+                    let imported: Vec<_> = conversations
+                        .iter()
+                        .filter(|c| summary.imported.contains(&c.id()))
+                        .map(|c| (c.id(), c.title.clone(), c.updated_at))
+                        .collect();
+                    weak.update(cx, |app, cx| {
+                        cx.update_global::<ConversationsStore, _>(|store, _| {
+                            for (id, title, updated_at) in &imported {
+                                store.upsert_metadata(id, title, 0.0, *updated_at);
+                            }
+                        });
+                        app.refresh_sidebar(cx);
+                    })
+                    .map_err(|e| warn!(error = ?e, "Failed to list imported conversations"))
+                    .ok();
+
+                    let mut message = format!(
+                        "**Imported {} {} conversation(s)** from `{}`.",
+                        summary.imported.len(),
+                        source.label(),
+                        path.display()
+                    );
+                    if summary.duplicates > 0 {
+                        message.push_str(&format!(
+                            " Skipped {} already imported.",
+                            summary.duplicates
+                        ));
+                    }
+                    if summary.failed > 0 {
+                        message.push_str(&format!(
+                            " {} could not be saved; see the error log.",
+                            summary.failed
+                        ));
+                    }
+                    message
+                }
+                Err(e) => {
+                    warn!(error = ?e, path = ?path, "Failed to import conversations");
+                    format!("Failed to import conversations: {e}")
+                }
+            };
+            weak.update(cx, |app, cx| {
+                app.chat_view
+                    .update(cx, |view, cx| view.add_info_message(message, cx))
+            })
+            .map_err(|e| warn!(error = ?e, "Failed to show import result"))
+            .ok();
+        })
+        .detach();
+    }
+}
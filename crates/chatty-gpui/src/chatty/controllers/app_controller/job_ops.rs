@@ -0,0 +1,234 @@
+//! Background jobs for `ChattyApp`.
+//!
+//! # What lives here
+//!
+//! - `enqueue_job` — record work in the `JobQueue` global; the queue
+//!   observer set up in `setup_callbacks` saves it and starts it.
+//! - `run_due_jobs` — start due jobs whose conversation is loaded, up to
+//!   the queue's concurrency limit, drop jobs of deleted conversations, and
+//!   wake up again when the next retry is due. A finished job changes the
+//!   queue, so the observer starts the next one.
+//! - The work behind each `JobKind`: title generation and auto-summaries
+//!   live here, exports in `export_ops.rs` and topic-shift embeddings in
+//!   `topic_split_ops.rs`. The search index needs no job: the repository
+//!   writes it with the conversation, off the UI thread. Each job snapshots what it needs
+//!   on the UI thread and does the rest on a tokio worker, so post-stream
+//!   work never stalls rendering; only the result is applied back here.
+//!
+//! Retry, backoff and queue bounds live in `chatty_core::models::job_queue`.
+
+use std::time::Duration;
+
+use chatty_core::models::{BackgroundJob, JobKind};
+
+use super::*;
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+impl ChattyApp {
+    /// Queue `kind` for `conv_id`; it starts as soon as its conversation is
+    /// loaded and is retried with backoff when it fails.
+    pub(super) fn enqueue_job(&self, kind: JobKind, conv_id: &str, cx: &mut Context<Self>) {
+        debug!(conv_id = %conv_id, kind = ?kind, "Queueing background job");
+        let now = unix_now();
+        cx.update_global::<JobQueue, _>(|queue, _cx| {
+            queue.enqueue(kind, conv_id, now);
+        });
+    }
+
+    /// Save the queue to disk off the UI thread.
+    pub(super) fn persist_job_queue(&self, cx: &mut Context<Self>) {
+        let Some(path) = JobQueue::default_path() else {
+            return;
+        };
+        let queue = cx.global::<JobQueue>().clone();
+        cx.background_executor()
+            .spawn(async move {
+                if let Err(e) = queue.save(&path) {
+                    warn!(error = ?e, "Failed to save background job queue");
+                }
+            })
+            .detach();
+    }
+
+    /// Start the due jobs that fit the concurrency limit and schedule a
+    /// wake-up for the next retry. Jobs wait while their conversation is not
+    /// loaded (it is loaded on demand) and are dropped once it was deleted.
+    pub(super) fn run_due_jobs(&mut self, cx: &mut Context<Self>) {
+        // Before metadata is loaded every conversation looks deleted
+        if !self.is_ready {
+            return;
+        }
+        let now = unix_now();
+        let (runnable, obsolete): (Vec<u64>, Vec<u64>) = {
+            let store = cx.global::<ConversationsStore>();
+            let due = cx
+                .global::<JobQueue>()
+                .jobs()
+                .iter()
+                .filter(|job| job.is_due(now));
+            let mut runnable = Vec::new();
+            let mut obsolete = Vec::new();
+            for job in due {
+                if store.is_loaded(&job.conversation_id) {
+                    runnable.push(job.id);
+                } else if store.updated_at(&job.conversation_id).is_none() {
+                    obsolete.push(job.id);
+                }
+            }
+            (runnable, obsolete)
+        };
+
+        if !runnable.is_empty() || !obsolete.is_empty() {
+            let started = cx.update_global::<JobQueue, _>(|queue, _cx| {
+                for id in &obsolete {
+                    queue.complete(*id);
+                }
+                queue.start(&runnable)
+            });
+            for job in started {
+                self.run_job(job, cx);
+            }
+        }
+
+        self.job_wakeup = cx.global::<JobQueue>().next_due_in(now).map(|secs| {
+            cx.spawn(async move |app, cx| {
+                cx.background_executor()
+                    .timer(Duration::from_secs(secs))
+                    .await;
+                app.update(cx, |app, cx| app.run_due_jobs(cx)).ok();
+            })
+        });
+    }
+
+    fn run_job(&mut self, job: BackgroundJob, cx: &mut Context<Self>) {
+        info!(
+            job_id = job.id,
+            kind = ?job.kind,
+            conv_id = %job.conversation_id,
+            attempt = job.attempts + 1,
+            "Running background job"
+        );
+        let conv_id = job.conversation_id.as_str();
+        let task = match job.kind {
+            JobKind::GenerateTitle => self.generate_conversation_title(conv_id, cx),
+            JobKind::ExportAtif => self.export_conversation_atif(conv_id, cx),
+            JobKind::ExportJsonl => self.export_conversation_jsonl(conv_id, cx),
+            JobKind::Summarize => self.summarize_conversation_history(conv_id, cx),
+            JobKind::DetectTopicShift => self.check_topic_shift(conv_id, cx),
+        };
+        cx.spawn(async move |app, cx| {
+            let result = task.await;
+            app.update(cx, |_app, cx| {
+                let now = unix_now();
+                cx.update_global::<JobQueue, _>(|queue, _cx| match result {
+                    Ok(()) => queue.complete(job.id),
+                    Err(e) => {
+                        warn!(job_id = job.id, kind = ?job.kind, error = ?e, "Background job failed");
+                        queue.fail(job.id, format!("{e:#}"), now);
+                    }
+                });
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Name a conversation after its first exchange. Does nothing once the
+    /// conversation has a title or was deleted.
+    fn generate_conversation_title(
+        &self,
+        conv_id: &str,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let conv_id = conv_id.to_string();
+        let sidebar = self.sidebar_view.clone();
+        let title_data = cx
+            .global::<ConversationsStore>()
+            .get_conversation(&conv_id)
+            .filter(|conv| conv.title() == "New Chat")
+            .map(|conv| {
+                // The first exchange, even if more messages arrived since
+                let history: Vec<_> = conv.messages().into_iter().take(2).collect();
+                (conv.agent().clone(), history)
+            });
+        let Some((agent, history)) = title_data else {
+            return Task::ready(Ok(()));
+        };
+
+        cx.spawn(async move |_weak, cx| {
+            let new_title = tokio::spawn(async move { generate_title(&agent, &history).await })
+                .await
+                .map_err(|e| anyhow::anyhow!("Title generation task failed: {e}"))??;
+            debug!(title = %new_title, "Generated title");
+
+            cx.update_global::<ConversationsStore, _>(|store, _cx| {
+                if let Some(conv) = store.get_conversation_mut(&conv_id) {
+                    conv.set_title(new_title.clone());
+                }
+                // Compute cost separately to avoid simultaneous borrow
+                let cost = store
+                    .get_conversation(&conv_id)
+                    .map(|c| c.token_usage().total_estimated_cost_usd)
+                    .unwrap_or(0.0);
+                // Also update metadata so sidebar reflects the new title
+                store.upsert_metadata(&conv_id, &new_title, cost, unix_now());
+            })
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+            // Update sidebar with new title from metadata
+            sidebar
+                .update(cx, |sidebar, cx| {
+                    let store = cx.global::<ConversationsStore>();
+                    let total = store.count();
+                    let convs = store.list_recent_metadata(sidebar.visible_limit());
+                    sidebar.set_conversations(convs, cx);
+                    sidebar.set_total_count(total);
+                })
+                .map_err(|e| warn!(error = ?e, "Failed to update sidebar with new title"))
+                .ok();
+
+            Ok(())
+        })
+    }
+
+    /// Replace the older half of a conversation's history with a summary.
+    fn summarize_conversation_history(
+        &self,
+        conv_id: &str,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let conv_id = conv_id.to_string();
+        let data = cx
+            .global::<ConversationsStore>()
+            .get_conversation(&conv_id)
+            .map(|conv| (conv.agent().clone(), conv.messages()));
+        let Some((agent, history)) = data else {
+            return Task::ready(Ok(()));
+        };
+
+        cx.spawn(async move |_weak, cx| {
+            let result = tokio::spawn(async move { summarize_oldest_half(&agent, &history).await })
+                .await
+                .map_err(|e| anyhow::anyhow!("Summarization task failed: {e}"))??;
+            info!(
+                conv_id = %conv_id,
+                messages_summarized = result.messages_summarized,
+                estimated_tokens_freed = result.estimated_tokens_freed,
+                "Auto-summarization complete"
+            );
+            cx.update_global::<ConversationsStore, _>(|store, _cx| {
+                if let Some(conv) = store.get_conversation_mut(&conv_id) {
+                    conv.compact_history(&result, CompactionTrigger::Automatic);
+                }
+            })
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,184 @@
+//! Merging conversations for `ChattyApp`.
+//!
+//! # What lives here
+//!
+//! - `merge_conversation_into_active` — `/merge <title>`: find the named
+//!   conversation, load it if it is not in memory, append its history to the
+//!   active conversation and delete it.
+//!
+//! The merge itself lives in `ConversationsStore::merge` and
+//! `Conversation::merge_from`.
+
+use super::*;
+
+/// Id of the conversation titled `query`, other than `exclude`. An exact
+/// title (ignoring case) wins; otherwise exactly one title must contain it.
+fn find_by_title(
+    conversations: &[(String, String, Option<f64>)],
+    query: &str,
+    exclude: &str,
+) -> Result<String, String> {
+    let lowered = query.to_lowercase();
+    let candidates: Vec<&(String, String, Option<f64>)> = conversations
+        .iter()
+        .filter(|(id, _, _)| id != exclude)
+        .collect();
+    if let Some((id, _, _)) = candidates
+        .iter()
+        .find(|(_, title, _)| title.to_lowercase() == lowered)
+    {
+        return Ok(id.clone());
+    }
+    let matches: Vec<_> = candidates
+        .iter()
+        .filter(|(_, title, _)| title.to_lowercase().contains(&lowered))
+        .collect();
+    match matches.as_slice() {
+        [] => Err(format!("No other conversation is titled \"{query}\".")),
+        [(id, _, _)] => Ok(id.clone()),
+        several => Err(format!(
+            "Several conversations match \"{query}\": {}. Use the full title.",
+            several
+                .iter()
+                .take(5)
+                .map(|(_, title, _)| format!("\"{title}\""))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+impl ChattyApp {
+    /// `/merge <title>` — append the conversation titled `query` to the
+    /// active one, then delete it. For a topic that got split across chats.
+    pub(super) fn merge_conversation_into_active(&mut self, query: &str, cx: &mut Context<Self>) {
+        let store = cx.global::<ConversationsStore>();
+        let Some(target_id) = store.active_id().cloned() else {
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message("No active conversation.".to_string(), cx);
+            });
+            return;
+        };
+        if query.is_empty() {
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message(
+                    "Usage: `/merge <title>` — append another conversation to this one \
+                     and delete it."
+                        .to_string(),
+                    cx,
+                );
+            });
+            return;
+        }
+        let source_id = match find_by_title(
+            &store.list_recent_metadata(store.count()),
+            query,
+            &target_id,
+        ) {
+            Ok(id) => id,
+            Err(message) => {
+                self.chat_view
+                    .update(cx, |view, cx| view.add_info_message(message, cx));
+                return;
+            }
+        };
+        if self.refuse_if_locked(&source_id, cx) {
+            return;
+        }
+
+        let store = cx.global::<ConversationsStore>();
+        if store.is_loaded(&source_id) {
+            self.finish_merge(&source_id, &target_id, cx);
+            return;
+        }
+        // The source is only read and then dropped, so it can borrow the
+        // target's agent instead of building its own.
+        let Some(agent) = store
+            .get_conversation(&target_id)
+            .map(|conv| conv.agent().clone())
+        else {
+            return;
+        };
+        let repo = self.conversation_repo.clone();
+        cx.spawn(async move |weak, cx| {
+            let source = match repo.load_one(&source_id).await {
+                Ok(Some(data)) => Conversation::from_data_with_agent(data, agent),
+                Ok(None) => Err(anyhow::anyhow!("Conversation not found")),
+                Err(e) => Err(e.into()),
+            };
+            let Some(app) = weak.upgrade() else {
+                return;
+            };
+            app.update(cx, |app, cx| match source {
+                Ok(source) => {
+                    cx.update_global::<ConversationsStore, _>(|store, _| {
+                        store.insert_loaded(source);
+                    });
+                    app.finish_merge(&source_id, &target_id, cx);
+                }
+                Err(e) => {
+                    warn!(conv_id = %source_id, error = ?e, "Failed to load conversation to merge");
+                    app.chat_view.update(cx, |view, cx| {
+                        view.add_info_message(format!("Could not merge: {e}"), cx);
+                    });
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Merge two loaded conversations, save the result and, once the save
+    /// succeeded, delete the source from disk.
+    fn finish_merge(&mut self, source_id: &str, target_id: &str, cx: &mut Context<Self>) {
+        let title = cx
+            .global::<ConversationsStore>()
+            .title(source_id)
+            .unwrap_or_default()
+            .to_string();
+        let result =
+            cx.update_global::<ConversationsStore, _>(|store, _| store.merge(source_id, target_id));
+        let added = match result {
+            Ok(added) => added,
+            Err(e) => {
+                self.chat_view.update(cx, |view, cx| {
+                    view.add_info_message(format!("Could not merge: {e}"), cx);
+                });
+                return;
+            }
+        };
+        info!(from = %source_id, into = %target_id, added, "Conversations merged");
+
+        // The source is deleted only once the merged target is on disk, so a
+        // failed save leaves both conversations where they were.
+        let save = self.start_conversation_save(target_id, cx);
+        let repo = self.conversation_repo.clone();
+        let source_id = source_id.to_string();
+        let target_id_for_save = target_id.to_string();
+        self.sidebar_view
+            .update(cx, |sidebar, cx| sidebar.mark_read(&source_id, cx));
+        cx.spawn(async move |_weak, _cx| {
+            let Some(save) = save else {
+                warn!(conv_id = %source_id, "Merged conversation not saved; keeping the source on disk");
+                return;
+            };
+            if let Err(e) = save.await {
+                warn!(error = ?e, conv_id = %target_id_for_save, "Failed to save merged conversation; keeping the source on disk");
+                return;
+            }
+            if let Err(e) = repo.delete(&source_id).await {
+                warn!(error = ?e, conv_id = %source_id, "Failed to delete merged conversation from disk");
+            }
+        })
+        .detach();
+
+        self.sync_pinned_items(target_id, cx);
+        self.load_conversation(target_id, cx);
+        self.chat_view.update(cx, |view, cx| {
+            view.add_info_message(
+                format!("**Merged** \"{title}\" into this conversation ({added} messages)."),
+                cx,
+            );
+        });
+    }
+}
@@ -29,9 +29,11 @@
 //!
 //! See `docs/stream-manager.md` for the full stream architecture.
 
+use chatty_core::models::{JobKind, TurnError};
+
 use super::message_ops_internals::{
     LlmStreamParams, attachment_to_user_content, run_llm_stream,
-    select_recent_assistant_attachments,
+    select_recent_assistant_attachments, video_to_user_contents,
 };
 use super::*;
 
@@ -60,14 +62,29 @@ impl ChattyApp {
             debug!("Not ready yet, ignoring message");
             return;
         }
+        if let Some(conv_id) = cx.global::<ConversationsStore>().active_id().cloned()
+            && self.refuse_if_locked(&conv_id, cx)
+        {
+            return;
+        }
+        telemetry_service::record(features::MESSAGE_SENT);
 
         let chat_view = self.chat_view.clone();
         let sidebar = self.sidebar_view.clone();
         let app_entity = cx.entity();
+        let routing = self.pending_routing.take();
+        let overrides = self.pending_overrides.take();
 
         // Get the conversation ID for task tracking
-        // If no conversation exists, we'll create one inside the async block
-        let conv_id_for_task = cx.global::<ConversationsStore>().active_id().cloned();
+        // If no conversation exists, we'll create one inside the async block.
+        // A conversation pre-warmed while the user typed may still be
+        // building; the stream waits for it like for a new one.
+        let prewarm = self.prewarm_task.clone();
+        let conv_id_for_task = cx
+            .global::<ConversationsStore>()
+            .active_id()
+            .cloned()
+            .filter(|_| prewarm.is_none());
         let needs_conversation_creation = conv_id_for_task.is_none();
 
         // Get pending artifacts handle for existing conversations (for stream registration)
@@ -100,12 +117,46 @@ impl ChattyApp {
                 debug!("Async task started");
 
                 // PHASE 1: Ensure conversation exists (create if needed)
+                if let Some(prewarm) = prewarm {
+                    debug!("Waiting for pre-warmed conversation");
+                    prewarm.await;
+                }
+                let promote_pending = |id: &String, cx: &mut AsyncApp| {
+                    // Update the shared resolved ID so load_conversation can find the active stream
+                    if let Ok(mut resolved) = resolved_id_for_closure.lock() {
+                        *resolved = Some(id.clone());
+                        debug!(conv_id = %id, "Updated resolved conversation ID for pending task");
+                    }
+                    // Get conversation's PendingArtifacts handle before promoting
+                    let pending_arts = cx.update(|cx| {
+                        cx.global::<ConversationsStore>()
+                            .get_conversation(id)
+                            .map(|c| c.pending_artifacts())
+                    }).ok().flatten();
+
+                    // Promote the pending stream and wire up artifacts
+                    if let Some(ref sm) = stream_manager {
+                        sm.update(cx, |mgr, _cx| {
+                            mgr.promote_pending(id);
+                            // Wire the conversation's PendingArtifacts to the StreamState
+                            // so finalize_stream can drain them directly
+                            if let Some(arts) = pending_arts {
+                                mgr.set_pending_artifacts(id, arts);
+                            }
+                        })
+                        .map_err(|e| debug!(error = ?e, "Failed to promote pending stream"))
+                        .ok();
+                    }
+                };
                 let conv_id: String = match cx
                     .update_global::<ConversationsStore, _>(|store, _| store.active_id().cloned())
                     .map_err(|e| anyhow::anyhow!(e.to_string()))?
                 {
                     Some(id) => {
                         debug!(conv_id = %id, "Found active conversation");
+                        if needs_conversation_creation {
+                            promote_pending(&id, cx);
+                        }
                         id
                     }
                     None => {
@@ -115,31 +166,7 @@ impl ChattyApp {
                         match create_task.await {
                             Ok(id) => {
                                 debug!(conv_id = %id, "Created new conversation");
-                                // Update the shared resolved ID so load_conversation can find the active stream
-                                if let Ok(mut resolved) = resolved_id_for_closure.lock() {
-                                    *resolved = Some(id.clone());
-                                    debug!(conv_id = %id, "Updated resolved conversation ID for pending task");
-                                }
-                                // Get conversation's PendingArtifacts handle before promoting
-                                let pending_arts = cx.update(|cx| {
-                                    cx.global::<ConversationsStore>()
-                                        .get_conversation(&id)
-                                        .map(|c| c.pending_artifacts())
-                                }).ok().flatten();
-
-                                // Promote the pending stream and wire up artifacts
-                                if let Some(ref sm) = stream_manager {
-                                    sm.update(cx, |mgr, _cx| {
-                                        mgr.promote_pending(&id);
-                                        // Wire the conversation's PendingArtifacts to the StreamState
-                                        // so finalize_stream can drain them directly
-                                        if let Some(arts) = pending_arts {
-                                            mgr.set_pending_artifacts(&id, arts);
-                                        }
-                                    })
-                                    .map_err(|e| debug!(error = ?e, "Failed to promote pending stream"))
-                                    .ok();
-                                }
+                                promote_pending(&id, cx);
                                 id
                             }
                             Err(e) => {
@@ -163,6 +190,7 @@ impl ChattyApp {
                 // PHASE 2: Initialize UI with user and assistant messages
                 // and add the user/assistant messages AFTER conversation exists
                 chat_view.update(cx, |view, cx| {
+                    let request_privacy = request_privacy_summary(&conv_id, cx);
                     view.set_conversation_id(conv_id.clone(), cx);
                     // Add user message to UI
                     view.add_user_message(message.clone(), attachments.clone(), cx);
@@ -170,6 +198,15 @@ impl ChattyApp {
                     // Start assistant message in UI
                     view.start_assistant_message(cx);
                     debug!("Assistant message started");
+                    if let Some(decision) = routing {
+                        view.set_routing_decision(decision, cx);
+                    }
+                    if let Some(overrides) = overrides.clone() {
+                        view.set_turn_overrides(overrides, cx);
+                    }
+                    if let Some(policy) = request_privacy {
+                        view.set_request_privacy(policy, cx);
+                    }
                     cx.notify();
                 }).map_err(|e| anyhow::anyhow!(e.to_string()))?;
                 debug!(conv_id = %conv_id, "Set conversation ID on chat view");
@@ -182,18 +219,8 @@ impl ChattyApp {
                 .ok();
 
                 let needs_agent_workspace_refresh = cx
-                    .update_global::<ConversationsStore, _>(|store, cx| {
-                        let settings = cx.global::<ExecutionSettingsModel>();
-                        store.get_conversation(&conv_id).map(|conv| {
-                            let effective_workspace_dir = conv
-                                .working_dir()
-                                .cloned()
-                                .or_else(|| settings.workspace_dir.as_ref().map(PathBuf::from));
-                            conv.agent_workspace_dir().cloned() != effective_workspace_dir
-                        })
-                    })
-                    .map_err(|e| anyhow::anyhow!(e.to_string()))?
-                    .unwrap_or(false);
+                    .update(|cx| agent_needs_workspace_refresh(&conv_id, cx))
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
                 if needs_agent_workspace_refresh {
                     info!(
@@ -204,20 +231,21 @@ impl ChattyApp {
                 }
 
                 // Extract agent, history, model_id, and capabilities synchronously
-                let (agent, history, _model_id, provider_type, provider_supports_pdf, provider_supports_images, conv_entries, invoke_agent_progress_slot) = cx
+                let (agent, history, _model_id, provider_type, provider_supports_pdf, provider_supports_images, max_video_frames, conv_entries, invoke_agent_progress_slot) = cx
                     .update_global::<ConversationsStore, _>(|store, cx| {
                         if let Some(conv) = store.get_conversation(&conv_id) {
                             let model_id = conv.model_id().to_string();
 
                             // Get capabilities from ModelsModel
-                            let (provider_type, supports_pdf, supports_images) = cx
+                            let (provider_type, supports_pdf, supports_images, max_video_frames) = cx
                                 .global::<ModelsModel>()
                                 .get_model(&model_id)
-                                .map(|m| (m.provider_type.clone(), m.supports_pdf, m.supports_images))
+                                .map(|m| (m.provider_type.clone(), m.supports_pdf, m.supports_images, m.max_video_frames))
                                 .unwrap_or((
                                     chatty_core::settings::models::providers_store::ProviderType::OpenRouter,
                                     false,
                                     false,
+                                    None,
                                 )); // Safe fallback if model not found
 
                             // Clear any leftover artifacts from a previous stream
@@ -232,6 +260,7 @@ impl ChattyApp {
                                 provider_type,
                                 supports_pdf,
                                 supports_images,
+                                max_video_frames,
                                 conv.entries().to_vec(),
                                 conv.invoke_agent_progress_slot(),
                             ))
@@ -243,6 +272,25 @@ impl ChattyApp {
                     })
                     .map_err(|e| anyhow::anyhow!(e.to_string()))??;
 
+                // Parameters chosen for this message only get a one-off agent;
+                // the conversation keeps its own agent and model.
+                let (agent, provider_type, provider_supports_pdf, provider_supports_images, max_video_frames, invoke_agent_progress_slot) = match &overrides {
+                    Some(overrides) => {
+                        let (turn_agent, model, progress_slot) =
+                            build_turn_agent(&conv_id, overrides, cx).await?;
+                        info!(conv_id = %conv_id, overrides = %overrides.summary(), "Sending with turn overrides");
+                        (turn_agent, model.provider_type, model.supports_pdf, model.supports_images, model.max_video_frames, progress_slot)
+                    }
+                    None => (agent, provider_type, provider_supports_pdf, provider_supports_images, max_video_frames, invoke_agent_progress_slot),
+                };
+                let video_transcribe_command = cx
+                    .update(|cx| {
+                        cx.try_global::<GeneralSettingsModel>()
+                            .and_then(|s| s.video_transcribe_command.clone())
+                    })
+                    .ok()
+                    .flatten();
+
                 // PHASE 3: Prepare user content and start LLM stream
                 let mut contents = vec![rig_core::message::UserContent::Text(
                     rig_core::completion::message::Text {
@@ -262,6 +310,25 @@ impl ChattyApp {
                         warn!(?path, "Skipping image attachment: provider does not support images");
                         continue;
                     }
+                    let is_video = path
+                        .extension()
+                        .and_then(|e| e.to_str())
+                        .is_some_and(chatty_core::models::attachment_validation::is_video_extension);
+                    if is_video {
+                        // Videos go to the model as sampled frames plus a
+                        // text part with timestamps and the transcript.
+                        match video_to_user_contents(
+                            path,
+                            chatty_core::services::video_frames::frame_cap(max_video_frames),
+                            video_transcribe_command.as_deref(),
+                        )
+                        .await
+                        {
+                            Ok(video_contents) => contents.extend(video_contents),
+                            Err(e) => warn!(?path, error = ?e, "Failed to sample video attachment"),
+                        }
+                        continue;
+                    }
                     match attachment_to_user_content(path).await {
                         Ok(content) => contents.push(content),
                         Err(e) => warn!(?path, error = ?e, "Failed to convert attachment"),
@@ -341,6 +408,12 @@ impl ChattyApp {
     ) {
         let chat_view = self.chat_view.clone();
 
+        // Smoothly streamed text still on its way must be shown before a tool
+        // call snapshots the message text, or the stream ends
+        if !matches!(event, StreamManagerEvent::TextChunk { .. }) {
+            chat_view.update(cx, |view, cx| view.flush_stream_reveal(cx));
+        }
+
         match event {
             StreamManagerEvent::StreamStarted { conversation_id } => {
                 debug!(conv_id = %conversation_id, "StreamManager: stream started");
@@ -373,7 +446,7 @@ impl ChattyApp {
                 let text = text.clone();
                 chat_view.update(cx, |view, cx| {
                     if view.conversation_id() == Some(conversation_id) {
-                        view.append_assistant_text(&text, cx);
+                        view.append_streamed_text(&text, cx);
                     }
                 });
             }
@@ -543,11 +616,13 @@ impl ChattyApp {
                 id,
                 command,
                 is_sandboxed,
+                question,
             } => {
                 debug!(id = %id, command = %command, sandboxed = is_sandboxed, "StreamManager: approval requested");
                 let id = id.clone();
                 let command = command.clone();
                 let is_sandboxed = *is_sandboxed;
+                let question = question.clone();
 
                 // Update Conversation model unconditionally
                 cx.update_global::<ConversationsStore, _>(|store, _cx| {
@@ -558,6 +633,8 @@ impl ChattyApp {
                             is_sandboxed,
                             state: ApprovalState::Pending,
                             created_at: std::time::SystemTime::now(),
+                            question: question.clone(),
+                            answer: None,
                         };
                         let trace = conv.ensure_streaming_trace();
                         let index = trace.items.len();
@@ -568,7 +645,7 @@ impl ChattyApp {
 
                 chat_view.update(cx, |view, cx| {
                     if view.conversation_id() == Some(conversation_id) {
-                        view.handle_approval_requested(id, command, is_sandboxed, cx);
+                        view.handle_approval_requested(id, command, is_sandboxed, question, cx);
                     }
                 });
             }
@@ -576,10 +653,12 @@ impl ChattyApp {
                 conversation_id,
                 id,
                 approved,
+                answer,
             } => {
                 debug!(id = %id, approved = approved, "StreamManager: approval resolved");
                 let id = id.clone();
                 let approved = *approved;
+                let answer = answer.clone();
 
                 // Update Conversation model unconditionally
                 cx.update_global::<ConversationsStore, _>(|store, _cx| {
@@ -592,13 +671,16 @@ impl ChattyApp {
                             ApprovalState::Denied
                         };
                         trace.update_approval_state(&id, new_state);
+                        if let Some(answer) = answer.clone() {
+                            trace.set_approval_answer(&id, answer);
+                        }
                         trace.clear_active_tool();
                     }
                 });
 
                 chat_view.update(cx, |view, cx| {
                     if view.conversation_id() == Some(conversation_id) {
-                        view.handle_approval_resolved(&id, approved, cx);
+                        view.handle_approval_resolved(&id, approved, answer, cx);
                     }
                 });
             }
@@ -637,6 +719,10 @@ impl ChattyApp {
                     }
                 });
 
+                // Speech mode reads the reply aloud (needs the streamed text,
+                // which is cleared below)
+                self.speech_turn_ended(conversation_id, status, cx);
+
                 match status {
                     StreamStatus::Completed => {
                         // Drain artifacts queued by AddAttachmentTool.
@@ -674,6 +760,15 @@ impl ChattyApp {
                             cx,
                         );
 
+                        // Finished in the background: flag it in the sidebar until opened
+                        let is_open =
+                            self.chat_view.read(cx).conversation_id() == Some(conversation_id);
+                        if !is_open && conversation_id != "__pending__" {
+                            self.sidebar_view.update(cx, |sidebar, cx| {
+                                sidebar.mark_unread(conversation_id, cx);
+                            });
+                        }
+
                         // Update display message with attachment paths
                         if !artifacts.is_empty() {
                             chat_view.update(cx, |view, cx| {
@@ -691,7 +786,10 @@ impl ChattyApp {
                             self.finalize_stopped_stream(conversation_id, trace_json.clone(), cx);
                         }
                     }
-                    _ => {}
+                    StreamStatus::Error(error) => {
+                        self.finalize_failed_stream(conversation_id, error.clone(), cx);
+                    }
+                    StreamStatus::Active => {}
                 }
 
                 // Clear streaming message and trace from Conversation model
@@ -702,10 +800,108 @@ impl ChattyApp {
                         conv.set_streaming_sub_agent_trace(None);
                     }
                 });
+
+                // Send the next message queued during the turn; a stopped or
+                // failed turn puts the queue back in the draft instead.
+                let completed = matches!(status, StreamStatus::Completed);
+                chat_view.update(cx, |view, cx| {
+                    if view.conversation_id() == Some(conversation_id)
+                        || conversation_id == "__pending__"
+                    {
+                        view.chat_input_state().update(cx, |input, cx| {
+                            if completed {
+                                input.dispatch_next_queued(cx);
+                            } else {
+                                input.restore_queued_to_draft(cx);
+                            }
+                        });
+                    }
+                });
+            }
+        }
+    }
+
+    /// Handle a stream that ended with a provider error.
+    ///
+    /// The partial response is discarded and the error is recorded on the
+    /// user message that started the turn, so the chat shows which prompt
+    /// failed and why, and can retry it later.
+    fn finalize_failed_stream(
+        &mut self,
+        conversation_id: &str,
+        error: String,
+        cx: &mut Context<Self>,
+    ) {
+        let failed_at = std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+
+        let history_index = cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            store
+                .get_conversation_mut(conversation_id)
+                .and_then(|conv| conv.record_turn_error(error.clone(), failed_at))
+        });
+
+        let conv_id = conversation_id.to_string();
+        self.chat_view.update(cx, |view, cx| {
+            if view.conversation_id() == Some(&conv_id) || conv_id == "__pending__" {
+                view.mark_turn_failed(
+                    history_index,
+                    TurnError {
+                        message: error,
+                        failed_at,
+                    },
+                    cx,
+                );
             }
+        });
+
+        if history_index.is_some() {
+            self.persist_conversation(conversation_id, cx);
         }
     }
 
+    /// Retry the failed prompt at `history_index`, which must still be the
+    /// last message of the active conversation.
+    pub(super) fn handle_retry_failed_turn(
+        &mut self,
+        history_index: usize,
+        cx: &mut Context<Self>,
+    ) {
+        let conv_id = match cx.global::<ConversationsStore>().active_id().cloned() {
+            Some(id) => id,
+            None => return,
+        };
+        if self.refuse_if_locked(&conv_id, cx) {
+            return;
+        }
+
+        let ok = cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            let conv = store.get_conversation_mut(&conv_id)?;
+            if history_index + 1 != conv.message_count() {
+                return None;
+            }
+            conv.clear_turn_error(history_index).then_some(())
+        });
+        if ok.is_none() {
+            return;
+        }
+
+        let chat_view = self.chat_view.clone();
+        let request_privacy = request_privacy_summary(&conv_id, cx);
+        chat_view.update(cx, |view, cx| {
+            view.clear_turn_error(history_index, cx);
+            view.start_assistant_message(cx);
+            if let Some(policy) = request_privacy {
+                view.set_request_privacy(policy, cx);
+            }
+        });
+
+        self.persist_conversation(&conv_id, cx);
+        self.restream_last_turn(conv_id, None, cx);
+    }
+
     /// Stop the currently active stream for the current conversation.
     /// Delegates to StreamManager which sets the cancellation token and emits StreamEnded.
     pub fn stop_stream(&mut self, cx: &mut Context<Self>) {
@@ -760,7 +956,7 @@ impl ChattyApp {
     /// 1. Finalize assistant message in UI (stop streaming animation)
     /// 2. Save response + trace to conversation model
     /// 3. Process token usage and calculate cost
-    /// 4. Generate title for first exchange (async)
+    /// 4. Queue title generation for the first exchange (background job)
     /// 5. Update sidebar with title/cost
     /// 6. Persist conversation to disk
     fn finalize_completed_stream(
@@ -773,7 +969,6 @@ impl ChattyApp {
         cx: &mut Context<Self>,
     ) {
         let chat_view = self.chat_view.clone();
-        let sidebar = self.sidebar_view.clone();
         let conv_id = conversation_id.to_string();
 
         // 1. Finalize UI - stop streaming animation
@@ -894,50 +1089,7 @@ impl ChattyApp {
                     .unwrap_or(false);
 
                 if is_critical {
-                    let conv_id_for_summary = conv_id.clone();
-                    cx.spawn(async move |_weak, cx| {
-                        let data = cx
-                            .update_global::<ConversationsStore, _>(|store, _cx| {
-                                store
-                                    .get_conversation(&conv_id_for_summary)
-                                    .map(|conv| (conv.agent().clone(), conv.messages()))
-                            })
-                            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
-
-                        if let Some((agent, history)) = data {
-                            // Compute midpoint from the captured snapshot so it stays
-                            // consistent with the history slice passed to the LLM, even
-                            // if new messages arrive while summarization is in flight.
-                            let midpoint = history.len() / 2;
-                            match summarize_oldest_half(&agent, &history).await {
-                                Ok(result) => {
-                                    info!(
-                                        conv_id = %conv_id_for_summary,
-                                        messages_summarized = result.messages_summarized,
-                                        estimated_tokens_freed = result.estimated_tokens_freed,
-                                        "Auto-summarization complete"
-                                    );
-                                    cx.update_global::<ConversationsStore, _>(|store, _cx| {
-                                        if let Some(conv) =
-                                            store.get_conversation_mut(&conv_id_for_summary)
-                                        {
-                                            conv.replace_history(result.new_history, midpoint);
-                                        }
-                                    })
-                                    .map_err(
-                                        |e| warn!(error = ?e, "Failed to apply auto-summarization"),
-                                    )
-                                    .ok();
-                                }
-                                Err(e) => {
-                                    warn!(error = ?e, "Auto-summarization failed");
-                                }
-                            }
-                        }
-
-                        Ok::<_, anyhow::Error>(())
-                    })
-                    .detach();
+                    self.enqueue_job(JobKind::Summarize, &conv_id, cx);
                 }
             }
         }
@@ -945,68 +1097,9 @@ impl ChattyApp {
         // 4. Update sidebar with latest data
         self.refresh_sidebar(cx);
 
-        // 5. Generate title for first exchange (async) + persist
+        // 5. Generate title for first exchange (background job, retried on failure)
         if should_generate_title {
-            let conv_id_for_title = conv_id.clone();
-            let sidebar_for_title = sidebar.clone();
-
-            cx.spawn(async move |_weak, cx| {
-                // Get agent and history for title generation
-                let title_data = cx
-                    .update_global::<ConversationsStore, _>(|store, _cx| {
-                        store
-                            .get_conversation(&conv_id_for_title)
-                            .map(|conv| (conv.agent().clone(), conv.messages()))
-                    })
-                    .map_err(|e| anyhow::anyhow!(e.to_string()))?;
-
-                if let Some((agent, history)) = title_data {
-                    match generate_title(&agent, &history).await {
-                        Ok(new_title) => {
-                            debug!(title = %new_title, "Generated title");
-
-                            cx.update_global::<ConversationsStore, _>(|store, _cx| {
-                                if let Some(conv) = store.get_conversation_mut(&conv_id_for_title) {
-                                    conv.set_title(new_title.clone());
-                                }
-                                // Compute cost separately to avoid simultaneous borrow
-                                let cost = store
-                                    .get_conversation(&conv_id_for_title)
-                                    .map(|c| c.token_usage().total_estimated_cost_usd)
-                                    .unwrap_or(0.0);
-                                let now_ts = std::time::SystemTime::now()
-                                    .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                                    .unwrap_or_default()
-                                    .as_secs() as i64;
-                                // Also update metadata so sidebar reflects the new title
-                                store.upsert_metadata(&conv_id_for_title, &new_title, cost, now_ts);
-                            })
-                            .map_err(|e| warn!(error = ?e, "Failed to update conversation title"))
-                            .ok();
-
-                            // Update sidebar with new title from metadata
-                            sidebar_for_title
-                                .update(cx, |sidebar, cx| {
-                                    let store = cx.global::<ConversationsStore>();
-                                    let total = store.count();
-                                    let convs = store.list_recent_metadata(sidebar.visible_limit());
-                                    sidebar.set_conversations(convs, cx);
-                                    sidebar.set_total_count(total);
-                                })
-                                .map_err(|e| {
-                                    warn!(error = ?e, "Failed to update sidebar with new title")
-                                })
-                                .ok();
-                        }
-                        Err(e) => {
-                            warn!(error = ?e, "Title generation failed");
-                        }
-                    }
-                }
-
-                Ok::<_, anyhow::Error>(())
-            })
-            .detach();
+            self.enqueue_job(JobKind::GenerateTitle, &conv_id, cx);
         }
 
         // 6. Persist to disk
@@ -1018,7 +1111,7 @@ impl ChattyApp {
             .map(|s| s.atif_auto_export)
             .unwrap_or(false)
         {
-            self.export_conversation_atif(&conv_id, cx);
+            self.enqueue_job(JobKind::ExportAtif, &conv_id, cx);
         }
 
         // 8. Auto-export JSONL (SFT + DPO) if enabled in training settings
@@ -1027,8 +1120,19 @@ impl ChattyApp {
             .map(|s| s.jsonl_auto_export)
             .unwrap_or(false)
         {
-            self.export_conversation_jsonl(&conv_id, cx);
+            self.enqueue_job(JobKind::ExportJsonl, &conv_id, cx);
         }
+
+        // 9. Suggest a split if this turn changed the topic (opt-in, background job)
+        if cx.global::<GeneralSettingsModel>().topic_split_suggestions {
+            self.enqueue_job(JobKind::DetectTopicShift, &conv_id, cx);
+        }
+
+        // 10. Rate the conversation with the judge model (opt-in)
+        self.score_conversation_quality(&conv_id, cx);
+
+        // 11. Critique the answer and retry once on low confidence (opt-in)
+        self.reflect_on_answer(&conv_id, cx);
     }
 
     /// Handle the finalization of a stopped stream (partial response saving).
@@ -1116,6 +1220,172 @@ impl ChattyApp {
         }
     }
 
+    /// Handle a reaction toggle or note edit: update ConversationsStore and persist
+    pub(super) fn handle_annotation_changed(
+        &self,
+        history_index: usize,
+        change: AnnotationChange,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(conv_id) = cx.global::<ConversationsStore>().active_id().cloned() else {
+            return;
+        };
+
+        cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            if let Some(conv) = store.get_conversation_mut(&conv_id) {
+                match change {
+                    AnnotationChange::ToggleReaction(emoji) => {
+                        conv.toggle_message_reaction(history_index, &emoji);
+                    }
+                    AnnotationChange::SetNote(note) => {
+                        conv.set_message_note(history_index, note);
+                    }
+                }
+            }
+        });
+        self.persist_conversation(&conv_id, cx);
+    }
+
+    /// Delete a message, or only its trace, from the active conversation's
+    /// history. The conversation records the edit in its log; the chat view
+    /// is reloaded so display indices line up with the history again.
+    pub(super) fn handle_history_deletion(
+        &self,
+        history_index: usize,
+        trace_only: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(conv_id) = cx.global::<ConversationsStore>().active_id().cloned() else {
+            return;
+        };
+        if self.refuse_if_locked(&conv_id, cx) {
+            return;
+        }
+
+        let edited = cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            let conv = store.get_conversation_mut(&conv_id)?;
+            if conv.streaming_message().is_some() {
+                return None;
+            }
+            let edit = if trace_only {
+                conv.delete_trace(history_index)
+            } else {
+                conv.delete_message(history_index)
+            }?;
+            Some((edit, conv.entries().to_vec()))
+        });
+        let Some((edit, entries)) = edited else {
+            warn!(conv_id = %conv_id, history_index, trace_only, "History deletion skipped");
+            return;
+        };
+        info!(conv_id = %conv_id, edit = %edit.summary(), "Conversation history edited");
+
+        self.chat_view.update(cx, |view, cx| {
+            if view.conversation_id().map(|id| id.as_str()) == Some(conv_id.as_str()) {
+                let offset = view.scroll_offset();
+                view.load_history(&entries, cx);
+                view.restore_scroll_offset(offset, cx);
+            }
+        });
+        self.persist_conversation(&conv_id, cx);
+    }
+
+    /// Pin the text of a message to the active conversation's context.
+    pub(super) fn handle_pin_message(&self, history_index: usize, cx: &mut Context<Self>) {
+        let Some(conv_id) = cx.global::<ConversationsStore>().active_id().cloned() else {
+            return;
+        };
+
+        let pinned = cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            store
+                .get_conversation_mut(&conv_id)
+                .is_some_and(|conv| conv.pin_message(history_index))
+        });
+
+        if pinned {
+            self.sync_pinned_items(&conv_id, cx);
+            self.persist_conversation(&conv_id, cx);
+        }
+    }
+
+    /// Remove an item from the active conversation's pinned context.
+    pub(super) fn handle_unpin_item(&self, id: &str, cx: &mut Context<Self>) {
+        let Some(conv_id) = cx.global::<ConversationsStore>().active_id().cloned() else {
+            return;
+        };
+
+        let removed = cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            store
+                .get_conversation_mut(&conv_id)
+                .is_some_and(|conv| conv.unpin_item(id))
+        });
+
+        if removed {
+            self.sync_pinned_items(&conv_id, cx);
+            self.persist_conversation(&conv_id, cx);
+        }
+    }
+
+    /// Push a conversation's pinned items into the chat view's context drawer.
+    pub(super) fn sync_pinned_items(&self, conv_id: &str, cx: &mut Context<Self>) {
+        let items = cx
+            .global::<ConversationsStore>()
+            .get_conversation(conv_id)
+            .map(|conv| conv.pinned_items().to_vec())
+            .unwrap_or_default();
+        self.chat_view.update(cx, |view, cx| {
+            if view.conversation_id().map(|id| id.as_str()) == Some(conv_id) {
+                view.set_pinned_items(items, cx);
+            }
+        });
+    }
+
+    /// Handle deletion of a conversation variable from the side panel.
+    pub(super) fn handle_delete_variable(&self, name: &str, cx: &mut Context<Self>) {
+        let Some(conv_id) = cx.global::<ConversationsStore>().active_id().cloned() else {
+            return;
+        };
+
+        let removed = cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            store
+                .get_conversation_mut(&conv_id)
+                .is_some_and(|conv| conv.remove_variable(name))
+        });
+
+        if removed {
+            let variables = cx
+                .global::<ConversationsStore>()
+                .get_conversation(&conv_id)
+                .map(|conv| conv.variables_snapshot())
+                .unwrap_or_default();
+            self.chat_view.update(cx, |view, cx| {
+                if view.conversation_id().map(|id| id.as_str()) == Some(conv_id.as_str()) {
+                    view.set_variables(variables, cx);
+                }
+            });
+            self.persist_conversation(&conv_id, cx);
+        }
+    }
+
+    /// Handle clearing the planner-mode plan from the checklist.
+    pub(super) fn handle_clear_execution_plan(&self, cx: &mut Context<Self>) {
+        let Some(conv_id) = cx.global::<ConversationsStore>().active_id().cloned() else {
+            return;
+        };
+
+        cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            if let Some(conv) = store.get_conversation_mut(&conv_id) {
+                conv.clear_execution_plan();
+            }
+        });
+        self.chat_view.update(cx, |view, cx| {
+            if view.conversation_id().map(|id| id.as_str()) == Some(conv_id.as_str()) {
+                view.set_execution_plan(None, cx);
+            }
+        });
+        self.persist_conversation(&conv_id, cx);
+    }
+
     /// Handle regeneration of the last assistant message.
     ///
     /// Records the original response as a DPO preference pair, removes the old
@@ -1127,6 +1397,9 @@ impl ChattyApp {
             Some(id) => id,
             None => return,
         };
+        if self.refuse_if_locked(&conv_id, cx) {
+            return;
+        }
 
         // PHASE 1: Remove old assistant message and record DPO pair
         let ok = cx.update_global::<ConversationsStore, _>(|store, _cx| {
@@ -1153,15 +1426,77 @@ impl ChattyApp {
 
         // PHASE 2: Update UI — remove old assistant message, start fresh placeholder
         let chat_view = self.chat_view.clone();
+        let request_privacy = request_privacy_summary(&conv_id, cx);
         chat_view.update(cx, |view, cx| {
             view.remove_last_assistant_message(cx);
             view.start_assistant_message(cx);
+            if let Some(policy) = request_privacy {
+                view.set_request_privacy(policy, cx);
+            }
         });
 
         // Persist the regeneration record before streaming
         self.persist_conversation(&conv_id, cx);
 
         // PHASE 3: Stream new response via shared helper
+        self.restream_last_turn(conv_id, None, cx);
+    }
+
+    /// Replace the text of the user message at `history_index`, drop every
+    /// later message and stream a new answer to the edited message.
+    pub(super) fn handle_edit_user_message(
+        &mut self,
+        history_index: usize,
+        text: String,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(conv_id) = cx.global::<ConversationsStore>().active_id().cloned() else {
+            return;
+        };
+        if self.refuse_if_locked(&conv_id, cx) {
+            return;
+        }
+
+        let edited = cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            let conv = store.get_conversation_mut(&conv_id)?;
+            if conv.streaming_message().is_some() {
+                return None;
+            }
+            let edit = conv.edit_user_message(history_index, text)?;
+            Some((edit, conv.entries().to_vec()))
+        });
+        let Some((edit, entries)) = edited else {
+            warn!(conv_id = %conv_id, history_index, "Message edit skipped");
+            return;
+        };
+        info!(conv_id = %conv_id, edit = %edit.summary(), "Conversation history edited");
+
+        let request_privacy = request_privacy_summary(&conv_id, cx);
+        self.chat_view.update(cx, |view, cx| {
+            view.load_history(&entries, cx);
+            view.start_assistant_message(cx);
+            if let Some(policy) = request_privacy {
+                view.set_request_privacy(policy, cx);
+            }
+        });
+
+        // Persist the edit before streaming
+        self.persist_conversation(&conv_id, cx);
+
+        self.restream_last_turn(conv_id, None, cx);
+    }
+
+    /// Stream a new answer to the last user message of `conv_id`, whose
+    /// previous answer was already removed from the model and the UI.
+    /// `appended_text` is added to the user message for this request only
+    /// (the stored history keeps the original message).
+    pub(super) fn restream_last_turn(
+        &mut self,
+        conv_id: String,
+        appended_text: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let chat_view = self.chat_view.clone();
         let sidebar = self.sidebar_view.clone();
         let pending_artifacts = cx
             .global::<ConversationsStore>()
@@ -1218,7 +1553,7 @@ impl ChattyApp {
                 return Err(anyhow::anyhow!("Empty history during regeneration"));
             }
             let history_context = history[..len - 1].to_vec();
-            let user_contents = match &history[len - 1] {
+            let mut user_contents = match &history[len - 1] {
                 rig_core::completion::Message::User { content, .. } => {
                     content.iter().cloned().collect::<Vec<_>>()
                 }
@@ -1228,6 +1563,9 @@ impl ChattyApp {
                     ));
                 }
             };
+            if let Some(text) = appended_text {
+                user_contents.push(rig_core::message::UserContent::text(text));
+            }
 
             // Run shared LLM stream (do NOT add user message — it's already in history)
             run_llm_stream(
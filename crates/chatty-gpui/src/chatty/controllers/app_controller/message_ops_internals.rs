@@ -34,7 +34,8 @@ pub(super) struct LlmStreamParams {
 /// 1. Approval channel setup
 /// 2. `stream_prompt()` call
 /// 3. Optionally adding user message to conversation model
-/// 4. Stream processing loop (chunks -> ConversationsStore + StreamManager)
+/// 4. Stream processing loop (chunks -> ConversationsStore + StreamManager), failing
+///    the turn when the provider stalls
 /// 5. Trace extraction and StreamManager finalization
 ///
 /// Callers are responsible for their own preamble (conversation creation, UI message
@@ -98,7 +99,101 @@ pub(super) async fn run_llm_stream(
         .ok()
         .flatten();
 
-    // 2b. Compute token budget snapshot in parallel with the LLM call.
+    // 2b. Apply the conversation's history policy, then context shaping to keep
+    // history within LLM context limits. Only the request is shaped; the stored
+    // conversation keeps its full history unless it had to be compacted to
+    // fit the model's context window.
+    let history_policy = cx
+        .update(|cx| {
+            cx.global::<ConversationsStore>()
+                .get_conversation(&conv_id)
+                .map(|conv| conv.history_policy())
+        })
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    let history = if matches!(
+        history_policy,
+        chatty_core::models::HistoryPolicy::LastTurns { .. }
+    ) {
+        history
+    } else {
+        compact_history_if_needed(&conv_id, &agent, history, cx).await
+    };
+    let (history, dropped_messages) = history_policy.apply(history);
+    if dropped_messages > 0 {
+        debug!(conv_id = %conv_id, policy = ?history_policy, dropped_messages,
+            "History policy left out older messages");
+    }
+    // Near the context limit, the relevance policy leaves out the middle turns
+    // least related to the new message. Without an embedding service the
+    // history is sent whole and only the context shaper applies.
+    let (history, pruned_turns) = if history_policy.prunes_by_relevance() {
+        let embeddings = get_embedding_service(cx);
+        let context_limit = cx
+            .update(|cx| {
+                let model_id = cx
+                    .global::<ConversationsStore>()
+                    .get_conversation(&conv_id)?
+                    .model_id()
+                    .to_string();
+                cx.global::<ModelsModel>()
+                    .get_model(&model_id)?
+                    .max_context_window
+                    .map(|limit| limit as usize)
+            })
+            .ok()
+            .flatten();
+        let budget = chatty_core::services::relevance_pruner::prune_budget_chars(context_limit);
+        let query = extract_user_message_text(&user_contents);
+        match embeddings {
+            Some(embeddings) => {
+                let fallback = history.clone();
+                match chatty_core::services::relevance_pruner::prune_by_relevance(
+                    &embeddings,
+                    history,
+                    &query,
+                    budget,
+                )
+                .await
+                {
+                    Ok(pruned) => {
+                        if !pruned.pruned.is_empty() {
+                            debug!(conv_id = %conv_id, pruned_turns = pruned.pruned.len(),
+                                "Relevance policy pruned history");
+                        }
+                        (pruned.messages, pruned.pruned)
+                    }
+                    Err(e) => {
+                        warn!(error = ?e, conv_id = %conv_id,
+                            "Relevance pruning failed, sending full history");
+                        (fallback, Vec::new())
+                    }
+                }
+            }
+            None => {
+                debug!(conv_id = %conv_id, "No embedding service, skipping relevance pruning");
+                (history, Vec::new())
+            }
+        }
+    } else {
+        (history, Vec::new())
+    };
+    let shaped_history = {
+        let settings = chatty_core::services::ContextShaperSettings::default();
+        let summarizer = history_policy.allows_summarization().then_some(&agent);
+        let shaped = chatty_core::services::shape_context(history, &settings, summarizer).await;
+        if let Some(stage) = shaped.stage_applied {
+            debug!(conv_id = %conv_id, stage = ?stage, freed = shaped.chars_freed,
+                "Context shaper applied");
+        }
+        shaped.messages
+    };
+
+    // 2c. Compute token budget snapshot in parallel with the LLM call.
+    //
+    // The snapshot counts the shaped history, so the context bar shows what is
+    // actually sent and which parts the shaper truncated.
     //
     // gather_snapshot_inputs() must run on the GPUI thread (reads globals, warms the
     // static cache), so we call it synchronously here.  The expensive part —
@@ -108,7 +203,12 @@ pub(super) async fn run_llm_stream(
     // whatever repaint follows the count completing (~1–10 ms later).
     {
         let user_message_text_for_budget = extract_user_message_text(&user_contents);
-        let history_for_budget = history.clone();
+        let attachments_for_budget: Vec<_> = user_contents
+            .iter()
+            .filter(|content| chatty_core::token_budget::breakdown::is_attachment(content))
+            .cloned()
+            .collect();
+        let history_for_budget = shaped_history.clone();
         let conv_id_for_budget = conv_id.clone();
 
         let budget_inputs = cx
@@ -116,7 +216,10 @@ pub(super) async fn run_llm_stream(
                 gather_snapshot_inputs(
                     &conv_id_for_budget,
                     user_message_text_for_budget,
+                    attachments_for_budget,
                     history_for_budget,
+                    dropped_messages,
+                    pruned_turns,
                     cx,
                 )
             })
@@ -162,20 +265,24 @@ pub(super) async fn run_llm_stream(
         }
     }
 
-    // 3. Apply context shaping to keep history within LLM context limits.
-    let shaped_history = {
-        let settings = chatty_core::services::ContextShaperSettings::default();
-        let shaped = chatty_core::services::shape_context(history, &settings, None).await;
-        if let Some(stage) = shaped.stage_applied {
-            debug!(conv_id = %conv_id, stage = ?stage, freed = shaped.chars_freed,
-                "Context shaper applied");
-        }
-        shaped.messages
-    };
-
-    // 3b. Call stream_prompt with user contents directly (no auto-context injection)
+    // 3. Call stream_prompt with the user contents, prefixed by any pinned
+    // context items. Pinned items are only sent to the LLM — the stored user
+    // message stays as typed (no retrieval auto-context injection).
     let agent_task_controller = agent.task_controller();
-    let llm_user_contents = user_contents.clone();
+    let pinned_block = cx
+        .update(|cx| {
+            cx.global::<ConversationsStore>()
+                .get_conversation(&conv_id)
+                .and_then(|conv| {
+                    chatty_core::models::build_pinned_context_block(conv.pinned_items())
+                })
+        })
+        .ok()
+        .flatten();
+    let mut llm_user_contents = user_contents.clone();
+    if let Some(block) = pinned_block {
+        llm_user_contents.insert(0, rig_core::message::UserContent::text(block));
+    }
     debug!(conv_id = %conv_id, "Calling stream_prompt()");
     let (mut stream, _user_message) = stream_prompt(
         &agent,
@@ -187,8 +294,15 @@ pub(super) async fn run_llm_stream(
     )
     .await?;
 
-    // 4. Optionally add user message to conversation model.
+    // 4. Optionally add user message to conversation model. Attachments are
+    // recorded by their copy in the attachment store, so the message keeps
+    // them even if the originals move.
     if add_user_message_to_model {
+        let attachment_paths =
+            match chatty_core::services::attachment_store::AttachmentStore::open_default() {
+                Some(store) => store.store_all(attachment_paths).await,
+                None => attachment_paths,
+            };
         let user_message = rig_core::completion::Message::User {
             content: rig_core::OneOrMany::many(user_contents).map_err(|e| {
                 anyhow::anyhow!("Failed to create user message from contents: {}", e)
@@ -205,6 +319,8 @@ pub(super) async fn run_llm_stream(
     // 5. Install invoke_agent progress channel
     let mut progress_rx =
         chatty_core::services::install_progress_channel(&invoke_agent_progress_slot);
+    // Long-running tools watch the same flag so Stop interrupts them mid-call.
+    agent.tool_cancellation().install(cancel_flag.clone());
 
     // 5b. AgentLoopGuard: detects repeated tool calls (loops) and verbosity bursts.
     // Desktop streams don't require an answer file, so answer_file_required=false.
@@ -218,6 +334,54 @@ pub(super) async fn run_llm_stream(
     let mut pending_follow_up: Option<String> = None;
     let mut text_overflow_stop_requested = false;
 
+    // 5c. Stall detection: a provider that sends nothing for longer than its
+    // read timeout fails the turn, which can then be retried, instead of
+    // leaving it spinning. Tool calls and approvals pause the clock.
+    let read_timeout = cx
+        .update(|cx| {
+            cx.global::<ProviderModel>()
+                .providers()
+                .iter()
+                .find(|p| p.provider_type == provider_type)
+                .map(|p| p.timeouts())
+                .unwrap_or_else(|| provider_type.default_timeouts())
+                .read()
+        })
+        .ok()
+        .flatten();
+    let mut stall_detector = chatty_core::services::StallDetector::new(read_timeout);
+
+    // 5d. `/timebox` budget of the task this stream belongs to. Near the
+    // budget the stream is interrupted and the agent told to wrap up; at the
+    // budget it stops and its trace is marked.
+    let mut session_budget = cx
+        .update_global::<ConversationsStore, _>(|store, _cx| {
+            store
+                .get_conversation_mut(&conv_id)
+                .and_then(|conv| conv.take_session_budget())
+        })
+        .ok()
+        .flatten();
+    if let Some(budget) = session_budget.as_mut() {
+        budget.start(std::time::Instant::now());
+    }
+    let pricing = cx
+        .update(|cx| {
+            let model_id = cx
+                .global::<ConversationsStore>()
+                .get_conversation(&conv_id)?
+                .model_id()
+                .to_string();
+            let model = cx.global::<ModelsModel>().get_model(&model_id)?;
+            Some((
+                model.cost_per_million_input_tokens,
+                model.cost_per_million_output_tokens,
+            ))
+        })
+        .ok()
+        .flatten();
+    let mut budget_marker: Option<String> = None;
+
     // 6. Stream processing loop
     debug!(conv_id = %conv_id, "Entering stream processing loop");
     use futures::StreamExt;
@@ -234,6 +398,7 @@ pub(super) async fn run_llm_stream(
             // Handle invoke_agent progress events first (sub-agent visualisation)
             Some(progress) = progress_rx.recv() => {
                 use chatty_core::tools::invoke_agent_tool::InvokeAgentProgress;
+                stall_detector.touch();
                 match progress {
                     InvokeAgentProgress::Started {
                         agent_name,
@@ -297,6 +462,9 @@ pub(super) async fn run_llm_stream(
                     Some(r) => r,
                     None => break,
                 };
+                if let Ok(ref chunk) = chunk_result {
+                    stall_detector.observe(chunk);
+                }
 
                 match chunk_result {
                     Ok(StreamChunk::Text(ref text)) => {
@@ -316,9 +484,27 @@ pub(super) async fn run_llm_stream(
                             debug!(conv_id = %conv_id,
                                 "Text-only response exceeded verbosity limit; will inject brevity prompt after response completes.");
                         }
+                        if let Some(budget) = session_budget.as_mut() {
+                            budget.on_text(text.len(), pricing.and_then(|(_, output)| output));
+                            if let Some(action) = budget.poll(std::time::Instant::now()) {
+                                apply_budget_action(action, &cancel_flag, &mut pending_follow_up, &mut budget_marker);
+                            }
+                        }
                     }
-                    Ok(StreamChunk::TokenUsage { .. }) => {
-                        // Token usage tracked by StreamManager
+                    Ok(StreamChunk::TokenUsage { input_tokens, output_tokens }) => {
+                        // Token usage tracked by StreamManager; the budget
+                        // swaps its estimate for the reported cost
+                        if let Some(budget) = session_budget.as_mut() {
+                            let cost = pricing.and_then(|(input, output)| {
+                                let mut usage = TokenUsage::new(input_tokens, output_tokens);
+                                usage.calculate_cost(input?, output?);
+                                usage.estimated_cost_usd
+                            });
+                            budget.on_usage(cost);
+                            if let Some(action) = budget.poll(std::time::Instant::now()) {
+                                apply_budget_action(action, &cancel_flag, &mut pending_follow_up, &mut budget_marker);
+                            }
+                        }
                     }
                     Ok(StreamChunk::Done) => {
                         debug!(conv_id = %conv_id, "Received Done chunk");
@@ -423,6 +609,78 @@ pub(super) async fn run_llm_stream(
                                 })
                                 .ok();
                         }
+                        if is_variable_tool(&tool_name) {
+                            let variables = cx
+                                .update(|cx| {
+                                    cx.global::<ConversationsStore>()
+                                        .get_conversation(&conv_id)
+                                        .map(|conv| conv.variables_snapshot())
+                                })
+                                .ok()
+                                .flatten()
+                                .unwrap_or_default();
+                            chat_view
+                                .update(cx, |view, cx| {
+                                    if view.conversation_id().map(|id| id.as_str())
+                                        == Some(conv_id.as_str())
+                                    {
+                                        view.set_variables(variables, cx);
+                                    }
+                                })
+                                .map_err(|e| {
+                                    warn!(
+                                        error = ?e,
+                                        "Failed to update variables panel after variable tool result"
+                                    )
+                                })
+                                .ok();
+                            if tool_name == "set_variable" {
+                                weak_ctrl
+                                    .update(&mut *cx, |app, cx| {
+                                        app.persist_conversation(&conv_id, cx);
+                                    })
+                                    .map_err(|e| {
+                                        warn!(
+                                            error = ?e,
+                                            "Failed to persist conversation variables to disk"
+                                        )
+                                    })
+                                    .ok();
+                            }
+                        }
+                        if is_plan_tool(&tool_name) {
+                            let plan = cx
+                                .update(|cx| {
+                                    cx.global::<ConversationsStore>()
+                                        .get_conversation(&conv_id)
+                                        .and_then(|conv| conv.execution_plan_snapshot())
+                                })
+                                .ok()
+                                .flatten();
+                            chat_view
+                                .update(cx, |view, cx| {
+                                    if view.conversation_id().map(|id| id.as_str())
+                                        == Some(conv_id.as_str())
+                                    {
+                                        view.set_execution_plan(plan, cx);
+                                    }
+                                })
+                                .map_err(|e| {
+                                    warn!(
+                                        error = ?e,
+                                        "Failed to update plan checklist after plan tool result"
+                                    )
+                                })
+                                .ok();
+                            weak_ctrl
+                                .update(&mut *cx, |app, cx| {
+                                    app.persist_conversation(&conv_id, cx);
+                                })
+                                .map_err(|e| {
+                                    warn!(error = ?e, "Failed to persist execution plan to disk")
+                                })
+                                .ok();
+                        }
                         if pending_follow_up.is_none()
                             && let Some(prompt) =
                                 agent_task_controller.observe_tool_result(&tool_name)
@@ -476,6 +734,29 @@ pub(super) async fn run_llm_stream(
                     }
                 }
             } // end of stream.next() branch
+            // Nothing received within the read timeout
+            () = wait_until(stall_detector.deadline()) => {
+                let message = stall_detector.error_message();
+                error!(conv_id = %conv_id, timeout = ?read_timeout, "Stream stalled");
+                if let Some(ref sm) = stream_manager {
+                    sm.update(cx, |sm: &mut crate::chatty::models::StreamManager, cx| {
+                        sm.handle_chunk(&conv_id, StreamChunk::Error(message), cx);
+                    })
+                    .map_err(|e| warn!(error = ?e, "Failed to forward stall error to StreamManager"))
+                    .ok();
+                }
+                break;
+            }
+            // The `/timebox` duration nears its end while nothing arrives
+            () = wait_until(session_budget.as_ref().and_then(|b| b.next_deadline())) => {
+                if let Some(action) = session_budget
+                    .as_mut()
+                    .and_then(|b| b.poll(std::time::Instant::now()))
+                {
+                    apply_budget_action(action, &cancel_flag, &mut pending_follow_up, &mut budget_marker);
+                    break;
+                }
+            }
         } // end of tokio::select!
     } // end of loop
 
@@ -525,6 +806,36 @@ pub(super) async fn run_llm_stream(
         *slot = None;
     }
 
+    // A task that continues with a follow-up keeps its budget. One that was
+    // told to wrap up and finished ends here, marked like a stopped one.
+    if let Some(budget) = session_budget {
+        if budget_marker.is_some() {
+            pending_follow_up = None;
+        } else if pending_follow_up.is_some() {
+            cx.update_global::<ConversationsStore, _>(|store, _cx| {
+                if let Some(conv) = store.get_conversation_mut(&conv_id)
+                    && conv.session_budget().is_none()
+                {
+                    conv.set_session_budget(Some(budget));
+                }
+            })
+            .map_err(|e| warn!(error = ?e, "Failed to keep the timebox for the follow-up"))
+            .ok();
+        } else if budget.wrap_up_sent() {
+            budget_marker = Some(budget.marker(std::time::Instant::now()));
+        }
+    }
+    if let Some(marker) = &budget_marker {
+        info!(conv_id = %conv_id, marker = %marker, "Timebox reached");
+        chat_view
+            .update(cx, |view, cx| {
+                if view.conversation_id().map(|id| id.as_str()) == Some(conv_id.as_str()) {
+                    view.set_budget_reached(marker.clone(), cx);
+                }
+            })
+            .ok();
+    }
+
     // 6. Extract trace and finalize via StreamManager
     debug!(conv_id = %conv_id, "Stream loop finished, finalizing via StreamManager");
 
@@ -543,6 +854,15 @@ pub(super) async fn run_llm_stream(
         .flatten()
     });
 
+    let trace = match budget_marker {
+        Some(marker) => {
+            let mut trace = trace.unwrap_or_else(SystemTrace::new);
+            trace.budget_reached = Some(marker);
+            Some(trace)
+        }
+        None => trace,
+    };
+
     let trace_json = trace.and_then(|trace| match serde_json::to_value(&trace) {
         Ok(val) => {
             debug!(conv_id = %conv_id, items = trace.items.len(), "Trace serialized successfully");
@@ -554,6 +874,31 @@ pub(super) async fn run_llm_stream(
         }
     });
 
+    // Swap the artifacts queued by tools for their copies in the attachment
+    // store before the stream hands them to the assistant message.
+    let pending_artifacts = cx
+        .try_read_global::<ConversationsStore, _>(|store, _| {
+            store
+                .get_conversation(&conv_id)
+                .map(|conv| conv.pending_artifacts())
+        })
+        .flatten();
+    if let Some(pending) = pending_artifacts
+        && let Some(store) =
+            chatty_core::services::attachment_store::AttachmentStore::open_default()
+    {
+        let queued = pending
+            .lock()
+            .map(|mut v| std::mem::take(&mut *v))
+            .unwrap_or_default();
+        if !queued.is_empty() {
+            let stored = store.store_all(queued).await;
+            if let Ok(mut artifacts) = pending.lock() {
+                artifacts.splice(0..0, stored);
+            }
+        }
+    }
+
     if let Some(ref sm) = stream_manager {
         sm.update(cx, |sm: &mut crate::chatty::models::StreamManager, cx| {
             sm.set_trace(&conv_id, trace_json);
@@ -578,6 +923,36 @@ pub(super) async fn run_llm_stream(
     Ok(())
 }
 
+/// Act on a `/timebox` budget: interrupt the stream to send the wrap-up
+/// instruction, or stop it and keep the budget-reached marker for its trace.
+fn apply_budget_action(
+    action: chatty_core::models::BudgetAction,
+    cancel_flag: &AtomicBool,
+    pending_follow_up: &mut Option<String>,
+    budget_marker: &mut Option<String>,
+) {
+    use chatty_core::models::BudgetAction;
+    cancel_flag.store(true, Ordering::Relaxed);
+    match action {
+        BudgetAction::WrapUp => {
+            *pending_follow_up =
+                Some(chatty_core::models::session_budget::WRAP_UP_PROMPT.to_string());
+        }
+        BudgetAction::Stop(marker) => {
+            *pending_follow_up = None;
+            *budget_marker = Some(marker);
+        }
+    }
+}
+
+/// Resolve at `deadline`, or never when there is none.
+async fn wait_until(deadline: Option<std::time::Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
 pub(super) fn is_auth_stream_error(err: &str) -> bool {
     err.contains("401") || err.contains("Unauthorized")
 }
@@ -599,6 +974,14 @@ fn is_agent_todo_tool(tool_name: &str) -> bool {
     )
 }
 
+fn is_variable_tool(tool_name: &str) -> bool {
+    matches!(tool_name, "get_variable" | "set_variable")
+}
+
+fn is_plan_tool(tool_name: &str) -> bool {
+    matches!(tool_name, "create_plan" | "update_plan")
+}
+
 /// Select attachment paths from the most recent assistant message that the
 /// current model can handle. Returns paths filtered by capability.
 ///
@@ -640,6 +1023,28 @@ pub(super) fn select_recent_assistant_attachments(
     Vec::new()
 }
 
+/// Expand a video attachment into a text part (duration, frame timestamps
+/// and transcript) followed by up to `frame_cap` sampled frames.
+pub(super) async fn video_to_user_contents(
+    path: &Path,
+    frame_cap: u32,
+    transcribe_command: Option<&str>,
+) -> anyhow::Result<Vec<rig_core::message::UserContent>> {
+    use chatty_core::services::video_frames::sample_video;
+
+    let sample = sample_video(path, frame_cap, transcribe_command).await?;
+    let frames = sample.select_frames(frame_cap);
+    let mut contents = vec![rig_core::message::UserContent::Text(
+        rig_core::completion::message::Text {
+            text: sample.describe(&frames),
+        },
+    )];
+    for frame in frames {
+        contents.push(attachment_to_user_content(&frame.path).await?);
+    }
+    Ok(contents)
+}
+
 /// Convert a file attachment to a rig-core UserContent
 pub(super) async fn attachment_to_user_content(
     path: &Path,
@@ -722,6 +1127,9 @@ mod tests {
             attachment_paths: attachments,
             timestamp: None,
             feedback: None,
+            reactions: Vec::new(),
+            note: None,
+            error: None,
         }
     }
 
@@ -859,3 +1267,74 @@ mod tests {
         assert_eq!(result, vec![PathBuf::from("/tmp/report.PDF")]);
     }
 }
+
+/// Compact the stored history when it nears the model's context window: the
+/// older half is summarized by the conversation's agent, replaced with the
+/// summary and recorded on the conversation. Returns the history to send,
+/// unchanged when the model has no context window configured, the history
+/// still fits, or summarizing fails.
+async fn compact_history_if_needed(
+    conv_id: &str,
+    agent: &AgentClient,
+    history: Vec<rig_core::completion::Message>,
+    cx: &mut AsyncApp,
+) -> Vec<rig_core::completion::Message> {
+    let limits = cx
+        .update(|cx| {
+            let model_id = cx
+                .global::<ConversationsStore>()
+                .get_conversation(conv_id)?
+                .model_id()
+                .to_string();
+            let model = cx.global::<ModelsModel>().get_model(&model_id)?;
+            let context_window = model.max_context_window? as usize;
+            let settings = cx
+                .try_global::<TokenTrackingSettings>()
+                .cloned()
+                .unwrap_or_default();
+            Some((model.model_identifier.clone(), context_window, settings))
+        })
+        .ok()
+        .flatten();
+    let Some((model_identifier, context_window, settings)) = limits else {
+        return history;
+    };
+
+    let counted = history.clone();
+    let history_tokens = tokio::task::spawn_blocking(move || {
+        chatty_core::token_budget::counter::TokenCounter::for_model(&model_identifier)
+            .count_history(&counted)
+    })
+    .await
+    .unwrap_or_default();
+    if !settings.should_compact(history_tokens, context_window) {
+        return history;
+    }
+
+    info!(conv_id = %conv_id, history_tokens, context_window,
+        "History nears the context window, compacting");
+    let result = match summarize_oldest_half(agent, &history).await {
+        Ok(result) => result,
+        Err(e) => {
+            warn!(error = ?e, conv_id = %conv_id, "Automatic compaction failed, sending full history");
+            return history;
+        }
+    };
+    let compacted = cx
+        .update_global::<ConversationsStore, _>(|store, _cx| {
+            store
+                .get_conversation_mut(conv_id)
+                .and_then(|conv| conv.compact_history(&result, CompactionTrigger::Automatic))
+        })
+        .ok()
+        .flatten();
+    match compacted {
+        Some(record) => {
+            info!(conv_id = %conv_id, messages_compacted = record.messages_compacted,
+                estimated_tokens_freed = record.estimated_tokens_freed,
+                "Automatic compaction complete");
+            result.new_history
+        }
+        None => history,
+    }
+}
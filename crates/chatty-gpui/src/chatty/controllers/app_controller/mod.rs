@@ -1,3 +1,4 @@
+use futures::future::Shared;
 use gpui::*;
 use gpui_component::ActiveTheme;
 use std::path::{Path, PathBuf};
@@ -9,8 +10,8 @@ use tracing::{debug, error, info, warn};
 use crate::MemoryInitSignal;
 use crate::chatty::models::token_usage::TokenUsage;
 use crate::chatty::models::{
-    Conversation, ConversationsStore, GlobalStreamManager, MessageFeedback, StreamManagerEvent,
-    StreamStatus,
+    Conversation, ConversationsStore, GlobalStreamManager, JobQueue, MessageFeedback,
+    StreamManagerEvent, StreamStatus,
 };
 use crate::chatty::services::StreamChunk;
 use crate::chatty::services::{generate_title, stream_prompt};
@@ -20,34 +21,61 @@ use crate::chatty::token_budget::{
 };
 use crate::chatty::views::chat_input::{ChatInputEvent, ChatInputState, ModelOption, SkillEntry};
 use crate::chatty::views::chat_view::ChatViewEvent;
+use crate::chatty::views::message_component::AnnotationChange;
 use crate::chatty::views::message_types::{
-    ApprovalBlock, ApprovalState, SystemTrace, ThinkingState, ToolCallBlock, ToolCallState,
-    ToolSource, TraceItem, friendly_tool_name, is_denial_result,
+    ApprovalBlock, ApprovalState, SystemTrace, ToolCallBlock, ToolCallState, ToolSource, TraceItem,
+    friendly_tool_name, is_denial_result,
 };
 use crate::chatty::views::sidebar_view::SidebarEvent;
-use crate::chatty::views::{ChatView, SidebarView};
+use crate::chatty::views::{ChatView, OnboardingState, SidebarView};
+use crate::settings::models::GeneralSettingsModel;
 use crate::settings::models::TokenTrackingSettings;
 use crate::settings::models::execution_settings::ExecutionSettingsModel;
-use crate::settings::models::models_store::{ModelConfig, ModelsModel};
-use crate::settings::models::providers_store::ProviderModel;
+use crate::settings::models::models_store::{ModelConfig, ModelRuleContext, ModelsModel};
+use crate::settings::models::providers_store::{ProviderConfig, ProviderModel, ProviderType};
 use crate::settings::models::training_settings::TrainingSettingsModel;
 use crate::settings::models::{AgentConfigEvent, AgentConfigNotifier, GlobalAgentConfigNotifier};
 use crate::settings::models::{DiscoveredModulesModel, ModuleLoadStatus};
+use crate::settings::models::{SessionState, WindowGeometry};
 use chatty_core::exporters::atif_exporter::conversation_to_atif;
+use chatty_core::exporters::document_exporter::{
+    DocumentFormat, render_document, suggested_file_name,
+};
+use chatty_core::exporters::html_exporter::{HtmlExportOptions, write_print_file};
 use chatty_core::exporters::jsonl_exporter::{
     SftExportOptions, append_jsonl_with_dedup, conversation_to_dpo_jsonl, conversation_to_sft_jsonl,
 };
+use chatty_core::exporters::pii::scrub_export;
 use chatty_core::factories::AgentClient;
 use chatty_core::factories::agent_factory::AgentBuildContext;
+use chatty_core::models::{CompactionTrigger, TurnOverrides};
 use chatty_core::repositories::{ConversationData, ConversationRepository};
+use chatty_core::services::model_router::{
+    RoutingDecision, choose_model, classify_heuristic, classify_with_ollama,
+};
+use chatty_core::services::startup_profile;
+use chatty_core::services::telemetry_service::{self, features};
+use chatty_core::services::workspace_rules::RulesFingerprint;
 use chatty_core::tools::LocalModuleAgentSummary;
 
 mod conversation_ops;
 mod conversation_ops_modify;
 mod export_ops;
+mod import_ops;
+mod job_ops;
+mod merge_ops;
 mod message_ops;
 mod message_ops_internals;
+mod polish_ops;
+mod prewarm_ops;
+mod quality_ops;
+mod reflection_ops;
 mod slash_commands;
+mod speech_ops;
+mod topic_split_ops;
+
+/// How often the active workspace's rules file is checked for changes.
+const WORKSPACE_RULES_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
 
 /// Collect WASM module agents from the global `DiscoveredModulesModel` and convert them to
 /// `LocalModuleAgentSummary` values suitable for the `list_agents` tool.
@@ -187,6 +215,21 @@ fn normalize_workspace_string(path: &str) -> String {
         .to_string()
 }
 
+/// Whether the conversation's agent was built for another workspace than
+/// the one it uses now (its own working directory, else the global one).
+fn agent_needs_workspace_refresh(conv_id: &str, cx: &App) -> bool {
+    let settings = cx.global::<ExecutionSettingsModel>();
+    cx.global::<ConversationsStore>()
+        .get_conversation(conv_id)
+        .is_some_and(|conv| {
+            let effective_workspace_dir = conv
+                .working_dir()
+                .cloned()
+                .or_else(|| settings.workspace_dir.as_ref().map(PathBuf::from));
+            conv.agent_workspace_dir().cloned() != effective_workspace_dir
+        })
+}
+
 async fn rebuild_conversation_agent(conv_id: &str, cx: &gpui::AsyncApp) -> anyhow::Result<()> {
     let conv_id = conv_id.to_string();
 
@@ -209,17 +252,77 @@ async fn rebuild_conversation_agent(conv_id: &str, cx: &gpui::AsyncApp) -> anyho
         .map_err(|e| anyhow::anyhow!(e.to_string()))?
         .ok_or_else(|| anyhow::anyhow!("Missing model/provider config for conversation rebuild"))?;
 
+    let (new_agent, new_shell_session, new_progress_slot, built_workspace_dir) =
+        build_conversation_agent(&conv_id, &model_config, &provider_config, cx).await?;
+
+    cx.update_global::<ConversationsStore, _>(|store, _cx| {
+        if let Some(conv) = store.get_conversation_mut(&conv_id) {
+            conv.set_agent(
+                new_agent,
+                model_config.id.clone(),
+                built_workspace_dir.clone(),
+            );
+            if new_shell_session.is_some() {
+                conv.set_shell_session(new_shell_session);
+            }
+            conv.set_invoke_agent_progress_slot(new_progress_slot);
+            info!(conv_id = %conv_id, "Agent successfully rebuilt with updated tool set");
+        } else {
+            warn!(
+                conv_id = %conv_id,
+                "Conversation not found during agent rebuild — skipping"
+            );
+        }
+    })
+    .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Build an agent for `model_config` with the tools, settings and shared
+/// state of conversation `conv_id`, without storing it on the conversation.
+///
+/// Returns the agent, its shell session, its sub-agent progress slot and the
+/// workspace directory it was built for.
+async fn build_conversation_agent(
+    conv_id: &str,
+    model_config: &ModelConfig,
+    provider_config: &ProviderConfig,
+    cx: &gpui::AsyncApp,
+) -> anyhow::Result<(
+    AgentClient,
+    Option<Arc<chatty_core::services::shell_service::ShellSession>>,
+    chatty_core::tools::invoke_agent_tool::InvokeAgentProgressSlot,
+    Option<PathBuf>,
+)> {
+    let conv_id = conv_id.to_string();
+
     let mcp_service = cx
         .update(|cx| cx.global::<crate::chatty::services::McpService>().clone())
         .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
-    let mcp_tools = chatty_core::services::gather_mcp_tools(&mcp_service).await;
+    let (disabled_mcp_servers, disabled_tools) = cx
+        .update(|cx| {
+            cx.global::<ConversationsStore>()
+                .get_conversation(&conv_id)
+                .map(|c| (c.disabled_mcp_servers().clone(), c.disabled_tools().clone()))
+        })
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?
+        .unwrap_or_default();
+    let mcp_tools = chatty_core::services::filter_mcp_tools(
+        chatty_core::services::gather_mcp_tools(&mcp_service).await,
+        &disabled_mcp_servers,
+    );
 
     let (
         exec_settings,
         pending_approvals,
         pending_write_approvals,
         pending_artifacts,
+        conversation_variables,
+        execution_plan,
+        confidential,
+        docker_resources,
         shell_session,
         user_secrets,
         theme_colors,
@@ -249,6 +352,10 @@ async fn rebuild_conversation_agent(conv_id: &str, cx: &gpui::AsyncApp) -> anyho
                 .as_ref()
                 .map(|dir| normalize_workspace_path(Path::new(dir)));
             let artifacts = conv.map(|c| c.pending_artifacts());
+            let variables = conv.map(|c| c.variables());
+            let plan = conv.map(|c| c.execution_plan());
+            let confidential = conv.map(|c| c.confidential_flag());
+            let docker_resources = conv.map(|c| c.docker_resources());
             let isolation_changed = conv
                 .and_then(|c| c.shell_session())
                 .map(|s| s.network_isolation() != settings.network_isolation)
@@ -286,6 +393,10 @@ async fn rebuild_conversation_agent(conv_id: &str, cx: &gpui::AsyncApp) -> anyho
                 Some(approvals),
                 Some(write_approvals),
                 artifacts,
+                variables,
+                plan,
+                confidential,
+                docker_resources,
                 session,
                 secrets,
                 Some(colors),
@@ -322,54 +433,90 @@ async fn rebuild_conversation_agent(conv_id: &str, cx: &gpui::AsyncApp) -> anyho
         })
         .unwrap_or_default();
 
-    let (new_agent, new_shell_session, new_progress_slot) =
-        AgentClient::from_model_config_with_tools(
-            &model_config,
-            &provider_config,
-            AgentBuildContext {
-                mcp_tools,
-                exec_settings,
-                pending_approvals,
-                pending_write_approvals,
-                pending_artifacts,
-                shell_session,
-                user_secrets,
-                theme_colors,
-                memory_service,
-                skill_service: Some(skill_service),
-                search_settings,
-                embedding_service,
-                allow_sub_agent: true, // interactive agent: sub-agent tool is allowed
-                module_agents,
-                gateway_port,
-                remote_agents,
-                available_model_ids,
-            },
-        )
-        .await?;
+    let (agent, shell_session, progress_slot) = AgentClient::from_model_config_with_tools(
+        model_config,
+        provider_config,
+        AgentBuildContext {
+            mcp_tools,
+            exec_settings,
+            pending_approvals,
+            pending_write_approvals,
+            pending_artifacts,
+            shell_session,
+            user_secrets,
+            theme_colors,
+            memory_service,
+            skill_service: Some(skill_service),
+            search_settings,
+            embedding_service,
+            allow_sub_agent: true, // interactive agent: sub-agent tool is allowed
+            module_agents,
+            gateway_port,
+            remote_agents,
+            available_model_ids,
+            conversation_variables,
+            execution_plan,
+            confidential,
+            docker_resources,
+            disabled_tools,
+        },
+    )
+    .await?;
+
+    Ok((agent, shell_session, progress_slot, built_workspace_dir))
+}
 
-    cx.update_global::<ConversationsStore, _>(|store, _cx| {
-        if let Some(conv) = store.get_conversation_mut(&conv_id) {
-            conv.set_agent(
-                new_agent,
-                model_config.id.clone(),
-                built_workspace_dir.clone(),
-            );
-            if new_shell_session.is_some() {
-                conv.set_shell_session(new_shell_session);
-            }
-            conv.set_invoke_agent_progress_slot(new_progress_slot);
-            info!(conv_id = %conv_id, "Agent successfully rebuilt with updated tool set");
-        } else {
-            warn!(
-                conv_id = %conv_id,
-                "Conversation not found during agent rebuild — skipping"
-            );
-        }
-    })
-    .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+/// Build a one-off agent for a turn sent with `overrides`: the override model
+/// (or the conversation's) with the temperature and reasoning effort applied.
+/// The conversation keeps its own agent and model.
+async fn build_turn_agent(
+    conv_id: &str,
+    overrides: &TurnOverrides,
+    cx: &gpui::AsyncApp,
+) -> anyhow::Result<(
+    AgentClient,
+    ModelConfig,
+    chatty_core::tools::invoke_agent_tool::InvokeAgentProgressSlot,
+)> {
+    let (model_config, provider_config) = cx
+        .update(|cx| {
+            let model_id = match &overrides.model_id {
+                Some(id) => id.clone(),
+                None => cx
+                    .global::<ConversationsStore>()
+                    .get_conversation(conv_id)?
+                    .model_id()
+                    .to_string(),
+            };
+            let model_config = cx.global::<ModelsModel>().get_model(&model_id)?.clone();
+            let provider_config = cx
+                .global::<ProviderModel>()
+                .providers()
+                .iter()
+                .find(|p| p.provider_type == model_config.provider_type)
+                .cloned()?;
+            Some((overrides.apply(&model_config), provider_config))
+        })
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?
+        .ok_or_else(|| anyhow::anyhow!("Missing model/provider config for turn overrides"))?;
 
-    Ok(())
+    let (agent, _, progress_slot, _) =
+        build_conversation_agent(conv_id, &model_config, &provider_config, cx).await?;
+    Ok((agent, model_config, progress_slot))
+}
+
+fn window_geometry(window: &Window) -> WindowGeometry {
+    let (bounds, maximized) = match window.window_bounds() {
+        WindowBounds::Windowed(bounds) => (bounds, false),
+        WindowBounds::Maximized(bounds) | WindowBounds::Fullscreen(bounds) => (bounds, true),
+    };
+    WindowGeometry {
+        x: bounds.origin.x.as_f32(),
+        y: bounds.origin.y.as_f32(),
+        width: bounds.size.width.as_f32(),
+        height: bounds.size.height.as_f32(),
+        maximized,
+    }
 }
 
 /// Global state to hold the main ChattyApp entity
@@ -383,6 +530,12 @@ pub struct ChattyApp {
     /// Held while a conversation is being created; prevents concurrent creations.
     /// Automatically dropped (and thus "cleared") when the task completes.
     active_create_task: Option<Task<anyhow::Result<String>>>,
+    /// Conversation being prepared while the user types its first message;
+    /// resolves to its ID, or None when creating it failed. A send waits for
+    /// it instead of creating another conversation.
+    prewarm_task: Option<Shared<Task<Option<String>>>>,
+    /// Last conversation that was pre-warmed, so typing again doesn't redo it
+    prewarmed_id: Option<String>,
     /// Keeps the AgentConfigNotifier entity alive for the app's lifetime so that
     /// GlobalAgentConfigNotifier's WeakEntity remains upgradeable.
     _mcp_notifier: Entity<AgentConfigNotifier>,
@@ -390,6 +543,23 @@ pub struct ChattyApp {
     /// chat UI (no ToolCallBlock) and instead visualised via the sub-agent progress
     /// system, identical to the `/agent` slash command.
     active_invoke_agent_ids: std::collections::HashSet<String>,
+    /// Hands-free voice conversation, while speech mode is on.
+    speech: Option<speech_ops::SpeechSession>,
+    /// Routing decision for the message about to be sent, attached to its trace.
+    pending_routing: Option<RoutingDecision>,
+    /// Parameters chosen for the message about to be sent only.
+    pending_overrides: Option<TurnOverrides>,
+    /// Workspace and rules file last seen by the workspace rules watcher.
+    workspace_rules_seen: Option<(PathBuf, Option<RulesFingerprint>)>,
+    /// Current main window placement, kept up to date for the session saved on quit.
+    window_geometry: Option<WindowGeometry>,
+    /// Session being restored; consumed once its conversation is displayed.
+    pending_session: Option<SessionState>,
+    /// Search result (conversation, history index) to jump to; consumed
+    /// once that conversation is displayed.
+    pending_reveal: Option<(String, usize)>,
+    /// Wakes the job runner when the next background job retry is due.
+    job_wakeup: Option<Task<()>>,
 }
 
 impl ChattyApp {
@@ -397,6 +567,7 @@ impl ChattyApp {
         window: &mut Window,
         cx: &mut Context<Self>,
         conversation_repo: Arc<dyn ConversationRepository>,
+        restored_session: Option<SessionState>,
     ) -> Self {
         // Initialize global conversations model if not already done
         if !cx.has_global::<ConversationsStore>() {
@@ -405,7 +576,14 @@ impl ChattyApp {
 
         // Create views
         let chat_view = cx.new(|cx| ChatView::new(window, cx));
-        let sidebar_view = cx.new(|_cx| SidebarView::new());
+        let sidebar_collapsed = restored_session
+            .as_ref()
+            .is_some_and(|session| session.sidebar_collapsed);
+        let sidebar_view = cx.new(|cx| {
+            let mut sidebar = SidebarView::new();
+            sidebar.set_collapsed(sidebar_collapsed, cx);
+            sidebar
+        });
 
         // Create the agent config notifier and keep the strong entity alive in ChattyApp
         // so GlobalAgentConfigNotifier's WeakEntity remains upgradeable for the app's lifetime.
@@ -418,10 +596,25 @@ impl ChattyApp {
             conversation_repo,
             is_ready: false,
             active_create_task: None,
+            prewarm_task: None,
+            prewarmed_id: None,
             _mcp_notifier: mcp_notifier,
             active_invoke_agent_ids: std::collections::HashSet::new(),
+            speech: None,
+            pending_routing: None,
+            pending_overrides: None,
+            workspace_rules_seen: None,
+            window_geometry: Some(window_geometry(window)),
+            pending_session: restored_session,
+            pending_reveal: None,
+            job_wakeup: None,
         };
 
+        cx.observe_window_bounds(window, |app, window, _cx| {
+            app.window_geometry = Some(window_geometry(window));
+        })
+        .detach();
+
         // Store entity in global state for later access
         let app_weak = cx.entity().downgrade();
         if !cx.has_global::<GlobalChattyApp>() {
@@ -438,6 +631,8 @@ impl ChattyApp {
         // Initialize chat input with available models
         app.initialize_models(cx);
 
+        app.watch_workspace_rules(cx);
+
         // is_ready is set by load_conversations_after_models_ready() once disk load completes.
         // Do NOT create an initial conversation here — ConversationsStore is always empty at
         // this point because disk loading hasn't happened yet. Creating one here causes a race
@@ -446,6 +641,22 @@ impl ChattyApp {
         app
     }
 
+    /// Window layout, sidebar and chat position to restore on the next launch.
+    pub fn session_state(&self, cx: &App) -> SessionState {
+        let active_conversation_id = cx
+            .try_global::<ConversationsStore>()
+            .and_then(|store| store.active_id().cloned());
+        let scroll_offset = active_conversation_id
+            .as_ref()
+            .map(|_| self.chat_view.read(cx).scroll_offset());
+        SessionState {
+            window: self.window_geometry.clone(),
+            sidebar_collapsed: self.sidebar_view.read(cx).is_collapsed(),
+            active_conversation_id,
+            scroll_offset,
+        }
+    }
+
     /// Load conversations after models and providers are ready
     /// This should be called from main.rs after both models and providers have been loaded
     pub fn load_conversations_after_models_ready(&self, cx: &mut Context<Self>) {
@@ -460,6 +671,8 @@ impl ChattyApp {
     /// 2. ChatInputState emits ChatInputEvent → ChattyApp handles
     /// 3. AgentConfigNotifier emits AgentConfigEvent → ChattyApp handles
     /// 4. StreamManager emits StreamManagerEvent → ChattyApp handles
+    /// 5. ChatView emits ChatViewEvent → ChattyApp handles
+    /// 6. JobQueue global changes → ChattyApp saves it and runs due jobs
     fn setup_callbacks(&self, cx: &mut Context<Self>) {
         // SUBSCRIPTION 1: SidebarView events
         cx.subscribe(
@@ -480,13 +693,51 @@ impl ChattyApp {
                 SidebarEvent::DeleteConversation(conv_id) => {
                     app.delete_conversation(conv_id, cx);
                 }
-                SidebarEvent::ExportConversation(conv_id) => {
-                    app.export_conversation_markdown(conv_id, cx);
+                SidebarEvent::ExportConversation(conv_id, format) => {
+                    app.export_conversation_document(conv_id, *format, cx);
+                }
+                SidebarEvent::PrintConversation(conv_id) => {
+                    app.print_conversation(conv_id, cx);
+                }
+                SidebarEvent::ImportConversations => {
+                    app.import_conversations(cx);
+                }
+                SidebarEvent::SetLabel(conv_id, label) => {
+                    app.set_conversation_label(conv_id, label.clone(), cx);
+                }
+                SidebarEvent::SetLocked(conv_id, locked) => {
+                    app.set_conversation_locked(conv_id, *locked, cx);
+                }
+                SidebarEvent::SetTags(conv_id, tags) => {
+                    app.organize_conversations(
+                        |store| {
+                            store.set_tags(conv_id, tags.clone());
+                            vec![conv_id.clone()]
+                        },
+                        cx,
+                    );
+                }
+                SidebarEvent::AddTag(ids, tag) => {
+                    app.organize_conversations(|store| store.add_tag(ids, tag), cx);
+                }
+                SidebarEvent::RemoveTag(ids, tag) => {
+                    app.organize_conversations(|store| store.remove_tag(ids, tag), cx);
+                }
+                SidebarEvent::MoveToFolder(ids, folder) => {
+                    app.organize_conversations(
+                        |store| store.set_folder(ids, folder.as_deref()),
+                        cx,
+                    );
                 }
                 SidebarEvent::ToggleCollapsed(collapsed) => {
                     // Optional: Could save collapsed state to settings here
                     debug!(collapsed = collapsed, "Sidebar toggled");
                 }
+                SidebarEvent::SetSort(sort) => {
+                    use crate::settings::controllers::general_settings_controller;
+                    general_settings_controller::set_conversation_sort(cx, *sort);
+                    app.refresh_sidebar(cx);
+                }
                 SidebarEvent::LoadMore => {
                     let sidebar = app.sidebar_view.clone();
                     sidebar.update(cx, |sidebar, cx| {
@@ -511,16 +762,68 @@ impl ChattyApp {
         let chat_input_state = self.chat_view.read(cx).chat_input_state().clone();
         cx.subscribe(
             &chat_input_state,
-            |app, _input, event: &ChatInputEvent, cx| match event {
+            |app, input, event: &ChatInputEvent, cx| match event {
                 ChatInputEvent::Send {
                     message,
                     attachments,
+                    overrides,
                 } => {
                     debug!(message = %message, attachment_count = attachments.len(), "ChatInputEvent::Send received");
+                    // A pending ask_user question consumes the input as its answer.
+                    let message_text = message.clone();
+                    if app.chat_view.update(cx, |view, cx| {
+                        view.answer_pending_question(Some(message_text), cx)
+                    }) {
+                        return;
+                    }
+                    // While a reply streams, hold the message until the turn ends.
+                    if input.read(cx).is_streaming() {
+                        debug!("Reply still streaming, queueing message");
+                        input.update(cx, |input, cx| {
+                            input.queue_message(
+                                message.clone(),
+                                attachments.clone(),
+                                overrides.clone(),
+                                cx,
+                            );
+                        });
+                        return;
+                    }
                     // Intercept arg-based slash commands before sending to LLM.
                     if app.try_handle_arg_slash_command(message.trim(), cx) {
                         return;
                     }
+                    app.pending_overrides = (!overrides.is_empty()).then(|| overrides.clone());
+                    // A default-model rule may switch the model before the first message.
+                    if let Some(switch) = app.apply_default_model_rules(message, attachments, cx) {
+                        let message = message.clone();
+                        let attachments = attachments.clone();
+                        cx.spawn(async move |app, cx| {
+                            if let Err(e) = switch.await {
+                                warn!(error = ?e, "Failed to switch to rule-selected model");
+                            }
+                            app.update(cx, |app, cx| app.send_message(message, attachments, cx))
+                                .ok();
+                        })
+                        .detach();
+                        return;
+                    }
+                    // Model routing may pick a cheaper model for this message,
+                    // unless a model was chosen for it explicitly.
+                    let model_overridden = overrides.model_id.is_some();
+                    if !model_overridden
+                        && let Some(route) = app.route_message(message, attachments, cx)
+                    {
+                        let message = message.clone();
+                        let attachments = attachments.clone();
+                        cx.spawn(async move |app, cx| {
+                            route.await;
+                            app.update(cx, |app, cx| app.send_message(message, attachments, cx))
+                                .ok();
+                        })
+                        .detach();
+                        return;
+                    }
                     app.send_message(message.clone(), attachments.clone(), cx);
                 }
                 ChatInputEvent::ModelChanged(model_id) => {
@@ -555,6 +858,33 @@ impl ChattyApp {
                     debug!(dir = ?dir, "ChatInputEvent::WorkingDirChanged received");
                     app.change_conversation_working_dir(dir.clone(), cx);
                 }
+                ChatInputEvent::SpeechModeToggled => {
+                    debug!("ChatInputEvent::SpeechModeToggled received");
+                    // Deferred: this runs while ChatInputState's event is dispatched
+                    let app_entity = cx.entity();
+                    cx.defer(move |cx| {
+                        app_entity.update(cx, |app, cx| app.toggle_speech_mode(cx));
+                    });
+                }
+                ChatInputEvent::HistoryPolicyChanged(policy) => {
+                    debug!(policy = ?policy, "ChatInputEvent::HistoryPolicyChanged received");
+                    app.change_conversation_history_policy(*policy, cx);
+                }
+                ChatInputEvent::SnippetInserted(name) => {
+                    debug!(name = %name, "ChatInputEvent::SnippetInserted received");
+                    crate::settings::controllers::snippets_controller::record_use(name, cx);
+                }
+                ChatInputEvent::PolishRequested { draft, model_id } => {
+                    debug!(model_id = %model_id, "ChatInputEvent::PolishRequested received");
+                    app.polish_draft(draft.clone(), model_id.clone(), cx);
+                }
+                ChatInputEvent::DraftStarted => {
+                    // Deferred: this runs while ChatInputState's event is dispatched
+                    let app_entity = cx.entity();
+                    cx.defer(move |cx| {
+                        app_entity.update(cx, |app, cx| app.prewarm_conversation(cx));
+                    });
+                }
             },
         )
         .detach();
@@ -583,7 +913,7 @@ impl ChattyApp {
             .detach();
         }
 
-        // SUBSCRIPTION 5: ChatView events — feedback persistence
+        // SUBSCRIPTION 5: ChatView events — feedback/annotation persistence, regeneration, pinning, history deletion, variables, plan, topic splits
         cx.subscribe(
             &self.chat_view,
             |app, _chat_view, event: &ChatViewEvent, cx| match event {
@@ -593,12 +923,64 @@ impl ChattyApp {
                 } => {
                     app.handle_feedback_changed(*history_index, feedback.clone(), cx);
                 }
+                ChatViewEvent::AnnotationChanged {
+                    history_index,
+                    change,
+                } => {
+                    app.handle_annotation_changed(*history_index, change.clone(), cx);
+                }
                 ChatViewEvent::RegenerateMessage { history_index } => {
                     app.handle_regeneration(*history_index, cx);
                 }
+                ChatViewEvent::PinMessage { history_index } => {
+                    app.handle_pin_message(*history_index, cx);
+                }
+                ChatViewEvent::DeleteMessage { history_index } => {
+                    app.handle_history_deletion(*history_index, false, cx);
+                }
+                ChatViewEvent::DeleteTrace { history_index } => {
+                    app.handle_history_deletion(*history_index, true, cx);
+                }
+                ChatViewEvent::UnpinItem { id } => {
+                    app.handle_unpin_item(id, cx);
+                }
+                ChatViewEvent::DeleteVariable { name } => {
+                    app.handle_delete_variable(name, cx);
+                }
+                ChatViewEvent::ClearExecutionPlan => {
+                    app.handle_clear_execution_plan(cx);
+                }
+                ChatViewEvent::SplitConversation => {
+                    app.handle_split_conversation(cx);
+                }
+                ChatViewEvent::RetryFailedTurn { history_index } => {
+                    app.handle_retry_failed_turn(*history_index, cx);
+                }
+                ChatViewEvent::EditUserMessage {
+                    history_index,
+                    text,
+                } => {
+                    app.handle_edit_user_message(*history_index, text.clone(), cx);
+                }
+                ChatViewEvent::UnlockConversation => {
+                    if let Some(conv_id) = cx
+                        .try_global::<ConversationsStore>()
+                        .and_then(|store| store.active_id().cloned())
+                    {
+                        app.set_conversation_locked(&conv_id, false, cx);
+                    }
+                }
             },
         )
         .detach();
+
+        // SUBSCRIPTION 6: JobQueue changes — new jobs, results and manual retries
+        // from the jobs panel are saved and due jobs started
+        cx.observe_global::<JobQueue>(|app, cx| {
+            app.persist_job_queue(cx);
+            app.run_due_jobs(cx);
+        })
+        .detach();
     }
 
     /// Initialize chat input with available models
@@ -610,7 +992,10 @@ impl ChattyApp {
             let models_list: Vec<ModelOption> = models_model
                 .models()
                 .iter()
-                .map(|m| ModelOption::new(m.id.clone(), m.name.clone(), m.provider_type.clone()))
+                .map(|m| {
+                    ModelOption::new(m.id.clone(), m.name.clone(), m.provider_type.clone())
+                        .with_aliases(m.aliases.clone())
+                })
                 .collect();
 
             let default_model_id = models_list.first().map(|model| model.id.clone());
@@ -669,6 +1054,81 @@ impl ChattyApp {
         });
     }
 
+    /// Poll the active workspace's rules file. The chat input badge follows the
+    /// file, and the active agent is rebuilt when the file changes so the next
+    /// turn sees the new rules.
+    fn watch_workspace_rules(&self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            loop {
+                let workspace_dir = this
+                    .update(cx, |app, cx| {
+                        app.chat_input_state(cx)
+                            .read(cx)
+                            .working_dir()
+                            .cloned()
+                            .or_else(|| {
+                                cx.try_global::<ExecutionSettingsModel>()
+                                    .and_then(|s| s.workspace_dir.clone())
+                                    .map(PathBuf::from)
+                            })
+                    })
+                    .ok();
+                let Some(workspace_dir) = workspace_dir else {
+                    // Entity dropped — stop the loop.
+                    break;
+                };
+
+                if let Some(dir) = workspace_dir {
+                    let fingerprint = cx
+                        .background_executor()
+                        .spawn({
+                            let dir = dir.clone();
+                            async move { RulesFingerprint::of(&dir) }
+                        })
+                        .await;
+                    this.update(cx, |app, cx| {
+                        app.on_workspace_rules_polled(dir, fingerprint, cx)
+                    })
+                    .ok();
+                } else {
+                    this.update(cx, |app, cx| {
+                        app.workspace_rules_seen = None;
+                        app.chat_input_state(cx)
+                            .update(cx, |state, cx| state.set_workspace_rules(None, cx));
+                    })
+                    .ok();
+                }
+
+                cx.background_executor()
+                    .timer(WORKSPACE_RULES_POLL_INTERVAL)
+                    .await;
+            }
+        })
+        .detach();
+    }
+
+    fn on_workspace_rules_polled(
+        &mut self,
+        workspace_dir: PathBuf,
+        fingerprint: Option<RulesFingerprint>,
+        cx: &mut Context<Self>,
+    ) {
+        let changed = match &self.workspace_rules_seen {
+            // A new workspace: its agent is built with the current rules already
+            Some((seen_dir, seen)) if *seen_dir == workspace_dir => *seen != fingerprint,
+            _ => false,
+        };
+        let rules_path = fingerprint.as_ref().map(|f| f.path.clone());
+        self.workspace_rules_seen = Some((workspace_dir, fingerprint));
+        self.chat_input_state(cx)
+            .update(cx, |state, cx| state.set_workspace_rules(rules_path, cx));
+
+        if changed {
+            info!("Workspace rules changed, rebuilding active agent");
+            self.rebuild_active_agent(cx);
+        }
+    }
+
     /// Refresh the sidebar with the latest conversation list from the metadata store
     fn refresh_sidebar(&self, cx: &mut Context<Self>) {
         self.sidebar_view.update(cx, |sidebar, cx| {
@@ -680,15 +1140,17 @@ impl ChattyApp {
         });
     }
 
+    /// Repository the conversations are stored in, e.g. for searching them.
+    pub fn conversation_repo(&self) -> Arc<dyn ConversationRepository> {
+        self.conversation_repo.clone()
+    }
+
     /// Get the chat input state entity
-    #[allow(dead_code)]
     pub fn chat_input_state(&self, cx: &App) -> Entity<ChatInputState> {
         self.chat_view.read(cx).chat_input_state().clone()
     }
 }
 
-/// Serialize a `Conversation` into a `ConversationData` snapshot suitable for persistence
-/// or export. Returns `None` if history or traces cannot be serialized.
 /// Extract the current theme's chart colors as hex strings.
 ///
 /// These are captured at agent-creation time so that charts saved to disk by the
@@ -712,59 +1174,37 @@ fn extract_theme_chart_colors(cx: &gpui::App) -> [String; 5] {
     })
 }
 
+/// Serialize a `Conversation` into a `ConversationData` snapshot suitable for persistence
+/// or export. Returns `None` if history or traces cannot be serialized.
 ///
 /// Sets `updated_at` to the current time; all other timestamps are taken from the
 /// conversation itself.
 fn build_conversation_data(conv: &Conversation) -> Option<ConversationData> {
-    let history = match conv.serialize_history() {
-        Ok(h) => h,
-        Err(e) => {
-            error!(conv_id = %conv.id(), error = ?e, "Failed to serialize history in build_conversation_data");
-            return None;
-        }
-    };
-    let traces = match conv.serialize_traces() {
-        Ok(t) => t,
-        Err(e) => {
-            error!(conv_id = %conv.id(), error = ?e, "Failed to serialize traces in build_conversation_data");
-            return None;
-        }
-    };
     let now = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs() as i64;
+    conv.to_data(now)
+        .map_err(|e| {
+            error!(conv_id = %conv.id(), error = ?e, "Failed to serialize conversation in build_conversation_data");
+        })
+        .ok()
+}
 
-    Some(ConversationData {
-        id: conv.id().to_string(),
-        title: conv.title().to_string(),
-        model_id: conv.model_id().to_string(),
-        message_history: history,
-        system_traces: traces,
-        token_usage: conv
-            .serialize_token_usage()
-            .unwrap_or_else(|_| "{}".to_string()),
-        attachment_paths: conv
-            .serialize_attachment_paths()
-            .unwrap_or_else(|_| "[]".to_string()),
-        message_timestamps: conv
-            .serialize_message_timestamps()
-            .unwrap_or_else(|_| "[]".to_string()),
-        message_feedback: conv
-            .serialize_message_feedback()
-            .unwrap_or_else(|_| "[]".to_string()),
-        regeneration_records: conv
-            .serialize_regeneration_records()
-            .unwrap_or_else(|_| "[]".to_string()),
-        created_at: conv
-            .created_at()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs() as i64,
-        updated_at: now,
-        working_dir: conv.working_dir().map(|p| p.to_string_lossy().to_string()),
-        agent_task_snapshot: conv.serialize_agent_task_snapshot().unwrap_or(None),
-    })
+/// Privacy policy of the provider serving `conv_id`'s model, for the
+/// request inspector in the response trace.
+fn request_privacy_summary(conv_id: &str, cx: &App) -> Option<String> {
+    let model_id = cx
+        .global::<ConversationsStore>()
+        .get_conversation(conv_id)?
+        .model_id()
+        .to_string();
+    let model = cx.global::<ModelsModel>().get_model(&model_id)?;
+    cx.global::<ProviderModel>()
+        .providers()
+        .iter()
+        .find(|p| p.provider_type == model.provider_type)?
+        .privacy_summary()
 }
 
 // ── Tool source classification ───────────────────────────────────────────────
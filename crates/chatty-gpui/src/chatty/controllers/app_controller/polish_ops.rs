@@ -0,0 +1,69 @@
+//! "Polish my prompt" for `ChattyApp`.
+//!
+//! # What lives here
+//!
+//! - `polish_draft` — rewrite the chat input's draft with the model selected
+//!   in the input and put the result back for review.
+//!
+//! The instruction and reply cleanup live in
+//! `chatty_core::services::prompt_polisher`.
+
+use chatty_core::services::prompt_polisher;
+
+use super::*;
+
+impl ChattyApp {
+    /// Rewrite `draft` for spelling, grammar and clarity with `model_id` and
+    /// replace the input's text with the result. The draft is kept when the
+    /// rewrite fails or the user edited it in the meantime.
+    pub(super) fn polish_draft(&self, draft: String, model_id: String, cx: &mut Context<Self>) {
+        let chat_input_state = self.chat_view.read(cx).chat_input_state().clone();
+        let model = cx.global::<ModelsModel>().get_model(&model_id).cloned();
+        let provider = model.as_ref().and_then(|model| {
+            cx.global::<ProviderModel>()
+                .providers()
+                .iter()
+                .find(|p| p.provider_type == model.provider_type)
+                .cloned()
+        });
+        let (Some(model), Some(provider)) = (model, provider) else {
+            warn!(model_id = %model_id, "No model or provider for prompt polishing");
+            // Deferred: this runs while ChatInputState's event is dispatched
+            cx.defer(move |cx| {
+                chat_input_state.update(cx, |state, cx| state.finish_polish(None, cx));
+            });
+            return;
+        };
+
+        cx.spawn(async move |_weak, cx| {
+            let result = async {
+                let (agent, _, _) = AgentClient::from_model_config_with_tools(
+                    &model,
+                    &provider,
+                    AgentBuildContext::default(),
+                )
+                .await?;
+                prompt_polisher::polish_prompt(&agent, &draft).await
+            }
+            .await;
+            let polished = match result {
+                Ok(polished) => {
+                    info!(model_id = %model.id, "Prompt polished");
+                    Some(polished)
+                }
+                Err(e) => {
+                    warn!(error = ?e, "Prompt polishing failed");
+                    None
+                }
+            };
+            chat_input_state
+                .update(cx, |state, cx| {
+                    let unchanged = state.input.read(cx).text().to_string() == draft;
+                    state.finish_polish(polished.filter(|_| unchanged), cx);
+                })
+                .map_err(|e| warn!(error = ?e, "Failed to apply polished prompt"))
+                .ok();
+        })
+        .detach();
+    }
+}
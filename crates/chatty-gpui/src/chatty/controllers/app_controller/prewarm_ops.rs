@@ -0,0 +1,120 @@
+//! Conversation pre-warming for `ChattyApp`.
+//!
+//! # What lives here
+//!
+//! - `prewarm_conversation` — called when the user starts typing into an
+//!   empty input. With no conversation open it creates one in the
+//!   background, keeping the draft, so MCP tools are fetched and the agent
+//!   is built while the user types. For an open conversation without
+//!   messages it rebuilds the agent if the working directory changed. Either
+//!   way the shell session is started, so the first command doesn't wait for
+//!   the sandbox to spawn.
+//!
+//! `send_message` waits for a pre-warm still in flight instead of creating a
+//! second conversation.
+
+use futures::FutureExt;
+
+use super::*;
+
+impl ChattyApp {
+    /// Prepare the conversation the user is typing into, showing "Preparing
+    /// tools…" in the input while there is slow work to do.
+    pub(super) fn prewarm_conversation(&mut self, cx: &mut Context<Self>) {
+        if !self.is_ready || self.prewarm_task.is_some() || self.active_create_task.is_some() {
+            return;
+        }
+
+        let store = cx.global::<ConversationsStore>();
+        let existing_id = store.active_id().cloned();
+        if let Some(id) = &existing_id {
+            let fresh = store
+                .get_conversation(id)
+                .is_some_and(|conv| conv.message_count() == 0);
+            if !fresh || store.is_locked(id) || self.prewarmed_id.as_ref() == Some(id) {
+                return;
+            }
+        }
+        let refresh_agent = existing_id
+            .as_deref()
+            .is_some_and(|id| agent_needs_workspace_refresh(id, cx));
+
+        let create_task = existing_id
+            .is_none()
+            .then(|| self.create_conversation(true, cx));
+        // Creating sets the new conversation active right away
+        let conv_id = cx.global::<ConversationsStore>().active_id().cloned();
+        debug!(conv_id = ?conv_id, creating = create_task.is_some(), "Pre-warming conversation");
+
+        let chat_input_state = self.chat_view.read(cx).chat_input_state().clone();
+        let show_preparing = create_task.is_some() || refresh_agent;
+        if show_preparing {
+            chat_input_state.update(cx, |state, cx| state.set_preparing_tools(true, cx));
+        }
+
+        let task = cx.spawn(async move |weak, cx| {
+            let created = match create_task {
+                Some(task) => Some(task.await),
+                None => None,
+            };
+            let warmed_id = match created {
+                Some(Ok(id)) => Some(id),
+                Some(Err(e)) => {
+                    warn!(error = ?e, "Failed to pre-warm a new conversation");
+                    None
+                }
+                None => conv_id.clone(),
+            };
+
+            if let Some(id) = &warmed_id {
+                if refresh_agent
+                    && let Err(e) = rebuild_conversation_agent(id, cx).await
+                {
+                    warn!(error = ?e, conv_id = %id, "Failed to refresh agent while pre-warming");
+                }
+                let session = cx
+                    .update(|cx| {
+                        cx.global::<ConversationsStore>()
+                            .get_conversation(id)
+                            .and_then(|conv| conv.shell_session())
+                    })
+                    .ok()
+                    .flatten();
+                if let Some(session) = session
+                    && let Err(e) = session.start().await
+                {
+                    warn!(error = ?e, conv_id = %id, "Failed to start shell session while pre-warming");
+                }
+            }
+
+            if show_preparing {
+                chat_input_state
+                    .update(cx, |state, cx| state.set_preparing_tools(false, cx))
+                    .ok();
+            }
+            if let Some(app) = weak.upgrade() {
+                app.update(cx, |app, cx| {
+                    if warmed_id.is_none()
+                        && let Some(id) = &conv_id
+                    {
+                        // Leave no half-created conversation behind; sending
+                        // creates one the usual way
+                        cx.update_global::<ConversationsStore, _>(|store, _| {
+                            if store.active_id() == Some(id) {
+                                store.clear_active();
+                            }
+                            store.remove_metadata(id);
+                        });
+                        app.refresh_sidebar(cx);
+                    }
+                    app.prewarmed_id = warmed_id.clone();
+                    app.prewarm_task = None;
+                })
+                .map_err(|e| debug!(error = ?e, "Failed to finish pre-warming"))
+                .ok();
+            }
+            warmed_id
+        });
+        self.prewarm_task = Some(task.shared());
+    }
+}
@@ -0,0 +1,109 @@
+//! Conversation quality scoring for `ChattyApp`.
+//!
+//! # What lives here
+//!
+//! - `score_conversation_quality` — run after a completed turn (when enabled
+//!   in the training settings) to have the judge model rate the conversation,
+//!   store the score on it and refresh the JSONL export.
+//!
+//! Prompting and reply parsing live in `chatty_core::services::quality_scorer`.
+
+use chatty_core::models::JobKind;
+use chatty_core::services::quality_scorer;
+
+use super::*;
+
+impl ChattyApp {
+    /// Rate `conv_id` with the configured judge model in the background.
+    /// No-op when scoring is off, no judge is chosen, or the conversation was
+    /// already scored at its current length.
+    pub(super) fn score_conversation_quality(&self, conv_id: &str, cx: &mut Context<Self>) {
+        let Some(settings) = cx.try_global::<TrainingSettingsModel>() else {
+            return;
+        };
+        if !settings.quality_scoring {
+            return;
+        }
+        let Some(model) = settings
+            .judge_model_id
+            .as_ref()
+            .and_then(|id| cx.global::<ModelsModel>().get_model(id))
+            .cloned()
+        else {
+            debug!("Quality scoring enabled without a judge model");
+            return;
+        };
+        let Some(provider) = cx
+            .global::<ProviderModel>()
+            .providers()
+            .iter()
+            .find(|p| p.provider_type == model.provider_type)
+            .cloned()
+        else {
+            warn!(model_id = %model.id, "No provider configured for judge model");
+            return;
+        };
+        let Some((history, already_scored)) = cx
+            .global::<ConversationsStore>()
+            .get_conversation(conv_id)
+            .map(|conv| {
+                let message_count = conv.message_count();
+                let scored = conv
+                    .quality_score()
+                    .is_some_and(|score| score.message_count == message_count);
+                (conv.messages(), scored)
+            })
+        else {
+            return;
+        };
+        if already_scored {
+            return;
+        }
+
+        let conv_id = conv_id.to_string();
+        cx.spawn(async move |weak, cx| {
+            let result = async {
+                let (judge, _, _) = AgentClient::from_model_config_with_tools(
+                    &model,
+                    &provider,
+                    AgentBuildContext::default(),
+                )
+                .await?;
+                quality_scorer::score_conversation(&judge, &model.id, &history).await
+            }
+            .await;
+            let score = match result {
+                Ok(score) => score,
+                Err(e) => {
+                    warn!(error = ?e, conv_id = %conv_id, "Conversation quality scoring failed");
+                    return;
+                }
+            };
+            info!(
+                conv_id = %conv_id,
+                overall = score.overall(),
+                judge = %score.judge_model_id,
+                "Conversation scored"
+            );
+
+            let Some(app) = weak.upgrade() else {
+                return;
+            };
+            app.update(cx, |app, cx| {
+                cx.update_global::<ConversationsStore, _>(|store, _cx| {
+                    if let Some(conv) = store.get_conversation_mut(&conv_id) {
+                        conv.set_quality_score(score);
+                    }
+                });
+                app.persist_conversation(&conv_id, cx);
+                // Re-export so sft.jsonl carries the new `_quality` field
+                if cx.global::<TrainingSettingsModel>().jsonl_auto_export {
+                    app.enqueue_job(JobKind::ExportJsonl, &conv_id, cx);
+                }
+            })
+            .map_err(|e| warn!(error = ?e, "Failed to store quality score"))
+            .ok();
+        })
+        .detach();
+    }
+}
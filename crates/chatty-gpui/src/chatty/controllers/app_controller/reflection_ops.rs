@@ -0,0 +1,143 @@
+//! Self-reflection retries for `ChattyApp`.
+//!
+//! # What lives here
+//!
+//! - `reflect_on_answer` — run after a completed turn (when enabled in the
+//!   execution settings) to have the agent critique its answer against the
+//!   question and the tool output it was based on.
+//! - `retry_with_critique` — replace a low-confidence answer with a second
+//!   attempt whose request carries the critique. The first attempt and the
+//!   critique are kept as a `Reflection` item on the new answer's trace.
+//!
+//! Prompting and parsing live in `chatty_core::services::self_reflection`.
+
+use chatty_core::models::message_types::ReflectionBlock;
+use chatty_core::services::self_reflection::{
+    self, critique_answer, last_answer, last_question, retrieved_context,
+};
+
+use super::*;
+
+impl ChattyApp {
+    /// Critique the newest answer of `conv_id` and retry once if the score is
+    /// below the threshold. Answers that are already a retry are not checked.
+    pub(super) fn reflect_on_answer(&self, conv_id: &str, cx: &mut Context<Self>) {
+        let settings = cx.global::<ExecutionSettingsModel>();
+        if !settings.self_reflection {
+            return;
+        }
+        let threshold = settings.reflection_threshold;
+        let Some((agent, history, trace)) = cx
+            .global::<ConversationsStore>()
+            .get_conversation(conv_id)
+            .map(|conv| {
+                let trace = conv
+                    .entries()
+                    .last()
+                    .and_then(|entry| entry.system_trace.clone())
+                    .and_then(|json| serde_json::from_value::<SystemTrace>(json).ok());
+                (conv.agent().clone(), conv.messages(), trace)
+            })
+        else {
+            return;
+        };
+        let already_retried = trace.as_ref().is_some_and(|trace| {
+            trace
+                .items
+                .iter()
+                .any(|item| matches!(item, TraceItem::Reflection(_)))
+        });
+        if already_retried {
+            return;
+        }
+        let (Some(question), Some(answer)) = (last_question(&history), last_answer(&history))
+        else {
+            return;
+        };
+        let context = trace.as_ref().map(retrieved_context).unwrap_or_default();
+        let message_count = history.len();
+
+        let conv_id = conv_id.to_string();
+        cx.spawn(async move |weak, cx| {
+            let critique = match critique_answer(&agent, &question, &answer, &context).await {
+                Ok(critique) => critique,
+                Err(e) => {
+                    warn!(error = ?e, conv_id = %conv_id, "Self-reflection failed");
+                    return;
+                }
+            };
+            if critique.passes(threshold) {
+                debug!(conv_id = %conv_id, score = critique.score, "Answer passed self-reflection");
+                return;
+            }
+            info!(
+                conv_id = %conv_id,
+                score = critique.score,
+                threshold,
+                "Answer failed self-reflection, retrying"
+            );
+
+            let Some(app) = weak.upgrade() else {
+                return;
+            };
+            app.update(cx, |app, cx| {
+                // Skip stale results: another turn started while critiquing
+                let unchanged = cx
+                    .global::<ConversationsStore>()
+                    .get_conversation(&conv_id)
+                    .is_some_and(|conv| conv.message_count() == message_count);
+                let streaming = cx
+                    .try_global::<GlobalStreamManager>()
+                    .and_then(|g| g.get())
+                    .is_some_and(|manager| manager.read(cx).is_streaming(&conv_id));
+                let visible = app.chat_view.read(cx).conversation_id() == Some(&conv_id);
+                if !unchanged || streaming || !visible {
+                    debug!(conv_id = %conv_id, "Conversation moved on, skipping retry");
+                    return;
+                }
+                let reflection = ReflectionBlock {
+                    first_attempt: answer,
+                    critique: critique.critique,
+                    score: critique.score,
+                    threshold,
+                };
+                app.retry_with_critique(conv_id, reflection, cx);
+            })
+            .map_err(|e| warn!(error = ?e, "Failed to start self-reflection retry"))
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Replace the newest answer of `conv_id` with a second attempt that is
+    /// asked to fix `reflection.critique`.
+    fn retry_with_critique(
+        &mut self,
+        conv_id: String,
+        reflection: ReflectionBlock,
+        cx: &mut Context<Self>,
+    ) {
+        let removed = cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            store
+                .get_conversation_mut(&conv_id)
+                .and_then(|conv| conv.remove_last_assistant_message())
+        });
+        if removed.is_none() {
+            return;
+        }
+
+        let instruction = self_reflection::retry_instruction(&reflection.critique);
+        let request_privacy = request_privacy_summary(&conv_id, cx);
+        self.chat_view.update(cx, |view, cx| {
+            view.remove_last_assistant_message(cx);
+            view.start_assistant_message(cx);
+            if let Some(policy) = request_privacy {
+                view.set_request_privacy(policy, cx);
+            }
+            view.set_reflection(reflection, cx);
+        });
+        self.persist_conversation(&conv_id, cx);
+
+        self.restream_last_turn(conv_id, Some(instruction), cx);
+    }
+}
@@ -1,4 +1,28 @@
 use super::*;
+use chatty_core::exporters::handoff::{AgentHandoff, HandoffFormat};
+use chatty_core::exporters::project::{GitOutcome, ProjectExport};
+use chatty_core::models::{BudgetTracker, PinnedItem, SessionBudget};
+use chatty_core::services::GuardrailRuleKind;
+use chatty_core::services::message_copy::format_for_copy;
+use chatty_core::services::notes_service::NotesService;
+
+/// Largest file `/pin-file` accepts; pinned content is re-sent on every turn.
+const MAX_PINNED_FILE_BYTES: u64 = 64 * 1024;
+
+/// A `/pin <note>` or `/pin-file <path>` command, with its argument trimmed.
+#[derive(Debug, PartialEq)]
+enum PinCommand<'a> {
+    Note(&'a str),
+    File(&'a str),
+}
+
+fn parse_pin_command(text: &str) -> Option<PinCommand<'_>> {
+    if let Some(note) = text.strip_prefix("/pin ") {
+        return Some(PinCommand::Note(note.trim()));
+    }
+    text.strip_prefix("/pin-file ")
+        .map(|path| PinCommand::File(path.trim()))
+}
 
 impl ChattyApp {
     // -----------------------------------------------------------------------
@@ -8,11 +32,17 @@ impl ChattyApp {
     /// Dispatch a slash command that was selected from the picker.
     pub(super) fn handle_slash_command(&mut self, command: String, cx: &mut Context<Self>) {
         debug!(command = %command, "handle_slash_command");
+        telemetry_service::record(features::SLASH_COMMAND);
         match command.as_str() {
             "/clear" | "/new" => {
                 info!("Slash command: start new conversation");
                 self.start_new_conversation(cx);
             }
+            "/artifacts" => {
+                info!("Slash command: toggle artifact gallery");
+                self.chat_view
+                    .update(cx, |view, cx| view.toggle_artifact_gallery(cx));
+            }
             "/compact" => {
                 info!("Slash command: compact conversation");
                 self.compact_conversation(cx);
@@ -21,6 +51,10 @@ impl ChattyApp {
                 info!("Slash command: show context usage");
                 self.show_context_info(cx);
             }
+            "/confidential" => {
+                info!("Slash command: toggle confidential mode");
+                self.toggle_confidential(cx);
+            }
             "/copy" => {
                 info!("Slash command: copy last response");
                 self.copy_last_response(cx);
@@ -29,6 +63,34 @@ impl ChattyApp {
                 info!("Slash command: show working directory");
                 self.show_working_directory(cx);
             }
+            "/voice" => {
+                info!("Slash command: toggle speech mode");
+                self.toggle_speech_mode(cx);
+            }
+            "/lock" => {
+                info!("Slash command: toggle conversation lock");
+                self.toggle_locked(cx);
+            }
+            "/print" => {
+                info!("Slash command: print conversation");
+                match cx
+                    .try_global::<ConversationsStore>()
+                    .and_then(|s| s.active_id().cloned())
+                {
+                    Some(id) => self.print_conversation(&id, cx),
+                    None => self.chat_view.update(cx, |view, cx| {
+                        view.add_info_message("No active conversation to print.".to_string(), cx);
+                    }),
+                }
+            }
+            "/providers" => {
+                info!("Slash command: open provider setup");
+                // The wizard takes the place of the start screen, so it needs an empty chat
+                if !self.chat_view.read(cx).messages().is_empty() {
+                    self.start_new_conversation(cx);
+                }
+                OnboardingState::open(cx);
+            }
             other => {
                 warn!(command = %other, "Unknown slash command received");
             }
@@ -50,6 +112,10 @@ impl ChattyApp {
             }
         };
 
+        if self.refuse_if_locked(&conv_id, cx) {
+            return;
+        }
+
         let data = cx.try_global::<ConversationsStore>().and_then(|store| {
             store
                 .get_conversation(&conv_id)
@@ -75,7 +141,6 @@ impl ChattyApp {
 
         let chat_view = self.chat_view.clone();
         let conv_id_clone = conv_id.clone();
-        let midpoint = history.len() / 2;
         cx.spawn(
             async move |_weak, cx| match summarize_oldest_half(&agent, &history).await {
                 Ok(result) => {
@@ -85,7 +150,7 @@ impl ChattyApp {
                     );
                     cx.update_global::<ConversationsStore, _>(|store, _cx| {
                         if let Some(conv) = store.get_conversation_mut(&conv_id_clone) {
-                            conv.replace_history(result.new_history, midpoint);
+                            conv.compact_history(&result, CompactionTrigger::Manual);
                         }
                     })
                     .map_err(|e| warn!(error = ?e, "Failed to apply compact"))
@@ -169,7 +234,11 @@ impl ChattyApp {
 
         match last_text {
             Some(text) => {
-                cx.write_to_clipboard(ClipboardItem::new_string(text));
+                let format = cx
+                    .try_global::<GeneralSettingsModel>()
+                    .map(|s| s.default_copy_format)
+                    .unwrap_or_default();
+                cx.write_to_clipboard(ClipboardItem::new_string(format_for_copy(&text, format)));
                 self.chat_view.update(cx, |view, cx| {
                     view.add_info_message(
                         "Copied latest assistant response to clipboard.".to_string(),
@@ -188,6 +257,76 @@ impl ChattyApp {
         }
     }
 
+    /// `/confidential` — toggle confidential mode for the active conversation.
+    /// Guardrails block network tools while it is on.
+    fn toggle_confidential(&mut self, cx: &mut Context<Self>) {
+        let Some(conv_id) = cx
+            .try_global::<ConversationsStore>()
+            .and_then(|s| s.active_id().cloned())
+        else {
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message("No active conversation.".to_string(), cx);
+            });
+            return;
+        };
+
+        let confidential = cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            store.get_conversation_mut(&conv_id).map(|conv| {
+                let confidential = !conv.is_confidential();
+                conv.set_confidential(confidential);
+                confidential
+            })
+        });
+        let Some(confidential) = confidential else {
+            return;
+        };
+        self.persist_conversation(&conv_id, cx);
+
+        let network_rule_enabled = cx.try_global::<ExecutionSettingsModel>().is_some_and(|s| {
+            s.guardrail_rules.iter().any(|rule| {
+                rule.enabled && rule.kind == GuardrailRuleKind::BlockNetworkWhenConfidential
+            })
+        });
+        let message = match (confidential, network_rule_enabled) {
+            (true, true) => "**Confidential mode on** — network tools are blocked.",
+            (true, false) => {
+                "**Confidential mode on** — enable the confidential network guardrail in \
+                 Settings → Execution to block network tools."
+            }
+            (false, _) => "**Confidential mode off** — network tools are allowed again.",
+        };
+        self.chat_view.update(cx, |view, cx| {
+            view.add_info_message(message.to_string(), cx);
+        });
+    }
+
+    /// `/lock` — toggle the read-only lock on the active conversation.
+    fn toggle_locked(&mut self, cx: &mut Context<Self>) {
+        let Some(conv_id) = cx
+            .try_global::<ConversationsStore>()
+            .and_then(|s| s.active_id().cloned())
+        else {
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message("No active conversation.".to_string(), cx);
+            });
+            return;
+        };
+
+        let locked = !cx
+            .try_global::<ConversationsStore>()
+            .is_some_and(|store| store.is_locked(&conv_id));
+        self.set_conversation_locked(&conv_id, locked, cx);
+
+        let message = if locked {
+            "**Conversation locked** — messages can no longer be sent, deleted or regenerated."
+        } else {
+            "**Conversation unlocked** — the conversation can be continued again."
+        };
+        self.chat_view.update(cx, |view, cx| {
+            view.add_info_message(message.to_string(), cx);
+        });
+    }
+
     /// `/cwd` — show the current working directory.
     fn show_working_directory(&mut self, cx: &mut Context<Self>) {
         let cwd = cx
@@ -266,9 +405,524 @@ impl ChattyApp {
             }
             return true;
         }
+        if let Some(query) = text
+            .strip_prefix("/merge ")
+            .or_else(|| (text == "/merge").then_some(""))
+        {
+            self.merge_conversation_into_active(query.trim(), cx);
+            return true;
+        }
+        if let Some(query) = text
+            .strip_prefix("/model ")
+            .or_else(|| (text == "/model").then_some(""))
+        {
+            self.switch_model_by_query(query.trim(), cx);
+            return true;
+        }
+        match parse_pin_command(text) {
+            Some(PinCommand::Note("")) => {
+                self.chat_view.update(cx, |view, cx| {
+                    view.add_info_message(
+                        "Usage: `/pin <note>` — keep a note in context for every turn.".to_string(),
+                        cx,
+                    );
+                });
+                return true;
+            }
+            Some(PinCommand::Note(note)) => {
+                self.pin_item(PinnedItem::note(note.to_string()), cx);
+                return true;
+            }
+            Some(PinCommand::File(path)) => {
+                if !path.is_empty() {
+                    self.pin_file(path.to_string(), cx);
+                }
+                return true;
+            }
+            None => {}
+        }
+        if let Some(rest) = text
+            .strip_prefix("/snippet ")
+            .or_else(|| (text == "/snippet").then_some(""))
+        {
+            self.save_snippet_from_command(rest, cx);
+            return true;
+        }
+        if let Some(format) = text
+            .strip_prefix("/handoff ")
+            .or_else(|| (text == "/handoff").then_some(""))
+        {
+            self.write_handoff(format, cx);
+            return true;
+        }
+        if let Some(rest) = text
+            .strip_prefix("/export-project ")
+            .or_else(|| (text == "/export-project").then_some(""))
+        {
+            self.export_project(rest, cx);
+            return true;
+        }
+        if let Some(rest) = text
+            .strip_prefix("/timebox ")
+            .or_else(|| (text == "/timebox").then_some(""))
+        {
+            self.set_timebox(rest.trim(), cx);
+            return true;
+        }
         false
     }
 
+    /// `/timebox <duration> <$cost>` — budget the next message and the
+    /// follow-ups the agent is sent on its own. Near the budget the agent is
+    /// told to wrap up; at the budget the stream stops. `/timebox off` clears
+    /// it and `/timebox` alone shows it.
+    fn set_timebox(&mut self, rest: &str, cx: &mut Context<Self>) {
+        let Some(conv_id) = cx
+            .try_global::<ConversationsStore>()
+            .and_then(|s| s.active_id().cloned())
+        else {
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message("No active conversation.".to_string(), cx);
+            });
+            return;
+        };
+
+        let budget = match rest {
+            "" => {
+                let current = cx
+                    .try_global::<ConversationsStore>()
+                    .and_then(|store| store.get_conversation(&conv_id))
+                    .and_then(|conv| conv.session_budget().map(|b| b.budget().summary()));
+                let message = match current {
+                    Some(summary) => format!("**Timebox:** {summary} for the next task."),
+                    None => "No timebox set. Usage: `/timebox 10m $0.50` — a duration, a cost \
+                             or both; `/timebox off` clears it."
+                        .to_string(),
+                };
+                self.chat_view
+                    .update(cx, |view, cx| view.add_info_message(message, cx));
+                return;
+            }
+            "off" => None,
+            _ => match SessionBudget::parse(rest) {
+                Ok(budget) => Some(budget),
+                Err(e) => {
+                    self.chat_view.update(cx, |view, cx| {
+                        view.add_info_message(format!("Invalid timebox: {e}"), cx);
+                    });
+                    return;
+                }
+            },
+        };
+
+        cx.update_global::<ConversationsStore, _>(|store, _| {
+            if let Some(conv) = store.get_conversation_mut(&conv_id) {
+                conv.set_session_budget(budget.map(BudgetTracker::new));
+            }
+        });
+        let message = match budget {
+            Some(budget) => format!(
+                "**Timebox set** — {} for the next task. The agent is told to wrap up at \
+                 {:.0}% and stopped at the limit.",
+                budget.summary(),
+                chatty_core::models::session_budget::WRAP_UP_AT * 100.0
+            ),
+            None => "**Timebox cleared.**".to_string(),
+        };
+        self.chat_view
+            .update(cx, |view, cx| view.add_info_message(message, cx));
+    }
+
+    /// `/export-project [git]` — ask for a folder and write the code files the
+    /// active conversation produced into it, with a README summarizing the
+    /// conversation. `git` also initializes a repository with an initial commit.
+    fn export_project(&mut self, rest: &str, cx: &mut Context<Self>) {
+        let git_init = match rest.trim() {
+            "" => false,
+            "git" => true,
+            _ => {
+                self.chat_view.update(cx, |view, cx| {
+                    view.add_info_message(
+                        "Usage: `/export-project [git]` — `git` also runs `git init` and \
+                         commits the files."
+                            .to_string(),
+                        cx,
+                    );
+                });
+                return;
+            }
+        };
+        let data = cx.try_global::<ConversationsStore>().and_then(|store| {
+            store
+                .active_id()
+                .and_then(|id| store.get_conversation(id))
+                .and_then(build_conversation_data)
+        });
+        let Some(data) = data else {
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message("No active conversation to export.".to_string(), cx);
+            });
+            return;
+        };
+        let project = match ProjectExport::from_conversation(&data) {
+            Ok(project) if project.is_empty() => {
+                self.chat_view.update(cx, |view, cx| {
+                    view.add_info_message(
+                        "This conversation has no code files or code blocks to export.".to_string(),
+                        cx,
+                    );
+                });
+                return;
+            }
+            Ok(project) => project,
+            Err(e) => {
+                warn!(error = ?e, conv_id = %data.id, "Failed to collect project files");
+                self.chat_view.update(cx, |view, cx| {
+                    view.add_info_message(format!("Failed to collect project files: {e}"), cx);
+                });
+                return;
+            }
+        };
+
+        info!(
+            conv_id = %data.id,
+            files = project.files.len(),
+            git_init,
+            "Slash command: export project"
+        );
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: false,
+            directories: true,
+            multiple: false,
+            prompt: Some("Export project".into()),
+        });
+        let chat_view = self.chat_view.clone();
+        cx.spawn(async move |_weak, cx| {
+            let Ok(Ok(Some(paths))) = receiver.await else {
+                return;
+            };
+            let Some(dest) = paths.into_iter().next() else {
+                return;
+            };
+            let message = match project.write(&dest, git_init).await {
+                Ok(summary) => {
+                    let mut message = format!(
+                        "**Project exported:** {} file(s) to `{}`, summary in `{}`",
+                        summary.files_written,
+                        dest.display(),
+                        summary.readme.display()
+                    );
+                    if !summary.skipped.is_empty() {
+                        message.push_str(&format!(
+                            "\n\nSkipped (no longer on disk): {}",
+                            summary
+                                .skipped
+                                .iter()
+                                .map(|p| format!("`{}`", p.display()))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ));
+                    }
+                    match summary.git {
+                        Some(GitOutcome::Committed) => message
+                            .push_str("\n\nInitialized a git repository with an initial commit."),
+                        Some(GitOutcome::InitializedOnly(e)) => message.push_str(&format!(
+                            "\n\nInitialized a git repository, but the initial commit failed: {e}"
+                        )),
+                        Some(GitOutcome::Failed(e)) => {
+                            message.push_str(&format!("\n\n`git init` failed: {e}"))
+                        }
+                        None => {}
+                    }
+                    message
+                }
+                Err(e) => {
+                    warn!(error = ?e, dest = ?dest, "Failed to export project");
+                    format!("Failed to export project: {e}")
+                }
+            };
+            chat_view
+                .update(cx, |view, cx| view.add_info_message(message, cx))
+                .map_err(|e| warn!(error = ?e, "Failed to show project export result"))
+                .ok();
+        })
+        .detach();
+    }
+
+    /// `/handoff [aider|json]` — write the active conversation's instructions,
+    /// files and plan to `.chatty/handoff.md` (or `.json`) in the working
+    /// directory so a CLI agent can continue the task.
+    fn write_handoff(&mut self, format: &str, cx: &mut Context<Self>) {
+        let Some(format) = HandoffFormat::parse(format) else {
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message(
+                    "Usage: `/handoff [aider|json]` — `aider` writes a Markdown brief, \
+                     `json` a task file."
+                        .to_string(),
+                    cx,
+                );
+            });
+            return;
+        };
+        let data = cx.try_global::<ConversationsStore>().and_then(|store| {
+            store
+                .active_id()
+                .and_then(|id| store.get_conversation(id))
+                .and_then(build_conversation_data)
+        });
+        let Some(data) = data else {
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message("No active conversation to hand off.".to_string(), cx);
+            });
+            return;
+        };
+        let handoff = match AgentHandoff::from_conversation(&data) {
+            Ok(handoff) => handoff,
+            Err(e) => {
+                warn!(error = ?e, conv_id = %data.id, "Failed to build handoff");
+                self.chat_view.update(cx, |view, cx| {
+                    view.add_info_message(format!("Failed to build handoff: {e}"), cx);
+                });
+                return;
+            }
+        };
+        let dir = data
+            .working_dir
+            .clone()
+            .or_else(|| {
+                cx.try_global::<ExecutionSettingsModel>()
+                    .and_then(|s| s.workspace_dir.clone())
+            })
+            .map(PathBuf::from)
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        info!(conv_id = %data.id, ?format, "Slash command: write handoff");
+        let chat_view = self.chat_view.clone();
+        cx.spawn(async move |_weak, cx| {
+            let message = match handoff.write(&dir, format).await {
+                Ok(path) => {
+                    let relative = path.strip_prefix(&dir).unwrap_or(&path);
+                    let mut message = format!(
+                        "**Handoff written:** `{}` ({} instructions, {} files, {} plan steps)",
+                        path.display(),
+                        handoff.instructions.len(),
+                        handoff.files.len(),
+                        handoff.plan.len()
+                    );
+                    if format == HandoffFormat::Brief {
+                        message.push_str(&format!(
+                            "\n\nContinue in aider from the working directory:\n\n```sh\n{}\n```",
+                            handoff.aider_command(relative)
+                        ));
+                    }
+                    message
+                }
+                Err(e) => {
+                    warn!(error = ?e, "Failed to write handoff");
+                    format!("Failed to write handoff: {e}")
+                }
+            };
+            chat_view
+                .update(cx, |view, cx| view.add_info_message(message, cx))
+                .map_err(|e| warn!(error = ?e, "Failed to show handoff result"))
+                .ok();
+        })
+        .detach();
+    }
+
+    /// `/snippet <name> <text>` — save `text` (which may span several lines) as a
+    /// snippet, replacing any snippet of the same name. `/snippet <name>` shows a
+    /// snippet and a bare `/snippet` lists the library.
+    fn save_snippet_from_command(&mut self, rest: &str, cx: &mut Context<Self>) {
+        use crate::settings::controllers::snippets_controller;
+        use crate::settings::models::SnippetsModel;
+
+        let rest = rest.trim_start();
+        let (name, content) = match rest.find(char::is_whitespace) {
+            Some(split) => (&rest[..split], rest[split..].trim()),
+            None => (rest, ""),
+        };
+
+        let message = if name.is_empty() {
+            let snippets = cx.global::<SnippetsModel>().by_usage();
+            if snippets.is_empty() {
+                "No snippets yet. Save one with `/snippet <name> <text>`.".to_string()
+            } else {
+                let list = snippets
+                    .iter()
+                    .map(|s| format!("- **/{}** — used {}×", s.name, s.use_count))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!(
+                    "**Snippets** (type `/` and a name to insert, manage in Settings → Snippets)\n\n{list}"
+                )
+            }
+        } else if !SnippetsModel::is_valid_name(name) {
+            format!("Invalid snippet name `{name}` — use letters, digits, `-` and `_` only.")
+        } else if content.is_empty() {
+            match cx.global::<SnippetsModel>().find(name) {
+                Some(snippet) => format!(
+                    "**/{}** (used {}×)\n\n```\n{}\n```",
+                    snippet.name, snippet.use_count, snippet.content
+                ),
+                None => {
+                    format!("No snippet named `{name}`. Save one with `/snippet {name} <text>`.")
+                }
+            }
+        } else {
+            let created =
+                snippets_controller::save_snippet(name.to_string(), content.to_string(), cx);
+            if created {
+                format!("**Saved snippet:** /{name}")
+            } else {
+                format!("**Updated snippet:** /{name}")
+            }
+        };
+
+        self.chat_view.update(cx, |view, cx| {
+            view.add_info_message(message, cx);
+        });
+    }
+
+    /// `/model <name|alias>` — switch the active conversation's model. Without
+    /// an argument, list the configured models and their aliases.
+    fn switch_model_by_query(&mut self, query: &str, cx: &mut Context<Self>) {
+        let models = cx.global::<ModelsModel>();
+        if query.is_empty() {
+            let list = models
+                .models()
+                .iter()
+                .map(|m| {
+                    if m.aliases.is_empty() {
+                        format!("- **{}**", m.name)
+                    } else {
+                        format!("- **{}** ({})", m.name, m.aliases.join(", "))
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message(format!("Usage: `/model <name or alias>`\n\n{list}"), cx);
+            });
+            return;
+        }
+
+        let Some(model) = models.resolve(query).cloned() else {
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message(
+                    format!("No model or alias matches `{query}`. Type `/model` to list them."),
+                    cx,
+                );
+            });
+            return;
+        };
+
+        info!(query = %query, model_id = %model.id, "Slash command: switch model");
+        self.select_model_in_input(&model.id, cx);
+        self.change_conversation_model(model.id.clone(), cx);
+        self.chat_view.update(cx, |view, cx| {
+            view.add_info_message(format!("**Model:** {}", model.name), cx);
+        });
+    }
+
+    /// Add an item to the active conversation's pinned context and confirm in the chat.
+    fn pin_item(&mut self, item: PinnedItem, cx: &mut Context<Self>) {
+        let Some(conv_id) = cx
+            .try_global::<ConversationsStore>()
+            .and_then(|s| s.active_id().cloned())
+        else {
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message("No active conversation to pin to.".to_string(), cx);
+            });
+            return;
+        };
+
+        let label = item.label.clone();
+        cx.update_global::<ConversationsStore, _>(|store, _cx| {
+            if let Some(conv) = store.get_conversation_mut(&conv_id) {
+                conv.pin_item(item);
+            }
+        });
+        self.sync_pinned_items(&conv_id, cx);
+        self.persist_conversation(&conv_id, cx);
+
+        self.chat_view.update(cx, |view, cx| {
+            view.add_info_message(format!("**Pinned to context:** {label}"), cx);
+        });
+    }
+
+    /// `/pin-file <path>` — read a file (relative to the workspace) and pin its contents.
+    ///
+    /// With a notes vault configured, a path that does not exist is looked up as
+    /// a note name, and `[[wiki links]]` in vault notes pull in the linked notes.
+    fn pin_file(&mut self, path: String, cx: &mut Context<Self>) {
+        let notes = cx
+            .try_global::<ExecutionSettingsModel>()
+            .and_then(|s| s.notes_vault_dir.clone())
+            .and_then(|dir| NotesService::new(dir).ok());
+        let resolved = {
+            let base = cx
+                .try_global::<ExecutionSettingsModel>()
+                .and_then(|s| s.workspace_dir.clone())
+                .map(std::path::PathBuf::from)
+                .or_else(|| std::env::current_dir().ok())
+                .unwrap_or_else(|| std::path::PathBuf::from("."));
+            let candidate = std::path::Path::new(&path);
+            if candidate.is_absolute() {
+                candidate.to_path_buf()
+            } else {
+                base.join(candidate)
+            }
+        };
+        let resolved = match &notes {
+            Some(notes) if !resolved.exists() => notes
+                .resolve_link(path.trim_start_matches("[[").trim_end_matches("]]"))
+                .unwrap_or(resolved),
+            _ => resolved,
+        };
+
+        match std::fs::metadata(&resolved) {
+            Ok(meta) if meta.len() > MAX_PINNED_FILE_BYTES => {
+                self.chat_view.update(cx, |view, cx| {
+                    view.add_info_message(
+                        format!(
+                            "`{path}` is too large to pin ({} KB, limit {} KB).",
+                            meta.len() / 1024,
+                            MAX_PINNED_FILE_BYTES / 1024
+                        ),
+                        cx,
+                    );
+                });
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                self.chat_view.update(cx, |view, cx| {
+                    view.add_info_message(format!("Cannot pin `{path}`: {e}"), cx);
+                });
+                return;
+            }
+        }
+
+        match std::fs::read_to_string(&resolved) {
+            Ok(content) => {
+                let content = match &notes {
+                    Some(notes) if notes.contains(&resolved) => {
+                        notes.expand_wiki_links(&content, Some(&resolved))
+                    }
+                    _ => content,
+                };
+                self.pin_item(PinnedItem::file(&resolved, content), cx)
+            }
+            Err(e) => {
+                self.chat_view.update(cx, |view, cx| {
+                    view.add_info_message(format!("Cannot pin `{path}`: {e}"), cx);
+                });
+            }
+        }
+    }
+
     /// Dispatch a task to a remote A2A agent and display the result.
     fn launch_a2a_agent(&mut self, agent_name: String, prompt: String, cx: &mut Context<Self>) {
         info!(agent = %agent_name, prompt = %prompt, "Dispatching task to remote A2A agent");
@@ -770,3 +1424,26 @@ impl ChattyApp {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    // Re-import standard #[test] to shadow gpui::test from `use gpui::*`
+    use core::prelude::rust_2021::test;
+
+    use super::*;
+
+    #[test]
+    fn pin_and_pin_file_do_not_shadow_each_other() {
+        assert_eq!(
+            parse_pin_command("/pin  use snake_case "),
+            Some(PinCommand::Note("use snake_case"))
+        );
+        assert_eq!(parse_pin_command("/pin "), Some(PinCommand::Note("")));
+        assert_eq!(
+            parse_pin_command("/pin-file ./notes.md"),
+            Some(PinCommand::File("./notes.md"))
+        );
+        assert_eq!(parse_pin_command("/pinned note"), None);
+        assert_eq!(parse_pin_command("pin this"), None);
+    }
+}
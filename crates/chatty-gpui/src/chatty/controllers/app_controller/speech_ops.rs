@@ -0,0 +1,240 @@
+//! Speech mode (hands-free voice conversation) for `ChattyApp`.
+//!
+//! # What lives here
+//!
+//! - `toggle_speech_mode` — start the recorder and the listening loop, or
+//!   tear both down.
+//! - `speech_turn_ended` — called when a reply stream ends: read a
+//!   completed reply aloud, otherwise go back to listening.
+//! - Barge-in: speech detected while a reply streams or is spoken stops
+//!   the stream and the playback before the new utterance is transcribed.
+//!
+//! Transcripts go through `ChatInputState::send_spoken`, the same path as
+//! a typed message, so they are persisted in the conversation history.
+//! Turn detection, transcription and playback live in
+//! `chatty_core::services::speech_mode`.
+
+use chatty_core::services::speech_mode::{self, SpeechListener, SpeechPhase, VadConfig, VadEvent};
+
+use super::*;
+use crate::settings::models::GeneralSettingsModel;
+
+/// A running speech mode. Dropping it kills the recorder and any playback.
+pub(super) struct SpeechSession {
+    /// Reads the microphone and reacts to turn-taking events
+    _listener: Task<()>,
+    /// The reply being read aloud, if any
+    playback: Option<Task<()>>,
+    /// Set while a reply is spoken, so barge-in needs longer speech
+    barge_in: Option<Arc<AtomicBool>>,
+}
+
+impl ChattyApp {
+    /// Start speech mode, or end it when it is already on.
+    pub(super) fn toggle_speech_mode(&mut self, cx: &mut Context<Self>) {
+        if self.speech.take().is_some() {
+            info!("Speech mode ended");
+            self.set_speech_phase(None, cx);
+            return;
+        }
+
+        let settings = cx.global::<GeneralSettingsModel>();
+        let (Some(record_command), Some(transcribe_command)) = (
+            settings.speech_record_command.clone(),
+            settings.speech_transcribe_command.clone(),
+        ) else {
+            self.chat_view.update(cx, |view, cx| {
+                view.add_info_message(
+                    "Speech mode needs a recording command and a transcription command. \
+                     Set them in Settings → General → Speech Mode."
+                        .to_string(),
+                    cx,
+                );
+            });
+            return;
+        };
+
+        info!("Speech mode started");
+        let listener = cx.spawn(async move |app, cx| {
+            let mut listener = match SpeechListener::start(&record_command, VadConfig::default()) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    warn!(error = ?e, "Failed to start speech mode recorder");
+                    app.update(cx, |app, cx| app.end_speech_mode(e.to_string(), cx))
+                        .ok();
+                    return;
+                }
+            };
+            let barge_in = listener.barge_in_flag();
+            if app
+                .update(cx, |app, _cx| {
+                    if let Some(session) = app.speech.as_mut() {
+                        session.barge_in = Some(barge_in);
+                    }
+                })
+                .is_err()
+            {
+                return;
+            }
+
+            while let Some(event) = listener.next_event().await {
+                match event {
+                    VadEvent::SpeechStarted => {
+                        app.update(cx, |app, cx| app.interrupt_for_speech(cx)).ok();
+                    }
+                    VadEvent::SpeechDiscarded => {}
+                    VadEvent::SpeechEnded(samples) => {
+                        app.update(cx, |app, cx| {
+                            app.set_speech_phase(Some(SpeechPhase::Transcribing), cx)
+                        })
+                        .ok();
+                        let text =
+                            match speech_mode::transcribe_utterance(&samples, &transcribe_command)
+                                .await
+                            {
+                                Ok(text) => text,
+                                Err(e) => {
+                                    warn!(error = ?e, "Failed to transcribe utterance");
+                                    String::new()
+                                }
+                            };
+                        let sent = app.update(cx, |app, cx| app.send_spoken_message(text, cx));
+                        if sent.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+
+            app.update(cx, |app, cx| {
+                app.end_speech_mode("The recording command exited.".to_string(), cx)
+            })
+            .ok();
+        });
+
+        self.speech = Some(SpeechSession {
+            _listener: listener,
+            playback: None,
+            barge_in: None,
+        });
+        self.set_speech_phase(Some(SpeechPhase::Listening), cx);
+    }
+
+    /// A reply stream of `conversation_id` ended. Completed replies in the
+    /// open conversation are read aloud when a text-to-speech command is set.
+    pub(super) fn speech_turn_ended(
+        &mut self,
+        conversation_id: &str,
+        status: &StreamStatus,
+        cx: &mut Context<Self>,
+    ) {
+        if self.speech.is_none() {
+            return;
+        }
+        let is_open = self
+            .chat_view
+            .read(cx)
+            .conversation_id()
+            .map(|s| s.as_str())
+            == Some(conversation_id)
+            || conversation_id == "__pending__";
+        let reply = if matches!(status, StreamStatus::Completed) && is_open {
+            cx.global::<ConversationsStore>()
+                .get_conversation(conversation_id)
+                .and_then(|conv| conv.streaming_message().cloned())
+                .map(|content| speech_mode::speakable_text(&content))
+                .filter(|text| !text.is_empty())
+        } else {
+            None
+        };
+        let tts_command = cx
+            .global::<GeneralSettingsModel>()
+            .speech_tts_command
+            .clone();
+        let (Some(reply), Some(tts_command)) = (reply, tts_command) else {
+            self.set_speech_phase(Some(SpeechPhase::Listening), cx);
+            return;
+        };
+
+        let playback = cx.spawn(async move |app, cx| {
+            if let Err(e) = speech_mode::speak(&reply, &tts_command).await {
+                warn!(error = ?e, "Failed to read the reply aloud");
+            }
+            app.update(cx, |app, cx| {
+                if let Some(session) = app.speech.as_mut() {
+                    session.playback = None;
+                    session.set_barge_in(false);
+                }
+                app.set_speech_phase(Some(SpeechPhase::Listening), cx);
+            })
+            .ok();
+        });
+        if let Some(session) = self.speech.as_mut() {
+            session.playback = Some(playback);
+            session.set_barge_in(true);
+        }
+        self.set_speech_phase(Some(SpeechPhase::Speaking), cx);
+    }
+
+    /// The user started talking: stop reading the reply aloud and stop a
+    /// reply that is still streaming.
+    fn interrupt_for_speech(&mut self, cx: &mut Context<Self>) {
+        let Some(session) = self.speech.as_mut() else {
+            return;
+        };
+        if session.playback.take().is_some() {
+            debug!("Speech mode: playback interrupted");
+        }
+        session.set_barge_in(false);
+        let streaming = self
+            .chat_view
+            .read(cx)
+            .chat_input_state()
+            .read(cx)
+            .is_streaming();
+        if streaming {
+            debug!("Speech mode: stream interrupted");
+            self.stop_stream(cx);
+        }
+        self.set_speech_phase(Some(SpeechPhase::Listening), cx);
+    }
+
+    /// Send a transcript as the next user message; empty ones are dropped.
+    fn send_spoken_message(&mut self, text: String, cx: &mut Context<Self>) {
+        if self.speech.is_none() {
+            return;
+        }
+        if text.trim().is_empty() {
+            self.set_speech_phase(Some(SpeechPhase::Listening), cx);
+            return;
+        }
+        debug!(chars = text.len(), "Speech mode: sending transcript");
+        self.set_speech_phase(Some(SpeechPhase::Thinking), cx);
+        let chat_input_state = self.chat_view.read(cx).chat_input_state().clone();
+        chat_input_state.update(cx, |state, cx| state.send_spoken(text, cx));
+    }
+
+    /// Turn speech mode off after a failure and tell the user why.
+    fn end_speech_mode(&mut self, reason: String, cx: &mut Context<Self>) {
+        if self.speech.take().is_none() {
+            return;
+        }
+        self.set_speech_phase(None, cx);
+        self.chat_view.update(cx, |view, cx| {
+            view.add_info_message(format!("Speech mode ended: {}", reason), cx);
+        });
+    }
+
+    fn set_speech_phase(&self, phase: Option<SpeechPhase>, cx: &mut Context<Self>) {
+        let chat_input_state = self.chat_view.read(cx).chat_input_state().clone();
+        chat_input_state.update(cx, |state, cx| state.set_speech_phase(phase, cx));
+    }
+}
+
+impl SpeechSession {
+    fn set_barge_in(&self, on: bool) {
+        if let Some(flag) = &self.barge_in {
+            flag.store(on, Ordering::Relaxed);
+        }
+    }
+}
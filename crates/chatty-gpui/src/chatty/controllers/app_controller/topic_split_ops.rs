@@ -0,0 +1,153 @@
+//! Topic-shift split suggestions for `ChattyApp`.
+//!
+//! # What lives here
+//!
+//! - `check_topic_shift` — the `DetectTopicShift` background job, queued
+//!   after a completed turn when enabled in the general settings: embed the
+//!   newest prompt against the earlier ones and ask `ChatView` to show the
+//!   split suggestion.
+//! - `handle_split_conversation` — summarize the active conversation, start
+//!   a new one and pin the summary to it. The old conversation is kept as is.
+//!
+//! Distance and summary logic live in `chatty_core::services::topic_shift`.
+
+use chatty_core::models::PinnedItem;
+use chatty_core::services::topic_shift::{detect_topic_shift, summarize_for_split};
+
+use super::*;
+
+impl ChattyApp {
+    /// Compare the newest prompt of `conv_id` with the earlier ones and
+    /// suggest a split when the topic changed. No-op without an embedding
+    /// model or when the suggestion is turned off.
+    pub(super) fn check_topic_shift(
+        &self,
+        conv_id: &str,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        if !cx.global::<GeneralSettingsModel>().topic_split_suggestions {
+            return Task::ready(Ok(()));
+        }
+        let Some(embeddings) = cx
+            .try_global::<chatty_core::services::EmbeddingService>()
+            .cloned()
+        else {
+            return Task::ready(Ok(()));
+        };
+        let Some((history, message_count)) = cx
+            .global::<ConversationsStore>()
+            .get_conversation(conv_id)
+            .map(|conv| (conv.messages(), conv.message_count()))
+        else {
+            return Task::ready(Ok(()));
+        };
+
+        let chat_view = self.chat_view.clone();
+        let conv_id = conv_id.to_string();
+        cx.spawn(async move |_weak, cx| {
+            let distance =
+                tokio::spawn(async move { detect_topic_shift(&embeddings, &history).await })
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Topic shift task failed: {e}"))??;
+            let Some(distance) = distance else {
+                return Ok(());
+            };
+            info!(conv_id = %conv_id, distance, "Topic shift detected");
+            chat_view
+                .update(cx, |view, cx| {
+                    // Skip stale results: another turn started while embedding.
+                    let unchanged = cx
+                        .global::<ConversationsStore>()
+                        .get_conversation(&conv_id)
+                        .is_some_and(|conv| conv.message_count() == message_count);
+                    if unchanged && view.conversation_id() == Some(&conv_id) {
+                        view.suggest_topic_split(cx);
+                    }
+                })
+                .map_err(|e| warn!(error = ?e, "Failed to show topic split suggestion"))
+                .ok();
+            Ok(())
+        })
+    }
+
+    /// Continue the active conversation's newest topic in a new conversation
+    /// whose pinned context holds a summary of the old one.
+    pub(super) fn handle_split_conversation(&mut self, cx: &mut Context<Self>) {
+        if self.active_create_task.is_some() {
+            debug!("Already creating a conversation, ignoring split request");
+            return;
+        }
+        let Some(conv_id) = cx.global::<ConversationsStore>().active_id().cloned() else {
+            return;
+        };
+        let Some((agent, history, title)) = cx
+            .global::<ConversationsStore>()
+            .get_conversation(&conv_id)
+            .map(|conv| {
+                (
+                    conv.agent().clone(),
+                    conv.messages(),
+                    conv.title().to_string(),
+                )
+            })
+        else {
+            return;
+        };
+
+        self.chat_view.update(cx, |view, cx| {
+            view.add_info_message(
+                "Summarizing this conversation for a new one…".to_string(),
+                cx,
+            );
+        });
+
+        let chat_view = self.chat_view.clone();
+        cx.spawn(async move |weak, cx| {
+            let summary = match summarize_for_split(&agent, &history).await {
+                Ok(summary) => summary,
+                Err(e) => {
+                    warn!(error = ?e, "Summary for conversation split failed");
+                    chat_view
+                        .update(cx, |view, cx| {
+                            view.add_info_message(format!("Could not split conversation: {e}"), cx)
+                        })
+                        .ok();
+                    return;
+                }
+            };
+
+            let Some(app) = weak.upgrade() else {
+                return;
+            };
+            let create_task = match app.update(cx, |app, cx| app.create_new_conversation(cx)) {
+                Ok(task) => task,
+                Err(e) => {
+                    warn!(error = ?e, "Failed to start split conversation");
+                    return;
+                }
+            };
+            let new_id = match create_task.await {
+                Ok(id) => id,
+                Err(e) => {
+                    warn!(error = ?e, "Failed to create split conversation");
+                    return;
+                }
+            };
+
+            let note = PinnedItem::note(format!("Summary of \"{title}\"\n\n{summary}"));
+            app.update(cx, |app, cx| {
+                cx.update_global::<ConversationsStore, _>(|store, _cx| {
+                    if let Some(conv) = store.get_conversation_mut(&new_id) {
+                        conv.pin_item(note);
+                    }
+                });
+                app.sync_pinned_items(&new_id, cx);
+                app.persist_conversation(&new_id, cx);
+                info!(from = %conv_id, to = %new_id, "Conversation split on topic change");
+            })
+            .map_err(|e| warn!(error = ?e, "Failed to seed split conversation"))
+            .ok();
+        })
+        .detach();
+    }
+}
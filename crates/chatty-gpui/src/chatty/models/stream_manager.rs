@@ -15,6 +15,7 @@ use tracing::{debug, warn};
 /// is not delayed by this interval.
 const FLUSH_INTERVAL: Duration = Duration::from_millis(5);
 
+use crate::chatty::models::execution_approval_store::UserQuestion;
 use crate::chatty::services::StreamChunk;
 use chatty_core::tools::PendingArtifacts;
 
@@ -92,11 +93,13 @@ pub enum StreamManagerEvent {
         id: String,
         command: String,
         is_sandboxed: bool,
+        question: Option<UserQuestion>,
     },
     ApprovalResolved {
         conversation_id: String,
         id: String,
         approved: bool,
+        answer: Option<String>,
     },
     TokenUsage {
         conversation_id: String,
@@ -340,6 +343,10 @@ impl StreamManager {
                 }
             }
             StreamChunk::ToolCallStarted { id, name } => {
+                chatty_core::services::telemetry_service::record(
+                    chatty_core::services::telemetry_service::features::TOOL_CALL,
+                );
+                chatty_core::services::tool_stats_service::record_started(&id, &name);
                 cx.emit(StreamManagerEvent::ToolCallStarted {
                     conversation_id: conv_id.to_string(),
                     id,
@@ -359,6 +366,7 @@ impl StreamManager {
                 if let Some(state) = self.streams.get_mut(conv_id) {
                     state.api_turn_count += 1;
                 }
+                chatty_core::services::tool_stats_service::record_finished(&id, true);
                 cx.emit(StreamManagerEvent::ToolCallResult {
                     conversation_id: conv_id.to_string(),
                     id,
@@ -372,6 +380,7 @@ impl StreamManager {
                 if let Some(state) = self.streams.get_mut(conv_id) {
                     state.api_turn_count += 1;
                 }
+                chatty_core::services::tool_stats_service::record_finished(&id, false);
                 cx.emit(StreamManagerEvent::ToolCallError {
                     conversation_id: conv_id.to_string(),
                     id,
@@ -382,19 +391,26 @@ impl StreamManager {
                 id,
                 command,
                 is_sandboxed,
+                question,
             } => {
                 cx.emit(StreamManagerEvent::ApprovalRequested {
                     conversation_id: conv_id.to_string(),
                     id,
                     command,
                     is_sandboxed,
+                    question,
                 });
             }
-            StreamChunk::ApprovalResolved { id, approved } => {
+            StreamChunk::ApprovalResolved {
+                id,
+                approved,
+                answer,
+            } => {
                 cx.emit(StreamManagerEvent::ApprovalResolved {
                     conversation_id: conv_id.to_string(),
                     id,
                     approved,
+                    answer,
                 });
             }
             StreamChunk::TokenUsage {
@@ -2,6 +2,7 @@ use gpui::BorrowAppContext;
 use tokio::sync::watch;
 use tracing::{debug, info, warn};
 
+use super::breakdown::ContextBreakdown;
 use super::cache::{CachedTokenCounts, build_tool_hint};
 use super::counter::TokenCounter;
 use super::snapshot::{ContextPressureEvent, TokenBudgetSnapshot};
@@ -126,6 +127,15 @@ pub struct SnapshotInputs {
     pub preamble: String,
     pub history: Vec<rig_core::completion::Message>,
     pub user_message_text: String,
+    /// Images, PDFs and other files attached to the new user message.
+    pub user_message_attachments: Vec<rig_core::message::UserContent>,
+    /// History policy applied before counting, and how many stored messages it left out.
+    pub history_policy: chatty_core::models::HistoryPolicy,
+    pub dropped_messages: usize,
+    /// Turns the relevance policy pruned before counting.
+    pub pruned_turns: Vec<chatty_core::models::PrunedTurn>,
+    /// Pinned context block prepended to the user message (empty when nothing is pinned).
+    pub pinned_context: String,
     // Populated for potential future use (e.g. re-running tool estimation in-task).
     #[allow(dead_code)]
     pub exec_settings: crate::settings::models::ExecutionSettingsModel,
@@ -184,6 +194,11 @@ pub async fn compute_snapshot_background(
     let preamble = inputs.preamble;
     let history = inputs.history;
     let user_message_text = inputs.user_message_text;
+    let user_message_attachments = inputs.user_message_attachments;
+    let history_policy = inputs.history_policy;
+    let dropped_messages = inputs.dropped_messages;
+    let pruned_turns = inputs.pruned_turns;
+    let pinned_context = inputs.pinned_context;
     let tool_count = inputs.tool_count;
 
     let snapshot = tokio::task::spawn_blocking(move || {
@@ -206,6 +221,10 @@ pub async fn compute_snapshot_background(
         // History and user message are always re-counted — they change every turn
         let history_tokens = counter.count_history(&history);
         let user_msg_tokens = counter.count(&user_message_text);
+        let pinned_tokens = counter.count(&pinned_context);
+        let breakdown = ContextBreakdown::compute(&counter, &history, &user_message_attachments)
+            .with_history_policy(history_policy, dropped_messages)
+            .with_pruned_turns(pruned_turns);
 
         let snap = TokenBudgetSnapshot {
             computed_at: std::time::Instant::now(),
@@ -215,6 +234,8 @@ pub async fn compute_snapshot_background(
             tool_definitions_tokens: tool_tokens,
             conversation_history_tokens: history_tokens,
             latest_user_message_tokens: user_msg_tokens,
+            pinned_context_tokens: pinned_tokens,
+            breakdown,
             actual_input_tokens: None,
             actual_output_tokens: None,
             conversation_id: conv_id,
@@ -225,6 +246,10 @@ pub async fn compute_snapshot_background(
             tool_tokens,
             history_tokens,
             user_msg_tokens,
+            pinned_tokens,
+            memory_tokens = snap.breakdown.memory.tokens,
+            retrieval_tokens = snap.breakdown.retrieval.tokens,
+            attachment_tokens = snap.breakdown.attachments.tokens,
             estimated_total = snap.estimated_total(),
             utilization = snap.utilization(),
             "Token budget snapshot computed"
@@ -314,7 +339,10 @@ pub fn extract_user_message_text(contents: &[rig_core::message::UserContent]) ->
 pub fn gather_snapshot_inputs(
     conv_id: &str,
     user_message_text: String,
+    user_message_attachments: Vec<rig_core::message::UserContent>,
     history: Vec<rig_core::completion::Message>,
+    dropped_messages: usize,
+    pruned_turns: Vec<chatty_core::models::PrunedTurn>,
     cx: &mut gpui::App,
 ) -> Option<SnapshotInputs> {
     use crate::chatty::models::ConversationsStore;
@@ -327,6 +355,9 @@ pub fn gather_snapshot_inputs(
         .global::<ConversationsStore>()
         .get_conversation(conv_id)?;
     let model_id_str = conv.model_id().to_string();
+    let history_policy = conv.history_policy();
+    let pinned_context =
+        chatty_core::models::build_pinned_context_block(conv.pinned_items()).unwrap_or_default();
 
     let model_config = cx.global::<ModelsModel>().get_model(&model_id_str)?.clone();
     let model_context_limit = model_config.max_context_window.map(|v| v as usize)?;
@@ -375,6 +406,11 @@ pub fn gather_snapshot_inputs(
         preamble,
         history,
         user_message_text,
+        user_message_attachments,
+        history_policy,
+        dropped_messages,
+        pruned_turns,
+        pinned_context,
         exec_settings,
         mcp_server_count,
         cached_preamble_tokens,
@@ -409,6 +445,8 @@ mod tests {
             tool_definitions_tokens: 2_000,
             conversation_history_tokens: 10_000,
             latest_user_message_tokens: 500,
+            pinned_context_tokens: 0,
+            breakdown: Default::default(),
             actual_input_tokens: None,
             actual_output_tokens: None,
             conversation_id: "conv-1".to_string(),
@@ -428,6 +466,8 @@ mod tests {
             tool_definitions_tokens: 0,
             conversation_history_tokens: 0,
             latest_user_message_tokens: 0,
+            pinned_context_tokens: 0,
+            breakdown: Default::default(),
             actual_input_tokens: None,
             actual_output_tokens: None,
             conversation_id: "conv-1".to_string(),
@@ -449,6 +489,8 @@ mod tests {
             tool_definitions_tokens: 2_000,
             conversation_history_tokens: 10_000,
             latest_user_message_tokens: 500,
+            pinned_context_tokens: 0,
+            breakdown: Default::default(),
             actual_input_tokens: None,
             actual_output_tokens: None,
             conversation_id: "conv-1".to_string(),
@@ -497,6 +539,8 @@ mod tests {
             tool_definitions_tokens: 0,
             conversation_history_tokens: 0,
             latest_user_message_tokens: 0,
+            pinned_context_tokens: 0,
+            breakdown: Default::default(),
             actual_input_tokens: None,
             actual_output_tokens: None,
             conversation_id: "c".to_string(),
@@ -515,6 +559,8 @@ mod tests {
             tool_definitions_tokens: 0,
             conversation_history_tokens: 0,
             latest_user_message_tokens: 0,
+            pinned_context_tokens: 0,
+            breakdown: Default::default(),
             actual_input_tokens: None,
             actual_output_tokens: None,
             conversation_id: "c".to_string(),
@@ -536,6 +582,8 @@ mod tests {
             tool_definitions_tokens: 0,
             conversation_history_tokens: 0,
             latest_user_message_tokens: 0,
+            pinned_context_tokens: 0,
+            breakdown: Default::default(),
             actual_input_tokens: None,
             actual_output_tokens: None,
             conversation_id: "c".to_string(),
@@ -557,6 +605,11 @@ mod tests {
             preamble: "You are a helpful assistant.".to_string(),
             history: vec![],
             user_message_text: "Hello!".to_string(),
+            user_message_attachments: vec![],
+            history_policy: Default::default(),
+            dropped_messages: 0,
+            pruned_turns: Vec::new(),
+            pinned_context: String::new(),
             exec_settings: crate::settings::models::ExecutionSettingsModel::default(),
             mcp_server_count: 0,
             cached_preamble_tokens: 0,
@@ -594,6 +647,11 @@ mod tests {
             preamble: "You are a helpful assistant.".to_string(),
             history: vec![],
             user_message_text: "Hello".to_string(),
+            user_message_attachments: vec![],
+            history_policy: Default::default(),
+            dropped_messages: 0,
+            pruned_turns: Vec::new(),
+            pinned_context: String::new(),
             exec_settings: crate::settings::models::ExecutionSettingsModel::default(),
             mcp_server_count: 0,
             cached_preamble_tokens: cached_pre,
@@ -1,10 +1,24 @@
 use crate::chatty::controllers::ChattyApp;
 use crate::chatty::views::AppTitleBar;
 use crate::chatty::views::footer::StatusFooterView;
+use crate::i18n::{self, t};
 use crate::settings::models::general_model::GeneralSettingsModel;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
-use gpui_component::{ActiveTheme as _, Icon, IconName, Root, Sizable, button::Button};
+use gpui_component::{
+    ActiveTheme as _, Icon, IconName, Root, Sizable, Size, button::Button, spinner::Spinner,
+};
+
+/// Below this window width (in logical pixels) the app switches to its
+/// compact layout: the sidebar becomes an icon rail, the footer keeps only
+/// the essential indicators and the model picker moves into the chat
+/// input's menu.
+const COMPACT_LAYOUT_WIDTH: f32 = 640.0;
+
+/// Whether the window is narrow enough for the compact layout.
+pub fn is_compact_layout(window: &Window) -> bool {
+    window.viewport_size().width < px(COMPACT_LAYOUT_WIDTH)
+}
 
 impl Render for ChattyApp {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
@@ -28,6 +42,10 @@ impl Render for ChattyApp {
                     .flex_1()
                     .flex()
                     .flex_row()
+                    // Mirrored layout puts the sidebar on the right
+                    .when(i18n::layout_direction(cx).is_rtl(), |d| {
+                        d.flex_row_reverse()
+                    })
                     .overflow_hidden()
                     .child(
                         // Sidebar - left panel
@@ -71,13 +89,30 @@ impl Render for ChattyApp {
                             .icon(Icon::new(IconName::Search))
                             .label("")
                             .small()
-                            .tooltip("Search conversations")
+                            .tooltip(t("search-conversations"))
                             .on_click(|_event, window, cx| {
                                 crate::chatty::views::SearchConversationsDialog::open(window, cx);
                             }),
                     ),
                 )
             })
+            .when(crate::shutdown::saving_overlay_visible(cx), |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .size_full()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .gap_2()
+                        .bg(cx.theme().background.opacity(0.85))
+                        .text_color(cx.theme().muted_foreground)
+                        .child(Spinner::new().with_size(Size::Small))
+                        .child(t("app-saving")),
+                )
+            })
             .children(dialog_layer)
     }
 }
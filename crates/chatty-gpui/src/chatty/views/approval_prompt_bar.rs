@@ -1,4 +1,5 @@
 use crate::assets::CustomIcon;
+use crate::i18n::t;
 use gpui::{prelude::*, *};
 use gpui_component::{ActiveTheme, Icon, Sizable, button::Button};
 use std::sync::Arc;
@@ -96,7 +97,7 @@ impl RenderOnce for ApprovalPromptBar {
                     .text_xs()
                     .text_color(cx.theme().foreground)
                     .flex_shrink_0()
-                    .child("Execute:"),
+                    .child(t("approval-execute")),
             )
             // Command text - single line with ellipsis
             .child(
@@ -0,0 +1,471 @@
+use std::path::PathBuf;
+
+use gpui::prelude::*;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::menu::{DropdownMenu, PopupMenuItem};
+use gpui_component::{ActiveTheme, Disableable, IconName, Sizable};
+
+use crate::chatty::models::ConversationsStore;
+use crate::chatty::services::artifact_gallery::{
+    Artifact, ArtifactFilter, ArtifactKind, collect_artifacts, export_artifacts,
+};
+use crate::i18n::{t, t_args};
+use crate::settings::models::execution_settings::ExecutionSettingsModel;
+
+const PANEL_WIDTH: f32 = 340.0;
+const THUMBNAIL_SIZE: f32 = 48.0;
+
+/// Events emitted by the artifact gallery for the chat view to act on.
+#[derive(Clone, Debug)]
+pub enum ArtifactGalleryEvent {
+    Close,
+}
+
+/// Side panel listing the files the active conversation's tools produced,
+/// filterable by kind and turn, with open and bulk-export actions.
+pub struct ArtifactGalleryPanel {
+    artifacts: Vec<Artifact>,
+    /// Conversation and entry count `artifacts` was collected for
+    computed_for: Option<(String, usize)>,
+    filter: ArtifactFilter,
+    exporting: bool,
+    status: Option<String>,
+}
+
+impl EventEmitter<ArtifactGalleryEvent> for ArtifactGalleryPanel {}
+
+/// Filter button label of an artifact kind.
+fn kind_label(kind: ArtifactKind) -> String {
+    match kind {
+        ArtifactKind::Image => t("artifacts-kind-images"),
+        ArtifactKind::Document => t("artifacts-kind-documents"),
+        ArtifactKind::Data => t("artifacts-kind-data"),
+        ArtifactKind::Other => t("artifacts-kind-other"),
+    }
+}
+
+impl ArtifactGalleryPanel {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let mut panel = Self {
+            artifacts: Vec::new(),
+            computed_for: None,
+            filter: ArtifactFilter::default(),
+            exporting: false,
+            status: None,
+        };
+        panel.refresh(cx);
+        panel
+    }
+
+    /// Re-collect the artifacts when the active conversation or its history
+    /// changed since the last render.
+    fn refresh(&mut self, cx: &App) {
+        let Some(store) = cx.try_global::<ConversationsStore>() else {
+            return;
+        };
+        let Some(conv) = store.active_id().and_then(|id| store.get_conversation(id)) else {
+            self.artifacts.clear();
+            self.computed_for = None;
+            return;
+        };
+        let key = (conv.id().to_string(), conv.entries().len());
+        if self.computed_for.as_ref() == Some(&key) {
+            return;
+        }
+
+        if self
+            .computed_for
+            .as_ref()
+            .is_none_or(|(id, _)| *id != key.0)
+        {
+            self.filter = ArtifactFilter::default();
+            self.status = None;
+        }
+        let base_dir = conv.working_dir().cloned().or_else(|| {
+            cx.try_global::<ExecutionSettingsModel>()
+                .and_then(|settings| settings.workspace_dir.as_ref().map(PathBuf::from))
+        });
+        self.artifacts = collect_artifacts(conv.entries(), base_dir.as_deref());
+        self.computed_for = Some(key);
+    }
+
+    fn set_kind(&mut self, kind: Option<ArtifactKind>, cx: &mut Context<Self>) {
+        self.filter.kind = kind;
+        cx.notify();
+    }
+
+    fn set_turn(&mut self, turn: Option<usize>, cx: &mut Context<Self>) {
+        self.filter.turn = turn;
+        cx.notify();
+    }
+
+    fn filtered(&self) -> Vec<Artifact> {
+        self.filter
+            .apply(&self.artifacts)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Ask for a folder and copy the filtered artifacts into it.
+    fn export(&mut self, cx: &mut Context<Self>) {
+        let artifacts = self.filtered();
+        if artifacts.is_empty() {
+            return;
+        }
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: false,
+            directories: true,
+            multiple: false,
+            prompt: Some(t("artifacts-export-prompt").into()),
+        });
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let Ok(Ok(Some(paths))) = receiver.await else {
+                return;
+            };
+            let Some(dest) = paths.into_iter().next() else {
+                return;
+            };
+            this.update(cx, |panel, cx| {
+                panel.exporting = true;
+                panel.status = None;
+                cx.notify();
+            })
+            .ok();
+
+            let dest_for_task = dest.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                export_artifacts(&artifacts, &dest_for_task).map_err(|e| e.to_string())
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Task error: {}", e)));
+
+            this.update(cx, |panel, cx| {
+                panel.exporting = false;
+                panel.status = Some(match result {
+                    Ok(summary) => {
+                        let count = summary.copied.len().to_string();
+                        let dest = dest.display().to_string();
+                        if summary.missing.is_empty() {
+                            t_args(
+                                "artifacts-exported",
+                                &[("count", count.as_str()), ("dest", dest.as_str())],
+                            )
+                        } else {
+                            t_args(
+                                "artifacts-exported-missing",
+                                &[
+                                    ("count", count.as_str()),
+                                    ("dest", dest.as_str()),
+                                    ("missing", summary.missing.len().to_string().as_str()),
+                                ],
+                            )
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(dest = ?dest, error = %e, "Failed to export artifacts");
+                        t_args("artifacts-export-failed", &[("error", e.as_str())])
+                    }
+                });
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn render_kind_filter(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let options = std::iter::once((None, t("artifacts-all")))
+            .chain(ArtifactKind::ALL.map(|kind| (Some(kind), kind_label(kind))));
+        div()
+            .flex()
+            .flex_wrap()
+            .gap_1()
+            .children(options.map(|(kind, label)| {
+                let button = Button::new(SharedString::from(format!("artifact-kind-{label}")))
+                    .xsmall()
+                    .label(label)
+                    .on_click(cx.listener(move |panel, _event, _window, cx| {
+                        panel.set_kind(kind, cx);
+                    }));
+                if self.filter.kind == kind {
+                    button.primary()
+                } else {
+                    button.ghost()
+                }
+            }))
+    }
+
+    fn render_turn_filter(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut turns: Vec<usize> = self.artifacts.iter().map(|a| a.turn).collect();
+        turns.sort_unstable();
+        turns.dedup();
+        let current = self.filter.turn;
+        let label = match current {
+            Some(turn) => t_args("artifacts-turn", &[("turn", turn.to_string().as_str())]),
+            None => t("artifacts-all-turns"),
+        };
+        let entity = cx.entity();
+
+        Button::new("artifact-turn-filter")
+            .xsmall()
+            .outline()
+            .label(label)
+            .dropdown_caret(true)
+            .dropdown_menu_with_anchor(Corner::TopLeft, move |menu, _, _| {
+                let all_entity = entity.clone();
+                let menu = menu.item(
+                    PopupMenuItem::new(t("artifacts-all-turns"))
+                        .checked(current.is_none())
+                        .on_click(move |_, _, cx| {
+                            all_entity.update(cx, |panel, cx| panel.set_turn(None, cx));
+                        }),
+                );
+                turns.iter().fold(menu, |menu, &turn| {
+                    let entity = entity.clone();
+                    menu.item(
+                        PopupMenuItem::new(t_args(
+                            "artifacts-turn",
+                            &[("turn", turn.to_string().as_str())],
+                        ))
+                        .checked(current == Some(turn))
+                        .on_click(move |_, _, cx| {
+                            entity.update(cx, |panel, cx| panel.set_turn(Some(turn), cx));
+                        }),
+                    )
+                })
+            })
+    }
+
+    fn render_artifact(&self, index: usize, artifact: Artifact, cx: &App) -> impl IntoElement {
+        let exists = artifact.path.exists();
+        let kind_badge = match artifact.kind {
+            ArtifactKind::Image => "IMG",
+            ArtifactKind::Document => "DOC",
+            ArtifactKind::Data => "DATA",
+            ArtifactKind::Other => "FILE",
+        };
+        let detail = if exists {
+            t_args(
+                "artifacts-detail",
+                &[
+                    ("turn", artifact.turn.to_string().as_str()),
+                    ("tool", artifact.tool.as_str()),
+                ],
+            )
+        } else {
+            t_args(
+                "artifacts-detail-missing",
+                &[("turn", artifact.turn.to_string().as_str())],
+            )
+        };
+        let open_path = artifact.path.clone();
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .px_3()
+            .py_1()
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .size(px(THUMBNAIL_SIZE))
+                    .rounded_md()
+                    .overflow_hidden()
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .map(|this| {
+                        if exists && artifact.kind == ArtifactKind::Image {
+                            this.child(
+                                img(artifact.path.clone())
+                                    .size_full()
+                                    .object_fit(ObjectFit::Cover),
+                            )
+                        } else {
+                            this.text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(kind_badge)
+                        }
+                    }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .min_w_0()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().foreground)
+                            .truncate()
+                            .child(artifact.file_name()),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(if exists {
+                                cx.theme().muted_foreground
+                            } else {
+                                cx.theme().danger
+                            })
+                            .truncate()
+                            .child(detail),
+                    ),
+            )
+            .child(
+                Button::new(("artifact-open", index))
+                    .ghost()
+                    .xsmall()
+                    .label(t("artifacts-open"))
+                    .disabled(!exists)
+                    .on_click(move |_event, _window, cx| {
+                        cx.open_with_system(&open_path);
+                    }),
+            )
+    }
+}
+
+impl Render for ArtifactGalleryPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.refresh(cx);
+        let visible = self.filtered();
+        let summary = match (self.artifacts.len(), visible.len()) {
+            (0, _) => t("artifacts-none"),
+            (total, shown) if total == shown => {
+                t_args("artifacts-count", &[("total", total.to_string().as_str())])
+            }
+            (total, shown) => t_args(
+                "artifacts-count-filtered",
+                &[
+                    ("shown", shown.to_string().as_str()),
+                    ("total", total.to_string().as_str()),
+                ],
+            ),
+        };
+        let can_export = !visible.is_empty() && !self.exporting;
+
+        div()
+            .id("artifact-gallery-panel")
+            .flex_shrink_0()
+            .w(px(PANEL_WIDTH))
+            .h_full()
+            .flex()
+            .flex_col()
+            .border_l_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().secondary.opacity(0.4))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .px_3()
+                    .py_2()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .flex_1()
+                            .min_w_0()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(cx.theme().foreground)
+                                    .child(t("artifacts-title")),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(summary),
+                            ),
+                    )
+                    .child(
+                        Button::new("artifact-gallery-close")
+                            .ghost()
+                            .xsmall()
+                            .icon(IconName::Close)
+                            .on_click(cx.listener(|_panel, _event, _window, cx| {
+                                cx.emit(ArtifactGalleryEvent::Close);
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .px_3()
+                    .py_2()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(self.render_kind_filter(cx))
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(self.render_turn_filter(cx))
+                            .child(div().flex_1())
+                            .child(
+                                Button::new("artifact-export")
+                                    .primary()
+                                    .xsmall()
+                                    .label(if self.exporting {
+                                        t("artifacts-exporting")
+                                    } else {
+                                        t("artifacts-export")
+                                    })
+                                    .disabled(!can_export)
+                                    .on_click(cx.listener(|panel, _event, _window, cx| {
+                                        panel.export(cx);
+                                    })),
+                            ),
+                    )
+                    .when_some(self.status.clone(), |this, status| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(status),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .id("artifact-gallery-list")
+                    .flex_1()
+                    .min_h_0()
+                    .overflow_y_scroll()
+                    .py_2()
+                    .when(visible.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .px_3()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(if self.artifacts.is_empty() {
+                                    t("artifacts-empty")
+                                } else {
+                                    t("artifacts-no-matches")
+                                }),
+                        )
+                    })
+                    .children(
+                        visible
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, artifact)| self.render_artifact(index, artifact, cx)),
+                    ),
+            )
+    }
+}
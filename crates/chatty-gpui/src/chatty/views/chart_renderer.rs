@@ -1,6 +1,7 @@
 use crate::assets::CustomIcon;
 use crate::chatty::services::MermaidRendererService;
 use crate::chatty::services::chart_svg_renderer;
+use crate::i18n::t;
 use chatty_core::tools::chart_tool::{CandlestickDataPoint, ChartSpec, SeriesData};
 use gpui::*;
 use gpui_component::ActiveTheme;
@@ -46,7 +47,7 @@ fn build_chart_copy_png_button(
     .ghost()
     .xsmall()
     .icon(Icon::new(CustomIcon::Image))
-    .tooltip("Copy as PNG")
+    .tooltip(t("action-copy-png"))
     .on_click(move |_event, _window, _cx| {
         let svg_str = chart_svg_renderer::render_chart_svg(&spec_clone, &colors);
 
@@ -491,7 +492,7 @@ pub(super) fn render_chart(
                     div()
                         .text_sm()
                         .text_color(cx.theme().muted_foreground)
-                        .child("No candlestick data provided"),
+                        .child(t("chart-no-candlestick-data")),
                 );
             }
         }
@@ -8,6 +8,9 @@
 //!   state, selection index, and file cache.
 //! - `render_at_menu` — the popover element shown above the input.
 //!
+//! The same picker lists workspace path completions when a path-like token
+//! is typed instead of an `@` query (see `path_completion.rs`).
+//!
 //! Helpers are `pub` (re-exported by `chat_input/mod.rs`) because the
 //! `chat_input_test.rs` unit tests exercise them directly.
 
@@ -17,6 +20,7 @@ use gpui_component::ActiveTheme;
 use gpui_component::scroll::ScrollableElement;
 
 use super::ChatInputState;
+use super::path_completion::path_token_from;
 
 // ---------------------------------------------------------------------------
 // @ mention / file picker — pure helpers
@@ -127,9 +131,17 @@ impl ChatInputState {
         !self.at_menu_items_for_text(&text).is_empty()
     }
 
-    /// Return filtered file items for `text` using the cached file list.
-    pub fn at_menu_items_for_text<'a>(&'a self, text: &str) -> Vec<&'a String> {
-        at_menu_items_for(text, &self.at_menu_files)
+    /// Return the picker items for `text`: files from the cached list for an
+    /// `@` query, otherwise workspace completions for a path-like token.
+    pub fn at_menu_items_for_text(&self, text: &str) -> Vec<String> {
+        if at_query_from(text).is_some() {
+            at_menu_items_for(text, &self.at_menu_files)
+                .into_iter()
+                .cloned()
+                .collect()
+        } else {
+            self.path_completions_for_text(text)
+        }
     }
 
     /// Current highlighted index in the `@` picker.
@@ -139,7 +151,9 @@ impl ChatInputState {
 
     /// Reset the `@` selection to 0 only when the query text actually changes.
     pub fn reset_at_menu_selection_if_query_changed(&mut self, new_text: &str) {
-        let query_raw = at_query_from(new_text).unwrap_or_default();
+        let query_raw = at_query_from(new_text)
+            .or_else(|| path_token_from(new_text).map(str::to_string))
+            .unwrap_or_default();
         let changed = self
             .last_at_query
             .as_deref()
@@ -203,18 +217,26 @@ impl ChatInputState {
         false
     }
 
-    /// Return the number of `@` mention items matching the current `input_text`.
+    /// Return the number of picker items matching the current `input_text`.
     /// Used by the keystroke interceptor without needing direct field access.
     pub fn at_items_count_for_input(&self, input_text: &str) -> usize {
-        at_menu_items_for(input_text, &self.at_menu_files).len()
+        self.at_menu_items_for_text(input_text).len()
     }
 
     /// Apply the currently highlighted `@` mention.
     ///
     /// The `@<query>` suffix of the current input is replaced with
-    /// `@<selected_filename> ` via `pending_at_insert`.
+    /// `@<selected_filename> ` via `pending_at_insert`; a path-like token is
+    /// replaced with the selected workspace path.
     pub fn apply_at_mention(&mut self, cx: &mut Context<Self>) {
         let input_text = self.input.read(cx).text().to_string();
+        if at_query_from(&input_text).is_none() {
+            self.apply_path_completion(&input_text);
+            self.at_menu_selected = 0;
+            self.at_menu_scroll_handle.scroll_to_item(0);
+            self.last_at_query = None;
+            return;
+        }
         let items = at_menu_items_for(&input_text, &self.at_menu_files);
         if items.is_empty() {
             return;
@@ -0,0 +1,54 @@
+//! Cold-start hints in the input toolbar.
+//!
+//! When the selected model runs on Ollama and Ollama reports it is not
+//! loaded, the input toolbar shows an amber "Not loaded" label so the user
+//! knows the first reply will wait for the model to load. The loaded models
+//! come from the footer's status poll (`OllamaStatusState`).
+//!
+//! While the conversation's tools, agent and shell are prepared in the
+//! background (started when the user begins typing), a muted "Preparing
+//! tools…" label shows instead.
+
+use gpui::*;
+use gpui_component::ActiveTheme;
+use gpui_component::tooltip::Tooltip;
+
+use crate::i18n::{t, t_args};
+use crate::settings::models::models_store::ModelsModel;
+use crate::settings::models::providers_store::ProviderType;
+use crate::settings::providers::OllamaStatusState;
+
+/// Ollama name of the selected model when sending to it would load it first.
+pub(super) fn cold_start_model(selected_model_id: Option<&str>, cx: &App) -> Option<String> {
+    let model = cx
+        .try_global::<ModelsModel>()?
+        .get_model(selected_model_id?)?;
+    if !matches!(model.provider_type, ProviderType::Ollama) {
+        return None;
+    }
+    cx.try_global::<OllamaStatusState>()?
+        .needs_cold_start(&model.model_identifier)
+        .then(|| model.model_identifier.clone())
+}
+
+/// Amber "Not loaded" label with an explanation in the tooltip.
+pub(super) fn render_cold_start_warning(model: String) -> impl IntoElement {
+    let tooltip = t_args("chat-cold-start-tooltip", &[("model", model.as_str())]);
+
+    div()
+        .id("cold-start-warning")
+        .text_xs()
+        .text_color(rgb(0xF59E0B))
+        .child(t("chat-cold-start"))
+        .tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx))
+}
+
+/// Muted label shown while the conversation is pre-warmed.
+pub(super) fn render_preparing_tools(cx: &App) -> impl IntoElement {
+    div()
+        .id("preparing-tools")
+        .text_xs()
+        .text_color(cx.theme().muted_foreground)
+        .child(t("chat-preparing-tools"))
+        .tooltip(|window, cx| Tooltip::new(t("chat-preparing-tools-tooltip")).build(window, cx))
+}
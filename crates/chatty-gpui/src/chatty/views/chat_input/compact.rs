@@ -0,0 +1,79 @@
+//! Input menu of the compact layout.
+//!
+//! In a narrow window the model picker and the history popover don't fit
+//! in the toolbar next to Send, so they are stacked into a single menu
+//! behind the provider icon. `render.rs` shows it instead of the two
+//! popovers while `app_view::is_compact_layout` holds.
+
+use chatty_core::models::HistoryPolicy;
+use chatty_core::models::history_policy::LAST_TURNS_PRESETS;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::menu::{DropdownMenu, PopupMenuItem};
+use gpui_component::{Icon, IconName, Sizable};
+
+use super::render::provider_icon;
+use super::{ChatInputEvent, ChatInputState};
+use crate::i18n::t;
+
+/// Menu button listing the models and history policies.
+pub(super) fn render_input_menu(state: &Entity<ChatInputState>, cx: &App) -> impl IntoElement {
+    let icon = match state.read(cx).selected_model() {
+        Some(model) => Icon::new(provider_icon(&model.provider_type)),
+        None => Icon::new(IconName::Ellipsis),
+    };
+    let state = state.clone();
+
+    Button::new("input-menu")
+        .icon(icon.size_3())
+        .ghost()
+        .small()
+        .tooltip(state.read(cx).get_selected_model_display_name())
+        .dropdown_menu_with_anchor(Corner::BottomRight, move |menu, _, cx| {
+            let models = state.read(cx).available_models.clone();
+            let selected_id = state.read(cx).selected_model_id.clone();
+            let selected_policy = state.read(cx).history_policy();
+
+            let menu = if models.is_empty() {
+                menu.item(PopupMenuItem::new(t("chat-no-models")).disabled(true))
+            } else {
+                models.into_iter().fold(menu, |menu, model| {
+                    let state = state.clone();
+                    let checked = selected_id.as_ref() == Some(&model.id);
+                    menu.item(
+                        PopupMenuItem::new(model.name.clone())
+                            .checked(checked)
+                            .on_click(move |_, _, cx| {
+                                state.update(cx, |s, cx| {
+                                    s.selected_model_id = Some(model.id.clone());
+                                    cx.emit(ChatInputEvent::ModelChanged(model.id.clone()));
+                                    cx.notify();
+                                });
+                            }),
+                    )
+                })
+            };
+
+            let policies = std::iter::once(HistoryPolicy::Full)
+                .chain(
+                    LAST_TURNS_PRESETS
+                        .iter()
+                        .map(|&turns| HistoryPolicy::LastTurns { turns }),
+                )
+                .chain([HistoryPolicy::Summarized, HistoryPolicy::Relevant]);
+            policies.fold(
+                menu.separator()
+                    .item(PopupMenuItem::new(t("chat-history-heading")).disabled(true)),
+                |menu, policy| {
+                    let state = state.clone();
+                    menu.item(
+                        PopupMenuItem::new(policy.label())
+                            .checked(policy == selected_policy)
+                            .on_click(move |_, _, cx| {
+                                state.update(cx, |s, cx| s.set_history_policy(policy, cx));
+                            }),
+                    )
+                },
+            )
+        })
+}
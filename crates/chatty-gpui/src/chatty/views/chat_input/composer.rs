@@ -0,0 +1,160 @@
+//! Multi-line composer tools for the chat input.
+//!
+//! # What lives here
+//!
+//! - Pure helpers `wrap_in_code_block` and `quote_lines` (re-exported for
+//!   the unit tests).
+//! - `ChatInputState::format_selection` — apply a helper to the selected
+//!   draft text, or insert an empty block at the cursor.
+//! - `render_composer_tools` — the code block / quote / preview / expand
+//!   buttons in the input toolbar.
+//!
+//! The expanded and preview modes are general settings, so they survive
+//! restarts and apply to every conversation.
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{IconName, Sizable};
+
+use super::ChatInputState;
+use crate::i18n::t;
+use crate::settings::controllers::general_settings_controller;
+
+/// Height of the input in the expanded composer.
+pub(super) const EXPANDED_COMPOSER_HEIGHT: f32 = 280.0;
+
+/// Formatting applied by the composer toolbar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComposerFormat {
+    CodeBlock,
+    Quote,
+}
+
+/// Fence `selected` as a Markdown code block (an empty block when nothing is
+/// selected).
+pub fn wrap_in_code_block(selected: &str) -> String {
+    let body = selected.trim_end_matches('\n');
+    format!("```\n{body}\n```\n")
+}
+
+/// Prefix every line of `selected` with `> `.
+pub fn quote_lines(selected: &str) -> String {
+    if selected.is_empty() {
+        return "> ".to_string();
+    }
+    selected
+        .trim_end_matches('\n')
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl ChatInputState {
+    /// Replace the selected draft text with its formatted version.
+    pub fn format_selection(
+        &mut self,
+        format: ComposerFormat,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.input.update(cx, |input, cx| {
+            let Some(selection) = input.selected_text_range(true, window, cx) else {
+                return;
+            };
+            let selected = input
+                .text_for_range(selection.range.clone(), &mut None, window, cx)
+                .unwrap_or_default();
+            let formatted = match format {
+                ComposerFormat::CodeBlock => wrap_in_code_block(&selected),
+                ComposerFormat::Quote => quote_lines(&selected),
+            };
+            input.replace_text_in_range(Some(selection.range), &formatted, window, cx);
+            input.focus(window, cx);
+        });
+    }
+}
+
+/// Code block, quote, preview and expand buttons.
+pub(super) fn render_composer_tools(
+    state: &Entity<ChatInputState>,
+    expanded: bool,
+    preview: bool,
+) -> impl IntoElement {
+    let state_for_code = state.clone();
+    let state_for_quote = state.clone();
+
+    div()
+        .flex()
+        .flex_row()
+        .items_center()
+        .child(
+            Button::new("composer-code-block")
+                .ghost()
+                .xsmall()
+                .label("</>")
+                .tooltip(t("chat-composer-code-block"))
+                .on_click(move |_, window, cx| {
+                    state_for_code.update(cx, |state, cx| {
+                        state.format_selection(ComposerFormat::CodeBlock, window, cx);
+                    });
+                }),
+        )
+        .child(
+            Button::new("composer-quote")
+                .ghost()
+                .xsmall()
+                .label("❝")
+                .tooltip(t("chat-composer-quote"))
+                .on_click(move |_, window, cx| {
+                    state_for_quote.update(cx, |state, cx| {
+                        state.format_selection(ComposerFormat::Quote, window, cx);
+                    });
+                }),
+        )
+        .when(expanded, |d| {
+            d.child(
+                Button::new("composer-preview")
+                    .ghost()
+                    .xsmall()
+                    .icon(if preview {
+                        IconName::EyeOff
+                    } else {
+                        IconName::Eye
+                    })
+                    .tooltip(if preview {
+                        t("chat-composer-hide-preview")
+                    } else {
+                        t("chat-composer-show-preview")
+                    })
+                    .on_click(move |_, _, cx| {
+                        general_settings_controller::set_composer_preview(cx, !preview);
+                    }),
+            )
+        })
+        .child(
+            Button::new("composer-expand")
+                .ghost()
+                .xsmall()
+                .icon(if expanded {
+                    IconName::ChevronDown
+                } else {
+                    IconName::ChevronUp
+                })
+                .tooltip(if expanded {
+                    t("chat-composer-collapse")
+                } else {
+                    t("chat-composer-expand")
+                })
+                .on_click(move |_, _, cx| {
+                    general_settings_controller::set_composer_expanded(cx, !expanded);
+                }),
+        )
+}
@@ -0,0 +1,116 @@
+//! Live token estimate for the chat input.
+//!
+//! # What lives here
+//!
+//! - `ChatInputState` methods that count the draft with the selected
+//!   model's tokenizer and estimate staged attachments in the background.
+//! - `render_draft_tokens` — the `~N tokens` label in the input toolbar,
+//!   turning amber when the message alone exceeds the warning threshold
+//!   from the general settings.
+//!
+//! The estimation rules live in `chatty_core::token_budget::draft`.
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme;
+use gpui_component::tooltip::Tooltip;
+use std::path::PathBuf;
+
+use chatty_core::models::token_usage::format_tokens;
+use chatty_core::token_budget::counter::TokenCounter;
+use chatty_core::token_budget::draft::{IMAGE_TOKENS, estimate_attachment_tokens};
+
+use super::ChatInputState;
+use crate::i18n::t_args;
+use crate::settings::models::TokenTrackingSettings;
+use crate::settings::models::models_store::ModelsModel;
+
+impl ChatInputState {
+    /// Re-count `text` with the selected model's tokenizer. Counts nothing
+    /// while token tracking is off. Returns whether the count changed.
+    pub fn refresh_draft_tokens(&mut self, text: &str, cx: &App) -> bool {
+        let enabled = cx
+            .try_global::<TokenTrackingSettings>()
+            .is_none_or(|s| s.enabled);
+        let tokens = if enabled {
+            let model_identifier = self
+                .selected_model_id
+                .as_deref()
+                .and_then(|id| cx.try_global::<ModelsModel>()?.get_model(id))
+                .map(|model| model.model_identifier.as_str())
+                .unwrap_or_default();
+            TokenCounter::for_model(model_identifier).count(text.trim())
+        } else {
+            0
+        };
+        let changed = tokens != self.draft_text_tokens;
+        self.draft_text_tokens = tokens;
+        changed
+    }
+
+    /// Estimated tokens of the draft text plus its staged attachments.
+    /// Attachments still being estimated count as zero.
+    pub fn draft_token_estimate(&self) -> usize {
+        let attachments: usize = self
+            .attachments
+            .iter()
+            .filter_map(|path| self.attachment_tokens.get(path))
+            .sum();
+        self.draft_text_tokens + attachments
+    }
+
+    /// Estimate an attachment's tokens off the UI thread (PDFs are read to
+    /// count their pages).
+    pub(super) fn start_attachment_token_estimate(&self, path: PathBuf, cx: &mut Context<Self>) {
+        if self.attachment_tokens.contains_key(&path) {
+            return;
+        }
+        cx.spawn(async move |this, cx| {
+            let task_path = path.clone();
+            let tokens =
+                tokio::task::spawn_blocking(move || estimate_attachment_tokens(&task_path))
+                    .await
+                    .unwrap_or(IMAGE_TOKENS);
+            this.update(cx, |state, cx| {
+                state.attachment_tokens.insert(path, tokens);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+}
+
+/// `~N tokens` label; amber with an explanation when `tokens` is above
+/// `warning_threshold` (0 = never warn).
+pub(super) fn render_draft_tokens(
+    tokens: usize,
+    warning_threshold: usize,
+    cx: &App,
+) -> impl IntoElement {
+    let over = warning_threshold > 0 && tokens > warning_threshold;
+    let label = t_args(
+        "chat-draft-tokens",
+        &[("count", format_tokens(tokens as u32).as_str())],
+    );
+
+    div()
+        .id("draft-token-count")
+        .text_xs()
+        .text_color(if over {
+            rgb(0xF59E0B).into()
+        } else {
+            cx.theme().muted_foreground
+        })
+        .child(label)
+        .when(over, |d| {
+            let tooltip = t_args(
+                "chat-draft-tokens-warning",
+                &[
+                    ("count", format_tokens(tokens as u32).as_str()),
+                    ("limit", format_tokens(warning_threshold as u32).as_str()),
+                ],
+            );
+            d.tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx))
+        })
+}
@@ -5,6 +5,17 @@
 //! - `ChatInputState` entity — text buffer, attachment list, selected
 //!   model/provider, capabilities (image/PDF support), slash-command and
 //!   skill (`@`-mention) popovers.
+//! - The speech mode toggle and voice turn phase (`voice.rs`).
+//! - Spell checking of the draft and the "Polish" rewrite (`spelling.rs`).
+//! - Live token estimate of the draft and attachments (`draft_tokens.rs`).
+//! - "Not loaded" hint for Ollama models that need a cold start
+//!   (`cold_start.rs`).
+//! - Multi-line composer: formatting helpers, expand and Markdown preview
+//!   (`composer.rs`).
+//! - The menu that stacks the model and history pickers in a narrow
+//!   window (`compact.rs`).
+//! - Messages sent while a reply streams, held as "Pending" bubbles until
+//!   the turn ends (`queue.rs`).
 //! - `ChatInputEvent` — events emitted to `ChattyApp` / `ChatView`
 //!   (send, model change, attachment added/removed, slash command, …).
 //! - Keyboard handling, drag-and-drop, paste of images/files, autocomplete
@@ -21,8 +32,16 @@
 //! Capability Architecture").
 
 mod at_mention;
+mod cold_start;
+mod compact;
+mod composer;
+mod draft_tokens;
+mod path_completion;
+mod queue;
 mod render;
 mod slash;
+mod spelling;
+mod voice;
 
 // Re-export the public surface for external callers and the unit-test
 // module so the `chat_input::*` namespace is unchanged after the
@@ -32,10 +51,18 @@ pub use at_mention::load_files_for_dir;
 #[cfg(test)]
 pub use at_mention::{apply_at_to_input, at_menu_items_for, at_query_from};
 #[cfg(test)]
-pub use slash::slash_menu_items_for;
-pub use slash::{SkillEntry, slash_menu_items_with_skills};
+pub use composer::{quote_lines, wrap_in_code_block};
+#[cfg(test)]
+pub use path_completion::{apply_path_to_input, path_token_from};
+#[cfg(test)]
+pub use queue::merge_into_draft;
+pub use slash::SkillEntry;
 #[allow(unused_imports)] // SlashCommand / SlashMenuItem are part of the public API
 pub use slash::{SlashCommand, SlashMenuItem};
+#[cfg(test)]
+pub use slash::{
+    slash_menu_items_for, slash_menu_items_with_skills, slash_menu_items_with_snippets,
+};
 
 use gpui::*;
 use gpui_component::input::InputState;
@@ -45,8 +72,14 @@ use tracing::{debug, warn};
 
 use super::attachment_validation::validate_attachment;
 use crate::chatty::services::pdf_thumbnail::render_pdf_thumbnail;
+use crate::chatty::services::video_frames::{DEFAULT_VIDEO_FRAMES, sample_video};
 use crate::settings::models::providers_store::ProviderType;
-use std::collections::HashMap;
+use chatty_core::models::{HistoryPolicy, TurnOverrides};
+use chatty_core::services::spell_checker::Misspelling;
+use chatty_core::services::workspace_file_index::WorkspaceFileIndex;
+use queue::QueuedMessage;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 use tokio::sync::RwLock;
 
 // ---------------------------------------------------------------------------
@@ -59,12 +92,27 @@ pub enum ChatInputEvent {
     Send {
         message: String,
         attachments: Vec<PathBuf>,
+        /// Parameters for this message only (empty when none were chosen)
+        overrides: TurnOverrides,
     },
     ModelChanged(String),
     Stop,
     /// A slash command that should be executed immediately (no args required).
     SlashCommandSelected(String),
     WorkingDirChanged(Option<PathBuf>),
+    /// Start or end the hands-free voice conversation.
+    SpeechModeToggled,
+    HistoryPolicyChanged(HistoryPolicy),
+    /// A snippet was inserted from the slash picker (carries its name).
+    SnippetInserted(String),
+    /// Rewrite the draft's spelling and grammar with the selected model.
+    PolishRequested {
+        draft: String,
+        model_id: String,
+    },
+    /// The user started typing into an empty input; a chance to prepare the
+    /// conversation's agent and shell before the message is sent.
+    DraftStarted,
 }
 
 impl EventEmitter<ChatInputEvent> for ChatInputState {}
@@ -74,6 +122,7 @@ pub struct ModelOption {
     pub id: String,
     pub name: String,
     pub provider_type: ProviderType,
+    pub aliases: Vec<String>,
 }
 
 impl ModelOption {
@@ -82,8 +131,14 @@ impl ModelOption {
             id,
             name,
             provider_type,
+            aliases: Vec::new(),
         }
     }
+
+    pub fn with_aliases(mut self, aliases: Vec<String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
 }
 
 /// State for the chat input component
@@ -114,6 +169,14 @@ pub struct ChatInputState {
     pending_slash_insert: Option<String>,
     /// Per-conversation working directory override (None = use global workspace_dir setting)
     working_dir: Option<PathBuf>,
+    /// Per-conversation history policy shown in the parameters popover
+    history_policy: HistoryPolicy,
+    /// Model, temperature and reasoning effort chosen in the send button's
+    /// popover; used for the next message only
+    turn_overrides: TurnOverrides,
+    /// Rules file (`.chatty/rules.md` or `AGENTS.md`) active for the current
+    /// workspace, shown as a badge next to the working directory
+    workspace_rules: Option<PathBuf>,
     /// Filesystem skills loaded from the workspace `.claude/skills/` and global skills
     /// directories.  Updated whenever the working directory changes.
     available_skills: Vec<SkillEntry>,
@@ -128,6 +191,33 @@ pub struct ChatInputState {
     last_at_query: Option<String>,
     /// When set, this text is written into the input on the next render frame.
     pending_at_insert: Option<String>,
+    /// Where the voice turn stands while speech mode is on
+    speech_phase: Option<chatty_core::services::speech_mode::SpeechPhase>,
+    /// Workspace file index for path completions and when it was built
+    /// (only kept while code execution is enabled).
+    path_index: Option<(WorkspaceFileIndex, Instant)>,
+    /// Directory whose file index is being built in the background.
+    indexing_dir: Option<PathBuf>,
+    /// Misspelled words in the current draft, underlined below the input
+    misspellings: Vec<Misspelling>,
+    /// Words the user chose to ignore for this session
+    ignored_words: HashSet<String>,
+    /// A "Polish" rewrite is in flight
+    polishing: bool,
+    /// Replacement for the whole draft (spelling fix or polished rewrite),
+    /// written into the input on the next render frame.
+    pending_text_replace: Option<String>,
+    /// Tokens of the draft text with the selected model's tokenizer
+    draft_text_tokens: usize,
+    /// Estimated tokens per attachment, filled in the background
+    attachment_tokens: HashMap<PathBuf, usize>,
+    /// Messages sent while a reply was streaming, dispatched in order when
+    /// the turn ends
+    queued_messages: Vec<QueuedMessage>,
+    /// The input was empty at the last text change
+    draft_empty: bool,
+    /// Tools, agent and shell for the conversation are being prepared
+    preparing_tools: bool,
 }
 
 impl ChatInputState {
@@ -147,12 +237,27 @@ impl ChatInputState {
             last_slash_query: None,
             pending_slash_insert: None,
             working_dir: None,
+            history_policy: HistoryPolicy::default(),
+            turn_overrides: TurnOverrides::default(),
+            workspace_rules: None,
             available_skills: Vec::new(),
             at_menu_files: Vec::new(),
             at_menu_selected: 0,
             at_menu_scroll_handle: ScrollHandle::new(),
             last_at_query: None,
             pending_at_insert: None,
+            speech_phase: None,
+            path_index: None,
+            indexing_dir: None,
+            misspellings: Vec::new(),
+            ignored_words: HashSet::new(),
+            polishing: false,
+            pending_text_replace: None,
+            draft_text_tokens: 0,
+            attachment_tokens: HashMap::new(),
+            queued_messages: Vec::new(),
+            draft_empty: true,
+            preparing_tools: false,
         }
     }
 
@@ -204,6 +309,26 @@ impl ChatInputState {
         self.is_streaming
     }
 
+    /// Track whether the draft is empty, emitting `DraftStarted` when the
+    /// first text is typed.
+    pub fn note_draft_text(&mut self, text: &str, cx: &mut Context<Self>) {
+        let was_empty = self.draft_empty;
+        self.draft_empty = text.trim().is_empty();
+        if was_empty && !self.draft_empty {
+            cx.emit(ChatInputEvent::DraftStarted);
+        }
+    }
+
+    /// Show or hide the "preparing tools" hint in the toolbar
+    pub fn set_preparing_tools(&mut self, preparing: bool, cx: &mut Context<Self>) {
+        self.preparing_tools = preparing;
+        cx.notify();
+    }
+
+    pub fn is_preparing_tools(&self) -> bool {
+        self.preparing_tools
+    }
+
     /// Get the per-conversation working directory override currently shown in the input UI
     pub fn working_dir(&self) -> Option<&PathBuf> {
         self.working_dir.as_ref()
@@ -223,8 +348,49 @@ impl ChatInputState {
         self.working_dir = dir;
     }
 
+    /// Rules file applied to the current workspace, if any
+    pub fn workspace_rules(&self) -> Option<&PathBuf> {
+        self.workspace_rules.as_ref()
+    }
+
+    /// Update the workspace rules badge
+    pub fn set_workspace_rules(&mut self, path: Option<PathBuf>, cx: &mut Context<Self>) {
+        if self.workspace_rules != path {
+            self.workspace_rules = path;
+            cx.notify();
+        }
+    }
+
+    /// Get the per-conversation history policy currently shown in the input UI
+    pub fn history_policy(&self) -> HistoryPolicy {
+        self.history_policy
+    }
+
+    /// Set the per-conversation history policy and emit event
+    pub fn set_history_policy(&mut self, policy: HistoryPolicy, cx: &mut Context<Self>) {
+        self.history_policy = policy;
+        cx.emit(ChatInputEvent::HistoryPolicyChanged(policy));
+        cx.notify();
+    }
+
+    /// Set the history policy without emitting an event (for restoring state on conversation load)
+    pub fn set_history_policy_silent(&mut self, policy: HistoryPolicy) {
+        self.history_policy = policy;
+    }
+
+    /// Parameters chosen for the next message only
+    pub fn turn_overrides(&self) -> &TurnOverrides {
+        &self.turn_overrides
+    }
+
+    /// Set the parameters for the next message; cleared once it is sent
+    pub fn set_turn_overrides(&mut self, overrides: TurnOverrides, cx: &mut Context<Self>) {
+        self.turn_overrides = overrides;
+        cx.notify();
+    }
+
     /// Add file attachments with validation
-    pub fn add_attachments(&mut self, paths: Vec<PathBuf>, _cx: &mut Context<Self>) {
+    pub fn add_attachments(&mut self, paths: Vec<PathBuf>, cx: &mut Context<Self>) {
         for path in paths {
             if self.attachments.contains(&path) {
                 warn!(?path, "File already attached");
@@ -243,7 +409,16 @@ impl ChatInputState {
                         .unwrap_or(false)
                     {
                         self.start_thumbnail_generation_for_pdf(path.clone());
+                    } else if path
+                        .extension()
+                        .map(|ext| {
+                            super::attachment_validation::is_video_extension(&ext.to_string_lossy())
+                        })
+                        .unwrap_or(false)
+                    {
+                        self.start_frame_sampling_for_video(path.clone());
                     }
+                    self.start_attachment_token_estimate(path.clone(), cx);
                     self.attachments.push(path);
                 }
                 Err(err) => {
@@ -253,6 +428,21 @@ impl ChatInputState {
         }
     }
 
+    /// Swap the staged attachment `original` for `replacement` (e.g. an
+    /// annotated copy of an image), keeping its position.
+    pub fn replace_attachment(
+        &mut self,
+        original: &std::path::Path,
+        replacement: PathBuf,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(slot) = self.attachments.iter_mut().find(|path| *path == original) {
+            *slot = replacement.clone();
+            self.start_attachment_token_estimate(replacement, cx);
+            cx.notify();
+        }
+    }
+
     /// Remove attachment by index
     pub fn remove_attachment(&mut self, index: usize) {
         if index < self.attachments.len() {
@@ -312,6 +502,44 @@ impl ChatInputState {
         });
     }
 
+    /// Sample frames from a staged video in the background so sending doesn't
+    /// wait on ffmpeg; the first frame becomes the chip's thumbnail.
+    fn start_frame_sampling_for_video(&self, video_path: PathBuf) {
+        let cache = self.thumbnail_cache.clone();
+
+        if let Ok(guard) = cache.try_read()
+            && guard.contains_key(&video_path)
+        {
+            return;
+        }
+
+        if let Ok(mut cache_write) = cache.try_write() {
+            cache_write
+                .entry(video_path.clone())
+                .or_insert_with(|| Err("Sampling...".to_string()));
+        }
+
+        tokio::spawn(async move {
+            let result = sample_video(&video_path, DEFAULT_VIDEO_FRAMES, None)
+                .await
+                .map_err(|e| format!("Failed to sample video: {}", e))
+                .and_then(|sample| {
+                    sample
+                        .frames
+                        .first()
+                        .map(|frame| frame.path.clone())
+                        .ok_or_else(|| "Video has no frames".to_string())
+                });
+            if let Err(e) = &result {
+                warn!(path = ?video_path, error = %e, "Video frame sampling failed");
+            }
+
+            if let Ok(mut cache_write) = cache.try_write() {
+                cache_write.insert(video_path, result);
+            }
+        });
+    }
+
     /// Send the current message
     pub fn send_message(&mut self, cx: &mut Context<Self>) {
         let message = self.input.read(cx).text().to_string();
@@ -328,6 +556,7 @@ impl ChatInputState {
         cx.emit(ChatInputEvent::Send {
             message: message.clone(),
             attachments: attachments.clone(),
+            overrides: std::mem::take(&mut self.turn_overrides),
         });
 
         self.should_clear = true;
@@ -371,6 +600,14 @@ impl ChatInputState {
                 input.insert(&text, window, cx);
             });
         }
+        // Apply a spelling correction or polished rewrite.
+        if let Some(text) = self.pending_text_replace.take() {
+            self.input.update(cx, |input, cx| {
+                input.set_value("", window, cx);
+                input.insert(&text, window, cx);
+            });
+            self.refresh_spelling(&text, cx);
+        }
     }
 
     /// Get the selected model ID
@@ -0,0 +1,140 @@
+//! Workspace path autocompletion for the chat input.
+//!
+//! # What lives here
+//!
+//! - Pure helpers (no UI context required): `path_token_from`,
+//!   `apply_path_to_input`.
+//! - `ChatInputState` methods that build the workspace file index in the
+//!   background and turn the typed path fragment into completions.
+//!
+//! Completions are shown in the `@` mention picker (`at_mention.rs`), which
+//! handles selection and insertion for both. They are only offered while
+//! code execution is enabled, since only then can the filesystem tools use
+//! the inserted paths.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use chatty_core::services::workspace_file_index::WorkspaceFileIndex;
+use gpui::*;
+
+use super::ChatInputState;
+
+/// Maximum number of path completions shown in the picker.
+const PATH_MENU_MAX_ITEMS: usize = 15;
+
+/// Rebuild the index when a path is typed after it has become this old,
+/// so files created in the meantime can be completed.
+const PATH_INDEX_MAX_AGE: Duration = Duration::from_secs(60);
+
+/// The path-like token being typed at the end of `input_text`, if any.
+///
+/// A token is path-like when it contains a `/` (`src/ma`, `./lib`), but it
+/// must not start with one (slash commands, absolute paths), be an `@`
+/// mention or a URL. Returns `None` once a space follows the token.
+pub fn path_token_from(input_text: &str) -> Option<&str> {
+    let last_line = input_text.lines().next_back().unwrap_or(input_text);
+    if last_line.ends_with(char::is_whitespace) {
+        return None;
+    }
+    let token = last_line
+        .rsplit(char::is_whitespace)
+        .next()
+        .filter(|token| !token.is_empty())?;
+    let path_like = token.contains('/')
+        && !token.starts_with('/')
+        && !token.starts_with('@')
+        && !token.contains("://");
+    path_like.then_some(token)
+}
+
+/// Build the replacement input text when a path completion is chosen: the
+/// path-like token at the end of the text is replaced with `path `.
+pub fn apply_path_to_input(input_text: &str, path: &str) -> String {
+    let input_text = input_text.trim_end_matches(['\r', '\n']);
+    let token_len = path_token_from(input_text).map_or(0, str::len);
+    let before = &input_text[..input_text.len() - token_len];
+    format!("{before}{path} ")
+}
+
+impl ChatInputState {
+    /// Workspace paths matching the path-like token at the end of `text`.
+    pub fn path_completions_for_text(&self, text: &str) -> Vec<String> {
+        let (Some(token), Some((index, _))) = (path_token_from(text), &self.path_index) else {
+            return Vec::new();
+        };
+        index
+            .complete(token, PATH_MENU_MAX_ITEMS)
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Build (or rebuild, once stale) the file index of the per-chat working
+    /// directory, falling back to `workspace_dir`, when a path-like token is
+    /// typed. Drops the index while execution is disabled.
+    pub fn refresh_path_index_if_needed(
+        &mut self,
+        input_text: &str,
+        workspace_dir: Option<PathBuf>,
+        execution_enabled: bool,
+        cx: &mut Context<Self>,
+    ) {
+        if !execution_enabled {
+            if self.path_index.take().is_some() {
+                cx.notify();
+            }
+            return;
+        }
+        if path_token_from(input_text).is_none() {
+            return;
+        }
+        let Some(dir) = self.working_dir.clone().or(workspace_dir) else {
+            return;
+        };
+        let fresh = self.path_index.as_ref().is_some_and(|(index, built_at)| {
+            index.root() == dir && built_at.elapsed() < PATH_INDEX_MAX_AGE
+        });
+        if fresh || self.indexing_dir.as_ref() == Some(&dir) {
+            return;
+        }
+
+        self.indexing_dir = Some(dir.clone());
+        cx.spawn(async move |this, cx| {
+            let task_dir = dir.clone();
+            let index =
+                tokio::task::spawn_blocking(move || WorkspaceFileIndex::build(&task_dir)).await;
+            this.update(cx, |state, cx| {
+                if state.indexing_dir.as_ref() == Some(&dir) {
+                    state.indexing_dir = None;
+                }
+                if let Ok(index) = index {
+                    state.path_index = Some((index, Instant::now()));
+                    cx.notify();
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Replace the typed path fragment with the highlighted completion, via
+    /// `pending_at_insert`. A path that no longer exists in the workspace is
+    /// not inserted; the index is dropped so it is rebuilt on the next key.
+    pub(super) fn apply_path_completion(&mut self, input_text: &str) {
+        let items = self.path_completions_for_text(input_text);
+        let selected = self.at_menu_selected.min(items.len().saturating_sub(1));
+        let Some(path) = items.get(selected) else {
+            return;
+        };
+        let valid = self
+            .path_index
+            .as_ref()
+            .is_some_and(|(index, _)| index.validate(path));
+        if !valid {
+            self.path_index = None;
+            return;
+        }
+        self.pending_at_insert = Some(apply_path_to_input(input_text, path));
+    }
+}
@@ -0,0 +1,187 @@
+//! Messages sent while a reply is still streaming.
+//!
+//! # What lives here
+//!
+//! - `QueuedMessage` and the `ChatInputState` methods that hold messages
+//!   back until the current turn ends, send them one at a time, or put them
+//!   back in the draft when the turn is stopped.
+//! - Pure helper `merge_into_draft` (re-exported for the unit tests).
+//! - `render_queued_messages` — the "Pending" bubbles above the input box.
+//!
+//! `ChattyApp` decides when to queue (the input is streaming and no
+//! `ask_user` question is waiting for an answer) and when to dispatch
+//! (the stream of the open conversation ended).
+
+use gpui::*;
+use gpui_component::ActiveTheme;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{IconName, Sizable};
+use std::path::PathBuf;
+
+use chatty_core::models::TurnOverrides;
+
+use super::{ChatInputEvent, ChatInputState};
+use crate::i18n::{t, t_args};
+
+/// A message waiting for the current turn to finish.
+#[derive(Clone, Debug)]
+pub struct QueuedMessage {
+    pub message: String,
+    pub attachments: Vec<PathBuf>,
+    pub overrides: TurnOverrides,
+}
+
+/// Join queued texts and the current draft into one draft, in that order,
+/// separated by blank lines. Empty parts are skipped.
+pub fn merge_into_draft<'a>(queued: impl IntoIterator<Item = &'a str>, draft: &str) -> String {
+    queued
+        .into_iter()
+        .chain(std::iter::once(draft))
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+// ---------------------------------------------------------------------------
+// ChatInputState — queue methods
+// ---------------------------------------------------------------------------
+
+impl ChatInputState {
+    /// Hold a message until the current turn finishes.
+    pub fn queue_message(
+        &mut self,
+        message: String,
+        attachments: Vec<PathBuf>,
+        overrides: TurnOverrides,
+        cx: &mut Context<Self>,
+    ) {
+        self.queued_messages.push(QueuedMessage {
+            message,
+            attachments,
+            overrides,
+        });
+        cx.notify();
+    }
+
+    /// Messages waiting to be sent, oldest first.
+    pub fn queued_messages(&self) -> &[QueuedMessage] {
+        &self.queued_messages
+    }
+
+    /// Drop the queued message at `index` without sending it.
+    pub fn remove_queued_message(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index < self.queued_messages.len() {
+            self.queued_messages.remove(index);
+            cx.notify();
+        }
+    }
+
+    /// Send the oldest queued message. Returns whether one was sent.
+    pub fn dispatch_next_queued(&mut self, cx: &mut Context<Self>) -> bool {
+        if self.queued_messages.is_empty() {
+            return false;
+        }
+        let QueuedMessage {
+            message,
+            attachments,
+            overrides,
+        } = self.queued_messages.remove(0);
+        cx.emit(ChatInputEvent::Send {
+            message,
+            attachments,
+            overrides,
+        });
+        cx.notify();
+        true
+    }
+
+    /// Move every queued message back into the draft (text, attachments and
+    /// the first chosen parameters) so nothing is sent without the user.
+    pub fn restore_queued_to_draft(&mut self, cx: &mut Context<Self>) {
+        if self.queued_messages.is_empty() {
+            return;
+        }
+        let queued = std::mem::take(&mut self.queued_messages);
+        let draft = self.input.read(cx).text().to_string();
+        self.pending_text_replace = Some(merge_into_draft(
+            queued.iter().map(|q| q.message.as_str()),
+            &draft,
+        ));
+        if self.turn_overrides.is_empty()
+            && let Some(q) = queued.iter().find(|q| !q.overrides.is_empty())
+        {
+            self.turn_overrides = q.overrides.clone();
+        }
+        let attachments = queued.into_iter().flat_map(|q| q.attachments).collect();
+        self.add_attachments(attachments, cx);
+        cx.notify();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pending bubbles
+// ---------------------------------------------------------------------------
+
+/// Right-aligned "Pending" bubbles for the queued messages, oldest first.
+pub(super) fn render_queued_messages(
+    queued: &[QueuedMessage],
+    state: &Entity<ChatInputState>,
+    cx: &App,
+) -> impl IntoElement {
+    div()
+        .flex()
+        .flex_col()
+        .items_end()
+        .gap_1()
+        .children(queued.iter().enumerate().map(|(ix, queued)| {
+            let state = state.clone();
+            let attachment_note = (!queued.attachments.is_empty()).then(|| {
+                t_args(
+                    "chat-queued-attachments",
+                    &[("count", queued.attachments.len().to_string().as_str())],
+                )
+            });
+            div()
+                .id(("queued-message", ix))
+                .flex()
+                .flex_row()
+                .items_start()
+                .gap_2()
+                .max_w(relative(0.8))
+                .px_3()
+                .py_2()
+                .rounded_lg()
+                .border_1()
+                .border_color(cx.theme().border)
+                .bg(cx.theme().muted)
+                .opacity(0.8)
+                .child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .min_w_0()
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(match attachment_note {
+                                    Some(note) => format!("{} · {note}", t("chat-queued")),
+                                    None => t("chat-queued"),
+                                }),
+                        )
+                        .child(div().text_sm().line_clamp(3).child(queued.message.clone())),
+                )
+                .child(
+                    Button::new(("remove-queued", ix))
+                        .ghost()
+                        .xsmall()
+                        .icon(IconName::Close)
+                        .tooltip(t("chat-queued-remove"))
+                        .on_click(move |_, _, cx| {
+                            state.update(cx, |state, cx| state.remove_queued_message(ix, cx));
+                        }),
+                )
+        }))
+}
@@ -4,8 +4,12 @@
 //!
 //! - `impl RenderOnce for ChatInput` — the giant element tree for the
 //!   composition area (text input + attachment chips + send/stop +
-//!   model picker + slash/at popovers).
-//! - `render_file_chip` — single-attachment thumbnail with remove button.
+//!   model picker + parameters popover + per-turn parameters popover +
+//!   slash/at popovers + misspelled words + "Polish" button + draft
+//!   token estimate + composer tools and Markdown preview + "Pending"
+//!   bubbles of queued messages + speech mode button).
+//! - `render_file_chip` — single-attachment thumbnail with remove button
+//!   (PDF chips open the inline viewer, image chips the annotation editor).
 //! - Local helpers `is_image`, `is_pdf`, `provider_icon`.
 //!
 //! This is split out so the visual layout can be reviewed and modified
@@ -16,23 +20,40 @@
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::ActiveTheme;
-use gpui_component::Icon;
-use gpui_component::button::Button;
+use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::input::Input;
 use gpui_component::popover::Popover;
 use gpui_component::scroll::ScrollableElement;
+use gpui_component::text::TextView;
 use gpui_component::tooltip::Tooltip;
+use gpui_component::{Icon, Sizable};
 use std::path::{Path, PathBuf};
 
 use crate::assets::CustomIcon;
+use crate::i18n::{self, t, t_args};
 use crate::settings::models::providers_store::ProviderType;
 
-use super::super::attachment_validation::{PDF_EXTENSION, is_image_extension};
+use super::super::app_view::is_compact_layout;
+use super::super::attachment_validation::{PDF_EXTENSION, is_image_extension, is_video_extension};
+use super::super::image_annotation_dialog::ImageAnnotationDialog;
+use super::super::message_component::open_pdf_in_viewer;
 use super::ThumbnailCache;
-use super::at_mention::{at_menu_items_for, render_at_menu};
-use super::slash::{render_slash_menu, slash_menu_items_with_skills};
+use super::at_mention::render_at_menu;
+use super::cold_start::{cold_start_model, render_cold_start_warning, render_preparing_tools};
+use super::compact::render_input_menu;
+use super::composer::{EXPANDED_COMPOSER_HEIGHT, render_composer_tools};
+use super::draft_tokens::render_draft_tokens;
+use super::queue::render_queued_messages;
+use super::slash::render_slash_menu;
+use super::spelling::render_misspellings;
+use super::voice::{render_voice_button, speech_mode_available};
 use super::{ChatInput, ChatInputEvent, ChatInputState};
+use crate::settings::models::GeneralSettingsModel;
 use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use chatty_core::models::history_policy::LAST_TURNS_PRESETS;
+use chatty_core::models::text_direction;
+use chatty_core::models::turn_overrides::TEMPERATURE_PRESETS;
+use chatty_core::models::{HistoryPolicy, ReasoningEffort, TurnOverrides};
 
 // ---------------------------------------------------------------------------
 // Path / type helpers
@@ -50,7 +71,13 @@ fn is_pdf(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
-fn provider_icon(provider_type: &ProviderType) -> CustomIcon {
+fn is_video(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| is_video_extension(&ext.to_string_lossy()))
+        .unwrap_or(false)
+}
+
+pub(super) fn provider_icon(provider_type: &ProviderType) -> CustomIcon {
     match provider_type {
         ProviderType::Ollama => CustomIcon::Ollama,
         ProviderType::OpenRouter => CustomIcon::OpenRouter,
@@ -65,13 +92,17 @@ fn render_file_chip(
     thumbnail_cache: &ThumbnailCache,
 ) -> impl IntoElement {
     let state_clone = state.clone();
+    let pdf_path = is_pdf(path).then(|| path.to_path_buf());
+    let image_path = is_image(path).then(|| path.to_path_buf());
+    let state_for_annotate = state.clone();
+    let placeholder = if is_video(path) { "VIDEO" } else { "PDF" };
 
     // Determine display path based on file type
     let display_path = if is_image(path) {
         // Images can be displayed directly
         Some(path.to_path_buf())
-    } else if is_pdf(path) {
-        // For PDFs, check cache (generation started in add_attachments)
+    } else if is_pdf(path) || is_video(path) {
+        // For PDFs and videos, check cache (generation started in add_attachments)
         // Use blocking read since we're not in a window context
         // Check the thumbnail cache (non-blocking)
         thumbnail_cache
@@ -91,6 +122,25 @@ fn render_file_chip(
         .justify_center()
         .overflow_hidden()
         .rounded_md()
+        .when_some(pdf_path, |d, pdf_path| {
+            // PDFs open in the inline viewer
+            d.cursor_pointer()
+                .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                    open_pdf_in_viewer(pdf_path.clone(), cx);
+                })
+        })
+        .when_some(image_path, |d, image_path| {
+            // Images open in the annotation editor
+            d.cursor_pointer()
+                .on_mouse_down(MouseButton::Left, move |_event, window, cx| {
+                    ImageAnnotationDialog::open(
+                        image_path.clone(),
+                        state_for_annotate.clone(),
+                        window,
+                        cx,
+                    );
+                })
+        })
         .when_some(display_path.clone(), |div, img_path| {
             div.child(
                 img(img_path)
@@ -100,7 +150,7 @@ fn render_file_chip(
             )
         })
         .when(display_path.is_none(), |d| {
-            // Show placeholder for PDFs (loading or no preview)
+            // Show placeholder for PDFs and videos (loading or no preview)
             d.child(
                 div()
                     .w_full()
@@ -111,7 +161,7 @@ fn render_file_chip(
                     .justify_center()
                     .text_xs()
                     .text_color(rgb(0x6b7280))
-                    .child("PDF"),
+                    .child(placeholder),
             )
         })
         .child(
@@ -135,24 +185,70 @@ fn render_file_chip(
                     state_clone.update(cx, |state, _cx| {
                         state.remove_attachment(index);
                     });
+                    cx.stop_propagation();
                 }),
         )
 }
 
+/// Section heading inside a popover menu.
+fn render_menu_heading(label: String, cx: &App) -> impl IntoElement {
+    div()
+        .px_3()
+        .py_1()
+        .text_xs()
+        .text_color(cx.theme().muted_foreground)
+        .child(label)
+}
+
+/// One choice in the turn parameters popover; clicking it applies `update`
+/// to the next message's overrides.
+fn render_override_option(
+    label: String,
+    selected: bool,
+    state: &Entity<ChatInputState>,
+    update: impl Fn(&mut TurnOverrides) + 'static,
+    cx: &App,
+) -> impl IntoElement {
+    let state = state.clone();
+    div()
+        .px_3()
+        .py_1()
+        .rounded_sm()
+        .cursor_pointer()
+        .text_sm()
+        .when(selected, |d| d.bg(cx.theme().secondary))
+        .hover(|style| style.bg(cx.theme().secondary))
+        .child(label)
+        .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+            state.update(cx, |s, cx| {
+                let mut overrides = s.turn_overrides().clone();
+                update(&mut overrides);
+                s.set_turn_overrides(overrides, cx);
+            });
+        })
+}
+
 // ---------------------------------------------------------------------------
 // RenderOnce impl
 // ---------------------------------------------------------------------------
 
 impl RenderOnce for ChatInput {
-    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let state_for_send = self.state.clone();
         let state_for_stop = self.state.clone();
+        let state_for_queue = self.state.clone();
         let state_for_model = self.state.clone();
+        let state_for_params = self.state.clone();
+        let state_for_overrides = self.state.clone();
         let state_for_image = self.state.clone();
         let state_for_pdf = self.state.clone();
         let state_for_dir = self.state.clone();
         let state_for_dir_reset = self.state.clone();
+        let state_for_polish = self.state.clone();
         let input_entity = self.state.read(cx).input.clone();
+        // Right-align the draft while it starts in a right-to-left script
+        let input_direction = text_direction::detect(&input_entity.read(cx).value())
+            .unwrap_or_else(|| i18n::layout_direction(cx));
 
         // Read capabilities and attachments
         let supports_images = self.state.read(cx).supports_images;
@@ -161,6 +257,10 @@ impl RenderOnce for ChatInput {
         let attachments = self.state.read(cx).get_attachments().to_vec();
         let is_streaming = self.state.read(cx).is_streaming();
 
+        // --- Speech mode ---
+        let speech_phase = self.state.read(cx).speech_phase();
+        let show_voice_button = speech_phase.is_some() || speech_mode_available(cx);
+
         // Read thumbnail cache (for PDF previews)
         let thumbnail_cache = self.state.read(cx).thumbnail_cache.clone();
 
@@ -172,6 +272,11 @@ impl RenderOnce for ChatInput {
             .map(PathBuf::from);
         let effective_working_dir = per_chat_working_dir.clone().or(global_workspace_dir);
         let has_working_dir_override = per_chat_working_dir.is_some();
+        let workspace_rules = self.state.read(cx).workspace_rules().cloned();
+        let rules_badge_colors = (cx.theme().accent, cx.theme().accent_foreground);
+
+        // Narrow window: model and history pickers share one menu
+        let compact = is_compact_layout(window);
 
         // Model display name
         let model_display = self.state.read(cx).get_selected_model_display_name();
@@ -180,20 +285,54 @@ impl RenderOnce for ChatInput {
 
         // --- Slash menu ---
         let input_text = input_entity.read(cx).text().to_string();
-        let available_skills = self.state.read(cx).available_skills.clone();
-        let menu_items = slash_menu_items_with_skills(&input_text, &available_skills);
+        let menu_items = self.state.read(cx).slash_menu_items(&input_text, cx);
         let slash_menu_selected = self.state.read(cx).slash_menu_selected();
 
         // --- @ mention menu ---
-        let at_items: Vec<String> = {
-            let state = self.state.read(cx);
-            at_menu_items_for(&input_text, &state.at_menu_files)
-                .into_iter()
-                .cloned()
-                .collect()
-        };
+        let at_items = self.state.read(cx).at_menu_items_for_text(&input_text);
         let at_menu_selected = self.state.read(cx).at_menu_selected();
 
+        // --- Composer mode ---
+        let (composer_expanded, composer_preview) = cx
+            .try_global::<GeneralSettingsModel>()
+            .map_or((false, false), |s| {
+                (s.composer_expanded, s.composer_preview)
+            });
+        let preview = (composer_expanded && composer_preview && !input_text.trim().is_empty())
+            .then(|| TextView::markdown("composer-preview-text", input_text.clone(), window, cx));
+
+        // --- Draft token estimate ---
+        let draft_tokens = self.state.read(cx).draft_token_estimate();
+        let draft_token_warning = cx
+            .try_global::<GeneralSettingsModel>()
+            .map_or(0, |s| s.draft_token_warning);
+
+        // --- Local model cold start ---
+        let cold_start = cold_start_model(self.state.read(cx).selected_model_id.as_deref(), cx);
+        let preparing_tools = self.state.read(cx).is_preparing_tools();
+
+        // --- Queued messages ---
+        let queued_messages = self.state.read(cx).queued_messages().to_vec();
+        let can_queue = is_streaming && (!input_text.trim().is_empty() || !attachments.is_empty());
+
+        // --- Spelling / polish ---
+        let misspellings = self.state.read(cx).misspellings().to_vec();
+        let is_polishing = self.state.read(cx).is_polishing();
+        let can_polish = !is_streaming && !input_text.trim().is_empty();
+        let polish_button = Button::new("polish-prompt")
+            .ghost()
+            .xsmall()
+            .label(if is_polishing {
+                t("chat-polishing")
+            } else {
+                t("chat-polish")
+            })
+            .disabled(is_polishing)
+            .tooltip(t("chat-polish-tooltip"))
+            .on_click(move |_, _, cx| {
+                state_for_polish.update(cx, |state, cx| state.request_polish(cx));
+            });
+
         // Model dropdown button
         let model_button = if let Some(model) = selected_model {
             Button::new("model-select")
@@ -229,7 +368,7 @@ impl RenderOnce for ChatInput {
                                 .py_2()
                                 .text_sm()
                                 .text_color(rgb(0x6b7280))
-                                .child("No Models Available"),
+                                .child(t("chat-no-models")),
                         )
                     })
                     .when(!models.is_empty(), |d| {
@@ -274,7 +413,19 @@ impl RenderOnce for ChatInput {
                                                             div()
                                                                 .text_sm()
                                                                 .child(model.name.clone()),
-                                                        ),
+                                                        )
+                                                        .when(!model.aliases.is_empty(), |d| {
+                                                            d.child(
+                                                                div()
+                                                                    .text_xs()
+                                                                    .text_color(
+                                                                        cx.theme().muted_foreground,
+                                                                    )
+                                                                    .child(
+                                                                        model.aliases.join(", "),
+                                                                    ),
+                                                            )
+                                                        }),
                                                 )
                                                 .child(
                                                     div()
@@ -300,9 +451,178 @@ impl RenderOnce for ChatInput {
                     })
             });
 
+        // Parameters popover: per-conversation history policy
+        let history_policy = self.state.read(cx).history_policy();
+        let params_popover = Popover::new("params-menu")
+            .trigger(
+                Button::new("params-select")
+                    .label(history_policy.label())
+                    .tooltip(t("chat-history-tooltip")),
+            )
+            .appearance(false)
+            .content(move |_, _window, cx| {
+                let state = state_for_params.clone();
+                let selected = state.read(cx).history_policy();
+                let options = std::iter::once(HistoryPolicy::Full)
+                    .chain(
+                        LAST_TURNS_PRESETS
+                            .iter()
+                            .map(|&turns| HistoryPolicy::LastTurns { turns }),
+                    )
+                    .chain([HistoryPolicy::Summarized, HistoryPolicy::Relevant]);
+
+                div()
+                    .flex()
+                    .flex_col()
+                    .bg(cx.theme().background)
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .rounded_md()
+                    .shadow_md()
+                    .p_1()
+                    .min_w(px(200.0))
+                    .child(
+                        div()
+                            .px_3()
+                            .py_1()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(t("chat-history-heading")),
+                    )
+                    .children(options.map(|policy| {
+                        let state_for_click = state.clone();
+                        div()
+                            .px_3()
+                            .py_2()
+                            .rounded_sm()
+                            .cursor_pointer()
+                            .text_sm()
+                            .when(policy == selected, |d| d.bg(cx.theme().secondary))
+                            .hover(|style| style.bg(cx.theme().secondary))
+                            .child(policy.label())
+                            .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                                state_for_click.update(cx, |s, cx| {
+                                    s.set_history_policy(policy, cx);
+                                });
+                            })
+                    }))
+            });
+
+        // Turn parameters popover next to Send: model, temperature and
+        // reasoning effort for the next message only
+        let turn_overrides = self.state.read(cx).turn_overrides().clone();
+        let overrides_button = if turn_overrides.is_empty() {
+            Button::new("turn-params-select")
+                .label("▾")
+                .tooltip(t("chat-turn-params-tooltip"))
+        } else {
+            Button::new("turn-params-select")
+                .label(t("chat-turn-params-once"))
+                .tooltip(t_args(
+                    "chat-turn-params-next",
+                    &[("summary", turn_overrides.summary().as_str())],
+                ))
+        };
+        let overrides_popover = Popover::new("turn-params-menu")
+            .trigger(overrides_button)
+            .appearance(false)
+            .content(move |_, _window, cx| {
+                let state = state_for_overrides.clone();
+                let current = state.read(cx).turn_overrides().clone();
+                let models = state.read(cx).available_models.clone();
+
+                div()
+                    .flex()
+                    .flex_col()
+                    .bg(cx.theme().background)
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .rounded_md()
+                    .shadow_md()
+                    .p_1()
+                    .min_w(px(220.0))
+                    .child(render_menu_heading(t("chat-turn-model-heading"), cx))
+                    .child(render_override_option(
+                        t("chat-turn-model-conversation"),
+                        current.model_id.is_none(),
+                        &state,
+                        |o| {
+                            o.model_id = None;
+                            o.model_name = None;
+                        },
+                        cx,
+                    ))
+                    .child(
+                        div()
+                            .max_h(px(160.0))
+                            .overflow_y_scrollbar()
+                            .flex()
+                            .flex_col()
+                            .children(models.into_iter().map(|model| {
+                                let selected = current.model_id.as_ref() == Some(&model.id);
+                                let label = model.name.clone();
+                                render_override_option(
+                                    label,
+                                    selected,
+                                    &state,
+                                    move |o| {
+                                        o.model_id = Some(model.id.clone());
+                                        o.model_name = Some(model.name.clone());
+                                    },
+                                    cx,
+                                )
+                            })),
+                    )
+                    .child(render_menu_heading(t("chat-turn-temperature-heading"), cx))
+                    .child(render_override_option(
+                        t("chat-turn-model-default"),
+                        current.temperature.is_none(),
+                        &state,
+                        |o| o.temperature = None,
+                        cx,
+                    ))
+                    .children(TEMPERATURE_PRESETS.iter().map(|&temperature| {
+                        render_override_option(
+                            format!("{temperature}"),
+                            current.temperature == Some(temperature),
+                            &state,
+                            move |o| o.temperature = Some(temperature),
+                            cx,
+                        )
+                    }))
+                    .child(render_menu_heading(t("chat-turn-reasoning-heading"), cx))
+                    .child(render_override_option(
+                        t("chat-turn-model-default"),
+                        current.reasoning_effort.is_none(),
+                        &state,
+                        |o| o.reasoning_effort = None,
+                        cx,
+                    ))
+                    .children(ReasoningEffort::ALL.into_iter().map(|effort| {
+                        render_override_option(
+                            effort.as_str().to_string(),
+                            current.reasoning_effort == Some(effort),
+                            &state,
+                            move |o| o.reasoning_effort = Some(effort),
+                            cx,
+                        )
+                    }))
+                    .when(!current.is_empty(), |d| {
+                        d.child(render_override_option(
+                            t("chat-turn-clear"),
+                            false,
+                            &state,
+                            |o| *o = TurnOverrides::default(),
+                            cx,
+                        ))
+                    })
+            });
+
         // Attachment button with popover (only shown when model supports it)
         let attachment_popover = if show_attachment_button {
-            let attach_button = Button::new("attach").label("+").tooltip("Add attachments");
+            let attach_button = Button::new("attach")
+                .label("+")
+                .tooltip(t("chat-attach-tooltip"));
 
             Some(
                 Popover::new("attachment-menu")
@@ -330,7 +650,7 @@ impl RenderOnce for ChatInput {
                                         .cursor_pointer()
                                         .hover(|style| style.bg(cx.theme().secondary))
                                         .text_sm()
-                                        .child("Image")
+                                        .child(t("chat-attach-image"))
                                         .on_mouse_down(
                                             MouseButton::Left,
                                             move |_event, _window, cx| {
@@ -343,7 +663,7 @@ impl RenderOnce for ChatInput {
                                                                 directories: false,
                                                                 multiple: true,
                                                                 prompt: Some(
-                                                                    "Select Images".into(),
+                                                                    t("chat-select-images").into(),
                                                                 ),
                                                             })
                                                         })
@@ -385,7 +705,7 @@ impl RenderOnce for ChatInput {
                                                                 directories: false,
                                                                 multiple: true,
                                                                 prompt: Some(
-                                                                    "Select PDF Files".into(),
+                                                                    t("chat-select-pdfs").into(),
                                                                 ),
                                                             })
                                                         })
@@ -428,7 +748,7 @@ impl RenderOnce for ChatInput {
                     cx,
                 ))
             })
-            // @ mention menu (visible when input ends with "@<query>")
+            // @ mention menu (visible when input ends with "@<query>" or a path)
             .when(!at_items.is_empty(), |d| {
                 let state_for_at = self.state.clone();
                 d.child(render_at_menu(
@@ -439,6 +759,10 @@ impl RenderOnce for ChatInput {
                     cx,
                 ))
             })
+            // Messages waiting for the streaming reply to finish
+            .when(!queued_messages.is_empty(), |d| {
+                d.child(render_queued_messages(&queued_messages, &self.state, cx))
+            })
             // Main input box
             .child(
                 div()
@@ -454,8 +778,28 @@ impl RenderOnce for ChatInput {
                                 div()
                                     .flex()
                                     .flex_row()
-                                    .child(Input::new(&input_entity).appearance(false)),
+                                    .when(input_direction.is_rtl(), |d| d.text_right())
+                                    .child(Input::new(&input_entity).appearance(false).when(
+                                        composer_expanded,
+                                        |input| input.h(px(EXPANDED_COMPOSER_HEIGHT)),
+                                    )),
                             )
+                            .when_some(preview, |d, preview| {
+                                d.child(
+                                    div()
+                                        .id("composer-preview")
+                                        .mt_2()
+                                        .pt_2()
+                                        .border_t_1()
+                                        .border_color(cx.theme().border)
+                                        .max_h(px(EXPANDED_COMPOSER_HEIGHT))
+                                        .overflow_y_scroll()
+                                        .child(preview),
+                                )
+                            })
+                            .when(!misspellings.is_empty(), |d| {
+                                d.child(render_misspellings(&misspellings, &self.state, cx))
+                            })
                             .child(
                                 div()
                                     .flex()
@@ -463,7 +807,12 @@ impl RenderOnce for ChatInput {
                                     .items_center()
                                     .gap_2()
                                     .when_some(attachment_popover, |d, popover| d.child(popover))
-                                    .when_some(effective_working_dir, |d, dir| {
+                                    .child(render_composer_tools(
+                                        &self.state,
+                                        composer_expanded,
+                                        composer_preview,
+                                    ))
+                                    .when_some(effective_working_dir.filter(|_| !compact), |d, dir| {
                                         // Compute display name: last path component or full path
                                         let dir_name = dir
                                             .file_name()
@@ -516,7 +865,7 @@ impl RenderOnce for ChatInput {
                                                                                         true,
                                                                                     multiple: false,
                                                                                     prompt: Some(
-                                                                                        "Select Working Directory".into(),
+                                                                                        t("chat-select-working-dir").into(),
                                                                                     ),
                                                                                 },
                                                                             )
@@ -561,7 +910,7 @@ impl RenderOnce for ChatInput {
                                                             .hover(|s| s.bg(rgb(0xe5e7eb)))
                                                             .tooltip(|window, cx| {
                                                                 Tooltip::new(
-                                                                    "Reset to global working directory",
+                                                                    t("chat-reset-working-dir"),
                                                                 )
                                                                 .build(window, cx)
                                                             })
@@ -582,11 +931,63 @@ impl RenderOnce for ChatInput {
                                                                 },
                                                             ),
                                                     )
+                                                })
+                                                .when_some(workspace_rules, |d, rules_path| {
+                                                    let rules_tooltip = t_args(
+                                                        "chat-workspace-rules-active",
+                                                        &[("path", rules_path.display().to_string().as_str())],
+                                                    );
+                                                    d.child(
+                                                        div()
+                                                            .id("workspace-rules-badge")
+                                                            .px_1p5()
+                                                            .py_0p5()
+                                                            .rounded_sm()
+                                                            .text_xs()
+                                                            .bg(rules_badge_colors.0)
+                                                            .text_color(rules_badge_colors.1)
+                                                            .tooltip(move |window, cx| {
+                                                                Tooltip::new(rules_tooltip.clone())
+                                                                    .build(window, cx)
+                                                            })
+                                                            .child(t("chat-workspace-rules")),
+                                                    )
                                                 }),
                                         )
                                     })
                                     .child(div().flex_grow())
-                                    .child(model_popover)
+                                    .when(show_voice_button, |d| {
+                                        d.child(render_voice_button(&self.state, speech_phase))
+                                    })
+                                    .when_some(cold_start, |d, model| {
+                                        d.child(render_cold_start_warning(model))
+                                    })
+                                    .when(preparing_tools, |d| d.child(render_preparing_tools(cx)))
+                                    .when(draft_tokens > 0, |d| {
+                                        d.child(render_draft_tokens(
+                                            draft_tokens,
+                                            draft_token_warning,
+                                            cx,
+                                        ))
+                                    })
+                                    .when(can_polish || is_polishing, |d| d.child(polish_button))
+                                    .when(!compact, |d| d.child(params_popover).child(model_popover))
+                                    .when(compact, |d| d.child(render_input_menu(&self.state, cx)))
+                                    .when(!is_streaming, |d| d.child(overrides_popover))
+                                    .when(can_queue, |d| {
+                                        d.child(
+                                            Button::new("queue-message")
+                                                .ghost()
+                                                .xsmall()
+                                                .label(t("chat-queue"))
+                                                .tooltip(t("chat-queue-tooltip"))
+                                                .on_click(move |_, _, cx| {
+                                                    state_for_queue.update(cx, |state, cx| {
+                                                        state.send_message(cx);
+                                                    });
+                                                }),
+                                        )
+                                    })
                                     .child(
                                         // Send/Stop button (conditional based on streaming state)
                                         div()
@@ -599,7 +1000,7 @@ impl RenderOnce for ChatInput {
                                                 // Stop button when streaming
                                                 div.bg(rgb(0xff4444))
                                                     .hover(|style| style.bg(rgb(0xff2222)))
-                                                    .child("Stop")
+                                                    .child(t("chat-stop"))
                                                     .on_mouse_down(
                                                         MouseButton::Left,
                                                         move |_event, _window, cx| {
@@ -616,7 +1017,7 @@ impl RenderOnce for ChatInput {
                                                 // Send button when not streaming
                                                 div.bg(rgb(0xffa033))
                                                     .hover(|style| style.bg(rgb(0xff8c1a)))
-                                                    .child("Send")
+                                                    .child(t("chat-send"))
                                                     .on_mouse_down(
                                                         MouseButton::Left,
                                                         move |_event, _window, cx| {
@@ -3,9 +3,9 @@
 //! # What lives here
 //!
 //! - `SlashCommand` / `SkillEntry` / `SlashMenuItem` types.
-//! - `slash_menu_items_for` / `slash_menu_items_with_skills` — pure
-//!   filtering helpers (also called from `chat_view` and from unit
-//!   tests).
+//! - `slash_menu_items_for` / `slash_menu_items_with_skills` /
+//!   `slash_menu_items_with_snippets` — pure filtering helpers (also
+//!   called from `chat_view` and from unit tests).
 //! - `ChatInputState` methods that manage the picker's open/closed
 //!   state, selection index, and command application.
 //! - `render_slash_menu` — the popover element shown above the input.
@@ -19,6 +19,8 @@ use gpui_component::ActiveTheme;
 use gpui_component::scroll::ScrollableElement;
 
 use super::{ChatInputEvent, ChatInputState};
+use crate::settings::models::SnippetsModel;
+use crate::settings::models::snippets_store::Snippet;
 
 // ---------------------------------------------------------------------------
 // Slash command menu — types and filters
@@ -49,12 +51,13 @@ pub struct SkillEntry {
     pub description: String,
 }
 
-/// A combined item in the slash-command picker: either a built-in command or a
-/// dynamic skill loaded from the filesystem.
+/// A combined item in the slash-command picker: a built-in command, a dynamic
+/// skill loaded from the filesystem, or a snippet from the snippet library.
 #[derive(Clone, Debug, PartialEq)]
 pub enum SlashMenuItem {
     Command(&'static SlashCommand),
     Skill(SkillEntry),
+    Snippet(Snippet),
 }
 
 impl SlashMenuItem {
@@ -63,6 +66,7 @@ impl SlashMenuItem {
         match self {
             SlashMenuItem::Command(cmd) => cmd.command.to_string(),
             SlashMenuItem::Skill(skill) => format!("/{}", skill.name),
+            SlashMenuItem::Snippet(snippet) => format!("/{}", snippet.name),
         }
     }
 
@@ -71,6 +75,7 @@ impl SlashMenuItem {
         match self {
             SlashMenuItem::Command(cmd) => cmd.description,
             SlashMenuItem::Skill(skill) => &skill.description,
+            SlashMenuItem::Snippet(snippet) => snippet.preview(),
         }
     }
 
@@ -81,6 +86,8 @@ impl SlashMenuItem {
             // Skills are not execute-immediately — we insert a prompt the user
             // can review and optionally extend before pressing Enter.
             SlashMenuItem::Skill(_) => false,
+            // Snippets replace the input so they can be edited before sending.
+            SlashMenuItem::Snippet(_) => false,
         }
     }
 
@@ -89,6 +96,7 @@ impl SlashMenuItem {
         match self {
             SlashMenuItem::Command(cmd) => cmd.insert_text.to_string(),
             SlashMenuItem::Skill(skill) => format!("Use the '{}' skill: ", skill.name),
+            SlashMenuItem::Snippet(snippet) => snippet.content.clone(),
         }
     }
 
@@ -96,6 +104,11 @@ impl SlashMenuItem {
     pub fn is_skill(&self) -> bool {
         matches!(self, SlashMenuItem::Skill(_))
     }
+
+    /// Returns true when this item represents a saved snippet.
+    pub fn is_snippet(&self) -> bool {
+        matches!(self, SlashMenuItem::Snippet(_))
+    }
 }
 
 const SLASH_COMMANDS: &[SlashCommand] = &[
@@ -111,6 +124,12 @@ const SLASH_COMMANDS: &[SlashCommand] = &[
         insert_text: "/agent ",
         execute_immediately: false,
     },
+    SlashCommand {
+        command: "/artifacts",
+        description: "Show files produced in this conversation",
+        insert_text: "/artifacts",
+        execute_immediately: true,
+    },
     SlashCommand {
         command: "/clear",
         description: "Clear conversation history",
@@ -135,6 +154,12 @@ const SLASH_COMMANDS: &[SlashCommand] = &[
         insert_text: "/context",
         execute_immediately: true,
     },
+    SlashCommand {
+        command: "/confidential",
+        description: "Toggle confidential mode (blocks network tools)",
+        insert_text: "/confidential",
+        execute_immediately: true,
+    },
     SlashCommand {
         command: "/copy",
         description: "Copy latest response to clipboard",
@@ -153,6 +178,78 @@ const SLASH_COMMANDS: &[SlashCommand] = &[
         insert_text: "/cd ",
         execute_immediately: false,
     },
+    SlashCommand {
+        command: "/export-project",
+        description: "Export generated code as a project: /export-project [git]",
+        insert_text: "/export-project ",
+        execute_immediately: false,
+    },
+    SlashCommand {
+        command: "/handoff",
+        description: "Write the task for a CLI agent: /handoff [aider|json]",
+        insert_text: "/handoff ",
+        execute_immediately: false,
+    },
+    SlashCommand {
+        command: "/lock",
+        description: "Lock or unlock the conversation (read-only)",
+        insert_text: "/lock",
+        execute_immediately: true,
+    },
+    SlashCommand {
+        command: "/merge",
+        description: "Append another conversation to this one: /merge <title>",
+        insert_text: "/merge ",
+        execute_immediately: false,
+    },
+    SlashCommand {
+        command: "/model",
+        description: "Switch model by name or alias (e.g. fast, smart)",
+        insert_text: "/model ",
+        execute_immediately: false,
+    },
+    SlashCommand {
+        command: "/pin",
+        description: "Pin a note so it is always in context",
+        insert_text: "/pin ",
+        execute_immediately: false,
+    },
+    SlashCommand {
+        command: "/pin-file",
+        description: "Pin a file's contents so it is always in context",
+        insert_text: "/pin-file ",
+        execute_immediately: false,
+    },
+    SlashCommand {
+        command: "/voice",
+        description: "Start or end a hands-free voice conversation",
+        insert_text: "/voice",
+        execute_immediately: true,
+    },
+    SlashCommand {
+        command: "/print",
+        description: "Print this conversation",
+        insert_text: "/print",
+        execute_immediately: true,
+    },
+    SlashCommand {
+        command: "/providers",
+        description: "Set up a model provider and test its key",
+        insert_text: "/providers",
+        execute_immediately: true,
+    },
+    SlashCommand {
+        command: "/snippet",
+        description: "Save a snippet: /snippet <name> <text>",
+        insert_text: "/snippet ",
+        execute_immediately: false,
+    },
+    SlashCommand {
+        command: "/timebox",
+        description: "Budget the next task: /timebox 10m $0.50, or off",
+        insert_text: "/timebox ",
+        execute_immediately: false,
+    },
 ];
 
 /// Returns the built-in slash commands that match the current `input_text`.
@@ -217,6 +314,31 @@ pub fn slash_menu_items_with_skills(input_text: &str, skills: &[SkillEntry]) ->
     items
 }
 
+/// Like [`slash_menu_items_with_skills`], followed by the snippets whose name
+/// matches the query. `snippets` are shown in the order given (most used first
+/// when taken from [`SnippetsModel::by_usage`]).
+pub fn slash_menu_items_with_snippets(
+    input_text: &str,
+    skills: &[SkillEntry],
+    snippets: &[Snippet],
+) -> Vec<SlashMenuItem> {
+    let mut items = slash_menu_items_with_skills(input_text, skills);
+    let Some(query) = input_text.trim().strip_prefix('/') else {
+        return items;
+    };
+    if query.chars().any(char::is_whitespace) {
+        return items;
+    }
+    let query = query.to_ascii_lowercase();
+    items.extend(
+        snippets
+            .iter()
+            .filter(|s| query.is_empty() || s.name.to_ascii_lowercase().starts_with(&query))
+            .map(|s| SlashMenuItem::Snippet(s.clone())),
+    );
+    items
+}
+
 // ---------------------------------------------------------------------------
 // ChatInputState — slash menu state methods
 // ---------------------------------------------------------------------------
@@ -226,10 +348,19 @@ impl ChatInputState {
     // Slash-command menu helpers
     // -----------------------------------------------------------------------
 
+    /// Picker items for `text`: commands, workspace skills and saved snippets.
+    pub fn slash_menu_items(&self, text: &str, cx: &App) -> Vec<SlashMenuItem> {
+        let snippets = cx
+            .try_global::<SnippetsModel>()
+            .map(SnippetsModel::by_usage)
+            .unwrap_or_default();
+        slash_menu_items_with_snippets(text, &self.available_skills, &snippets)
+    }
+
     /// Whether the slash-command picker should be shown given the current input.
     pub fn is_slash_menu_open(&self, cx: &mut Context<Self>) -> bool {
         let text = self.input.read(cx).text().to_string();
-        !slash_menu_items_with_skills(&text, &self.available_skills).is_empty()
+        !self.slash_menu_items(&text, cx).is_empty()
     }
 
     /// Current highlighted index in the picker.
@@ -300,9 +431,11 @@ impl ChatInputState {
     ///   `ChatInputEvent::SlashCommandSelected` and the input is cleared.
     /// * For argument commands (and all skills) the `insert_text` is written
     ///   into the input on the next render frame via `pending_slash_insert`.
+    /// * Snippets replace the input with their content and emit
+    ///   `ChatInputEvent::SnippetInserted` so the usage count is updated.
     pub fn apply_slash_command(&mut self, cx: &mut Context<Self>) {
         let input_text = self.input.read(cx).text().to_string();
-        let items = slash_menu_items_with_skills(&input_text, &self.available_skills);
+        let items = self.slash_menu_items(&input_text, cx);
         if items.is_empty() {
             return;
         }
@@ -323,6 +456,9 @@ impl ChatInputState {
         } else {
             // Insert command text (with trailing space) so user can type args.
             self.pending_slash_insert = Some(item.insert_text());
+            if let SlashMenuItem::Snippet(snippet) = item {
+                cx.emit(ChatInputEvent::SnippetInserted(snippet.name.clone()));
+            }
         }
     }
 }
@@ -337,6 +473,7 @@ impl ChatInputState {
 ///
 /// Built-in commands keep their description visible, while skills only show the
 /// slash-prefixed skill name to avoid horizontal overflow in the popover.
+/// Snippets show the first line of their content, cut to fit.
 pub(super) fn render_slash_menu(
     items: &[SlashMenuItem],
     selected: usize,
@@ -369,11 +506,14 @@ pub(super) fn render_slash_menu(
                     let display_command = item.display_command();
                     let description = item.description().to_string();
                     let is_skill = item.is_skill();
+                    let is_snippet = item.is_snippet();
                     let is_selected = idx == selected.min(items.len().saturating_sub(1));
 
-                    // Skills use a purple accent; commands use the standard blue.
+                    // Skills use a purple accent, snippets amber; commands use the standard blue.
                     let command_color = if is_skill {
                         rgb(0x8b5cf6)
+                    } else if is_snippet {
+                        rgb(0xf59e0b)
                     } else {
                         rgb(0x3b82f6)
                     };
@@ -420,7 +560,18 @@ pub(super) fn render_slash_menu(
                                 .child(display_command),
                         )
                         .when(!is_skill, |d| {
-                            d.child(div().text_sm().text_color(rgb(0x6b7280)).child(description))
+                            let line = div().text_sm().text_color(rgb(0x6b7280));
+                            // Snippet bodies can be long; keep them on one line
+                            let line = if is_snippet {
+                                line.flex_1()
+                                    .min_w_0()
+                                    .overflow_hidden()
+                                    .whitespace_nowrap()
+                                    .text_ellipsis()
+                            } else {
+                                line
+                            };
+                            d.child(line.child(description))
                         })
                 })),
         )
@@ -0,0 +1,204 @@
+//! Spell checking and "Polish" for the chat input.
+//!
+//! # What lives here
+//!
+//! - `ChatInputState` methods that keep the draft's misspellings current,
+//!   apply a suggestion or ignore a word, and drive the "Polish" rewrite.
+//! - `render_misspellings` — the row of underlined misspelled words shown
+//!   below the input; right-clicking a word opens its suggestions.
+//!
+//! The input widget draws its own text, so misspellings are underlined in a
+//! row under it rather than inline. Dictionaries are loaded into the
+//! `SpellChecker` global by `general_settings_controller::load_spell_checker`;
+//! without one nothing is checked.
+
+use gpui::*;
+use gpui_component::ActiveTheme;
+use gpui_component::menu::{ContextMenuExt, PopupMenuItem};
+use gpui_component::tooltip::Tooltip;
+
+use chatty_core::services::SpellChecker;
+use chatty_core::services::spell_checker::Misspelling;
+
+use super::{ChatInputEvent, ChatInputState};
+use crate::i18n::t;
+use crate::settings::models::GeneralSettingsModel;
+
+/// Maximum number of misspelled words listed below the input.
+const MAX_LISTED_MISSPELLINGS: usize = 8;
+
+// ---------------------------------------------------------------------------
+// ChatInputState — spelling and polish methods
+// ---------------------------------------------------------------------------
+
+impl ChatInputState {
+    /// Re-check `text` against the loaded dictionary. Clears the list when
+    /// spell checking is off or no dictionary is available. Returns whether
+    /// the list changed.
+    pub fn refresh_spelling(&mut self, text: &str, cx: &App) -> bool {
+        let enabled = cx
+            .try_global::<GeneralSettingsModel>()
+            .is_some_and(|s| s.spell_check_enabled);
+        let misspellings: Vec<Misspelling> = match cx.try_global::<SpellChecker>() {
+            Some(checker) if enabled => checker
+                .misspellings(text)
+                .into_iter()
+                .filter(|m| !self.ignored_words.contains(&m.word))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let changed = misspellings != self.misspellings;
+        self.misspellings = misspellings;
+        changed
+    }
+
+    /// Misspelled words in the current draft, in text order.
+    pub fn misspellings(&self) -> &[Misspelling] {
+        &self.misspellings
+    }
+
+    /// Replace `misspelling` with `replacement` in the draft. Ignored when
+    /// the draft changed since the word was found.
+    pub fn replace_misspelling(
+        &mut self,
+        misspelling: &Misspelling,
+        replacement: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let mut text = self.input.read(cx).text().to_string();
+        if text.get(misspelling.range.clone()) != Some(misspelling.word.as_str()) {
+            return;
+        }
+        text.replace_range(misspelling.range.clone(), replacement);
+        self.pending_text_replace = Some(text);
+        cx.notify();
+    }
+
+    /// Stop flagging `word` until the app restarts.
+    pub fn ignore_word(&mut self, word: String, cx: &mut Context<Self>) {
+        self.misspellings.retain(|m| m.word != word);
+        self.ignored_words.insert(word);
+        cx.notify();
+    }
+
+    /// Ask `ChattyApp` to rewrite the draft with the selected model.
+    pub fn request_polish(&mut self, cx: &mut Context<Self>) {
+        if self.polishing {
+            return;
+        }
+        let draft = self.input.read(cx).text().to_string();
+        let Some(model_id) = self.selected_model_id.clone() else {
+            return;
+        };
+        if draft.trim().is_empty() {
+            return;
+        }
+        self.polishing = true;
+        cx.emit(ChatInputEvent::PolishRequested { draft, model_id });
+        cx.notify();
+    }
+
+    /// Whether a "Polish" rewrite is in flight.
+    pub fn is_polishing(&self) -> bool {
+        self.polishing
+    }
+
+    /// End a "Polish" rewrite, replacing the draft with `polished` if any.
+    pub fn finish_polish(&mut self, polished: Option<String>, cx: &mut Context<Self>) {
+        self.polishing = false;
+        if polished.is_some() {
+            self.pending_text_replace = polished;
+        }
+        cx.notify();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Misspelling row
+// ---------------------------------------------------------------------------
+
+/// Underlined misspelled words; right-click one for suggestions.
+pub(super) fn render_misspellings(
+    misspellings: &[Misspelling],
+    state: &Entity<ChatInputState>,
+    cx: &App,
+) -> impl IntoElement {
+    let underline = HighlightStyle {
+        underline: Some(UnderlineStyle {
+            thickness: px(1.),
+            color: Some(cx.theme().danger),
+            wavy: true,
+        }),
+        ..Default::default()
+    };
+
+    div()
+        .flex()
+        .flex_row()
+        .flex_wrap()
+        .gap_2()
+        .pt_1()
+        .text_xs()
+        .children(
+            misspellings
+                .iter()
+                .take(MAX_LISTED_MISSPELLINGS)
+                .enumerate()
+                .map(|(ix, misspelling)| {
+                    let word = misspelling.word.clone();
+                    let text = StyledText::new(word.clone())
+                        .with_highlights(vec![(0..word.len(), underline)]);
+                    let misspelling = misspelling.clone();
+                    let state = state.clone();
+                    div()
+                        .id(("misspelling", ix))
+                        .px_1()
+                        .rounded_sm()
+                        .cursor_context_menu()
+                        .hover(|style| style.bg(cx.theme().muted))
+                        .tooltip(|window, cx| {
+                            Tooltip::new(t("chat-spelling-hint")).build(window, cx)
+                        })
+                        .child(text)
+                        .context_menu(move |menu, _, cx| {
+                            let suggestions = cx
+                                .try_global::<SpellChecker>()
+                                .map(|checker| checker.suggest(&misspelling.word))
+                                .unwrap_or_default();
+                            let menu = if suggestions.is_empty() {
+                                menu.item(
+                                    PopupMenuItem::new(t("chat-spelling-no-suggestions"))
+                                        .disabled(true),
+                                )
+                            } else {
+                                suggestions.into_iter().fold(menu, |menu, suggestion| {
+                                    let misspelling = misspelling.clone();
+                                    let state = state.clone();
+                                    menu.item(PopupMenuItem::new(suggestion.clone()).on_click(
+                                        move |_, _, cx| {
+                                            state.update(cx, |state, cx| {
+                                                state.replace_misspelling(
+                                                    &misspelling,
+                                                    &suggestion,
+                                                    cx,
+                                                );
+                                            });
+                                        },
+                                    ))
+                                })
+                            };
+                            let word = misspelling.word.clone();
+                            let state = state.clone();
+                            menu.separator().item(
+                                PopupMenuItem::new(t("chat-spelling-ignore")).on_click(
+                                    move |_, _, cx| {
+                                        state.update(cx, |state, cx| {
+                                            state.ignore_word(word.clone(), cx);
+                                        });
+                                    },
+                                ),
+                            )
+                        })
+                }),
+        )
+}
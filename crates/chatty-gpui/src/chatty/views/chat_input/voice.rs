@@ -0,0 +1,102 @@
+//! Speech mode toggle in the input toolbar.
+//!
+//! # What lives here
+//!
+//! - `ChatInputState` methods that ask `ChattyApp` to start or end speech
+//!   mode, show where the current voice turn stands, and send a transcript
+//!   through the normal send path.
+//! - `render_voice_button` — "Voice" while speech mode is off, the current
+//!   phase ("Listening…", "Speaking…", …) while it is on.
+//!
+//! The recorder, turn detection and read-aloud run in `ChattyApp`
+//! (`speech_ops`) on top of `chatty_core::services::speech_mode`. The
+//! button only shows once the recording and transcription commands are
+//! configured.
+
+use gpui::*;
+use gpui_component::Sizable;
+use gpui_component::button::{Button, ButtonVariants};
+
+use chatty_core::models::TurnOverrides;
+use chatty_core::services::speech_mode::SpeechPhase;
+
+use super::{ChatInputEvent, ChatInputState};
+use crate::i18n::t;
+use crate::settings::models::GeneralSettingsModel;
+
+// ---------------------------------------------------------------------------
+// ChatInputState — speech mode methods
+// ---------------------------------------------------------------------------
+
+impl ChatInputState {
+    /// Ask `ChattyApp` to start or end speech mode.
+    pub fn toggle_speech_mode(&mut self, cx: &mut Context<Self>) {
+        cx.emit(ChatInputEvent::SpeechModeToggled);
+    }
+
+    /// Show where the current voice turn stands (None = speech mode off).
+    pub fn set_speech_phase(&mut self, phase: Option<SpeechPhase>, cx: &mut Context<Self>) {
+        if self.speech_phase != phase {
+            self.speech_phase = phase;
+            cx.notify();
+        }
+    }
+
+    /// Current voice turn phase, or None when speech mode is off.
+    pub fn speech_phase(&self) -> Option<SpeechPhase> {
+        self.speech_phase
+    }
+
+    /// Send a transcribed utterance like a typed message. The draft and
+    /// attachments are left alone.
+    pub fn send_spoken(&mut self, text: String, cx: &mut Context<Self>) {
+        cx.emit(ChatInputEvent::Send {
+            message: text,
+            attachments: Vec::new(),
+            overrides: TurnOverrides::default(),
+        });
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Voice button
+// ---------------------------------------------------------------------------
+
+/// Whether the recording and transcription commands are configured, so
+/// speech mode can start.
+pub(super) fn speech_mode_available(cx: &App) -> bool {
+    cx.try_global::<GeneralSettingsModel>()
+        .is_some_and(|s| s.speech_record_command.is_some() && s.speech_transcribe_command.is_some())
+}
+
+/// "Voice" button, or the current phase while speech mode is on.
+pub(super) fn render_voice_button(
+    state: &Entity<ChatInputState>,
+    phase: Option<SpeechPhase>,
+) -> impl IntoElement {
+    let state = state.clone();
+    let (label, tooltip) = match phase {
+        None => (t("chat-voice"), t("chat-voice-tooltip")),
+        Some(phase) => {
+            let label = match phase {
+                SpeechPhase::Listening => t("chat-voice-listening"),
+                SpeechPhase::Transcribing => t("chat-voice-transcribing"),
+                SpeechPhase::Thinking => t("chat-voice-thinking"),
+                SpeechPhase::Speaking => t("chat-voice-speaking"),
+            };
+            (label, t("chat-voice-stop-tooltip"))
+        }
+    };
+    let button = Button::new("speech-mode")
+        .xsmall()
+        .label(label)
+        .tooltip(tooltip)
+        .on_click(move |_, _, cx| {
+            state.update(cx, |state, cx| state.toggle_speech_mode(cx));
+        });
+    if phase.is_some() {
+        button.primary()
+    } else {
+        button.ghost()
+    }
+}
@@ -1,5 +1,8 @@
 #[cfg(test)]
-use super::{apply_at_to_input, at_menu_items_for, at_query_from, slash_menu_items_for};
+use super::{
+    apply_at_to_input, apply_path_to_input, at_menu_items_for, at_query_from, merge_into_draft,
+    path_token_from, quote_lines, slash_menu_items_for, wrap_in_code_block,
+};
 
 // -----------------------------------------------------------------------
 // @ mention menu tests (pure, no GPUI context required)
@@ -82,6 +85,38 @@ fn test_apply_at_mid_sentence() {
     );
 }
 
+// -----------------------------------------------------------------------
+// Path completion tests (pure, no GPUI context required)
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_path_token_needs_a_slash() {
+    assert_eq!(path_token_from("look at src/ma"), Some("src/ma"));
+    assert_eq!(path_token_from("./lib"), Some("./lib"));
+    assert!(path_token_from("main.rs").is_none());
+    assert!(path_token_from("src/main.rs ").is_none());
+}
+
+#[test]
+fn test_path_token_skips_commands_mentions_and_urls() {
+    assert!(path_token_from("/clear").is_none());
+    assert!(path_token_from("see /etc/hosts").is_none());
+    assert!(path_token_from("@src/main").is_none());
+    assert!(path_token_from("https://example.com/a").is_none());
+}
+
+#[test]
+fn test_apply_path_replaces_token() {
+    assert_eq!(
+        apply_path_to_input("fix src/ma", "src/main.rs"),
+        "fix src/main.rs "
+    );
+    assert_eq!(
+        apply_path_to_input("first line\n./vi\n", "src/views/chat.rs"),
+        "first line\nsrc/views/chat.rs "
+    );
+}
+
 // -----------------------------------------------------------------------
 // slash-command menu tests (pure, no GPUI context required)
 // -----------------------------------------------------------------------
@@ -219,6 +254,33 @@ fn test_skills_menu_closes_on_space() {
     assert!(slash_menu_items_with_skills("/fix-ci extra", &skills).is_empty());
 }
 
+#[test]
+fn test_snippets_follow_skills_and_insert_content() {
+    use super::{SlashMenuItem, slash_menu_items_with_snippets};
+    use crate::settings::models::snippets_store::Snippet;
+
+    let snippets = vec![Snippet {
+        name: "sql-style".to_string(),
+        content: "Use lowercase SQL keywords.\nAlias every table.".to_string(),
+        use_count: 3,
+    }];
+
+    let items = slash_menu_items_with_snippets("/sq", &[], &snippets);
+    assert_eq!(items.len(), 1);
+    let item = &items[0];
+    assert!(item.is_snippet());
+    assert!(!item.execute_immediately());
+    assert_eq!(item.display_command(), "/sql-style");
+    assert_eq!(item.description(), "Use lowercase SQL keywords.");
+    assert_eq!(item.insert_text(), snippets[0].content);
+
+    // `/snippet` itself is a built-in and comes before any snippet
+    let items = slash_menu_items_with_snippets("/s", &[], &snippets);
+    assert!(matches!(items.first(), Some(SlashMenuItem::Command(c)) if c.command == "/snippet"));
+    assert!(slash_menu_items_with_snippets("/sql-style more", &[], &snippets).is_empty());
+    assert!(slash_menu_items_with_snippets("sql", &[], &snippets).is_empty());
+}
+
 /// Verify that the /agent command prefix extraction used by
 /// `try_handle_arg_slash_command` works correctly.
 #[test]
@@ -252,3 +314,43 @@ fn test_add_dir_prefix_extraction() {
     let msg = "/add-dir ./src";
     assert_eq!(msg.strip_prefix("/add-dir "), Some("./src"));
 }
+
+#[test]
+fn test_slash_menu_voice() {
+    let items = slash_menu_items_for("/vo");
+    assert!(
+        items
+            .iter()
+            .any(|i| i.command == "/voice" && i.execute_immediately),
+        "/voice should match /vo prefix and run immediately"
+    );
+}
+
+// -----------------------------------------------------------------------
+// Composer formatting helpers (pure, no GPUI context)
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_wrap_in_code_block() {
+    assert_eq!(wrap_in_code_block(""), "```\n\n```\n");
+    assert_eq!(wrap_in_code_block("let x = 1;\n"), "```\nlet x = 1;\n```\n");
+}
+
+#[test]
+fn test_quote_lines() {
+    assert_eq!(quote_lines(""), "> ");
+    assert_eq!(quote_lines("first\n\nsecond\n"), "> first\n>\n> second");
+}
+
+// -----------------------------------------------------------------------
+// Message queue (pure, no GPUI context)
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_merge_queued_into_draft() {
+    assert_eq!(
+        merge_into_draft(["first\n", "  ", "second"], "still typing"),
+        "first\n\nsecond\n\nstill typing"
+    );
+    assert_eq!(merge_into_draft(["only queued"], ""), "only queued");
+}
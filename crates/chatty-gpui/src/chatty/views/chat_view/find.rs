@@ -0,0 +1,227 @@
+//! Find-in-conversation for `ChatView`.
+//!
+//! # What lives here
+//!
+//! The find bar opened with Ctrl/Cmd-F: its query input, the list of matches
+//! across the loaded messages, the match counter, and cycling with
+//! Enter / Shift-Enter. Matches are highlighted while the messages render
+//! (see `find_highlight.rs`).
+//!
+//! # What does NOT live here
+//!
+//! - Searching conversation titles — `search_conversations_dialog.rs`.
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::{Input, InputEvent, InputState};
+use gpui_component::{ActiveTheme, IconName, Sizable, h_flex};
+
+use super::super::find_highlight::{FindHighlighter, find_matches};
+use super::ChatView;
+use crate::i18n::t;
+
+actions!(chat_view, [FindInConversation]);
+
+/// Open find bar state.
+pub(super) struct FindBar {
+    input: Entity<InputState>,
+    query: String,
+    /// Every match as (index into `messages`, occurrence within that message)
+    matches: Vec<(usize, usize)>,
+    current: usize,
+    /// Message count and total content length the matches were computed for
+    computed_for: (usize, usize),
+    _subscription: Subscription,
+}
+
+impl ChatView {
+    /// Open the find bar, or focus it if it is already open.
+    pub(super) fn open_find(
+        &mut self,
+        _: &FindInConversation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.find_bar.is_none() {
+            let input =
+                cx.new(|cx| InputState::new(window, cx).placeholder(t("chat-find-placeholder")));
+            let subscription =
+                cx.subscribe(&input, |view, input, event: &InputEvent, cx| match event {
+                    InputEvent::Change => {
+                        let query = input.read(cx).value().to_string();
+                        view.set_find_query(query, cx);
+                    }
+                    InputEvent::PressEnter { secondary } => {
+                        view.cycle_find_match(if *secondary { -1 } else { 1 }, cx);
+                    }
+                    _ => {}
+                });
+            self.find_bar = Some(FindBar {
+                input,
+                query: String::new(),
+                matches: Vec::new(),
+                current: 0,
+                computed_for: (0, 0),
+                _subscription: subscription,
+            });
+        }
+        if let Some(find) = &self.find_bar {
+            find.input.update(cx, |input, cx| input.focus(window, cx));
+        }
+        cx.notify();
+    }
+
+    fn close_find(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.find_bar = None;
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    fn set_find_query(&mut self, query: String, cx: &mut Context<Self>) {
+        let Some(find) = &mut self.find_bar else {
+            return;
+        };
+        find.query = query;
+        find.current = 0;
+        self.refresh_find_matches(true);
+        self.scroll_to_find_match();
+        cx.notify();
+    }
+
+    /// Move to the next (`1`) or previous (`-1`) match, wrapping around.
+    fn cycle_find_match(&mut self, delta: isize, cx: &mut Context<Self>) {
+        let Some(find) = &mut self.find_bar else {
+            return;
+        };
+        let count = find.matches.len() as isize;
+        if count == 0 {
+            return;
+        }
+        find.current = (find.current as isize + delta).rem_euclid(count) as usize;
+        self.scroll_to_find_match();
+        cx.notify();
+    }
+
+    /// Recompute the matches when the query or the messages changed
+    /// (`force` for a new query). Keeps the current match where possible.
+    pub(super) fn refresh_find_matches(&mut self, force: bool) {
+        let listed = self.listed_message_indices();
+        let Some(find) = &mut self.find_bar else {
+            return;
+        };
+        let computed_for = (
+            self.messages.len(),
+            self.messages.iter().map(|msg| msg.content.len()).sum(),
+        );
+        if !force && find.computed_for == computed_for {
+            return;
+        }
+        find.computed_for = computed_for;
+        find.matches = listed
+            .into_iter()
+            .flat_map(|ix| {
+                let count = find_matches(&self.messages[ix].content, &find.query).len();
+                (0..count).map(move |occurrence| (ix, occurrence))
+            })
+            .collect();
+        find.current = find.current.min(find.matches.len().saturating_sub(1));
+    }
+
+    fn scroll_to_find_match(&mut self) {
+        let Some(&(message, _)) = self
+            .find_bar
+            .as_ref()
+            .and_then(|find| find.matches.get(find.current))
+        else {
+            return;
+        };
+        if let Some(position) = self
+            .listed_message_indices()
+            .iter()
+            .position(|&ix| ix == message)
+        {
+            self.scroll_item_into_view(position);
+        }
+    }
+
+    /// Highlighter for the message at `index`, while a query is entered.
+    pub(super) fn find_highlighter(&self, index: usize) -> Option<FindHighlighter> {
+        let find = self
+            .find_bar
+            .as_ref()
+            .filter(|find| !find.query.is_empty())?;
+        let current = find
+            .matches
+            .get(find.current)
+            .filter(|(message, _)| *message == index)
+            .map(|(_, occurrence)| *occurrence);
+        Some(FindHighlighter::new(find.query.clone(), current))
+    }
+
+    pub(super) fn render_find_bar(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let find = self.find_bar.as_ref()?;
+        let counter = match (find.query.is_empty(), find.matches.len()) {
+            (true, _) => String::new(),
+            (false, 0) => "No matches".to_string(),
+            (false, count) => format!("{} of {}", find.current + 1, count),
+        };
+        let has_matches = !find.matches.is_empty();
+
+        Some(
+            h_flex()
+                .id("find-bar")
+                .flex_shrink_0()
+                .justify_end()
+                .gap_1()
+                .px_4()
+                .py_1()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .capture_key_down(cx.listener(|view, event: &KeyDownEvent, window, cx| {
+                    if event.keystroke.key == "escape" {
+                        view.close_find(window, cx);
+                        cx.stop_propagation();
+                    }
+                }))
+                .child(div().w(px(260.)).child(Input::new(&find.input).small()))
+                .child(
+                    div()
+                        .min_w(px(72.))
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(counter),
+                )
+                .child(
+                    Button::new("find-previous")
+                        .ghost()
+                        .xsmall()
+                        .icon(IconName::ChevronUp)
+                        .tooltip(t("chat-find-previous"))
+                        .disabled(!has_matches)
+                        .on_click(cx.listener(|view, _, _, cx| view.cycle_find_match(-1, cx))),
+                )
+                .child(
+                    Button::new("find-next")
+                        .ghost()
+                        .xsmall()
+                        .icon(IconName::ChevronDown)
+                        .tooltip(t("chat-find-next"))
+                        .disabled(!has_matches)
+                        .on_click(cx.listener(|view, _, _, cx| view.cycle_find_match(1, cx))),
+                )
+                .child(
+                    Button::new("find-close")
+                        .ghost()
+                        .xsmall()
+                        .icon(IconName::Close)
+                        .tooltip(t("chat-find-close"))
+                        .on_click(cx.listener(|view, _, window, cx| view.close_find(window, cx))),
+                )
+                .when(!has_matches && !find.query.is_empty(), |this| {
+                    this.bg(cx.theme().danger.opacity(0.05))
+                })
+                .into_any_element(),
+        )
+    }
+}
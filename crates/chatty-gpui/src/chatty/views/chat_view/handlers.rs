@@ -31,6 +31,9 @@ use super::super::message_types::{
 };
 use super::super::trace_components::SystemTraceView;
 use super::{ChatView, PendingApprovalInfo};
+use crate::chatty::models::execution_approval_store::{
+    ApprovalDecision, ExecutionApprovalStore, UserQuestion,
+};
 
 impl ChatView {
     /// Handle tool call started event
@@ -332,6 +335,7 @@ impl ChatView {
         id: String,
         command: String,
         is_sandboxed: bool,
+        question: Option<UserQuestion>,
         cx: &mut Context<Self>,
     ) {
         debug!(approval_id = %id, command = %command, sandboxed = is_sandboxed, "UI: handle_approval_requested called");
@@ -343,6 +347,7 @@ impl ChatView {
                 command: command.clone(),
                 is_sandboxed,
                 conversation_id: conv_id.clone(),
+                question: question.clone(),
             });
         }
 
@@ -353,6 +358,8 @@ impl ChatView {
             is_sandboxed,
             state: ApprovalState::Pending,
             created_at: SystemTime::now(),
+            question,
+            answer: None,
         };
 
         // Update live trace and create/update system_trace_view entity
@@ -405,7 +412,13 @@ impl ChatView {
     }
 
     /// Handle approval resolved event
-    pub fn handle_approval_resolved(&mut self, id: &str, approved: bool, cx: &mut Context<Self>) {
+    pub fn handle_approval_resolved(
+        &mut self,
+        id: &str,
+        approved: bool,
+        answer: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
         debug!(approval_id = %id, approved = approved, "UI: handle_approval_resolved called");
 
         // Clear pending approval (hide floating bar)
@@ -424,6 +437,9 @@ impl ChatView {
                     ApprovalState::Denied
                 };
                 trace.update_approval_state(id, new_state);
+                if let Some(answer) = answer {
+                    trace.set_approval_answer(id, answer);
+                }
 
                 // Clear active tool after resolution
                 trace.clear_active_tool();
@@ -596,10 +612,51 @@ impl ChatView {
             self.pending_approval = None;
 
             // Also update the trace
-            self.handle_approval_resolved(&id, approved, cx);
+            self.handle_approval_resolved(&id, approved, None, cx);
         }
     }
 
+    /// Answer the pending `ask_user` question of the active conversation, or
+    /// dismiss it when `answer` is `None`. Returns `false` when no question is
+    /// pending, so the caller can treat the input as a regular message.
+    ///
+    /// Typed answers are matched against the options (by number or text); an
+    /// answer that is not accepted leaves the question pending.
+    pub fn answer_pending_question(
+        &mut self,
+        answer: Option<String>,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(pending) = self.active_approval_for_display() else {
+            return false;
+        };
+        let Some(question) = pending.question else {
+            return false;
+        };
+
+        let decision = match answer {
+            Some(text) => match question.resolve_typed_answer(&text) {
+                Some(answer) => ApprovalDecision::Answered(answer),
+                None => {
+                    warn!(approval_id = %pending.id, "Ignoring answer that matches no option");
+                    return true;
+                }
+            },
+            None => ApprovalDecision::Denied,
+        };
+        let answer = match &decision {
+            ApprovalDecision::Answered(answer) => Some(answer.clone()),
+            _ => None,
+        };
+
+        if let Some(store) = cx.try_global::<ExecutionApprovalStore>() {
+            store.resolve(&pending.id, decision);
+        }
+        self.pending_approval = None;
+        self.handle_approval_resolved(&pending.id, answer.is_some(), answer, cx);
+        true
+    }
+
     /// Expand trace and scroll to approval for "View Details" button
     pub(super) fn expand_trace_to_approval(&mut self, cx: &mut Context<Self>) {
         trace!("expand_trace_to_approval called");
@@ -42,7 +42,12 @@ impl ChatView {
 
         // Reset sub-agent tracking (sub-agent progress is UI-only, not in history)
         self.sub_agent_progress_msg_idx = None;
+        self.discard_stream_reveal();
 
+        self.selected_message = None;
+        self.selection_anchor = None;
+        self.topic_split_suggested = false;
+        self.editing = None;
         self.messages.clear();
 
         for (idx, entry) in entries.iter().enumerate() {
@@ -61,6 +66,9 @@ impl ChatView {
                             is_markdown: true,
                             attachments,
                             feedback: None,
+                            reactions: Vec::new(),
+                            note: None,
+                            error: entry.error.clone(),
                             history_index: Some(idx),
                         });
                     }
@@ -100,6 +108,9 @@ impl ChatView {
                             is_markdown: true,
                             attachments,
                             feedback,
+                            reactions: entry.reactions.clone(),
+                            note: entry.note.clone(),
+                            error: None,
                             history_index: Some(idx),
                         });
                     }
@@ -0,0 +1,570 @@
+//! Keyboard-driven message navigation for `ChatView`.
+//!
+//! # What lives here
+//!
+//! The actions bound in the message list's `ChatMessages` key context
+//! (next/previous message, Shift-extended selection, copy, copy as context,
+//! edit, regenerate, bookmark, delete from history, cheat sheet), their
+//! `impl ChatView` handlers, and the cheat-sheet overlay.
+//!
+//! Keystrokes come from `KeybindingsModel` (`keybindings.json`), read once
+//! at startup. The keys only reach the list while it has focus (after a
+//! click into it), so typing in the chat input is never affected.
+//!
+//! # What does NOT live here
+//!
+//! - App-wide shortcuts (new chat, sidebar, quit) — see `actions.rs`.
+//! - The approve/deny shortcuts for tool approvals — see `handlers.rs`.
+
+use chatty_core::exporters::context_quote::{QuoteRole, QuotedMessage, format_context_quote};
+use chatty_core::services::message_copy::format_for_copy;
+use chatty_core::settings::models::{GeneralSettingsModel, KeybindingsModel, MessageAction};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme, WindowExt as _, h_flex, v_flex};
+use tracing::warn;
+
+use super::super::message_component::{AnnotationChange, BOOKMARK_REACTION, MessageRole};
+use super::{ChatView, ChatViewEvent, is_listed};
+use crate::i18n::t;
+
+/// Key context of the focused message list.
+const MESSAGES_CONTEXT: &str = "ChatMessages";
+
+actions!(
+    chat_view,
+    [
+        SelectNextMessage,
+        SelectPreviousMessage,
+        ExtendSelectionDown,
+        ExtendSelectionUp,
+        CopySelectedMessage,
+        CopySelectionAsContext,
+        EditSelectedMessage,
+        RegenerateSelectedMessage,
+        BookmarkSelectedMessage,
+        DeleteSelectedMessage,
+        DeleteSelectedTrace,
+        ToggleShortcutsHelp
+    ]
+);
+
+/// Key bindings for the message list. Actions whose keystroke is unbound or
+/// does not parse are skipped.
+pub(crate) fn message_key_bindings(model: &KeybindingsModel) -> Vec<KeyBinding> {
+    let context = Some(MESSAGES_CONTEXT);
+    MessageAction::ALL
+        .into_iter()
+        .filter_map(|action| {
+            let keystrokes = model.keystroke(action)?;
+            if let Some(invalid) = keystrokes
+                .split_whitespace()
+                .find(|part| Keystroke::parse(part).is_err())
+            {
+                warn!(
+                    action = action.id(),
+                    keystroke = invalid,
+                    "Ignoring invalid key binding"
+                );
+                return None;
+            }
+            Some(match action {
+                MessageAction::NextMessage => {
+                    KeyBinding::new(keystrokes, SelectNextMessage, context)
+                }
+                MessageAction::PreviousMessage => {
+                    KeyBinding::new(keystrokes, SelectPreviousMessage, context)
+                }
+                MessageAction::ExtendSelectionDown => {
+                    KeyBinding::new(keystrokes, ExtendSelectionDown, context)
+                }
+                MessageAction::ExtendSelectionUp => {
+                    KeyBinding::new(keystrokes, ExtendSelectionUp, context)
+                }
+                MessageAction::CopyMessage => {
+                    KeyBinding::new(keystrokes, CopySelectedMessage, context)
+                }
+                MessageAction::CopyAsContext => {
+                    KeyBinding::new(keystrokes, CopySelectionAsContext, context)
+                }
+                MessageAction::EditMessage => {
+                    KeyBinding::new(keystrokes, EditSelectedMessage, context)
+                }
+                MessageAction::RegenerateMessage => {
+                    KeyBinding::new(keystrokes, RegenerateSelectedMessage, context)
+                }
+                MessageAction::BookmarkMessage => {
+                    KeyBinding::new(keystrokes, BookmarkSelectedMessage, context)
+                }
+                MessageAction::DeleteMessage => {
+                    KeyBinding::new(keystrokes, DeleteSelectedMessage, context)
+                }
+                MessageAction::DeleteTrace => {
+                    KeyBinding::new(keystrokes, DeleteSelectedTrace, context)
+                }
+                MessageAction::ToggleShortcuts => {
+                    KeyBinding::new(keystrokes, ToggleShortcutsHelp, context)
+                }
+            })
+        })
+        .collect()
+}
+
+impl ChatView {
+    /// Indices into `messages` of the messages shown in the list, in order.
+    pub(super) fn listed_message_indices(&self) -> Vec<usize> {
+        self.messages
+            .iter()
+            .enumerate()
+            .filter(|(_, msg)| is_listed(msg))
+            .map(|(ix, _)| ix)
+            .collect()
+    }
+
+    /// Indices into `messages` spanned by the selection, anchor included.
+    pub(super) fn selected_range(&self) -> Option<std::ops::RangeInclusive<usize>> {
+        let selected = self.selected_message?;
+        let anchor = self.selection_anchor.unwrap_or(selected);
+        Some(selected.min(anchor)..=selected.max(anchor))
+    }
+
+    /// Move the selection by `delta` listed messages. Without a selection,
+    /// the last message is selected first. With `extend`, the message the
+    /// selection started from stays selected.
+    fn move_selection(&mut self, delta: isize, extend: bool, cx: &mut Context<Self>) {
+        let listed = self.listed_message_indices();
+        if listed.is_empty() {
+            return;
+        }
+        let position = match self
+            .selected_message
+            .and_then(|selected| listed.iter().position(|&ix| ix == selected))
+        {
+            Some(position) => (position as isize + delta).clamp(0, listed.len() as isize - 1),
+            None => listed.len() as isize - 1,
+        } as usize;
+        self.selection_anchor = if extend {
+            self.selection_anchor.or(self.selected_message)
+        } else {
+            None
+        };
+        self.selected_message = Some(listed[position]);
+        self.scroll_item_into_view(position);
+        cx.notify();
+    }
+
+    /// Scroll the message list so the `position`-th listed message is visible,
+    /// using the bounds from the last layout.
+    pub(super) fn scroll_item_into_view(&mut self, position: usize) {
+        let Some(item) = self.message_items.bounds_for_item(position) else {
+            return;
+        };
+        let viewport = self.scroll_handle.bounds();
+        let mut offset = self.scroll_handle.offset();
+        if item.top() < viewport.top() {
+            offset.y += viewport.top() - item.top();
+        } else if item.bottom() > viewport.bottom() {
+            // Bring the bottom into view without pushing a tall message's top out.
+            let overflow = item.bottom() - viewport.bottom();
+            let room = item.top() - viewport.top();
+            offset.y -= if overflow < room { overflow } else { room };
+        } else {
+            return;
+        }
+        self.stick_to_bottom = false;
+        self.pending_scroll_offset = None;
+        self.scroll_handle.set_offset(offset);
+    }
+
+    /// Whether `ix` is the message the regenerate button is shown on.
+    fn is_last_finished_response(&self, ix: usize) -> bool {
+        self.listed_message_indices().into_iter().rev().find(|&i| {
+            let msg = &self.messages[i];
+            matches!(msg.role, MessageRole::Assistant)
+                && !msg.is_streaming
+                && msg.live_trace.is_none()
+        }) == Some(ix)
+    }
+
+    fn select_next_message(
+        &mut self,
+        _: &SelectNextMessage,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_selection(1, false, cx);
+    }
+
+    fn select_previous_message(
+        &mut self,
+        _: &SelectPreviousMessage,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_selection(-1, false, cx);
+    }
+
+    fn extend_selection_down(
+        &mut self,
+        _: &ExtendSelectionDown,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_selection(1, true, cx);
+    }
+
+    fn extend_selection_up(
+        &mut self,
+        _: &ExtendSelectionUp,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_selection(-1, true, cx);
+    }
+
+    fn copy_selected_message(
+        &mut self,
+        _: &CopySelectedMessage,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(msg) = self.selected_message.and_then(|ix| self.messages.get(ix)) else {
+            return;
+        };
+        // Assistant answers use the copy format from the general settings.
+        let text = match msg.role {
+            MessageRole::Assistant => {
+                let format = cx
+                    .try_global::<GeneralSettingsModel>()
+                    .map(|s| s.default_copy_format)
+                    .unwrap_or_default();
+                format_for_copy(&msg.content, format)
+            }
+            _ => msg.content.clone(),
+        };
+        cx.write_to_clipboard(ClipboardItem::new_string(text));
+        window.push_notification("Message copied", cx);
+    }
+
+    /// Copy the selected messages as one quoted block, role-prefixed, with
+    /// traces when enabled in the general settings.
+    fn copy_selection_as_context(
+        &mut self,
+        _: &CopySelectionAsContext,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(range) = self.selected_range() else {
+            return;
+        };
+        let include_traces = cx
+            .try_global::<GeneralSettingsModel>()
+            .is_some_and(|settings| settings.copy_context_include_traces);
+        let selected: Vec<_> = self.messages[range]
+            .iter()
+            .filter(|msg| is_listed(msg))
+            .collect();
+        let traces: Vec<_> = selected
+            .iter()
+            .map(|msg| {
+                msg.live_trace.clone().or_else(|| {
+                    msg.system_trace_view
+                        .as_ref()
+                        .map(|view| view.read(cx).get_trace().clone())
+                })
+            })
+            .collect();
+        let quoted: Vec<_> = selected
+            .iter()
+            .zip(&traces)
+            .map(|(msg, trace)| QuotedMessage {
+                role: match msg.role {
+                    MessageRole::User => QuoteRole::User,
+                    MessageRole::Assistant => QuoteRole::Assistant,
+                },
+                content: &msg.content,
+                trace: trace.as_ref(),
+            })
+            .collect();
+        let quote = format_context_quote(&quoted, include_traces);
+        cx.write_to_clipboard(ClipboardItem::new_string(quote));
+        let notification = match quoted.len() {
+            1 => "Copied 1 message as context".to_string(),
+            count => format!("Copied {count} messages as context"),
+        };
+        window.push_notification(notification, cx);
+    }
+
+    /// Put the selected prompt (or the prompt a selected response answers)
+    /// into the chat input for editing and resending.
+    fn edit_selected_message(
+        &mut self,
+        _: &EditSelectedMessage,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(text) = self
+            .selected_message
+            .and_then(|selected| self.messages.get(..=selected))
+            .and_then(|earlier| {
+                earlier
+                    .iter()
+                    .rev()
+                    .find(|msg| matches!(msg.role, MessageRole::User))
+            })
+            .map(|msg| msg.content.clone())
+        else {
+            return;
+        };
+        let input = self.chat_input_state.read(cx).input.clone();
+        input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+            input.insert(&text, window, cx);
+            input.focus(window, cx);
+        });
+    }
+
+    fn regenerate_selected_message(
+        &mut self,
+        _: &RegenerateSelectedMessage,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selected) = self.selected_message else {
+            return;
+        };
+        if !self.is_last_finished_response(selected) {
+            return;
+        }
+        if let Some(history_index) = self.messages[selected].history_index {
+            cx.emit(ChatViewEvent::RegenerateMessage { history_index });
+        }
+    }
+
+    /// Toggle the bookmark reaction on the selected response.
+    fn bookmark_selected_message(
+        &mut self,
+        _: &BookmarkSelectedMessage,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(msg) = self
+            .selected_message
+            .and_then(|ix| self.messages.get_mut(ix))
+            .filter(|msg| {
+                matches!(msg.role, MessageRole::Assistant)
+                    && !msg.is_streaming
+                    && msg.live_trace.is_none()
+            })
+        else {
+            return;
+        };
+        let change = AnnotationChange::ToggleReaction(BOOKMARK_REACTION.to_string());
+        msg.apply_annotation(&change);
+        if let Some(history_index) = msg.history_index {
+            cx.emit(ChatViewEvent::AnnotationChanged {
+                history_index,
+                change,
+            });
+        }
+        cx.notify();
+    }
+
+    fn delete_selected_message(
+        &mut self,
+        _: &DeleteSelectedMessage,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.confirm_history_deletion(false, window, cx);
+    }
+
+    fn delete_selected_trace(
+        &mut self,
+        _: &DeleteSelectedTrace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.confirm_history_deletion(true, window, cx);
+    }
+
+    /// Ask before deleting the selected message (or only its trace) from
+    /// the conversation history; the app applies and records the deletion.
+    fn confirm_history_deletion(
+        &mut self,
+        trace_only: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(msg) = self.selected_message.and_then(|ix| self.messages.get(ix)) else {
+            return;
+        };
+        let Some(history_index) = msg.history_index else {
+            return;
+        };
+        if self
+            .messages
+            .iter()
+            .any(|msg| msg.is_streaming || msg.live_trace.is_some())
+        {
+            window.push_notification("Wait for the response to finish before editing history", cx);
+            return;
+        }
+        if trace_only && msg.system_trace_view.is_none() {
+            window.push_notification("This message has no tool trace", cx);
+            return;
+        }
+
+        let (title, body) = if trace_only {
+            (
+                "Delete Tool Trace",
+                "Remove the thinking and tool calls of this response from the history? \
+                 Its text is kept. The deletion is noted in the conversation's edit log.",
+            )
+        } else {
+            (
+                "Delete Message",
+                "Remove this message from the history? Its token usage is dropped from \
+                 the totals. The deletion is noted in the conversation's edit log.",
+            )
+        };
+        let event = if trace_only {
+            ChatViewEvent::DeleteTrace { history_index }
+        } else {
+            ChatViewEvent::DeleteMessage { history_index }
+        };
+        let view = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            dialog
+                .title(title)
+                .overlay(true)
+                .keyboard(true)
+                .close_button(true)
+                .overlay_closable(true)
+                .w(px(440.))
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .p_4()
+                        .child(div().text_sm().child(body))
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .justify_end()
+                                .child(
+                                    Button::new("cancel-delete")
+                                        .label(t("action-cancel"))
+                                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                                )
+                                .child(
+                                    Button::new("confirm-delete")
+                                        .danger()
+                                        .label(t("action-delete"))
+                                        .on_click({
+                                            let view = view.clone();
+                                            let event = event.clone();
+                                            move |_, window, cx| {
+                                                view.update(cx, |_, cx| cx.emit(event.clone()));
+                                                window.close_dialog(cx);
+                                            }
+                                        }),
+                                ),
+                        ),
+                )
+        });
+    }
+
+    fn toggle_shortcuts_help(
+        &mut self,
+        _: &ToggleShortcutsHelp,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_shortcuts = !self.show_shortcuts;
+        cx.notify();
+    }
+
+    /// Make the message list focusable and register the action handlers on it.
+    pub(super) fn message_list_actions(&self, list: Div, cx: &mut Context<Self>) -> Div {
+        list.track_focus(&self.focus_handle)
+            .key_context(MESSAGES_CONTEXT)
+            .on_action(cx.listener(Self::select_next_message))
+            .on_action(cx.listener(Self::select_previous_message))
+            .on_action(cx.listener(Self::extend_selection_down))
+            .on_action(cx.listener(Self::extend_selection_up))
+            .on_action(cx.listener(Self::copy_selected_message))
+            .on_action(cx.listener(Self::copy_selection_as_context))
+            .on_action(cx.listener(Self::edit_selected_message))
+            .on_action(cx.listener(Self::regenerate_selected_message))
+            .on_action(cx.listener(Self::bookmark_selected_message))
+            .on_action(cx.listener(Self::delete_selected_message))
+            .on_action(cx.listener(Self::delete_selected_trace))
+            .on_action(cx.listener(Self::toggle_shortcuts_help))
+    }
+
+    /// Cheat sheet listing the message keys, shown over the chat.
+    pub(super) fn render_shortcuts_overlay(&self, cx: &mut Context<Self>) -> AnyElement {
+        let keybindings = cx
+            .try_global::<KeybindingsModel>()
+            .cloned()
+            .unwrap_or_default();
+        let muted = cx.theme().muted_foreground;
+
+        let rows = MessageAction::ALL.into_iter().map(|action| {
+            let keystroke = keybindings.keystroke(action);
+            h_flex()
+                .justify_between()
+                .gap_6()
+                .text_sm()
+                .child(action.description())
+                .child(
+                    div()
+                        .px_1p5()
+                        .rounded_sm()
+                        .border_1()
+                        .border_color(cx.theme().border)
+                        .bg(cx.theme().secondary)
+                        .font_family("monospace")
+                        .when(keystroke.is_none(), |this| this.text_color(muted))
+                        .child(keystroke.unwrap_or("unbound").to_string()),
+                )
+        });
+        let rows: Vec<_> = rows.collect();
+
+        div()
+            .id("shortcuts-overlay")
+            .absolute()
+            .inset_0()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(gpui::black().opacity(0.3))
+            .on_click(cx.listener(|view, _, _, cx| {
+                view.show_shortcuts = false;
+                cx.notify();
+            }))
+            .child(
+                v_flex()
+                    .p_4()
+                    .gap_2()
+                    .min_w(px(320.))
+                    .rounded_lg()
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .bg(cx.theme().background)
+                    .shadow_lg()
+                    .child(
+                        div()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(t("chat-message-shortcuts")),
+                    )
+                    .children(rows)
+                    .child(div().pt_1().text_xs().text_color(muted).child(
+                        "Click the conversation to use these keys. \
+                                 Edit keybindings.json to change them.",
+                    )),
+            )
+            .into_any_element()
+    }
+}
@@ -0,0 +1,65 @@
+//! Read-only banner for locked conversations in `ChatView`.
+//!
+//! # What lives here
+//!
+//! The banner that takes the place of the chat input while the open
+//! conversation is locked, with a button to unlock it.
+//!
+//! # What does NOT live here
+//!
+//! - The lock itself and the guards refusing changes —
+//!   `app_controller/conversation_ops_modify.rs`.
+
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme, Icon, Sizable, h_flex};
+
+use super::{ChatView, ChatViewEvent};
+use crate::assets::CustomIcon;
+use crate::chatty::models::ConversationsStore;
+use crate::i18n::t;
+
+impl ChatView {
+    /// Whether the open conversation is locked read-only.
+    pub(super) fn is_conversation_locked(&self, cx: &App) -> bool {
+        self.conversation_id.as_ref().is_some_and(|id| {
+            cx.try_global::<ConversationsStore>()
+                .is_some_and(|store| store.is_locked(id))
+        })
+    }
+
+    pub(super) fn render_lock_banner(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        if !self.is_conversation_locked(cx) {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .mx_4()
+                .px_3()
+                .py_2()
+                .gap_2()
+                .rounded_md()
+                .border_1()
+                .border_color(cx.theme().border)
+                .bg(cx.theme().muted)
+                .child(
+                    Icon::new(CustomIcon::Lock)
+                        .size_4()
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .child(div().flex_1().text_sm().child(t("chat-locked")))
+                .child(
+                    Button::new("conversation-unlock")
+                        .ghost()
+                        .xsmall()
+                        .label(t("conversation-unlock"))
+                        .tooltip(t("chat-unlock-tooltip"))
+                        .on_click(cx.listener(|_view, _, _, cx| {
+                            cx.emit(ChatViewEvent::UnlockConversation);
+                        })),
+                )
+                .into_any_element(),
+        )
+    }
+}
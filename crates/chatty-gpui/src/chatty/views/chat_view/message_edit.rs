@@ -0,0 +1,151 @@
+//! Inline editing of sent user messages in `ChatView`.
+//!
+//! # What lives here
+//!
+//! - `start_editing` / `cancel_editing` — open or close the editor on a
+//!   user message.
+//! - `render_edit_button` — the "Edit" button under a user message.
+//! - `render_message_editor` — the editor shown in place of the message,
+//!   which emits `ChatViewEvent::EditUserMessage` on "Save & re-run".
+//!
+//! # What does NOT live here
+//!
+//! - Rewriting the history and re-streaming from the edited message —
+//!   `Conversation::edit_user_message` and `app_controller/message_ops.rs`.
+
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::{Input, InputState};
+use gpui_component::{ActiveTheme, Sizable, h_flex, v_flex};
+
+use super::{ChatView, ChatViewEvent};
+use crate::i18n::t;
+
+/// A user message open in the inline editor.
+pub(super) struct MessageEdit {
+    /// Index into `ChatView::messages`
+    pub(super) index: usize,
+    pub(super) history_index: usize,
+    pub(super) input: Entity<InputState>,
+}
+
+impl ChatView {
+    /// Open the editor on the user message at `index`, filled with its text.
+    pub(super) fn start_editing(
+        &mut self,
+        index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((history_index, content)) = self
+            .messages
+            .get(index)
+            .and_then(|msg| Some((msg.history_index?, msg.content.clone())))
+        else {
+            return;
+        };
+        let input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .auto_grow(2, 15)
+                .default_value(content)
+        });
+        input.update(cx, |input, cx| input.focus(window, cx));
+        self.editing = Some(MessageEdit {
+            index,
+            history_index,
+            input,
+        });
+        cx.notify();
+    }
+
+    pub(super) fn cancel_editing(&mut self, cx: &mut Context<Self>) {
+        if self.editing.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Send the edited text up to be re-run, unless it is empty.
+    fn save_editing(&mut self, cx: &mut Context<Self>) {
+        let Some(edit) = self.editing.as_ref() else {
+            return;
+        };
+        let text = edit.input.read(cx).value().trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let history_index = edit.history_index;
+        self.editing = None;
+        cx.emit(ChatViewEvent::EditUserMessage {
+            history_index,
+            text,
+        });
+        cx.notify();
+    }
+}
+
+/// "Edit" button under a user message that is in the history.
+pub(super) fn render_edit_button(index: usize, chat_view: Entity<ChatView>) -> AnyElement {
+    h_flex()
+        .justify_end()
+        .child(
+            Button::new(("edit-user-message", index))
+                .ghost()
+                .xsmall()
+                .label(t("action-edit"))
+                .tooltip(t("chat-edit-tooltip"))
+                .on_click(move |_, window, cx| {
+                    chat_view.update(cx, |view, cx| view.start_editing(index, window, cx));
+                }),
+        )
+        .into_any_element()
+}
+
+/// Editor shown in place of the message being edited. Saving drops every
+/// later message and re-runs the conversation from the edited one.
+pub(super) fn render_message_editor(
+    edit: &MessageEdit,
+    chat_view: Entity<ChatView>,
+    cx: &App,
+) -> AnyElement {
+    let index = edit.index;
+    let cancel_view = chat_view.clone();
+    v_flex()
+        .w_full()
+        .p_2()
+        .gap_2()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().primary)
+        .child(Input::new(&edit.input))
+        .child(
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(
+                    div()
+                        .flex_1()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(t("chat-edit-hint")),
+                )
+                .child(
+                    Button::new(("cancel-message-edit", index))
+                        .ghost()
+                        .xsmall()
+                        .label(t("action-cancel"))
+                        .on_click(move |_, _, cx| {
+                            cancel_view.update(cx, |view, cx| view.cancel_editing(cx));
+                        }),
+                )
+                .child(
+                    Button::new(("save-message-edit", index))
+                        .primary()
+                        .xsmall()
+                        .label(t("chat-edit-save-rerun"))
+                        .on_click(move |_, _, cx| {
+                            chat_view.update(cx, |view, cx| view.save_editing(cx));
+                        }),
+                ),
+        )
+        .into_any_element()
+}
@@ -34,35 +34,75 @@
 //!   thinking blocks, floating-approval keyboard shortcuts).
 //! - [`sub_agent`] — sub-agent progress trace and `add_info_message`.
 //! - [`history`] — `load_history` (conversation switching).
+//! - [`keyboard`] — j/k message navigation, message shortcuts and the
+//!   cheat-sheet overlay.
+//! - [`find`] — the Ctrl/Cmd-F find bar and its match list.
 //! - [`start_screen`] — onboarding / empty-state rendering.
+//! - [`message_edit`] — the inline editor for re-running from an edited
+//!   user message.
+//! - [`lock`] — the read-only banner shown instead of the input on a
+//!   locked conversation.
+//! - [`topic_split`] — the "new topic" banner offering to split the
+//!   conversation.
+//! - [`stream_reveal`] — smooth streaming, pacing text deltas for display.
+//! - [`turn_error`] — the error card on a prompt whose response failed.
 
 #![allow(clippy::collapsible_if)]
 
+mod find;
 mod handlers;
 mod history;
+mod keyboard;
+mod lock;
+mod message_edit;
 mod start_screen;
+mod stream_reveal;
 mod sub_agent;
+mod topic_split;
+mod turn_error;
 
+use chatty_core::models::{PinnedItem, TurnOverrides};
 use chatty_core::services::AgentTaskSnapshot;
+use chatty_core::services::model_router::RoutingDecision;
+use chatty_core::services::text_reveal::TextReveal;
+use chatty_core::tools::ExecutionPlan;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::ActiveTheme;
 use gpui_component::input::{InputEvent, InputState};
 use gpui_component::scroll::ScrollableElement;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use tracing::{debug, info, trace, warn};
 
+pub(crate) use find::FindInConversation;
+pub(crate) use keyboard::message_key_bindings;
+
 use super::agent_todo_panel::AgentTodoPanel;
-use super::chat_input::{ChatInput, ChatInputState, ModelOption, slash_menu_items_with_skills};
-use super::message_component::{DisplayMessage, MessageRenderCaches, MessageRole, render_message};
-use super::message_types::SystemTrace;
+use super::artifact_gallery_panel::{ArtifactGalleryEvent, ArtifactGalleryPanel};
+use super::chat_input::{ChatInput, ChatInputState, ModelOption};
+use super::conversation_variables_panel::ConversationVariablesPanel;
+use super::message_component::{
+    AnnotationChange, DisplayMessage, MessageRenderCaches, MessageRole, render_message,
+};
+use super::message_types::{ReflectionBlock, SystemTrace, TraceItem};
+use super::onboarding_view::{OnboardingState, OnboardingView};
 use super::parsed_cache::{ParsedContentCache, StreamingParseState};
+use super::pdf_viewer_panel::{PdfViewerEvent, PdfViewerPanel};
+use super::pinned_context_panel::PinnedContextPanel;
+use super::plan_checklist_panel::PlanChecklistPanel;
 use super::thinking_indicator::{ThinkingIndicator, new_thinking_indicator};
 use super::trace_components::SystemTraceView;
+use super::user_question_card::UserQuestionCard;
 use crate::chatty::models::MessageFeedback;
+use crate::chatty::models::execution_approval_store::UserQuestion;
+use crate::i18n::t;
 use crate::settings::models::execution_settings::ExecutionSettingsModel;
 use crate::settings::models::models_store::ModelsModel;
+use crate::settings::models::{GeneralSettingsModel, SendShortcut};
+
+/// Frames to wait for a restored conversation to become scrollable.
+const SCROLL_RESTORE_FRAMES: u8 = 10;
 
 /// Main chat view component
 #[derive(Clone)]
@@ -71,6 +111,8 @@ pub struct PendingApprovalInfo {
     pub command: String,
     pub is_sandboxed: bool,
     pub conversation_id: String,
+    /// Set when the agent asked a question via `ask_user`
+    pub question: Option<UserQuestion>,
 }
 
 pub struct ChatView {
@@ -78,6 +120,23 @@ pub struct ChatView {
     messages: Vec<DisplayMessage>,
     conversation_id: Option<String>,
     scroll_handle: ScrollHandle,
+    /// Tracks the bounds of the rendered messages (children of the list
+    /// inside the scroll area) so keyboard selection can scroll to them.
+    message_items: ScrollHandle,
+    /// Focus of the message list; its key context receives the message shortcuts.
+    focus_handle: FocusHandle,
+    /// Index into `messages` of the message selected with the keyboard.
+    selected_message: Option<usize>,
+    /// Other end of a Shift-extended selection (index into `messages`).
+    selection_anchor: Option<usize>,
+    /// Whether the message-shortcuts cheat sheet is open.
+    show_shortcuts: bool,
+    /// Find-in-conversation bar, while open.
+    find_bar: Option<find::FindBar>,
+    /// Whether the latest message changed the topic and a split is offered.
+    topic_split_suggested: bool,
+    /// User message open in the inline editor.
+    editing: Option<message_edit::MessageEdit>,
     pending_approval: Option<PendingApprovalInfo>,
     /// Tracks which tool calls are collapsed: (message_idx, tool_idx) -> collapsed
     collapsed_tool_calls: HashMap<(usize, usize), bool>,
@@ -88,10 +147,20 @@ pub struct ChatView {
     /// Incremental streaming parse state, reusing stable content/markdown segments
     /// across streaming renders. Cleared on stream finalization or conversation switch.
     streaming_parse_cache: Option<StreamingParseState>,
+    /// Streamed text waiting to be shown while smooth streaming is on.
+    stream_reveal: Option<TextReveal>,
+    /// Whether the timer draining `stream_reveal` is running.
+    stream_reveal_ticking: bool,
     /// When true, every render re-asserts scroll_to_bottom so that async
     /// layout changes (image loading, SVG math, code blocks) never leave
     /// the view stuck above the true bottom. Disabled when user scrolls up.
     stick_to_bottom: bool,
+    /// Scroll offset to apply once the restored conversation has been laid
+    /// out, with the number of frames left to wait for that.
+    pending_scroll_offset: Option<(Pixels, u8)>,
+    /// Message (index into `messages`) to scroll to once it has been laid
+    /// out, e.g. a search result, with the number of frames left to wait.
+    pending_reveal: Option<(usize, u8)>,
     /// Keystroke interceptor that handles ↑/↓ for the slash-command picker.
     /// Must be held here so it stays alive (dropping it unregisters the handler).
     _slash_menu_interceptor: Subscription,
@@ -107,6 +176,21 @@ pub struct ChatView {
     thinking_indicator: Entity<ThinkingIndicator>,
     agent_task_snapshot: Option<AgentTaskSnapshot>,
     agent_task_panel_collapsed: bool,
+    /// Items pinned to the active conversation, shown in the context drawer.
+    pinned_items: Vec<PinnedItem>,
+    pinned_panel_collapsed: bool,
+    /// Agent-written conversation variables, shown in the side panel.
+    variables: BTreeMap<String, String>,
+    variables_panel_collapsed: bool,
+    /// Planner-mode plan of the active conversation, shown as a checklist.
+    execution_plan: Option<ExecutionPlan>,
+    plan_panel_collapsed: bool,
+    /// PDF attachment open in the inline viewer, shown beside the messages.
+    pdf_viewer: Option<Entity<PdfViewerPanel>>,
+    /// Files produced by the conversation's tools, while the gallery is open.
+    artifact_gallery: Option<Entity<ArtifactGalleryPanel>>,
+    /// Provider setup wizard, shown in place of the start screen while open.
+    onboarding: Option<Entity<OnboardingView>>,
 }
 
 /// Events emitted by ChatView for actions that require app-level handling
@@ -118,8 +202,33 @@ pub enum ChatViewEvent {
         history_index: usize,
         feedback: Option<MessageFeedback>,
     },
+    /// User toggled a reaction or edited the private note on a message
+    AnnotationChanged {
+        history_index: usize,
+        change: AnnotationChange,
+    },
     /// User clicked "Regenerate" on an assistant message
     RegenerateMessage { history_index: usize },
+    /// User clicked "Pin to context" on a message
+    PinMessage { history_index: usize },
+    /// User confirmed deleting a message from the history
+    DeleteMessage { history_index: usize },
+    /// User confirmed deleting a message's tool trace from the history
+    DeleteTrace { history_index: usize },
+    /// User removed an item from the context drawer
+    UnpinItem { id: String },
+    /// User deleted a conversation variable from the side panel
+    DeleteVariable { name: String },
+    /// User cleared the planner-mode plan from the checklist
+    ClearExecutionPlan,
+    /// User accepted continuing a changed topic in a new conversation
+    SplitConversation,
+    /// User clicked "Retry" on the error card of a failed prompt
+    RetryFailedTurn { history_index: usize },
+    /// User clicked "Unlock" on the banner of a locked conversation
+    UnlockConversation,
+    /// User saved an edit of a sent message, to re-run the conversation from it
+    EditUserMessage { history_index: usize, text: String },
 }
 
 impl EventEmitter<ChatViewEvent> for ChatView {}
@@ -128,7 +237,7 @@ impl ChatView {
     pub fn new(window: &mut Window, cx: &mut App) -> Self {
         let input = cx.new(|cx| {
             InputState::new(window, cx)
-                .placeholder("Type a message...")
+                .placeholder(t("chat-input-placeholder"))
                 .clean_on_escape()
                 .auto_grow(2, 15)
         });
@@ -142,9 +251,14 @@ impl ChatView {
         cx.subscribe(&input, move |_input_state, event: &InputEvent, cx| {
             match event {
                 InputEvent::PressEnter { secondary } => {
-                    // Only send on plain Enter (not Shift+Enter)
+                    // Only send on plain Enter (not Shift+Enter), and only when
+                    // Enter is the send key; Ctrl+Enter is handled by the
+                    // keystroke interceptor below.
                     if !secondary {
                         tracing::debug!("Enter key pressed");
+                        let enter_sends = cx
+                            .try_global::<GeneralSettingsModel>()
+                            .is_none_or(|s| s.send_shortcut == SendShortcut::Enter);
                         state_for_enter.update(cx, |state, cx| {
                             // If the slash-command menu is open, apply the selected
                             // command instead of sending the message as a chat turn.
@@ -152,7 +266,7 @@ impl ChatView {
                                 state.apply_slash_command(cx);
                             } else if state.is_at_menu_open(cx) {
                                 state.apply_at_mention(cx);
-                            } else {
+                            } else if enter_sends {
                                 state.send_message(cx);
                             }
                         });
@@ -166,16 +280,35 @@ impl ChatView {
                         let new_text = state.input.read(cx).text().to_string();
                         state.reset_slash_menu_selection_if_query_changed(&new_text);
                         state.reset_at_menu_selection_if_query_changed(&new_text);
+                        state.note_draft_text(&new_text, cx);
+                        let spelling_changed = state.refresh_spelling(&new_text, cx);
+                        if state.refresh_draft_tokens(&new_text, cx) || spelling_changed {
+                            cx.notify();
+                        }
 
                         // Load files for the @ menu on first use.
-                        let global_dir = cx
+                        let (execution_enabled, workspace_dir) = cx
                             .try_global::<ExecutionSettingsModel>()
-                            .and_then(|s| s.workspace_dir.clone())
-                            .map(std::path::PathBuf::from)
+                            .map_or((false, None), |s| {
+                                (
+                                    s.enabled,
+                                    s.workspace_dir.clone().map(std::path::PathBuf::from),
+                                )
+                            });
+                        let global_dir = workspace_dir
+                            .clone()
                             .or_else(|| std::env::current_dir().ok());
                         if state.refresh_at_files_if_needed(&new_text, global_dir) {
                             cx.notify();
                         }
+                        // Index the workspace for path completions; paths are
+                        // relative to the directory the filesystem tools use.
+                        state.refresh_path_index_if_needed(
+                            &new_text,
+                            workspace_dir,
+                            execution_enabled,
+                            cx,
+                        );
                     });
                 }
                 _ => {}
@@ -194,10 +327,29 @@ impl ChatView {
         // slash-command picker and the @ mention picker.  This fires *before*
         // GPUI dispatches action handlers, so calling cx.stop_propagation()
         // here prevents the InputState's MoveUp/MoveDown cursor-movement
-        // actions from running.
+        // actions from running.  It also sends on Ctrl+Enter (Cmd+Enter on
+        // macOS) when that is the configured send key, before the input
+        // inserts a newline.
         let input_for_interceptor = chat_input_state.clone();
-        let slash_menu_interceptor = cx.intercept_keystrokes(move |event, _window, cx| {
+        let slash_menu_interceptor = cx.intercept_keystrokes(move |event, window, cx| {
             let key = event.keystroke.key.as_str();
+            let modifiers = &event.keystroke.modifiers;
+            if key == "enter" && (modifiers.control || modifiers.platform) && !modifiers.shift {
+                let ctrl_enter_sends = cx
+                    .try_global::<GeneralSettingsModel>()
+                    .is_some_and(|s| s.send_shortcut == SendShortcut::CtrlEnter);
+                let input_focused = input_for_interceptor
+                    .read(cx)
+                    .input
+                    .read(cx)
+                    .focus_handle(cx)
+                    .is_focused(window);
+                if ctrl_enter_sends && input_focused {
+                    input_for_interceptor.update(cx, |state, cx| state.send_message(cx));
+                    cx.stop_propagation();
+                }
+                return;
+            }
             // Only intercept plain ↑ / ↓ (no modifier keys).
             if (key != "up" && key != "down")
                 || event.keystroke.modifiers.control
@@ -207,14 +359,12 @@ impl ChatView {
                 return;
             }
             // Check whether the slash-command picker is currently showing.
-            let (input_text, skills) = {
+            let (input_text, items) = {
                 let state = input_for_interceptor.read(cx);
-                (
-                    state.input.read(cx).text().to_string(),
-                    state.available_skills().to_vec(),
-                )
+                let input_text = state.input.read(cx).text().to_string();
+                let items = state.slash_menu_items(&input_text, cx);
+                (input_text, items)
             };
-            let items = slash_menu_items_with_skills(&input_text, &skills);
             if !items.is_empty() {
                 let num = items.len();
                 input_for_interceptor.update(cx, |state, cx| {
@@ -250,17 +400,38 @@ impl ChatView {
             messages: Vec::new(),
             conversation_id: None,
             scroll_handle,
+            message_items: ScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            selected_message: None,
+            selection_anchor: None,
+            show_shortcuts: false,
+            find_bar: None,
+            topic_split_suggested: false,
+            editing: None,
             pending_approval: None,
             collapsed_tool_calls: HashMap::new(),
             diff_expanded: HashMap::new(),
             parsed_cache: ParsedContentCache::new(),
             streaming_parse_cache: None,
+            stream_reveal: None,
+            stream_reveal_ticking: false,
             stick_to_bottom: true,
+            pending_scroll_offset: None,
+            pending_reveal: None,
             _slash_menu_interceptor: slash_menu_interceptor,
             sub_agent_progress_msg_idx: None,
             thinking_indicator: new_thinking_indicator(cx),
             agent_task_snapshot: None,
             agent_task_panel_collapsed: false,
+            pinned_items: Vec::new(),
+            pinned_panel_collapsed: true,
+            variables: BTreeMap::new(),
+            variables_panel_collapsed: false,
+            execution_plan: None,
+            plan_panel_collapsed: false,
+            pdf_viewer: None,
+            artifact_gallery: None,
+            onboarding: None,
         }
     }
 
@@ -331,6 +502,173 @@ impl ChatView {
         )
     }
 
+    /// Replace the items shown in the pinned-context drawer.
+    pub fn set_pinned_items(&mut self, items: Vec<PinnedItem>, cx: &mut Context<Self>) {
+        self.pinned_items = items;
+        cx.notify();
+    }
+
+    fn toggle_pinned_panel(&mut self, cx: &mut Context<Self>) {
+        self.pinned_panel_collapsed = !self.pinned_panel_collapsed;
+        cx.notify();
+    }
+
+    fn render_pinned_context_panel(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        if self.pinned_items.is_empty() {
+            return None;
+        }
+
+        let toggle_entity = cx.entity();
+        let unpin_entity = cx.entity();
+        Some(
+            PinnedContextPanel::new(self.pinned_items.clone(), self.pinned_panel_collapsed)
+                .on_toggle(move |cx| {
+                    toggle_entity.update(cx, |view, cx| view.toggle_pinned_panel(cx));
+                })
+                .on_unpin(move |id, cx| {
+                    unpin_entity.update(cx, |_view, cx| {
+                        cx.emit(ChatViewEvent::UnpinItem { id });
+                    });
+                })
+                .into_any_element(),
+        )
+    }
+
+    /// Replace the variables shown in the side panel.
+    pub fn set_variables(&mut self, variables: BTreeMap<String, String>, cx: &mut Context<Self>) {
+        self.variables = variables;
+        cx.notify();
+    }
+
+    fn toggle_variables_panel(&mut self, cx: &mut Context<Self>) {
+        self.variables_panel_collapsed = !self.variables_panel_collapsed;
+        cx.notify();
+    }
+
+    fn render_variables_panel(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        if self.variables.is_empty() {
+            return None;
+        }
+
+        let toggle_entity = cx.entity();
+        let delete_entity = cx.entity();
+        Some(
+            ConversationVariablesPanel::new(self.variables.clone(), self.variables_panel_collapsed)
+                .on_toggle(move |cx| {
+                    toggle_entity.update(cx, |view, cx| view.toggle_variables_panel(cx));
+                })
+                .on_delete(move |name, cx| {
+                    delete_entity.update(cx, |_view, cx| {
+                        cx.emit(ChatViewEvent::DeleteVariable { name });
+                    });
+                })
+                .into_any_element(),
+        )
+    }
+
+    /// Open `path` in the inline PDF viewer, replacing any PDF already open.
+    pub fn open_pdf(&mut self, path: PathBuf, cx: &mut Context<Self>) {
+        if self
+            .pdf_viewer
+            .as_ref()
+            .is_some_and(|viewer| viewer.read(cx).path() == &path)
+        {
+            return;
+        }
+
+        let viewer = cx.new(|cx| PdfViewerPanel::new(path, cx));
+        cx.subscribe(
+            &viewer,
+            |view, _viewer, event: &PdfViewerEvent, cx| match event {
+                PdfViewerEvent::AskAboutPage { image, page } => {
+                    debug!(?image, page, "Attaching PDF page render to the next prompt");
+                    view.chat_input_state.update(cx, |state, cx| {
+                        state.add_attachments(vec![image.clone()], cx);
+                    });
+                }
+                PdfViewerEvent::Close => {
+                    view.pdf_viewer = None;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+        self.pdf_viewer = Some(viewer);
+        cx.notify();
+    }
+
+    /// Open the artifact gallery beside the messages, or close it if open.
+    pub fn toggle_artifact_gallery(&mut self, cx: &mut Context<Self>) {
+        if self.artifact_gallery.take().is_none() {
+            let gallery = cx.new(ArtifactGalleryPanel::new);
+            cx.subscribe(
+                &gallery,
+                |view, _gallery, event: &ArtifactGalleryEvent, cx| match event {
+                    ArtifactGalleryEvent::Close => {
+                        view.artifact_gallery = None;
+                        cx.notify();
+                    }
+                },
+            )
+            .detach();
+            self.artifact_gallery = Some(gallery);
+        }
+        cx.notify();
+    }
+
+    /// Replace the plan shown in the planner-mode checklist.
+    pub fn set_execution_plan(&mut self, plan: Option<ExecutionPlan>, cx: &mut Context<Self>) {
+        // Collapse a finished plan; re-expand when a new or reopened plan arrives.
+        self.plan_panel_collapsed = plan.as_ref().is_some_and(|plan| plan.is_complete());
+        self.execution_plan = plan;
+        cx.notify();
+    }
+
+    fn toggle_plan_panel(&mut self, cx: &mut Context<Self>) {
+        self.plan_panel_collapsed = !self.plan_panel_collapsed;
+        cx.notify();
+    }
+
+    fn render_plan_panel(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let plan = self.execution_plan.clone()?;
+
+        let toggle_entity = cx.entity();
+        let dismiss_entity = cx.entity();
+        Some(
+            PlanChecklistPanel::new(plan, self.plan_panel_collapsed)
+                .on_toggle(move |cx| {
+                    toggle_entity.update(cx, |view, cx| view.toggle_plan_panel(cx));
+                })
+                .on_dismiss(move |cx| {
+                    dismiss_entity.update(cx, |_view, cx| {
+                        cx.emit(ChatViewEvent::ClearExecutionPlan);
+                    });
+                })
+                .into_any_element(),
+        )
+    }
+
+    fn render_question_card(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let question = self.active_approval_for_display()?.question?;
+
+        let answer_entity = cx.entity();
+        let dismiss_entity = cx.entity();
+        Some(
+            UserQuestionCard::new(question)
+                .on_answer(move |answer, cx| {
+                    answer_entity.update(cx, |view, cx| {
+                        view.answer_pending_question(Some(answer), cx);
+                    });
+                })
+                .on_dismiss(move |cx| {
+                    dismiss_entity.update(cx, |view, cx| {
+                        view.answer_pending_question(None, cx);
+                    });
+                })
+                .into_any_element(),
+        )
+    }
+
     /// Add a user message to the chat
     pub fn add_user_message(
         &mut self,
@@ -349,8 +687,12 @@ impl ChatView {
             is_markdown: true,
             attachments,
             feedback: None,
+            reactions: Vec::new(),
+            note: None,
+            error: None,
             history_index: None,
         });
+        self.topic_split_suggested = false;
 
         debug!(total_messages = self.messages.len(), "User message added");
         cx.notify();
@@ -360,6 +702,7 @@ impl ChatView {
     /// Start an assistant message (for streaming)
     pub fn start_assistant_message(&mut self, cx: &mut Context<Self>) {
         debug!("Starting assistant message");
+        self.discard_stream_reveal();
 
         self.messages.push(DisplayMessage {
             role: MessageRole::Assistant,
@@ -370,6 +713,9 @@ impl ChatView {
             is_markdown: true,
             attachments: Vec::new(),
             feedback: None,
+            reactions: Vec::new(),
+            note: None,
+            error: None,
             history_index: None,
         });
 
@@ -388,6 +734,74 @@ impl ChatView {
         self.activate_sticky_scroll();
     }
 
+    /// Record the model routing decision on the streaming message's trace
+    pub fn set_routing_decision(&mut self, decision: RoutingDecision, cx: &mut Context<Self>) {
+        if let Some(trace) = self
+            .messages
+            .last_mut()
+            .filter(|m| m.is_streaming)
+            .and_then(|m| m.live_trace.as_mut())
+        {
+            trace.routing = Some(decision);
+            cx.notify();
+        }
+    }
+
+    /// Record the parameters chosen for this turn only on the streaming
+    /// message's trace
+    pub fn set_turn_overrides(&mut self, overrides: TurnOverrides, cx: &mut Context<Self>) {
+        if let Some(trace) = self
+            .messages
+            .last_mut()
+            .filter(|m| m.is_streaming)
+            .and_then(|m| m.live_trace.as_mut())
+        {
+            trace.overrides = Some(overrides);
+            cx.notify();
+        }
+    }
+
+    /// Record the provider privacy policy on the streaming message's trace
+    pub fn set_request_privacy(&mut self, policy: String, cx: &mut Context<Self>) {
+        if let Some(trace) = self
+            .messages
+            .last_mut()
+            .filter(|m| m.is_streaming)
+            .and_then(|m| m.live_trace.as_mut())
+        {
+            trace.request_privacy = Some(policy);
+            cx.notify();
+        }
+    }
+
+    /// Mark the streaming message's trace as ended by a `/timebox` budget
+    pub fn set_budget_reached(&mut self, marker: String, cx: &mut Context<Self>) {
+        if let Some(trace) = self
+            .messages
+            .last_mut()
+            .filter(|m| m.is_streaming)
+            .and_then(|m| m.live_trace.as_mut())
+        {
+            trace.budget_reached = Some(marker);
+            cx.notify();
+        }
+    }
+
+    /// Put the self-check that triggered this retry at the top of the
+    /// streaming message's trace
+    pub fn set_reflection(&mut self, reflection: ReflectionBlock, cx: &mut Context<Self>) {
+        let Some(mut trace) = self
+            .messages
+            .last()
+            .filter(|m| m.is_streaming)
+            .map(|m| m.live_trace.clone().unwrap_or_else(SystemTrace::new))
+        else {
+            return;
+        };
+        trace.items.insert(0, TraceItem::Reflection(reflection));
+        self.restore_live_trace(trace, cx);
+    }
+
     /// Append text to the current streaming assistant message
     pub fn append_assistant_text(&mut self, text: &str, cx: &mut Context<Self>) {
         let last_msg_streaming = self
@@ -429,6 +843,7 @@ impl ChatView {
 
     /// Finalize the current streaming assistant message
     pub fn finalize_assistant_message(&mut self, cx: &mut Context<Self>) {
+        self.flush_stream_reveal(cx);
         if let Some(last) = self.messages.last_mut() {
             let had_live_trace = last.live_trace.is_some();
             let had_streaming_cache = self.streaming_parse_cache.is_some();
@@ -495,6 +910,18 @@ impl ChatView {
                 cx.notify();
             }
         }
+        // The prompt it answers directly precedes it in the history; knowing
+        // its index lets the freshly sent message be edited too
+        let len = self.messages.len();
+        if let Some(prompt) = self.messages[..len.saturating_sub(1)]
+            .iter_mut()
+            .rev()
+            .find(|m| matches!(m.role, MessageRole::User))
+        {
+            if prompt.history_index.is_none() {
+                prompt.history_index = history_index.checked_sub(1);
+            }
+        }
     }
 
     /// Set attachments on the last assistant DisplayMessage.
@@ -515,6 +942,7 @@ impl ChatView {
 
     /// Mark the current streaming message as cancelled by the user
     pub fn mark_message_cancelled(&mut self, cx: &mut Context<Self>) {
+        self.flush_stream_reveal(cx);
         if let Some(last) = self.messages.last_mut() {
             if last.is_streaming {
                 // Append cancellation notice to the message
@@ -601,9 +1029,41 @@ impl ChatView {
     /// Sticky mode is automatically disabled when the user scrolls up.
     fn activate_sticky_scroll(&mut self) {
         self.stick_to_bottom = true;
+        self.pending_scroll_offset = None;
         self.scroll_handle.scroll_to_bottom();
     }
 
+    /// Vertical offset of the message list, saved with the session on quit.
+    pub fn scroll_offset(&self) -> f32 {
+        self.scroll_handle.offset().y.as_f32()
+    }
+
+    /// Scroll to a saved offset instead of the bottom once the loaded
+    /// history has been laid out.
+    pub fn restore_scroll_offset(&mut self, offset: f32, cx: &mut Context<Self>) {
+        self.stick_to_bottom = false;
+        self.pending_scroll_offset = Some((px(offset), SCROLL_RESTORE_FRAMES));
+        cx.notify();
+    }
+
+    /// Select the message at `history_index` (or the closest one before it)
+    /// and scroll to it once the loaded history has been laid out.
+    pub fn reveal_history_message(&mut self, history_index: usize, cx: &mut Context<Self>) {
+        let Some(message) = self.listed_message_indices().into_iter().rfind(|&ix| {
+            self.messages[ix]
+                .history_index
+                .is_some_and(|i| i <= history_index)
+        }) else {
+            return;
+        };
+        self.selected_message = Some(message);
+        self.selection_anchor = None;
+        self.stick_to_bottom = false;
+        self.pending_scroll_offset = None;
+        self.pending_reveal = Some((message, SCROLL_RESTORE_FRAMES));
+        cx.notify();
+    }
+
     /// If sticky-scroll is active, re-assert scroll_to_bottom for this frame.
     /// Used for incremental streaming updates — respects the user's decision
     /// to scroll up by not re-enabling sticky mode.
@@ -660,11 +1120,52 @@ impl ChatView {
             }
         }
 
+        // Restored scroll offset: wait until the history has a scrollable
+        // height, for a few frames at most (short histories never do).
+        if let Some((offset, frames_left)) = self.pending_scroll_offset.take() {
+            let max_offset = self.scroll_handle.max_offset().height;
+            if max_offset > px(0.0) {
+                self.scroll_handle
+                    .set_offset(point(px(0.0), offset.max(-max_offset)));
+            } else if frames_left > 0 {
+                self.pending_scroll_offset = Some((offset, frames_left - 1));
+                cx.on_next_frame(window, |_view, _window, cx| cx.notify());
+            }
+        }
+
+        // Revealed message: wait until the list has bounds for it
+        if let Some((message, frames_left)) = self.pending_reveal.take()
+            && let Some(position) = self
+                .listed_message_indices()
+                .iter()
+                .position(|&ix| ix == message)
+        {
+            if self.message_items.bounds_for_item(position).is_some() {
+                self.scroll_item_into_view(position);
+            } else if frames_left > 0 {
+                self.pending_reveal = Some((message, frames_left - 1));
+                cx.on_next_frame(window, |_view, _window, cx| cx.notify());
+            }
+        }
+
+        // Keep find matches current while messages stream in or change
+        self.refresh_find_matches(false);
+
         // Clear the input if a message was sent
         self.chat_input_state.update(cx, |state, cx| {
             state.clear_if_needed(window, cx);
         });
 
+        // Create the setup wizard when it opens and drop it when it closes,
+        // so reopening it starts from the first step
+        if OnboardingState::is_open(cx) {
+            if self.onboarding.is_none() {
+                self.onboarding = Some(cx.new(|cx| OnboardingView::new(window, cx)));
+            }
+        } else {
+            self.onboarding = None;
+        }
+
         // Auto-create first conversation if needed (one-time check)
         use crate::chatty::models::ConversationsStore;
         if self.conversation_id.is_none() {
@@ -685,7 +1186,10 @@ impl ChatView {
             let models_list: Vec<ModelOption> = models_model
                 .models()
                 .iter()
-                .map(|m| ModelOption::new(m.id.clone(), m.name.clone(), m.provider_type.clone()))
+                .map(|m| {
+                    ModelOption::new(m.id.clone(), m.name.clone(), m.provider_type.clone())
+                        .with_aliases(m.aliases.clone())
+                })
                 .collect();
 
             if !models_list.is_empty() {
@@ -702,8 +1206,13 @@ impl ChatView {
     }
 
     /// Render the scrollable message list area including the loading skeleton.
-    fn render_message_list(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_message_list(&mut self, window: &Window, cx: &mut Context<Self>) -> impl IntoElement {
         let is_awaiting = self.is_awaiting_response();
+        // The keyboard selection is only shown while the list has focus.
+        let selected_range = self
+            .selected_range()
+            .filter(|_| self.focus_handle.contains_focused(window, cx));
+        let selection_color = cx.theme().primary;
         let chat_view_entity = cx.entity();
 
         // Temporarily move state out to avoid split borrows
@@ -717,14 +1226,7 @@ impl ChatView {
             .messages
             .iter()
             .enumerate()
-            .filter(|(_, msg)| {
-                !(msg.is_streaming
-                    && msg.content.is_empty()
-                    && !msg
-                        .live_trace
-                        .as_ref()
-                        .is_some_and(|trace| trace.has_items()))
-            })
+            .filter(|(_, msg)| is_listed(msg))
             .collect();
 
         trace!(
@@ -748,6 +1250,11 @@ impl ChatView {
             })
             .map(|(idx, _)| *idx);
 
+        let conversation_id = self.conversation_id.clone();
+        // Sent messages can be edited while nothing streams into the list
+        let can_edit = !self.messages.last().is_some_and(|m| m.is_streaming)
+            && !self.is_conversation_locked(cx);
+        let editing = self.editing.as_ref();
         let mut rendered: Vec<AnyElement> = visible_messages
             .into_iter()
             .map(|(index, msg)| {
@@ -755,15 +1262,21 @@ impl ChatView {
                 let entity_for_diff = chat_view_entity.clone();
                 let entity_for_feedback = chat_view_entity.clone();
                 let entity_for_regenerate = chat_view_entity.clone();
+                let entity_for_pin = chat_view_entity.clone();
+                let entity_for_annotate = chat_view_entity.clone();
                 let history_index = msg.history_index;
                 let is_last_message = last_visible_assistant_idx == Some(index);
+                let is_selected = selected_range
+                    .as_ref()
+                    .is_some_and(|range| range.contains(&index));
+                let mut find = self.find_highlighter(index);
                 let mut no_cache: Option<StreamingParseState> = None;
                 let sc = if msg.is_streaming {
                     &mut streaming_cache
                 } else {
                     &mut no_cache
                 };
-                render_message(
+                let message = render_message(
                     msg,
                     index,
                     is_last_message,
@@ -772,6 +1285,7 @@ impl ChatView {
                     &mut MessageRenderCaches {
                         parsed: &mut parsed_cache,
                         streaming: sc,
+                        find: &mut find,
                     },
                     move |msg_idx, tool_idx, cx| {
                         entity_clone.update(cx, |chat_view, cx| {
@@ -817,15 +1331,71 @@ impl ChatView {
                             }
                         });
                     },
+                    move |_msg_idx, cx| {
+                        entity_for_pin.update(cx, |_chat_view, cx| {
+                            if let Some(h_idx) = history_index {
+                                cx.emit(ChatViewEvent::PinMessage {
+                                    history_index: h_idx,
+                                });
+                            }
+                        });
+                    },
+                    move |msg_idx, change, cx| {
+                        entity_for_annotate.update(cx, |chat_view, cx| {
+                            if let Some(display_msg) = chat_view.messages.get_mut(msg_idx) {
+                                display_msg.apply_annotation(&change);
+                            }
+                            if let Some(h_idx) = history_index {
+                                cx.emit(ChatViewEvent::AnnotationChanged {
+                                    history_index: h_idx,
+                                    change,
+                                });
+                            }
+                            cx.notify();
+                        });
+                    },
                     cx,
-                )
-                .into_any_element()
+                );
+                let error_card = turn_error::render_turn_error_card(
+                    msg,
+                    index,
+                    index + 1 == total_messages && !is_awaiting,
+                    conversation_id.as_deref(),
+                    chat_view_entity.clone(),
+                    cx,
+                );
+                let message = match editing.filter(|edit| edit.index == index) {
+                    Some(edit) => {
+                        message_edit::render_message_editor(edit, chat_view_entity.clone(), cx)
+                    }
+                    None => message,
+                };
+                let edit_button = (can_edit
+                    && editing.is_none()
+                    && matches!(msg.role, MessageRole::User)
+                    && history_index.is_some())
+                .then(|| message_edit::render_edit_button(index, chat_view_entity.clone()));
+                div()
+                    .w_full()
+                    .border_l_2()
+                    .border_color(if is_selected {
+                        selection_color
+                    } else {
+                        transparent_black()
+                    })
+                    .child(message)
+                    .children(edit_button)
+                    .children(error_card)
+                    .into_any_element()
             })
             .collect();
 
         let show_start_screen = rendered.is_empty() && !is_awaiting;
         if show_start_screen {
-            rendered.push(self.render_start_screen(cx).into_any_element());
+            match &self.onboarding {
+                Some(onboarding) => rendered.push(onboarding.clone().into_any_element()),
+                None => rendered.push(self.render_start_screen(cx).into_any_element()),
+            }
         }
 
         // Move state back
@@ -837,10 +1407,8 @@ impl ChatView {
         let thinking_visible = self.is_thinking_indicator_visible();
         let thinking_indicator = self.thinking_indicator.clone();
 
-        div()
-            .flex_1()
-            .min_h_0()
-            .relative()
+        let list = div().flex_1().min_h_0().relative();
+        self.message_list_actions(list, cx)
             .child(
                 div()
                     .id("chat-messages")
@@ -849,6 +1417,7 @@ impl ChatView {
                     .size_full()
                     .child(
                         div()
+                            .track_scroll(&self.message_items)
                             .p_4()
                             .w_full()
                             .flex()
@@ -884,18 +1453,7 @@ impl ChatView {
         }
 
         let total = self.messages.len();
-        let visible = self
-            .messages
-            .iter()
-            .filter(|msg| {
-                !(msg.is_streaming
-                    && msg.content.is_empty()
-                    && !msg
-                        .live_trace
-                        .as_ref()
-                        .is_some_and(|trace| trace.has_items()))
-            })
-            .count();
+        let visible = self.messages.iter().filter(|msg| is_listed(msg)).count();
         let filtered = total - visible;
         let is_awaiting = self.is_awaiting_response();
 
@@ -954,6 +1512,17 @@ impl ChatView {
     }
 }
 
+/// Whether a message gets a row in the list. A response without text or
+/// tool calls yet is represented by the thinking indicator instead.
+fn is_listed(msg: &DisplayMessage) -> bool {
+    !(msg.is_streaming
+        && msg.content.is_empty()
+        && !msg
+            .live_trace
+            .as_ref()
+            .is_some_and(|trace| trace.has_items()))
+}
+
 /// Process-wide flag for the `CHATTY_DEBUG_UI` env var. Read once at startup
 /// so each render call is a single atomic load rather than a syscall.
 static DEBUG_UI_ENABLED: std::sync::LazyLock<bool> = std::sync::LazyLock::new(|| {
@@ -966,13 +1535,23 @@ impl Render for ChatView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.prepare_render(window, cx);
 
-        let has_pending_approval = self.pending_approval.is_some();
+        // Questions are answered through the card / chat input, not the approve/deny shortcuts
+        let has_pending_approval = self
+            .pending_approval
+            .as_ref()
+            .is_some_and(|pending| pending.question.is_none());
         let view_entity_for_keys = cx.entity();
         let pending_conv_id = self
             .pending_approval
             .as_ref()
             .map(|p| p.conversation_id.clone());
         let current_conv_id = self.conversation_id.clone();
+        let approval_for_bar = self
+            .active_approval_for_display()
+            .filter(|pending| pending.question.is_none());
+        let shortcuts_overlay = self
+            .show_shortcuts
+            .then(|| self.render_shortcuts_overlay(cx));
 
         div()
             .flex_1()
@@ -984,6 +1563,7 @@ impl Render for ChatView {
             .bg(cx.theme().background)
             .overflow_hidden()
             .when(cfg!(target_os = "macos"), |this| this.pt(px(24.)))
+            .on_action(cx.listener(Self::open_find))
             .when(has_pending_approval, |this| {
                 this.on_key_down(move |event: &KeyDownEvent, _window, cx| {
                     let modifiers = event.keystroke.modifiers;
@@ -1031,11 +1611,27 @@ impl Render for ChatView {
                     }
                 })
             })
-            .child(self.render_message_list(cx))
+            .when_some(self.render_find_bar(cx), |this, bar| this.child(bar))
+            .child(
+                div()
+                    .flex_1()
+                    .min_h_0()
+                    .w_full()
+                    .flex()
+                    .flex_row()
+                    .child(self.render_message_list(window, cx))
+                    .when_some(self.pdf_viewer.clone(), |this, viewer| this.child(viewer))
+                    .when_some(self.artifact_gallery.clone(), |this, gallery| {
+                        this.child(gallery)
+                    })
+                    .when_some(self.render_variables_panel(cx), |this, panel| {
+                        this.child(panel)
+                    }),
+            )
             .when_some(self.render_debug_overlay(cx), |this, overlay| {
                 this.child(overlay)
             })
-            .when_some(self.active_approval_for_display(), |this, pending| {
+            .when_some(approval_for_bar, |this, pending| {
                 let view_entity = cx.entity();
                 this.child(
                     div().child(
@@ -1061,11 +1657,27 @@ impl Render for ChatView {
                     .pb_4()
                     .child(
                         div()
+                            .when_some(self.render_topic_split_banner(cx), |this, banner| {
+                                this.child(banner)
+                            })
+                            .when_some(self.render_question_card(cx), |this, card| {
+                                this.child(card)
+                            })
+                            .when_some(self.render_pinned_context_panel(cx), |this, panel| {
+                                this.child(panel)
+                            })
+                            .when_some(self.render_plan_panel(cx), |this, panel| {
+                                this.child(panel)
+                            })
                             .when_some(self.render_agent_task_panel(cx), |this, panel| {
                                 this.child(panel)
                             })
-                            .child(div().px_4().child(ChatInput::new(self.chat_input_state.clone()))),
+                            .map(|this| match self.render_lock_banner(cx) {
+                                Some(banner) => this.child(banner),
+                                None => this.child(div().px_4().child(ChatInput::new(self.chat_input_state.clone()))),
+                            }),
                     )
             )
+            .children(shortcuts_overlay)
     }
 }
@@ -17,6 +17,7 @@ use gpui_component::ActiveTheme;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+use crate::i18n::t;
 use crate::settings::models::execution_settings::ExecutionSettingsModel;
 use crate::settings::models::{
     DiscoveredModulesModel, ExtensionsModel, ModuleLoadStatus, ModuleSettingsModel,
@@ -231,7 +232,7 @@ impl ChatView {
                                 .text_xl()
                                 .font_weight(FontWeight::BOLD)
                                 .text_color(cx.theme().foreground)
-                                .child("Welcome to Chatty"),
+                                .child(t("chat-welcome")),
                         )
                         .child(
                             div()
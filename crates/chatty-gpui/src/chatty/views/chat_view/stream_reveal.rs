@@ -0,0 +1,109 @@
+//! Smooth streaming for `ChatView`.
+//!
+//! # What lives here
+//!
+//! - `append_streamed_text` — the entry point for text deltas from the
+//!   stream, which either appends them right away or queues them in a
+//!   `TextReveal` buffer when smooth streaming is on.
+//! - `flush_stream_reveal` — shows everything still queued, before anything
+//!   that depends on the message text being complete (tool calls, the end
+//!   of the stream).
+//! - The frame timer that drains the buffer.
+//!
+//! # What does NOT live here
+//!
+//! - The pacing itself — `chatty_core::services::text_reveal`.
+//! - The streamed text of record, which `ConversationsStore` accumulates
+//!   without delay; this only changes when the view shows it.
+
+use std::time::{Duration, Instant};
+
+use chatty_core::services::text_reveal::TextReveal;
+use gpui::*;
+
+use super::ChatView;
+use crate::settings::models::GeneralSettingsModel;
+
+/// How often buffered text is revealed (~60fps).
+const REVEAL_INTERVAL: Duration = Duration::from_millis(16);
+
+impl ChatView {
+    /// Append a text delta from the stream to the streaming message, paced
+    /// when smooth streaming is enabled.
+    pub fn append_streamed_text(&mut self, text: &str, cx: &mut Context<Self>) {
+        let smooth = cx
+            .try_global::<GeneralSettingsModel>()
+            .filter(|s| s.smooth_streaming)
+            .map(|s| s.smooth_streaming_rate);
+        match (smooth, self.stream_reveal.as_mut()) {
+            (_, Some(reveal)) => reveal.push(text),
+            (Some(rate), None) => {
+                let mut reveal = TextReveal::new(rate);
+                reveal.push(text);
+                self.stream_reveal = Some(reveal);
+            }
+            (None, None) => {
+                self.append_assistant_text(text, cx);
+                return;
+            }
+        }
+        self.schedule_reveal_tick(cx);
+    }
+
+    /// Show all text still waiting to be revealed.
+    pub fn flush_stream_reveal(&mut self, cx: &mut Context<Self>) {
+        if let Some(mut reveal) = self.stream_reveal.take() {
+            let text = reveal.take_all();
+            if !text.is_empty() {
+                self.append_assistant_text(&text, cx);
+            }
+        }
+    }
+
+    /// Drop queued text that belongs to a message no longer displayed.
+    pub(super) fn discard_stream_reveal(&mut self) {
+        self.stream_reveal = None;
+    }
+
+    fn schedule_reveal_tick(&mut self, cx: &mut Context<Self>) {
+        if self.stream_reveal_ticking {
+            return;
+        }
+        self.stream_reveal_ticking = true;
+        cx.spawn(async move |entity, cx| {
+            let mut last_tick = Instant::now();
+            loop {
+                cx.background_executor().timer(REVEAL_INTERVAL).await;
+                let elapsed = last_tick.elapsed();
+                last_tick = Instant::now();
+                let keep_going = entity
+                    .update(cx, |view, cx| view.reveal_tick(elapsed, cx))
+                    .unwrap_or(false);
+                if !keep_going {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Reveal the text due after `elapsed`. Returns whether the timer
+    /// should keep running.
+    fn reveal_tick(&mut self, elapsed: Duration, cx: &mut Context<Self>) -> bool {
+        let Some(reveal) = self.stream_reveal.as_mut() else {
+            self.stream_reveal_ticking = false;
+            return false;
+        };
+        let text = reveal.take(elapsed);
+        // An empty buffer stays in place until the stream ends, so the next
+        // burst is paced too; the timer restarts when it arrives
+        let drained = reveal.is_empty();
+        if !text.is_empty() {
+            self.append_assistant_text(&text, cx);
+        }
+        if drained {
+            self.stream_reveal_ticking = false;
+        }
+        !drained
+    }
+}
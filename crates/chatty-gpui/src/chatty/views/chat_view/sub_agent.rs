@@ -36,12 +36,20 @@ impl ChatView {
     /// Clear all messages from the chat view
     pub fn clear_messages(&mut self, cx: &mut Context<Self>) {
         self.messages.clear();
+        self.selected_message = None;
+        self.selection_anchor = None;
+        self.topic_split_suggested = false;
         self.parsed_cache.clear();
         self.streaming_parse_cache = None;
+        self.discard_stream_reveal();
         self.sub_agent_progress_msg_idx = None;
         self.pending_approval = None;
         self.agent_task_snapshot = None;
         self.agent_task_panel_collapsed = false;
+        self.pinned_items.clear();
+        self.variables.clear();
+        self.execution_plan = None;
+        self.plan_panel_collapsed = false;
         cx.notify();
     }
 
@@ -74,6 +82,9 @@ impl ChatView {
             is_markdown: true,
             attachments: Vec::new(),
             feedback: None,
+            reactions: Vec::new(),
+            note: None,
+            error: None,
             history_index: None,
         });
 
@@ -171,6 +182,9 @@ impl ChatView {
             is_markdown: true,
             attachments: Vec::new(),
             feedback: None,
+            reactions: Vec::new(),
+            note: None,
+            error: None,
             history_index: None,
         });
         cx.notify();
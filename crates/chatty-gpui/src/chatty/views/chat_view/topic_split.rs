@@ -0,0 +1,78 @@
+//! Topic-change split suggestion for `ChatView`.
+//!
+//! # What lives here
+//!
+//! The banner above the chat input offering to continue a conversation
+//! whose latest message changed the topic in a new conversation.
+//!
+//! # What does NOT live here
+//!
+//! - Detecting the topic change and creating the new conversation —
+//!   `app_controller/topic_split_ops.rs`.
+
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme, Icon, IconName, Sizable, h_flex};
+
+use super::{ChatView, ChatViewEvent};
+use crate::i18n::t;
+
+impl ChatView {
+    /// Offer to split the conversation after a topic change.
+    pub fn suggest_topic_split(&mut self, cx: &mut Context<Self>) {
+        self.topic_split_suggested = true;
+        cx.notify();
+    }
+
+    pub(super) fn dismiss_topic_split(&mut self, cx: &mut Context<Self>) {
+        if self.topic_split_suggested {
+            self.topic_split_suggested = false;
+            cx.notify();
+        }
+    }
+
+    pub(super) fn render_topic_split_banner(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        if !self.topic_split_suggested {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .mx_4()
+                .mb_2()
+                .px_3()
+                .py_2()
+                .gap_2()
+                .rounded_md()
+                .border_1()
+                .border_color(cx.theme().border)
+                .bg(cx.theme().muted)
+                .child(
+                    Icon::new(IconName::Info)
+                        .size_4()
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .child(div().flex_1().text_sm().child(t("chat-topic-split-prompt")))
+                .child(
+                    Button::new("topic-split-accept")
+                        .primary()
+                        .xsmall()
+                        .label(t("chat-topic-split-new"))
+                        .tooltip(t("chat-topic-split-new-tooltip"))
+                        .on_click(cx.listener(|view, _, _, cx| {
+                            view.dismiss_topic_split(cx);
+                            cx.emit(ChatViewEvent::SplitConversation);
+                        })),
+                )
+                .child(
+                    Button::new("topic-split-dismiss")
+                        .ghost()
+                        .xsmall()
+                        .icon(IconName::Close)
+                        .tooltip(t("chat-topic-split-stay"))
+                        .on_click(cx.listener(|view, _, _, cx| view.dismiss_topic_split(cx))),
+                )
+                .into_any_element(),
+        )
+    }
+}
@@ -0,0 +1,175 @@
+//! Failed-turn annotations for `ChatView`.
+//!
+//! # What lives here
+//!
+//! - `mark_turn_failed` / `clear_turn_error` — put the stream error on the
+//!   user message whose response failed, or take it off again on retry.
+//! - `render_turn_error_card` — the card under that message with the
+//!   provider's error, a retry button and a link to the error log.
+//!
+//! # What does NOT live here
+//!
+//! - Recording the error on the conversation and retrying the turn —
+//!   `app_controller/message_ops.rs`.
+
+use chatty_core::models::error_store::ErrorStore;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme, Icon, Sizable, h_flex, v_flex};
+
+use super::{ChatView, ChatViewEvent};
+use crate::assets::CustomIcon;
+use crate::chatty::models::TurnError;
+use crate::chatty::views::ErrorLogDialog;
+use crate::chatty::views::message_component::{DisplayMessage, MessageRole};
+use crate::i18n::t;
+
+impl ChatView {
+    /// Replace the streaming placeholder with an error card on the user
+    /// message it answered. `history_index` is that message's index in the
+    /// conversation, when the error could be recorded there.
+    pub fn mark_turn_failed(
+        &mut self,
+        history_index: Option<usize>,
+        error: TurnError,
+        cx: &mut Context<Self>,
+    ) {
+        // The partial response is not kept in the history, so drop it here too
+        self.discard_stream_reveal();
+        if self
+            .messages
+            .last()
+            .is_some_and(|m| matches!(m.role, MessageRole::Assistant) && m.is_streaming)
+        {
+            self.messages.pop();
+            self.streaming_parse_cache = None;
+        }
+        if let Some(msg) = self
+            .messages
+            .iter_mut()
+            .rev()
+            .find(|m| matches!(m.role, MessageRole::User))
+        {
+            msg.error = Some(error);
+            msg.history_index = msg.history_index.or(history_index);
+        }
+        cx.notify();
+    }
+
+    /// Remove the error card from the message at `history_index`.
+    pub fn clear_turn_error(&mut self, history_index: usize, cx: &mut Context<Self>) {
+        if let Some(msg) = self
+            .messages
+            .iter_mut()
+            .find(|m| m.history_index == Some(history_index))
+        {
+            msg.error = None;
+            cx.notify();
+        }
+    }
+}
+
+/// Card shown under a user message whose response failed. Retrying is only
+/// offered on the latest message, and only while nothing is streaming.
+pub(super) fn render_turn_error_card(
+    msg: &DisplayMessage,
+    index: usize,
+    can_retry: bool,
+    conversation_id: Option<&str>,
+    chat_view: Entity<ChatView>,
+    cx: &App,
+) -> Option<AnyElement> {
+    let error = msg.error.as_ref()?;
+    let history_index = msg.history_index;
+
+    // The stream loop logs the failure with these fields; the entry is gone
+    // after a restart or once newer errors push it out of the store
+    let log_fields = conversation_id.map(|id| {
+        vec![
+            ("conv_id".to_string(), id.to_string()),
+            ("error".to_string(), error.message.clone()),
+        ]
+    });
+    let in_error_log = log_fields.as_ref().is_some_and(|fields| {
+        let fields: Vec<(&str, &str)> = fields
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        cx.try_global::<ErrorStore>()
+            .is_some_and(|store| store.contains_fields(&fields))
+    });
+
+    Some(
+        h_flex()
+            .mt_2()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .items_start()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().danger)
+            .bg(cx.theme().secondary)
+            .child(
+                Icon::new(CustomIcon::TriangleAlert)
+                    .size(px(14.0))
+                    .text_color(cx.theme().danger),
+            )
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(t("chat-turn-error")),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(error.message.clone()),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .mt_1()
+                            .when_some(history_index.filter(|_| can_retry), |this, h_idx| {
+                                this.child(
+                                    Button::new(("retry-failed-turn", index))
+                                        .primary()
+                                        .xsmall()
+                                        .label(t("chat-turn-retry"))
+                                        .on_click(move |_, _, cx| {
+                                            chat_view.update(cx, |view, cx| {
+                                                view.clear_turn_error(h_idx, cx);
+                                                cx.emit(ChatViewEvent::RetryFailedTurn {
+                                                    history_index: h_idx,
+                                                });
+                                            });
+                                        }),
+                                )
+                            })
+                            .when_some(log_fields.filter(|_| in_error_log), |this, fields| {
+                                this.child(
+                                    Button::new(("show-turn-error", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .label(t("chat-turn-show-error-log"))
+                                        .on_click(move |_, window, cx| {
+                                            ErrorLogDialog::open_focused(
+                                                fields.clone(),
+                                                window,
+                                                cx,
+                                            );
+                                        }),
+                                )
+                            }),
+                    ),
+            )
+            .into_any_element(),
+    )
+}
@@ -1,5 +1,7 @@
+use super::find_highlight::match_styles;
 use super::syntax_highlighter;
 use crate::assets::CustomIcon;
+use crate::i18n::t;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::ActiveTheme;
@@ -27,6 +29,8 @@ pub struct CodeBlockComponent {
     pre_highlighted: Option<Vec<(Range<usize>, HighlightStyle)>>,
     render_mode: CodeBlockRenderMode,
     status_label: Option<String>,
+    /// Find-in-conversation matches, flagged `true` for the current one
+    find_matches: Vec<(Range<usize>, bool)>,
 }
 
 impl CodeBlockComponent {
@@ -39,6 +43,7 @@ impl CodeBlockComponent {
             pre_highlighted: None,
             render_mode: CodeBlockRenderMode::Highlighted,
             status_label: None,
+            find_matches: Vec::new(),
         }
     }
 
@@ -56,6 +61,7 @@ impl CodeBlockComponent {
             pre_highlighted: Some(styles),
             render_mode: CodeBlockRenderMode::Highlighted,
             status_label: None,
+            find_matches: Vec::new(),
         }
     }
 
@@ -67,6 +73,7 @@ impl CodeBlockComponent {
             pre_highlighted: Some(vec![]),
             render_mode: CodeBlockRenderMode::Plain,
             status_label: None,
+            find_matches: Vec::new(),
         }
     }
 
@@ -83,8 +90,14 @@ impl CodeBlockComponent {
             pre_highlighted: Some(vec![]),
             render_mode: CodeBlockRenderMode::Streaming,
             status_label: Some(status_label),
+            find_matches: Vec::new(),
         }
     }
+
+    pub fn find_matches(mut self, matches: Vec<(Range<usize>, bool)>) -> Self {
+        self.find_matches = matches;
+        self
+    }
 }
 
 impl RenderOnce for CodeBlockComponent {
@@ -98,6 +111,7 @@ impl RenderOnce for CodeBlockComponent {
             pre_highlighted,
             render_mode,
             status_label,
+            find_matches,
         } = self;
 
         // Use pre-highlighted styles if available, otherwise compute
@@ -109,6 +123,11 @@ impl RenderOnce for CodeBlockComponent {
             None => vec![],
         };
 
+        let styles = if find_matches.is_empty() {
+            styles
+        } else {
+            combine_highlights(styles, match_styles(&find_matches, cx)).collect()
+        };
         let styled_text = StyledText::new(code.clone()).with_highlights(styles);
         let bg_color = theme.muted;
         let border_color = theme.border;
@@ -163,6 +182,7 @@ impl RenderOnce for CodeBlockComponent {
             }));
         }
 
+        // Code is always left-to-right, even inside a right-to-left message
         div()
             .bg(bg_color)
             .border_1()
@@ -170,6 +190,7 @@ impl RenderOnce for CodeBlockComponent {
             .rounded_md()
             .mb_3()
             .p_3()
+            .text_left()
             .child(
                 div()
                     .flex()
@@ -191,7 +212,7 @@ impl RenderOnce for CodeBlockComponent {
                         .ghost()
                         .xsmall()
                         .icon(Icon::new(CustomIcon::Copy))
-                        .tooltip("Copy code")
+                        .tooltip(t("message-copy-code"))
                         .on_click({
                             let code = code.clone();
                             move |_event, _window, cx| {
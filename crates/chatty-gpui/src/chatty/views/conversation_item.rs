@@ -1,29 +1,49 @@
 use crate::assets::CustomIcon;
+use crate::i18n::t;
+use chatty_core::models::conversation_label::LABEL_ICONS;
+use chatty_core::models::{ConversationLabel, LabelColor};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::popover::Popover;
+use gpui_component::tooltip::Tooltip;
 use gpui_component::{
-    ActiveTheme, Collapsible, Icon, IconName, Sizable,
+    ActiveTheme, Collapsible, Icon, IconName, Selectable, Sizable,
     button::{Button, ButtonVariants},
     h_flex,
     kbd::Kbd,
+    v_flex,
 };
 use std::sync::Arc;
 
 /// Callback type for conversation actions
 pub type ConversationActionCallback = Arc<dyn Fn(&str, &mut App) + Send + Sync>;
 
+/// Callback invoked with the new label picked for a conversation
+pub type ConversationLabelCallback = Arc<dyn Fn(&str, ConversationLabel, &mut App) + Send + Sync>;
+
+/// Callback for actions that open a dialog and need the window
+pub type ConversationWindowCallback = Arc<dyn Fn(&str, &mut Window, &mut App) + Send + Sync>;
+
 /// A single conversation item in the sidebar
 #[derive(IntoElement, Clone)]
 pub struct ConversationItem {
     id: String,
     title: String,
     is_active: bool,
+    is_unread: bool,
     on_click: Option<ConversationActionCallback>,
     on_delete: Option<ConversationActionCallback>,
-    on_export: Option<ConversationActionCallback>,
+    on_export: Option<ConversationWindowCallback>,
+    on_print: Option<ConversationActionCallback>,
+    on_label: Option<ConversationLabelCallback>,
+    on_tags: Option<ConversationWindowCallback>,
+    on_lock: Option<ConversationActionCallback>,
+    label: ConversationLabel,
+    tags: Vec<String>,
+    is_locked: bool,
     is_collapsed: bool,
     cost_usd: Option<f64>,
+    updated_label: Option<String>,
 }
 
 impl ConversationItem {
@@ -32,11 +52,20 @@ impl ConversationItem {
             id,
             title,
             is_active: false,
+            is_unread: false,
             on_click: None,
             on_delete: None,
             on_export: None,
+            on_print: None,
+            on_label: None,
+            on_tags: None,
+            on_lock: None,
+            label: ConversationLabel::default(),
+            tags: Vec::new(),
+            is_locked: false,
             is_collapsed: false,
             cost_usd: None,
+            updated_label: None,
         }
     }
 
@@ -45,11 +74,40 @@ impl ConversationItem {
         self
     }
 
+    /// Relative update time shown on hover, e.g. "Updated 5 min ago"
+    pub fn updated(mut self, label: Option<String>) -> Self {
+        self.updated_label = label;
+        self
+    }
+
+    pub fn label(mut self, label: ConversationLabel) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// Tags shown under the title
+    pub fn tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Show a lock and a dimmed title for read-only conversations
+    pub fn locked(mut self, is_locked: bool) -> Self {
+        self.is_locked = is_locked;
+        self
+    }
+
     pub fn active(mut self, is_active: bool) -> Self {
         self.is_active = is_active;
         self
     }
 
+    /// Show the title in bold with a dot until the conversation is opened
+    pub fn unread(mut self, is_unread: bool) -> Self {
+        self.is_unread = is_unread;
+        self
+    }
+
     pub fn on_click<F>(mut self, callback: F) -> Self
     where
         F: Fn(&str, &mut App) + Send + Sync + 'static,
@@ -68,11 +126,129 @@ impl ConversationItem {
 
     pub fn on_export<F>(mut self, callback: F) -> Self
     where
-        F: Fn(&str, &mut App) + Send + Sync + 'static,
+        F: Fn(&str, &mut Window, &mut App) + Send + Sync + 'static,
     {
         self.on_export = Some(Arc::new(callback));
         self
     }
+
+    pub fn on_print<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str, &mut App) + Send + Sync + 'static,
+    {
+        self.on_print = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn on_label<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str, ConversationLabel, &mut App) + Send + Sync + 'static,
+    {
+        self.on_label = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn on_tags<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str, &mut Window, &mut App) + Send + Sync + 'static,
+    {
+        self.on_tags = Some(Arc::new(callback));
+        self
+    }
+
+    /// Toggle the read-only lock
+    pub fn on_lock<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str, &mut App) + Send + Sync + 'static,
+    {
+        self.on_lock = Some(Arc::new(callback));
+        self
+    }
+}
+
+/// Color swatches and emoji for the conversation menu. Picking the current
+/// color or emoji again removes it.
+fn label_picker(
+    id: &str,
+    current: &ConversationLabel,
+    on_label: ConversationLabelCallback,
+    cx: &App,
+) -> impl IntoElement {
+    let colors = h_flex()
+        .gap_1()
+        .children(LabelColor::ALL.iter().map(|&color| {
+            let selected = current.color == Some(color);
+            let next = ConversationLabel {
+                color: (!selected).then_some(color),
+                icon: current.icon.clone(),
+            };
+            let on_label = on_label.clone();
+            let id = id.to_string();
+            div()
+                .id(ElementId::Name(
+                    format!("label-color-{}-{}", id, color.name()).into(),
+                ))
+                .size(px(14.))
+                .rounded_full()
+                .cursor_pointer()
+                .bg(rgb(color.rgb()))
+                .when(selected, |swatch| {
+                    swatch.border_2().border_color(cx.theme().foreground)
+                })
+                .on_click(move |_event, _window, cx| {
+                    cx.stop_propagation();
+                    on_label(&id, next.clone(), cx);
+                })
+        }));
+
+    let icons = h_flex()
+        .flex_wrap()
+        .children(LABEL_ICONS.iter().map(|&icon| {
+            let selected = current.icon.as_deref() == Some(icon);
+            let next = ConversationLabel {
+                color: current.color,
+                icon: (!selected).then(|| icon.to_string()),
+            };
+            let on_label = on_label.clone();
+            let id = id.to_string();
+            Button::new(ElementId::Name(
+                format!("label-icon-{}-{}", id, icon).into(),
+            ))
+            .label(icon)
+            .ghost()
+            .xsmall()
+            .selected(selected)
+            .on_click(move |_event, _window, cx| {
+                cx.stop_propagation();
+                on_label(&id, next.clone(), cx);
+            })
+        }));
+
+    v_flex()
+        .gap_1()
+        .px_2()
+        .py_1()
+        .child(
+            div()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .child(t("conversation-label")),
+        )
+        .child(colors)
+        .child(icons)
+        .when(!current.is_empty(), |this| {
+            let id = id.to_string();
+            this.child(
+                Button::new(ElementId::Name(format!("label-clear-{}", id).into()))
+                    .label(t("conversation-clear-label"))
+                    .ghost()
+                    .xsmall()
+                    .on_click(move |_event, _window, cx| {
+                        cx.stop_propagation();
+                        on_label(&id, ConversationLabel::default(), cx);
+                    }),
+            )
+        })
 }
 
 impl Collapsible for ConversationItem {
@@ -91,9 +267,19 @@ impl RenderOnce for ConversationItem {
         let id_for_click = self.id.clone();
         let id_for_delete = self.id.clone();
         let id_for_export = self.id.clone();
+        let id_for_print = self.id.clone();
+        let id_for_label = self.id.clone();
+        let id_for_tags = self.id.clone();
+        let id_for_lock = self.id.clone();
         let on_click = self.on_click.clone();
         let on_delete = self.on_delete.clone();
         let on_export = self.on_export.clone();
+        let on_print = self.on_print.clone();
+        let on_label = self.on_label.clone();
+        let on_tags = self.on_tags.clone();
+        let on_lock = self.on_lock.clone();
+        let is_locked = self.is_locked;
+        let label = self.label.clone();
 
         let bg_color = if self.is_active {
             cx.theme().secondary
@@ -108,7 +294,13 @@ impl RenderOnce for ConversationItem {
             .py_2()
             .rounded_md()
             .bg(bg_color)
+            .when_some(self.label.color, |this, color| {
+                this.border_l_2().border_color(rgb(color.rgb()))
+            })
             .hover(|style| style.bg(cx.theme().secondary))
+            .when_some(self.updated_label.clone(), |this, label| {
+                this.tooltip(move |window, cx| Tooltip::new(label.clone()).build(window, cx))
+            })
             .cursor_pointer()
             .flex()
             .items_center()
@@ -127,14 +319,60 @@ impl RenderOnce for ConversationItem {
                         }
                     })
                     .child(
-                        div()
-                            .text_sm()
-                            .overflow_hidden()
-                            .text_ellipsis()
-                            .whitespace_nowrap()
-                            .when(self.is_collapsed, |d| d.child("•"))
-                            .when(!self.is_collapsed, |d| d.child(self.title.clone())),
+                        h_flex()
+                            .gap_2()
+                            .items_center()
+                            .when(self.is_unread && !self.is_collapsed, |row| {
+                                row.child(
+                                    div()
+                                        .flex_shrink_0()
+                                        .size(px(6.))
+                                        .rounded_full()
+                                        .bg(cx.theme().primary),
+                                )
+                            })
+                            .when_some(
+                                self.label.icon.clone().filter(|_| !self.is_collapsed),
+                                |row, icon| row.child(div().flex_shrink_0().text_sm().child(icon)),
+                            )
+                            .when(self.is_locked && !self.is_collapsed, |row| {
+                                row.child(
+                                    Icon::new(CustomIcon::Lock)
+                                        .size(px(12.0))
+                                        .flex_shrink_0()
+                                        .text_color(cx.theme().muted_foreground),
+                                )
+                            })
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .text_sm()
+                                    .when(self.is_locked, |d| {
+                                        d.text_color(cx.theme().muted_foreground)
+                                    })
+                                    .overflow_hidden()
+                                    .text_ellipsis()
+                                    .whitespace_nowrap()
+                                    .when(self.is_unread, |d| d.font_weight(FontWeight::BOLD))
+                                    .when(self.is_collapsed, |d| d.child("•"))
+                                    .when(!self.is_collapsed, |d| d.child(self.title.clone())),
+                            ),
                     )
+                    .when(!self.is_collapsed && !self.tags.is_empty(), |parent| {
+                        parent.child(
+                            div()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .truncate()
+                                .child(
+                                    self.tags
+                                        .iter()
+                                        .map(|tag| format!("#{tag}"))
+                                        .collect::<Vec<_>>()
+                                        .join(" "),
+                                ),
+                        )
+                    })
                     .when(!self.is_collapsed && self.cost_usd.is_some(), |parent| {
                         let cost = self.cost_usd.unwrap();
                         if cost <= 0.0 {
@@ -159,16 +397,25 @@ impl RenderOnce for ConversationItem {
                     }),
             )
             .when(
-                !self.is_collapsed && (on_delete.is_some() || on_export.is_some()),
+                !self.is_collapsed
+                    && (on_delete.is_some()
+                        || on_export.is_some()
+                        || on_print.is_some()
+                        || on_label.is_some()
+                        || on_tags.is_some()
+                        || on_lock.is_some()),
                 |this| {
-                    // "…" button that opens a popover with Download / Delete actions
+                    // "…" button that opens a popover with Export / Print / Delete actions
                     let trigger = Button::new(ElementId::Name(format!("menu-{}", self.id).into()))
                         .icon(Icon::new(IconName::Ellipsis))
                         .xsmall()
                         .ghost();
 
                     let export_btn_id = SharedString::from(format!("export-{}", self.id));
+                    let print_btn_id = SharedString::from(format!("print-{}", self.id));
                     let delete_btn_id = SharedString::from(format!("delete-{}", self.id));
+                    let tags_btn_id = SharedString::from(format!("tags-{}", self.id));
+                    let lock_btn_id = SharedString::from(format!("lock-{}", self.id));
 
                     this.child(
                         Popover::new(SharedString::from(format!("conv-menu-{}", self.id)))
@@ -177,10 +424,20 @@ impl RenderOnce for ConversationItem {
                             .content(move |_, _window, cx| {
                                 let on_delete = on_delete.clone();
                                 let on_export = on_export.clone();
+                                let on_print = on_print.clone();
+                                let on_label = on_label.clone();
+                                let on_tags = on_tags.clone();
+                                let on_lock = on_lock.clone();
+                                let id_lock = id_for_lock.clone();
+                                let id_tags = id_for_tags.clone();
                                 let id_del = id_for_delete.clone();
                                 let id_exp = id_for_export.clone();
+                                let id_print = id_for_print.clone();
                                 let export_btn_id = export_btn_id.clone();
+                                let print_btn_id = print_btn_id.clone();
                                 let delete_btn_id = delete_btn_id.clone();
+                                let tags_btn_id = tags_btn_id.clone();
+                                let lock_btn_id = lock_btn_id.clone();
 
                                 div()
                                     .flex()
@@ -215,16 +472,91 @@ impl RenderOnce for ConversationItem {
                                                                 .child(
                                                                     div()
                                                                         .text_xs()
-                                                                        .child("Download"),
+                                                                        .child(t("conversation-export")),
                                                                 ),
                                                         ),
                                                 )
+                                                .on_click(move |_event, window, cx| {
+                                                    cx.stop_propagation();
+                                                    cb(&id_exp, window, cx);
+                                                }),
+                                        )
+                                    })
+                                    .when_some(on_print, |this, cb| {
+                                        this.child(
+                                            Button::new(print_btn_id)
+                                                .ghost()
+                                                .xsmall()
+                                                .w_full()
+                                                .justify_start()
+                                                .child(
+                                                    h_flex()
+                                                        .gap_2()
+                                                        .items_center()
+                                                        .child(
+                                                            Icon::new(CustomIcon::Printer)
+                                                                .size(px(12.0)),
+                                                        )
+                                                        .child(
+                                                            div()
+                                                                .text_xs()
+                                                                .child(t("conversation-print")),
+                                                        ),
+                                                )
                                                 .on_click(move |_event, _window, cx| {
                                                     cx.stop_propagation();
-                                                    cb(&id_exp, cx);
+                                                    cb(&id_print, cx);
                                                 }),
                                         )
                                     })
+                                    .when_some(on_tags, |this, cb| {
+                                        this.child(
+                                            Button::new(tags_btn_id)
+                                                .ghost()
+                                                .xsmall()
+                                                .w_full()
+                                                .justify_start()
+                                                .child(
+                                                    div()
+                                                        .text_xs()
+                                                        .child(t("conversation-tags")),
+                                                )
+                                                .on_click(move |_event, window, cx| {
+                                                    cx.stop_propagation();
+                                                    cb(&id_tags, window, cx);
+                                                }),
+                                        )
+                                    })
+                                    .when_some(on_lock, |this, cb| {
+                                        this.child(
+                                            Button::new(lock_btn_id)
+                                                .ghost()
+                                                .xsmall()
+                                                .w_full()
+                                                .justify_start()
+                                                .child(
+                                                    h_flex()
+                                                        .gap_2()
+                                                        .items_center()
+                                                        .child(
+                                                            Icon::new(CustomIcon::Lock)
+                                                                .size(px(12.0)),
+                                                        )
+                                                        .child(div().text_xs().child(if is_locked {
+                                                            t("conversation-unlock")
+                                                        } else {
+                                                            t("conversation-lock")
+                                                        })),
+                                                )
+                                                .on_click(move |_event, _window, cx| {
+                                                    cx.stop_propagation();
+                                                    cb(&id_lock, cx);
+                                                }),
+                                        )
+                                    })
+                                    .when_some(on_label, |this, cb| {
+                                        this.child(label_picker(&id_for_label, &label, cb, cx))
+                                    })
                                     .when_some(on_delete, |this, cb| {
                                         this.child(
                                              Button::new(delete_btn_id)
@@ -249,7 +581,7 @@ impl RenderOnce for ConversationItem {
                                                                 .child(
                                                                     div()
                                                                         .text_xs()
-                                                                        .child("Delete"),
+                                                                        .child(t("conversation-delete")),
                                                                 ),
                                                         )
                                                         .child(Kbd::new(
@@ -0,0 +1,128 @@
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::{Input, InputState};
+use gpui_component::{ActiveTheme, WindowExt as _, h_flex, v_flex};
+
+use super::sidebar_view::{SidebarEvent, SidebarView};
+use crate::chatty::models::ConversationsStore;
+use crate::i18n::{t, t_args};
+use chatty_core::models::conversation_tags::{normalize_folder, parse_tags};
+
+/// Open the tags and folder dialog for `ids`.
+///
+/// For a single conversation the inputs show its current tags and folder and
+/// saving replaces them. For several conversations (the filtered sidebar
+/// list) the tags are added to each one, and a folder, if given, moves them
+/// all there.
+pub fn open_tags_dialog(
+    sidebar: Entity<SidebarView>,
+    ids: Vec<String>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let single = ids.len() == 1;
+    let (tags, folder) = match (single, cx.try_global::<ConversationsStore>()) {
+        (true, Some(store)) => (
+            store.tags(&ids[0]).join(", "),
+            store.folder(&ids[0]).unwrap_or_default().to_string(),
+        ),
+        _ => (String::new(), String::new()),
+    };
+    let tags_input = cx.new(|cx| {
+        InputState::new(window, cx)
+            .placeholder(t("conversation-tags-placeholder"))
+            .default_value(tags)
+    });
+    let folder_input = cx.new(|cx| {
+        InputState::new(window, cx)
+            .placeholder(t("conversation-folder-placeholder"))
+            .default_value(folder)
+    });
+    let title = if single {
+        t("conversation-tags-title")
+    } else {
+        t_args(
+            "conversation-tags-title-bulk",
+            &[("count", ids.len().to_string().as_str())],
+        )
+    };
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let muted = cx.theme().muted_foreground;
+        dialog
+            .title(title.clone())
+            .overlay(true)
+            .keyboard(true)
+            .close_button(true)
+            .overlay_closable(true)
+            .w(px(420.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .p_4()
+                    .child(div().text_xs().text_color(muted).child(if single {
+                        t("conversation-tags-label")
+                    } else {
+                        t("conversation-tags-add-label")
+                    }))
+                    .child(Input::new(&tags_input))
+                    .child(div().text_xs().text_color(muted).child(if single {
+                        t("conversation-folder-label")
+                    } else {
+                        t("conversation-folder-move-label")
+                    }))
+                    .child(Input::new(&folder_input))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .justify_end()
+                            .child(
+                                Button::new("cancel-tags")
+                                    .label(t("action-cancel"))
+                                    .on_click(|_, window, cx| window.close_dialog(cx)),
+                            )
+                            .child(
+                                Button::new("save-tags")
+                                    .primary()
+                                    .label(t("action-save"))
+                                    .on_click({
+                                        let sidebar = sidebar.clone();
+                                        let ids = ids.clone();
+                                        let tags_input = tags_input.clone();
+                                        let folder_input = folder_input.clone();
+                                        move |_, window, cx| {
+                                            let tags = parse_tags(&tags_input.read(cx).value());
+                                            let folder =
+                                                normalize_folder(&folder_input.read(cx).value());
+                                            let ids = ids.clone();
+                                            sidebar.update(cx, |_, cx| {
+                                                if single {
+                                                    cx.emit(SidebarEvent::SetTags(
+                                                        ids[0].clone(),
+                                                        tags,
+                                                    ));
+                                                    cx.emit(SidebarEvent::MoveToFolder(
+                                                        ids, folder,
+                                                    ));
+                                                } else {
+                                                    for tag in tags {
+                                                        cx.emit(SidebarEvent::AddTag(
+                                                            ids.clone(),
+                                                            tag,
+                                                        ));
+                                                    }
+                                                    if folder.is_some() {
+                                                        cx.emit(SidebarEvent::MoveToFolder(
+                                                            ids, folder,
+                                                        ));
+                                                    }
+                                                }
+                                            });
+                                            window.close_dialog(cx);
+                                        }
+                                    }),
+                            ),
+                    ),
+            )
+    });
+}
@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::i18n::t;
+use gpui::prelude::*;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme, Sizable};
+
+pub type ToggleCallback = Arc<dyn Fn(&mut App) + Send + Sync>;
+pub type DeleteCallback = Arc<dyn Fn(String, &mut App) + Send + Sync>;
+
+const PANEL_WIDTH: f32 = 260.0;
+const COLLAPSED_WIDTH: f32 = 36.0;
+/// Values longer than this are truncated in the panel (the agent sees them in full).
+const VALUE_PREVIEW_CHARS: usize = 400;
+
+/// Side panel listing the conversation's key-value variables written by the
+/// agent via `set_variable`. Shown to the right of the message list whenever
+/// at least one variable is set.
+#[derive(IntoElement)]
+pub struct ConversationVariablesPanel {
+    variables: BTreeMap<String, String>,
+    collapsed: bool,
+    on_toggle: Option<ToggleCallback>,
+    on_delete: Option<DeleteCallback>,
+}
+
+impl ConversationVariablesPanel {
+    pub fn new(variables: BTreeMap<String, String>, collapsed: bool) -> Self {
+        Self {
+            variables,
+            collapsed,
+            on_toggle: None,
+            on_delete: None,
+        }
+    }
+
+    pub fn on_toggle<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut App) + Send + Sync + 'static,
+    {
+        self.on_toggle = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn on_delete<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(String, &mut App) + Send + Sync + 'static,
+    {
+        self.on_delete = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl RenderOnce for ConversationVariablesPanel {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let count = self.variables.len();
+        let on_toggle = self.on_toggle.clone();
+
+        if self.collapsed {
+            return div()
+                .id("conversation-variables-collapsed")
+                .flex_shrink_0()
+                .w(px(COLLAPSED_WIDTH))
+                .h_full()
+                .flex()
+                .flex_col()
+                .items_center()
+                .gap_1()
+                .pt_4()
+                .border_l_1()
+                .border_color(cx.theme().border)
+                .bg(cx.theme().secondary.opacity(0.4))
+                .cursor_pointer()
+                .hover(|style| style.bg(cx.theme().muted.opacity(0.7)))
+                .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                    if let Some(callback) = &on_toggle {
+                        callback(cx);
+                    }
+                })
+                .child(
+                    div()
+                        .font_family("monospace")
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("{x}"),
+                )
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(count.to_string()),
+                );
+        }
+
+        let on_delete = self.on_delete.clone();
+        let summary = match count {
+            1 => "1 variable".to_string(),
+            n => format!("{n} variables"),
+        };
+
+        div()
+            .id("conversation-variables-panel")
+            .flex_shrink_0()
+            .w(px(PANEL_WIDTH))
+            .h_full()
+            .flex()
+            .flex_col()
+            .border_l_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().secondary.opacity(0.4))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .px_3()
+                    .py_2()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .flex_1()
+                            .min_w_0()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(cx.theme().foreground)
+                                    .child(t("variables-title")),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(summary),
+                            ),
+                    )
+                    .child(
+                        Button::new("conversation-variables-hide")
+                            .ghost()
+                            .xsmall()
+                            .label(t("variables-hide"))
+                            .on_click(move |_event, _window, cx| {
+                                if let Some(callback) = &on_toggle {
+                                    callback(cx);
+                                }
+                            }),
+                    ),
+            )
+            .child(
+                div()
+                    .id("conversation-variables-list")
+                    .flex_1()
+                    .min_h_0()
+                    .overflow_y_scroll()
+                    .p_2()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .children(self.variables.iter().map(|(name, value)| {
+                        render_variable_row(name, value, on_delete.clone(), cx)
+                    })),
+            )
+    }
+}
+
+fn render_variable_row(
+    name: &str,
+    value: &str,
+    on_delete: Option<DeleteCallback>,
+    cx: &App,
+) -> Div {
+    let mut preview: String = value.chars().take(VALUE_PREVIEW_CHARS).collect();
+    if value.chars().count() > VALUE_PREVIEW_CHARS {
+        preview.push('…');
+    }
+    let variable_name = name.to_string();
+
+    div()
+        .flex()
+        .flex_col()
+        .gap_1()
+        .rounded_md()
+        .px_2()
+        .py_1()
+        .bg(cx.theme().background.opacity(0.35))
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .gap_2()
+                .child(
+                    div()
+                        .flex_1()
+                        .min_w_0()
+                        .font_family("monospace")
+                        .text_xs()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(cx.theme().foreground)
+                        .overflow_hidden()
+                        .whitespace_nowrap()
+                        .text_ellipsis()
+                        .child(name.to_string()),
+                )
+                .child(
+                    Button::new(ElementId::Name(format!("delete-variable-{name}").into()))
+                        .ghost()
+                        .xsmall()
+                        .label(t("action-delete"))
+                        .on_click(move |_event, _window, cx| {
+                            if let Some(callback) = &on_delete {
+                                callback(variable_name.clone(), cx);
+                            }
+                        }),
+                ),
+        )
+        .child(
+            div()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .child(preview),
+        )
+}
@@ -1,5 +1,8 @@
 use crate::assets::CustomIcon;
 use crate::chatty::models::error_store::{ErrorEntry, ErrorLevel, ErrorStore, format_timestamp};
+use crate::i18n::t;
+use crate::settings::providers::ProviderHealthState;
+use chatty_core::settings::providers::health::HealthWarning;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{
@@ -14,9 +17,27 @@ pub struct ErrorLogDialog;
 
 impl ErrorLogDialog {
     pub fn open(window: &mut Window, cx: &mut App) {
-        window.open_dialog(cx, |dialog, _window, cx| {
+        Self::open_focused(Vec::new(), window, cx);
+    }
+
+    /// Open the log with the entries logged with all of `fields` highlighted
+    /// and listed first, e.g. the stream error behind a failed turn.
+    pub fn open_focused(fields: Vec<(String, String)>, window: &mut Window, cx: &mut App) {
+        window.open_dialog(cx, move |dialog, _window, cx| {
+            let fields: Vec<(&str, &str)> = fields
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect();
             let entries = cx.global::<ErrorStore>().get_all_entries();
-            let entries_reversed: Vec<_> = entries.into_iter().rev().collect(); // Most recent first
+            // Most recent first, with focused entries on top
+            let mut entries_reversed: Vec<(bool, ErrorEntry)> = entries
+                .into_iter()
+                .rev()
+                .map(|entry| (!fields.is_empty() && entry.has_fields(&fields), entry))
+                .collect();
+            entries_reversed.sort_by_key(|(focused, _)| !focused);
+            let health_warnings = cx.global::<ProviderHealthState>().warnings.clone();
+            let nothing_to_show = entries_reversed.is_empty() && health_warnings.is_empty();
 
             dialog
                 .title("Errors & Warnings")
@@ -29,53 +50,115 @@ impl ErrorLogDialog {
                         .overflow_y_scrollbar()
                         .px(px(16.0))
                         .py(px(12.0))
-                        .when(entries_reversed.is_empty(), |this| {
+                        // Provider checks stay listed until the problem is fixed
+                        .when(!health_warnings.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .mb_2()
+                                    .child(t("error-log-provider-checks")),
+                            )
+                            .children(health_warnings.into_iter().map(HealthWarningView))
+                        })
+                        .when(nothing_to_show, |this| {
                             this.child(
                                 div()
                                     .text_sm()
                                     .text_color(cx.theme().muted_foreground)
-                                    .child("No errors or warnings to display."),
+                                    .child(t("error-log-empty")),
                             )
                         })
                         .when(!entries_reversed.is_empty(), |this| {
-                            this.children(
-                                entries_reversed
-                                    .into_iter()
-                                    .enumerate()
-                                    .map(|(ix, entry)| ErrorEntryView::new(entry).id(ix)),
-                            )
+                            this.children(entries_reversed.into_iter().enumerate().map(
+                                |(ix, (focused, entry))| {
+                                    ErrorEntryView::new(entry).id(ix).focused(focused)
+                                },
+                            ))
                         }),
                 )
                 .footer(|_, _window, _, _cx| {
-                    vec![Button::new("clear-all").label("Clear All").on_click({
-                        move |_, window, cx| {
-                            cx.update_global::<ErrorStore, _>(|store, _cx| {
-                                store.clear();
-                            });
-                            cx.refresh_windows();
-                            window.close_dialog(cx);
-                        }
-                    })]
+                    vec![
+                        Button::new("clear-all")
+                            .label(t("error-log-clear-all"))
+                            .on_click({
+                                move |_, window, cx| {
+                                    cx.update_global::<ErrorStore, _>(|store, _cx| {
+                                        store.clear();
+                                    });
+                                    cx.refresh_windows();
+                                    window.close_dialog(cx);
+                                }
+                            }),
+                    ]
                 })
         });
     }
 }
 
+#[derive(IntoElement)]
+struct HealthWarningView(HealthWarning);
+
+impl RenderOnce for HealthWarningView {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        h_flex()
+            .mb_3()
+            .p_3()
+            .gap_2()
+            .items_start()
+            .bg(cx.theme().secondary)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .child(
+                Icon::new(CustomIcon::TriangleAlert)
+                    .size(px(14.0))
+                    .text_color(cx.theme().ring),
+            )
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().foreground)
+                            .child(self.0.problem),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(self.0.fix),
+                    ),
+            )
+    }
+}
+
 #[derive(IntoElement)]
 struct ErrorEntryView {
     entry: ErrorEntry,
     id: usize,
+    focused: bool,
 }
 
 impl ErrorEntryView {
     fn new(entry: ErrorEntry) -> Self {
-        Self { entry, id: 0 }
+        Self {
+            entry,
+            id: 0,
+            focused: false,
+        }
     }
 
     fn id(mut self, id: usize) -> Self {
         self.id = id;
         self
     }
+
+    fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
 }
 
 impl RenderOnce for ErrorEntryView {
@@ -113,7 +196,11 @@ impl RenderOnce for ErrorEntryView {
             .p_3()
             .bg(cx.theme().secondary)
             .border_1()
-            .border_color(cx.theme().border)
+            .border_color(if self.focused {
+                cx.theme().danger
+            } else {
+                cx.theme().border
+            })
             .rounded_md()
             .cursor_text() // Enable text selection
             .child(
@@ -154,7 +241,7 @@ impl RenderOnce for ErrorEntryView {
                             .ghost()
                             .xsmall()
                             .icon(Icon::new(CustomIcon::Copy))
-                            .tooltip("Copy error")
+                            .tooltip(t("error-log-copy"))
                             .on_click({
                                 let text = error_text.clone();
                                 move |_, _, cx| {
@@ -0,0 +1,67 @@
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme, WindowExt as _, h_flex, v_flex};
+
+use super::sidebar_view::{SidebarEvent, SidebarView};
+use crate::i18n::t;
+use chatty_core::exporters::document_exporter::DocumentFormat;
+
+/// Open the "Export conversation…" dialog for `id`. Picking a format closes
+/// the dialog and asks the app to save the conversation in it; formats this
+/// build can't write are left out.
+pub fn open_export_dialog(
+    sidebar: Entity<SidebarView>,
+    id: String,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let muted = cx.theme().muted_foreground;
+        let buttons = DocumentFormat::ALL
+            .into_iter()
+            .filter(|format| format.is_available())
+            .map(|format| {
+                let sidebar = sidebar.clone();
+                let id = id.clone();
+                Button::new(SharedString::from(format!(
+                    "export-as-{}",
+                    format.extension()
+                )))
+                .label(format.label())
+                .on_click(move |_, window, cx| {
+                    sidebar.update(cx, |_, cx| {
+                        cx.emit(SidebarEvent::ExportConversation(id.clone(), format));
+                    });
+                    window.close_dialog(cx);
+                })
+            });
+
+        dialog
+            .title(t("conversation-export-title"))
+            .overlay(true)
+            .keyboard(true)
+            .close_button(true)
+            .overlay_closable(true)
+            .w(px(380.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .p_4()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(muted)
+                            .child(t("conversation-export-description")),
+                    )
+                    .child(h_flex().gap_2().children(buttons))
+                    .child(
+                        h_flex().justify_end().child(
+                            Button::new("cancel-export")
+                                .ghost()
+                                .label(t("action-cancel"))
+                                .on_click(|_, window, cx| window.close_dialog(cx)),
+                        ),
+                    ),
+            )
+    });
+}
@@ -0,0 +1,164 @@
+//! Match finding and highlighting for find-in-conversation.
+//!
+//! The find bar counts matches per message over `DisplayMessage::content`.
+//! While rendering a message, a [`FindHighlighter`] is handed each piece of
+//! shown text (markdown text, code blocks, thinking blocks) in content order,
+//! so the running occurrence count lines up with the bar's counter and the
+//! current match can be drawn differently from the others.
+
+use gpui::*;
+use gpui_component::ActiveTheme;
+use std::ops::Range;
+
+use super::math_parser::MathSegment;
+
+/// Background opacity of a match and of the current match.
+const MATCH_OPACITY: f32 = 0.3;
+const CURRENT_MATCH_OPACITY: f32 = 0.8;
+
+/// Byte ranges of case-insensitive, non-overlapping occurrences of `query`.
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+    let mut next_start = 0;
+    for (start, _) in text.char_indices() {
+        if start < next_start {
+            continue;
+        }
+        if let Some(len) = match_len(&text[start..], query) {
+            matches.push(start..start + len);
+            next_start = start + len;
+        }
+    }
+    matches
+}
+
+/// Length in bytes of `query` matched at the start of `haystack`, if it matches.
+fn match_len(haystack: &str, query: &str) -> Option<usize> {
+    let mut chars = haystack.char_indices();
+    for q in query.chars() {
+        let (_, h) = chars.next()?;
+        if !h.to_lowercase().eq(q.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(haystack.len(), |(end, _)| end))
+}
+
+/// Highlights find matches across the rendered pieces of one message.
+pub struct FindHighlighter {
+    query: String,
+    /// Occurrence within the message that is the current match
+    current: Option<usize>,
+    /// Matches handed out so far
+    seen: usize,
+}
+
+impl FindHighlighter {
+    pub fn new(query: String, current: Option<usize>) -> Self {
+        Self {
+            query,
+            current,
+            seen: 0,
+        }
+    }
+
+    pub fn has_match(&self, text: &str) -> bool {
+        !find_matches(text, &self.query).is_empty()
+    }
+
+    /// Match ranges in the next piece of text, flagged `true` for the current match.
+    pub fn next_piece(&mut self, text: &str) -> Vec<(Range<usize>, bool)> {
+        let first = self.seen;
+        let matches = find_matches(text, &self.query);
+        self.seen += matches.len();
+        matches
+            .into_iter()
+            .enumerate()
+            .map(|(i, range)| (range, self.current == Some(first + i)))
+            .collect()
+    }
+}
+
+/// Background highlights for `matches`, as returned by [`FindHighlighter::next_piece`].
+pub fn match_styles(
+    matches: &[(Range<usize>, bool)],
+    cx: &App,
+) -> Vec<(Range<usize>, HighlightStyle)> {
+    let color = cx.theme().warning;
+    matches
+        .iter()
+        .map(|(range, is_current)| {
+            let opacity = if *is_current {
+                CURRENT_MATCH_OPACITY
+            } else {
+                MATCH_OPACITY
+            };
+            (
+                range.clone(),
+                HighlightStyle {
+                    background_color: Some(color.opacity(opacity)),
+                    ..Default::default()
+                },
+            )
+        })
+        .collect()
+}
+
+/// Matches in the next piece of a message, if find is active.
+pub fn piece_matches(find: &mut Option<FindHighlighter>, text: &str) -> Vec<(Range<usize>, bool)> {
+    find.as_mut()
+        .map(|find| find.next_piece(text))
+        .unwrap_or_default()
+}
+
+/// Plain text with the matches in it highlighted.
+pub fn highlighted_text(text: &str, find: &mut Option<FindHighlighter>, cx: &App) -> StyledText {
+    let styles = match_styles(&piece_matches(find, text), cx);
+    StyledText::new(text.to_string()).with_highlights(styles)
+}
+
+/// Source text of a markdown/math run, used to show it with highlights
+/// while it contains a match (the markdown renderer cannot highlight).
+pub fn math_segments_source(segments: &[MathSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            MathSegment::Text(text) => text.clone(),
+            MathSegment::InlineMath(math) => format!("${math}$"),
+            MathSegment::BlockMath(math) => format!("\n$${math}$$\n"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    // Re-import standard #[test] to shadow gpui::test from `use gpui::*`
+    use core::prelude::rust_2021::test;
+
+    use super::*;
+
+    #[test]
+    fn matches_ignore_case_and_do_not_overlap() {
+        assert_eq!(find_matches("Foo foo FOO", "foo"), vec![0..3, 4..7, 8..11]);
+        assert_eq!(find_matches("aaaa", "aa"), vec![0..2, 2..4]);
+        assert!(find_matches("abc", "").is_empty());
+    }
+
+    #[test]
+    fn ranges_are_byte_offsets() {
+        let text = "Grüße, GRÜSSE, grüße";
+        let ranges = find_matches(text, "grüße");
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(&text[ranges[1].clone()], "grüße");
+    }
+
+    #[test]
+    fn current_match_is_counted_across_pieces() {
+        let mut find = FindHighlighter::new("x".to_string(), Some(2));
+        assert_eq!(find.next_piece("x x"), vec![(0..1, false), (2..3, false)]);
+        assert_eq!(find.next_piece("a x"), vec![(2..3, true)]);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::i18n::t;
 use crate::settings::models::extensions_store::{ExtensionKind, ExtensionSource, ExtensionsModel};
 use crate::settings::models::{DiscoveredModulesModel, ModuleLoadStatus};
 use gpui::prelude::FluentBuilder;
@@ -71,7 +72,7 @@ impl RenderOnce for AgentIndicatorView {
 
             let module_name = match &ext.source {
                 ExtensionSource::Hive { module_name, .. } => module_name.as_str(),
-                ExtensionSource::Custom => ext.id.as_str(),
+                ExtensionSource::Custom | ExtensionSource::McpCatalog { .. } => ext.id.as_str(),
             };
 
             if let Some(module) = discovered_by_name.get(module_name)
@@ -189,7 +190,7 @@ impl RenderOnce for AgentIndicatorView {
                                     .font_weight(FontWeight::BOLD)
                                     .text_color(cx.theme().foreground)
                                     .pb_2()
-                                    .child("Agents"),
+                                    .child(t("footer-agents-title")),
                             )
                             .child(div().h(px(1.0)).w_full().bg(cx.theme().border).mb_2())
                             .children(
@@ -244,7 +245,7 @@ fn render_agent_item(entry: AgentEntry) -> impl IntoElement {
                                         .rounded_sm()
                                         .bg(gpui::rgb(0xFEF3C7))
                                         .text_color(gpui::rgb(0x92400E))
-                                        .child("Paid"),
+                                        .child(t("footer-paid")),
                                 )
                             },
                         )
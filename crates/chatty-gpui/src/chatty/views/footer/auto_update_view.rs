@@ -1,6 +1,7 @@
 use crate::assets::CustomIcon;
 use crate::auto_updater::{AutoUpdateStatus, AutoUpdater};
 use crate::chatty::views::footer::progress_circle::ProgressCircle;
+use crate::i18n::t;
 use gpui::*;
 use gpui_component::{ActiveTheme as _, Icon, Sizable, button::*, h_flex, tooltip::Tooltip};
 
@@ -82,7 +83,7 @@ impl RenderOnce for AutoUpdateView {
                         div()
                             .text_xs()
                             .text_color(cx.theme().foreground)
-                            .child("Downloading..."),
+                            .child(t("footer-update-downloading")),
                     )
                     .tooltip(move |window, cx| Tooltip::new(tooltip_text.clone()).build(window, cx))
                     .into_any_element()
@@ -1,5 +1,6 @@
 use crate::assets::CustomIcon;
 use crate::chatty::models::ErrorStore;
+use crate::settings::providers::ProviderHealthState;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{Icon, Sizable, button::*, h_flex};
@@ -29,8 +30,14 @@ impl RenderOnce for ErrorIndicatorView {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
         let store = cx.global::<ErrorStore>();
         let error_count = store.error_count();
-        let warning_count = store.warning_count();
+        // Live provider health warnings count alongside logged warnings
+        let health = &cx.global::<ProviderHealthState>().warnings;
+        let warning_count = store.warning_count() + health.len();
         let total = error_count + warning_count;
+        let tooltip: SharedString = match health.first() {
+            Some(warning) => warning.problem.clone().into(),
+            None => "View errors and warnings".into(),
+        };
 
         // Colors: yellow for warnings, red for errors
         // Use GPUI's rgb to create explicit yellow and red colors
@@ -41,7 +48,7 @@ impl RenderOnce for ErrorIndicatorView {
             let mut button = Button::new("error-indicator")
                 .ghost()
                 .xsmall()
-                .tooltip("View errors and warnings")
+                .tooltip(tooltip)
                 .child(
                     h_flex()
                         .gap_1()
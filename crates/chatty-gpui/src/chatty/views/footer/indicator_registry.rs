@@ -0,0 +1,80 @@
+use crate::auto_updater::{AutoUpdateStatus, AutoUpdater};
+use crate::chatty::views::footer::{
+    AgentIndicatorView, AutoUpdateView, ErrorIndicatorView, FetchIndicatorView, JobsIndicatorView,
+    LocalModelsIndicatorView, McpIndicatorView, NetworkIndicatorView, PackUpdatesIndicatorView,
+    TokenContextBarView, ToolsIndicatorView,
+};
+use crate::i18n::t;
+use crate::settings::models::FooterIndicator;
+use gpui::*;
+
+/// Display name of an indicator in settings and the footer overflow menu.
+pub fn indicator_label(indicator: FooterIndicator) -> String {
+    match indicator {
+        FooterIndicator::Errors => t("footer-indicator-errors"),
+        FooterIndicator::Jobs => t("footer-indicator-jobs"),
+        FooterIndicator::PackUpdates => t("footer-indicator-pack-updates"),
+        FooterIndicator::AutoUpdate => t("footer-indicator-auto-update"),
+        FooterIndicator::Tokens => t("footer-indicator-tokens"),
+        FooterIndicator::LocalModels => t("footer-indicator-local-models"),
+        FooterIndicator::Fetch => t("footer-indicator-fetch"),
+        FooterIndicator::Network => t("footer-indicator-network"),
+        FooterIndicator::Tools => t("footer-indicator-tools"),
+        FooterIndicator::Mcp => t("footer-indicator-mcp"),
+        FooterIndicator::Agent => t("footer-indicator-agent"),
+    }
+}
+
+/// Build the view for an indicator, with its click action wired up.
+pub fn render_indicator(indicator: FooterIndicator) -> AnyElement {
+    match indicator {
+        FooterIndicator::Errors => ErrorIndicatorView::new()
+            .on_click(move |window, cx| {
+                // Open error log dialog as inline overlay
+                crate::chatty::views::ErrorLogDialog::open(window, cx);
+            })
+            .into_any_element(),
+        FooterIndicator::Jobs => JobsIndicatorView::new()
+            .on_click(move |window, cx| {
+                crate::chatty::views::JobsDialog::open(window, cx);
+            })
+            .into_any_element(),
+        FooterIndicator::PackUpdates => PackUpdatesIndicatorView::new()
+            .on_click(move |_window, cx| {
+                crate::settings::controllers::SettingsView::open_or_focus_settings_window(cx);
+            })
+            .into_any_element(),
+        FooterIndicator::AutoUpdate => AutoUpdateView::new()
+            .on_click(move |_window, cx| {
+                // Determine which action to take based on current status
+                let status = cx.global::<AutoUpdater>().status().clone();
+
+                match status {
+                    AutoUpdateStatus::Idle => {
+                        let updater = cx.global::<AutoUpdater>().clone();
+                        updater.check_for_update(cx);
+                    }
+                    AutoUpdateStatus::Ready(..) => {
+                        let mut updater = cx.global::<AutoUpdater>().clone();
+                        updater.install_and_restart(cx);
+                    }
+                    AutoUpdateStatus::Error(_) => {
+                        cx.update_global::<AutoUpdater, _>(|updater, _cx| {
+                            updater.dismiss_error();
+                        });
+                    }
+                    _ => {
+                        // Do nothing for Checking, Downloading states
+                    }
+                }
+            })
+            .into_any_element(),
+        FooterIndicator::Tokens => TokenContextBarView::new().into_any_element(),
+        FooterIndicator::LocalModels => LocalModelsIndicatorView::new().into_any_element(),
+        FooterIndicator::Fetch => FetchIndicatorView::new().into_any_element(),
+        FooterIndicator::Network => NetworkIndicatorView::new().into_any_element(),
+        FooterIndicator::Tools => ToolsIndicatorView::new().into_any_element(),
+        FooterIndicator::Mcp => McpIndicatorView::new().into_any_element(),
+        FooterIndicator::Agent => AgentIndicatorView::new().into_any_element(),
+    }
+}
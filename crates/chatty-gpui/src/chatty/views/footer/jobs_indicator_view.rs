@@ -0,0 +1,78 @@
+use crate::assets::CustomIcon;
+use crate::chatty::models::JobQueue;
+use crate::i18n::t;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, Icon, Sizable, button::*, h_flex};
+
+type ClickHandler = Box<dyn Fn(&mut Window, &mut App) + 'static>;
+
+#[derive(IntoElement)]
+pub struct JobsIndicatorView {
+    on_click: Option<ClickHandler>,
+}
+
+impl JobsIndicatorView {
+    pub fn new() -> Self {
+        Self { on_click: None }
+    }
+
+    pub fn on_click<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&mut Window, &mut App) + 'static,
+    {
+        self.on_click = Some(Box::new(handler));
+        self
+    }
+}
+
+impl RenderOnce for JobsIndicatorView {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let (active, failed) = cx.global::<JobQueue>().counts();
+        let muted = cx.theme().muted_foreground;
+        let error_color = rgb(0xFF0000);
+
+        div().when(active + failed > 0, |this| {
+            let mut button = Button::new("jobs-indicator")
+                .ghost()
+                .xsmall()
+                .tooltip(t("jobs-indicator-tooltip"))
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .items_center()
+                        // Waiting and running jobs
+                        .when(active > 0, |this| {
+                            this.child(
+                                Icon::new(CustomIcon::Loader)
+                                    .size(px(12.0))
+                                    .text_color(muted),
+                            )
+                            .child(div().text_xs().text_color(muted).child(active.to_string()))
+                        })
+                        // Jobs that gave up
+                        .when(failed > 0, |this| {
+                            this.child(
+                                Icon::new(CustomIcon::CircleX)
+                                    .size(px(12.0))
+                                    .text_color(error_color),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(error_color)
+                                    .child(failed.to_string()),
+                            )
+                        }),
+                );
+
+            if let Some(handler) = self.on_click {
+                button = button.on_click(move |_event, window, cx| {
+                    handler(window, cx);
+                });
+            }
+
+            this.child(button)
+        })
+    }
+}
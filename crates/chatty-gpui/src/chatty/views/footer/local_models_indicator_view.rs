@@ -0,0 +1,153 @@
+use crate::assets::CustomIcon;
+use crate::i18n::{t, t_args};
+use crate::settings::providers::OllamaStatusState;
+use chatty_core::settings::providers::ollama::status::{LoadedModel, format_memory};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::popover::Popover;
+use gpui_component::{ActiveTheme, Icon, Sizable, button::*, h_flex};
+
+const POPOVER_MIN_WIDTH: f32 = 220.0;
+const POPOVER_MAX_WIDTH: f32 = 320.0;
+
+/// Loaded Ollama models with their GPU/RAM split. Hidden while Ollama is not
+/// configured or not reachable.
+#[derive(IntoElement, Default)]
+pub struct LocalModelsIndicatorView;
+
+impl LocalModelsIndicatorView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn model_row(model: &LoadedModel, muted: Hsla, foreground: Hsla) -> impl IntoElement {
+    let details = [
+        model.parameter_size.as_deref(),
+        model.quantization.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" · ");
+    let mut memory = t_args(
+        "footer-local-models-memory",
+        &[
+            ("vram", format_memory(model.vram_bytes).as_str()),
+            ("ram", format_memory(model.ram_bytes()).as_str()),
+            ("percent", model.gpu_percent().to_string().as_str()),
+        ],
+    );
+    if let Some(minutes) = model.unloads_in_minutes() {
+        memory.push_str(" · ");
+        memory.push_str(&t_args(
+            "footer-local-models-unloads",
+            &[("minutes", minutes.to_string().as_str())],
+        ));
+    }
+
+    div()
+        .flex()
+        .flex_col()
+        .py_1()
+        .child(
+            div()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(foreground)
+                .child(model.name.clone()),
+        )
+        .when(!details.is_empty(), |this| {
+            this.child(div().text_xs().text_color(muted).child(details))
+        })
+        .child(div().text_xs().text_color(muted).child(memory))
+}
+
+impl RenderOnce for LocalModelsIndicatorView {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let Some(status) = cx
+            .try_global::<OllamaStatusState>()
+            .and_then(|state| state.status.clone())
+        else {
+            return div();
+        };
+
+        // Green while everything fits on the GPU, amber once models spill into RAM
+        let color = if status.total_ram_bytes() > 0 {
+            rgb(0xF59E0B) // Amber-500
+        } else {
+            rgb(0x10B981) // Emerald-500
+        };
+        let summary = if status.loaded.is_empty() {
+            t("footer-local-models-idle")
+        } else {
+            format_memory(status.total_vram_bytes())
+        };
+
+        let indicator_button = Button::new("local-models-indicator")
+            .ghost()
+            .xsmall()
+            .tooltip(t("footer-local-models-tooltip"))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .items_center()
+                    .child(Icon::new(CustomIcon::Cpu).size(px(12.0)).text_color(color))
+                    .child(div().text_xs().text_color(color).child(summary)),
+            );
+
+        div().child(
+            Popover::new("local-models-popover")
+                .trigger(indicator_button)
+                .appearance(false)
+                .content(move |_, _window, cx| {
+                    let muted = cx.theme().muted_foreground;
+                    let foreground = cx.theme().foreground;
+
+                    div()
+                        .flex()
+                        .flex_col()
+                        .bg(cx.theme().background)
+                        .border_1()
+                        .border_color(cx.theme().border)
+                        .rounded_md()
+                        .shadow_md()
+                        .p_2()
+                        .min_w(px(POPOVER_MIN_WIDTH))
+                        .max_w(px(POPOVER_MAX_WIDTH))
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_weight(FontWeight::BOLD)
+                                .text_color(foreground)
+                                .pb_2()
+                                .child("Ollama"),
+                        )
+                        .child(div().h(px(1.0)).w_full().bg(cx.theme().border).mb_1())
+                        .when(status.loaded.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .text_xs()
+                                    .text_color(muted)
+                                    .child(t("footer-local-models-none")),
+                            )
+                        })
+                        .children(
+                            status
+                                .loaded
+                                .iter()
+                                .map(|model| model_row(model, muted, foreground)),
+                        )
+                        .when(!status.loaded.is_empty(), |this| {
+                            this.child(div().pt_1().text_xs().text_color(muted).child(t_args(
+                                "footer-local-models-total",
+                                &[
+                                    ("vram", format_memory(status.total_vram_bytes()).as_str()),
+                                    ("ram", format_memory(status.total_ram_bytes()).as_str()),
+                                ],
+                            )))
+                        })
+                }),
+        )
+    }
+}
@@ -1,6 +1,12 @@
 use crate::assets::CustomIcon;
-use crate::settings::controllers::extensions_controller;
+use crate::chatty::controllers::GlobalChattyApp;
+use crate::chatty::models::conversations_store::ConversationsStore;
+use crate::i18n::t;
+use crate::settings::controllers::{execution_settings_controller, extensions_controller};
+use crate::settings::models::execution_settings::{CustomToolSource, ExecutionSettingsModel};
 use crate::settings::models::extensions_store::ExtensionsModel;
+use chatty_core::services::McpService;
+use chatty_core::services::mcp_service::McpServerState;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::popover::Popover;
@@ -8,7 +14,7 @@ use gpui_component::{ActiveTheme, Icon, Sizable, button::*, h_flex};
 
 // Popover dimensions
 const MCP_POPOVER_MIN_WIDTH: f32 = 200.0;
-const MCP_POPOVER_MAX_WIDTH: f32 = 300.0;
+const MCP_POPOVER_MAX_WIDTH: f32 = 360.0;
 
 #[derive(IntoElement, Default)]
 pub struct McpIndicatorView;
@@ -25,11 +31,23 @@ impl RenderOnce for McpIndicatorView {
         let all_servers = store.all_mcp_servers();
         let total_count = all_servers.len();
         let enabled_count = store.enabled_mcp_count();
+        let api_sources = cx.global::<ExecutionSettingsModel>().custom_tool_sources();
+        let states = cx
+            .try_global::<McpService>()
+            .map(|service| service.states())
+            .unwrap_or_default();
+        // Servers switched off for the active conversation; `None` without one
+        let conversation_disabled = cx.try_global::<ConversationsStore>().and_then(|store| {
+            store
+                .active_id()
+                .and_then(|id| store.get_conversation(id))
+                .map(|conv| conv.disabled_mcp_servers().clone())
+        });
 
         // MCP blue color (matches brand)
         let mcp_color = rgb(0x3B82F6); // Blue-500
 
-        div().when(total_count > 0, |this| {
+        div().when(total_count > 0 || !api_sources.is_empty(), |this| {
             // Main indicator button
             let indicator_button = Button::new("mcp-indicator")
                 .ghost()
@@ -63,6 +81,8 @@ impl RenderOnce for McpIndicatorView {
                     .appearance(false)
                     .content(move |_, _window, cx| {
                         let servers = all_servers.clone();
+                        let sources = api_sources.clone();
+                        let muted = cx.theme().muted_foreground;
 
                         div()
                             .flex()
@@ -81,33 +101,80 @@ impl RenderOnce for McpIndicatorView {
                                     .font_weight(FontWeight::BOLD)
                                     .text_color(cx.theme().foreground)
                                     .pb_2()
-                                    .child("MCP Servers"),
+                                    .child(t("footer-mcp-servers")),
                             )
                             .child(div().h(px(1.0)).w_full().bg(cx.theme().border).mb_2())
                             .children(
                                 servers
                                     .into_iter()
                                     .map(|(id, cfg, enabled)| {
-                                        let is_external = !cfg.url.contains("localhost")
-                                            && !cfg.url.contains("127.0.0.1");
-                                        render_server_item(id, cfg.name, enabled, is_external)
+                                        let is_external = !cfg.is_local();
+                                        let state_label = enabled
+                                            .then(|| states.get(&cfg.name).map(state_label))
+                                            .flatten();
+                                        let in_conversation = enabled
+                                            .then(|| {
+                                                conversation_disabled
+                                                    .as_ref()
+                                                    .map(|disabled| !disabled.contains(&cfg.name))
+                                            })
+                                            .flatten();
+                                        render_server_item(
+                                            id,
+                                            cfg.name,
+                                            enabled,
+                                            in_conversation,
+                                            is_external,
+                                            state_label,
+                                            muted,
+                                        )
                                     })
                                     .collect::<Vec<_>>(),
                             )
+                            .when(!sources.is_empty(), |list| {
+                                list.child(
+                                    div()
+                                        .text_sm()
+                                        .font_weight(FontWeight::BOLD)
+                                        .text_color(cx.theme().foreground)
+                                        .pt_2()
+                                        .pb_2()
+                                        .child(t("footer-api-tools")),
+                                )
+                                .child(div().h(px(1.0)).w_full().bg(cx.theme().border).mb_2())
+                                .children(sources.into_iter().map(render_source_item))
+                            })
                     }),
             )
         })
     }
 }
 
-/// Render a single server item in the popover
+/// Short label for a server's connection state
+fn state_label(state: &McpServerState) -> &'static str {
+    match state {
+        McpServerState::Stopped => "idle",
+        McpServerState::Starting => "starting…",
+        McpServerState::Running => "running",
+        McpServerState::Failed(_) => "failed",
+    }
+}
+
+/// Render a single server item in the popover. `in_conversation` is whether
+/// the server is used in the active conversation (`None` when it is disabled
+/// globally or no conversation is open).
 fn render_server_item(
     ext_id: String,
     name: String,
     enabled: bool,
+    in_conversation: Option<bool>,
     is_external: bool,
+    state_label: Option<&'static str>,
+    muted: Hsla,
 ) -> impl IntoElement {
     let button_id = SharedString::from(format!("toggle-{}", name));
+    let conversation_button_id = SharedString::from(format!("toggle-chat-{}", name));
+    let server_name = name.clone();
     let display_name = if is_external {
         format!("↗ {}", name)
     } else {
@@ -125,13 +192,83 @@ fn render_server_item(
         .py_1()
         .rounded_md()
         .child(
-            div()
-                .text_sm()
-                .when(name_color.is_some(), |el| {
-                    el.text_color(rgb(name_color.unwrap()))
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(
+                    div()
+                        .text_sm()
+                        .when(name_color.is_some(), |el| {
+                            el.text_color(rgb(name_color.unwrap()))
+                        })
+                        .child(display_name),
+                )
+                .when_some(state_label, |el, label| {
+                    el.child(div().text_xs().text_color(muted).child(label))
+                }),
+        )
+        .child(
+            h_flex()
+                .gap_1()
+                .when_some(in_conversation, |el, in_conversation| {
+                    el.child(
+                        Button::new(conversation_button_id)
+                            .xsmall()
+                            .when(in_conversation, |btn| btn.outline())
+                            .when(!in_conversation, |btn| btn.ghost())
+                            .child(if in_conversation {
+                                "This chat"
+                            } else {
+                                "Off in chat"
+                            })
+                            .tooltip(t("footer-mcp-use-server"))
+                            .on_click(move |_event, _window, cx| {
+                                if let Some(app) = cx
+                                    .try_global::<GlobalChattyApp>()
+                                    .and_then(|g| g.try_upgrade())
+                                {
+                                    app.update(cx, |app, cx| {
+                                        app.set_conversation_mcp_server_enabled(
+                                            &server_name,
+                                            !in_conversation,
+                                            cx,
+                                        );
+                                    });
+                                }
+                            }),
+                    )
                 })
-                .child(display_name),
+                .child(
+                    Button::new(button_id)
+                        .xsmall()
+                        .when(enabled, |btn| btn.primary())
+                        .when(!enabled, |btn| btn.ghost())
+                        .child(if enabled { "Enabled" } else { "Disabled" })
+                        .on_click(move |_event, _window, cx| {
+                            extensions_controller::toggle_extension(ext_id.clone(), cx);
+                        }),
+                ),
         )
+}
+
+/// Render an imported OpenAPI spec; the toggle switches all of its tools.
+fn render_source_item(source: CustomToolSource) -> impl IntoElement {
+    let button_id = SharedString::from(format!("toggle-api-{}", source.name));
+    let enabled = source.enabled_count > 0;
+
+    div()
+        .flex()
+        .flex_row()
+        .items_center()
+        .justify_between()
+        .gap_2()
+        .px_2()
+        .py_1()
+        .rounded_md()
+        .child(div().text_sm().child(format!(
+            "{} ({}/{})",
+            source.name, source.enabled_count, source.tool_count
+        )))
         .child(
             Button::new(button_id)
                 .xsmall()
@@ -139,7 +276,11 @@ fn render_server_item(
                 .when(!enabled, |btn| btn.ghost())
                 .child(if enabled { "Enabled" } else { "Disabled" })
                 .on_click(move |_event, _window, cx| {
-                    extensions_controller::toggle_extension(ext_id.clone(), cx);
+                    execution_settings_controller::set_custom_http_source_enabled(
+                        &source.name,
+                        !enabled,
+                        cx,
+                    );
                 }),
         )
 }
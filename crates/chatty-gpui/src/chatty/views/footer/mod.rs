@@ -2,8 +2,12 @@ pub mod agent_indicator_view;
 pub mod auto_update_view;
 pub mod error_indicator_view;
 pub mod fetch_indicator_view;
+pub mod indicator_registry;
+pub mod jobs_indicator_view;
+pub mod local_models_indicator_view;
 pub mod mcp_indicator_view;
 pub mod network_indicator_view;
+pub mod pack_updates_indicator_view;
 pub mod progress_circle;
 pub mod status_footer_view;
 pub mod token_context_bar_view;
@@ -13,8 +17,11 @@ pub use agent_indicator_view::AgentIndicatorView;
 pub use auto_update_view::AutoUpdateView;
 pub use error_indicator_view::ErrorIndicatorView;
 pub use fetch_indicator_view::FetchIndicatorView;
+pub use jobs_indicator_view::JobsIndicatorView;
+pub use local_models_indicator_view::LocalModelsIndicatorView;
 pub use mcp_indicator_view::McpIndicatorView;
 pub use network_indicator_view::NetworkIndicatorView;
+pub use pack_updates_indicator_view::PackUpdatesIndicatorView;
 pub use status_footer_view::StatusFooterView;
 pub use token_context_bar_view::TokenContextBarView;
 pub use tools_indicator_view::ToolsIndicatorView;
@@ -0,0 +1,64 @@
+use crate::assets::CustomIcon;
+use crate::i18n::t;
+use crate::settings::models::PromptPacksState;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, Icon, Sizable, button::*, h_flex};
+
+type ClickHandler = Box<dyn Fn(&mut Window, &mut App) + 'static>;
+
+/// Shown while newer versions of installed prompt packs are available.
+#[derive(IntoElement)]
+pub struct PackUpdatesIndicatorView {
+    on_click: Option<ClickHandler>,
+}
+
+impl PackUpdatesIndicatorView {
+    pub fn new() -> Self {
+        Self { on_click: None }
+    }
+
+    pub fn on_click<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&mut Window, &mut App) + 'static,
+    {
+        self.on_click = Some(Box::new(handler));
+        self
+    }
+}
+
+impl RenderOnce for PackUpdatesIndicatorView {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let count = cx
+            .try_global::<PromptPacksState>()
+            .map(|state| state.updates.len())
+            .unwrap_or_default();
+        let muted = cx.theme().muted_foreground;
+
+        div().when(count > 0, |this| {
+            let mut button = Button::new("pack-updates-indicator")
+                .ghost()
+                .xsmall()
+                .tooltip(t("pack-updates-indicator-tooltip"))
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .items_center()
+                        .child(
+                            Icon::new(CustomIcon::Download)
+                                .size(px(12.0))
+                                .text_color(muted),
+                        )
+                        .child(div().text_xs().text_color(muted).child(count.to_string())),
+                );
+
+            if let Some(handler) = self.on_click {
+                button = button.on_click(move |_event, window, cx| {
+                    handler(window, cx);
+                });
+            }
+
+            this.child(button)
+        })
+    }
+}
@@ -1,10 +1,12 @@
-use crate::auto_updater::{AutoUpdateStatus, AutoUpdater};
-use crate::chatty::views::footer::{
-    AgentIndicatorView, AutoUpdateView, ErrorIndicatorView, FetchIndicatorView, McpIndicatorView,
-    NetworkIndicatorView, TokenContextBarView, ToolsIndicatorView,
-};
+use crate::chatty::views::app_view::is_compact_layout;
+use crate::chatty::views::footer::indicator_registry::{indicator_label, render_indicator};
+use crate::i18n::t;
+use crate::settings::models::{FooterIndicator, GeneralSettingsModel};
+use gpui::prelude::FluentBuilder;
 use gpui::*;
-use gpui_component::ActiveTheme as _;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::popover::Popover;
+use gpui_component::{ActiveTheme as _, IconName, Sizable};
 
 #[derive(IntoElement)]
 pub struct StatusFooterView;
@@ -15,67 +17,95 @@ impl StatusFooterView {
     }
 }
 
+/// "…" button listing the indicators hidden in settings.
+fn render_overflow_menu(hidden: Vec<FooterIndicator>) -> impl IntoElement {
+    let trigger = Button::new("footer-overflow")
+        .icon(IconName::Ellipsis)
+        .xsmall()
+        .ghost();
+
+    Popover::new("footer-overflow-popover")
+        .trigger(trigger)
+        .content(move |_, _window, cx| {
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .p_2()
+                .min_w(px(180.0))
+                .children(hidden.iter().map(|indicator| {
+                    div()
+                        .flex()
+                        .flex_row()
+                        .items_center()
+                        .justify_between()
+                        .gap_3()
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(indicator_label(*indicator)),
+                        )
+                        .child(render_indicator(*indicator))
+                }))
+                .child(
+                    Button::new("footer-customize")
+                        .ghost()
+                        .xsmall()
+                        .w_full()
+                        .label(t("footer-customize"))
+                        .on_click(|_event, _window, cx| {
+                            crate::settings::controllers::SettingsView::open_or_focus_settings_window(
+                                cx,
+                            );
+                        }),
+                )
+        })
+}
+
 impl RenderOnce for StatusFooterView {
-    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let layout = &cx.global::<GeneralSettingsModel>().footer_layout;
+        // A narrow window keeps only the essential indicators
+        let compact = is_compact_layout(window);
+        let (visible, hidden) = if compact {
+            layout.compact()
+        } else {
+            (layout.visible(), layout.overflow())
+        };
+        let (left, right): (Vec<_>, Vec<_>) = visible.into_iter().partition(|i| i.is_left());
+
         div()
-            .h(px(24.0))
+            .h(px(if compact { 20.0 } else { 24.0 }))
             .w_full()
             .flex()
             .flex_row()
             .items_center()
             .justify_between()
-            .px(px(8.0))
+            .px(px(if compact { 4.0 } else { 8.0 }))
             .bg(cx.theme().background)
             .border_t_1()
             .border_color(cx.theme().border)
-            // Left side: errors/warnings + auto-updater
+            // Left side: errors/warnings, jobs, updates
             .child(
                 div()
                     .flex()
                     .flex_row()
                     .items_center()
                     .gap_1()
-                    .child(ErrorIndicatorView::new().on_click(move |window, cx| {
-                        // Open error log dialog as inline overlay
-                        crate::chatty::views::ErrorLogDialog::open(window, cx);
-                    }))
-                    .child(AutoUpdateView::new().on_click(move |_window, cx| {
-                        // Determine which action to take based on current status
-                        let status = cx.global::<AutoUpdater>().status().clone();
-
-                        match status {
-                            AutoUpdateStatus::Idle => {
-                                let updater = cx.global::<AutoUpdater>().clone();
-                                updater.check_for_update(cx);
-                            }
-                            AutoUpdateStatus::Ready(..) => {
-                                let mut updater = cx.global::<AutoUpdater>().clone();
-                                updater.install_and_restart(cx);
-                            }
-                            AutoUpdateStatus::Error(_) => {
-                                cx.update_global::<AutoUpdater, _>(|updater, _cx| {
-                                    updater.dismiss_error();
-                                });
-                            }
-                            _ => {
-                                // Do nothing for Checking, Downloading states
-                            }
-                        }
-                    })),
+                    .children(left.into_iter().map(render_indicator)),
             )
-            // Right side: Tools + MCP
+            // Right side: context, tools, MCP, agents, then the overflow menu
             .child(
                 div()
                     .flex()
                     .flex_row()
                     .items_center()
                     .gap_1()
-                    .child(TokenContextBarView::new())
-                    .child(FetchIndicatorView::new())
-                    .child(NetworkIndicatorView::new())
-                    .child(ToolsIndicatorView::new())
-                    .child(McpIndicatorView::new())
-                    .child(AgentIndicatorView::new()),
+                    .children(right.into_iter().map(render_indicator))
+                    .when(!hidden.is_empty(), |this| {
+                        this.child(render_overflow_menu(hidden))
+                    }),
             )
     }
 }
@@ -1,7 +1,9 @@
 use crate::chatty::models::conversations_store::ConversationsStore;
 use crate::chatty::models::token_usage::{format_cost, format_tokens};
 use crate::chatty::token_budget::{ContextStatus, GlobalTokenBudget, TokenBudgetSnapshot};
+use crate::i18n::t;
 use crate::settings::models::token_tracking_settings::TokenTrackingSettings;
+use chatty_core::models::HistoryPolicy;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::popover::Popover;
@@ -22,6 +24,11 @@ const COLOR_PREAMBLE: u32 = 0x60A5FA; // Blue-400  — system preamble
 const COLOR_TOOLS: u32 = 0xA78BFA; // Violet-400 — tool JSON schemas
 const COLOR_HISTORY: u32 = 0x34D399; // Emerald-400 — conversation history
 const COLOR_USER_MSG: u32 = 0x22D3EE; // Cyan-400  — latest user message
+const COLOR_PINNED: u32 = 0xF472B6; // Pink-400  — pinned context items
+const COLOR_MEMORY: u32 = 0xFBBF24; // Amber-400 — recalled memory
+const COLOR_RETRIEVAL: u32 = 0xFB923C; // Orange-400 — retrieved documents
+const COLOR_ATTACHMENTS: u32 = 0x94A3B8; // Slate-400 — images, PDFs
+const COLOR_TRUNCATED: u32 = 0xF59E0B; // Amber-500 — truncation badge
 
 // ── Main view type ────────────────────────────────────────────────────────────
 
@@ -71,7 +78,7 @@ fn read_budget_snapshot(cx: &App) -> Option<TokenBudgetSnapshot> {
 
 /// Render a segmented horizontal bar showing each context component as a
 /// proportional coloured strip. Segments are ordered:
-///   preamble | tools | history | user_msg | remaining (theme bg)
+///   preamble | tools | history | pinned | user_msg | remaining (theme bg)
 ///
 /// The border colour signals the current `ContextStatus`:
 /// - Normal / Moderate → theme border (no special colour)
@@ -105,6 +112,7 @@ fn render_stacked_bar(
         .child(bar_segment(bar_width, frac.preamble as f32, COLOR_PREAMBLE))
         .child(bar_segment(bar_width, frac.tools as f32, COLOR_TOOLS))
         .child(bar_segment(bar_width, frac.history as f32, COLOR_HISTORY))
+        .child(bar_segment(bar_width, frac.pinned as f32, COLOR_PINNED))
         .child(bar_segment(bar_width, frac.user_msg as f32, COLOR_USER_MSG))
         // Remaining: a slightly darker grey so it blends into the bg
         .when(remaining > 0.0, |this| {
@@ -118,6 +126,87 @@ fn bar_segment(total_width: f32, fraction: f32, color_hex: u32) -> Div {
     div().w(px(w)).h_full().bg(rgb(color_hex))
 }
 
+// ── Prompt breakdown ──────────────────────────────────────────────────────────
+
+/// One row of the expandable "what filled the prompt" list.
+#[derive(Clone)]
+struct BreakdownRow {
+    label: String,
+    tokens: usize,
+    truncated: bool,
+    color: u32,
+}
+
+/// Rows for the expandable breakdown, in prompt order.
+fn breakdown_rows(snap: &TokenBudgetSnapshot) -> Vec<BreakdownRow> {
+    let b = &snap.breakdown;
+    vec![
+        BreakdownRow {
+            label: "System prompt + tools".to_string(),
+            tokens: snap.preamble_tokens + snap.tool_definitions_tokens,
+            truncated: false,
+            color: COLOR_PREAMBLE,
+        },
+        BreakdownRow {
+            label: "Memory".to_string(),
+            tokens: b.memory.tokens,
+            truncated: b.memory.truncated,
+            color: COLOR_MEMORY,
+        },
+        BreakdownRow {
+            label: "Retrieval".to_string(),
+            tokens: b.retrieval.tokens,
+            truncated: b.retrieval.truncated,
+            color: COLOR_RETRIEVAL,
+        },
+        BreakdownRow {
+            label: match b.history_policy {
+                HistoryPolicy::Full => "History".to_string(),
+                policy => format!("History · {}", policy.label()),
+            },
+            tokens: b.conversation.tokens,
+            truncated: b.conversation.truncated,
+            color: COLOR_HISTORY,
+        },
+        BreakdownRow {
+            label: "Attachments".to_string(),
+            tokens: b.attachments.tokens,
+            truncated: b.attachments.truncated,
+            color: COLOR_ATTACHMENTS,
+        },
+    ]
+}
+
+fn render_breakdown_row(row: &BreakdownRow, cx: &App) -> impl IntoElement {
+    h_flex()
+        .gap_2()
+        .items_center()
+        .child(
+            div()
+                .w(px(10.0))
+                .h(px(10.0))
+                .rounded_sm()
+                .bg(rgb(row.color)),
+        )
+        .child(div().flex_1().child(row.label.clone()))
+        .child(
+            div()
+                .text_color(cx.theme().foreground)
+                .child(format!("~{}", format_tokens(row.tokens as u32))),
+        )
+        .when(row.truncated, |this| {
+            this.child(
+                div()
+                    .px_1()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(rgb(COLOR_TRUNCATED))
+                    .text_color(rgb(COLOR_TRUNCATED))
+                    .child("truncated"),
+            )
+        })
+}
+
 // ── Empty bar (no snapshot) ───────────────────────────────────────────────────
 
 /// Rendered while waiting for the first snapshot (no model configured,
@@ -187,6 +276,12 @@ impl RenderOnce for TokenContextBarView {
             format_tokens(snap.conversation_history_tokens as u32),
             frac.history * 100.0
         );
+        let pinned_text = format!(
+            "~{}  ({:.1}%)",
+            format_tokens(snap.pinned_context_tokens as u32),
+            frac.pinned * 100.0
+        );
+        let has_pinned = snap.pinned_context_tokens > 0;
         let user_msg_text = format!(
             "~{}  ({:.1}%)",
             format_tokens(snap.latest_user_message_tokens as u32),
@@ -253,6 +348,27 @@ impl RenderOnce for TokenContextBarView {
         let session_output_text = format_tokens(session_output);
         let cost_text = format_cost(session_cost);
 
+        // Per-source breakdown, shown when the user expands it
+        let rows = breakdown_rows(&snap);
+        let any_truncated = rows.iter().any(|row| row.truncated);
+        let dropped_text = (snap.breakdown.dropped_messages > 0).then(|| {
+            format!(
+                "{} older messages not sent",
+                snap.breakdown.dropped_messages
+            )
+        });
+        let pruned_lines: Vec<String> = snap
+            .breakdown
+            .pruned_turns
+            .iter()
+            .map(|turn| {
+                format!(
+                    "Pruned: \"{}\" · {} messages · {}% relevant",
+                    turn.preview, turn.messages, turn.relevance_pct
+                )
+            })
+            .collect();
+
         // Clone snap fractions for the popover closure (must be 'static)
         let _snap_pct = pct;
         let _snap_context_limit = snap.model_context_limit;
@@ -290,12 +406,17 @@ impl RenderOnce for TokenContextBarView {
         let dot_tools: Hsla = rgb(COLOR_TOOLS).into();
         let dot_history: Hsla = rgb(COLOR_HISTORY).into();
         let dot_user_msg: Hsla = rgb(COLOR_USER_MSG).into();
+        let dot_pinned: Hsla = rgb(COLOR_PINNED).into();
 
         // ── Build and return the popover ──────────────────────────────────────
         div().id("token-context-bar").child(
             Popover::new("token-context-popover")
                 .trigger(trigger)
-                .content(move |_, _window, cx| {
+                .content(move |_, window, cx| {
+                    let expanded =
+                        window
+                            .use_keyed_state("token-context-breakdown-expanded", cx, |_, _| false);
+                    let is_expanded = *expanded.read(cx);
                     div()
                         .flex()
                         .flex_col()
@@ -368,6 +489,22 @@ impl RenderOnce for TokenContextBarView {
                                         )
                                         .child(format!("History: {}", history_text.clone())),
                                 )
+                                // Pinned context (only when something is pinned)
+                                .when(has_pinned, |this| {
+                                    this.child(
+                                        h_flex()
+                                            .gap_2()
+                                            .items_center()
+                                            .child(
+                                                div()
+                                                    .w(px(10.0))
+                                                    .h(px(10.0))
+                                                    .rounded_sm()
+                                                    .bg(dot_pinned),
+                                            )
+                                            .child(format!("Pinned: {}", pinned_text.clone())),
+                                    )
+                                })
                                 // User message
                                 .child(
                                     h_flex()
@@ -400,6 +537,57 @@ impl RenderOnce for TokenContextBarView {
                                         .child(format!("Remaining: {}", remaining_text.clone())),
                                 ),
                         )
+                        // Expandable per-source breakdown
+                        .child(
+                            div()
+                                .flex()
+                                .flex_col()
+                                .gap_2()
+                                .pt_2()
+                                .border_t_1()
+                                .border_color(cx.theme().border)
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(
+                                    h_flex()
+                                        .id("token-context-breakdown-toggle")
+                                        .gap_1()
+                                        .cursor_pointer()
+                                        .font_weight(gpui::FontWeight::SEMIBOLD)
+                                        .text_color(cx.theme().foreground)
+                                        .child(if is_expanded { "▾" } else { "▸" })
+                                        .child(t("footer-tokens-breakdown"))
+                                        .when(any_truncated && !is_expanded, |this| {
+                                            this.child(
+                                                div()
+                                                    .text_color(rgb(COLOR_TRUNCATED))
+                                                    .child("· truncated"),
+                                            )
+                                        })
+                                        .on_click({
+                                            let expanded = expanded.clone();
+                                            move |_, _, cx| {
+                                                expanded.update(cx, |open, cx| {
+                                                    *open = !*open;
+                                                    cx.notify();
+                                                });
+                                            }
+                                        }),
+                                )
+                                .when(is_expanded, |this| {
+                                    this.children(
+                                        rows.iter().map(|row| render_breakdown_row(row, cx)),
+                                    )
+                                    .when_some(dropped_text.clone(), |this, text| {
+                                        this.child(div().italic().child(text))
+                                    })
+                                    .children(
+                                        pruned_lines
+                                            .iter()
+                                            .map(|line| div().italic().child(line.clone())),
+                                    )
+                                }),
+                        )
                         // Actual counts section (only if available)
                         .when(has_actuals, |this| {
                             this.child(
@@ -416,7 +604,7 @@ impl RenderOnce for TokenContextBarView {
                                         div()
                                             .font_weight(gpui::FontWeight::SEMIBOLD)
                                             .text_color(cx.theme().foreground)
-                                            .child("Actual (from provider):"),
+                                            .child(t("footer-tokens-actual")),
                                     )
                                     .child(format!("Input: {}", actual_input_text.clone()))
                                     .child(format!("Output: {}", actual_output_text.clone()))
@@ -459,7 +647,7 @@ impl RenderOnce for TokenContextBarView {
                                         div()
                                             .font_weight(gpui::FontWeight::SEMIBOLD)
                                             .text_color(cx.theme().foreground)
-                                            .child("Session totals:"),
+                                            .child(t("footer-tokens-session")),
                                     )
                                     .child(format!("Input: {}", session_input_text))
                                     .child(format!("Output: {}", session_output_text))
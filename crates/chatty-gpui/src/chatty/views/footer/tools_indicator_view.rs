@@ -1,10 +1,18 @@
 use crate::assets::CustomIcon;
+use crate::chatty::controllers::GlobalChattyApp;
+use crate::chatty::models::conversations_store::ConversationsStore;
+use crate::chatty::views::ToolReferenceDialog;
+use crate::i18n::t;
 use crate::settings::controllers::execution_settings_controller;
+use crate::settings::models::ManagedConfigModel;
 use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use crate::settings::models::managed_config::ToolGroup;
+use chatty_core::services::tool_stats_service::{self, ToolStats};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::popover::Popover;
 use gpui_component::{ActiveTheme, Disableable, Icon, Sizable, button::*, h_flex};
+use std::collections::BTreeSet;
 
 // Popover dimensions (same as MCP indicator)
 const TOOLS_POPOVER_MIN_WIDTH: f32 = 200.0;
@@ -21,8 +29,10 @@ impl ToolsIndicatorView {
 
 impl RenderOnce for ToolsIndicatorView {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
-        let settings = cx.global::<ExecutionSettingsModel>();
-        let enabled_count = count_enabled_categories(settings);
+        let mut settings = cx.global::<ExecutionSettingsModel>().clone();
+        cx.global::<ManagedConfigModel>()
+            .restrict_tools(&mut settings);
+        let enabled_count = count_enabled_categories(&settings);
 
         // Amber color for tools/construction theme (distinct from MCP blue)
         let tools_color = rgb(0xF59E0B); // Amber-500
@@ -66,6 +76,33 @@ impl RenderOnce for ToolsIndicatorView {
                     let fs_write_enabled = settings.filesystem_write_enabled;
                     let code_enabled = settings.execute_code_enabled;
                     let docker_enabled = settings.docker_code_execution_enabled;
+                    let managed = cx.global::<ManagedConfigModel>();
+                    let blocked = |group| managed.blocked_reason(group);
+                    // Tools switched off for the active conversation; `None` without one
+                    let conversation_disabled: Option<BTreeSet<String>> =
+                        cx.try_global::<ConversationsStore>().and_then(|store| {
+                            store
+                                .active_id()
+                                .and_then(|id| store.get_conversation(id))
+                                .map(|conv| conv.disabled_tools().clone())
+                        });
+                    let failing: Vec<(String, ToolStats)> = match &conversation_disabled {
+                        Some(disabled) => tool_stats_service::tool_stats()
+                            .map(|service| service.snapshot())
+                            .map(|data| {
+                                data.by_usage()
+                                    .into_iter()
+                                    .filter(|(name, stats)| {
+                                        stats.is_consistently_failing() && !disabled.contains(name)
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                        None => Vec::new(),
+                    };
+                    let disabled_here: Vec<String> = conversation_disabled
+                        .map(|disabled| disabled.into_iter().collect())
+                        .unwrap_or_default();
 
                     div()
                         .flex()
@@ -84,19 +121,32 @@ impl RenderOnce for ToolsIndicatorView {
                                 .font_weight(FontWeight::BOLD)
                                 .text_color(cx.theme().foreground)
                                 .pb_2()
-                                .child("Filesystem Tools"),
+                                .child(t("footer-tools-filesystem")),
                         )
                         .child(div().h(px(1.0)).w_full().bg(cx.theme().border).mb_2())
                         // Shell Execution - always toggleable (danger!)
-                        .child(render_shell_item(shell_enabled, cx))
-                        .child(render_code_item(code_enabled, cx))
+                        .child(render_shell_item(
+                            shell_enabled,
+                            blocked(ToolGroup::Shell),
+                            cx,
+                        ))
+                        .child(render_code_item(
+                            code_enabled,
+                            blocked(ToolGroup::CodeExec),
+                            cx,
+                        ))
                         // Docker - always toggleable (danger!)
-                        .child(render_docker_item(docker_enabled, cx))
+                        .child(render_docker_item(
+                            docker_enabled,
+                            blocked(ToolGroup::DockerExec),
+                            cx,
+                        ))
                         // Filesystem Read - toggleable only if workspace_dir is set
                         .child(render_filesystem_toggle_item(
                             "Filesystem Read",
                             fs_read_enabled,
                             workspace_set,
+                            blocked(ToolGroup::FsRead),
                             "toggle-fs-read",
                             execution_settings_controller::toggle_filesystem_read,
                         ))
@@ -105,6 +155,7 @@ impl RenderOnce for ToolsIndicatorView {
                             "Filesystem Write",
                             fs_write_enabled,
                             workspace_set,
+                            blocked(ToolGroup::FsWrite),
                             "toggle-fs-write",
                             execution_settings_controller::toggle_filesystem_write,
                         ))
@@ -126,6 +177,36 @@ impl RenderOnce for ToolsIndicatorView {
                                     .child("ℹ Configure workspace in Settings to enable filesystem tools"),
                             )
                         })
+                        // Tools that keep failing, per the tool statistics
+                        .when(!failing.is_empty(), |this| {
+                            this.child(render_section_header("Failing Tools", cx))
+                                .children(failing.into_iter().map(|(name, stats)| {
+                                    render_conversation_tool_item(
+                                        name,
+                                        Some(format!(
+                                            "{:.0}% of {} calls succeeded",
+                                            stats.success_rate() * 100.0,
+                                            stats.calls
+                                        )),
+                                        false,
+                                        cx,
+                                    )
+                                }))
+                        })
+                        .when(!disabled_here.is_empty(), |this| {
+                            this.child(render_section_header("Off in This Chat", cx))
+                                .children(disabled_here.into_iter().map(|name| {
+                                    render_conversation_tool_item(name, None, true, cx)
+                                }))
+                        })
+                        .child(div().h(px(1.0)).w_full().bg(cx.theme().border).mt_2().mb_1())
+                        .child(
+                            Button::new("open-tool-reference")
+                                .xsmall()
+                                .ghost()
+                                .label(t("tool-reference-open"))
+                                .on_click(|_, window, cx| ToolReferenceDialog::open(window, cx)),
+                        )
                 })
         })
     }
@@ -153,7 +234,7 @@ fn count_enabled_categories(settings: &ExecutionSettingsModel) -> usize {
 }
 
 /// Render the shell execution toggle button
-fn render_shell_item(enabled: bool, _cx: &App) -> impl IntoElement {
+fn render_shell_item(enabled: bool, blocked: Option<String>, _cx: &App) -> impl IntoElement {
     let button_id = SharedString::from("toggle-shell");
 
     div()
@@ -165,9 +246,10 @@ fn render_shell_item(enabled: bool, _cx: &App) -> impl IntoElement {
         .px_2()
         .py_1()
         .rounded_md()
-        .child(div().text_sm().child("Shell (danger!)"))
-        .child(
-            Button::new(button_id)
+        .child(div().text_sm().child(t("footer-tools-shell")))
+        .child(match blocked {
+            Some(reason) => blocked_button(button_id, reason),
+            None => Button::new(button_id)
                 .xsmall()
                 .when(enabled, |btn| btn.danger())
                 .when(!enabled, |btn| btn.ghost())
@@ -175,11 +257,11 @@ fn render_shell_item(enabled: bool, _cx: &App) -> impl IntoElement {
                 .on_click(move |_event, _window, cx| {
                     execution_settings_controller::toggle_execution(cx);
                 }),
-        )
+        })
 }
 
 /// Render the code execution toggle button.
-fn render_code_item(enabled: bool, _cx: &App) -> impl IntoElement {
+fn render_code_item(enabled: bool, blocked: Option<String>, _cx: &App) -> impl IntoElement {
     let button_id = SharedString::from("toggle-code-exec");
 
     div()
@@ -191,9 +273,10 @@ fn render_code_item(enabled: bool, _cx: &App) -> impl IntoElement {
         .px_2()
         .py_1()
         .rounded_md()
-        .child(div().text_sm().child("Code Execution"))
-        .child(
-            Button::new(button_id)
+        .child(div().text_sm().child(t("footer-tools-code-execution")))
+        .child(match blocked {
+            Some(reason) => blocked_button(button_id, reason),
+            None => Button::new(button_id)
                 .xsmall()
                 .when(enabled, |btn| btn.primary())
                 .when(!enabled, |btn| btn.ghost())
@@ -201,11 +284,11 @@ fn render_code_item(enabled: bool, _cx: &App) -> impl IntoElement {
                 .on_click(move |_event, _window, cx| {
                     execution_settings_controller::toggle_execute_code(cx);
                 }),
-        )
+        })
 }
 
 /// Render the Docker fallback toggle button (danger!)
-fn render_docker_item(enabled: bool, _cx: &App) -> impl IntoElement {
+fn render_docker_item(enabled: bool, blocked: Option<String>, _cx: &App) -> impl IntoElement {
     let button_id = SharedString::from("toggle-docker");
 
     div()
@@ -217,9 +300,10 @@ fn render_docker_item(enabled: bool, _cx: &App) -> impl IntoElement {
         .px_2()
         .py_1()
         .rounded_md()
-        .child(div().text_sm().child("Docker Fallback"))
-        .child(
-            Button::new(button_id)
+        .child(div().text_sm().child(t("footer-tools-docker-fallback")))
+        .child(match blocked {
+            Some(reason) => blocked_button(button_id, reason),
+            None => Button::new(button_id)
                 .xsmall()
                 .when(enabled, |btn| btn.danger())
                 .when(!enabled, |btn| btn.ghost())
@@ -227,7 +311,7 @@ fn render_docker_item(enabled: bool, _cx: &App) -> impl IntoElement {
                 .on_click(move |_event, _window, cx| {
                     execution_settings_controller::toggle_docker_code_execution(cx);
                 }),
-        )
+        })
 }
 
 /// Render a toggleable filesystem tool item (disabled if workspace not set)
@@ -235,6 +319,7 @@ fn render_filesystem_toggle_item(
     name: &str,
     enabled: bool,
     workspace_set: bool,
+    blocked: Option<String>,
     button_id: &str,
     toggle_fn: fn(&mut App),
 ) -> impl IntoElement {
@@ -251,8 +336,9 @@ fn render_filesystem_toggle_item(
         .py_1()
         .rounded_md()
         .child(div().text_sm().child(name))
-        .child(
-            Button::new(button_id)
+        .child(match blocked {
+            Some(reason) => blocked_button(button_id, reason),
+            None => Button::new(button_id)
                 .xsmall()
                 .when(workspace_set && enabled, |btn| btn.primary())
                 .when(!workspace_set || !enabled, |btn| btn.ghost())
@@ -269,5 +355,95 @@ fn render_filesystem_toggle_item(
                         toggle_fn(cx);
                     }
                 }),
+        })
+}
+
+/// A disabled button for a tool category blocked by the managed config,
+/// with the reason as its tooltip.
+fn blocked_button(button_id: SharedString, reason: String) -> Button {
+    Button::new(button_id)
+        .xsmall()
+        .ghost()
+        .disabled(true)
+        .tooltip(reason)
+        .child(t("footer-tools-blocked"))
+}
+
+/// Section title with a divider above, for the per-conversation tool lists
+fn render_section_header(title: &'static str, cx: &App) -> impl IntoElement {
+    div()
+        .flex()
+        .flex_col()
+        .child(
+            div()
+                .h(px(1.0))
+                .w_full()
+                .bg(cx.theme().border)
+                .mt_2()
+                .mb_2(),
+        )
+        .child(
+            div()
+                .text_sm()
+                .font_weight(FontWeight::BOLD)
+                .text_color(cx.theme().foreground)
+                .pb_1()
+                .child(title),
+        )
+}
+
+/// A tool that can be switched off (or back on when `disabled`) for the
+/// active conversation only.
+fn render_conversation_tool_item(
+    name: String,
+    detail: Option<String>,
+    disabled: bool,
+    cx: &App,
+) -> impl IntoElement {
+    let button_id = SharedString::from(format!("toggle-chat-tool-{name}"));
+    let tool_name = name.clone();
+
+    div()
+        .flex()
+        .flex_row()
+        .items_center()
+        .justify_between()
+        .gap_2()
+        .px_2()
+        .py_1()
+        .rounded_md()
+        .child(
+            div()
+                .flex()
+                .flex_col()
+                .child(div().text_sm().child(name))
+                .when_some(detail, |el, detail| {
+                    el.child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(detail),
+                    )
+                }),
+        )
+        .child(
+            Button::new(button_id)
+                .xsmall()
+                .ghost()
+                .child(if disabled {
+                    "Enable"
+                } else {
+                    "Disable in chat"
+                })
+                .on_click(move |_event, _window, cx| {
+                    if let Some(app) = cx
+                        .try_global::<GlobalChattyApp>()
+                        .and_then(|g| g.try_upgrade())
+                    {
+                        app.update(cx, |app, cx| {
+                            app.set_conversation_tool_enabled(&tool_name, disabled, cx);
+                        });
+                    }
+                }),
         )
 }
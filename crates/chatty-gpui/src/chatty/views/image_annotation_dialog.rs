@@ -0,0 +1,463 @@
+//! Annotation editor for staged image attachments.
+//!
+//! Opened by clicking an image chip in the chat input. The user drags on the
+//! preview to crop, draw arrows and boxes, or blur regions; "Apply" burns the
+//! edits into a copy of the image (`chatty_core::services::image_annotation`)
+//! and swaps it in for the original attachment, so only the annotated copy is
+//! sent to the provider.
+
+use std::path::PathBuf;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme as _, Disableable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex, v_flex,
+};
+
+use super::chat_input::ChatInputState;
+use crate::chatty::services::image_annotation::{
+    Annotation, NormPoint, NormRect, annotate_image, image_size,
+};
+use crate::i18n::t;
+
+const MAX_PREVIEW_WIDTH: f32 = 720.0;
+const MAX_PREVIEW_HEIGHT: f32 = 440.0;
+const STROKE_COLOR: u32 = 0xe63946;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AnnotationTool {
+    Crop,
+    Arrow,
+    Box,
+    Blur,
+}
+
+impl AnnotationTool {
+    const ALL: [AnnotationTool; 4] = [
+        AnnotationTool::Crop,
+        AnnotationTool::Arrow,
+        AnnotationTool::Box,
+        AnnotationTool::Blur,
+    ];
+
+    fn label(self) -> String {
+        match self {
+            AnnotationTool::Crop => t("annotate-crop"),
+            AnnotationTool::Arrow => t("annotate-arrow"),
+            AnnotationTool::Box => t("annotate-box"),
+            AnnotationTool::Blur => t("annotate-blur"),
+        }
+    }
+
+    /// The annotation for a drag from `start` to `end`, or `None` when the
+    /// drag was too short to mean anything.
+    fn annotation(self, start: NormPoint, end: NormPoint) -> Option<Annotation> {
+        let rect = NormRect::from_corners(start, end);
+        match self {
+            AnnotationTool::Arrow => {
+                (rect.width.max(rect.height) >= 0.02).then_some(Annotation::Arrow {
+                    from: start,
+                    to: end,
+                })
+            }
+            _ if rect.is_degenerate() => None,
+            AnnotationTool::Crop => Some(Annotation::Crop(rect)),
+            AnnotationTool::Box => Some(Annotation::Box(rect)),
+            AnnotationTool::Blur => Some(Annotation::Blur(rect)),
+        }
+    }
+}
+
+/// Stateful view inside the annotation dialog.
+pub struct ImageAnnotationView {
+    source: PathBuf,
+    input_state: Entity<ChatInputState>,
+    preview_size: Size<Pixels>,
+    tool: AnnotationTool,
+    annotations: Vec<Annotation>,
+    /// Start and current point of the drag in progress.
+    drag: Option<(NormPoint, NormPoint)>,
+    /// Window bounds of the preview, captured each frame to map mouse positions.
+    preview_bounds: Option<Bounds<Pixels>>,
+    saving: bool,
+    error: Option<String>,
+}
+
+impl ImageAnnotationView {
+    pub fn new(source: PathBuf, input_state: Entity<ChatInputState>) -> Self {
+        let (preview_size, error) = match image_size(&source) {
+            Ok((width, height)) => (fit_preview(width, height), None),
+            Err(e) => (
+                size(px(MAX_PREVIEW_WIDTH), px(MAX_PREVIEW_HEIGHT)),
+                Some(e.to_string()),
+            ),
+        };
+        Self {
+            source,
+            input_state,
+            preview_size,
+            tool: AnnotationTool::Box,
+            annotations: Vec::new(),
+            drag: None,
+            preview_bounds: None,
+            saving: false,
+            error,
+        }
+    }
+
+    /// Map a window position to normalized preview coordinates.
+    fn to_norm(&self, position: Point<Pixels>) -> Option<NormPoint> {
+        let bounds = self.preview_bounds?;
+        Some(NormPoint::new(
+            (position.x - bounds.origin.x).as_f32() / bounds.size.width.as_f32(),
+            (position.y - bounds.origin.y).as_f32() / bounds.size.height.as_f32(),
+        ))
+    }
+
+    fn start_drag(&mut self, position: Point<Pixels>, cx: &mut Context<Self>) {
+        if let Some(point) = self.to_norm(position) {
+            self.drag = Some((point, point));
+            cx.notify();
+        }
+    }
+
+    fn update_drag(&mut self, position: Point<Pixels>, cx: &mut Context<Self>) {
+        if let (Some((start, _)), Some(point)) = (self.drag, self.to_norm(position)) {
+            self.drag = Some((start, point));
+            cx.notify();
+        }
+    }
+
+    fn finish_drag(&mut self, cx: &mut Context<Self>) {
+        if let Some((start, end)) = self.drag.take() {
+            if let Some(annotation) = self.tool.annotation(start, end) {
+                // Only one crop applies; a new one replaces the old.
+                if matches!(annotation, Annotation::Crop(_)) {
+                    self.annotations
+                        .retain(|a| !matches!(a, Annotation::Crop(_)));
+                }
+                self.annotations.push(annotation);
+            }
+            cx.notify();
+        }
+    }
+
+    fn apply(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.annotations.is_empty() {
+            window.close_dialog(cx);
+            return;
+        }
+        self.saving = true;
+        self.error = None;
+        cx.notify();
+
+        let source = self.source.clone();
+        let annotations = self.annotations.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let task_source = source.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                annotate_image(&task_source, &annotations).map_err(|e| e.to_string())
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Task error: {}", e)));
+
+            this.update_in(cx, |view, window, cx| match result {
+                Ok(annotated) => {
+                    view.input_state.update(cx, |state, cx| {
+                        state.replace_attachment(&source, annotated, cx);
+                    });
+                    window.close_dialog(cx);
+                }
+                Err(e) => {
+                    tracing::warn!(path = ?source, error = %e, "Failed to annotate image");
+                    view.saving = false;
+                    view.error = Some(e);
+                    cx.notify();
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+}
+
+/// Scale an image down (never up) to fit the preview area.
+fn fit_preview(width: u32, height: u32) -> Size<Pixels> {
+    let (width, height) = (width.max(1) as f32, height.max(1) as f32);
+    let scale = (MAX_PREVIEW_WIDTH / width)
+        .min(MAX_PREVIEW_HEIGHT / height)
+        .min(1.0);
+    size(px(width * scale), px(height * scale))
+}
+
+/// Absolutely positioned overlay for a rect-shaped annotation.
+fn render_rect_overlay(rect: NormRect, tool: AnnotationTool, preview: Size<Pixels>) -> Div {
+    let overlay = div()
+        .absolute()
+        .left(preview.width * rect.x)
+        .top(preview.height * rect.y)
+        .w(preview.width * rect.width)
+        .h(preview.height * rect.height);
+    match tool {
+        AnnotationTool::Crop => overlay
+            .border_2()
+            .border_color(rgb(0x3b82f6))
+            .bg(Hsla::from(rgb(0x3b82f6)).opacity(0.08)),
+        AnnotationTool::Blur => overlay
+            .bg(black().opacity(0.6))
+            .flex()
+            .items_center()
+            .justify_center()
+            .text_xs()
+            .text_color(white())
+            .child(t("annotate-blur")),
+        AnnotationTool::Box | AnnotationTool::Arrow => {
+            overlay.border_2().border_color(rgb(STROKE_COLOR))
+        }
+    }
+}
+
+/// Stroke an arrow from `from` to `to` inside `bounds`.
+fn paint_arrow(window: &mut Window, bounds: Bounds<Pixels>, from: NormPoint, to: NormPoint) {
+    let start = point(
+        bounds.origin.x + bounds.size.width * from.x,
+        bounds.origin.y + bounds.size.height * from.y,
+    );
+    let end = point(
+        bounds.origin.x + bounds.size.width * to.x,
+        bounds.origin.y + bounds.size.height * to.y,
+    );
+    let (dx, dy) = ((end.x - start.x).as_f32(), (end.y - start.y).as_f32());
+    let angle = dy.atan2(dx);
+    let head = 12.0_f32.min((dx * dx + dy * dy).sqrt() / 2.0);
+
+    let mut builder = PathBuilder::stroke(px(3.));
+    builder.move_to(start);
+    builder.line_to(end);
+    for barb in [angle + 2.7, angle - 2.7] {
+        builder.move_to(end);
+        builder.line_to(point(
+            end.x + px(head * barb.cos()),
+            end.y + px(head * barb.sin()),
+        ));
+    }
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, rgb(STROKE_COLOR));
+    }
+}
+
+impl Render for ImageAnnotationView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let preview = self.preview_size;
+        let view = cx.entity();
+
+        // Committed annotations plus the drag in progress, as (tool, annotation).
+        let mut shapes: Vec<(AnnotationTool, Annotation)> = self
+            .annotations
+            .iter()
+            .map(|annotation| {
+                let tool = match annotation {
+                    Annotation::Crop(_) => AnnotationTool::Crop,
+                    Annotation::Arrow { .. } => AnnotationTool::Arrow,
+                    Annotation::Box(_) => AnnotationTool::Box,
+                    Annotation::Blur(_) => AnnotationTool::Blur,
+                };
+                (tool, *annotation)
+            })
+            .collect();
+        if let Some((start, end)) = self.drag {
+            let pending = match self.tool {
+                AnnotationTool::Arrow => Annotation::Arrow {
+                    from: start,
+                    to: end,
+                },
+                _ => Annotation::Box(NormRect::from_corners(start, end)),
+            };
+            shapes.push((self.tool, pending));
+        }
+        let arrows: Vec<(NormPoint, NormPoint)> = shapes
+            .iter()
+            .filter_map(|(_, annotation)| match annotation {
+                Annotation::Arrow { from, to } => Some((*from, *to)),
+                _ => None,
+            })
+            .collect();
+
+        v_flex()
+            .gap_3()
+            .p_4()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .children(AnnotationTool::ALL.into_iter().map(|tool| {
+                        let button =
+                            Button::new(SharedString::from(format!("annotate-tool-{tool:?}")))
+                                .small()
+                                .label(tool.label())
+                                .on_click(cx.listener(move |view, _, _, cx| {
+                                    view.tool = tool;
+                                    cx.notify();
+                                }));
+                        if tool == self.tool {
+                            button.primary()
+                        } else {
+                            button.ghost()
+                        }
+                    }))
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("annotate-undo")
+                            .small()
+                            .ghost()
+                            .label(t("annotate-undo"))
+                            .disabled(self.annotations.is_empty())
+                            .on_click(cx.listener(|view, _, _, cx| {
+                                view.annotations.pop();
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(
+                div().flex().justify_center().child(
+                    div()
+                        .id("annotate-preview")
+                        .relative()
+                        .w(preview.width)
+                        .h(preview.height)
+                        .overflow_hidden()
+                        .cursor_crosshair()
+                        .border_1()
+                        .border_color(cx.theme().border)
+                        .child(img(self.source.clone()).size_full())
+                        .children(
+                            shapes
+                                .iter()
+                                .filter_map(|(tool, annotation)| match annotation {
+                                    Annotation::Crop(rect)
+                                    | Annotation::Box(rect)
+                                    | Annotation::Blur(rect) => {
+                                        Some(render_rect_overlay(*rect, *tool, preview))
+                                    }
+                                    Annotation::Arrow { .. } => None,
+                                }),
+                        )
+                        .child(
+                            canvas(
+                                move |bounds, _window, cx| {
+                                    view.update(cx, |view, _| view.preview_bounds = Some(bounds));
+                                },
+                                move |bounds, _, window, _cx| {
+                                    for (from, to) in &arrows {
+                                        paint_arrow(window, bounds, *from, *to);
+                                    }
+                                },
+                            )
+                            .absolute()
+                            .size_full(),
+                        )
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(|view, event: &MouseDownEvent, _, cx| {
+                                view.start_drag(event.position, cx);
+                            }),
+                        )
+                        .on_mouse_move(cx.listener(|view, event: &MouseMoveEvent, _, cx| {
+                            view.update_drag(event.position, cx);
+                        }))
+                        .on_mouse_up(
+                            MouseButton::Left,
+                            cx.listener(|view, _: &MouseUpEvent, _, cx| {
+                                view.finish_drag(cx);
+                            }),
+                        )
+                        .on_mouse_up_out(
+                            MouseButton::Left,
+                            cx.listener(|view, _: &MouseUpEvent, _, cx| {
+                                view.finish_drag(cx);
+                            }),
+                        ),
+                ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+            })
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(t("annotate-hint")),
+                    )
+                    .child(
+                        Button::new("annotate-cancel")
+                            .label(t("action-cancel"))
+                            .on_click(|_, window, cx| window.close_dialog(cx)),
+                    )
+                    .child(
+                        Button::new("annotate-apply")
+                            .primary()
+                            .label(if self.saving {
+                                t("annotate-applying")
+                            } else {
+                                t("annotate-apply")
+                            })
+                            .disabled(self.saving)
+                            .on_click(cx.listener(|view, _, window, cx| {
+                                view.apply(window, cx);
+                            })),
+                    ),
+            )
+    }
+}
+
+/// Static helper that opens the annotation editor for a staged image.
+pub struct ImageAnnotationDialog;
+
+impl ImageAnnotationDialog {
+    pub fn open(
+        source: PathBuf,
+        input_state: Entity<ChatInputState>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let view = cx.new(|_cx| ImageAnnotationView::new(source, input_state));
+        window.open_dialog(cx, move |dialog, _window, _cx| {
+            dialog
+                .title(t("annotate-title"))
+                .w(px(MAX_PREVIEW_WIDTH + 48.))
+                .child(view.clone())
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_preview_scales_down_only() {
+        let small = fit_preview(200, 100);
+        assert_eq!(small, size(px(200.), px(100.)));
+
+        let wide = fit_preview(1440, 400);
+        assert_eq!(wide.width, px(MAX_PREVIEW_WIDTH));
+        assert_eq!(wide.height, px(200.));
+    }
+
+    #[test]
+    fn short_drags_are_ignored() {
+        let p = NormPoint::new(0.5, 0.5);
+        assert_eq!(AnnotationTool::Box.annotation(p, p), None);
+        assert_eq!(AnnotationTool::Arrow.annotation(p, p), None);
+        assert!(
+            AnnotationTool::Blur
+                .annotation(p, NormPoint::new(0.7, 0.8))
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Jobs panel: background jobs that are waiting, running or gave up.
+//!
+//! Opened from the jobs indicator in the status footer. "Retry" makes a job
+//! due again with a fresh set of attempts; `ChattyApp` observes the
+//! `JobQueue` global and starts it.
+
+use crate::chatty::models::{BackgroundJob, ConversationsStore, JobKind, JobQueue, JobState};
+use crate::i18n::{t, t_args};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme as _, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn kind_label(kind: JobKind) -> String {
+    t(match kind {
+        JobKind::GenerateTitle => "jobs-kind-title",
+        JobKind::ExportAtif => "jobs-kind-export-atif",
+        JobKind::ExportJsonl => "jobs-kind-export-jsonl",
+        JobKind::Summarize => "jobs-kind-summarize",
+        JobKind::DetectTopicShift => "jobs-kind-topic-shift",
+    })
+}
+
+fn state_label(job: &BackgroundJob, now: i64) -> String {
+    match job.state {
+        JobState::Running => t("jobs-state-running"),
+        JobState::Pending if job.attempts == 0 => t("jobs-state-pending"),
+        JobState::Pending => t_args(
+            "jobs-state-retrying",
+            &[
+                ("attempt", (job.attempts + 1).to_string().as_str()),
+                (
+                    "seconds",
+                    (job.next_attempt_at - now).max(0).to_string().as_str(),
+                ),
+            ],
+        ),
+        JobState::Failed if job.attempts == 0 => t("jobs-state-not-started"),
+        JobState::Failed => t_args(
+            "jobs-state-failed",
+            &[("attempts", job.attempts.to_string().as_str())],
+        ),
+    }
+}
+
+pub struct JobsDialog;
+
+impl JobsDialog {
+    pub fn open(window: &mut Window, cx: &mut App) {
+        window.open_dialog(cx, |dialog, _window, cx| {
+            let jobs = cx.global::<JobQueue>().jobs().to_vec();
+            let has_failed = jobs.iter().any(|job| job.state == JobState::Failed);
+
+            let dialog = dialog
+                .title(t("jobs-title"))
+                .w(px(600.0))
+                .h(px(420.0))
+                .child(
+                    div()
+                        .id("job-list")
+                        .h_full()
+                        .overflow_y_scrollbar()
+                        .px(px(16.0))
+                        .py(px(12.0))
+                        .when(jobs.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(t("jobs-empty")),
+                            )
+                        })
+                        .children(jobs.into_iter().map(JobRow)),
+                );
+            if !has_failed {
+                return dialog;
+            }
+            dialog.footer(|_, _window, _, _cx| {
+                vec![
+                    Button::new("retry-failed-jobs")
+                        .label(t("jobs-retry-all"))
+                        .on_click(|_, _window, cx| {
+                            let now = unix_now();
+                            cx.update_global::<JobQueue, _>(|queue, _cx| {
+                                let failed: Vec<u64> = queue
+                                    .jobs()
+                                    .iter()
+                                    .filter(|job| job.state == JobState::Failed)
+                                    .map(|job| job.id)
+                                    .collect();
+                                for id in failed {
+                                    queue.retry(id, now);
+                                }
+                            });
+                            cx.refresh_windows();
+                        }),
+                ]
+            })
+        });
+    }
+}
+
+#[derive(IntoElement)]
+struct JobRow(BackgroundJob);
+
+impl RenderOnce for JobRow {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let job = self.0;
+        let id = job.id;
+        let conversation = cx
+            .global::<ConversationsStore>()
+            .title(&job.conversation_id)
+            .map(str::to_string)
+            .unwrap_or_else(|| t("jobs-untitled"));
+        let failed = job.state == JobState::Failed;
+        let running = job.state == JobState::Running;
+
+        v_flex()
+            .id(("job", id as usize))
+            .mb_3()
+            .p_3()
+            .gap_1()
+            .bg(cx.theme().secondary)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        v_flex()
+                            .min_w_0()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .child(kind_label(job.kind)),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(conversation),
+                            ),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .items_center()
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(if failed {
+                                        cx.theme().danger
+                                    } else {
+                                        cx.theme().muted_foreground
+                                    })
+                                    .child(state_label(&job, unix_now())),
+                            )
+                            .when(!running, |this| {
+                                this.child(
+                                    Button::new(("retry-job", id as usize))
+                                        .ghost()
+                                        .xsmall()
+                                        .label(t("jobs-retry"))
+                                        .on_click(move |_, _, cx| {
+                                            let now = unix_now();
+                                            cx.update_global::<JobQueue, _>(|queue, _cx| {
+                                                queue.retry(id, now);
+                                            });
+                                            cx.refresh_windows();
+                                        }),
+                                )
+                                .child(
+                                    Button::new(("dismiss-job", id as usize))
+                                        .ghost()
+                                        .xsmall()
+                                        .label(t("jobs-dismiss"))
+                                        .on_click(move |_, _, cx| {
+                                            cx.update_global::<JobQueue, _>(|queue, _cx| {
+                                                queue.dismiss(id);
+                                            });
+                                            cx.refresh_windows();
+                                        }),
+                                )
+                            }),
+                    ),
+            )
+            .when_some(job.last_error, |this, error| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(error),
+                )
+            })
+    }
+}
@@ -0,0 +1,168 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Objects and arrays shallower than this start expanded.
+const DEFAULT_OPEN_DEPTH: usize = 2;
+
+/// Maximum number of children rendered per object or array.
+const MAX_CHILDREN: usize = 200;
+
+/// Maximum characters shown for a string value.
+const MAX_STRING_CHARS: usize = 300;
+
+/// Nodes whose expanded state differs from the default, keyed by path.
+#[derive(Default)]
+struct JsonTreeState {
+    toggled: HashSet<String>,
+}
+
+/// Collapsible tree view of a JSON value, used for GraphQL query results.
+///
+/// Objects and arrays can be expanded and collapsed individually; the
+/// expanded state is kept per `id` across re-renders.
+#[derive(IntoElement)]
+pub struct JsonTreeComponent {
+    id: SharedString,
+    value: Value,
+}
+
+impl JsonTreeComponent {
+    pub fn new(id: impl Into<SharedString>, value: Value) -> Self {
+        Self {
+            id: id.into(),
+            value,
+        }
+    }
+}
+
+impl RenderOnce for JsonTreeComponent {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(
+            ElementId::Name(format!("{}-state", self.id).into()),
+            cx,
+            |_, _| JsonTreeState::default(),
+        );
+        let mut rows = Vec::new();
+        render_node(&state, None, &self.value, "$", 0, &mut rows, cx);
+
+        div()
+            .id(ElementId::Name(self.id.clone()))
+            .flex()
+            .flex_col()
+            .font_family("monospace")
+            .text_xs()
+            .children(rows)
+    }
+}
+
+fn render_node(
+    state: &Entity<JsonTreeState>,
+    key: Option<String>,
+    value: &Value,
+    path: &str,
+    depth: usize,
+    rows: &mut Vec<AnyElement>,
+    cx: &App,
+) {
+    let muted = cx.theme().muted_foreground;
+    let indent = px(depth as f32 * 14.0);
+    let key_label = key.map(|k| format!("{k}: "));
+
+    let children: Vec<(String, &Value)> = match value {
+        Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i.to_string(), v))
+            .collect(),
+        leaf => {
+            let (text, color): (String, Hsla) = match leaf {
+                Value::String(s) => {
+                    let mut text: String = s.chars().take(MAX_STRING_CHARS).collect();
+                    if s.chars().count() > MAX_STRING_CHARS {
+                        text.push('…');
+                    }
+                    (format!("\"{text}\""), rgb(0x16A34A).into())
+                }
+                Value::Number(n) => (n.to_string(), rgb(0x2563EB).into()),
+                Value::Bool(b) => (b.to_string(), rgb(0x9333EA).into()),
+                _ => ("null".to_string(), muted),
+            };
+            rows.push(
+                div()
+                    .flex()
+                    .flex_row()
+                    .pl(indent + px(14.0))
+                    .when_some(key_label, |row, label| {
+                        row.child(div().text_color(cx.theme().foreground).child(label))
+                    })
+                    .child(div().text_color(color).child(text))
+                    .into_any_element(),
+            );
+            return;
+        }
+    };
+
+    let toggled = state.read(cx).toggled.contains(path);
+    let expanded = (depth < DEFAULT_OPEN_DEPTH) != toggled;
+    let summary = match value {
+        Value::Array(_) => format!("[{}]", children.len()),
+        _ => format!("{{{}}}", children.len()),
+    };
+    let toggle_state = state.clone();
+    let toggle_path = path.to_string();
+
+    rows.push(
+        div()
+            .id(ElementId::Name(format!("json-node-{path}").into()))
+            .flex()
+            .flex_row()
+            .gap_1()
+            .pl(indent)
+            .cursor_pointer()
+            .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                toggle_state.update(cx, |state, _| {
+                    if !state.toggled.remove(&toggle_path) {
+                        state.toggled.insert(toggle_path.clone());
+                    }
+                });
+                window.refresh();
+            })
+            .child(
+                div()
+                    .text_color(muted)
+                    .child(if expanded { "▼" } else { "▶" }),
+            )
+            .when_some(key_label, |row, label| {
+                row.child(div().text_color(cx.theme().foreground).child(label))
+            })
+            .child(div().text_color(muted).child(summary))
+            .into_any_element(),
+    );
+
+    if expanded {
+        for (child_key, child) in children.iter().take(MAX_CHILDREN) {
+            render_node(
+                state,
+                Some(child_key.clone()),
+                child,
+                &format!("{path}/{child_key}"),
+                depth + 1,
+                rows,
+                cx,
+            );
+        }
+        if children.len() > MAX_CHILDREN {
+            rows.push(
+                div()
+                    .pl(indent + px(28.0))
+                    .text_color(muted)
+                    .child(format!("… {} more", children.len() - MAX_CHILDREN))
+                    .into_any_element(),
+            );
+        }
+    }
+}
@@ -6,6 +6,7 @@ use gpui_component::{Icon, Sizable};
 use tracing::debug;
 
 use crate::assets::CustomIcon;
+use crate::i18n::t;
 
 // Inline math dimensions
 const INLINE_MATH_MAX_HEIGHT: f32 = 32.0;
@@ -97,7 +98,7 @@ impl MathComponent {
             .ghost()
             .xsmall()
             .icon(Icon::new(CustomIcon::Copy))
-            .tooltip("Copy LaTeX")
+            .tooltip(t("message-copy-latex"))
             .on_click(move |_event, _window, cx| {
                 cx.write_to_clipboard(ClipboardItem::new_string(latex_content.clone()));
             })
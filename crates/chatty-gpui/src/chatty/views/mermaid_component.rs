@@ -7,6 +7,7 @@ use tracing::warn;
 
 use crate::assets::CustomIcon;
 use crate::chatty::services::MermaidRendererService;
+use crate::i18n::t;
 
 // Mermaid diagram dimensions
 const MERMAID_MAX_WIDTH: f32 = 800.0;
@@ -97,7 +98,7 @@ impl MermaidComponent {
         .ghost()
         .xsmall()
         .icon(Icon::new(CustomIcon::Copy))
-        .tooltip("Copy Mermaid")
+        .tooltip(t("message-copy-mermaid"))
         .on_click(move |_event, _window, cx| {
             cx.write_to_clipboard(ClipboardItem::new_string(source.clone()));
         })
@@ -110,7 +111,7 @@ impl MermaidComponent {
             .ghost()
             .xsmall()
             .icon(Icon::new(CustomIcon::Image))
-            .tooltip("Copy as PNG")
+            .tooltip(t("action-copy-png"))
             .on_click(move |_event, _window, _cx| {
                 match MermaidRendererService::render_svg_to_png(&svg_path) {
                     Ok(png_bytes) => {
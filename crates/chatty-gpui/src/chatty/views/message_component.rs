@@ -1,14 +1,29 @@
 use crate::assets::CustomIcon;
-use crate::chatty::models::MessageFeedback;
+use crate::chatty::controllers::GlobalChattyApp;
+use crate::chatty::models::{MessageFeedback, TurnError};
+use crate::i18n;
+use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use crate::settings::models::{CopyFormat, GeneralSettingsModel};
+use chatty_core::models::attachment_validation::is_video_extension;
+use chatty_core::models::text_direction;
+use chatty_core::services::message_copy::format_for_copy;
+use chatty_core::services::notes_service::NotesService;
+use chatty_core::services::video_frames::{cached_sample, format_timestamp};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
-use gpui_component::ActiveTheme;
 use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::{Input, InputState};
+use gpui_component::menu::{DropdownMenu, PopupMenuItem};
+use gpui_component::tooltip::Tooltip;
+use gpui_component::{ActiveTheme, WindowExt as _, h_flex, v_flex};
 use gpui_component::{Icon, IconName, Sizable};
 use std::path::PathBuf;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use super::code_block_component::CodeBlockComponent;
+use super::find_highlight::{
+    FindHighlighter, highlighted_text, math_segments_source, piece_matches,
+};
 use super::mermaid_component::MermaidComponent;
 use super::message_math_render::render_math_segments;
 use super::message_parsing::{build_cached_parse_result, build_streaming_parse_result};
@@ -17,6 +32,7 @@ use super::parsed_cache::{
     CachedContentSegment, CachedMarkdownSegment, CachedParseResult, ContentCacheKey,
     ParsedContentCache, StreamingParseState,
 };
+use super::reference_cards::render_reference_cards;
 use super::trace_components::SystemTraceView;
 
 /// Message role indicator
@@ -41,11 +57,51 @@ pub struct DisplayMessage {
     pub attachments: Vec<PathBuf>,
     // User feedback signal (thumbs up/down) for assistant messages
     pub feedback: Option<MessageFeedback>,
+    // Emoji reactions and private reviewer note for assistant messages
+    pub reactions: Vec<String>,
+    pub note: Option<String>,
+    // Why the response to this (user) message failed, shown as an error card
+    pub error: Option<TurnError>,
     // Index into the conversation's history (parallel arrays) for this message
     pub history_index: Option<usize>,
 }
 
+/// Emoji offered by the "Add reaction" menu.
+const REACTION_EMOJIS: &[&str] = &["👍", "❤️", "😂", "🎯", "🤔", "⚠️", "🐛", "✅"];
+
+/// Reaction the keyboard "bookmark" action toggles.
+pub const BOOKMARK_REACTION: &str = "🔖";
+
+/// Change to a message's reviewer annotations (reactions and private note).
+#[derive(Clone, Debug)]
+pub enum AnnotationChange {
+    /// Add the emoji if absent, remove it otherwise
+    ToggleReaction(String),
+    /// Replace the note (`None` clears it)
+    SetNote(Option<String>),
+}
+
 impl DisplayMessage {
+    /// Apply a reaction/note change locally, mirroring `Conversation`'s rules.
+    pub fn apply_annotation(&mut self, change: &AnnotationChange) {
+        match change {
+            AnnotationChange::ToggleReaction(emoji) => {
+                match self.reactions.iter().position(|r| r == emoji) {
+                    Some(pos) => {
+                        self.reactions.remove(pos);
+                    }
+                    None => self.reactions.push(emoji.clone()),
+                }
+            }
+            AnnotationChange::SetNote(note) => {
+                self.note = note
+                    .as_ref()
+                    .map(|n| n.trim().to_string())
+                    .filter(|n| !n.is_empty());
+            }
+        }
+    }
+
     /// Create an assistant display message.
     ///
     /// Kept as a convenience constructor for future callers (e.g., tests or
@@ -69,6 +125,9 @@ impl DisplayMessage {
             is_markdown: true,
             attachments: Vec::new(),
             feedback: None,
+            reactions: Vec::new(),
+            note: None,
+            error: None,
             history_index: None,
         }
     }
@@ -78,17 +137,22 @@ impl DisplayMessage {
 ///
 /// Mirrors the logic of the thinking-block + code-block + math rendering paths
 /// but reads from the cache instead of re-parsing.
-fn render_from_cached(cached: &CachedParseResult, index: usize, cx: &App) -> Vec<AnyElement> {
+fn render_from_cached(
+    cached: &CachedParseResult,
+    index: usize,
+    find: &mut Option<FindHighlighter>,
+    cx: &App,
+) -> Vec<AnyElement> {
     cached
         .segments
         .iter()
         .enumerate()
         .flat_map(|(seg_idx, segment)| match segment {
             CachedContentSegment::Thinking(content) => {
-                vec![render_thinking_block(content, index, seg_idx, cx).into_any_element()]
+                vec![render_thinking_block(content, index, seg_idx, find, cx).into_any_element()]
             }
             CachedContentSegment::Text(md_segments) => {
-                render_cached_markdown_segments(md_segments, index * 100 + seg_idx, cx)
+                render_cached_markdown_segments(md_segments, index * 100 + seg_idx, find, cx)
             }
         })
         .collect()
@@ -96,9 +160,13 @@ fn render_from_cached(cached: &CachedParseResult, index: usize, cx: &App) -> Vec
 
 /// Render cached markdown segments (code blocks with pre-highlighted spans,
 /// text with pre-parsed math segments).
+///
+/// Text containing a find-in-conversation match is shown as its source with
+/// the matches highlighted, since the markdown renderer cannot highlight.
 fn render_cached_markdown_segments(
     segments: &[CachedMarkdownSegment],
     base_index: usize,
+    find: &mut Option<FindHighlighter>,
     cx: &App,
 ) -> Vec<AnyElement> {
     let mut elements = Vec::new();
@@ -112,20 +180,34 @@ fn render_cached_markdown_segments(
                     cached_cb.code.clone(),
                     cached_cb.styles.clone(),
                     base_index * 100 + code_block_index,
-                );
+                )
+                .find_matches(piece_matches(find, &cached_cb.code));
                 elements.push(block.into_any_element());
                 code_block_index += 1;
             }
             CachedMarkdownSegment::TextWithMath(math_segments) => {
-                let math_elements = render_math_segments(math_segments, base_index, cx);
-                elements.extend(math_elements);
+                let matched_source = find.as_ref().and_then(|find| {
+                    let source = math_segments_source(math_segments);
+                    find.has_match(&source).then_some(source)
+                });
+                if let Some(source) = matched_source {
+                    elements.push(
+                        div()
+                            .w_full()
+                            .child(highlighted_text(&source, find, cx))
+                            .into_any_element(),
+                    );
+                } else {
+                    elements.extend(render_math_segments(math_segments, base_index, cx));
+                }
             }
             CachedMarkdownSegment::IncompleteCodeBlock { language, code } => {
                 let block = CodeBlockComponent::streaming(
                     language.clone(),
                     code.clone(),
                     base_index * 100 + code_block_index,
-                );
+                )
+                .find_matches(piece_matches(find, code));
                 elements.push(block.into_any_element());
                 code_block_index += 1;
             }
@@ -134,7 +216,8 @@ fn render_cached_markdown_segments(
                     language.clone(),
                     code.clone(),
                     base_index * 100 + code_block_index,
-                );
+                )
+                .find_matches(piece_matches(find, code));
                 elements.push(block.into_any_element());
                 code_block_index += 1;
             }
@@ -161,6 +244,7 @@ fn render_thinking_block(
     content: &str,
     index: usize,
     segment_index: usize,
+    find: &mut Option<FindHighlighter>,
     cx: &App,
 ) -> Stateful<Div> {
     let border_color = cx.theme().border;
@@ -189,14 +273,14 @@ fn render_thinking_block(
                         .text_xs()
                         .text_color(muted_text)
                         .font_weight(FontWeight::SEMIBOLD)
-                        .child("Thinking"),
+                        .child(i18n::t("message-thinking")),
                 ),
         )
         .child(
             div()
                 .text_sm()
                 .text_color(muted_text)
-                .child(content.to_string()),
+                .child(highlighted_text(content, find, cx)),
         )
 }
 
@@ -235,13 +319,21 @@ fn render_attachments(attachments: &[PathBuf], id_prefix: &str, cx: &App) -> Div
                             .max_h(px(300.))
                             .rounded_md(),
                     )
+            } else if is_video_file(path) {
+                render_video_strip(path, element_id, cx)
             } else {
-                // Non-image attachment (PDF etc) - show filename
+                // Non-image attachment (PDF etc) - show filename; PDFs open
+                // in the inline viewer on click
                 let filename = path
                     .file_name()
                     .and_then(|n| n.to_str())
                     .unwrap_or("file")
                     .to_string();
+                let is_pdf = path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| e.eq_ignore_ascii_case("pdf"));
+                let pdf_path = path.clone();
 
                 div()
                     .id(element_id)
@@ -255,10 +347,102 @@ fn render_attachments(attachments: &[PathBuf], id_prefix: &str, cx: &App) -> Div
                     .gap_2()
                     .child(Icon::new(CustomIcon::Paperclip).size_4())
                     .child(div().text_sm().child(filename))
+                    .when(is_pdf, |this| {
+                        this.cursor_pointer()
+                            .hover(|style| style.bg(cx.theme().muted.opacity(0.5)))
+                            .on_click(move |_event, _window, cx| {
+                                open_pdf_in_viewer(pdf_path.clone(), cx);
+                            })
+                    })
             }
         }))
 }
 
+fn is_video_file(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(is_video_extension)
+}
+
+/// Video attachment: filename and duration above a strip of the frames that
+/// were sampled for the model, each labelled with its timestamp.
+fn render_video_strip(path: &std::path::Path, element_id: ElementId, cx: &App) -> Stateful<Div> {
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("video")
+        .to_string();
+    let sample = cached_sample(path);
+    let duration = sample
+        .as_ref()
+        .map(|sample| format_timestamp(sample.duration_secs));
+
+    div()
+        .id(element_id)
+        .max_w(px(600.))
+        .p_2()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().border)
+        .flex()
+        .flex_col()
+        .gap_2()
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .gap_2()
+                .child(Icon::new(CustomIcon::Paperclip).size_4())
+                .child(div().text_sm().child(filename))
+                .when_some(duration, |this, duration| {
+                    this.child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(duration),
+                    )
+                }),
+        )
+        .when_some(sample, |this, sample| {
+            this.child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .gap_1()
+                    .children(sample.frames.into_iter().map(|frame| {
+                        div()
+                            .flex()
+                            .flex_col()
+                            .items_center()
+                            .child(
+                                img(frame.path)
+                                    .w(px(96.))
+                                    .h(px(54.))
+                                    .rounded_sm()
+                                    .object_fit(ObjectFit::Cover),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(format_timestamp(frame.timestamp_secs)),
+                            )
+                    })),
+            )
+        })
+}
+
+/// Open `path` in the active chat view's inline PDF viewer.
+pub(crate) fn open_pdf_in_viewer(path: PathBuf, cx: &mut App) {
+    if let Some(app) = cx
+        .try_global::<GlobalChattyApp>()
+        .and_then(|g| g.try_upgrade())
+    {
+        let chat_view = app.read(cx).chat_view.clone();
+        chat_view.update(cx, |view, cx| view.open_pdf(path, cx));
+    }
+}
+
 /// Extract the file path from an `add_attachment` tool call output JSON.
 /// Returns `None` if the output is missing, not valid JSON, or lacks a `"path"` field.
 fn extract_attachment_path(tool_call: &super::message_types::ToolCallBlock) -> Option<PathBuf> {
@@ -305,6 +489,8 @@ fn extract_daytona_downloaded_files(
 pub struct MessageRenderCaches<'a> {
     pub parsed: &'a mut ParsedContentCache,
     pub streaming: &'a mut Option<StreamingParseState>,
+    /// Find-in-conversation matches to highlight in this message
+    pub find: &'a mut Option<FindHighlighter>,
 }
 
 /// Render a text segment using the cache, handling embedded `<thinking>` blocks.
@@ -329,11 +515,11 @@ fn render_text_segment_cached(
             caches.parsed.insert(cache_key, result);
         }
         let cached = caches.parsed.get(&cache_key).unwrap();
-        render_from_cached(cached, base_index, cx)
+        render_from_cached(cached, base_index, caches.find, cx)
     } else if is_markdown {
         // Streaming: incremental parse with stable prefix reuse
         let state = build_streaming_parse_result(text_segment, caches.streaming.as_ref(), cx);
-        let elements = render_from_cached(&state.result, base_index, cx);
+        let elements = render_from_cached(&state.result, base_index, caches.find, cx);
         *caches.streaming = Some(state);
         // Wrap in single container — same blank-space-during-streaming fix
         // as the main assistant rendering path.
@@ -346,7 +532,11 @@ fn render_text_segment_cached(
                 .into_any_element(),
         ]
     } else {
-        vec![div().child(text_segment.to_string()).into_any_element()]
+        vec![
+            div()
+                .child(highlighted_text(text_segment, caches.find, cx))
+                .into_any_element(),
+        ]
     }
 }
 
@@ -440,6 +630,7 @@ where
                         &mut MessageRenderCaches {
                             parsed: caches.parsed,
                             streaming: &mut None, // don't pollute the streaming cache
+                            find: caches.find,
                         },
                         cx,
                     );
@@ -545,6 +736,27 @@ where
                     cx,
                 ));
             }
+        } else if let TraceItem::Reflection(reflection) = item {
+            // Self-check that rejected the first attempt (shares the tool
+            // collapse state, collapsed by default)
+            let is_collapsed = collapsed_tool_calls
+                .get(&(index, tool_idx))
+                .copied()
+                .unwrap_or(true);
+            let on_toggle_clone = on_toggle_tool.clone();
+            let msg_idx = index;
+            container = container.child(div().mt_2().mb_2().w_full().child(
+                super::trace_components::render_reflection_inline(
+                    reflection,
+                    index,
+                    tool_idx,
+                    is_collapsed,
+                    move |_event: &MouseDownEvent, _window: &mut Window, cx: &mut App| {
+                        on_toggle_clone(msg_idx, tool_idx, cx);
+                    },
+                    cx,
+                ),
+            ));
         }
     }
 
@@ -579,30 +791,92 @@ where
     container
 }
 
-/// Render the action row (copy + feedback + regenerate buttons) for assistant messages
-fn render_assistant_actions<G, R>(
-    content: &str,
-    feedback: &Option<MessageFeedback>,
+/// Render the action row (reactions + feedback + regenerate + pin + note + export + copy buttons)
+/// for assistant messages
+#[allow(clippy::too_many_arguments)]
+fn render_assistant_actions<G, R, P, A>(
+    msg: &DisplayMessage,
     index: usize,
     is_last_message: bool,
     on_feedback: G,
     on_regenerate: R,
+    on_pin: P,
+    on_annotate: A,
     cx: &App,
 ) -> Div
 where
     G: Fn(usize, Option<MessageFeedback>, &mut App) + 'static + Clone,
     R: Fn(usize, &mut App) + 'static + Clone,
+    P: Fn(usize, &mut App) + 'static + Clone,
+    A: Fn(usize, AnnotationChange, &mut App) + 'static + Clone,
 {
+    let content = msg.content.as_str();
+    let feedback = &msg.feedback;
     let muted = cx.theme().muted_foreground;
+    let notes_vault_dir = cx
+        .try_global::<ExecutionSettingsModel>()
+        .and_then(|s| s.notes_vault_dir.clone());
+    let default_copy_format = cx
+        .try_global::<GeneralSettingsModel>()
+        .map(|s| s.default_copy_format)
+        .unwrap_or_default();
 
     let thumbs_up_active = matches!(feedback, Some(MessageFeedback::ThumbsUp));
     let thumbs_down_active = matches!(feedback, Some(MessageFeedback::ThumbsDown));
 
+    let reaction_chips = msg.reactions.iter().enumerate().map(|(ix, emoji)| {
+        let on_annotate = on_annotate.clone();
+        let emoji = emoji.clone();
+        div()
+            .id(ElementId::Name(format!("reaction-{}-{}", index, ix).into()))
+            .px_1p5()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().secondary)
+            .text_sm()
+            .cursor_pointer()
+            .child(emoji.clone())
+            .tooltip(|window, cx| Tooltip::new("Remove reaction").build(window, cx))
+            .on_click(move |_event, _window, cx| {
+                on_annotate(index, AnnotationChange::ToggleReaction(emoji.clone()), cx);
+            })
+    });
+
     div()
         .flex()
         .justify_end()
+        .items_center()
         .gap_1()
         .pt_2()
+        .children(reaction_chips)
+        .child(
+            Button::new(ElementId::Name(format!("react-msg-{}", index).into()))
+                .ghost()
+                .xsmall()
+                .icon(Icon::new(CustomIcon::SmilePlus).text_color(muted))
+                .tooltip(i18n::t("message-add-reaction"))
+                .dropdown_menu_with_anchor(Corner::TopRight, {
+                    let on_annotate = on_annotate.clone();
+                    let current = msg.reactions.clone();
+                    move |menu, _, _| {
+                        REACTION_EMOJIS.iter().fold(menu, |menu, emoji| {
+                            let on_annotate = on_annotate.clone();
+                            menu.item(
+                                PopupMenuItem::new(*emoji)
+                                    .checked(current.iter().any(|r| r == emoji))
+                                    .on_click(move |_, _, cx| {
+                                        on_annotate(
+                                            index,
+                                            AnnotationChange::ToggleReaction(emoji.to_string()),
+                                            cx,
+                                        );
+                                    }),
+                            )
+                        })
+                    }
+                }),
+        )
         .child(
             Button::new(ElementId::Name(format!("thumbs-up-msg-{}", index).into()))
                 .ghost()
@@ -614,7 +888,7 @@ where
                         muted
                     }),
                 )
-                .tooltip("Good response")
+                .tooltip(i18n::t("message-good-response"))
                 .on_click({
                     let on_feedback = on_feedback.clone();
                     let new_feedback = if thumbs_up_active {
@@ -638,7 +912,7 @@ where
                         muted
                     }),
                 )
-                .tooltip("Bad response")
+                .tooltip(i18n::t("message-bad-response"))
                 .on_click({
                     let on_feedback = on_feedback.clone();
                     let new_feedback = if thumbs_down_active {
@@ -657,7 +931,7 @@ where
                     .ghost()
                     .xsmall()
                     .icon(Icon::new(CustomIcon::Refresh).text_color(muted))
-                    .tooltip("Regenerate response")
+                    .tooltip(i18n::t("message-regenerate"))
                     .on_click({
                         let on_regenerate = on_regenerate.clone();
                         move |_event, _window, cx| {
@@ -666,23 +940,206 @@ where
                     }),
             )
         })
+        .child(
+            Button::new(ElementId::Name(format!("pin-msg-{}", index).into()))
+                .ghost()
+                .xsmall()
+                .icon(Icon::new(CustomIcon::Pin).text_color(muted))
+                .tooltip(i18n::t("message-pin"))
+                .on_click(move |_event, _window, cx| {
+                    on_pin(index, cx);
+                }),
+        )
+        .child(
+            Button::new(ElementId::Name(format!("note-msg-{}", index).into()))
+                .ghost()
+                .xsmall()
+                .icon(
+                    Icon::new(CustomIcon::MessageSquare).text_color(if msg.note.is_some() {
+                        cx.theme().primary
+                    } else {
+                        muted
+                    }),
+                )
+                .tooltip(if msg.note.is_some() {
+                    "Edit note"
+                } else {
+                    "Add note"
+                })
+                .on_click({
+                    let note = msg.note.clone();
+                    move |_event, window, cx| {
+                        open_note_dialog(index, note.clone(), on_annotate.clone(), window, cx);
+                    }
+                }),
+        )
+        .when_some(notes_vault_dir, |this, vault_dir| {
+            this.child(
+                Button::new(ElementId::Name(format!("export-note-msg-{}", index).into()))
+                    .ghost()
+                    .xsmall()
+                    .icon(Icon::new(CustomIcon::FilePlus).text_color(muted))
+                    .tooltip(i18n::t("message-export-note"))
+                    .on_click({
+                        let content = content.to_string();
+                        move |_event, window, cx| {
+                            let result = NotesService::new(&vault_dir)
+                                .and_then(|notes| notes.export(&content));
+                            let message = match result {
+                                Ok(path) => format!("Exported to {path}"),
+                                Err(e) => {
+                                    warn!(error = ?e, "Failed to export answer to note");
+                                    format!("Export failed: {e}")
+                                }
+                            };
+                            window.push_notification(message, cx);
+                        }
+                    }),
+            )
+        })
         .child(
             Button::new(ElementId::Name(format!("copy-msg-{}", index).into()))
                 .ghost()
                 .xsmall()
                 .icon(Icon::new(CustomIcon::Copy))
-                .tooltip("Copy message")
+                .tooltip(copy_format_label(default_copy_format))
                 .on_click({
                     let content = content.to_string();
                     move |_event, _window, cx| {
-                        cx.write_to_clipboard(ClipboardItem::new_string(content.clone()));
+                        cx.write_to_clipboard(ClipboardItem::new_string(format_for_copy(
+                            &content,
+                            default_copy_format,
+                        )));
                     }
                 }),
         )
+        .child(
+            Button::new(ElementId::Name(format!("copy-as-msg-{}", index).into()))
+                .ghost()
+                .xsmall()
+                .icon(Icon::new(IconName::ChevronDown).text_color(muted))
+                .tooltip(i18n::t("message-copy-as"))
+                .dropdown_menu_with_anchor(Corner::TopRight, {
+                    let content = content.to_string();
+                    move |menu, _, _| {
+                        CopyFormat::ALL.into_iter().fold(menu, |menu, format| {
+                            let content = content.clone();
+                            menu.item(
+                                PopupMenuItem::new(copy_format_label(format))
+                                    .checked(format == default_copy_format)
+                                    .on_click(move |_, _, cx| {
+                                        cx.write_to_clipboard(ClipboardItem::new_string(
+                                            format_for_copy(&content, format),
+                                        ));
+                                    }),
+                            )
+                        })
+                    }
+                }),
+        )
+}
+
+/// Menu label for copying a message in `format`.
+fn copy_format_label(format: CopyFormat) -> &'static str {
+    match format {
+        CopyFormat::Markdown => "Copy as Markdown",
+        CopyFormat::PlainText => "Copy as plain text",
+        CopyFormat::Html => "Copy as HTML",
+        CopyFormat::Clean => "Copy without citations or thinking",
+    }
+}
+
+/// Render the private reviewer note under an assistant message, if any.
+fn render_annotation_note(msg: &DisplayMessage, cx: &App) -> Option<Div> {
+    let note = msg.note.as_ref()?;
+    Some(
+        div()
+            .mt_2()
+            .px_3()
+            .py_2()
+            .rounded_md()
+            .border_l_2()
+            .border_color(cx.theme().primary)
+            .bg(cx.theme().secondary)
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(note.clone()),
+    )
+}
+
+/// Open the dialog for adding, editing or clearing a message's private note.
+fn open_note_dialog<A>(
+    index: usize,
+    note: Option<String>,
+    on_annotate: A,
+    window: &mut Window,
+    cx: &mut App,
+) where
+    A: Fn(usize, AnnotationChange, &mut App) + 'static + Clone,
+{
+    let has_note = note.is_some();
+    let input = cx.new(|cx| {
+        InputState::new(window, cx)
+            .multi_line(true)
+            .rows(5)
+            .placeholder(i18n::t("message-note-placeholder"))
+            .default_value(note.unwrap_or_default())
+    });
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        dialog
+            .title("Message Note")
+            .overlay(true)
+            .keyboard(true)
+            .close_button(true)
+            .overlay_closable(true)
+            .w(px(480.))
+            .child(
+                v_flex().gap_3().p_4().child(Input::new(&input)).child(
+                    h_flex()
+                        .gap_2()
+                        .justify_end()
+                        .when(has_note, |this| {
+                            let on_annotate = on_annotate.clone();
+                            this.child(
+                                Button::new("clear-note")
+                                    .label(i18n::t("action-clear"))
+                                    .on_click(move |_, window, cx| {
+                                        on_annotate(index, AnnotationChange::SetNote(None), cx);
+                                        window.close_dialog(cx);
+                                    }),
+                            )
+                        })
+                        .child(
+                            Button::new("cancel-note")
+                                .label(i18n::t("action-cancel"))
+                                .on_click(|_, window, cx| window.close_dialog(cx)),
+                        )
+                        .child(
+                            Button::new("save-note")
+                                .primary()
+                                .label(i18n::t("action-save"))
+                                .on_click({
+                                    let on_annotate = on_annotate.clone();
+                                    let input = input.clone();
+                                    move |_, window, cx| {
+                                        let text = input.read(cx).value().to_string();
+                                        on_annotate(
+                                            index,
+                                            AnnotationChange::SetNote(Some(text)),
+                                            cx,
+                                        );
+                                        window.close_dialog(cx);
+                                    }
+                                }),
+                        ),
+                ),
+            )
+    });
 }
 
-#[allow(clippy::too_many_arguments)] // Rendering function with 4 generic callbacks
-pub fn render_message<F, D, G, R>(
+#[allow(clippy::too_many_arguments)] // Rendering function with 6 generic callbacks
+pub fn render_message<F, D, G, R, P, A>(
     msg: &DisplayMessage,
     index: usize,
     is_last_message: bool,
@@ -693,6 +1150,8 @@ pub fn render_message<F, D, G, R>(
     on_toggle_diff: D,
     on_feedback: G,
     on_regenerate: R,
+    on_pin: P,
+    on_annotate: A,
     cx: &App,
 ) -> AnyElement
 where
@@ -700,6 +1159,8 @@ where
     D: Fn(usize, usize, &mut App) + 'static + Clone,
     G: Fn(usize, Option<MessageFeedback>, &mut App) + 'static + Clone,
     R: Fn(usize, &mut App) + 'static + Clone,
+    P: Fn(usize, &mut App) + 'static + Clone,
+    A: Fn(usize, AnnotationChange, &mut App) + 'static + Clone,
 {
     let is_dark = cx.theme().mode.is_dark();
 
@@ -753,11 +1214,11 @@ where
                 caches.parsed.insert(cache_key, result);
             }
             let cached = caches.parsed.get(&cache_key).unwrap();
-            render_from_cached(cached, index, cx)
+            render_from_cached(cached, index, caches.find, cx)
         } else {
             // Streaming: incremental parse with stable prefix reuse
             let state = build_streaming_parse_result(&msg.content, caches.streaming.as_ref(), cx);
-            let elements = render_from_cached(&state.result, index, cx);
+            let elements = render_from_cached(&state.result, index, caches.find, cx);
             *caches.streaming = Some(state);
             // Wrap all streaming elements in a single container to prevent the
             // blank-space-during-streaming layout bug.  When multiple top-level
@@ -782,13 +1243,16 @@ where
         return match msg.role {
             MessageRole::Assistant if is_finalized && !msg.content.is_empty() => div()
                 .child(message_with_content)
+                .children(render_reference_cards(&msg.content, index, cx))
+                .children(render_annotation_note(msg, cx))
                 .child(render_assistant_actions(
-                    &msg.content,
-                    &msg.feedback,
+                    msg,
                     index,
                     is_last_message,
                     on_feedback,
                     on_regenerate,
+                    on_pin,
+                    on_annotate,
                     cx,
                 ))
                 .into_any_element(),
@@ -859,8 +1323,12 @@ where
         );
         container.children(content_elements)
     } else {
-        // Non-markdown plain text
-        container.child(msg.content.clone())
+        // Non-markdown plain text, aligned by its first strong character
+        let direction =
+            text_direction::detect(&msg.content).unwrap_or_else(|| i18n::layout_direction(cx));
+        container
+            .when(direction.is_rtl(), |d| d.text_right())
+            .child(highlighted_text(&msg.content, caches.find, cx))
     };
 
     // Wrap with action buttons for finalized assistant messages
@@ -872,13 +1340,16 @@ where
             .flex()
             .flex_col()
             .child(final_container)
+            .children(render_reference_cards(&msg.content, index, cx))
+            .children(render_annotation_note(msg, cx))
             .child(render_assistant_actions(
-                &msg.content,
-                &msg.feedback,
+                msg,
                 index,
                 is_last_message,
                 on_feedback,
                 on_regenerate,
+                on_pin,
+                on_annotate,
                 cx,
             ))
             .into_any_element(),
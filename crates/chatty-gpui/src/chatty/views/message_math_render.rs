@@ -6,6 +6,10 @@
 //! - **Text-only runs**: Passed through as [`MarkdownContent`] with full formatting
 
 use crate::chatty::services::MathRendererService;
+use crate::i18n;
+use chatty_core::models::text_direction::{self, TextDirection};
+use chatty_core::services::citation_service::resolve_citekeys;
+use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::ActiveTheme;
 use gpui_component::text::TextView;
@@ -26,7 +30,37 @@ impl RenderOnce for MarkdownContent {
         // Use message index for stable ID during streaming
         let id = ElementId::Name(format!("msg-{}-markdown", self.message_index).into());
 
-        TextView::markdown(id, self.content, window, cx).selectable(true)
+        // Known `[@citekey]` markers become author-year links.
+        let content = resolve_citekeys(&self.content).into_owned();
+
+        // Paragraphs are aligned by their own direction. All-LTR content in
+        // an LTR layout (the common case) skips the split.
+        let base = i18n::layout_direction(cx);
+        if base == TextDirection::LeftToRight && !text_direction::contains_rtl(&content) {
+            return TextView::markdown(id, content, window, cx)
+                .selectable(true)
+                .into_any_element();
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .w_full()
+            .children(
+                text_direction::split_by_direction(&content, base)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(ix, (direction, run))| {
+                        let id = ElementId::Name(
+                            format!("msg-{}-markdown-{ix}", self.message_index).into(),
+                        );
+                        div()
+                            .w_full()
+                            .when(direction.is_rtl(), |d| d.text_right())
+                            .child(TextView::markdown(id, run, window, cx).selectable(true))
+                    }),
+            )
+            .into_any_element()
     }
 }
 
@@ -1,15 +1,23 @@
 pub mod agent_todo_panel;
 pub mod app_view;
 pub mod approval_prompt_bar;
+pub mod artifact_gallery_panel;
 pub use chatty_core::models::attachment_validation;
 mod chart_renderer;
 pub mod chat_input;
 pub mod chat_view;
 pub mod code_block_component;
 pub mod conversation_item;
+pub mod conversation_tags_dialog;
+pub mod conversation_variables_panel;
 pub mod diff_view_component;
 pub mod error_log_dialog;
+pub mod export_conversation_dialog;
+pub mod find_highlight;
 pub mod footer;
+pub mod image_annotation_dialog;
+pub mod jobs_dialog;
+pub mod json_tree_component;
 pub mod math_parser;
 pub mod math_renderer;
 pub mod mermaid_component;
@@ -17,16 +25,26 @@ pub mod message_component;
 mod message_math_render;
 mod message_parsing;
 pub use chatty_core::models::message_types;
+pub mod onboarding_view;
 pub mod parsed_cache;
+pub mod pdf_viewer_panel;
+pub mod pinned_context_panel;
+pub mod plan_checklist_panel;
+mod reference_cards;
 pub mod search_conversations_dialog;
 pub mod sidebar_view;
 pub mod syntax_highlighter;
 pub mod thinking_indicator;
 pub mod titlebar;
+pub mod tool_reference_dialog;
 pub mod trace_components;
+pub mod user_question_card;
 
 pub use chat_view::ChatView;
 pub use error_log_dialog::ErrorLogDialog;
+pub use jobs_dialog::JobsDialog;
+pub use onboarding_view::{OnboardingState, OnboardingView};
 pub use search_conversations_dialog::SearchConversationsDialog;
 pub use sidebar_view::SidebarView;
 pub use titlebar::AppTitleBar;
+pub use tool_reference_dialog::ToolReferenceDialog;
@@ -0,0 +1,556 @@
+//! First-run provider setup shown in place of the start screen.
+//!
+//! Walks through picking a provider, testing its credentials with a cheap
+//! request (see `chatty_core::settings::providers::validation`), discovering
+//! its models and choosing the default one. Opened on first run and whenever
+//! a message cannot be sent because no model is configured.
+
+use chatty_core::settings::providers::validation::{
+    ProviderCheck, check_azure, check_ollama, check_openrouter, suggest_default_model,
+};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::{Input, InputState};
+use gpui_component::{ActiveTheme, Disableable, Sizable, h_flex, v_flex};
+use tracing::warn;
+
+use crate::i18n::t;
+use crate::settings::controllers::{
+    SettingsView, general_settings_controller, models_controller, providers_controller,
+};
+use crate::settings::models::ProviderModel;
+use crate::settings::models::models_store::ModelsModel;
+use crate::settings::models::providers_store::ProviderType;
+use crate::settings::providers::openrouter::sync_openrouter_models;
+use crate::settings::providers::sync_ollama_models;
+
+const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+/// Whether the setup wizard replaces the start screen.
+#[derive(Clone, Default)]
+pub struct OnboardingState {
+    pub open: bool,
+}
+
+impl Global for OnboardingState {}
+
+impl OnboardingState {
+    pub fn is_open(cx: &App) -> bool {
+        cx.try_global::<Self>().is_some_and(|state| state.open)
+    }
+
+    pub fn open(cx: &mut App) {
+        cx.set_global(Self { open: true });
+        cx.refresh_windows();
+    }
+
+    fn close(cx: &mut App) {
+        cx.set_global(Self { open: false });
+        cx.refresh_windows();
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Step {
+    ChooseProvider,
+    Credentials,
+    Discovering,
+    PickDefault,
+}
+
+pub struct OnboardingView {
+    step: Step,
+    provider: ProviderType,
+    key_input: Entity<InputState>,
+    endpoint_input: Entity<InputState>,
+    ollama_url_input: Entity<InputState>,
+    checking: bool,
+    check: Option<ProviderCheck>,
+    /// Model picked as the default for new conversations
+    selected_model: Option<String>,
+}
+
+impl OnboardingView {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let key_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(t("onboarding-api-key"))
+                .masked(true)
+        });
+        let endpoint_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("https://my-resource.openai.azure.com")
+        });
+        let ollama_url_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(DEFAULT_OLLAMA_URL));
+        Self {
+            step: Step::ChooseProvider,
+            provider: ProviderType::OpenRouter,
+            key_input,
+            endpoint_input,
+            ollama_url_input,
+            checking: false,
+            check: None,
+            selected_model: None,
+        }
+    }
+
+    fn choose_provider(
+        &mut self,
+        provider: ProviderType,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Start from what is already saved, so a retry does not lose the URL
+        if let Some(input) = self.url_input(&provider).cloned() {
+            let url = configured_base_url(&provider, cx).unwrap_or_else(|| match provider {
+                ProviderType::Ollama => DEFAULT_OLLAMA_URL.to_string(),
+                _ => String::new(),
+            });
+            input.update(cx, |input, cx| input.set_value(url, window, cx));
+        }
+        self.provider = provider;
+        self.check = None;
+        self.step = Step::Credentials;
+        cx.notify();
+    }
+
+    /// Azure endpoint or Ollama URL input; OpenRouter has no URL.
+    fn url_input(&self, provider: &ProviderType) -> Option<&Entity<InputState>> {
+        match provider {
+            ProviderType::OpenRouter => None,
+            ProviderType::AzureOpenAI => Some(&self.endpoint_input),
+            ProviderType::Ollama => Some(&self.ollama_url_input),
+        }
+    }
+
+    /// Test the entered credentials; on success save them and discover models.
+    fn test_and_continue(&mut self, cx: &mut Context<Self>) {
+        let provider = self.provider.clone();
+        let key = self.key_input.read(cx).value().trim().to_string();
+        let url = self
+            .url_input(&provider)
+            .map(|input| input.read(cx).value().trim().to_string())
+            .unwrap_or_default();
+        self.checking = true;
+        self.check = None;
+        cx.notify();
+
+        cx.spawn(async move |this, cx| {
+            let check = match provider {
+                ProviderType::OpenRouter => check_openrouter(&key).await,
+                ProviderType::AzureOpenAI => check_azure(&url, &key).await,
+                ProviderType::Ollama => check_ollama(&url).await,
+            };
+            let passed = check.is_passed();
+            let updated = this.update(cx, |view, cx| {
+                view.checking = false;
+                view.check = Some(check);
+                if passed {
+                    match provider {
+                        ProviderType::OpenRouter => {
+                            providers_controller::update_or_create_provider(
+                                cx,
+                                ProviderType::OpenRouter,
+                                key,
+                            )
+                        }
+                        ProviderType::AzureOpenAI => {
+                            providers_controller::update_or_create_azure(cx, key, url.clone())
+                        }
+                        ProviderType::Ollama => {
+                            providers_controller::update_or_create_ollama(cx, url.clone())
+                        }
+                    }
+                    view.step = Step::Discovering;
+                }
+                cx.notify();
+            });
+            if !passed || updated.is_err() {
+                return;
+            }
+
+            // Azure deployments are named by the user, so there is nothing to discover
+            let synced = match provider {
+                ProviderType::OpenRouter => sync_openrouter_models(cx).await.map(|_| ()),
+                ProviderType::Ollama => sync_ollama_models(&url, cx).await.map(|_| ()),
+                ProviderType::AzureOpenAI => Ok(()),
+            };
+            if let Err(e) = synced {
+                warn!(error = ?e, "Model discovery during onboarding failed");
+            }
+
+            let _ = this.update(cx, |view, cx| {
+                let models = cx.global::<ModelsModel>().models();
+                view.selected_model =
+                    suggest_default_model(models, &view.provider).map(|m| m.id.clone());
+                view.step = Step::PickDefault;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn finish(&mut self, cx: &mut Context<Self>) {
+        if let Some(model_id) = &self.selected_model {
+            models_controller::set_default_model(model_id, cx);
+        }
+        general_settings_controller::set_onboarding_completed(cx);
+        OnboardingState::close(cx);
+    }
+
+    fn skip(&mut self, cx: &mut Context<Self>) {
+        general_settings_controller::set_onboarding_completed(cx);
+        OnboardingState::close(cx);
+    }
+
+    fn render_provider_choice(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let options = [
+            (
+                ProviderType::OpenRouter,
+                "onboarding-openrouter-description",
+            ),
+            (ProviderType::Ollama, "onboarding-ollama-description"),
+            (ProviderType::AzureOpenAI, "onboarding-azure-description"),
+        ];
+        v_flex()
+            .gap_2()
+            .children(options.into_iter().map(|(provider, description)| {
+                let name = provider.display_name().to_string();
+                div()
+                    .id(SharedString::from(format!("onboarding-provider-{name}")))
+                    .w_full()
+                    .p_3()
+                    .rounded_md()
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .bg(cx.theme().background)
+                    .cursor_pointer()
+                    .hover(|style| style.bg(cx.theme().secondary))
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(cx.theme().foreground)
+                            .child(name),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(t(description)),
+                    )
+                    .on_click(cx.listener(move |view, _, window, cx| {
+                        view.choose_provider(provider.clone(), window, cx);
+                    }))
+            }))
+    }
+
+    fn render_credentials(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let needs_key = self.provider != ProviderType::Ollama;
+        let url_label = match self.provider {
+            ProviderType::Ollama => t("onboarding-ollama-url"),
+            _ => t("onboarding-endpoint"),
+        };
+
+        v_flex()
+            .gap_3()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(cx.theme().foreground)
+                    .child(self.provider.display_name().to_string()),
+            )
+            .when_some(self.url_input(&self.provider), |this, input| {
+                this.child(field(url_label, Input::new(input), cx))
+            })
+            .when(needs_key, |this| {
+                this.child(field(
+                    t("onboarding-api-key"),
+                    Input::new(&self.key_input).mask_toggle(),
+                    cx,
+                ))
+            })
+            .when_some(self.check.as_ref(), |this, check| {
+                this.child(render_check(check, cx))
+            })
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("onboarding-back")
+                            .small()
+                            .ghost()
+                            .label(t("onboarding-back"))
+                            .disabled(self.checking)
+                            .on_click(cx.listener(|view, _, _, cx| {
+                                view.step = Step::ChooseProvider;
+                                view.check = None;
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("onboarding-test")
+                            .small()
+                            .primary()
+                            .label(if self.checking {
+                                t("onboarding-testing")
+                            } else {
+                                t("onboarding-test")
+                            })
+                            .loading(self.checking)
+                            .disabled(self.checking)
+                            .on_click(cx.listener(|view, _, _, cx| {
+                                view.test_and_continue(cx);
+                            })),
+                    ),
+            )
+    }
+
+    fn render_pick_default(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let models: Vec<(String, String, bool)> = cx
+            .global::<ModelsModel>()
+            .models_by_provider(&self.provider)
+            .into_iter()
+            .map(|m| (m.id.clone(), m.name.clone(), m.supports_images))
+            .collect();
+        let suggested = suggest_default_model(cx.global::<ModelsModel>().models(), &self.provider)
+            .map(|m| m.id.clone());
+        let is_azure = self.provider == ProviderType::AzureOpenAI;
+
+        v_flex()
+            .gap_3()
+            .when_some(self.check.as_ref(), |this, check| {
+                this.child(render_check(check, cx))
+            })
+            .when(is_azure, |this| {
+                this.child(
+                    h_flex()
+                        .gap_2()
+                        .items_center()
+                        .child(
+                            div()
+                                .flex_1()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(t("onboarding-azure-models")),
+                        )
+                        .child(
+                            Button::new("onboarding-open-settings")
+                                .small()
+                                .label(t("onboarding-open-settings"))
+                                .on_click(|_, _, cx| {
+                                    SettingsView::open_or_focus_settings_window(cx);
+                                }),
+                        ),
+                )
+            })
+            .when(!is_azure && models.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(t("onboarding-no-models")),
+                )
+            })
+            .when(!models.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(cx.theme().foreground)
+                        .child(t("onboarding-pick-default")),
+                )
+                .child(
+                    v_flex()
+                        .id("onboarding-models")
+                        .max_h(px(240.))
+                        .overflow_y_scroll()
+                        .gap_1()
+                        .children(models.into_iter().map(|(id, name, images)| {
+                            let selected = self.selected_model.as_ref() == Some(&id);
+                            let is_suggested = suggested.as_ref() == Some(&id);
+                            let id_for_click = id.clone();
+                            h_flex()
+                                .id(SharedString::from(format!("onboarding-model-{id}")))
+                                .px_3()
+                                .py_2()
+                                .gap_2()
+                                .rounded_md()
+                                .cursor_pointer()
+                                .border_1()
+                                .border_color(if selected {
+                                    cx.theme().primary
+                                } else {
+                                    cx.theme().border
+                                })
+                                .hover(|style| style.bg(cx.theme().secondary))
+                                .child(
+                                    div()
+                                        .flex_1()
+                                        .text_sm()
+                                        .text_color(cx.theme().foreground)
+                                        .child(name),
+                                )
+                                .when(images, |this| {
+                                    this.child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(cx.theme().muted_foreground)
+                                            .child("images"),
+                                    )
+                                })
+                                .when(is_suggested, |this| {
+                                    this.child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(cx.theme().primary)
+                                            .child(t("onboarding-suggested")),
+                                    )
+                                })
+                                .on_click(cx.listener(move |view, _, _, cx| {
+                                    view.selected_model = Some(id_for_click.clone());
+                                    cx.notify();
+                                }))
+                        })),
+                )
+            })
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("onboarding-back")
+                            .small()
+                            .ghost()
+                            .label(t("onboarding-back"))
+                            .on_click(cx.listener(|view, _, _, cx| {
+                                view.step = Step::Credentials;
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("onboarding-finish")
+                            .small()
+                            .primary()
+                            .label(t("onboarding-finish"))
+                            .on_click(cx.listener(|view, _, _, cx| view.finish(cx))),
+                    ),
+            )
+    }
+}
+
+impl Render for OnboardingView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let body = match self.step {
+            Step::ChooseProvider => self.render_provider_choice(cx).into_any_element(),
+            Step::Credentials => self.render_credentials(cx).into_any_element(),
+            Step::Discovering => div()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .child(t("onboarding-discovering"))
+                .into_any_element(),
+            Step::PickDefault => self.render_pick_default(cx).into_any_element(),
+        };
+
+        div().w_full().flex().justify_center().child(
+            v_flex()
+                .w_full()
+                .max_w(px(560.))
+                .px_4()
+                .py_6()
+                .gap_4()
+                .child(
+                    v_flex()
+                        .gap_1()
+                        .child(
+                            div()
+                                .text_xl()
+                                .font_weight(FontWeight::BOLD)
+                                .text_color(cx.theme().foreground)
+                                .child(t("onboarding-title")),
+                        )
+                        .child(
+                            div()
+                                .text_sm()
+                                .line_height(relative(1.4))
+                                .text_color(cx.theme().muted_foreground)
+                                .child(t("onboarding-subtitle")),
+                        ),
+                )
+                .child(
+                    div()
+                        .w_full()
+                        .rounded_lg()
+                        .border_1()
+                        .border_color(cx.theme().border)
+                        .bg(cx.theme().secondary)
+                        .p_4()
+                        .child(body),
+                )
+                .child(
+                    h_flex().justify_center().child(
+                        Button::new("onboarding-skip")
+                            .xsmall()
+                            .ghost()
+                            .label(t("onboarding-skip"))
+                            .on_click(cx.listener(|view, _, _, cx| view.skip(cx))),
+                    ),
+                ),
+        )
+    }
+}
+
+/// Base URL saved for `provider`, if any.
+fn configured_base_url(provider: &ProviderType, cx: &App) -> Option<String> {
+    cx.global::<ProviderModel>()
+        .providers()
+        .iter()
+        .find(|p| &p.provider_type == provider)
+        .and_then(|p| p.base_url.clone())
+        .filter(|url| !url.is_empty())
+}
+
+fn field(label: String, input: Input, cx: &App) -> impl IntoElement {
+    v_flex()
+        .gap_1()
+        .child(
+            div()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .child(label),
+        )
+        .child(input.small())
+}
+
+/// The outcome of a credentials test: a confirmation, or the problem with
+/// what to do about it.
+fn render_check(check: &ProviderCheck, cx: &App) -> impl IntoElement {
+    match check {
+        ProviderCheck::Passed { detail } => v_flex().child(
+            div()
+                .text_sm()
+                .text_color(cx.theme().success)
+                .child(detail.clone()),
+        ),
+        ProviderCheck::Failed { problem, fix } => v_flex()
+            .gap_1()
+            .p_2()
+            .rounded_md()
+            .bg(cx.theme().danger.opacity(0.1))
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().danger)
+                    .child(problem.clone()),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().foreground)
+                    .child(fix.clone()),
+            ),
+    }
+}
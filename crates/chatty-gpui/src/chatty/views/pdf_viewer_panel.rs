@@ -0,0 +1,338 @@
+use std::path::PathBuf;
+
+use gpui::prelude::*;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme, Disableable, IconName, Sizable};
+
+use super::trace_components::SelectableText;
+use crate::chatty::services::pdf_viewer::{RenderedPage, page_width, render_page, step_zoom};
+use crate::i18n::{t, t_args};
+
+const PANEL_WIDTH: f32 = 420.0;
+/// Pages are rendered at twice their on-screen size so they stay sharp on
+/// HiDPI displays.
+const DISPLAY_SCALE: f32 = 0.5;
+
+/// Events emitted by the PDF viewer for the chat view to act on.
+#[derive(Clone, Debug)]
+pub enum PdfViewerEvent {
+    /// Attach the render of `page` (zero-based) to the next prompt.
+    AskAboutPage {
+        image: PathBuf,
+        page: u32,
+    },
+    Close,
+}
+
+/// Side panel showing one page of a PDF attachment at a time, with paging,
+/// zoom, the page's selectable text and an "Ask about this page" action.
+pub struct PdfViewerPanel {
+    path: PathBuf,
+    page: u32,
+    zoom: f32,
+    rendered: Option<RenderedPage>,
+    loading: bool,
+    error: Option<String>,
+    show_text: bool,
+}
+
+impl EventEmitter<PdfViewerEvent> for PdfViewerPanel {}
+
+impl PdfViewerPanel {
+    pub fn new(path: PathBuf, cx: &mut Context<Self>) -> Self {
+        let mut panel = Self {
+            path,
+            page: 0,
+            zoom: 1.0,
+            rendered: None,
+            loading: false,
+            error: None,
+            show_text: false,
+        };
+        panel.render_current_page(cx);
+        panel
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    fn page_count(&self) -> Option<u32> {
+        self.rendered.as_ref().map(|page| page.page_count)
+    }
+
+    /// Render the current page and zoom off the UI thread.
+    fn render_current_page(&mut self, cx: &mut Context<Self>) {
+        self.loading = true;
+        self.error = None;
+        let path = self.path.clone();
+        let page = self.page;
+        let width = page_width(self.zoom);
+        cx.spawn(async move |this, cx| {
+            let result = tokio::task::spawn_blocking(move || {
+                render_page(&path, page, width).map_err(|e| e.to_string())
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Task error: {}", e)));
+
+            this.update(cx, |panel, cx| {
+                // Ignore renders superseded by a later page or zoom change.
+                if panel.page != page || page_width(panel.zoom) != width {
+                    return;
+                }
+                panel.loading = false;
+                match result {
+                    Ok(rendered) => {
+                        panel.page = rendered.index;
+                        panel.rendered = Some(rendered);
+                    }
+                    Err(e) => {
+                        tracing::warn!(path = ?panel.path, error = %e, "Failed to render PDF page");
+                        panel.error = Some(e);
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+        cx.notify();
+    }
+
+    fn go_to_page(&mut self, page: u32, cx: &mut Context<Self>) {
+        let last = self.page_count().unwrap_or(1).saturating_sub(1);
+        let page = page.min(last);
+        if page != self.page {
+            self.page = page;
+            self.render_current_page(cx);
+        }
+    }
+
+    fn change_zoom(&mut self, step: i32, cx: &mut Context<Self>) {
+        let zoom = step_zoom(self.zoom, step);
+        if zoom != self.zoom {
+            self.zoom = zoom;
+            self.render_current_page(cx);
+        }
+    }
+
+    fn toggle_text(&mut self, cx: &mut Context<Self>) {
+        self.show_text = !self.show_text;
+        cx.notify();
+    }
+
+    fn ask_about_page(&mut self, cx: &mut Context<Self>) {
+        if let Some(rendered) = &self.rendered {
+            cx.emit(PdfViewerEvent::AskAboutPage {
+                image: rendered.image.clone(),
+                page: rendered.index,
+            });
+        }
+    }
+}
+
+impl Render for PdfViewerPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let file_name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "PDF".to_string());
+        let page_label = match self.page_count() {
+            Some(count) => t_args(
+                "pdf-page-of",
+                &[
+                    ("page", (self.page + 1).to_string().as_str()),
+                    ("count", count.to_string().as_str()),
+                ],
+            ),
+            None => t("pdf-loading"),
+        };
+        let has_prev = self.page > 0;
+        let has_next = self.page_count().is_some_and(|count| self.page + 1 < count);
+        let zoom_label = format!("{}%", (self.zoom * 100.0).round() as u32);
+        let display_width = page_width(self.zoom) as f32 * DISPLAY_SCALE;
+        let page_text = self
+            .rendered
+            .as_ref()
+            .map(|page| page.text.trim().to_string())
+            .unwrap_or_default();
+
+        div()
+            .id("pdf-viewer-panel")
+            .flex_shrink_0()
+            .w(px(PANEL_WIDTH))
+            .h_full()
+            .flex()
+            .flex_col()
+            .border_l_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().secondary.opacity(0.4))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .px_3()
+                    .py_2()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .flex_1()
+                            .min_w_0()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(cx.theme().foreground)
+                                    .truncate()
+                                    .child(file_name),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(page_label),
+                            ),
+                    )
+                    .child(
+                        Button::new("pdf-viewer-close")
+                            .ghost()
+                            .xsmall()
+                            .icon(IconName::Close)
+                            .on_click(cx.listener(|_panel, _event, _window, cx| {
+                                cx.emit(PdfViewerEvent::Close);
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .px_3()
+                    .py_1()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        Button::new("pdf-viewer-prev")
+                            .ghost()
+                            .xsmall()
+                            .icon(IconName::ChevronLeft)
+                            .disabled(!has_prev)
+                            .on_click(cx.listener(|panel, _event, _window, cx| {
+                                panel.go_to_page(panel.page.saturating_sub(1), cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("pdf-viewer-next")
+                            .ghost()
+                            .xsmall()
+                            .icon(IconName::ChevronRight)
+                            .disabled(!has_next)
+                            .on_click(cx.listener(|panel, _event, _window, cx| {
+                                panel.go_to_page(panel.page + 1, cx);
+                            })),
+                    )
+                    .child(div().w_2())
+                    .child(
+                        Button::new("pdf-viewer-zoom-out")
+                            .ghost()
+                            .xsmall()
+                            .icon(IconName::Minus)
+                            .on_click(cx.listener(|panel, _event, _window, cx| {
+                                panel.change_zoom(-1, cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(zoom_label),
+                    )
+                    .child(
+                        Button::new("pdf-viewer-zoom-in")
+                            .ghost()
+                            .xsmall()
+                            .icon(IconName::Plus)
+                            .on_click(cx.listener(|panel, _event, _window, cx| {
+                                panel.change_zoom(1, cx);
+                            })),
+                    )
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("pdf-viewer-text")
+                            .ghost()
+                            .xsmall()
+                            .label(if self.show_text {
+                                t("pdf-show-page")
+                            } else {
+                                t("pdf-show-text")
+                            })
+                            .on_click(cx.listener(|panel, _event, _window, cx| {
+                                panel.toggle_text(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("pdf-viewer-ask")
+                            .primary()
+                            .xsmall()
+                            .label(t("pdf-ask-page"))
+                            .disabled(self.rendered.is_none())
+                            .on_click(cx.listener(|panel, _event, _window, cx| {
+                                panel.ask_about_page(cx);
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .id("pdf-viewer-page")
+                    .flex_1()
+                    .min_h_0()
+                    .overflow_scroll()
+                    .p_3()
+                    .when_some(self.error.clone(), |this, error| {
+                        this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                    })
+                    .when(self.error.is_none() && self.show_text, |this| {
+                        if page_text.is_empty() {
+                            this.child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(t("pdf-no-text")),
+                            )
+                        } else {
+                            this.child(
+                                div()
+                                    .text_sm()
+                                    .child(SelectableText::new("pdf-viewer-page-text", page_text)),
+                            )
+                        }
+                    })
+                    .when(
+                        self.error.is_none() && !self.show_text,
+                        |this| match &self.rendered {
+                            Some(rendered) => this.child(
+                                img(rendered.image.clone())
+                                    .w(px(display_width))
+                                    .flex_shrink_0()
+                                    .border_1()
+                                    .border_color(cx.theme().border)
+                                    .opacity(if self.loading { 0.5 } else { 1.0 }),
+                            ),
+                            None => this.child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(t("pdf-rendering")),
+                            ),
+                        },
+                    ),
+            )
+    }
+}
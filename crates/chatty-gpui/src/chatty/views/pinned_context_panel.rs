@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use crate::i18n::t;
+use chatty_core::models::PinnedItem;
+use gpui::prelude::*;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme, Sizable};
+
+pub type ToggleCallback = Arc<dyn Fn(&mut App) + Send + Sync>;
+pub type UnpinCallback = Arc<dyn Fn(String, &mut App) + Send + Sync>;
+
+/// Collapsible "context drawer" listing the items pinned to the active
+/// conversation. Shown above the chat input whenever at least one item is pinned.
+#[derive(IntoElement)]
+pub struct PinnedContextPanel {
+    items: Vec<PinnedItem>,
+    collapsed: bool,
+    on_toggle: Option<ToggleCallback>,
+    on_unpin: Option<UnpinCallback>,
+}
+
+impl PinnedContextPanel {
+    pub fn new(items: Vec<PinnedItem>, collapsed: bool) -> Self {
+        Self {
+            items,
+            collapsed,
+            on_toggle: None,
+            on_unpin: None,
+        }
+    }
+
+    pub fn on_toggle<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut App) + Send + Sync + 'static,
+    {
+        self.on_toggle = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn on_unpin<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(String, &mut App) + Send + Sync + 'static,
+    {
+        self.on_unpin = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl RenderOnce for PinnedContextPanel {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let chevron = if self.collapsed { ">" } else { "v" };
+        let summary = match self.items.len() {
+            1 => "1 item".to_string(),
+            n => format!("{n} items"),
+        };
+        let total_chars: usize = self.items.iter().map(|item| item.content.len()).sum();
+        let on_toggle = self.on_toggle.clone();
+        let on_unpin = self.on_unpin.clone();
+
+        div().w_full().px_4().pb_2().child(
+            div()
+                .w_full()
+                .rounded_lg()
+                .border_1()
+                .border_color(cx.theme().border)
+                .bg(cx.theme().secondary.opacity(0.65))
+                .shadow_sm()
+                .overflow_hidden()
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .px_3()
+                        .py_2()
+                        .cursor_pointer()
+                        .hover(|style| style.bg(cx.theme().muted.opacity(0.7)))
+                        .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                            if let Some(callback) = &on_toggle {
+                                callback(cx);
+                            }
+                        })
+                        .child(
+                            div()
+                                .font_family("monospace")
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .w_4()
+                                .child(chevron),
+                        )
+                        .child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .flex_1()
+                                .min_w_0()
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .font_weight(FontWeight::SEMIBOLD)
+                                        .text_color(cx.theme().foreground)
+                                        .child(t("pinned-title")),
+                                )
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(format!("{total_chars} chars sent every turn")),
+                                ),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .px_2()
+                                .py(px(1.0))
+                                .rounded_full()
+                                .bg(cx.theme().background)
+                                .border_1()
+                                .border_color(cx.theme().border)
+                                .text_color(cx.theme().muted_foreground)
+                                .child(summary),
+                        ),
+                )
+                .when(!self.collapsed, |panel| {
+                    panel.child(
+                        div().px_3().pb_3().flex().flex_col().gap_1().children(
+                            self.items
+                                .iter()
+                                .map(|item| render_pinned_row(item, on_unpin.clone(), cx)),
+                        ),
+                    )
+                }),
+        )
+    }
+}
+
+fn render_pinned_row(item: &PinnedItem, on_unpin: Option<UnpinCallback>, cx: &App) -> Div {
+    let preview: String = item
+        .content
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or_default()
+        .chars()
+        .take(120)
+        .collect();
+    let item_id = item.id.clone();
+
+    div()
+        .flex()
+        .items_start()
+        .gap_2()
+        .rounded_md()
+        .px_2()
+        .py_1()
+        .bg(cx.theme().background.opacity(0.35))
+        .child(
+            div()
+                .mt(px(1.0))
+                .text_xs()
+                .px_1()
+                .rounded_sm()
+                .border_1()
+                .border_color(cx.theme().border)
+                .text_color(cx.theme().muted_foreground)
+                .child(item.kind.label()),
+        )
+        .child(
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .flex_1()
+                .min_w_0()
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().foreground)
+                        .child(item.label.clone()),
+                )
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .overflow_hidden()
+                        .whitespace_nowrap()
+                        .text_ellipsis()
+                        .child(preview),
+                ),
+        )
+        .child(
+            Button::new(ElementId::Name(format!("unpin-{}", item.id).into()))
+                .ghost()
+                .xsmall()
+                .label(t("pinned-unpin"))
+                .on_click(move |_event, _window, cx| {
+                    if let Some(callback) = &on_unpin {
+                        callback(item_id.clone(), cx);
+                    }
+                }),
+        )
+}
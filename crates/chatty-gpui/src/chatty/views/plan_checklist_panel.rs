@@ -0,0 +1,255 @@
+use std::sync::Arc;
+
+use crate::assets::CustomIcon;
+use crate::i18n::t;
+use chatty_core::tools::{ExecutionPlan, PlanStep, PlanStepStatus};
+use gpui::prelude::*;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme, Icon, Sizable};
+
+pub type ToggleCallback = Arc<dyn Fn(&mut App) + Send + Sync>;
+pub type DismissCallback = Arc<dyn Fn(&mut App) + Send + Sync>;
+
+/// Live checklist for the planner-mode plan of the active conversation.
+/// Updated whenever the agent calls `create_plan` or `update_plan`.
+#[derive(IntoElement)]
+pub struct PlanChecklistPanel {
+    plan: ExecutionPlan,
+    collapsed: bool,
+    on_toggle: Option<ToggleCallback>,
+    on_dismiss: Option<DismissCallback>,
+}
+
+impl PlanChecklistPanel {
+    pub fn new(plan: ExecutionPlan, collapsed: bool) -> Self {
+        Self {
+            plan,
+            collapsed,
+            on_toggle: None,
+            on_dismiss: None,
+        }
+    }
+
+    pub fn on_toggle<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut App) + Send + Sync + 'static,
+    {
+        self.on_toggle = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn on_dismiss<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut App) + Send + Sync + 'static,
+    {
+        self.on_dismiss = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl RenderOnce for PlanChecklistPanel {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let chevron = if self.collapsed { ">" } else { "v" };
+        let title = if self.plan.is_complete() {
+            "Plan complete"
+        } else {
+            "Plan"
+        };
+        let summary = format!(
+            "{}/{} finished",
+            self.plan.finished_count(),
+            self.plan.steps.len()
+        );
+        let on_toggle = self.on_toggle.clone();
+        let on_dismiss = self.on_dismiss.clone();
+
+        div().w_full().px_4().pb_2().child(
+            div()
+                .w_full()
+                .rounded_lg()
+                .border_1()
+                .border_color(cx.theme().border)
+                .bg(cx.theme().secondary.opacity(0.65))
+                .shadow_sm()
+                .overflow_hidden()
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .px_3()
+                        .py_2()
+                        .cursor_pointer()
+                        .hover(|style| style.bg(cx.theme().muted.opacity(0.7)))
+                        .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                            if let Some(callback) = &on_toggle {
+                                callback(cx);
+                            }
+                        })
+                        .child(
+                            div()
+                                .font_family("monospace")
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .w_4()
+                                .child(chevron),
+                        )
+                        .child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .flex_1()
+                                .min_w_0()
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .font_weight(FontWeight::SEMIBOLD)
+                                        .text_color(cx.theme().foreground)
+                                        .child(title),
+                                )
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .overflow_hidden()
+                                        .whitespace_nowrap()
+                                        .text_ellipsis()
+                                        .child(self.plan.goal.clone()),
+                                ),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .px_2()
+                                .py(px(1.0))
+                                .rounded_full()
+                                .bg(cx.theme().background)
+                                .border_1()
+                                .border_color(cx.theme().border)
+                                .text_color(cx.theme().muted_foreground)
+                                .child(summary),
+                        ),
+                )
+                .when(!self.collapsed, |panel| {
+                    panel.child(
+                        div()
+                            .px_3()
+                            .pb_3()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .children(
+                                self.plan
+                                    .steps
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(index, step)| render_step_row(index, step, cx)),
+                            )
+                            .child(
+                                div().flex().justify_end().child(
+                                    Button::new("plan-checklist-clear")
+                                        .ghost()
+                                        .xsmall()
+                                        .label(t("plan-clear"))
+                                        .on_click(move |_event, _window, cx| {
+                                            if let Some(callback) = &on_dismiss {
+                                                callback(cx);
+                                            }
+                                        }),
+                                ),
+                            ),
+                    )
+                }),
+        )
+    }
+}
+
+fn render_step_row(index: usize, step: &PlanStep, cx: &App) -> Div {
+    let (marker, color) = status_marker(step.status);
+    let title_color = if matches!(step.status, PlanStepStatus::Skipped) {
+        cx.theme().muted_foreground
+    } else {
+        cx.theme().foreground
+    };
+
+    div()
+        .flex()
+        .items_start()
+        .gap_2()
+        .rounded_md()
+        .px_2()
+        .py_1()
+        .bg(row_background(step.status, cx))
+        .child(
+            div()
+                .mt(px(1.0))
+                .w_5()
+                .h_5()
+                .flex()
+                .items_center()
+                .justify_center()
+                .child(render_status_marker(marker, color)),
+        )
+        .child(
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .flex_1()
+                .min_w_0()
+                .child(div().text_sm().text_color(title_color).child(format!(
+                    "{}. {}",
+                    index + 1,
+                    step.title
+                )))
+                .when_some(step.note.clone(), |this, note| {
+                    this.child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(note),
+                    )
+                }),
+        )
+}
+
+#[derive(Clone, Copy)]
+enum StatusMarker {
+    Icon(CustomIcon),
+    Text(&'static str),
+}
+
+fn render_status_marker(marker: StatusMarker, color: Rgba) -> AnyElement {
+    match marker {
+        StatusMarker::Icon(icon) => Icon::new(icon)
+            .size(px(14.0))
+            .text_color(color)
+            .into_any_element(),
+        StatusMarker::Text(text) => div()
+            .font_family("monospace")
+            .text_xs()
+            .text_color(color)
+            .child(text)
+            .into_any_element(),
+    }
+}
+
+fn status_marker(status: PlanStepStatus) -> (StatusMarker, Rgba) {
+    match status {
+        PlanStepStatus::Pending => (StatusMarker::Icon(CustomIcon::CircleDashed), rgb(0x6b7280)),
+        PlanStepStatus::InProgress => (StatusMarker::Icon(CustomIcon::Loader), rgb(0x2563eb)),
+        PlanStepStatus::Done => (StatusMarker::Icon(CustomIcon::CircleDot), rgb(0x16a34a)),
+        PlanStepStatus::Skipped => (StatusMarker::Text("[-]"), rgb(0x6b7280)),
+        PlanStepStatus::Failed => (StatusMarker::Text("[!]"), rgb(0xdc2626)),
+    }
+}
+
+fn row_background(status: PlanStepStatus, cx: &App) -> Hsla {
+    match status {
+        PlanStepStatus::InProgress => cx.theme().accent.opacity(0.16),
+        PlanStepStatus::Failed => cx.theme().ring.opacity(0.12),
+        _ => cx.theme().background.opacity(0.35),
+    }
+}
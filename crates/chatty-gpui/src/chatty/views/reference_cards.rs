@@ -0,0 +1,118 @@
+//! Reference cards for `@citekey` citations in assistant messages.
+//!
+//! Keys known to the citation service (from the BibTeX library or Zotero
+//! results) are rendered as a row of chips below the message. Hovering a chip
+//! shows the full reference; clicking opens its DOI or URL.
+
+use crate::i18n::t;
+use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use chatty_core::services::citation_service::{Reference, cited_references};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme;
+use gpui_component::tooltip::Tooltip;
+
+/// Width of the hover card.
+const CARD_WIDTH: f32 = 420.;
+
+/// Render the reference row for `content`, or `None` when it cites nothing known.
+pub(super) fn render_reference_cards(content: &str, index: usize, cx: &App) -> Option<Div> {
+    let references = cited_references(content);
+    if references.is_empty() {
+        return None;
+    }
+    let style = cx
+        .try_global::<ExecutionSettingsModel>()
+        .map(|s| s.citation_style)
+        .unwrap_or_default();
+
+    let chips = references.into_iter().enumerate().map(|(ix, reference)| {
+        let formatted = reference.format(style);
+        let link = reference.link();
+        let card_reference = reference.clone();
+        div()
+            .id(ElementId::Name(format!("ref-{}-{}", index, ix).into()))
+            .px_2()
+            .py_0p5()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .hover(|s| s.bg(cx.theme().secondary))
+            .when(link.is_some(), |this| this.cursor_pointer())
+            .child(reference.in_text())
+            .tooltip(move |window, cx| {
+                let reference = card_reference.clone();
+                let formatted = formatted.clone();
+                Tooltip::element(move |_, cx| render_card(&reference, &formatted, cx))
+                    .build(window, cx)
+            })
+            .when_some(link, |this, link| {
+                this.on_click(move |_, _, cx| cx.open_url(&link))
+            })
+    });
+
+    Some(
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_1()
+            .pt_2()
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(t("message-references")),
+            )
+            .children(chips),
+    )
+}
+
+/// Hover card body: title, authors, venue and the formatted entry.
+fn render_card(reference: &Reference, formatted: &str, cx: &App) -> Div {
+    let venue = [reference.container.as_deref(), reference.year.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+    div()
+        .w(px(CARD_WIDTH))
+        .flex()
+        .flex_col()
+        .gap_1()
+        .p_1()
+        .child(
+            div()
+                .text_sm()
+                .font_weight(FontWeight::SEMIBOLD)
+                .child(reference.title.clone()),
+        )
+        .when(!reference.authors.is_empty(), |this| {
+            this.child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(reference.authors.join("; ")),
+            )
+        })
+        .when(!venue.is_empty(), |this| {
+            this.child(
+                div()
+                    .text_xs()
+                    .italic()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(venue),
+            )
+        })
+        .child(div().pt_1().text_xs().child(formatted.replace('*', "")))
+        .child(
+            div()
+                .text_xs()
+                .font_family("monospace")
+                .text_color(cx.theme().muted_foreground)
+                .child(format!("@{}", reference.key)),
+        )
+}
@@ -1,4 +1,7 @@
-use chatty_core::models::ConversationsStore;
+use std::time::Duration;
+
+use chatty_core::models::{ConversationMatch, ConversationsStore};
+use chatty_core::repositories::MessageSearchHit;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{
@@ -7,23 +10,36 @@ use gpui_component::{
     scroll::ScrollableElement,
     v_flex,
 };
+use tracing::warn;
 
 use crate::chatty::controllers::GlobalChattyApp;
-use crate::chatty::views::sidebar_view::SidebarEvent;
+use crate::i18n::t;
+
+/// Pause after the last keystroke before message contents are searched.
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Most matching messages fetched per search.
+const SEARCH_LIMIT: usize = 50;
 
 /// Stateful view inside the search-conversations dialog. Holds the input,
-/// a lowercase query string, and a snapshot of all conversation metadata
-/// taken when the dialog was opened (sorted most-recent-first).
+/// the query, a snapshot of all conversation metadata taken when the dialog
+/// was opened (sorted most-recent-first), and the messages matching the query.
 pub struct SearchConversationsView {
     input: Entity<InputState>,
     query: String,
     all: Vec<(String, String, Option<f64>)>,
+    /// Matching messages from the full-text index, for `query`
+    hits: Vec<MessageSearchHit>,
+    /// Pending message search; replacing it cancels the previous one
+    search_task: Option<Task<()>>,
     _sub: Subscription,
 }
 
 impl SearchConversationsView {
     pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
-        let input = cx.new(|cx| InputState::new(window, cx).placeholder("Search conversations..."));
+        let input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder(t("search-conversations-placeholder"))
+        });
 
         let store = cx.global::<ConversationsStore>();
         let count = store.count();
@@ -31,7 +47,8 @@ impl SearchConversationsView {
 
         let _sub = cx.subscribe(&input, |this, input, event: &InputEvent, cx| {
             if let InputEvent::Change = event {
-                this.query = input.read(cx).value().to_string().to_lowercase();
+                this.query = input.read(cx).value().trim().to_string();
+                this.search_messages(cx);
                 cx.notify();
             }
         });
@@ -40,23 +57,72 @@ impl SearchConversationsView {
             input,
             query: String::new(),
             all,
+            hits: Vec::new(),
+            search_task: None,
             _sub,
         }
     }
+
+    /// Search message contents for the query once typing pauses. Title
+    /// matches show right away; message matches follow when this finishes.
+    fn search_messages(&mut self, cx: &mut Context<Self>) {
+        self.hits.clear();
+        let Some(repo) = cx
+            .try_global::<GlobalChattyApp>()
+            .and_then(|g| g.try_upgrade())
+            .map(|app| app.read(cx).conversation_repo())
+        else {
+            return;
+        };
+        if self.query.is_empty() {
+            self.search_task = None;
+            return;
+        }
+
+        let query = self.query.clone();
+        self.search_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(SEARCH_DEBOUNCE).await;
+            let hits = match repo.search_messages(&query, SEARCH_LIMIT).await {
+                Ok(hits) => hits,
+                Err(e) => {
+                    warn!(error = ?e, "Failed to search conversation messages");
+                    return;
+                }
+            };
+            this.update(cx, |this, cx| {
+                this.hits = hits;
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
 }
 
 impl Render for SearchConversationsView {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let query = self.query.clone();
-        let filtered: Vec<(String, String, Option<f64>)> = self
-            .all
-            .iter()
-            .filter(|(_, title, _)| query.is_empty() || title.to_lowercase().contains(&query))
-            .cloned()
-            .collect();
-
-        let is_empty = filtered.is_empty();
-        let empty_label = if query.is_empty() {
+        let results: Vec<(ConversationMatch, Option<f64>)> = if self.query.is_empty() {
+            self.all
+                .iter()
+                .map(|(id, title, cost)| {
+                    let result = ConversationMatch {
+                        id: id.clone(),
+                        title: title.clone(),
+                        history_index: None,
+                        snippet: None,
+                    };
+                    (result, *cost)
+                })
+                .collect()
+        } else {
+            cx.global::<ConversationsStore>()
+                .search(&self.query, &self.hits)
+                .into_iter()
+                .map(|result| (result, None))
+                .collect()
+        };
+
+        let is_empty = results.is_empty();
+        let empty_label = if self.query.is_empty() {
             "No conversations."
         } else {
             "No matches."
@@ -82,57 +148,63 @@ impl Render for SearchConversationsView {
                                 .child(empty_label),
                         )
                     })
-                    .children(
-                        filtered
-                            .into_iter()
-                            .enumerate()
-                            .map(|(ix, (id, title, cost))| {
-                                let id_for_click = id.clone();
-                                div()
-                                    .id(ix)
-                                    .px_3()
-                                    .py_2()
-                                    .rounded_md()
-                                    .cursor_pointer()
-                                    .hover(|style| style.bg(cx.theme().secondary))
+                    .children(results.into_iter().enumerate().map(|(ix, (result, cost))| {
+                        let ConversationMatch {
+                            id,
+                            title,
+                            history_index,
+                            snippet,
+                        } = result;
+                        v_flex()
+                            .id(ix)
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(cx.theme().secondary))
+                            .child(
+                                h_flex()
+                                    .w_full()
+                                    .items_center()
+                                    .justify_between()
+                                    .gap_2()
                                     .child(
-                                        h_flex()
-                                            .w_full()
-                                            .items_center()
-                                            .justify_between()
-                                            .gap_2()
-                                            .child(
-                                                div()
-                                                    .flex_1()
-                                                    .text_sm()
-                                                    .text_color(cx.theme().foreground)
-                                                    .child(title),
-                                            )
-                                            .when_some(cost, |this, c| {
-                                                this.child(
-                                                    div()
-                                                        .text_xs()
-                                                        .text_color(cx.theme().muted_foreground)
-                                                        .child(format!("${c:.2}")),
-                                                )
-                                            }),
+                                        div()
+                                            .flex_1()
+                                            .text_sm()
+                                            .text_color(cx.theme().foreground)
+                                            .child(title),
                                     )
-                                    .on_mouse_down(MouseButton::Left, move |_, window, cx| {
-                                        if let Some(app) = cx
-                                            .try_global::<GlobalChattyApp>()
-                                            .and_then(|g| g.try_upgrade())
-                                        {
-                                            let id = id_for_click.clone();
-                                            app.update(cx, |app, cx| {
-                                                app.sidebar_view.update(cx, |_, cx| {
-                                                    cx.emit(SidebarEvent::SelectConversation(id));
-                                                });
-                                            });
-                                        }
-                                        window.close_dialog(cx);
-                                    })
-                            }),
-                    ),
+                                    .when_some(cost, |this, c| {
+                                        this.child(
+                                            div()
+                                                .text_xs()
+                                                .text_color(cx.theme().muted_foreground)
+                                                .child(format!("${c:.2}")),
+                                        )
+                                    }),
+                            )
+                            .when_some(snippet, |this, snippet| {
+                                this.child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .line_clamp(2)
+                                        .child(snippet),
+                                )
+                            })
+                            .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                                if let Some(app) = cx
+                                    .try_global::<GlobalChattyApp>()
+                                    .and_then(|g| g.try_upgrade())
+                                {
+                                    app.update(cx, |app, cx| {
+                                        app.open_search_result(&id, history_index, cx);
+                                    });
+                                }
+                                window.close_dialog(cx);
+                            })
+                    })),
             )
     }
 }
@@ -141,7 +213,8 @@ impl Render for SearchConversationsView {
 ///
 /// Mirrors the pattern used by [`super::ErrorLogDialog::open`]: creates a
 /// stateful view entity and embeds it as the dialog's child so that typing
-/// filters the list live without rebuilding the dialog.
+/// filters the list live without rebuilding the dialog. Titles and message
+/// contents are both searched; picking a message match jumps to it.
 pub struct SearchConversationsDialog;
 
 impl SearchConversationsDialog {
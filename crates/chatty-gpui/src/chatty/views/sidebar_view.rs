@@ -1,10 +1,23 @@
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{
-    ActiveTheme, Collapsible, Icon, IconName, Sizable, button::Button, h_flex, v_flex,
+    ActiveTheme, Collapsible, Icon, IconName, Selectable, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    menu::{DropdownMenu, PopupMenuItem},
+    v_flex,
 };
 
+use super::app_view::is_compact_layout;
 use super::conversation_item::ConversationItem;
+use super::conversation_tags_dialog::open_tags_dialog;
+use super::export_conversation_dialog::open_export_dialog;
+use crate::chatty::models::ConversationsStore;
+use crate::i18n::{t, t_args};
+use chatty_core::exporters::document_exporter::DocumentFormat;
+use chatty_core::models::conversation_groups::{DateGroup, keep_positions, relative_time};
+use chatty_core::models::{ConversationLabel, ConversationSort, LabelColor};
+use std::collections::{HashMap, HashSet};
 
 /// Events emitted by SidebarView for entity-to-entity communication
 #[derive(Clone, Debug)]
@@ -13,9 +26,25 @@ pub enum SidebarEvent {
     OpenSettings,
     SelectConversation(String),
     DeleteConversation(String),
-    ExportConversation(String),
+    /// Save a conversation as a document in the chosen format
+    ExportConversation(String, DocumentFormat),
+    PrintConversation(String),
+    /// Import a ChatGPT or Claude conversations export
+    ImportConversations,
+    SetLabel(String, ConversationLabel),
+    /// Lock (true) or unlock a conversation
+    SetLocked(String, bool),
+    /// Replace the tags of a conversation
+    SetTags(String, Vec<String>),
+    /// Add a tag to each of these conversations
+    AddTag(Vec<String>, String),
+    /// Remove a tag from each of these conversations
+    RemoveTag(Vec<String>, String),
+    /// Move these conversations to a folder (None = no folder)
+    MoveToFolder(Vec<String>, Option<String>),
     ToggleCollapsed(bool),
     LoadMore,
+    SetSort(ConversationSort),
 }
 
 impl EventEmitter<SidebarEvent> for SidebarView {}
@@ -25,6 +54,23 @@ pub struct SidebarView {
     conversations: Vec<(String, String, Option<f64>)>, // (id, title, cost)
     active_conversation_id: Option<String>,
     is_collapsed: bool,
+    /// Conversations whose response finished while another one was open
+    unread: HashSet<String>,
+    /// Only list unread conversations
+    unread_only: bool,
+    /// Time each conversation is grouped by under the current sort; frozen
+    /// while it streams
+    group_times: HashMap<String, i64>,
+    /// Only list conversations with this label color
+    color_filter: Option<LabelColor>,
+    /// Only list conversations with this tag. Tag and folder filters search
+    /// all conversations, not just the loaded page.
+    tag_filter: Option<String>,
+    /// Only list conversations in this folder or its subfolders
+    folder_filter: Option<String>,
+    /// Only list conversations with messages on one day, picked in the
+    /// activity calendar: (day label, conversation IDs)
+    day_filter: Option<(String, HashSet<String>)>,
     // OPTIMIZATION: Pagination for sidebar
     visible_limit: usize, // How many conversations to show (starts at 20)
     total_count: usize,   // Total available conversations
@@ -36,6 +82,13 @@ impl SidebarView {
             conversations: Vec::new(),
             active_conversation_id: None,
             is_collapsed: false,
+            unread: HashSet::new(),
+            unread_only: false,
+            group_times: HashMap::new(),
+            color_filter: None,
+            tag_filter: None,
+            folder_filter: None,
+            day_filter: None,
             visible_limit: 20, // Start with 20 conversations
             total_count: 0,
         }
@@ -55,16 +108,105 @@ impl SidebarView {
         for (id, title, cost) in &conversations {
             tracing::debug!(id = %id, title = %title, cost = ?cost, "  - Conversation");
         }
+
+        // Conversations with a running stream keep their place and date group
+        // until the stream ends, so the list doesn't jump on every save.
+        let store = cx.try_global::<ConversationsStore>();
+        let is_streaming = |id: &str| store.is_some_and(|store| store.is_streaming(id));
+        let previous: Vec<String> = self
+            .conversations
+            .iter()
+            .map(|(id, _, _)| id.clone())
+            .collect();
+        let conversations = keep_positions(
+            &previous,
+            conversations,
+            |(id, _, _)| id.as_str(),
+            is_streaming,
+        );
+        for (id, _, _) in &conversations {
+            if is_streaming(id) && self.group_times.contains_key(id) {
+                continue;
+            }
+            if let Some(sort_time) = store.and_then(|store| store.sort_time(id)) {
+                self.group_times.insert(id.clone(), sort_time);
+            }
+        }
+
         self.conversations = conversations;
         cx.notify();
     }
 
-    /// Set the active conversation
+    /// Set the active conversation. Opening a conversation marks it read.
     pub fn set_active_conversation(&mut self, id: Option<String>, cx: &mut Context<Self>) {
+        if let Some(id) = &id {
+            self.unread.remove(id);
+        }
         self.active_conversation_id = id;
         cx.notify();
     }
 
+    /// Mark a conversation unread, unless it is the one currently open
+    pub fn mark_unread(&mut self, id: &str, cx: &mut Context<Self>) {
+        if self.active_conversation_id.as_deref() != Some(id) {
+            self.unread.insert(id.to_string());
+            cx.notify();
+        }
+    }
+
+    pub fn mark_read(&mut self, id: &str, cx: &mut Context<Self>) {
+        if self.unread.remove(id) {
+            cx.notify();
+        }
+    }
+
+    pub fn mark_all_read(&mut self, cx: &mut Context<Self>) {
+        self.unread.clear();
+        self.unread_only = false;
+        cx.notify();
+    }
+
+    pub fn toggle_unread_only(&mut self, cx: &mut Context<Self>) {
+        self.unread_only = !self.unread_only;
+        cx.notify();
+    }
+
+    /// Filter the list by label color; selecting the active color clears it
+    pub fn toggle_color_filter(&mut self, color: LabelColor, cx: &mut Context<Self>) {
+        self.color_filter = (self.color_filter != Some(color)).then_some(color);
+        cx.notify();
+    }
+
+    pub fn set_tag_filter(&mut self, tag: Option<String>, cx: &mut Context<Self>) {
+        self.tag_filter = tag;
+        cx.notify();
+    }
+
+    pub fn set_folder_filter(&mut self, folder: Option<String>, cx: &mut Context<Self>) {
+        self.folder_filter = folder;
+        cx.notify();
+    }
+
+    /// Filter the list to the conversations active on a day, given as
+    /// (day label, conversation IDs); `None` clears it.
+    pub fn set_day_filter(&mut self, day: Option<(String, Vec<String>)>, cx: &mut Context<Self>) {
+        self.day_filter = day.map(|(label, ids)| (label, ids.into_iter().collect()));
+        if self.day_filter.is_some() && self.is_collapsed {
+            self.toggle_collapsed(cx);
+        }
+        cx.notify();
+    }
+
+    /// Whether the list comes from the tag, folder or day filter instead of
+    /// the paginated page
+    fn is_organized(&self) -> bool {
+        self.tag_filter.is_some() || self.folder_filter.is_some() || self.day_filter.is_some()
+    }
+
+    pub fn active_conversation_id(&self) -> Option<&String> {
+        self.active_conversation_id.as_ref()
+    }
+
     /// Toggle the collapsed state of the sidebar
     pub fn toggle_collapsed(&mut self, cx: &mut Context<Self>) {
         self.is_collapsed = !self.is_collapsed;
@@ -73,7 +215,6 @@ impl SidebarView {
     }
 
     /// Set the collapsed state of the sidebar
-    #[allow(dead_code)]
     pub fn set_collapsed(&mut self, collapsed: bool, cx: &mut Context<Self>) {
         self.is_collapsed = collapsed;
         cx.notify();
@@ -102,19 +243,209 @@ impl SidebarView {
     }
 }
 
+/// Number of conversations under "Continue where you left off".
+const CONTINUE_LIMIT: usize = 3;
+
+fn folder_label(folder: Option<&str>) -> String {
+    folder.map_or_else(|| t("sidebar-all-folders"), |folder| format!("📁 {folder}"))
+}
+
+fn tag_label(tag: Option<&str>) -> String {
+    tag.map_or_else(|| t("sidebar-all-tags"), |tag| format!("#{tag}"))
+}
+
+fn sort_label(sort: ConversationSort) -> String {
+    match sort {
+        ConversationSort::Updated => t("sidebar-sort-updated"),
+        ConversationSort::Viewed => t("sidebar-sort-viewed"),
+        ConversationSort::Created => t("sidebar-sort-created"),
+    }
+}
+
+impl SidebarView {
+    /// Narrow column of icon buttons shown instead of the list in the
+    /// compact layout.
+    fn render_icon_rail(&self, cx: &mut Context<Self>) -> AnyElement {
+        let sidebar_entity = cx.entity().clone();
+        let emit = move |event: SidebarEvent| {
+            let entity = sidebar_entity.clone();
+            move |_event: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                entity.update(cx, |_, cx| cx.emit(event.clone()));
+            }
+        };
+
+        v_flex()
+            .id("sidebar-rail")
+            .w(px(44.))
+            .flex_shrink_0()
+            .h_full()
+            .items_center()
+            .gap_2()
+            .pt_2()
+            .pb_2()
+            // Leave room for the traffic light buttons on macOS
+            .when(cfg!(target_os = "macos"), |this| this.pt(px(40.0)))
+            .bg(cx.theme().sidebar)
+            .text_color(cx.theme().sidebar_foreground)
+            .border_r_1()
+            .border_color(cx.theme().sidebar_border)
+            .child(
+                Button::new("rail-new-chat")
+                    .icon(Icon::new(IconName::Plus))
+                    .ghost()
+                    .small()
+                    .tooltip(t("sidebar-new-chat"))
+                    .on_click(emit(SidebarEvent::NewChat)),
+            )
+            .child(
+                Button::new("rail-search")
+                    .icon(Icon::new(IconName::Search))
+                    .ghost()
+                    .small()
+                    .tooltip(t("search-conversations"))
+                    .on_click(|_event, window, cx| {
+                        super::SearchConversationsDialog::open(window, cx);
+                    }),
+            )
+            .child(div().flex_1())
+            .child(
+                Button::new("rail-settings")
+                    .icon(Icon::new(IconName::Settings))
+                    .ghost()
+                    .small()
+                    .tooltip(t("sidebar-settings"))
+                    .on_click(emit(SidebarEvent::OpenSettings)),
+            )
+            .into_any_element()
+    }
+}
+
 impl Render for SidebarView {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         tracing::debug!(
             count = self.conversations.len(),
             "SidebarView: render called with {} conversations",
             self.conversations.len()
         );
 
+        // A narrow window keeps only the icon rail
+        if !self.is_collapsed && is_compact_layout(window) {
+            return self.render_icon_rail(cx);
+        }
+
         let sidebar_entity = cx.entity().clone();
         let active_id = self.active_conversation_id.clone();
 
         let width = if self.is_collapsed { px(0.) } else { px(255.) };
 
+        let store = cx.try_global::<ConversationsStore>();
+        let label_of = |id: &str| {
+            store
+                .and_then(|store| store.label(id))
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        // A tag, folder or day filter lists every matching conversation,
+        // loaded into the page or not
+        let organized: Vec<(String, String, Option<f64>)> = if self.is_organized() {
+            store
+                .map(|store| {
+                    store.list_matching(self.tag_filter.as_deref(), self.folder_filter.as_deref())
+                })
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(id, _, _)| {
+                    self.day_filter
+                        .as_ref()
+                        .is_none_or(|(_, ids)| ids.contains(id))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let source = if self.is_organized() {
+            &organized
+        } else {
+            &self.conversations
+        };
+        let all_tags = store.map(|store| store.all_tags()).unwrap_or_default();
+        let all_folders = store.map(|store| store.all_folders()).unwrap_or_default();
+
+        // Label colors in use, in picker order, for the color filter chips
+        let colors_in_use: Vec<LabelColor> = LabelColor::ALL
+            .into_iter()
+            .filter(|color| {
+                self.color_filter == Some(*color)
+                    || source
+                        .iter()
+                        .any(|(id, _, _)| label_of(id).color == Some(*color))
+            })
+            .collect();
+
+        let visible: Vec<&(String, String, Option<f64>)> = source
+            .iter()
+            .filter(|(id, _, _)| !self.unread_only || self.unread.contains(id))
+            .filter(|(id, _, _)| {
+                self.color_filter
+                    .is_none_or(|color| label_of(id).color == Some(color))
+            })
+            .collect();
+
+        // Recently opened conversations, unless the list is already in that
+        // order or filtered
+        let sort = store.map(|store| store.sort()).unwrap_or_default();
+        let continue_items = if sort != ConversationSort::Viewed
+            && !self.unread_only
+            && self.color_filter.is_none()
+            && !self.is_organized()
+        {
+            store
+                .map(|store| store.recently_viewed(CONTINUE_LIMIT, active_id.as_deref()))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        // Date group header to show above each entry (when the group changes)
+        // and the relative update time shown on hover.
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        let mut last_group = None;
+        #[allow(clippy::type_complexity)]
+        let annotations: Vec<(
+            Option<&'static str>,
+            Option<String>,
+            ConversationLabel,
+            Vec<String>,
+            bool,
+        )> = visible
+            .iter()
+            .map(|(id, _, _)| {
+                let group = self
+                    .group_times
+                    .get(id)
+                    .copied()
+                    .or_else(|| store.and_then(|store| store.sort_time(id)))
+                    .map(|updated_at| DateGroup::of(updated_at, now));
+                let header = group
+                    .filter(|group| last_group != Some(*group))
+                    .map(DateGroup::label);
+                last_group = group.or(last_group);
+                let updated = store
+                    .and_then(|store| store.updated_at(id))
+                    .map(|updated_at| format!("Updated {}", relative_time(updated_at, now)));
+                let tags = store
+                    .map(|store| store.tags(id).to_vec())
+                    .unwrap_or_default();
+                let locked = store.is_some_and(|store| store.is_locked(id));
+                (header, updated, label_of(id), tags, locked)
+            })
+            .collect();
+        let muted = cx.theme().muted_foreground;
+
         v_flex()
             .id("sidebar")
             .w(width)
@@ -140,7 +471,11 @@ impl Render for SidebarView {
                         .when(cfg!(target_os = "macos"), |this| this.pt(px(40.0)))
                         .child(
                             Button::new("new-chat")
-                                .label(if self.is_collapsed { "+" } else { "New Chat" })
+                                .label(if self.is_collapsed {
+                                    "+".to_string()
+                                } else {
+                                    t("sidebar-new-chat")
+                                })
                                 .small()
                                 .w_full()
                                 .on_click({
@@ -154,6 +489,336 @@ impl Render for SidebarView {
                         ),
                 )
             })
+            .when_some(
+                self.day_filter
+                    .as_ref()
+                    .filter(|_| !self.is_collapsed)
+                    .map(|(label, _)| label.clone()),
+                |this, label| {
+                    this.child(
+                        // Day picked in the activity calendar
+                        h_flex().id("day-filter").pt_2().px_3().child(
+                            Button::new("clear-day-filter")
+                                .label(label)
+                                .icon(IconName::Close)
+                                .xsmall()
+                                .ghost()
+                                .selected(true)
+                                .tooltip(t("sidebar-clear-day-filter"))
+                                .on_click({
+                                    let entity = sidebar_entity.clone();
+                                    move |_event, _window, cx| {
+                                        entity.update(cx, |sidebar, cx| {
+                                            sidebar.set_day_filter(None, cx);
+                                        });
+                                    }
+                                }),
+                        ),
+                    )
+                },
+            )
+            .when(
+                !self.is_collapsed && (!self.unread.is_empty() || self.unread_only),
+                |this| {
+                    this.child(
+                        // Unread filter and mark-all-read
+                        h_flex()
+                            .id("unread-actions")
+                            .pt_2()
+                            .px_3()
+                            .gap_2()
+                            .child(
+                                Button::new("unread-only")
+                                    .label(format!("Unread only ({})", self.unread.len()))
+                                    .xsmall()
+                                    .ghost()
+                                    .selected(self.unread_only)
+                                    .on_click({
+                                        let entity = sidebar_entity.clone();
+                                        move |_event, _window, cx| {
+                                            entity.update(cx, |sidebar, cx| {
+                                                sidebar.toggle_unread_only(cx);
+                                            });
+                                        }
+                                    }),
+                            )
+                            .child(
+                                Button::new("mark-all-read")
+                                    .label(t("sidebar-mark-all-read"))
+                                    .xsmall()
+                                    .ghost()
+                                    .on_click({
+                                        let entity = sidebar_entity.clone();
+                                        move |_event, _window, cx| {
+                                            entity.update(cx, |sidebar, cx| {
+                                                sidebar.mark_all_read(cx);
+                                            });
+                                        }
+                                    }),
+                            ),
+                    )
+                },
+            )
+            .when(!self.is_collapsed && !colors_in_use.is_empty(), |this| {
+                this.child(
+                    // Label color filter
+                    h_flex()
+                        .id("color-filter")
+                        .pt_2()
+                        .px_3()
+                        .gap_2()
+                        .items_center()
+                        .child(div().text_xs().text_color(muted).child(t("sidebar-labels")))
+                        .children(colors_in_use.iter().map(|&color| {
+                            let selected = self.color_filter == Some(color);
+                            div()
+                                .id(ElementId::Name(
+                                    format!("color-filter-{}", color.name()).into(),
+                                ))
+                                .size(px(12.))
+                                .rounded_full()
+                                .cursor_pointer()
+                                .bg(rgb(color.rgb()))
+                                .when(selected, |chip| {
+                                    chip.border_2().border_color(cx.theme().foreground)
+                                })
+                                .on_click({
+                                    let entity = sidebar_entity.clone();
+                                    move |_event, _window, cx| {
+                                        entity.update(cx, |sidebar, cx| {
+                                            sidebar.toggle_color_filter(color, cx);
+                                        });
+                                    }
+                                })
+                        })),
+                )
+            })
+            .when(!self.is_collapsed, |this| {
+                this.child(
+                    // Sidebar order and import
+                    h_flex()
+                        .id("sort")
+                        .pt_2()
+                        .px_3()
+                        .justify_between()
+                        .child(
+                        Button::new("conversation-sort")
+                            .label(sort_label(sort))
+                            .xsmall()
+                            .ghost()
+                            .dropdown_caret(true)
+                            .dropdown_menu_with_anchor(Corner::TopLeft, {
+                                let entity = sidebar_entity.clone();
+                                move |menu, _, _| {
+                                    ConversationSort::ALL.into_iter().fold(menu, |menu, option| {
+                                        let entity = entity.clone();
+                                        menu.item(
+                                            PopupMenuItem::new(sort_label(option))
+                                                .checked(sort == option)
+                                                .on_click(move |_, _, cx| {
+                                                    entity.update(cx, |_, cx| {
+                                                        cx.emit(SidebarEvent::SetSort(option));
+                                                    });
+                                                }),
+                                        )
+                                    })
+                                }
+                            }),
+                        )
+                        .child(
+                            Button::new("import-conversations")
+                                .label(t("sidebar-import"))
+                                .xsmall()
+                                .ghost()
+                                .tooltip(t("sidebar-import-tooltip"))
+                                .on_click({
+                                    let entity = sidebar_entity.clone();
+                                    move |_event, _window, cx| {
+                                        entity.update(cx, |_, cx| {
+                                            cx.emit(SidebarEvent::ImportConversations);
+                                        });
+                                    }
+                                }),
+                        ),
+                )
+            })
+            .when(
+                !self.is_collapsed
+                    && (self.is_organized() || !all_tags.is_empty() || !all_folders.is_empty()),
+                |this| {
+                    let shown_ids: Vec<String> =
+                        visible.iter().map(|(id, _, _)| id.clone()).collect();
+                    this.child(
+                        // Folder and tag filters, with bulk actions on the result
+                        h_flex()
+                            .id("organize")
+                            .pt_1()
+                            .px_3()
+                            .gap_1()
+                            .flex_wrap()
+                            .child(
+                                Button::new("folder-filter")
+                                    .label(folder_label(self.folder_filter.as_deref()))
+                                    .xsmall()
+                                    .ghost()
+                                    .selected(self.folder_filter.is_some())
+                                    .dropdown_caret(true)
+                                    .dropdown_menu_with_anchor(Corner::TopLeft, {
+                                        let entity = sidebar_entity.clone();
+                                        let current = self.folder_filter.clone();
+                                        move |menu, _, _| {
+                                            let options = std::iter::once(None)
+                                                .chain(all_folders.iter().cloned().map(Some));
+                                            options.fold(menu, |menu, option| {
+                                                let entity = entity.clone();
+                                                menu.item(
+                                                    PopupMenuItem::new(folder_label(
+                                                        option.as_deref(),
+                                                    ))
+                                                    .checked(current == option)
+                                                    .on_click(move |_, _, cx| {
+                                                        entity.update(cx, |sidebar, cx| {
+                                                            sidebar.set_folder_filter(
+                                                                option.clone(),
+                                                                cx,
+                                                            );
+                                                        });
+                                                    }),
+                                                )
+                                            })
+                                        }
+                                    }),
+                            )
+                            .child(
+                                Button::new("tag-filter")
+                                    .label(tag_label(self.tag_filter.as_deref()))
+                                    .xsmall()
+                                    .ghost()
+                                    .selected(self.tag_filter.is_some())
+                                    .dropdown_caret(true)
+                                    .dropdown_menu_with_anchor(Corner::TopLeft, {
+                                        let entity = sidebar_entity.clone();
+                                        let current = self.tag_filter.clone();
+                                        let all_ids = store
+                                            .map(|store| store.all_metadata_ids())
+                                            .unwrap_or_default();
+                                        move |menu, _, _| {
+                                            let all_option = {
+                                                let entity = entity.clone();
+                                                PopupMenuItem::new(tag_label(None))
+                                                    .checked(current.is_none())
+                                                    .on_click(move |_, _, cx| {
+                                                        entity.update(cx, |sidebar, cx| {
+                                                            sidebar.set_tag_filter(None, cx);
+                                                        });
+                                                    })
+                                            };
+                                            let menu = all_tags.iter().fold(
+                                                menu.item(all_option),
+                                                |menu, (tag, count)| {
+                                                    let entity = entity.clone();
+                                                    let tag = tag.clone();
+                                                    let checked = current
+                                                        .as_deref()
+                                                        .is_some_and(|current| current.eq_ignore_ascii_case(&tag));
+                                                    menu.item(
+                                                        PopupMenuItem::new(format!(
+                                                            "{} ({count})",
+                                                            tag_label(Some(&tag))
+                                                        ))
+                                                        .checked(checked)
+                                                        .on_click(move |_, _, cx| {
+                                                            entity.update(cx, |sidebar, cx| {
+                                                                sidebar.set_tag_filter(
+                                                                    Some(tag.clone()),
+                                                                    cx,
+                                                                );
+                                                            });
+                                                        }),
+                                                    )
+                                                },
+                                            );
+                                            match current.clone() {
+                                                Some(tag) => {
+                                                    let entity = entity.clone();
+                                                    let all_ids = all_ids.clone();
+                                                    menu.separator().item(
+                                                        PopupMenuItem::new(t("sidebar-remove-tag"))
+                                                            .on_click(move |_, _, cx| {
+                                                                entity.update(cx, |sidebar, cx| {
+                                                                    sidebar.set_tag_filter(None, cx);
+                                                                    cx.emit(SidebarEvent::RemoveTag(
+                                                                        all_ids.clone(),
+                                                                        tag.clone(),
+                                                                    ));
+                                                                });
+                                                            }),
+                                                    )
+                                                }
+                                                None => menu,
+                                            }
+                                        }
+                                    }),
+                            )
+                            .when(self.is_organized() && !shown_ids.is_empty(), |this| {
+                                this.child(
+                                    Button::new("organize-shown")
+                                        .label(t("sidebar-organize-shown"))
+                                        .xsmall()
+                                        .ghost()
+                                        .on_click({
+                                            let entity = sidebar_entity.clone();
+                                            move |_event, window, cx| {
+                                                open_tags_dialog(
+                                                    entity.clone(),
+                                                    shown_ids.clone(),
+                                                    window,
+                                                    cx,
+                                                );
+                                            }
+                                        }),
+                                )
+                            }),
+                    )
+                },
+            )
+            .when(!self.is_collapsed && !continue_items.is_empty(), |this| {
+                this.child(
+                    // Continue where you left off
+                    v_flex()
+                        .id("continue")
+                        .pt_2()
+                        .px_3()
+                        .child(
+                            div()
+                                .px_3()
+                                .pb_1()
+                                .text_xs()
+                                .text_color(muted)
+                                .child(t("sidebar-continue")),
+                        )
+                        .children(continue_items.into_iter().map(|(id, title)| {
+                            div()
+                                .id(ElementId::Name(format!("continue-{id}").into()))
+                                .px_3()
+                                .py_1()
+                                .rounded_md()
+                                .text_sm()
+                                .truncate()
+                                .cursor_pointer()
+                                .hover(|style| style.bg(cx.theme().secondary))
+                                .child(title)
+                                .on_click({
+                                    let entity = sidebar_entity.clone();
+                                    move |_event, _window, cx| {
+                                        entity.update(cx, |_, cx| {
+                                            cx.emit(SidebarEvent::SelectConversation(id.clone()));
+                                        });
+                                    }
+                                })
+                        })),
+                )
+            })
             .when(!self.is_collapsed, |this| {
                 this.child(
                     // Content: Conversation list
@@ -169,20 +834,39 @@ impl Render for SidebarView {
                                 .gap_y_1()
                                 .when(self.is_collapsed, |this| this.p_2())
                                 .children(
-                                    self.conversations
+                                    visible
                                         .iter()
                                         .enumerate()
                                         .map(|(ix, (id, title, cost))| {
                                             let is_active = active_id.as_ref() == Some(id);
+                                            let is_unread = self.unread.contains(id);
+                                            let (header, updated, label, tags, locked) =
+                                                annotations[ix].clone();
 
                                             div()
                                                 .id(ix)
+                                                .when_some(header, |this, label| {
+                                                    this.child(
+                                                        div()
+                                                            .px_3()
+                                                            .pt_2()
+                                                            .pb_1()
+                                                            .text_xs()
+                                                            .text_color(muted)
+                                                            .child(label),
+                                                    )
+                                                })
                                                 .child(
                                                     ConversationItem::new(
                                                         id.clone(),
                                                         title.clone(),
                                                     )
                                                     .active(is_active)
+                                                    .unread(is_unread)
+                                                    .updated(updated)
+                                                    .label(label)
+                                                    .tags(tags)
+                                                    .locked(locked)
                                                     .collapsed(self.is_collapsed)
                                                     .cost(*cost)
                                                     .on_click({
@@ -204,35 +888,71 @@ impl Render for SidebarView {
                                                         }
                                                     })
                                                     .on_export({
+                                                        let entity = sidebar_entity.clone();
+                                                        let id = id.clone();
+                                                        move |_conv_id, window, cx| {
+                                                            open_export_dialog(entity.clone(), id.clone(), window, cx);
+                                                        }
+                                                    })
+                                                    .on_print({
+                                                        let entity = sidebar_entity.clone();
+                                                        let id = id.clone();
+                                                        move |_conv_id, cx| {
+                                                            entity.update(cx, |_, cx| {
+                                                                cx.emit(SidebarEvent::PrintConversation(id.clone()));
+                                                            });
+                                                        }
+                                                    })
+                                                    .on_label({
+                                                        let entity = sidebar_entity.clone();
+                                                        let id = id.clone();
+                                                        move |_conv_id, label, cx| {
+                                                            entity.update(cx, |_, cx| {
+                                                                cx.emit(SidebarEvent::SetLabel(id.clone(), label));
+                                                            });
+                                                        }
+                                                    })
+                                                    .on_tags({
+                                                        let entity = sidebar_entity.clone();
+                                                        let id = id.clone();
+                                                        move |_conv_id, window, cx| {
+                                                            open_tags_dialog(entity.clone(), vec![id.clone()], window, cx);
+                                                        }
+                                                    })
+                                                    .on_lock({
                                                         let entity = sidebar_entity.clone();
                                                         let id = id.clone();
                                                         move |_conv_id, cx| {
                                                             entity.update(cx, |_, cx| {
-                                                                cx.emit(SidebarEvent::ExportConversation(id.clone()));
+                                                                cx.emit(SidebarEvent::SetLocked(id.clone(), !locked));
                                                             });
                                                         }
                                                     }),
                                                 )
                                                 .when(ix == 0, |this| this.mt_3())
                                                 .when(
-                                                    ix == self
-                                                        .conversations
-                                                        .len()
-                                                        .saturating_sub(1),
+                                                    ix == visible.len().saturating_sub(1),
                                                     |this| this.mb_3(),
                                                 )
                                         })
                                         .collect::<Vec<_>>(),
                                 )
                                 // OPTIMIZATION: "Load More" button for pagination
-                                .when(self.conversations.len() < self.total_count, |this| {
+                                .when(
+                                    !self.unread_only
+                                        && self.color_filter.is_none()
+                                        && !self.is_organized()
+                                        && self.conversations.len() < self.total_count,
+                                    |this| {
                                     this.child(
                                         div().px_3().py_2().child(
                                             Button::new("load-more-conversations")
-                                                .label(format!(
-                                                    "Load 20 more... ({}/{})",
-                                                    self.conversations.len(),
-                                                    self.total_count
+                                                .label(t_args(
+                                                    "sidebar-load-more",
+                                                    &[
+                                                        ("loaded", self.conversations.len().to_string().as_str()),
+                                                        ("total", self.total_count.to_string().as_str()),
+                                                    ],
                                                 ))
                                                 .small()
                                                 .w_full()
@@ -263,7 +983,11 @@ impl Render for SidebarView {
                         .child(
                             Button::new("settings")
                                 .icon(Icon::new(IconName::Settings))
-                                .label(if self.is_collapsed { "" } else { "Settings" })
+                                .label(if self.is_collapsed {
+                                    String::new()
+                                } else {
+                                    t("sidebar-settings")
+                                })
                                 .small()
                                 .w_full()
                                 .on_click({
@@ -277,5 +1001,6 @@ impl Render for SidebarView {
                         ),
                 )
             })
+            .into_any_element()
     }
 }
@@ -1,6 +1,10 @@
 use super::SidebarView;
+use crate::i18n::t;
 use gpui::*;
 
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+use gpui::prelude::FluentBuilder;
+
 #[cfg(any(target_os = "linux", target_os = "windows"))]
 use gpui_component::{Icon, IconName, Sizable, TitleBar, button::Button, h_flex, menu::AppMenuBar};
 
@@ -31,6 +35,17 @@ impl RenderOnce for AppTitleBar {
         let is_collapsed = sidebar.read(cx).is_collapsed();
         let app_menu_bar = AppMenuBar::new(window, cx);
 
+        // Label of the open conversation, shown next to the search button
+        let label = sidebar
+            .read(cx)
+            .active_conversation_id()
+            .and_then(|id| {
+                cx.try_global::<crate::chatty::models::ConversationsStore>()
+                    .and_then(|store| store.label(id))
+            })
+            .filter(|label| !label.is_empty())
+            .cloned();
+
         h_flex()
             .w_full()
             .child(
@@ -56,11 +71,25 @@ impl RenderOnce for AppTitleBar {
                     .icon(Icon::new(IconName::Search))
                     .label("")
                     .small()
-                    .tooltip("Search conversations")
+                    .tooltip(t("search-conversations"))
                     .on_click(|_event, window, cx| {
                         super::SearchConversationsDialog::open(window, cx);
                     }),
             )
+            .when_some(label, |this, label| {
+                this.child(
+                    h_flex()
+                        .px_2()
+                        .gap_1()
+                        .items_center()
+                        .when_some(label.color, |badge, color| {
+                            badge.child(div().size(px(8.)).rounded_full().bg(rgb(color.rgb())))
+                        })
+                        .when_some(label.icon, |badge, icon| {
+                            badge.child(div().text_sm().child(icon))
+                        }),
+                )
+            })
             .child(
                 div()
                     .flex_1()
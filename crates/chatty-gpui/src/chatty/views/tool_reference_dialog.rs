@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+
+use chatty_core::services::tool_reference::{self, PermissionClass, ToolReference};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, WindowExt, h_flex,
+    input::{Input, InputEvent, InputState},
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use super::code_block_component::CodeBlockComponent;
+use crate::i18n::t;
+
+/// Stateful view inside the tool reference dialog. Holds the search input,
+/// a snapshot of the recorded tool definitions taken when the dialog was
+/// opened, and which entries are expanded to show their schema.
+pub struct ToolReferenceView {
+    input: Entity<InputState>,
+    query: String,
+    tools: Vec<ToolReference>,
+    expanded: HashSet<String>,
+    _sub: Subscription,
+}
+
+impl ToolReferenceView {
+    /// `focus` names a tool to show expanded and filtered to, e.g. the tool
+    /// of the trace the dialog was opened from.
+    pub fn new(focus: Option<String>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let query = focus.clone().unwrap_or_default();
+        let input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(t("tool-reference-search-placeholder"))
+                .default_value(query.clone())
+        });
+
+        let _sub = cx.subscribe(&input, |this, input, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                this.query = input.read(cx).value().trim().to_string();
+                cx.notify();
+            }
+        });
+
+        Self {
+            input,
+            query,
+            tools: tool_reference::all(),
+            expanded: focus.into_iter().collect(),
+            _sub,
+        }
+    }
+
+    fn toggle(&mut self, name: &str, cx: &mut Context<Self>) {
+        if !self.expanded.remove(name) {
+            self.expanded.insert(name.to_string());
+        }
+        cx.notify();
+    }
+}
+
+impl Render for ToolReferenceView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let muted = cx.theme().muted_foreground;
+        let matching: Vec<ToolReference> = self
+            .tools
+            .iter()
+            .filter(|tool| tool.matches(&self.query))
+            .cloned()
+            .collect();
+        let empty_label = if self.tools.is_empty() {
+            Some(t("tool-reference-empty"))
+        } else if matching.is_empty() {
+            Some(t("tool-reference-no-matches"))
+        } else {
+            None
+        };
+
+        let entries = matching.into_iter().enumerate().map(|(ix, tool)| {
+            let expanded = self.expanded.contains(&tool.name);
+            let name = tool.name.clone();
+            let permission_color = match tool.permission {
+                PermissionClass::ReadOnly => cx.theme().success,
+                PermissionClass::WritesFiles | PermissionClass::Network => cx.theme().warning,
+                PermissionClass::RunsCode => cx.theme().danger,
+                PermissionClass::External => cx.theme().info,
+            };
+
+            v_flex()
+                .id(ix)
+                .px_3()
+                .py_2()
+                .gap_1()
+                .rounded_md()
+                .border_1()
+                .border_color(cx.theme().border)
+                .child(
+                    h_flex()
+                        .id("tool-reference-header")
+                        .gap_2()
+                        .items_center()
+                        .cursor_pointer()
+                        .on_click(cx.listener(move |this, _, _, cx| this.toggle(&name, cx)))
+                        .child(div().text_xs().text_color(muted).child(if expanded {
+                            "▼"
+                        } else {
+                            "▶"
+                        }))
+                        .child(
+                            div()
+                                .flex_1()
+                                .text_sm()
+                                .font_family("monospace")
+                                .font_weight(FontWeight::BOLD)
+                                .child(tool.name.clone()),
+                        )
+                        .child(div().text_xs().text_color(muted).child(tool.origin.label()))
+                        .child(
+                            div()
+                                .text_xs()
+                                .px_2()
+                                .py(px(0.5))
+                                .rounded_sm()
+                                .border_1()
+                                .border_color(permission_color)
+                                .text_color(permission_color)
+                                .child(tool.permission.label()),
+                        ),
+                )
+                .when(!tool.description.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .text_xs()
+                            .text_color(muted)
+                            .when(!expanded, |this| this.line_clamp(2))
+                            .child(tool.description.clone()),
+                    )
+                })
+                .when(expanded, |this| {
+                    let schema = serde_json::to_string_pretty(&tool.parameters)
+                        .unwrap_or_else(|_| tool.parameters.to_string());
+                    let examples = tool
+                        .example_invocations()
+                        .into_iter()
+                        .map(|args| {
+                            let args = serde_json::to_string_pretty(&args)
+                                .unwrap_or_else(|_| args.to_string());
+                            format!("{}({})", tool.name, args)
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n\n");
+                    this.child(
+                        div()
+                            .text_xs()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(t("tool-reference-schema")),
+                    )
+                    .child(CodeBlockComponent::plain(
+                        Some("json".to_string()),
+                        schema,
+                        ix * 2,
+                    ))
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(t("tool-reference-examples")),
+                    )
+                    .child(CodeBlockComponent::plain(
+                        None,
+                        examples,
+                        ix * 2 + 1,
+                    ))
+                })
+        });
+
+        v_flex()
+            .size_full()
+            .gap_2()
+            .child(Input::new(&self.input))
+            .child(
+                v_flex()
+                    .id("tool-reference-list")
+                    .flex_1()
+                    .min_h_0()
+                    .gap_2()
+                    .overflow_y_scrollbar()
+                    .when_some(empty_label, |this, label| {
+                        this.child(div().px_3().py_4().text_sm().text_color(muted).child(label))
+                    })
+                    .children(entries),
+            )
+    }
+}
+
+/// Static helper that opens the tool reference dialog, listing the tools
+/// offered to the model with their schemas and example calls.
+pub struct ToolReferenceDialog;
+
+impl ToolReferenceDialog {
+    pub fn open(window: &mut Window, cx: &mut App) {
+        Self::open_focused(None, window, cx);
+    }
+
+    /// Open the reference filtered to `tool` with its entry expanded, e.g.
+    /// to compare a malformed call against the schema the model was given.
+    pub fn open_focused(tool: Option<String>, window: &mut Window, cx: &mut App) {
+        let view = cx.new(|cx| ToolReferenceView::new(tool, window, cx));
+        window.open_dialog(cx, move |dialog, _window, _cx| {
+            dialog
+                .title(t("tool-reference-title"))
+                .w(px(680.))
+                .h(px(560.))
+                .child(view.clone())
+        });
+    }
+}
@@ -6,7 +6,7 @@
 
 use gpui::*;
 
-use super::super::message_types::{ExecutionEngine, ToolCallBlock, ToolSource};
+use super::super::message_types::{ExecutionEngine, ToolCallBlock, ToolSource, is_cached_result};
 
 pub(super) fn render_outline_badge(text: String, color: Rgba) -> AnyElement {
     div()
@@ -30,6 +30,15 @@ pub(super) fn tool_source_badge(source: &ToolSource) -> Option<(String, Rgba)> {
     }
 }
 
+/// Badge for a tool call answered from the MCP result cache.
+pub(super) fn cached_result_badge(tool_call: &ToolCallBlock) -> Option<(String, Rgba)> {
+    tool_call
+        .output
+        .as_deref()
+        .is_some_and(is_cached_result)
+        .then(|| ("cached".to_string(), rgba(0x10B981FF)))
+}
+
 pub(super) fn execution_engine_badge(engine: ExecutionEngine) -> (String, Rgba) {
     let color = match engine {
         ExecutionEngine::Shell => rgba(0x6B7280FF),
@@ -5,7 +5,7 @@
 //! - `render_header` — the collapsible "Tool calls" / "Sub-agent" header.
 //! - `render_items` — the list of trace items (one card per item).
 //! - `render_thinking_block` / `render_tool_call_block` /
-//!   `render_approval_block` — the three card variants.
+//!   `render_approval_block` / `render_reflection_block` — the card variants.
 //!
 //! All methods take `&self` or `&mut self` on `SystemTraceView`; they
 //! are declared here in a second `impl SystemTraceView` block so the
@@ -15,21 +15,24 @@
 
 use crate::assets::CustomIcon;
 use crate::chatty::models::execution_approval_store::{ApprovalDecision, ExecutionApprovalStore};
+use crate::i18n::t;
 use gpui::{prelude::FluentBuilder, *};
 use gpui_component::{ActiveTheme, Icon, Sizable, button::Button};
 use std::time::Duration;
 
 use super::super::message_types::{
-    ApprovalState, ThinkingBlock, ToolCallBlock, ToolCallState, TraceItem,
+    ApprovalState, ReflectionBlock, ThinkingBlock, ToolCallBlock, ToolCallState, TraceItem,
 };
 use super::SystemTraceView;
 use super::badges::{
-    execution_engine_badge, is_code_execution_tool, render_execution_mode_badge,
-    render_outline_badge, render_sub_agent_mode_badge, tool_source_badge,
+    cached_result_badge, execution_engine_badge, is_code_execution_tool,
+    render_execution_mode_badge, render_outline_badge, render_sub_agent_mode_badge,
+    tool_source_badge,
 };
 use super::inline::{
     SelectableText, extract_command_display, extract_full_command, format_tool_output,
-    render_code_run_input, render_full_command_box,
+    render_code_run_input, render_full_command_box, render_graphql_output, render_restore_link,
+    render_tool_reference_link,
 };
 
 impl SystemTraceView {
@@ -114,6 +117,19 @@ impl SystemTraceView {
                             ("✓", "analysis", cx.theme().accent)
                         }
                     }
+                    TraceItem::ApprovalPrompt(approval) if approval.question.is_some() => {
+                        match approval.state {
+                            crate::chatty::views::message_types::ApprovalState::Pending => {
+                                ("?", "question", cx.theme().primary)
+                            }
+                            crate::chatty::views::message_types::ApprovalState::Approved => {
+                                ("✓", "answered", cx.theme().accent)
+                            }
+                            crate::chatty::views::message_types::ApprovalState::Denied => {
+                                ("-", "skipped", cx.theme().muted_foreground)
+                            }
+                        }
+                    }
                     TraceItem::ApprovalPrompt(approval) => match approval.state {
                         crate::chatty::views::message_types::ApprovalState::Pending => {
                             ("?", "approval", cx.theme().primary)
@@ -125,6 +141,7 @@ impl SystemTraceView {
                             ("✗", "denied", cx.theme().ring)
                         }
                     },
+                    TraceItem::Reflection(_) => ("↻", "self-check", cx.theme().ring),
                 };
 
                 let mut step_container = div().flex().items_center().gap_1();
@@ -187,6 +204,53 @@ impl SystemTraceView {
             }
         }
 
+        if let Some(routing) = &self.trace.routing {
+            header = header.child(
+                div()
+                    .ml_auto()
+                    .text_xs()
+                    .text_color(muted_text)
+                    .child(format!("routed: {}", routing.summary())),
+            );
+        }
+
+        if let Some(overrides) = &self.trace.overrides {
+            header = header.child(
+                div()
+                    .when(self.trace.routing.is_none(), |this| this.ml_auto())
+                    .when(self.trace.routing.is_some(), |this| this.ml_2())
+                    .text_xs()
+                    .text_color(muted_text)
+                    .child(format!("this turn: {}", overrides.summary())),
+            );
+        }
+
+        if let Some(policy) = &self.trace.request_privacy {
+            let first_badge = self.trace.routing.is_none() && self.trace.overrides.is_none();
+            header = header.child(
+                div()
+                    .when(first_badge, |this| this.ml_auto())
+                    .when(!first_badge, |this| this.ml_2())
+                    .text_xs()
+                    .text_color(muted_text)
+                    .child(format!("privacy: {policy}")),
+            );
+        }
+
+        if let Some(marker) = &self.trace.budget_reached {
+            let first_badge = self.trace.routing.is_none()
+                && self.trace.overrides.is_none()
+                && self.trace.request_privacy.is_none();
+            header = header.child(
+                div()
+                    .when(first_badge, |this| this.ml_auto())
+                    .when(!first_badge, |this| this.ml_2())
+                    .text_xs()
+                    .text_color(cx.theme().warning)
+                    .child(marker.clone()),
+            );
+        }
+
         header
     }
 
@@ -209,9 +273,15 @@ impl SystemTraceView {
                         TraceItem::ToolCall(tool_call) => self
                             .render_tool_call_block(index, tool_call, cx)
                             .into_any_element(),
+                        TraceItem::ApprovalPrompt(approval) if approval.question.is_some() => {
+                            self.render_question_block(approval, cx).into_any_element()
+                        }
                         TraceItem::ApprovalPrompt(approval) => self
                             .render_approval_block(index, approval, entity.clone(), cx)
                             .into_any_element(),
+                        TraceItem::Reflection(reflection) => self
+                            .render_reflection_block(reflection, cx)
+                            .into_any_element(),
                     }),
             )
     }
@@ -285,6 +355,55 @@ impl SystemTraceView {
             )
     }
 
+    /// Render a rejected first attempt and its critique (terminal style)
+    fn render_reflection_block(&self, reflection: &ReflectionBlock, cx: &App) -> impl IntoElement {
+        let muted_text = cx.theme().muted_foreground;
+        let border_color = cx.theme().border;
+        let text_color = cx.theme().foreground;
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .font_family("monospace")
+                    .text_sm()
+                    .child(
+                        div()
+                            .text_color(cx.theme().ring)
+                            .font_weight(FontWeight::BOLD)
+                            .child("↻"),
+                    )
+                    .child(div().text_color(muted_text).child(format!(
+                        "self-check scored {}/5 (below {}), retried",
+                        reflection.score, reflection.threshold
+                    ))),
+            )
+            .child(
+                div()
+                    .ml_4()
+                    .pl_3()
+                    .border_l_2()
+                    .border_color(border_color)
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .font_family("monospace")
+                    .text_sm()
+                    .text_color(text_color)
+                    .child(reflection.critique.clone())
+                    .child(
+                        div()
+                            .text_color(muted_text)
+                            .child(reflection.first_attempt.clone()),
+                    ),
+            )
+    }
+
     /// Render a tool call block (terminal style)
     fn render_tool_call_block(
         &self,
@@ -378,6 +497,12 @@ impl SystemTraceView {
                         this
                     }
                 })
+                .when_some(
+                    cached_result_badge(tool_call),
+                    |this, (badge_text, badge_color)| {
+                        this.child(render_outline_badge(badge_text, badge_color))
+                    },
+                )
                 .when_some(tool_call.duration, |this, duration| {
                     this.child(
                         div()
@@ -385,7 +510,17 @@ impl SystemTraceView {
                             .text_color(muted_text)
                             .child(format!("({:.1}s)", duration.as_secs_f32())),
                     )
-                }),
+                })
+                .children(render_restore_link(
+                    tool_call,
+                    ElementId::Name(format!("tool-restore-{}", index).into()),
+                    muted_text,
+                ))
+                .children(render_tool_reference_link(
+                    &tool_call.tool_name,
+                    ElementId::Name(format!("tool-schema-{}", index).into()),
+                    muted_text,
+                )),
         );
 
         // Show runnable code with syntax highlighting when available.
@@ -418,7 +553,15 @@ impl SystemTraceView {
             .as_ref()
             .or(tool_call.output_preview.as_ref())
         {
-            let formatted_output = format_tool_output(output);
+            let output_id = format!("tool-output-{}", index);
+            let output_body = match render_graphql_output(tool_call, output, &output_id, cx) {
+                Some(tree) => tree,
+                None => SelectableText::new(
+                    ElementId::Name(output_id.into()),
+                    format_tool_output(output),
+                )
+                .into_any_element(),
+            };
             container = container.child(
                 div()
                     .ml_4()
@@ -444,10 +587,7 @@ impl SystemTraceView {
                             .bg(panel_bg)
                             .rounded_sm()
                             .text_color(text_color)
-                            .child(SelectableText::new(
-                                ElementId::Name(format!("tool-output-{}", index).into()),
-                                formatted_output,
-                            )),
+                            .child(output_body),
                     ),
             );
         }
@@ -495,6 +635,98 @@ impl SystemTraceView {
         container
     }
 
+    /// Render a question asked through `ask_user`. Answers are given via the
+    /// question card above the chat input, so this block only shows the outcome.
+    fn render_question_block(
+        &self,
+        approval: &crate::chatty::views::message_types::ApprovalBlock,
+        cx: &App,
+    ) -> impl IntoElement {
+        let (prefix, prefix_color, state_label) = match &approval.state {
+            ApprovalState::Pending => ("?", cx.theme().primary, "awaiting answer"),
+            ApprovalState::Approved => ("✓", cx.theme().accent, "answered"),
+            ApprovalState::Denied => ("-", cx.theme().muted_foreground, "skipped"),
+        };
+        let muted_text = cx.theme().muted_foreground;
+        let text_color = cx.theme().foreground;
+        let options = approval
+            .question
+            .as_ref()
+            .map(|question| question.options.join(" · "))
+            .unwrap_or_default();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .font_family("monospace")
+                    .text_sm()
+                    .child(
+                        div()
+                            .text_color(prefix_color)
+                            .font_weight(FontWeight::BOLD)
+                            .child(prefix),
+                    )
+                    .child(
+                        div()
+                            .text_color(text_color)
+                            .font_weight(FontWeight::BOLD)
+                            .child(t("trace-question")),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .px_2()
+                            .py(px(0.5))
+                            .rounded_sm()
+                            .bg(prefix_color)
+                            .text_color(cx.theme().primary_foreground)
+                            .child(state_label),
+                    ),
+            )
+            .child(
+                div()
+                    .ml_4()
+                    .pl_3()
+                    .border_l_2()
+                    .border_color(cx.theme().border)
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(text_color)
+                            .child(approval.command.clone()),
+                    )
+                    .when(!options.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(muted_text)
+                                .child(format!("options: {options}")),
+                        )
+                    })
+                    .when_some(approval.answer.clone(), |this, answer| {
+                        this.child(
+                            div()
+                                .text_sm()
+                                .px_2()
+                                .py_1()
+                                .bg(cx.theme().muted)
+                                .rounded_sm()
+                                .text_color(text_color)
+                                .child(answer),
+                        )
+                    }),
+            )
+    }
+
     /// Render an approval prompt block (for code execution)
     fn render_approval_block(
         &self,
@@ -538,7 +770,7 @@ impl SystemTraceView {
                         div()
                             .text_color(text_color)
                             .font_weight(FontWeight::BOLD)
-                            .child("Execution approval requested"),
+                            .child(t("trace-approval-requested")),
                     )
                     .child(
                         div()
@@ -601,7 +833,7 @@ impl SystemTraceView {
                         .gap_2()
                         .child(
                             Button::new(ElementId::Name(format!("approve-{}", approval.id).into()))
-                                .label("Approve")
+                                .label(t("action-approve"))
                                 .small()
                                 .on_click({
                                     let id = approval_id.clone();
@@ -635,7 +867,7 @@ impl SystemTraceView {
                         )
                         .child(
                             Button::new(ElementId::Name(format!("deny-{}", approval.id).into()))
-                                .label("Deny")
+                                .label(t("action-deny"))
                                 .small()
                                 .on_click({
                                     let id = approval_id;
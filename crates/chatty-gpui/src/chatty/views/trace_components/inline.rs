@@ -4,6 +4,11 @@
 //! # What lives here
 //!
 //! - The public `render_tool_call_inline` entry point.
+//! - `render_reflection_inline` — the self-check card above a retried answer.
+//! - `render_tool_reference_link` — the header link into the tool reference,
+//!   shared with `blocks.rs`.
+//! - `render_restore_link` — the header link undoing a `delete_file` or
+//!   `move_file` call from the workspace trash, shared with `blocks.rs`.
 //! - Internal helpers for diff rendering, command extraction, output
 //!   formatting, and a small `SelectableText` element wrapper.
 //!
@@ -14,14 +19,21 @@
 #![allow(clippy::collapsible_if)]
 
 use gpui::{prelude::FluentBuilder, *};
-use gpui_component::{ActiveTheme, text::TextView};
+use gpui_component::{ActiveTheme, WindowExt as _, text::TextView};
 use std::time::Duration;
 
 use super::super::code_block_component::CodeBlockComponent;
 use super::super::diff_view_component::DiffViewComponent;
-use super::super::message_types::{ToolCallBlock, ToolCallState};
+use super::super::json_tree_component::JsonTreeComponent;
+use super::super::message_types::{ReflectionBlock, ToolCallBlock, ToolCallState};
+use super::super::tool_reference_dialog::ToolReferenceDialog;
+use crate::i18n::t;
+use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use chatty_core::services::{tool_reference, workspace_trash};
+
 use super::badges::{
-    is_code_execution_tool, render_execution_mode_badge, render_sub_agent_mode_badge,
+    cached_result_badge, is_code_execution_tool, render_execution_mode_badge, render_outline_badge,
+    render_sub_agent_mode_badge,
 };
 
 pub struct InlineToolCallRenderArgs<'a, F, D>
@@ -154,6 +166,12 @@ where
                 this.child(inline_badge)
             }
         })
+        .when_some(
+            cached_result_badge(tool_call),
+            |this, (badge_text, badge_color)| {
+                this.child(render_outline_badge(badge_text, badge_color))
+            },
+        )
         .when_some(tool_call.duration, |this, duration| {
             this.child(
                 div()
@@ -162,7 +180,17 @@ where
                     .flex_shrink_0()
                     .child(format!("({:.1}s)", duration.as_secs_f32())),
             )
-        });
+        })
+        .children(render_restore_link(
+            tool_call,
+            ElementId::Name(format!("inline-restore-{}-{}", message_index, tool_index).into()),
+            muted_text,
+        ))
+        .children(render_tool_reference_link(
+            &tool_call.tool_name,
+            ElementId::Name(format!("inline-schema-{}-{}", message_index, tool_index).into()),
+            muted_text,
+        ));
 
     // Build accordion content children (what shows when expanded)
     let mut content_children = Vec::new();
@@ -204,7 +232,15 @@ where
             .as_ref()
             .or(tool_call.output_preview.as_ref())
         {
-            let formatted_output = format_tool_output(output);
+            let output_id = format!("inline-tool-output-{}-{}", message_index, tool_index);
+            let output_body = match render_graphql_output(tool_call, output, &output_id, cx) {
+                Some(tree) => tree,
+                None => SelectableText::new(
+                    ElementId::Name(output_id.into()),
+                    format_tool_output(output),
+                )
+                .into_any_element(),
+            };
             content_children.push(
                 div()
                     .font_family("monospace")
@@ -214,12 +250,7 @@ where
                     .bg(panel_bg)
                     .rounded_sm()
                     .text_color(text_color)
-                    .child(SelectableText::new(
-                        ElementId::Name(
-                            format!("inline-tool-output-{}-{}", message_index, tool_index).into(),
-                        ),
-                        formatted_output,
-                    ))
+                    .child(output_body)
                     .into_any_element(),
             );
         } else if matches!(tool_call.state, ToolCallState::Running) {
@@ -229,7 +260,7 @@ where
                     .font_family("monospace")
                     .text_xs()
                     .text_color(muted_text)
-                    .child("Running...")
+                    .child(t("trace-running"))
                     .into_any_element(),
             );
         }
@@ -251,7 +282,7 @@ where
                         .text_xs()
                         .text_color(error_color)
                         .font_weight(FontWeight::BOLD)
-                        .child("Error:"),
+                        .child(t("trace-error")),
                 )
                 .child(
                     div()
@@ -299,6 +330,121 @@ where
         })
 }
 
+/// Render the self-check that rejected the first attempt at this answer.
+/// Expanding it shows the critique and the first attempt.
+pub fn render_reflection_inline(
+    reflection: &ReflectionBlock,
+    message_index: usize,
+    item_index: usize,
+    collapsed: bool,
+    on_toggle: impl Fn(&MouseDownEvent, &mut Window, &mut App) + 'static,
+    cx: &App,
+) -> impl IntoElement {
+    let muted_text = cx.theme().muted_foreground;
+    let text_color = cx.theme().foreground;
+    let panel_bg = cx.theme().muted;
+    let prefix_color = cx.theme().ring;
+
+    let header = div()
+        .flex()
+        .flex_row()
+        .items_center()
+        .gap_2()
+        .cursor_pointer()
+        .on_mouse_down(MouseButton::Left, move |event, window, cx| {
+            on_toggle(event, window, cx);
+        })
+        .child(
+            div()
+                .text_color(muted_text)
+                .flex_shrink_0()
+                .child(if collapsed { "▶" } else { "▼" }),
+        )
+        .child(
+            div()
+                .text_color(prefix_color)
+                .font_weight(FontWeight::BOLD)
+                .flex_shrink_0()
+                .child("↻"),
+        )
+        .child(
+            div()
+                .text_color(text_color)
+                .font_weight(FontWeight::BOLD)
+                .font_family("monospace")
+                .text_sm()
+                .flex_1()
+                .child("self-check: first attempt retried"),
+        )
+        .child(
+            div()
+                .text_xs()
+                .text_color(muted_text)
+                .flex_shrink_0()
+                .child(format!(
+                    "scored {}/5, below {}",
+                    reflection.score, reflection.threshold
+                )),
+        );
+
+    let section = |label: &'static str, id: String, text: String| {
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(muted_text)
+                    .child(label),
+            )
+            .child(
+                div()
+                    .font_family("monospace")
+                    .text_xs()
+                    .px_2()
+                    .py_1()
+                    .bg(panel_bg)
+                    .rounded_sm()
+                    .text_color(text_color)
+                    .child(SelectableText::new(ElementId::Name(id.into()), text)),
+            )
+    };
+
+    div()
+        .flex()
+        .flex_col()
+        .gap_1()
+        .w_full()
+        .px_3()
+        .py_2()
+        .border_1()
+        .border_color(cx.theme().border)
+        .rounded_md()
+        .bg(panel_bg.opacity(0.3))
+        .child(header)
+        .when(!collapsed, |this| {
+            this.child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .pl_4()
+                    .child(section(
+                        "Critique",
+                        format!("reflection-critique-{message_index}-{item_index}"),
+                        reflection.critique.clone(),
+                    ))
+                    .child(section(
+                        "First attempt",
+                        format!("reflection-first-{message_index}-{item_index}"),
+                        reflection.first_attempt.clone(),
+                    )),
+            )
+        })
+}
+
 /// Try to build a diff view from apply_diff tool input JSON.
 /// Returns None if parsing fails.
 pub(super) fn try_build_diff_view(
@@ -331,6 +477,28 @@ pub(super) fn try_build_diff_view(
     )
 }
 
+/// Render the result of a GraphQL endpoint tool as a collapsible tree.
+/// Returns None for other tools and for non-JSON output (schema descriptions).
+pub(super) fn render_graphql_output(
+    tool_call: &ToolCallBlock,
+    output: &str,
+    id: &str,
+    cx: &App,
+) -> Option<AnyElement> {
+    let is_graphql = cx.try_global::<ExecutionSettingsModel>().is_some_and(|s| {
+        s.graphql_endpoints
+            .iter()
+            .any(|e| e.name == tool_call.tool_name)
+    });
+    if !is_graphql {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(output).ok()?;
+    value
+        .is_object()
+        .then(|| JsonTreeComponent::new(format!("{id}-tree"), value).into_any_element())
+}
+
 /// Render the full command text box (used when the header was truncated)
 pub(super) fn render_full_command_box(
     full_command: String,
@@ -357,6 +525,89 @@ pub(super) fn render_code_run_input(
     Some(CodeBlockComponent::new(Some(language), code, block_index).into_any_element())
 }
 
+/// "schema" link in a tool call header, opening the tool's entry in the
+/// tool reference to compare the call with the schema the model was given.
+/// `None` for tools not recorded in this process.
+pub(super) fn render_tool_reference_link(
+    tool_name: &str,
+    id: ElementId,
+    color: Hsla,
+) -> Option<AnyElement> {
+    tool_reference::lookup(tool_name)?;
+    let tool_name = tool_name.to_string();
+    Some(
+        div()
+            .id(id)
+            .text_xs()
+            .text_color(color)
+            .flex_shrink_0()
+            .cursor_pointer()
+            .hover(|style| style.underline())
+            .child("schema")
+            .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                cx.stop_propagation();
+                ToolReferenceDialog::open_focused(Some(tool_name.clone()), window, cx);
+            })
+            .into_any_element(),
+    )
+}
+
+/// "restore" link in the header of a `delete_file` or `move_file` call,
+/// undoing it from the workspace trash. `None` once restored, after the undo
+/// window, or for calls made before this process started.
+pub(super) fn render_restore_link(
+    tool_call: &ToolCallBlock,
+    id: ElementId,
+    color: Hsla,
+) -> Option<AnyElement> {
+    if !matches!(tool_call.tool_name.as_str(), "delete_file" | "move_file") {
+        return None;
+    }
+    let output: serde_json::Value = serde_json::from_str(tool_call.output.as_deref()?).ok()?;
+    let trash_id = output.get("trash_id")?.as_str()?.to_string();
+    let now = || {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default()
+    };
+    if !workspace_trash::is_restorable(&trash_id, now()) {
+        return None;
+    }
+    Some(
+        div()
+            .id(id)
+            .text_xs()
+            .text_color(color)
+            .flex_shrink_0()
+            .cursor_pointer()
+            .hover(|style| style.underline())
+            .child("restore")
+            .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                cx.stop_propagation();
+                let trash_id = trash_id.clone();
+                let window_handle = window.window_handle();
+                cx.spawn(async move |cx: &mut AsyncApp| {
+                    let message = match workspace_trash::restore(&trash_id, now()).await {
+                        Ok(entry) => entry.restored_message(),
+                        Err(e) => {
+                            tracing::warn!(error = ?e, trash_id, "Failed to restore from the trash");
+                            format!("Failed to restore: {e}")
+                        }
+                    };
+                    window_handle
+                        .update(cx, |_, window, cx| {
+                            window.push_notification(message, cx);
+                            cx.refresh_windows();
+                        })
+                        .ok();
+                })
+                .detach();
+            })
+            .into_any_element(),
+    )
+}
+
 /// Format the inline header text for a tool call.
 ///
 /// Most tools show `$ <command>` (shell-style), but internet and memory tools use their
@@ -610,13 +861,13 @@ pub(super) fn format_tool_output(output: &str) -> String {
 /// text selection enabled. Using a struct defers the `window`/`cx` requirement
 /// of `TextView::markdown` to render time, so callers don't need `&mut Window`.
 #[derive(IntoElement)]
-pub(super) struct SelectableText {
+pub(crate) struct SelectableText {
     id: ElementId,
     text: SharedString,
 }
 
 impl SelectableText {
-    pub(super) fn new(id: impl Into<ElementId>, text: impl Into<SharedString>) -> Self {
+    pub(crate) fn new(id: impl Into<ElementId>, text: impl Into<SharedString>) -> Self {
         Self {
             id: id.into(),
             text: text.into(),
@@ -28,7 +28,8 @@ mod inline;
 // Re-export the public API so external callers (chat_view,
 // message_component) see the same `trace_components::*` namespace as
 // before the split.
-pub use inline::{InlineToolCallRenderArgs, render_tool_call_inline};
+pub use inline::{InlineToolCallRenderArgs, render_reflection_inline, render_tool_call_inline};
+pub(crate) use inline::SelectableText;
 
 use gpui::*;
 
@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use crate::chatty::models::execution_approval_store::UserQuestion;
+use crate::i18n::t;
+use gpui::prelude::*;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{ActiveTheme, Sizable};
+
+pub type AnswerCallback = Arc<dyn Fn(String, &mut App) + Send + Sync>;
+pub type DismissCallback = Arc<dyn Fn(&mut App) + Send + Sync>;
+
+/// Inline card for a question asked by the agent through `ask_user`.
+/// Options are answered with a click; free-text answers are typed into the
+/// chat input, which is routed to the pending question while it is shown.
+#[derive(IntoElement)]
+pub struct UserQuestionCard {
+    question: UserQuestion,
+    on_answer: Option<AnswerCallback>,
+    on_dismiss: Option<DismissCallback>,
+}
+
+impl UserQuestionCard {
+    pub fn new(question: UserQuestion) -> Self {
+        Self {
+            question,
+            on_answer: None,
+            on_dismiss: None,
+        }
+    }
+
+    pub fn on_answer<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(String, &mut App) + Send + Sync + 'static,
+    {
+        self.on_answer = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn on_dismiss<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut App) + Send + Sync + 'static,
+    {
+        self.on_dismiss = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl RenderOnce for UserQuestionCard {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let hint = match (
+            self.question.options.is_empty(),
+            self.question.allow_free_text,
+        ) {
+            (true, _) => "Type your answer in the message box and press Enter.",
+            (false, true) => "Pick an option or type your own answer in the message box.",
+            (false, false) => "Pick an option, or type its number in the message box.",
+        };
+        let on_answer = self.on_answer.clone();
+        let on_dismiss = self.on_dismiss.clone();
+
+        div().w_full().px_4().pb_2().child(
+            div()
+                .w_full()
+                .rounded_lg()
+                .border_1()
+                .border_color(cx.theme().accent)
+                .bg(cx.theme().secondary.opacity(0.65))
+                .shadow_sm()
+                .px_3()
+                .py_2()
+                .flex()
+                .flex_col()
+                .gap_2()
+                .child(
+                    div()
+                        .flex()
+                        .items_start()
+                        .gap_2()
+                        .child(
+                            div()
+                                .flex_1()
+                                .min_w_0()
+                                .flex()
+                                .flex_col()
+                                .gap_1()
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(t("question-title")),
+                                )
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .font_weight(FontWeight::SEMIBOLD)
+                                        .text_color(cx.theme().foreground)
+                                        .child(self.question.question.clone()),
+                                ),
+                        )
+                        .child(
+                            Button::new("user-question-dismiss")
+                                .ghost()
+                                .xsmall()
+                                .label(t("question-skip"))
+                                .on_click(move |_event, _window, cx| {
+                                    if let Some(callback) = &on_dismiss {
+                                        callback(cx);
+                                    }
+                                }),
+                        ),
+                )
+                .when(!self.question.options.is_empty(), |card| {
+                    card.child(
+                        div().flex().flex_wrap().gap_2().children(
+                            self.question
+                                .options
+                                .iter()
+                                .enumerate()
+                                .map(|(index, option)| {
+                                    let answer = option.clone();
+                                    let on_answer = on_answer.clone();
+                                    Button::new(ElementId::Name(
+                                        format!("user-question-option-{index}").into(),
+                                    ))
+                                    .small()
+                                    .label(format!("{}. {}", index + 1, option))
+                                    .on_click(
+                                        move |_event, _window, cx| {
+                                            if let Some(callback) = &on_answer {
+                                                callback(answer.clone(), cx);
+                                            }
+                                        },
+                                    )
+                                }),
+                        ),
+                    )
+                })
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(hint),
+                ),
+        )
+    }
+}
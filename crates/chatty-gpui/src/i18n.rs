@@ -0,0 +1,247 @@
+//! UI string localization.
+//!
+//! Strings live in Fluent-style catalogs embedded from `assets/locales`, one
+//! `<locale>.ftl` file per language. Only the subset of Fluent the UI needs
+//! is understood: `key = value` messages, indented continuation lines, `#`
+//! comments and `{ $name }` placeholders. Views look strings up with [`t`] or
+//! [`t_args`]; a key missing from the active locale falls back to English,
+//! and a key missing from English renders as the key itself so it is easy to
+//! spot.
+//!
+//! The active locale is process-wide. It is chosen at startup from the saved
+//! language setting, or from the system locale when none is saved, and
+//! switched live from the general settings page. The locale also decides
+//! whether the layout is mirrored when the layout direction is automatic.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+use chatty_core::models::text_direction::{RTL_LANGUAGES, TextDirection};
+use gpui::App;
+use rust_embed::RustEmbed;
+use tracing::{info, warn};
+
+use crate::settings::models::GeneralSettingsModel;
+
+#[derive(RustEmbed)]
+#[folder = "../../assets/locales"]
+#[include = "*.ftl"]
+struct LocaleFiles;
+
+/// Locale every other catalog falls back to.
+pub const FALLBACK_LOCALE: &str = "en";
+
+/// Shipped locales as `(code, native name)`, in picker order.
+pub const LOCALES: &[(&str, &str)] = &[("en", "English"), ("de", "Deutsch")];
+
+struct Catalog {
+    locale: &'static str,
+    messages: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+static CATALOG: LazyLock<RwLock<Catalog>> = LazyLock::new(|| {
+    let fallback = load_messages(FALLBACK_LOCALE);
+    RwLock::new(Catalog {
+        locale: FALLBACK_LOCALE,
+        messages: fallback.clone(),
+        fallback,
+    })
+});
+
+/// Switch the UI language. `preference` is the saved language setting;
+/// `None` follows the system locale. Unknown locales fall back to English.
+pub fn set_locale(preference: Option<&str>) {
+    let requested = preference
+        .map(str::to_string)
+        .or_else(system_locale)
+        .unwrap_or_else(|| FALLBACK_LOCALE.to_string());
+    let locale = supported_locale(&requested).unwrap_or_else(|| {
+        warn!(locale = %requested, "No translation for locale, using English");
+        FALLBACK_LOCALE
+    });
+
+    let mut catalog = CATALOG.write().unwrap_or_else(|e| e.into_inner());
+    if catalog.locale == locale {
+        return;
+    }
+    catalog.messages = load_messages(locale);
+    catalog.locale = locale;
+    info!(locale, "UI language set");
+}
+
+/// Code of the active locale, e.g. `"de"`.
+pub fn current_locale() -> &'static str {
+    CATALOG.read().unwrap_or_else(|e| e.into_inner()).locale
+}
+
+/// Whether the active locale is written right to left.
+pub fn is_rtl_locale() -> bool {
+    RTL_LANGUAGES.contains(&current_locale())
+}
+
+/// Base direction of the layout: the layout direction setting, resolved
+/// against the active locale when set to automatic.
+pub fn layout_direction(cx: &App) -> TextDirection {
+    cx.global::<GeneralSettingsModel>()
+        .layout_direction
+        .resolve(is_rtl_locale())
+}
+
+/// Native name of a shipped locale, for the language picker.
+pub fn locale_name(code: &str) -> &'static str {
+    LOCALES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map_or("English", |(_, name)| name)
+}
+
+/// The translation of `key` in the active locale.
+pub fn t(key: &str) -> String {
+    t_args(key, &[])
+}
+
+/// The translation of `key` with its `{ $name }` placeholders filled from
+/// `args`.
+pub fn t_args(key: &str, args: &[(&str, &str)]) -> String {
+    let catalog = CATALOG.read().unwrap_or_else(|e| e.into_inner());
+    match catalog
+        .messages
+        .get(key)
+        .or_else(|| catalog.fallback.get(key))
+    {
+        Some(pattern) => format_pattern(pattern, args),
+        None => key.to_string(),
+    }
+}
+
+/// The system UI locale from the usual POSIX variables, e.g. `de_DE.UTF-8`.
+fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+}
+
+/// The shipped locale matching `requested` (`de`, `de-AT`, `de_DE.UTF-8`).
+fn supported_locale(requested: &str) -> Option<&'static str> {
+    let language = requested
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    LOCALES
+        .iter()
+        .map(|(code, _)| *code)
+        .find(|code| *code == language)
+}
+
+fn load_messages(locale: &str) -> HashMap<String, String> {
+    match LocaleFiles::get(&format!("{locale}.ftl")) {
+        Some(file) => parse_ftl(&String::from_utf8_lossy(&file.data)),
+        None => {
+            warn!(locale, "Locale catalog not bundled");
+            HashMap::new()
+        }
+    }
+}
+
+/// Parse the messages of a Fluent catalog. Indented lines continue the
+/// previous message on a new line.
+fn parse_ftl(source: &str) -> HashMap<String, String> {
+    let mut messages = HashMap::new();
+    let mut current: Option<(String, String)> = None;
+
+    for line in source.lines() {
+        let continues = line.starts_with([' ', '\t']) && !line.trim().is_empty();
+        if continues && let Some((_, value)) = current.as_mut() {
+            if !value.is_empty() {
+                value.push('\n');
+            }
+            value.push_str(line.trim());
+            continue;
+        }
+        if let Some((key, value)) = current.take() {
+            messages.insert(key, value);
+        }
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            current = Some((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    if let Some((key, value)) = current {
+        messages.insert(key, value);
+    }
+    messages
+}
+
+/// Replace `{ $name }` placeholders with their values. Unknown placeholders
+/// are left as written.
+fn format_pattern(pattern: &str, args: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let placeholder = &rest[start..start + len + 1];
+        let name = placeholder[1..len].trim().trim_start_matches('$');
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(placeholder),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_messages_and_fills_placeholders() {
+        let messages = parse_ftl(
+            "# Comment\n\
+             send = Send\n\
+             \n\
+             greeting = Hello { $name },\n    \
+             welcome back\n\
+             broken line\n",
+        );
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages["send"], "Send");
+        assert_eq!(
+            format_pattern(&messages["greeting"], &[("name", "Ada")]),
+            "Hello Ada,\nwelcome back"
+        );
+        assert_eq!(format_pattern("{ $missing }!", &[]), "{ $missing }!");
+    }
+
+    #[test]
+    fn matches_system_locales_to_shipped_catalogs() {
+        assert_eq!(supported_locale("de_DE.UTF-8"), Some("de"));
+        assert_eq!(supported_locale("en-GB"), Some("en"));
+        assert_eq!(supported_locale("fr_FR"), None);
+    }
+
+    #[test]
+    fn shipped_locales_translate_every_english_key() {
+        let english = load_messages(FALLBACK_LOCALE);
+        assert!(!english.is_empty());
+        for (code, _) in LOCALES {
+            let messages = load_messages(code);
+            let missing: Vec<_> = english
+                .keys()
+                .filter(|key| !messages.contains_key(*key))
+                .collect();
+            assert!(missing.is_empty(), "{code} is missing {missing:?}");
+        }
+    }
+}
@@ -30,12 +30,14 @@ mod auto_updater;
 mod chatty;
 pub mod cli_installer;
 pub mod global_entity;
+mod i18n;
 mod settings;
 
 use assets::ChattyAssets;
 use auto_updater::AutoUpdater;
 use chatty::{ChattyApp, GlobalChattyApp};
 use chatty_core::repositories::{ConversationRepository, ConversationSqliteRepository};
+use chatty_core::services::startup_profile;
 use settings::SettingsView;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -84,6 +86,7 @@ actions!(
 );
 
 mod actions;
+mod shutdown;
 mod themes;
 
 use actions::register_actions;
@@ -92,6 +95,8 @@ use actions::set_app_menus;
 use themes::{apply_theme_from_settings, init_themes};
 
 fn main() {
+    startup_profile::mark_process_start();
+
     // Initialize error collector layer
     let (error_layer, error_receiver) = chatty::services::ErrorCollectorLayer::new();
 
@@ -120,16 +125,58 @@ fn main() {
 
     // Initialize all settings repositories (providers, models, MCP, etc.).
     // This must happen before anything accesses the repository singletons.
-    chatty_core::init_repositories()
-        .expect("Failed to initialize settings repositories (is HOME set?)");
+    {
+        let _phase = startup_profile::phase("repositories");
+        chatty_core::init_repositories()
+            .expect("Failed to initialize settings repositories (is HOME set?)");
+    }
+
+    // Opt-in usage metrics: load the local aggregate and count crashes
+    chatty_core::services::telemetry_service::init();
+    chatty_core::services::telemetry_service::install_panic_hook();
+    // Per-tool call statistics (always on, local only)
+    chatty_core::services::tool_stats_service::init();
+    // CHATTY_RECORD_FLOW / CHATTY_REPLAY_FLOW: capture or replay provider responses
+    let flow_mode = chatty_core::services::flow_recorder::mode_from_env();
+    chatty_core::services::flow_recorder::init(flow_mode)
+        .expect("Failed to load the flow recording named by CHATTY_REPLAY_FLOW");
 
     // Initialize the SQLite conversation repository here, where the Tokio runtime is
     // explicitly set up, so the block_on call is clearly safe and in a known context.
+    let conversation_db_phase = startup_profile::phase("conversation_db");
     let conversation_repo: Arc<dyn ConversationRepository> = Arc::new(
         _tokio_runtime
             .block_on(ConversationSqliteRepository::new())
             .expect("Failed to create SQLite conversation repository"),
     );
+    drop(conversation_db_phase);
+
+    // The saved window geometry and UI language must be known before the
+    // main window opens, so they are read here rather than in the async
+    // settings load below.
+    let startup_settings = _tokio_runtime
+        .block_on(chatty_core::general_settings_repository().load())
+        .ok();
+    i18n::set_locale(
+        startup_settings
+            .as_ref()
+            .and_then(|settings| settings.language.as_deref()),
+    );
+    let conversation_sort = startup_settings
+        .as_ref()
+        .map(|settings| settings.conversation_sort)
+        .unwrap_or_default();
+    let restored_session = startup_settings
+        .filter(|settings| settings.restore_session)
+        .map(|settings| settings.last_session);
+
+    // Key bindings are registered once at startup, so they are read up front too.
+    let keybindings = _tokio_runtime
+        .block_on(chatty_core::keybindings_repository().load())
+        .unwrap_or_else(|e| {
+            warn!(error = ?e, "Failed to load keybindings, using defaults");
+            Default::default()
+        });
 
     let app = Application::new()
         .with_assets(gpui_component_assets::Assets)
@@ -144,11 +191,23 @@ fn main() {
         // Initialize general settings with default - will be populated async
         cx.set_global(settings::models::general_model::GeneralSettingsModel::default());
 
+        cx.set_global(keybindings);
+
+        // The sidebar order must be set before conversation metadata loads
+        let mut conversations = chatty::models::ConversationsStore::new();
+        conversations.set_sort(conversation_sort);
+        cx.set_global(conversations);
+
         // Initialize theme system
         init_themes(cx);
 
+        // Heavy services (MCP connections, math/mermaid renderers, Azure token
+        // pre-warming) wait for the first frame so they don't delay the window.
+        let (first_paint_tx, first_paint_rx) = tokio::sync::watch::channel(false);
+
         // Load general settings asynchronously without blocking startup
         cx.spawn(async move |cx: &mut AsyncApp| {
+            let _phase = startup_profile::phase("theme_load");
             let repo = chatty_core::general_settings_repository();
             match repo.load().await {
                 Ok(settings) => {
@@ -158,6 +217,11 @@ fn main() {
 
                         // Apply theme from loaded settings
                         apply_theme_from_settings(cx);
+
+                        // Dictionary parsing runs on a blocking thread
+                        settings::controllers::general_settings_controller::load_spell_checker(
+                            cx,
+                        );
                     })
                     .map_err(|e| warn!(error = ?e, "Failed to apply theme from loaded settings"))
                     .ok();
@@ -177,6 +241,9 @@ fn main() {
         // Initialize models model with empty state - will be populated async
         cx.set_global(settings::models::ModelsModel::new());
 
+        // Initialize embedding models with empty state - populated during memory init
+        cx.set_global(settings::models::EmbeddingModelsModel::new());
+
         // Initialize MCP servers model with empty state - will be populated async
         cx.set_global(settings::models::McpServersModel::new());
 
@@ -192,6 +259,25 @@ fn main() {
         // Initialize user secrets with empty state - will be populated async
         cx.set_global(settings::models::UserSecretsModel::default());
 
+        // Initialize the snippet library empty - will be populated async
+        cx.set_global(settings::models::SnippetsModel::default());
+
+        // Initialize installed prompt packs empty - will be populated async
+        cx.set_global(settings::models::PromptPacksModel::default());
+        cx.set_global(settings::models::PromptPacksState::default());
+
+        // Loaded Ollama models are unknown until the first status poll
+        cx.set_global(settings::providers::OllamaStatusState::default());
+
+        // Provider credential problems are unknown until the first health check
+        cx.set_global(settings::providers::ProviderHealthState::default());
+
+        // The provider setup wizard opens when the first conversation cannot be created
+        cx.set_global(chatty::views::OnboardingState::default());
+
+        // Initialize the managed (team) config layer as disabled - populated by the loaders below
+        cx.set_global(settings::models::ManagedConfigModel::default());
+
         // Initialize module settings with default - will be populated async
         cx.set_global(settings::models::ModuleSettingsModel::default());
         cx.set_global(settings::models::DiscoveredModulesModel::default());
@@ -200,6 +286,7 @@ fn main() {
         cx.set_global(settings::models::HiveSettingsModel::default());
         cx.set_global(settings::models::ExtensionsModel::default());
         cx.set_global(settings::models::MarketplaceState::default());
+        cx.set_global(settings::models::McpCatalogState::default());
         cx.set_global(settings::models::MemoryBrowserState::default());
 
         settings::controllers::module_settings_controller::refresh_runtime(cx);
@@ -241,16 +328,36 @@ fn main() {
                 info!("Agent memory disabled by settings");
             }
 
+            // Load registered embedding models before resolving the memory embedder
+            match chatty_core::embedding_models_repository().load_all().await {
+                Ok(models) => {
+                    cx.update(|cx| {
+                        cx.update_global::<settings::models::EmbeddingModelsModel, _>(|model, _cx| {
+                            model.replace_all(models);
+                        });
+                    })
+                    .map_err(|e| warn!(error = ?e, "Failed to update global embedding models after load"))
+                    .ok();
+                }
+                Err(e) => {
+                    warn!(error = ?e, "Failed to load embedding models");
+                }
+            }
+
             // Initialize embedding service for semantic memory search (if configured)
-            let embedding_settings = cx
+            let embedding_config = cx
                 .update(|cx| {
-                    cx.try_global::<settings::models::ExecutionSettingsModel>()
-                        .map(|s| (s.embedding_enabled, s.embedding_provider.clone(), s.embedding_model.clone()))
+                    let settings = cx.try_global::<settings::models::ExecutionSettingsModel>()?;
+                    if !settings.embedding_enabled {
+                        return None;
+                    }
+                    cx.global::<settings::models::EmbeddingModelsModel>().resolve(settings)
                 })
                 .ok()
                 .flatten();
 
-            if let Some((true, Some(embed_provider_type), Some(embed_model))) = embedding_settings {
+            if let Some(embed_config) = embedding_config {
+                let embed_provider_type = embed_config.provider_type.clone();
                 let provider_config = cx
                     .update(|cx| {
                         cx.try_global::<settings::models::ProviderModel>()
@@ -282,9 +389,8 @@ fn main() {
                     None
                 };
 
-                if let Some(embed_svc) = chatty_core::services::embedding_service::try_create_embedding_service(
-                    &embed_provider_type,
-                    &embed_model,
+                if let Some(embed_svc) = chatty_core::services::embedding_service::try_create_embedding_service_from_config(
+                    &embed_config,
                     api_key.as_deref(),
                     base_url.as_deref(),
                     azure_token,
@@ -308,9 +414,8 @@ fn main() {
                     }
 
                     cx.update(|cx| {
-                        let skill_service = chatty_core::services::SkillService::new(Some(embed_svc.clone()));
                         cx.set_global(embed_svc);
-                        cx.set_global(skill_service);
+                        settings::controllers::execution_settings_controller::reinit_skill_service(cx);
                     })
                     .map_err(|e| warn!(error = ?e, "Failed to set EmbeddingService and SkillService globals"))
                     .ok();
@@ -349,6 +454,23 @@ fn main() {
         })
         .detach();
 
+        // Write recorded telemetry counts (no-op while opted out) and tool
+        // statistics to disk once a minute
+        cx.spawn(async move |cx: &mut AsyncApp| {
+            loop {
+                cx.background_executor()
+                    .timer(std::time::Duration::from_secs(60))
+                    .await;
+                if let Some(telemetry) = chatty_core::services::telemetry_service::telemetry() {
+                    telemetry.flush();
+                }
+                if let Some(stats) = chatty_core::services::tool_stats_service::tool_stats() {
+                    stats.flush();
+                }
+            }
+        })
+        .detach();
+
         // Spawn background task to watch the token budget channel and trigger window refreshes.
         // This is necessary because token counting runs asynchronously in parallel with the LLM
         // call, and the snapshot arrives after the initial render. The watch channel update needs
@@ -456,6 +578,14 @@ fn main() {
         // Initialize error store and notifier
         cx.set_global(chatty::models::ErrorStore::new(100)); // Max 100 entries
 
+        // Load background jobs left over from the last session; ChattyApp
+        // retries them once conversations are loaded
+        cx.set_global(
+            chatty::models::JobQueue::default_path()
+                .map(|path| chatty::models::JobQueue::load(&path))
+                .unwrap_or_default(),
+        );
+
         let error_notifier = cx.new(|_cx| chatty::models::ErrorNotifier::new());
         cx.set_global(chatty::models::GlobalErrorNotifier::new(
             error_notifier.downgrade(),
@@ -518,16 +648,6 @@ fn main() {
         // on macOS/Windows the symlink / installer already keeps it in sync.
         cli_installer::update_cli_if_installed(cx);
 
-        // Initialize math renderer service for LaTeX math rendering
-        let math_renderer = chatty::services::MathRendererService::new();
-        cx.set_global(math_renderer);
-        info!("Math renderer service initialized");
-
-        // Initialize mermaid renderer service for diagram rendering
-        let mermaid_renderer = chatty::services::MermaidRendererService::new();
-        cx.set_global(mermaid_renderer);
-        info!("Mermaid renderer service initialized");
-
         // Clean up old SVG cache files from previous sessions in a background thread.
         // This is pure filesystem I/O with no dependencies — safe to run off the main thread.
         std::thread::spawn(|| {
@@ -553,21 +673,107 @@ fn main() {
         MCP_SERVICE.set(mcp_service.clone())
             .map_err(|_| warn!("MCP_SERVICE already initialized"))
             .ok();
-        cx.set_global(mcp_service);
+        cx.set_global(mcp_service.clone());
         info!("MCP service initialized");
 
+        // Mirror MCP server states (servers now start on first use) into the
+        // extension models so status badges follow connects and idle stops.
+        let mut mcp_states = mcp_service.subscribe_states();
+        cx.spawn(async move |cx: &mut AsyncApp| {
+            while mcp_states.changed().await.is_ok() {
+                let states = mcp_states.borrow_and_update().clone();
+                let applied = cx.update(|cx| {
+                    use chatty::services::mcp_service::McpServerState;
+                    use settings::models::mcp_store::McpAuthStatus;
+
+                    for (name, state) in states {
+                        let status = match state {
+                            // A stopped server keeps its last auth status
+                            McpServerState::Stopped => continue,
+                            McpServerState::Starting => McpAuthStatus::Connecting,
+                            McpServerState::Running => McpAuthStatus::Authenticated,
+                            McpServerState::Failed(msg)
+                                if msg.contains("Auth required") || msg.contains("AuthRequired") =>
+                            {
+                                McpAuthStatus::NeedsAuth
+                            }
+                            McpServerState::Failed(msg) => McpAuthStatus::Failed(msg),
+                        };
+                        cx.global_mut::<settings::models::McpServersModel>()
+                            .set_auth_status(name.clone(), status.clone());
+                        cx.global_mut::<settings::models::ExtensionsModel>()
+                            .set_mcp_auth_status(name, status);
+                    }
+                    cx.refresh_windows();
+                });
+                if applied.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+
+        // Stop MCP servers that have not been used within the configured idle time
+        cx.spawn(async move |cx: &mut AsyncApp| {
+            loop {
+                cx.background_executor()
+                    .timer(std::time::Duration::from_secs(60))
+                    .await;
+                let Ok(minutes) = cx.update(|cx| {
+                    cx.global::<settings::models::ExecutionSettingsModel>()
+                        .mcp_idle_shutdown_minutes
+                }) else {
+                    break;
+                };
+                if minutes > 0 {
+                    mcp_service
+                        .stop_idle(std::time::Duration::from_secs(u64::from(minutes) * 60))
+                        .await;
+                }
+            }
+        })
+        .detach();
+
         // Load providers, models, and execution settings concurrently (dependency tier 1).
         // Conversations depend on all three being loaded (dependency tier 2).
         // Using tokio::join! makes the dependency graph explicit and eliminates AtomicBool polling.
+        let mut first_paint = first_paint_rx;
         cx.spawn(async move |cx: &mut AsyncApp| {
-            // Run all three I/O operations in parallel before touching global state
-            let (providers_result, models_result, exec_settings_result, search_settings_result) = tokio::join!(
+            let settings_phase = startup_profile::phase("settings_load");
+            // Run all I/O operations in parallel before touching global state
+            let (providers_result, models_result, exec_settings_result, search_settings_result, managed) = tokio::join!(
                 chatty_core::provider_repository().load_all(),
                 chatty_core::models_repository().load_all(),
                 chatty_core::execution_settings_repository().load(),
                 chatty_core::search_settings_repository().load(),
+                chatty_core::services::managed_config_service::load(),
             );
 
+            // Overlay the managed (team) layer; overridden local entries are
+            // recorded so the settings window can show them.
+            let mut managed_overrides = Vec::new();
+            let providers_result = providers_result.map(|providers| {
+                let (providers, overridden) = managed.config.apply_to_providers(providers);
+                managed_overrides.push(("Provider", overridden));
+                providers
+            });
+            let models_result = models_result.map(|models| {
+                let (models, overridden) = managed.config.apply_to_models(models);
+                managed_overrides.push(("Model", overridden));
+                models
+            });
+            cx.update(|cx| {
+                for (kind, overridden) in managed_overrides {
+                    settings::controllers::managed_config_controller::record_overrides(
+                        kind,
+                        overridden,
+                        cx,
+                    );
+                }
+            })
+            .map_err(|e| warn!(error = ?e, "Failed to publish managed config"))
+            .ok();
+
             // Apply providers result
             match providers_result {
                 Ok(providers) => {
@@ -609,6 +815,7 @@ fn main() {
                         if needs_cache {
                             tracing::info!("Pre-initializing Azure token cache");
                             cx.spawn(|_cx: &mut AsyncApp| async move {
+                                let _ = first_paint.wait_for(|painted| *painted).await;
                                 if let Ok(cache) = chatty_core::auth::AzureTokenCache::new() {
                                     // Pre-warm cache with initial token
                                     if let Err(e) = cache.get_token().await {
@@ -695,6 +902,18 @@ fn main() {
                         })
                         .detach();
 
+                        // Track which Ollama models are loaded (footer + cold-start warning)
+                        cx.spawn(async move |cx: &mut AsyncApp| {
+                            settings::providers::poll_ollama_status(cx).await;
+                        })
+                        .detach();
+
+                        // Warn about expiring, revoked or exhausted keys before a send fails
+                        cx.spawn(async move |cx: &mut AsyncApp| {
+                            settings::providers::poll_provider_health(cx).await;
+                        })
+                        .detach();
+
                         // Always attempt to auto-sync curated OpenRouter models on startup
                         let openrouter_api_key = cx
                             .global::<settings::models::ProviderModel>()
@@ -772,6 +991,7 @@ fn main() {
             }
 
             // All tier-1 loads complete; trigger conversation loading
+            drop(settings_phase);
             info!("Models, providers, and execution settings loaded, triggering conversation load");
             cx.update(|cx| {
                 with_chatty_app(cx, |app, cx| {
@@ -786,8 +1006,21 @@ fn main() {
         // Load MCP server configurations asynchronously without blocking startup
         cx.spawn(async move |cx: &mut AsyncApp| {
             let repo = chatty_core::mcp_repository();
-            match repo.load_all().await {
+            let (servers_result, managed) = tokio::join!(
+                repo.load_all(),
+                chatty_core::services::managed_config_service::load(),
+            );
+            match servers_result {
                 Ok(servers) => {
+                    let (servers, overridden) = managed.config.apply_to_mcp_servers(servers);
+                    cx.update(|cx| {
+                        settings::controllers::managed_config_controller::record_overrides(
+                            "MCP server",
+                            overridden,
+                            cx,
+                        );
+                    })
+                    .ok();
                     let servers_clone = servers.clone();
                     cx.update(|cx| {
                         cx.update_global::<settings::models::McpServersModel, _>(|model, _cx| {
@@ -834,52 +1067,11 @@ fn main() {
                             info!("Injected Hive token into MCP server config for startup");
                         }
 
-                        // Connect to all enabled MCP servers and track auth status
+                        // Register enabled MCP servers; each connects on first tool use
                         let mcp_service = cx.global::<chatty::services::McpService>().clone();
-                        cx.spawn(async move |cx: &mut AsyncApp| {
-                            let results =
-                                mcp_service.connect_all_with_status(servers_clone).await;
-
-                            cx.update(|cx| {
-                                use settings::models::mcp_store::McpAuthStatus;
-
-                                // Derive status once per connection result, then
-                                // write to both models (ExtensionsModel is
-                                // canonical; McpServersModel kept for legacy).
-                                let statuses: Vec<(String, McpAuthStatus)> = results
-                                    .into_iter()
-                                    .map(|(name, ok, err_msg)| {
-                                        let status = if ok {
-                                            McpAuthStatus::Authenticated
-                                        } else if let Some(ref msg) = err_msg {
-                                            if msg.contains("Auth required")
-                                                || msg.contains("AuthRequired")
-                                            {
-                                                McpAuthStatus::NeedsAuth
-                                            } else {
-                                                McpAuthStatus::Failed(msg.clone())
-                                            }
-                                        } else {
-                                            McpAuthStatus::Failed("Unknown error".to_string())
-                                        };
-                                        (name, status)
-                                    })
-                                    .collect();
-
-                                let model = cx.global_mut::<settings::models::McpServersModel>();
-                                for (name, status) in &statuses {
-                                    model.set_auth_status(name.clone(), status.clone());
-                                }
-
-                                let ext_model = cx.global_mut::<settings::models::ExtensionsModel>();
-                                for (name, status) in statuses {
-                                    ext_model.set_mcp_auth_status(name, status);
-                                }
-
-                                cx.refresh_windows();
-                            })
-                            .map_err(|e| warn!(error = ?e, "Failed to update MCP auth statuses"))
-                            .ok();
+                        cx.spawn(async move |_cx: &mut AsyncApp| {
+                            let _phase = startup_profile::phase("mcp_register");
+                            mcp_service.register_all(servers_clone).await;
                         })
                         .detach();
                     })
@@ -989,6 +1181,49 @@ fn main() {
         })
         .detach();
 
+        // Load the snippet library asynchronously
+        cx.spawn(async move |cx: &mut AsyncApp| {
+            let repo = chatty_core::snippets_repository();
+            match repo.load().await {
+                Ok(snippets) => {
+                    let count = snippets.snippets.len();
+                    cx.update(|cx| {
+                        info!(count, "Snippets loaded from disk");
+                        cx.set_global(snippets);
+                    })
+                    .map_err(|e| warn!(error = ?e, "Failed to update global snippets"))
+                    .ok();
+                }
+                Err(e) => {
+                    warn!(error = ?e, "Failed to load snippets, using defaults");
+                }
+            }
+        })
+        .detach();
+
+        // Load installed prompt packs, offer their tools in the MCP catalog and
+        // look for newer published versions
+        cx.spawn(async move |cx: &mut AsyncApp| {
+            let repo = chatty_core::prompt_packs_repository();
+            match repo.load().await {
+                Ok(packs) => {
+                    let count = packs.packs.len();
+                    cx.update(|cx| {
+                        info!(count, "Prompt packs loaded from disk");
+                        cx.set_global(packs);
+                        settings::controllers::prompt_packs_controller::merge_catalog_entries(cx);
+                        settings::controllers::prompt_packs_controller::check_for_updates(cx);
+                    })
+                    .map_err(|e| warn!(error = ?e, "Failed to update global prompt packs"))
+                    .ok();
+                }
+                Err(e) => {
+                    warn!(error = ?e, "Failed to load prompt packs, using defaults");
+                }
+            }
+        })
+        .detach();
+
         // Load module settings asynchronously
         cx.spawn(async move |cx: &mut AsyncApp| {
             let repo = chatty_core::module_settings_repository();
@@ -1211,14 +1446,42 @@ fn main() {
         set_app_menus(cx);
 
         // Get platform-specific window options for main window
-        let options = settings::utils::window_utils::get_main_window_options();
+        let options = settings::utils::window_utils::get_main_window_options(
+            restored_session
+                .as_ref()
+                .and_then(|session| session.window.as_ref()),
+        );
 
         let repo = conversation_repo.clone();
         cx.open_window(options, |window, cx| {
-            let view = cx.new(|cx| ChattyApp::new(window, cx, repo.clone()));
+            window.on_next_frame(move |_window, cx| {
+                startup_profile::mark("first_paint");
+                init_renderers(cx);
+                first_paint_tx.send_replace(true);
+            });
+
+            let view = cx.new(|cx| ChattyApp::new(window, cx, repo.clone(), restored_session));
 
             cx.new(|cx| Root::new(view, window, cx))
         })
         .expect("Failed to open main window");
     });
 }
+
+/// Set up the math and mermaid renderers. Runs after the first frame; messages
+/// rendered before then show their source until the next repaint.
+fn init_renderers(cx: &mut App) {
+    let _phase = startup_profile::phase("renderers");
+
+    // Initialize math renderer service for LaTeX math rendering
+    let math_renderer = chatty::services::MathRendererService::new();
+    cx.set_global(math_renderer);
+    info!("Math renderer service initialized");
+
+    // Initialize mermaid renderer service for diagram rendering
+    let mermaid_renderer = chatty::services::MermaidRendererService::new();
+    cx.set_global(mermaid_renderer);
+    info!("Mermaid renderer service initialized");
+
+    cx.refresh_windows();
+}
@@ -0,0 +1,69 @@
+use crate::settings::models::ExecutionSettingsModel;
+use crate::settings::models::embedding_models_store::{EmbeddingModelConfig, EmbeddingModelsModel};
+use gpui::{App, AsyncApp};
+use tracing::{error, info};
+
+/// Register a new embedding model
+pub fn create_embedding_model(config: EmbeddingModelConfig, cx: &mut App) {
+    // 1. Apply update immediately (optimistic update)
+    cx.global_mut::<EmbeddingModelsModel>().add_model(config);
+
+    // 2. Get updated state for async save
+    let models_to_save = cx.global::<EmbeddingModelsModel>().models().to_vec();
+
+    // 3. Refresh UI immediately (optimistic update)
+    cx.refresh_windows();
+
+    // 4. Save async with error handling
+    save_embedding_models_async(models_to_save, cx);
+}
+
+/// Delete an embedding model by ID.
+///
+/// If the memory store was using it, the selection is cleared so memory falls
+/// back to keyword-only search instead of pointing at a missing model. The
+/// skills index falls back to the memory model.
+pub fn delete_embedding_model(model_id: String, cx: &mut App) {
+    if !cx
+        .global_mut::<EmbeddingModelsModel>()
+        .delete_model(&model_id)
+    {
+        error!("Failed to delete embedding model: model not found");
+        return;
+    }
+
+    let models_to_save = cx.global::<EmbeddingModelsModel>().models().to_vec();
+
+    if cx
+        .global::<ExecutionSettingsModel>()
+        .embedding_model_id
+        .as_deref()
+        == Some(model_id.as_str())
+    {
+        info!(model_id = %model_id, "Deleted embedding model was selected for memory; clearing");
+        super::execution_settings_controller::select_embedding_model(None, cx);
+    }
+    if cx
+        .global::<ExecutionSettingsModel>()
+        .skills_embedding_model_id
+        .as_deref()
+        == Some(model_id.as_str())
+    {
+        info!(model_id = %model_id, "Deleted embedding model was selected for skills; clearing");
+        super::execution_settings_controller::select_skills_embedding_model(None, cx);
+    }
+
+    cx.refresh_windows();
+    save_embedding_models_async(models_to_save, cx);
+}
+
+/// Save embedding models asynchronously to disk
+fn save_embedding_models_async(models: Vec<EmbeddingModelConfig>, cx: &mut App) {
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::embedding_models_repository();
+        if let Err(e) = repo.save_all(models).await {
+            error!(error = ?e, "Failed to save embedding models, changes will be lost on restart");
+        }
+    })
+    .detach();
+}
@@ -1,6 +1,11 @@
-use crate::settings::models::execution_settings::{ApprovalMode, ExecutionSettingsModel};
+use crate::settings::models::execution_settings::{
+    ApprovalMode, DockerCleanup, ExecutionSettingsModel,
+};
 use crate::settings::models::{AgentConfigEvent, GlobalAgentConfigNotifier};
-use chatty_core::services::MemoryService;
+use chatty_core::services::citation_service::CitationStyle;
+use chatty_core::services::ssh_service::SshHostProfile;
+use chatty_core::services::{GuardrailRule, MemoryService};
+use chatty_core::tools::{CustomHttpToolConfig, GraphQlEndpointConfig};
 use gpui::{App, AsyncApp};
 use tracing::{debug, error, info, warn};
 
@@ -275,6 +280,207 @@ pub fn toggle_git(cx: &mut App) {
     .detach();
 }
 
+/// Toggle the kubectl-backed Kubernetes tools enabled/disabled and persist to disk.
+pub fn toggle_kubernetes(cx: &mut App) {
+    // 1. Apply update immediately (optimistic update)
+    let new_enabled = !cx.global::<ExecutionSettingsModel>().kubernetes_enabled;
+    cx.global_mut::<ExecutionSettingsModel>().kubernetes_enabled = new_enabled;
+
+    // 2. Get updated state for async save
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+
+    // 3. Refresh UI immediately (optimistic update)
+    cx.refresh_windows();
+
+    // 4. Notify so the active conversation's agent is rebuilt with the new tool set
+    notify_tool_set_changed(cx);
+
+    // 5. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
+/// Toggle whether the k8s_action tool (delete, restart, scale, cordon) is offered.
+pub fn toggle_kubernetes_mutations(cx: &mut App) {
+    // 1. Apply update immediately (optimistic update)
+    let new_enabled = !cx
+        .global::<ExecutionSettingsModel>()
+        .kubernetes_allow_mutations;
+    cx.global_mut::<ExecutionSettingsModel>()
+        .kubernetes_allow_mutations = new_enabled;
+
+    // 2. Get updated state for async save
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+
+    // 3. Refresh UI immediately (optimistic update)
+    cx.refresh_windows();
+
+    // 4. Notify so the active conversation's agent is rebuilt with the new tool set
+    notify_tool_set_changed(cx);
+
+    // 5. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
+/// Update the kubeconfig contexts and namespaces the Kubernetes tools may use
+/// and persist to disk.
+pub fn set_kubernetes_scope(contexts: Vec<String>, namespaces: Vec<String>, cx: &mut App) {
+    // 1. Apply update immediately
+    let settings = cx.global_mut::<ExecutionSettingsModel>();
+    settings.kubernetes_contexts = contexts;
+    settings.kubernetes_namespaces = namespaces;
+
+    // 2. Get updated state for async save
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+
+    // 3. Refresh UI immediately
+    cx.refresh_windows();
+
+    // 4. Notify so the active conversation's agent is rebuilt with the new scope
+    notify_tool_set_changed(cx);
+
+    // 5. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
+/// Toggle the docker tool (containers, logs, exec, compose) and persist to disk.
+pub fn toggle_docker_tools(cx: &mut App) {
+    // 1. Apply update immediately (optimistic update)
+    let new_enabled = !cx.global::<ExecutionSettingsModel>().docker_tools_enabled;
+    cx.global_mut::<ExecutionSettingsModel>()
+        .docker_tools_enabled = new_enabled;
+
+    // 2. Get updated state for async save
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+
+    // 3. Refresh UI immediately (optimistic update)
+    cx.refresh_windows();
+
+    // 4. Notify so the active conversation's agent is rebuilt with the new tool set
+    notify_tool_set_changed(cx);
+
+    // 5. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
+/// Set what happens to compose stacks when their conversation is deleted.
+pub fn set_docker_cleanup(cleanup: DockerCleanup, cx: &mut App) {
+    // 1. Apply update immediately
+    cx.global_mut::<ExecutionSettingsModel>().docker_cleanup = cleanup;
+
+    // 2. Get updated state for async save
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+
+    // 3. Refresh UI immediately
+    cx.refresh_windows();
+
+    // 4. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
+/// Toggle planner mode (create_plan/update_plan checklist) and persist to disk.
+pub fn toggle_planner_mode(cx: &mut App) {
+    // 1. Apply update immediately (optimistic update)
+    let new_enabled = !cx.global::<ExecutionSettingsModel>().planner_mode;
+    cx.global_mut::<ExecutionSettingsModel>().planner_mode = new_enabled;
+
+    // 2. Get updated state for async save
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+
+    // 3. Refresh UI immediately (optimistic update)
+    cx.refresh_windows();
+
+    // 4. Notify so the active conversation's agent is rebuilt with the plan tools
+    notify_tool_set_changed(cx);
+
+    // 5. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
+/// Toggle self-reflection (critique and one retry per answer) and persist to disk.
+pub fn toggle_self_reflection(cx: &mut App) {
+    // 1. Apply update immediately (optimistic update)
+    let new_enabled = !cx.global::<ExecutionSettingsModel>().self_reflection;
+    info!(enabled = new_enabled, "Toggling self-reflection");
+    cx.global_mut::<ExecutionSettingsModel>().self_reflection = new_enabled;
+
+    // 2. Get updated state for async save
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+
+    // 3. Refresh UI immediately (optimistic update)
+    cx.refresh_windows();
+
+    // 4. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
+/// Update the critique score an answer needs to pass without a retry and
+/// persist to disk. Clamps the value to 1–5.
+pub fn set_reflection_threshold(threshold: u8, cx: &mut App) {
+    let threshold = threshold.clamp(1, 5);
+
+    // 1. Apply update immediately
+    info!(threshold, "Setting self-reflection threshold");
+    cx.global_mut::<ExecutionSettingsModel>()
+        .reflection_threshold = threshold;
+
+    // 2. Get updated state for async save
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+
+    // 3. Refresh UI immediately
+    cx.refresh_windows();
+
+    // 4. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
 /// Update the shell timeout in seconds and persist to disk.
 /// Clamps the value to 1–600 seconds.
 pub fn set_timeout_seconds(seconds: u32, cx: &mut App) {
@@ -347,6 +553,29 @@ pub fn set_max_agent_turns(turns: u32, cx: &mut App) {
     .detach();
 }
 
+/// Set how long an unused MCP server stays connected (0 = never disconnect).
+pub fn set_mcp_idle_shutdown_minutes(minutes: u32, cx: &mut App) {
+    // 1. Apply update immediately
+    info!(minutes, "Setting MCP idle shutdown");
+    cx.global_mut::<ExecutionSettingsModel>()
+        .mcp_idle_shutdown_minutes = minutes;
+
+    // 2. Get updated state for async save
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+
+    // 3. Refresh UI immediately
+    cx.refresh_windows();
+
+    // 4. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
 /// Toggle agent memory enabled/disabled and persist to disk.
 ///
 /// When toggled ON, initializes the MemoryService global if not already present.
@@ -422,13 +651,12 @@ pub fn purge_memory(cx: &mut App) {
 /// TODO: Re-fetch the token on each embedding call or add expiry tracking so that
 /// long-running sessions (>1h) do not start producing authentication errors.
 async fn init_azure_entra_embedding(
-    provider_type: chatty_core::settings::models::providers_store::ProviderType,
-    model_name: String,
+    config: chatty_core::settings::models::embedding_models_store::EmbeddingModelConfig,
     base_url: Option<String>,
     mem_svc: Option<MemoryService>,
     cx: &mut AsyncApp,
 ) {
-    use chatty_core::services::embedding_service::try_create_embedding_service;
+    use chatty_core::services::embedding_service::try_create_embedding_service_from_config;
 
     info!(
         "Fetching Entra ID token for Azure OpenAI embeddings — service will be available shortly"
@@ -441,18 +669,13 @@ async fn init_azure_entra_embedding(
         }
     };
     // api_key is None for Entra ID; the bearer token handles auth
-    if let Some(embed_svc) = try_create_embedding_service(
-        &provider_type,
-        &model_name,
-        None,
-        base_url.as_deref(),
-        azure_token,
-    ) {
+    if let Some(embed_svc) =
+        try_create_embedding_service_from_config(&config, None, base_url.as_deref(), azure_token)
+    {
         let model_id = embed_svc.model_identifier();
         cx.update(|cx| {
-            let skill_service = chatty_core::services::SkillService::new(Some(embed_svc.clone()));
             cx.set_global(embed_svc);
-            cx.set_global(skill_service);
+            reinit_skill_service(cx);
         })
         .map_err(|e| warn!(error = ?e, "Failed to set EmbeddingService and SkillService globals"))
         .ok();
@@ -472,87 +695,15 @@ async fn init_azure_entra_embedding(
 /// Toggle semantic (vector) search for memory.
 /// Initializes or removes the EmbeddingService global accordingly.
 pub fn toggle_embedding(cx: &mut App) {
-    use chatty_core::services::embedding_service::{
-        EmbeddingService, try_create_embedding_service,
-    };
+    use chatty_core::services::embedding_service::EmbeddingService;
 
     let new_enabled = !cx.global::<ExecutionSettingsModel>().embedding_enabled;
     info!(new = new_enabled, "Toggling semantic search");
     cx.global_mut::<ExecutionSettingsModel>().embedding_enabled = new_enabled;
 
-    if new_enabled {
-        // Try to initialize EmbeddingService if not already set
-        if cx.try_global::<EmbeddingService>().is_none() {
-            use chatty_core::settings::models::providers_store::{AzureAuthMethod, ProviderType};
-
-            let settings = cx.global::<ExecutionSettingsModel>().clone();
-            if let (Some(provider_type), Some(model_name)) = (
-                settings.embedding_provider.as_ref(),
-                settings.embedding_model.as_ref(),
-            ) {
-                let provider_config = cx
-                    .try_global::<chatty_core::settings::models::ProviderModel>()
-                    .and_then(|pm| {
-                        pm.providers()
-                            .iter()
-                            .find(|p| &p.provider_type == provider_type)
-                            .cloned()
-                    });
-                let base_url = provider_config.as_ref().and_then(|p| p.base_url.clone());
-                let is_azure_entra = *provider_type == ProviderType::AzureOpenAI
-                    && provider_config.as_ref().map(|p| p.azure_auth_method())
-                        == Some(AzureAuthMethod::EntraId);
-
-                if is_azure_entra {
-                    // Entra ID requires async token fetch — spawn a task
-                    let provider_type = provider_type.clone();
-                    let model_name = model_name.clone();
-                    let mem_svc = cx.try_global::<MemoryService>().cloned();
-                    cx.spawn(async move |cx| {
-                        init_azure_entra_embedding(
-                            provider_type,
-                            model_name,
-                            base_url,
-                            mem_svc,
-                            cx,
-                        )
-                        .await;
-                    })
-                    .detach();
-                } else {
-                    // API key auth — initialize synchronously
-                    let api_key = provider_config.as_ref().and_then(|p| p.api_key.clone());
-                    if let Some(embed_svc) = try_create_embedding_service(
-                        provider_type,
-                        model_name,
-                        api_key.as_deref(),
-                        base_url.as_deref(),
-                        None,
-                    ) {
-                        let model_id = embed_svc.model_identifier();
-                        let skill_service =
-                            chatty_core::services::SkillService::new(Some(embed_svc.clone()));
-                        cx.set_global(embed_svc);
-                        cx.set_global(skill_service);
-
-                        // Enable vector index on memory service
-                        let mem_svc = cx.try_global::<MemoryService>().cloned();
-                        if let Some(mem_svc) = mem_svc {
-                            cx.spawn(async move |_cx: &mut AsyncApp| {
-                                if let Err(e) = mem_svc.enable_vec().await {
-                                    warn!(error = ?e, "Failed to enable vector index");
-                                } else if let Err(e) = mem_svc.set_vec_model(&model_id).await {
-                                    warn!(error = ?e, "Failed to set vector model");
-                                } else {
-                                    info!(model = %model_id, "Vector search enabled for memory");
-                                }
-                            })
-                            .detach();
-                        }
-                    }
-                }
-            }
-        }
+    // Initialize EmbeddingService if not already set
+    if new_enabled && cx.try_global::<EmbeddingService>().is_none() {
+        reinit_embedding_service(cx);
     }
 
     let settings = cx.global::<ExecutionSettingsModel>().clone();
@@ -568,6 +719,50 @@ pub fn toggle_embedding(cx: &mut App) {
     .detach();
 }
 
+/// Select a registered embedding model for the memory vector store
+/// (`None` falls back to the provider/model fields) and reinitialize the
+/// EmbeddingService.
+pub fn select_embedding_model(model_id: Option<String>, cx: &mut App) {
+    info!(model_id = ?model_id, "Selecting embedding model");
+    cx.global_mut::<ExecutionSettingsModel>().embedding_model_id = model_id;
+
+    reinit_embedding_service(cx);
+
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+    cx.refresh_windows();
+    notify_tool_set_changed(cx);
+
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
+/// Select a registered embedding model for the skills index (`None` uses
+/// the memory model) and reinitialize the SkillService.
+pub fn select_skills_embedding_model(model_id: Option<String>, cx: &mut App) {
+    info!(model_id = ?model_id, "Selecting skills embedding model");
+    cx.global_mut::<ExecutionSettingsModel>()
+        .skills_embedding_model_id = model_id;
+
+    reinit_skill_service(cx);
+
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+    cx.refresh_windows();
+    notify_tool_set_changed(cx);
+
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
 /// Set the embedding provider and reinitialize the EmbeddingService.
 pub fn set_embedding_provider(
     provider_type: chatty_core::settings::models::providers_store::ProviderType,
@@ -621,9 +816,34 @@ pub fn set_embedding_model(model: Option<String>, cx: &mut App) {
     .detach();
 }
 
+/// Set the keyword-vs-vector weighting for hybrid memory search and persist to disk.
+pub fn set_hybrid_keyword_weight(weight: f32, cx: &mut App) {
+    let weight = weight.clamp(0.0, 1.0);
+    if (cx.global::<ExecutionSettingsModel>().hybrid_keyword_weight - weight).abs() < f32::EPSILON {
+        return;
+    }
+    cx.global_mut::<ExecutionSettingsModel>()
+        .hybrid_keyword_weight = weight;
+
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+    cx.refresh_windows();
+
+    // Notify so the search_memory tool is rebuilt with the new weighting
+    notify_tool_set_changed(cx);
+
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
 /// Recreate the EmbeddingService global from current settings.
 fn reinit_embedding_service(cx: &mut App) {
-    use chatty_core::services::embedding_service::try_create_embedding_service;
+    use chatty_core::services::embedding_service::try_create_embedding_service_from_config;
+    use chatty_core::settings::models::EmbeddingModelsModel;
     use chatty_core::settings::models::providers_store::{AzureAuthMethod, ProviderType};
 
     let settings = cx.global::<ExecutionSettingsModel>();
@@ -631,10 +851,12 @@ fn reinit_embedding_service(cx: &mut App) {
         return;
     }
 
-    let (Some(provider_type), Some(model_name)) = (
-        settings.embedding_provider.clone(),
-        settings.embedding_model.clone(),
-    ) else {
+    let Some(config) = cx
+        .try_global::<EmbeddingModelsModel>()
+        .cloned()
+        .unwrap_or_default()
+        .resolve(settings)
+    else {
         return;
     };
 
@@ -643,12 +865,12 @@ fn reinit_embedding_service(cx: &mut App) {
         .and_then(|pm| {
             pm.providers()
                 .iter()
-                .find(|p| p.provider_type == provider_type)
+                .find(|p| p.provider_type == config.provider_type)
                 .cloned()
         });
     let api_key = provider_config.as_ref().and_then(|p| p.api_key.clone());
     let base_url = provider_config.as_ref().and_then(|p| p.base_url.clone());
-    let is_azure_entra = provider_type == ProviderType::AzureOpenAI
+    let is_azure_entra = config.provider_type == ProviderType::AzureOpenAI
         && provider_config.as_ref().map(|p| p.azure_auth_method())
             == Some(AzureAuthMethod::EntraId);
 
@@ -656,20 +878,18 @@ fn reinit_embedding_service(cx: &mut App) {
         // Entra ID requires async token fetch — spawn a task
         let mem_svc = cx.try_global::<MemoryService>().cloned();
         cx.spawn(async move |cx| {
-            init_azure_entra_embedding(provider_type, model_name, base_url, mem_svc, cx).await;
+            init_azure_entra_embedding(config, base_url, mem_svc, cx).await;
         })
         .detach();
-    } else if let Some(embed_svc) = try_create_embedding_service(
-        &provider_type,
-        &model_name,
+    } else if let Some(embed_svc) = try_create_embedding_service_from_config(
+        &config,
         api_key.as_deref(), // api_key unused for Entra ID; only used in this API key branch
         base_url.as_deref(),
         None,
     ) {
         let model_id = embed_svc.model_identifier();
-        let skill_service = chatty_core::services::SkillService::new(Some(embed_svc.clone()));
         cx.set_global(embed_svc);
-        cx.set_global(skill_service);
+        reinit_skill_service(cx);
 
         let mem_svc = cx.try_global::<MemoryService>().cloned();
         if let Some(mem_svc) = mem_svc {
@@ -687,6 +907,45 @@ fn reinit_embedding_service(cx: &mut App) {
     }
 }
 
+/// Recreate the SkillService global. The skills index shares the memory
+/// EmbeddingService unless a different model is selected for it, in which
+/// case its own service is created.
+pub fn reinit_skill_service(cx: &mut App) {
+    use chatty_core::services::SkillService;
+    use chatty_core::services::embedding_service::{
+        EmbeddingService, create_embedding_service_with_providers,
+    };
+    use chatty_core::settings::models::EmbeddingModelsModel;
+    use chatty_core::settings::models::embedding_models_store::KnowledgeBase;
+
+    let settings = cx.global::<ExecutionSettingsModel>();
+    let models = cx
+        .try_global::<EmbeddingModelsModel>()
+        .cloned()
+        .unwrap_or_default();
+    let skills_config = models.resolve_for(KnowledgeBase::Skills, settings);
+    let own_model = settings.embedding_enabled
+        && skills_config != models.resolve_for(KnowledgeBase::Memory, settings);
+
+    let Some(config) = skills_config.filter(|_| own_model) else {
+        let memory_svc = cx.try_global::<EmbeddingService>().cloned();
+        cx.set_global(SkillService::new(memory_svc));
+        return;
+    };
+
+    let providers = cx
+        .try_global::<chatty_core::settings::models::ProviderModel>()
+        .map(|pm| pm.providers().to_vec())
+        .unwrap_or_default();
+    cx.spawn(async move |cx| {
+        let embed_svc = create_embedding_service_with_providers(&config, &providers).await;
+        cx.update(|cx| cx.set_global(SkillService::new(embed_svc)))
+            .map_err(|e| warn!(error = ?e, "Failed to set SkillService global"))
+            .ok();
+    })
+    .detach();
+}
+
 /// Toggle filesystem write tools enabled/disabled and persist to disk
 pub fn toggle_filesystem_write(cx: &mut App) {
     // 1. Apply update immediately (optimistic update)
@@ -714,3 +973,221 @@ pub fn toggle_filesystem_write(cx: &mut App) {
     })
     .detach();
 }
+
+/// Persist the execution settings after a guardrail or custom tool edit and
+/// rebuild the agent so its tool set picks up the change.
+fn save_tool_definitions(cx: &mut App) {
+    let settings = cx.global::<ExecutionSettingsModel>().clone();
+    cx.refresh_windows();
+    notify_tool_set_changed(cx);
+
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::execution_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save execution settings");
+        }
+    })
+    .detach();
+}
+
+/// Add a guardrail rule and persist to disk.
+pub fn add_guardrail_rule(rule: GuardrailRule, cx: &mut App) {
+    info!(name = %rule.name, "Adding guardrail rule");
+    cx.global_mut::<ExecutionSettingsModel>()
+        .guardrail_rules
+        .push(rule);
+    save_tool_definitions(cx);
+}
+
+/// Remove a guardrail rule by ID and persist to disk.
+pub fn remove_guardrail_rule(id: &str, cx: &mut App) {
+    info!(id = %id, "Removing guardrail rule");
+    cx.global_mut::<ExecutionSettingsModel>()
+        .guardrail_rules
+        .retain(|rule| rule.id != id);
+    save_tool_definitions(cx);
+}
+
+/// Enable or disable a guardrail rule by ID and persist to disk.
+pub fn toggle_guardrail_rule(id: &str, cx: &mut App) {
+    let settings = cx.global_mut::<ExecutionSettingsModel>();
+    let Some(rule) = settings.guardrail_rules.iter_mut().find(|r| r.id == id) else {
+        warn!(id = %id, "toggle_guardrail_rule: rule not found");
+        return;
+    };
+    rule.enabled = !rule.enabled;
+    info!(id = %id, enabled = rule.enabled, "Toggling guardrail rule");
+    save_tool_definitions(cx);
+}
+
+/// Add or replace (by ID) a user-defined REST tool and persist to disk.
+pub fn upsert_custom_http_tool(tool: CustomHttpToolConfig, cx: &mut App) {
+    info!(name = %tool.name, "Saving custom HTTP tool");
+    let tools = &mut cx.global_mut::<ExecutionSettingsModel>().custom_http_tools;
+    match tools.iter_mut().find(|t| t.id == tool.id) {
+        Some(existing) => *existing = tool,
+        None => tools.push(tool),
+    }
+    save_tool_definitions(cx);
+}
+
+/// Remove a user-defined REST tool by ID and persist to disk.
+pub fn remove_custom_http_tool(id: &str, cx: &mut App) {
+    info!(id = %id, "Removing custom HTTP tool");
+    cx.global_mut::<ExecutionSettingsModel>()
+        .custom_http_tools
+        .retain(|tool| tool.id != id);
+    save_tool_definitions(cx);
+}
+
+/// Enable or disable a user-defined REST tool by ID and persist to disk.
+pub fn toggle_custom_http_tool(id: &str, cx: &mut App) {
+    let settings = cx.global_mut::<ExecutionSettingsModel>();
+    let Some(tool) = settings.custom_http_tools.iter_mut().find(|t| t.id == id) else {
+        warn!(id = %id, "toggle_custom_http_tool: tool not found");
+        return;
+    };
+    tool.enabled = !tool.enabled;
+    info!(id = %id, enabled = tool.enabled, "Toggling custom HTTP tool");
+    save_tool_definitions(cx);
+}
+
+/// Add tools generated from an OpenAPI spec (see `merge_imported_tools`) and
+/// persist to disk. Returns the number of tools added or replaced.
+pub fn import_custom_http_tools(tools: Vec<CustomHttpToolConfig>, cx: &mut App) -> usize {
+    let existing = &mut cx.global_mut::<ExecutionSettingsModel>().custom_http_tools;
+    let count = chatty_core::services::openapi_import::merge_imported_tools(existing, tools);
+    info!(count, "Imported custom HTTP tools");
+    save_tool_definitions(cx);
+    count
+}
+
+/// Enable or disable every tool imported from the OpenAPI spec `source`.
+pub fn set_custom_http_source_enabled(source: &str, enabled: bool, cx: &mut App) {
+    info!(source = %source, enabled, "Toggling imported API tools");
+    cx.global_mut::<ExecutionSettingsModel>()
+        .custom_http_tools
+        .iter_mut()
+        .filter(|tool| tool.source.as_deref() == Some(source))
+        .for_each(|tool| tool.enabled = enabled);
+    save_tool_definitions(cx);
+}
+
+/// Add or replace (by ID) a GraphQL endpoint and persist to disk.
+pub fn upsert_graphql_endpoint(endpoint: GraphQlEndpointConfig, cx: &mut App) {
+    info!(name = %endpoint.name, "Saving GraphQL endpoint");
+    let endpoints = &mut cx.global_mut::<ExecutionSettingsModel>().graphql_endpoints;
+    match endpoints.iter_mut().find(|e| e.id == endpoint.id) {
+        Some(existing) => *existing = endpoint,
+        None => endpoints.push(endpoint),
+    }
+    save_tool_definitions(cx);
+}
+
+/// Remove a GraphQL endpoint by ID and persist to disk.
+pub fn remove_graphql_endpoint(id: &str, cx: &mut App) {
+    info!(id = %id, "Removing GraphQL endpoint");
+    cx.global_mut::<ExecutionSettingsModel>()
+        .graphql_endpoints
+        .retain(|endpoint| endpoint.id != id);
+    save_tool_definitions(cx);
+}
+
+/// Enable or disable a GraphQL endpoint by ID and persist to disk.
+pub fn toggle_graphql_endpoint(id: &str, cx: &mut App) {
+    let settings = cx.global_mut::<ExecutionSettingsModel>();
+    let Some(endpoint) = settings.graphql_endpoints.iter_mut().find(|e| e.id == id) else {
+        warn!(id = %id, "toggle_graphql_endpoint: endpoint not found");
+        return;
+    };
+    endpoint.enabled = !endpoint.enabled;
+    info!(id = %id, enabled = endpoint.enabled, "Toggling GraphQL endpoint");
+    save_tool_definitions(cx);
+}
+
+/// Toggle the ssh_execute tool enabled/disabled and persist to disk.
+pub fn toggle_ssh(cx: &mut App) {
+    let settings = cx.global_mut::<ExecutionSettingsModel>();
+    settings.ssh_enabled = !settings.ssh_enabled;
+    info!(enabled = settings.ssh_enabled, "Toggling SSH tool");
+    save_tool_definitions(cx);
+}
+
+/// Add or replace (by ID) an SSH host profile and persist to disk.
+pub fn upsert_ssh_host(host: SshHostProfile, cx: &mut App) {
+    info!(name = %host.name, "Saving SSH host profile");
+    let hosts = &mut cx.global_mut::<ExecutionSettingsModel>().ssh_hosts;
+    match hosts.iter_mut().find(|h| h.id == host.id) {
+        Some(existing) => *existing = host,
+        None => hosts.push(host),
+    }
+    save_tool_definitions(cx);
+}
+
+/// Remove an SSH host profile by ID and persist to disk.
+pub fn remove_ssh_host(id: &str, cx: &mut App) {
+    info!(id = %id, "Removing SSH host profile");
+    cx.global_mut::<ExecutionSettingsModel>()
+        .ssh_hosts
+        .retain(|host| host.id != id);
+    save_tool_definitions(cx);
+}
+
+/// Enable or disable an SSH host profile by ID and persist to disk.
+pub fn toggle_ssh_host(id: &str, cx: &mut App) {
+    let settings = cx.global_mut::<ExecutionSettingsModel>();
+    let Some(host) = settings.ssh_hosts.iter_mut().find(|h| h.id == id) else {
+        warn!(id = %id, "toggle_ssh_host: host not found");
+        return;
+    };
+    host.enabled = !host.enabled;
+    info!(id = %id, enabled = host.enabled, "Toggling SSH host profile");
+    save_tool_definitions(cx);
+}
+
+/// Toggle the notes tools (notes_search, notes_read, notes_append) and persist to disk.
+pub fn toggle_notes(cx: &mut App) {
+    let settings = cx.global_mut::<ExecutionSettingsModel>();
+    settings.notes_enabled = !settings.notes_enabled;
+    info!(enabled = settings.notes_enabled, "Toggling notes tools");
+    save_tool_definitions(cx);
+}
+
+/// Set the Markdown notes vault directory and persist to disk.
+pub fn set_notes_vault_dir(dir: Option<String>, cx: &mut App) {
+    info!(vault = ?dir, "Setting notes vault");
+    cx.global_mut::<ExecutionSettingsModel>().notes_vault_dir = dir;
+    save_tool_definitions(cx);
+}
+
+/// Toggle the citations tool and persist to disk.
+pub fn toggle_citations(cx: &mut App) {
+    let settings = cx.global_mut::<ExecutionSettingsModel>();
+    settings.citations_enabled = !settings.citations_enabled;
+    info!(
+        enabled = settings.citations_enabled,
+        "Toggling citations tool"
+    );
+    save_tool_definitions(cx);
+}
+
+/// Set the BibTeX library file and persist to disk.
+pub fn set_bibtex_path(path: Option<String>, cx: &mut App) {
+    info!(path = ?path, "Setting BibTeX library");
+    cx.global_mut::<ExecutionSettingsModel>().bibtex_path = path;
+    save_tool_definitions(cx);
+}
+
+/// Set the Zotero local API base URL and persist to disk.
+pub fn set_zotero_api_url(url: Option<String>, cx: &mut App) {
+    info!(url = ?url, "Setting Zotero API URL");
+    cx.global_mut::<ExecutionSettingsModel>().zotero_api_url = url;
+    save_tool_definitions(cx);
+}
+
+/// Set the bibliography style used for formatted references and persist to disk.
+pub fn set_citation_style(style: CitationStyle, cx: &mut App) {
+    info!(style = ?style, "Setting citation style");
+    cx.global_mut::<ExecutionSettingsModel>().citation_style = style;
+    save_tool_definitions(cx);
+}
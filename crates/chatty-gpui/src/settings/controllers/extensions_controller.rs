@@ -1,9 +1,11 @@
 use crate::chatty::services::mcp_service::McpService;
-use crate::settings::controllers::module_settings_controller;
+use crate::settings::controllers::{module_settings_controller, user_secrets_controller};
 use crate::settings::models::marketplace_state::MarketplaceState;
 use crate::settings::models::{AgentConfigEvent, GlobalAgentConfigNotifier};
+use crate::settings::models::{McpCatalogState, UserSecretsModel};
 use chatty_core::hive::HiveRegistryClient;
 use chatty_core::install;
+use chatty_core::mcp_catalog::{self, McpCatalogEntry};
 use chatty_core::services::A2aClient;
 use chatty_core::settings::models::a2a_store::A2aAgentStatus;
 use chatty_core::settings::models::extensions_store::{
@@ -12,6 +14,7 @@ use chatty_core::settings::models::extensions_store::{
 use chatty_core::settings::models::hive_settings::HiveSettingsModel;
 use chatty_core::settings::models::mcp_store::{McpAuthStatus, McpServerConfig, McpServersModel};
 use gpui::{App, AsyncApp};
+use std::collections::{HashMap, HashSet};
 use tracing::{error, info, warn};
 
 // ── Default Hive MCP ──────────────────────────────────────────────────────
@@ -425,6 +428,42 @@ pub fn set_execution_mode(id: String, mode: String, cx: &mut App) {
     cx.refresh_windows();
 }
 
+/// Set how long identical tool calls to an MCP server reuse their previous
+/// result (0 disables caching). Results cached so far are dropped.
+pub fn set_mcp_result_cache_ttl(id: String, secs: u64, cx: &mut App) {
+    let extensions = cx.global_mut::<ExtensionsModel>();
+    let Some(ext) = extensions.find_mut(&id) else {
+        return;
+    };
+    let ExtensionKind::McpServer(ref mut config) = ext.kind else {
+        return;
+    };
+    config.result_cache_ttl_secs = secs;
+    let name = config.name.clone();
+    info!(server = %name, secs, "Set MCP result cache TTL");
+    save_extensions_async(extensions.clone(), cx);
+
+    let model = cx.global_mut::<McpServersModel>();
+    if let Some(server) = model.servers_mut().iter_mut().find(|s| s.name == name) {
+        server.result_cache_ttl_secs = secs;
+        save_servers_async(model.servers().to_vec(), cx);
+    }
+
+    let service = cx.global::<McpService>().clone();
+    cx.spawn(async move |_cx: &mut AsyncApp| {
+        service.set_result_cache_ttl(&name, secs).await;
+    })
+    .detach();
+    cx.refresh_windows();
+}
+
+/// Drop an MCP server's cached tool results so the next calls reach the server.
+pub fn clear_mcp_result_cache(name: String, cx: &mut App) {
+    cx.global::<McpService>()
+        .invalidate_cached_results(Some(&name));
+    info!(server = %name, "Cleared MCP result cache");
+}
+
 /// Toggle the enabled state of an extension.
 pub fn toggle_extension(id: String, cx: &mut App) {
     let extensions = cx.global_mut::<ExtensionsModel>();
@@ -581,6 +620,9 @@ pub fn add_custom_mcp(name: String, url: String, api_key: Option<String>, cx: &m
         api_key,
         enabled: true,
         is_module: false,
+        result_cache_ttl_secs: 0,
+        cached_tools: Vec::new(),
+        launch: None,
     };
 
     // Add to unified extensions store
@@ -615,6 +657,85 @@ pub fn add_custom_mcp(name: String, url: String, api_key: Option<String>, cx: &m
     info!(server = %name, "Created custom MCP server extension");
 }
 
+// ── MCP catalog ────────────────────────────────────────────────────────────
+
+/// Fetch the published MCP catalog and merge it over the bundled index.
+pub fn refresh_mcp_catalog(cx: &mut App) {
+    {
+        let state = cx.global_mut::<McpCatalogState>();
+        state.loading = true;
+        state.error = None;
+    }
+    cx.refresh_windows();
+
+    cx.spawn(async move |cx| {
+        let result = mcp_catalog::fetch_catalog().await;
+        cx.update(|cx| {
+            let state = cx.global_mut::<McpCatalogState>();
+            state.loading = false;
+            match result {
+                Ok(entries) => state.entries = entries,
+                Err(e) => state.error = Some(e.to_string()),
+            }
+            cx.refresh_windows();
+        })
+        .map_err(|e| warn!(error = ?e, "Failed to update UI after MCP catalog refresh"))
+        .ok();
+    })
+    .detach();
+}
+
+/// Install or update a catalog entry with the values the user entered.
+/// Secret inputs are saved as user secrets; the config only references them.
+pub fn install_mcp_catalog_entry(
+    entry: McpCatalogEntry,
+    inputs: HashMap<String, String>,
+    cx: &mut App,
+) -> Result<(), String> {
+    let known_secrets: HashSet<String> = cx
+        .global::<UserSecretsModel>()
+        .secrets
+        .iter()
+        .map(|s| s.key.clone())
+        .collect();
+    let mut servers = cx.global::<McpServersModel>().servers().to_vec();
+    let extensions = cx.global_mut::<ExtensionsModel>();
+    let (config, enabled) =
+        mcp_catalog::install_entry(&entry, &inputs, &known_secrets, extensions, &mut servers)
+            .map_err(|e| e.to_string())?;
+    save_extensions_async(extensions.clone(), cx);
+    cx.global_mut::<McpServersModel>()
+        .replace_all(servers.clone());
+    save_servers_async(servers, cx);
+    for (key, value) in entry.secret_values(&inputs) {
+        user_secrets_controller::add_secret(key, value, cx);
+    }
+    info!(server = %config.name, version = %entry.version, "Installed MCP catalog entry");
+
+    // Drop a connection made with the previous settings. The secrets are
+    // saved first because the service resolves them from disk.
+    let secrets = cx.global::<UserSecretsModel>().clone();
+    let service = cx.global::<McpService>().clone();
+    cx.spawn(async move |cx| {
+        if let Err(e) = chatty_core::user_secrets_repository().save(secrets).await {
+            error!(error = ?e, "Failed to save user secrets");
+        }
+        let _ = service.disconnect_server(&config.name).await;
+        if enabled && let Err(e) = service.connect_server(config).await {
+            error!(error = ?e, "Failed to connect to installed MCP server");
+        }
+        cx.update(|cx| {
+            emit_rebuild_required(cx);
+        })
+        .map_err(|e| warn!(error = ?e, "Failed to emit rebuild after MCP install"))
+        .ok();
+    })
+    .detach();
+
+    cx.refresh_windows();
+    Ok(())
+}
+
 // ── Hive ↔ MCP token sync ──────────────────────────────────────────────────
 
 /// Propagate the Hive JWT token into the "hive" MCP server's `api_key` so
@@ -685,13 +806,13 @@ fn save_servers_async(servers: Vec<McpServerConfig>, cx: &mut App) {
     cx.spawn(|_cx: &mut AsyncApp| async move {
         let repo = chatty_core::mcp_repository();
         if let Err(e) = repo.save_all(servers).await {
-            error!(error = ?e, "Failed to save MCP servers after token sync");
+            error!(error = ?e, "Failed to save MCP servers");
         }
     })
     .detach();
 }
 
-fn emit_rebuild_required(cx: &mut App) {
+pub(crate) fn emit_rebuild_required(cx: &mut App) {
     if let Some(notifier) = cx
         .try_global::<GlobalAgentConfigNotifier>()
         .and_then(|g| g.try_upgrade())
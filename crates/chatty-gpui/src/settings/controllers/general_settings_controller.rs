@@ -1,8 +1,15 @@
-use crate::settings::models::GeneralSettingsModel;
+use crate::chatty::models::ConversationsStore;
+use crate::settings::models::{
+    CopyFormat, FooterIndicator, GeneralSettingsModel, SendShortcut, SessionState,
+};
 use crate::settings::utils::find_theme_variant;
+use anyhow::anyhow;
+use chatty_core::models::{ConversationSort, LayoutDirection};
+use chatty_core::services::SpellChecker;
+use chatty_core::services::spell_checker::{available_dictionaries, preferred_dictionary};
 use gpui::{App, AsyncApp, SharedString};
 use gpui_component::{ActiveTheme, Theme, ThemeRegistry};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 /// Update font size and persist to disk
 pub fn update_font_size(cx: &mut App, font_size: f32) {
@@ -25,6 +32,288 @@ pub fn update_font_size(cx: &mut App, font_size: f32) {
     .detach();
 }
 
+/// Set the command that records the microphone for speech mode and persist to disk
+pub fn set_speech_record_command(cx: &mut App, command: Option<String>) {
+    info!(command = ?command, "Setting speech record command");
+    cx.global_mut::<GeneralSettingsModel>()
+        .speech_record_command = command;
+    save_general_settings(cx);
+}
+
+/// Set the command that transcribes utterances in speech mode and persist to disk
+pub fn set_speech_transcribe_command(cx: &mut App, command: Option<String>) {
+    info!(command = ?command, "Setting speech transcribe command");
+    cx.global_mut::<GeneralSettingsModel>()
+        .speech_transcribe_command = command;
+    save_general_settings(cx);
+}
+
+/// Set the command that reads replies aloud in speech mode and persist to disk
+pub fn set_speech_tts_command(cx: &mut App, command: Option<String>) {
+    info!(command = ?command, "Setting speech TTS command");
+    cx.global_mut::<GeneralSettingsModel>().speech_tts_command = command;
+    save_general_settings(cx);
+}
+
+/// Toggle restoring the previous session on startup and persist to disk
+pub fn set_restore_session(cx: &mut App, enabled: bool) {
+    info!(enabled, "Setting session restore");
+    cx.global_mut::<GeneralSettingsModel>().restore_session = enabled;
+    save_general_settings(cx);
+}
+
+/// Set what the copy button on assistant messages copies and persist to disk
+pub fn set_default_copy_format(cx: &mut App, format: CopyFormat) {
+    info!(format = ?format, "Setting default copy format");
+    cx.global_mut::<GeneralSettingsModel>().default_copy_format = format;
+    save_general_settings(cx);
+}
+
+/// Toggle including traces in "Copy as context" and persist to disk
+pub fn set_copy_context_include_traces(cx: &mut App, enabled: bool) {
+    info!(enabled, "Setting copy-as-context traces");
+    cx.global_mut::<GeneralSettingsModel>()
+        .copy_context_include_traces = enabled;
+    save_general_settings(cx);
+}
+
+/// Toggle printing traces with conversations and persist to disk
+pub fn set_print_include_traces(cx: &mut App, enabled: bool) {
+    info!(enabled, "Setting print traces");
+    cx.global_mut::<GeneralSettingsModel>().print_include_traces = enabled;
+    save_general_settings(cx);
+}
+
+/// Toggle printing attachments as thumbnails and persist to disk
+pub fn set_print_attachment_thumbnails(cx: &mut App, enabled: bool) {
+    info!(enabled, "Setting print attachment thumbnails");
+    cx.global_mut::<GeneralSettingsModel>()
+        .print_attachment_thumbnails = enabled;
+    save_general_settings(cx);
+}
+
+/// Toggle the title/date header on printed pages and persist to disk
+pub fn set_print_page_header(cx: &mut App, enabled: bool) {
+    info!(enabled, "Setting print page header");
+    cx.global_mut::<GeneralSettingsModel>().print_page_header = enabled;
+    save_general_settings(cx);
+}
+
+/// Show a status footer indicator or move it to the overflow menu, and persist to disk
+pub fn set_footer_indicator_hidden(cx: &mut App, indicator: FooterIndicator, hidden: bool) {
+    info!(indicator = ?indicator, hidden, "Setting footer indicator visibility");
+    cx.global_mut::<GeneralSettingsModel>()
+        .footer_layout
+        .set_hidden(indicator, hidden);
+    save_general_settings(cx);
+}
+
+/// Move a status footer indicator `offset` places (-1 = left) and persist to disk
+pub fn move_footer_indicator(cx: &mut App, indicator: FooterIndicator, offset: isize) {
+    info!(indicator = ?indicator, offset, "Moving footer indicator");
+    cx.global_mut::<GeneralSettingsModel>()
+        .footer_layout
+        .move_by(indicator, offset);
+    save_general_settings(cx);
+}
+
+/// Record that the first-run provider setup was finished or skipped, and persist to disk
+pub fn set_onboarding_completed(cx: &mut App) {
+    info!("Onboarding completed");
+    cx.global_mut::<GeneralSettingsModel>().onboarding_completed = true;
+    save_general_settings(cx);
+}
+
+/// Set the order of the sidebar conversation list and persist to disk
+pub fn set_conversation_sort(cx: &mut App, sort: ConversationSort) {
+    info!(sort = ?sort, "Setting conversation sort");
+    cx.global_mut::<GeneralSettingsModel>().conversation_sort = sort;
+    cx.global_mut::<ConversationsStore>().set_sort(sort);
+    save_general_settings(cx);
+}
+
+/// Toggle topic-shift split suggestions and persist to disk
+pub fn set_topic_split_suggestions(cx: &mut App, enabled: bool) {
+    info!(enabled, "Setting topic split suggestions");
+    cx.global_mut::<GeneralSettingsModel>()
+        .topic_split_suggestions = enabled;
+    save_general_settings(cx);
+}
+
+/// Switch the UI language (None = system locale) and persist to disk
+pub fn set_language(cx: &mut App, language: Option<String>) {
+    info!(language = ?language, "Setting UI language");
+    crate::i18n::set_locale(language.as_deref());
+    cx.global_mut::<GeneralSettingsModel>().language = language;
+    save_general_settings(cx);
+    // The dictionary follows the UI language unless one was picked
+    if cx
+        .global::<GeneralSettingsModel>()
+        .spell_check_dictionary
+        .is_none()
+    {
+        load_spell_checker(cx);
+    }
+}
+
+/// Set the layout direction (mirrored for right-to-left) and persist to disk
+pub fn set_layout_direction(cx: &mut App, direction: LayoutDirection) {
+    info!(direction = ?direction, "Setting layout direction");
+    cx.global_mut::<GeneralSettingsModel>().layout_direction = direction;
+    save_general_settings(cx);
+}
+
+/// Toggle spell checking of the chat input and persist to disk
+pub fn set_spell_check_enabled(cx: &mut App, enabled: bool) {
+    info!(enabled, "Setting spell check");
+    cx.global_mut::<GeneralSettingsModel>().spell_check_enabled = enabled;
+    save_general_settings(cx);
+    load_spell_checker(cx);
+}
+
+/// Set the spell check dictionary (None = match the UI language) and persist to disk
+pub fn set_spell_check_dictionary(cx: &mut App, dictionary: Option<String>) {
+    info!(dictionary = ?dictionary, "Setting spell check dictionary");
+    cx.global_mut::<GeneralSettingsModel>()
+        .spell_check_dictionary = dictionary;
+    save_general_settings(cx);
+    load_spell_checker(cx);
+}
+
+/// Set the per-message token warning threshold (0 = off) and persist to disk
+pub fn set_draft_token_warning(cx: &mut App, tokens: usize) {
+    info!(tokens, "Setting draft token warning");
+    cx.global_mut::<GeneralSettingsModel>().draft_token_warning = tokens;
+    save_general_settings(cx);
+}
+
+/// Set which key sends a message from the chat input and persist to disk
+pub fn set_send_shortcut(cx: &mut App, shortcut: SendShortcut) {
+    info!(shortcut = ?shortcut, "Setting send shortcut");
+    cx.global_mut::<GeneralSettingsModel>().send_shortcut = shortcut;
+    save_general_settings(cx);
+}
+
+/// Toggle the paced reveal of streamed responses and persist to disk
+pub fn set_smooth_streaming(cx: &mut App, enabled: bool) {
+    info!(enabled, "Setting smooth streaming");
+    cx.global_mut::<GeneralSettingsModel>().smooth_streaming = enabled;
+    save_general_settings(cx);
+}
+
+/// Set how many characters per second smooth streaming reveals and persist to disk
+pub fn set_smooth_streaming_rate(cx: &mut App, rate: u32) {
+    info!(rate, "Setting smooth streaming rate");
+    cx.global_mut::<GeneralSettingsModel>()
+        .smooth_streaming_rate = rate;
+    save_general_settings(cx);
+}
+
+/// Expand or collapse the multi-line composer and persist to disk
+pub fn set_composer_expanded(cx: &mut App, expanded: bool) {
+    info!(expanded, "Setting composer expanded");
+    cx.global_mut::<GeneralSettingsModel>().composer_expanded = expanded;
+    save_general_settings(cx);
+}
+
+/// Toggle the composer's Markdown preview and persist to disk
+pub fn set_composer_preview(cx: &mut App, preview: bool) {
+    info!(preview, "Setting composer preview");
+    cx.global_mut::<GeneralSettingsModel>().composer_preview = preview;
+    save_general_settings(cx);
+}
+
+/// Set the command that transcribes video audio (None = no transcript) and persist to disk
+pub fn set_video_transcribe_command(cx: &mut App, command: Option<String>) {
+    info!(command = ?command, "Setting video transcribe command");
+    cx.global_mut::<GeneralSettingsModel>()
+        .video_transcribe_command = command;
+    save_general_settings(cx);
+}
+
+/// Load the configured Hunspell dictionary into the `SpellChecker` global.
+/// Parsing runs on a blocking thread; without a matching dictionary the
+/// global is removed and the input is simply not checked.
+pub fn load_spell_checker(cx: &mut App) {
+    let settings = cx.global::<GeneralSettingsModel>();
+    if !settings.spell_check_enabled {
+        return;
+    }
+    let configured = settings.spell_check_dictionary.clone();
+    let ui_language = crate::i18n::current_locale();
+    if cx
+        .try_global::<SpellChecker>()
+        .is_some_and(|checker| configured.as_deref() == Some(checker.language()))
+    {
+        return;
+    }
+
+    cx.spawn(async move |cx| {
+        let result = tokio::task::spawn_blocking(move || {
+            let language = configured
+                .or_else(|| preferred_dictionary(&available_dictionaries(), ui_language))
+                .ok_or_else(|| anyhow!("No Hunspell dictionaries installed"))?;
+            SpellChecker::load(&language)
+        })
+        .await;
+
+        cx.update(|cx| {
+            match result {
+                Ok(Ok(checker)) => {
+                    info!(language = %checker.language(), "Spell check dictionary loaded");
+                    cx.set_global(checker);
+                }
+                Ok(Err(e)) => {
+                    info!(reason = %e, "Spell checking unavailable");
+                    if cx.has_global::<SpellChecker>() {
+                        cx.remove_global::<SpellChecker>();
+                    }
+                }
+                Err(e) => warn!(error = ?e, "Spell check dictionary task failed"),
+            }
+            cx.refresh_windows();
+        })
+        .map_err(|e| warn!(error = ?e, "Failed to install spell checker"))
+        .ok();
+    })
+    .detach();
+}
+
+/// Record the session captured on quit. Returns the updated settings so the
+/// caller can await the save before exiting.
+pub fn record_last_session(cx: &mut App, session: SessionState) -> GeneralSettingsModel {
+    let settings = cx.global_mut::<GeneralSettingsModel>();
+    settings.last_session = session;
+    settings.clone()
+}
+
+fn save_general_settings(cx: &mut App) {
+    let settings = cx.global::<GeneralSettingsModel>().clone();
+    cx.refresh_windows();
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::general_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save general settings, changes will be lost on restart");
+        }
+    })
+    .detach();
+}
+
+/// Toggle per-message model routing and persist to disk
+pub fn set_model_routing_enabled(cx: &mut App, enabled: bool) {
+    info!(enabled, "Setting model routing");
+    cx.global_mut::<GeneralSettingsModel>()
+        .model_routing_enabled = enabled;
+    save_general_settings(cx);
+}
+
+/// Set the model used to classify messages for routing and persist to disk
+pub fn set_router_model(cx: &mut App, model_id: Option<String>) {
+    info!(model_id = ?model_id, "Setting router model");
+    cx.global_mut::<GeneralSettingsModel>().router_model_id = model_id;
+    save_general_settings(cx);
+}
+
 /// Update selected theme (persistence automatic via observer)
 pub fn update_theme(cx: &mut App, base_theme_name: SharedString) {
     // Determine full theme name based on current dark mode
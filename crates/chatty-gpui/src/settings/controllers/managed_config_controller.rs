@@ -0,0 +1,34 @@
+use crate::settings::models::ManagedConfigModel;
+use crate::settings::models::managed_config::ToolGroup;
+use gpui::App;
+use tracing::info;
+
+/// Publish the loaded managed layer to the global and record the local
+/// entries of `kind` it overrode. Called by each settings loader after it has
+/// merged the managed layer over its local list; the first call copies the
+/// layer into the global.
+pub fn record_overrides(kind: &str, keys: Vec<String>, cx: &mut App) {
+    let Some(loaded) = chatty_core::services::managed_config_service::current() else {
+        return;
+    };
+    if !keys.is_empty() {
+        info!(kind, overridden = ?keys, "Managed config overrides local settings");
+    }
+
+    let model = cx.global_mut::<ManagedConfigModel>();
+    if model.source_url.is_none() && loaded.source_url.is_some() {
+        let overridden = std::mem::take(&mut model.overridden);
+        *model = loaded.clone();
+        model.overridden = overridden;
+    }
+    model.record_overridden(kind, keys);
+    cx.refresh_windows();
+}
+
+/// Whether `group` is blocked for the user's managed role. Settings switches
+/// show blocked groups as off; the agent factory never registers their tools.
+pub fn is_blocked(group: ToolGroup, cx: &App) -> bool {
+    cx.global::<ManagedConfigModel>()
+        .blocked_reason(group)
+        .is_some()
+}
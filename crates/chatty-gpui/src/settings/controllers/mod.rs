@@ -1,13 +1,17 @@
 pub mod a2a_controller;
+pub mod embedding_models_controller;
 pub mod execution_settings_controller;
 pub mod extensions_controller;
 pub mod general_settings_controller;
+pub mod managed_config_controller;
 pub mod memory_browser_controller;
 pub mod models_controller;
 pub mod module_settings_controller;
+pub mod prompt_packs_controller;
 pub mod providers_controller;
 pub mod search_settings_controller;
 pub mod settings_controller;
+pub mod snippets_controller;
 pub mod training_settings_controller;
 pub mod user_secrets_controller;
 
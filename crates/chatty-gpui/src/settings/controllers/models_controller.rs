@@ -63,6 +63,18 @@ pub fn delete_model(model_id: String, cx: &mut App) {
     save_models_async(models_to_save, cx);
 }
 
+/// Make a model the default for new conversations by moving it to the top
+pub fn set_default_model(model_id: &str, cx: &mut App) {
+    if !cx.global_mut::<ModelsModel>().make_default(model_id) {
+        error!("Failed to set default model: model not found");
+        return;
+    }
+
+    let models_to_save = cx.global::<ModelsModel>().models().to_vec();
+    cx.refresh_windows();
+    save_models_async(models_to_save, cx);
+}
+
 /// Save models asynchronously to disk
 fn save_models_async(models: Vec<ModelConfig>, cx: &mut App) {
     cx.spawn(|_cx: &mut AsyncApp| async move {
@@ -576,6 +576,9 @@ fn sync_module_mcp_servers(gateway_port: u16, cx: &mut App) {
                     api_key: None,
                     enabled: false,
                     is_module: true,
+                    result_cache_ttl_secs: 0,
+                    cached_tools: Vec::new(),
+                    launch: None,
                 });
                 changed = true;
             }
@@ -0,0 +1,160 @@
+use crate::chatty::services::mcp_service::McpService;
+use crate::settings::controllers::extensions_controller;
+use crate::settings::models::extensions_store::ExtensionsModel;
+use crate::settings::models::mcp_store::McpServersModel;
+use crate::settings::models::models_store::ModelsModel;
+use crate::settings::models::snippets_store::SnippetsModel;
+use crate::settings::models::{McpCatalogState, PromptPacksModel, PromptPacksState};
+use chatty_core::mcp_catalog;
+use chatty_core::prompt_packs::{self, PackChanges, PackTargets, VerifiedPromptPack};
+use gpui::{App, AsyncApp};
+use tracing::{error, info, warn};
+
+/// Install a previewed pack, or update the installed version.
+pub fn install_pack(pack: &VerifiedPromptPack, cx: &mut App) -> Result<(), String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    let changes = apply(cx, |targets| prompt_packs::install(pack, now, targets))?;
+    info!(
+        pack = %pack.manifest.id,
+        version = %pack.manifest.version,
+        "Installed prompt pack"
+    );
+    cx.global_mut::<PromptPacksState>()
+        .updates
+        .retain(|update| update.manifest.id != pack.manifest.id);
+    finish(changes, cx);
+    Ok(())
+}
+
+/// Remove pack `id` with the snippets, models and servers it installed.
+pub fn remove_pack(id: &str, cx: &mut App) {
+    match apply(cx, |targets| {
+        prompt_packs::uninstall(id, targets).ok_or("Pack is not installed")
+    }) {
+        Ok(changes) => {
+            info!(pack = %id, "Removed prompt pack");
+            cx.global_mut::<PromptPacksState>()
+                .updates
+                .retain(|update| update.manifest.id != id);
+            finish(changes, cx);
+        }
+        Err(e) => warn!(pack = %id, error = %e, "Failed to remove prompt pack"),
+    }
+}
+
+/// Look for newer published versions of the installed packs.
+pub fn check_for_updates(cx: &mut App) {
+    let packs = cx.global::<PromptPacksModel>().packs.clone();
+    if packs.iter().all(|pack| pack.update_url().is_none()) {
+        return;
+    }
+    cx.global_mut::<PromptPacksState>().checking = true;
+    cx.refresh_windows();
+
+    cx.spawn(async move |cx: &mut AsyncApp| {
+        let updates = prompt_packs::check_updates(&packs).await;
+        cx.update(|cx| {
+            if !updates.is_empty() {
+                info!(count = updates.len(), "Prompt pack updates available");
+            }
+            let state = cx.global_mut::<PromptPacksState>();
+            state.checking = false;
+            state.updates = updates;
+            cx.refresh_windows();
+        })
+        .map_err(|e| warn!(error = ?e, "Failed to update UI after pack update check"))
+        .ok();
+    })
+    .detach();
+}
+
+/// Offer the tools of installed packs in the MCP catalog, so tools that need
+/// settings can be installed from the Extensions page.
+pub fn merge_catalog_entries(cx: &mut App) {
+    let entries = prompt_packs::catalog_entries(cx.global::<PromptPacksModel>());
+    if entries.is_empty() {
+        return;
+    }
+    let state = cx.global_mut::<McpCatalogState>();
+    state.entries = mcp_catalog::merge(std::mem::take(&mut state.entries), entries);
+}
+
+/// Run `change` against the settings globals and save them when it succeeds.
+fn apply<E: ToString>(
+    cx: &mut App,
+    change: impl FnOnce(PackTargets<'_>) -> Result<PackChanges, E>,
+) -> Result<PackChanges, String> {
+    let mut packs = cx.global::<PromptPacksModel>().clone();
+    let mut snippets = cx.global::<SnippetsModel>().clone();
+    let mut models = cx.global::<ModelsModel>().clone();
+    let mut extensions = cx.global::<ExtensionsModel>().clone();
+    let mut servers = cx.global::<McpServersModel>().servers().to_vec();
+
+    let changes = change(PackTargets {
+        packs: &mut packs,
+        snippets: &mut snippets,
+        models: &mut models,
+        extensions: &mut extensions,
+        servers: &mut servers,
+    })
+    .map_err(|e| e.to_string())?;
+
+    let model_list = models.models().to_vec();
+    cx.set_global(packs.clone());
+    cx.set_global(snippets.clone());
+    cx.set_global(models);
+    cx.set_global(extensions.clone());
+    cx.global_mut::<McpServersModel>()
+        .replace_all(servers.clone());
+
+    cx.spawn(async move |_cx: &mut AsyncApp| {
+        if let Err(e) = chatty_core::prompt_packs_repository().save(packs).await {
+            error!(error = ?e, "Failed to save prompt packs");
+        }
+        if let Err(e) = chatty_core::snippets_repository().save(snippets).await {
+            error!(error = ?e, "Failed to save snippets");
+        }
+        if let Err(e) = chatty_core::models_repository().save_all(model_list).await {
+            error!(error = ?e, "Failed to save models");
+        }
+        if let Err(e) = chatty_core::extensions_repository().save(extensions).await {
+            error!(error = ?e, "Failed to save extensions");
+        }
+        if let Err(e) = chatty_core::mcp_repository().save_all(servers).await {
+            error!(error = ?e, "Failed to save MCP servers");
+        }
+    })
+    .detach();
+    Ok(changes)
+}
+
+/// Refresh the catalog and reconnect the MCP servers a pack changed.
+fn finish(changes: PackChanges, cx: &mut App) {
+    merge_catalog_entries(cx);
+    cx.refresh_windows();
+    if changes.connect.is_empty() && changes.disconnect.is_empty() {
+        return;
+    }
+
+    let service = cx.global::<McpService>().clone();
+    cx.spawn(async move |cx: &mut AsyncApp| {
+        for name in &changes.disconnect {
+            let _ = service.disconnect_server(name).await;
+        }
+        for config in changes.connect {
+            let _ = service.disconnect_server(&config.name).await;
+            if let Err(e) = service.connect_server(config).await {
+                error!(error = ?e, "Failed to connect to MCP server from prompt pack");
+            }
+        }
+        cx.update(|cx| {
+            extensions_controller::emit_rebuild_required(cx);
+        })
+        .map_err(|e| warn!(error = ?e, "Failed to emit rebuild after prompt pack change"))
+        .ok();
+    })
+    .detach();
+}
@@ -1,11 +1,17 @@
 use crate::settings::models::providers_store::{
-    AzureAuthMethod, ProviderConfig, ProviderModel, ProviderType,
+    AzureAuthMethod, ProviderConfig, ProviderModel, ProviderPrivacy, ProviderTimeouts, ProviderType,
 };
+use crate::settings::models::{AgentConfigEvent, GlobalAgentConfigNotifier};
+use crate::settings::providers::{ProviderHealthState, recheck_provider_health};
 use gpui::{App, AsyncApp};
-use tracing::error;
+use tracing::{error, info};
 
 /// Update or create a provider with an API key
 pub fn update_or_create_provider(cx: &mut App, provider_type: ProviderType, api_key: String) {
+    // Warnings about the old key no longer apply
+    cx.global_mut::<ProviderHealthState>()
+        .forget(&provider_type);
+
     // 1. Apply update immediately (optimistic update)
     let model = cx.global_mut::<ProviderModel>();
 
@@ -46,6 +52,9 @@ pub fn update_or_create_provider(cx: &mut App, provider_type: ProviderType, api_
 
 /// Update or create Azure OpenAI provider (requires API key and endpoint URL)
 pub fn update_or_create_azure(cx: &mut App, api_key: String, endpoint_url: String) {
+    cx.global_mut::<ProviderHealthState>()
+        .forget(&ProviderType::AzureOpenAI);
+
     // Read current Azure auth method and whether a complete record already exists.
     // "Complete" for Azure means: has endpoint AND (has API key OR uses Entra ID),
     // matching the same logic used in configured_providers().
@@ -203,3 +212,145 @@ pub fn update_azure_auth_method(cx: &mut App, use_entra_id: bool) {
     })
     .detach();
 }
+
+/// Update a provider's privacy opt-outs and rebuild the active agent so the
+/// next request carries them.
+pub fn update_provider_privacy(
+    cx: &mut App,
+    provider_type: ProviderType,
+    privacy: ProviderPrivacy,
+) {
+    // 1. Update in-memory state
+    let model = cx.global_mut::<ProviderModel>();
+
+    if let Some(provider) = model
+        .providers_mut()
+        .iter_mut()
+        .find(|p| p.provider_type == provider_type)
+    {
+        provider.set_privacy(privacy);
+    } else {
+        // Create the provider so the choice is kept until it is configured
+        let mut config =
+            ProviderConfig::new(provider_type.display_name().to_string(), provider_type);
+        config.set_privacy(privacy);
+        model.add_provider(config);
+    }
+    info!(
+        ?privacy,
+        "Provider privacy updated — triggering agent rebuild"
+    );
+    if let Some(notifier) = cx
+        .try_global::<GlobalAgentConfigNotifier>()
+        .and_then(|g| g.try_upgrade())
+    {
+        notifier.update(cx, |_notifier, cx| {
+            cx.emit(AgentConfigEvent::RebuildRequired);
+        });
+    }
+
+    // 2. Get updated state for async save
+    let providers_to_save = cx.global::<ProviderModel>().providers().to_vec();
+
+    // 3. Refresh UI immediately
+    cx.refresh_windows();
+
+    // 4. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::provider_repository();
+        if let Err(e) = repo.save_all(providers_to_save).await {
+            error!(error = ?e, "Failed to save providers, changes will be lost on restart");
+        }
+    })
+    .detach();
+}
+
+/// Update a provider's connect and read timeouts and rebuild the active
+/// agent so its HTTP client uses them.
+pub fn update_provider_timeouts(
+    cx: &mut App,
+    provider_type: ProviderType,
+    timeouts: ProviderTimeouts,
+) {
+    // 1. Update in-memory state
+    let model = cx.global_mut::<ProviderModel>();
+
+    if let Some(provider) = model
+        .providers_mut()
+        .iter_mut()
+        .find(|p| p.provider_type == provider_type)
+    {
+        provider.set_timeouts(timeouts);
+    } else {
+        // Create the provider so the choice is kept until it is configured
+        let mut config =
+            ProviderConfig::new(provider_type.display_name().to_string(), provider_type);
+        config.set_timeouts(timeouts);
+        model.add_provider(config);
+    }
+    info!(
+        ?timeouts,
+        "Provider timeouts updated — triggering agent rebuild"
+    );
+    if let Some(notifier) = cx
+        .try_global::<GlobalAgentConfigNotifier>()
+        .and_then(|g| g.try_upgrade())
+    {
+        notifier.update(cx, |_notifier, cx| {
+            cx.emit(AgentConfigEvent::RebuildRequired);
+        });
+    }
+
+    // 2. Get updated state for async save
+    let providers_to_save = cx.global::<ProviderModel>().providers().to_vec();
+
+    // 3. Refresh UI immediately
+    cx.refresh_windows();
+
+    // 4. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::provider_repository();
+        if let Err(e) = repo.save_all(providers_to_save).await {
+            error!(error = ?e, "Failed to save providers, changes will be lost on restart");
+        }
+    })
+    .detach();
+}
+
+/// Record when a provider's key expires (empty = unknown) and re-run the
+/// health check so the expiry warning updates right away.
+pub fn update_key_expiry(cx: &mut App, provider_type: ProviderType, value: String) {
+    // 1. Update in-memory state
+    let model = cx.global_mut::<ProviderModel>();
+
+    if let Some(provider) = model
+        .providers_mut()
+        .iter_mut()
+        .find(|p| p.provider_type == provider_type)
+    {
+        provider.set_key_expires_on(&value);
+    } else {
+        // Create the provider so the date is kept until it is configured
+        let mut config =
+            ProviderConfig::new(provider_type.display_name().to_string(), provider_type);
+        config.set_key_expires_on(&value);
+        model.add_provider(config);
+    }
+    info!(expires_on = %value, "Provider key expiry updated");
+
+    // 2. Get updated state for async save
+    let providers_to_save = cx.global::<ProviderModel>().providers().to_vec();
+
+    // 3. Refresh UI immediately
+    cx.refresh_windows();
+    recheck_provider_health(cx);
+
+    // 4. Save async with error handling
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::provider_repository();
+        if let Err(e) = repo.save_all(providers_to_save).await {
+            error!(error = ?e, "Failed to save providers, changes will be lost on restart");
+        }
+    })
+    .detach();
+}
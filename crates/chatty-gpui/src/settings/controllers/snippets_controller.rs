@@ -0,0 +1,50 @@
+use crate::settings::models::snippets_store::SnippetsModel;
+use gpui::{App, AsyncApp};
+use tracing::{error, info};
+
+/// Save the current snippet library to disk asynchronously.
+fn save_snippets_async(cx: &mut App) {
+    let model = cx.global::<SnippetsModel>().clone();
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::snippets_repository();
+        if let Err(e) = repo.save(model).await {
+            error!(error = ?e, "Failed to save snippets");
+        }
+    })
+    .detach();
+}
+
+/// Create or update a snippet, refresh UI, and save to disk.
+/// Returns `true` when a new snippet was created.
+pub fn save_snippet(name: String, content: String, cx: &mut App) -> bool {
+    info!(name = %name, "Saving snippet");
+
+    let created = cx.global_mut::<SnippetsModel>().upsert(&name, content);
+
+    cx.refresh_windows();
+    save_snippets_async(cx);
+    created
+}
+
+/// Rename a snippet, keeping its usage count. The caller saves the new
+/// content afterwards with [`save_snippet`].
+pub fn rename_snippet(name: &str, new_name: &str, cx: &mut App) {
+    info!(name = %name, new_name = %new_name, "Renaming snippet");
+    cx.global_mut::<SnippetsModel>().rename(name, new_name);
+}
+
+/// Remove a snippet by name.
+pub fn remove_snippet(name: &str, cx: &mut App) {
+    info!(name = %name, "Removing snippet");
+
+    cx.global_mut::<SnippetsModel>().remove(name);
+
+    cx.refresh_windows();
+    save_snippets_async(cx);
+}
+
+/// Count an insertion of the named snippet into the chat input.
+pub fn record_use(name: &str, cx: &mut App) {
+    cx.global_mut::<SnippetsModel>().record_use(name);
+    save_snippets_async(cx);
+}
@@ -1,4 +1,5 @@
 use crate::settings::models::training_settings::TrainingSettingsModel;
+use chatty_core::exporters::pii::PiiScrubConfig;
 use gpui::{App, AsyncApp};
 use tracing::{error, info};
 
@@ -57,3 +58,73 @@ pub fn toggle_jsonl_auto_export(cx: &mut App) {
     })
     .detach();
 }
+
+/// Set the reviewer name recorded with dataset review decisions and persist to disk
+pub fn set_reviewer_name(name: Option<String>, cx: &mut App) {
+    info!(reviewer = ?name, "Setting dataset reviewer name");
+    cx.global_mut::<TrainingSettingsModel>().reviewer_name = name;
+
+    let settings = cx.global::<TrainingSettingsModel>().clone();
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::training_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save training settings");
+        }
+    })
+    .detach();
+}
+
+/// Update the PII scrubbing configuration for exports and persist to disk
+pub fn update_pii_scrub(cx: &mut App, update: impl FnOnce(&mut PiiScrubConfig)) {
+    update(&mut cx.global_mut::<TrainingSettingsModel>().pii_scrub);
+    let settings = cx.global::<TrainingSettingsModel>().clone();
+    info!(
+        enabled = settings.pii_scrub.enabled,
+        names = settings.pii_scrub.names.len(),
+        "Updating PII scrubbing for exports"
+    );
+
+    cx.refresh_windows();
+
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::training_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save training settings");
+        }
+    })
+    .detach();
+}
+
+/// Enable or disable judge-model quality scoring and persist to disk
+pub fn set_quality_scoring(enabled: bool, cx: &mut App) {
+    info!(enabled, "Setting conversation quality scoring");
+    cx.global_mut::<TrainingSettingsModel>().quality_scoring = enabled;
+    save_settings(cx);
+}
+
+/// Set the judge model used for quality scoring and persist to disk
+pub fn set_judge_model(model_id: Option<String>, cx: &mut App) {
+    info!(model_id = ?model_id, "Setting quality scoring judge model");
+    cx.global_mut::<TrainingSettingsModel>().judge_model_id = model_id;
+    save_settings(cx);
+}
+
+/// Set the minimum quality score for the approved dataset and persist to disk
+pub fn set_min_quality_score(min_score: Option<f32>, cx: &mut App) {
+    info!(min_score = ?min_score, "Setting minimum quality score");
+    cx.global_mut::<TrainingSettingsModel>().min_quality_score = min_score;
+    save_settings(cx);
+}
+
+fn save_settings(cx: &mut App) {
+    let settings = cx.global::<TrainingSettingsModel>().clone();
+    cx.refresh_windows();
+
+    cx.spawn(|_cx: &mut AsyncApp| async move {
+        let repo = chatty_core::training_settings_repository();
+        if let Err(e) = repo.save(settings).await {
+            error!(error = ?e, "Failed to save training settings");
+        }
+    })
+    .detach();
+}
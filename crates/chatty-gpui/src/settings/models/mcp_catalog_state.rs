@@ -0,0 +1,23 @@
+use chatty_core::mcp_catalog::{self, McpCatalogEntry};
+use gpui::Global;
+
+/// Entries of the MCP server catalog shown on the Extensions page.
+/// Starts with the bundled index; "Check for Updates" merges the published one.
+#[derive(Clone)]
+pub struct McpCatalogState {
+    pub entries: Vec<McpCatalogEntry>,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+impl Default for McpCatalogState {
+    fn default() -> Self {
+        Self {
+            entries: mcp_catalog::bundled_catalog(),
+            loading: false,
+            error: None,
+        }
+    }
+}
+
+impl Global for McpCatalogState {}
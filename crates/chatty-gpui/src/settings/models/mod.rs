@@ -3,8 +3,9 @@ pub use chatty_core::settings::models::*;
 
 // Re-export submodules for path-based access
 pub use chatty_core::settings::models::{
-    execution_settings, extensions_store, general_model, hive_settings, mcp_store, models_store,
-    module_settings, providers_store, search_settings, token_tracking_settings, training_settings,
+    embedding_models_store, execution_settings, extensions_store, general_model, hive_settings,
+    managed_config, mcp_store, models_store, module_settings, prompt_packs_store, providers_store,
+    search_settings, snippets_store, token_tracking_settings, training_settings,
     user_secrets_store,
 };
 
@@ -12,11 +13,15 @@ pub use chatty_core::settings::models::{
 pub mod agent_config_notifier;
 pub mod discovered_modules;
 pub mod marketplace_state;
+pub mod mcp_catalog_state;
 pub mod memory_browser_state;
 pub mod models_notifier;
+pub mod prompt_packs_state;
 
 pub use agent_config_notifier::{AgentConfigEvent, AgentConfigNotifier, GlobalAgentConfigNotifier};
 pub use discovered_modules::{DiscoveredModuleEntry, DiscoveredModulesModel, ModuleLoadStatus};
 pub use marketplace_state::MarketplaceState;
+pub use mcp_catalog_state::McpCatalogState;
 pub use memory_browser_state::MemoryBrowserState;
 pub use models_notifier::{GlobalModelsNotifier, ModelsNotifier, ModelsNotifierEvent};
+pub use prompt_packs_state::PromptPacksState;
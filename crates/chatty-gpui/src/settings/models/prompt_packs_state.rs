@@ -0,0 +1,19 @@
+use chatty_core::prompt_packs::VerifiedPromptPack;
+use gpui::Global;
+
+/// Published pack versions newer than the installed ones, found by the
+/// update check at startup or by "Check for Updates".
+#[derive(Clone, Default)]
+pub struct PromptPacksState {
+    pub updates: Vec<VerifiedPromptPack>,
+    pub checking: bool,
+}
+
+impl PromptPacksState {
+    /// The pending update for pack `id`, if any.
+    pub fn update_for(&self, id: &str) -> Option<&VerifiedPromptPack> {
+        self.updates.iter().find(|pack| pack.manifest.id == id)
+    }
+}
+
+impl Global for PromptPacksState {}
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use chatty_core::settings::providers::health::{HealthWarning, check_provider_health};
+use gpui::{App, AsyncApp, Global};
+use tracing::info;
+
+use crate::settings::models::ProviderModel;
+use crate::settings::models::providers_store::ProviderType;
+
+/// How often configured providers are checked for expired, revoked or
+/// exhausted credentials.
+const POLL_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Provider problems found by the last health check, shown by the error
+/// indicator until a later check no longer finds them.
+#[derive(Clone, Default)]
+pub struct ProviderHealthState {
+    pub warnings: Vec<HealthWarning>,
+}
+
+impl ProviderHealthState {
+    /// Drop the warnings about `provider_type`, e.g. after its key was replaced.
+    /// The next check reports them again if they still apply.
+    pub fn forget(&mut self, provider_type: &ProviderType) {
+        self.warnings.retain(|w| &w.provider != provider_type);
+    }
+}
+
+impl Global for ProviderHealthState {}
+
+/// Check every configured provider's credentials until the app quits,
+/// publishing the problems found in [`ProviderHealthState`].
+pub async fn poll_provider_health(cx: &mut AsyncApp) {
+    while check_providers(cx).await {
+        cx.background_executor().timer(POLL_INTERVAL).await;
+    }
+}
+
+/// Run one health check now instead of waiting for the next poll.
+pub fn recheck_provider_health(cx: &mut App) {
+    cx.spawn(async move |cx: &mut AsyncApp| {
+        check_providers(cx).await;
+    })
+    .detach();
+}
+
+/// Check all configured providers once. Returns false when the app is gone.
+async fn check_providers(cx: &mut AsyncApp) -> bool {
+    let Ok(providers) = cx.update(|cx| {
+        cx.global::<ProviderModel>()
+            .configured_providers()
+            .cloned()
+            .collect::<Vec<_>>()
+    }) else {
+        return false;
+    };
+
+    let mut warnings = Vec::new();
+    for provider in &providers {
+        warnings.extend(check_provider_health(provider).await);
+    }
+
+    cx.update(|cx| {
+        let previous = &cx.global::<ProviderHealthState>().warnings;
+        if *previous == warnings {
+            return;
+        }
+        // Logged at info level: the indicator already counts these warnings
+        for warning in warnings.iter().filter(|w| !previous.contains(w)) {
+            info!(problem = %warning.problem, "Provider health warning");
+        }
+        for warning in previous.iter().filter(|w| !warnings.contains(w)) {
+            info!(problem = %warning.problem, "Provider health warning resolved");
+        }
+        cx.global_mut::<ProviderHealthState>().warnings = warnings;
+        cx.refresh_windows();
+    })
+    .is_ok()
+}
@@ -1,4 +1,8 @@
+pub mod health_service;
 pub mod ollama;
 pub mod openrouter;
 
-pub use ollama::{ensure_default_ollama_provider, sync_ollama_models};
+pub use health_service::{ProviderHealthState, poll_provider_health, recheck_provider_health};
+pub use ollama::{
+    OllamaStatusState, ensure_default_ollama_provider, poll_ollama_status, sync_ollama_models,
+};
@@ -1,7 +1,9 @@
 // Re-export discovery from chatty-core
 pub use chatty_core::settings::providers::ollama::discovery;
 
-// Local gpui-specific module
+// Local gpui-specific modules
+pub mod status_service;
 pub mod sync_service;
 
+pub use status_service::{OllamaStatusState, poll_ollama_status};
 pub use sync_service::{ensure_default_ollama_provider, sync_ollama_models};
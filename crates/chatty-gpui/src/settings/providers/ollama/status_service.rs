@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use chatty_core::settings::providers::ollama::status::{OllamaStatus, fetch_ollama_status};
+use gpui::{App, AsyncApp, Global};
+use tracing::debug;
+
+use crate::settings::models::ProviderModel;
+use crate::settings::models::models_store::ModelsModel;
+use crate::settings::models::providers_store::ProviderType;
+
+/// How often Ollama is asked which models it has loaded.
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Latest answer from Ollama's `/api/ps`. `status` is `None` while no Ollama
+/// model is configured or the server cannot be reached.
+#[derive(Clone, Default)]
+pub struct OllamaStatusState {
+    pub status: Option<OllamaStatus>,
+}
+
+impl OllamaStatusState {
+    /// Whether sending to `identifier` would have to load the model first.
+    /// False when the status is unknown, so no warning is shown by mistake.
+    pub fn needs_cold_start(&self, identifier: &str) -> bool {
+        self.status
+            .as_ref()
+            .is_some_and(|status| !status.is_loaded(identifier))
+    }
+}
+
+impl Global for OllamaStatusState {}
+
+/// Base URL of the Ollama provider, or `None` when no Ollama model is set up.
+fn ollama_base_url(cx: &App) -> Option<String> {
+    if cx
+        .global::<ModelsModel>()
+        .models_by_provider(&ProviderType::Ollama)
+        .is_empty()
+    {
+        return None;
+    }
+    let base_url = cx
+        .global::<ProviderModel>()
+        .providers()
+        .iter()
+        .find(|p| matches!(p.provider_type, ProviderType::Ollama))
+        .and_then(|p| p.base_url.clone())
+        .unwrap_or_else(|| "http://localhost:11434".to_string());
+    Some(base_url)
+}
+
+/// Poll Ollama for its loaded models and their GPU/RAM split until the app
+/// quits, publishing each answer in [`OllamaStatusState`].
+pub async fn poll_ollama_status(cx: &mut AsyncApp) {
+    loop {
+        let Ok(base_url) = cx.update(|cx| ollama_base_url(cx)) else {
+            return;
+        };
+
+        let status = match base_url {
+            Some(url) => fetch_ollama_status(&url)
+                .await
+                .map_err(|e| debug!(url = %url, error = ?e, "Ollama status unavailable"))
+                .ok(),
+            None => None,
+        };
+
+        let updated = cx.update(|cx| {
+            if cx.global::<OllamaStatusState>().status != status {
+                cx.global_mut::<OllamaStatusState>().status = status;
+                cx.refresh_windows();
+            }
+        });
+        if updated.is_err() {
+            return;
+        }
+
+        cx.background_executor().timer(POLL_INTERVAL).await;
+    }
+}
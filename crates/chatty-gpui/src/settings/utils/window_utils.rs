@@ -1,24 +1,50 @@
+use crate::settings::models::WindowGeometry;
 use gpui::*;
 
+/// Smallest saved window size that is restored; anything smaller falls back
+/// to the default size.
+const MIN_RESTORED_SIZE: f32 = 300.0;
+
 /// Returns platform-specific WindowOptions for the main Chatty window.
 ///
+/// `restored` is the geometry saved when the app last quit; without it the
+/// window opens at the default 1000x600 size.
+///
 /// Platform-specific behavior:
 /// - macOS: Transparent titlebar with native decorations
 /// - Windows: Transparent titlebar with client-side decorations
 /// - Linux: Transparent titlebar with client-side decorations (custom titlebar)
-pub fn get_main_window_options() -> WindowOptions {
+pub fn get_main_window_options(restored: Option<&WindowGeometry>) -> WindowOptions {
     WindowOptions {
         titlebar: Some(get_titlebar_options()),
         window_decorations: get_window_decorations(),
-        window_bounds: Some(WindowBounds::Windowed(Bounds {
-            origin: Point::default(),
-            size: size(px(1000.0), px(600.0)),
-        })),
+        window_bounds: Some(get_window_bounds(restored)),
         app_id: Some("chatty".to_string()),
         ..Default::default()
     }
 }
 
+fn get_window_bounds(restored: Option<&WindowGeometry>) -> WindowBounds {
+    let Some(geometry) =
+        restored.filter(|g| g.width >= MIN_RESTORED_SIZE && g.height >= MIN_RESTORED_SIZE)
+    else {
+        return WindowBounds::Windowed(Bounds {
+            origin: Point::default(),
+            size: size(px(1000.0), px(600.0)),
+        });
+    };
+
+    let bounds = Bounds {
+        origin: point(px(geometry.x), px(geometry.y)),
+        size: size(px(geometry.width), px(geometry.height)),
+    };
+    if geometry.maximized {
+        WindowBounds::Maximized(bounds)
+    } else {
+        WindowBounds::Windowed(bounds)
+    }
+}
+
 /// Returns platform-specific titlebar options.
 ///
 /// All platforms use transparent titlebar for custom titlebar rendering.
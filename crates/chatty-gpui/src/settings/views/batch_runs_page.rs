@@ -0,0 +1,537 @@
+use crate::i18n::t;
+use crate::settings::models::models_store::ModelsModel;
+use crate::settings::models::providers_store::ProviderModel;
+use chatty_core::factories::AgentClient;
+use chatty_core::factories::agent_factory::AgentBuildContext;
+use chatty_core::services::batch_job_service::BatchJobService;
+use chatty_core::services::batch_runner::{
+    self, BatchControl, BatchMode, BatchProgress, load_inputs, run_batch, run_provider_batch,
+};
+use futures::StreamExt;
+use gpui::{
+    App, AsyncApp, Context, Corner, FocusHandle, Focusable, IntoElement, PathPromptOptions, Render,
+    Styled, WeakEntity, Window, div, prelude::*,
+};
+use gpui_component::setting::{SettingGroup, SettingItem, SettingPage};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    menu::{DropdownMenu, PopupMenuItem},
+    progress::Progress,
+    v_flex,
+};
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+const CONCURRENCY_OPTIONS: &[usize] = &[1, 2, 4, 8, 16];
+
+// ── Global singleton ────────────────────────────────────────────────────────
+
+pub type GlobalBatchRunnerView = crate::global_entity::GlobalStrongEntity<BatchRunnerView>;
+
+// ── Batch runner entity ─────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq)]
+enum RunState {
+    Running,
+    Paused,
+    Finished,
+    Cancelled,
+}
+
+struct BatchRun {
+    control: BatchControl,
+    progress: BatchProgress,
+    output: PathBuf,
+    mode: BatchMode,
+    state: RunState,
+}
+
+/// Runs a CSV/JSONL file of prompts against one model and shows progress.
+pub struct BatchRunnerView {
+    focus_handle: FocusHandle,
+    input_path: Option<PathBuf>,
+    model_id: Option<String>,
+    concurrency: usize,
+    mode: BatchMode,
+    run: Option<BatchRun>,
+    error: Option<String>,
+}
+
+impl BatchRunnerView {
+    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            input_path: None,
+            model_id: cx
+                .global::<ModelsModel>()
+                .models()
+                .first()
+                .map(|m| m.id.clone()),
+            concurrency: 4,
+            mode: BatchMode::Realtime,
+            run: None,
+            error: None,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.run
+            .as_ref()
+            .is_some_and(|run| matches!(run.state, RunState::Running | RunState::Paused))
+    }
+
+    fn batch_supported(&self, cx: &App) -> bool {
+        self.model_id
+            .as_ref()
+            .and_then(|id| cx.global::<ModelsModel>().get_model(id))
+            .is_some_and(|model| BatchJobService::supports(&model.provider_type))
+    }
+
+    /// The selected mode, falling back to real-time when the chosen model's
+    /// provider has no batch endpoint.
+    fn effective_mode(&self, cx: &App) -> BatchMode {
+        if self.batch_supported(cx) {
+            self.mode
+        } else {
+            BatchMode::Realtime
+        }
+    }
+
+    fn pick_input(&mut self, cx: &mut Context<Self>) {
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Select CSV or JSONL".into()),
+        });
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            if let Ok(Ok(Some(paths))) = receiver.await
+                && let Some(path) = paths.into_iter().next()
+            {
+                this.update(cx, |view, cx| {
+                    view.input_path = Some(path);
+                    view.error = None;
+                    cx.notify();
+                })
+                .ok();
+            }
+        })
+        .detach();
+    }
+
+    fn start(&mut self, cx: &mut Context<Self>) {
+        let Some(input_path) = self.input_path.clone() else {
+            return;
+        };
+        let Some(model) = self
+            .model_id
+            .as_ref()
+            .and_then(|id| cx.global::<ModelsModel>().get_model(id))
+            .cloned()
+        else {
+            self.error = Some("Choose a model first".to_string());
+            cx.notify();
+            return;
+        };
+        let Some(provider) = cx
+            .global::<ProviderModel>()
+            .providers()
+            .iter()
+            .find(|p| p.provider_type == model.provider_type)
+            .cloned()
+        else {
+            self.error = Some(format!("No provider configured for {}", model.name));
+            cx.notify();
+            return;
+        };
+        let Some(dir) = batch_runner::default_batch_dir() else {
+            self.error = Some("Cannot determine the exports directory".to_string());
+            cx.notify();
+            return;
+        };
+
+        let output = batch_runner::output_path(&dir, &input_path, &model.id);
+        let control = BatchControl::default();
+        let concurrency = self.concurrency;
+        let mode = self.effective_mode(cx);
+        self.error = None;
+        self.run = Some(BatchRun {
+            control: control.clone(),
+            progress: BatchProgress::default(),
+            output: output.clone(),
+            mode,
+            state: RunState::Running,
+        });
+        cx.notify();
+
+        info!(input = %input_path.display(), model_id = %model.id, concurrency, ?mode, "Starting batch run");
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let (tx, mut rx) = futures::channel::mpsc::unbounded::<BatchProgress>();
+            let run = async {
+                let inputs = load_inputs(&input_path).await?;
+                let on_progress = move |progress: &BatchProgress| {
+                    tx.unbounded_send(progress.clone()).ok();
+                };
+                if mode == BatchMode::ProviderBatch {
+                    let service = BatchJobService::for_provider(&model, &provider).await?;
+                    return run_provider_batch(
+                        service,
+                        model,
+                        inputs,
+                        &output,
+                        control,
+                        on_progress,
+                    )
+                    .await;
+                }
+                let (agent, _, _) = AgentClient::from_model_config_with_tools(
+                    &model,
+                    &provider,
+                    AgentBuildContext::default(),
+                )
+                .await?;
+                run_batch(
+                    agent,
+                    model,
+                    inputs,
+                    concurrency,
+                    &output,
+                    control,
+                    on_progress,
+                )
+                .await
+            };
+            let forward = async {
+                while let Some(progress) = rx.next().await {
+                    this.update(cx, |view, cx| {
+                        if let Some(run) = view.run.as_mut() {
+                            run.progress = progress;
+                            cx.notify();
+                        }
+                    })
+                    .ok();
+                }
+            };
+            let (result, ()) = futures::join!(run, forward);
+
+            this.update(cx, |view, cx| {
+                if let Some(run) = view.run.as_mut() {
+                    match result {
+                        Ok(progress) => {
+                            run.state = if run.control.is_cancelled() {
+                                RunState::Cancelled
+                            } else {
+                                RunState::Finished
+                            };
+                            run.progress = progress;
+                        }
+                        Err(e) => {
+                            warn!(error = ?e, "Batch run failed");
+                            run.state = RunState::Cancelled;
+                            view.error = Some(e.to_string());
+                        }
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn toggle_pause(&mut self, cx: &mut Context<Self>) {
+        if let Some(run) = self.run.as_mut() {
+            match run.state {
+                RunState::Running => {
+                    run.control.pause();
+                    run.state = RunState::Paused;
+                }
+                RunState::Paused => {
+                    run.control.resume();
+                    run.state = RunState::Running;
+                }
+                RunState::Finished | RunState::Cancelled => {}
+            }
+            cx.notify();
+        }
+    }
+
+    fn cancel(&mut self, cx: &mut Context<Self>) {
+        if let Some(run) = self.run.as_mut() {
+            run.control.cancel();
+            cx.notify();
+        }
+    }
+
+    fn render_progress(&self, run: &BatchRun, cx: &Context<Self>) -> impl IntoElement {
+        let p = &run.progress;
+        let percent = if p.total == 0 {
+            0.0
+        } else {
+            p.completed as f32 / p.total as f32 * 100.0
+        };
+        let status = match (run.state, run.mode) {
+            (RunState::Running, BatchMode::ProviderBatch) => {
+                "Waiting for the provider — results arrive within 24 hours"
+            }
+            (RunState::Running, _) => "Running",
+            (RunState::Paused, _) => "Paused — in-flight prompts finish first",
+            (RunState::Finished, _) => "Finished",
+            (RunState::Cancelled, _) => "Stopped",
+        };
+
+        v_flex()
+            .w_full()
+            .gap_2()
+            .p_3()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .child(
+                h_flex()
+                    .justify_between()
+                    .text_sm()
+                    .child(status)
+                    .child(format!("{} / {}", p.completed, p.total)),
+            )
+            .child(Progress::new().value(percent))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!(
+                        "{} failed · {} input / {} output tokens · ${:.4}",
+                        p.failed, p.input_tokens, p.output_tokens, p.cost_usd
+                    )),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!("Output: {}", run.output.display())),
+            )
+    }
+}
+
+impl Focusable for BatchRunnerView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for BatchRunnerView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let active = self.is_active();
+        let models: Vec<(String, String)> = cx
+            .global::<ModelsModel>()
+            .models()
+            .iter()
+            .map(|m| (m.id.clone(), m.name.clone()))
+            .collect();
+        let model_label = self
+            .model_id
+            .as_ref()
+            .and_then(|id| models.iter().find(|(mid, _)| mid == id))
+            .map(|(_, name)| name.clone())
+            .unwrap_or_else(|| "Choose model".to_string());
+        let input_label = self
+            .input_path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Choose CSV or JSONL…".to_string());
+
+        let entity = cx.entity();
+        let model_entity = entity.clone();
+        let concurrency_entity = entity.clone();
+        let mode_entity = entity.clone();
+        let start_entity = entity.clone();
+        let pause_entity = entity.clone();
+        let cancel_entity = entity.clone();
+        let current_model = self.model_id.clone();
+        let current_concurrency = self.concurrency;
+        let current_mode = self.effective_mode(cx);
+        let batch_supported = self.batch_supported(cx);
+        let paused = self
+            .run
+            .as_ref()
+            .is_some_and(|run| run.state == RunState::Paused);
+        let pausable = self
+            .run
+            .as_ref()
+            .is_some_and(|run| run.mode == BatchMode::Realtime);
+
+        v_flex()
+            .size_full()
+            .gap_3()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("batch-input")
+                            .label(input_label)
+                            .outline()
+                            .disabled(active)
+                            .on_click(move |_, _, cx| {
+                                entity.update(cx, |view, cx| view.pick_input(cx));
+                            }),
+                    )
+                    .child(
+                        Button::new("batch-model")
+                            .label(model_label)
+                            .dropdown_caret(true)
+                            .outline()
+                            .disabled(active)
+                            .dropdown_menu_with_anchor(Corner::BottomLeft, move |menu, _, _| {
+                                let menu = menu.max_h(gpui::px(300.0)).scrollable(true);
+                                models.iter().fold(menu, |menu, (id, name)| {
+                                    let id = id.clone();
+                                    let model_entity = model_entity.clone();
+                                    menu.item(
+                                        PopupMenuItem::new(name.clone())
+                                            .checked(current_model.as_deref() == Some(id.as_str()))
+                                            .on_click(move |_, _, cx| {
+                                                model_entity.update(cx, |view, cx| {
+                                                    view.model_id = Some(id.clone());
+                                                    cx.notify();
+                                                });
+                                            }),
+                                    )
+                                })
+                            }),
+                    )
+                    .child(
+                        Button::new("batch-concurrency")
+                            .label(format!("{current_concurrency} at a time"))
+                            .dropdown_caret(true)
+                            .outline()
+                            .disabled(active || current_mode == BatchMode::ProviderBatch)
+                            .dropdown_menu_with_anchor(Corner::BottomLeft, move |menu, _, _| {
+                                CONCURRENCY_OPTIONS.iter().fold(menu, |menu, &n| {
+                                    let concurrency_entity = concurrency_entity.clone();
+                                    menu.item(
+                                        PopupMenuItem::new(format!("{n} at a time"))
+                                            .checked(n == current_concurrency)
+                                            .on_click(move |_, _, cx| {
+                                                concurrency_entity.update(cx, |view, cx| {
+                                                    view.concurrency = n;
+                                                    cx.notify();
+                                                });
+                                            }),
+                                    )
+                                })
+                            }),
+                    )
+                    .child(
+                        Button::new("batch-mode")
+                            .label(mode_label(current_mode))
+                            .dropdown_caret(true)
+                            .outline()
+                            .disabled(active || !batch_supported)
+                            .tooltip(if batch_supported {
+                                "Provider batch jobs cost half as much but finish within 24 hours"
+                            } else {
+                                "The selected model's provider has no batch endpoint"
+                            })
+                            .dropdown_menu_with_anchor(Corner::BottomLeft, move |menu, _, _| {
+                                [BatchMode::Realtime, BatchMode::ProviderBatch]
+                                    .into_iter()
+                                    .fold(menu, |menu, mode| {
+                                        let mode_entity = mode_entity.clone();
+                                        menu.item(
+                                            PopupMenuItem::new(mode_label(mode))
+                                                .checked(mode == current_mode)
+                                                .on_click(move |_, _, cx| {
+                                                    mode_entity.update(cx, |view, cx| {
+                                                        view.mode = mode;
+                                                        cx.notify();
+                                                    });
+                                                }),
+                                        )
+                                    })
+                            }),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("batch-start")
+                            .label(t("batch-start"))
+                            .primary()
+                            .small()
+                            .disabled(active || self.input_path.is_none())
+                            .on_click(move |_, _, cx| {
+                                start_entity.update(cx, |view, cx| view.start(cx));
+                            }),
+                    )
+                    .child(
+                        Button::new("batch-pause")
+                            .label(if paused { "Resume" } else { "Pause" })
+                            .small()
+                            .disabled(!active || !pausable)
+                            .on_click(move |_, _, cx| {
+                                pause_entity.update(cx, |view, cx| view.toggle_pause(cx));
+                            }),
+                    )
+                    .child(
+                        Button::new("batch-cancel")
+                            .label(t("action-cancel"))
+                            .small()
+                            .disabled(!active)
+                            .on_click(move |_, _, cx| {
+                                cancel_entity.update(cx, |view, cx| view.cancel(cx));
+                            }),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+            })
+            .when_some(self.run.as_ref(), |this, run| {
+                this.child(self.render_progress(run, cx))
+            })
+    }
+}
+
+fn mode_label(mode: BatchMode) -> &'static str {
+    match mode {
+        BatchMode::Realtime => "Real-time",
+        BatchMode::ProviderBatch => "Provider batch (50% off)",
+    }
+}
+
+// ── Setting page entry point ────────────────────────────────────────────────
+
+pub fn batch_runs_page() -> SettingPage {
+    SettingPage::new(t("settings-page-batch-runs"))
+        .description("Run a file of prompts against a model for labeling and evaluation")
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title("Batch Prompt Runner")
+                .description(
+                    "Choose a CSV with a \"prompt\" column or a JSONL file with a \"prompt\" \
+                     field (an \"id\" is optional). Each prompt is sent on its own, without \
+                     tools, and responses, tokens and costs are written to a JSONL file in \
+                     the exports/batch folder. Azure OpenAI models with a Global Batch \
+                     deployment can run as one provider batch job at half the cost.",
+                )
+                .items(vec![SettingItem::render(|_options, window, cx| {
+                    let view = match cx
+                        .try_global::<GlobalBatchRunnerView>()
+                        .and_then(|g| g.get())
+                    {
+                        Some(view) => view,
+                        None => {
+                            let new_view = cx.new(|cx| BatchRunnerView::new(window, cx));
+                            cx.set_global(GlobalBatchRunnerView::new(new_view.clone()));
+                            new_view
+                        }
+                    };
+
+                    div().w_full().child(view)
+                })]),
+        ])
+}
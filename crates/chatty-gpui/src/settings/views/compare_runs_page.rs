@@ -0,0 +1,381 @@
+use crate::i18n::t;
+use chatty_core::exporters::compare::{
+    self, CompareRun, DiffSpan, TurnComparison, compare_runs, comparison_to_markdown,
+};
+use chatty_core::exporters::review;
+use gpui::{
+    App, AsyncApp, Context, FocusHandle, Focusable, FontWeight, HighlightStyle, Hsla, IntoElement,
+    PathPromptOptions, Render, SharedString, Styled, StyledText, WeakEntity, Window, div,
+    prelude::*,
+};
+use gpui_component::setting::{SettingGroup, SettingItem, SettingPage};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt as _,
+    button::{Button, ButtonVariants},
+    h_flex, v_flex,
+};
+use std::ops::Range;
+use std::path::PathBuf;
+use tracing::warn;
+
+// ── Global singleton ────────────────────────────────────────────────────────
+
+pub type GlobalCompareRunsView = crate::global_entity::GlobalStrongEntity<CompareRunsView>;
+
+// ── Compare entity ──────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// Shows two runs side by side, aligned turn by turn.
+pub struct CompareRunsView {
+    focus_handle: FocusHandle,
+    left: Option<CompareRun>,
+    right: Option<CompareRun>,
+    comparisons: Vec<TurnComparison>,
+    error: Option<String>,
+}
+
+impl CompareRunsView {
+    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            left: None,
+            right: None,
+            comparisons: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn pick_run(&mut self, side: Side, cx: &mut Context<Self>) {
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Select ATIF export or batch results".into()),
+        });
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let Ok(Ok(Some(paths))) = receiver.await else {
+                return;
+            };
+            let Some(path) = paths.into_iter().next() else {
+                return;
+            };
+            let result = compare::load_run(&path).await;
+            this.update(cx, |view, cx| {
+                match result {
+                    Ok(run) => {
+                        match side {
+                            Side::Left => view.left = Some(run),
+                            Side::Right => view.right = Some(run),
+                        }
+                        view.error = None;
+                        view.recompute();
+                    }
+                    Err(e) => {
+                        warn!(error = ?e, path = %path.display(), "Failed to load run for comparison");
+                        view.error = Some(e.to_string());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn recompute(&mut self) {
+        self.comparisons = match (&self.left, &self.right) {
+            (Some(left), Some(right)) => compare_runs(left, right),
+            _ => Vec::new(),
+        };
+    }
+
+    fn export_markdown(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(left), Some(right)) = (&self.left, &self.right) else {
+            return;
+        };
+        let Some(dir) = review::default_exports_dir().map(|d| d.join("compare")) else {
+            return;
+        };
+        let markdown = comparison_to_markdown(left, right, &self.comparisons);
+        let path: PathBuf = compare::markdown_path(&dir, left, right);
+        let window_handle = window.window_handle();
+        cx.spawn(async move |_this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = async {
+                tokio::fs::create_dir_all(&dir).await?;
+                tokio::fs::write(&path, markdown).await
+            }
+            .await;
+            let message = match result {
+                Ok(()) => format!("Comparison written to {}", path.display()),
+                Err(e) => {
+                    warn!(error = ?e, "Failed to write comparison");
+                    format!("Failed to write comparison: {e}")
+                }
+            };
+            window_handle
+                .update(cx, |_, window, cx| window.push_notification(message, cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    /// Reply text for one side with the words unique to it highlighted.
+    fn highlighted_reply(
+        comparison: &TurnComparison,
+        side: Side,
+        color: Hsla,
+    ) -> (String, Vec<(Range<usize>, HighlightStyle)>) {
+        let turn = match side {
+            Side::Left => comparison.left.as_ref(),
+            Side::Right => comparison.right.as_ref(),
+        };
+        let Some(turn) = turn else {
+            return (String::new(), Vec::new());
+        };
+        if comparison.diff.is_empty() {
+            return (turn.assistant.clone(), Vec::new());
+        }
+
+        let mut text = String::new();
+        let mut highlights = Vec::new();
+        for span in &comparison.diff {
+            let (piece, highlighted) = match (span, side) {
+                (DiffSpan::Same(t), _) => (t, false),
+                (DiffSpan::Removed(t), Side::Left) | (DiffSpan::Added(t), Side::Right) => (t, true),
+                _ => continue,
+            };
+            let start = text.len();
+            text.push_str(piece);
+            if highlighted {
+                highlights.push((
+                    start..text.len(),
+                    HighlightStyle {
+                        background_color: Some(color.opacity(0.2)),
+                        color: Some(color),
+                        ..Default::default()
+                    },
+                ));
+            }
+        }
+        (text, highlights)
+    }
+
+    fn render_side(
+        comparison: &TurnComparison,
+        side: Side,
+        color: Hsla,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let turn = match side {
+            Side::Left => comparison.left.as_ref(),
+            Side::Right => comparison.right.as_ref(),
+        };
+        let muted = cx.theme().muted_foreground;
+        let body = match turn {
+            None => div()
+                .text_xs()
+                .italic()
+                .text_color(muted)
+                .child(t("compare-no-matching-turn"))
+                .into_any_element(),
+            Some(turn) => {
+                let (text, highlights) = Self::highlighted_reply(comparison, side, color);
+                v_flex()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_sm()
+                            .child(StyledText::new(text).with_highlights(highlights)),
+                    )
+                    .children(turn.tool_calls.iter().map(|call| {
+                        div()
+                            .text_xs()
+                            .font_family("monospace")
+                            .text_color(if comparison.tool_calls_differ {
+                                color
+                            } else {
+                                muted
+                            })
+                            .child(SharedString::from(format!("⚙ {call}")))
+                    }))
+                    .into_any_element()
+            }
+        };
+        div().flex_1().min_w_0().p_2().child(body)
+    }
+
+    fn render_comparison(
+        &self,
+        ix: usize,
+        comparison: &TurnComparison,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let removed = cx.theme().ring;
+        let added = gpui::green();
+        let prompt = comparison
+            .left
+            .as_ref()
+            .or(comparison.right.as_ref())
+            .map(|t| t.user.trim().chars().take(300).collect::<String>())
+            .unwrap_or_default();
+        let status = if comparison.is_identical() {
+            "identical".to_string()
+        } else {
+            format!("{:.0}% similar", comparison.similarity() * 100.0)
+        };
+
+        v_flex()
+            .id(("compare-turn", ix))
+            .w_full()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .px_2()
+                    .py_1()
+                    .bg(cx.theme().muted)
+                    .text_xs()
+                    .child(
+                        div()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(comparison.label.clone()),
+                    )
+                    .child(div().text_color(cx.theme().muted_foreground).child(status))
+                    .when(comparison.tool_calls_differ, |this| {
+                        this.child(div().text_color(removed).child("tool calls diverge"))
+                    }),
+            )
+            .when(!prompt.is_empty(), |this| {
+                this.child(
+                    div()
+                        .px_2()
+                        .pt_1()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!("Prompt: {prompt}")),
+                )
+            })
+            .child(
+                h_flex()
+                    .items_start()
+                    .child(Self::render_side(comparison, Side::Left, removed, cx))
+                    .child(div().w(gpui::px(1.)).h_full().bg(cx.theme().border))
+                    .child(Self::render_side(comparison, Side::Right, added, cx)),
+            )
+    }
+}
+
+impl Focusable for CompareRunsView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for CompareRunsView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let label = |run: &Option<CompareRun>, fallback: &str| {
+            run.as_ref()
+                .map(|r| r.label.clone())
+                .unwrap_or_else(|| fallback.to_string())
+        };
+        let left_entity = cx.entity();
+        let right_entity = cx.entity();
+        let export_entity = cx.entity();
+        let identical = self.comparisons.iter().filter(|c| c.is_identical()).count();
+
+        v_flex()
+            .size_full()
+            .gap_3()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("compare-left")
+                            .label(label(&self.left, "Choose left run…"))
+                            .outline()
+                            .on_click(move |_, _, cx| {
+                                left_entity.update(cx, |view, cx| view.pick_run(Side::Left, cx));
+                            }),
+                    )
+                    .child(
+                        Button::new("compare-right")
+                            .label(label(&self.right, "Choose right run…"))
+                            .outline()
+                            .on_click(move |_, _, cx| {
+                                right_entity.update(cx, |view, cx| view.pick_run(Side::Right, cx));
+                            }),
+                    )
+                    .child(
+                        Button::new("compare-export")
+                            .label(t("compare-export-markdown"))
+                            .primary()
+                            .disabled(self.comparisons.is_empty())
+                            .on_click(move |_, window, cx| {
+                                export_entity
+                                    .update(cx, |view, cx| view.export_markdown(window, cx));
+                            }),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+            })
+            .when(!self.comparisons.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!(
+                            "{} turns · {} identical · {} differ",
+                            self.comparisons.len(),
+                            identical,
+                            self.comparisons.len() - identical
+                        )),
+                )
+            })
+            .children(
+                self.comparisons
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, comparison)| self.render_comparison(ix, comparison, cx)),
+            )
+    }
+}
+
+// ── Setting page entry point ────────────────────────────────────────────────
+
+pub fn compare_runs_page() -> SettingPage {
+    SettingPage::new(t("settings-page-compare-runs"))
+        .description("Compare two conversations or batch runs turn by turn")
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title("Run Comparison")
+                .description(
+                    "Pick two ATIF exports (*.atif.json) or two batch result files (*.jsonl). \
+                     Batch results are matched by row id, conversations by turn. Words only in \
+                     the left reply are shown in red, words only in the right reply in green.",
+                )
+                .items(vec![SettingItem::render(|_options, window, cx| {
+                    let view = match cx
+                        .try_global::<GlobalCompareRunsView>()
+                        .and_then(|g| g.get())
+                    {
+                        Some(view) => view,
+                        None => {
+                            let new_view = cx.new(|cx| CompareRunsView::new(window, cx));
+                            cx.set_global(GlobalCompareRunsView::new(new_view.clone()));
+                            new_view
+                        }
+                    };
+
+                    div().w_full().child(view)
+                })]),
+        ])
+}
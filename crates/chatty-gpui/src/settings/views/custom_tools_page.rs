@@ -0,0 +1,1197 @@
+use crate::i18n::t;
+use crate::settings::controllers::execution_settings_controller;
+use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use chatty_core::services::openapi_import::{OpenApiSpec, parse_openapi_spec};
+use chatty_core::tools::GraphQlEndpointConfig;
+use chatty_core::tools::custom_http_tool::{
+    CustomHttpMethod, CustomHttpToolConfig, format_header_lines, parse_header_lines,
+};
+use gpui::{
+    App, Context, Entity, FocusHandle, Focusable, FontWeight, IntoElement, Render, SharedString,
+    Styled, Window, div, prelude::*, px,
+};
+use gpui_component::{
+    ActiveTheme, IndexPath, Sizable, WindowExt as _,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    select::{Select, SelectState},
+    setting::{SettingGroup, SettingItem, SettingPage},
+    v_flex,
+};
+use gpui_component::{Icon, IconName};
+
+// ── Global singleton ────────────────────────────────────────────────────────
+
+pub type GlobalCustomToolsTableView =
+    crate::global_entity::GlobalStrongEntity<CustomToolsTableView>;
+pub type GlobalGraphQlEndpointsView =
+    crate::global_entity::GlobalStrongEntity<GraphQlEndpointsView>;
+
+/// Create a dialog input prefilled with `text`; `multi_line` inputs grow with their content.
+fn text_input<V: 'static>(
+    text: String,
+    placeholder: &'static str,
+    multi_line: bool,
+    window: &mut Window,
+    cx: &mut Context<V>,
+) -> Entity<InputState> {
+    cx.new(|cx| {
+        let mut state = InputState::new(window, cx).placeholder(placeholder);
+        if multi_line {
+            state = state.auto_grow(3, 10);
+        }
+        state.set_value(text, window, cx);
+        state
+    })
+}
+
+/// Read an OpenAPI spec from a file path, or treat the text as the spec itself.
+fn load_spec_text(input: &str) -> Result<String, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Enter a file path or paste a spec".to_string());
+    }
+    if input.contains('\n') || input.starts_with('{') {
+        return Ok(input.to_string());
+    }
+    let path = expand_home(input);
+    std::fs::read_to_string(&path).map_err(|e| format!("Could not read {}: {e}", path.display()))
+}
+
+/// Expand a leading `~/` to the home directory.
+fn expand_home(path: &str) -> std::path::PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => std::path::PathBuf::from(path),
+    }
+}
+
+/// A parsed spec waiting for the user to pick operations.
+struct PendingImport {
+    spec: OpenApiSpec,
+    selected: Vec<bool>,
+}
+
+// ── Table view entity ───────────────────────────────────────────────────────
+
+pub struct CustomToolsTableView {
+    focus_handle: FocusHandle,
+    pending_import: Option<PendingImport>,
+}
+
+impl CustomToolsTableView {
+    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        Self {
+            focus_handle,
+            pending_import: None,
+        }
+    }
+
+    /// Ask for an OpenAPI spec (file path or pasted text) and parse it.
+    fn show_import_dialog(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let spec_input = text_input(
+            String::new(),
+            "~/specs/openapi.yaml, or paste a JSON/YAML spec",
+            true,
+            window,
+            cx,
+        );
+        let view_entity = cx.entity().clone();
+
+        window.open_dialog(cx, move |dialog, _, _| {
+            dialog
+                .title("Import OpenAPI Spec")
+                .overlay(true)
+                .keyboard(true)
+                .close_button(true)
+                .overlay_closable(true)
+                .w(px(560.))
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .p_4()
+                        .child(div().text_sm().child(t("custom-tools-openapi-spec")))
+                        .child(Input::new(&spec_input))
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .justify_end()
+                                .pt_4()
+                                .child(
+                                    Button::new("cancel-openapi-import")
+                                        .label(t("action-cancel"))
+                                        .on_click(move |_, window, cx| {
+                                            window.close_dialog(cx);
+                                        }),
+                                )
+                                .child(
+                                    Button::new("load-openapi-spec")
+                                        .primary()
+                                        .label(t("custom-tools-load"))
+                                        .on_click({
+                                            let spec_input = spec_input.clone();
+                                            let view_entity = view_entity.clone();
+                                            move |_, window, cx| {
+                                                let text = spec_input.read(cx).value().to_string();
+                                                let spec = load_spec_text(&text).and_then(|text| {
+                                                    parse_openapi_spec(&text)
+                                                        .map_err(|e| format!("{e:#}"))
+                                                });
+                                                match spec {
+                                                    Ok(spec) => {
+                                                        window.close_dialog(cx);
+                                                        view_entity.update(cx, |view, cx| {
+                                                            view.show_operations_dialog(
+                                                                spec, window, cx,
+                                                            );
+                                                        });
+                                                    }
+                                                    Err(message) => {
+                                                        window.push_notification(message, cx)
+                                                    }
+                                                }
+                                            }
+                                        }),
+                                ),
+                        ),
+                )
+        });
+    }
+
+    /// Let the user pick operations from a parsed spec and configure auth.
+    fn show_operations_dialog(
+        &mut self,
+        spec: OpenApiSpec,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let base_url_input = text_input(
+            spec.base_url.clone().unwrap_or_default(),
+            "https://api.example.com/v1",
+            false,
+            window,
+            cx,
+        );
+        let secret_input = text_input(String::new(), "EXAMPLE_API_TOKEN", false, window, cx);
+        let title = spec.title.clone();
+        let auth_label = spec.auth.as_ref().map(|auth| auth.label());
+        let skipped = spec.skipped.clone();
+        self.pending_import = Some(PendingImport {
+            selected: vec![true; spec.operations.len()],
+            spec,
+        });
+        let view_entity = cx.entity().clone();
+
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let (operations, selected) = view_entity
+                .read(cx)
+                .pending_import
+                .as_ref()
+                .map(|p| (p.spec.operations.clone(), p.selected.clone()))
+                .unwrap_or_default();
+            let selected_count = selected.iter().filter(|s| **s).count();
+            let set_all = |id: &'static str, label: &'static str, value: bool| {
+                let view_entity = view_entity.clone();
+                Button::new(id)
+                    .label(label)
+                    .ghost()
+                    .xsmall()
+                    .on_click(move |_, _, cx| {
+                        view_entity.update(cx, |view, _| {
+                            if let Some(pending) = view.pending_import.as_mut() {
+                                pending.selected.fill(value);
+                            }
+                        });
+                        cx.refresh_windows();
+                    })
+            };
+
+            dialog
+                .title(format!("Import {title}"))
+                .overlay(true)
+                .keyboard(true)
+                .close_button(true)
+                .overlay_closable(true)
+                .w(px(640.))
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .p_4()
+                        .child(
+                            v_flex()
+                                .gap_1()
+                                .child(div().text_sm().child(t("custom-tools-base-url")))
+                                .child(Input::new(&base_url_input)),
+                        )
+                        .when_some(auth_label.clone(), |col, label| {
+                            col.child(
+                                v_flex()
+                                    .gap_1()
+                                    .child(div().text_sm().child(format!(
+                                        "Secret holding the credential ({label})"
+                                    )))
+                                    .child(Input::new(&secret_input)),
+                            )
+                        })
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    div()
+                                        .flex_1()
+                                        .text_sm()
+                                        .font_weight(FontWeight::SEMIBOLD)
+                                        .child(format!(
+                                            "Operations ({selected_count} of {} selected)",
+                                            operations.len()
+                                        )),
+                                )
+                                .child(set_all("openapi-select-all", "All", true))
+                                .child(set_all("openapi-select-none", "None", false)),
+                        )
+                        .child(
+                            v_flex()
+                                .id("openapi-operations")
+                                .max_h(px(320.))
+                                .overflow_y_scroll()
+                                .rounded_md()
+                                .border_1()
+                                .border_color(cx.theme().border)
+                                .children(operations.iter().enumerate().map(|(ix, op)| {
+                                    let is_selected = selected.get(ix).copied().unwrap_or(false);
+                                    let view_entity = view_entity.clone();
+                                    h_flex()
+                                        .px_3()
+                                        .py_1()
+                                        .gap_2()
+                                        .border_b_1()
+                                        .border_color(cx.theme().border)
+                                        .child(
+                                            Button::new(SharedString::from(format!(
+                                                "openapi-op-{ix}"
+                                            )))
+                                            .label(if is_selected { "On" } else { "Off" })
+                                            .when(is_selected, |b| b.primary())
+                                            .when(!is_selected, |b| b.ghost())
+                                            .xsmall()
+                                            .on_click(move |_, _, cx| {
+                                                view_entity.update(cx, |view, _| {
+                                                    if let Some(flag) = view
+                                                        .pending_import
+                                                        .as_mut()
+                                                        .and_then(|p| p.selected.get_mut(ix))
+                                                    {
+                                                        *flag = !*flag;
+                                                    }
+                                                });
+                                                cx.refresh_windows();
+                                            }),
+                                        )
+                                        .child(
+                                            v_flex()
+                                                .flex_1()
+                                                .min_w_0()
+                                                .child(div().text_sm().child(op.tool_name.clone()))
+                                                .child(
+                                                    div()
+                                                        .text_xs()
+                                                        .font_family("monospace")
+                                                        .text_color(cx.theme().muted_foreground)
+                                                        .child(format!(
+                                                            "{} {}",
+                                                            op.method.as_str(),
+                                                            op.path
+                                                        )),
+                                                ),
+                                        )
+                                })),
+                        )
+                        .when(!skipped.is_empty(), |col| {
+                            col.child(
+                                div()
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(format!("Skipped: {}", skipped.join("; "))),
+                            )
+                        })
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .justify_end()
+                                .pt_4()
+                                .child(Button::new("cancel-openapi-ops").label(t("action-cancel")).on_click({
+                                    let view_entity = view_entity.clone();
+                                    move |_, window, cx| {
+                                        view_entity.update(cx, |view, _| {
+                                            view.pending_import = None;
+                                        });
+                                        window.close_dialog(cx);
+                                    }
+                                }))
+                                .child(
+                                    Button::new("import-openapi-ops")
+                                        .primary()
+                                        .label(t("custom-tools-import"))
+                                        .on_click({
+                                            let base_url_input = base_url_input.clone();
+                                            let secret_input = secret_input.clone();
+                                            let view_entity = view_entity.clone();
+                                            move |_, window, cx| {
+                                                let base_url =
+                                                    base_url_input.read(cx).value().to_string();
+                                                let secret_key =
+                                                    secret_input.read(cx).value().to_string();
+                                                let Some(result) =
+                                                    view_entity.read(cx).pending_import.as_ref().map(
+                                                        |pending| {
+                                                            let indices: Vec<usize> = pending
+                                                                .selected
+                                                                .iter()
+                                                                .enumerate()
+                                                                .filter(|(_, s)| **s)
+                                                                .map(|(ix, _)| ix)
+                                                                .collect();
+                                                            if indices.is_empty() {
+                                                                return Err(
+                                                                    "Select at least one operation"
+                                                                        .to_string(),
+                                                                );
+                                                            }
+                                                            pending
+                                                                .spec
+                                                                .tool_configs(
+                                                                    &indices,
+                                                                    &base_url,
+                                                                    Some(&secret_key),
+                                                                )
+                                                                .map_err(|e| format!("{e:#}"))
+                                                        },
+                                                    )
+                                                else {
+                                                    window.close_dialog(cx);
+                                                    return;
+                                                };
+
+                                                match result {
+                                                    Ok(tools) => {
+                                                        let count = execution_settings_controller::import_custom_http_tools(
+                                                            tools, cx,
+                                                        );
+                                                        view_entity.update(cx, |view, cx| {
+                                                            view.pending_import = None;
+                                                            cx.notify();
+                                                        });
+                                                        window.close_dialog(cx);
+                                                        window.push_notification(
+                                                            format!("Imported {count} tools"),
+                                                            cx,
+                                                        );
+                                                    }
+                                                    Err(message) => {
+                                                        window.push_notification(message, cx)
+                                                    }
+                                                }
+                                            }
+                                        }),
+                                ),
+                        ),
+                )
+        });
+    }
+
+    /// Open the add dialog, or the edit dialog when `existing` is set.
+    fn show_tool_dialog(
+        &self,
+        existing: Option<CustomHttpToolConfig>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let name_input = text_input(
+            existing
+                .as_ref()
+                .map(|t| t.name.clone())
+                .unwrap_or_default(),
+            "github_issues",
+            false,
+            window,
+            cx,
+        );
+        let description_input = text_input(
+            existing
+                .as_ref()
+                .map(|t| t.description.clone())
+                .unwrap_or_default(),
+            "List issues of a GitHub repository",
+            false,
+            window,
+            cx,
+        );
+        let url_input = text_input(
+            existing
+                .as_ref()
+                .map(|t| t.url_template.clone())
+                .unwrap_or_default(),
+            "https://api.github.com/repos/{owner}/{repo}/issues",
+            false,
+            window,
+            cx,
+        );
+        let headers_input = text_input(
+            existing
+                .as_ref()
+                .map(|t| format_header_lines(&t.headers))
+                .unwrap_or_default(),
+            "Authorization: Bearer {{secret:GITHUB_TOKEN}}",
+            true,
+            window,
+            cx,
+        );
+        let schema_input = text_input(
+            existing
+                .as_ref()
+                .map(|t| serde_json::to_string_pretty(&t.parameters).unwrap_or_default())
+                .unwrap_or_default(),
+            "JSON schema (leave empty to derive one from the URL placeholders)",
+            true,
+            window,
+            cx,
+        );
+        let methods: Vec<String> = CustomHttpMethod::ALL
+            .iter()
+            .map(|m| m.as_str().to_string())
+            .collect();
+        let selected_method = existing
+            .as_ref()
+            .and_then(|t| CustomHttpMethod::ALL.iter().position(|m| *m == t.method))
+            .unwrap_or(0);
+        let method_select = cx
+            .new(|cx| SelectState::new(methods, Some(IndexPath::new(selected_method)), window, cx));
+        let view_entity = cx.entity().clone();
+        let title = if existing.is_some() {
+            "Edit REST Tool"
+        } else {
+            "Add REST Tool"
+        };
+
+        let labeled = |label: &'static str, child: gpui::AnyElement| {
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child(label))
+                .child(child)
+        };
+
+        window.open_dialog(cx, move |dialog, _, _| {
+            dialog
+                .title(title)
+                .overlay(true)
+                .keyboard(true)
+                .close_button(true)
+                .overlay_closable(true)
+                .w(px(560.))
+                .child(
+                    div().id("custom-tool-form").child(
+                        v_flex()
+                            .gap_3()
+                            .p_4()
+                            .child(labeled("Name", Input::new(&name_input).into_any_element()))
+                            .child(labeled(
+                                "Description",
+                                Input::new(&description_input).into_any_element(),
+                            ))
+                            .child(labeled(
+                                "Method",
+                                Select::new(&method_select).into_any_element(),
+                            ))
+                            .child(labeled(
+                                "URL template",
+                                Input::new(&url_input).into_any_element(),
+                            ))
+                            .child(labeled(
+                                "Headers (one 'Name: value' per line, {{secret:KEY}} for secrets)",
+                                Input::new(&headers_input).into_any_element(),
+                            ))
+                            .child(labeled(
+                                "Parameters",
+                                Input::new(&schema_input).into_any_element(),
+                            ))
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .justify_end()
+                                    .pt_4()
+                                    .child(Button::new("cancel-custom-tool").label(t("action-cancel")).on_click(
+                                        move |_, window, cx| {
+                                            window.close_dialog(cx);
+                                        },
+                                    ))
+                                    .child(
+                                        Button::new("save-custom-tool")
+                                            .primary()
+                                            .label(t("action-save"))
+                                            .on_click({
+                                                let existing = existing.clone();
+                                                let name_input = name_input.clone();
+                                                let description_input = description_input.clone();
+                                                let url_input = url_input.clone();
+                                                let headers_input = headers_input.clone();
+                                                let schema_input = schema_input.clone();
+                                                let method_select = method_select.clone();
+                                                let view_entity = view_entity.clone();
+                                                move |_, window, cx| {
+                                                    let read = |input: &Entity<InputState>,
+                                                                cx: &App| {
+                                                        input.read(cx).value().trim().to_string()
+                                                    };
+                                                    let method = method_select
+                                                        .read(cx)
+                                                        .selected_index(cx)
+                                                        .and_then(|ix| {
+                                                            CustomHttpMethod::ALL.get(ix.row).copied()
+                                                        })
+                                                        .unwrap_or_default();
+                                                    let mut tool = existing.clone().unwrap_or_else(
+                                                        || {
+                                                            CustomHttpToolConfig::new(
+                                                                "",
+                                                                method,
+                                                                "",
+                                                            )
+                                                        },
+                                                    );
+                                                    tool.name = read(&name_input, cx);
+                                                    tool.description =
+                                                        read(&description_input, cx);
+                                                    tool.method = method;
+                                                    tool.url_template = read(&url_input, cx);
+
+                                                    let result = parse_header_lines(
+                                                        &read(&headers_input, cx),
+                                                    )
+                                                    .and_then(|headers| {
+                                                        tool.headers = headers;
+                                                        let schema = read(&schema_input, cx);
+                                                        tool.parameters = if schema.is_empty() {
+                                                            tool.placeholder_schema()
+                                                        } else {
+                                                            serde_json::from_str(&schema).map_err(
+                                                                |e| {
+                                                                    format!(
+                                                                        "Parameters are not valid JSON: {e}"
+                                                                    )
+                                                                },
+                                                            )?
+                                                        };
+                                                        tool.validate()
+                                                    });
+                                                    if let Err(message) = result {
+                                                        window.push_notification(message, cx);
+                                                        return;
+                                                    }
+
+                                                    let settings =
+                                                        cx.global::<ExecutionSettingsModel>();
+                                                    let duplicate = settings
+                                                        .custom_http_tools
+                                                        .iter()
+                                                        .any(|t| {
+                                                            t.name == tool.name && t.id != tool.id
+                                                        })
+                                                        || settings
+                                                            .graphql_endpoints
+                                                            .iter()
+                                                            .any(|e| e.name == tool.name);
+                                                    if duplicate {
+                                                        window.push_notification(
+                                                            "A tool with this name already exists",
+                                                            cx,
+                                                        );
+                                                        return;
+                                                    }
+
+                                                    execution_settings_controller::upsert_custom_http_tool(
+                                                        tool, cx,
+                                                    );
+                                                    view_entity.update(cx, |_, cx| cx.notify());
+                                                    window.close_dialog(cx);
+                                                }
+                                            }),
+                                    ),
+                            ),
+                    ),
+                )
+        });
+    }
+
+    /// Render a single tool row.
+    fn render_row(
+        &self,
+        row_ix: usize,
+        tool: &CustomHttpToolConfig,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let id_for_toggle = tool.id.clone();
+        let id_for_delete = tool.id.clone();
+        let tool_for_edit = tool.clone();
+        let view_for_toggle = cx.entity().clone();
+        let view_for_edit = cx.entity().clone();
+        let view_for_delete = cx.entity().clone();
+        let enabled = tool.enabled;
+        let secrets = tool.referenced_secrets();
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(if enabled {
+                                cx.theme().foreground
+                            } else {
+                                cx.theme().muted_foreground
+                            })
+                            .child(tool.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_family("monospace")
+                            .text_color(cx.theme().muted_foreground)
+                            .child(format!("{} {}", tool.method.as_str(), tool.url_template)),
+                    )
+                    .when(!secrets.is_empty(), |col| {
+                        col.child(
+                            div()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(format!("Secrets: {}", secrets.join(", "))),
+                        )
+                    })
+                    .when_some(tool.source.clone(), |col, source| {
+                        col.child(
+                            div()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(format!("Imported from {source}")),
+                        )
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new(SharedString::from(format!("custom-tool-toggle-{}", row_ix)))
+                            .label(if enabled { "On" } else { "Off" })
+                            .when(enabled, |b| b.primary())
+                            .when(!enabled, |b| b.ghost())
+                            .xsmall()
+                            .on_click(move |_, _, cx| {
+                                execution_settings_controller::toggle_custom_http_tool(
+                                    &id_for_toggle,
+                                    cx,
+                                );
+                                view_for_toggle.update(cx, |_, cx| cx.notify());
+                            }),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("custom-tool-edit-{}", row_ix)))
+                            .label(t("action-edit"))
+                            .ghost()
+                            .xsmall()
+                            .on_click(move |_, window, cx| {
+                                let tool = tool_for_edit.clone();
+                                view_for_edit.update(cx, |view, cx| {
+                                    view.show_tool_dialog(Some(tool), window, cx);
+                                });
+                            }),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("custom-tool-del-{}", row_ix)))
+                            .icon(Icon::new(IconName::Close))
+                            .ghost()
+                            .xsmall()
+                            .on_click(move |_, _, cx| {
+                                execution_settings_controller::remove_custom_http_tool(
+                                    &id_for_delete,
+                                    cx,
+                                );
+                                view_for_delete.update(cx, |_, cx| cx.notify());
+                            }),
+                    ),
+            )
+    }
+
+    /// Render the empty state.
+    fn render_empty(&self, cx: &Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .justify_center()
+            .py_6()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(t("custom-tools-no-rest"))
+    }
+}
+
+impl Focusable for CustomToolsTableView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for CustomToolsTableView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let tools = cx
+            .global::<ExecutionSettingsModel>()
+            .custom_http_tools
+            .clone();
+        let entity = cx.entity().clone();
+        let import_entity = cx.entity().clone();
+
+        let table = v_flex()
+            .w_full()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .overflow_hidden()
+            .map(|this| {
+                if tools.is_empty() {
+                    this.child(self.render_empty(cx))
+                } else {
+                    this.children(
+                        tools
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, tool)| self.render_row(ix, tool, cx).into_any_element()),
+                    )
+                }
+            });
+
+        v_flex().size_full().gap_3().child(table).child(
+            h_flex()
+                .gap_2()
+                .child(
+                    Button::new("add-custom-tool")
+                        .label("+ Add REST Tool")
+                        .small()
+                        .on_click(move |_, window, cx| {
+                            entity.update(cx, |view, cx| {
+                                view.show_tool_dialog(None, window, cx);
+                            });
+                        }),
+                )
+                .child(
+                    Button::new("import-openapi")
+                        .label(t("custom-tools-import-openapi"))
+                        .small()
+                        .on_click(move |_, window, cx| {
+                            import_entity.update(cx, |view, cx| {
+                                view.show_import_dialog(window, cx);
+                            });
+                        }),
+                ),
+        )
+    }
+}
+
+// ── GraphQL endpoints ───────────────────────────────────────────────────────
+
+pub struct GraphQlEndpointsView {
+    focus_handle: FocusHandle,
+}
+
+impl GraphQlEndpointsView {
+    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        Self { focus_handle }
+    }
+
+    /// Open the add dialog, or the edit dialog when `existing` is set.
+    fn show_endpoint_dialog(
+        &self,
+        existing: Option<GraphQlEndpointConfig>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let name_input = text_input(
+            existing
+                .as_ref()
+                .map(|e| e.name.clone())
+                .unwrap_or_default(),
+            "github_graphql",
+            false,
+            window,
+            cx,
+        );
+        let description_input = text_input(
+            existing
+                .as_ref()
+                .map(|e| e.description.clone())
+                .unwrap_or_default(),
+            "Query GitHub repositories, issues and pull requests",
+            false,
+            window,
+            cx,
+        );
+        let endpoint_input = text_input(
+            existing
+                .as_ref()
+                .map(|e| e.endpoint.clone())
+                .unwrap_or_default(),
+            "https://api.github.com/graphql",
+            false,
+            window,
+            cx,
+        );
+        let headers_input = text_input(
+            existing
+                .as_ref()
+                .map(|e| format_header_lines(&e.headers))
+                .unwrap_or_default(),
+            "Authorization: Bearer {{secret:GITHUB_TOKEN}}",
+            true,
+            window,
+            cx,
+        );
+        let view_entity = cx.entity().clone();
+        let title = if existing.is_some() {
+            "Edit GraphQL Endpoint"
+        } else {
+            "Add GraphQL Endpoint"
+        };
+
+        let labeled = |label: &'static str, child: gpui::AnyElement| {
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child(label))
+                .child(child)
+        };
+
+        window.open_dialog(cx, move |dialog, _, _| {
+            dialog
+                .title(title)
+                .overlay(true)
+                .keyboard(true)
+                .close_button(true)
+                .overlay_closable(true)
+                .w(px(560.))
+                .child(
+                    div().id("graphql-endpoint-form").child(
+                        v_flex()
+                            .gap_3()
+                            .p_4()
+                            .child(labeled("Tool name", Input::new(&name_input).into_any_element()))
+                            .child(labeled(
+                                "Description",
+                                Input::new(&description_input).into_any_element(),
+                            ))
+                            .child(labeled(
+                                "Endpoint URL",
+                                Input::new(&endpoint_input).into_any_element(),
+                            ))
+                            .child(labeled(
+                                "Headers (one 'Name: value' per line, {{secret:KEY}} for secrets)",
+                                Input::new(&headers_input).into_any_element(),
+                            ))
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .justify_end()
+                                    .pt_4()
+                                    .child(Button::new("cancel-graphql-endpoint").label(t("action-cancel")).on_click(
+                                        move |_, window, cx| {
+                                            window.close_dialog(cx);
+                                        },
+                                    ))
+                                    .child(
+                                        Button::new("save-graphql-endpoint")
+                                            .primary()
+                                            .label(t("action-save"))
+                                            .on_click({
+                                                let existing = existing.clone();
+                                                let name_input = name_input.clone();
+                                                let description_input = description_input.clone();
+                                                let endpoint_input = endpoint_input.clone();
+                                                let headers_input = headers_input.clone();
+                                                let view_entity = view_entity.clone();
+                                                move |_, window, cx| {
+                                                    let read = |input: &Entity<InputState>,
+                                                                cx: &App| {
+                                                        input.read(cx).value().trim().to_string()
+                                                    };
+                                                    let mut endpoint = existing
+                                                        .clone()
+                                                        .unwrap_or_else(|| {
+                                                            GraphQlEndpointConfig::new("", "")
+                                                        });
+                                                    endpoint.name = read(&name_input, cx);
+                                                    endpoint.description =
+                                                        read(&description_input, cx);
+                                                    endpoint.endpoint = read(&endpoint_input, cx);
+
+                                                    let result = parse_header_lines(
+                                                        &read(&headers_input, cx),
+                                                    )
+                                                    .and_then(|headers| {
+                                                        endpoint.headers = headers;
+                                                        endpoint.validate()
+                                                    });
+                                                    if let Err(message) = result {
+                                                        window.push_notification(message, cx);
+                                                        return;
+                                                    }
+
+                                                    let settings =
+                                                        cx.global::<ExecutionSettingsModel>();
+                                                    let duplicate = settings
+                                                        .graphql_endpoints
+                                                        .iter()
+                                                        .any(|e| {
+                                                            e.name == endpoint.name
+                                                                && e.id != endpoint.id
+                                                        })
+                                                        || settings
+                                                            .custom_http_tools
+                                                            .iter()
+                                                            .any(|t| t.name == endpoint.name);
+                                                    if duplicate {
+                                                        window.push_notification(
+                                                            "A tool with this name already exists",
+                                                            cx,
+                                                        );
+                                                        return;
+                                                    }
+
+                                                    execution_settings_controller::upsert_graphql_endpoint(
+                                                        endpoint, cx,
+                                                    );
+                                                    view_entity.update(cx, |_, cx| cx.notify());
+                                                    window.close_dialog(cx);
+                                                }
+                                            }),
+                                    ),
+                            ),
+                    ),
+                )
+        });
+    }
+
+    /// Render a single endpoint row.
+    fn render_row(
+        &self,
+        row_ix: usize,
+        endpoint: &GraphQlEndpointConfig,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let id_for_toggle = endpoint.id.clone();
+        let id_for_delete = endpoint.id.clone();
+        let endpoint_for_edit = endpoint.clone();
+        let view_for_toggle = cx.entity().clone();
+        let view_for_edit = cx.entity().clone();
+        let view_for_delete = cx.entity().clone();
+        let enabled = endpoint.enabled;
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(if enabled {
+                                cx.theme().foreground
+                            } else {
+                                cx.theme().muted_foreground
+                            })
+                            .child(endpoint.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_family("monospace")
+                            .text_color(cx.theme().muted_foreground)
+                            .child(endpoint.endpoint.clone()),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new(SharedString::from(format!("graphql-toggle-{}", row_ix)))
+                            .label(if enabled { "On" } else { "Off" })
+                            .when(enabled, |b| b.primary())
+                            .when(!enabled, |b| b.ghost())
+                            .xsmall()
+                            .on_click(move |_, _, cx| {
+                                execution_settings_controller::toggle_graphql_endpoint(
+                                    &id_for_toggle,
+                                    cx,
+                                );
+                                view_for_toggle.update(cx, |_, cx| cx.notify());
+                            }),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("graphql-edit-{}", row_ix)))
+                            .label(t("action-edit"))
+                            .ghost()
+                            .xsmall()
+                            .on_click(move |_, window, cx| {
+                                let endpoint = endpoint_for_edit.clone();
+                                view_for_edit.update(cx, |view, cx| {
+                                    view.show_endpoint_dialog(Some(endpoint), window, cx);
+                                });
+                            }),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("graphql-del-{}", row_ix)))
+                            .icon(Icon::new(IconName::Close))
+                            .ghost()
+                            .xsmall()
+                            .on_click(move |_, _, cx| {
+                                execution_settings_controller::remove_graphql_endpoint(
+                                    &id_for_delete,
+                                    cx,
+                                );
+                                view_for_delete.update(cx, |_, cx| cx.notify());
+                            }),
+                    ),
+            )
+    }
+}
+
+impl Focusable for GraphQlEndpointsView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for GraphQlEndpointsView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let endpoints = cx
+            .global::<ExecutionSettingsModel>()
+            .graphql_endpoints
+            .clone();
+        let entity = cx.entity().clone();
+
+        let table =
+            v_flex()
+                .w_full()
+                .rounded_md()
+                .border_1()
+                .border_color(cx.theme().border)
+                .overflow_hidden()
+                .map(|this| {
+                    if endpoints.is_empty() {
+                        this.child(
+                            h_flex()
+                                .w_full()
+                                .justify_center()
+                                .py_6()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(t("custom-tools-no-graphql")),
+                        )
+                    } else {
+                        this.children(endpoints.iter().enumerate().map(|(ix, endpoint)| {
+                            self.render_row(ix, endpoint, cx).into_any_element()
+                        }))
+                    }
+                });
+
+        v_flex().size_full().gap_3().child(table).child(
+            h_flex().child(
+                Button::new("add-graphql-endpoint")
+                    .label("+ Add GraphQL Endpoint")
+                    .small()
+                    .on_click(move |_, window, cx| {
+                        entity.update(cx, |view, cx| {
+                            view.show_endpoint_dialog(None, window, cx);
+                        });
+                    }),
+            ),
+        )
+    }
+}
+
+// ── Setting page entry point ────────────────────────────────────────────────
+
+pub fn custom_tools_page() -> SettingPage {
+    SettingPage::new(t("settings-page-custom-tools"))
+        .description(
+            "Define REST and GraphQL endpoints the agent can call as tools, without writing \
+             code. Requests go through the same SSRF and redirect checks as the fetch tool.",
+        )
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title("REST Tools")
+                .description(
+                    "Use {name} in the URL for path parameters; other arguments are sent as query \
+                 parameters (GET, DELETE) or a JSON body (POST, PUT, PATCH). Reference secrets \
+                 from the Secrets page in headers as {{secret:KEY}}. Calls other than GET ask \
+                 for approval. Importing an OpenAPI 3.x spec generates one tool per selected \
+                 operation.",
+                )
+                .items(vec![SettingItem::render(|_options, window, cx| {
+                    let view = if let Some(existing) = cx.try_global::<GlobalCustomToolsTableView>()
+                    {
+                        if let Some(view) = existing.get() {
+                            view
+                        } else {
+                            let new_view = cx.new(|cx| CustomToolsTableView::new(window, cx));
+                            cx.set_global(GlobalCustomToolsTableView::new(new_view.clone()));
+                            new_view
+                        }
+                    } else {
+                        let new_view = cx.new(|cx| CustomToolsTableView::new(window, cx));
+                        cx.set_global(GlobalCustomToolsTableView::new(new_view.clone()));
+                        new_view
+                    };
+
+                    div().w_full().child(view)
+                })]),
+            SettingGroup::new()
+                .title("GraphQL Endpoints")
+                .description(
+                    "Each endpoint becomes a read-only query tool. The schema is introspected on \
+                 first use and every query is validated against it before it is sent; \
+                 mutations are rejected. Reference secrets in headers as {{secret:KEY}}.",
+                )
+                .items(vec![SettingItem::render(|_options, window, cx| {
+                    let view = if let Some(existing) = cx.try_global::<GlobalGraphQlEndpointsView>()
+                    {
+                        if let Some(view) = existing.get() {
+                            view
+                        } else {
+                            let new_view = cx.new(|cx| GraphQlEndpointsView::new(window, cx));
+                            cx.set_global(GlobalGraphQlEndpointsView::new(new_view.clone()));
+                            new_view
+                        }
+                    } else {
+                        let new_view = cx.new(|cx| GraphQlEndpointsView::new(window, cx));
+                        cx.set_global(GlobalGraphQlEndpointsView::new(new_view.clone()));
+                        new_view
+                    };
+
+                    div().w_full().child(view)
+                })]),
+        ])
+}
@@ -0,0 +1,67 @@
+use crate::i18n::t;
+use chatty_core::services::startup_profile;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, h_flex,
+    setting::{SettingGroup, SettingItem, SettingPage},
+    v_flex,
+};
+
+fn millis(duration: std::time::Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn phases_item(cx: &App) -> AnyElement {
+    let phases = startup_profile::phases();
+    if phases.is_empty() {
+        return div()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(t("diagnostics-no-phases"))
+            .into_any_element();
+    }
+    let muted = cx.theme().muted_foreground;
+    v_flex()
+        .gap_1()
+        .children(phases.into_iter().map(|phase| {
+            let duration = if phase.duration.is_zero() {
+                "—".to_string()
+            } else {
+                millis(phase.duration)
+            };
+            h_flex()
+                .gap_4()
+                .text_sm()
+                .child(div().w(px(180.)).child(phase.name))
+                .child(
+                    div()
+                        .w(px(100.))
+                        .text_color(muted)
+                        .child(SharedString::from(format!(
+                            "at {}",
+                            millis(phase.started_at)
+                        ))),
+                )
+                .child(div().child(SharedString::from(duration)))
+        }))
+        .into_any_element()
+}
+
+pub fn diagnostics_page() -> SettingPage {
+    SettingPage::new(t("settings-page-diagnostics"))
+        .description(
+            "Internal timings for troubleshooting. Shown because CHATTY_DIAGNOSTICS is set.",
+        )
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title("Startup Phases")
+                .description(
+                    "Offset from process start and duration of each phase. MCP connections and \
+                     renderers start after the first paint.",
+                )
+                .items(vec![SettingItem::render(|_options, _window, cx| {
+                    phases_item(cx)
+                })]),
+        ])
+}
@@ -1,5 +1,10 @@
-use crate::settings::controllers::execution_settings_controller;
-use crate::settings::models::execution_settings::{ApprovalMode, ExecutionSettingsModel};
+use crate::i18n::t;
+use crate::settings::controllers::{execution_settings_controller, managed_config_controller};
+use crate::settings::models::execution_settings::{
+    ApprovalMode, DockerCleanup, ExecutionSettingsModel,
+};
+use crate::settings::models::managed_config::ToolGroup;
+use chatty_core::services::citation_service::CitationStyle;
 use gpui::{App, IntoElement, ParentElement, SharedString, Styled, div};
 use gpui_component::{
     ActiveTheme,
@@ -9,7 +14,7 @@ use gpui_component::{
 };
 
 pub fn execution_settings_page() -> SettingPage {
-    SettingPage::new("Code Execution")
+    SettingPage::new(t("settings-page-code-execution"))
         .description("Configure code execution and filesystem access")
         .resettable(false)
         .groups(vec![
@@ -23,7 +28,10 @@ pub fn execution_settings_page() -> SettingPage {
                     SettingItem::new(
                         "Enable Code Execution",
                         SettingField::switch(
-                            |cx: &App| cx.global::<ExecutionSettingsModel>().enabled,
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().enabled
+                                    && !managed_config_controller::is_blocked(ToolGroup::Shell, cx)
+                            },
                             |_val: bool, cx: &mut App| {
                                 execution_settings_controller::toggle_execution(cx);
                             },
@@ -34,7 +42,10 @@ pub fn execution_settings_page() -> SettingPage {
                     SettingItem::new(
                         "Enable Git Integration",
                         SettingField::switch(
-                            |cx: &App| cx.global::<ExecutionSettingsModel>().git_enabled,
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().git_enabled
+                                    && !managed_config_controller::is_blocked(ToolGroup::Git, cx)
+                            },
                             |_val: bool, cx: &mut App| {
                                 execution_settings_controller::toggle_git(cx);
                             },
@@ -49,7 +60,10 @@ pub fn execution_settings_page() -> SettingPage {
                     SettingItem::new(
                         "Enable Code Execution Tool",
                         SettingField::switch(
-                            |cx: &App| cx.global::<ExecutionSettingsModel>().execute_code_enabled,
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().execute_code_enabled
+                                    && !managed_config_controller::is_blocked(ToolGroup::CodeExec, cx)
+                            },
                             |_val: bool, cx: &mut App| {
                                 execution_settings_controller::toggle_execute_code(cx);
                             },
@@ -62,7 +76,10 @@ pub fn execution_settings_page() -> SettingPage {
                     SettingItem::new(
                         "Enable Docker Fallback",
                         SettingField::switch(
-                            |cx: &App| cx.global::<ExecutionSettingsModel>().docker_code_execution_enabled,
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().docker_code_execution_enabled
+                                    && !managed_config_controller::is_blocked(ToolGroup::DockerExec, cx)
+                            },
                             |_val: bool, cx: &mut App| {
                                 execution_settings_controller::toggle_docker_code_execution(cx);
                             },
@@ -165,6 +182,161 @@ pub fn execution_settings_page() -> SettingPage {
                          Auto-approve All runs all commands without asking (use with caution).",
                     ),
                 ]),
+            SettingGroup::new()
+                .title("Kubernetes")
+                .description(
+                    "kubectl-backed tools for on-call debugging. Read-only by default; \
+                     secrets are never read.",
+                )
+                .items(vec![
+                    SettingItem::new(
+                        "Enable Kubernetes Tools",
+                        SettingField::switch(
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().kubernetes_enabled
+                                    && !managed_config_controller::is_blocked(ToolGroup::K8s, cx)
+                            },
+                            |_val: bool, cx: &mut App| {
+                                execution_settings_controller::toggle_kubernetes(cx);
+                            },
+                        )
+                        .default_value(false),
+                    )
+                    .description(
+                        "Get pods, logs, describe and events through kubectl. \
+                         Requires kubectl on PATH and a kubeconfig.",
+                    ),
+                    SettingItem::new(
+                        "Allowed Contexts",
+                        SettingField::input(
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>()
+                                    .kubernetes_contexts
+                                    .join(", ")
+                                    .into()
+                            },
+                            |val: SharedString, cx: &mut App| {
+                                let namespaces = cx
+                                    .global::<ExecutionSettingsModel>()
+                                    .kubernetes_namespaces
+                                    .clone();
+                                execution_settings_controller::set_kubernetes_scope(
+                                    split_list(&val),
+                                    namespaces,
+                                    cx,
+                                );
+                            },
+                        ),
+                    )
+                    .description(
+                        "Comma-separated kubeconfig contexts; the first is the default. \
+                         Leave empty to use only the current context.",
+                    ),
+                    SettingItem::new(
+                        "Allowed Namespaces",
+                        SettingField::input(
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>()
+                                    .kubernetes_namespaces
+                                    .join(", ")
+                                    .into()
+                            },
+                            |val: SharedString, cx: &mut App| {
+                                let contexts = cx
+                                    .global::<ExecutionSettingsModel>()
+                                    .kubernetes_contexts
+                                    .clone();
+                                execution_settings_controller::set_kubernetes_scope(
+                                    contexts,
+                                    split_list(&val),
+                                    cx,
+                                );
+                            },
+                        ),
+                    )
+                    .description(
+                        "Comma-separated namespaces; the first is the default. \
+                         Leave empty to allow any namespace.",
+                    ),
+                    SettingItem::new(
+                        "Allow Mutating Actions",
+                        SettingField::switch(
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().kubernetes_allow_mutations
+                            },
+                            |_val: bool, cx: &mut App| {
+                                execution_settings_controller::toggle_kubernetes_mutations(cx);
+                            },
+                        )
+                        .default_value(false),
+                    )
+                    .description(
+                        "Offer delete, rollout restart, scale and cordon/uncordon. \
+                         Every action asks for approval, whatever the approval mode.",
+                    ),
+                ]),
+            SettingGroup::new()
+                .title("Docker")
+                .description(
+                    "Container tools backed by the Docker daemon configured above. \
+                     exec and compose follow the approval mode.",
+                )
+                .items(vec![
+                    SettingItem::new(
+                        "Enable Docker Tool",
+                        SettingField::switch(
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().docker_tools_enabled
+                                    && !managed_config_controller::is_blocked(ToolGroup::Docker, cx)
+                            },
+                            |_val: bool, cx: &mut App| {
+                                execution_settings_controller::toggle_docker_tools(cx);
+                            },
+                        )
+                        .default_value(false),
+                    )
+                    .description(
+                        "List containers and images, read logs, exec into containers and run \
+                         docker compose up/down in the workspace directory.",
+                    ),
+                    SettingItem::new(
+                        "On Conversation Delete",
+                        SettingField::render(|_options, _window, cx| {
+                            let current = cx.global::<ExecutionSettingsModel>().docker_cleanup.clone();
+                            let current_label = docker_cleanup_label(&current);
+
+                            Button::new("docker-cleanup-dropdown")
+                                .label(current_label)
+                                .dropdown_caret(true)
+                                .outline()
+                                .w_full()
+                                .dropdown_menu_with_anchor(
+                                    gpui::Corner::BottomLeft,
+                                    move |menu, _, _| {
+                                        [DockerCleanup::Keep, DockerCleanup::Stop, DockerCleanup::Remove]
+                                            .into_iter()
+                                            .fold(menu, |menu, option| {
+                                                menu.item(
+                                                    PopupMenuItem::new(docker_cleanup_label(&option))
+                                                        .checked(option == current)
+                                                        .on_click(move |_, _, cx| {
+                                                            execution_settings_controller::set_docker_cleanup(
+                                                                option.clone(),
+                                                                cx,
+                                                            );
+                                                        }),
+                                                )
+                                            })
+                                    },
+                                )
+                                .into_any_element()
+                        }),
+                    )
+                    .description(
+                        "What to do with compose stacks a conversation started when it is deleted: \
+                         keep them running, stop them, or remove them (compose down).",
+                    ),
+                ]),
             SettingGroup::new()
                 .title("Filesystem Access")
                 .description("Configure workspace directory for file read/write operations")
@@ -191,6 +363,157 @@ pub fn execution_settings_page() -> SettingPage {
                     )
                     .description("Optional directory path for file operations. Leave empty to disable filesystem tools."),
                 ]),
+            SettingGroup::new()
+                .title("Notes Vault")
+                .description(
+                    "A folder of Markdown notes, such as an Obsidian vault. Answers can be \
+                     exported to it, and [[wiki links]] in pinned vault notes are resolved.",
+                )
+                .items(vec![
+                    SettingItem::new(
+                        "Vault Directory",
+                        SettingField::input(
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>()
+                                    .notes_vault_dir
+                                    .clone()
+                                    .unwrap_or_default()
+                                    .into()
+                            },
+                            |val: SharedString, cx: &mut App| {
+                                let vault_dir = if val.is_empty() {
+                                    None
+                                } else {
+                                    Some(val.to_string())
+                                };
+                                execution_settings_controller::set_notes_vault_dir(vault_dir, cx);
+                            },
+                        ),
+                    )
+                    .description("Path to the vault. Leave empty to disable notes features."),
+                    SettingItem::new(
+                        "Enable Notes Tools",
+                        SettingField::switch(
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().notes_enabled
+                                    && !managed_config_controller::is_blocked(ToolGroup::Notes, cx)
+                            },
+                            |_val: bool, cx: &mut App| {
+                                execution_settings_controller::toggle_notes(cx);
+                            },
+                        )
+                        .default_value(false),
+                    )
+                    .description(
+                        "Let the agent search and read notes, and append to them after approval.",
+                    ),
+                ]),
+            SettingGroup::new()
+                .title("References")
+                .description(
+                    "A BibTeX file or a running Zotero library. Cited references appear as \
+                     cards below the answer.",
+                )
+                .items(vec![
+                    SettingItem::new(
+                        "BibTeX File",
+                        SettingField::input(
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>()
+                                    .bibtex_path
+                                    .clone()
+                                    .unwrap_or_default()
+                                    .into()
+                            },
+                            |val: SharedString, cx: &mut App| {
+                                let path = if val.is_empty() {
+                                    None
+                                } else {
+                                    Some(val.to_string())
+                                };
+                                execution_settings_controller::set_bibtex_path(path, cx);
+                            },
+                        ),
+                    )
+                    .description("Path to a .bib file. Takes precedence over Zotero when set."),
+                    SettingItem::new(
+                        "Zotero Local API URL",
+                        SettingField::input(
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>()
+                                    .zotero_api_url
+                                    .clone()
+                                    .unwrap_or_default()
+                                    .into()
+                            },
+                            |val: SharedString, cx: &mut App| {
+                                let url = if val.is_empty() {
+                                    None
+                                } else {
+                                    Some(val.to_string())
+                                };
+                                execution_settings_controller::set_zotero_api_url(url, cx);
+                            },
+                        ),
+                    )
+                    .description(
+                        "Zotero 7 local API, e.g. http://localhost:23119/api. Enable it in \
+                         Zotero under Settings > Advanced.",
+                    ),
+                    SettingItem::new(
+                        "Citation Style",
+                        SettingField::render(|_options, _window, cx| {
+                            let current = cx.global::<ExecutionSettingsModel>().citation_style;
+
+                            Button::new("citation-style-dropdown")
+                                .label(citation_style_label(current))
+                                .dropdown_caret(true)
+                                .outline()
+                                .w_full()
+                                .dropdown_menu_with_anchor(
+                                    gpui::Corner::BottomLeft,
+                                    move |menu, _, _| {
+                                        [
+                                            CitationStyle::Apa,
+                                            CitationStyle::Mla,
+                                            CitationStyle::Chicago,
+                                            CitationStyle::Ieee,
+                                        ]
+                                        .into_iter()
+                                        .fold(menu, |menu, option| {
+                                            menu.item(
+                                                PopupMenuItem::new(citation_style_label(option))
+                                                    .checked(option == current)
+                                                    .on_click(move |_, _, cx| {
+                                                        execution_settings_controller::set_citation_style(
+                                                            option, cx,
+                                                        );
+                                                    }),
+                                            )
+                                        })
+                                    },
+                                )
+                                .into_any_element()
+                        }),
+                    )
+                    .description("Bibliography style for formatted references."),
+                    SettingItem::new(
+                        "Enable Citations Tool",
+                        SettingField::switch(
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().citations_enabled
+                                    && !managed_config_controller::is_blocked(ToolGroup::Citations, cx)
+                            },
+                            |_val: bool, cx: &mut App| {
+                                execution_settings_controller::toggle_citations(cx);
+                            },
+                        )
+                        .default_value(false),
+                    )
+                    .description(
+                        "Let the agent search the library and cite references with [@citekey].",
+                    ),
+                ]),
             SettingGroup::new()
                 .title("Agent Settings")
                 .description("Configure agent behavior for multi-step interactions")
@@ -218,6 +541,82 @@ pub fn execution_settings_page() -> SettingPage {
                         "Maximum number of tool-call rounds the agent can perform per response. \
                          Applies to all agentic interactions, including code execution and MCP tool calls.",
                     ),
+                    SettingItem::new(
+                        "MCP Idle Shutdown (minutes)",
+                        SettingField::number_input(
+                            NumberFieldOptions {
+                                min: 0.0,
+                                max: 1440.0,
+                                ..Default::default()
+                            },
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().mcp_idle_shutdown_minutes
+                                    as f64
+                            },
+                            |val: f64, cx: &mut App| {
+                                execution_settings_controller::set_mcp_idle_shutdown_minutes(
+                                    val.clamp(0.0, 1440.0) as u32, cx,
+                                );
+                            },
+                        )
+                        .default_value(10.0),
+                    )
+                    .description(
+                        "MCP servers connect on first tool use. Disconnect a server after this many \
+                         minutes without tool calls; it reconnects on the next call. 0 keeps servers connected.",
+                    ),
+                    SettingItem::new(
+                        "Planner Mode",
+                        SettingField::switch(
+                            |cx: &App| cx.global::<ExecutionSettingsModel>().planner_mode,
+                            |_val: bool, cx: &mut App| {
+                                execution_settings_controller::toggle_planner_mode(cx);
+                            },
+                        )
+                        .default_value(false),
+                    )
+                    .description(
+                        "Have the agent write a step-by-step plan before working and keep it \
+                         updated as a live checklist above the chat input.",
+                    ),
+                    SettingItem::new(
+                        "Self-Reflection",
+                        SettingField::switch(
+                            |cx: &App| cx.global::<ExecutionSettingsModel>().self_reflection,
+                            |_val: bool, cx: &mut App| {
+                                execution_settings_controller::toggle_self_reflection(cx);
+                            },
+                        )
+                        .default_value(false),
+                    )
+                    .description(
+                        "After each answer, have the agent check it against your question and the \
+                         context its tools retrieved. A low-confidence answer is retried once with \
+                         the critique; the first attempt stays visible above the new answer.",
+                    ),
+                    SettingItem::new(
+                        "Reflection Threshold",
+                        SettingField::number_input(
+                            NumberFieldOptions {
+                                min: 1.0,
+                                max: 5.0,
+                                ..Default::default()
+                            },
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().reflection_threshold as f64
+                            },
+                            |val: f64, cx: &mut App| {
+                                execution_settings_controller::set_reflection_threshold(
+                                    val.clamp(1.0, 5.0) as u8,
+                                    cx,
+                                );
+                            },
+                        )
+                        .default_value(3.0),
+                    )
+                    .description(
+                        "Critique score (1–5) an answer needs to pass. Lower scores trigger the retry.",
+                    ),
                 ]),
             SettingGroup::new()
                 .title("Execution Limits")
@@ -277,3 +676,32 @@ pub fn execution_settings_page() -> SettingPage {
                 ]),
         ])
 }
+
+/// Split a comma-separated settings field into trimmed, non-empty entries.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Dropdown label for a Docker cleanup policy.
+fn docker_cleanup_label(cleanup: &DockerCleanup) -> &'static str {
+    match cleanup {
+        DockerCleanup::Keep => "Keep Running",
+        DockerCleanup::Stop => "Stop Containers",
+        DockerCleanup::Remove => "Remove Containers",
+    }
+}
+
+/// Dropdown label for a bibliography style.
+fn citation_style_label(style: CitationStyle) -> &'static str {
+    match style {
+        CitationStyle::Apa => "APA",
+        CitationStyle::Mla => "MLA",
+        CitationStyle::Chicago => "Chicago",
+        CitationStyle::Ieee => "IEEE",
+    }
+}
@@ -1,20 +1,27 @@
 use crate::chatty::views::footer::progress_circle::ProgressCircle;
+use crate::i18n::t;
 use crate::settings::controllers::extensions_controller;
 use crate::settings::models::DiscoveredModulesModel;
 use crate::settings::models::extensions_store::{ExtensionKind, ExtensionsModel};
 use crate::settings::models::hive_settings::HiveSettingsModel;
 use crate::settings::models::marketplace_state::MarketplaceState;
+use crate::settings::models::{McpCatalogState, UserSecretsModel};
+use chatty_core::mcp_catalog::{self, McpCatalogEntry};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::button::*;
 use gpui_component::input::{Input, InputState};
+use gpui_component::menu::{DropdownMenu, PopupMenuItem};
 use gpui_component::setting::{SettingGroup, SettingItem, SettingPage};
 use gpui_component::{
     ActiveTheme, Disableable, Icon, IconName, Sizable, WindowExt as _, alert::Alert, h_flex, v_flex,
 };
 
+/// Result cache TTLs offered for MCP servers, in seconds.
+const CACHE_TTL_OPTIONS: &[u64] = &[0, 60, 300, 900, 3600];
+
 pub fn extensions_page() -> SettingPage {
-    SettingPage::new("Extensions")
+    SettingPage::new(t("settings-page-extensions"))
         .description(
             "Browse the Hive marketplace to discover and install extensions, \
              or add your own MCP servers and A2A agents.",
@@ -24,6 +31,7 @@ pub fn extensions_page() -> SettingPage {
             hive_account_group(),
             installed_extensions_group(),
             marketplace_group(),
+            mcp_catalog_group(),
             add_custom_group(),
         ])
 }
@@ -59,7 +67,7 @@ fn hive_account_group() -> SettingGroup {
                     .child(
                         Button::new("hive-logout")
                             .small()
-                            .label("Sign Out")
+                            .label(t("extensions-sign-out"))
                             .on_click(|_, _window, cx| {
                                 extensions_controller::logout(cx);
                             }),
@@ -74,18 +82,21 @@ fn hive_account_group() -> SettingGroup {
                         div()
                             .text_sm()
                             .text_color(cx.theme().muted_foreground)
-                            .child("Not signed in"),
+                            .child(t("extensions-not-signed-in")),
+                    )
+                    .child(
+                        Button::new("hive-login")
+                            .small()
+                            .label(t("action-sign-in"))
+                            .on_click(|_, window, cx| {
+                                show_login_dialog(window, cx);
+                            }),
                     )
-                    .child(Button::new("hive-login").small().label("Sign In").on_click(
-                        |_, window, cx| {
-                            show_login_dialog(window, cx);
-                        },
-                    ))
                     .child(
                         Button::new("hive-register")
                             .small()
                             .ghost()
-                            .label("Register")
+                            .label(t("action-register"))
                             .on_click(|_, window, cx| {
                                 show_register_dialog(window, cx);
                             }),
@@ -112,7 +123,7 @@ fn installed_extensions_group() -> SettingGroup {
                         div()
                             .text_sm()
                             .text_color(cx.theme().muted_foreground)
-                            .child("No extensions installed yet. Browse the marketplace below."),
+                            .child(t("extensions-none-installed")),
                     )
                 })
                 .when(!extensions.is_empty(), |this| {
@@ -126,6 +137,12 @@ fn installed_extensions_group() -> SettingGroup {
                             ExtensionKind::A2aAgent(_) => "A2A",
                         };
                         let status_icon = if ext.enabled { "🟢" } else { "⏸" };
+                        let mcp_cache = match &ext.kind {
+                            ExtensionKind::McpServer(cfg) => {
+                                Some((cfg.name.clone(), cfg.result_cache_ttl_secs))
+                            }
+                            _ => None,
+                        };
 
                         // For WASM modules: look up discovered module metadata for
                         // execution_mode and wasm_file presence.
@@ -159,9 +176,7 @@ fn installed_extensions_group() -> SettingGroup {
                                 _ => Some(("• Local", 0x6B7280)),
                             },
                             ExtensionKind::McpServer(cfg) => {
-                                let is_local =
-                                    cfg.url.contains("localhost") || cfg.url.contains("127.0.0.1");
-                                if !is_local {
+                                if !cfg.is_local() {
                                     Some(("↗ External", 0xA855F7))
                                 } else {
                                     None
@@ -207,7 +222,7 @@ fn installed_extensions_group() -> SettingGroup {
                                                     .rounded_sm()
                                                     .bg(gpui::rgb(0xFEF3C7))
                                                     .text_color(gpui::rgb(0x92400E))
-                                                    .child("Paid"),
+                                                    .child(t("footer-paid")),
                                             )
                                         },
                                     )
@@ -274,6 +289,9 @@ fn installed_extensions_group() -> SettingGroup {
                                             )
                                         },
                                     )
+                                    .when_some(mcp_cache, |el, (server_name, ttl)| {
+                                        el.child(render_cache_menu(id.clone(), server_name, ttl))
+                                    })
                                     .child(
                                         Button::new(SharedString::from(format!("toggle-{id}")))
                                             .small()
@@ -311,6 +329,50 @@ fn installed_extensions_group() -> SettingGroup {
         })])
 }
 
+fn cache_ttl_label(secs: u64) -> String {
+    match secs {
+        0 => "No Cache".to_string(),
+        s if s % 3600 == 0 => format!("Cache {}h", s / 3600),
+        s if s % 60 == 0 => format!("Cache {}m", s / 60),
+        s => format!("Cache {s}s"),
+    }
+}
+
+/// Dropdown choosing how long an MCP server's identical tool calls reuse
+/// their previous result.
+fn render_cache_menu(ext_id: String, server_name: String, ttl: u64) -> Button {
+    Button::new(SharedString::from(format!("cache-{ext_id}")))
+        .small()
+        .ghost()
+        .label(cache_ttl_label(ttl))
+        .dropdown_caret(true)
+        .dropdown_menu_with_anchor(Corner::TopRight, move |menu, _, _| {
+            let menu = CACHE_TTL_OPTIONS.iter().fold(menu, |menu, &secs| {
+                let ext_id = ext_id.clone();
+                menu.item(
+                    PopupMenuItem::new(cache_ttl_label(secs))
+                        .checked(secs == ttl)
+                        .on_click(move |_, _, cx| {
+                            extensions_controller::set_mcp_result_cache_ttl(
+                                ext_id.clone(),
+                                secs,
+                                cx,
+                            );
+                        }),
+                )
+            });
+            if ttl == 0 {
+                return menu;
+            }
+            let server_name = server_name.clone();
+            menu.item(
+                PopupMenuItem::new("Clear Cached Results").on_click(move |_, _, cx| {
+                    extensions_controller::clear_mcp_result_cache(server_name.clone(), cx);
+                }),
+            )
+        })
+}
+
 // ── Marketplace ────────────────────────────────────────────────────────────
 
 fn marketplace_group() -> SettingGroup {
@@ -328,7 +390,7 @@ fn marketplace_group() -> SettingGroup {
             // so the input keeps focus and typed text between frames.
             let search_input =
                 window.use_keyed_state("marketplace-search-input", cx, |window, cx| {
-                    InputState::new(window, cx).placeholder("Search extensions...")
+                    InputState::new(window, cx).placeholder(t("extensions-search-placeholder"))
                 });
 
             v_flex()
@@ -344,7 +406,7 @@ fn marketplace_group() -> SettingGroup {
                             Button::new("search-marketplace")
                                 .small()
                                 .icon(Icon::new(IconName::Search))
-                                .label("Search")
+                                .label(t("action-search"))
                                 .loading(loading)
                                 .on_click({
                                     let search_input = search_input.clone();
@@ -433,7 +495,7 @@ fn marketplace_group() -> SettingGroup {
                                                         .rounded_sm()
                                                         .bg(gpui::rgb(0xFEF3C7))
                                                         .text_color(gpui::rgb(0x92400E))
-                                                        .child("Paid"),
+                                                        .child(t("footer-paid")),
                                                 )
                                             }),
                                     ),
@@ -442,7 +504,7 @@ fn marketplace_group() -> SettingGroup {
                                 Button::new(SharedString::from(format!("uninstall-{name}")))
                                     .small()
                                     .ghost()
-                                    .label("Uninstall")
+                                    .label(t("extensions-uninstall"))
                                     .on_click({
                                         let name = name.clone();
                                         move |_, _window, cx| {
@@ -475,7 +537,7 @@ fn marketplace_group() -> SettingGroup {
                             } else {
                                 Button::new(SharedString::from(format!("install-{name}")))
                                     .small()
-                                    .label("Install")
+                                    .label(t("action-install"))
                                     .on_click({
                                         let name = name.clone();
                                         let version = version.clone();
@@ -503,6 +565,133 @@ fn marketplace_group() -> SettingGroup {
         })])
 }
 
+// ── MCP Server Catalog ─────────────────────────────────────────────────────
+
+fn mcp_catalog_group() -> SettingGroup {
+    SettingGroup::new()
+        .title("MCP Server Catalog")
+        .description(
+            "Popular MCP servers, ready to install. Local servers are launched by \
+             Chatty and need their runtime (npx or uvx) on your PATH.",
+        )
+        .items(vec![SettingItem::render(|_options, _window, cx| {
+            let state = cx.global::<McpCatalogState>();
+            let loading = state.loading;
+            let error = state.error.clone();
+            let entries = state.entries.clone();
+            let installed = cx.global::<ExtensionsModel>().clone();
+
+            v_flex()
+                .w_full()
+                .gap_3()
+                .child(
+                    h_flex().w_full().justify_end().child(
+                        Button::new("refresh-mcp-catalog")
+                            .small()
+                            .ghost()
+                            .label(t("action-check-updates"))
+                            .loading(loading)
+                            .on_click(|_, _window, cx| {
+                                extensions_controller::refresh_mcp_catalog(cx);
+                            }),
+                    ),
+                )
+                .when_some(error, |this, error| {
+                    this.child(Alert::error("mcp-catalog-error", error).small().on_close(
+                        |_event, _window, cx| {
+                            cx.global_mut::<McpCatalogState>().error = None;
+                        },
+                    ))
+                })
+                .children(entries.iter().map(|entry| {
+                    let ext = installed.find(&entry.extension_id());
+                    let update = ext.and_then(|ext| {
+                        mcp_catalog::available_update(ext, std::slice::from_ref(entry))
+                    });
+                    let id = entry.id.clone();
+
+                    h_flex()
+                        .w_full()
+                        .items_center()
+                        .justify_between()
+                        .py_1p5()
+                        .border_b_1()
+                        .border_color(cx.theme().border)
+                        .child(
+                            v_flex()
+                                .flex_1()
+                                .gap_0p5()
+                                .child(
+                                    h_flex()
+                                        .gap_2()
+                                        .items_center()
+                                        .child(
+                                            div()
+                                                .text_sm()
+                                                .font_weight(FontWeight::MEDIUM)
+                                                .text_color(cx.theme().foreground)
+                                                .child(entry.name.clone()),
+                                        )
+                                        .child(
+                                            div()
+                                                .text_xs()
+                                                .text_color(cx.theme().muted_foreground)
+                                                .child(if entry.is_launched() {
+                                                    "Local"
+                                                } else {
+                                                    "Hosted"
+                                                }),
+                                        ),
+                                )
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(entry.description.clone()),
+                                )
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(format!("v{}", entry.version)),
+                                ),
+                        )
+                        .child(match (ext, update) {
+                            (Some(_), Some(update)) => {
+                                Button::new(SharedString::from(format!("update-catalog-{id}")))
+                                    .small()
+                                    .label(format!("Update to v{}", update.version))
+                                    .on_click({
+                                        let entry = update.clone();
+                                        move |_, window, cx| {
+                                            show_install_catalog_dialog(entry.clone(), window, cx);
+                                        }
+                                    })
+                            }
+                            (Some(_), None) => {
+                                Button::new(SharedString::from(format!("installed-catalog-{id}")))
+                                    .small()
+                                    .ghost()
+                                    .label(t("extensions-installed"))
+                                    .disabled(true)
+                            }
+                            (None, _) => {
+                                Button::new(SharedString::from(format!("install-catalog-{id}")))
+                                    .small()
+                                    .label(t("action-install"))
+                                    .on_click({
+                                        let entry = entry.clone();
+                                        move |_, window, cx| {
+                                            show_install_catalog_dialog(entry.clone(), window, cx);
+                                        }
+                                    })
+                            }
+                        })
+                }))
+                .into_any_element()
+        })])
+}
+
 // ── Add Custom Extension ───────────────────────────────────────────────────
 
 fn add_custom_group() -> SettingGroup {
@@ -517,7 +706,7 @@ fn add_custom_group() -> SettingGroup {
                     Button::new("add-custom-mcp")
                         .small()
                         .icon(Icon::new(IconName::Plus))
-                        .label("Add MCP Server")
+                        .label(t("extensions-add-mcp"))
                         .on_click(|_, window, cx| {
                             show_add_mcp_dialog(window, cx);
                         }),
@@ -529,8 +718,9 @@ fn add_custom_group() -> SettingGroup {
 // ── Dialogs ────────────────────────────────────────────────────────────────
 
 fn show_login_dialog(window: &mut Window, cx: &mut App) {
-    let email_input = cx.new(|cx| InputState::new(window, cx).placeholder("Email"));
-    let password_input = cx.new(|cx| InputState::new(window, cx).placeholder("Password"));
+    let email_input = cx.new(|cx| InputState::new(window, cx).placeholder(t("extensions-email")));
+    let password_input =
+        cx.new(|cx| InputState::new(window, cx).placeholder(t("extensions-password")));
 
     window.open_dialog(cx, move |dialog, _window, _cx| {
         dialog
@@ -545,7 +735,7 @@ fn show_login_dialog(window: &mut Window, cx: &mut App) {
             .child(
                 Button::new("do-login")
                     .primary()
-                    .label("Sign In")
+                    .label(t("action-sign-in"))
                     .on_click({
                         let email_input = email_input.clone();
                         let password_input = password_input.clone();
@@ -564,10 +754,11 @@ fn show_login_dialog(window: &mut Window, cx: &mut App) {
 
 fn show_register_dialog(window: &mut Window, cx: &mut App) {
     let username_input =
-        cx.new(|cx| InputState::new(window, cx).placeholder("Username (3-39 chars, lowercase)"));
-    let email_input = cx.new(|cx| InputState::new(window, cx).placeholder("Email"));
-    let password_input =
-        cx.new(|cx| InputState::new(window, cx).placeholder("Password (12+ characters)"));
+        cx.new(|cx| InputState::new(window, cx).placeholder(t("extensions-username-placeholder")));
+    let email_input = cx.new(|cx| InputState::new(window, cx).placeholder(t("extensions-email")));
+    let password_input = cx.new(|cx| {
+        InputState::new(window, cx).placeholder(t("extensions-new-password-placeholder"))
+    });
 
     window.open_dialog(cx, move |dialog, _window, _cx| {
         dialog
@@ -583,7 +774,7 @@ fn show_register_dialog(window: &mut Window, cx: &mut App) {
             .child(
                 Button::new("do-register")
                     .primary()
-                    .label("Register")
+                    .label(t("action-register"))
                     .on_click({
                         let username_input = username_input.clone();
                         let email_input = email_input.clone();
@@ -606,7 +797,8 @@ fn show_add_mcp_dialog(window: &mut Window, cx: &mut App) {
     let name_input = cx.new(|cx| InputState::new(window, cx).placeholder("e.g. github-mcp"));
     let url_input =
         cx.new(|cx| InputState::new(window, cx).placeholder("http://localhost:3000/mcp"));
-    let key_input = cx.new(|cx| InputState::new(window, cx).placeholder("Optional API key"));
+    let key_input =
+        cx.new(|cx| InputState::new(window, cx).placeholder(t("extensions-api-key-placeholder")));
 
     window.open_dialog(cx, move |dialog, _window, _cx| {
         dialog
@@ -622,7 +814,7 @@ fn show_add_mcp_dialog(window: &mut Window, cx: &mut App) {
             .child(
                 Button::new("save-add-mcp")
                     .primary()
-                    .label("Add")
+                    .label(t("extensions-add"))
                     .on_click({
                         let name_input = name_input.clone();
                         let url_input = url_input.clone();
@@ -643,3 +835,72 @@ fn show_add_mcp_dialog(window: &mut Window, cx: &mut App) {
             )
     });
 }
+
+/// Ask for the entry's inputs, then install it. Secret inputs that already
+/// have a saved value may be left blank to keep it.
+fn show_install_catalog_dialog(entry: McpCatalogEntry, window: &mut Window, cx: &mut App) {
+    if entry.inputs.is_empty() {
+        install_catalog_entry(entry, Default::default(), cx);
+        return;
+    }
+
+    let saved = cx.global::<UserSecretsModel>().secrets.clone();
+    let inputs: Vec<(String, Entity<InputState>)> = entry
+        .inputs
+        .iter()
+        .map(|input| {
+            let placeholder = if input.secret && saved.iter().any(|s| s.key == input.id) {
+                format!("{} (leave blank to keep the saved value)", input.label)
+            } else {
+                input.label.clone()
+            };
+            let state = cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder(placeholder)
+                    .masked(input.secret)
+            });
+            (input.id.clone(), state)
+        })
+        .collect();
+
+    window.open_dialog(cx, move |dialog, _window, _cx| {
+        dialog
+            .title(format!("Install {}", entry.name))
+            .w(px(500.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .children(inputs.iter().map(|(_, state)| Input::new(state))),
+            )
+            .child(
+                Button::new("do-install-catalog")
+                    .primary()
+                    .label(t("action-install"))
+                    .on_click({
+                        let entry = entry.clone();
+                        let inputs = inputs.clone();
+                        move |_, window, cx| {
+                            let values = inputs
+                                .iter()
+                                .map(|(id, state)| {
+                                    (id.clone(), state.read(cx).value().trim().to_string())
+                                })
+                                .collect();
+                            install_catalog_entry(entry.clone(), values, cx);
+                            window.close_dialog(cx);
+                        }
+                    }),
+            )
+    });
+}
+
+fn install_catalog_entry(
+    entry: McpCatalogEntry,
+    inputs: std::collections::HashMap<String, String>,
+    cx: &mut App,
+) {
+    if let Err(e) = extensions_controller::install_mcp_catalog_entry(entry, inputs, cx) {
+        cx.global_mut::<McpCatalogState>().error = Some(e);
+        cx.refresh_windows();
+    }
+}
@@ -0,0 +1,385 @@
+use crate::i18n::t;
+use crate::settings::controllers::execution_settings_controller;
+use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use chatty_core::services::{GuardrailRule, GuardrailRuleKind};
+use gpui::{
+    App, Context, FocusHandle, Focusable, FontWeight, IntoElement, Render, SharedString, Styled,
+    Window, div, prelude::*, px,
+};
+use gpui_component::{
+    ActiveTheme, Sizable, WindowExt as _,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    setting::{SettingGroup, SettingItem, SettingPage},
+    v_flex,
+};
+use gpui_component::{Icon, IconName};
+
+// ── Global singleton ────────────────────────────────────────────────────────
+
+pub type GlobalGuardrailsTableView = crate::global_entity::GlobalStrongEntity<GuardrailsTableView>;
+
+/// Rule types that take a single text value in the add dialog.
+#[derive(Clone, Copy)]
+enum RuleTemplate {
+    BlockCommand,
+    RestrictWrites,
+    BlockTool,
+}
+
+impl RuleTemplate {
+    fn title(self) -> &'static str {
+        match self {
+            Self::BlockCommand => "Block Command Pattern",
+            Self::RestrictWrites => "Restrict Writes",
+            Self::BlockTool => "Block Tool",
+        }
+    }
+
+    fn value_label(self) -> &'static str {
+        match self {
+            Self::BlockCommand => "Command pattern",
+            Self::RestrictWrites => "Allowed directories (comma-separated)",
+            Self::BlockTool => "Tool name",
+        }
+    }
+
+    fn placeholder(self) -> &'static str {
+        match self {
+            Self::BlockCommand => "git push --force",
+            Self::RestrictWrites => "src, docs",
+            Self::BlockTool => "delete_file",
+        }
+    }
+
+    fn kind(self, value: &str) -> GuardrailRuleKind {
+        match self {
+            Self::BlockCommand => GuardrailRuleKind::BlockCommand {
+                pattern: value.to_string(),
+            },
+            Self::RestrictWrites => GuardrailRuleKind::RestrictWrites {
+                allowed_dirs: value
+                    .split(',')
+                    .map(|d| d.trim().to_string())
+                    .filter(|d| !d.is_empty())
+                    .collect(),
+            },
+            Self::BlockTool => GuardrailRuleKind::BlockTool {
+                tool_name: value.to_string(),
+            },
+        }
+    }
+}
+
+// ── Table view entity ───────────────────────────────────────────────────────
+
+pub struct GuardrailsTableView {
+    focus_handle: FocusHandle,
+}
+
+impl GuardrailsTableView {
+    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        Self { focus_handle }
+    }
+
+    fn show_add_rule_dialog(
+        &self,
+        template: RuleTemplate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let name_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(t("guardrails-name-placeholder")));
+        let value_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(template.placeholder()));
+        let view_entity = cx.entity().clone();
+
+        window.open_dialog(cx, move |dialog, _, _| {
+            dialog
+                .title(template.title())
+                .overlay(true)
+                .keyboard(true)
+                .close_button(true)
+                .overlay_closable(true)
+                .w(px(450.))
+                .child(
+                    div().id("add-guardrail-form").child(
+                        v_flex()
+                            .gap_3()
+                            .p_4()
+                            .child(
+                                v_flex()
+                                    .gap_1()
+                                    .child(div().text_sm().child(t("guardrails-name")))
+                                    .child(Input::new(&name_input)),
+                            )
+                            .child(
+                                v_flex()
+                                    .gap_1()
+                                    .child(div().text_sm().child(template.value_label()))
+                                    .child(Input::new(&value_input)),
+                            )
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .justify_end()
+                                    .pt_4()
+                                    .child(Button::new("cancel-guardrail").label(t("action-cancel")).on_click(
+                                        move |_, window, cx| {
+                                            window.close_dialog(cx);
+                                        },
+                                    ))
+                                    .child(
+                                        Button::new("save-guardrail")
+                                            .primary()
+                                            .label(t("action-save"))
+                                            .on_click({
+                                                let name_input = name_input.clone();
+                                                let value_input = value_input.clone();
+                                                let view_entity = view_entity.clone();
+                                                move |_, window, cx| {
+                                                    let value = value_input
+                                                        .read(cx)
+                                                        .value()
+                                                        .trim()
+                                                        .to_string();
+                                                    if value.is_empty() {
+                                                        window.push_notification(
+                                                            "A value is required",
+                                                            cx,
+                                                        );
+                                                        return;
+                                                    }
+
+                                                    let kind = template.kind(&value);
+                                                    let name = name_input
+                                                        .read(cx)
+                                                        .value()
+                                                        .trim()
+                                                        .to_string();
+                                                    let name = if name.is_empty() {
+                                                        kind.summary()
+                                                    } else {
+                                                        name
+                                                    };
+
+                                                    execution_settings_controller::add_guardrail_rule(
+                                                        GuardrailRule::new(name, kind),
+                                                        cx,
+                                                    );
+                                                    view_entity.update(cx, |_, cx| cx.notify());
+                                                    window.close_dialog(cx);
+                                                }
+                                            }),
+                                    ),
+                            ),
+                    ),
+                )
+        });
+    }
+
+    /// Render a single rule row.
+    fn render_row(
+        &self,
+        row_ix: usize,
+        rule: &GuardrailRule,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let id_for_toggle = rule.id.clone();
+        let id_for_delete = rule.id.clone();
+        let view_for_toggle = cx.entity().clone();
+        let view_for_delete = cx.entity().clone();
+        let enabled = rule.enabled;
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(if enabled {
+                                cx.theme().foreground
+                            } else {
+                                cx.theme().muted_foreground
+                            })
+                            .child(rule.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(rule.kind.summary()),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new(SharedString::from(format!("guardrail-toggle-{}", row_ix)))
+                            .label(if enabled { "On" } else { "Off" })
+                            .when(enabled, |b| b.primary())
+                            .when(!enabled, |b| b.ghost())
+                            .xsmall()
+                            .on_click(move |_, _, cx| {
+                                execution_settings_controller::toggle_guardrail_rule(
+                                    &id_for_toggle,
+                                    cx,
+                                );
+                                view_for_toggle.update(cx, |_, cx| cx.notify());
+                            }),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("guardrail-del-{}", row_ix)))
+                            .icon(Icon::new(IconName::Close))
+                            .ghost()
+                            .xsmall()
+                            .on_click(move |_, _, cx| {
+                                execution_settings_controller::remove_guardrail_rule(
+                                    &id_for_delete,
+                                    cx,
+                                );
+                                view_for_delete.update(cx, |_, cx| cx.notify());
+                            }),
+                    ),
+            )
+    }
+
+    /// Render the empty state.
+    fn render_empty(&self, cx: &Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .justify_center()
+            .py_6()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(t("guardrails-empty"))
+    }
+
+    fn add_button(
+        &self,
+        id: &'static str,
+        template: RuleTemplate,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let entity = cx.entity().clone();
+        Button::new(id)
+            .label(format!("+ {}", template.title()))
+            .small()
+            .on_click(move |_, window, cx| {
+                entity.update(cx, |view, cx| {
+                    view.show_add_rule_dialog(template, window, cx);
+                });
+            })
+    }
+}
+
+impl Focusable for GuardrailsTableView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for GuardrailsTableView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let rules = cx
+            .global::<ExecutionSettingsModel>()
+            .guardrail_rules
+            .clone();
+        let has_network_rule = rules
+            .iter()
+            .any(|r| r.kind == GuardrailRuleKind::BlockNetworkWhenConfidential);
+
+        let table = v_flex()
+            .w_full()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .overflow_hidden()
+            .map(|this| {
+                if rules.is_empty() {
+                    this.child(self.render_empty(cx))
+                } else {
+                    this.children(
+                        rules
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, rule)| self.render_row(ix, rule, cx).into_any_element()),
+                    )
+                }
+            });
+
+        v_flex().size_full().gap_3().child(table).child(
+            h_flex()
+                .gap_2()
+                .flex_wrap()
+                .child(self.add_button("add-guardrail-command", RuleTemplate::BlockCommand, cx))
+                .child(self.add_button("add-guardrail-writes", RuleTemplate::RestrictWrites, cx))
+                .child(self.add_button("add-guardrail-tool", RuleTemplate::BlockTool, cx))
+                .when(!has_network_rule, |row| {
+                    row.child(
+                        Button::new("add-guardrail-network")
+                            .label("+ Confidential Network Block")
+                            .small()
+                            .on_click(|_, _, cx| {
+                                execution_settings_controller::add_guardrail_rule(
+                                    GuardrailRule::new(
+                                        "No network in confidential conversations",
+                                        GuardrailRuleKind::BlockNetworkWhenConfidential,
+                                    ),
+                                    cx,
+                                );
+                            }),
+                    )
+                }),
+        )
+    }
+}
+
+// ── Setting page entry point ────────────────────────────────────────────────
+
+pub fn guardrails_page() -> SettingPage {
+    SettingPage::new(t("settings-page-guardrails"))
+        .description(
+            "Rules checked against every tool call before it runs. A call that breaks a rule \
+             is denied without asking for approval, and the reason is shown in the trace.",
+        )
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title("Rules")
+                .description(
+                    "Command patterns apply to shell and code execution. Write restrictions use \
+                 directories relative to the workspace. Use /confidential in a conversation to \
+                 turn on the network block for it.",
+                )
+                .items(vec![SettingItem::render(|_options, window, cx| {
+                    let view = if let Some(existing) = cx.try_global::<GlobalGuardrailsTableView>()
+                    {
+                        if let Some(view) = existing.get() {
+                            view
+                        } else {
+                            let new_view = cx.new(|cx| GuardrailsTableView::new(window, cx));
+                            cx.set_global(GlobalGuardrailsTableView::new(new_view.clone()));
+                            new_view
+                        }
+                    } else {
+                        let new_view = cx.new(|cx| GuardrailsTableView::new(window, cx));
+                        cx.set_global(GlobalGuardrailsTableView::new(new_view.clone()));
+                        new_view
+                    };
+
+                    div().w_full().child(view)
+                })]),
+        ])
+}
@@ -0,0 +1,175 @@
+use crate::i18n::t;
+use crate::settings::models::ManagedConfigModel;
+use crate::settings::models::managed_config::ManagedConfigStatus;
+use gpui::{AnyElement, App, IntoElement, ParentElement, SharedString, Styled, div, prelude::*};
+use gpui_component::{
+    ActiveTheme,
+    setting::{SettingGroup, SettingItem, SettingPage},
+    v_flex,
+};
+
+/// "just now" / "12 min ago" / "3 h ago" / "2 days ago" for a Unix timestamp.
+fn age_label(fetched_at: i64) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    let minutes = (now - fetched_at).max(0) / 60;
+    match minutes {
+        0 => "just now".to_string(),
+        m if m < 60 => format!("{m} min ago"),
+        m if m < 60 * 24 => format!("{} h ago", m / 60),
+        m => format!("{} days ago", m / (60 * 24)),
+    }
+}
+
+/// A muted placeholder line, or one line per entry.
+fn lines(entries: Vec<String>, empty: &'static str, cx: &App) -> AnyElement {
+    if entries.is_empty() {
+        return div()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(empty)
+            .into_any_element();
+    }
+    v_flex()
+        .gap_1()
+        .children(
+            entries
+                .into_iter()
+                .map(|entry| div().text_sm().child(SharedString::from(entry))),
+        )
+        .into_any_element()
+}
+
+fn status_item(cx: &App) -> AnyElement {
+    let model = cx.global::<ManagedConfigModel>();
+    let theme = cx.theme();
+    let (label, color) = match &model.status {
+        ManagedConfigStatus::Disabled => (
+            "No managed configuration. Set CHATTY_MANAGED_CONFIG_URL and \
+             CHATTY_MANAGED_CONFIG_PUBLIC_KEY to use one."
+                .to_string(),
+            theme.muted_foreground,
+        ),
+        ManagedConfigStatus::Verified { fetched_at } => (
+            format!("Signature verified, fetched {}", age_label(*fetched_at)),
+            theme.success,
+        ),
+        ManagedConfigStatus::Cached { fetched_at, error } => (
+            format!(
+                "Using the copy verified {} — refresh failed: {error}",
+                age_label(*fetched_at)
+            ),
+            theme.warning,
+        ),
+        ManagedConfigStatus::Failed(error) => (format!("Not applied: {error}"), theme.danger),
+    };
+
+    v_flex()
+        .gap_1()
+        .when_some(model.source_url.clone(), |this, url| {
+            this.child(div().text_sm().child(SharedString::from(url)))
+        })
+        .child(
+            div()
+                .text_sm()
+                .text_color(color)
+                .child(SharedString::from(label)),
+        )
+        .into_any_element()
+}
+
+pub fn managed_config_page() -> SettingPage {
+    SettingPage::new(t("settings-page-managed"))
+        .description(
+            "Settings provided by your administrator. They are merged over your own settings \
+             and cannot be changed here.",
+        )
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title("Source")
+                .items(vec![SettingItem::render(|_options, _window, cx| {
+                    status_item(cx)
+                })]),
+            SettingGroup::new()
+                .title("Providers and Models")
+                .items(vec![SettingItem::render(|_options, _window, cx| {
+                    let config = &cx.global::<ManagedConfigModel>().config;
+                    let entries = config
+                        .providers
+                        .iter()
+                        .map(|p| {
+                            format!("Provider: {} ({})", p.name, p.provider_type.display_name())
+                        })
+                        .chain(
+                            config
+                                .models
+                                .iter()
+                                .map(|m| format!("Model: {} ({})", m.name, m.model_identifier)),
+                        )
+                        .collect();
+                    lines(entries, "None recommended", cx)
+                })]),
+            SettingGroup::new()
+                .title("MCP Servers")
+                .items(vec![SettingItem::render(|_options, _window, cx| {
+                    let entries = cx
+                        .global::<ManagedConfigModel>()
+                        .config
+                        .mcp_servers
+                        .iter()
+                        .map(|s| format!("{} — {}", s.name, s.url))
+                        .collect();
+                    lines(entries, "None recommended", cx)
+                })]),
+            SettingGroup::new()
+                .title("Tool Restrictions")
+                .description(
+                    "Set by your role (CHATTY_MANAGED_ROLE, or the config's default role). \
+                     Blocked tools are not offered to the agent.",
+                )
+                .items(vec![SettingItem::render(|_options, _window, cx| {
+                    let entries = match cx.global::<ManagedConfigModel>().active_role() {
+                        Some((name, role)) => {
+                            let mut entries = vec![format!("Role: {name}")];
+                            entries.extend(
+                                role.blocked_tools
+                                    .iter()
+                                    .map(|group| format!("Blocked: {}", group.label())),
+                            );
+                            if !role.allowed_write_dirs.is_empty() {
+                                entries.push(format!(
+                                    "Writes only in: {}",
+                                    role.allowed_write_dirs.join(", ")
+                                ));
+                            }
+                            entries
+                        }
+                        None => Vec::new(),
+                    };
+                    lines(entries, "No role applies", cx)
+                })]),
+            SettingGroup::new()
+                .title("Guardrail Policies")
+                .description("Always enforced, in addition to your own guardrails.")
+                .items(vec![SettingItem::render(|_options, _window, cx| {
+                    let entries = cx
+                        .global::<ManagedConfigModel>()
+                        .config
+                        .guardrail_rules
+                        .iter()
+                        .map(|r| format!("{} — {}", r.name, r.kind.summary()))
+                        .collect();
+                    lines(entries, "None", cx)
+                })]),
+            SettingGroup::new()
+                .title("Overridden Local Settings")
+                .description("Your own entries that the managed configuration replaced.")
+                .items(vec![SettingItem::render(|_options, _window, cx| {
+                    let entries = cx.global::<ManagedConfigModel>().overridden.clone();
+                    lines(entries, "None", cx)
+                })]),
+        ])
+}
@@ -1,9 +1,11 @@
+use crate::i18n::t;
 use crate::settings::controllers::execution_settings_controller;
 use crate::settings::controllers::memory_browser_controller;
 use crate::settings::models::MemoryBrowserState;
 use crate::settings::models::execution_settings::ExecutionSettingsModel;
 use chatty_core::services::MemoryService;
 use chatty_core::services::embedding_service::EmbeddingService;
+use chatty_core::settings::models::EmbeddingModelsModel;
 use chatty_core::settings::models::providers_store::ProviderType;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
@@ -14,11 +16,12 @@ use gpui_component::{
     input::{Input, InputState},
     menu::{DropdownMenu, PopupMenuItem},
     setting::{SettingField, SettingGroup, SettingItem, SettingPage},
+    slider::{Slider, SliderEvent, SliderState},
     v_flex,
 };
 
 pub fn memory_settings_page() -> SettingPage {
-    SettingPage::new("Memory")
+    SettingPage::new(t("settings-page-memory"))
         .description(
             "Persistent agent memory across conversations. \
              The agent can store facts, preferences, and decisions, \
@@ -54,7 +57,7 @@ pub fn memory_settings_page() -> SettingPage {
                             let enabled = cx.global::<ExecutionSettingsModel>().memory_enabled;
 
                             Button::new("purge-memory-btn")
-                                .label("Purge All Memory")
+                                .label(t("memory-purge-all"))
                                 .danger()
                                 .disabled(!has_memory || !enabled)
                                 .on_click(|_, _, cx| {
@@ -88,6 +91,118 @@ pub fn memory_settings_page() -> SettingPage {
                         "When enabled, memory search uses both keyword matching and \
                          vector similarity for more accurate recall.",
                     ),
+                    SettingItem::new(
+                        "Keyword vs. Semantic Weight",
+                        SettingField::render(|_options, window, cx| {
+                            let settings = cx.global::<ExecutionSettingsModel>();
+                            let weight = settings.hybrid_keyword_weight;
+                            let enabled = settings.memory_enabled && settings.embedding_enabled;
+
+                            let slider = window.use_keyed_state(
+                                "memory-hybrid-keyword-weight",
+                                cx,
+                                |_window, cx| {
+                                    cx.subscribe(&cx.entity(), |_, _, event: &SliderEvent, cx| {
+                                        let SliderEvent::Change(value) = event;
+                                        execution_settings_controller::set_hybrid_keyword_weight(
+                                            value.start(),
+                                            cx,
+                                        );
+                                    })
+                                    .detach();
+                                    SliderState::new()
+                                        .min(0.0)
+                                        .max(1.0)
+                                        .step(0.05)
+                                        .default_value(weight)
+                                },
+                            );
+
+                            h_flex()
+                                .w_full()
+                                .gap_3()
+                                .items_center()
+                                .child(div().text_xs().child(t("memory-semantic")))
+                                .child(
+                                    div()
+                                        .flex_1()
+                                        .child(Slider::new(&slider).disabled(!enabled)),
+                                )
+                                .child(div().text_xs().child(t("memory-keyword")))
+                                .child(
+                                    div()
+                                        .w(px(40.))
+                                        .text_xs()
+                                        .child(format!("{:.0}%", weight * 100.0)),
+                                )
+                                .into_any_element()
+                        }),
+                    )
+                    .description(
+                        "How hybrid search fuses results (reciprocal rank fusion). \
+                         Slide toward Keyword to favor exact identifiers and code symbols, \
+                         toward Semantic to favor meaning.",
+                    ),
+                    SettingItem::new(
+                        "Registered Embedding Model",
+                        SettingField::render(|_options, _window, cx| {
+                            let settings = cx.global::<ExecutionSettingsModel>();
+                            let enabled = settings.memory_enabled && settings.embedding_enabled;
+                            let selected_id = settings.embedding_model_id.clone();
+                            let models = cx
+                                .try_global::<EmbeddingModelsModel>()
+                                .map(|m| m.models().to_vec())
+                                .unwrap_or_default();
+
+                            let current_label = selected_id
+                                .as_deref()
+                                .and_then(|id| models.iter().find(|m| m.id == id))
+                                .map(|m| m.name.clone())
+                                .unwrap_or_else(|| "Use provider & model below".to_string());
+
+                            Button::new("embedding-model-registry-dropdown")
+                                .label(current_label)
+                                .dropdown_caret(true)
+                                .outline()
+                                .w_full()
+                                .disabled(!enabled)
+                                .dropdown_menu_with_anchor(
+                                    gpui::Corner::BottomLeft,
+                                    move |mut menu, _, _| {
+                                        menu = menu.item(
+                                            PopupMenuItem::new("Use provider & model below")
+                                                .checked(selected_id.is_none())
+                                                .on_click(|_, _, cx| {
+                                                    execution_settings_controller::select_embedding_model(
+                                                        None, cx,
+                                                    );
+                                                }),
+                                        );
+                                        for model in &models {
+                                            let id = model.id.clone();
+                                            let is_checked =
+                                                selected_id.as_deref() == Some(model.id.as_str());
+                                            menu = menu.item(
+                                                PopupMenuItem::new(model.name.clone())
+                                                    .checked(is_checked)
+                                                    .on_click(move |_, _, cx| {
+                                                        execution_settings_controller::select_embedding_model(
+                                                            Some(id.clone()),
+                                                            cx,
+                                                        );
+                                                    }),
+                                            );
+                                        }
+                                        menu
+                                    },
+                                )
+                                .into_any_element()
+                        }),
+                    )
+                    .description(
+                        "Embedding model registered in Settings → Models → Embeddings. \
+                         Overrides the provider and model fields below.",
+                    ),
                     SettingItem::new(
                         "Embedding Provider",
                         SettingField::render(|_options, _window, cx| {
@@ -180,6 +295,66 @@ pub fn memory_settings_page() -> SettingPage {
                     .description(
                         "Model identifier for embeddings. Leave empty to use the provider's default.",
                     ),
+                    SettingItem::new(
+                        "Skills Embedding Model",
+                        SettingField::render(|_options, _window, cx| {
+                            let settings = cx.global::<ExecutionSettingsModel>();
+                            let enabled = settings.memory_enabled && settings.embedding_enabled;
+                            let selected_id = settings.skills_embedding_model_id.clone();
+                            let models = cx
+                                .try_global::<EmbeddingModelsModel>()
+                                .map(|m| m.models().to_vec())
+                                .unwrap_or_default();
+
+                            let current_label = selected_id
+                                .as_deref()
+                                .and_then(|id| models.iter().find(|m| m.id == id))
+                                .map(|m| m.name.clone())
+                                .unwrap_or_else(|| "Same as memory".to_string());
+
+                            Button::new("skills-embedding-model-dropdown")
+                                .label(current_label)
+                                .dropdown_caret(true)
+                                .outline()
+                                .w_full()
+                                .disabled(!enabled)
+                                .dropdown_menu_with_anchor(
+                                    gpui::Corner::BottomLeft,
+                                    move |mut menu, _, _| {
+                                        menu = menu.item(
+                                            PopupMenuItem::new("Same as memory")
+                                                .checked(selected_id.is_none())
+                                                .on_click(|_, _, cx| {
+                                                    execution_settings_controller::select_skills_embedding_model(
+                                                        None, cx,
+                                                    );
+                                                }),
+                                        );
+                                        for model in &models {
+                                            let id = model.id.clone();
+                                            let is_checked =
+                                                selected_id.as_deref() == Some(model.id.as_str());
+                                            menu = menu.item(
+                                                PopupMenuItem::new(model.name.clone())
+                                                    .checked(is_checked)
+                                                    .on_click(move |_, _, cx| {
+                                                        execution_settings_controller::select_skills_embedding_model(
+                                                            Some(id.clone()),
+                                                            cx,
+                                                        );
+                                                    }),
+                                            );
+                                        }
+                                        menu
+                                    },
+                                )
+                                .into_any_element()
+                        }),
+                    )
+                    .description(
+                        "Registered embedding model used to match skills against the query. \
+                         Skill embeddings are recomputed when it changes.",
+                    ),
                 ]),
         ])
 }
@@ -197,7 +372,7 @@ fn memory_browser_group() -> SettingGroup {
 
             // Persist the search input across frames
             let search_input = window.use_keyed_state("memory-browser-search", cx, |window, cx| {
-                InputState::new(window, cx).placeholder("Search memories...")
+                InputState::new(window, cx).placeholder(t("memory-search-placeholder"))
             });
 
             v_flex()
@@ -217,7 +392,7 @@ fn memory_browser_group() -> SettingGroup {
                             Button::new("memory-browser-search-btn")
                                 .small()
                                 .icon(Icon::new(IconName::Search))
-                                .label("Search")
+                                .label(t("action-search"))
                                 .loading(state.loading)
                                 .disabled(!has_memory)
                                 .on_click({
@@ -233,7 +408,7 @@ fn memory_browser_group() -> SettingGroup {
                             Button::new("memory-browser-refresh-btn")
                                 .small()
                                 .ghost()
-                                .label("Refresh")
+                                .label(t("action-refresh"))
                                 .disabled(!has_memory)
                                 .on_click(|_, _window, cx| {
                                     memory_browser_controller::load_stats(cx);
@@ -251,7 +426,7 @@ fn memory_browser_group() -> SettingGroup {
                         this.child(
                             h_flex().w_full().justify_center().py_4().child(
                                 Button::new("memory-browser-load-btn")
-                                    .label("Load Memories")
+                                    .label(t("memory-load"))
                                     .on_click(|_, _window, cx| {
                                         memory_browser_controller::load_stats(cx);
                                         memory_browser_controller::load_memories(String::new(), cx);
@@ -266,7 +441,7 @@ fn memory_browser_group() -> SettingGroup {
                         div()
                             .text_sm()
                             .text_color(cx.theme().muted_foreground)
-                            .child("Enable Agent Memory above to use the Memory Browser."),
+                            .child(t("memory-browser-disabled")),
                     )
                 })
                 // Error message
@@ -284,7 +459,7 @@ fn memory_browser_group() -> SettingGroup {
                         div()
                             .text_sm()
                             .text_color(cx.theme().muted_foreground)
-                            .child("Loading memories…"),
+                            .child(t("memory-loading")),
                     )
                 })
                 // Memory list
@@ -437,7 +612,7 @@ fn render_memory_entry(
                                 .small()
                                 .danger()
                                 .icon(Icon::new(IconName::Delete))
-                                .label("Delete")
+                                .label(t("action-delete"))
                                 .when(frame_id.is_none(), |btn| btn.disabled(true))
                                 .on_click(move |_, _window, cx| {
                                     if let Some(fid) = frame_id {
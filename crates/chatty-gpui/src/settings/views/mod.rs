@@ -1,9 +1,22 @@
+pub mod batch_runs_page;
+pub mod compare_runs_page;
+pub mod custom_tools_page;
+pub mod diagnostics_page;
 pub mod execution_settings_page;
 pub mod extensions_page;
+pub mod guardrails_page;
+pub mod managed_config_page;
 pub mod memory_settings_page;
 pub mod models_page;
+pub mod prompt_packs_page;
 pub mod providers_view;
 pub mod search_settings_page;
 pub mod settings_view;
+pub mod snippets_page;
+pub mod ssh_hosts_page;
+pub mod storage_page;
+pub mod telemetry_page;
+pub mod token_usage_page;
+pub mod tool_stats_page;
 pub mod training_settings_page;
 pub mod user_secrets_page;
@@ -5,6 +5,7 @@
 //! reads top-to-bottom as one rendered surface.
 
 use super::*;
+use crate::i18n::t;
 
 pub(super) struct ModelsListDelegate {
     sections: Vec<(ProviderType, Vec<ModelConfig>)>,
@@ -161,7 +162,7 @@ impl ListDelegate for ModelsListDelegate {
                                         .gap_2()
                                         .child(
                                             Button::new(("edit-model", row_index))
-                                                .label("Edit")
+                                                .label(t("action-edit"))
                                                 .small()
                                                 .outline()
                                                 .on_click(move |_, window, cx| {
@@ -183,7 +184,7 @@ impl ListDelegate for ModelsListDelegate {
                                         )
                                         .child(
                                             Button::new(("delete-model", row_index))
-                                                .label("Delete")
+                                                .label(t("action-delete"))
                                                 .small()
                                                 .outline()
                                                 .on_click(move |_, _, cx| {
@@ -212,6 +213,14 @@ impl ListDelegate for ModelsListDelegate {
                                 .text_color(theme.muted_foreground)
                                 .child(format!("Model: {}", model.model_identifier)),
                         )
+                        .when_some(model.lineage.as_ref(), |this, lineage| {
+                            this.child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.muted_foreground)
+                                    .child(lineage.summary()),
+                            )
+                        })
                         .child(
                             h_flex()
                                 .gap_4()
@@ -261,7 +270,7 @@ impl ListDelegate for ModelsListDelegate {
                     div()
                         .text_sm()
                         .text_color(theme.muted_foreground.opacity(0.7))
-                        .child("Try adjusting your search terms"),
+                        .child(t("models-no-results-hint")),
                 )
             })
     }
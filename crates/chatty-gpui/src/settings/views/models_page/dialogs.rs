@@ -7,6 +7,13 @@
 //! view lifecycle and Render impl, not by dialog plumbing.
 
 use super::*;
+use crate::i18n::t;
+use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use crate::settings::models::models_store::{ModelLineage, ModelRule};
+use chatty_core::services::prompt_template::{
+    PromptVariables, TEMPLATE_VARIABLES, render_prompt_template,
+};
+use gpui::{Div, FontWeight};
 
 impl ModelsListView {
     pub(super) fn show_add_model_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -24,16 +31,28 @@ impl ModelsListView {
             state.set_value("1.0".to_string(), window, cx);
             state
         });
-        let preamble_input = cx
-            .new(|cx| InputState::new(window, cx).placeholder("System instructions for the model"));
+        let preamble_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder(t("models-preamble-placeholder")));
         let max_tokens_input = cx.new(|cx| InputState::new(window, cx).placeholder("e.g., 4096"));
         let max_context_window_input =
             cx.new(|cx| InputState::new(window, cx).placeholder("e.g., 200000"));
+        let max_video_frames_input = cx.new(|cx| InputState::new(window, cx).placeholder("8"));
         let top_p_input = cx.new(|cx| InputState::new(window, cx).placeholder("0.0 - 1.0"));
         let cost_input_input = cx.new(|cx| InputState::new(window, cx).placeholder("e.g., 2.50"));
         let cost_output_input = cx.new(|cx| InputState::new(window, cx).placeholder("e.g., 10.00"));
         let api_version_input =
             cx.new(|cx| InputState::new(window, cx).placeholder("e.g., 2024-10-21"));
+        let aliases_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("e.g., fast, cheap"));
+        let rules_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("e.g., tag:code, project:~/work/api, attachment:image")
+        });
+        let base_model_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("e.g., gpt-4o-mini-2024-07-18"));
+        let dataset_hash_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("e.g., sha256 of train.jsonl"));
+        let trained_on_input = cx.new(|cx| InputState::new(window, cx).placeholder("YYYY-MM-DD"));
 
         // Get configured providers from the global store
         let providers: Vec<String> = cx
@@ -108,7 +127,7 @@ impl ModelsListView {
         let muted_foreground = cx.theme().muted_foreground;
 
         let search_input =
-            cx.new(|cx| InputState::new(window, cx).placeholder("Search OpenRouter models..."));
+            cx.new(|cx| InputState::new(window, cx).placeholder(t("models-openrouter-search")));
 
         window.open_dialog(cx, move |dialog, _window, cx| {
             dialog
@@ -137,8 +156,8 @@ impl ModelsListView {
                                                 active_tab.set(*index);
                                             }
                                         })
-                                        .child(Tab::new().label("Basic"))
-                                        .child(Tab::new().label("Advanced"))
+                                        .child(Tab::new().label(t("models-tab-basic")))
+                                        .child(Tab::new().label(t("models-tab-advanced")))
                                 })
                                 .child({
                                     let current_tab = active_tab.get();
@@ -152,13 +171,13 @@ impl ModelsListView {
                                             .child(
                                                 v_flex()
                                                     .gap_1()
-                                                    .child(div().text_sm().child("Model Name *"))
+                                                    .child(div().text_sm().child(t("models-name")))
                                                     .child(Input::new(&name_input)),
                                             )
                                             .child(
                                                 v_flex()
                                                     .gap_1()
-                                                    .child(div().text_sm().child("Provider *"))
+                                                    .child(div().text_sm().child(t("models-provider")))
                                                     .child(Select::new(&provider_select)),
                                             );
 
@@ -172,7 +191,7 @@ impl ModelsListView {
                                                 root = root.child(
                                                     v_flex()
                                                         .gap_1()
-                                                        .child(div().text_sm().child("OpenRouter Catalog"))
+                                                        .child(div().text_sm().child(t("models-openrouter-catalog")))
                                                         .child(Input::new(&search_input))
                                                         .child({
                                                             let query = search_input.read(cx).value().to_lowercase();
@@ -200,7 +219,7 @@ impl ModelsListView {
                                                                         .py_2()
                                                                         .text_sm()
                                                                         .text_color(muted_foreground)
-                                                                        .child("No matches")
+                                                                        .child(t("models-no-matches"))
                                                                         .into_any_element()]
                                                                 } else {
                                                                     filtered.into_iter().map(|m| {
@@ -245,7 +264,7 @@ impl ModelsListView {
                                             v_flex()
                                                 .gap_1()
                                                 .child(
-                                                    div().text_sm().child("Model Identifier *"),
+                                                    div().text_sm().child(t("models-identifier")),
                                                 )
                                                 .child(Input::new(&model_id_input)),
                                         );
@@ -258,7 +277,7 @@ impl ModelsListView {
                                             .child(
                                                 v_flex()
                                                     .gap_1()
-                                                    .child(div().text_sm().child("Temperature"))
+                                                    .child(div().text_sm().child(t("models-temperature")))
                                                     .child(Input::new(&temperature_input)),
                                             )
                                             .child(
@@ -267,9 +286,13 @@ impl ModelsListView {
                                                     .child(
                                                         div()
                                                             .text_sm()
-                                                            .child("Preamble / System Prompt"),
+                                                            .child(t("models-preamble")),
                                                     )
-                                                    .child(Input::new(&preamble_input)),
+                                                    .child(Input::new(&preamble_input))
+                                                    .child(render_preamble_preview(
+                                                        &preamble_input.read(cx).value(),
+                                                        cx,
+                                                    )),
                                             )
                                             .child(
                                                 v_flex()
@@ -277,7 +300,7 @@ impl ModelsListView {
                                                     .child(
                                                         div()
                                                             .text_sm()
-                                                            .child("Max Tokens (optional)"),
+                                                            .child(t("models-max-tokens")),
                                                     )
                                                     .child(Input::new(&max_tokens_input)),
                                             )
@@ -287,7 +310,7 @@ impl ModelsListView {
                                                     .child(
                                                         div()
                                                             .text_sm()
-                                                            .child("Max Context Window (optional)"),
+                                                            .child(t("models-max-context")),
                                                     )
                                                     .child(Input::new(&max_context_window_input)),
                                             )
@@ -295,7 +318,17 @@ impl ModelsListView {
                                                 v_flex()
                                                     .gap_1()
                                                     .child(
-                                                        div().text_sm().child("Top P (optional)"),
+                                                        div()
+                                                            .text_sm()
+                                                            .child(t("models-max-video-frames")),
+                                                    )
+                                                    .child(Input::new(&max_video_frames_input)),
+                                            )
+                                            .child(
+                                                v_flex()
+                                                    .gap_1()
+                                                    .child(
+                                                        div().text_sm().child(t("models-top-p")),
                                                     )
                                                     .child(Input::new(&top_p_input)),
                                             )
@@ -315,6 +348,28 @@ impl ModelsListView {
                                                     ))
                                                     .child(Input::new(&cost_output_input)),
                                             )
+                                            .child(
+                                                v_flex()
+                                                    .gap_1()
+                                                    .child(div().text_sm().child(
+                                                        "Aliases (comma-separated)",
+                                                    ))
+                                                    .child(Input::new(&aliases_input)),
+                                            )
+                                            .child(
+                                                v_flex()
+                                                    .gap_1()
+                                                    .child(div().text_sm().child(
+                                                        "Default For (tag:, project:, attachment:)",
+                                                    ))
+                                                    .child(Input::new(&rules_input)),
+                                            )
+                                            .child(render_lineage_fields(
+                                                &base_model_input,
+                                                &dataset_hash_input,
+                                                &trained_on_input,
+                                                cx,
+                                            ))
                                             .when(is_azure, |this| {
                                                 this.child(
                                                     v_flex()
@@ -333,13 +388,13 @@ impl ModelsListView {
                                         .gap_2()
                                         .justify_end()
                                         .pt_4()
-                                        .child(Button::new("cancel").label("Cancel").on_click(
+                                        .child(Button::new("cancel").label(t("action-cancel")).on_click(
                                             move |_, window, cx| {
                                                 window.close_dialog(cx);
                                             },
                                         ))
                                         .child(
-                                            Button::new("save").primary().label("Save").on_click({
+                                            Button::new("save").primary().label(t("action-save")).on_click({
                                                 let view = view.clone();
                                                 let name_input = name_input.clone();
                                                 let model_id_input = model_id_input.clone();
@@ -348,10 +403,18 @@ impl ModelsListView {
                                                 let max_tokens_input = max_tokens_input.clone();
                                                 let max_context_window_input =
                                                     max_context_window_input.clone();
+                                                let max_video_frames_input =
+                                                    max_video_frames_input.clone();
                                                 let top_p_input = top_p_input.clone();
                                                 let cost_input_input = cost_input_input.clone();
                                                 let cost_output_input = cost_output_input.clone();
                                                 let api_version_input = api_version_input.clone();
+                                                let aliases_input = aliases_input.clone();
+                                                let rules_input = rules_input.clone();
+                                                let base_model_input = base_model_input.clone();
+                                                let dataset_hash_input =
+                                                    dataset_hash_input.clone();
+                                                let trained_on_input = trained_on_input.clone();
                                                 let provider_select = provider_select.clone();
 
                                                 move |_, window, cx| {
@@ -366,6 +429,8 @@ impl ModelsListView {
                                                         max_tokens_input.read(cx).value();
                                                     let max_context_window_str =
                                                         max_context_window_input.read(cx).value();
+                                                    let max_video_frames_str =
+                                                        max_video_frames_input.read(cx).value();
                                                     let top_p_str = top_p_input.read(cx).value();
                                                     let provider_index =
                                                         provider_select.read(cx).selected_index(cx);
@@ -412,6 +477,12 @@ impl ModelsListView {
                                                                 .filter(|&v| v > 0)
                                                         };
 
+                                                    let max_video_frames = max_video_frames_str
+                                                        .trim()
+                                                        .parse::<u32>()
+                                                        .ok()
+                                                        .filter(|&v| v > 0);
+
                                                     let top_p = if top_p_str.trim().is_empty() {
                                                         None
                                                     } else {
@@ -474,6 +545,32 @@ impl ModelsListView {
                                                         );
                                                     }
 
+                                                    let aliases = parse_aliases(
+                                                        &aliases_input.read(cx).value(),
+                                                    );
+                                                    let default_rules =
+                                                        match ModelRule::parse_list(
+                                                            &rules_input.read(cx).value(),
+                                                        ) {
+                                                            Ok(rules) => rules,
+                                                            Err(e) => {
+                                                                window.push_notification(e, cx);
+                                                                return;
+                                                            }
+                                                        };
+                                                    let lineage = match ModelLineage::from_fields(
+                                                        &model_identifier,
+                                                        &base_model_input.read(cx).value(),
+                                                        &dataset_hash_input.read(cx).value(),
+                                                        &trained_on_input.read(cx).value(),
+                                                    ) {
+                                                        Ok(lineage) => lineage,
+                                                        Err(e) => {
+                                                            window.push_notification(e, cx);
+                                                            return;
+                                                        }
+                                                    };
+
                                                     let config = ModelConfig {
                                                         id: uuid::Uuid::new_v4().to_string(),
                                                         name: name.trim().to_string(),
@@ -485,6 +582,7 @@ impl ModelsListView {
                                                         preamble: preamble.to_string(),
                                                         max_tokens,
                                                         max_context_window,
+                                                        max_video_frames,
                                                         top_p,
                                                         extra_params,
                                                         cost_per_million_input_tokens,
@@ -492,6 +590,9 @@ impl ModelsListView {
                                                         supports_images: false,
                                                         supports_pdf: false,
                                                         supports_temperature: true,
+                                                        aliases,
+                                                        default_rules,
+                                                        lineage,
                                                     };
 
                                                     // Save the model (capabilities auto-set by create_model)
@@ -551,7 +652,7 @@ impl ModelsListView {
         });
         let preamble_input = cx.new(|cx| {
             let mut state =
-                InputState::new(window, cx).placeholder("System instructions for the model");
+                InputState::new(window, cx).placeholder(t("models-preamble-placeholder"));
             state.set_value(existing_model.preamble.clone(), window, cx);
             state
         });
@@ -569,6 +670,13 @@ impl ModelsListView {
             }
             state
         });
+        let max_video_frames_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder("8");
+            if let Some(max_video_frames) = existing_model.max_video_frames {
+                state.set_value(max_video_frames.to_string(), window, cx);
+            }
+            state
+        });
         let top_p_input = cx.new(|cx| {
             let mut state = InputState::new(window, cx).placeholder("0.0 - 1.0");
             if let Some(top_p) = existing_model.top_p {
@@ -598,6 +706,37 @@ impl ModelsListView {
             }
             state
         });
+        let aliases_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder("e.g., fast, cheap");
+            state.set_value(existing_model.aliases.join(", "), window, cx);
+            state
+        });
+        let rules_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx)
+                .placeholder("e.g., tag:code, project:~/work/api, attachment:image");
+            state.set_value(
+                ModelRule::format_list(&existing_model.default_rules),
+                window,
+                cx,
+            );
+            state
+        });
+        let lineage = existing_model.lineage.clone().unwrap_or_default();
+        let base_model_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder("e.g., gpt-4o-mini-2024-07-18");
+            state.set_value(lineage.base_model.clone(), window, cx);
+            state
+        });
+        let dataset_hash_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder("e.g., sha256 of train.jsonl");
+            state.set_value(lineage.dataset_hash.clone().unwrap_or_default(), window, cx);
+            state
+        });
+        let trained_on_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder("YYYY-MM-DD");
+            state.set_value(lineage.trained_on.clone().unwrap_or_default(), window, cx);
+            state
+        });
 
         // Get configured providers and find the index of the current provider.
         // Collect once since the result is used twice (provider names + position lookup).
@@ -621,7 +760,7 @@ impl ModelsListView {
         let model_id_for_update = model_id.clone();
         let is_azure = matches!(existing_model.provider_type, ProviderType::AzureOpenAI);
 
-        window.open_dialog(cx, move |dialog, _, _| {
+        window.open_dialog(cx, move |dialog, _window, cx| {
             dialog
                 .title("Edit Model")
                 .overlay(true)
@@ -648,8 +787,8 @@ impl ModelsListView {
                                                 active_tab.set(*index);
                                             }
                                         })
-                                        .child(Tab::new().label("Basic"))
-                                        .child(Tab::new().label("Advanced"))
+                                        .child(Tab::new().label(t("models-tab-basic")))
+                                        .child(Tab::new().label(t("models-tab-advanced")))
                                 })
                                 .child({
                                     let current_tab = active_tab.get();
@@ -661,20 +800,24 @@ impl ModelsListView {
                                             .child(
                                                 v_flex()
                                                     .gap_1()
-                                                    .child(div().text_sm().child("Model Name *"))
+                                                    .child(div().text_sm().child(t("models-name")))
                                                     .child(Input::new(&name_input)),
                                             )
                                             .child(
                                                 v_flex()
                                                     .gap_1()
-                                                    .child(div().text_sm().child("Provider *"))
+                                                    .child(
+                                                        div().text_sm().child(t("models-provider")),
+                                                    )
                                                     .child(Select::new(&provider_select)),
                                             )
                                             .child(
                                                 v_flex()
                                                     .gap_1()
                                                     .child(
-                                                        div().text_sm().child("Model Identifier *"),
+                                                        div()
+                                                            .text_sm()
+                                                            .child(t("models-identifier")),
                                                     )
                                                     .child(Input::new(&model_id_input)),
                                             )
@@ -686,18 +829,24 @@ impl ModelsListView {
                                             .child(
                                                 v_flex()
                                                     .gap_1()
-                                                    .child(div().text_sm().child("Temperature"))
+                                                    .child(
+                                                        div()
+                                                            .text_sm()
+                                                            .child(t("models-temperature")),
+                                                    )
                                                     .child(Input::new(&temperature_input)),
                                             )
                                             .child(
                                                 v_flex()
                                                     .gap_1()
                                                     .child(
-                                                        div()
-                                                            .text_sm()
-                                                            .child("Preamble / System Prompt"),
+                                                        div().text_sm().child(t("models-preamble")),
                                                     )
-                                                    .child(Input::new(&preamble_input)),
+                                                    .child(Input::new(&preamble_input))
+                                                    .child(render_preamble_preview(
+                                                        &preamble_input.read(cx).value(),
+                                                        cx,
+                                                    )),
                                             )
                                             .child(
                                                 v_flex()
@@ -705,7 +854,7 @@ impl ModelsListView {
                                                     .child(
                                                         div()
                                                             .text_sm()
-                                                            .child("Max Tokens (optional)"),
+                                                            .child(t("models-max-tokens")),
                                                     )
                                                     .child(Input::new(&max_tokens_input)),
                                             )
@@ -715,7 +864,7 @@ impl ModelsListView {
                                                     .child(
                                                         div()
                                                             .text_sm()
-                                                            .child("Max Context Window (optional)"),
+                                                            .child(t("models-max-context")),
                                                     )
                                                     .child(Input::new(&max_context_window_input)),
                                             )
@@ -723,8 +872,16 @@ impl ModelsListView {
                                                 v_flex()
                                                     .gap_1()
                                                     .child(
-                                                        div().text_sm().child("Top P (optional)"),
+                                                        div()
+                                                            .text_sm()
+                                                            .child(t("models-max-video-frames")),
                                                     )
+                                                    .child(Input::new(&max_video_frames_input)),
+                                            )
+                                            .child(
+                                                v_flex()
+                                                    .gap_1()
+                                                    .child(div().text_sm().child(t("models-top-p")))
                                                     .child(Input::new(&top_p_input)),
                                             )
                                             .child(
@@ -743,6 +900,28 @@ impl ModelsListView {
                                                     ))
                                                     .child(Input::new(&cost_output_input)),
                                             )
+                                            .child(
+                                                v_flex()
+                                                    .gap_1()
+                                                    .child(
+                                                        div().text_sm().child(t("models-aliases")),
+                                                    )
+                                                    .child(Input::new(&aliases_input)),
+                                            )
+                                            .child(
+                                                v_flex()
+                                                    .gap_1()
+                                                    .child(div().text_sm().child(
+                                                        "Default For (tag:, project:, attachment:)",
+                                                    ))
+                                                    .child(Input::new(&rules_input)),
+                                            )
+                                            .child(render_lineage_fields(
+                                                &base_model_input,
+                                                &dataset_hash_input,
+                                                &trained_on_input,
+                                                cx,
+                                            ))
                                             .when(is_azure, |this| {
                                                 this.child(
                                                     v_flex()
@@ -761,181 +940,244 @@ impl ModelsListView {
                                         .gap_2()
                                         .justify_end()
                                         .pt_4()
-                                        .child(Button::new("cancel").label("Cancel").on_click(
-                                            move |_, window, cx| {
-                                                window.close_dialog(cx);
-                                            },
-                                        ))
                                         .child(
-                                            Button::new("save").primary().label("Save").on_click({
-                                                let view = view.clone();
-                                                let name_input = name_input.clone();
-                                                let model_id_input = model_id_input.clone();
-                                                let temperature_input = temperature_input.clone();
-                                                let preamble_input = preamble_input.clone();
-                                                let max_tokens_input = max_tokens_input.clone();
-                                                let max_context_window_input =
-                                                    max_context_window_input.clone();
-                                                let top_p_input = top_p_input.clone();
-                                                let cost_input_input = cost_input_input.clone();
-                                                let cost_output_input = cost_output_input.clone();
-                                                let api_version_input = api_version_input.clone();
-                                                let provider_select = provider_select.clone();
-                                                let model_id_for_update =
-                                                    model_id_for_update.clone();
+                                            Button::new("cancel")
+                                                .label(t("action-cancel"))
+                                                .on_click(move |_, window, cx| {
+                                                    window.close_dialog(cx);
+                                                }),
+                                        )
+                                        .child(
+                                            Button::new("save")
+                                                .primary()
+                                                .label(t("action-save"))
+                                                .on_click({
+                                                    let view = view.clone();
+                                                    let name_input = name_input.clone();
+                                                    let model_id_input = model_id_input.clone();
+                                                    let temperature_input =
+                                                        temperature_input.clone();
+                                                    let preamble_input = preamble_input.clone();
+                                                    let max_tokens_input = max_tokens_input.clone();
+                                                    let max_context_window_input =
+                                                        max_context_window_input.clone();
+                                                    let max_video_frames_input =
+                                                        max_video_frames_input.clone();
+                                                    let top_p_input = top_p_input.clone();
+                                                    let cost_input_input = cost_input_input.clone();
+                                                    let cost_output_input =
+                                                        cost_output_input.clone();
+                                                    let api_version_input =
+                                                        api_version_input.clone();
+                                                    let aliases_input = aliases_input.clone();
+                                                    let rules_input = rules_input.clone();
+                                                    let base_model_input = base_model_input.clone();
+                                                    let dataset_hash_input =
+                                                        dataset_hash_input.clone();
+                                                    let trained_on_input = trained_on_input.clone();
+                                                    let provider_select = provider_select.clone();
+                                                    let model_id_for_update =
+                                                        model_id_for_update.clone();
 
-                                                move |_, window, cx| {
-                                                    // Validate and collect form data
-                                                    let name = name_input.read(cx).value();
-                                                    let model_identifier =
-                                                        model_id_input.read(cx).value();
-                                                    let temperature_str =
-                                                        temperature_input.read(cx).value();
-                                                    let preamble = preamble_input.read(cx).value();
-                                                    let max_tokens_str =
-                                                        max_tokens_input.read(cx).value();
-                                                    let max_context_window_str =
-                                                        max_context_window_input.read(cx).value();
-                                                    let top_p_str = top_p_input.read(cx).value();
-                                                    let provider_index =
-                                                        provider_select.read(cx).selected_index(cx);
+                                                    move |_, window, cx| {
+                                                        // Validate and collect form data
+                                                        let name = name_input.read(cx).value();
+                                                        let model_identifier =
+                                                            model_id_input.read(cx).value();
+                                                        let temperature_str =
+                                                            temperature_input.read(cx).value();
+                                                        let preamble =
+                                                            preamble_input.read(cx).value();
+                                                        let max_tokens_str =
+                                                            max_tokens_input.read(cx).value();
+                                                        let max_context_window_str =
+                                                            max_context_window_input
+                                                                .read(cx)
+                                                                .value();
+                                                        let max_video_frames_str =
+                                                            max_video_frames_input.read(cx).value();
+                                                        let top_p_str =
+                                                            top_p_input.read(cx).value();
+                                                        let provider_index = provider_select
+                                                            .read(cx)
+                                                            .selected_index(cx);
 
-                                                    // Validation
-                                                    if name.trim().is_empty() {
-                                                        window.push_notification(
-                                                            "Model name is required",
-                                                            cx,
-                                                        );
-                                                        return;
-                                                    }
-                                                    if model_identifier.trim().is_empty() {
-                                                        window.push_notification(
-                                                            "Model identifier is required",
-                                                            cx,
-                                                        );
-                                                        return;
-                                                    }
+                                                        // Validation
+                                                        if name.trim().is_empty() {
+                                                            window.push_notification(
+                                                                "Model name is required",
+                                                                cx,
+                                                            );
+                                                            return;
+                                                        }
+                                                        if model_identifier.trim().is_empty() {
+                                                            window.push_notification(
+                                                                "Model identifier is required",
+                                                                cx,
+                                                            );
+                                                            return;
+                                                        }
 
-                                                    let temperature = temperature_str
-                                                        .parse::<f32>()
-                                                        .unwrap_or(1.0)
-                                                        .clamp(0.0, 2.0);
+                                                        let temperature = temperature_str
+                                                            .parse::<f32>()
+                                                            .unwrap_or(1.0)
+                                                            .clamp(0.0, 2.0);
 
-                                                    let max_tokens =
-                                                        if max_tokens_str.trim().is_empty() {
-                                                            None
-                                                        } else {
-                                                            max_tokens_str
-                                                                .parse::<i32>()
-                                                                .ok()
-                                                                .filter(|&v| v > 0)
-                                                        };
+                                                        let max_tokens =
+                                                            if max_tokens_str.trim().is_empty() {
+                                                                None
+                                                            } else {
+                                                                max_tokens_str
+                                                                    .parse::<i32>()
+                                                                    .ok()
+                                                                    .filter(|&v| v > 0)
+                                                            };
 
-                                                    let max_context_window =
-                                                        if max_context_window_str.trim().is_empty()
-                                                        {
-                                                            None
-                                                        } else {
-                                                            max_context_window_str
-                                                                .parse::<i32>()
-                                                                .ok()
-                                                                .filter(|&v| v > 0)
-                                                        };
+                                                        let max_context_window =
+                                                            if max_context_window_str
+                                                                .trim()
+                                                                .is_empty()
+                                                            {
+                                                                None
+                                                            } else {
+                                                                max_context_window_str
+                                                                    .parse::<i32>()
+                                                                    .ok()
+                                                                    .filter(|&v| v > 0)
+                                                            };
 
-                                                    let top_p = if top_p_str.trim().is_empty() {
-                                                        None
-                                                    } else {
-                                                        top_p_str
-                                                            .parse::<f32>()
+                                                        let max_video_frames = max_video_frames_str
+                                                            .trim()
+                                                            .parse::<u32>()
                                                             .ok()
-                                                            .filter(|&v| (0.0..=1.0).contains(&v))
-                                                    };
-
-                                                    let cost_input_str =
-                                                        cost_input_input.read(cx).value();
-                                                    let cost_output_str =
-                                                        cost_output_input.read(cx).value();
+                                                            .filter(|&v| v > 0);
 
-                                                    let cost_per_million_input_tokens =
-                                                        if cost_input_str.trim().is_empty() {
+                                                        let top_p = if top_p_str.trim().is_empty() {
                                                             None
                                                         } else {
-                                                            cost_input_str
-                                                                .parse::<f64>()
-                                                                .ok()
-                                                                .filter(|&v| v >= 0.0)
+                                                            top_p_str.parse::<f32>().ok().filter(
+                                                                |&v| (0.0..=1.0).contains(&v),
+                                                            )
                                                         };
 
-                                                    let cost_per_million_output_tokens =
-                                                        if cost_output_str.trim().is_empty() {
-                                                            None
-                                                        } else {
-                                                            cost_output_str
-                                                                .parse::<f64>()
-                                                                .ok()
-                                                                .filter(|&v| v >= 0.0)
-                                                        };
+                                                        let cost_input_str =
+                                                            cost_input_input.read(cx).value();
+                                                        let cost_output_str =
+                                                            cost_output_input.read(cx).value();
 
-                                                    let all_providers: Vec<&str> = cx
-                                                        .global::<ProviderModel>()
-                                                        .configured_providers()
-                                                        .map(|p| p.provider_type.display_name())
-                                                        .collect();
-                                                    let provider_str = provider_index
-                                                        .and_then(|idx| {
-                                                            all_providers.get(idx.row).copied()
-                                                        })
-                                                        .unwrap_or("OpenRouter");
-                                                    let provider_type =
-                                                        string_to_provider_type(provider_str);
+                                                        let cost_per_million_input_tokens =
+                                                            if cost_input_str.trim().is_empty() {
+                                                                None
+                                                            } else {
+                                                                cost_input_str
+                                                                    .parse::<f64>()
+                                                                    .ok()
+                                                                    .filter(|&v| v >= 0.0)
+                                                            };
 
-                                                    let api_version_str =
-                                                        api_version_input.read(cx).value();
-                                                    let mut extra_params =
-                                                        std::collections::HashMap::new();
-                                                    if matches!(
-                                                        provider_type,
-                                                        ProviderType::AzureOpenAI
-                                                    ) && !api_version_str.trim().is_empty()
-                                                    {
-                                                        extra_params.insert(
-                                                            "api_version".to_string(),
-                                                            api_version_str.trim().to_string(),
+                                                        let cost_per_million_output_tokens =
+                                                            if cost_output_str.trim().is_empty() {
+                                                                None
+                                                            } else {
+                                                                cost_output_str
+                                                                    .parse::<f64>()
+                                                                    .ok()
+                                                                    .filter(|&v| v >= 0.0)
+                                                            };
+
+                                                        let all_providers: Vec<&str> = cx
+                                                            .global::<ProviderModel>()
+                                                            .configured_providers()
+                                                            .map(|p| p.provider_type.display_name())
+                                                            .collect();
+                                                        let provider_str = provider_index
+                                                            .and_then(|idx| {
+                                                                all_providers.get(idx.row).copied()
+                                                            })
+                                                            .unwrap_or("OpenRouter");
+                                                        let provider_type =
+                                                            string_to_provider_type(provider_str);
+
+                                                        let api_version_str =
+                                                            api_version_input.read(cx).value();
+                                                        let mut extra_params =
+                                                            std::collections::HashMap::new();
+                                                        if matches!(
+                                                            provider_type,
+                                                            ProviderType::AzureOpenAI
+                                                        ) && !api_version_str.trim().is_empty()
+                                                        {
+                                                            extra_params.insert(
+                                                                "api_version".to_string(),
+                                                                api_version_str.trim().to_string(),
+                                                            );
+                                                        }
+
+                                                        let aliases = parse_aliases(
+                                                            &aliases_input.read(cx).value(),
                                                         );
-                                                    }
+                                                        let default_rules =
+                                                            match ModelRule::parse_list(
+                                                                &rules_input.read(cx).value(),
+                                                            ) {
+                                                                Ok(rules) => rules,
+                                                                Err(e) => {
+                                                                    window.push_notification(e, cx);
+                                                                    return;
+                                                                }
+                                                            };
+                                                        let lineage =
+                                                            match ModelLineage::from_fields(
+                                                                &model_identifier,
+                                                                &base_model_input.read(cx).value(),
+                                                                &dataset_hash_input
+                                                                    .read(cx)
+                                                                    .value(),
+                                                                &trained_on_input.read(cx).value(),
+                                                            ) {
+                                                                Ok(lineage) => lineage,
+                                                                Err(e) => {
+                                                                    window.push_notification(e, cx);
+                                                                    return;
+                                                                }
+                                                            };
 
-                                                    let config = ModelConfig {
-                                                        id: model_id_for_update.clone(),
-                                                        name: name.trim().to_string(),
-                                                        provider_type,
-                                                        model_identifier: model_identifier
-                                                            .trim()
-                                                            .to_string(),
-                                                        temperature,
-                                                        preamble: preamble.to_string(),
-                                                        max_tokens,
-                                                        max_context_window,
-                                                        top_p,
-                                                        extra_params,
-                                                        cost_per_million_input_tokens,
-                                                        cost_per_million_output_tokens,
-                                                        supports_images: false,
-                                                        supports_pdf: false,
-                                                        supports_temperature: true,
-                                                    };
+                                                        let config = ModelConfig {
+                                                            id: model_id_for_update.clone(),
+                                                            name: name.trim().to_string(),
+                                                            provider_type,
+                                                            model_identifier: model_identifier
+                                                                .trim()
+                                                                .to_string(),
+                                                            temperature,
+                                                            preamble: preamble.to_string(),
+                                                            max_tokens,
+                                                            max_context_window,
+                                                            max_video_frames,
+                                                            top_p,
+                                                            extra_params,
+                                                            cost_per_million_input_tokens,
+                                                            cost_per_million_output_tokens,
+                                                            supports_images: false,
+                                                            supports_pdf: false,
+                                                            supports_temperature: true,
+                                                            aliases,
+                                                            default_rules,
+                                                            lineage,
+                                                        };
 
-                                                    // Update the model
-                                                    models_controller::update_model(config, cx);
+                                                        // Update the model
+                                                        models_controller::update_model(config, cx);
 
-                                                    // Close dialog
-                                                    window.close_dialog(cx);
+                                                        // Close dialog
+                                                        window.close_dialog(cx);
 
-                                                    // Refresh list
-                                                    view.update(cx, |view, cx| {
-                                                        view.refresh(cx);
-                                                    });
-                                                }
-                                            }),
+                                                        // Refresh list
+                                                        view.update(cx, |view, cx| {
+                                                            view.refresh(cx);
+                                                        });
+                                                    }
+                                                }),
                                         ),
                                 ),
                         ),
@@ -943,3 +1185,102 @@ impl ModelsListView {
         });
     }
 }
+
+/// Split a comma-separated alias list, dropping blanks and duplicates.
+fn parse_aliases(input: &str) -> Vec<String> {
+    let mut aliases: Vec<String> = Vec::new();
+    for alias in input.split(',').map(str::trim).filter(|a| !a.is_empty()) {
+        if !aliases.iter().any(|a| a.eq_ignore_ascii_case(alias)) {
+            aliases.push(alias.to_string());
+        }
+    }
+    aliases
+}
+
+/// Fine-tune lineage inputs: base model, dataset hash and training date.
+fn render_lineage_fields(
+    base_model_input: &Entity<InputState>,
+    dataset_hash_input: &Entity<InputState>,
+    trained_on_input: &Entity<InputState>,
+    cx: &App,
+) -> Div {
+    v_flex()
+        .gap_2()
+        .pt_2()
+        .child(
+            div()
+                .text_sm()
+                .font_weight(FontWeight::SEMIBOLD)
+                .child(t("models-lineage")),
+        )
+        .child(
+            div()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .child(
+                    "For OpenAI ft: models, Azure fine-tuned deployments and Ollama adapters. \
+                     Shown in the models list and stamped into ATIF exports.",
+                ),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child(t("models-base-model")))
+                .child(Input::new(base_model_input)),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child(t("models-dataset-hash")))
+                .child(Input::new(dataset_hash_input)),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child(t("models-training-date")))
+                .child(Input::new(trained_on_input)),
+        )
+}
+
+/// Variable hint and, when the preamble uses `{{variables}}`, the prompt as
+/// it will be resolved when the agent is built.
+fn render_preamble_preview(preamble: &str, cx: &App) -> Div {
+    let hint = TEMPLATE_VARIABLES
+        .iter()
+        .map(|(name, _)| format!("{{{{{name}}}}}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let container = v_flex().gap_1().child(
+        div()
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .child(format!("Variables: {hint}")),
+    );
+    if !preamble.contains("{{") {
+        return container;
+    }
+
+    let workspace = cx
+        .try_global::<ExecutionSettingsModel>()
+        .and_then(|s| s.workspace_dir.clone());
+    let resolved =
+        render_prompt_template(preamble, &PromptVariables::current(workspace.as_deref()))
+            .into_owned();
+    container.child(
+        v_flex()
+            .gap_1()
+            .p_2()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().secondary)
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(cx.theme().muted_foreground)
+                    .child(t("action-preview")),
+            )
+            .child(div().text_sm().whitespace_normal().child(resolved)),
+    )
+}
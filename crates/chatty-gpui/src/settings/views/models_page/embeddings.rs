@@ -0,0 +1,325 @@
+//! "Embeddings" section of the models page.
+//!
+//! Registers embedding models (OpenAI / Gemini via OpenRouter, Azure
+//! deployments, Ollama `nomic-embed-text`, …) with their vector dimensions
+//! and batch-embedding rate controls. The memory store picks one of these in
+//! Settings → Memory.
+
+use super::*;
+use crate::i18n::t;
+use crate::settings::controllers::embedding_models_controller;
+use crate::settings::models::embedding_models_store::{
+    EmbeddingModelConfig, EmbeddingModelsModel, known_dimensions,
+};
+
+impl ModelsListView {
+    pub(super) fn render_embeddings_section(&self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = cx.theme();
+        let models = cx.global::<EmbeddingModelsModel>().models().to_vec();
+
+        let rows: Vec<AnyElement> = models
+            .into_iter()
+            .map(|model| {
+                let dims = model
+                    .dimensions
+                    .map(|d| format!("{d} dims"))
+                    .unwrap_or_else(|| "dims unknown".to_string());
+                let rate = model
+                    .requests_per_minute
+                    .map(|rpm| format!("{rpm} req/min"))
+                    .unwrap_or_else(|| "no rate limit".to_string());
+                let details = format!(
+                    "{} · {} · {} · batch {} · {}",
+                    model.provider_type.display_name(),
+                    model.model_identifier,
+                    dims,
+                    model.batch_size,
+                    rate
+                );
+                let model_id = model.id.clone();
+
+                h_flex()
+                    .w_full()
+                    .px_3()
+                    .py_2()
+                    .gap_2()
+                    .justify_between()
+                    .items_center()
+                    .border_b_1()
+                    .border_color(theme.border)
+                    .child(
+                        v_flex()
+                            .gap_0p5()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(gpui::FontWeight::MEDIUM)
+                                    .child(model.name.clone()),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.muted_foreground)
+                                    .child(details),
+                            ),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("delete-embedding-{}", model.id)))
+                            .label(t("action-delete"))
+                            .small()
+                            .ghost()
+                            .on_click(move |_, _, cx| {
+                                embedding_models_controller::delete_embedding_model(
+                                    model_id.clone(),
+                                    cx,
+                                );
+                            }),
+                    )
+                    .into_any_element()
+            })
+            .collect();
+
+        v_flex()
+            .w_full()
+            .gap_2()
+            .pt_4()
+            .border_t_1()
+            .border_color(theme.border)
+            .child(
+                h_flex()
+                    .justify_between()
+                    .items_center()
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_lg()
+                                    .font_weight(gpui::FontWeight::BOLD)
+                                    .child(t("models-embeddings")),
+                            )
+                            .child(div().text_xs().text_color(theme.muted_foreground).child(
+                                "Embedding models used for semantic memory search. \
+                                         Select the active one in Settings → Memory.",
+                            )),
+                    )
+                    .child(
+                        Button::new("add-embedding-model-btn")
+                            .label("+ Add Embedding Model")
+                            .outline()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                trace!("Add Embedding Model button clicked");
+                                this.show_add_embedding_model_dialog(window, cx);
+                            })),
+                    ),
+            )
+            .child(if rows.is_empty() {
+                div()
+                    .px_3()
+                    .py_2()
+                    .text_sm()
+                    .text_color(theme.muted_foreground)
+                    .child(t("models-embeddings-empty"))
+                    .into_any_element()
+            } else {
+                v_flex().w_full().children(rows).into_any_element()
+            })
+            .into_any_element()
+    }
+
+    pub(super) fn show_add_embedding_model_dialog(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        trace!("Opening Add Embedding Model dialog");
+
+        let providers: Vec<String> = cx
+            .global::<ProviderModel>()
+            .configured_providers()
+            .map(|p| p.provider_type.display_name().to_string())
+            .collect();
+
+        if providers.is_empty() {
+            window.push_notification(
+                "Please configure at least one provider in Settings > Providers before adding embedding models",
+                cx,
+            );
+            return;
+        }
+
+        let name_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("e.g., Nomic Embed (local)"));
+        let model_id_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("e.g., nomic-embed-text"));
+        let dimensions_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder(t("models-embeddings-dimensions-placeholder"))
+        });
+        let batch_size_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder("32");
+            state.set_value("32".to_string(), window, cx);
+            state
+        });
+        let rpm_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder(t("models-embeddings-rate-placeholder"))
+        });
+        let provider_select =
+            cx.new(|cx| SelectState::new(providers, Some(IndexPath::new(0)), window, cx));
+        let muted = cx.theme().muted_foreground;
+
+        window.open_dialog(cx, move |dialog, _window, _cx| {
+            dialog
+                .title("Add Embedding Model")
+                .overlay(true)
+                .keyboard(true)
+                .close_button(true)
+                .overlay_closable(true)
+                .w(px(500.))
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .p_4()
+                        .child(
+                            v_flex()
+                                .gap_1()
+                                .child(div().text_sm().child(t("models-embeddings-name")))
+                                .child(Input::new(&name_input)),
+                        )
+                        .child(
+                            v_flex()
+                                .gap_1()
+                                .child(div().text_sm().child(t("models-provider")))
+                                .child(Select::new(&provider_select))
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(muted)
+                                        .child(t("models-embeddings-gemini-hint")),
+                                ),
+                        )
+                        .child(
+                            v_flex()
+                                .gap_1()
+                                .child(div().text_sm().child(t("models-identifier")))
+                                .child(Input::new(&model_id_input)),
+                        )
+                        .child(
+                            v_flex()
+                                .gap_1()
+                                .child(div().text_sm().child(t("models-embeddings-dimensions")))
+                                .child(Input::new(&dimensions_input)),
+                        )
+                        .child(
+                            v_flex()
+                                .gap_1()
+                                .child(div().text_sm().child(t("models-embeddings-batch-size")))
+                                .child(Input::new(&batch_size_input)),
+                        )
+                        .child(
+                            v_flex()
+                                .gap_1()
+                                .child(div().text_sm().child(t("models-embeddings-rate")))
+                                .child(Input::new(&rpm_input)),
+                        )
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .justify_end()
+                                .pt_4()
+                                .child(Button::new("cancel").label(t("action-cancel")).on_click(
+                                    move |_, window, cx| {
+                                        window.close_dialog(cx);
+                                    },
+                                ))
+                                .child(
+                                    Button::new("save")
+                                        .primary()
+                                        .label(t("action-save"))
+                                        .on_click({
+                                            let name_input = name_input.clone();
+                                            let model_id_input = model_id_input.clone();
+                                            let dimensions_input = dimensions_input.clone();
+                                            let batch_size_input = batch_size_input.clone();
+                                            let rpm_input = rpm_input.clone();
+                                            let provider_select = provider_select.clone();
+
+                                            move |_, window, cx| {
+                                                let name = name_input.read(cx).value();
+                                                let model_identifier =
+                                                    model_id_input.read(cx).value();
+                                                let dimensions_str =
+                                                    dimensions_input.read(cx).value();
+                                                let batch_size_str =
+                                                    batch_size_input.read(cx).value();
+                                                let rpm_str = rpm_input.read(cx).value();
+                                                let provider_index =
+                                                    provider_select.read(cx).selected_index(cx);
+
+                                                if name.trim().is_empty() {
+                                                    window
+                                                        .push_notification("Name is required", cx);
+                                                    return;
+                                                }
+                                                if model_identifier.trim().is_empty() {
+                                                    window.push_notification(
+                                                        "Model identifier is required",
+                                                        cx,
+                                                    );
+                                                    return;
+                                                }
+
+                                                let all_providers: Vec<&str> = cx
+                                                    .global::<ProviderModel>()
+                                                    .configured_providers()
+                                                    .map(|p| p.provider_type.display_name())
+                                                    .collect();
+                                                let provider_str = provider_index
+                                                    .and_then(|idx| {
+                                                        all_providers.get(idx.row).copied()
+                                                    })
+                                                    .unwrap_or("OpenRouter");
+                                                let provider_type =
+                                                    string_to_provider_type(provider_str);
+
+                                                let model_identifier =
+                                                    model_identifier.trim().to_string();
+                                                let mut config = EmbeddingModelConfig::new(
+                                                    uuid::Uuid::new_v4().to_string(),
+                                                    name.trim().to_string(),
+                                                    provider_type,
+                                                    model_identifier.clone(),
+                                                );
+                                                config.dimensions = dimensions_str
+                                                    .trim()
+                                                    .parse::<usize>()
+                                                    .ok()
+                                                    .filter(|&d| d > 0)
+                                                    .or_else(|| {
+                                                        known_dimensions(&model_identifier)
+                                                    });
+                                                if let Some(batch_size) = batch_size_str
+                                                    .trim()
+                                                    .parse::<usize>()
+                                                    .ok()
+                                                    .filter(|&b| b > 0)
+                                                {
+                                                    config.batch_size = batch_size;
+                                                }
+                                                config.requests_per_minute = rpm_str
+                                                    .trim()
+                                                    .parse::<u32>()
+                                                    .ok()
+                                                    .filter(|&r| r > 0);
+
+                                                embedding_models_controller::create_embedding_model(
+                                                    config, cx,
+                                                );
+                                                window.close_dialog(cx);
+                                            }
+                                        }),
+                                ),
+                        ),
+                )
+        });
+    }
+}
@@ -11,6 +11,7 @@
 //!   Azure, Ollama, Mistral, Gemini, OpenRouter, …).
 //! - Capability toggles (image / PDF / temperature) per model.
 //! - OpenRouter catalog search & one-click import.
+//! - The "Embeddings" section for registering embedding models.
 //!
 //! # What does NOT live here
 //!
@@ -19,13 +20,14 @@
 //! - Capability defaults per provider — `ProviderType::default_capabilities`.
 //! - The actual LLM agent construction — `chatty_core::factories::agent_factory`.
 
+use crate::i18n::t;
 use crate::settings::controllers::models_controller;
 use crate::settings::models::models_store::{AZURE_DEFAULT_API_VERSION, ModelConfig, ModelsModel};
 use crate::settings::models::providers_store::{ProviderModel, ProviderType};
 use crate::settings::providers::openrouter::OpenRouterCatalog;
 use gpui::{
-    App, Context, Entity, FocusHandle, Focusable, IntoElement, MouseButton, Render, Styled, Window,
-    div, prelude::*, px,
+    AnyElement, App, Context, Entity, FocusHandle, Focusable, IntoElement, MouseButton, Render,
+    SharedString, Styled, Window, div, prelude::*, px,
 };
 use gpui_component::{
     ActiveTheme, IndexPath, Sizable, WindowExt as _,
@@ -86,6 +88,7 @@ impl Focusable for ModelsListView {
 
 impl Render for ModelsListView {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let embeddings_section = self.render_embeddings_section(cx);
         let theme = cx.theme();
 
         v_flex()
@@ -105,7 +108,7 @@ impl Render for ModelsListView {
                             div()
                                 .text_2xl()
                                 .font_weight(gpui::FontWeight::BOLD)
-                                .child("AI Models"),
+                                .child(t("models-title")),
                         ),
                     )
                     .child(
@@ -125,10 +128,12 @@ impl Render for ModelsListView {
                     .w_full()
                     .child(List::new(&self.list_state).max_h(px(600.)).min_w(px(500.))),
             )
+            .child(embeddings_section)
     }
 }
 
 mod delegate;
 mod dialogs;
+mod embeddings;
 
 use delegate::ModelsListDelegate;
@@ -0,0 +1,483 @@
+use crate::i18n::{t, t_args};
+use crate::settings::controllers::prompt_packs_controller;
+use crate::settings::models::extensions_store::ExtensionsModel;
+use crate::settings::models::models_store::ModelsModel;
+use crate::settings::models::prompt_packs_store::{InstalledPromptPack, PromptPackSource};
+use crate::settings::models::snippets_store::SnippetsModel;
+use crate::settings::models::{PromptPacksModel, PromptPacksState};
+use chatty_core::prompt_packs::{
+    self, PackItemChange, PackItemKind, PackPreviewItem, PromptPackPreview, VerifiedPromptPack,
+};
+use gpui::{
+    App, AsyncApp, Context, Entity, FocusHandle, Focusable, FontWeight, IntoElement,
+    PathPromptOptions, Render, SharedString, Styled, WeakEntity, Window, div, prelude::*, px,
+};
+use gpui_component::setting::{SettingGroup, SettingItem, SettingPage};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    v_flex,
+};
+use tracing::warn;
+
+// ── Global singleton ────────────────────────────────────────────────────────
+
+pub type GlobalPromptPacksView = crate::global_entity::GlobalStrongEntity<PromptPacksView>;
+
+// ── Prompt packs entity ─────────────────────────────────────────────────────
+
+/// Imports signed prompt packs, previews them and lists the installed ones.
+pub struct PromptPacksView {
+    focus_handle: FocusHandle,
+    source_input: Entity<InputState>,
+    loading: bool,
+    preview: Option<PromptPackPreview>,
+    error: Option<String>,
+}
+
+impl PromptPacksView {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            source_input: cx.new(|cx| {
+                InputState::new(window, cx).placeholder(t("prompt-packs-source-placeholder"))
+            }),
+            loading: false,
+            preview: None,
+            error: None,
+        }
+    }
+
+    fn pick_file(&mut self, cx: &mut Context<Self>) {
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some(t("prompt-packs-select").into()),
+        });
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            if let Ok(Ok(Some(paths))) = receiver.await
+                && let Some(path) = paths.into_iter().next()
+            {
+                this.update(cx, |view, cx| {
+                    view.load_preview(PromptPackSource::File { path }, cx);
+                })
+                .ok();
+            }
+        })
+        .detach();
+    }
+
+    /// Read or download the pack and show what installing it would change.
+    fn load_preview(&mut self, source: PromptPackSource, cx: &mut Context<Self>) {
+        self.loading = true;
+        self.preview = None;
+        self.error = None;
+        cx.notify();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let display = source.display();
+            let result = prompt_packs::load(source).await;
+            this.update(cx, |view, cx| {
+                view.loading = false;
+                match result {
+                    Ok(pack) => view.show_preview(pack, cx),
+                    Err(e) => {
+                        warn!(error = ?e, source = %display, "Failed to load prompt pack");
+                        view.error = Some(e.to_string());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn show_preview(&mut self, pack: VerifiedPromptPack, cx: &mut Context<Self>) {
+        self.error = None;
+        self.preview = Some(prompt_packs::preview(
+            pack,
+            cx.global::<PromptPacksModel>(),
+            cx.global::<SnippetsModel>(),
+            cx.global::<ModelsModel>(),
+            cx.global::<ExtensionsModel>(),
+        ));
+        cx.notify();
+    }
+
+    fn install(&mut self, cx: &mut Context<Self>) {
+        let Some(preview) = self.preview.take() else {
+            return;
+        };
+        if let Err(e) = prompt_packs_controller::install_pack(&preview.pack, cx) {
+            warn!(error = %e, pack = %preview.pack.manifest.id, "Failed to install prompt pack");
+            self.error = Some(e);
+            self.preview = Some(preview);
+        }
+        cx.notify();
+    }
+
+    fn render_import(&self, cx: &Context<Self>) -> impl IntoElement {
+        let for_browse = cx.entity().clone();
+        let for_preview = cx.entity().clone();
+        let source_input = self.source_input.clone();
+
+        h_flex()
+            .w_full()
+            .gap_2()
+            .child(div().flex_1().child(Input::new(&self.source_input)))
+            .child(
+                Button::new("browse-prompt-pack")
+                    .label(t("prompt-packs-browse"))
+                    .disabled(self.loading)
+                    .on_click(move |_, _, cx| {
+                        for_browse.update(cx, |view, cx| view.pick_file(cx));
+                    }),
+            )
+            .child(
+                Button::new("preview-prompt-pack")
+                    .label(t("action-preview"))
+                    .primary()
+                    .loading(self.loading)
+                    .on_click(move |_, _, cx| {
+                        let input = source_input.read(cx).value().trim().to_string();
+                        if input.is_empty() {
+                            return;
+                        }
+                        for_preview.update(cx, |view, cx| {
+                            view.load_preview(PromptPackSource::parse(&input), cx);
+                        });
+                    }),
+            )
+    }
+
+    fn render_preview(&self, preview: &PromptPackPreview, cx: &Context<Self>) -> impl IntoElement {
+        let manifest = &preview.pack.manifest;
+        let for_install = cx.entity().clone();
+        let for_cancel = cx.entity().clone();
+        let version = match &preview.installed_version {
+            Some(installed) => format!("v{installed} → v{}", manifest.version),
+            None => format!("v{}", manifest.version),
+        };
+        let install_label = if preview.installed_version.is_some() {
+            t("prompt-packs-update")
+        } else {
+            t("action-install")
+        };
+
+        v_flex()
+            .w_full()
+            .gap_2()
+            .p_3()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(manifest.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(version),
+                    ),
+            )
+            .when(!manifest.description.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(manifest.description.clone()),
+                )
+            })
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(t_args(
+                        "prompt-packs-provenance",
+                        &[
+                            (
+                                "author",
+                                manifest
+                                    .author
+                                    .clone()
+                                    .unwrap_or_else(|| t("prompt-packs-unknown-author"))
+                                    .as_str(),
+                            ),
+                            ("key", preview.pack.fingerprint().as_str()),
+                            ("source", preview.pack.source.display().to_string().as_str()),
+                        ],
+                    )),
+            )
+            .when(preview.publisher_changed, |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(t("prompt-packs-publisher-changed")),
+                )
+            })
+            .child(
+                v_flex()
+                    .w_full()
+                    .rounded_md()
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .overflow_hidden()
+                    .children(
+                        preview
+                            .items
+                            .iter()
+                            .map(|item| self.render_item(item, cx).into_any_element()),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("cancel-prompt-pack")
+                            .label(t("action-cancel"))
+                            .on_click(move |_, _, cx| {
+                                for_cancel.update(cx, |view, cx| {
+                                    view.preview = None;
+                                    cx.notify();
+                                });
+                            }),
+                    )
+                    .child(
+                        Button::new("install-prompt-pack")
+                            .label(install_label)
+                            .primary()
+                            .disabled(preview.publisher_changed)
+                            .on_click(move |_, _, cx| {
+                                for_install.update(cx, |view, cx| view.install(cx));
+                            }),
+                    ),
+            )
+    }
+
+    fn render_item(&self, item: &PackPreviewItem, cx: &Context<Self>) -> impl IntoElement {
+        let kind = match item.kind {
+            PackItemKind::Template => t("prompt-packs-kind-template"),
+            PackItemKind::Profile => t("prompt-packs-kind-profile"),
+            PackItemKind::Tool => t("prompt-packs-kind-tool"),
+        };
+        let (change, color) = match item.change {
+            PackItemChange::Added => (t("prompt-packs-change-new"), cx.theme().success),
+            PackItemChange::Updated => (t("prompt-packs-change-updated"), cx.theme().info),
+            PackItemChange::Unchanged => (
+                t("prompt-packs-change-unchanged"),
+                cx.theme().muted_foreground,
+            ),
+            PackItemChange::Conflict => (t("prompt-packs-change-conflict"), cx.theme().warning),
+            PackItemChange::NeedsSetup => {
+                (t("prompt-packs-change-needs-setup"), cx.theme().warning)
+            }
+            PackItemChange::Removed => (t("prompt-packs-change-removed"), cx.theme().danger),
+        };
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                div()
+                    .w(px(64.))
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(kind),
+            )
+            .child(div().w(px(160.)).text_sm().child(item.name.clone()))
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .text_ellipsis()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(item.detail.clone()),
+            )
+            .child(div().w(px(88.)).text_xs().text_color(color).child(change))
+    }
+
+    fn render_installed(
+        &self,
+        row_ix: usize,
+        pack: &InstalledPromptPack,
+        update: Option<&VerifiedPromptPack>,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let id_for_remove = pack.manifest.id.clone();
+        let view_for_remove = cx.entity().clone();
+        let summary = t_args(
+            "prompt-packs-installed-summary",
+            &[
+                ("version", pack.manifest.version.as_str()),
+                (
+                    "templates",
+                    pack.manifest.templates.len().to_string().as_str(),
+                ),
+                (
+                    "profiles",
+                    pack.manifest.profiles.len().to_string().as_str(),
+                ),
+                ("tools", pack.manifest.tools.len().to_string().as_str()),
+                (
+                    "key",
+                    prompt_packs::fingerprint(&pack.publisher_key).as_str(),
+                ),
+            ],
+        );
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .child(div().text_sm().child(pack.manifest.name.clone()))
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(summary),
+                    ),
+            )
+            .when_some(update.cloned(), |this, update| {
+                let view_for_update = cx.entity().clone();
+                this.child(
+                    Button::new(SharedString::from(format!("update-pack-{}", row_ix)))
+                        .label(t_args(
+                            "prompt-packs-update-to",
+                            &[("version", update.manifest.version.as_str())],
+                        ))
+                        .primary()
+                        .xsmall()
+                        .on_click(move |_, _, cx| {
+                            let update = update.clone();
+                            view_for_update.update(cx, |view, cx| view.show_preview(update, cx));
+                        }),
+                )
+            })
+            .child(
+                Button::new(SharedString::from(format!("remove-pack-{}", row_ix)))
+                    .label(t("action-remove"))
+                    .ghost()
+                    .xsmall()
+                    .on_click(move |_, _, cx| {
+                        prompt_packs_controller::remove_pack(&id_for_remove, cx);
+                        view_for_remove.update(cx, |_, cx| cx.notify());
+                    }),
+            )
+    }
+}
+
+impl Focusable for PromptPacksView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for PromptPacksView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let packs = cx.global::<PromptPacksModel>().packs.clone();
+        let state = cx.global::<PromptPacksState>().clone();
+
+        let installed = v_flex()
+            .w_full()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .overflow_hidden()
+            .map(|this| {
+                if packs.is_empty() {
+                    this.child(
+                        h_flex()
+                            .w_full()
+                            .justify_center()
+                            .py_6()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(t("prompt-packs-empty")),
+                    )
+                } else {
+                    this.children(packs.iter().enumerate().map(|(ix, pack)| {
+                        self.render_installed(ix, pack, state.update_for(&pack.manifest.id), cx)
+                            .into_any_element()
+                    }))
+                }
+            });
+
+        v_flex()
+            .size_full()
+            .gap_3()
+            .child(self.render_import(cx))
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+            })
+            .when_some(self.preview.as_ref(), |this, preview| {
+                this.child(self.render_preview(preview, cx))
+            })
+            .child(installed)
+            .child(
+                h_flex().child(
+                    Button::new("check-pack-updates")
+                        .label(t("action-check-updates"))
+                        .loading(state.checking)
+                        .disabled(packs.is_empty())
+                        .on_click(|_, _, cx| {
+                            prompt_packs_controller::check_for_updates(cx);
+                        }),
+                ),
+            )
+    }
+}
+
+// ── Setting page entry point ────────────────────────────────────────────────
+
+pub fn prompt_packs_page() -> SettingPage {
+    SettingPage::new(t("settings-page-prompt-packs"))
+        .description(t("prompt-packs-description"))
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title(t("settings-page-prompt-packs"))
+                .description(t("prompt-packs-import-description"))
+                .items(vec![SettingItem::render(|_options, window, cx| {
+                    let view = match cx
+                        .try_global::<GlobalPromptPacksView>()
+                        .and_then(|g| g.get())
+                    {
+                        Some(view) => view,
+                        None => {
+                            let new_view = cx.new(|cx| PromptPacksView::new(window, cx));
+                            cx.set_global(GlobalPromptPacksView::new(new_view.clone()));
+                            new_view
+                        }
+                    };
+
+                    div().w_full().child(view)
+                })]),
+        ])
+}
@@ -1,21 +1,28 @@
+use crate::i18n::t;
 use crate::settings::controllers::providers_controller;
-use crate::settings::models::providers_store::{AzureAuthMethod, ProviderModel, ProviderType};
+use crate::settings::models::providers_store::{
+    AzureAuthMethod, ProviderModel, ProviderPrivacy, ProviderTimeouts, ProviderType,
+};
 use gpui::{
     App, AppContext as _, Axis, Entity, SharedString, Styled, Window, prelude::FluentBuilder as _,
 };
 use gpui_component::{
     AxisExt as _, Sizable,
     input::{Input, InputEvent, InputState},
-    setting::{RenderOptions, SettingField, SettingGroup, SettingItem, SettingPage},
+    setting::{
+        NumberFieldOptions, RenderOptions, SettingField, SettingGroup, SettingItem, SettingPage,
+    },
 };
 use std::rc::Rc;
 
 pub fn providers_page() -> SettingPage {
-    SettingPage::new("Providers").resettable(true).groups(vec![
-        create_openrouter_group(),
-        create_ollama_group(),
-        create_azure_openai_group(),
-    ])
+    SettingPage::new(t("settings-page-providers"))
+        .resettable(true)
+        .groups(vec![
+            create_openrouter_group(),
+            create_ollama_group(),
+            create_azure_openai_group(),
+        ])
 }
 
 fn create_openrouter_group() -> SettingGroup {
@@ -24,6 +31,28 @@ fn create_openrouter_group() -> SettingGroup {
         "Configure OpenRouter API access - gateway to 200+ models (Claude, Gemini, GPT, Mistral, Llama, and more)",
         ProviderType::OpenRouter,
         "Enter your OpenRouter API key (starts with sk-or-) to access all supported models",
+        vec![
+            SettingItem::new(
+                "Deny Data Collection",
+                privacy_switch(ProviderType::OpenRouter, |privacy| {
+                    &mut privacy.disable_storage
+                }),
+            )
+            .description(
+                "Only route to upstream providers (OpenAI, Anthropic, …) that do not store \
+                 or train on your prompts",
+            ),
+            SettingItem::new(
+                "Zero Data Retention Only",
+                privacy_switch(ProviderType::OpenRouter, |privacy| {
+                    &mut privacy.zero_data_retention
+                }),
+            )
+            .description("Only route to endpoints that retain no request data at all"),
+            key_expiry_item(ProviderType::OpenRouter),
+            connect_timeout_item(ProviderType::OpenRouter),
+            read_timeout_item(ProviderType::OpenRouter),
+        ],
     )
 }
 
@@ -51,6 +80,8 @@ fn create_ollama_group() -> SettingGroup {
             )
             .description("Ollama server URL (default: http://localhost:11434)")
             .layout(Axis::Vertical),
+            connect_timeout_item(ProviderType::Ollama),
+            read_timeout_item(ProviderType::Ollama),
         ])
 }
 
@@ -100,6 +131,7 @@ fn create_azure_openai_group() -> SettingGroup {
             )
             .description("Azure API key (not needed if using Entra ID)")
             .layout(Axis::Vertical),
+            key_expiry_item(ProviderType::AzureOpenAI),
             SettingItem::new(
                 "Endpoint URL",
                 SettingField::input(
@@ -112,9 +144,135 @@ fn create_azure_openai_group() -> SettingGroup {
             )
             .description("Azure resource URL (e.g., https://my-resource.openai.azure.com)")
             .layout(Axis::Vertical),
+            SettingItem::new(
+                "Disable Stored Completions",
+                privacy_switch(ProviderType::AzureOpenAI, |privacy| {
+                    &mut privacy.disable_storage
+                }),
+            )
+            .description(
+                "Send `store: false` so completions are not kept for distillation or evals",
+            ),
+            connect_timeout_item(ProviderType::AzureOpenAI),
+            read_timeout_item(ProviderType::AzureOpenAI),
         ])
 }
 
+/// Switch for one privacy opt-out of `provider_type`, selected by `flag`.
+fn privacy_switch(
+    provider_type: ProviderType,
+    flag: fn(&mut ProviderPrivacy) -> &mut bool,
+) -> SettingField<bool> {
+    let provider_type_for_set = provider_type.clone();
+    SettingField::switch(
+        move |cx: &App| *flag(&mut provider_privacy(cx, &provider_type)),
+        move |enabled: bool, cx: &mut App| {
+            let mut privacy = provider_privacy(cx, &provider_type_for_set);
+            *flag(&mut privacy) = enabled;
+            providers_controller::update_provider_privacy(
+                cx,
+                provider_type_for_set.clone(),
+                privacy,
+            );
+        },
+    )
+    .default_value(false)
+}
+
+/// Optional expiry date of a provider's key, used for the health warning.
+fn key_expiry_item(provider_type: ProviderType) -> SettingItem {
+    let provider_type_for_set = provider_type.clone();
+    SettingItem::new(
+        "Key Expires On",
+        SettingField::input(
+            move |cx: &App| {
+                cx.global::<ProviderModel>()
+                    .providers()
+                    .iter()
+                    .find(|p| p.provider_type == provider_type)
+                    .and_then(|p| p.extra_config.get("key_expires_on").cloned())
+                    .unwrap_or_default()
+                    .into()
+            },
+            move |val: SharedString, cx: &mut App| {
+                providers_controller::update_key_expiry(
+                    cx,
+                    provider_type_for_set.clone(),
+                    val.to_string(),
+                );
+            },
+        ),
+    )
+    .description("Optional. Date the key stops working (YYYY-MM-DD); Chatty warns a week ahead")
+    .layout(Axis::Vertical)
+}
+
+/// Seconds allowed to connect to the provider.
+fn connect_timeout_item(provider_type: ProviderType) -> SettingItem {
+    SettingItem::new(
+        "Connect Timeout (seconds)",
+        timeout_field(provider_type, |timeouts| &mut timeouts.connect_secs),
+    )
+    .description("How long to wait for a connection to the provider (0 = no limit)")
+}
+
+/// Seconds a response may go quiet before the turn fails as stalled.
+fn read_timeout_item(provider_type: ProviderType) -> SettingItem {
+    SettingItem::new(
+        "Read Timeout (seconds)",
+        timeout_field(provider_type, |timeouts| &mut timeouts.read_secs),
+    )
+    .description(
+        "How long a response may send nothing before it is stopped as stalled and can be \
+         retried; time spent running tools or waiting for approval doesn't count (0 = no limit)",
+    )
+}
+
+/// Number input for one timeout of `provider_type`, selected by `secs`.
+fn timeout_field(
+    provider_type: ProviderType,
+    secs: fn(&mut ProviderTimeouts) -> &mut u64,
+) -> SettingField<f64> {
+    let default = *secs(&mut provider_type.default_timeouts()) as f64;
+    let provider_type_for_set = provider_type.clone();
+    SettingField::number_input(
+        NumberFieldOptions {
+            min: 0.0,
+            max: 3600.0,
+            ..Default::default()
+        },
+        move |cx: &App| *secs(&mut provider_timeouts(cx, &provider_type)) as f64,
+        move |val: f64, cx: &mut App| {
+            let mut timeouts = provider_timeouts(cx, &provider_type_for_set);
+            *secs(&mut timeouts) = val.clamp(0.0, 3600.0) as u64;
+            providers_controller::update_provider_timeouts(
+                cx,
+                provider_type_for_set.clone(),
+                timeouts,
+            );
+        },
+    )
+    .default_value(default)
+}
+
+fn provider_timeouts(cx: &App, provider_type: &ProviderType) -> ProviderTimeouts {
+    cx.global::<ProviderModel>()
+        .providers()
+        .iter()
+        .find(|p| &p.provider_type == provider_type)
+        .map(|p| p.timeouts())
+        .unwrap_or_else(|| provider_type.default_timeouts())
+}
+
+fn provider_privacy(cx: &App, provider_type: &ProviderType) -> ProviderPrivacy {
+    cx.global::<ProviderModel>()
+        .providers()
+        .iter()
+        .find(|p| &p.provider_type == provider_type)
+        .map(|p| p.privacy())
+        .unwrap_or_default()
+}
+
 fn azure_api_key(cx: &App) -> String {
     cx.global::<ProviderModel>()
         .providers()
@@ -196,42 +354,47 @@ where
     )
 }
 
-/// Generic helper to create a provider group with a masked API key field.
+/// Generic helper to create a provider group with a masked API key field,
+/// followed by `extra_items`.
 fn create_provider_group(
     title: &'static str,
     description: &'static str,
     provider_type: ProviderType,
     api_key_description: &'static str,
+    extra_items: Vec<SettingItem>,
 ) -> SettingGroup {
     let provider_type_for_api = provider_type.clone();
     let provider_type_for_api_set = provider_type;
 
+    let mut items = vec![
+        SettingItem::new(
+            "API Key",
+            masked_api_key_field(
+                move |cx: &App| {
+                    cx.global::<ProviderModel>()
+                        .providers()
+                        .iter()
+                        .find(|p| p.provider_type == provider_type_for_api)
+                        .and_then(|p| p.api_key.clone())
+                        .unwrap_or_default()
+                        .into()
+                },
+                move |val: SharedString, cx: &mut App| {
+                    providers_controller::update_or_create_provider(
+                        cx,
+                        provider_type_for_api_set.clone(),
+                        val.to_string(),
+                    );
+                },
+            ),
+        )
+        .description(api_key_description)
+        .layout(Axis::Vertical),
+    ];
+    items.extend(extra_items);
+
     SettingGroup::new()
         .title(title)
         .description(description)
-        .items(vec![
-            SettingItem::new(
-                "API Key",
-                masked_api_key_field(
-                    move |cx: &App| {
-                        cx.global::<ProviderModel>()
-                            .providers()
-                            .iter()
-                            .find(|p| p.provider_type == provider_type_for_api)
-                            .and_then(|p| p.api_key.clone())
-                            .unwrap_or_default()
-                            .into()
-                    },
-                    move |val: SharedString, cx: &mut App| {
-                        providers_controller::update_or_create_provider(
-                            cx,
-                            provider_type_for_api_set.clone(),
-                            val.to_string(),
-                        );
-                    },
-                ),
-            )
-            .description(api_key_description)
-            .layout(Axis::Vertical),
-        ])
+        .items(items)
 }
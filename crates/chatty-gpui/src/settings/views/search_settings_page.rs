@@ -1,5 +1,9 @@
-use crate::settings::controllers::{execution_settings_controller, search_settings_controller};
+use crate::i18n::t;
+use crate::settings::controllers::{
+    execution_settings_controller, managed_config_controller, search_settings_controller,
+};
 use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use crate::settings::models::managed_config::ToolGroup;
 use crate::settings::models::search_settings::{SearchProvider, SearchSettingsModel};
 use crate::settings::views::providers_view::masked_api_key_field;
 use gpui::{App, IntoElement, SharedString, Styled};
@@ -10,7 +14,7 @@ use gpui_component::{
 };
 
 pub fn search_settings_page() -> SettingPage {
-    SettingPage::new("Internet")
+    SettingPage::new(t("settings-page-internet"))
         .description("Configure how the AI accesses the internet")
         .resettable(false)
         .groups(vec![
@@ -26,7 +30,10 @@ pub fn search_settings_page() -> SettingPage {
                     SettingItem::new(
                         "Enable Internet Access",
                         SettingField::switch(
-                            |cx: &App| cx.global::<ExecutionSettingsModel>().fetch_enabled,
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().fetch_enabled
+                                    && !managed_config_controller::is_blocked(ToolGroup::Fetch, cx)
+                            },
                             |_val: bool, cx: &mut App| {
                                 execution_settings_controller::toggle_fetch(cx);
                             },
@@ -1,32 +1,718 @@
+use crate::chatty::views::footer::indicator_registry::indicator_label;
+use crate::i18n::{self, t, t_args};
 use crate::settings::controllers::SettingsView;
 use crate::settings::controllers::general_settings_controller;
-use crate::settings::models::GeneralSettingsModel;
+use crate::settings::models::models_store::ModelsModel;
+use crate::settings::models::providers_store::ProviderType;
+use crate::settings::models::{CopyFormat, FooterIndicator, GeneralSettingsModel, SendShortcut};
+use crate::settings::views::batch_runs_page::batch_runs_page;
+use crate::settings::views::compare_runs_page::compare_runs_page;
+use crate::settings::views::custom_tools_page::custom_tools_page;
+use crate::settings::views::diagnostics_page::diagnostics_page;
 use crate::settings::views::execution_settings_page::execution_settings_page;
 use crate::settings::views::extensions_page::extensions_page;
+use crate::settings::views::guardrails_page::guardrails_page;
+use crate::settings::views::managed_config_page::managed_config_page;
 use crate::settings::views::memory_settings_page::memory_settings_page;
 use crate::settings::views::models_page::{GlobalModelsListView, ModelsListView};
+use crate::settings::views::prompt_packs_page::prompt_packs_page;
 use crate::settings::views::providers_view::providers_page;
 use crate::settings::views::search_settings_page::search_settings_page;
+use crate::settings::views::snippets_page::snippets_page;
+use crate::settings::views::ssh_hosts_page::ssh_hosts_page;
+use crate::settings::views::storage_page::storage_page;
+use crate::settings::views::telemetry_page::telemetry_page;
+use crate::settings::views::token_usage_page::token_usage_page;
+use crate::settings::views::tool_stats_page::tool_stats_page;
 use crate::settings::views::training_settings_page::training_settings_page;
 use crate::settings::views::user_secrets_page::user_secrets_page;
+use chatty_core::models::LayoutDirection;
+use chatty_core::services::spell_checker::available_dictionaries;
+use chatty_core::services::startup_profile;
 
+use gpui::prelude::FluentBuilder;
 use gpui::*;
 
 use gpui_component::{
-    ActiveTheme, Disableable, Root, Sizable, Size, Theme, ThemeMode,
-    button::Button,
+    ActiveTheme, Disableable, IconName, Root, Sizable, Size, Theme, ThemeMode,
+    button::{Button, ButtonVariants},
     group_box::GroupBoxVariant,
     menu::{DropdownMenu, PopupMenuItem},
     setting::{NumberFieldOptions, SettingField, SettingGroup, SettingItem, SettingPage, Settings},
 };
 
+fn speech_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-speech"))
+        .description(t("settings-speech-description"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-speech-record-command"),
+                SettingField::input(
+                    |cx: &App| {
+                        cx.global::<GeneralSettingsModel>()
+                            .speech_record_command
+                            .clone()
+                            .unwrap_or_default()
+                            .into()
+                    },
+                    |val: SharedString, cx: &mut App| {
+                        let command = val.trim();
+                        let command = (!command.is_empty()).then(|| command.to_string());
+                        general_settings_controller::set_speech_record_command(cx, command);
+                    },
+                ),
+            )
+            .description(t("settings-speech-record-command-description")),
+            SettingItem::new(
+                t("settings-speech-transcribe-command"),
+                SettingField::input(
+                    |cx: &App| {
+                        cx.global::<GeneralSettingsModel>()
+                            .speech_transcribe_command
+                            .clone()
+                            .unwrap_or_default()
+                            .into()
+                    },
+                    |val: SharedString, cx: &mut App| {
+                        let command = val.trim();
+                        let command = (!command.is_empty()).then(|| command.to_string());
+                        general_settings_controller::set_speech_transcribe_command(cx, command);
+                    },
+                ),
+            )
+            .description(t("settings-speech-transcribe-command-description")),
+            SettingItem::new(
+                t("settings-speech-tts-command"),
+                SettingField::input(
+                    |cx: &App| {
+                        cx.global::<GeneralSettingsModel>()
+                            .speech_tts_command
+                            .clone()
+                            .unwrap_or_default()
+                            .into()
+                    },
+                    |val: SharedString, cx: &mut App| {
+                        let command = val.trim();
+                        let command = (!command.is_empty()).then(|| command.to_string());
+                        general_settings_controller::set_speech_tts_command(cx, command);
+                    },
+                ),
+            )
+            .description(t("settings-speech-tts-command-description")),
+        ])
+}
+
+fn language_item() -> SettingItem {
+    SettingItem::new(
+        t("settings-language"),
+        SettingField::render(|_options, _window, cx| {
+            let current = cx.global::<GeneralSettingsModel>().language.clone();
+            let system_label = t_args(
+                "settings-language-system",
+                &[("language", i18n::locale_name(i18n::current_locale()))],
+            );
+            let label = match current.as_deref() {
+                Some(code) => i18n::locale_name(code).to_string(),
+                None => system_label.clone(),
+            };
+
+            Button::new("language-dropdown")
+                .label(label)
+                .dropdown_caret(true)
+                .outline()
+                .w_full()
+                .dropdown_menu_with_anchor(Corner::BottomLeft, move |menu, _, _| {
+                    let menu = menu.item(
+                        PopupMenuItem::new(system_label.clone())
+                            .checked(current.is_none())
+                            .on_click(|_, _, cx| {
+                                general_settings_controller::set_language(cx, None);
+                            }),
+                    );
+                    i18n::LOCALES.iter().fold(menu, |menu, (code, name)| {
+                        menu.item(
+                            PopupMenuItem::new(*name)
+                                .checked(current.as_deref() == Some(*code))
+                                .on_click(move |_, _, cx| {
+                                    general_settings_controller::set_language(
+                                        cx,
+                                        Some(code.to_string()),
+                                    );
+                                }),
+                        )
+                    })
+                })
+                .into_any_element()
+        }),
+    )
+    .description(t("settings-language-description"))
+}
+
+fn layout_direction_label(direction: LayoutDirection) -> String {
+    match direction {
+        LayoutDirection::Auto => t("settings-layout-auto"),
+        LayoutDirection::LeftToRight => t("settings-layout-ltr"),
+        LayoutDirection::RightToLeft => t("settings-layout-rtl"),
+    }
+}
+
+fn layout_direction_item() -> SettingItem {
+    SettingItem::new(
+        t("settings-layout-direction"),
+        SettingField::render(|_options, _window, cx| {
+            let current = cx.global::<GeneralSettingsModel>().layout_direction;
+
+            Button::new("layout-direction-dropdown")
+                .label(layout_direction_label(current))
+                .dropdown_caret(true)
+                .outline()
+                .w_full()
+                .dropdown_menu_with_anchor(Corner::BottomLeft, move |menu, _, _| {
+                    LayoutDirection::ALL
+                        .into_iter()
+                        .fold(menu, |menu, direction| {
+                            menu.item(
+                                PopupMenuItem::new(layout_direction_label(direction))
+                                    .checked(current == direction)
+                                    .on_click(move |_, _, cx| {
+                                        general_settings_controller::set_layout_direction(
+                                            cx, direction,
+                                        );
+                                    }),
+                            )
+                        })
+                })
+                .into_any_element()
+        }),
+    )
+    .description(t("settings-layout-direction-description"))
+}
+
+fn model_routing_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-routing"))
+        .description(t("settings-routing-description"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-route-each"),
+                SettingField::switch(
+                    |cx: &App| cx.global::<GeneralSettingsModel>().model_routing_enabled,
+                    |val: bool, cx: &mut App| {
+                        general_settings_controller::set_model_routing_enabled(cx, val);
+                    },
+                )
+                .default_value(false),
+            )
+            .description(t("settings-route-each-description")),
+            SettingItem::new(
+                t("settings-classifier"),
+                SettingField::render(|_options, _window, cx| {
+                    let current = cx.global::<GeneralSettingsModel>().router_model_id.clone();
+                    let local_models: Vec<(String, String)> = cx
+                        .global::<ModelsModel>()
+                        .models_by_provider(&ProviderType::Ollama)
+                        .into_iter()
+                        .map(|m| (m.id.clone(), m.name.clone()))
+                        .collect();
+                    let label = current
+                        .as_ref()
+                        .and_then(|id| local_models.iter().find(|(mid, _)| mid == id))
+                        .map(|(_, name)| name.clone())
+                        .unwrap_or_else(|| t("settings-classifier-heuristic"));
+
+                    Button::new("router-model-dropdown")
+                        .label(label)
+                        .dropdown_caret(true)
+                        .outline()
+                        .w_full()
+                        .dropdown_menu_with_anchor(Corner::BottomLeft, move |menu, _, _| {
+                            let menu = menu.item(
+                                PopupMenuItem::new(t("settings-classifier-heuristic"))
+                                    .checked(current.is_none())
+                                    .on_click(|_, _, cx| {
+                                        general_settings_controller::set_router_model(cx, None);
+                                    }),
+                            );
+                            local_models.iter().fold(menu, |menu, (id, name)| {
+                                let id = id.clone();
+                                menu.item(
+                                    PopupMenuItem::new(name.clone())
+                                        .checked(current.as_deref() == Some(id.as_str()))
+                                        .on_click(move |_, _, cx| {
+                                            general_settings_controller::set_router_model(
+                                                cx,
+                                                Some(id.clone()),
+                                            );
+                                        }),
+                                )
+                            })
+                        })
+                        .into_any_element()
+                }),
+            )
+            .description(t("settings-classifier-description")),
+        ])
+}
+
+fn session_group() -> SettingGroup {
+    SettingGroup::new().title(t("settings-startup")).items(vec![
+        SettingItem::new(
+            t("settings-restore-session"),
+            SettingField::switch(
+                |cx: &App| cx.global::<GeneralSettingsModel>().restore_session,
+                |val: bool, cx: &mut App| {
+                    general_settings_controller::set_restore_session(cx, val);
+                },
+            )
+            .default_value(true),
+        )
+        .description(t("settings-restore-session-description")),
+    ])
+}
+
+fn copy_format_label(format: CopyFormat) -> String {
+    match format {
+        CopyFormat::Markdown => t("settings-copy-format-markdown"),
+        CopyFormat::PlainText => t("settings-copy-format-plain"),
+        CopyFormat::Html => t("settings-copy-format-html"),
+        CopyFormat::Clean => t("settings-copy-format-clean"),
+    }
+}
+
+fn copy_format_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-copy-messages"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-copy-format"),
+                SettingField::render(|_options, _window, cx| {
+                    let current = cx.global::<GeneralSettingsModel>().default_copy_format;
+
+                    Button::new("copy-format-dropdown")
+                        .label(copy_format_label(current))
+                        .dropdown_caret(true)
+                        .outline()
+                        .w_full()
+                        .dropdown_menu_with_anchor(Corner::BottomLeft, move |menu, _, _| {
+                            CopyFormat::ALL.into_iter().fold(menu, |menu, format| {
+                                menu.item(
+                                    PopupMenuItem::new(copy_format_label(format))
+                                        .checked(current == format)
+                                        .on_click(move |_, _, cx| {
+                                            general_settings_controller::set_default_copy_format(
+                                                cx, format,
+                                            );
+                                        }),
+                                )
+                            })
+                        })
+                        .into_any_element()
+                }),
+            )
+            .description(t("settings-copy-format-description")),
+        ])
+}
+
+fn copy_context_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-copy-context"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-include-traces"),
+                SettingField::switch(
+                    |cx: &App| {
+                        cx.global::<GeneralSettingsModel>()
+                            .copy_context_include_traces
+                    },
+                    |val: bool, cx: &mut App| {
+                        general_settings_controller::set_copy_context_include_traces(cx, val);
+                    },
+                )
+                .default_value(false),
+            )
+            .description(t("settings-include-traces-description")),
+        ])
+}
+
+fn print_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-printing"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-print-traces"),
+                SettingField::switch(
+                    |cx: &App| cx.global::<GeneralSettingsModel>().print_include_traces,
+                    |val: bool, cx: &mut App| {
+                        general_settings_controller::set_print_include_traces(cx, val);
+                    },
+                )
+                .default_value(false),
+            )
+            .description(t("settings-print-traces-description")),
+            SettingItem::new(
+                t("settings-print-thumbnails"),
+                SettingField::switch(
+                    |cx: &App| {
+                        cx.global::<GeneralSettingsModel>()
+                            .print_attachment_thumbnails
+                    },
+                    |val: bool, cx: &mut App| {
+                        general_settings_controller::set_print_attachment_thumbnails(cx, val);
+                    },
+                )
+                .default_value(true),
+            )
+            .description(t("settings-print-thumbnails-description")),
+            SettingItem::new(
+                t("settings-print-header"),
+                SettingField::switch(
+                    |cx: &App| cx.global::<GeneralSettingsModel>().print_page_header,
+                    |val: bool, cx: &mut App| {
+                        general_settings_controller::set_print_page_header(cx, val);
+                    },
+                )
+                .default_value(true),
+            )
+            .description(t("settings-print-header-description")),
+        ])
+}
+
+/// One row per footer indicator: move left/right and show or hide it.
+fn footer_indicator_row(
+    indicator: FooterIndicator,
+    index: usize,
+    count: usize,
+    hidden: bool,
+    cx: &App,
+) -> AnyElement {
+    let id = format!("{indicator:?}").to_lowercase();
+
+    div()
+        .flex()
+        .flex_row()
+        .items_center()
+        .gap_1()
+        .child(
+            div()
+                .flex_1()
+                .text_sm()
+                .when(hidden, |this| this.text_color(cx.theme().muted_foreground))
+                .child(indicator_label(indicator)),
+        )
+        .child(
+            Button::new(SharedString::from(format!("footer-up-{id}")))
+                .ghost()
+                .xsmall()
+                .icon(IconName::ChevronUp)
+                .disabled(index == 0)
+                .on_click(move |_, _, cx| {
+                    general_settings_controller::move_footer_indicator(cx, indicator, -1);
+                }),
+        )
+        .child(
+            Button::new(SharedString::from(format!("footer-down-{id}")))
+                .ghost()
+                .xsmall()
+                .icon(IconName::ChevronDown)
+                .disabled(index + 1 == count)
+                .on_click(move |_, _, cx| {
+                    general_settings_controller::move_footer_indicator(cx, indicator, 1);
+                }),
+        )
+        .child(
+            Button::new(SharedString::from(format!("footer-visible-{id}")))
+                .ghost()
+                .xsmall()
+                .icon(if hidden {
+                    IconName::EyeOff
+                } else {
+                    IconName::Eye
+                })
+                .on_click(move |_, _, cx| {
+                    general_settings_controller::set_footer_indicator_hidden(
+                        cx, indicator, !hidden,
+                    );
+                }),
+        )
+        .into_any_element()
+}
+
+fn footer_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-status-footer"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-footer-indicators"),
+                SettingField::render(|_options, _window, cx| {
+                    let layout = &cx.global::<GeneralSettingsModel>().footer_layout;
+                    let ordered = layout.ordered();
+                    let count = ordered.len();
+
+                    div()
+                        .flex()
+                        .flex_col()
+                        .w_full()
+                        .gap_1()
+                        .children(ordered.into_iter().enumerate().map(|(index, indicator)| {
+                            footer_indicator_row(
+                                indicator,
+                                index,
+                                count,
+                                layout.is_hidden(indicator),
+                                cx,
+                            )
+                        }))
+                        .into_any_element()
+                }),
+            )
+            .description(t("settings-footer-indicators-description")),
+        ])
+}
+
+fn topic_split_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-topic-changes"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-suggest-split"),
+                SettingField::switch(
+                    |cx: &App| cx.global::<GeneralSettingsModel>().topic_split_suggestions,
+                    |val: bool, cx: &mut App| {
+                        general_settings_controller::set_topic_split_suggestions(cx, val);
+                    },
+                )
+                .default_value(false),
+            )
+            .description(t("settings-suggest-split-description")),
+        ])
+}
+
+/// Entry of the spell check dictionary menu; `code` None matches the UI language.
+fn dictionary_menu_item(
+    label: String,
+    code: Option<String>,
+    current: &Option<String>,
+) -> PopupMenuItem {
+    PopupMenuItem::new(label)
+        .checked(*current == code)
+        .on_click(move |_, _, cx| {
+            general_settings_controller::set_spell_check_dictionary(cx, code.clone());
+        })
+}
+
+fn spelling_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-spelling"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-spell-check"),
+                SettingField::switch(
+                    |cx: &App| cx.global::<GeneralSettingsModel>().spell_check_enabled,
+                    |val: bool, cx: &mut App| {
+                        general_settings_controller::set_spell_check_enabled(cx, val);
+                    },
+                )
+                .default_value(true),
+            )
+            .description(t("settings-spell-check-description")),
+            SettingItem::new(
+                t("settings-spell-dictionary"),
+                SettingField::render(|_options, _window, cx| {
+                    let settings = cx.global::<GeneralSettingsModel>();
+                    let current = settings.spell_check_dictionary.clone();
+                    let enabled = settings.spell_check_enabled;
+                    let auto_label = t("settings-spell-dictionary-auto");
+
+                    Button::new("spell-dictionary-dropdown")
+                        .label(current.clone().unwrap_or_else(|| auto_label.clone()))
+                        .dropdown_caret(true)
+                        .outline()
+                        .w_full()
+                        .disabled(!enabled)
+                        .dropdown_menu_with_anchor(Corner::BottomLeft, move |menu, _, _| {
+                            let menu = menu
+                                .max_h(px(300.0))
+                                .scrollable(true)
+                                .item(dictionary_menu_item(auto_label.clone(), None, &current));
+                            // Listed when opened so newly installed dictionaries show up
+                            available_dictionaries()
+                                .into_iter()
+                                .fold(menu, |menu, code| {
+                                    menu.item(dictionary_menu_item(
+                                        code.clone(),
+                                        Some(code),
+                                        &current,
+                                    ))
+                                })
+                        })
+                        .into_any_element()
+                }),
+            )
+            .description(t("settings-spell-dictionary-description")),
+        ])
+}
+
+fn send_shortcut_label(shortcut: SendShortcut) -> String {
+    match shortcut {
+        SendShortcut::Enter => t("settings-send-enter"),
+        SendShortcut::CtrlEnter if cfg!(target_os = "macos") => t("settings-send-cmd-enter"),
+        SendShortcut::CtrlEnter => t("settings-send-ctrl-enter"),
+    }
+}
+
+fn composer_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-composer"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-send-shortcut"),
+                SettingField::render(|_options, _window, cx| {
+                    let current = cx.global::<GeneralSettingsModel>().send_shortcut;
+
+                    Button::new("send-shortcut-dropdown")
+                        .label(send_shortcut_label(current))
+                        .dropdown_caret(true)
+                        .outline()
+                        .w_full()
+                        .dropdown_menu_with_anchor(Corner::BottomLeft, move |menu, _, _| {
+                            SendShortcut::ALL.into_iter().fold(menu, |menu, shortcut| {
+                                menu.item(
+                                    PopupMenuItem::new(send_shortcut_label(shortcut))
+                                        .checked(current == shortcut)
+                                        .on_click(move |_, _, cx| {
+                                            general_settings_controller::set_send_shortcut(
+                                                cx, shortcut,
+                                            );
+                                        }),
+                                )
+                            })
+                        })
+                        .into_any_element()
+                }),
+            )
+            .description(t("settings-send-shortcut-description")),
+            SettingItem::new(
+                t("settings-composer-expanded"),
+                SettingField::switch(
+                    |cx: &App| cx.global::<GeneralSettingsModel>().composer_expanded,
+                    |val: bool, cx: &mut App| {
+                        general_settings_controller::set_composer_expanded(cx, val);
+                    },
+                )
+                .default_value(false),
+            )
+            .description(t("settings-composer-expanded-description")),
+            SettingItem::new(
+                t("settings-composer-preview"),
+                SettingField::switch(
+                    |cx: &App| cx.global::<GeneralSettingsModel>().composer_preview,
+                    |val: bool, cx: &mut App| {
+                        general_settings_controller::set_composer_preview(cx, val);
+                    },
+                )
+                .default_value(false),
+            )
+            .description(t("settings-composer-preview-description")),
+        ])
+}
+
+fn draft_tokens_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-message-size"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-draft-token-warning"),
+                SettingField::number_input(
+                    NumberFieldOptions {
+                        min: 0.0,
+                        max: 1_000_000.0,
+                        ..Default::default()
+                    },
+                    |cx: &App| cx.global::<GeneralSettingsModel>().draft_token_warning as f64,
+                    |val: f64, cx: &mut App| {
+                        general_settings_controller::set_draft_token_warning(
+                            cx,
+                            val.clamp(0.0, 1_000_000.0) as usize,
+                        );
+                    },
+                )
+                .default_value(8_000.0),
+            )
+            .description(t("settings-draft-token-warning-description")),
+        ])
+}
+
+fn streaming_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-streaming"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-smooth-streaming"),
+                SettingField::switch(
+                    |cx: &App| cx.global::<GeneralSettingsModel>().smooth_streaming,
+                    |val: bool, cx: &mut App| {
+                        general_settings_controller::set_smooth_streaming(cx, val);
+                    },
+                )
+                .default_value(false),
+            )
+            .description(t("settings-smooth-streaming-description")),
+            SettingItem::new(
+                t("settings-smooth-streaming-rate"),
+                SettingField::number_input(
+                    NumberFieldOptions {
+                        min: 20.0,
+                        max: 5_000.0,
+                        ..Default::default()
+                    },
+                    |cx: &App| cx.global::<GeneralSettingsModel>().smooth_streaming_rate as f64,
+                    |val: f64, cx: &mut App| {
+                        general_settings_controller::set_smooth_streaming_rate(
+                            cx,
+                            val.clamp(20.0, 5_000.0) as u32,
+                        );
+                    },
+                )
+                .default_value(400.0),
+            )
+            .description(t("settings-smooth-streaming-rate-description")),
+        ])
+}
+
+fn video_group() -> SettingGroup {
+    SettingGroup::new()
+        .title(t("settings-video"))
+        .description(t("settings-video-description"))
+        .items(vec![
+            SettingItem::new(
+                t("settings-video-transcribe-command"),
+                SettingField::input(
+                    |cx: &App| {
+                        cx.global::<GeneralSettingsModel>()
+                            .video_transcribe_command
+                            .clone()
+                            .unwrap_or_default()
+                            .into()
+                    },
+                    |val: SharedString, cx: &mut App| {
+                        let command = val.trim();
+                        let command = (!command.is_empty()).then(|| command.to_string());
+                        general_settings_controller::set_video_transcribe_command(cx, command);
+                    },
+                ),
+            )
+            .description(t("settings-video-transcribe-command-description")),
+        ])
+}
+
 #[cfg(not(target_os = "macos"))]
 fn cli_group() -> SettingGroup {
     use crate::cli_installer::{CliInstallState, CliInstallStatus};
 
     SettingGroup::new()
-        .title("CLI Tool")
-        .description("Install the chatty-tui command-line interface so you can use Chatty from your terminal.")
+        .title(t("settings-cli"))
+        .description(t("settings-cli-description"))
         .items(vec![SettingItem::render(|_options, _window, cx| {
             let status = cx
                 .try_global::<CliInstallState>()
@@ -35,14 +721,14 @@ fn cli_group() -> SettingGroup {
 
             match status {
                 CliInstallStatus::Idle => Button::new("install-cli")
-                    .label("Install CLI…")
+                    .label(t("settings-cli-install"))
                     .outline()
                     .on_click(|_event, _window, cx| {
                         crate::cli_installer::install_cli(cx);
                     })
                     .into_any_element(),
                 CliInstallStatus::Installing => Button::new("install-cli")
-                    .label("Installing…")
+                    .label(t("settings-cli-installing"))
                     .outline()
                     .disabled(true)
                     .into_any_element(),
@@ -50,14 +736,10 @@ fn cli_group() -> SettingGroup {
                     .flex()
                     .flex_col()
                     .gap_1()
-                    .child(
-                        div()
-                            .text_color(cx.theme().success)
-                            .child(msg),
-                    )
+                    .child(div().text_color(cx.theme().success).child(msg))
                     .child(
                         Button::new("reinstall-cli")
-                            .label("Reinstall CLI")
+                            .label(t("settings-cli-reinstall"))
                             .outline()
                             .on_click(|_event, _window, cx| {
                                 crate::cli_installer::install_cli(cx);
@@ -71,11 +753,11 @@ fn cli_group() -> SettingGroup {
                     .child(
                         div()
                             .text_color(cx.theme().danger)
-                            .child(format!("Installation failed: {err}")),
+                            .child(t_args("settings-cli-failed", &[("error", err.as_str())])),
                     )
                     .child(
                         Button::new("retry-install-cli")
-                            .label("Retry")
+                            .label(t("settings-cli-retry"))
                             .outline()
                             .on_click(|_event, _window, cx| {
                                 crate::cli_installer::install_cli(cx);
@@ -99,13 +781,15 @@ impl Render for SettingsView {
             .with_group_variant(GroupBoxVariant::Outline)
             .sidebar_width(px(200.0))
             .pages(vec![
-                SettingPage::new("General")
+                SettingPage::new(t("settings-page-general"))
                     .resettable(false)
                     .default_open(true)
                     .groups(vec![
-                        SettingGroup::new().title("Appearance").items(vec![
+                        SettingGroup::new().title(t("settings-appearance")).items(vec![
+                            language_item(),
+                            layout_direction_item(),
                             SettingItem::new(
-                                "Theme",
+                                t("settings-theme"),
                                 SettingField::render(move |_options, _window, cx| {
                                     let theme_opts = theme_options.clone();
                                     let current_theme = cx
@@ -145,9 +829,9 @@ impl Render for SettingsView {
                                         .into_any_element()
                                 }),
                             )
-                            .description("Select a theme family (use Dark Mode toggle for light/dark variant)"),
+                            .description(t("settings-theme-description")),
                             SettingItem::new(
-                                "Dark Mode",
+                                t("settings-dark-mode"),
                                 SettingField::switch(
                                     |cx: &App| cx.theme().mode.is_dark(),
                                     |val: bool, cx: &mut App| {
@@ -173,13 +857,11 @@ impl Render for SettingsView {
                                 )
                                 .default_value(false),
                             )
-                            .description(
-                                "Switch between light and dark variants of the selected theme.",
-                            ),
+                            .description(t("settings-dark-mode-description")),
                         ]),
-                        SettingGroup::new().title("Text Settings").items(vec![
+                        SettingGroup::new().title(t("settings-text")).items(vec![
                             SettingItem::new(
-                                "Font Size",
+                                t("settings-font-size"),
                                 SettingField::number_input(
                                     NumberFieldOptions {
                                         min: 8.0,
@@ -195,18 +877,30 @@ impl Render for SettingsView {
                                 )
                                 .default_value(14.0),
                             )
-                            .description("Adjust the default font size."),
+                            .description(t("settings-font-size-description")),
                         ]),
+                        speech_group(),
+                        composer_group(),
+                        spelling_group(),
+                        draft_tokens_group(),
+                        streaming_group(),
+                        video_group(),
+                        session_group(),
+                        copy_format_group(),
+                        copy_context_group(),
+                        print_group(),
+                        footer_group(),
+                        topic_split_group(),
                         #[cfg(not(target_os = "macos"))]
                         cli_group(),
                     ]),
-                SettingPage::new("Models")
-                    .description("Configure AI models and their parameters")
+                SettingPage::new(t("settings-page-models"))
+                    .description(t("settings-page-models-description"))
                     .resettable(false)
                     .groups(vec![
                         SettingGroup::new()
-                            .title("Models List")
-                            .description("All configured AI models")
+                            .title(t("settings-models-list"))
+                            .description(t("settings-models-list-description"))
                             .items(vec![SettingItem::render(|_options, window, cx| {
                                 // Get or create the global singleton view
                                 let view =
@@ -234,15 +928,32 @@ impl Render for SettingsView {
 
                                 div().w_full().min_h(px(400.)).child(view)
                             })]),
+                        model_routing_group(),
                     ]),
                 providers_page(),
                 extensions_page(),
                 search_settings_page(),
                 execution_settings_page(),
+                guardrails_page(),
+                custom_tools_page(),
+                ssh_hosts_page(),
                 memory_settings_page(),
                 training_settings_page(),
+                batch_runs_page(),
+                compare_runs_page(),
+                snippets_page(),
+                prompt_packs_page(),
                 user_secrets_page(),
-            ]))
+                managed_config_page(),
+                telemetry_page(),
+                tool_stats_page(),
+                token_usage_page(),
+                storage_page(),
+            ]
+            .into_iter()
+            // Hidden unless CHATTY_DIAGNOSTICS is set
+            .chain(startup_profile::diagnostics_enabled().then(diagnostics_page))
+            .collect::<Vec<_>>()))
             .children(dialog_layer)
     }
 }
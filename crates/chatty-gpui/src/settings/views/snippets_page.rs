@@ -0,0 +1,343 @@
+use crate::i18n::t;
+use crate::settings::controllers::snippets_controller;
+use crate::settings::models::snippets_store::{Snippet, SnippetsModel};
+use gpui::{
+    App, Context, FocusHandle, Focusable, FontWeight, IntoElement, Render, SharedString, Styled,
+    Window, div, prelude::*, px,
+};
+use gpui_component::{
+    ActiveTheme, Sizable, WindowExt as _,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    setting::{SettingGroup, SettingItem, SettingPage},
+    v_flex,
+};
+use gpui_component::{Icon, IconName};
+
+// ── Global singleton ────────────────────────────────────────────────────────
+
+pub type GlobalSnippetsTableView = crate::global_entity::GlobalStrongEntity<SnippetsTableView>;
+
+// ── Table view entity ───────────────────────────────────────────────────────
+
+pub struct SnippetsTableView {
+    focus_handle: FocusHandle,
+}
+
+impl SnippetsTableView {
+    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        Self { focus_handle }
+    }
+
+    /// Open the add dialog, or the edit dialog when `existing` is given.
+    fn show_snippet_dialog(
+        &self,
+        existing: Option<Snippet>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let original_name = existing.as_ref().map(|s| s.name.clone());
+        let (name, content) = existing.map(|s| (s.name, s.content)).unwrap_or_default();
+        let name_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder("review-checklist");
+            state.set_value(name, window, cx);
+            state
+        });
+        let content_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx)
+                .placeholder(t("snippets-content-placeholder"))
+                .auto_grow(4, 14);
+            state.set_value(content, window, cx);
+            state
+        });
+        let view_entity = cx.entity().clone();
+        let title = if original_name.is_some() {
+            "Edit Snippet"
+        } else {
+            "Add Snippet"
+        };
+
+        window.open_dialog(cx, move |dialog, _, _| {
+            dialog
+                .title(title)
+                .overlay(true)
+                .keyboard(true)
+                .close_button(true)
+                .overlay_closable(true)
+                .w(px(560.))
+                .child(
+                    div().id("snippet-form").child(
+                        v_flex()
+                            .gap_3()
+                            .p_4()
+                            .child(
+                                v_flex()
+                                    .gap_1()
+                                    .child(div().text_sm().child(t("snippets-name")))
+                                    .child(Input::new(&name_input)),
+                            )
+                            .child(
+                                v_flex()
+                                    .gap_1()
+                                    .child(div().text_sm().child(t("snippets-content")))
+                                    .child(Input::new(&content_input)),
+                            )
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .justify_end()
+                                    .pt_4()
+                                    .child(Button::new("cancel-snippet").label(t("action-cancel")).on_click(
+                                        move |_, window, cx| {
+                                            window.close_dialog(cx);
+                                        },
+                                    ))
+                                    .child(
+                                        Button::new("save-snippet")
+                                            .primary()
+                                            .label(t("action-save"))
+                                            .on_click({
+                                                let name_input = name_input.clone();
+                                                let content_input = content_input.clone();
+                                                let original_name = original_name.clone();
+                                                let view_entity = view_entity.clone();
+                                                move |_, window, cx| {
+                                                    let name = name_input
+                                                        .read(cx)
+                                                        .value()
+                                                        .trim()
+                                                        .to_string();
+                                                    let content =
+                                                        content_input.read(cx).value().to_string();
+
+                                                    if !SnippetsModel::is_valid_name(&name) {
+                                                        window.push_notification(
+                                                            "Snippet name must contain only \
+                                                             letters, digits, '-' and '_'",
+                                                            cx,
+                                                        );
+                                                        return;
+                                                    }
+                                                    if content.trim().is_empty() {
+                                                        window.push_notification(
+                                                            "Snippet content is required",
+                                                            cx,
+                                                        );
+                                                        return;
+                                                    }
+                                                    let renamed = original_name.as_ref().is_none_or(
+                                                        |original| !original.eq_ignore_ascii_case(&name),
+                                                    );
+                                                    if renamed
+                                                        && cx
+                                                            .global::<SnippetsModel>()
+                                                            .find(&name)
+                                                            .is_some()
+                                                    {
+                                                        window.push_notification(
+                                                            format!("A snippet named /{name} already exists"),
+                                                            cx,
+                                                        );
+                                                        return;
+                                                    }
+
+                                                    // Renaming keeps the usage count
+                                                    if let Some(original) = &original_name
+                                                        && !original.eq_ignore_ascii_case(&name)
+                                                    {
+                                                        snippets_controller::rename_snippet(
+                                                            original, &name, cx,
+                                                        );
+                                                    }
+                                                    snippets_controller::save_snippet(
+                                                        name, content, cx,
+                                                    );
+                                                    view_entity.update(cx, |_, cx| cx.notify());
+                                                    window.close_dialog(cx);
+                                                }
+                                            }),
+                                    ),
+                            ),
+                    ),
+                )
+        });
+    }
+
+    /// Render a header row for the snippets table.
+    fn render_header(&self, cx: &Context<Self>) -> impl IntoElement {
+        let header = |label: &'static str| {
+            div()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(cx.theme().muted_foreground)
+                .child(label)
+        };
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().muted)
+            .child(header("Name").w(px(160.)))
+            .child(header("Content").flex_1())
+            .child(header("Uses").w(px(48.)))
+            .child(div().w(px(80.)))
+    }
+
+    /// Render a single snippet row.
+    fn render_row(&self, row_ix: usize, snippet: &Snippet, cx: &Context<Self>) -> impl IntoElement {
+        let snippet_for_edit = snippet.clone();
+        let name_for_delete = snippet.name.clone();
+        let view_for_edit = cx.entity().clone();
+        let view_for_delete = cx.entity().clone();
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                div()
+                    .w(px(160.))
+                    .text_sm()
+                    .text_color(cx.theme().foreground)
+                    .child(format!("/{}", snippet.name)),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .text_ellipsis()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(snippet.preview().to_string()),
+            )
+            .child(
+                div()
+                    .w(px(48.))
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(snippet.use_count.to_string()),
+            )
+            .child(
+                h_flex()
+                    .w(px(80.))
+                    .gap_1()
+                    .justify_end()
+                    .child(
+                        Button::new(SharedString::from(format!("edit-snippet-{}", row_ix)))
+                            .label(t("action-edit"))
+                            .ghost()
+                            .xsmall()
+                            .on_click(move |_, window, cx| {
+                                let snippet = snippet_for_edit.clone();
+                                view_for_edit.update(cx, |view, cx| {
+                                    view.show_snippet_dialog(Some(snippet), window, cx);
+                                });
+                            }),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("del-snippet-{}", row_ix)))
+                            .icon(Icon::new(IconName::Close))
+                            .ghost()
+                            .xsmall()
+                            .on_click(move |_, _, cx| {
+                                snippets_controller::remove_snippet(&name_for_delete, cx);
+                                view_for_delete.update(cx, |_, cx| cx.notify());
+                            }),
+                    ),
+            )
+    }
+
+    /// Render the empty state.
+    fn render_empty(&self, cx: &Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .justify_center()
+            .py_6()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child("No snippets yet. Click \"Add Snippet\" below or type /snippet <name> <text> in the chat.")
+    }
+}
+
+impl Focusable for SnippetsTableView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for SnippetsTableView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entity = cx.entity().clone();
+        let snippets = cx.global::<SnippetsModel>().by_usage();
+
+        let table =
+            v_flex()
+                .w_full()
+                .rounded_md()
+                .border_1()
+                .border_color(cx.theme().border)
+                .overflow_hidden()
+                .child(self.render_header(cx))
+                .map(|this| {
+                    if snippets.is_empty() {
+                        this.child(self.render_empty(cx))
+                    } else {
+                        this.children(snippets.iter().enumerate().map(|(ix, snippet)| {
+                            self.render_row(ix, snippet, cx).into_any_element()
+                        }))
+                    }
+                });
+
+        v_flex().size_full().gap_3().child(table).child(
+            Button::new("add-snippet-btn")
+                .label("+ Add Snippet")
+                .primary()
+                .on_click(move |_, window, cx| {
+                    entity.update(cx, |view, cx| {
+                        view.show_snippet_dialog(None, window, cx);
+                    });
+                }),
+        )
+    }
+}
+
+// ── Setting page entry point ────────────────────────────────────────────────
+
+pub fn snippets_page() -> SettingPage {
+    SettingPage::new(t("settings-page-snippets"))
+        .description("Named blocks of text or code you paste often")
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title("Snippet Library")
+                .description(
+                    "Type / followed by a snippet's name in the chat input to insert it. \
+                     Snippets are listed most used first.",
+                )
+                .items(vec![SettingItem::render(|_options, window, cx| {
+                    let view = match cx
+                        .try_global::<GlobalSnippetsTableView>()
+                        .and_then(|g| g.get())
+                    {
+                        Some(view) => view,
+                        None => {
+                            let new_view = cx.new(|cx| SnippetsTableView::new(window, cx));
+                            cx.set_global(GlobalSnippetsTableView::new(new_view.clone()));
+                            new_view
+                        }
+                    };
+
+                    div().w_full().child(view)
+                })]),
+        ])
+}
@@ -0,0 +1,420 @@
+use crate::i18n::t;
+use crate::settings::controllers::{execution_settings_controller, managed_config_controller};
+use crate::settings::models::execution_settings::ExecutionSettingsModel;
+use crate::settings::models::managed_config::ToolGroup;
+use chatty_core::services::ssh_service::SshHostProfile;
+use gpui::{
+    App, Context, Entity, FocusHandle, Focusable, FontWeight, IntoElement, Render, SharedString,
+    Styled, Window, div, prelude::*, px,
+};
+use gpui_component::{
+    ActiveTheme, Sizable, WindowExt as _,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    setting::{SettingField, SettingGroup, SettingItem, SettingPage},
+    v_flex,
+};
+use gpui_component::{Icon, IconName};
+
+// ── Global singleton ────────────────────────────────────────────────────────
+
+pub type GlobalSshHostsView = crate::global_entity::GlobalStrongEntity<SshHostsView>;
+
+/// Create a single-line dialog input prefilled with `text`.
+fn text_input<V: 'static>(
+    text: String,
+    placeholder: &'static str,
+    window: &mut Window,
+    cx: &mut Context<V>,
+) -> Entity<InputState> {
+    cx.new(|cx| {
+        let mut state = InputState::new(window, cx).placeholder(placeholder);
+        state.set_value(text, window, cx);
+        state
+    })
+}
+
+// ── Host list entity ────────────────────────────────────────────────────────
+
+pub struct SshHostsView {
+    focus_handle: FocusHandle,
+}
+
+impl SshHostsView {
+    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        Self { focus_handle }
+    }
+
+    /// Open the add dialog, or the edit dialog when `existing` is set.
+    fn show_host_dialog(
+        &self,
+        existing: Option<SshHostProfile>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let name_input = text_input(
+            existing
+                .as_ref()
+                .map(|h| h.name.clone())
+                .unwrap_or_default(),
+            "staging-web",
+            window,
+            cx,
+        );
+        let hostname_input = text_input(
+            existing
+                .as_ref()
+                .map(|h| h.hostname.clone())
+                .unwrap_or_default(),
+            "web1.staging.example.com",
+            window,
+            cx,
+        );
+        let port_input = text_input(
+            existing
+                .as_ref()
+                .map(|h| h.port.to_string())
+                .unwrap_or_else(|| "22".to_string()),
+            "22",
+            window,
+            cx,
+        );
+        let user_input = text_input(
+            existing
+                .as_ref()
+                .map(|h| h.user.clone())
+                .unwrap_or_default(),
+            "deploy",
+            window,
+            cx,
+        );
+        let identity_input = text_input(
+            existing
+                .as_ref()
+                .and_then(|h| h.identity_file.clone())
+                .unwrap_or_default(),
+            "~/.ssh/id_ed25519 (empty = ssh-agent)",
+            window,
+            cx,
+        );
+        let view_entity = cx.entity().clone();
+        let title = if existing.is_some() {
+            "Edit SSH Host"
+        } else {
+            "Add SSH Host"
+        };
+
+        let labeled = |label: &'static str, child: gpui::AnyElement| {
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child(label))
+                .child(child)
+        };
+
+        window.open_dialog(cx, move |dialog, _, _| {
+            dialog
+                .title(title)
+                .overlay(true)
+                .keyboard(true)
+                .close_button(true)
+                .overlay_closable(true)
+                .w(px(520.))
+                .child(
+                    div().id("ssh-host-form").child(
+                        v_flex()
+                            .gap_3()
+                            .p_4()
+                            .child(labeled("Name", Input::new(&name_input).into_any_element()))
+                            .child(labeled(
+                                "Hostname",
+                                Input::new(&hostname_input).into_any_element(),
+                            ))
+                            .child(labeled("Port", Input::new(&port_input).into_any_element()))
+                            .child(labeled("User", Input::new(&user_input).into_any_element()))
+                            .child(labeled(
+                                "Private key path (leave empty to use ssh-agent)",
+                                Input::new(&identity_input).into_any_element(),
+                            ))
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .justify_end()
+                                    .pt_4()
+                                    .child(
+                                        Button::new("cancel-ssh-host")
+                                            .label(t("action-cancel"))
+                                            .on_click(move |_, window, cx| {
+                                                window.close_dialog(cx);
+                                            }),
+                                    )
+                                    .child(
+                                        Button::new("save-ssh-host")
+                                            .primary()
+                                            .label(t("action-save"))
+                                            .on_click({
+                                                let existing = existing.clone();
+                                                let name_input = name_input.clone();
+                                                let hostname_input = hostname_input.clone();
+                                                let port_input = port_input.clone();
+                                                let user_input = user_input.clone();
+                                                let identity_input = identity_input.clone();
+                                                let view_entity = view_entity.clone();
+                                                move |_, window, cx| {
+                                                    let read =
+                                                        |input: &Entity<InputState>, cx: &App| {
+                                                            input
+                                                                .read(cx)
+                                                                .value()
+                                                                .trim()
+                                                                .to_string()
+                                                        };
+                                                    let mut host =
+                                                        existing.clone().unwrap_or_else(|| {
+                                                            SshHostProfile::new("", "", "")
+                                                        });
+                                                    host.name = read(&name_input, cx);
+                                                    host.hostname = read(&hostname_input, cx);
+                                                    host.user = read(&user_input, cx);
+                                                    let identity = read(&identity_input, cx);
+                                                    host.identity_file =
+                                                        (!identity.is_empty()).then_some(identity);
+
+                                                    let result = read(&port_input, cx)
+                                                        .parse::<u16>()
+                                                        .map_err(|_| {
+                                                            "Port must be between 1 and 65535"
+                                                                .to_string()
+                                                        })
+                                                        .and_then(|port| {
+                                                            host.port = port;
+                                                            host.validate()
+                                                        });
+                                                    if let Err(message) = result {
+                                                        window.push_notification(message, cx);
+                                                        return;
+                                                    }
+
+                                                    let duplicate = cx
+                                                        .global::<ExecutionSettingsModel>()
+                                                        .ssh_hosts
+                                                        .iter()
+                                                        .any(|h| {
+                                                            h.name == host.name && h.id != host.id
+                                                        });
+                                                    if duplicate {
+                                                        window.push_notification(
+                                                            "A host with this name already exists",
+                                                            cx,
+                                                        );
+                                                        return;
+                                                    }
+
+                                                    execution_settings_controller::upsert_ssh_host(
+                                                        host, cx,
+                                                    );
+                                                    view_entity.update(cx, |_, cx| cx.notify());
+                                                    window.close_dialog(cx);
+                                                }
+                                            }),
+                                    ),
+                            ),
+                    ),
+                )
+        });
+    }
+
+    /// Render a single host row.
+    fn render_row(
+        &self,
+        row_ix: usize,
+        host: &SshHostProfile,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let id_for_toggle = host.id.clone();
+        let id_for_delete = host.id.clone();
+        let host_for_edit = host.clone();
+        let view_for_toggle = cx.entity().clone();
+        let view_for_edit = cx.entity().clone();
+        let view_for_delete = cx.entity().clone();
+        let enabled = host.enabled;
+        let auth = match &host.identity_file {
+            Some(path) => format!("key {}", path),
+            None => "ssh-agent".to_string(),
+        };
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(if enabled {
+                                cx.theme().foreground
+                            } else {
+                                cx.theme().muted_foreground
+                            })
+                            .child(host.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_family("monospace")
+                            .text_color(cx.theme().muted_foreground)
+                            .child(format!("{}:{} · {}", host.target(), host.port, auth)),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new(SharedString::from(format!("ssh-toggle-{}", row_ix)))
+                            .label(if enabled { "On" } else { "Off" })
+                            .when(enabled, |b| b.primary())
+                            .when(!enabled, |b| b.ghost())
+                            .xsmall()
+                            .on_click(move |_, _, cx| {
+                                execution_settings_controller::toggle_ssh_host(&id_for_toggle, cx);
+                                view_for_toggle.update(cx, |_, cx| cx.notify());
+                            }),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("ssh-edit-{}", row_ix)))
+                            .label(t("action-edit"))
+                            .ghost()
+                            .xsmall()
+                            .on_click(move |_, window, cx| {
+                                let host = host_for_edit.clone();
+                                view_for_edit.update(cx, |view, cx| {
+                                    view.show_host_dialog(Some(host), window, cx);
+                                });
+                            }),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("ssh-del-{}", row_ix)))
+                            .icon(Icon::new(IconName::Close))
+                            .ghost()
+                            .xsmall()
+                            .on_click(move |_, _, cx| {
+                                execution_settings_controller::remove_ssh_host(&id_for_delete, cx);
+                                view_for_delete.update(cx, |_, cx| cx.notify());
+                            }),
+                    ),
+            )
+    }
+}
+
+impl Focusable for SshHostsView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for SshHostsView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let hosts = cx.global::<ExecutionSettingsModel>().ssh_hosts.clone();
+        let entity = cx.entity().clone();
+
+        let table = v_flex()
+            .w_full()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .overflow_hidden()
+            .map(|this| {
+                if hosts.is_empty() {
+                    this.child(
+                        h_flex()
+                            .w_full()
+                            .justify_center()
+                            .py_6()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(t("ssh-hosts-empty")),
+                    )
+                } else {
+                    this.children(
+                        hosts
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, host)| self.render_row(ix, host, cx).into_any_element()),
+                    )
+                }
+            });
+
+        v_flex().size_full().gap_3().child(table).child(
+            h_flex().child(
+                Button::new("add-ssh-host")
+                    .label("+ Add SSH Host")
+                    .small()
+                    .on_click(move |_, window, cx| {
+                        entity.update(cx, |view, cx| {
+                            view.show_host_dialog(None, window, cx);
+                        });
+                    }),
+            ),
+        )
+    }
+}
+
+// ── Setting page entry point ────────────────────────────────────────────────
+
+pub fn ssh_hosts_page() -> SettingPage {
+    SettingPage::new(t("settings-page-remote-hosts"))
+        .description(
+            "Machines the agent may run commands on over SSH. Only the hosts listed here are \
+             reachable, and every command asks for approval.",
+        )
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title("SSH")
+                .description(
+                    "Uses the system ssh client with your ~/.ssh/config ignored. Host keys must \
+                     already be in known_hosts; connect once from a terminal to add them.",
+                )
+                .items(vec![
+                    SettingItem::new(
+                        "Enable SSH Tool",
+                        SettingField::switch(
+                            |cx: &App| {
+                                cx.global::<ExecutionSettingsModel>().ssh_enabled
+                                    && !managed_config_controller::is_blocked(ToolGroup::Ssh, cx)
+                            },
+                            |_val: bool, cx: &mut App| {
+                                execution_settings_controller::toggle_ssh(cx);
+                            },
+                        )
+                        .default_value(false),
+                    )
+                    .description("Expose ssh_execute for the enabled hosts below."),
+                    SettingItem::render(|_options, window, cx| {
+                        let view = if let Some(existing) = cx.try_global::<GlobalSshHostsView>() {
+                            if let Some(view) = existing.get() {
+                                view
+                            } else {
+                                let new_view = cx.new(|cx| SshHostsView::new(window, cx));
+                                cx.set_global(GlobalSshHostsView::new(new_view.clone()));
+                                new_view
+                            }
+                        } else {
+                            let new_view = cx.new(|cx| SshHostsView::new(window, cx));
+                            cx.set_global(GlobalSshHostsView::new(new_view.clone()));
+                            new_view
+                        };
+
+                        div().w_full().child(view)
+                    }),
+                ]),
+        ])
+}
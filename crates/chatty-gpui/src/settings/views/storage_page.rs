@@ -0,0 +1,253 @@
+use crate::chatty::controllers::GlobalChattyApp;
+use crate::i18n::{t, t_args};
+use chatty_core::services::attachment_store::{AttachmentStore, StorageUsage, reference_counts};
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Disableable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    setting::{SettingGroup, SettingItem, SettingPage},
+    v_flex,
+};
+use std::time::SystemTime;
+use tracing::warn;
+
+/// Largest stored files listed on the page.
+const LARGEST_SHOWN: usize = 10;
+
+pub type GlobalStorageView = crate::global_entity::GlobalStrongEntity<StorageView>;
+
+/// Usage of the attachment store and removal of files no conversation uses.
+pub struct StorageView {
+    usage: StorageUsage,
+    loading: bool,
+    cleaning: bool,
+    /// Outcome of the last cleanup, or why it failed
+    status: Option<String>,
+}
+
+/// Format a byte count as a human-readable string (e.g. "1.4 MB").
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else if bytes < 1024 * 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+    }
+}
+
+impl StorageView {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let mut view = Self {
+            usage: StorageUsage::default(),
+            loading: false,
+            cleaning: false,
+            status: None,
+        };
+        view.reload(cx);
+        view
+    }
+
+    /// Scan the store and count references from every saved conversation.
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        let (Some(repo), Some(store)) = (
+            cx.try_global::<GlobalChattyApp>()
+                .and_then(|g| g.try_upgrade())
+                .map(|app| app.read(cx).conversation_repo()),
+            AttachmentStore::open_default(),
+        ) else {
+            return;
+        };
+        self.loading = true;
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let usage = match repo.load_all().await {
+                Ok(conversations) => store.usage(&reference_counts(&conversations)).await,
+                Err(e) => Err(e.into()),
+            };
+            let usage = usage.unwrap_or_else(|e| {
+                warn!(error = ?e, "Failed to read attachment storage usage");
+                StorageUsage::default()
+            });
+            this.update(cx, |view, cx| {
+                view.usage = usage;
+                view.loading = false;
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Remove orphaned files. References are counted again right before, and
+    /// nothing is removed if the conversations cannot be loaded.
+    fn remove_orphans(&mut self, cx: &mut Context<Self>) {
+        let (Some(repo), Some(store)) = (
+            cx.try_global::<GlobalChattyApp>()
+                .and_then(|g| g.try_upgrade())
+                .map(|app| app.read(cx).conversation_repo()),
+            AttachmentStore::open_default(),
+        ) else {
+            return;
+        };
+        self.cleaning = true;
+        cx.notify();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let status = match repo.load_all().await {
+                Ok(conversations) => match store
+                    .remove_orphans(&reference_counts(&conversations), SystemTime::now())
+                    .await
+                {
+                    Ok(report) if report.kept_recent > 0 => t_args(
+                        "storage-removed-kept",
+                        &[
+                            ("count", report.removed.to_string().as_str()),
+                            ("size", format_size(report.freed_bytes).as_str()),
+                            ("kept", report.kept_recent.to_string().as_str()),
+                        ],
+                    ),
+                    Ok(report) => t_args(
+                        "storage-removed",
+                        &[
+                            ("count", report.removed.to_string().as_str()),
+                            ("size", format_size(report.freed_bytes).as_str()),
+                        ],
+                    ),
+                    Err(e) => t_args(
+                        "storage-cleanup-failed",
+                        &[("error", e.to_string().as_str())],
+                    ),
+                },
+                Err(e) => t_args(
+                    "storage-cleanup-skipped",
+                    &[("error", e.to_string().as_str())],
+                ),
+            };
+            this.update(cx, |view, cx| {
+                view.cleaning = false;
+                view.status = Some(status);
+                view.reload(cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+}
+
+impl Render for StorageView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let muted = cx.theme().muted_foreground;
+        let usage = &self.usage;
+        let orphans = usage.orphans().count();
+        let summary = if self.loading {
+            t("storage-scanning")
+        } else {
+            t_args(
+                "storage-summary",
+                &[
+                    ("count", usage.blobs.len().to_string().as_str()),
+                    ("size", format_size(usage.total_bytes()).as_str()),
+                    ("saved", format_size(usage.deduplicated_bytes()).as_str()),
+                ],
+            )
+        };
+        let orphan_summary = match orphans {
+            0 => t("storage-no-orphans"),
+            1 => t_args(
+                "storage-orphan",
+                &[("size", format_size(usage.orphaned_bytes()).as_str())],
+            ),
+            n => t_args(
+                "storage-orphans",
+                &[
+                    ("count", n.to_string().as_str()),
+                    ("size", format_size(usage.orphaned_bytes()).as_str()),
+                ],
+            ),
+        };
+
+        let largest = usage.blobs.iter().take(LARGEST_SHOWN).map(|blob| {
+            let name = blob
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let used_by = match blob.references {
+                0 => t("storage-unused"),
+                1 => t("storage-reference"),
+                n => t_args("storage-references", &[("count", n.to_string().as_str())]),
+            };
+            h_flex()
+                .gap_4()
+                .text_sm()
+                .child(div().w(px(220.)).truncate().child(name))
+                .child(div().w(px(80.)).child(format_size(blob.bytes)))
+                .child(div().text_color(muted).child(used_by))
+        });
+
+        v_flex()
+            .gap_2()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().flex_1().text_sm().child(summary))
+                    .child(
+                        Button::new("reload-attachment-storage")
+                            .label(t("action-refresh"))
+                            .ghost()
+                            .disabled(self.loading)
+                            .on_click(cx.listener(|view, _, _, cx| view.reload(cx))),
+                    ),
+            )
+            .children(largest)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_sm()
+                            .text_color(muted)
+                            .child(orphan_summary),
+                    )
+                    .child(
+                        Button::new("remove-orphaned-attachments")
+                            .label(t("storage-remove-unused"))
+                            .danger()
+                            .disabled(self.loading || self.cleaning || orphans == 0)
+                            .on_click(cx.listener(|view, _, _, cx| view.remove_orphans(cx))),
+                    ),
+            )
+            .children(
+                self.status
+                    .clone()
+                    .map(|status| div().text_xs().text_color(muted).child(status)),
+            )
+    }
+}
+
+pub fn storage_page() -> SettingPage {
+    SettingPage::new(t("settings-page-storage"))
+        .description(t("storage-description"))
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title(t("storage-attachments"))
+                .description(t("storage-attachments-description"))
+                .items(vec![SettingItem::render(|_options, _window, cx| {
+                    let view = match cx.try_global::<GlobalStorageView>().and_then(|g| g.get()) {
+                        Some(view) => view,
+                        None => {
+                            let new_view = cx.new(StorageView::new);
+                            cx.set_global(GlobalStorageView::new(new_view.clone()));
+                            new_view
+                        }
+                    };
+                    div().w_full().child(view)
+                })]),
+        ])
+}
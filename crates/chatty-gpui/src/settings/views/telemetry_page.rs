@@ -0,0 +1,130 @@
+use crate::i18n::t;
+use chatty_core::services::telemetry_service::{self, KILL_SWITCH_ENV, TelemetryService};
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Disableable,
+    button::{Button, ButtonVariants},
+    setting::{SettingField, SettingGroup, SettingItem, SettingPage},
+    v_flex,
+};
+use std::path::PathBuf;
+use tracing::warn;
+
+/// Ask for a destination and write the exported report there.
+fn export_report(cx: &mut App) {
+    let Some(service) = telemetry_service::telemetry() else {
+        return;
+    };
+    let json = service.export_json();
+    let home = dirs::home_dir()
+        .unwrap_or_else(|| dirs::document_dir().unwrap_or_else(|| PathBuf::from(".")));
+    let receiver = cx.prompt_for_new_path(&home, Some("chatty-telemetry.json"));
+    cx.spawn(async move |_cx: &mut AsyncApp| match receiver.await {
+        Ok(Ok(Some(path))) => {
+            if let Err(e) = tokio::fs::write(&path, json).await {
+                warn!(error = ?e, path = ?path, "Failed to write telemetry export");
+            }
+        }
+        Ok(Ok(None)) => {} // user cancelled
+        Ok(Err(e)) => warn!(error = ?e, "Save dialog returned error"),
+        Err(e) => warn!(error = ?e, "Failed to receive save dialog result"),
+    })
+    .detach();
+}
+
+pub fn telemetry_page() -> SettingPage {
+    SettingPage::new(t("settings-page-telemetry"))
+        .description(
+            "Optional usage metrics that never leave this machine. Only feature counts and \
+             crashes are recorded — no message content, file paths or tool arguments.",
+        )
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new().title("Collection").items(vec![
+                SettingItem::new(
+                    "Collect Usage Metrics",
+                    SettingField::switch(
+                        |_cx: &App| telemetry_service::telemetry().is_some_and(|t| t.is_enabled()),
+                        |val: bool, cx: &mut App| {
+                            if let Some(service) = telemetry_service::telemetry() {
+                                service.set_enabled(val);
+                            }
+                            cx.refresh_windows();
+                        },
+                    )
+                    .default_value(false),
+                )
+                .description("Turning this off deletes everything collected so far."),
+                SettingItem::render(|_options, _window, cx| {
+                    if !TelemetryService::kill_switch_active() {
+                        return div().into_any_element();
+                    }
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().warning)
+                        .child(SharedString::from(format!(
+                            "{KILL_SWITCH_ENV} is set, so nothing is collected."
+                        )))
+                        .into_any_element()
+                }),
+            ]),
+            SettingGroup::new().title("Collected Data").items(vec![
+                SettingItem::render(|_options, _window, cx| {
+                    let data = telemetry_service::telemetry()
+                        .map(|t| t.snapshot())
+                        .unwrap_or_default();
+                    let mut entries: Vec<String> = data
+                        .feature_counts
+                        .iter()
+                        .map(|(feature, count)| format!("{feature}: {count}"))
+                        .collect();
+                    if data.crash_count > 0 {
+                        entries.push(format!("crashes: {}", data.crash_count));
+                    }
+                    if entries.is_empty() {
+                        return div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(t("telemetry-empty"))
+                            .into_any_element();
+                    }
+                    v_flex()
+                        .gap_1()
+                        .children(
+                            entries
+                                .into_iter()
+                                .map(|entry| div().text_sm().child(SharedString::from(entry))),
+                        )
+                        .into_any_element()
+                }),
+                SettingItem::new(
+                    "Export",
+                    SettingField::render(|_options, _window, _cx| {
+                        Button::new("export-telemetry-btn")
+                            .label(t("telemetry-export"))
+                            .disabled(telemetry_service::telemetry().is_none())
+                            .on_click(|_, _, cx| export_report(cx))
+                            .into_any_element()
+                    }),
+                )
+                .description("Save the counts as a JSON file you can share."),
+                SettingItem::new(
+                    "Clear",
+                    SettingField::render(|_options, _window, _cx| {
+                        Button::new("clear-telemetry-btn")
+                            .label(t("telemetry-clear"))
+                            .danger()
+                            .disabled(telemetry_service::telemetry().is_none())
+                            .on_click(|_, _, cx| {
+                                if let Some(service) = telemetry_service::telemetry() {
+                                    service.clear();
+                                }
+                                cx.refresh_windows();
+                            })
+                            .into_any_element()
+                    }),
+                )
+                .description("Reset all counts without turning collection off."),
+            ]),
+        ])
+}
@@ -0,0 +1,288 @@
+use crate::chatty::controllers::GlobalChattyApp;
+use crate::i18n::{t, t_args};
+use crate::settings::models::models_store::ModelsModel;
+use chatty_core::exporters::usage_csv::{UsageExportRange, usage_csv, usage_rows};
+use chatty_core::models::{ActivityCalendar, HeatmapDay};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Disableable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    setting::{SettingField, SettingGroup, SettingItem, SettingPage},
+    tooltip::Tooltip,
+    v_flex,
+};
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+/// Weeks shown in the activity calendar.
+const CALENDAR_WEEKS: usize = 26;
+
+// ── Activity calendar ───────────────────────────────────────────────────────
+
+pub type GlobalActivityCalendarView =
+    crate::global_entity::GlobalStrongEntity<ActivityCalendarView>;
+
+/// GitHub-style heatmap of messages per day. Clicking a day filters the
+/// sidebar to the conversations active on it.
+pub struct ActivityCalendarView {
+    calendar: ActivityCalendar,
+    weeks: Vec<[Option<HeatmapDay>; 7]>,
+    loading: bool,
+}
+
+impl ActivityCalendarView {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let mut view = Self {
+            calendar: ActivityCalendar::default(),
+            weeks: Vec::new(),
+            loading: false,
+        };
+        view.reload(cx);
+        view
+    }
+
+    /// Count the messages of every conversation again.
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        let Some(repo) = cx
+            .try_global::<GlobalChattyApp>()
+            .and_then(|g| g.try_upgrade())
+            .map(|app| app.read(cx).conversation_repo())
+        else {
+            return;
+        };
+        self.loading = true;
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let calendar = match repo.load_all().await {
+                Ok(conversations) => ActivityCalendar::local(&conversations),
+                Err(e) => {
+                    warn!(error = ?e, "Failed to load conversations for the activity calendar");
+                    ActivityCalendar::default()
+                }
+            };
+            this.update(cx, |view, cx| {
+                view.weeks = calendar.heatmap_to_today(CALENDAR_WEEKS);
+                view.calendar = calendar;
+                view.loading = false;
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Filter the sidebar to the conversations with messages on `day`.
+    fn show_day(&self, day: &HeatmapDay, cx: &mut App) {
+        let Some(ids) = self
+            .calendar
+            .day(day.date)
+            .map(|activity| activity.conversation_ids.clone())
+        else {
+            return;
+        };
+        let Some(app) = cx
+            .try_global::<GlobalChattyApp>()
+            .and_then(|g| g.try_upgrade())
+        else {
+            return;
+        };
+        let sidebar = app.read(cx).sidebar_view.clone();
+        let label = day.label();
+        sidebar.update(cx, |sidebar, cx| {
+            sidebar.set_day_filter(Some((label, ids)), cx)
+        });
+    }
+}
+
+impl Render for ActivityCalendarView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let muted = cx.theme().muted_foreground;
+        let empty = cx.theme().secondary;
+        let filled = cx.theme().success;
+        let total: usize = self
+            .weeks
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|day| day.messages)
+            .sum();
+        let summary = if self.loading {
+            t("token-usage-counting")
+        } else {
+            t_args(
+                "token-usage-calendar-summary",
+                &[
+                    ("count", total.to_string().as_str()),
+                    ("weeks", CALENDAR_WEEKS.to_string().as_str()),
+                ],
+            )
+        };
+
+        let columns = self.weeks.iter().enumerate().map(|(week, days)| {
+            v_flex()
+                .gap(px(2.))
+                .children(days.iter().enumerate().map(|(weekday, day)| {
+                    let cell = div().id(week * 7 + weekday).size(px(11.)).rounded_sm();
+                    let Some(day) = day.clone() else {
+                        return cell;
+                    };
+                    let tooltip = match day.messages {
+                        0 => t_args("token-usage-day-none", &[("day", day.label().as_str())]),
+                        1 => t_args("token-usage-day-one", &[("day", day.label().as_str())]),
+                        n => t_args(
+                            "token-usage-day-many",
+                            &[
+                                ("count", n.to_string().as_str()),
+                                ("day", day.label().as_str()),
+                            ],
+                        ),
+                    };
+                    cell.bg(match day.level {
+                        0 => empty,
+                        level => filled.opacity(f32::from(level) / 4.0),
+                    })
+                    .tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx))
+                    .when(day.messages > 0, |cell| {
+                        cell.cursor_pointer()
+                            .on_click(cx.listener(move |view, _, _, cx| {
+                                view.show_day(&day, cx);
+                            }))
+                    })
+                }))
+        });
+
+        v_flex()
+            .gap_2()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().flex_1().text_sm().text_color(muted).child(summary))
+                    .child(
+                        Button::new("reload-activity-calendar")
+                            .label(t("action-refresh"))
+                            .ghost()
+                            .disabled(self.loading)
+                            .on_click(cx.listener(|view, _, _, cx| view.reload(cx))),
+                    ),
+            )
+            .child(h_flex().gap(px(2.)).children(columns))
+    }
+}
+
+/// Ask for a destination, then write the usage of every conversation in
+/// `range` there as CSV.
+fn export_usage(range: UsageExportRange, cx: &mut App) {
+    let Some(repo) = cx
+        .try_global::<GlobalChattyApp>()
+        .and_then(|g| g.try_upgrade())
+        .map(|app| app.read(cx).conversation_repo())
+    else {
+        return;
+    };
+    let since = range.since_now();
+    let home = dirs::home_dir()
+        .unwrap_or_else(|| dirs::document_dir().unwrap_or_else(|| PathBuf::from(".")));
+    let receiver = cx.prompt_for_new_path(&home, Some(range.file_name()));
+    cx.spawn(async move |cx: &mut AsyncApp| {
+        let path = match receiver.await {
+            Ok(Ok(Some(path))) => path,
+            Ok(Ok(None)) => return, // user cancelled
+            Ok(Err(e)) => {
+                warn!(error = ?e, "Save dialog returned error");
+                return;
+            }
+            Err(e) => {
+                warn!(error = ?e, "Failed to receive save dialog result");
+                return;
+            }
+        };
+        let conversations = match repo.load_all().await {
+            Ok(conversations) => conversations,
+            Err(e) => {
+                warn!(error = ?e, "Failed to load conversations for usage export");
+                return;
+            }
+        };
+        let mut rows: Vec<_> = conversations
+            .iter()
+            .flat_map(|data| usage_rows(data, since))
+            .collect();
+        rows.sort_by_key(|row| row.timestamp);
+
+        // Export the provider's model name rather than the internal config id
+        cx.update(|cx| {
+            let models = cx.global::<ModelsModel>();
+            for row in &mut rows {
+                if let Some(model) = models.get_model(&row.model) {
+                    row.model = model.model_identifier.clone();
+                }
+            }
+        })
+        .ok();
+
+        match tokio::fs::write(&path, usage_csv(&rows)).await {
+            Ok(()) => info!(rows = rows.len(), path = ?path, "Exported token usage"),
+            Err(e) => warn!(error = ?e, path = ?path, "Failed to write usage export"),
+        }
+    })
+    .detach();
+}
+
+fn export_item(id: &'static str, title: String, range: UsageExportRange) -> SettingItem {
+    SettingItem::new(
+        title,
+        SettingField::render(move |_options, _window, _cx| {
+            Button::new(id)
+                .label(t("token-usage-export"))
+                .on_click(move |_, _, cx| export_usage(range, cx))
+                .into_any_element()
+        }),
+    )
+}
+
+pub fn token_usage_page() -> SettingPage {
+    SettingPage::new(t("settings-page-token-usage"))
+        .description(t("token-usage-description"))
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title(t("token-usage-activity"))
+                .description(t("token-usage-activity-description"))
+                .items(vec![SettingItem::render(|_options, _window, cx| {
+                    let view = match cx
+                        .try_global::<GlobalActivityCalendarView>()
+                        .and_then(|g| g.get())
+                    {
+                        Some(view) => view,
+                        None => {
+                            let new_view = cx.new(ActivityCalendarView::new);
+                            cx.set_global(GlobalActivityCalendarView::new(new_view.clone()));
+                            new_view
+                        }
+                    };
+                    div().w_full().child(view)
+                })]),
+            SettingGroup::new()
+                .title(t("token-usage-export-title"))
+                .description(t("token-usage-export-description"))
+                .items(vec![
+                    export_item(
+                        "export-usage-month-btn",
+                        t("token-usage-this-month"),
+                        UsageExportRange::ThisMonth,
+                    ),
+                    export_item(
+                        "export-usage-30-days-btn",
+                        t("token-usage-last-30-days"),
+                        UsageExportRange::LastDays(30),
+                    ),
+                    export_item(
+                        "export-usage-all-btn",
+                        t("token-usage-all-time"),
+                        UsageExportRange::All,
+                    ),
+                ]),
+        ])
+}
@@ -0,0 +1,106 @@
+use crate::i18n::t;
+use chatty_core::services::tool_stats_service;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    ActiveTheme, Disableable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    setting::{SettingField, SettingGroup, SettingItem, SettingPage},
+    v_flex,
+};
+
+fn latency(duration: std::time::Duration) -> String {
+    if duration.as_millis() < 1000 {
+        format!("{} ms", duration.as_millis())
+    } else {
+        format!("{:.1} s", duration.as_secs_f64())
+    }
+}
+
+fn stats_item(cx: &App) -> AnyElement {
+    let tools = tool_stats_service::tool_stats()
+        .map(|service| service.snapshot().by_usage())
+        .unwrap_or_default();
+    let muted = cx.theme().muted_foreground;
+    if tools.is_empty() {
+        return div()
+            .text_sm()
+            .text_color(muted)
+            .child(t("tool-stats-empty"))
+            .into_any_element();
+    }
+
+    let header = h_flex()
+        .gap_4()
+        .text_xs()
+        .text_color(muted)
+        .child(div().w(px(200.)).child(t("tool-stats-tool")))
+        .child(div().w(px(60.)).child(t("tool-stats-calls")))
+        .child(div().w(px(70.)).child(t("tool-stats-success")))
+        .child(div().w(px(80.)).child(t("tool-stats-latency")));
+
+    v_flex()
+        .gap_1()
+        .child(header)
+        .children(tools.into_iter().map(|(name, stats)| {
+            let failing = stats.is_consistently_failing();
+            h_flex()
+                .gap_4()
+                .text_sm()
+                .child(div().w(px(200.)).child(name))
+                .child(div().w(px(60.)).child(stats.calls.to_string()))
+                .child(
+                    div()
+                        .w(px(70.))
+                        .when(failing, |el| el.text_color(cx.theme().danger))
+                        .child(format!("{:.0}%", stats.success_rate() * 100.0)),
+                )
+                .child(
+                    div()
+                        .w(px(80.))
+                        .text_color(muted)
+                        .child(latency(stats.average_latency())),
+                )
+        }))
+        .into_any_element()
+}
+
+pub fn tool_stats_page() -> SettingPage {
+    SettingPage::new(t("settings-page-tool-usage"))
+        .description(
+            "How often each tool is called, how often it succeeds and how long it takes, \
+             across all conversations. Stored only on this machine.",
+        )
+        .resettable(false)
+        .groups(vec![
+            SettingGroup::new()
+                .title("Statistics")
+                .description(
+                    "Tools shown in red keep failing; the tools popover in the footer offers \
+                     to switch them off for the current conversation.",
+                )
+                .items(vec![SettingItem::render(|_options, _window, cx| {
+                    stats_item(cx)
+                })]),
+            SettingGroup::new().title("Reset").items(vec![
+                SettingItem::new(
+                    "Clear",
+                    SettingField::render(|_options, _window, _cx| {
+                        Button::new("clear-tool-stats-btn")
+                            .label(t("tool-stats-clear"))
+                            .danger()
+                            .disabled(tool_stats_service::tool_stats().is_none())
+                            .on_click(|_, _, cx| {
+                                if let Some(service) = tool_stats_service::tool_stats() {
+                                    service.clear();
+                                }
+                                cx.refresh_windows();
+                            })
+                            .into_any_element()
+                    }),
+                )
+                .description("Forget all recorded tool calls."),
+            ]),
+        ])
+}
@@ -1,10 +1,442 @@
+use crate::i18n::t;
 use crate::settings::controllers::training_settings_controller;
+use crate::settings::models::models_store::ModelsModel;
 use crate::settings::models::training_settings::TrainingSettingsModel;
-use gpui::App;
-use gpui_component::setting::{SettingField, SettingGroup, SettingItem, SettingPage};
+use chatty_core::exporters::review::{
+    self, APPROVED_SFT_FILE, DatasetExample, ReviewState, ReviewStatus,
+};
+use gpui::{
+    App, AsyncApp, Context, Corner, FocusHandle, Focusable, FontWeight, IntoElement, Render,
+    SharedString, Styled, WeakEntity, Window, div, prelude::*, px,
+};
+use gpui_component::menu::{DropdownMenu, PopupMenuItem};
+use gpui_component::setting::{
+    NumberFieldOptions, SettingField, SettingGroup, SettingItem, SettingPage,
+};
+use gpui_component::{
+    ActiveTheme, Icon, IconName, Sizable, WindowExt as _,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    v_flex,
+};
+use std::path::PathBuf;
+use tracing::warn;
+
+// ── Global singleton ────────────────────────────────────────────────────────
+
+pub type GlobalDatasetBrowserView = crate::global_entity::GlobalStrongEntity<DatasetBrowserView>;
+
+// ── Dataset browser entity ──────────────────────────────────────────────────
+
+/// Lists exported SFT examples with their review state and lets the reviewer
+/// approve, reject or comment on each one.
+pub struct DatasetBrowserView {
+    focus_handle: FocusHandle,
+    exports_dir: Option<PathBuf>,
+    examples: Vec<DatasetExample>,
+    loading: bool,
+    error: Option<String>,
+}
+
+impl DatasetBrowserView {
+    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut view = Self {
+            focus_handle: cx.focus_handle(),
+            exports_dir: review::default_exports_dir(),
+            examples: Vec::new(),
+            loading: false,
+            error: None,
+        };
+        view.reload(cx);
+        view
+    }
+
+    /// Re-read `sft.jsonl` and the review state from disk.
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        let Some(dir) = self.exports_dir.clone() else {
+            self.error = Some("Cannot determine the exports directory".to_string());
+            return;
+        };
+        self.loading = true;
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = review::list_examples(&dir).await;
+            this.update(cx, |view, cx| view.finish_load(result, cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    fn finish_load(&mut self, result: anyhow::Result<Vec<DatasetExample>>, cx: &mut Context<Self>) {
+        self.loading = false;
+        match result {
+            Ok(examples) => {
+                self.examples = examples;
+                self.error = None;
+            }
+            Err(e) => {
+                warn!(error = ?e, "Failed to load exported examples");
+                self.error = Some(e.to_string());
+            }
+        }
+        cx.notify();
+    }
+
+    /// Record a decision for `id` and refresh the list.
+    fn review(
+        &mut self,
+        id: String,
+        status: ReviewStatus,
+        comment: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(dir) = self.exports_dir.clone() else {
+            return;
+        };
+        let reviewer = cx.global::<TrainingSettingsModel>().reviewer_name.clone();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = async {
+                let mut state = ReviewState::load(&dir).await?;
+                state.set(&id, status, comment, reviewer);
+                state.save(&dir).await?;
+                review::list_examples(&dir).await
+            }
+            .await;
+            this.update(cx, |view, cx| view.finish_load(result, cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    /// Write `sft.approved.jsonl`, applying the minimum quality score, and
+    /// report the result.
+    fn write_approved(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(dir) = self.exports_dir.clone() else {
+            return;
+        };
+        let min_quality = cx.global::<TrainingSettingsModel>().min_quality_score;
+        let window_handle = window.window_handle();
+        cx.spawn(async move |_this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let message = match review::write_approved_dataset(&dir, min_quality).await {
+                Ok(count) => format!("Wrote {count} approved examples to {APPROVED_SFT_FILE}"),
+                Err(e) => {
+                    warn!(error = ?e, "Failed to write approved dataset");
+                    format!("Failed to write approved dataset: {e}")
+                }
+            };
+            window_handle
+                .update(cx, |_, window, cx| window.push_notification(message, cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    fn show_comment_dialog(
+        &self,
+        example: &DatasetExample,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let id = example.id.clone();
+        let status = example
+            .review
+            .as_ref()
+            .map(|r| r.status)
+            .unwrap_or_default();
+        let comment = example
+            .review
+            .as_ref()
+            .and_then(|r| r.comment.clone())
+            .unwrap_or_default();
+        let input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .rows(4)
+                .placeholder(t("training-comment-placeholder"))
+                .default_value(comment)
+        });
+        let view_entity = cx.entity().clone();
+
+        window.open_dialog(cx, move |dialog, _, _| {
+            dialog
+                .title("Review Comment")
+                .overlay(true)
+                .keyboard(true)
+                .close_button(true)
+                .overlay_closable(true)
+                .w(px(480.))
+                .child(
+                    v_flex().gap_3().p_4().child(Input::new(&input)).child(
+                        h_flex()
+                            .gap_2()
+                            .justify_end()
+                            .child(
+                                Button::new("cancel-review-comment")
+                                    .label(t("action-cancel"))
+                                    .on_click(|_, window, cx| window.close_dialog(cx)),
+                            )
+                            .child(
+                                Button::new("save-review-comment")
+                                    .primary()
+                                    .label(t("action-save"))
+                                    .on_click({
+                                        let input = input.clone();
+                                        let view_entity = view_entity.clone();
+                                        let id = id.clone();
+                                        move |_, window, cx| {
+                                            let comment = input.read(cx).value().to_string();
+                                            view_entity.update(cx, |view, cx| {
+                                                view.review(id.clone(), status, Some(comment), cx);
+                                            });
+                                            window.close_dialog(cx);
+                                        }
+                                    }),
+                            ),
+                    ),
+                )
+        });
+    }
+
+    /// Render a single example row.
+    fn render_row(
+        &self,
+        row_ix: usize,
+        example: &DatasetExample,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let status = example
+            .review
+            .as_ref()
+            .map(|r| r.status)
+            .unwrap_or_default();
+        let (status_label, status_color) = match status {
+            ReviewStatus::Pending => ("Pending", cx.theme().muted_foreground),
+            ReviewStatus::Approved => ("Approved", gpui_component::green_500()),
+            ReviewStatus::Rejected => ("Rejected", gpui_component::red_500()),
+        };
+        let attribution = example.review.as_ref().and_then(|r| {
+            let mut parts = Vec::new();
+            if let Some(reviewer) = &r.reviewer {
+                parts.push(reviewer.clone());
+            }
+            if let Some(comment) = &r.comment {
+                parts.push(format!("“{comment}”"));
+            }
+            (!parts.is_empty()).then(|| parts.join(" — "))
+        });
+
+        let entity = cx.entity().clone();
+        let toggle = |target: ReviewStatus| {
+            let entity = entity.clone();
+            let id = example.id.clone();
+            let comment = example.review.as_ref().and_then(|r| r.comment.clone());
+            // Clicking the active decision resets the example to pending
+            let next = if status == target {
+                ReviewStatus::Pending
+            } else {
+                target
+            };
+            move |_: &gpui::ClickEvent, _: &mut Window, cx: &mut App| {
+                entity.update(cx, |view, cx| {
+                    view.review(id.clone(), next, comment.clone(), cx)
+                });
+            }
+        };
+        let comment_entity = cx.entity().clone();
+        let comment_example = example.clone();
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_3()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .gap_0p5()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().foreground)
+                            .truncate()
+                            .child(if example.preview.is_empty() {
+                                example.id.clone()
+                            } else {
+                                example.preview.clone()
+                            }),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(format!(
+                                "{} messages{}{}",
+                                example.message_count,
+                                if example.annotated {
+                                    " · annotated"
+                                } else {
+                                    ""
+                                },
+                                example
+                                    .quality
+                                    .map(|q| format!(" · quality {q:.1}"))
+                                    .unwrap_or_default()
+                            )),
+                    )
+                    .when_some(attribution, |this, attribution| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .italic()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(attribution),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .w(px(70.))
+                    .text_xs()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(status_color)
+                    .child(status_label),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new(SharedString::from(format!("approve-{}", row_ix)))
+                            .icon(Icon::new(IconName::Check))
+                            .ghost()
+                            .xsmall()
+                            .tooltip(t("action-approve"))
+                            .on_click(toggle(ReviewStatus::Approved)),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("reject-{}", row_ix)))
+                            .icon(Icon::new(IconName::Close))
+                            .ghost()
+                            .xsmall()
+                            .tooltip(t("training-reject"))
+                            .on_click(toggle(ReviewStatus::Rejected)),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("comment-{}", row_ix)))
+                            .label(t("training-comment"))
+                            .ghost()
+                            .xsmall()
+                            .on_click(move |_, window, cx| {
+                                comment_entity.update(cx, |view, cx| {
+                                    view.show_comment_dialog(&comment_example, window, cx);
+                                });
+                            }),
+                    ),
+            )
+    }
+
+    /// Render the empty state.
+    fn render_empty(&self, cx: &Context<Self>) -> impl IntoElement {
+        let message = if self.loading {
+            "Loading exported examples…".to_string()
+        } else if let Some(error) = &self.error {
+            format!("Could not load examples: {error}")
+        } else {
+            "No exported examples yet. Enable \"Auto-export JSONL\" above to build sft.jsonl."
+                .to_string()
+        };
+        h_flex()
+            .w_full()
+            .justify_center()
+            .py_6()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(message)
+    }
+}
+
+impl Focusable for DatasetBrowserView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DatasetBrowserView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let count = |status: ReviewStatus| {
+            self.examples
+                .iter()
+                .filter(|e| e.review.as_ref().map(|r| r.status).unwrap_or_default() == status)
+                .count()
+        };
+        let summary = format!(
+            "{} examples · {} approved · {} rejected · {} pending",
+            self.examples.len(),
+            count(ReviewStatus::Approved),
+            count(ReviewStatus::Rejected),
+            count(ReviewStatus::Pending),
+        );
+
+        let table = v_flex()
+            .w_full()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .overflow_hidden()
+            .map(|this| {
+                if self.examples.is_empty() {
+                    this.child(self.render_empty(cx))
+                } else {
+                    this.children(
+                        self.examples
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, example)| self.render_row(ix, example, cx)),
+                    )
+                }
+            });
+
+        let reload_entity = cx.entity().clone();
+        let write_entity = cx.entity().clone();
+
+        v_flex()
+            .size_full()
+            .gap_3()
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(summary),
+            )
+            .child(table)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("reload-dataset")
+                            .label(t("action-refresh"))
+                            .on_click(move |_, _, cx| {
+                                reload_entity.update(cx, |view, cx| view.reload(cx));
+                            }),
+                    )
+                    .child(
+                        Button::new("write-approved-dataset")
+                            .label(t("training-write-dataset"))
+                            .primary()
+                            .on_click(move |_, window, cx| {
+                                write_entity.update(cx, |view, cx| view.write_approved(window, cx));
+                            }),
+                    ),
+            )
+    }
+}
+
+// ── Setting page entry point ────────────────────────────────────────────────
 
 pub fn training_settings_page() -> SettingPage {
-    SettingPage::new("Training Data")
+    SettingPage::new(t("settings-page-training-data"))
         .description("Configure automatic conversation export for model training")
         .resettable(false)
         .groups(vec![
@@ -53,5 +485,236 @@ pub fn training_settings_page() -> SettingPage {
                      to dpo.jsonl in the exports directory.",
                     ),
                 ]),
+            SettingGroup::new()
+                .title("PII Scrubbing")
+                .description(
+                    "Replace personal data in ATIF and JSONL exports with placeholders such as \
+                     [EMAIL_1]. The mapping is kept in pii_mapping.json so exports can be \
+                     de-pseudonymized locally — never share that file. Counts per conversation \
+                     are written to pii_report.json.",
+                )
+                .items(vec![
+                    SettingItem::new(
+                        "Scrub Exports",
+                        SettingField::switch(
+                            |cx: &App| cx.global::<TrainingSettingsModel>().pii_scrub.enabled,
+                            |val: bool, cx: &mut App| {
+                                training_settings_controller::update_pii_scrub(cx, |c| {
+                                    c.enabled = val
+                                });
+                            },
+                        )
+                        .default_value(false),
+                    )
+                    .description("Run every export through the scrubber before it is written."),
+                    SettingItem::new(
+                        "Names",
+                        SettingField::input(
+                            |cx: &App| {
+                                cx.global::<TrainingSettingsModel>()
+                                    .pii_scrub
+                                    .names
+                                    .join(", ")
+                                    .into()
+                            },
+                            |val: SharedString, cx: &mut App| {
+                                let names = val
+                                    .split(',')
+                                    .map(|n| n.trim().to_string())
+                                    .filter(|n| !n.is_empty())
+                                    .collect();
+                                training_settings_controller::update_pii_scrub(cx, |c| {
+                                    c.names = names
+                                });
+                            },
+                        ),
+                    )
+                    .description("Comma-separated names to pseudonymize (case-insensitive)."),
+                    SettingItem::new(
+                        "Email Addresses",
+                        SettingField::switch(
+                            |cx: &App| cx.global::<TrainingSettingsModel>().pii_scrub.emails,
+                            |val: bool, cx: &mut App| {
+                                training_settings_controller::update_pii_scrub(cx, |c| {
+                                    c.emails = val
+                                });
+                            },
+                        )
+                        .default_value(true),
+                    ),
+                    SettingItem::new(
+                        "API Keys & Tokens",
+                        SettingField::switch(
+                            |cx: &App| cx.global::<TrainingSettingsModel>().pii_scrub.keys,
+                            |val: bool, cx: &mut App| {
+                                training_settings_controller::update_pii_scrub(cx, |c| {
+                                    c.keys = val
+                                });
+                            },
+                        )
+                        .default_value(true),
+                    )
+                    .description(
+                        "OpenAI/Anthropic-style keys, GitHub and Slack tokens, AWS and Google \
+                         keys, JWTs and private key blocks.",
+                    ),
+                    SettingItem::new(
+                        "Home Directory Paths",
+                        SettingField::switch(
+                            |cx: &App| cx.global::<TrainingSettingsModel>().pii_scrub.paths,
+                            |val: bool, cx: &mut App| {
+                                training_settings_controller::update_pii_scrub(cx, |c| {
+                                    c.paths = val
+                                });
+                            },
+                        )
+                        .default_value(true),
+                    )
+                    .description(
+                        "Replace the user-specific prefix of paths such as /home/alice or \
+                         C:\\Users\\alice, keeping the rest of the path.",
+                    ),
+                ]),
+            SettingGroup::new()
+                .title("Quality Scoring")
+                .description(
+                    "Have a judge model rate each completed conversation from 1 to 5 on \
+                     helpfulness, tool efficiency and resolution. Scores are stored with the \
+                     conversation and included in sft.jsonl as \"_quality\".",
+                )
+                .items(vec![
+                    SettingItem::new(
+                        "Score Conversations",
+                        SettingField::switch(
+                            |cx: &App| cx.global::<TrainingSettingsModel>().quality_scoring,
+                            |val: bool, cx: &mut App| {
+                                training_settings_controller::set_quality_scoring(val, cx);
+                            },
+                        )
+                        .default_value(false),
+                    )
+                    .description(
+                        "Each completed response triggers one extra request to the judge model.",
+                    ),
+                    SettingItem::new(
+                        "Judge Model",
+                        SettingField::render(|_options, _window, cx| {
+                            let current =
+                                cx.global::<TrainingSettingsModel>().judge_model_id.clone();
+                            let models: Vec<(String, String)> = cx
+                                .global::<ModelsModel>()
+                                .models()
+                                .iter()
+                                .map(|m| (m.id.clone(), m.name.clone()))
+                                .collect();
+                            let label = current
+                                .as_ref()
+                                .and_then(|id| models.iter().find(|(mid, _)| mid == id))
+                                .map(|(_, name)| name.clone())
+                                .unwrap_or_else(|| "None".to_string());
+
+                            Button::new("judge-model-dropdown")
+                                .label(label)
+                                .dropdown_caret(true)
+                                .outline()
+                                .w_full()
+                                .dropdown_menu_with_anchor(Corner::BottomLeft, move |menu, _, _| {
+                                    let menu = menu.item(
+                                        PopupMenuItem::new("None")
+                                            .checked(current.is_none())
+                                            .on_click(|_, _, cx| {
+                                                training_settings_controller::set_judge_model(
+                                                    None, cx,
+                                                );
+                                            }),
+                                    );
+                                    models.iter().fold(menu, |menu, (id, name)| {
+                                        let id = id.clone();
+                                        menu.item(
+                                            PopupMenuItem::new(name.clone())
+                                                .checked(current.as_deref() == Some(id.as_str()))
+                                                .on_click(move |_, _, cx| {
+                                                    training_settings_controller::set_judge_model(
+                                                        Some(id.clone()),
+                                                        cx,
+                                                    );
+                                                }),
+                                        )
+                                    })
+                                })
+                                .into_any_element()
+                        }),
+                    )
+                    .description("Scoring is skipped until a judge model is chosen."),
+                    SettingItem::new(
+                        "Minimum Score",
+                        SettingField::number_input(
+                            NumberFieldOptions {
+                                min: 0.0,
+                                max: 5.0,
+                                ..Default::default()
+                            },
+                            |cx: &App| {
+                                cx.global::<TrainingSettingsModel>()
+                                    .min_quality_score
+                                    .unwrap_or(0.0) as f64
+                            },
+                            |val: f64, cx: &mut App| {
+                                let min_score = (val > 0.0).then(|| val.clamp(1.0, 5.0) as f32);
+                                training_settings_controller::set_min_quality_score(min_score, cx);
+                            },
+                        )
+                        .default_value(0.0),
+                    )
+                    .description(
+                        "Approved examples scoring below this are left out of \
+                         sft.approved.jsonl, as are unscored ones. 0 keeps every approved example.",
+                    ),
+                ]),
+            SettingGroup::new()
+                .title("Dataset Review")
+                .description(
+                    "Approve or reject exported SFT examples. Decisions are stored in \
+                     review_state.json in the exports directory, so a teammate can review \
+                     the same folder; approved examples can be written to sft.approved.jsonl.",
+                )
+                .items(vec![
+                    SettingItem::new(
+                        "Reviewer Name",
+                        SettingField::input(
+                            |cx: &App| {
+                                cx.global::<TrainingSettingsModel>()
+                                    .reviewer_name
+                                    .clone()
+                                    .unwrap_or_default()
+                                    .into()
+                            },
+                            |val: SharedString, cx: &mut App| {
+                                let name = if val.trim().is_empty() {
+                                    None
+                                } else {
+                                    Some(val.trim().to_string())
+                                };
+                                training_settings_controller::set_reviewer_name(name, cx);
+                            },
+                        ),
+                    )
+                    .description("Recorded with each review decision."),
+                    SettingItem::render(|_options, window, cx| {
+                        let view = match cx
+                            .try_global::<GlobalDatasetBrowserView>()
+                            .and_then(|g| g.get())
+                        {
+                            Some(view) => view,
+                            None => {
+                                let new_view = cx.new(|cx| DatasetBrowserView::new(window, cx));
+                                cx.set_global(GlobalDatasetBrowserView::new(new_view.clone()));
+                                new_view
+                            }
+                        };
+
+                        div().w_full().child(view)
+                    }),
+                ]),
         ])
 }
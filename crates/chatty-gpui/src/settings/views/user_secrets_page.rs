@@ -1,3 +1,4 @@
+use crate::i18n::t;
 use crate::settings::controllers::user_secrets_controller;
 use crate::settings::models::user_secrets_store::UserSecretsModel;
 use gpui::{
@@ -51,13 +52,13 @@ impl SecretsTableView {
                             .child(
                                 v_flex()
                                     .gap_1()
-                                    .child(div().text_sm().child("Variable Name"))
+                                    .child(div().text_sm().child(t("secrets-variable-name")))
                                     .child(Input::new(&key_input)),
                             )
                             .child(
                                 v_flex()
                                     .gap_1()
-                                    .child(div().text_sm().child("Value"))
+                                    .child(div().text_sm().child(t("secrets-value")))
                                     .child(Input::new(&value_input)),
                             )
                             .child(
@@ -65,15 +66,17 @@ impl SecretsTableView {
                                     .gap_2()
                                     .justify_end()
                                     .pt_4()
-                                    .child(Button::new("cancel-secret").label("Cancel").on_click(
-                                        move |_, window, cx| {
-                                            window.close_dialog(cx);
-                                        },
-                                    ))
+                                    .child(
+                                        Button::new("cancel-secret")
+                                            .label(t("action-cancel"))
+                                            .on_click(move |_, window, cx| {
+                                                window.close_dialog(cx);
+                                            }),
+                                    )
                                     .child(
                                         Button::new("save-secret")
                                             .primary()
-                                            .label("Save")
+                                            .label(t("action-save"))
                                             .on_click({
                                                 let key_input = key_input.clone();
                                                 let value_input = value_input.clone();
@@ -137,7 +140,7 @@ impl SecretsTableView {
                     .text_xs()
                     .font_weight(FontWeight::SEMIBOLD)
                     .text_color(cx.theme().muted_foreground)
-                    .child("Variable Name"),
+                    .child(t("secrets-variable-name")),
             )
             .child(
                 div()
@@ -145,7 +148,7 @@ impl SecretsTableView {
                     .text_xs()
                     .font_weight(FontWeight::SEMIBOLD)
                     .text_color(cx.theme().muted_foreground)
-                    .child("Value"),
+                    .child(t("secrets-value")),
             )
             .child(
                 div()
@@ -296,7 +299,7 @@ impl Render for SecretsTableView {
 // ── Setting page entry point ────────────────────────────────────────────────
 
 pub fn user_secrets_page() -> SettingPage {
-    SettingPage::new("Secrets")
+    SettingPage::new(t("settings-page-secrets"))
         .description(
             "Environment variables injected into shell sessions. \
              Scripts can access these via os.environ[\"KEY\"] — \
@@ -0,0 +1,139 @@
+//! Graceful quit.
+//!
+//! `begin_shutdown` runs on Quit: it stops active streams (their partial text
+//! is saved as cancelled responses), records the window layout and open
+//! conversation for "Restore previous session", kills the shell sessions and
+//! MCP server processes started by tools, flushes local statistics, waits for
+//! pending conversation writes, and only then exits. If that takes longer than a
+//! second, the main window shows a "Saving…" overlay.
+
+use super::*;
+use chatty_core::services::pending_writes;
+use std::time::Duration;
+
+/// Delay before the "Saving…" overlay appears.
+const OVERLAY_DELAY: Duration = Duration::from_secs(1);
+/// Longest the quit waits for cleanup and pending writes.
+const CLEANUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Present once quitting has started.
+#[derive(Default)]
+pub(crate) struct ShutdownState {
+    show_overlay: bool,
+}
+
+impl Global for ShutdownState {}
+
+/// Whether the main window should show the "Saving…" overlay.
+pub(crate) fn saving_overlay_visible(cx: &App) -> bool {
+    cx.try_global::<ShutdownState>()
+        .is_some_and(|state| state.show_overlay)
+}
+
+pub(crate) fn begin_shutdown(cx: &mut App) {
+    if cx.has_global::<ShutdownState>() {
+        debug!("Quit already in progress");
+        return;
+    }
+    cx.set_global(ShutdownState::default());
+    chatty::services::cleanup_thumbnails();
+
+    // Stop all active streams; their StreamEnded handlers save the partial
+    // responses before the cleanup task below first runs.
+    if let Some(manager) = cx
+        .try_global::<chatty::models::GlobalStreamManager>()
+        .and_then(|g| g.get())
+    {
+        manager.update(cx, |mgr, cx| {
+            mgr.stop_all(cx);
+        });
+    }
+
+    // Capture the session now; the save is awaited with the other writes below.
+    let restore_session = cx
+        .global::<settings::models::GeneralSettingsModel>()
+        .restore_session;
+    let session_settings = cx
+        .try_global::<GlobalChattyApp>()
+        .and_then(|g| g.try_upgrade())
+        .filter(|_| restore_session)
+        .map(|app| {
+            let session = app.read(cx).session_state(cx);
+            settings::controllers::general_settings_controller::record_last_session(cx, session)
+        });
+
+    let shell_sessions = cx
+        .try_global::<chatty_core::models::ConversationsStore>()
+        .map(|store| store.shell_sessions())
+        .unwrap_or_default();
+    let mcp_service = cx.global::<chatty::services::McpService>().clone();
+
+    cx.spawn(async move |cx: &mut AsyncApp| {
+        cx.background_executor().timer(OVERLAY_DELAY).await;
+        let _ = cx.update(|cx| {
+            cx.global_mut::<ShutdownState>().show_overlay = true;
+            cx.refresh_windows();
+        });
+    })
+    .detach();
+
+    cx.spawn(async move |cx: &mut AsyncApp| {
+        let cleanup = async {
+            for session in shell_sessions {
+                session.shutdown().await;
+            }
+            if let Err(e) = mcp_service.disconnect_all().await {
+                error!(error = ?e, "Failed to disconnect MCP servers during shutdown");
+            }
+        };
+        if tokio::time::timeout(CLEANUP_TIMEOUT, cleanup)
+            .await
+            .is_err()
+        {
+            warn!("Tool cleanup timed out during shutdown");
+        }
+
+        if let Some(settings) = session_settings
+            && let Err(e) = chatty_core::general_settings_repository()
+                .save(settings)
+                .await
+        {
+            error!(error = ?e, "Failed to save the session for the next launch");
+        }
+
+        if let Some(telemetry) = chatty_core::services::telemetry_service::telemetry() {
+            telemetry.flush();
+        }
+        if let Some(stats) = chatty_core::services::tool_stats_service::tool_stats() {
+            stats.flush();
+        }
+
+        if !pending_writes::wait_idle(CLEANUP_TIMEOUT).await {
+            warn!(
+                pending = pending_writes::in_flight(),
+                "Quitting with conversation writes still pending"
+            );
+        }
+
+        let _ = cx.update(finish_quit);
+    })
+    .detach();
+}
+
+fn finish_quit(cx: &mut App) {
+    // Mandatory auto-update: if an update has been downloaded and is ready,
+    // install it silently before quitting so the next launch runs the new
+    // version. install_on_quit() does NOT relaunch — the user intended to
+    // quit, so we respect that while still ensuring the update is applied.
+    if let Some(updater) = cx.try_global::<AutoUpdater>()
+        && matches!(updater.status(), auto_updater::AutoUpdateStatus::Ready(..))
+    {
+        info!("Pending update found on quit — installing before exit");
+        cx.update_global::<AutoUpdater, _>(|updater, cx| {
+            updater.install_on_quit(cx);
+        });
+        return;
+    }
+
+    cx.quit();
+}
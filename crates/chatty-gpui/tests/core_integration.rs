@@ -166,6 +166,8 @@ fn token_budget_snapshot_usable_from_gpui_crate() {
         tool_definitions_tokens: 2_000,
         conversation_history_tokens: 15_000,
         latest_user_message_tokens: 200,
+        pinned_context_tokens: 0,
+        breakdown: Default::default(),
         actual_input_tokens: None,
         actual_output_tokens: None,
         conversation_id: "conv-1".to_string(),
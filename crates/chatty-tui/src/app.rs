@@ -152,6 +152,11 @@ async fn run_loop(
                                     engine.add_system_message(e.to_string());
                                 }
                             }
+                            KeyAction::WriteHandoff(format) => {
+                                if let Err(e) = engine.write_handoff(format.as_deref()).await {
+                                    engine.add_system_message(e.to_string());
+                                }
+                            }
                             KeyAction::UpdateCli => {
                                 engine.update_cli_if_installed().await;
                             }
@@ -212,6 +217,7 @@ enum KeyAction {
     CompactConversation,
     ShowContext,
     CopyLastResponse,
+    WriteHandoff(Option<String>),
     UpdateCli,
     ShowWorkingDirectory,
     ChangeWorkingDirectory(String),
@@ -357,8 +363,24 @@ fn handle_key_event(
         }
     }
 
-    // If there's a pending approval, handle y/n first
-    if engine.pending_approval.is_some() {
+    // A pending ask_user question is answered through the regular input:
+    // Enter submits the answer, Esc skips the question, other keys edit the input.
+    if engine.pending_question().is_some() {
+        match key.code {
+            KeyCode::Esc => {
+                engine.deny();
+                return KeyAction::None;
+            }
+            KeyCode::Enter if key.modifiers.is_empty() => {
+                if engine.answer_question(&input_state.peek_input()) {
+                    input_state.take_input();
+                }
+                return KeyAction::None;
+            }
+            _ => {}
+        }
+    } else if engine.pending_approval.is_some() {
+        // If there's a pending approval, handle y/n first
         match key.code {
             KeyCode::Char('y') | KeyCode::Char('Y') => {
                 engine.approve();
@@ -505,7 +527,12 @@ fn map_command_to_action(cmd: Command, engine: &mut ChatEngine) -> Option<KeyAct
         Command::Clear => Some(KeyAction::ClearConversation),
         Command::Compact => Some(KeyAction::CompactConversation),
         Command::Context => Some(KeyAction::ShowContext),
+        Command::Confidential => {
+            engine.toggle_confidential();
+            None
+        }
         Command::Copy => Some(KeyAction::CopyLastResponse),
+        Command::Handoff(format) => Some(KeyAction::WriteHandoff(format)),
         Command::Update => Some(KeyAction::UpdateCli),
         Command::Cwd(Some(directory)) => Some(KeyAction::ChangeWorkingDirectory(directory)),
         Command::Cwd(None) => Some(KeyAction::ShowWorkingDirectory),
@@ -3,6 +3,8 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result, bail};
+use chatty_core::exporters::handoff::{AgentHandoff, HandoffFormat};
+use chatty_core::models::CompactionTrigger;
 use futures::StreamExt;
 use tracing::{info, warn};
 
@@ -28,8 +30,12 @@ pub enum Command {
     Compact,
     /// /context — show context usage stats
     Context,
+    /// /confidential — toggle confidential mode (guardrails block network tools)
+    Confidential,
     /// /copy — copy latest assistant response to clipboard
     Copy,
+    /// /handoff [aider|json] — write the task state for a CLI agent
+    Handoff(Option<String>),
     /// /update — trigger CLI auto-update if an installed CLI exists
     Update,
     /// /cwd, /cd [directory] — show or change working directory
@@ -63,7 +69,9 @@ impl ChatEngine {
             "/clear" | "/new" => Some(Command::Clear),
             "/compact" => Some(Command::Compact),
             "/context" => Some(Command::Context),
+            "/confidential" => Some(Command::Confidential),
             "/copy" => Some(Command::Copy),
+            "/handoff" => Some(Command::Handoff(arg)),
             "/update" => Some(Command::Update),
             "/cwd" | "/cd" => Some(Command::Cwd(arg)),
             "/quit" | "/exit" => Some(Command::Quit),
@@ -121,6 +129,33 @@ impl ChatEngine {
         }
     }
 
+    /// Toggle confidential mode on the active conversation and report the result.
+    pub fn toggle_confidential(&mut self) {
+        let Some(conversation) = self.conversation.as_mut() else {
+            self.add_system_message(
+                "No active conversation yet — send a message first.".to_string(),
+            );
+            return;
+        };
+        let confidential = !conversation.is_confidential();
+        conversation.set_confidential(confidential);
+
+        let network_rule_enabled = self.execution_settings.guardrail_rules.iter().any(|rule| {
+            rule.enabled
+                && rule.kind
+                    == chatty_core::services::GuardrailRuleKind::BlockNetworkWhenConfidential
+        });
+        let message = match (confidential, network_rule_enabled) {
+            (true, true) => "Confidential mode on: network tools are blocked.",
+            (true, false) => {
+                "Confidential mode on, but the confidential network guardrail is disabled \
+                 in the execution settings."
+            }
+            (false, _) => "Confidential mode off: network tools are allowed again.",
+        };
+        self.add_system_message(message.to_string());
+    }
+
     /// Return the active working directory for tool execution.
     pub fn current_working_directory(&self) -> String {
         if let Some(dir) = &self.execution_settings.workspace_dir {
@@ -206,13 +241,12 @@ impl ChatEngine {
             return Ok(());
         }
 
-        let midpoint = history.len() / 2;
         let result = chatty_core::token_budget::summarize_oldest_half(&agent, &history)
             .await
             .context("Failed to summarize conversation")?;
 
         if let Some(conv) = self.conversation.as_mut() {
-            conv.replace_history(result.new_history, midpoint);
+            conv.compact_history(&result, CompactionTrigger::Manual);
         }
 
         self.add_system_message(format!(
@@ -239,6 +273,47 @@ impl ChatEngine {
         Ok(())
     }
 
+    /// Write the conversation's instructions, files and plan to
+    /// `.chatty/handoff.md` (or `.json`) in the working directory.
+    pub async fn write_handoff(&mut self, format: Option<&str>) -> Result<()> {
+        let Some(format) = HandoffFormat::parse(format.unwrap_or_default()) else {
+            bail!("Usage: /handoff [aider|json]");
+        };
+        let Some(conversation) = self.conversation.as_ref() else {
+            bail!("No active conversation to hand off");
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        let data = conversation.to_data(now)?;
+        let handoff = AgentHandoff::from_conversation(&data)?;
+        let dir = data
+            .working_dir
+            .clone()
+            .unwrap_or_else(|| self.current_working_directory());
+        let dir = PathBuf::from(dir);
+        let path = handoff.write(&dir, format).await?;
+
+        info!(path = %path.display(), ?format, "Wrote handoff");
+        let mut message = format!(
+            "Handoff written to {} ({} instructions, {} files, {} plan steps).",
+            path.display(),
+            handoff.instructions.len(),
+            handoff.files.len(),
+            handoff.plan.len()
+        );
+        if format == HandoffFormat::Brief {
+            let relative = path.strip_prefix(&dir).unwrap_or(&path);
+            message.push_str(&format!(
+                "\nContinue in aider: {}",
+                handoff.aider_command(relative)
+            ));
+        }
+        self.add_system_message(message);
+        Ok(())
+    }
+
     /// Trigger CLI auto-update behavior.
     pub async fn update_cli_if_installed(&mut self) {
         match do_update_cli_if_installed().await {
@@ -435,31 +510,13 @@ impl ChatEngine {
     /// Prepare to switch models: resolve the model, update config, mark not ready.
     /// Call `init_conversation()` after this to complete the switch.
     pub fn prepare_model_switch(&mut self, query: &str) -> Result<()> {
-        let all_models = self.models.models();
-
-        // Try exact match on id first
-        let new_model = all_models
-            .iter()
-            .find(|m| m.id == query)
-            // Then case-insensitive name match
-            .or_else(|| {
-                all_models
-                    .iter()
-                    .find(|m| m.name.to_lowercase() == query.to_lowercase())
-            })
-            // Then partial match on model identifier
-            .or_else(|| {
-                all_models
-                    .iter()
-                    .find(|m| m.model_identifier.contains(query))
-            })
-            .cloned()
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Model '{}' not found. Type /model to see available models.",
-                    query
-                )
-            })?;
+        // Id, then alias, then name, then partial model identifier
+        let new_model = self.models.resolve(query).cloned().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Model '{}' not found. Type /model to see available models.",
+                query
+            )
+        })?;
 
         let new_provider = self
             .providers
@@ -501,7 +558,11 @@ impl ChatEngine {
                 }
                 ModelPickerItem {
                     id: m.id.clone(),
-                    name: m.name.clone(),
+                    name: if m.aliases.is_empty() {
+                        m.name.clone()
+                    } else {
+                        format!("{} [{}]", m.name, m.aliases.join(", "))
+                    },
                     provider: format!("{:?}", m.provider_type),
                     is_active,
                 }
@@ -550,6 +611,31 @@ impl ChatEngine {
                 label: "Git".to_string(),
                 enabled: es.git_enabled,
             },
+            ToolPickerItem {
+                key: "k8s".to_string(),
+                label: "Kubernetes".to_string(),
+                enabled: es.kubernetes_enabled,
+            },
+            ToolPickerItem {
+                key: "docker".to_string(),
+                label: "Docker".to_string(),
+                enabled: es.docker_tools_enabled,
+            },
+            ToolPickerItem {
+                key: "ssh".to_string(),
+                label: "SSH".to_string(),
+                enabled: es.ssh_enabled,
+            },
+            ToolPickerItem {
+                key: "notes".to_string(),
+                label: "Notes Vault".to_string(),
+                enabled: es.notes_enabled,
+            },
+            ToolPickerItem {
+                key: "citations".to_string(),
+                label: "Citations".to_string(),
+                enabled: es.citations_enabled,
+            },
             ToolPickerItem {
                 key: "code-exec".to_string(),
                 label: "Code Execution".to_string(),
@@ -584,6 +670,11 @@ impl ChatEngine {
                 "fs-write" => self.execution_settings.filesystem_write_enabled = item.enabled,
                 "fetch" => self.execution_settings.fetch_enabled = item.enabled,
                 "git" => self.execution_settings.git_enabled = item.enabled,
+                "k8s" => self.execution_settings.kubernetes_enabled = item.enabled,
+                "docker" => self.execution_settings.docker_tools_enabled = item.enabled,
+                "ssh" => self.execution_settings.ssh_enabled = item.enabled,
+                "notes" => self.execution_settings.notes_enabled = item.enabled,
+                "citations" => self.execution_settings.citations_enabled = item.enabled,
                 "code-exec" => self.execution_settings.execute_code_enabled = item.enabled,
                 "docker-exec" => {
                     self.execution_settings.docker_code_execution_enabled = item.enabled
@@ -618,6 +709,22 @@ impl ChatEngine {
                 self.execution_settings.fetch_enabled = !self.execution_settings.fetch_enabled
             }
             "git" => self.execution_settings.git_enabled = !self.execution_settings.git_enabled,
+            "k8s" => {
+                self.execution_settings.kubernetes_enabled =
+                    !self.execution_settings.kubernetes_enabled
+            }
+            "docker" => {
+                self.execution_settings.docker_tools_enabled =
+                    !self.execution_settings.docker_tools_enabled
+            }
+            "ssh" => self.execution_settings.ssh_enabled = !self.execution_settings.ssh_enabled,
+            "notes" => {
+                self.execution_settings.notes_enabled = !self.execution_settings.notes_enabled
+            }
+            "citations" => {
+                self.execution_settings.citations_enabled =
+                    !self.execution_settings.citations_enabled
+            }
             "code-exec" => {
                 self.execution_settings.execute_code_enabled =
                     !self.execution_settings.execute_code_enabled
@@ -631,7 +738,7 @@ impl ChatEngine {
             }
             _ => {
                 self.add_system_message(format!(
-                    "Unknown tool '{}'. Valid: shell, fs-read, fs-write, fetch, git, code-exec, docker-exec",
+                    "Unknown tool '{}'. Valid: shell, fs-read, fs-write, fetch, git, k8s, docker, ssh, notes, citations, code-exec, docker-exec",
                     name
                 ));
                 return false;
@@ -644,6 +751,11 @@ impl ChatEngine {
             "fs-write" => self.execution_settings.filesystem_write_enabled,
             "fetch" => self.execution_settings.fetch_enabled,
             "git" => self.execution_settings.git_enabled,
+            "k8s" => self.execution_settings.kubernetes_enabled,
+            "docker" => self.execution_settings.docker_tools_enabled,
+            "ssh" => self.execution_settings.ssh_enabled,
+            "notes" => self.execution_settings.notes_enabled,
+            "citations" => self.execution_settings.citations_enabled,
             "code-exec" => self.execution_settings.execute_code_enabled,
             "docker-exec" => self.execution_settings.docker_code_execution_enabled,
             _ => false,
@@ -204,7 +204,15 @@ mod tests {
             ChatEngine::parse_command("/context"),
             Some(Command::Context)
         );
+        assert_eq!(
+            ChatEngine::parse_command("/confidential"),
+            Some(Command::Confidential)
+        );
         assert_eq!(ChatEngine::parse_command("/copy"), Some(Command::Copy));
+        assert_eq!(
+            ChatEngine::parse_command("/handoff json"),
+            Some(Command::Handoff(Some("json".to_string())))
+        );
         assert_eq!(ChatEngine::parse_command("/update"), Some(Command::Update));
         assert_eq!(ChatEngine::parse_command("/cwd"), Some(Command::Cwd(None)));
         assert_eq!(
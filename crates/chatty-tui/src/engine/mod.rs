@@ -9,6 +9,7 @@ use chatty_core::factories::agent_factory::AgentBuildContext;
 use chatty_core::models::Conversation;
 use chatty_core::models::execution_approval_store::{
     ApprovalDecision, ApprovalNotification, ApprovalResolution, ExecutionApprovalStore,
+    UserQuestion,
 };
 use chatty_core::models::message_types::{
     ExecutionEngine, ToolSource, classify_initial_execution_engine, classify_tool_source,
@@ -61,6 +62,8 @@ pub struct PendingApproval {
     pub id: String,
     pub command: String,
     pub is_sandboxed: bool,
+    /// Set when the agent asked a question via `ask_user`
+    pub question: Option<UserQuestion>,
 }
 
 /// A message for display in the TUI
@@ -137,6 +140,18 @@ impl DisplayMessage {
         })
     }
 
+    /// Mark tool calls still running when the user stopped the stream.
+    pub fn cancel_running_tool_calls(&mut self) {
+        for block in &mut self.blocks {
+            if let MessageBlock::ToolCall(tc) = block
+                && matches!(tc.state, ToolCallState::Running)
+            {
+                tc.output = Some(chatty_core::services::CANCELLED_BY_USER.to_string());
+                tc.state = ToolCallState::Error;
+            }
+        }
+    }
+
     pub fn tool_calls(&self) -> impl Iterator<Item = &ToolCallInfo> {
         self.blocks.iter().filter_map(|b| match b {
             MessageBlock::ToolCall(tc) => Some(tc),
@@ -307,6 +322,8 @@ pub struct ChatEngineConfig {
     pub search_settings:
         Option<chatty_core::settings::models::search_settings::SearchSettingsModel>,
     pub embedding_service: Option<chatty_core::services::EmbeddingService>,
+    /// Embedding service for the skills index (may use another model than memory).
+    pub skill_embedding_service: Option<chatty_core::services::EmbeddingService>,
     pub user_secrets: Vec<(String, String)>,
     pub remote_agents: Vec<A2aAgentConfig>,
     pub module_agents: Vec<LocalModuleAgentSummary>,
@@ -319,7 +336,7 @@ pub struct ChatEngineConfig {
 impl ChatEngine {
     pub fn new(config: ChatEngineConfig, event_tx: mpsc::UnboundedSender<AppEvent>) -> Self {
         let skill_service =
-            chatty_core::services::SkillService::new(config.embedding_service.clone());
+            chatty_core::services::SkillService::new(config.skill_embedding_service);
         Self {
             conversation: None,
             model_config: config.model_config,
@@ -421,6 +438,11 @@ impl ChatEngine {
             || es.filesystem_write_enabled
             || es.fetch_enabled
             || es.git_enabled
+            || es.kubernetes_enabled
+            || es.docker_tools_enabled
+            || es.ssh_enabled
+            || es.notes_enabled
+            || es.citations_enabled
             || es.execute_code_enabled;
         let exec_settings = if any_tool_enabled {
             Some(self.execution_settings.clone())
@@ -457,6 +479,11 @@ impl ChatEngine {
                     .then_some(self.module_settings.gateway_port),
                 remote_agents: self.remote_agents.clone(),
                 available_model_ids: self.available_model_ids(),
+                conversation_variables: None, // set inside Conversation::new
+                execution_plan: None,         // set inside Conversation::new
+                confidential: None,           // set inside Conversation::new
+                docker_resources: None,       // set inside Conversation::new
+                disabled_tools: Default::default(),
             },
         )
         .await
@@ -510,6 +537,11 @@ impl ChatEngine {
                 || es.filesystem_write_enabled
                 || es.fetch_enabled
                 || es.git_enabled
+                || es.kubernetes_enabled
+                || es.docker_tools_enabled
+                || es.ssh_enabled
+                || es.notes_enabled
+                || es.citations_enabled
                 || es.execute_code_enabled;
             let exec_settings = if any_tool_enabled {
                 Some(execution_settings.clone())
@@ -542,6 +574,11 @@ impl ChatEngine {
                         .then_some(module_settings.gateway_port),
                     remote_agents,
                     available_model_ids,
+                    conversation_variables: None, // set inside Conversation::new
+                    execution_plan: None,         // set inside Conversation::new
+                    confidential: None,           // set inside Conversation::new
+                    docker_resources: None,       // set inside Conversation::new
+                    disabled_tools: Default::default(),
                 },
             )
             .await;
@@ -737,15 +774,17 @@ impl ChatEngine {
                 id,
                 command,
                 is_sandboxed,
+                question,
             } => {
                 self.pending_approval = Some(PendingApproval {
                     id,
                     command,
                     is_sandboxed,
+                    question,
                 });
                 EngineAction::Redraw
             }
-            AppEvent::ApprovalResolved { id: _, approved: _ } => {
+            AppEvent::ApprovalResolved { .. } => {
                 self.pending_approval = None;
                 EngineAction::Redraw
             }
@@ -781,6 +820,7 @@ impl ChatEngine {
             }
             AppEvent::StreamCancelled => {
                 if let Some(last) = self.messages.last_mut() {
+                    last.cancel_running_tool_calls();
                     last.push_text("\n\n[Cancelled]");
                     last.is_streaming = false;
                 }
@@ -822,7 +862,7 @@ impl ChatEngine {
                 self.memory_service = services.memory_service;
                 self.search_settings = services.search_settings;
                 self.skill_service =
-                    chatty_core::services::SkillService::new(services.embedding_service.clone());
+                    chatty_core::services::SkillService::new(services.skill_embedding_service);
                 self.embedding_service = services.embedding_service;
                 self.services_loaded = true;
                 // Re-initialize conversation only if the user hasn't sent any messages yet.
@@ -896,6 +936,28 @@ impl ChatEngine {
         }
     }
 
+    /// The pending `ask_user` question, if any
+    pub fn pending_question(&self) -> Option<&UserQuestion> {
+        self.pending_approval.as_ref()?.question.as_ref()
+    }
+
+    /// Answer the pending `ask_user` question with text typed by the user.
+    /// Returns `false` (leaving the question pending) when the text is not an
+    /// acceptable answer, e.g. free text for an options-only question.
+    pub fn answer_question(&mut self, text: &str) -> bool {
+        let Some(answer) = self
+            .pending_question()
+            .and_then(|question| question.resolve_typed_answer(text))
+        else {
+            return false;
+        };
+        if let Some(approval) = self.pending_approval.take() {
+            self.execution_approval_store
+                .resolve(&approval.id, ApprovalDecision::Answered(answer));
+        }
+        true
+    }
+
     /// Add a system message to the display
     pub fn add_system_message(&mut self, text: String) {
         self.messages
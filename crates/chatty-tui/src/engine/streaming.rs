@@ -81,18 +81,26 @@ impl chatty_core::services::StreamChunkHandler for TuiStreamHandler {
                 id,
                 command,
                 is_sandboxed,
+                question,
             } => {
                 let _ = self.event_tx.send(AppEvent::ApprovalRequested {
                     id,
                     command,
                     is_sandboxed,
+                    question,
                 });
                 Ok(ChunkAction::Continue)
             }
-            StreamChunk::ApprovalResolved { id, approved } => {
-                let _ = self
-                    .event_tx
-                    .send(AppEvent::ApprovalResolved { id, approved });
+            StreamChunk::ApprovalResolved {
+                id,
+                approved,
+                answer,
+            } => {
+                let _ = self.event_tx.send(AppEvent::ApprovalResolved {
+                    id,
+                    approved,
+                    answer,
+                });
                 Ok(ChunkAction::Continue)
             }
             StreamChunk::TokenUsage {
@@ -185,6 +193,7 @@ pub(super) async fn run_stream(params: StreamParams) -> Result<()> {
 
     let mut progress_rx =
         chatty_core::services::install_progress_channel(&invoke_agent_progress_slot);
+    agent.tool_cancellation().install(cancel_flag.clone());
     let mut handler = TuiStreamHandler {
         event_tx,
         task_controller,
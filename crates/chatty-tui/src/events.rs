@@ -1,6 +1,7 @@
 use crossterm::event::Event as CrosstermEvent;
 
 use chatty_core::models::Conversation;
+use chatty_core::models::execution_approval_store::UserQuestion;
 use chatty_core::services::{EmbeddingService, McpService, MemoryService};
 
 /// Heavy services loaded in the background after the TUI is displayed.
@@ -12,6 +13,7 @@ pub struct DeferredServices {
     pub search_settings:
         Option<chatty_core::settings::models::search_settings::SearchSettingsModel>,
     pub embedding_service: Option<EmbeddingService>,
+    pub skill_embedding_service: Option<EmbeddingService>,
 }
 
 /// Unified event type for the TUI application.
@@ -42,10 +44,12 @@ pub enum AppEvent {
         id: String,
         command: String,
         is_sandboxed: bool,
+        question: Option<UserQuestion>,
     },
     ApprovalResolved {
         id: String,
         approved: bool,
+        answer: Option<String>,
     },
     TokenUsage {
         input_tokens: u32,
@@ -107,16 +111,23 @@ impl std::fmt::Debug for AppEvent {
                 id,
                 command,
                 is_sandboxed,
+                question,
             } => f
                 .debug_struct("ApprovalRequested")
                 .field("id", id)
                 .field("command", command)
                 .field("is_sandboxed", is_sandboxed)
+                .field("question", question)
                 .finish(),
-            Self::ApprovalResolved { id, approved } => f
+            Self::ApprovalResolved {
+                id,
+                approved,
+                answer,
+            } => f
                 .debug_struct("ApprovalResolved")
                 .field("id", id)
                 .field("approved", approved)
+                .field("answer", answer)
                 .finish(),
             Self::TokenUsage {
                 input_tokens,
@@ -411,6 +411,11 @@ pub async fn run_headless(
             // Handle other events silently
             _ => {
                 engine.handle_event(event);
+                // Nobody can answer an ask_user question here; skip it so the turn continues.
+                if let Some(question) = engine.pending_question() {
+                    eprintln!("Skipping question from the agent: {}", question.question);
+                    engine.deny();
+                }
             }
         }
     }
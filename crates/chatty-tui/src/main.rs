@@ -7,6 +7,7 @@ mod ui;
 use anyhow::{Context, Result, bail};
 use chatty_core::MCP_SERVICE;
 use chatty_core::services::McpService;
+use chatty_core::services::flow_recorder::{self, FlowMode};
 use chatty_core::settings::models::ModelsModel;
 use chatty_core::settings::models::extensions_store::ExtensionsModel;
 use chatty_core::settings::models::models_store::ModelConfig;
@@ -76,7 +77,9 @@ EXAMPLES:
   chatty-tui --openai-compat-url http://localhost:8000  # Connect to vllm/llama.cpp
   chatty-tui --enable git,shell --disable fetch   # Custom tool set
   chatty-tui --headless -m \"What is Rust?\"        # One-shot query
-  cat src/main.rs | chatty-tui --pipe             # Pipe file contents as input"
+  cat src/main.rs | chatty-tui --pipe             # Pipe file contents as input
+  chatty-tui --record-flow bug.json              # Record responses for a bug report
+  chatty-tui --replay-flow bug.json              # Re-run a recorded session offline"
 )]
 struct Cli {
     /// Select which LLM model to use for the conversation.
@@ -117,7 +120,7 @@ struct Cli {
     ///
     /// Overrides the persisted Chatty execution settings. Multiple groups
     /// can be specified as a comma-separated list. Valid tool group names:
-    ///   shell, fs-read, fs-write, fetch, git, code-exec, docker-exec
+    ///   shell, fs-read, fs-write, fetch, git, k8s, docker, ssh, notes, citations, code-exec, docker-exec
     ///
     /// Example: --enable shell,git,fetch
     #[arg(long, value_delimiter = ',', value_name = "GROUPS")]
@@ -176,6 +179,23 @@ struct Cli {
     /// For servers that don't need auth, this can be omitted.
     #[arg(long, value_name = "KEY")]
     api_key: Option<String>,
+
+    /// Record every provider response of this session into a fixture file.
+    ///
+    /// Streamed text, tool calls with their results, token usage and errors
+    /// are written to FILE as each turn finishes. Attach the file to a bug
+    /// report, or replay it with --replay-flow. Same as setting
+    /// CHATTY_RECORD_FLOW.
+    #[arg(long, value_name = "FILE", conflicts_with = "replay_flow")]
+    record_flow: Option<std::path::PathBuf>,
+
+    /// Replay provider responses from a fixture written by --record-flow.
+    ///
+    /// No provider is contacted: each message is answered with the next
+    /// recorded turn, so the session runs the same way every time. Same as
+    /// setting CHATTY_REPLAY_FLOW.
+    #[arg(long, value_name = "FILE")]
+    replay_flow: Option<std::path::PathBuf>,
 }
 
 #[tokio::main]
@@ -208,6 +228,14 @@ async fn main() -> Result<()> {
     chatty_core::init_repositories()
         .context("Failed to initialize settings repositories (is HOME set?)")?;
 
+    // --record-flow / --replay-flow, falling back to the environment variables
+    let flow_mode = match (cli.record_flow.clone(), cli.replay_flow.clone()) {
+        (_, Some(path)) => Some(FlowMode::Replay(path)),
+        (Some(path), None) => Some(FlowMode::Record(path)),
+        (None, None) => flow_recorder::mode_from_env(),
+    };
+    flow_recorder::init(flow_mode)?;
+
     // Load providers, models, execution settings, module settings, and A2A agents
     let (
         providers_result,
@@ -216,6 +244,7 @@ async fn main() -> Result<()> {
         module_settings_result,
         extensions_result,
         a2a_agents_result,
+        managed,
     ) = tokio::join!(
         chatty_core::provider_repository().load_all(),
         chatty_core::models_repository().load_all(),
@@ -223,10 +252,16 @@ async fn main() -> Result<()> {
         chatty_core::module_settings_repository().load(),
         chatty_core::extensions_repository().load(),
         chatty_core::a2a_repository().load_all(),
+        chatty_core::services::managed_config_service::load(),
     );
 
-    let mut providers = providers_result.context("Failed to load providers")?;
-    let mut models_list = models_result.context("Failed to load models")?;
+    // Overlay the admin-provided managed layer on the local settings
+    let (mut providers, _) = managed
+        .config
+        .apply_to_providers(providers_result.context("Failed to load providers")?);
+    let (mut models_list, _) = managed
+        .config
+        .apply_to_models(models_result.context("Failed to load models")?);
     let mut execution_settings = exec_settings_result.unwrap_or_default();
     let module_settings = module_settings_result.unwrap_or_default();
     let extensions = extensions_result.unwrap_or_default();
@@ -339,7 +374,7 @@ async fn main() -> Result<()> {
         let (user_secrets, mcp_service, memory_service, search_settings) =
             load_deferred_services(&execution_settings).await;
 
-        let embedding_service =
+        let (embedding_service, skill_embedding_service) =
             init_embedding_service(&execution_settings, &providers, &memory_service).await;
 
         let mut engine = ChatEngine::new(
@@ -354,6 +389,7 @@ async fn main() -> Result<()> {
                 memory_service,
                 search_settings,
                 embedding_service,
+                skill_embedding_service,
                 user_secrets,
                 remote_agents,
                 module_agents: module_agents.clone(),
@@ -386,6 +422,7 @@ async fn main() -> Result<()> {
                 memory_service: None,
                 search_settings: None,
                 embedding_service: None,
+                skill_embedding_service: None,
                 user_secrets: vec![],
                 remote_agents,
                 module_agents,
@@ -402,7 +439,7 @@ async fn main() -> Result<()> {
             let (user_secrets, mcp_service, memory_service, search_settings) =
                 load_deferred_services(&execution_settings).await;
 
-            let embedding_service =
+            let (embedding_service, skill_embedding_service) =
                 init_embedding_service(&execution_settings, &providers, &memory_service).await;
 
             let _ = bg_tx.send(AppEvent::ServicesReady(Box::new(
@@ -412,6 +449,7 @@ async fn main() -> Result<()> {
                     memory_service,
                     search_settings,
                     embedding_service,
+                    skill_embedding_service,
                 },
             )));
         });
@@ -481,58 +519,41 @@ async fn load_deferred_services(
     )
 }
 
-/// Initialize embedding service for semantic memory search (if configured).
+/// Initialize the embedding services for semantic memory search and skill
+/// matching (if configured). Returns `(memory, skills)`; the skills index
+/// shares the memory service unless another model is selected for it.
 async fn init_embedding_service(
     execution_settings: &chatty_core::settings::models::ExecutionSettingsModel,
     providers: &[ProviderConfig],
     memory_service: &Option<chatty_core::services::MemoryService>,
-) -> Option<chatty_core::services::EmbeddingService> {
+) -> (
+    Option<chatty_core::services::EmbeddingService>,
+    Option<chatty_core::services::EmbeddingService>,
+) {
+    use chatty_core::services::embedding_service::create_embedding_service_with_providers;
+    use chatty_core::settings::models::embedding_models_store::KnowledgeBase;
+
     if !execution_settings.embedding_enabled {
-        return None;
+        return (None, None);
     }
 
-    let (embed_provider_type, embed_model) = match (
-        execution_settings.embedding_provider.as_ref(),
-        execution_settings.embedding_model.as_ref(),
-    ) {
-        (Some(pt), Some(m)) => (pt, m),
-        _ => {
-            info!("Semantic search enabled but no embedding provider/model configured");
-            return None;
-        }
-    };
+    let mut embedding_models = chatty_core::settings::models::EmbeddingModelsModel::new();
+    match chatty_core::embedding_models_repository().load_all().await {
+        Ok(models) => embedding_models.replace_all(models),
+        Err(e) => warn!(error = ?e, "Failed to load embedding models"),
+    }
 
-    let embed_provider_config = providers
-        .iter()
-        .find(|p| &p.provider_type == embed_provider_type);
-    let api_key = embed_provider_config.and_then(|p| p.api_key.as_deref());
-    let base_url = embed_provider_config.and_then(|p| p.base_url.as_deref());
-
-    // Fetch Entra ID token if the Azure provider uses Entra ID auth
-    let azure_token = if *embed_provider_type
-        == chatty_core::settings::models::providers_store::ProviderType::AzureOpenAI
-        && embed_provider_config.map(|p| p.azure_auth_method())
-            == Some(chatty_core::settings::models::providers_store::AzureAuthMethod::EntraId)
-    {
-        match chatty_core::auth::azure_auth::fetch_entra_id_token().await {
-            Ok(token) => Some(token),
-            Err(e) => {
-                warn!(error = ?e, "Failed to fetch Entra ID token for Azure OpenAI embeddings");
-                None
-            }
+    let memory_config = embedding_models.resolve_for(KnowledgeBase::Memory, execution_settings);
+    let skills_config = embedding_models.resolve_for(KnowledgeBase::Skills, execution_settings);
+
+    let svc = match &memory_config {
+        Some(config) => create_embedding_service_with_providers(config, providers).await,
+        None => {
+            info!("Semantic search enabled but no embedding provider/model configured");
+            None
         }
-    } else {
-        None
     };
 
-    let svc = chatty_core::services::embedding_service::try_create_embedding_service(
-        embed_provider_type,
-        embed_model,
-        api_key,
-        base_url,
-        azure_token,
-    );
-
     // Enable vector index on memory service if embedding service is available
     if let (Some(embed_svc), Some(mem_svc)) = (&svc, memory_service) {
         if let Err(e) = mem_svc.enable_vec().await {
@@ -542,7 +563,14 @@ async fn init_embedding_service(
         }
     }
 
-    svc
+    let skill_svc = match &skills_config {
+        Some(config) if skills_config != memory_config => {
+            create_embedding_service_with_providers(config, providers).await
+        }
+        _ => svc.clone(),
+    };
+
+    (svc, skill_svc)
 }
 
 fn discover_module_agents(
@@ -602,22 +630,8 @@ fn resolve_model(cli: &Cli, models: &ModelsModel) -> Result<ModelConfig> {
     }
 
     if let Some(ref model_id) = cli.model {
-        // Try exact match on id first, then name
-        if let Some(config) = models.get_model(model_id) {
-            return Ok(config.clone());
-        }
-        // Try case-insensitive name match
-        if let Some(config) = all_models
-            .iter()
-            .find(|m| m.name.to_lowercase() == model_id.to_lowercase())
-        {
-            return Ok(config.clone());
-        }
-        // Try partial match on model identifier
-        if let Some(config) = all_models
-            .iter()
-            .find(|m| m.model_identifier.contains(model_id.as_str()))
-        {
+        // Id, then alias, then name, then partial model identifier
+        if let Some(config) = models.resolve(model_id) {
             return Ok(config.clone());
         }
 
@@ -648,6 +662,11 @@ fn apply_tool_overrides(
             "fs-write" => settings.filesystem_write_enabled = true,
             "fetch" => settings.fetch_enabled = true,
             "git" => settings.git_enabled = true,
+            "k8s" => settings.kubernetes_enabled = true,
+            "docker" => settings.docker_tools_enabled = true,
+            "ssh" => settings.ssh_enabled = true,
+            "notes" => settings.notes_enabled = true,
+            "citations" => settings.citations_enabled = true,
             "code-exec" => settings.execute_code_enabled = true,
             "docker-exec" => {
                 settings.execute_code_enabled = true;
@@ -656,7 +675,7 @@ fn apply_tool_overrides(
             other => {
                 tracing::warn!(
                     name = other,
-                    "Unknown tool group in --enable (valid: shell, fs-read, fs-write, fetch, git, code-exec, docker-exec)"
+                    "Unknown tool group in --enable (valid: shell, fs-read, fs-write, fetch, git, k8s, docker, ssh, notes, citations, code-exec, docker-exec)"
                 );
             }
         }
@@ -668,12 +687,17 @@ fn apply_tool_overrides(
             "fs-write" => settings.filesystem_write_enabled = false,
             "fetch" => settings.fetch_enabled = false,
             "git" => settings.git_enabled = false,
+            "k8s" => settings.kubernetes_enabled = false,
+            "docker" => settings.docker_tools_enabled = false,
+            "ssh" => settings.ssh_enabled = false,
+            "notes" => settings.notes_enabled = false,
+            "citations" => settings.citations_enabled = false,
             "code-exec" => settings.execute_code_enabled = false,
             "docker-exec" => settings.docker_code_execution_enabled = false,
             other => {
                 tracing::warn!(
                     name = other,
-                    "Unknown tool group in --disable (valid: shell, fs-read, fs-write, fetch, git, code-exec, docker-exec)"
+                    "Unknown tool group in --disable (valid: shell, fs-read, fs-write, fetch, git, k8s, docker, ssh, notes, citations, code-exec, docker-exec)"
                 );
             }
         }
@@ -682,13 +706,17 @@ fn apply_tool_overrides(
 
 async fn start_mcp_servers() -> Option<McpService> {
     let mcp_repo = chatty_core::mcp_repository();
-    let mut servers = match mcp_repo.load_all().await {
+    let servers = match mcp_repo.load_all().await {
         Ok(s) => s,
         Err(e) => {
             tracing::warn!(error = ?e, "Failed to load MCP server configs");
             vec![]
         }
     };
+    let (mut servers, _) = chatty_core::services::managed_config_service::load()
+        .await
+        .config
+        .apply_to_mcp_servers(servers);
 
     // Load extensions and ensure the built-in Hive MCP server exists
     let ext_repo = chatty_core::extensions_repository();
@@ -736,12 +764,8 @@ async fn start_mcp_servers() -> Option<McpService> {
         .map_err(|_| tracing::warn!("MCP_SERVICE already initialized"))
         .ok();
 
-    let svc = service.clone();
-    tokio::spawn(async move {
-        if let Err(e) = svc.connect_all(enabled_servers).await {
-            tracing::error!(error = ?e, "Failed to connect to MCP servers");
-        }
-    });
+    // Servers connect on first tool use
+    service.register_all(enabled_servers).await;
 
     Some(service)
 }
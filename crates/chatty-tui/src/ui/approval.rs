@@ -50,3 +50,55 @@ pub fn render_approval_prompt(frame: &mut Frame, area: Rect, engine: &ChatEngine
 
     frame.render_widget(paragraph, area);
 }
+
+/// Rows needed for the `ask_user` question panel (0 when no question is pending).
+pub fn question_prompt_height(engine: &ChatEngine) -> u16 {
+    match engine.pending_question() {
+        // borders + question + options + hint
+        Some(question) => 4 + question.options.len() as u16,
+        None => 0,
+    }
+}
+
+pub fn render_question_prompt(frame: &mut Frame, area: Rect, engine: &ChatEngine) {
+    let Some(question) = engine.pending_question() else {
+        return;
+    };
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            " QUESTION ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(theme::WARNING)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::raw(question.question.clone()),
+    ])];
+    for (index, option) in question.options.iter().enumerate() {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  [{}] ", index + 1),
+                theme::accent().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(option.clone()),
+        ]));
+    }
+    let hint = if question.options.is_empty() {
+        "Type your answer and press Enter · Esc to skip"
+    } else if question.allow_free_text {
+        "Type an option number or your own answer, then Enter · Esc to skip"
+    } else {
+        "Type an option number and press Enter · Esc to skip"
+    };
+    lines.push(Line::from(Span::styled(hint, theme::muted())));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme::border()),
+    );
+
+    frame.render_widget(paragraph, area);
+}
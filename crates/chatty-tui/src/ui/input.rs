@@ -153,12 +153,24 @@ const SLASH_COMMANDS: &[SlashCommandEntry] = &[
         insert_text: "/context",
         execute_immediately: true,
     },
+    SlashCommandEntry {
+        command: "/confidential",
+        description: "Toggle confidential mode (blocks network tools)",
+        insert_text: "/confidential",
+        execute_immediately: true,
+    },
     SlashCommandEntry {
         command: "/copy",
         description: "Copy latest response to clipboard",
         insert_text: "/copy",
         execute_immediately: true,
     },
+    SlashCommandEntry {
+        command: "/handoff",
+        description: "Write the task for a CLI agent: /handoff [aider|json]",
+        insert_text: "/handoff ",
+        execute_immediately: false,
+    },
     SlashCommandEntry {
         command: "/update",
         description: "Trigger CLI auto-update (if installed)",
@@ -23,6 +23,7 @@ pub fn render(frame: &mut Frame, engine: &mut ChatEngine, input_state: &mut Inpu
         .constraints([
             Constraint::Min(3),    // Chat messages (fills remaining space)
             Constraint::Length(1), // Status bar
+            Constraint::Length(approval::question_prompt_height(engine)), // ask_user question
             Constraint::Length(3), // Input area
             Constraint::Length(1), // Hint footer
         ])
@@ -34,15 +35,18 @@ pub fn render(frame: &mut Frame, engine: &mut ChatEngine, input_state: &mut Inpu
     // Status bar
     status_bar::render_status_bar(frame, chunks[1], engine);
 
+    // Question from the agent, answered through the input below
+    approval::render_question_prompt(frame, chunks[2], engine);
+
     // Input area (or approval prompt if pending)
-    if engine.pending_approval.is_some() {
-        approval::render_approval_prompt(frame, chunks[2], engine);
+    if engine.pending_approval.is_some() && engine.pending_question().is_none() {
+        approval::render_approval_prompt(frame, chunks[3], engine);
     } else {
-        input::render_input(frame, chunks[2], input_state);
+        input::render_input(frame, chunks[3], input_state);
     }
 
     // Hint footer
-    hint_bar::render_hint_bar(frame, chunks[3], engine);
+    hint_bar::render_hint_bar(frame, chunks[4], engine);
 
     // Model picker overlay (rendered last so it appears on top)
     if let Some(ref picker) = engine.model_picker {